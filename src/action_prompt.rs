@@ -1,3 +1,8 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::app::KnotView;
+use crate::installed_workflows;
 use crate::profile::ProfileDefinition;
 
 pub fn render_for_profile(profile: &ProfileDefinition, action_state: &str) -> Option<String> {
@@ -17,3 +22,70 @@ pub fn render_for_profile(profile: &ProfileDefinition, action_state: &str) -> Op
     }
     Some(rendered)
 }
+
+/// Values for the `{{ knot.* }}`, `{{ profile.* }}`, and `{{ repo.* }}`
+/// variables a skill may use. Knot variables are only set when a knot is given.
+pub fn skill_variables(
+    profile: &ProfileDefinition,
+    knot: Option<&KnotView>,
+    repo_root: &Path,
+) -> BTreeMap<String, String> {
+    let mut vars = BTreeMap::new();
+    let repo_name = repo_root
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    vars.insert("repo.name".to_string(), repo_name);
+    vars.insert("profile.id".to_string(), profile.id.clone());
+    vars.insert(
+        "profile.workflow_id".to_string(),
+        profile.workflow_id.clone(),
+    );
+    vars.insert(
+        "profile.initial_state".to_string(),
+        profile.initial_state.clone(),
+    );
+    for (name, states) in [
+        ("states", &profile.states),
+        ("queue_states", &profile.queue_states),
+        ("action_states", &profile.action_states),
+        ("terminal_states", &profile.terminal_states),
+    ] {
+        vars.insert(format!("profile.{name}"), states.join(", "));
+    }
+    let Some(knot) = knot else {
+        return vars;
+    };
+    let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+    for (name, value) in [
+        ("id", knot.id.clone()),
+        (
+            "alias",
+            knot.alias.clone().unwrap_or_else(|| knot.id.clone()),
+        ),
+        ("title", knot.title.clone()),
+        ("state", knot.state.clone()),
+        ("type", knot.knot_type.as_str().to_string()),
+        (
+            "priority",
+            knot.priority.map(|p| p.to_string()).unwrap_or_default(),
+        ),
+        ("tags", knot.tags.join(", ")),
+        ("description", optional(&knot.description)),
+        ("acceptance", optional(&knot.acceptance)),
+        ("profile_id", knot.profile_id.clone()),
+    ] {
+        vars.insert(format!("knot.{name}"), value);
+    }
+    for (name, value) in &knot.custom_fields {
+        vars.insert(format!("knot.fields.{name}"), value.clone());
+    }
+    vars
+}
+
+/// Replaces every `{{ name }}` in `skill` that `vars` knows. Anything
+/// else is left as written, so workflow parameters still show through.
+pub fn expand_variables(skill: &str, vars: &BTreeMap<String, String>) -> String {
+    let mut unresolved = Vec::new();
+    installed_workflows::render_prompt_template(skill, vars, &mut unresolved)
+}
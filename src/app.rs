@@ -11,37 +11,132 @@ use crate::sync::SyncSummary;
 use crate::workflow::{ProfileDefinition, ProfileRegistry};
 
 mod alias;
-mod edges;
+mod async_ops;
+mod changelog;
+mod criteria;
+mod custom_fields;
+mod dashboard;
+mod dedupe;
+mod definition_of_done;
+mod diff;
+pub(crate) mod digest;
+mod edge_apply;
+pub(crate) mod edges;
 pub mod error;
+mod estimate_stats;
+mod event_emit;
+mod event_tail;
 mod gate;
 mod gate_metadata;
+mod git_links;
+mod grep;
 pub mod helpers;
+mod idempotency;
+mod import_jsonl;
+mod import_knots;
+mod import_rollback;
+mod knot_branch;
 mod knot_create;
-mod knot_lease;
+pub(crate) mod knot_lease;
+mod knot_links;
 mod knot_profile;
 mod knot_update;
+mod lint;
+mod memory_cache;
+mod merge_hook;
+mod org_sync;
+mod pool;
+mod pr_body;
+mod private_knots;
 mod profile_config;
+mod pull_digest;
 mod query;
+mod redact;
 pub mod rehydrate;
+mod remote_edges;
+mod replay;
+mod search;
+mod secret_guard;
+#[cfg(feature = "semantic")]
+mod semantic_search;
+mod settings;
+mod settings_validate;
+mod snapshot_verify;
+mod snooze;
 mod state_ops;
 mod state_resolve;
+mod status;
+mod subtree_next;
+mod suggest;
+mod summarize;
+mod sync_conflicts;
 mod sync_ops;
+mod tags;
+mod transfer;
 pub mod types;
+mod usage_stats;
+mod vault_sync;
+mod visibility;
+mod watch;
+pub(crate) mod wip_limits;
 
+pub use changelog::Changelog;
+pub use dedupe::{DuplicatePair, SimilarKnot, DEFAULT_SIMILARITY_THRESHOLD};
+pub use diff::FieldChange;
+pub use digest::{Digest, DigestGrouping};
+pub use edge_apply::EdgePlan;
 pub use error::AppError;
+pub use estimate_stats::{EstimateGrouping, EstimateReport, EstimateTotals};
+pub use git_links::GitLinkKind;
+pub use grep::GrepHit;
+pub use import_jsonl::{ImportMapping, JsonlImportOptions};
+pub use lint::LintReport;
+pub use merge_hook::MergedPr;
+pub use pool::AppPool;
+pub use pr_body::PrBody;
+pub use pull_digest::PullDigest;
+pub(crate) use settings::normalize_setting;
+pub use settings::{ConfigEntry, ConfigScope};
+#[cfg(test)]
+pub use status::{CacheStatus, LockStatus};
+pub use status::{StatusReport, SyncMark};
+pub use subtree_next::SubtreeAdvance;
+pub use suggest::SuggestReport;
+pub use summarize::KnotSummary;
+pub use sync_conflicts::SyncConflict;
+pub use tags::TagGroup;
 pub use types::{
-    CreateKnotOptions, EdgeView, GateDecision, KnotView, PaginatedList, StateActorMetadata,
-    UpdateKnotPatch,
+    ColdKnotView, CreateKnotOptions, EdgeView, GateDecision, KnotView, PaginatedList,
+    StateActorMetadata, UpdateKnotPatch,
 };
+pub(crate) use usage_stats::percentile;
+#[cfg(test)]
+pub use usage_stats::CommandUsage;
+pub use usage_stats::UsageReport;
+pub use wip_limits::{AgeReport, StateAge, WipUsage};
 
+#[cfg(test)]
+pub(crate) use changelog::{ChangelogEntry, ChangelogSection};
+#[cfg(test)]
+pub(crate) use digest::{DigestGroup, DigestItem};
+#[cfg(test)]
+pub(crate) use estimate_stats::EstimateGroup;
 #[cfg(test)]
 pub(crate) use helpers::{
     ensure_profile_etag, metadata_entry_from_input, non_empty, normalize_tag, parse_edge_direction,
 };
 #[cfg(test)]
-pub(crate) use rehydrate::apply_event::apply_rehydrate_event;
+pub(crate) use pr_body::{PrBodyChild, ReviewChecklist};
+#[cfg(test)]
+pub(crate) use pull_digest::DigestKnot;
+#[cfg(test)]
+pub(crate) use rehydrate::{
+    apply_event::apply_rehydrate_event, rehydrate_from_events, RehydrateProjection,
+};
+#[cfg(test)]
+pub(crate) use suggest::Suggestion;
 #[cfg(test)]
-pub(crate) use rehydrate::{rehydrate_from_events, RehydrateProjection};
+pub(crate) use tags::TagCount;
 #[cfg(test)]
 pub(crate) use types::ChildSummary;
 
@@ -85,8 +180,13 @@ impl App {
         {
             return Err(AppError::NotInitialized);
         }
-        helpers::ensure_parent_dir(db_path)?;
+        let in_memory = db::is_memory_path(db_path);
+        if !in_memory {
+            helpers::ensure_parent_dir(db_path)?;
+            crate::cache_location::claim_if_xdg(db, &context.store_paths.root)?;
+        }
         let conn = crate::trace::measure("db_open", || db::open_connection(db_path))?;
+        crate::usage_metrics::note_store(db_path, db::get_local_metrics_enabled(&conn)?);
         let workflow_config_path =
             installed_workflows::workflows_root(context.workflow_root()).join("current");
         if !workflow_config_path.exists()
@@ -98,8 +198,9 @@ impl App {
         let profile_registry = crate::trace::measure("profile_registry", || {
             ProfileRegistry::load_for_repo(context.workflow_root())
         })?;
-        let writer = EventWriter::new(context.store_paths.root.clone());
-        Ok(Self {
+        let writer = EventWriter::new(context.store_paths.root.clone())
+            .with_private_knots(db::list_private_knots(&conn)?);
+        let app = Self {
             conn,
             writer,
             repo_root: context.repo_root.clone(),
@@ -108,7 +209,17 @@ impl App {
             project_id: context.project_id.clone(),
             profile_registry,
             home_override: None,
-        })
+        };
+        if in_memory {
+            app.hydrate_memory_cache()?;
+        }
+        if let Err(err) = app.restore_private_knots() {
+            eprintln!("warning: {err}");
+        }
+        if let Err(err) = app.apply_repo_config() {
+            eprintln!("warning: {err}");
+        }
+        Ok(app)
     }
 
     pub(crate) fn with_home_override(mut self, home: Option<PathBuf>) -> Self {
@@ -116,6 +227,11 @@ impl App {
         self
     }
 
+    /// The `.knots` directory this app reads and writes.
+    pub(crate) fn store_root(&self) -> &std::path::Path {
+        &self.store_paths.root
+    }
+
     fn repo_lock_path(&self) -> PathBuf {
         self.store_paths.repo_lock_path()
     }
@@ -140,28 +256,6 @@ impl App {
             .ok_or_else(|| AppError::InvalidArgument("no profiles are defined".to_string()))
     }
 
-    fn read_user_config(&self) -> Result<UserConfig, AppError> {
-        match self.home_override.as_ref() {
-            Some(Some(home)) => crate::project::read_global_config(Some(home.as_path()))
-                .map_err(AppError::InvalidArgument),
-            Some(None) => Ok(UserConfig::default()),
-            None => crate::project::read_global_config(None).map_err(AppError::InvalidArgument),
-        }
-    }
-
-    fn write_user_config(&self, config: &UserConfig) -> Result<(), AppError> {
-        match self.home_override.as_ref() {
-            Some(Some(home)) => crate::project::write_global_config(Some(home.as_path()), config)
-                .map_err(AppError::InvalidArgument),
-            Some(None) => Err(AppError::InvalidArgument(
-                "unable to resolve $HOME for profile config".to_string(),
-            )),
-            None => {
-                crate::project::write_global_config(None, config).map_err(AppError::InvalidArgument)
-            }
-        }
-    }
-
     fn resolve_config_profile(&self, raw: &Option<String>) -> Option<String> {
         let raw_id = raw.as_deref()?;
         self.resolve_profile_id(raw_id, None).ok()
@@ -243,21 +337,57 @@ mod tests;
 #[path = "app/tests_acceptance_ext.rs"]
 mod tests_acceptance_ext;
 #[cfg(test)]
+#[path = "app/tests_async_ops.rs"]
+mod tests_async_ops;
+#[cfg(test)]
+#[path = "app/tests_changelog.rs"]
+mod tests_changelog;
+#[cfg(test)]
 #[path = "app/tests_coverage_ext.rs"]
 mod tests_coverage_ext;
 #[cfg(test)]
 #[path = "app/tests_coverage_ext2.rs"]
 mod tests_coverage_ext2;
 #[cfg(test)]
+#[path = "app/tests_criteria.rs"]
+mod tests_criteria;
+#[cfg(test)]
+#[path = "app/tests_custom_fields.rs"]
+mod tests_custom_fields;
+#[cfg(test)]
+#[path = "app/tests_dashboard.rs"]
+mod tests_dashboard;
+#[cfg(test)]
+#[path = "app/tests_dedupe.rs"]
+mod tests_dedupe;
+#[cfg(test)]
+#[path = "app/tests_diff.rs"]
+mod tests_diff;
+#[cfg(test)]
+#[path = "app/tests_digest.rs"]
+mod tests_digest;
+#[cfg(test)]
+#[path = "app/tests_edge_apply.rs"]
+mod tests_edge_apply;
+#[cfg(test)]
 #[path = "app/tests_error_paths.rs"]
 mod tests_error_paths;
 #[cfg(test)]
+#[path = "app/tests_estimates.rs"]
+mod tests_estimates;
+#[cfg(test)]
+#[path = "app/tests_event_tail.rs"]
+mod tests_event_tail;
+#[cfg(test)]
 #[path = "app/tests_exploration.rs"]
 mod tests_exploration;
 #[cfg(test)]
 #[path = "app/tests_gate_ext.rs"]
 mod tests_gate_ext;
 #[cfg(test)]
+#[path = "app/tests_grep.rs"]
+mod tests_grep;
+#[cfg(test)]
 #[path = "app/tests_hierarchy.rs"]
 mod tests_hierarchy;
 #[cfg(test)]
@@ -270,20 +400,86 @@ mod tests_hierarchy_ext;
 #[path = "app/tests_legacy_workflow_ids.rs"]
 mod tests_legacy_workflow_ids;
 #[cfg(test)]
+#[path = "app/tests_lint.rs"]
+mod tests_lint;
+#[cfg(test)]
+#[path = "app/tests_org_sync.rs"]
+mod tests_org_sync;
+#[cfg(test)]
+#[path = "app/tests_pool.rs"]
+mod tests_pool;
+#[cfg(test)]
+#[path = "app/tests_private_knots.rs"]
+mod tests_private_knots;
+#[cfg(test)]
+#[path = "app/tests_pull_digest.rs"]
+mod tests_pull_digest;
+#[cfg(test)]
+#[path = "app/tests_redact.rs"]
+mod tests_redact;
+#[cfg(test)]
+#[path = "app/tests_replay.rs"]
+mod tests_replay;
+#[cfg(test)]
+#[path = "app/tests_repo_profile.rs"]
+mod tests_repo_profile;
+#[cfg(test)]
+#[path = "app/tests_search.rs"]
+mod tests_search;
+#[cfg(test)]
+#[path = "app/tests_secret_guard.rs"]
+mod tests_secret_guard;
+#[cfg(test)]
+#[path = "app/tests_settings.rs"]
+mod tests_settings;
+#[cfg(test)]
 #[path = "app/tests_show_lease.rs"]
 mod tests_show_lease;
 #[cfg(test)]
+#[path = "app/tests_snooze.rs"]
+mod tests_snooze;
+#[cfg(test)]
 #[path = "app/tests_step_history.rs"]
 mod tests_step_history;
 #[cfg(test)]
 #[path = "app/tests_step_metadata_responses.rs"]
 mod tests_step_metadata_responses;
 #[cfg(test)]
+#[path = "app/tests_subtree_next.rs"]
+mod tests_subtree_next;
+#[cfg(test)]
+#[path = "app/tests_suggest.rs"]
+mod tests_suggest;
+#[cfg(test)]
+#[path = "app/tests_summarize.rs"]
+mod tests_summarize;
+#[cfg(test)]
+#[path = "app/tests_sync_conflicts.rs"]
+mod tests_sync_conflicts;
+#[cfg(test)]
+#[path = "app/tests_tags.rs"]
+mod tests_tags;
+#[cfg(test)]
 #[path = "app/tests_terminal_deferred.rs"]
 mod tests_terminal_deferred;
 #[cfg(test)]
 #[path = "app/tests_update_ext.rs"]
 mod tests_update_ext;
 #[cfg(test)]
+#[path = "app/tests_usage_stats.rs"]
+mod tests_usage_stats;
+#[cfg(test)]
+#[path = "app/tests_vault_sync.rs"]
+mod tests_vault_sync;
+#[cfg(test)]
+#[path = "app/tests_visibility.rs"]
+mod tests_visibility;
+#[cfg(test)]
+#[path = "app/tests_watch.rs"]
+mod tests_watch;
+#[cfg(test)]
+#[path = "app/tests_wip_limits.rs"]
+mod tests_wip_limits;
+#[cfg(test)]
 #[path = "app/tests_workflow_roots.rs"]
 mod tests_workflow_roots;
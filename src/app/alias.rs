@@ -10,18 +10,10 @@ use super::types::KnotView;
 use super::App;
 
 impl App {
+    /// Ids of every knot in any tier, read without loading the rows.
     pub(super) fn known_knot_ids(&self) -> Result<HashSet<String>, AppError> {
-        let mut ids = HashSet::new();
-        for r in crate::trace::measure("alias_scan_hot", || db::list_knot_hot(&self.conn))? {
-            ids.insert(r.id);
-        }
-        for r in crate::trace::measure("alias_scan_warm", || db::list_knot_warm(&self.conn))? {
-            ids.insert(r.id);
-        }
-        for r in crate::trace::measure("alias_scan_cold", || db::list_cold_catalog(&self.conn))? {
-            ids.insert(r.id);
-        }
-        Ok(ids)
+        let ids = crate::trace::measure("alias_scan_ids", || db::list_known_knot_ids(&self.conn))?;
+        Ok(ids.into_iter().collect())
     }
 
     pub(super) fn alias_maps(&self) -> Result<AliasMaps, AppError> {
@@ -82,7 +74,23 @@ impl App {
     }
 
     pub(super) fn next_knot_id(&self) -> Result<String, AppError> {
-        let existing = self.known_knot_ids()?;
+        self.next_knot_id_avoiding(&HashSet::new())
+    }
+
+    /// Like `next_knot_id`, but also avoids ids handed out earlier in the
+    /// same batch that are not in the cache yet.
+    pub(super) fn next_knot_id_avoiding(
+        &self,
+        taken: &HashSet<String>,
+    ) -> Result<String, AppError> {
+        let mut existing = self.known_knot_ids()?;
+        existing.extend(taken.iter().cloned());
+        let prefix = db::get_meta(&self.conn, "id_prefix")?.filter(|value| !value.is_empty());
+        if let Some(prefix) = prefix {
+            return Ok(generate_knot_id_from_slug(&prefix, |c| {
+                existing.contains(c)
+            }));
+        }
         Ok(match self.project_id.as_deref() {
             Some(pid) => generate_knot_id_from_slug(pid, |c| existing.contains(c)),
             None => generate_knot_id(&self.repo_root, |c| existing.contains(c)),
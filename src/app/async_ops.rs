@@ -0,0 +1,90 @@
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+use crate::replication::{PushSummary, ReplicationSummary};
+use crate::sync::SyncSummary;
+
+use super::error::AppError;
+use super::pool::AppPool;
+
+/// Runs a blocking operation on its own thread and resolves once it
+/// finishes. It needs no particular async runtime, so an embedding server
+/// can await a long pull without tying up one of its executor threads.
+/// A panic in the operation resumes when the future is polled.
+#[cfg_attr(not(test), allow(dead_code))]
+pub struct Blocking<T> {
+    shared: Arc<Mutex<BlockingState<T>>>,
+}
+
+struct BlockingState<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn spawn_blocking<T, F>(operation: F) -> Blocking<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let shared = Arc::new(Mutex::new(BlockingState {
+        result: None,
+        waker: None,
+    }));
+    let worker = Arc::clone(&shared);
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(operation));
+        let mut state = worker
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.result = Some(result);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    });
+    Blocking { shared }
+}
+
+impl<T> Future for Blocking<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self
+            .shared
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match state.result.take() {
+            Some(Ok(value)) => Poll::Ready(value),
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Async variants of the replication operations. Each checks an `App` out
+/// of the pool on the worker thread, so waiting for a free connection does
+/// not block the caller either.
+#[cfg_attr(not(test), allow(dead_code))]
+impl AppPool {
+    pub fn pull_async(&self) -> Blocking<Result<SyncSummary, AppError>> {
+        let pool = self.clone();
+        spawn_blocking(move || pool.get()?.pull())
+    }
+
+    pub fn push_async(&self) -> Blocking<Result<PushSummary, AppError>> {
+        let pool = self.clone();
+        spawn_blocking(move || pool.get()?.push())
+    }
+
+    pub fn sync_async(&self) -> Blocking<Result<ReplicationSummary, AppError>> {
+        let pool = self.clone();
+        spawn_blocking(move || pool.get()?.sync())
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use serde::Serialize;
+use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::db;
+use crate::domain::knot_type::KnotType;
+use crate::events::FullEventKind;
+use crate::state_hierarchy;
+
+use super::digest::parse_window_start;
+use super::error::AppError;
+use super::rehydrate::replay::full_events_between;
+use super::App;
+
+/// Release-note sections in print order, with the tag names (matched on the
+/// last `/` segment, so `type/bug` counts) that put a knot in each.
+const SECTIONS: [(&str, &[&str]); 3] = [
+    ("feature", &["feature", "feat", "enhancement"]),
+    ("bug", &["bug", "bugfix", "fix"]),
+    ("chore", &["chore", "maintenance", "refactor", "docs"]),
+];
+const OTHER: &str = "other";
+
+/// Knots that reached a terminal state since `since`, grouped by kind.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct Changelog {
+    pub since: String,
+    pub since_at: String,
+    pub sections: Vec<ChangelogSection>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ChangelogSection {
+    pub kind: String,
+    pub entries: Vec<ChangelogEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ChangelogEntry {
+    pub id: String,
+    pub title: String,
+    pub state: String,
+    pub at: String,
+}
+
+impl App {
+    /// Knots whose latest terminal transition falls after `since`, a git tag
+    /// or anything `kno digest --since` accepts. Knots that were reopened
+    /// since are left out, and so are abandoned ones unless
+    /// `include_abandoned` is set.
+    pub fn changelog(&self, since: &str, include_abandoned: bool) -> Result<Changelog, AppError> {
+        let now = OffsetDateTime::now_utc();
+        let cutoff = match parse_window_start(since, now) {
+            Ok(cutoff) => cutoff,
+            Err(_) => self.git_tag_time(since.trim()).ok_or_else(|| {
+                AppError::InvalidArgument(format!(
+                    "invalid --since '{}': expected a git tag, YYYY-MM-DD, yesterday, \
+                     a span like 7d, or an RFC 3339 timestamp",
+                    since.trim()
+                ))
+            })?,
+        };
+        let roots = [
+            self.store_paths.root.clone(),
+            self.store_paths.worktree_path(),
+        ];
+        let mut finished: BTreeMap<String, String> = BTreeMap::new();
+        for event in full_events_between(&roots, cutoff, now)? {
+            if event.event_type != FullEventKind::KnotStateSet.as_str() {
+                continue;
+            }
+            let Some(to) = event.data.get("to").and_then(Value::as_str) else {
+                continue;
+            };
+            if state_hierarchy::is_terminal_state(to)? {
+                finished.insert(event.knot_id, event.occurred_at);
+            }
+        }
+        let mut sections: BTreeMap<usize, Vec<ChangelogEntry>> = BTreeMap::new();
+        for (id, at) in finished {
+            let Some(record) = db::get_knot_hot(&self.conn, &id)? else {
+                continue;
+            };
+            if record.knot_type.as_deref() == Some(KnotType::Lease.as_str())
+                || !state_hierarchy::is_terminal_state(&record.state)?
+                || (record.state == "abandoned" && !include_abandoned)
+            {
+                continue;
+            }
+            sections
+                .entry(section_index(&record.tags))
+                .or_default()
+                .push(ChangelogEntry {
+                    id: record.id,
+                    title: record.title,
+                    state: record.state,
+                    at,
+                });
+        }
+        Ok(Changelog {
+            since: since.trim().to_string(),
+            since_at: cutoff
+                .format(&Rfc3339)
+                .expect("RFC3339 formatting for UTC timestamp should never fail"),
+            sections: sections
+                .into_iter()
+                .map(|(index, mut entries)| {
+                    entries.sort_by(|a, b| a.at.cmp(&b.at).then_with(|| a.id.cmp(&b.id)));
+                    ChangelogSection {
+                        kind: SECTIONS
+                            .get(index)
+                            .map_or(OTHER, |(kind, _)| kind)
+                            .to_string(),
+                        entries,
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    /// Commit time of git tag `tag` in the project repo.
+    fn git_tag_time(&self, tag: &str) -> Option<OffsetDateTime> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.repo_root)
+            .args(["log", "-1", "--format=%cI"])
+            .arg(format!("refs/tags/{tag}"))
+            .arg("--")
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        OffsetDateTime::parse(String::from_utf8_lossy(&output.stdout).trim(), &Rfc3339).ok()
+    }
+}
+
+/// Index into `SECTIONS`, or `SECTIONS.len()` for knots without a kind tag.
+fn section_index(tags: &[String]) -> usize {
+    SECTIONS
+        .iter()
+        .position(|(_, names)| {
+            tags.iter().any(|tag| {
+                let name = tag.rsplit('/').next().unwrap_or(tag);
+                names.contains(&name)
+            })
+        })
+        .unwrap_or(SECTIONS.len())
+}
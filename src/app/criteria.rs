@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::db::{self, CriterionRecord, KnotCacheRecord};
+use crate::events::{new_event_id, now_utc_rfc3339, EventRecord, FullEvent, FullEventKind};
+use crate::locks::FileLock;
+
+use super::error::AppError;
+use super::App;
+
+mod plan;
+
+/// `require_criteria` entry that applies to every profile.
+const ALL_PROFILES: &str = "*";
+
+impl App {
+    /// The knot's acceptance criteria, numbered from 1 in the order added.
+    pub fn criteria(&self, id: &str) -> Result<Vec<CriterionRecord>, AppError> {
+        let id = self.resolve_knot_token(id)?;
+        if db::get_knot_hot(&self.conn, &id)?.is_none() {
+            return Err(AppError::NotFound(id));
+        }
+        Ok(db::list_criteria(&self.conn, &id)?)
+    }
+
+    /// Appends an unchecked criterion and returns the updated list.
+    pub fn add_criterion(&self, id: &str, text: &str) -> Result<Vec<CriterionRecord>, AppError> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(AppError::InvalidArgument(
+                "acceptance criterion must not be empty".to_string(),
+            ));
+        }
+        let (id, _repo_guard, _cache_guard) = self.lock_criteria_knot(id)?;
+        let event_id = new_event_id();
+        let criterion = CriterionRecord {
+            criterion_id: event_id.clone(),
+            text: text.to_string(),
+            checked: false,
+            checked_at: None,
+            added_at: now_utc_rfc3339(),
+        };
+        self.writer
+            .write(&EventRecord::full(FullEvent::with_identity(
+                event_id,
+                criterion.added_at.clone(),
+                id.clone(),
+                FullEventKind::KnotCriterionAdded.as_str(),
+                json!({"criterion_id": criterion.criterion_id, "text": criterion.text}),
+            )))?;
+        db::insert_criterion(&self.conn, &id, &criterion)?;
+        Ok(db::list_criteria(&self.conn, &id)?)
+    }
+
+    /// Checks (or unchecks) criterion `number`, counted from 1. Setting a
+    /// criterion to the state it is already in writes nothing.
+    pub fn check_criterion(
+        &self,
+        id: &str,
+        number: usize,
+        checked: bool,
+    ) -> Result<Vec<CriterionRecord>, AppError> {
+        let (id, _repo_guard, _cache_guard) = self.lock_criteria_knot(id)?;
+        let criteria = db::list_criteria(&self.conn, &id)?;
+        let Some(criterion) = number.checked_sub(1).and_then(|index| criteria.get(index)) else {
+            return Err(AppError::InvalidArgument(format!(
+                "{} has {} acceptance criteria; there is no #{number}",
+                crate::knot_id::display_id(&id),
+                criteria.len()
+            )));
+        };
+        if criterion.checked == checked {
+            return Ok(criteria);
+        }
+        let occurred_at = now_utc_rfc3339();
+        self.writer
+            .write(&EventRecord::full(FullEvent::with_identity(
+                new_event_id(),
+                occurred_at.clone(),
+                id.clone(),
+                FullEventKind::KnotCriterionChecked.as_str(),
+                json!({"criterion_id": criterion.criterion_id, "checked": checked}),
+            )))?;
+        db::set_criterion_checked(
+            &self.conn,
+            &id,
+            &criterion.criterion_id,
+            checked,
+            &occurred_at,
+        )?;
+        Ok(db::list_criteria(&self.conn, &id)?)
+    }
+
+    /// Rejects finishing a knot with unchecked criteria when the repo
+    /// config's `require_criteria` names its profile. Abandoning is always
+    /// allowed, and `force` overrides the check.
+    pub(crate) fn enforce_criteria(
+        &self,
+        current: &KnotCacheRecord,
+        profile_id: &str,
+        next_state: &str,
+        next_is_terminal: bool,
+        force: bool,
+    ) -> Result<(), AppError> {
+        if force || !next_is_terminal || next_state == "abandoned" {
+            return Ok(());
+        }
+        let required = self
+            .read_repo_config()?
+            .unwrap_or_default()
+            .require_criteria;
+        let applies = required.iter().any(|name| {
+            name == ALL_PROFILES
+                || self
+                    .profile_registry
+                    .require(name)
+                    .is_ok_and(|profile| profile.id == profile_id)
+        });
+        if !applies {
+            return Ok(());
+        }
+        let open = db::list_criteria(&self.conn, &current.id)?
+            .into_iter()
+            .filter(|criterion| !criterion.checked)
+            .count();
+        if open > 0 {
+            return Err(AppError::InvalidArgument(format!(
+                "{} has {open} unchecked acceptance criteria; check them with \
+                 `kno criteria check` first, or rerun with --force",
+                crate::knot_id::display_id(&current.id)
+            )));
+        }
+        Ok(())
+    }
+
+    fn lock_criteria_knot(&self, id: &str) -> Result<(String, FileLock, FileLock), AppError> {
+        let id = self.resolve_knot_token(id)?;
+        let repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        let cache_guard = FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        if db::get_knot_hot(&self.conn, &id)?.is_none() {
+            return Err(AppError::NotFound(id));
+        }
+        Ok((id, repo_guard, cache_guard))
+    }
+}
@@ -0,0 +1,102 @@
+use serde_json::json;
+
+use crate::db::{self, KnotCacheRecord, PlanRecord};
+use crate::events::{new_event_id, now_utc_rfc3339, EventRecord, FullEvent, FullEventKind};
+use crate::profile::ProfileDefinition;
+
+use super::super::error::AppError;
+use super::super::App;
+use super::ALL_PROFILES;
+
+/// States a knot may move between without leaving the planning stage.
+const PLANNING_STATES: [&str; 2] = ["ready_for_planning", "planning"];
+
+impl App {
+    /// Every version of the knot's plan, numbered from 1, oldest first.
+    pub fn plans(&self, id: &str) -> Result<Vec<PlanRecord>, AppError> {
+        let id = self.resolve_knot_token(id)?;
+        if db::get_knot_hot(&self.conn, &id)?.is_none() {
+            return Err(AppError::NotFound(id));
+        }
+        Ok(db::list_plans(&self.conn, &id)?)
+    }
+
+    /// Records `content` as the knot's next plan version. Setting the same
+    /// document as the latest version writes nothing and returns it.
+    pub fn set_plan(
+        &self,
+        id: &str,
+        content: &str,
+        author: Option<&str>,
+    ) -> Result<PlanRecord, AppError> {
+        if content.trim().is_empty() {
+            return Err(AppError::InvalidArgument(
+                "plan document must not be empty".to_string(),
+            ));
+        }
+        self.guard_secrets(&[("plan", content)])?;
+        let (id, _repo_guard, _cache_guard) = self.lock_criteria_knot(id)?;
+        let mut plans = db::list_plans(&self.conn, &id)?;
+        let digest = crate::vault::content_hash(content);
+        if plans.last().is_some_and(|latest| latest.digest == digest) {
+            return Ok(plans.pop().expect("latest plan"));
+        }
+        let plan = PlanRecord {
+            plan_id: new_event_id(),
+            version: plans.len() + 1,
+            content: content.to_string(),
+            digest,
+            author: author
+                .map(str::trim)
+                .filter(|a| !a.is_empty())
+                .map(String::from),
+            set_at: now_utc_rfc3339(),
+        };
+        self.writer
+            .write(&EventRecord::full(FullEvent::with_identity(
+                plan.plan_id.clone(),
+                plan.set_at.clone(),
+                id.clone(),
+                FullEventKind::KnotPlanSet.as_str(),
+                json!({"content": plan.content, "digest": plan.digest, "author": plan.author}),
+            )))?;
+        db::insert_plan(&self.conn, &id, &plan)?;
+        Ok(plan)
+    }
+
+    /// Rejects moving a knot out of planning without a plan when the repo
+    /// config's `require_plan` names its profile. Escape states and
+    /// abandoning are always allowed, and `force` overrides the check.
+    pub(crate) fn enforce_plan(
+        &self,
+        current: &KnotCacheRecord,
+        profile: &ProfileDefinition,
+        next_state: &str,
+        force: bool,
+    ) -> Result<(), AppError> {
+        if force
+            || current.state != "planning"
+            || PLANNING_STATES.contains(&next_state)
+            || next_state == "abandoned"
+            || profile.is_escape_state(next_state)
+        {
+            return Ok(());
+        }
+        let required = self.read_repo_config()?.unwrap_or_default().require_plan;
+        let applies = required.iter().any(|name| {
+            name == ALL_PROFILES
+                || self
+                    .profile_registry
+                    .require(name)
+                    .is_ok_and(|required| required.id == profile.id)
+        });
+        if !applies || !db::list_plans(&self.conn, &current.id)?.is_empty() {
+            return Ok(());
+        }
+        Err(AppError::InvalidArgument(format!(
+            "{} has no plan; attach one with `kno plan set --file` first, \
+             or rerun with --force",
+            crate::knot_id::display_id(&current.id)
+        )))
+    }
+}
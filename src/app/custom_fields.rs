@@ -0,0 +1,54 @@
+use serde_json::json;
+
+use crate::domain::custom_field::parse_assignment;
+use crate::events::{new_event_id, FullEvent, FullEventKind};
+
+use super::error::AppError;
+use super::App;
+
+impl App {
+    /// Validates `name=value` assignments against the `[fields]` schema in
+    /// the repo config. An empty value clears the field and maps to `None`.
+    pub(crate) fn resolve_custom_fields(
+        &self,
+        assignments: &[String],
+    ) -> Result<Vec<(String, Option<String>)>, AppError> {
+        if assignments.is_empty() {
+            return Ok(Vec::new());
+        }
+        let schema = self.read_repo_config()?.unwrap_or_default().fields;
+        let mut resolved = Vec::with_capacity(assignments.len());
+        for raw in assignments {
+            let (name, value) = parse_assignment(raw).map_err(AppError::InvalidArgument)?;
+            let spec = schema.get(&name).ok_or_else(|| {
+                let declared = schema.keys().cloned().collect::<Vec<_>>();
+                AppError::InvalidArgument(if declared.is_empty() {
+                    format!(
+                        "unknown field '{name}'; declare it under [fields] in .knots/config.toml"
+                    )
+                } else {
+                    format!("unknown field '{name}'; declared: {}", declared.join(", "))
+                })
+            })?;
+            let value = match value.as_str() {
+                "" => None,
+                raw => Some(
+                    spec.normalize(&name, raw)
+                        .map_err(AppError::InvalidArgument)?,
+                ),
+            };
+            resolved.push((name, value));
+        }
+        Ok(resolved)
+    }
+}
+
+pub(crate) fn field_set_event(id: &str, at: &str, name: &str, value: Option<&str>) -> FullEvent {
+    FullEvent::with_identity(
+        new_event_id(),
+        at.to_string(),
+        id.to_string(),
+        FullEventKind::KnotFieldSet.as_str(),
+        json!({ "field": name, "value": value }),
+    )
+}
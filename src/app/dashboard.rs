@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::db;
+use crate::events::now_utc_rfc3339;
+
+use super::error::AppError;
+use super::types::{EdgeView, KnotView};
+use super::App;
+
+/// One line of a knot's activity feed in the dashboard.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ActivityItem {
+    pub at: String,
+    /// `note`, `handoff`, or `step`.
+    pub kind: String,
+    pub actor: String,
+    pub summary: String,
+}
+
+impl App {
+    /// Every shared knot for the board, with sensitive fields hidden.
+    pub fn dashboard_board(&self) -> Result<Value, AppError> {
+        let knots = self.list_shared_knots()?;
+        Ok(json!({ "knots": self.knots_for_export(&knots, false)? }))
+    }
+
+    /// One knot with its activity feed and edges, or `None` when missing
+    /// or private. Edges to private knots are left out.
+    pub fn dashboard_knot(&self, id: &str) -> Result<Option<Value>, AppError> {
+        let Some(knot) = self.show_knot(id)? else {
+            return Ok(None);
+        };
+        let private = db::list_private_knots(&self.conn)?;
+        if private.contains(&knot.id) {
+            return Ok(None);
+        }
+        let edges = self
+            .list_edges(&knot.id, "both")?
+            .into_iter()
+            .filter(|edge| !private.contains(&edge.src) && !private.contains(&edge.dst))
+            .collect();
+        Ok(Some(self.knot_page(&knot, edges)?))
+    }
+
+    /// Shared knots as nodes and their parent and blocking edges.
+    pub fn dashboard_graph(&self) -> Result<Value, AppError> {
+        let knots = self.list_shared_knots()?;
+        let private = db::list_private_knots(&self.conn)?;
+        let edges: Vec<_> = self
+            .list_layout_edges()?
+            .into_iter()
+            .filter(|edge| !private.contains(&edge.src) && !private.contains(&edge.dst))
+            .collect();
+        Ok(json!({ "nodes": self.graph_nodes(&knots)?, "edges": edges }))
+    }
+
+    /// Every dashboard response for `knots`, keyed by route, for a static
+    /// copy of the dashboard. Edges to knots outside `knots` are left out.
+    pub fn dashboard_snapshot(&self, knots: &[KnotView]) -> Result<Value, AppError> {
+        let members: HashSet<&str> = knots.iter().map(|knot| knot.id.as_str()).collect();
+        let inside = |src: &str, dst: &str| members.contains(src) && members.contains(dst);
+        let mut routes = Map::new();
+        routes.insert(
+            "/api/board".to_string(),
+            json!({ "knots": self.knots_for_export(knots, false)? }),
+        );
+        let edges: Vec<_> = self
+            .list_layout_edges()?
+            .into_iter()
+            .filter(|edge| inside(&edge.src, &edge.dst))
+            .collect();
+        routes.insert(
+            "/api/graph".to_string(),
+            json!({ "nodes": self.graph_nodes(knots)?, "edges": edges }),
+        );
+        for knot in knots {
+            let Some(knot) = self.show_knot(&knot.id)? else {
+                continue;
+            };
+            let edges = self
+                .list_edges(&knot.id, "both")?
+                .into_iter()
+                .filter(|edge| inside(&edge.src, &edge.dst))
+                .collect();
+            routes.insert(
+                format!("/api/knots/{}", knot.id),
+                self.knot_page(&knot, edges)?,
+            );
+        }
+        Ok(json!({ "generated_at": now_utc_rfc3339(), "routes": routes }))
+    }
+
+    fn knot_page(&self, knot: &KnotView, edges: Vec<EdgeView>) -> Result<Value, AppError> {
+        let exported = self.knots_for_export(std::slice::from_ref(knot), false)?;
+        Ok(json!({
+            "knot": exported.into_iter().next(),
+            "activity": knot_activity(knot, &self.sensitive_fields()?),
+            "edges": edges,
+        }))
+    }
+
+    fn graph_nodes(&self, knots: &[KnotView]) -> Result<Vec<Value>, AppError> {
+        Ok(self
+            .knots_for_export(knots, false)?
+            .into_iter()
+            .map(|knot| {
+                json!({
+                    "id": knot["id"],
+                    "alias": knot["alias"],
+                    "title": knot["title"],
+                    "state": knot["state"],
+                })
+            })
+            .collect())
+    }
+}
+
+/// Notes, handoffs, and workflow steps of `knot`, newest first, leaving
+/// out each kind whose field is in `hidden` (the repo's sensitive fields).
+pub fn knot_activity(knot: &KnotView, hidden: &[String]) -> Vec<ActivityItem> {
+    let shown = |field: &str| !hidden.iter().any(|name| name == field);
+    let mut items: Vec<ActivityItem> = Vec::new();
+    for (kind, field, entries) in [
+        ("note", "notes", &knot.notes),
+        ("handoff", "handoff_capsules", &knot.handoff_capsules),
+    ] {
+        if !shown(field) {
+            continue;
+        }
+        items.extend(entries.iter().map(|entry| ActivityItem {
+            at: entry.datetime.clone(),
+            kind: kind.to_string(),
+            actor: entry.username.clone(),
+            summary: entry.content.clone(),
+        }));
+    }
+    let steps = match shown("step_history") {
+        true => knot.step_history.as_slice(),
+        false => &[],
+    };
+    items.extend(steps.iter().map(|step| {
+        let status = serde_json::to_value(&step.status)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let mut summary = format!("{} {status}: {}", step.step, step.from_state);
+        if let Some(to) = &step.to_state {
+            summary.push_str(&format!(" -> {to}"));
+        }
+        ActivityItem {
+            at: step
+                .ended_at
+                .clone()
+                .unwrap_or_else(|| step.started_at.clone()),
+            kind: "step".to_string(),
+            actor: step
+                .agent_name
+                .clone()
+                .or_else(|| step.actor_kind.clone())
+                .unwrap_or_default(),
+            summary,
+        }
+    }));
+    items.sort_by(|a, b| b.at.cmp(&a.at));
+    items
+}
@@ -0,0 +1,157 @@
+use serde::Serialize;
+
+use super::error::AppError;
+use super::types::KnotView;
+use super::App;
+use crate::domain::similarity::TextProfile;
+
+const DEDUPE_ON_CREATE_KEY: &str = "dedupe.on_create";
+/// Score from which two knots are reported as likely duplicates.
+pub const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.6;
+/// Similar knots named in the `kno new` warning.
+const WARN_LIMIT: usize = 5;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DedupeKnot {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    pub title: String,
+    pub state: String,
+}
+
+impl DedupeKnot {
+    pub(super) fn from_view(knot: &KnotView) -> Self {
+        Self {
+            id: knot.id.clone(),
+            alias: knot.alias.clone(),
+            title: knot.title.clone(),
+            state: knot.state.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimilarKnot {
+    #[serde(flatten)]
+    pub knot: DedupeKnot,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicatePair {
+    pub score: f64,
+    pub left: DedupeKnot,
+    pub right: DedupeKnot,
+}
+
+impl App {
+    /// Open knots whose title and description score at least `threshold`
+    /// against the given text, most similar first.
+    pub fn similar_knots(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        threshold: f64,
+    ) -> Result<Vec<SimilarKnot>, AppError> {
+        let profile = TextProfile::new(title, description);
+        let mut similar = Vec::new();
+        for knot in self.dedupe_candidates(false)? {
+            let score = round(profile.similarity(&knot_profile(&knot)));
+            if score >= threshold {
+                similar.push(SimilarKnot {
+                    knot: DedupeKnot::from_view(&knot),
+                    score,
+                });
+            }
+        }
+        similar.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.knot.id.cmp(&b.knot.id)));
+        Ok(similar)
+    }
+
+    /// Every pair of knots scoring at least `threshold`, most similar
+    /// first. Terminal knots are left out unless `include_terminal`.
+    pub fn duplicate_pairs(
+        &self,
+        threshold: f64,
+        include_terminal: bool,
+    ) -> Result<Vec<DuplicatePair>, AppError> {
+        let knots = self.dedupe_candidates(include_terminal)?;
+        let profiles: Vec<TextProfile> = knots.iter().map(knot_profile).collect();
+        let mut pairs = Vec::new();
+        for (i, left) in knots.iter().enumerate() {
+            for (j, right) in knots.iter().enumerate().skip(i + 1) {
+                let score = round(profiles[i].similarity(&profiles[j]));
+                if score >= threshold {
+                    pairs.push(DuplicatePair {
+                        score,
+                        left: DedupeKnot::from_view(left),
+                        right: DedupeKnot::from_view(right),
+                    });
+                }
+            }
+        }
+        pairs.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.left.id.cmp(&b.left.id))
+                .then_with(|| a.right.id.cmp(&b.right.id))
+        });
+        Ok(pairs)
+    }
+
+    /// Applies the `dedupe.on_create` setting before `kno new`: `warn`
+    /// names open knots that look like the one about to be created.
+    pub fn warn_similar_knots(
+        &self,
+        title: &str,
+        description: Option<&str>,
+    ) -> Result<(), AppError> {
+        if self.config_get(DEDUPE_ON_CREATE_KEY)?.value != "warn" {
+            return Ok(());
+        }
+        let similar = self.similar_knots(title, description, DEFAULT_SIMILARITY_THRESHOLD)?;
+        if similar.is_empty() {
+            return Ok(());
+        }
+        let noun = if similar.len() == 1 {
+            "knot exists"
+        } else {
+            "knots exist"
+        };
+        eprintln!("warning: {} similar {noun}:", similar.len());
+        for entry in similar.iter().take(WARN_LIMIT) {
+            let id = entry
+                .knot
+                .alias
+                .as_deref()
+                .unwrap_or(crate::knot_id::display_id(&entry.knot.id));
+            eprintln!("  {id} {:.2} {}", entry.score, entry.knot.title);
+        }
+        Ok(())
+    }
+
+    pub(super) fn dedupe_candidates(
+        &self,
+        include_terminal: bool,
+    ) -> Result<Vec<KnotView>, AppError> {
+        let mut knots = Vec::new();
+        for knot in self.list_knots()? {
+            let profile = self.profile_registry.require(&knot.profile_id)?;
+            if include_terminal || !profile.is_terminal_state(&knot.state) {
+                knots.push(knot);
+            }
+        }
+        Ok(knots)
+    }
+}
+
+fn knot_profile(knot: &KnotView) -> TextProfile {
+    TextProfile::new(&knot.title, knot.description.as_deref())
+}
+
+/// Scores are reported to two decimal places, which also keeps threshold checks
+/// from hinging on float noise.
+fn round(score: f64) -> f64 {
+    (score * 100.0).round() / 100.0
+}
@@ -0,0 +1,69 @@
+use crate::db::{self, KnotCacheRecord};
+use crate::workflow::{DoneRequirement, ProfileDefinition};
+
+use super::error::AppError;
+use super::App;
+
+impl App {
+    /// Rejects finishing a knot that lacks an artifact its profile lists in
+    /// `done_requires`, naming everything that is missing. Abandoning is
+    /// always allowed, and `force` overrides the check. A capsule added by
+    /// the same update counts.
+    pub(crate) fn enforce_definition_of_done(
+        &self,
+        current: &KnotCacheRecord,
+        profile: &ProfileDefinition,
+        next_state: &str,
+        next_is_terminal: bool,
+        adds_handoff_capsule: bool,
+        force: bool,
+    ) -> Result<(), AppError> {
+        if force || !next_is_terminal || next_state == "abandoned" {
+            return Ok(());
+        }
+        let mut missing = Vec::new();
+        for requirement in &profile.done_requires {
+            if let Some(gap) = self.done_gap(current, *requirement, adds_handoff_capsule)? {
+                missing.push(gap);
+            }
+        }
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(AppError::DefinitionOfDoneUnmet {
+            knot_id: crate::knot_id::display_id(&current.id).to_string(),
+            target_state: next_state.to_string(),
+            profile_id: profile.id.clone(),
+            missing,
+        })
+    }
+
+    fn done_gap(
+        &self,
+        current: &KnotCacheRecord,
+        requirement: DoneRequirement,
+        adds_handoff_capsule: bool,
+    ) -> Result<Option<String>, AppError> {
+        let gap = match requirement {
+            DoneRequirement::HandoffCapsule => (current.handoff_capsules.is_empty()
+                && !adds_handoff_capsule)
+                .then(|| "a handoff capsule (none recorded)".to_string()),
+            DoneRequirement::CriteriaChecked => {
+                let criteria = db::list_criteria(&self.conn, &current.id)?;
+                let open = criteria.iter().filter(|c| !c.checked).count();
+                (open > 0).then(|| {
+                    format!(
+                        "all acceptance criteria checked ({open} of {} unchecked)",
+                        criteria.len()
+                    )
+                })
+            }
+            DoneRequirement::LinkedCommit => {
+                let links = db::list_git_links(&self.conn, &current.id)?;
+                (!links.iter().any(|link| link.kind == "commit"))
+                    .then(|| "a linked commit (see `kno link`)".to_string())
+            }
+        };
+        Ok(gap)
+    }
+}
@@ -0,0 +1,125 @@
+use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+
+use super::error::AppError;
+use super::rehydrate::replay::parse_replay_timestamp;
+use super::types::KnotView;
+use super::App;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct KnotDiff {
+    pub left: String,
+    pub right: String,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl App {
+    /// Field-level changes to one knot between `since` (a span such as `7d`
+    /// or an RFC 3339 timestamp) and now, derived from the event log.
+    pub fn diff_since(&self, id: &str, since: &str) -> Result<KnotDiff, AppError> {
+        let now = OffsetDateTime::now_utc();
+        let cutoff = parse_since(since, now)?;
+        let cutoff_text = cutoff
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| since.trim().to_string());
+        let before = match self.replay_at(cutoff, Some(id)) {
+            Ok(mut knots) => knots.pop(),
+            Err(AppError::NotFound(_)) => None,
+            Err(err) => return Err(err),
+        };
+        let after = self
+            .show_knot(id)?
+            .ok_or_else(|| AppError::NotFound(id.to_string()))?;
+        Ok(KnotDiff {
+            left: format!("{}@{}", after.id, cutoff_text),
+            right: after.id.clone(),
+            changes: diff_views(before.as_ref(), &after),
+        })
+    }
+
+    /// Compares the current projections of two knots field by field.
+    pub fn diff_knots(&self, left: &str, right: &str) -> Result<KnotDiff, AppError> {
+        let a = self
+            .show_knot(left)?
+            .ok_or_else(|| AppError::NotFound(left.to_string()))?;
+        let b = self
+            .show_knot(right)?
+            .ok_or_else(|| AppError::NotFound(right.to_string()))?;
+        Ok(KnotDiff {
+            left: a.id.clone(),
+            right: b.id.clone(),
+            changes: diff_views(Some(&a), &b),
+        })
+    }
+}
+
+type FieldReader = fn(&KnotView) -> Option<String>;
+
+fn diff_views(before: Option<&KnotView>, after: &KnotView) -> Vec<FieldChange> {
+    let fields: [(&'static str, FieldReader); 8] = [
+        ("title", |k| Some(k.title.clone())),
+        ("state", |k| Some(k.state.clone())),
+        ("type", |k| Some(k.knot_type.as_str().to_string())),
+        ("priority", |k| k.priority.map(|p| p.to_string())),
+        ("profile", |k| Some(k.profile_id.clone())),
+        ("tags", |k| (!k.tags.is_empty()).then(|| k.tags.join(", "))),
+        ("description", |k| k.description.clone()),
+        ("acceptance", |k| k.acceptance.clone()),
+    ];
+    fields
+        .into_iter()
+        .filter_map(|(field, read)| {
+            let old = before.and_then(read);
+            let new = read(after);
+            (old != new).then_some(FieldChange {
+                field,
+                before: old,
+                after: new,
+            })
+        })
+        .collect()
+}
+
+/// A span such as `30m`, `12h`, `7d`, or `2w`; `None` for any other unit,
+/// including non-ASCII ones, and for amounts too large to represent.
+pub(crate) fn parse_span(raw: &str) -> Option<Duration> {
+    let trimmed = raw.trim();
+    let unit = trimmed.chars().last()?;
+    let amount = trimmed.strip_suffix(unit)?.parse::<i64>().ok()?;
+    let seconds = match unit {
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    amount.checked_mul(seconds).map(Duration::seconds)
+}
+
+/// Accepts `<n>m`, `<n>h`, `<n>d`, `<n>w`, or an RFC 3339 timestamp.
+pub(crate) fn parse_since(raw: &str, now: OffsetDateTime) -> Result<OffsetDateTime, AppError> {
+    let trimmed = raw.trim();
+    match parse_span(trimmed) {
+        Some(span) if !span.is_negative() => now.checked_sub(span).ok_or_else(|| {
+            AppError::InvalidArgument(format!("--since span '{}' is too large", raw))
+        }),
+        Some(_) => Err(AppError::InvalidArgument(format!(
+            "--since span '{}' must not be negative",
+            raw
+        ))),
+        None => parse_replay_timestamp(trimmed).map_err(|_| {
+            AppError::InvalidArgument(format!(
+                "invalid --since '{}': use a span like 7d, 12h, 2w, or an RFC 3339 timestamp",
+                raw
+            ))
+        }),
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime, Time};
+
+use crate::db;
+use crate::domain::knot_type::KnotType;
+use crate::events::{FullEvent, FullEventKind};
+use crate::state_hierarchy;
+
+use super::diff::parse_since;
+use super::error::AppError;
+use super::rehydrate::replay::full_events_between;
+use super::App;
+
+mod queue_report;
+
+pub use queue_report::{QueueReport, ReportKnot};
+
+const UNATTRIBUTED: &str = "unattributed";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestGrouping {
+    None,
+    Actor,
+}
+
+/// Activity in the event log since `since`, split into one group per actor
+/// (or a single group when ungrouped).
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct Digest {
+    pub since: String,
+    pub groups: Vec<DigestGroup>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct DigestGroup {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    pub created: Vec<DigestItem>,
+    pub completed: Vec<DigestItem>,
+    pub blocked: Vec<DigestItem>,
+    pub state_changes: Vec<DigestItem>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct DigestItem {
+    pub id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    pub at: String,
+}
+
+impl DigestGroup {
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty()
+            && self.completed.is_empty()
+            && self.blocked.is_empty()
+            && self.state_changes.is_empty()
+    }
+}
+
+impl App {
+    /// Summarizes knot creations and state changes recorded since `since`
+    /// (`today`, `yesterday`, a `YYYY-MM-DD` date, a span like `7d`, or an
+    /// RFC 3339 timestamp). Lease knots are left out.
+    pub fn digest(&self, since: &str, grouping: DigestGrouping) -> Result<Digest, AppError> {
+        let now = OffsetDateTime::now_utc();
+        let cutoff = parse_window_start(since, now)?;
+        let roots = [
+            self.store_paths.root.clone(),
+            self.store_paths.worktree_path(),
+        ];
+        let mut groups: BTreeMap<Option<String>, DigestGroup> = BTreeMap::new();
+        for event in full_events_between(&roots, cutoff, now)? {
+            let record = db::get_knot_hot(&self.conn, &event.knot_id)?;
+            if record
+                .as_ref()
+                .is_some_and(|record| record.knot_type.as_deref() == Some(KnotType::Lease.as_str()))
+            {
+                continue;
+            }
+            let title = record
+                .map(|record| record.title)
+                .or_else(|| payload_str(&event, "title"))
+                .unwrap_or_else(|| event.knot_id.clone());
+            let key = match grouping {
+                DigestGrouping::None => None,
+                DigestGrouping::Actor => Some(actor_of(&event)),
+            };
+            let group = groups.entry(key.clone()).or_insert_with(|| DigestGroup {
+                actor: key,
+                ..DigestGroup::default()
+            });
+            record_event(group, &event, title)?;
+        }
+        Ok(Digest {
+            since: cutoff
+                .format(&Rfc3339)
+                .expect("RFC3339 formatting for UTC timestamp should never fail"),
+            groups: groups
+                .into_values()
+                .filter(|group| !group.is_empty())
+                .collect(),
+        })
+    }
+}
+
+fn record_event(group: &mut DigestGroup, event: &FullEvent, title: String) -> Result<(), AppError> {
+    let item = |from: Option<String>, to: Option<String>| DigestItem {
+        id: event.knot_id.clone(),
+        title,
+        from,
+        to,
+        at: event.occurred_at.clone(),
+    };
+    if event.event_type == FullEventKind::KnotCreated.as_str() {
+        if payload_str(event, "type").as_deref() != Some(KnotType::Lease.as_str()) {
+            group.created.push(item(None, payload_str(event, "state")));
+        }
+        return Ok(());
+    }
+    if event.event_type != FullEventKind::KnotStateSet.as_str() {
+        return Ok(());
+    }
+    let (from, to) = (payload_str(event, "from"), payload_str(event, "to"));
+    let Some(next) = to.as_deref() else {
+        return Ok(());
+    };
+    let bucket = if state_hierarchy::is_terminal_state(next)? {
+        &mut group.completed
+    } else if next == "blocked" {
+        &mut group.blocked
+    } else {
+        &mut group.state_changes
+    };
+    bucket.push(item(from, to));
+    Ok(())
+}
+
+fn actor_of(event: &FullEvent) -> String {
+    payload_str(event, "agent_name")
+        .or_else(|| payload_str(event, "actor_kind"))
+        .unwrap_or_else(|| UNATTRIBUTED.to_string())
+}
+
+fn payload_str(event: &FullEvent, key: &str) -> Option<String> {
+    event
+        .data
+        .get(key)
+        .and_then(Value::as_str)
+        .filter(|value| !value.trim().is_empty())
+        .map(str::to_string)
+}
+
+/// Start of a report window: `today` and `yesterday` are UTC midnights and a
+/// bare date is midnight UTC on that day; anything else goes to `parse_since`.
+pub(crate) fn parse_window_start(
+    raw: &str,
+    now: OffsetDateTime,
+) -> Result<OffsetDateTime, AppError> {
+    let midnight = now.replace_time(Time::MIDNIGHT);
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "today" => Ok(midnight),
+        "yesterday" => Ok(midnight - Duration::days(1)),
+        date if date.len() == 10 => OffsetDateTime::parse(&format!("{date}T00:00:00Z"), &Rfc3339)
+            .map_err(|_| {
+                AppError::InvalidArgument(format!(
+                    "invalid date '{}': expected YYYY-MM-DD",
+                    raw.trim()
+                ))
+            }),
+        _ => parse_since(raw, now),
+    }
+}
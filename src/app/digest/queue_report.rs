@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::db;
+use crate::domain::knot_type::KnotType;
+use crate::repo_config::DigestTarget;
+
+use super::super::diff::parse_span;
+use super::super::error::AppError;
+use super::super::rehydrate::replay::parse_replay_timestamp;
+use super::super::App;
+
+/// How long a knot may sit in one state before a digest calls it stale,
+/// when neither a state budget nor the digest's `stale_after` says.
+const DEFAULT_STALE_AFTER: &str = "7d";
+
+/// What one `[digests.<name>]` target reports: knots queued for a review
+/// or gate step, and knots that have sat in their state too long.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct QueueReport {
+    pub name: String,
+    /// Profile ids the report covers; every profile when empty.
+    pub profiles: Vec<String>,
+    pub generated_at: String,
+    pub review_queue: Vec<ReportKnot>,
+    pub stale: Vec<ReportKnot>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ReportKnot {
+    pub id: String,
+    pub title: String,
+    pub state: String,
+    pub profile_id: String,
+    pub waited: String,
+    pub waited_seconds: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget: Option<String>,
+}
+
+impl App {
+    /// The `[digests.<name>]` tables from the repo config, by name.
+    pub fn digest_targets(&self) -> Result<BTreeMap<String, DigestTarget>, AppError> {
+        Ok(self.read_repo_config()?.unwrap_or_default().digests)
+    }
+
+    /// The review queue and stale knots for the digest `name`, newest
+    /// waits last.
+    pub fn queue_report(&self, name: &str, target: &DigestTarget) -> Result<QueueReport, AppError> {
+        self.queue_report_at(name, target, OffsetDateTime::now_utc())
+    }
+
+    pub(crate) fn queue_report_at(
+        &self,
+        name: &str,
+        target: &DigestTarget,
+        now: OffsetDateTime,
+    ) -> Result<QueueReport, AppError> {
+        let profiles = target
+            .profiles
+            .iter()
+            .map(|profile| Ok(self.profile_registry.require(profile)?.id.clone()))
+            .collect::<Result<Vec<_>, AppError>>()?;
+        let stale_after = digest_span(
+            name,
+            "stale_after",
+            target.stale_after.as_deref().unwrap_or(DEFAULT_STALE_AFTER),
+        )?;
+        let mut report = QueueReport {
+            name: name.to_string(),
+            profiles,
+            generated_at: now
+                .format(&Rfc3339)
+                .expect("RFC3339 formatting for UTC timestamp should never fail"),
+            review_queue: Vec::new(),
+            stale: Vec::new(),
+        };
+        for knot in self.list_knots()? {
+            if knot.knot_type == KnotType::Lease
+                || !(report.profiles.is_empty() || report.profiles.contains(&knot.profile_id))
+            {
+                continue;
+            }
+            let profile = self.profile_registry.require(&knot.profile_id)?;
+            if profile.is_terminal_state(&knot.state) || knot.state == "deferred" {
+                continue;
+            }
+            let age = self.state_age_at(&knot, now)?;
+            let entry = ReportKnot {
+                id: knot.id.clone(),
+                title: knot.title.clone(),
+                state: knot.state.clone(),
+                profile_id: knot.profile_id.clone(),
+                waited: age.waited(),
+                waited_seconds: age.waited_seconds,
+                budget: age.budget(),
+            };
+            let awaits_review = profile
+                .action_for_queue_state(&knot.state)
+                .is_some_and(|action| profile.is_gate_action_state(action));
+            if awaits_review {
+                report.review_queue.push(entry.clone());
+            }
+            if age.over_budget || age.waited_seconds > stale_after {
+                report.stale.push(entry);
+            }
+        }
+        report
+            .review_queue
+            .sort_by_key(|knot| std::cmp::Reverse(knot.waited_seconds));
+        report
+            .stale
+            .sort_by_key(|knot| std::cmp::Reverse(knot.waited_seconds));
+        Ok(report)
+    }
+
+    /// Whether `--due` should send digest `name` now: its `every` span has
+    /// passed since this clone last sent it. Digests without `every` are
+    /// only sent on demand.
+    pub fn digest_due(&self, name: &str, target: &DigestTarget) -> Result<bool, AppError> {
+        let Some(every) = target.every.as_deref() else {
+            return Ok(false);
+        };
+        let every = digest_span(name, "every", every)?;
+        let Some(last) = db::get_meta(&self.conn, &last_sent_key(name))? else {
+            return Ok(true);
+        };
+        let elapsed = OffsetDateTime::now_utc() - parse_replay_timestamp(&last)?;
+        Ok(elapsed.whole_seconds() >= every as i64)
+    }
+
+    /// Remembers that this clone sent digest `name` at `at`.
+    pub fn record_digest_sent(&self, name: &str, at: &str) -> Result<(), AppError> {
+        Ok(db::set_meta(&self.conn, &last_sent_key(name), at)?)
+    }
+}
+
+fn last_sent_key(name: &str) -> String {
+    format!("digest_last_sent.{name}")
+}
+
+fn digest_span(name: &str, key: &str, raw: &str) -> Result<u64, AppError> {
+    parse_span(raw)
+        .filter(|span| span.is_positive())
+        .map(|span| span.whole_seconds() as u64)
+        .ok_or_else(|| {
+            AppError::InvalidArgument(format!(
+                "invalid digests.{name}.{key} = '{raw}' in .knots/config.toml; \
+                 use a span like 30m, 4h, 2d, or 1w"
+            ))
+        })
+}
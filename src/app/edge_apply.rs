@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::db;
+use crate::locks::FileLock;
+
+use super::error::AppError;
+use super::App;
+
+/// One edge an edge file adds or removes, with the line that asked for it.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct EdgeChange {
+    pub line: usize,
+    /// `add` or `remove`.
+    pub op: &'static str,
+    pub src: String,
+    pub kind: String,
+    pub dst: String,
+}
+
+/// What `kno edge apply` did, or would do under `--dry-run`.
+#[derive(Debug, Clone, Serialize)]
+pub struct EdgePlan {
+    pub changes: Vec<EdgeChange>,
+    /// Lines whose edge already exists (or is already absent).
+    pub unchanged: usize,
+    pub applied: bool,
+}
+
+struct EdgeLine<'a> {
+    line: usize,
+    add: bool,
+    src: &'a str,
+    kind: &'a str,
+    dst: &'a str,
+}
+
+impl App {
+    /// Applies an edge file: one `src kind dst` per line, where a leading
+    /// `-` removes the edge and `+` (or nothing) adds it. Blank lines and
+    /// `#` comments are skipped. Every line is parsed and resolved before
+    /// anything is written, and a failed write undoes the changes already
+    /// made, so the file applies completely or not at all.
+    pub fn apply_edge_file(&self, text: &str, dry_run: bool) -> Result<EdgePlan, AppError> {
+        let (lines, mut errors) = parse_edge_lines(text);
+        let mut wanted: BTreeMap<(String, String, String), (usize, bool)> = BTreeMap::new();
+        for entry in lines {
+            let (src, dst) = match self.resolve_edge_line(&entry) {
+                Ok(ids) => ids,
+                Err(err) => {
+                    errors.push(format!("line {}: {err}", entry.line));
+                    continue;
+                }
+            };
+            let key = (src, entry.kind.to_string(), dst);
+            match wanted.get(&key) {
+                Some((first, add)) if *add != entry.add => errors.push(format!(
+                    "line {}: contradicts line {first}, which {} the same edge",
+                    entry.line,
+                    if *add { "adds" } else { "removes" }
+                )),
+                Some(_) => {}
+                None => {
+                    wanted.insert(key, (entry.line, entry.add));
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(AppError::InvalidArgument(format!(
+                "edge file has {} problem(s); nothing was applied:\n{}",
+                errors.len(),
+                errors.join("\n")
+            )));
+        }
+
+        let _locks = if dry_run {
+            None
+        } else {
+            Some(self.lock_edges()?)
+        };
+        let mut changes = Vec::new();
+        let mut unchanged = 0;
+        for ((src, kind, dst), (line, add)) in wanted {
+            let exists = db::list_edges(&self.conn, &src, db::EdgeDirection::Outgoing)?
+                .iter()
+                .any(|edge| edge.kind == kind && edge.dst == dst);
+            if exists == add {
+                unchanged += 1;
+                continue;
+            }
+            changes.push(EdgeChange {
+                line,
+                op: if add { "add" } else { "remove" },
+                src,
+                kind,
+                dst,
+            });
+        }
+        changes.sort_by_key(|change| change.line);
+        if !dry_run {
+            self.apply_edge_changes(&changes)?;
+        }
+        Ok(EdgePlan {
+            changes,
+            unchanged,
+            applied: !dry_run,
+        })
+    }
+
+    /// Both ends must be existing knots, except a `repo:id` target.
+    fn resolve_edge_line(&self, entry: &EdgeLine<'_>) -> Result<(String, String), AppError> {
+        let src = self.resolve_knot_token(entry.src)?;
+        if db::get_knot_hot(&self.conn, &src)?.is_none() {
+            return Err(AppError::NotFound(entry.src.to_string()));
+        }
+        if let Some(remote) = self.normalize_remote_ref(entry.dst)? {
+            return Ok((src, remote));
+        }
+        let dst = self.resolve_knot_token(entry.dst)?;
+        if db::get_knot_hot(&self.conn, &dst)?.is_none() {
+            return Err(AppError::NotFound(entry.dst.to_string()));
+        }
+        Ok((src, dst))
+    }
+
+    fn apply_edge_changes(&self, changes: &[EdgeChange]) -> Result<(), AppError> {
+        for (done, change) in changes.iter().enumerate() {
+            let add = change.op == "add";
+            if let Err(err) = self.apply_edge_change(&change.src, &change.kind, &change.dst, add) {
+                for undo in changes[..done].iter().rev() {
+                    let _ =
+                        self.apply_edge_change(&undo.src, &undo.kind, &undo.dst, undo.op != "add");
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    fn lock_edges(&self) -> Result<(FileLock, FileLock), AppError> {
+        let repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        let cache_guard = FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        Ok((repo_guard, cache_guard))
+    }
+}
+
+fn parse_edge_lines(text: &str) -> (Vec<EdgeLine<'_>>, Vec<String>) {
+    let mut lines = Vec::new();
+    let mut errors = Vec::new();
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let content = raw.split('#').next().unwrap_or_default().trim();
+        if content.is_empty() {
+            continue;
+        }
+        let (add, rest) = match content.strip_prefix('-') {
+            Some(rest) => (false, rest),
+            None => (true, content.strip_prefix('+').unwrap_or(content)),
+        };
+        let words: Vec<&str> = rest.split_whitespace().collect();
+        match words.as_slice() {
+            [src, kind, dst] => lines.push(EdgeLine {
+                line,
+                add,
+                src,
+                kind,
+                dst,
+            }),
+            _ => errors.push(format!(
+                "line {line}: expected `<src> <kind> <dst>`, got `{content}`"
+            )),
+        }
+    }
+    (lines, errors)
+}
@@ -18,10 +18,16 @@ use super::helpers::{
 use super::types::{EdgeView, StateActorMetadata};
 use super::App;
 
+mod graph_stats;
+
+#[cfg(test)]
+pub(crate) use graph_stats::BlockingKnot;
+pub use graph_stats::GraphReport;
+
 impl App {
     pub fn add_edge(&self, src: &str, kind: &str, dst: &str) -> Result<EdgeView, AppError> {
         let src = self.resolve_knot_token(src)?;
-        let dst = self.resolve_knot_token(dst)?;
+        let dst = self.resolve_edge_target(dst)?;
         let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
         let _cache_guard =
             FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
@@ -30,13 +36,22 @@ impl App {
 
     pub fn remove_edge(&self, src: &str, kind: &str, dst: &str) -> Result<EdgeView, AppError> {
         let src = self.resolve_knot_token(src)?;
-        let dst = self.resolve_knot_token(dst)?;
+        let dst = self.resolve_edge_target(dst)?;
         let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
         let _cache_guard =
             FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
         self.apply_edge_change(&src, kind, &dst, false)
     }
 
+    /// Local targets resolve like any knot token; `repo-name:knot-id`
+    /// targets point into another registered repo and stay qualified.
+    fn resolve_edge_target(&self, dst: &str) -> Result<String, AppError> {
+        match self.normalize_remote_ref(dst)? {
+            Some(remote) => Ok(remote),
+            None => self.resolve_knot_token(dst),
+        }
+    }
+
     pub fn list_edges(&self, id: &str, direction: &str) -> Result<Vec<EdgeView>, AppError> {
         let id = self.resolve_knot_token(id)?;
         let direction = parse_edge_direction(direction)?;
@@ -44,6 +59,12 @@ impl App {
         Ok(rows.into_iter().map(EdgeView::from).collect())
     }
 
+    /// Every edge touching one of `ids` (already resolved), in one query.
+    pub fn edges_for_knots(&self, ids: &[String]) -> Result<Vec<EdgeView>, AppError> {
+        let rows = db::list_edges_touching(&self.conn, ids)?;
+        Ok(rows.into_iter().map(EdgeView::from).collect())
+    }
+
     pub fn list_layout_edges(&self) -> Result<Vec<EdgeView>, AppError> {
         let mut rows = db::list_edges_by_kind(&self.conn, "parent_of")?;
         rows.extend(db::list_edges_by_kind(&self.conn, "blocked_by")?);
@@ -51,7 +72,7 @@ impl App {
         Ok(rows.into_iter().map(EdgeView::from).collect())
     }
 
-    fn apply_edge_change(
+    pub(super) fn apply_edge_change(
         &self,
         src: &str,
         kind: &str,
@@ -65,6 +86,9 @@ impl App {
         }
         let current = db::get_knot_hot(&self.conn, src)?
             .ok_or_else(|| AppError::NotFound(src.to_string()))?;
+        if add {
+            self.guard_private_edge_target(src, dst)?;
+        }
         let occurred_at = now_utc_rfc3339();
         let full_kind = if add {
             FullEventKind::KnotEdgeAdd
@@ -181,6 +205,8 @@ impl App {
                 body: current.body.as_deref(),
                 description: current.description.as_deref(),
                 acceptance: current.acceptance.as_deref(),
+                estimate: current.estimate.as_deref(),
+                custom_fields: &current.custom_fields,
                 priority: current.priority,
                 knot_type: current.knot_type.as_deref(),
                 tags: &current.tags,
@@ -197,6 +223,7 @@ impl App {
                 deferred_from_state: current.deferred_from_state.as_deref(),
                 blocked_from_state: current.blocked_from_state.as_deref(),
                 created_at: current.created_at.as_deref(),
+                snoozed_until: current.snoozed_until.as_deref(),
             },
         )?;
         Ok(())
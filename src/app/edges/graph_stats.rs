@@ -0,0 +1,165 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use serde::Serialize;
+
+use crate::db;
+use crate::domain::knot_type::KnotType;
+
+use super::super::error::AppError;
+use super::super::App;
+
+/// Shape of the knot graph for `kno stats graph`. Chains, blockers, and
+/// orphans only consider open knots; edge counts cover every local edge.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct GraphReport {
+    pub open_knots: usize,
+    pub edges: BTreeMap<String, usize>,
+    pub dangling_edges: usize,
+    /// Ids along the longest dependency chain, from the knot that waits
+    /// longest to the prerequisite nothing else gates.
+    pub longest_chain: Vec<String>,
+    pub most_blocking: Vec<BlockingKnot>,
+    /// Open knots with no local edge of any kind.
+    pub orphans: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct BlockingKnot {
+    pub id: String,
+    pub title: String,
+    pub state: String,
+    /// Open knots waiting on this one directly.
+    pub direct: usize,
+    /// Open knots waiting on this one through any chain of dependencies.
+    pub total: usize,
+}
+
+impl App {
+    /// Graph metrics over local edges, keeping the `top` knots that hold
+    /// up the most open work.
+    pub fn graph_report(&self, top: usize) -> Result<GraphReport, AppError> {
+        let mut open = HashMap::new();
+        for knot in self.list_knots()? {
+            let terminal = self
+                .profile_registry
+                .require(&knot.profile_id)?
+                .is_terminal_state(&knot.state);
+            if knot.knot_type != KnotType::Lease && !terminal {
+                open.insert(knot.id.clone(), knot);
+            }
+        }
+        let edges = db::list_local_edges(&self.conn)?;
+        let mut by_kind = BTreeMap::new();
+        let mut linked = HashSet::new();
+        // dependent -> prerequisites, and the reverse, among open knots.
+        let mut waits_on: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut gates: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &edges {
+            *by_kind.entry(edge.kind.clone()).or_insert(0) += 1;
+            linked.insert(edge.src.as_str());
+            linked.insert(edge.dst.as_str());
+            let (dependent, prerequisite) = match edge.kind.as_str() {
+                "blocked_by" => (edge.src.as_str(), edge.dst.as_str()),
+                "blocks" => (edge.dst.as_str(), edge.src.as_str()),
+                _ => continue,
+            };
+            if open.contains_key(dependent) && open.contains_key(prerequisite) {
+                waits_on.entry(dependent).or_default().push(prerequisite);
+                gates.entry(prerequisite).or_default().push(dependent);
+            }
+        }
+
+        let mut most_blocking: Vec<BlockingKnot> = gates
+            .iter()
+            .map(|(id, dependents)| {
+                let knot = &open[*id];
+                BlockingKnot {
+                    id: knot.id.clone(),
+                    title: knot.title.clone(),
+                    state: knot.state.clone(),
+                    direct: dependents.len(),
+                    total: reachable(&gates, id),
+                }
+            })
+            .collect();
+        most_blocking.sort_by(|a, b| {
+            (b.total, b.direct)
+                .cmp(&(a.total, a.direct))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        most_blocking.truncate(top);
+
+        Ok(GraphReport {
+            open_knots: open.len(),
+            edges: by_kind,
+            dangling_edges: db::list_dangling_edges(&self.conn)?.len(),
+            longest_chain: longest_chain(&waits_on),
+            most_blocking,
+            orphans: open
+                .keys()
+                .filter(|id| !linked.contains(id.as_str()))
+                .count(),
+        })
+    }
+}
+
+/// How many distinct knots can be reached from `start` along `graph`.
+fn reachable(graph: &HashMap<&str, Vec<&str>>, start: &str) -> usize {
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(id) = queue.pop_front() {
+        for next in graph.get(id).into_iter().flatten() {
+            if seen.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    seen.len() - 1
+}
+
+/// Longest path along `waits_on`. An edge that closes a cycle ends the
+/// path there rather than looping.
+fn longest_chain(waits_on: &HashMap<&str, Vec<&str>>) -> Vec<String> {
+    fn depth<'a>(
+        id: &'a str,
+        waits_on: &HashMap<&'a str, Vec<&'a str>>,
+        memo: &mut HashMap<&'a str, (usize, Option<&'a str>)>,
+        visiting: &mut HashSet<&'a str>,
+    ) -> usize {
+        if let Some((length, _)) = memo.get(id) {
+            return *length;
+        }
+        visiting.insert(id);
+        let mut best = (1, None);
+        for next in waits_on.get(id).into_iter().flatten() {
+            if visiting.contains(next) {
+                continue;
+            }
+            let length = depth(next, waits_on, memo, visiting) + 1;
+            if length > best.0 || (length == best.0 && Some(*next) < best.1) {
+                best = (length, Some(*next));
+            }
+        }
+        visiting.remove(id);
+        memo.insert(id, best);
+        best.0
+    }
+
+    let mut memo = HashMap::new();
+    let mut starts: Vec<&str> = waits_on.keys().copied().collect();
+    starts.sort_unstable();
+    let mut best: Option<(usize, &str)> = None;
+    for id in starts {
+        let length = depth(id, waits_on, &mut memo, &mut HashSet::new());
+        if best.is_none_or(|(longest, _)| length > longest) {
+            best = Some((length, id));
+        }
+    }
+    let mut chain = Vec::new();
+    let mut next = best.map(|(_, id)| id);
+    while let Some(id) = next {
+        chain.push(id.to_string());
+        next = memo.get(id).and_then(|(_, next)| *next);
+    }
+    chain
+}
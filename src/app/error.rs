@@ -42,6 +42,16 @@ pub enum AppError {
         target_state: String,
         descendants: Vec<HierarchyKnot>,
     },
+    WipLimitReached {
+        state: String,
+        limit: usize,
+    },
+    DefinitionOfDoneUnmet {
+        knot_id: String,
+        target_state: String,
+        profile_id: String,
+        missing: Vec<String>,
+    },
     InvalidArgument(String),
     UnsupportedDistribution {
         action: String,
@@ -105,6 +115,26 @@ impl fmt::Display for AppError {
                 target_state,
                 state_hierarchy::format_hierarchy_knots(descendants)
             ),
+            AppError::WipLimitReached { state, limit } => write!(
+                f,
+                "WIP limit reached: '{}' already holds {} knot(s) (limit {}); \
+                 finish or move one first, or rerun with --force",
+                state, limit, limit
+            ),
+            AppError::DefinitionOfDoneUnmet {
+                knot_id,
+                target_state,
+                profile_id,
+                missing,
+            } => write!(
+                f,
+                "cannot move '{}' to '{}': profile '{}' requires {}; \
+                 add what is missing, or rerun with --force",
+                knot_id,
+                target_state,
+                profile_id,
+                missing.join("; ")
+            ),
             AppError::InvalidArgument(message) => write!(f, "{}", message),
             AppError::UnsupportedDistribution { action, mode } => {
                 write!(f, "{action} is not supported in {mode} mode")
@@ -140,6 +170,8 @@ impl Error for AppError {
             AppError::StaleWorkflowHead { .. }
             | AppError::HierarchyProgressBlocked { .. }
             | AppError::TerminalCascadeApprovalRequired { .. }
+            | AppError::WipLimitReached { .. }
+            | AppError::DefinitionOfDoneUnmet { .. }
             | AppError::InvalidArgument(_)
             | AppError::UnsupportedDistribution { .. }
             | AppError::NotFound(_)
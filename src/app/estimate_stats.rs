@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::domain::estimate::Estimate;
+use crate::domain::step_history::StepRecord;
+
+use super::error::AppError;
+use super::types::KnotView;
+use super::App;
+
+const UNTAGGED: &str = "(untagged)";
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EstimateGrouping {
+    Type,
+    Tag,
+}
+
+/// Estimates of shipped knots next to what they actually took, per group.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct EstimateReport {
+    pub group_by: EstimateGrouping,
+    pub groups: Vec<EstimateGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct EstimateGroup {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points: Option<EstimateTotals>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<EstimateTotals>,
+}
+
+/// Sums over the knots of one group that share an estimate kind.
+/// `estimated` is story points or minutes; `cycle_minutes` runs from
+/// creation to the knot's last update, and `tracked_minutes` adds up the
+/// finished steps in its step history.
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct EstimateTotals {
+    pub knots: u64,
+    pub estimated: u64,
+    pub cycle_minutes: u64,
+    pub tracked_minutes: u64,
+}
+
+impl App {
+    pub fn estimate_report(&self, group_by: EstimateGrouping) -> Result<EstimateReport, AppError> {
+        let mut groups: BTreeMap<String, EstimateGroup> = BTreeMap::new();
+        for knot in self.list_knots()? {
+            let Some(Ok(estimate)) = knot.estimate.as_deref().map(Estimate::parse) else {
+                continue;
+            };
+            if !self.is_shipped(&knot)? {
+                continue;
+            }
+            let cycle = cycle_minutes(&knot);
+            let tracked = tracked_minutes(&knot.step_history);
+            for key in group_keys(&knot, group_by) {
+                let group = groups.entry(key.clone()).or_insert_with(|| EstimateGroup {
+                    key,
+                    points: None,
+                    duration: None,
+                });
+                let (slot, estimated) = match estimate {
+                    Estimate::Points(points) => (&mut group.points, u64::from(points)),
+                    Estimate::Minutes(minutes) => (&mut group.duration, minutes),
+                };
+                let totals = slot.get_or_insert_with(EstimateTotals::default);
+                totals.knots += 1;
+                totals.estimated += estimated;
+                totals.cycle_minutes += cycle;
+                totals.tracked_minutes += tracked;
+            }
+        }
+        Ok(EstimateReport {
+            group_by,
+            groups: groups.into_values().collect(),
+        })
+    }
+
+    /// Terminal knots count as delivered unless they were abandoned.
+    fn is_shipped(&self, knot: &KnotView) -> Result<bool, AppError> {
+        let profile = self.profile_registry.require(&knot.profile_id)?;
+        Ok(profile.is_terminal_state(&knot.state) && knot.state != "abandoned")
+    }
+}
+
+fn group_keys(knot: &KnotView, group_by: EstimateGrouping) -> Vec<String> {
+    match group_by {
+        EstimateGrouping::Type => vec![knot.knot_type.as_str().to_string()],
+        EstimateGrouping::Tag if knot.tags.is_empty() => vec![UNTAGGED.to_string()],
+        EstimateGrouping::Tag => knot.tags.clone(),
+    }
+}
+
+fn cycle_minutes(knot: &KnotView) -> u64 {
+    knot.created_at
+        .as_deref()
+        .map(|created| minutes_between(created, &knot.updated_at))
+        .unwrap_or(0)
+}
+
+fn tracked_minutes(steps: &[StepRecord]) -> u64 {
+    steps
+        .iter()
+        .filter_map(|step| {
+            let ended = step.ended_at.as_deref()?;
+            Some(minutes_between(&step.started_at, ended))
+        })
+        .sum()
+}
+
+fn minutes_between(start: &str, end: &str) -> u64 {
+    let (Ok(start), Ok(end)) = (
+        OffsetDateTime::parse(start, &Rfc3339),
+        OffsetDateTime::parse(end, &Rfc3339),
+    ) else {
+        return 0;
+    };
+    u64::try_from((end - start).whole_minutes()).unwrap_or(0)
+}
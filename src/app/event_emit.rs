@@ -0,0 +1,319 @@
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::db::{self, UpsertKnotHot};
+use crate::domain::event_type::{
+    apply_field_changes, payload_value, validate_event_type_name, EventTypeSpec,
+};
+use crate::domain::metadata::MetadataEntryInput;
+use crate::events::{new_event_id, now_utc_rfc3339, EventRecord, FullEvent};
+use crate::locks::FileLock;
+
+use super::error::AppError;
+use super::types::{KnotView, UpdateKnotPatch};
+use super::App;
+
+/// Event types `kno events emit` accepts.
+pub const EMITTABLE_EVENT_TYPES: &[&str] = &[
+    "knot.title_set",
+    "knot.description_set",
+    "knot.acceptance_set",
+    "knot.estimate_set",
+    "knot.priority_set",
+    "knot.field_set",
+    "knot.state_set",
+    "knot.tag_add",
+    "knot.tag_remove",
+    "knot.note_added",
+    "knot.handoff_capsule_added",
+    "knot.edge_add",
+    "knot.edge_remove",
+    "knot.criterion_added",
+];
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TitleData {
+    to: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DescriptionData {
+    description: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AcceptanceData {
+    acceptance: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EstimateData {
+    estimate: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PriorityData {
+    priority: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FieldData {
+    field: String,
+    /// `null` clears the field.
+    value: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StateData {
+    to: String,
+    #[serde(default)]
+    force: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TagData {
+    tag: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EntryData {
+    content: String,
+    username: Option<String>,
+    agentname: Option<String>,
+    model: Option<String>,
+    version: Option<String>,
+}
+
+impl From<EntryData> for MetadataEntryInput {
+    fn from(data: EntryData) -> Self {
+        MetadataEntryInput {
+            content: data.content,
+            username: data.username,
+            datetime: None,
+            agentname: data.agentname,
+            model: data.model,
+            version: data.version,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EdgeData {
+    kind: String,
+    dst: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CriterionData {
+    text: String,
+}
+
+impl App {
+    /// Records an event from an external producer. `data` must match the
+    /// type's schema exactly (required fields, value types, no extras); it
+    /// is then applied the way the matching `kno` command applies it, so
+    /// the event id, timestamp, locks, index head, and cache are all
+    /// handled as for any other edit.
+    pub fn emit_event(
+        &self,
+        event_type: &str,
+        knot: &str,
+        data: &Value,
+    ) -> Result<KnotView, AppError> {
+        let mut patch = UpdateKnotPatch::default();
+        match event_type {
+            "knot.title_set" => patch.title = Some(parse::<TitleData>(event_type, data)?.to),
+            "knot.description_set" => {
+                patch.description = Some(parse::<DescriptionData>(event_type, data)?.description)
+            }
+            "knot.acceptance_set" => {
+                patch.acceptance = Some(parse::<AcceptanceData>(event_type, data)?.acceptance)
+            }
+            "knot.estimate_set" => {
+                patch.estimate = Some(parse::<EstimateData>(event_type, data)?.estimate)
+            }
+            "knot.priority_set" => {
+                patch.priority = Some(parse::<PriorityData>(event_type, data)?.priority)
+            }
+            "knot.field_set" => {
+                let field = parse::<FieldData>(event_type, data)?;
+                let value = field.value.unwrap_or_default();
+                patch.custom_fields = vec![format!("{}={value}", field.field)];
+            }
+            "knot.state_set" => {
+                let state = parse::<StateData>(event_type, data)?;
+                patch.status = Some(state.to);
+                patch.force = state.force;
+            }
+            "knot.tag_add" => patch.add_tags = vec![parse::<TagData>(event_type, data)?.tag],
+            "knot.tag_remove" => patch.remove_tags = vec![parse::<TagData>(event_type, data)?.tag],
+            "knot.note_added" => {
+                patch.add_note = Some(parse::<EntryData>(event_type, data)?.into())
+            }
+            "knot.handoff_capsule_added" => {
+                patch.add_handoff_capsule = Some(parse::<EntryData>(event_type, data)?.into())
+            }
+            "knot.edge_add" | "knot.edge_remove" => {
+                let edge = parse::<EdgeData>(event_type, data)?;
+                if event_type == "knot.edge_add" {
+                    self.add_edge(knot, &edge.kind, &edge.dst)?;
+                } else {
+                    self.remove_edge(knot, &edge.kind, &edge.dst)?;
+                }
+                return self.emitted_knot(knot);
+            }
+            "knot.criterion_added" => {
+                self.add_criterion(knot, &parse::<CriterionData>(event_type, data)?.text)?;
+                return self.emitted_knot(knot);
+            }
+            custom => return self.emit_custom_event(custom, knot, data),
+        }
+        self.update_knot(knot, patch)
+    }
+
+    /// Emits a type declared under `[event_types]`. Every payload key the
+    /// type projects must be present, and each value must already be in the
+    /// form its custom field stores, since other clones project it as is.
+    fn emit_custom_event(
+        &self,
+        event_type: &str,
+        knot: &str,
+        data: &Value,
+    ) -> Result<KnotView, AppError> {
+        let config = self.read_repo_config()?.unwrap_or_default();
+        let Some(spec) = config.event_types.get(event_type) else {
+            let mut supported: Vec<&str> = EMITTABLE_EVENT_TYPES.to_vec();
+            supported.extend(config.event_types.keys().map(String::as_str));
+            return Err(AppError::InvalidArgument(format!(
+                "cannot emit '{event_type}'; supported types: {}",
+                supported.join(", ")
+            )));
+        };
+        validate_event_type_name(event_type).map_err(AppError::InvalidArgument)?;
+        let Some(object) = data.as_object() else {
+            return Err(AppError::InvalidArgument(format!(
+                "{event_type} data must be a JSON object"
+            )));
+        };
+        for (field, key) in &spec.set {
+            let value = payload_value(object, key).ok_or_else(|| {
+                AppError::InvalidArgument(format!("invalid {event_type} data: missing `{key}`"))
+            })?;
+            let field_spec = config.fields.get(field).ok_or_else(|| {
+                AppError::InvalidArgument(format!(
+                    "{event_type} sets field '{field}', which is not declared under [fields]"
+                ))
+            })?;
+            let raw = match value {
+                Value::Null => continue,
+                Value::String(text) => text.clone(),
+                Value::Number(_) | Value::Bool(_) => value.to_string(),
+                _ => {
+                    return Err(AppError::InvalidArgument(format!(
+                        "invalid {event_type} data: `{key}` must be a string, number, \
+                         boolean, or null"
+                    )))
+                }
+            };
+            let stored = field_spec
+                .normalize(field, &raw)
+                .map_err(AppError::InvalidArgument)?;
+            if stored != raw {
+                return Err(AppError::InvalidArgument(format!(
+                    "invalid {event_type} data: `{key}` must be '{stored}', not '{raw}'"
+                )));
+            }
+        }
+        self.write_custom_event(event_type, knot, object, spec)
+    }
+
+    fn write_custom_event(
+        &self,
+        event_type: &str,
+        knot: &str,
+        data: &Map<String, Value>,
+        spec: &EventTypeSpec,
+    ) -> Result<KnotView, AppError> {
+        let id = self.resolve_knot_token(knot)?;
+        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        let _cache_guard =
+            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        let mut record =
+            db::get_knot_hot(&self.conn, &id)?.ok_or_else(|| AppError::NotFound(id.clone()))?;
+        let occurred_at = now_utc_rfc3339();
+        self.writer
+            .write(&EventRecord::full(FullEvent::with_identity(
+                new_event_id(),
+                occurred_at.clone(),
+                id.clone(),
+                event_type,
+                Value::Object(data.clone()),
+            )))?;
+        apply_field_changes(&mut record.custom_fields, &spec.field_changes(data));
+        db::upsert_knot_hot(
+            &self.conn,
+            &UpsertKnotHot {
+                id: &record.id,
+                title: &record.title,
+                state: &record.state,
+                updated_at: &occurred_at,
+                body: record.body.as_deref(),
+                description: record.description.as_deref(),
+                acceptance: record.acceptance.as_deref(),
+                estimate: record.estimate.as_deref(),
+                custom_fields: &record.custom_fields,
+                priority: record.priority,
+                knot_type: record.knot_type.as_deref(),
+                tags: &record.tags,
+                notes: &record.notes,
+                handoff_capsules: &record.handoff_capsules,
+                invariants: &record.invariants,
+                step_history: &record.step_history,
+                gate_data: &record.gate_data,
+                lease_data: &record.lease_data,
+                lease_id: record.lease_id.as_deref(),
+                workflow_id: &record.workflow_id,
+                profile_id: &record.profile_id,
+                profile_etag: record.profile_etag.as_deref(),
+                deferred_from_state: record.deferred_from_state.as_deref(),
+                blocked_from_state: record.blocked_from_state.as_deref(),
+                created_at: record.created_at.as_deref(),
+                snoozed_until: record.snoozed_until.as_deref(),
+            },
+        )?;
+        self.emitted_knot(&id)
+    }
+
+    fn emitted_knot(&self, knot: &str) -> Result<KnotView, AppError> {
+        self.show_knot(knot)?
+            .ok_or_else(|| AppError::NotFound(knot.to_string()))
+    }
+}
+
+fn parse<T: DeserializeOwned>(event_type: &str, data: &Value) -> Result<T, AppError> {
+    if !data.is_object() {
+        return Err(AppError::InvalidArgument(format!(
+            "{event_type} data must be a JSON object"
+        )));
+    }
+    T::deserialize(data)
+        .map_err(|err| AppError::InvalidArgument(format!("invalid {event_type} data: {err}")))
+}
+
+#[cfg(test)]
+#[path = "tests_event_emit.rs"]
+mod tests;
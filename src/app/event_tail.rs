@@ -0,0 +1,62 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::events::{private_root, FullEvent};
+
+use super::error::AppError;
+use super::rehydrate::{collect_json_paths, resolve_subdir};
+use super::App;
+
+/// Follows the full-event log: events this clone writes, private events,
+/// and events a pull brings into the synced worktree.
+pub struct EventTail {
+    roots: Vec<PathBuf>,
+    read_paths: HashSet<PathBuf>,
+    event_ids: HashSet<String>,
+}
+
+impl EventTail {
+    /// Events that appeared since the last poll (every event, on the first
+    /// one), ordered by when they occurred. A file that does not parse yet
+    /// is assumed to be mid-write and read again on the next poll.
+    pub fn poll(&mut self) -> Result<Vec<FullEvent>, AppError> {
+        let mut events = Vec::new();
+        for root in &self.roots {
+            for path in collect_json_paths(&resolve_subdir(root, "events"))? {
+                if self.read_paths.contains(&path) {
+                    continue;
+                }
+                let parsed = crate::compression::read(&path)
+                    .ok()
+                    .and_then(|bytes| serde_json::from_slice::<FullEvent>(&bytes).ok());
+                let Some(event) = parsed else {
+                    continue;
+                };
+                self.read_paths.insert(path);
+                if self.event_ids.insert(event.event_id.clone()) {
+                    events.push(event);
+                }
+            }
+        }
+        events.sort_by(|a, b| {
+            (a.occurred_at.as_str(), a.event_id.as_str())
+                .cmp(&(b.occurred_at.as_str(), b.event_id.as_str()))
+        });
+        Ok(events)
+    }
+}
+
+impl App {
+    pub fn event_tail(&self) -> EventTail {
+        let root = &self.store_paths.root;
+        EventTail {
+            roots: vec![
+                root.clone(),
+                private_root(root),
+                self.store_paths.worktree_path(),
+            ],
+            read_paths: HashSet::new(),
+            event_ids: HashSet::new(),
+        }
+    }
+}
@@ -144,6 +144,8 @@ impl App {
                 body: current.body.as_deref(),
                 description: current.description.as_deref(),
                 acceptance: current.acceptance.as_deref(),
+                estimate: current.estimate.as_deref(),
+                custom_fields: &current.custom_fields,
                 priority: current.priority,
                 knot_type: current.knot_type.as_deref(),
                 tags: &current.tags,
@@ -160,6 +162,7 @@ impl App {
                 deferred_from_state: current.deferred_from_state.as_deref(),
                 blocked_from_state: current.blocked_from_state.as_deref(),
                 created_at: current.created_at.as_deref(),
+                snoozed_until: current.snoozed_until.as_deref(),
             },
         )?;
         db::get_knot_hot(&self.conn, &current.id)?
@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::db::{self, GitLinkRecord};
+use crate::events::{new_event_id, now_utc_rfc3339, EventRecord, FullEvent, FullEventKind};
+use crate::locks::FileLock;
+use crate::sync::GitAdapter;
+
+use super::error::AppError;
+use super::App;
+
+/// Commit trailer naming the knots a commit works on: `KNO: <id>[, <id>]`.
+pub(super) const TRAILER_KEY: &str = "KNO";
+/// HEAD of the code repo when push last scanned it for trailers.
+const SCANNED_HEAD_META: &str = "git_link_scanned_head";
+/// Cap on commits read per scan, so a first push in an old repo stays fast.
+const SCAN_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitLinkKind {
+    Commit,
+    Branch,
+}
+
+impl GitLinkKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GitLinkKind::Commit => "commit",
+            GitLinkKind::Branch => "branch",
+        }
+    }
+}
+
+impl App {
+    /// Links a commit or branch of the code repo to knot `id`. Commits are
+    /// resolved to their full sha and subject; linking twice is a no-op.
+    pub fn link_git(
+        &self,
+        id: &str,
+        kind: GitLinkKind,
+        target: &str,
+    ) -> Result<GitLinkRecord, AppError> {
+        let id = self.resolve_knot_token(id)?;
+        let target = target.trim();
+        if target.is_empty() || target.contains(char::is_whitespace) {
+            return Err(AppError::InvalidArgument(format!(
+                "invalid {} '{}'",
+                kind.as_str(),
+                target
+            )));
+        }
+        let (target, subject) = match kind {
+            GitLinkKind::Commit => {
+                let commit = GitAdapter::new()
+                    .log_commits(&self.repo_root, &[target], TRAILER_KEY, 1)
+                    .ok()
+                    .and_then(|commits| commits.into_iter().next())
+                    .ok_or_else(|| {
+                        AppError::InvalidArgument(format!("unknown commit '{target}'"))
+                    })?;
+                (commit.sha, Some(commit.subject))
+            }
+            GitLinkKind::Branch => (target.to_string(), None),
+        };
+
+        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        let _cache_guard =
+            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        if db::get_knot_hot(&self.conn, &id)?.is_none() {
+            return Err(AppError::NotFound(id));
+        }
+        let link = GitLinkRecord {
+            kind: kind.as_str().to_string(),
+            target,
+            subject,
+            linked_at: now_utc_rfc3339(),
+        };
+        self.record_git_link_locked(&id, &link)?;
+        Ok(link)
+    }
+
+    /// Links commits on HEAD since the last scan to the knots named in their
+    /// `KNO:` trailers. Push calls this under the repo lock before
+    /// publishing, so the new link events go out with it.
+    pub(super) fn link_commit_trailers(&self) -> Result<u64, AppError> {
+        let git = GitAdapter::new();
+        let Ok(head) = git.rev_parse(&self.repo_root, "HEAD") else {
+            return Ok(0);
+        };
+        let last = db::get_meta(&self.conn, SCANNED_HEAD_META)?;
+        if last.as_deref() == Some(head.as_str()) {
+            return Ok(0);
+        }
+        // A rewritten history can drop the last scanned commit; rescan HEAD.
+        let since_last = last
+            .map(|last| format!("{last}..{head}"))
+            .and_then(|range| {
+                git.log_commits(&self.repo_root, &[&range], TRAILER_KEY, SCAN_LIMIT)
+                    .ok()
+            });
+        let commits = match since_last {
+            Some(commits) => commits,
+            None => git.log_commits(&self.repo_root, &[&head], TRAILER_KEY, SCAN_LIMIT)?,
+        };
+
+        let _cache_guard =
+            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        let mut linked = 0u64;
+        for commit in commits.iter().rev() {
+            for token in commit
+                .trailers
+                .iter()
+                .flat_map(|value| trailer_tokens(value))
+            {
+                let Ok(id) = self.resolve_knot_token(token) else {
+                    continue;
+                };
+                if db::get_knot_hot(&self.conn, &id)?.is_none() {
+                    continue;
+                }
+                let link = GitLinkRecord {
+                    kind: GitLinkKind::Commit.as_str().to_string(),
+                    target: commit.sha.clone(),
+                    subject: Some(commit.subject.clone()),
+                    linked_at: now_utc_rfc3339(),
+                };
+                if self.record_git_link_locked(&id, &link)? {
+                    linked += 1;
+                }
+            }
+        }
+        db::set_meta(&self.conn, SCANNED_HEAD_META, &head)?;
+        Ok(linked)
+    }
+
+    /// Writes the link event and caches the link, unless it is already known.
+    pub(super) fn record_git_link_locked(
+        &self,
+        id: &str,
+        link: &GitLinkRecord,
+    ) -> Result<bool, AppError> {
+        let known = db::list_git_links(&self.conn, id)?
+            .iter()
+            .any(|existing| existing.kind == link.kind && existing.target == link.target);
+        if known {
+            return Ok(false);
+        }
+        self.writer
+            .write(&EventRecord::full(FullEvent::with_identity(
+                new_event_id(),
+                link.linked_at.clone(),
+                id.to_string(),
+                FullEventKind::KnotGitLinkAdded.as_str(),
+                json!({"kind": link.kind, "target": link.target, "subject": link.subject}),
+            )))?;
+        db::insert_git_link(&self.conn, id, link)?;
+        Ok(true)
+    }
+}
+
+/// Splits a `KNO:` trailer value into knot tokens.
+pub(super) fn trailer_tokens(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split([',', ' '])
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// Values of `KNO:` trailer lines in free text such as a PR description.
+pub(super) fn trailer_values(text: &str) -> impl Iterator<Item = &str> {
+    text.lines().filter_map(|line| {
+        let (key, value) = line.trim().split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case(TRAILER_KEY)
+            .then_some(value)
+    })
+}
@@ -0,0 +1,134 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use super::error::AppError;
+use super::types::KnotView;
+use super::App;
+use crate::db;
+use crate::grep::{GrepField, LineMatch, Matcher};
+
+/// Shortest literal the trigram index can look up.
+const MIN_INDEXED_CHARS: usize = 3;
+
+/// One matching line of one knot field.
+#[derive(Debug, Clone, Serialize)]
+pub struct GrepHit {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    pub field: GrepField,
+    /// Event type, for matches in the event log.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>,
+    #[serde(flatten)]
+    pub line: LineMatch,
+}
+
+impl App {
+    /// Searches `fields` of every cached knot. Plain patterns of three or
+    /// more characters are narrowed through the `knot_fts` trigram index
+    /// first; regexes, short patterns, and event searches scan every knot.
+    pub fn grep(&self, matcher: &Matcher, fields: &[GrepField]) -> Result<Vec<GrepHit>, AppError> {
+        let columns: Vec<&str> = fields.iter().filter_map(|f| f.index_column()).collect();
+        let indexed = matcher
+            .literal()
+            .filter(|needle| needle.chars().count() >= MIN_INDEXED_CHARS)
+            .filter(|_| columns.len() == fields.len());
+        let knots = match indexed {
+            Some(needle) => {
+                crate::trace::measure("refresh_grep_index", || db::refresh_grep_index(&self.conn))?;
+                let mut knots = Vec::new();
+                for id in db::grep_index_candidates(&self.conn, &columns, needle)? {
+                    if let Some(record) = db::get_knot_hot(&self.conn, &id)? {
+                        knots.push(self.apply_alias_to_knot(KnotView::from(record))?);
+                    }
+                }
+                knots
+            }
+            None => self.list_knots()?,
+        };
+        let mut events = if fields.contains(&GrepField::Events) {
+            let ids: Vec<String> = knots.iter().map(|knot| knot.id.clone()).collect();
+            self.knot_events(&ids)?
+        } else {
+            Default::default()
+        };
+        let mut hits = Vec::new();
+        for knot in &knots {
+            let hit = |field: GrepField, event_type: Option<&str>, line: LineMatch| GrepHit {
+                id: knot.id.clone(),
+                alias: knot.alias.clone(),
+                field,
+                event_type: event_type.map(str::to_string),
+                line,
+            };
+            for field in fields {
+                if *field == GrepField::Events {
+                    for event in events.remove(&knot.id).unwrap_or_default() {
+                        let mut texts = Vec::new();
+                        string_leaves(&event.data, &mut texts);
+                        for text in texts {
+                            hits.extend(
+                                matcher
+                                    .match_lines(text)
+                                    .into_iter()
+                                    .map(|line| hit(*field, Some(&event.event_type), line)),
+                            );
+                        }
+                    }
+                    continue;
+                }
+                for text in field_texts(knot, *field) {
+                    hits.extend(
+                        matcher
+                            .match_lines(text)
+                            .into_iter()
+                            .map(|line| hit(*field, None, line)),
+                    );
+                }
+            }
+        }
+        Ok(hits)
+    }
+
+    /// Drops hits the repo's `sensitive_fields` would hide from JSON output.
+    /// Event payloads can carry any field, so they go whenever anything is
+    /// sensitive.
+    pub fn visible_grep_hits(&self, hits: Vec<GrepHit>) -> Result<Vec<GrepHit>, AppError> {
+        let sensitive = self.sensitive_fields()?;
+        Ok(hits
+            .into_iter()
+            .filter(|hit| match hit.field {
+                GrepField::Events => sensitive.is_empty(),
+                field => !sensitive.iter().any(|key| key == field.sensitive_key()),
+            })
+            .collect())
+    }
+}
+
+fn field_texts(knot: &KnotView, field: GrepField) -> Vec<&str> {
+    match field {
+        GrepField::Title => vec![knot.title.as_str()],
+        GrepField::Description => knot.description.as_deref().into_iter().collect(),
+        GrepField::Notes => knot
+            .notes
+            .iter()
+            .map(|note| note.content.as_str())
+            .collect(),
+        GrepField::Handoffs => knot
+            .handoff_capsules
+            .iter()
+            .map(|capsule| capsule.content.as_str())
+            .collect(),
+        GrepField::Events => Vec::new(),
+    }
+}
+
+fn string_leaves<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+    match value {
+        Value::String(text) => out.push(text),
+        Value::Array(items) => items.iter().for_each(|item| string_leaves(item, out)),
+        Value::Object(map) => map.values().for_each(|item| string_leaves(item, out)),
+        _ => {}
+    }
+}
@@ -32,6 +32,16 @@ pub(crate) fn parse_edge_direction(raw: &str) -> Result<EdgeDirection, AppError>
     }
 }
 
+/// Canonical estimate text for `raw`; blank input clears the estimate.
+pub(crate) fn normalize_estimate(raw: &str) -> Result<Option<String>, AppError> {
+    let Some(raw) = non_empty(raw) else {
+        return Ok(None);
+    };
+    crate::domain::estimate::Estimate::parse(&raw)
+        .map(|estimate| Some(estimate.to_string()))
+        .map_err(AppError::InvalidArgument)
+}
+
 pub(crate) fn non_empty(raw: &str) -> Option<String> {
     let trimmed = raw.trim();
     if trimmed.is_empty() {
@@ -0,0 +1,48 @@
+use crate::db::{self, IdempotencyRecord};
+use crate::events::now_utc_rfc3339;
+
+use super::error::AppError;
+use super::App;
+
+impl App {
+    /// Returns the recorded output for a previously applied request, or
+    /// `None` when the key has not been used yet. Reusing a key for a
+    /// different command or payload is rejected.
+    pub fn lookup_idempotent_output(
+        &self,
+        key: &str,
+        command: &str,
+        fingerprint: &str,
+    ) -> Result<Option<String>, AppError> {
+        let Some(record) = db::get_idempotency_record(&self.conn, key)? else {
+            return Ok(None);
+        };
+        if record.command != command || record.fingerprint != fingerprint {
+            return Err(AppError::InvalidArgument(format!(
+                "idempotency key '{}' was already used for a different {} request",
+                key, record.command
+            )));
+        }
+        Ok(Some(record.output))
+    }
+
+    pub fn record_idempotent_output(
+        &self,
+        key: &str,
+        command: &str,
+        fingerprint: &str,
+        output: &str,
+    ) -> Result<(), AppError> {
+        db::insert_idempotency_record(
+            &self.conn,
+            &IdempotencyRecord {
+                key: key.to_string(),
+                command: command.to_string(),
+                fingerprint: fingerprint.to_string(),
+                output: output.to_string(),
+                created_at: now_utc_rfc3339(),
+            },
+        )?;
+        Ok(())
+    }
+}
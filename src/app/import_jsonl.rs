@@ -0,0 +1,344 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::events::{new_event_id, now_utc_rfc3339};
+
+use super::error::AppError;
+use super::import_knots::ImportedKnot;
+use super::App;
+
+mod checkpoint;
+mod mapping;
+mod pipeline;
+mod record;
+
+use checkpoint::Checkpoint;
+pub use mapping::ImportMapping;
+use pipeline::{parse_in_parallel, parse_line, read_lines, ParsedLine};
+use record::SourceRecord;
+
+/// Lines between checkpoint saves unless the caller picks another size.
+pub const DEFAULT_CHUNK_SIZE: u64 = 500;
+/// Per-line errors kept in the summary; the rest are only counted.
+const MAX_REPORTED_ERRORS: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonlImportOptions {
+    pub chunk_size: u64,
+    /// Continue the interrupted import of the same file.
+    pub resume: bool,
+    /// Drop an interrupted import's checkpoint and start a new run.
+    pub restart: bool,
+    pub dry_run: bool,
+    /// Threads parsing lines ahead of the writer; 1 parses inline.
+    pub workers: usize,
+    /// Rewrites each record before import; `None` reads knots field names.
+    pub mapping: Option<ImportMapping>,
+}
+
+impl Default for JsonlImportOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            resume: false,
+            restart: false,
+            dry_run: false,
+            workers: 1,
+            mapping: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct JsonlImportSummary {
+    pub source: String,
+    /// Names this import for `kno import rollback --source`.
+    pub run: String,
+    pub dry_run: bool,
+    pub resumed: bool,
+    /// Ctrl-C stopped the import after saving a checkpoint.
+    pub interrupted: bool,
+    /// First line this invocation read.
+    pub start_line: u64,
+    /// Lines before this one are done.
+    pub next_line: u64,
+    pub imported: u64,
+    /// Records an interrupted attempt of the same run had already imported.
+    pub already_imported: u64,
+    pub error_count: u64,
+    pub errors: Vec<String>,
+    pub workers: usize,
+    pub elapsed_ms: u64,
+    /// Lines handled per second by this invocation.
+    pub records_per_sec: u64,
+}
+
+/// Progress through the file since the last checkpoint.
+struct Cursor {
+    line: u64,
+    offset: u64,
+    hasher: Sha256,
+}
+
+/// State of one invocation, threaded through the line loop.
+struct ImportRun {
+    checkpoint_path: PathBuf,
+    options: JsonlImportOptions,
+    cursor: Cursor,
+    summary: JsonlImportSummary,
+    done: HashSet<String>,
+}
+
+impl App {
+    /// Streams a JSONL dump into knots, one record per line. Progress is
+    /// checkpointed every `chunk_size` lines and when `stop` is raised, so
+    /// an interrupted import continues with `resume` from where it stopped.
+    /// Each knot gets a `knot.imported` event naming the run, which both
+    /// skips records a resumed run already wrote and lets
+    /// `rollback_import` undo the run. Lines that do not parse or do not
+    /// make a valid knot are reported and skipped.
+    pub fn import_jsonl(
+        &self,
+        path: &Path,
+        mut options: JsonlImportOptions,
+        stop: &AtomicBool,
+    ) -> Result<JsonlImportSummary, AppError> {
+        let source = path.canonicalize()?.display().to_string();
+        let checkpoint_path = checkpoint::checkpoint_path(&self.store_paths.root, &source);
+        let pending = match checkpoint::load(&checkpoint_path)? {
+            Some(_) if options.restart => {
+                std::fs::remove_file(&checkpoint_path)?;
+                None
+            }
+            pending => pending,
+        };
+        let (cursor, run, done) = match (pending, options.resume) {
+            (Some(pending), true) => self.resume_from(path, &source, pending)?,
+            (Some(pending), false) if !options.dry_run => {
+                return Err(AppError::InvalidArgument(format!(
+                    "an import of '{source}' (run {}) was interrupted at line {}; pass \
+                     --resume to continue it or --restart to start over",
+                    pending.run, pending.line
+                )))
+            }
+            (None, true) => {
+                return Err(AppError::InvalidArgument(format!(
+                    "no interrupted import of '{source}' to resume"
+                )))
+            }
+            _ => (
+                Cursor {
+                    line: 1,
+                    offset: 0,
+                    hasher: Sha256::new(),
+                },
+                new_event_id(),
+                HashSet::new(),
+            ),
+        };
+
+        let workers = options.workers.max(1);
+        let summary = JsonlImportSummary {
+            source,
+            run,
+            dry_run: options.dry_run,
+            resumed: options.resume,
+            interrupted: false,
+            start_line: cursor.line,
+            next_line: cursor.line,
+            imported: 0,
+            already_imported: 0,
+            error_count: 0,
+            errors: Vec::new(),
+            workers,
+            elapsed_ms: 0,
+            records_per_sec: 0,
+        };
+        let started = Instant::now();
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(cursor.offset))?;
+        let lines = read_lines(BufReader::new(file), cursor.line);
+        let first = cursor.line;
+        let mapping = options.mapping.take();
+        let mut run = ImportRun {
+            checkpoint_path,
+            options,
+            cursor,
+            summary,
+            done,
+        };
+
+        self.writer.begin_batch();
+        let result = if workers == 1 {
+            let parsed = lines
+                .map(|line| line.map(|(line, bytes)| parse_line(line, bytes, mapping.as_ref())));
+            self.import_lines(&mut run, parsed, stop)
+        } else {
+            std::thread::scope(|scope| {
+                self.import_lines(
+                    &mut run,
+                    parse_in_parallel(scope, lines, first, workers, mapping.as_ref()),
+                    stop,
+                )
+            })
+        };
+        let flushed = self.writer.flush_batch(true);
+        result?;
+        flushed?;
+
+        let mut summary = run.summary;
+        summary.next_line = run.cursor.line;
+        summary.elapsed_ms = started.elapsed().as_millis() as u64;
+        let handled = summary.next_line - summary.start_line;
+        summary.records_per_sec = handled * 1000 / summary.elapsed_ms.max(1);
+        if !summary.interrupted && !run.options.dry_run && run.checkpoint_path.exists() {
+            std::fs::remove_file(&run.checkpoint_path)?;
+        }
+        Ok(summary)
+    }
+
+    /// Handles `lines` in file order, checkpointing every `chunk_size`
+    /// lines, when `stop` is raised, and before a store failure is returned.
+    fn import_lines(
+        &self,
+        run: &mut ImportRun,
+        lines: impl Iterator<Item = io::Result<ParsedLine>>,
+        stop: &AtomicBool,
+    ) -> Result<(), AppError> {
+        let mut since_checkpoint = 0;
+        for parsed in lines {
+            let parsed = parsed?;
+            if let Err(err) = self.import_line(run, parsed.line, parsed.record) {
+                self.save_checkpoint(run)?;
+                return Err(err);
+            }
+            run.cursor.hasher.update(&parsed.bytes);
+            run.cursor.offset += parsed.bytes.len() as u64;
+            run.cursor.line += 1;
+            since_checkpoint += 1;
+            if stop.load(Ordering::SeqCst) {
+                run.summary.interrupted = true;
+                return self.save_checkpoint(run);
+            }
+            if since_checkpoint >= run.options.chunk_size.max(1) {
+                self.save_checkpoint(run)?;
+                since_checkpoint = 0;
+            }
+        }
+        Ok(())
+    }
+
+    fn resume_from(
+        &self,
+        path: &Path,
+        source: &str,
+        pending: Checkpoint,
+    ) -> Result<(Cursor, String, HashSet<String>), AppError> {
+        let hasher = checkpoint::hash_prefix(path, pending.offset)?
+            .filter(|hasher| checkpoint::hex(hasher) == pending.prefix_sha256)
+            .ok_or_else(|| {
+                AppError::InvalidArgument(format!(
+                    "'{source}' changed before line {} since the import was interrupted; \
+                     it cannot be resumed safely",
+                    pending.line
+                ))
+            })?;
+        let done = self
+            .import_provenance()?
+            .into_iter()
+            .filter(|(_, knot)| knot.run == pending.run)
+            .map(|(_, knot)| knot.source_id)
+            .collect();
+        let cursor = Cursor {
+            line: pending.line,
+            offset: pending.offset,
+            hasher,
+        };
+        Ok((cursor, pending.run, done))
+    }
+
+    /// Imports one parsed line. Only store-level failures are returned;
+    /// problems with the record itself are counted in the summary.
+    fn import_line(
+        &self,
+        run: &mut ImportRun,
+        line: u64,
+        record: Option<Result<(SourceRecord, String), String>>,
+    ) -> Result<(), AppError> {
+        let summary = &mut run.summary;
+        let (record, source_id) = match record {
+            None => return Ok(()),
+            Some(Ok(parsed)) => parsed,
+            Some(Err(err)) => {
+                record_error(summary, line, err);
+                return Ok(());
+            }
+        };
+        if run.done.contains(&source_id) {
+            summary.already_imported += 1;
+            return Ok(());
+        }
+        if summary.dry_run {
+            summary.imported += 1;
+            return Ok(());
+        }
+        match self.create_from_record(record) {
+            Ok(id) => {
+                let knot = ImportedKnot { source_id, id };
+                self.record_imported(&summary.run, &summary.source, &knot, &now_utc_rfc3339())?;
+                run.done.insert(knot.source_id);
+                summary.imported += 1;
+                Ok(())
+            }
+            Err(err) if is_store_error(&err) => Err(err),
+            Err(err) => {
+                record_error(summary, line, err.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    /// Syncs the events written so far, then records the cursor, so a
+    /// checkpoint never runs ahead of what is on disk.
+    fn save_checkpoint(&self, run: &ImportRun) -> Result<(), AppError> {
+        if run.options.dry_run {
+            return Ok(());
+        }
+        self.writer.flush_batch(false)?;
+        checkpoint::save(
+            &run.checkpoint_path,
+            &Checkpoint {
+                source: run.summary.source.clone(),
+                run: run.summary.run.clone(),
+                line: run.cursor.line,
+                offset: run.cursor.offset,
+                prefix_sha256: checkpoint::hex(&run.cursor.hasher),
+                imported: run.summary.imported,
+            },
+        )
+    }
+}
+fn record_error(summary: &mut JsonlImportSummary, line: u64, err: String) {
+    summary.error_count += 1;
+    if summary.errors.len() < MAX_REPORTED_ERRORS {
+        summary.errors.push(format!("line {line}: {err}"));
+    }
+}
+
+/// Failures of the store rather than of one record; these stop the import.
+fn is_store_error(err: &AppError) -> bool {
+    matches!(
+        err,
+        AppError::Io(_) | AppError::Db(_) | AppError::Event(_) | AppError::Lock(_)
+    )
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,71 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::super::error::AppError;
+
+/// Where an interrupted JSONL import stopped. Stored in the local cache, so
+/// only the clone that ran the import can resume it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(super) struct Checkpoint {
+    pub source: String,
+    pub run: String,
+    /// Lines before `line` are done.
+    pub line: u64,
+    /// Byte offset of `line`.
+    pub offset: u64,
+    /// SHA-256 of the file's first `offset` bytes, to refuse resuming a
+    /// file that changed underneath.
+    pub prefix_sha256: String,
+    pub imported: u64,
+}
+
+/// `<store>/cache/imports/<hash of the source path>.json`.
+pub(super) fn checkpoint_path(store_root: &Path, source: &str) -> PathBuf {
+    let digest = format!("{:x}", Sha256::digest(source.as_bytes()));
+    store_root
+        .join("cache")
+        .join("imports")
+        .join(format!("{}.json", &digest[..16]))
+}
+
+pub(super) fn load(path: &Path) -> Result<Option<Checkpoint>, AppError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let bytes = std::fs::read(path)?;
+    serde_json::from_slice(&bytes).map(Some).map_err(|err| {
+        AppError::InvalidArgument(format!(
+            "unreadable import checkpoint '{}': {err}",
+            path.display()
+        ))
+    })
+}
+
+/// Written to a temp file and renamed, so an interrupt mid-save leaves the
+/// previous checkpoint intact.
+pub(super) fn save(path: &Path, checkpoint: &Checkpoint) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    let bytes = serde_json::to_vec_pretty(checkpoint).expect("checkpoint should serialize");
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// The hash state after the first `len` bytes of `path`, or `None` if the
+/// file is shorter.
+pub(super) fn hash_prefix(path: &Path, len: u64) -> Result<Option<Sha256>, AppError> {
+    let mut hasher = Sha256::new();
+    let copied = std::io::copy(&mut File::open(path)?.take(len), &mut hasher)?;
+    Ok((copied == len).then_some(hasher))
+}
+
+pub(super) fn hex(hasher: &Sha256) -> String {
+    format!("{:x}", hasher.clone().finalize())
+}
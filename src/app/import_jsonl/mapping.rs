@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::super::error::AppError;
+
+/// Knot fields a mapping may fill, with the source keys read by default.
+const KNOT_FIELDS: [(&str, &[&str]); 10] = [
+    ("id", &["id"]),
+    ("title", &["title"]),
+    ("description", &["description", "body"]),
+    ("acceptance", &["acceptance"]),
+    ("priority", &["priority"]),
+    ("state", &["state"]),
+    ("profile", &["profile"]),
+    ("tags", &["tags", "labels"]),
+    ("notes", &["notes"]),
+    ("handoff_capsules", &["handoff_capsules"]),
+];
+
+/// How an exporter's records become knots, loaded from `--mapping`.
+///
+/// ```toml
+/// profile = "autopilot"
+///
+/// [fields]
+/// id = "key"
+/// title = "fields.summary"
+/// tags = "labels.*.name"
+///
+/// [states]
+/// "To Do" = "ready_for_planning"
+///
+/// [tags]
+/// lowercase = true
+/// prefix = "jira:"
+/// rename = { bug = "defect" }
+/// drop = ["triage"]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ImportMapping {
+    /// Profile for records that do not name one.
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Knot field to a dotted path in the source record; `*` steps into
+    /// every element of an array.
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// Source state to knots state. Unlisted states are kept as they are.
+    #[serde(default)]
+    pub states: BTreeMap<String, String>,
+    #[serde(default)]
+    pub tags: TagRules,
+}
+
+/// Applied to each source tag in order: drop, rename, lowercase, prefix.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TagRules {
+    #[serde(default)]
+    pub drop: Vec<String>,
+    #[serde(default)]
+    pub rename: BTreeMap<String, String>,
+    #[serde(default)]
+    pub lowercase: bool,
+    #[serde(default)]
+    pub prefix: Option<String>,
+}
+
+impl ImportMapping {
+    pub fn load(path: &Path) -> Result<Self, AppError> {
+        let invalid = |err: String| {
+            AppError::InvalidArgument(format!("invalid mapping '{}': {err}", path.display()))
+        };
+        let raw = std::fs::read_to_string(path)?;
+        let mapping: Self = toml::from_str(&raw).map_err(|err| invalid(err.to_string()))?;
+        if let Some(field) = mapping
+            .fields
+            .keys()
+            .find(|field| !KNOT_FIELDS.iter().any(|(known, _)| known == field))
+        {
+            let known: Vec<&str> = KNOT_FIELDS.iter().map(|(known, _)| *known).collect();
+            return Err(invalid(format!(
+                "unknown knot field '{field}' (expected one of: {})",
+                known.join(", ")
+            )));
+        }
+        Ok(mapping)
+    }
+
+    /// Rewrites a source record into the importer's own field names.
+    pub(super) fn apply(&self, source: &Value) -> Result<Value, String> {
+        let Value::Object(object) = source else {
+            return Err("record is not a JSON object".to_string());
+        };
+        let mut record = Map::new();
+        for (field, defaults) in KNOT_FIELDS {
+            let value = match self.fields.get(field) {
+                Some(path) => lookup(source, &path.split('.').collect::<Vec<_>>()),
+                None => defaults.iter().find_map(|key| object.get(*key).cloned()),
+            };
+            if let Some(value) = value.filter(|value| !value.is_null()) {
+                record.insert(field.to_string(), value);
+            }
+        }
+        if let Some(Value::String(state)) = record.get_mut("state") {
+            if let Some(mapped) = self.states.get(state.as_str()) {
+                *state = mapped.clone();
+            }
+        }
+        if let Some(tags) = record.remove("tags") {
+            record.insert("tags".to_string(), self.tags.apply(tags));
+        }
+        if let Some(profile) = &self.profile {
+            record
+                .entry("profile")
+                .or_insert_with(|| Value::String(profile.clone()));
+        }
+        Ok(Value::Object(record))
+    }
+}
+
+impl TagRules {
+    fn apply(&self, tags: Value) -> Value {
+        let tags = match tags {
+            Value::Array(tags) => tags,
+            Value::String(tag) => vec![Value::String(tag)],
+            other => return other,
+        };
+        let tags = tags.into_iter().filter_map(|tag| {
+            let Value::String(tag) = tag else {
+                return Some(tag);
+            };
+            if self.drop.contains(&tag) {
+                return None;
+            }
+            let mut tag = self.rename.get(&tag).cloned().unwrap_or(tag);
+            if self.lowercase {
+                tag = tag.to_lowercase();
+            }
+            if let Some(prefix) = &self.prefix {
+                tag.insert_str(0, prefix);
+            }
+            Some(Value::String(tag))
+        });
+        Value::Array(tags.collect())
+    }
+}
+
+/// The value at `path`, where `*` collects the rest of the path from each
+/// array element and numeric segments index arrays.
+fn lookup(value: &Value, path: &[&str]) -> Option<Value> {
+    let Some((segment, rest)) = path.split_first() else {
+        return Some(value.clone());
+    };
+    match (value, *segment) {
+        (Value::Array(items), "*") => Some(Value::Array(
+            items.iter().filter_map(|item| lookup(item, rest)).collect(),
+        )),
+        (Value::Array(items), index) => lookup(items.get(index.parse::<usize>().ok()?)?, rest),
+        (Value::Object(object), key) => lookup(object.get(key)?, rest),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::ImportMapping;
+
+    #[test]
+    fn maps_nested_fields_states_and_tags() {
+        let mapping: ImportMapping = toml::from_str(
+            r#"
+            profile = "autopilot"
+            [fields]
+            id = "key"
+            title = "fields.summary"
+            state = "fields.status.name"
+            tags = "fields.labels.*.name"
+            [states]
+            "To Do" = "ready_for_planning"
+            [tags]
+            drop = ["triage"]
+            rename = { Bug = "defect" }
+            lowercase = true
+            prefix = "jira:"
+            "#,
+        )
+        .expect("mapping should parse");
+        let record = mapping
+            .apply(&json!({
+                "key": "OPS-7",
+                "title": "ignored when title is mapped",
+                "body": "kept by default",
+                "fields": {
+                    "summary": "Fix login",
+                    "status": {"name": "To Do"},
+                    "labels": [{"name": "Bug"}, {"name": "triage"}, {"name": "UI"}]
+                }
+            }))
+            .expect("record should map");
+        assert_eq!(
+            record,
+            json!({
+                "id": "OPS-7",
+                "title": "Fix login",
+                "description": "kept by default",
+                "state": "ready_for_planning",
+                "profile": "autopilot",
+                "tags": ["jira:defect", "jira:ui"]
+            })
+        );
+        assert!(mapping.apply(&json!([1])).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+use std::io::{self, BufRead};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::Scope;
+
+use super::mapping::ImportMapping;
+use super::record::SourceRecord;
+
+/// Lines each parse worker may have queued ahead of the writer.
+const QUEUE_PER_WORKER: usize = 64;
+
+/// A line of the dump, parsed off the writing thread when workers are on.
+pub(super) struct ParsedLine {
+    pub line: u64,
+    pub bytes: Vec<u8>,
+    /// The record and its source id, or why it does not parse; `None` for
+    /// a blank line.
+    pub record: Option<Result<(SourceRecord, String), String>>,
+}
+
+pub(super) fn parse_line(line: u64, bytes: Vec<u8>, mapping: Option<&ImportMapping>) -> ParsedLine {
+    let text = String::from_utf8_lossy(&bytes);
+    let record = (!text.trim().is_empty()).then(|| {
+        SourceRecord::parse(&text, mapping).map(|record| {
+            let source_id = record.source_id(line);
+            (record, source_id)
+        })
+    });
+    ParsedLine {
+        line,
+        bytes,
+        record,
+    }
+}
+
+/// Raw lines of `reader`, numbered from `first`.
+pub(super) fn read_lines<R: BufRead>(
+    mut reader: R,
+    first: u64,
+) -> impl Iterator<Item = io::Result<(u64, Vec<u8>)>> {
+    let mut line = first;
+    std::iter::from_fn(move || {
+        let mut bytes = Vec::new();
+        match reader.read_until(b'\n', &mut bytes) {
+            Ok(0) => None,
+            Ok(_) => {
+                line += 1;
+                Some(Ok((line - 1, bytes)))
+            }
+            Err(err) => Some(Err(err)),
+        }
+    })
+}
+
+/// Parses `lines` on `workers` threads and yields them back in file order.
+/// Dropping the iterator early winds the threads down.
+pub(super) fn parse_in_parallel<'scope, I>(
+    scope: &'scope Scope<'scope, '_>,
+    lines: I,
+    first: u64,
+    workers: usize,
+    mapping: Option<&'scope ImportMapping>,
+) -> impl Iterator<Item = io::Result<ParsedLine>> + 'scope
+where
+    I: Iterator<Item = io::Result<(u64, Vec<u8>)>> + Send + 'scope,
+{
+    let (work_tx, work_rx) = mpsc::sync_channel::<(u64, Vec<u8>)>(workers * QUEUE_PER_WORKER);
+    let (done_tx, done_rx) = mpsc::sync_channel(workers * QUEUE_PER_WORKER);
+    let read_failed = done_tx.clone();
+    scope.spawn(move || {
+        for item in lines {
+            match item {
+                Ok(work) => {
+                    if work_tx.send(work).is_err() {
+                        return;
+                    }
+                }
+                Err(err) => {
+                    let _ = read_failed.send(Err(err));
+                    return;
+                }
+            }
+        }
+    });
+    let work_rx = Arc::new(Mutex::new(work_rx));
+    for _ in 0..workers {
+        let work_rx = Arc::clone(&work_rx);
+        let done_tx = done_tx.clone();
+        scope.spawn(move || loop {
+            let next = work_rx
+                .lock()
+                .map_err(|_| ())
+                .and_then(|rx| rx.recv().map_err(|_| ()));
+            let Ok((line, bytes)) = next else {
+                return;
+            };
+            if done_tx.send(Ok(parse_line(line, bytes, mapping))).is_err() {
+                return;
+            }
+        });
+    }
+    drop(done_tx);
+
+    let mut ahead: BTreeMap<u64, ParsedLine> = BTreeMap::new();
+    let mut next = first;
+    std::iter::from_fn(move || loop {
+        if let Some(parsed) = ahead.remove(&next) {
+            next += 1;
+            return Some(Ok(parsed));
+        }
+        match done_rx.recv() {
+            Ok(Ok(parsed)) => {
+                ahead.insert(parsed.line, parsed);
+            }
+            Ok(Err(err)) => return Some(Err(err)),
+            Err(_) => return None,
+        }
+    })
+}
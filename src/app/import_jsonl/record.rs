@@ -0,0 +1,141 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::domain::metadata::MetadataEntryInput;
+
+use super::super::error::AppError;
+use super::super::types::{CreateKnotOptions, UpdateKnotPatch};
+use super::super::App;
+use super::mapping::ImportMapping;
+
+/// One line of a JSONL dump. Unknown keys are ignored, since exporters
+/// carry far more than knots stores.
+#[derive(Debug, Deserialize)]
+pub(super) struct SourceRecord {
+    #[serde(default)]
+    id: Option<Value>,
+    title: String,
+    #[serde(default, alias = "body")]
+    description: Option<String>,
+    #[serde(default)]
+    acceptance: Option<String>,
+    #[serde(default)]
+    priority: Option<i64>,
+    #[serde(default)]
+    state: Option<String>,
+    #[serde(default)]
+    profile: Option<String>,
+    #[serde(default, alias = "labels")]
+    tags: Vec<String>,
+    #[serde(default)]
+    notes: Option<Entries>,
+    #[serde(default)]
+    handoff_capsules: Vec<SourceEntry>,
+}
+
+/// Notes come either as one legacy string or as structured entries.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Entries {
+    Text(String),
+    List(Vec<SourceEntry>),
+}
+
+#[derive(Debug, Deserialize)]
+struct SourceEntry {
+    content: String,
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    datetime: Option<String>,
+    #[serde(default)]
+    agentname: Option<String>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+}
+
+impl From<SourceEntry> for MetadataEntryInput {
+    fn from(entry: SourceEntry) -> Self {
+        MetadataEntryInput {
+            content: entry.content,
+            username: entry.username,
+            datetime: entry.datetime,
+            agentname: entry.agentname,
+            model: entry.model,
+            version: entry.version,
+        }
+    }
+}
+
+impl SourceRecord {
+    /// Parses one line, rewritten by `mapping` when one is given.
+    pub(super) fn parse(line: &str, mapping: Option<&ImportMapping>) -> Result<Self, String> {
+        let record: SourceRecord = match mapping {
+            Some(mapping) => {
+                let source = serde_json::from_str(line).map_err(|err| err.to_string())?;
+                serde_json::from_value(mapping.apply(&source)?)
+            }
+            None => serde_json::from_str(line),
+        }
+        .map_err(|err| err.to_string())?;
+        if record.title.trim().is_empty() {
+            return Err("title is empty".to_string());
+        }
+        Ok(record)
+    }
+
+    /// The exporter's id for the record, else its line number.
+    pub(super) fn source_id(&self, line: u64) -> String {
+        match &self.id {
+            Some(Value::String(id)) if !id.trim().is_empty() => id.trim().to_string(),
+            Some(Value::Number(id)) => id.to_string(),
+            _ => format!("line:{line}"),
+        }
+    }
+}
+
+impl App {
+    /// Creates the knot `record` describes, then applies what creation
+    /// cannot set: priority, tags, and one update per note and capsule.
+    /// Returns the new knot id.
+    pub(super) fn create_from_record(&self, record: SourceRecord) -> Result<String, AppError> {
+        let options = CreateKnotOptions {
+            acceptance: record.acceptance,
+            ..CreateKnotOptions::default()
+        };
+        let knot = self.create_knot_with_options(
+            &record.title,
+            record.description.as_deref(),
+            record.state.as_deref(),
+            record.profile.as_deref(),
+            None,
+            options,
+        )?;
+        let notes: Vec<MetadataEntryInput> = match record.notes {
+            Some(Entries::Text(text)) if !text.trim().is_empty() => vec![MetadataEntryInput {
+                content: text,
+                ..MetadataEntryInput::default()
+            }],
+            Some(Entries::List(entries)) => entries.into_iter().map(Into::into).collect(),
+            _ => Vec::new(),
+        };
+        let mut notes = notes.into_iter();
+        let mut capsules = record.handoff_capsules.into_iter().map(Into::into);
+        let mut patch = UpdateKnotPatch {
+            priority: record.priority,
+            add_tags: record.tags,
+            ..UpdateKnotPatch::default()
+        };
+        loop {
+            patch.add_note = notes.next();
+            patch.add_handoff_capsule = capsules.next();
+            if !patch.has_changes() {
+                break;
+            }
+            self.update_knot(&knot.id, std::mem::take(&mut patch))?;
+        }
+        Ok(knot.id)
+    }
+}
@@ -0,0 +1,246 @@
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+
+use sha2::{Digest, Sha256};
+
+use crate::app::{App, AppError, JsonlImportOptions};
+
+use super::checkpoint::{self, Checkpoint};
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-import-jsonl-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn write_dump(root: &std::path::Path, lines: &[&str]) -> PathBuf {
+    let path = root.join("dump.jsonl");
+    std::fs::write(&path, lines.join("\n") + "\n").expect("dump should be writable");
+    path
+}
+
+const RECORDS: [&str; 3] = [
+    r#"{"id": "GH-1", "title": "First", "labels": ["ops"], "priority": 2}"#,
+    r#"{"id": "GH-2", "title": "Second", "notes": "legacy note"}"#,
+    r#"{"id": "GH-3", "title": "Third", "description": "third body"}"#,
+];
+
+#[test]
+fn imports_records_and_reports_bad_lines() {
+    let (root, app) = open_app();
+    let path = write_dump(
+        &root,
+        &[
+            r#"{"id": 7, "title": "Typed", "body": "from body", "tags": ["a"], "priority": 1,
+                "notes": [{"content": "n1", "username": "ana"}, {"content": "n2"}],
+                "handoff_capsules": [{"content": "cap"}], "assignee": "ignored"}"#
+                .replace('\n', " ")
+                .as_str(),
+            "",
+            "not json",
+            r#"{"title": "   "}"#,
+            r#"{"title": "Bad state", "state": "nonsense_state"}"#,
+        ],
+    );
+    let stop = AtomicBool::new(false);
+    let dry = app
+        .import_jsonl(
+            &path,
+            JsonlImportOptions {
+                dry_run: true,
+                ..JsonlImportOptions::default()
+            },
+            &stop,
+        )
+        .expect("dry run");
+    assert_eq!((dry.imported, dry.error_count), (2, 2));
+    assert!(app.list_knots().expect("list").is_empty());
+
+    let summary = app
+        .import_jsonl(&path, JsonlImportOptions::default(), &stop)
+        .expect("import");
+    assert_eq!(summary.imported, 1);
+    assert_eq!(summary.error_count, 3, "{:?}", summary.errors);
+    assert!(
+        summary.errors[0].starts_with("line 3:"),
+        "{:?}",
+        summary.errors
+    );
+    assert_eq!(summary.next_line, 6);
+    let knots = app.list_knots().expect("list");
+    assert_eq!(knots.len(), 1);
+    let knot = app.show_knot(&knots[0].id).expect("show").expect("knot");
+    assert_eq!(knot.title, "Typed");
+    assert_eq!(knot.description.as_deref(), Some("from body"));
+    assert_eq!(knot.priority, Some(1));
+    assert_eq!(knot.tags, vec!["a".to_string()]);
+    assert_eq!(knot.notes.len(), 2);
+    assert_eq!(knot.notes[0].username, "ana");
+    assert_eq!(knot.handoff_capsules.len(), 1);
+
+    let rolled = app
+        .rollback_import(path.to_str().expect("utf8 path"), false)
+        .expect("rollback by file");
+    assert_eq!(rolled.closed.len(), 1);
+    assert_eq!(rolled.closed[0].source_id, "7");
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn interrupted_import_resumes_without_duplicates() {
+    let (root, app) = open_app();
+    let path = write_dump(&root, &RECORDS);
+    let stop = AtomicBool::new(true);
+    let first = app
+        .import_jsonl(&path, JsonlImportOptions::default(), &stop)
+        .expect("interrupted import");
+    assert!(first.interrupted);
+    assert_eq!((first.imported, first.next_line), (1, 2));
+
+    let stop = AtomicBool::new(false);
+    let pending = app
+        .import_jsonl(&path, JsonlImportOptions::default(), &stop)
+        .expect_err("pending checkpoint blocks a fresh run");
+    assert!(pending.to_string().contains("--resume"), "{pending}");
+
+    // A crash between writing a knot and saving the checkpoint leaves the
+    // checkpoint behind the log; the run's provenance covers the gap.
+    let source = path
+        .canonicalize()
+        .expect("canonical")
+        .display()
+        .to_string();
+    let checkpoint_path = checkpoint::checkpoint_path(&root.join(".knots"), &source);
+    checkpoint::save(
+        &checkpoint_path,
+        &Checkpoint {
+            source,
+            run: first.run.clone(),
+            line: 1,
+            offset: 0,
+            prefix_sha256: format!("{:x}", Sha256::new().finalize()),
+            imported: 0,
+        },
+    )
+    .expect("rewind checkpoint");
+
+    let resumed = app
+        .import_jsonl(
+            &path,
+            JsonlImportOptions {
+                resume: true,
+                ..JsonlImportOptions::default()
+            },
+            &stop,
+        )
+        .expect("resume");
+    assert_eq!(resumed.run, first.run);
+    assert_eq!((resumed.already_imported, resumed.imported), (1, 2));
+    assert!(!resumed.interrupted);
+    assert!(!checkpoint_path.exists());
+    let mut titles: Vec<String> = app
+        .list_knots()
+        .expect("list")
+        .into_iter()
+        .map(|knot| knot.title)
+        .collect();
+    titles.sort();
+    assert_eq!(titles, vec!["First", "Second", "Third"]);
+
+    let nothing = app
+        .import_jsonl(
+            &path,
+            JsonlImportOptions {
+                resume: true,
+                ..JsonlImportOptions::default()
+            },
+            &stop,
+        )
+        .expect_err("no checkpoint left");
+    assert!(matches!(nothing, AppError::InvalidArgument(_)));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn resume_refuses_a_file_changed_before_the_checkpoint() {
+    let (root, app) = open_app();
+    let path = write_dump(&root, &RECORDS);
+    let stop = AtomicBool::new(true);
+    app.import_jsonl(&path, JsonlImportOptions::default(), &stop)
+        .expect("interrupted import");
+    write_dump(&root, &[r#"{"title": "Edited"}"#, RECORDS[1], RECORDS[2]]);
+
+    let stop = AtomicBool::new(false);
+    let resume = JsonlImportOptions {
+        resume: true,
+        ..JsonlImportOptions::default()
+    };
+    let err = app
+        .import_jsonl(&path, resume, &stop)
+        .expect_err("changed prefix");
+    assert!(err.to_string().contains("changed before line 2"), "{err}");
+
+    let restart = JsonlImportOptions {
+        restart: true,
+        chunk_size: 1,
+        ..JsonlImportOptions::default()
+    };
+    let fresh = app.import_jsonl(&path, restart, &stop).expect("restart");
+    assert_eq!(fresh.imported, 3);
+    assert_eq!(app.list_knots().expect("list").len(), 4);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn worker_pool_matches_serial_import_order_and_errors() {
+    let (root, app) = open_app();
+    let lines: Vec<String> = (1..=40)
+        .map(|n| match n % 10 {
+            0 => "not json".to_string(),
+            _ => format!(r#"{{"id": "R-{n}", "title": "Record {n}"}}"#),
+        })
+        .collect();
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let path = write_dump(&root, &lines);
+    let stop = AtomicBool::new(false);
+    let summary = app
+        .import_jsonl(
+            &path,
+            JsonlImportOptions {
+                workers: 4,
+                chunk_size: 7,
+                ..JsonlImportOptions::default()
+            },
+            &stop,
+        )
+        .expect("parallel import");
+    assert_eq!(summary.workers, 4);
+    assert_eq!((summary.imported, summary.error_count), (36, 4));
+    let error_lines: Vec<&str> = summary
+        .errors
+        .iter()
+        .map(|err| err.split(':').next().expect("line prefix"))
+        .collect();
+    assert_eq!(
+        error_lines,
+        vec!["line 10", "line 20", "line 30", "line 40"]
+    );
+    assert_eq!(summary.next_line, 41);
+
+    let sources: Vec<String> = app
+        .import_provenance()
+        .expect("provenance")
+        .into_iter()
+        .map(|(_, knot)| knot.source_id)
+        .collect();
+    let expected: Vec<String> = (1..=40)
+        .filter(|n| n % 10 != 0)
+        .map(|n| format!("R-{n}"))
+        .collect();
+    assert_eq!(sources, expected, "knots are written in file order");
+    let _ = std::fs::remove_dir_all(root);
+}
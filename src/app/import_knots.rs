@@ -0,0 +1,366 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::events::{
+    new_event_id, now_utc_rfc3339, EventRecord, FullEvent, FullEventKind, IndexEvent,
+    IndexEventKind,
+};
+use crate::locks::FileLock;
+use crate::sync::{known_workflow_ids, GitAdapter, IncrementalApplier};
+
+use super::error::AppError;
+use super::rehydrate::{collect_json_paths, resolve_subdir};
+use super::App;
+
+/// Lease bookkeeping points at lease knots in the source repo, so it stays
+/// behind.
+const SKIPPED_EVENT_TYPES: [&str; 2] = ["knot.lease_data_set", "knot.lease_id_set"];
+const EDGE_EVENT_TYPES: [&str; 2] = ["knot.edge_add", "knot.edge_remove"];
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ImportedKnot {
+    pub source_id: String,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct KnotsImportSummary {
+    pub source: PathBuf,
+    /// Names this import for `kno import rollback --source`.
+    pub run: String,
+    pub dry_run: bool,
+    /// Subtree root first, then descendants breadth-first.
+    pub knots: Vec<ImportedKnot>,
+    pub events_written: u64,
+    /// Edges from the subtree to knots outside it, which are not copied.
+    pub edges_dropped: u64,
+}
+
+/// Events of a source store, deduplicated by event id and sorted by time.
+struct SourceEvents {
+    full: Vec<FullEvent>,
+    index: Vec<IndexEvent>,
+}
+
+impl App {
+    /// Copies the knot `subtree` and its `parent_of` descendants from the
+    /// knots store at `from` into this repo. Every event is rewritten with a
+    /// new event id and the knot ids this repo allocates; edges inside the
+    /// subtree are kept, edges leaving it are dropped. Each copied knot also
+    /// gets a `knot.imported` event naming the run and source, which
+    /// `rollback_import` reads. The source is not modified.
+    pub fn import_knots_subtree(
+        &self,
+        from: &Path,
+        subtree: &str,
+        dry_run: bool,
+    ) -> Result<KnotsImportSummary, AppError> {
+        self.import_from_store(source_store(from)?, subtree, true, dry_run)
+    }
+
+    /// Imports `token` from the knots store at `store`, with its `parent_of`
+    /// descendants when `with_descendants` is set. Takes this repo's locks.
+    pub(super) fn import_from_store(
+        &self,
+        store: PathBuf,
+        token: &str,
+        with_descendants: bool,
+        dry_run: bool,
+    ) -> Result<KnotsImportSummary, AppError> {
+        let source = SourceEvents::load(&[store.clone(), store.join("_worktree")])?;
+        let root = source.resolve_id(token)?;
+        let members = if with_descendants {
+            source.subtree(&root)
+        } else {
+            vec![root]
+        };
+
+        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        let _cache_guard =
+            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+
+        let mut taken = HashSet::new();
+        let mut knots = Vec::with_capacity(members.len());
+        for source_id in members {
+            let id = self.next_knot_id_avoiding(&taken)?;
+            taken.insert(id.clone());
+            knots.push(ImportedKnot { source_id, id });
+        }
+        let ids: HashMap<&str, &str> = knots
+            .iter()
+            .map(|knot| (knot.source_id.as_str(), knot.id.as_str()))
+            .collect();
+        let (records, edges_dropped) = source.rewrite(&ids);
+
+        let mut summary = KnotsImportSummary {
+            source: store.clone(),
+            run: new_event_id(),
+            dry_run,
+            knots: knots.clone(),
+            events_written: records.len() as u64,
+            edges_dropped,
+        };
+        if dry_run {
+            return Ok(summary);
+        }
+
+        let mut index_files = Vec::new();
+        let mut full_files = Vec::new();
+        for record in &records {
+            let path = self.writer.write(record)?;
+            match record {
+                EventRecord::Index(_) => index_files.push(path),
+                EventRecord::Full(_) => full_files.push(path),
+            }
+        }
+        let source = store.canonicalize().unwrap_or(store);
+        let imported_at = now_utc_rfc3339();
+        for knot in &knots {
+            full_files.push(self.record_imported(
+                &summary.run,
+                &source.display().to_string(),
+                knot,
+                &imported_at,
+            )?);
+        }
+        let mut applier = IncrementalApplier::new(
+            &self.conn,
+            self.store_paths.root.clone(),
+            GitAdapter::new(),
+            known_workflow_ids(&self.repo_root),
+        )
+        .with_event_types(crate::repo_config::event_types(&self.store_paths.root));
+        applier.apply_event_files(index_files, full_files)?;
+        summary.knots = knots;
+        Ok(summary)
+    }
+}
+
+impl App {
+    /// Writes the `knot.imported` event that ties `knot` to import `run`.
+    pub(super) fn record_imported(
+        &self,
+        run: &str,
+        source: &str,
+        knot: &ImportedKnot,
+        occurred_at: &str,
+    ) -> Result<PathBuf, AppError> {
+        Ok(self
+            .writer
+            .write(&EventRecord::full(FullEvent::with_identity(
+                new_event_id(),
+                occurred_at,
+                knot.id.clone(),
+                FullEventKind::KnotImported.as_str(),
+                serde_json::json!({"run": run, "source": source, "source_id": knot.source_id}),
+            )))?)
+    }
+}
+
+impl SourceEvents {
+    fn load(roots: &[PathBuf]) -> Result<Self, AppError> {
+        let mut seen = HashSet::new();
+        let mut full: Vec<FullEvent> = Vec::new();
+        let mut index: Vec<IndexEvent> = Vec::new();
+        for root in roots {
+            for path in collect_json_paths(&resolve_subdir(root, "events"))? {
+                let event: FullEvent = read_source_event(&path)?;
+                if seen.insert(event.event_id.clone()) {
+                    full.push(event);
+                }
+            }
+            for path in collect_json_paths(&resolve_subdir(root, "index"))? {
+                let event: IndexEvent = read_source_event(&path)?;
+                if event.event_type == IndexEventKind::KnotHead.as_str()
+                    && seen.insert(event.event_id.clone())
+                {
+                    index.push(event);
+                }
+            }
+        }
+        full.sort_by_cached_key(|event| sort_key(&event.occurred_at, &event.event_id));
+        index.sort_by_cached_key(|event| sort_key(&event.occurred_at, &event.event_id));
+        Ok(Self { full, index })
+    }
+
+    fn knot_ids(&self) -> BTreeSet<&str> {
+        let heads = self
+            .index
+            .iter()
+            .filter_map(|event| event.data.get("knot_id").and_then(Value::as_str));
+        self.full
+            .iter()
+            .map(|event| event.knot_id.as_str())
+            .chain(heads)
+            .collect()
+    }
+
+    /// Accepts a full id or the short suffix after the last `-`.
+    fn resolve_id(&self, token: &str) -> Result<String, AppError> {
+        let token = token.trim();
+        let ids = self.knot_ids();
+        if ids.contains(token) {
+            return Ok(token.to_string());
+        }
+        let matches: Vec<&str> = ids
+            .iter()
+            .copied()
+            .filter(|id| {
+                id.rsplit_once('-')
+                    .is_some_and(|(_, suffix)| suffix == token)
+            })
+            .collect();
+        match matches.as_slice() {
+            [id] => Ok((*id).to_string()),
+            [] => Err(AppError::NotFound(token.to_string())),
+            _ => Err(AppError::InvalidArgument(format!(
+                "'{}' matches several source knots: {}",
+                token,
+                matches.join(", ")
+            ))),
+        }
+    }
+
+    /// Edges that survive the source's add/remove history.
+    fn edges(&self) -> BTreeSet<(String, String, String)> {
+        let mut edges = BTreeSet::new();
+        for event in &self.full {
+            let (Some(kind), Some(dst)) = (
+                event.data.get("kind").and_then(Value::as_str),
+                event.data.get("dst").and_then(Value::as_str),
+            ) else {
+                continue;
+            };
+            let edge = (event.knot_id.clone(), kind.to_string(), dst.to_string());
+            match event.event_type.as_str() {
+                "knot.edge_add" => {
+                    edges.insert(edge);
+                }
+                "knot.edge_remove" => {
+                    edges.remove(&edge);
+                }
+                _ => {}
+            }
+        }
+        edges
+    }
+
+    /// `root` followed by its `parent_of` descendants, breadth-first.
+    fn subtree(&self, root: &str) -> Vec<String> {
+        let edges = self.edges();
+        let mut members = vec![root.to_string()];
+        let mut seen: HashSet<String> = members.iter().cloned().collect();
+        let mut queue = VecDeque::from([root.to_string()]);
+        while let Some(parent) = queue.pop_front() {
+            for (_, _, child) in edges
+                .iter()
+                .filter(|(src, kind, _)| *src == parent && kind == "parent_of")
+            {
+                if seen.insert(child.clone()) {
+                    members.push(child.clone());
+                    queue.push_back(child.clone());
+                }
+            }
+        }
+        members
+    }
+
+    /// The subtree's events with new event ids and knot ids `ids`, in time
+    /// order, plus the number of live edges dropped for leaving the subtree.
+    fn rewrite(&self, ids: &HashMap<&str, &str>) -> (Vec<EventRecord>, u64) {
+        let mut timeline: Vec<((OffsetDateTime, String), EventRecord)> = Vec::new();
+        for event in &self.full {
+            let Some(knot_id) = ids.get(event.knot_id.as_str()) else {
+                continue;
+            };
+            if SKIPPED_EVENT_TYPES.contains(&event.event_type.as_str()) {
+                continue;
+            }
+            let mut data = event.data.clone();
+            if EDGE_EVENT_TYPES.contains(&event.event_type.as_str()) {
+                let dst = data.get("dst").and_then(Value::as_str).unwrap_or_default();
+                let Some(mapped) = ids.get(dst) else {
+                    continue;
+                };
+                data["dst"] = Value::String((*mapped).to_string());
+            }
+            timeline.push((
+                sort_key(&event.occurred_at, &event.event_id),
+                EventRecord::full(FullEvent::with_identity(
+                    new_event_id(),
+                    event.occurred_at.clone(),
+                    *knot_id,
+                    event.event_type.clone(),
+                    data,
+                )),
+            ));
+        }
+        for event in &self.index {
+            let source_id = event.data.get("knot_id").and_then(Value::as_str);
+            let Some(knot_id) = source_id.and_then(|id| ids.get(id)) else {
+                continue;
+            };
+            let mut data = event.data.clone();
+            data["knot_id"] = Value::String((*knot_id).to_string());
+            timeline.push((
+                sort_key(&event.occurred_at, &event.event_id),
+                EventRecord::index(IndexEvent::with_identity(
+                    new_event_id(),
+                    event.occurred_at.clone(),
+                    event.event_type.clone(),
+                    data,
+                )),
+            ));
+        }
+        timeline.sort_by(|a, b| a.0.cmp(&b.0));
+        let records = timeline.into_iter().map(|(_, record)| record).collect();
+        (records, self.outbound_edges(ids))
+    }
+
+    fn outbound_edges(&self, ids: &HashMap<&str, &str>) -> u64 {
+        self.edges()
+            .iter()
+            .filter(|(src, _, dst)| {
+                ids.contains_key(src.as_str()) && !ids.contains_key(dst.as_str())
+            })
+            .count() as u64
+    }
+}
+
+/// `from` may name a repo root or its `.knots` store directly.
+fn source_store(from: &Path) -> Result<PathBuf, AppError> {
+    let nested = from.join(".knots");
+    if nested.is_dir() {
+        return Ok(nested);
+    }
+    if from.join("events").is_dir() || from.join("index").is_dir() {
+        return Ok(from.to_path_buf());
+    }
+    Err(AppError::InvalidArgument(format!(
+        "'{}' has no knots store; pass a repo root or its .knots directory",
+        from.display()
+    )))
+}
+
+fn read_source_event<T: DeserializeOwned>(path: &Path) -> Result<T, AppError> {
+    let bytes = crate::compression::read(path)?;
+    serde_json::from_slice(&bytes).map_err(|err| {
+        AppError::InvalidArgument(format!(
+            "invalid source event '{}': {}",
+            path.display(),
+            err
+        ))
+    })
+}
+
+fn sort_key(occurred_at: &str, event_id: &str) -> (OffsetDateTime, String) {
+    let at = OffsetDateTime::parse(occurred_at, &Rfc3339).unwrap_or(OffsetDateTime::UNIX_EPOCH);
+    (at, event_id.to_string())
+}
@@ -0,0 +1,192 @@
+use std::collections::{BTreeSet, HashSet};
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::db;
+use crate::domain::metadata::MetadataEntryInput;
+use crate::events::FullEventKind;
+use crate::locks::FileLock;
+
+use super::error::AppError;
+use super::knot_update::update_knot_locked;
+use super::rehydrate::replay::full_events_between;
+use super::types::{EdgeView, UpdateKnotPatch};
+use super::App;
+
+/// Terminal state rolled-back knots are left in, as for `kno transfer`.
+const ROLLED_BACK_STATE: &str = "abandoned";
+
+/// A knot an import run introduced, from its `knot.imported` event.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ImportedKnotRecord {
+    pub id: String,
+    pub source_id: String,
+    pub run: String,
+}
+
+/// What `kno import rollback` closed, or would close under `--dry-run`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ImportRollback {
+    pub source: String,
+    pub runs: Vec<String>,
+    /// Knots closed as `abandoned`, children before parents.
+    pub closed: Vec<ImportedKnotRecord>,
+    /// Knots already abandoned, or no longer in the cache.
+    pub skipped: Vec<String>,
+    /// Edges linking the imported knots to knots the import did not bring.
+    pub edges_removed: Vec<EdgeView>,
+    pub dry_run: bool,
+}
+
+impl App {
+    /// Undoes the `kno import knots` runs matching `source`, which is either
+    /// a run id or the source repo the runs copied from. Imported knots are
+    /// closed as `abandoned` with a note naming the run, and edges between
+    /// them and the rest of the backlog are removed. The event log keeps the
+    /// imported history; these are compensating events, not deletions.
+    pub fn rollback_import(&self, source: &str, dry_run: bool) -> Result<ImportRollback, AppError> {
+        let imported = self.imported_knots(source)?;
+        let runs: BTreeSet<String> = imported.iter().map(|knot| knot.run.clone()).collect();
+        let members: HashSet<&str> = imported.iter().map(|knot| knot.id.as_str()).collect();
+
+        let _locks = if dry_run {
+            None
+        } else {
+            let repo_guard =
+                FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+            let cache_guard =
+                FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+            Some((repo_guard, cache_guard))
+        };
+        let mut edges_removed = BTreeSet::new();
+        for knot in &imported {
+            for edge in db::list_edges(&self.conn, &knot.id, db::EdgeDirection::Both)? {
+                if !members.contains(edge.src.as_str()) || !members.contains(edge.dst.as_str()) {
+                    edges_removed.insert((edge.src, edge.kind, edge.dst));
+                }
+            }
+        }
+        if !dry_run {
+            for (src, kind, dst) in &edges_removed {
+                self.apply_edge_change(src, kind, dst, false)?;
+            }
+        }
+
+        let mut closed = Vec::new();
+        let mut skipped = Vec::new();
+        for knot in imported.into_iter().rev() {
+            let current = match db::get_knot_hot(&self.conn, &knot.id)? {
+                Some(current) if current.state != ROLLED_BACK_STATE => current,
+                _ => {
+                    skipped.push(knot.id);
+                    continue;
+                }
+            };
+            if !dry_run {
+                let patch = UpdateKnotPatch {
+                    status: Some(ROLLED_BACK_STATE.to_string()),
+                    force: true,
+                    add_note: Some(MetadataEntryInput {
+                        content: format!(
+                            "import rolled back: run {} (source knot {})",
+                            knot.run, knot.source_id
+                        ),
+                        ..MetadataEntryInput::default()
+                    }),
+                    ..UpdateKnotPatch::default()
+                };
+                update_knot_locked(self, &knot.id, current, patch, false)?;
+            }
+            closed.push(knot);
+        }
+        Ok(ImportRollback {
+            source: source.to_string(),
+            runs: runs.into_iter().collect(),
+            closed,
+            skipped,
+            edges_removed: edges_removed
+                .into_iter()
+                .map(|(src, kind, dst)| EdgeView { src, kind, dst })
+                .collect(),
+            dry_run,
+        })
+    }
+
+    /// Knots recorded by `knot.imported` events whose run id or source
+    /// matches `source`, in import order.
+    fn imported_knots(&self, source: &str) -> Result<Vec<ImportedKnotRecord>, AppError> {
+        let wanted_store = source_store_path(source);
+        let mut known_runs = BTreeSet::new();
+        let mut seen = HashSet::new();
+        let mut imported = Vec::new();
+        for (from, knot) in self.import_provenance()? {
+            known_runs.insert(format!("{} ({from})", knot.run));
+            let matches = knot.run == source.trim()
+                || from == source.trim()
+                || wanted_store.as_deref() == Some(from.as_str());
+            if matches && seen.insert(knot.id.clone()) {
+                imported.push(knot);
+            }
+        }
+        if imported.is_empty() {
+            let known = if known_runs.is_empty() {
+                "none".to_string()
+            } else {
+                known_runs.into_iter().collect::<Vec<_>>().join(", ")
+            };
+            return Err(AppError::InvalidArgument(format!(
+                "no import run matches '{source}'; recorded runs: {known}"
+            )));
+        }
+        Ok(imported)
+    }
+
+    /// Every `knot.imported` event in the log with the source it names,
+    /// oldest first.
+    pub(super) fn import_provenance(&self) -> Result<Vec<(String, ImportedKnotRecord)>, AppError> {
+        let roots = [
+            self.store_paths.root.clone(),
+            self.store_paths.worktree_path(),
+        ];
+        let until = PrimitiveDateTime::MAX.assume_utc();
+        let events = full_events_between(&roots, OffsetDateTime::UNIX_EPOCH, until)?;
+        Ok(events
+            .into_iter()
+            .filter(|event| event.event_type == FullEventKind::KnotImported.as_str())
+            .map(|event| {
+                let field = |key: &str| {
+                    event
+                        .data
+                        .get(key)
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                let knot = ImportedKnotRecord {
+                    id: event.knot_id.clone(),
+                    source_id: field("source_id"),
+                    run: field("run"),
+                };
+                (field("source"), knot)
+            })
+            .collect())
+    }
+}
+
+/// The canonical store path an import from `source` recorded, when
+/// `source` names a repo root or its `.knots` directory.
+fn source_store_path(source: &str) -> Option<String> {
+    let path = Path::new(source.trim());
+    let nested = path.join(".knots");
+    let store = if nested.is_dir() {
+        nested
+    } else {
+        path.to_path_buf()
+    };
+    let store = store.canonicalize().ok()?;
+    Some(store.display().to_string())
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::branch_name::render_branch_name;
+use crate::db::GitLinkRecord;
+use crate::events::now_utc_rfc3339;
+use crate::locks::FileLock;
+use crate::sync::GitAdapter;
+
+use super::error::AppError;
+use super::{App, GitLinkKind};
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct KnotBranch {
+    pub id: String,
+    pub branch: String,
+    pub created: bool,
+    pub checked_out: bool,
+}
+
+impl App {
+    /// Names a code branch for knot `id` from the `branch_template` setting.
+    /// `create` makes the branch at HEAD when missing and `checkout` also
+    /// switches to it; either records the branch on the knot.
+    pub fn knot_branch(
+        &self,
+        id: &str,
+        create: bool,
+        checkout: bool,
+    ) -> Result<KnotBranch, AppError> {
+        let knot = self
+            .show_knot(id)?
+            .ok_or_else(|| AppError::NotFound(id.to_string()))?;
+        let template = self.config_get("branch_template")?.value;
+        let branch = render_branch_name(&template, &knot).map_err(AppError::InvalidArgument)?;
+        let git = GitAdapter::new();
+        if !git.is_valid_branch_name(&self.repo_root, &branch)? {
+            return Err(AppError::InvalidArgument(format!(
+                "'{branch}' is not a valid branch name; adjust the branch_template setting"
+            )));
+        }
+        let mut result = KnotBranch {
+            id: knot.id,
+            branch,
+            created: false,
+            checked_out: false,
+        };
+        if !create && !checkout {
+            return Ok(result);
+        }
+        if !git.branch_exists(&self.repo_root, &result.branch)? {
+            git.create_branch(&self.repo_root, &result.branch)?;
+            result.created = true;
+        }
+        if checkout {
+            git.checkout_branch(&self.repo_root, &result.branch)?;
+            result.checked_out = true;
+        }
+
+        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        let _cache_guard =
+            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        let link = GitLinkRecord {
+            kind: GitLinkKind::Branch.as_str().to_string(),
+            target: result.branch.clone(),
+            subject: None,
+            linked_at: now_utc_rfc3339(),
+        };
+        self.record_git_link_locked(&result.id, &link)?;
+        Ok(result)
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use serde_json::json;
@@ -11,10 +12,11 @@ use crate::events::{
 use crate::locks::FileLock;
 use crate::workflow_runtime;
 
+use super::custom_fields::field_set_event;
 use super::error::AppError;
 use super::helpers::{
-    build_knot_head_data, non_empty, normalize_state_input, require_state_for_knot_type,
-    resolve_step_metadata, KnotHeadData,
+    build_knot_head_data, non_empty, normalize_estimate, normalize_state_input,
+    require_state_for_knot_type, resolve_step_metadata, KnotHeadData,
 };
 use super::types::{CreateKnotOptions, KnotView};
 use super::App;
@@ -57,6 +59,7 @@ impl App {
         workflow_id: Option<&str>,
         options: CreateKnotOptions,
     ) -> Result<KnotView, AppError> {
+        self.guard_create_secrets(title, body, &options)?;
         let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
         let _cache_guard =
             FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
@@ -118,7 +121,7 @@ impl App {
         profile: &crate::workflow::ProfileDefinition,
         options: &CreateKnotOptions,
     ) -> Result<KnotView, AppError> {
-        let knot_id = self.next_knot_id()?;
+        let knot_id = self.next_create_knot_id(options)?;
         let occurred_at = now_utc_rfc3339();
         let terminal = workflow_runtime::is_terminal_state(
             &self.profile_registry,
@@ -127,6 +130,7 @@ impl App {
             state,
         )?;
         let acceptance = options.acceptance.as_deref().and_then(non_empty);
+        let (estimate, custom_fields) = self.resolve_create_fields(options)?;
         let (step_metadata, next_step_metadata) = resolve_step_metadata(
             &self.profile_registry,
             profile.workflow_id.as_str(),
@@ -173,7 +177,14 @@ impl App {
             }),
         );
         self.writer.write(&EventRecord::full(full_event))?;
-        self.write_optional_create_events(&knot_id, &occurred_at, acceptance.as_deref(), options)?;
+        self.write_optional_create_events(
+            &knot_id,
+            &occurred_at,
+            acceptance.as_deref(),
+            estimate.as_deref(),
+            &custom_fields,
+            options,
+        )?;
         self.writer.write(&EventRecord::index(idx_event))?;
         db::upsert_knot_hot(
             &self.conn,
@@ -185,6 +196,8 @@ impl App {
                 body,
                 description: body,
                 acceptance: acceptance.as_deref(),
+                estimate: estimate.as_deref(),
+                custom_fields: &custom_fields,
                 priority: None,
                 knot_type: Some(options.knot_type.as_str()),
                 tags: &[],
@@ -201,6 +214,7 @@ impl App {
                 deferred_from_state: None,
                 blocked_from_state: None,
                 created_at: Some(&occurred_at),
+                snoozed_until: None,
             },
         )?;
         let record = db::get_knot_hot(&self.conn, &knot_id)?
@@ -208,11 +222,30 @@ impl App {
         self.apply_alias_and_enrich_knot(KnotView::from(record))
     }
 
+    /// Normalized estimate and custom field values for a new knot.
+    fn resolve_create_fields(
+        &self,
+        options: &CreateKnotOptions,
+    ) -> Result<(Option<String>, BTreeMap<String, String>), AppError> {
+        let estimate = match options.estimate.as_deref() {
+            Some(raw) => normalize_estimate(raw)?,
+            None => None,
+        };
+        let custom_fields = self
+            .resolve_custom_fields(&options.custom_fields)?
+            .into_iter()
+            .filter_map(|(name, value)| Some((name, value?)))
+            .collect();
+        Ok((estimate, custom_fields))
+    }
+
     fn write_optional_create_events(
         &self,
         knot_id: &str,
         occurred_at: &str,
         acceptance: Option<&str>,
+        estimate: Option<&str>,
+        custom_fields: &BTreeMap<String, String>,
         options: &CreateKnotOptions,
     ) -> Result<(), AppError> {
         if let Some(acceptance) = acceptance {
@@ -225,6 +258,20 @@ impl App {
             );
             self.writer.write(&EventRecord::full(event))?;
         }
+        if let Some(estimate) = estimate {
+            let event = FullEvent::with_identity(
+                new_event_id(),
+                occurred_at.to_string(),
+                knot_id.to_string(),
+                FullEventKind::KnotEstimateSet.as_str(),
+                json!({ "estimate": estimate }),
+            );
+            self.writer.write(&EventRecord::full(event))?;
+        }
+        for (name, value) in custom_fields {
+            let event = field_set_event(knot_id, occurred_at, name, Some(value));
+            self.writer.write(&EventRecord::full(event))?;
+        }
         if options.knot_type == KnotType::Lease {
             let event = FullEvent::new(
                 knot_id.to_string(),
@@ -9,6 +9,9 @@ use crate::locks::FileLock;
 use super::error::AppError;
 use super::App;
 
+mod handback;
+pub use handback::{handback_cooldown_until, normalize_handback_reason};
+
 impl App {
     pub fn set_lease_expiry(&self, id: &str, ts: i64) -> Result<(), AppError> {
         crate::db::update_lease_expiry_ts(&self.conn, id, ts)
@@ -38,6 +41,8 @@ impl App {
                 body: record.body.as_deref(),
                 description: record.description.as_deref(),
                 acceptance: record.acceptance.as_deref(),
+                estimate: record.estimate.as_deref(),
+                custom_fields: &record.custom_fields,
                 priority: record.priority,
                 knot_type: record.knot_type.as_deref(),
                 tags: &record.tags,
@@ -54,6 +59,7 @@ impl App {
                 deferred_from_state: record.deferred_from_state.as_deref(),
                 blocked_from_state: record.blocked_from_state.as_deref(),
                 created_at: record.created_at.as_deref(),
+                snoozed_until: record.snoozed_until.as_deref(),
             },
         )?;
         Ok(())
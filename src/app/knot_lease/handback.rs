@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde_json::json;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::db::{self, HandbackRecord};
+use crate::events::{EventRecord, FullEvent, FullEventKind};
+use crate::locks::FileLock;
+
+use super::super::diff::parse_span;
+use super::super::error::AppError;
+use super::super::App;
+
+/// Reasons `kno handback` accepts.
+const HANDBACK_REASONS: &[&str] = &["blocked", "needs_input", "out_of_scope", "failed", "other"];
+
+impl App {
+    /// Writes the `knot.handback` event for a released claim and caches it.
+    pub fn record_handback(&self, id: &str, handback: &HandbackRecord) -> Result<(), AppError> {
+        let id = self.resolve_knot_token(id)?;
+        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        let _cache_guard =
+            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        self.writer
+            .write(&EventRecord::full(FullEvent::with_identity(
+                handback.handback_id.clone(),
+                handback.handed_back_at.clone(),
+                id.clone(),
+                FullEventKind::KnotHandback.as_str(),
+                json!({
+                    "reason": handback.reason,
+                    "note": handback.note,
+                    "agent_name": handback.agent_name,
+                    "from_state": handback.from_state,
+                    "to_state": handback.to_state,
+                    "cooldown_until": handback.cooldown_until,
+                }),
+            )))?;
+        db::insert_handback(&self.conn, &id, handback)?;
+        Ok(())
+    }
+
+    /// Knots `agent_name` handed back that are still cooling down, with the
+    /// time each cooldown ends.
+    pub fn handback_cooldowns(
+        &self,
+        agent_name: &str,
+    ) -> Result<BTreeMap<String, String>, AppError> {
+        let now = OffsetDateTime::now_utc();
+        let mut cooling = BTreeMap::new();
+        for (knot_id, until) in db::list_handback_cooldowns(&self.conn, agent_name)? {
+            let Ok(ends) = OffsetDateTime::parse(&until, &Rfc3339) else {
+                continue;
+            };
+            if ends > now {
+                cooling.insert(knot_id, until);
+            }
+        }
+        Ok(cooling)
+    }
+}
+
+/// `raw` as one of `HANDBACK_REASONS`; `needs-input` reads as `needs_input`.
+pub fn normalize_handback_reason(raw: &str) -> Result<String, AppError> {
+    let reason = raw.trim().to_ascii_lowercase().replace('-', "_");
+    if HANDBACK_REASONS.contains(&reason.as_str()) {
+        return Ok(reason);
+    }
+    Err(AppError::InvalidArgument(format!(
+        "unknown handback reason '{raw}'; expected one of: {}",
+        HANDBACK_REASONS.join(", ")
+    )))
+}
+
+/// End of a cooldown of `span` (such as `30m` or `2h`) starting now.
+pub fn handback_cooldown_until(span: &str) -> Result<String, AppError> {
+    let span = parse_span(span).ok_or_else(|| {
+        AppError::InvalidArgument(format!(
+            "invalid cooldown '{span}'; use a span such as 30m, 2h, or 1d"
+        ))
+    })?;
+    Ok((OffsetDateTime::now_utc() + span)
+        .format(&Rfc3339)
+        .expect("RFC3339 formatting for UTC timestamp should never fail"))
+}
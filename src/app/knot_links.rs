@@ -0,0 +1,47 @@
+use serde::Serialize;
+
+use super::error::AppError;
+use super::App;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct KnotLink {
+    pub name: String,
+    pub url: String,
+}
+
+impl App {
+    /// Renders the `[links]` template `name` from the repo config for knot
+    /// `id`. Without a name the repo must define exactly one link.
+    pub fn knot_link(&self, id: &str, name: Option<&str>) -> Result<KnotLink, AppError> {
+        let links = self.read_repo_config()?.unwrap_or_default().links;
+        let available = || links.keys().cloned().collect::<Vec<_>>().join(", ");
+        let (name, template) = match name.map(str::trim) {
+            Some(name) => links.get_key_value(name).ok_or_else(|| {
+                AppError::InvalidArgument(format!(
+                    "no '{name}' link in .knots/config.toml [links]; available: {}",
+                    available()
+                ))
+            })?,
+            None if links.len() == 1 => links.iter().next().expect("one link"),
+            None if links.is_empty() => {
+                return Err(AppError::InvalidArgument(
+                    "no links configured; add a [links] table to .knots/config.toml".to_string(),
+                ))
+            }
+            None => {
+                return Err(AppError::InvalidArgument(format!(
+                    "choose a link: {}",
+                    available()
+                )))
+            }
+        };
+        let knot = self
+            .show_knot(id)?
+            .ok_or_else(|| AppError::NotFound(id.to_string()))?;
+        let url = crate::links::render_link(template, &knot).map_err(AppError::InvalidArgument)?;
+        Ok(KnotLink {
+            name: name.clone(),
+            url,
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use crate::db::{self, KnotCacheRecord, UpsertKnotHot};
@@ -16,6 +17,7 @@ use super::helpers::{
     next_blocked_from_state, next_deferred_from_state, normalize_state_input,
     resolve_step_metadata, KnotHeadData, StateEventParams,
 };
+use super::snooze::snooze_set_event;
 use super::types::{KnotView, UpdateKnotPatch};
 use super::App;
 
@@ -41,6 +43,7 @@ impl App {
                 "update requires at least one field change".to_string(),
             ));
         }
+        self.guard_patch_secrets(&patch)?;
         let current =
             db::get_knot_hot(&self.conn, &id)?.ok_or_else(|| AppError::NotFound(id.to_string()))?;
         ensure_profile_etag(&current, patch.expected_profile_etag.as_deref())?;
@@ -54,10 +57,13 @@ struct UpdateState {
     description: Option<String>,
     body: Option<String>,
     acceptance: Option<String>,
+    estimate: Option<String>,
+    custom_fields: BTreeMap<String, String>,
     priority: Option<i64>,
     knot_type: crate::domain::knot_type::KnotType,
     deferred: Option<String>,
     blocked: Option<String>,
+    snoozed_until: Option<String>,
     tags: Vec<String>,
     notes: Vec<crate::domain::metadata::MetadataEntry>,
     handoff_capsules: Vec<crate::domain::metadata::MetadataEntry>,
@@ -74,10 +80,13 @@ impl UpdateState {
             description: record.description.clone(),
             body: record.body.clone(),
             acceptance: record.acceptance.clone(),
+            estimate: record.estimate.clone(),
+            custom_fields: record.custom_fields.clone(),
             priority: record.priority,
             knot_type: parse_knot_type(record.knot_type.as_deref()),
             deferred: record.deferred_from_state.clone(),
             blocked: record.blocked_from_state.clone(),
+            snoozed_until: record.snoozed_until.clone(),
             tags: record.tags.clone(),
             notes: record.notes.clone(),
             handoff_capsules: record.handoff_capsules.clone(),
@@ -93,10 +102,13 @@ impl UpdateState {
         self.description = record.description.clone();
         self.body = record.body.clone();
         self.acceptance = record.acceptance.clone();
+        self.estimate = record.estimate.clone();
+        self.custom_fields = record.custom_fields.clone();
         self.priority = record.priority;
         self.knot_type = parse_knot_type(record.knot_type.as_deref());
         self.deferred = record.deferred_from_state.clone();
         self.blocked = record.blocked_from_state.clone();
+        self.snoozed_until = record.snoozed_until.clone();
         self.tags = record.tags.clone();
         self.notes = record.notes.clone();
         self.handoff_capsules = record.handoff_capsules.clone();
@@ -105,7 +117,7 @@ impl UpdateState {
     }
 }
 
-fn update_knot_locked(
+pub(super) fn update_knot_locked(
     app: &App,
     id: &str,
     mut current: KnotCacheRecord,
@@ -115,6 +127,8 @@ fn update_knot_locked(
     let profile = app.resolve_profile_for_record(&current)?;
     let profile_id = profile.id.clone();
     let occurred_at = now_utc_rfc3339();
+    let field_changes = app.resolve_custom_fields(&patch.custom_fields)?;
+    app.validate_tag_namespaces(&patch.add_tags)?;
     let mut us = UpdateState::from_record(&current, patch.expected_profile_etag.clone());
     let mut full_events = Vec::new();
 
@@ -142,6 +156,13 @@ fn update_knot_locked(
         &mut us,
         &current,
     )?;
+    fields::collect_custom_fields(
+        &field_changes,
+        &mut full_events,
+        id,
+        &occurred_at,
+        &mut us.custom_fields,
+    );
 
     if full_events.is_empty() {
         return app.apply_alias_and_enrich_knot(KnotView::from(current));
@@ -199,6 +220,22 @@ fn apply_status_change(
         &us.blocked,
         app,
     )?;
+    app.enforce_wip_limit(current, &next_state, patch.force)?;
+    app.enforce_criteria(
+        current,
+        profile_id,
+        &next_state,
+        next_is_terminal,
+        patch.force,
+    )?;
+    app.enforce_definition_of_done(
+        current,
+        profile,
+        &next_state,
+        next_is_terminal,
+        patch.add_handoff_capsule.is_some(),
+        patch.force,
+    )?;
     match state_hierarchy::plan_state_transition(
         &app.conn,
         current,
@@ -229,6 +266,9 @@ fn apply_status_change(
                 FullEventKind::KnotStateSet.as_str(),
                 data,
             ));
+            if us.snoozed_until.take().is_some() {
+                full_events.push(snooze_set_event(id, occurred_at, None));
+            }
         }
         state_hierarchy::TransitionPlan::Allowed => {}
         state_hierarchy::TransitionPlan::CascadeTerminal { descendants } => {
@@ -373,6 +413,8 @@ fn write_update_events_and_cache(
             body: us.body.as_deref(),
             description: us.description.as_deref(),
             acceptance: us.acceptance.as_deref(),
+            estimate: us.estimate.as_deref(),
+            custom_fields: &us.custom_fields,
             priority: us.priority,
             knot_type: Some(us.knot_type.as_str()),
             tags: &us.tags,
@@ -396,6 +438,7 @@ fn write_update_events_and_cache(
             deferred_from_state: us.deferred.as_deref(),
             blocked_from_state: us.blocked.as_deref(),
             created_at: current.created_at.as_deref(),
+            snoozed_until: us.snoozed_until.as_deref(),
         },
     )?;
     Ok(())
@@ -1,11 +1,15 @@
+use std::collections::BTreeMap;
+
 use serde_json::json;
 
 use crate::db::KnotCacheRecord;
 use crate::events::{new_event_id, FullEvent, FullEventKind};
 
+use crate::app::custom_fields::field_set_event;
 use crate::app::error::AppError;
 use crate::app::helpers::{
-    metadata_entry_from_input, non_empty, normalize_tag, require_gate_metadata_scope,
+    metadata_entry_from_input, non_empty, normalize_estimate, normalize_tag,
+    require_gate_metadata_scope,
 };
 use crate::app::types::UpdateKnotPatch;
 
@@ -22,6 +26,7 @@ pub(crate) fn collect_field_events(
     collect_title(patch, events, id, at, &mut us.title)?;
     collect_description(patch, events, id, at, &mut us.description, &mut us.body);
     collect_acceptance(patch, events, id, at, &mut us.acceptance);
+    collect_estimate(patch, events, id, at, &mut us.estimate)?;
     collect_priority(patch, events, id, at, &mut us.priority)?;
     collect_type(patch, events, id, at, &mut us.knot_type);
     collect_gate(patch, events, id, at, &mut us.gate_data, us.knot_type)?;
@@ -106,6 +111,50 @@ fn collect_acceptance(
     }
 }
 
+fn collect_estimate(
+    patch: &UpdateKnotPatch,
+    events: &mut Vec<FullEvent>,
+    id: &str,
+    at: &str,
+    estimate: &mut Option<String>,
+) -> Result<(), AppError> {
+    if let Some(raw) = patch.estimate.as_deref() {
+        let next = normalize_estimate(raw)?;
+        if next != *estimate {
+            events.push(FullEvent::with_identity(
+                new_event_id(),
+                at.to_string(),
+                id.to_string(),
+                FullEventKind::KnotEstimateSet.as_str(),
+                json!({"estimate": next}),
+            ));
+            *estimate = next;
+        }
+    }
+    Ok(())
+}
+
+/// Appends a `knot.field_set` event for each custom field whose value
+/// changes; `None` removes the field.
+pub(crate) fn collect_custom_fields(
+    changes: &[(String, Option<String>)],
+    events: &mut Vec<FullEvent>,
+    id: &str,
+    at: &str,
+    custom_fields: &mut BTreeMap<String, String>,
+) {
+    for (name, value) in changes {
+        if custom_fields.get(name) == value.as_ref() {
+            continue;
+        }
+        events.push(field_set_event(id, at, name, value.as_deref()));
+        match value {
+            Some(value) => custom_fields.insert(name.clone(), value.clone()),
+            None => custom_fields.remove(name),
+        };
+    }
+}
+
 fn collect_priority(
     patch: &UpdateKnotPatch,
     events: &mut Vec<FullEvent>,
@@ -0,0 +1,140 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::db;
+use crate::domain::knot_type::KnotType;
+
+use super::error::AppError;
+use super::types::{EdgeView, KnotView};
+use super::App;
+
+/// A knot named in a lint finding.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct LintKnot {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    pub title: String,
+    pub state: String,
+}
+
+/// A knot id mentioned in a note that no longer names a knot.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct MissingNoteRef {
+    pub knot: LintKnot,
+    pub note_entry_id: String,
+    pub reference: String,
+}
+
+/// Structural problems `kno lint` looks for.
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct LintReport {
+    /// Open knots with no parent and no tags, which no view groups.
+    pub orphans: Vec<LintKnot>,
+    pub dangling_edges: Vec<EdgeView>,
+    pub missing_note_refs: Vec<MissingNoteRef>,
+    /// Deferred knots that do not record the state to resume into.
+    pub deferred_without_origin: Vec<LintKnot>,
+}
+
+impl LintReport {
+    pub fn issue_count(&self) -> usize {
+        self.orphans.len()
+            + self.dangling_edges.len()
+            + self.missing_note_refs.len()
+            + self.deferred_without_origin.len()
+    }
+}
+
+impl App {
+    pub fn lint(&self) -> Result<LintReport, AppError> {
+        let knots = self.list_knots()?;
+        let known: HashSet<String> = db::list_known_knot_ids(&self.conn)?.into_iter().collect();
+        let children: HashSet<String> = db::list_edges_by_kind(&self.conn, "parent_of")?
+            .into_iter()
+            .map(|edge| edge.dst)
+            .collect();
+        let prefixes: HashSet<&str> = known
+            .iter()
+            .filter_map(|id| id.rsplit_once('-').map(|(prefix, _)| prefix))
+            .collect();
+
+        let mut report = LintReport {
+            dangling_edges: db::list_dangling_edges(&self.conn)?
+                .into_iter()
+                .map(EdgeView::from)
+                .collect(),
+            ..LintReport::default()
+        };
+        for knot in &knots {
+            if knot.knot_type == KnotType::Lease {
+                continue;
+            }
+            let terminal = self
+                .profile_registry
+                .require(&knot.profile_id)?
+                .is_terminal_state(&knot.state);
+            if !terminal && knot.tags.is_empty() && !children.contains(&knot.id) {
+                report.orphans.push(lint_knot(knot));
+            }
+            if knot.state == "deferred" && knot.deferred_from_state.is_none() {
+                report.deferred_without_origin.push(lint_knot(knot));
+            }
+            for note in &knot.notes {
+                for reference in knot_id_mentions(&note.content, &prefixes) {
+                    if !known.contains(reference) {
+                        report.missing_note_refs.push(MissingNoteRef {
+                            knot: lint_knot(knot),
+                            note_entry_id: note.entry_id.clone(),
+                            reference: reference.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Hands the findings doctor knows how to repair to its fixes, then
+    /// lints again.
+    pub fn lint_fix(&self) -> Result<LintReport, AppError> {
+        let report = self.lint()?;
+        let checks = [crate::doctor_edges::dangling_edges_check(
+            report.dangling_edges.len(),
+        )];
+        crate::doctor_fix::apply_fixes(&self.repo_root, &checks);
+        self.lint()
+    }
+}
+
+fn lint_knot(knot: &KnotView) -> LintKnot {
+    LintKnot {
+        id: knot.id.clone(),
+        alias: knot.alias.clone(),
+        title: knot.title.clone(),
+        state: knot.state.clone(),
+    }
+}
+
+/// Words shaped like `<prefix>-<hex>` for a prefix this repo's knot ids
+/// use, e.g. `knots-3f2a`. Cross-repo `repo:id` refs are not ours to check.
+fn knot_id_mentions<'a>(text: &'a str, prefixes: &HashSet<&str>) -> Vec<&'a str> {
+    let mut seen = Vec::new();
+    let word_char = |ch: char| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | ':');
+    for word in text.split(|ch: char| !word_char(ch)) {
+        let word = word.trim_matches(['-', ':']);
+        if word.contains(':') {
+            continue;
+        }
+        let Some((prefix, suffix)) = word.rsplit_once('-') else {
+            continue;
+        };
+        let suffix_ok =
+            (4..=12).contains(&suffix.len()) && suffix.chars().all(|ch| ch.is_ascii_hexdigit());
+        if suffix_ok && prefixes.contains(prefix) && !seen.contains(&word) {
+            seen.push(word);
+        }
+    }
+    seen
+}
@@ -0,0 +1,40 @@
+use crate::db;
+use crate::project::DistributionMode;
+use crate::sync::{known_workflow_ids, GitAdapter, IncrementalApplier, KnotsWorktree};
+
+use super::error::AppError;
+use super::App;
+
+impl App {
+    /// `--db :memory:` starts empty. Fill it from the snapshots and events
+    /// checked out in the knots worktree, without fetching, and turn off
+    /// auto-sync so nothing on disk changes.
+    pub(super) fn hydrate_memory_cache(&self) -> Result<(), AppError> {
+        if self.distribution != DistributionMode::Git {
+            return Err(AppError::InvalidArgument(
+                "--db :memory: needs a git workspace with a knots worktree".to_string(),
+            ));
+        }
+        db::set_meta(&self.conn, "sync_policy", "never")?;
+        let worktree = KnotsWorktree::with_store_paths(self.repo_root.clone(), &self.store_paths);
+        if !worktree.path().join(".git").exists() {
+            eprintln!(
+                "warning: no knots worktree at {}; the in-memory cache is empty \
+                 (run `kno pull` once to create it)",
+                worktree.path().display()
+            );
+            return Ok(());
+        }
+        let git = GitAdapter::new();
+        let head = git.rev_parse(worktree.path(), "HEAD")?;
+        IncrementalApplier::new(
+            &self.conn,
+            worktree.path().to_path_buf(),
+            git,
+            known_workflow_ids(&self.repo_root),
+        )
+        .with_event_types(crate::repo_config::event_types(&self.store_paths.root))
+        .apply_to_head(&head)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use serde::Serialize;
+
+use crate::artifact_target::ArtifactTarget;
+use crate::db;
+use crate::domain::metadata::MetadataEntryInput;
+use crate::profile::ProfileDefinition;
+use crate::sync::GitAdapter;
+
+use super::error::AppError;
+use super::git_links::{trailer_tokens, trailer_values, GitLinkKind, TRAILER_KEY};
+use super::types::{StateActorMetadata, UpdateKnotPatch};
+use super::App;
+
+/// A merged PR as CI reports it to `kno automerge-hook`.
+#[derive(Debug, Clone, Default)]
+pub struct MergedPr {
+    /// Merge (or squash) commit on the target branch.
+    pub commit: String,
+    /// PR number or URL, recorded in the knot note.
+    pub pr: Option<String>,
+    /// Head branch of the PR; knots linked to it are advanced.
+    pub branch: Option<String>,
+    /// PR title and body; `KNO: <id>` lines in it name knots.
+    pub message: Option<String>,
+    /// Knots named explicitly.
+    pub knots: Vec<String>,
+    pub actor: StateActorMetadata,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct MergeHookOutcome {
+    pub id: String,
+    pub from_state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+}
+
+impl App {
+    /// Advances every knot the merged PR references to the state after its
+    /// profile's last `pr`-producing action, and links the merge commit.
+    /// Knots on profiles without PR output, or already past that state, are
+    /// only linked.
+    pub fn automerge_hook(&self, merged: &MergedPr) -> Result<Vec<MergeHookOutcome>, AppError> {
+        let commit = GitAdapter::new()
+            .log_commits(&self.repo_root, &[merged.commit.as_str()], TRAILER_KEY, 1)
+            .ok()
+            .and_then(|commits| commits.into_iter().next())
+            .ok_or_else(|| {
+                AppError::InvalidArgument(format!("unknown commit '{}'", merged.commit))
+            })?;
+
+        let mut ids: Vec<String> = Vec::new();
+        for token in &merged.knots {
+            ids.push(self.resolve_knot_token(token)?);
+        }
+        let referenced = commit
+            .trailers
+            .iter()
+            .map(String::as_str)
+            .chain(
+                merged
+                    .message
+                    .as_deref()
+                    .into_iter()
+                    .flat_map(trailer_values),
+            )
+            .flat_map(trailer_tokens)
+            .filter_map(|token| self.resolve_knot_token(token).ok());
+        ids.extend(referenced);
+        if let Some(branch) = merged.branch.as_deref() {
+            ids.extend(db::knots_with_git_link(
+                &self.conn,
+                GitLinkKind::Branch.as_str(),
+                branch,
+            )?);
+        }
+        let mut seen = std::collections::HashSet::new();
+        ids.retain(|id| seen.insert(id.clone()));
+        if ids.is_empty() {
+            return Err(AppError::InvalidArgument(
+                "the merged PR references no knots; pass --knot, a KNO: line, or a linked --branch"
+                    .to_string(),
+            ));
+        }
+
+        let mut outcomes = Vec::with_capacity(ids.len());
+        for id in ids {
+            let knot = self
+                .show_knot(&id)?
+                .ok_or_else(|| AppError::NotFound(id.clone()))?;
+            let profile = self.profile_registry.require(&knot.profile_id)?;
+            let mut outcome = MergeHookOutcome {
+                id: knot.id.clone(),
+                from_state: knot.state.clone(),
+                to_state: None,
+                skipped: None,
+            };
+            match post_merge_state(profile) {
+                None => {
+                    outcome.skipped = Some(format!("profile '{}' has no pr output", profile.id))
+                }
+                Some(target) if !is_before(profile, &knot.state, target) => {
+                    outcome.skipped = Some(format!("already at '{}'", knot.state))
+                }
+                Some(target) => {
+                    let short_sha = &commit.sha[..commit.sha.len().min(12)];
+                    let content = match merged.pr.as_deref() {
+                        Some(pr) => format!("Merged PR {pr} as {short_sha}."),
+                        None => format!("Merged as {short_sha}."),
+                    };
+                    self.update_knot(
+                        &knot.id,
+                        UpdateKnotPatch {
+                            status: Some(target.to_string()),
+                            force: true,
+                            add_note: Some(MetadataEntryInput {
+                                content,
+                                agentname: merged.actor.agent_name.clone(),
+                                model: merged.actor.agent_model.clone(),
+                                version: merged.actor.agent_version.clone(),
+                                ..MetadataEntryInput::default()
+                            }),
+                            state_actor: merged.actor.clone(),
+                            ..UpdateKnotPatch::default()
+                        },
+                    )?;
+                    outcome.to_state = Some(target.to_string());
+                }
+            }
+            self.link_git(&knot.id, GitLinkKind::Commit, &commit.sha)?;
+            outcomes.push(outcome);
+        }
+        Ok(outcomes)
+    }
+}
+
+/// The state after the last action whose output is a PR; merging the PR
+/// is what completes that action.
+fn post_merge_state(profile: &ProfileDefinition) -> Option<&str> {
+    let action = profile.states.iter().rev().find(|state| {
+        !profile.is_gate_action_state(state)
+            && profile
+                .outputs
+                .get(*state)
+                .is_some_and(|output| output.artifact_type == ArtifactTarget::Pr.as_str())
+    })?;
+    profile.next_happy_path_state(action)
+}
+
+fn is_before(profile: &ProfileDefinition, state: &str, target: &str) -> bool {
+    if profile.is_terminal_state(state) {
+        return false;
+    }
+    let position = |needle: &str| profile.states.iter().position(|state| state == needle);
+    match (position(state), position(target)) {
+        (Some(state), Some(target)) => state < target,
+        _ => true,
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::error::AppError;
+use super::types::{KnotView, UpdateKnotPatch};
+use super::App;
+use crate::db;
+use crate::dispatch::profile_lookup_id;
+use crate::org::{self, OrgEntry, OrgKeywords};
+
+/// One knot an org import changed (or would change, on a dry run).
+#[derive(Debug, Clone, Serialize)]
+pub struct OrgImportChange {
+    pub id: String,
+    pub line: usize,
+    /// Knot fields the heading differs in: `title`, `state`, `tags`.
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrgImportSummary {
+    pub dry_run: bool,
+    pub changed: Vec<OrgImportChange>,
+    pub unchanged: usize,
+    /// Headings without an `ID` property or whose id is not a knot here.
+    pub skipped: Vec<String>,
+}
+
+impl App {
+    /// `knots` as an org outline, children under their `parent_of` parent,
+    /// with TODO keywords for the states of the profiles in use. A knot
+    /// whose parent is not among `knots` becomes a top-level heading.
+    pub fn export_org(&self, knots: Vec<KnotView>) -> Result<String, AppError> {
+        let exported = self.knots_for_export(&knots, false)?;
+        let mut entries: HashMap<String, OrgEntry> = knots
+            .iter()
+            .zip(&exported)
+            .map(|(knot, value)| (knot.id.clone(), org_entry(knot, value)))
+            .collect();
+        let mut children: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut has_parent = BTreeSet::new();
+        for edge in db::list_edges_by_kind(&self.conn, "parent_of")? {
+            if entries.contains_key(&edge.src) && entries.contains_key(&edge.dst) {
+                has_parent.insert(edge.dst.clone());
+                children.entry(edge.src).or_default().push(edge.dst);
+            }
+        }
+        let mut placed = BTreeSet::new();
+        let mut roots = Vec::new();
+        for knot in knots.iter().filter(|knot| !has_parent.contains(&knot.id)) {
+            roots.extend(nest(&knot.id, &mut entries, &children, &mut placed));
+        }
+        // Knots only reachable through a parent_of cycle still get exported.
+        for knot in &knots {
+            roots.extend(nest(&knot.id, &mut entries, &children, &mut placed));
+        }
+        Ok(org::render_org(&self.org_keywords(&knots), &roots))
+    }
+
+    /// Writes title, state, and tag edits from an org file back to the
+    /// knots its `ID` properties name; re-importing an unchanged file
+    /// changes nothing. Deeper headings go first so children can move
+    /// ahead before a parent whose progress they would otherwise block.
+    pub fn import_org(&self, text: &str, dry_run: bool) -> Result<OrgImportSummary, AppError> {
+        let mut summary = OrgImportSummary {
+            dry_run,
+            changed: Vec::new(),
+            unchanged: 0,
+            skipped: Vec::new(),
+        };
+        let mut headings = org::parse_org(text);
+        headings.sort_by_key(|heading| std::cmp::Reverse(heading.level));
+        for heading in headings {
+            let Some(id) = heading.id.as_deref() else {
+                summary
+                    .skipped
+                    .push(format!("line {}: no ID property", heading.line));
+                continue;
+            };
+            let Some(knot) = self.show_knot(id).ok().flatten() else {
+                summary
+                    .skipped
+                    .push(format!("line {}: unknown knot {id}", heading.line));
+                continue;
+            };
+            let (patch, fields) = org_patch(&knot, &heading);
+            if fields.is_empty() {
+                summary.unchanged += 1;
+                continue;
+            }
+            if !dry_run {
+                self.update_knot(&knot.id, patch).map_err(|err| {
+                    AppError::InvalidArgument(format!("line {}: {err}", heading.line))
+                })?;
+            }
+            summary.changed.push(OrgImportChange {
+                id: knot.id,
+                line: heading.line,
+                fields,
+            });
+        }
+        summary.changed.sort_by_key(|change| change.line);
+        Ok(summary)
+    }
+
+    fn org_keywords(&self, knots: &[KnotView]) -> OrgKeywords {
+        let mut keywords = OrgKeywords::default();
+        let mut push = |state: &str, done: bool| {
+            if !keywords
+                .open
+                .iter()
+                .chain(&keywords.done)
+                .any(|s| s == state)
+            {
+                let list = if done {
+                    &mut keywords.done
+                } else {
+                    &mut keywords.open
+                };
+                list.push(state.to_string());
+            }
+        };
+        for knot in knots {
+            match self.profile_registry.require(&profile_lookup_id(knot)) {
+                Ok(profile) => {
+                    for state in &profile.states {
+                        push(state, profile.is_terminal_state(state));
+                    }
+                }
+                Err(_) => push(&knot.state, false),
+            }
+        }
+        keywords
+    }
+}
+
+fn org_entry(knot: &KnotView, exported: &Value) -> OrgEntry {
+    OrgEntry {
+        id: knot.id.clone(),
+        state: knot.state.clone(),
+        title: exported.get("title").map(|_| knot.title.clone()),
+        tags: exported.get("tags").map(|_| knot.tags.clone()),
+        description: exported
+            .get("description")
+            .and_then(|_| knot.description.clone()),
+        children: Vec::new(),
+    }
+}
+
+fn nest(
+    id: &str,
+    entries: &mut HashMap<String, OrgEntry>,
+    children: &BTreeMap<String, Vec<String>>,
+    placed: &mut BTreeSet<String>,
+) -> Option<OrgEntry> {
+    if !placed.insert(id.to_string()) {
+        return None;
+    }
+    let mut entry = entries.remove(id)?;
+    for child in children.get(id).into_iter().flatten() {
+        entry
+            .children
+            .extend(nest(child, entries, children, placed));
+    }
+    Some(entry)
+}
+
+fn org_patch(knot: &KnotView, heading: &org::OrgHeading) -> (UpdateKnotPatch, Vec<String>) {
+    let mut patch = UpdateKnotPatch::default();
+    let mut fields = Vec::new();
+    let hidden = |field: &str| heading.hidden.iter().any(|hidden| hidden == field);
+    if !hidden("title") && heading.title != knot.title {
+        patch.title = Some(heading.title.clone());
+        fields.push("title".to_string());
+    }
+    if let Some(state) = heading.state.as_ref().filter(|state| **state != knot.state) {
+        patch.status = Some(state.clone());
+        fields.push("state".to_string());
+    }
+    if !hidden("tags") {
+        let wanted: BTreeSet<&String> = heading.tags.iter().collect();
+        let current: BTreeSet<&String> = knot.tags.iter().collect();
+        patch.add_tags = wanted
+            .difference(&current)
+            .map(|tag| (*tag).clone())
+            .collect();
+        patch.remove_tags = current
+            .difference(&wanted)
+            .map(|tag| (*tag).clone())
+            .collect();
+        if !patch.add_tags.is_empty() || !patch.remove_tags.is_empty() {
+            fields.push("tags".to_string());
+        }
+    }
+    (patch, fields)
+}
@@ -0,0 +1,133 @@
+use std::ops::Deref;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+use crate::db;
+use crate::project::ProjectContext;
+
+use super::error::AppError;
+use super::App;
+
+/// A thread-safe, cloneable set of `App`s over one store, for long-running
+/// modes that serve concurrent requests. Each `App` keeps its connection and
+/// prepared-statement cache for the life of the pool instead of reopening
+/// the cache per request.
+#[derive(Clone)]
+pub struct AppPool {
+    inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+    context: ProjectContext,
+    db_path: String,
+    max_size: usize,
+    state: Mutex<PoolState>,
+    released: Condvar,
+}
+
+struct PoolState {
+    idle: Vec<App>,
+    open: usize,
+}
+
+/// An `App` checked out of an `AppPool`; it goes back to the pool on drop.
+pub struct PooledApp<'a> {
+    pool: &'a PoolInner,
+    app: Option<App>,
+}
+
+impl AppPool {
+    /// Opens the first `App` up front, so migrations run and open errors
+    /// surface here; the rest open on demand up to `max_size`.
+    pub fn open(
+        context: &ProjectContext,
+        db_path: &str,
+        max_size: usize,
+    ) -> Result<Self, AppError> {
+        // Every `:memory:` connection is its own database, hydrated on
+        // open, so one shared `App` is both cheaper and consistent.
+        let max_size = if db::is_memory_path(db_path) {
+            1
+        } else {
+            max_size.max(1)
+        };
+        let first = App::open_with_context(context, db_path)?;
+        Ok(Self {
+            inner: Arc::new(PoolInner {
+                context: context.clone(),
+                db_path: db_path.to_string(),
+                max_size,
+                state: Mutex::new(PoolState {
+                    idle: vec![first],
+                    open: 1,
+                }),
+                released: Condvar::new(),
+            }),
+        })
+    }
+
+    /// `App`s opened so far, idle or checked out.
+    #[cfg(test)]
+    pub fn open_count(&self) -> usize {
+        self.inner.lock_state().open
+    }
+
+    /// Checks out an idle `App`, opening another if the pool has room and
+    /// waiting for one to come back otherwise.
+    pub fn get(&self) -> Result<PooledApp<'_>, AppError> {
+        let mut state = self.inner.lock_state();
+        loop {
+            if let Some(app) = state.idle.pop() {
+                return Ok(self.inner.checked_out(app));
+            }
+            if state.open < self.inner.max_size {
+                state.open += 1;
+                drop(state);
+                return match App::open_with_context(&self.inner.context, &self.inner.db_path) {
+                    Ok(app) => Ok(self.inner.checked_out(app)),
+                    Err(err) => {
+                        self.inner.lock_state().open -= 1;
+                        self.inner.released.notify_one();
+                        Err(err)
+                    }
+                };
+            }
+            state = self
+                .inner
+                .released
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+}
+
+impl PoolInner {
+    fn lock_state(&self) -> MutexGuard<'_, PoolState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn checked_out(&self, app: App) -> PooledApp<'_> {
+        PooledApp {
+            pool: self,
+            app: Some(app),
+        }
+    }
+}
+
+impl Deref for PooledApp<'_> {
+    type Target = App;
+
+    fn deref(&self) -> &App {
+        self.app.as_ref().expect("pooled app is present until drop")
+    }
+}
+
+impl Drop for PooledApp<'_> {
+    fn drop(&mut self) {
+        if let Some(app) = self.app.take() {
+            self.pool.lock_state().idle.push(app);
+            self.pool.released.notify_one();
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use serde::Serialize;
+
+use super::error::AppError;
+use super::App;
+
+/// What `kno pr-body` renders: the knot's own text and acceptance
+/// criteria, its `parent_of` children, and the acceptance items of the
+/// review gates still ahead.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct PrBody {
+    pub id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acceptance: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub criteria: Vec<crate::db::CriterionRecord>,
+    pub children: Vec<PrBodyChild>,
+    pub checklist: Vec<ReviewChecklist>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct PrBodyChild {
+    pub id: String,
+    pub title: String,
+    pub state: String,
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ReviewChecklist {
+    pub state: String,
+    pub items: Vec<String>,
+}
+
+impl App {
+    pub fn pr_body(&self, id: &str) -> Result<PrBody, AppError> {
+        let knot = self
+            .show_knot(id)?
+            .ok_or_else(|| AppError::NotFound(id.to_string()))?;
+        let profile = self.profile_registry.require(&knot.profile_id)?;
+        let children = knot
+            .child_summaries
+            .into_iter()
+            .map(|child| PrBodyChild {
+                done: profile.is_terminal_state(&child.state),
+                id: child.id,
+                title: child.title,
+                state: child.state,
+            })
+            .collect();
+        // Gates before the knot's current state were already passed.
+        let ahead = profile
+            .states
+            .iter()
+            .position(|state| *state == knot.state)
+            .unwrap_or(0);
+        let checklist = profile.states[ahead..]
+            .iter()
+            .filter(|state| profile.is_gate_action_state(state))
+            .map(|state| ReviewChecklist {
+                state: state.clone(),
+                items: profile.acceptance_for_action_state(state).to_vec(),
+            })
+            .filter(|checklist| !checklist.items.is_empty())
+            .collect();
+        Ok(PrBody {
+            id: knot.id,
+            title: knot.title,
+            description: knot.description.or(knot.body),
+            acceptance: knot.acceptance,
+            criteria: knot.criteria,
+            children,
+            checklist,
+        })
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::db;
+use crate::events::{
+    now_utc_rfc3339, private_event_files, private_root, publish_private_events,
+    read_private_registry, register_private_knot, unregister_private_knot,
+};
+use crate::locks::FileLock;
+use crate::sync::{known_workflow_ids, GitAdapter, IncrementalApplier};
+
+use super::error::AppError;
+use super::types::KnotView;
+use super::{App, CreateKnotOptions};
+
+/// A private knot or draft moved into the shared stream by `kno publish`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct PublishedKnot {
+    pub id: String,
+    pub draft: bool,
+    pub event_files: u64,
+}
+
+impl App {
+    /// Allocates the id for a new knot and, for a private knot or draft,
+    /// registers it so its events go to the private directory from the first
+    /// one on. The registry under `.knots/private/` outlives the cache.
+    pub(super) fn next_create_knot_id(
+        &self,
+        options: &CreateKnotOptions,
+    ) -> Result<String, AppError> {
+        let knot_id = self.next_knot_id()?;
+        if options.private || options.draft {
+            register_private_knot(&self.store_paths.root, &knot_id, options.draft)?;
+            db::insert_private_knot(&self.conn, &knot_id, options.draft)?;
+            self.writer.set_private(&knot_id, true);
+        }
+        Ok(knot_id)
+    }
+
+    /// Moves a private knot's event files into the shared stream so the
+    /// next push publishes its whole history. A draft's events are first
+    /// restamped to now, as if it had just been created.
+    pub fn publish_knot(&self, token: &str) -> Result<PublishedKnot, AppError> {
+        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        let _cache_guard =
+            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        let id = self.resolve_knot_token(token)?;
+        let Some(draft) = db::private_knot_draft(&self.conn, &id)? else {
+            return Err(AppError::InvalidArgument(format!(
+                "knot {id} is not private"
+            )));
+        };
+        let private = db::list_private_knots(&self.conn)?;
+        if let Some(edge) = db::list_edges(&self.conn, &id, db::EdgeDirection::Outgoing)?
+            .into_iter()
+            .find(|edge| edge.dst != id && private.contains(&edge.dst))
+        {
+            return Err(AppError::InvalidArgument(format!(
+                "knot {id} has a {} edge to private knot {}; publish {} first",
+                edge.kind, edge.dst, edge.dst
+            )));
+        }
+        let restamp = draft.then(now_utc_rfc3339);
+        let event_files = publish_private_events(&self.store_paths.root, &id, restamp.as_deref())?;
+        if let Some(at) = &restamp {
+            db::restamp_knot_hot(&self.conn, &id, at)?;
+        }
+        db::delete_private_knot(&self.conn, &id)?;
+        unregister_private_knot(&self.store_paths.root, &id)?;
+        self.writer.set_private(&id, false);
+        Ok(PublishedKnot {
+            id,
+            draft,
+            event_files,
+        })
+    }
+
+    /// An edge event lives in its source knot's stream, so a shared knot may
+    /// not point at a private one: pushing the edge would publish the
+    /// private knot's id and leave the edge dangling on every other clone.
+    pub(super) fn guard_private_edge_target(&self, src: &str, dst: &str) -> Result<(), AppError> {
+        let private = db::list_private_knots(&self.conn)?;
+        if private.contains(dst) && !private.contains(src) {
+            return Err(AppError::InvalidArgument(format!(
+                "knot {dst} is private; run `kno publish {dst}` before linking \
+                 shared knot {src} to it"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Ids of private knots and drafts, which views served to others skip.
+    pub fn private_knot_ids(&self) -> Result<HashSet<String>, AppError> {
+        Ok(db::list_private_knots(&self.conn)?)
+    }
+
+    /// Every knot but private knots and drafts, for views served to others.
+    pub fn list_shared_knots(&self) -> Result<Vec<KnotView>, AppError> {
+        let private = self.private_knot_ids()?;
+        let mut knots = self.list_knots()?;
+        knots.retain(|knot| !private.contains(&knot.id));
+        Ok(knots)
+    }
+
+    /// Restores registered private knots the cache has lost track of, as
+    /// after it is deleted. Sync only reads the shared stream, so their rows
+    /// are rebuilt here from the events kept under `.knots/private/`.
+    pub(super) fn restore_private_knots(&self) -> Result<(), AppError> {
+        let registry = read_private_registry(&self.store_paths.root)?;
+        let known = db::list_private_knots(&self.conn)?;
+        let missing: HashSet<&str> = registry
+            .keys()
+            .map(String::as_str)
+            .filter(|id| !known.contains(*id))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        for id in &missing {
+            db::insert_private_knot(&self.conn, id, registry[*id])?;
+            self.writer.set_private(id, true);
+        }
+        let (index_files, full_files) = private_event_files(&self.store_paths.root, &missing)?;
+        IncrementalApplier::new(
+            &self.conn,
+            private_root(&self.store_paths.root),
+            GitAdapter::new(),
+            known_workflow_ids(&self.repo_root),
+        )
+        .with_event_types(crate::repo_config::event_types(&self.store_paths.root))
+        .apply_event_files(index_files, full_files)?;
+        Ok(())
+    }
+}
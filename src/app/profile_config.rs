@@ -1,11 +1,35 @@
 use crate::domain::knot_type::KnotType;
 use crate::installed_workflows;
+use crate::profile::ProfileDefinition;
 use crate::workflow::ProfileRegistry;
 
 use super::error::AppError;
-use super::App;
+use super::types::KnotView;
+use super::{App, UserConfig};
 
 impl App {
+    pub(super) fn read_user_config(&self) -> Result<UserConfig, AppError> {
+        match self.home_override.as_ref() {
+            Some(Some(home)) => crate::project::read_global_config(Some(home.as_path()))
+                .map_err(AppError::InvalidArgument),
+            Some(None) => Ok(UserConfig::default()),
+            None => crate::project::read_global_config(None).map_err(AppError::InvalidArgument),
+        }
+    }
+
+    pub(super) fn write_user_config(&self, config: &UserConfig) -> Result<(), AppError> {
+        match self.home_override.as_ref() {
+            Some(Some(home)) => crate::project::write_global_config(Some(home.as_path()), config)
+                .map_err(AppError::InvalidArgument),
+            Some(None) => Err(AppError::InvalidArgument(
+                "unable to resolve $HOME for profile config".to_string(),
+            )),
+            None => {
+                crate::project::write_global_config(None, config).map_err(AppError::InvalidArgument)
+            }
+        }
+    }
+
     pub fn default_profile_id(&self) -> Result<String, AppError> {
         let wf = self.default_workflow_id()?;
         self.default_profile_id_for_workflow(&wf)
@@ -42,7 +66,30 @@ impl App {
         default_profile_id_for_workflow_inner(self, workflow_id)
     }
 
+    /// Records the repo default (or quick) profile in `.knots/config.toml`
+    /// so every clone resolves the same default ahead of user config.
+    pub fn set_repo_default_profile_id(
+        &self,
+        profile_id: &str,
+        quick: bool,
+    ) -> Result<String, AppError> {
+        let resolved = self.resolve_profile_id(profile_id, None)?;
+        let profile = self.profile_registry.require(&resolved)?;
+        let mut config = self.read_repo_config()?.unwrap_or_default();
+        if quick {
+            config.default_quick_profile = Some(profile.id.clone());
+        } else {
+            config.default_profile = Some(profile.id.clone());
+        }
+        self.write_repo_config(&config)?;
+        Ok(profile.id.clone())
+    }
+
     pub fn default_quick_profile_id(&self) -> Result<String, AppError> {
+        let repo = self.read_repo_config()?.unwrap_or_default();
+        if let Some(id) = self.resolve_config_profile(&repo.default_quick_profile) {
+            return Ok(id);
+        }
         let config = self.read_user_config()?;
         if let Some(id) = self.resolve_config_profile(&config.default_quick_profile) {
             return Ok(id);
@@ -72,6 +119,17 @@ impl App {
         &self.profile_registry
     }
 
+    /// Expands skill variables in `skill` for `knot` under `profile`.
+    pub(crate) fn expand_skill(
+        &self,
+        skill: &str,
+        profile: &ProfileDefinition,
+        knot: Option<&KnotView>,
+    ) -> String {
+        let vars = crate::action_prompt::skill_variables(profile, knot, &self.repo_root);
+        crate::action_prompt::expand_variables(skill, &vars)
+    }
+
     pub(crate) fn resolve_profile_id(
         &self,
         raw_profile_id: &str,
@@ -146,11 +204,14 @@ fn resolve_final(
 }
 
 fn default_profile_id_for_workflow_inner(app: &App, workflow_id: &str) -> Result<String, AppError> {
-    let config = app.read_user_config()?;
-    if let Some(id) = app.resolve_config_profile(&config.default_profile) {
-        let profile = app.profile_registry.require(&id)?;
-        if profile.workflow_id == workflow_id {
-            return Ok(id);
+    let repo = app.read_repo_config()?.unwrap_or_default();
+    let user = app.read_user_config()?;
+    for configured in [&repo.default_profile, &user.default_profile] {
+        if let Some(id) = app.resolve_config_profile(configured) {
+            let profile = app.profile_registry.require(&id)?;
+            if profile.workflow_id == workflow_id {
+                return Ok(id);
+            }
         }
     }
     if let Ok(registry) = installed_workflows::InstalledWorkflowRegistry::load(app.workflow_root())
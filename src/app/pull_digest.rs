@@ -0,0 +1,166 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::db;
+
+use super::error::AppError;
+use super::App;
+
+/// The cache as it stood before a pull, so `pull_digest` can tell what the
+/// pull brought in.
+#[derive(Debug, Clone, Default)]
+pub struct PullDigestBaseline {
+    hot: HashMap<String, BaselineKnot>,
+    known: HashSet<String>,
+}
+
+#[derive(Debug, Clone)]
+struct BaselineKnot {
+    state: String,
+    updated_at: String,
+    claimed: bool,
+    /// Note and handoff capsule ids, so new ones can be told apart.
+    entries: HashSet<String>,
+}
+
+/// What a pull changed, for people rather than scripts.
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct PullDigest {
+    pub new_knots: Vec<DigestKnot>,
+    pub state_changes: Vec<DigestKnot>,
+    /// Knots that changed without moving state: edits, notes, tags.
+    pub other_updates: Vec<DigestKnot>,
+    /// Notes and handoff capsules the pull added.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub new_notes: Vec<DigestNote>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct DigestNote {
+    pub knot_id: String,
+    pub title: String,
+    /// `note` or `handoff capsule`.
+    pub kind: &'static str,
+    pub author: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct DigestKnot {
+    pub id: String,
+    pub title: String,
+    pub state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_state: Option<String>,
+    /// This clone held a lease on the knot before the pull.
+    pub claimed: bool,
+}
+
+impl PullDigest {
+    /// Whether no knot changed; new notes always come with an update.
+    pub fn is_empty(&self) -> bool {
+        self.new_knots.is_empty() && self.state_changes.is_empty() && self.other_updates.is_empty()
+    }
+}
+
+impl App {
+    pub fn pull_digest_baseline(&self) -> Result<PullDigestBaseline, AppError> {
+        let mut baseline = PullDigestBaseline::default();
+        for record in db::list_knot_hot(&self.conn)? {
+            baseline.known.insert(record.id.clone());
+            baseline.hot.insert(
+                record.id,
+                BaselineKnot {
+                    entries: record
+                        .notes
+                        .iter()
+                        .chain(&record.handoff_capsules)
+                        .map(|entry| entry.entry_id.clone())
+                        .collect(),
+                    state: record.state,
+                    updated_at: record.updated_at,
+                    claimed: record.lease_id.is_some(),
+                },
+            );
+        }
+        baseline.known.extend(
+            db::list_knot_warm(&self.conn)?
+                .into_iter()
+                .map(|warm| warm.id),
+        );
+        baseline.known.extend(
+            db::list_cold_catalog(&self.conn)?
+                .into_iter()
+                .map(|cold| cold.id),
+        );
+        Ok(baseline)
+    }
+
+    /// Compares the hot cache with `baseline`. Claimed knots sort first in
+    /// each section so changes to your own work stand out.
+    pub fn pull_digest(&self, baseline: &PullDigestBaseline) -> Result<PullDigest, AppError> {
+        let mut digest = PullDigest::default();
+        for record in db::list_knot_hot(&self.conn)? {
+            let before = baseline.hot.get(&record.id);
+            if before.is_some_and(|knot| knot.updated_at == record.updated_at) {
+                continue;
+            }
+            let new = !baseline.known.contains(&record.id);
+            // A knot that was only warm or cold has no entries to compare.
+            if new || before.is_some() {
+                collect_new_notes(&mut digest.new_notes, &record, before);
+            }
+            let previous_state = before
+                .map(|knot| knot.state.clone())
+                .filter(|state| *state != record.state);
+            let section = if new {
+                &mut digest.new_knots
+            } else if previous_state.is_some() {
+                &mut digest.state_changes
+            } else {
+                &mut digest.other_updates
+            };
+            section.push(DigestKnot {
+                claimed: before.is_some_and(|knot| knot.claimed),
+                id: record.id,
+                title: record.title,
+                state: record.state,
+                previous_state,
+            });
+        }
+        for section in [
+            &mut digest.new_knots,
+            &mut digest.state_changes,
+            &mut digest.other_updates,
+        ] {
+            section.sort_by(|a, b| b.claimed.cmp(&a.claimed).then_with(|| a.id.cmp(&b.id)));
+        }
+        Ok(digest)
+    }
+}
+
+fn collect_new_notes(
+    notes: &mut Vec<DigestNote>,
+    record: &db::KnotCacheRecord,
+    before: Option<&BaselineKnot>,
+) {
+    let sections = [
+        ("note", &record.notes),
+        ("handoff capsule", &record.handoff_capsules),
+    ];
+    for (kind, entries) in sections {
+        for entry in entries {
+            if before.is_some_and(|knot| knot.entries.contains(&entry.entry_id)) {
+                continue;
+            }
+            notes.push(DigestNote {
+                knot_id: record.id.clone(),
+                title: record.title.clone(),
+                kind,
+                author: entry.username.clone(),
+                content: entry.content.clone(),
+            });
+        }
+    }
+}
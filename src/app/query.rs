@@ -55,6 +55,10 @@ impl App {
                 db::list_edges(&self.conn, &id, db::EdgeDirection::Both)
             })?;
             view.edges = edges.into_iter().map(EdgeView::from).collect();
+            view.git_links = db::list_git_links(&self.conn, &id)?;
+            view.criteria = db::list_criteria(&self.conn, &id)?;
+            view.handbacks = db::list_handbacks(&self.conn, &id)?;
+            view.plan = db::list_plans(&self.conn, &id)?.pop();
             view.child_summaries = view
                 .edges
                 .iter()
@@ -166,6 +170,8 @@ impl App {
                 body: current.body.as_deref(),
                 description: current.description.as_deref(),
                 acceptance: current.acceptance.as_deref(),
+                estimate: current.estimate.as_deref(),
+                custom_fields: &current.custom_fields,
                 priority: current.priority,
                 knot_type: current.knot_type.as_deref(),
                 tags: &current.tags,
@@ -182,6 +188,7 @@ impl App {
                 deferred_from_state: current.deferred_from_state.as_deref(),
                 blocked_from_state: current.blocked_from_state.as_deref(),
                 created_at: current.created_at.as_deref(),
+                snoozed_until: current.snoozed_until.as_deref(),
             },
         )?;
         let updated =
@@ -193,6 +200,18 @@ impl App {
         self.pull()
     }
 
+    pub fn cold_list(&self) -> Result<Vec<ColdKnotView>, AppError> {
+        Ok(db::list_cold_catalog(&self.conn)?
+            .into_iter()
+            .map(|r| ColdKnotView {
+                id: r.id,
+                title: r.title,
+                state: r.state,
+                updated_at: r.updated_at,
+            })
+            .collect())
+    }
+
     pub fn cold_search(&self, term: &str) -> Result<Vec<ColdKnotView>, AppError> {
         Ok(crate::trace::measure("search_cold_catalog", || {
             db::search_cold_catalog(&self.conn, term)
@@ -246,6 +265,8 @@ impl App {
                 body: record.body.as_deref(),
                 description: record.description.as_deref(),
                 acceptance: record.acceptance.as_deref(),
+                estimate: record.estimate.as_deref(),
+                custom_fields: &record.custom_fields,
                 priority: record.priority,
                 knot_type: Some(record.knot_type.as_str()),
                 tags: &record.tags,
@@ -262,6 +283,7 @@ impl App {
                 deferred_from_state: record.deferred_from_state.as_deref(),
                 blocked_from_state: record.blocked_from_state.as_deref(),
                 created_at: record.created_at.as_deref(),
+                snoozed_until: record.snoozed_until.as_deref(),
             },
         )?;
         let hot =
@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::db::{self, KnotCacheRecord, UpsertKnotHot};
+use crate::events::{
+    new_event_id, now_utc_rfc3339, private_root, redact_event_files, EventRecord, FullEvent,
+    FullEventKind,
+};
+use crate::locks::FileLock;
+use crate::snapshots::redact_snapshot_files;
+
+use super::error::AppError;
+use super::App;
+
+/// What `kno redact` rewrote, and the marker event recording it.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct Redaction {
+    pub event_id: String,
+    pub knot_id: String,
+    pub event_files: u64,
+    /// Snapshot files that held a match and were rewritten.
+    pub snapshot_files: u64,
+    pub replacements: u64,
+    pub fields: Vec<String>,
+    pub marker_event_id: String,
+    /// The event was already on the knots branch, whose history still
+    /// holds the secret until it is rewritten and force-pushed.
+    pub published: bool,
+}
+
+impl App {
+    /// Replaces each literal `patterns` match in every stored copy of
+    /// `event_id` and in every snapshot, scrubs the knot's cache row the
+    /// same way and writes a `knot.redacted` marker. The marker names the
+    /// event but never the pattern, so it can be pushed like any other
+    /// event.
+    pub fn redact_event(
+        &self,
+        event_id: &str,
+        patterns: &[String],
+        replacement: &str,
+    ) -> Result<Redaction, AppError> {
+        let event_id = event_id.trim();
+        if event_id.is_empty() {
+            return Err(AppError::InvalidArgument(
+                "event id is required".to_string(),
+            ));
+        }
+        if patterns.is_empty() || patterns.iter().any(|pattern| pattern.is_empty()) {
+            return Err(AppError::InvalidArgument(
+                "--pattern must be a non-empty string".to_string(),
+            ));
+        }
+        if patterns
+            .iter()
+            .any(|pattern| replacement.contains(pattern.as_str()))
+        {
+            return Err(AppError::InvalidArgument(
+                "--replacement must not contain a --pattern".to_string(),
+            ));
+        }
+        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        let _cache_guard =
+            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        let worktree = self.store_paths.worktree_path().join(".knots");
+        let roots = [
+            self.store_paths.root.clone(),
+            private_root(&self.store_paths.root),
+            worktree.clone(),
+        ];
+        let redacted = redact_event_files(&roots, event_id, patterns, replacement)?;
+        let Some(knot_id) = redacted.knot_id else {
+            return Err(AppError::InvalidArgument(format!(
+                "event '{event_id}' not found in the local store"
+            )));
+        };
+        if redacted.replacements == 0 {
+            return Err(AppError::InvalidArgument(format!(
+                "no --pattern match in event '{event_id}'"
+            )));
+        }
+        let snapshots = redact_snapshot_files(
+            &[self.store_paths.root.clone(), worktree.clone()],
+            patterns,
+            replacement,
+        )?;
+        if let Some(record) = db::get_knot_hot(&self.conn, &knot_id)? {
+            scrub_knot_hot(&self.conn, record, patterns, replacement)?;
+        }
+        // The row keeps its `updated_at`, so the search indexes would not
+        // notice the rewrite on their own.
+        db::forget_grep_index_entry(&self.conn, &knot_id)?;
+        #[cfg(feature = "semantic")]
+        db::delete_knot_embedding(&self.conn, &knot_id)?;
+        let marker = FullEvent::with_identity(
+            new_event_id(),
+            now_utc_rfc3339(),
+            knot_id.clone(),
+            FullEventKind::KnotRedacted.as_str(),
+            json!({
+                "redacted_event_id": event_id,
+                "replacements": redacted.replacements,
+                "fields": redacted.fields,
+            }),
+        );
+        let marker_event_id = marker.event_id.clone();
+        self.writer.write(&EventRecord::full(marker))?;
+        Ok(Redaction {
+            event_id: event_id.to_string(),
+            knot_id,
+            event_files: redacted.paths.len() as u64,
+            snapshot_files: snapshots.len() as u64,
+            replacements: redacted.replacements,
+            fields: redacted.fields,
+            marker_event_id,
+            published: redacted
+                .paths
+                .iter()
+                .chain(&snapshots)
+                .any(|path| path.starts_with(&worktree)),
+        })
+    }
+}
+
+/// Applies the redaction to every string in the cached knot, which was
+/// projected from the event before it was rewritten.
+fn scrub_knot_hot(
+    conn: &rusqlite::Connection,
+    record: KnotCacheRecord,
+    patterns: &[String],
+    replacement: &str,
+) -> Result<(), AppError> {
+    let mut value = serde_json::to_value(&record).map_err(std::io::Error::other)?;
+    if crate::events::redact_value(&mut value, patterns, replacement) == 0 {
+        return Ok(());
+    }
+    let record: KnotCacheRecord = serde_json::from_value(value).map_err(std::io::Error::other)?;
+    db::upsert_knot_hot(
+        conn,
+        &UpsertKnotHot {
+            id: &record.id,
+            title: &record.title,
+            state: &record.state,
+            updated_at: &record.updated_at,
+            body: record.body.as_deref(),
+            description: record.description.as_deref(),
+            acceptance: record.acceptance.as_deref(),
+            estimate: record.estimate.as_deref(),
+            custom_fields: &record.custom_fields,
+            priority: record.priority,
+            knot_type: record.knot_type.as_deref(),
+            tags: &record.tags,
+            notes: &record.notes,
+            handoff_capsules: &record.handoff_capsules,
+            invariants: &record.invariants,
+            step_history: &record.step_history,
+            gate_data: &record.gate_data,
+            lease_data: &record.lease_data,
+            lease_id: record.lease_id.as_deref(),
+            workflow_id: &record.workflow_id,
+            profile_id: &record.profile_id,
+            profile_etag: record.profile_etag.as_deref(),
+            deferred_from_state: record.deferred_from_state.as_deref(),
+            blocked_from_state: record.blocked_from_state.as_deref(),
+            created_at: record.created_at.as_deref(),
+            snoozed_until: record.snoozed_until.as_deref(),
+        },
+    )?;
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
@@ -15,6 +16,7 @@ use crate::workflow::normalize_profile_id;
 use super::error::AppError;
 
 pub(crate) mod apply_event;
+pub(crate) mod replay;
 
 #[derive(Debug)]
 pub(crate) struct RehydrateProjection {
@@ -24,6 +26,8 @@ pub(crate) struct RehydrateProjection {
     pub body: Option<String>,
     pub description: Option<String>,
     pub acceptance: Option<String>,
+    pub estimate: Option<String>,
+    pub custom_fields: BTreeMap<String, String>,
     pub priority: Option<i64>,
     pub knot_type: KnotType,
     pub tags: Vec<String>,
@@ -40,6 +44,7 @@ pub(crate) struct RehydrateProjection {
     pub deferred_from_state: Option<String>,
     pub blocked_from_state: Option<String>,
     pub created_at: Option<String>,
+    pub snoozed_until: Option<String>,
 }
 
 pub(crate) fn rehydrate_from_events(
@@ -64,6 +69,8 @@ fn new_projection(title: String, state: String, updated_at: String) -> Rehydrate
         body: None,
         description: None,
         acceptance: None,
+        estimate: None,
+        custom_fields: BTreeMap::new(),
         priority: None,
         knot_type: KnotType::default(),
         tags: Vec::new(),
@@ -80,10 +87,11 @@ fn new_projection(title: String, state: String, updated_at: String) -> Rehydrate
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: Some(updated_at),
+        snoozed_until: None,
     }
 }
 
-fn collect_json_paths(root: &Path) -> Result<Vec<std::path::PathBuf>, AppError> {
+pub(super) fn collect_json_paths(root: &Path) -> Result<Vec<std::path::PathBuf>, AppError> {
     let mut stack = vec![root.to_path_buf()];
     let mut paths = Vec::new();
     while let Some(dir) = stack.pop() {
@@ -110,8 +118,9 @@ fn apply_full_events(
 ) -> Result<(), AppError> {
     let event_root = resolve_subdir(store_root, "events");
     let full_paths = collect_json_paths(&event_root)?;
+    let event_types = crate::repo_config::event_types(store_root);
     for path in full_paths {
-        let bytes = fs::read(&path)?;
+        let bytes = crate::compression::read(&path)?;
         let event: FullEvent = serde_json::from_slice(&bytes).map_err(|err| {
             AppError::InvalidArgument(format!(
                 "invalid rehydrate event '{}': {}",
@@ -122,7 +131,7 @@ fn apply_full_events(
         if event.knot_id != knot_id {
             continue;
         }
-        apply_event::apply_rehydrate_event(projection, &event);
+        apply_event::apply_rehydrate_event(projection, &event, &event_types);
     }
     Ok(())
 }
@@ -135,7 +144,7 @@ fn apply_index_events(
     let index_root = resolve_subdir(store_root, "index");
     let idx_paths = collect_json_paths(&index_root)?;
     for path in idx_paths {
-        let bytes = fs::read(&path)?;
+        let bytes = crate::compression::read(&path)?;
         let event: IndexEvent = serde_json::from_slice(&bytes).map_err(|err| {
             AppError::InvalidArgument(format!(
                 "invalid rehydrate index '{}': {}",
@@ -229,7 +238,7 @@ fn finalize_projection(
     Ok(())
 }
 
-fn resolve_subdir(store_root: &Path, name: &str) -> std::path::PathBuf {
+pub(super) fn resolve_subdir(store_root: &Path, name: &str) -> std::path::PathBuf {
     let nested = store_root.join(".knots");
     if nested.exists() {
         nested.join(name)
@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+
 use serde_json::Value;
 
+use crate::domain::event_type::{apply_field_changes, EventTypeSpec};
+
 use crate::domain::knot_type::parse_knot_type;
 use crate::events::FullEvent;
 use crate::workflow::normalize_profile_id;
@@ -9,7 +13,13 @@ use super::{
     RehydrateProjection,
 };
 
-pub(crate) fn apply_rehydrate_event(projection: &mut RehydrateProjection, event: &FullEvent) {
+/// Applies one full event; `event_types` holds the repo's custom event
+/// types, whose projections set custom fields.
+pub(crate) fn apply_rehydrate_event(
+    projection: &mut RehydrateProjection,
+    event: &FullEvent,
+    event_types: &BTreeMap<String, EventTypeSpec>,
+) {
     let Some(data) = event.data.as_object() else {
         return;
     };
@@ -26,6 +36,15 @@ pub(crate) fn apply_rehydrate_event(projection: &mut RehydrateProjection, event:
         "knot.acceptance_set" => {
             apply_acceptance_set(projection, data, event);
         }
+        "knot.estimate_set" => {
+            apply_estimate_set(projection, data, event);
+        }
+        "knot.field_set" => {
+            apply_field_set(projection, data, event);
+        }
+        "knot.snooze_set" => {
+            apply_snooze_set(projection, data, event);
+        }
         "knot.priority_set" => {
             apply_priority_set(projection, data, event);
         }
@@ -42,7 +61,11 @@ pub(crate) fn apply_rehydrate_event(projection: &mut RehydrateProjection, event:
         "knot.invariants_set" => {
             apply_invariants_set(projection, data, event);
         }
-        _ => {}
+        custom => {
+            if let Some(spec) = event_types.get(custom) {
+                apply_field_changes(&mut projection.custom_fields, &spec.field_changes(data));
+            }
+        }
     }
 }
 
@@ -82,6 +105,16 @@ fn apply_created(
     if data.contains_key("gate") {
         p.gate_data = parse_gate_data_value(data.get("gate"));
     }
+    let body = data
+        .get("body")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToString::to_string);
+    if body.is_some() {
+        p.description = body.clone();
+        p.body = body;
+    }
     p.created_at = Some(event.occurred_at.clone());
     p.updated_at = event.occurred_at.clone();
 }
@@ -178,6 +211,45 @@ fn apply_acceptance_set(
     p.updated_at = event.occurred_at.clone();
 }
 
+fn apply_estimate_set(
+    p: &mut RehydrateProjection,
+    data: &serde_json::Map<String, Value>,
+    event: &FullEvent,
+) {
+    p.estimate = data
+        .get("estimate")
+        .and_then(Value::as_str)
+        .map(ToString::to_string);
+    p.updated_at = event.occurred_at.clone();
+}
+
+fn apply_field_set(
+    p: &mut RehydrateProjection,
+    data: &serde_json::Map<String, Value>,
+    event: &FullEvent,
+) {
+    let Some(name) = data.get("field").and_then(Value::as_str) else {
+        return;
+    };
+    match data.get("value").and_then(Value::as_str) {
+        Some(value) => p.custom_fields.insert(name.to_string(), value.to_string()),
+        None => p.custom_fields.remove(name),
+    };
+    p.updated_at = event.occurred_at.clone();
+}
+
+fn apply_snooze_set(
+    p: &mut RehydrateProjection,
+    data: &serde_json::Map<String, Value>,
+    event: &FullEvent,
+) {
+    p.snoozed_until = data
+        .get("until")
+        .and_then(Value::as_str)
+        .map(ToString::to_string);
+    p.updated_at = event.occurred_at.clone();
+}
+
 fn apply_priority_set(
     p: &mut RehydrateProjection,
     data: &serde_json::Map<String, Value>,
@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::events::{FullEvent, IndexEvent, IndexEventKind};
+
+use super::super::error::AppError;
+use super::{
+    apply_event, apply_index_head, collect_json_paths, finalize_projection, new_projection,
+    resolve_subdir, RehydrateProjection,
+};
+
+/// Rebuilds every knot (or only `knot_id`) from the events recorded at or
+/// before `until`. Events found under several store roots are deduplicated
+/// by event id, so the local store and the sync worktree can both be read.
+pub(crate) fn replay_until(
+    store_roots: &[PathBuf],
+    until: OffsetDateTime,
+    knot_id: Option<&str>,
+) -> Result<Vec<(String, RehydrateProjection)>, AppError> {
+    let mut timeline = load_full_events(store_roots, until)?;
+    timeline.extend(load_index_heads(store_roots, until)?);
+    timeline.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let event_types = store_roots
+        .first()
+        .map(|root| crate::repo_config::event_types(root))
+        .unwrap_or_default();
+    let mut projections: BTreeMap<String, RehydrateProjection> = BTreeMap::new();
+    for (_, entry) in timeline {
+        let (id, occurred_at) = match &entry {
+            Entry::Full(event) => (event.knot_id.as_str(), &event.occurred_at),
+            Entry::Head(event) => match event.data.get("knot_id").and_then(Value::as_str) {
+                Some(id) => (id, &event.occurred_at),
+                None => continue,
+            },
+        };
+        if knot_id.is_some_and(|wanted| wanted != id) {
+            continue;
+        }
+        let projection = projections
+            .entry(id.to_string())
+            .or_insert_with(|| new_projection(String::new(), String::new(), occurred_at.clone()));
+        match &entry {
+            Entry::Full(event) => {
+                apply_event::apply_rehydrate_event(projection, event, &event_types)
+            }
+            Entry::Head(event) => {
+                if let Some(data) = event.data.as_object() {
+                    apply_index_head(data, &event.event_id, projection);
+                }
+            }
+        }
+    }
+
+    let mut out = Vec::with_capacity(projections.len());
+    for (id, mut projection) in projections {
+        finalize_projection(&mut projection, &id)?;
+        out.push((id, projection));
+    }
+    Ok(out)
+}
+
+/// Full events that occurred at or after `since` and at or before `until`,
+/// oldest first, deduplicated by event id across store roots.
+pub(crate) fn full_events_between(
+    store_roots: &[PathBuf],
+    since: OffsetDateTime,
+    until: OffsetDateTime,
+) -> Result<Vec<FullEvent>, AppError> {
+    let mut timeline = load_full_events(store_roots, until)?;
+    timeline.retain(|((at, _), _)| *at >= since);
+    timeline.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(timeline
+        .into_iter()
+        .filter_map(|(_, entry)| match entry {
+            Entry::Full(event) => Some(event),
+            Entry::Head(_) => None,
+        })
+        .collect())
+}
+
+pub(crate) fn parse_replay_timestamp(raw: &str) -> Result<OffsetDateTime, AppError> {
+    OffsetDateTime::parse(raw.trim(), &Rfc3339).map_err(|err| {
+        AppError::InvalidArgument(format!(
+            "invalid replay timestamp '{}': expected RFC 3339 \
+             (e.g. 2025-06-01T00:00:00Z): {}",
+            raw, err
+        ))
+    })
+}
+
+enum Entry {
+    Full(FullEvent),
+    Head(IndexEvent),
+}
+
+type Timeline = Vec<((OffsetDateTime, String), Entry)>;
+
+fn load_full_events(store_roots: &[PathBuf], until: OffsetDateTime) -> Result<Timeline, AppError> {
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for path in event_paths(store_roots, "events")? {
+        let event: FullEvent = read_event(&path, "event")?;
+        let at = event_time(&path, &event.occurred_at)?;
+        if at > until || !seen.insert(event.event_id.clone()) {
+            continue;
+        }
+        out.push(((at, event.event_id.clone()), Entry::Full(event)));
+    }
+    Ok(out)
+}
+
+fn load_index_heads(store_roots: &[PathBuf], until: OffsetDateTime) -> Result<Timeline, AppError> {
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for path in event_paths(store_roots, "index")? {
+        let event: IndexEvent = read_event(&path, "index")?;
+        if event.event_type != IndexEventKind::KnotHead.as_str() {
+            continue;
+        }
+        let at = event_time(&path, &event.occurred_at)?;
+        if at > until || !seen.insert(event.event_id.clone()) {
+            continue;
+        }
+        out.push(((at, event.event_id.clone()), Entry::Head(event)));
+    }
+    Ok(out)
+}
+
+fn event_paths(store_roots: &[PathBuf], name: &str) -> Result<Vec<PathBuf>, AppError> {
+    let mut paths = Vec::new();
+    for root in store_roots {
+        paths.extend(collect_json_paths(&resolve_subdir(root, name))?);
+    }
+    Ok(paths)
+}
+
+fn read_event<T: serde::de::DeserializeOwned>(path: &Path, kind: &str) -> Result<T, AppError> {
+    let bytes = crate::compression::read(path)?;
+    serde_json::from_slice(&bytes).map_err(|err| {
+        AppError::InvalidArgument(format!(
+            "invalid replay {} '{}': {}",
+            kind,
+            path.display(),
+            err
+        ))
+    })
+}
+
+fn event_time(path: &Path, occurred_at: &str) -> Result<OffsetDateTime, AppError> {
+    OffsetDateTime::parse(occurred_at, &Rfc3339).map_err(|err| {
+        AppError::InvalidArgument(format!(
+            "invalid occurred_at '{}' in '{}': {}",
+            occurred_at,
+            path.display(),
+            err
+        ))
+    })
+}
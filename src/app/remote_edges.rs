@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
+
+use crate::db;
+use crate::domain::edge_ref::RemoteKnotRef;
+
+use super::error::AppError;
+use super::App;
+
+/// A cross-repo edge target as its own repo's cache currently sees it.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct RemoteKnotSummary {
+    pub repo: String,
+    pub id: String,
+    pub title: String,
+    pub state: String,
+}
+
+impl App {
+    /// Normalizes a `repo-name:knot-id` edge target, requiring `repo-name`
+    /// to be a registered project with a repo root. The knot itself is only
+    /// looked up when the edge is displayed.
+    pub(super) fn normalize_remote_ref(&self, dst: &str) -> Result<Option<String>, AppError> {
+        let Some(remote) = RemoteKnotRef::parse(dst) else {
+            return Ok(None);
+        };
+        self.registered_repo_root(remote.repo)?;
+        Ok(Some(format!("{}:{}", remote.repo, remote.id)))
+    }
+
+    /// Reads a cross-repo edge target from its repo's cache. `None` when the
+    /// target is local, or its repo or knot cannot be found.
+    pub fn resolve_remote_knot(&self, dst: &str) -> Option<RemoteKnotSummary> {
+        let remote = RemoteKnotRef::parse(dst)?;
+        let db_path = self
+            .registered_repo_root(remote.repo)
+            .ok()?
+            .join(".knots/cache/state.sqlite");
+        let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY).ok()?;
+        let record = db::get_knot_hot(&conn, remote.id).ok()??;
+        Some(RemoteKnotSummary {
+            repo: remote.repo.to_string(),
+            id: record.id,
+            title: record.title,
+            state: record.state,
+        })
+    }
+
+    fn registered_repo_root(&self, repo: &str) -> Result<PathBuf, AppError> {
+        let home = match self.home_override.as_ref() {
+            Some(Some(home)) => Some(home.as_path()),
+            Some(None) => {
+                return Err(AppError::InvalidArgument(
+                    "unable to resolve $HOME for registered projects".to_string(),
+                ))
+            }
+            None => None,
+        };
+        let record = crate::project::load_named_project(home, repo).map_err(|err| {
+            AppError::InvalidArgument(format!(
+                "{err}; register it with `kno project create {repo} --repo-root <path>`"
+            ))
+        })?;
+        record
+            .repo_root
+            .ok_or_else(|| AppError::InvalidArgument(format!("project '{repo}' has no repo root")))
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::{BTreeMap, HashSet};
+
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::db::KnotCacheRecord;
+use crate::events::FullEvent;
+
+use super::error::AppError;
+use super::rehydrate::replay::{full_events_between, parse_replay_timestamp, replay_until};
+use super::rehydrate::RehydrateProjection;
+use super::types::KnotView;
+use super::App;
+
+impl App {
+    /// Rebuilds knots as they stood at `until` (RFC 3339) straight from the
+    /// event log. The result is a temporary view: the cache is not touched.
+    pub fn replay(&self, until: &str, id: Option<&str>) -> Result<Vec<KnotView>, AppError> {
+        self.replay_at(parse_replay_timestamp(until)?, id)
+    }
+
+    /// The full events of each of `knot_ids` (already resolved), oldest
+    /// first, read from the event log in one pass.
+    pub fn knot_events(
+        &self,
+        knot_ids: &[String],
+    ) -> Result<BTreeMap<String, Vec<FullEvent>>, AppError> {
+        let wanted: HashSet<&str> = knot_ids.iter().map(String::as_str).collect();
+        let roots = [
+            self.store_paths.root.clone(),
+            self.store_paths.worktree_path(),
+        ];
+        let mut events: BTreeMap<String, Vec<FullEvent>> = BTreeMap::new();
+        let until = PrimitiveDateTime::MAX.assume_utc();
+        for event in full_events_between(&roots, OffsetDateTime::UNIX_EPOCH, until)? {
+            if wanted.contains(event.knot_id.as_str()) {
+                events.entry(event.knot_id.clone()).or_default().push(event);
+            }
+        }
+        Ok(events)
+    }
+
+    pub(crate) fn replay_at(
+        &self,
+        until: OffsetDateTime,
+        id: Option<&str>,
+    ) -> Result<Vec<KnotView>, AppError> {
+        let knot_id = id.map(|token| {
+            self.resolve_knot_token(token)
+                .unwrap_or_else(|_| token.trim().to_string())
+        });
+        let roots = [
+            self.store_paths.root.clone(),
+            self.store_paths.worktree_path(),
+        ];
+        let projections = replay_until(&roots, until, knot_id.as_deref())?;
+        if let Some(id) = knot_id.as_deref() {
+            if projections.is_empty() {
+                return Err(AppError::NotFound(id.to_string()));
+            }
+        }
+        let knots = projections
+            .into_iter()
+            .map(|(id, record)| KnotView::from(projection_record(id, record)))
+            .collect();
+        self.apply_aliases_to_knots(knots)
+    }
+}
+
+/// The cache row a replayed projection stands for. Lease expiry is not part
+/// of the event log, so it stays 0.
+pub(super) fn projection_record(id: String, record: RehydrateProjection) -> KnotCacheRecord {
+    KnotCacheRecord {
+        id,
+        title: record.title,
+        state: record.state,
+        updated_at: record.updated_at,
+        body: record.body,
+        description: record.description,
+        acceptance: record.acceptance,
+        estimate: record.estimate,
+        custom_fields: record.custom_fields,
+        priority: record.priority,
+        knot_type: Some(record.knot_type.as_str().to_string()),
+        tags: record.tags,
+        notes: record.notes,
+        handoff_capsules: record.handoff_capsules,
+        invariants: record.invariants,
+        step_history: record.step_history,
+        gate_data: record.gate_data,
+        lease_data: record.lease_data,
+        lease_id: record.lease_id,
+        lease_expiry_ts: 0,
+        workflow_id: record.workflow_id,
+        profile_id: record.profile_id,
+        profile_etag: record.profile_etag,
+        deferred_from_state: record.deferred_from_state,
+        blocked_from_state: record.blocked_from_state,
+        created_at: record.created_at,
+        snoozed_until: record.snoozed_until,
+    }
+}
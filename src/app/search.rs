@@ -0,0 +1,43 @@
+use super::dedupe::{DedupeKnot, SimilarKnot};
+use super::error::AppError;
+use super::App;
+use crate::domain::similarity::TextProfile;
+
+/// Least share of the query's trigrams a knot must contain to be listed.
+const MIN_COVERAGE: f64 = 0.5;
+
+impl App {
+    /// Knots ranked by how much of `query` their title and description
+    /// contain, best first. Terminal knots are left out unless
+    /// `include_terminal`.
+    pub fn search_knots(
+        &self,
+        query: &str,
+        include_terminal: bool,
+        limit: usize,
+    ) -> Result<Vec<SimilarKnot>, AppError> {
+        let wanted = TextProfile::new(query, None);
+        let mut hits = Vec::new();
+        for knot in self.dedupe_candidates(include_terminal)? {
+            let profile = TextProfile::new(&knot.title, knot.description.as_deref());
+            let score = wanted.coverage(&profile);
+            if score >= MIN_COVERAGE {
+                hits.push(SimilarKnot {
+                    knot: DedupeKnot::from_view(&knot),
+                    score,
+                });
+            }
+        }
+        Ok(rank(hits, limit))
+    }
+}
+
+/// Best score first, ties by id, rounded to two decimal places.
+pub(super) fn rank(mut hits: Vec<SimilarKnot>, limit: usize) -> Vec<SimilarKnot> {
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.knot.id.cmp(&b.knot.id)));
+    hits.truncate(limit);
+    for hit in &mut hits {
+        hit.score = (hit.score * 100.0).round() / 100.0;
+    }
+    hits
+}
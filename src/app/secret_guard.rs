@@ -0,0 +1,84 @@
+use crate::domain::secret::scan_for_secrets;
+
+use super::error::AppError;
+use super::types::{CreateKnotOptions, UpdateKnotPatch};
+use super::App;
+
+const SECRETS_SCAN_KEY: &str = "secrets.scan";
+
+impl App {
+    /// Applies the `secrets.scan` setting to text about to be written as
+    /// `(field, text)` pairs: `warn` prints the kinds of credential found,
+    /// `block` refuses the write. Events are hard to scrub once pushed, so
+    /// this runs before anything reaches the event log.
+    pub(super) fn guard_secrets(&self, fields: &[(&str, &str)]) -> Result<(), AppError> {
+        let mode = self.config_get(SECRETS_SCAN_KEY)?.value;
+        if mode == "off" {
+            return Ok(());
+        }
+        let findings = fields
+            .iter()
+            .flat_map(|(field, text)| {
+                scan_for_secrets(text)
+                    .into_iter()
+                    .map(move |finding| format!("{} in {field}", finding.kind))
+            })
+            .collect::<Vec<_>>();
+        if findings.is_empty() {
+            return Ok(());
+        }
+        let summary = findings.join(", ");
+        if mode == "block" {
+            return Err(AppError::InvalidArgument(format!(
+                "refusing to write what looks like a credential ({summary}); remove it, \
+                 or run `kno config set {SECRETS_SCAN_KEY} warn` if this is a false positive"
+            )));
+        }
+        eprintln!(
+            "warning: this looks like a credential ({summary}); \
+             once pushed it takes `kno redact` and a history rewrite to remove"
+        );
+        Ok(())
+    }
+
+    /// The same fields `guard_patch_secrets` checks, as a new knot sets them.
+    pub(super) fn guard_create_secrets(
+        &self,
+        title: &str,
+        body: Option<&str>,
+        options: &CreateKnotOptions,
+    ) -> Result<(), AppError> {
+        let mut fields = vec![("title", title), ("description", body.unwrap_or_default())];
+        if let Some(acceptance) = &options.acceptance {
+            fields.push(("acceptance", acceptance.as_str()));
+        }
+        for assignment in &options.custom_fields {
+            fields.push(("custom field", assignment.as_str()));
+        }
+        self.guard_secrets(&fields)
+    }
+
+    pub(super) fn guard_patch_secrets(&self, patch: &UpdateKnotPatch) -> Result<(), AppError> {
+        let mut fields = Vec::new();
+        let text_fields = [
+            ("title", &patch.title),
+            ("description", &patch.description),
+            ("acceptance", &patch.acceptance),
+        ];
+        for (field, text) in text_fields {
+            if let Some(text) = text {
+                fields.push((field, text.as_str()));
+            }
+        }
+        for assignment in &patch.custom_fields {
+            fields.push(("custom field", assignment.as_str()));
+        }
+        if let Some(note) = &patch.add_note {
+            fields.push(("note", note.content.as_str()));
+        }
+        if let Some(capsule) = &patch.add_handoff_capsule {
+            fields.push(("handoff capsule", capsule.content.as_str()));
+        }
+        self.guard_secrets(&fields)
+    }
+}
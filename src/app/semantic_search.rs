@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use super::dedupe::{DedupeKnot, SimilarKnot};
+use super::error::AppError;
+use super::search::rank;
+use super::types::KnotView;
+use super::App;
+use crate::db;
+use crate::semantic::{self, EmbeddingProvider, HttpEmbeddings};
+
+const ENDPOINT_KEY: &str = "semantic.endpoint";
+const MODEL_KEY: &str = "semantic.model";
+/// Texts sent per embedding request.
+const EMBED_BATCH: usize = 32;
+
+impl App {
+    /// The endpoint from `semantic.endpoint`. Knot text is sent there, so
+    /// only a value set in this clone counts, never one from the repo config.
+    pub fn embedding_endpoint(&self) -> Result<HttpEmbeddings, AppError> {
+        let endpoint = self.config_get(ENDPOINT_KEY)?;
+        if endpoint.value.is_empty() || endpoint.source != "local" {
+            return Err(AppError::InvalidArgument(format!(
+                "semantic search needs an embeddings endpoint; run \
+                 `kno config set {ENDPOINT_KEY} http://localhost:11434/v1/embeddings`"
+            )));
+        }
+        Ok(HttpEmbeddings {
+            endpoint: endpoint.value,
+            model: self.config_get(MODEL_KEY)?.value,
+            api_key: std::env::var(semantic::API_KEY_ENV).ok(),
+        })
+    }
+
+    /// Embeds the knots whose text or model changed since their stored
+    /// vector was made; returns how many were embedded.
+    pub fn refresh_embeddings(
+        &self,
+        provider: &dyn EmbeddingProvider,
+        knots: &[KnotView],
+    ) -> Result<usize, AppError> {
+        let hidden = self.sensitive_fields()?;
+        let stored: HashMap<String, String> = db::list_knot_embeddings(&self.conn)?
+            .into_iter()
+            .map(|record| (record.knot_id, record.content_hash))
+            .collect();
+        let stale: Vec<(String, String, String)> = knots
+            .iter()
+            .filter_map(|knot| {
+                let text = embedding_text(knot, &hidden)?;
+                let hash = semantic::content_hash(provider.model(), &text);
+                (stored.get(&knot.id) != Some(&hash)).then(|| (knot.id.clone(), hash, text))
+            })
+            .collect();
+        for batch in stale.chunks(EMBED_BATCH) {
+            let texts: Vec<String> = batch.iter().map(|(_, _, text)| text.clone()).collect();
+            let vectors = provider.embed(&texts).map_err(std::io::Error::other)?;
+            for ((id, hash, _), vector) in batch.iter().zip(vectors) {
+                db::upsert_knot_embedding(&self.conn, id, hash, &semantic::encode_vector(&vector))?;
+            }
+        }
+        Ok(stale.len())
+    }
+
+    /// Knots ranked by cosine similarity between their embedding and the
+    /// query's, refreshing stale embeddings first.
+    pub fn semantic_search(
+        &self,
+        provider: &dyn EmbeddingProvider,
+        query: &str,
+        include_terminal: bool,
+        limit: usize,
+    ) -> Result<Vec<SimilarKnot>, AppError> {
+        let knots = self.dedupe_candidates(include_terminal)?;
+        self.refresh_embeddings(provider, &knots)?;
+        let wanted = provider
+            .embed(&[query.to_string()])
+            .map_err(std::io::Error::other)?
+            .pop()
+            .unwrap_or_default();
+        let vectors: HashMap<String, Vec<f32>> = db::list_knot_embeddings(&self.conn)?
+            .into_iter()
+            .map(|record| (record.knot_id, semantic::decode_vector(&record.vector)))
+            .collect();
+        let hits = knots
+            .iter()
+            .filter_map(|knot| {
+                let score = semantic::cosine(&wanted, vectors.get(&knot.id)?);
+                Some(SimilarKnot {
+                    knot: DedupeKnot::from_view(knot),
+                    score,
+                })
+            })
+            .collect();
+        Ok(rank(hits, limit))
+    }
+}
+
+/// Title and description, minus whichever `sensitive_fields` hides, since
+/// the text leaves the machine. `None` when nothing is left to embed.
+fn embedding_text(knot: &KnotView, hidden: &[String]) -> Option<String> {
+    let shown = |field: &str| !hidden.iter().any(|hidden| hidden == field);
+    let mut parts = Vec::new();
+    if shown("title") {
+        parts.push(knot.title.trim());
+    }
+    if shown("description") {
+        parts.extend(knot.description.as_deref().map(str::trim));
+    }
+    let text = parts
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    (!text.is_empty()).then_some(text)
+}
@@ -0,0 +1,395 @@
+use serde::Serialize;
+
+use crate::db;
+use crate::repo_config::{self, RepoConfig};
+
+use super::error::AppError;
+use super::settings_validate::*;
+use super::App;
+
+/// Operational settings stored in the cache `meta` table. Defaults mirror
+/// the values seeded by the schema bootstrap.
+pub(crate) struct SettingSpec {
+    pub key: &'static str,
+    pub default: &'static str,
+    pub description: &'static str,
+    validate: fn(&str) -> Result<String, String>,
+}
+
+pub(crate) static SETTINGS: [SettingSpec; 29] = [
+    SettingSpec {
+        key: "sync_policy",
+        default: "auto",
+        description: "When commands sync implicitly: auto, always, or never.",
+        validate: validate_sync_policy,
+    },
+    SettingSpec {
+        key: "sync_auto_budget_ms",
+        default: "750",
+        description: "Time budget for implicit sync under the auto policy.",
+        validate: validate_u64,
+    },
+    SettingSpec {
+        key: "sync_try_lock_ms",
+        default: "0",
+        description: "How long read commands wait for the repo lock before skipping sync.",
+        validate: validate_u64,
+    },
+    SettingSpec {
+        key: "push_retry_budget_ms",
+        default: "800",
+        description: "Time budget for retrying rejected pushes.",
+        validate: validate_u64,
+    },
+    SettingSpec {
+        key: "sync_mode",
+        default: "worktree",
+        description: "How sync reads the knots branch: worktree, or mirror for a bare clone.",
+        validate: validate_sync_mode,
+    },
+    SettingSpec {
+        key: "remote_store",
+        default: "git",
+        description: "Replication target: git, dir:<path>, or s3://bucket[/prefix].",
+        validate: validate_remote_store,
+    },
+    SettingSpec {
+        key: "sync_fetch_blob_limit_kb",
+        default: "0",
+        description: "Partial-clone blob size limit for fetches; 0 disables filtering.",
+        validate: validate_u64,
+    },
+    SettingSpec {
+        key: "pull_drift_warn_threshold",
+        default: "25",
+        description: "Unpushed event files that trigger a pull warning.",
+        validate: validate_u64,
+    },
+    SettingSpec {
+        key: "compression",
+        default: "none",
+        description: "Compression for written snapshots: none or zstd.",
+        validate: validate_compression,
+    },
+    SettingSpec {
+        key: "compact.auto",
+        default: "off",
+        description: "Snapshot on push/sync when due: off, daily, weekly, or monthly.",
+        validate: validate_compact_auto,
+    },
+    SettingSpec {
+        key: "compact.event_threshold",
+        default: "5000",
+        description: "New event files that make auto compaction due early; 0 disables.",
+        validate: validate_u64,
+    },
+    SettingSpec {
+        key: "compact.prune_events",
+        default: "false",
+        description: "Whether auto compaction removes published local event files.",
+        validate: validate_bool,
+    },
+    SettingSpec {
+        key: "metrics.local",
+        default: "false",
+        description: "Record command timings and sync sizes locally for kno stats usage.",
+        validate: validate_bool,
+    },
+    SettingSpec {
+        key: "hot_window_days",
+        default: "7",
+        description: "Days a terminal knot stays in the hot tier.",
+        validate: validate_u64,
+    },
+    SettingSpec {
+        key: "manage_gitignore",
+        default: "true",
+        description: "Whether kno init adds the .knots rule to .gitignore.",
+        validate: validate_bool,
+    },
+    SettingSpec {
+        key: "id_prefix",
+        default: "",
+        description: "Prefix for new knot ids; empty derives it from the repo name.",
+        validate: validate_id_prefix,
+    },
+    SettingSpec {
+        key: "branch_template",
+        default: crate::branch_name::DEFAULT_BRANCH_TEMPLATE,
+        description: "Name template for kno branch: {alias}, {id}, {short_id}, {slug}, {type}.",
+        validate: validate_branch_template,
+    },
+    SettingSpec {
+        key: "ls.columns",
+        default: "",
+        description:
+            "Table columns for kno ls, e.g. id,state,title:50,tags; empty keeps one-line rows.",
+        validate: validate_ls_columns,
+    },
+    SettingSpec {
+        key: "notify.command",
+        default: "",
+        description:
+            "Shell command pull --notify pipes its digest to; only honored when set locally.",
+        validate: validate_notify_command,
+    },
+    SettingSpec {
+        key: "digest.smtp_url",
+        default: "",
+        description: "smtp:// or smtps:// server kno digest send mails through; only honored when set locally.",
+        validate: validate_smtp_url,
+    },
+    SettingSpec {
+        key: "digest.smtp_from",
+        default: "",
+        description: "Sender address for mailed digests.",
+        validate: validate_email_address,
+    },
+    SettingSpec {
+        key: "inbox.handle",
+        default: "",
+        description: "Name that @mentions use to reach this clone's inbox; empty uses $USER.",
+        validate: validate_inbox_handle,
+    },
+    SettingSpec {
+        key: "secrets.scan",
+        default: "off",
+        description: "Check titles, descriptions, notes, and capsules for credentials before writing: off, warn, or block.",
+        validate: validate_secrets_scan,
+    },
+    SettingSpec {
+        key: "dedupe.on_create",
+        default: "off",
+        description: "Whether kno new warns about open knots with similar titles: off or warn.",
+        validate: validate_dedupe_on_create,
+    },
+    SettingSpec {
+        key: "semantic.endpoint",
+        default: "",
+        description: "Embeddings URL kno search --semantic posts to; only honored when set locally.",
+        validate: validate_http_url,
+    },
+    SettingSpec {
+        key: "semantic.model",
+        default: "",
+        description: "Model name sent to semantic.endpoint; changing it re-embeds every knot.",
+        validate: validate_model_name,
+    },
+    SettingSpec {
+        key: "summarize.endpoint",
+        default: "",
+        description: "Chat completions URL kno summarize posts notes to; only honored when set locally.",
+        validate: validate_http_url,
+    },
+    SettingSpec {
+        key: "summarize.model",
+        default: "",
+        description: "Model name sent to summarize.endpoint.",
+        validate: validate_model_name,
+    },
+    SettingSpec {
+        key: "vault.path",
+        default: "",
+        description: "Markdown vault folder kno vault sync writes knot notes to; only honored when set locally.",
+        validate: validate_vault_path,
+    },
+];
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ConfigEntry {
+    pub key: String,
+    pub value: String,
+    pub source: &'static str,
+    pub description: &'static str,
+}
+
+/// Where `kno config set` / `unset` writes. `Local` overrides live in the
+/// cache database for this clone; `Repo` edits `.knots/config.toml`, which
+/// is shared with every clone through the knots branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigScope {
+    Local,
+    Repo,
+}
+
+const SOURCE_LOCAL: &str = "local";
+const SOURCE_REPO: &str = "repo";
+
+impl App {
+    pub fn config_list(&self) -> Result<Vec<ConfigEntry>, AppError> {
+        SETTINGS
+            .iter()
+            .map(|spec| self.config_entry(spec))
+            .collect()
+    }
+
+    pub fn config_get(&self, key: &str) -> Result<ConfigEntry, AppError> {
+        self.config_entry(setting_spec(key)?)
+    }
+
+    pub fn config_set(
+        &self,
+        key: &str,
+        value: &str,
+        scope: ConfigScope,
+    ) -> Result<ConfigEntry, AppError> {
+        let spec = setting_spec(key)?;
+        let normalized = (spec.validate)(value.trim()).map_err(|reason| {
+            AppError::InvalidArgument(format!("invalid value for {}: {}", spec.key, reason))
+        })?;
+        match scope {
+            ConfigScope::Local => {
+                db::set_meta(&self.conn, spec.key, &normalized)?;
+                db::set_meta(&self.conn, &source_key(spec.key), SOURCE_LOCAL)?;
+            }
+            ConfigScope::Repo => {
+                let mut config = self.read_repo_config()?.unwrap_or_default();
+                config.set_setting(spec.key, &normalized);
+                self.write_repo_config(&config)?;
+                self.apply_repo_config()?;
+            }
+        }
+        self.config_entry(spec)
+    }
+
+    pub fn config_unset(&self, key: &str, scope: ConfigScope) -> Result<ConfigEntry, AppError> {
+        let spec = setting_spec(key)?;
+        match scope {
+            ConfigScope::Local => {
+                db::set_meta(&self.conn, spec.key, spec.default)?;
+                db::delete_meta(&self.conn, &source_key(spec.key))?;
+            }
+            ConfigScope::Repo => {
+                if let Some(mut config) = self.read_repo_config()? {
+                    if config.remove_setting(spec.key) {
+                        self.write_repo_config(&config)?;
+                    }
+                }
+            }
+        }
+        self.apply_repo_config()?;
+        self.config_entry(spec)
+    }
+
+    /// Copies repo-level settings into the cache so every reader sees the
+    /// effective value. Local overrides are left alone.
+    pub(crate) fn apply_repo_config(&self) -> Result<(), AppError> {
+        let config = self.read_repo_config()?;
+        for spec in &SETTINGS {
+            let source = self.setting_source(spec)?;
+            if source == SOURCE_LOCAL {
+                continue;
+            }
+            let shared = config
+                .as_ref()
+                .and_then(|config| config.setting(spec.key))
+                .and_then(|raw| match (spec.validate)(raw.trim()) {
+                    Ok(value) => Some(value),
+                    Err(reason) => {
+                        eprintln!(
+                            "warning: ignoring {} in .knots/config.toml: {}",
+                            spec.key, reason
+                        );
+                        None
+                    }
+                });
+            match shared {
+                Some(value) => {
+                    if db::get_meta(&self.conn, spec.key)?.as_deref() != Some(value.as_str()) {
+                        db::set_meta(&self.conn, spec.key, &value)?;
+                    }
+                    if source != SOURCE_REPO {
+                        db::set_meta(&self.conn, &source_key(spec.key), SOURCE_REPO)?;
+                    }
+                }
+                None if source == SOURCE_REPO => {
+                    db::set_meta(&self.conn, spec.key, spec.default)?;
+                    db::delete_meta(&self.conn, &source_key(spec.key))?;
+                }
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Names the branch (and remote) that carry knots data in
+    /// `.knots/config.toml`; `None` keeps the current value.
+    pub fn set_repo_knots_ref(
+        &self,
+        branch: Option<&str>,
+        remote: Option<&str>,
+    ) -> Result<(), AppError> {
+        repo_config::set_knots_ref(&self.store_paths.root, branch, remote)
+            .map_err(AppError::InvalidArgument)
+    }
+
+    /// The configured `(remote, branch)` for knots data.
+    pub fn knots_ref(&self) -> (String, String) {
+        repo_config::knots_ref(&self.store_paths.root)
+    }
+
+    pub fn repo_config_path(&self) -> std::path::PathBuf {
+        repo_config::repo_config_path(&self.store_paths.root)
+    }
+
+    pub(super) fn read_repo_config(&self) -> Result<Option<RepoConfig>, AppError> {
+        repo_config::read_repo_config(&self.store_paths.root).map_err(AppError::InvalidArgument)
+    }
+
+    pub(super) fn write_repo_config(&self, config: &RepoConfig) -> Result<(), AppError> {
+        repo_config::write_repo_config(&self.store_paths.root, config)
+            .map_err(AppError::InvalidArgument)
+    }
+
+    /// `local` for explicit overrides (or legacy values edited directly in
+    /// SQLite), `repo` for values copied from `.knots/config.toml`.
+    fn setting_source(&self, spec: &SettingSpec) -> Result<&'static str, AppError> {
+        match db::get_meta(&self.conn, &source_key(spec.key))?.as_deref() {
+            Some(SOURCE_LOCAL) => Ok(SOURCE_LOCAL),
+            Some(SOURCE_REPO) => Ok(SOURCE_REPO),
+            _ => match db::get_meta(&self.conn, spec.key)? {
+                Some(value) if value != spec.default => Ok(SOURCE_LOCAL),
+                _ => Ok("default"),
+            },
+        }
+    }
+
+    fn config_entry(&self, spec: &SettingSpec) -> Result<ConfigEntry, AppError> {
+        let source = self.setting_source(spec)?;
+        let value = match source {
+            "default" => spec.default.to_string(),
+            _ => db::get_meta(&self.conn, spec.key)?.unwrap_or_else(|| spec.default.to_string()),
+        };
+        Ok(ConfigEntry {
+            key: spec.key.to_string(),
+            value,
+            source,
+            description: spec.description,
+        })
+    }
+}
+
+/// Validates and normalizes `raw` for `key` without storing it.
+pub(crate) fn normalize_setting(key: &str, raw: &str) -> Result<String, String> {
+    let spec = setting_spec(key).map_err(|err| err.to_string())?;
+    (spec.validate)(raw.trim())
+}
+
+fn source_key(key: &str) -> String {
+    format!("config_source:{key}")
+}
+
+fn setting_spec(key: &str) -> Result<&'static SettingSpec, AppError> {
+    let key = key.trim();
+    SETTINGS.iter().find(|spec| spec.key == key).ok_or_else(|| {
+        let known = SETTINGS
+            .iter()
+            .map(|spec| spec.key)
+            .collect::<Vec<_>>()
+            .join(", ");
+        AppError::InvalidArgument(format!(
+            "unknown config key '{}'; expected one of: {}",
+            key, known
+        ))
+    })
+}
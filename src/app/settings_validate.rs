@@ -0,0 +1,140 @@
+pub(super) fn validate_ls_columns(raw: &str) -> Result<String, String> {
+    crate::list_layout::parse_columns(raw)
+        .map(|columns| crate::list_layout::format_columns(&columns))
+}
+
+pub(super) fn validate_sync_policy(raw: &str) -> Result<String, String> {
+    let value = raw.to_ascii_lowercase();
+    match value.as_str() {
+        "auto" | "always" | "never" => Ok(value),
+        _ => Err(format!("'{}' is not one of auto, always, never", raw)),
+    }
+}
+
+pub(super) fn validate_sync_mode(raw: &str) -> Result<String, String> {
+    let value = raw.to_ascii_lowercase();
+    match value.as_str() {
+        "worktree" | "mirror" => Ok(value),
+        _ => Err(format!("'{}' is not one of worktree, mirror", raw)),
+    }
+}
+
+pub(super) fn validate_remote_store(raw: &str) -> Result<String, String> {
+    crate::replication::RemoteStoreSpec::parse(raw)?;
+    Ok(raw.to_string())
+}
+
+pub(super) fn validate_compression(raw: &str) -> Result<String, String> {
+    let value = raw.to_ascii_lowercase();
+    match value.as_str() {
+        "none" | "zstd" => Ok(value),
+        _ => Err(format!("'{}' is not one of none, zstd", raw)),
+    }
+}
+
+pub(super) fn validate_compact_auto(raw: &str) -> Result<String, String> {
+    let value = raw.to_ascii_lowercase();
+    match value.as_str() {
+        "off" | "daily" | "weekly" | "monthly" => Ok(value),
+        _ => Err(format!(
+            "'{}' is not one of off, daily, weekly, monthly",
+            raw
+        )),
+    }
+}
+
+pub(super) fn validate_secrets_scan(raw: &str) -> Result<String, String> {
+    let value = raw.to_ascii_lowercase();
+    match value.as_str() {
+        "off" | "warn" | "block" => Ok(value),
+        _ => Err(format!("'{}' is not one of off, warn, block", raw)),
+    }
+}
+
+pub(super) fn validate_dedupe_on_create(raw: &str) -> Result<String, String> {
+    let value = raw.to_ascii_lowercase();
+    match value.as_str() {
+        "off" | "warn" => Ok(value),
+        _ => Err(format!("'{}' is not one of off, warn", raw)),
+    }
+}
+
+pub(super) fn validate_u64(raw: &str) -> Result<String, String> {
+    raw.parse::<u64>()
+        .map(|value| value.to_string())
+        .map_err(|_| format!("'{}' is not a non-negative integer", raw))
+}
+
+pub(super) fn validate_bool(raw: &str) -> Result<String, String> {
+    match raw.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Ok("true".to_string()),
+        "false" | "no" | "off" | "0" => Ok("false".to_string()),
+        _ => Err(format!("'{}' is not true or false", raw)),
+    }
+}
+
+pub(super) fn validate_notify_command(raw: &str) -> Result<String, String> {
+    Ok(raw.to_string())
+}
+
+pub(super) fn validate_http_url(raw: &str) -> Result<String, String> {
+    if !raw.is_empty() && !raw.starts_with("http://") && !raw.starts_with("https://") {
+        return Err(format!("'{}' is not an http:// or https:// URL", raw));
+    }
+    Ok(raw.to_string())
+}
+
+pub(super) fn validate_smtp_url(raw: &str) -> Result<String, String> {
+    if !raw.is_empty() && !raw.starts_with("smtp://") && !raw.starts_with("smtps://") {
+        return Err(format!("'{}' is not an smtp:// or smtps:// URL", raw));
+    }
+    Ok(raw.to_string())
+}
+
+pub(super) fn validate_email_address(raw: &str) -> Result<String, String> {
+    if !raw.is_empty() && !crate::digest_send::is_email_address(raw) {
+        return Err(format!("'{}' is not an email address", raw));
+    }
+    Ok(raw.to_string())
+}
+
+pub(super) fn validate_model_name(raw: &str) -> Result<String, String> {
+    Ok(raw.to_string())
+}
+
+pub(super) fn validate_vault_path(raw: &str) -> Result<String, String> {
+    Ok(raw.to_string())
+}
+
+pub(super) fn validate_inbox_handle(raw: &str) -> Result<String, String> {
+    let value = raw.trim_start_matches('@').to_ascii_lowercase();
+    let valid = value
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.'));
+    if !valid {
+        return Err(format!(
+            "'{}' must be letters, digits, '_', '-' or '.'",
+            raw
+        ));
+    }
+    Ok(value)
+}
+
+pub(super) fn validate_id_prefix(raw: &str) -> Result<String, String> {
+    let value = raw.to_ascii_lowercase();
+    let valid = value
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_'));
+    if value.is_empty() || !valid {
+        return Err(format!(
+            "'{}' must be letters, digits, '-' or '_' and not empty",
+            raw
+        ));
+    }
+    Ok(value)
+}
+
+pub(super) fn validate_branch_template(raw: &str) -> Result<String, String> {
+    crate::branch_name::check_branch_template(raw)?;
+    Ok(raw.to_string())
+}
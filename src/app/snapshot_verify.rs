@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::db::KnotCacheRecord;
+use crate::snapshots::read_latest_snapshot;
+
+use super::error::AppError;
+use super::rehydrate::replay::{parse_replay_timestamp, replay_until};
+use super::replay::projection_record;
+use super::App;
+
+/// Cache-only fields that the event log does not carry: lease expiry is
+/// stamped locally and step history is kept per clone.
+const UNREPLAYED_FIELDS: [&str; 2] = ["lease_expiry_ts", "step_history"];
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SnapshotMismatch {
+    pub id: String,
+    pub tier: &'static str,
+    /// Fields whose snapshot value differs from the replayed one, or
+    /// `missing_from_events` / `missing_from_snapshot`.
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SnapshotVerifyReport {
+    pub active_path: PathBuf,
+    pub cold_path: Option<PathBuf>,
+    pub written_at: String,
+    pub knots_checked: u64,
+    pub mismatches: Vec<SnapshotMismatch>,
+}
+
+impl SnapshotVerifyReport {
+    pub fn ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl App {
+    /// Replays the event log up to the newest snapshot's `written_at` and
+    /// compares every knot with what the snapshot recorded for it.
+    pub fn verify_snapshot(&self) -> Result<SnapshotVerifyReport, AppError> {
+        let roots = [
+            self.store_paths.root.clone(),
+            self.store_paths.worktree_path(),
+        ];
+        let dirs: Vec<PathBuf> = roots.iter().map(|root| root.join("snapshots")).collect();
+        let snapshot = read_latest_snapshot(&dirs)?.ok_or_else(|| {
+            AppError::InvalidArgument(
+                "no snapshot found; run `kno compact --write-snapshots` first".to_string(),
+            )
+        })?;
+        let until = parse_replay_timestamp(&snapshot.written_at)?;
+        let mut replayed: BTreeMap<String, KnotCacheRecord> = replay_until(&roots, until, None)?
+            .into_iter()
+            .map(|(id, projection)| (id.clone(), projection_record(id, projection)))
+            .collect();
+
+        let mut mismatches = Vec::new();
+        let mut checked = 0u64;
+        for record in &snapshot.hot {
+            checked += 1;
+            let fields = match replayed.remove(&record.id) {
+                Some(replay) => differing_fields(record, &replay),
+                None => vec!["missing_from_events".to_string()],
+            };
+            push_mismatch(&mut mismatches, &record.id, "hot", fields);
+        }
+        for record in &snapshot.warm {
+            checked += 1;
+            let fields = match replayed.remove(&record.id) {
+                Some(replay) if replay.title == record.title => Vec::new(),
+                Some(_) => vec!["title".to_string()],
+                None => vec!["missing_from_events".to_string()],
+            };
+            push_mismatch(&mut mismatches, &record.id, "warm", fields);
+        }
+        for record in &snapshot.cold {
+            checked += 1;
+            let fields = match replayed.remove(&record.id) {
+                Some(replay) => [
+                    ("title", replay.title == record.title),
+                    ("state", replay.state == record.state),
+                    ("updated_at", replay.updated_at == record.updated_at),
+                ]
+                .into_iter()
+                .filter(|(_, same)| !same)
+                .map(|(field, _)| field.to_string())
+                .collect(),
+                None => vec!["missing_from_events".to_string()],
+            };
+            push_mismatch(&mut mismatches, &record.id, "cold", fields);
+        }
+        for id in replayed.into_keys() {
+            checked += 1;
+            let fields = vec!["missing_from_snapshot".to_string()];
+            push_mismatch(&mut mismatches, &id, "none", fields);
+        }
+
+        Ok(SnapshotVerifyReport {
+            active_path: snapshot.active_path,
+            cold_path: snapshot.cold_path,
+            written_at: snapshot.written_at,
+            knots_checked: checked,
+            mismatches,
+        })
+    }
+}
+
+fn push_mismatch(
+    mismatches: &mut Vec<SnapshotMismatch>,
+    id: &str,
+    tier: &'static str,
+    fields: Vec<String>,
+) {
+    if !fields.is_empty() {
+        mismatches.push(SnapshotMismatch {
+            id: id.to_string(),
+            tier,
+            fields,
+        });
+    }
+}
+
+/// Compares the serialized rows so every field, nested metadata included,
+/// has to match exactly.
+fn differing_fields(snapshot: &KnotCacheRecord, replay: &KnotCacheRecord) -> Vec<String> {
+    let as_object = |record: &KnotCacheRecord| match serde_json::to_value(record) {
+        Ok(Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    let (left, right) = (as_object(snapshot), as_object(replay));
+    left.keys()
+        .chain(right.keys().filter(|key| !left.contains_key(*key)))
+        .filter(|key| !UNREPLAYED_FIELDS.contains(&key.as_str()))
+        .filter(|key| left.get(*key) != right.get(*key))
+        .cloned()
+        .collect()
+}
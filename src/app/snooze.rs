@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use serde_json::json;
+use time::format_description::well_known::Rfc3339;
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::db::{self, UpsertKnotHot};
+use crate::events::{new_event_id, now_utc_rfc3339, EventRecord, FullEvent, FullEventKind};
+use crate::locks::FileLock;
+use crate::state_hierarchy;
+
+use super::error::AppError;
+use super::types::{KnotView, StateActorMetadata};
+use super::App;
+
+impl App {
+    /// Defers knot `id` until `until` (a `YYYY-MM-DD` date, read as midnight
+    /// UTC, or an RFC 3339 timestamp). The knot resumes to its
+    /// `deferred_from_state` on the first wake pass after that time.
+    pub fn snooze_knot(
+        &self,
+        id: &str,
+        until: &str,
+        state_actor: StateActorMetadata,
+    ) -> Result<KnotView, AppError> {
+        let until = parse_wake_time(until)?;
+        if until <= OffsetDateTime::now_utc() {
+            return Err(AppError::InvalidArgument(
+                "snooze wake time must be in the future".to_string(),
+            ));
+        }
+        let until = until
+            .format(&Rfc3339)
+            .expect("RFC3339 formatting for UTC timestamp should never fail");
+        let id = self.resolve_knot_token(id)?;
+        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        let _cache_guard =
+            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        let mut record =
+            db::get_knot_hot(&self.conn, &id)?.ok_or_else(|| AppError::NotFound(id.clone()))?;
+        if state_hierarchy::is_terminal_state(&record.state)? {
+            return Err(AppError::InvalidArgument(format!(
+                "cannot snooze a knot in terminal state '{}'",
+                record.state
+            )));
+        }
+        if record.state != "deferred" {
+            record = self.apply_state_transition_locked(
+                &record,
+                "deferred",
+                false,
+                None,
+                &state_actor,
+                false,
+                false,
+            )?;
+        }
+        let occurred_at = now_utc_rfc3339();
+        self.writer.write(&EventRecord::full(snooze_set_event(
+            &id,
+            &occurred_at,
+            Some(&until),
+        )))?;
+        db::upsert_knot_hot(
+            &self.conn,
+            &UpsertKnotHot {
+                id: &record.id,
+                title: &record.title,
+                state: &record.state,
+                updated_at: &occurred_at,
+                body: record.body.as_deref(),
+                description: record.description.as_deref(),
+                acceptance: record.acceptance.as_deref(),
+                estimate: record.estimate.as_deref(),
+                custom_fields: &record.custom_fields,
+                priority: record.priority,
+                knot_type: record.knot_type.as_deref(),
+                tags: &record.tags,
+                notes: &record.notes,
+                handoff_capsules: &record.handoff_capsules,
+                invariants: &record.invariants,
+                step_history: &record.step_history,
+                gate_data: &record.gate_data,
+                lease_data: &record.lease_data,
+                lease_id: record.lease_id.as_deref(),
+                workflow_id: &record.workflow_id,
+                profile_id: &record.profile_id,
+                profile_etag: record.profile_etag.as_deref(),
+                deferred_from_state: record.deferred_from_state.as_deref(),
+                blocked_from_state: record.blocked_from_state.as_deref(),
+                created_at: record.created_at.as_deref(),
+                snoozed_until: Some(&until),
+            },
+        )?;
+        let updated =
+            db::get_knot_hot(&self.conn, &id)?.ok_or_else(|| AppError::NotFound(id.clone()))?;
+        self.apply_alias_and_enrich_knot(KnotView::from(updated))
+    }
+
+    /// Resumes every snoozed knot whose wake time has passed and returns the
+    /// resumed knots. The resume is an ordinary state change, so it also
+    /// clears the snooze.
+    pub fn wake_snoozed_knots(&self) -> Result<Vec<KnotView>, AppError> {
+        let now = OffsetDateTime::now_utc();
+        let due: Vec<_> = db::list_knot_hot(&self.conn)?
+            .into_iter()
+            .filter(|record| record.state == "deferred")
+            .filter(|record| {
+                record
+                    .snoozed_until
+                    .as_deref()
+                    .and_then(|until| OffsetDateTime::parse(until, &Rfc3339).ok())
+                    .is_some_and(|until| until <= now)
+            })
+            .collect();
+        let mut woken = Vec::with_capacity(due.len());
+        for record in due {
+            let Some(resume_to) = record.deferred_from_state.as_deref() else {
+                continue;
+            };
+            woken.push(self.set_state_with_actor(
+                &record.id,
+                resume_to,
+                false,
+                None,
+                StateActorMetadata::default(),
+            )?);
+        }
+        Ok(woken)
+    }
+
+    /// Wake passes piggyback on sync; a failure must not fail the sync.
+    pub(super) fn wake_snoozed_knots_or_warn(&self) {
+        if let Err(err) = self.wake_snoozed_knots() {
+            eprintln!("warning: could not wake snoozed knots: {err}");
+        }
+    }
+}
+
+pub(crate) fn snooze_set_event(id: &str, at: &str, until: Option<&str>) -> FullEvent {
+    FullEvent::with_identity(
+        new_event_id(),
+        at.to_string(),
+        id.to_string(),
+        FullEventKind::KnotSnoozeSet.as_str(),
+        json!({ "until": until }),
+    )
+}
+
+fn parse_wake_time(raw: &str) -> Result<OffsetDateTime, AppError> {
+    let raw = raw.trim();
+    let timestamp = if raw.len() == 10 {
+        format!("{raw}T00:00:00Z")
+    } else {
+        raw.to_string()
+    };
+    OffsetDateTime::parse(&timestamp, &Rfc3339)
+        .map(|at| at.to_offset(UtcOffset::UTC))
+        .map_err(|_| {
+            AppError::InvalidArgument(format!(
+                "invalid wake time '{raw}'; expected YYYY-MM-DD or an RFC 3339 timestamp"
+            ))
+        })
+}
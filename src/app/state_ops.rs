@@ -16,6 +16,7 @@ use super::helpers::{
     next_blocked_from_state, next_deferred_from_state, normalize_state_input,
     resolve_step_metadata, KnotHeadData, StateCascadeMetadata, StateEventParams,
 };
+use super::snooze::snooze_set_event;
 use super::types::{KnotView, StateActorMetadata};
 use super::App;
 
@@ -129,6 +130,17 @@ impl App {
             next_state,
         )?;
         self.validate_resume_or_transition(current, next_state, force, next_is_terminal)?;
+        self.enforce_wip_limit(current, next_state, force)?;
+        self.enforce_criteria(current, &profile.id, next_state, next_is_terminal, force)?;
+        self.enforce_plan(current, profile, next_state, force)?;
+        self.enforce_definition_of_done(
+            current,
+            profile,
+            next_state,
+            next_is_terminal,
+            false,
+            force,
+        )?;
         // Explore knots require at least one related edge before shipping.
         if next_state == "shipped" && knot_type == KnotType::Explore {
             let out_edges = db::list_edges(&self.conn, &current.id, db::EdgeDirection::Outgoing)?;
@@ -268,6 +280,18 @@ impl App {
             knot_type,
         )?;
         self.writer.write(&EventRecord::full(full_event))?;
+        // Any state change ends a snooze; a fresh `kno snooze` sets it again.
+        let snoozed_until = match current.snoozed_until.as_deref() {
+            Some(_) if current.state != next_state => {
+                self.writer.write(&EventRecord::full(snooze_set_event(
+                    &current.id,
+                    &occurred_at,
+                    None,
+                )))?;
+                None
+            }
+            until => until,
+        };
         self.writer.write(&EventRecord::index(idx_event))?;
         let step_history = apply_step_transition(
             &current.step_history,
@@ -287,6 +311,8 @@ impl App {
                 body: current.body.as_deref(),
                 description: current.description.as_deref(),
                 acceptance: current.acceptance.as_deref(),
+                estimate: current.estimate.as_deref(),
+                custom_fields: &current.custom_fields,
                 priority: current.priority,
                 knot_type: current.knot_type.as_deref(),
                 tags: &current.tags,
@@ -303,6 +329,7 @@ impl App {
                 deferred_from_state: deferred.as_deref(),
                 blocked_from_state: blocked.as_deref(),
                 created_at: current.created_at.as_deref(),
+                snoozed_until,
             },
         )?;
         let updated = db::get_knot_hot(&self.conn, &current.id)?
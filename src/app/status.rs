@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::db;
+use crate::doctor::{DoctorCheck, DoctorStatus};
+use crate::locks::FileLock;
+use crate::replication::{ReplicationService, LAST_PUSH_AT_META, LAST_PUSH_HEAD_META};
+
+use super::error::AppError;
+use super::App;
+
+/// One-glance health of this clone: what is waiting to sync, when it last
+/// synced, how big the cache is, and what `kno doctor` is unhappy about.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct StatusReport {
+    pub distribution: String,
+    pub sync_policy: String,
+    pub remote_store: String,
+    /// `None` when the count could not be taken (local-only, remote
+    /// unreachable, or the repo lock is busy).
+    pub unpushed_event_files: Option<u64>,
+    pub sync_pending: bool,
+    pub last_pull: Option<SyncMark>,
+    pub last_push: Option<SyncMark>,
+    pub cache: CacheStatus,
+    pub locks: LockStatus,
+    pub warnings: Vec<DoctorCheck>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SyncMark {
+    pub at_ms: Option<u64>,
+    pub head: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub struct CacheStatus {
+    pub hot: u64,
+    pub warm: u64,
+    pub cold: u64,
+    pub db_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub struct LockStatus {
+    pub repo_held: bool,
+    pub cache_held: bool,
+}
+
+impl StatusReport {
+    pub fn is_healthy(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+impl App {
+    pub fn status(&self) -> Result<StatusReport, AppError> {
+        let locks = LockStatus {
+            repo_held: FileLock::try_acquire(&self.repo_lock_path())?.is_none(),
+            cache_held: FileLock::try_acquire(&self.cache_lock_path())?.is_none(),
+        };
+        let warnings = self
+            .doctor(false)?
+            .checks
+            .into_iter()
+            .filter(|check| check.status != DoctorStatus::Pass)
+            .collect();
+        let meta = |key: &str| db::get_meta(&self.conn, key);
+        let [hot, warm, cold] = db::count_tier_rows(&self.conn)?;
+        Ok(StatusReport {
+            distribution: if self.is_git_distribution() {
+                "git".to_string()
+            } else {
+                "local-only".to_string()
+            },
+            sync_policy: self.config_get("sync_policy")?.value,
+            remote_store: db::get_remote_store(&self.conn)?,
+            unpushed_event_files: self.unpushed_event_files(),
+            sync_pending: meta("sync_pending")?.as_deref() == Some("true"),
+            last_pull: sync_mark(
+                meta("last_sync_success_at_ms")?,
+                meta("last_full_head_commit")?,
+            ),
+            last_push: sync_mark(meta(LAST_PUSH_AT_META)?, meta(LAST_PUSH_HEAD_META)?),
+            cache: CacheStatus {
+                hot: hot as u64,
+                warm: warm as u64,
+                cold: cold as u64,
+                db_bytes: std::fs::metadata(self.store_paths.db_path())
+                    .map(|meta| meta.len())
+                    .unwrap_or(0),
+            },
+            locks,
+            warnings,
+        })
+    }
+
+    /// Best effort: status must still answer when the remote is down or
+    /// another command holds the repo lock.
+    fn unpushed_event_files(&self) -> Option<u64> {
+        if !self.is_git_distribution() {
+            return None;
+        }
+        self.count_unpushed_event_files().ok()
+    }
+
+    /// Event files this clone wrote that the remote does not have yet.
+    pub(crate) fn count_unpushed_event_files(&self) -> Result<u64, AppError> {
+        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(500))?;
+        Ok(ReplicationService::with_store_paths(
+            &self.conn,
+            self.repo_root.clone(),
+            self.store_paths.clone(),
+        )
+        .count_unpushed_event_files()?)
+    }
+}
+
+fn sync_mark(at_ms: Option<String>, head: Option<String>) -> Option<SyncMark> {
+    let at_ms = at_ms.and_then(|raw| raw.parse().ok());
+    (at_ms.is_some() || head.is_some()).then_some(SyncMark { at_ms, head })
+}
@@ -0,0 +1,154 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::db::{self, EdgeDirection, KnotCacheRecord};
+use crate::domain::knot_type::parse_knot_type;
+use crate::locks::FileLock;
+use crate::state_hierarchy;
+use crate::workflow_runtime;
+
+use super::error::AppError;
+use super::types::StateActorMetadata;
+use super::App;
+
+/// Outcome of advancing every direct child of `parent` one happy-path step.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SubtreeAdvance {
+    pub parent: String,
+    pub advanced: Vec<AdvancedChild>,
+    pub blocked: Vec<BlockedChild>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct AdvancedChild {
+    pub id: String,
+    pub previous_state: String,
+    pub state: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct BlockedChild {
+    pub id: String,
+    pub state: String,
+    pub reason: String,
+}
+
+impl App {
+    /// Advances the non-terminal children of `parent` to their next
+    /// happy-path state under a single lock. Children that cannot move are
+    /// left alone and reported with the reason.
+    pub fn advance_children(
+        &self,
+        parent: &str,
+        state_actor: StateActorMetadata,
+    ) -> Result<SubtreeAdvance, AppError> {
+        let parent = self.resolve_knot_token(parent)?;
+        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        let _cache_guard =
+            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        db::get_knot_hot(&self.conn, &parent)?.ok_or_else(|| AppError::NotFound(parent.clone()))?;
+        let mut report = SubtreeAdvance {
+            parent: parent.clone(),
+            advanced: Vec::new(),
+            blocked: Vec::new(),
+        };
+        let mut resolved = Vec::new();
+        for edge in db::list_edges(&self.conn, &parent, EdgeDirection::Outgoing)? {
+            if edge.kind != "parent_of" {
+                continue;
+            }
+            let Some(child) = db::get_knot_hot(&self.conn, &edge.dst)? else {
+                continue;
+            };
+            if state_hierarchy::is_terminal_state(&child.state)? {
+                continue;
+            }
+            let outcome = self
+                .blocked_reason(&child)?
+                .map_or_else(|| self.advance_child_locked(&child, &state_actor), Err);
+            match outcome {
+                Ok(updated) => {
+                    if self.transitioned_to_terminal_resolution_state(&child, &updated)? {
+                        resolved.push(updated.id.clone());
+                    }
+                    report.advanced.push(AdvancedChild {
+                        id: updated.id,
+                        previous_state: child.state,
+                        state: updated.state,
+                    });
+                }
+                Err(reason) => report.blocked.push(BlockedChild {
+                    id: child.id,
+                    state: child.state,
+                    reason,
+                }),
+            }
+        }
+        if !resolved.is_empty() {
+            self.auto_resolve_terminal_parents_locked(resolved.iter().map(String::as_str))?;
+        }
+        Ok(report)
+    }
+
+    /// `root` and every knot below it through `parent_of` edges.
+    pub fn subtree_ids(&self, root: &str) -> Result<BTreeSet<String>, AppError> {
+        let root = self.resolve_knot_token(root)?;
+        db::get_knot_hot(&self.conn, &root)?.ok_or_else(|| AppError::NotFound(root.clone()))?;
+        let mut members = BTreeSet::from([root.clone()]);
+        let mut queue = VecDeque::from([root]);
+        while let Some(parent) = queue.pop_front() {
+            for edge in db::list_edges(&self.conn, &parent, EdgeDirection::Outgoing)? {
+                if edge.kind == "parent_of" && members.insert(edge.dst.clone()) {
+                    queue.push_back(edge.dst);
+                }
+            }
+        }
+        Ok(members)
+    }
+
+    /// Why `child` must not be advanced in a bulk pass, if anything.
+    fn blocked_reason(&self, child: &KnotCacheRecord) -> Result<Option<String>, AppError> {
+        if matches!(child.state.as_str(), "blocked" | "deferred") {
+            return Ok(Some(format!("knot is {}", child.state)));
+        }
+        if let Some(lease_id) = child.lease_id.as_deref() {
+            return Ok(Some(format!("bound to lease {lease_id}")));
+        }
+        let mut blockers = Vec::new();
+        for edge in db::list_edges(&self.conn, &child.id, EdgeDirection::Outgoing)? {
+            if edge.kind != "blocked_by" {
+                continue;
+            }
+            let open = match db::get_knot_hot(&self.conn, &edge.dst)? {
+                Some(blocker) => !state_hierarchy::is_terminal_state(&blocker.state)?,
+                None => false,
+            };
+            if open {
+                blockers.push(crate::knot_id::display_id(&edge.dst).to_string());
+            }
+        }
+        Ok((!blockers.is_empty()).then(|| format!("blocked by {}", blockers.join(", "))))
+    }
+
+    fn advance_child_locked(
+        &self,
+        child: &KnotCacheRecord,
+        state_actor: &StateActorMetadata,
+    ) -> Result<KnotCacheRecord, String> {
+        let profile = self
+            .resolve_profile_for_record(child)
+            .map_err(|err| err.to_string())?;
+        let next = workflow_runtime::next_happy_path_state(
+            &self.profile_registry,
+            &profile.id,
+            parse_knot_type(child.knot_type.as_deref()),
+            &child.state,
+        )
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("no next state from '{}'", child.state))?;
+        self.apply_state_transition_locked(child, &next, false, None, state_actor, false, false)
+            .map_err(|err| err.to_string())
+    }
+}
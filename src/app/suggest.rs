@@ -0,0 +1,179 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::db::{self, EdgeDirection};
+use crate::domain::knot_type::KnotType;
+use crate::domain::tag;
+use crate::repo_config::AgentConfig;
+use crate::state_hierarchy;
+
+use super::error::AppError;
+use super::helpers::normalize_tag;
+use super::types::KnotView;
+use super::App;
+
+const PRIORITY_WEIGHT: i64 = 10;
+const MAX_AGE_DAYS: i64 = 30;
+const UNBLOCK_WEIGHT: i64 = 15;
+const AFFINITY_WEIGHT: i64 = 20;
+
+/// Ranked claim suggestions for one agent, trimmed to its free capacity.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SuggestReport {
+    pub agent: String,
+    pub capacity: Option<usize>,
+    pub active: usize,
+    pub suggestions: Vec<Suggestion>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct Suggestion {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    pub title: String,
+    pub state: String,
+    pub score: i64,
+    pub reasons: Vec<String>,
+}
+
+impl App {
+    /// Scores `candidates` for `agent` by priority, age, how many open knots
+    /// they unblock, and tag affinity from the agent's `[agents.<name>]`
+    /// repo config. Returns at most `limit` suggestions, fewer when the agent
+    /// is close to its configured capacity.
+    pub fn suggest_for_agent(
+        &self,
+        agent: &str,
+        candidates: Vec<KnotView>,
+        limit: usize,
+    ) -> Result<SuggestReport, AppError> {
+        let config = self.agent_config(agent)?;
+        let active = self.active_claims(agent)?;
+        let free = config
+            .capacity
+            .map_or(limit, |capacity| capacity.saturating_sub(active).min(limit));
+        let affinity: Vec<String> = config.tags.iter().map(|raw| normalize_tag(raw)).collect();
+        let now = OffsetDateTime::now_utc();
+        let mut suggestions = Vec::with_capacity(candidates.len());
+        for knot in candidates {
+            let dependents = self.open_dependents(&knot.id)?;
+            let (score, reasons) = score_candidate(&knot, now, dependents, &affinity);
+            suggestions.push(Suggestion {
+                id: knot.id,
+                alias: knot.alias,
+                title: knot.title,
+                state: knot.state,
+                score,
+                reasons,
+            });
+        }
+        suggestions.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.id.cmp(&b.id)));
+        suggestions.truncate(free);
+        Ok(SuggestReport {
+            agent: agent.to_string(),
+            capacity: config.capacity,
+            active,
+            suggestions,
+        })
+    }
+
+    fn agent_config(&self, agent: &str) -> Result<AgentConfig, AppError> {
+        let agents = self.read_repo_config()?.unwrap_or_default().agents;
+        Ok(agents
+            .into_iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(agent))
+            .map(|(_, config)| config)
+            .unwrap_or_default())
+    }
+
+    /// Knots currently bound to a lease held by `agent`.
+    fn active_claims(&self, agent: &str) -> Result<usize, AppError> {
+        let knots = db::list_knot_hot(&self.conn)?;
+        let leases: HashSet<&str> = knots
+            .iter()
+            .filter(|record| record.knot_type.as_deref() == Some(KnotType::Lease.as_str()))
+            .filter(|record| {
+                record
+                    .lease_data
+                    .agent_info
+                    .as_ref()
+                    .is_some_and(|info| info.agent_name.eq_ignore_ascii_case(agent))
+            })
+            .map(|record| record.id.as_str())
+            .collect();
+        Ok(knots
+            .iter()
+            .filter(|record| {
+                record
+                    .lease_id
+                    .as_deref()
+                    .is_some_and(|lease| leases.contains(lease))
+            })
+            .count())
+    }
+
+    /// Open knots waiting on `id` through `blocked_by` or `blocks` edges.
+    fn open_dependents(&self, id: &str) -> Result<usize, AppError> {
+        let mut waiting = HashSet::new();
+        for edge in db::list_edges(&self.conn, id, EdgeDirection::Both)? {
+            let dependent = match edge.kind.as_str() {
+                "blocked_by" if edge.dst == id => edge.src,
+                "blocks" if edge.src == id => edge.dst,
+                _ => continue,
+            };
+            if let Some(record) = db::get_knot_hot(&self.conn, &dependent)? {
+                if !state_hierarchy::is_terminal_state(&record.state)? {
+                    waiting.insert(record.id);
+                }
+            }
+        }
+        Ok(waiting.len())
+    }
+}
+
+/// Score and human-readable reasons for one candidate. Lower priority
+/// numbers score higher; age counts whole days since creation, capped.
+pub(crate) fn score_candidate(
+    knot: &KnotView,
+    now: OffsetDateTime,
+    dependents: usize,
+    affinity: &[String],
+) -> (i64, Vec<String>) {
+    let mut score = 0;
+    let mut reasons = Vec::new();
+    if let Some(priority) = knot.priority {
+        score += (5 - priority.clamp(0, 4)) * PRIORITY_WEIGHT;
+        reasons.push(format!("priority {priority}"));
+    }
+    let created = knot.created_at.as_deref().unwrap_or(&knot.updated_at);
+    if let Ok(created) = OffsetDateTime::parse(created, &Rfc3339) {
+        let days = (now - created).whole_days().clamp(0, MAX_AGE_DAYS);
+        if days > 0 {
+            score += days;
+            reasons.push(format!("waiting {days} day(s)"));
+        }
+    }
+    if dependents > 0 {
+        score += UNBLOCK_WEIGHT * i64::try_from(dependents).unwrap_or(i64::MAX / UNBLOCK_WEIGHT);
+        reasons.push(format!("unblocks {dependents} knot(s)"));
+    }
+    let matched: Vec<&str> = knot
+        .tags
+        .iter()
+        .filter(|knot_tag| {
+            affinity
+                .iter()
+                .any(|filter| tag::matches_filter(knot_tag, filter))
+        })
+        .map(String::as_str)
+        .collect();
+    if !matched.is_empty() {
+        score += AFFINITY_WEIGHT * i64::try_from(matched.len()).unwrap_or_default();
+        reasons.push(format!("matches {}", matched.join(", ")));
+    }
+    (score, reasons)
+}
@@ -0,0 +1,162 @@
+use serde::Serialize;
+
+use super::error::AppError;
+use super::types::{KnotView, UpdateKnotPatch};
+use super::App;
+use crate::domain::metadata::{MetadataEntry, MetadataEntryInput};
+use crate::summarize::{self, HistoryEntry, HttpSummarizer, Summarizer};
+
+const ENDPOINT_KEY: &str = "summarize.endpoint";
+const MODEL_KEY: &str = "summarize.model";
+/// Agent name on capsules written by `kno summarize`, which also marks
+/// where the next summary picks up.
+pub const SUMMARY_AGENT: &str = "kno summarize";
+const EXTRACTIVE_MODEL: &str = "extractive";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KnotSummary {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// `extractive`, or the model that wrote the summary.
+    pub model: String,
+    pub entries: usize,
+    pub content: String,
+    pub written: bool,
+}
+
+impl App {
+    /// The endpoint from `summarize.endpoint`, or `None` when unset. Knot
+    /// history is sent there, so only a value set in this clone counts.
+    pub fn summarize_endpoint(&self) -> Result<Option<HttpSummarizer>, AppError> {
+        let endpoint = self.config_get(ENDPOINT_KEY)?;
+        if endpoint.value.is_empty() || endpoint.source != "local" {
+            return Ok(None);
+        }
+        Ok(Some(HttpSummarizer {
+            endpoint: endpoint.value,
+            model: self.config_get(MODEL_KEY)?.value,
+            api_key: std::env::var(summarize::API_KEY_ENV).ok(),
+        }))
+    }
+
+    /// Condenses a knot's notes and handoff capsules into a new capsule.
+    /// Work starts from the last summary, so repeated runs fold in only
+    /// what was added since. Without a `summarizer`, or when it fails or
+    /// the history is sensitive, an extractive summary is written instead.
+    pub fn summarize_knot(
+        &self,
+        id: &str,
+        summarizer: Option<&dyn Summarizer>,
+        dry_run: bool,
+    ) -> Result<KnotSummary, AppError> {
+        let knot = self
+            .show_knot(id)?
+            .ok_or_else(|| AppError::NotFound(id.to_string()))?;
+        let (entries, fresh) = history_since_last_summary(&knot);
+        if fresh == 0 {
+            return Err(AppError::InvalidArgument(format!(
+                "nothing to summarize: {} has no notes or handoff capsules since its last summary",
+                crate::knot_id::display_id(&knot.id)
+            )));
+        }
+        let (model, content) = match summarizer {
+            Some(summarizer) => self.llm_summary(summarizer, &knot, &entries)?,
+            None => None,
+        }
+        .unwrap_or_else(|| {
+            (
+                EXTRACTIVE_MODEL.to_string(),
+                summarize::extractive(&entries),
+            )
+        });
+        if !dry_run {
+            let patch = UpdateKnotPatch {
+                add_handoff_capsule: Some(MetadataEntryInput {
+                    content: content.clone(),
+                    agentname: Some(SUMMARY_AGENT.to_string()),
+                    model: Some(model.clone()),
+                    version: Some(env!("CARGO_PKG_VERSION").to_string()),
+                    ..MetadataEntryInput::default()
+                }),
+                ..UpdateKnotPatch::default()
+            };
+            self.update_knot(&knot.id, patch)?;
+        }
+        Ok(KnotSummary {
+            id: knot.id,
+            alias: knot.alias,
+            model,
+            entries: entries.len(),
+            content,
+            written: !dry_run,
+        })
+    }
+
+    /// `None` when the history must stay local or the endpoint fails, in
+    /// which case the caller falls back to an extractive summary.
+    fn llm_summary(
+        &self,
+        summarizer: &dyn Summarizer,
+        knot: &KnotView,
+        entries: &[HistoryEntry],
+    ) -> Result<Option<(String, String)>, AppError> {
+        let hidden = self.sensitive_fields()?;
+        let shown = |field: &str| !hidden.iter().any(|hidden| hidden == field);
+        if !shown("notes") || !shown("handoff_capsules") {
+            eprintln!(
+                "warning: notes or handoff capsules are sensitive here; \
+                 summarizing locally instead of sending them to {ENDPOINT_KEY}"
+            );
+            return Ok(None);
+        }
+        let title = shown("title").then_some(knot.title.as_str());
+        let (system, prompt) = summarize::llm_prompt(title, entries);
+        match summarizer.summarize(system, &prompt) {
+            Ok(content) => {
+                let model = match summarizer.model() {
+                    "" => "unknown".to_string(),
+                    model => model.to_string(),
+                };
+                Ok(Some((model, content)))
+            }
+            Err(err) => {
+                eprintln!("warning: {err}; falling back to an extractive summary");
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Notes and capsules oldest first, starting at the most recent capsule
+/// `kno summarize` wrote, and how many entries came after that summary.
+fn history_since_last_summary(knot: &KnotView) -> (Vec<HistoryEntry>, usize) {
+    let mut entries: Vec<(&'static str, &MetadataEntry)> = knot
+        .notes
+        .iter()
+        .map(|note| ("note", note))
+        .chain(
+            knot.handoff_capsules
+                .iter()
+                .map(|capsule| ("handoff", capsule)),
+        )
+        .collect();
+    entries.sort_by(|a, b| {
+        (a.1.datetime.as_str(), a.1.entry_id.as_str())
+            .cmp(&(b.1.datetime.as_str(), b.1.entry_id.as_str()))
+    });
+    let last_summary = entries
+        .iter()
+        .rposition(|(kind, entry)| *kind == "handoff" && entry.agentname == SUMMARY_AGENT);
+    let start = last_summary.unwrap_or(0);
+    let history: Vec<HistoryEntry> = entries[start..]
+        .iter()
+        .map(|(kind, entry)| HistoryEntry {
+            kind,
+            datetime: entry.datetime.clone(),
+            content: entry.content.clone(),
+        })
+        .collect();
+    let fresh = history.len() - usize::from(last_summary.is_some());
+    (history, fresh)
+}
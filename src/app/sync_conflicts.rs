@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::db::{self, SyncSkippedRecord};
+use crate::locks::FileLock;
+use crate::sync::{known_workflow_ids, GitAdapter, IncrementalApplier};
+
+use super::error::AppError;
+use super::App;
+
+/// An incoming event that sync skipped because its knot had already moved
+/// past the event's precondition.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SyncConflict {
+    pub id: i64,
+    pub knot_id: String,
+    pub event_type: String,
+    pub event_id: String,
+    pub path: String,
+    pub reason: String,
+    pub expected_etag: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_etag: Option<String>,
+    pub recorded_at: String,
+}
+
+impl From<SyncSkippedRecord> for SyncConflict {
+    fn from(record: SyncSkippedRecord) -> Self {
+        Self {
+            id: record.id,
+            knot_id: record.knot_id,
+            event_type: record.event_type,
+            event_id: record.event_id,
+            path: record.path,
+            reason: record.reason,
+            expected_etag: record.expected_etag,
+            actual_etag: record.actual_etag,
+            recorded_at: record.recorded_at,
+        }
+    }
+}
+
+impl App {
+    /// Skipped events awaiting review, oldest first.
+    pub fn sync_conflicts(&self, knot: Option<&str>) -> Result<Vec<SyncConflict>, AppError> {
+        let knot_id = knot
+            .map(|token| self.resolve_knot_token(token))
+            .transpose()?;
+        Ok(db::list_sync_skipped(&self.conn, knot_id.as_deref())?
+            .into_iter()
+            .map(SyncConflict::from)
+            .collect())
+    }
+
+    /// Applies a skipped event to the local cache over the newer state it
+    /// lost to. Only the cache changes; no new event is written.
+    pub fn reapply_sync_conflict(&self, id: i64, force: bool) -> Result<SyncConflict, AppError> {
+        if !force {
+            return Err(AppError::InvalidArgument(
+                "re-applying a skipped event overwrites newer knot state; \
+                 rerun with --force"
+                    .to_string(),
+            ));
+        }
+        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        let _cache_guard =
+            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        let record = self.require_sync_conflict(id)?;
+        let mut applier = IncrementalApplier::new(
+            &self.conn,
+            self.store_paths.worktree_path(),
+            GitAdapter::new(),
+            known_workflow_ids(&self.repo_root),
+        )
+        .with_event_types(crate::repo_config::event_types(&self.store_paths.root));
+        applier.reapply_skipped(&record)?;
+        db::delete_sync_skipped(&self.conn, id)?;
+        Ok(record.into())
+    }
+
+    /// Drops a skipped event from review without applying it.
+    pub fn dismiss_sync_conflict(&self, id: i64) -> Result<SyncConflict, AppError> {
+        let _cache_guard =
+            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        let record = self.require_sync_conflict(id)?;
+        db::delete_sync_skipped(&self.conn, id)?;
+        Ok(record.into())
+    }
+
+    fn require_sync_conflict(&self, id: i64) -> Result<SyncSkippedRecord, AppError> {
+        db::get_sync_skipped(&self.conn, id)?
+            .ok_or_else(|| AppError::InvalidArgument(format!("no sync conflict with id {id}")))
+    }
+}
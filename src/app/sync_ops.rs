@@ -1,11 +1,12 @@
 use std::time::Duration;
 
-use crate::doctor::{run_doctor_with_fix_at, DoctorReport};
+use crate::doctor::{
+    run_doctor_with_confirm_at, run_doctor_with_fix_at, DoctorCheck, DoctorReport,
+};
 use crate::fsck::{run_fsck_at_store, FsckReport};
 use crate::locks::FileLock;
-use crate::perf::{run_perf_harness, PerfReport};
 use crate::progress::ProgressReporter;
-use crate::remote_init::init_remote_knots_branch;
+use crate::remote_init::init_remote_branch;
 use crate::replication::{PushSummary, ReplicationService, ReplicationSummary, SyncOutcome};
 use crate::snapshots::{write_snapshots_at_store, SnapshotWriteSummary};
 use crate::sync::SyncSummary;
@@ -25,10 +26,26 @@ impl App {
     ) -> Result<SyncSummary, AppError> {
         self.require_git_distribution("pull")?;
         let mut reporter = reporter;
-        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
-        let _cache_guard =
-            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
-        self.pull_unlocked_with_progress(&mut reporter)
+        let summary = {
+            let _repo_guard =
+                FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+            let _cache_guard =
+                FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+            self.pull_unlocked_with_progress(&mut reporter)?
+        };
+        self.wake_snoozed_knots_or_warn();
+        Ok(summary)
+    }
+
+    /// `pull_with_progress` that also fills `details` on the summary.
+    pub fn pull_with_details(
+        &self,
+        reporter: Option<&mut dyn ProgressReporter>,
+    ) -> Result<SyncSummary, AppError> {
+        let (summary, details) = crate::sync::record_details(|| self.pull_with_progress(reporter));
+        let mut summary = summary?;
+        summary.details = Some(details);
+        Ok(summary)
     }
 
     pub fn pull_drift_warning(&self) -> Result<Option<PullDriftWarning>, AppError> {
@@ -54,6 +71,7 @@ impl App {
     pub fn push(&self) -> Result<PushSummary, AppError> {
         self.require_git_distribution("push")?;
         let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        self.link_commit_trailers_or_warn();
         let service = ReplicationService::with_store_paths(
             &self.conn,
             self.repo_root.clone(),
@@ -69,6 +87,7 @@ impl App {
         self.require_git_distribution("push")?;
         let mut reporter = reporter;
         let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        self.link_commit_trailers_or_warn();
         let service = ReplicationService::with_store_paths(
             &self.conn,
             self.repo_root.clone(),
@@ -77,17 +96,29 @@ impl App {
         Ok(service.push_with_progress(&mut reporter)?)
     }
 
+    /// A failed trailer scan must not hold up the push itself.
+    fn link_commit_trailers_or_warn(&self) {
+        if let Err(err) = self.link_commit_trailers() {
+            eprintln!("warning: could not link commits from KNO: trailers: {err}");
+        }
+    }
+
     pub fn sync(&self) -> Result<ReplicationSummary, AppError> {
         self.require_git_distribution("sync")?;
-        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
-        let _cache_guard =
-            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
-        let service = ReplicationService::with_store_paths(
-            &self.conn,
-            self.repo_root.clone(),
-            self.store_paths.clone(),
-        );
-        Ok(service.sync()?)
+        let summary = {
+            let _repo_guard =
+                FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+            let _cache_guard =
+                FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+            let service = ReplicationService::with_store_paths(
+                &self.conn,
+                self.repo_root.clone(),
+                self.store_paths.clone(),
+            );
+            service.sync()?
+        };
+        self.wake_snoozed_knots_or_warn();
+        Ok(summary)
     }
 
     #[allow(dead_code)]
@@ -114,17 +145,24 @@ impl App {
     ) -> Result<SyncOutcome, AppError> {
         self.require_git_distribution("sync")?;
         let mut reporter = reporter;
-        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
-        let _cache_guard =
-            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
-        let service = ReplicationService::with_store_paths(
-            &self.conn,
-            self.repo_root.clone(),
-            self.store_paths.clone(),
-        );
-        let outcome = service.sync_or_defer_with_progress(&mut reporter)?;
-        if matches!(outcome, SyncOutcome::Deferred { .. }) {
-            self.mark_sync_pending()?;
+        let outcome = {
+            let _repo_guard =
+                FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+            let _cache_guard =
+                FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+            let service = ReplicationService::with_store_paths(
+                &self.conn,
+                self.repo_root.clone(),
+                self.store_paths.clone(),
+            );
+            let outcome = service.sync_or_defer_with_progress(&mut reporter)?;
+            if matches!(outcome, SyncOutcome::Deferred { .. }) {
+                self.mark_sync_pending()?;
+            }
+            outcome
+        };
+        if matches!(outcome, SyncOutcome::Completed(_)) {
+            self.wake_snoozed_knots_or_warn();
         }
         Ok(outcome)
     }
@@ -133,7 +171,8 @@ impl App {
         self.require_git_distribution("init-remote")?;
         let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
         crate::init::ensure_knots_gitignore(&self.repo_root)?;
-        init_remote_knots_branch(&self.repo_root)?;
+        let (remote, branch) = self.knots_ref();
+        init_remote_branch(&self.repo_root, &remote, &branch)?;
         Ok(())
     }
 
@@ -150,6 +189,19 @@ impl App {
         )?)
     }
 
+    /// `doctor(true)`, but only applying the fixes `confirm` accepts.
+    pub fn doctor_with_confirm(
+        &self,
+        confirm: impl FnMut(&DoctorCheck, &str) -> bool,
+    ) -> Result<DoctorReport, AppError> {
+        Ok(run_doctor_with_confirm_at(
+            &self.repo_root,
+            &self.store_paths.root,
+            self.distribution,
+            confirm,
+        )?)
+    }
+
     pub fn compact_write_snapshots(&self) -> Result<SnapshotWriteSummary, AppError> {
         let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
         let _cache_guard =
@@ -159,9 +211,4 @@ impl App {
             &self.store_paths.root,
         )?)
     }
-
-    pub fn perf_harness(&self, iterations: u32) -> Result<PerfReport, AppError> {
-        let _ = self;
-        Ok(run_perf_harness(iterations)?)
-    }
 }
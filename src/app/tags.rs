@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::domain::tag;
+
+use super::error::AppError;
+use super::helpers::normalize_tag;
+use super::App;
+
+/// Tags in use under one namespace; `namespace` is `None` for plain tags.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct TagGroup {
+    pub namespace: Option<String>,
+    pub tags: Vec<TagCount>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct TagCount {
+    pub tag: String,
+    pub knots: usize,
+}
+
+impl App {
+    /// Every tag on a hot knot with its knot count, grouped by namespace.
+    /// Plain tags come first, then namespaces in name order.
+    pub fn tag_groups(&self) -> Result<Vec<TagGroup>, AppError> {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for knot in self.list_knots()? {
+            for tag in knot.tags {
+                *counts.entry(tag).or_default() += 1;
+            }
+        }
+        let mut groups: BTreeMap<Option<String>, Vec<TagCount>> = BTreeMap::new();
+        for (name, knots) in counts {
+            groups
+                .entry(tag::namespace(&name).map(ToString::to_string))
+                .or_default()
+                .push(TagCount { tag: name, knots });
+        }
+        Ok(groups
+            .into_iter()
+            .map(|(namespace, tags)| TagGroup { namespace, tags })
+            .collect())
+    }
+
+    /// Rejects namespaced tags outside the repo config's `tag_namespaces`.
+    pub(crate) fn validate_tag_namespaces(&self, tags: &[String]) -> Result<(), AppError> {
+        if tags.is_empty() {
+            return Ok(());
+        }
+        let allowed = self.read_repo_config()?.unwrap_or_default().tag_namespaces;
+        if allowed.is_empty() {
+            return Ok(());
+        }
+        match tags
+            .iter()
+            .map(|raw| normalize_tag(raw))
+            .find(|tag| !tag::namespace_allowed(tag, &allowed))
+        {
+            Some(tag) => Err(AppError::InvalidArgument(format!(
+                "tag '{tag}' uses an undeclared namespace; allowed: {}",
+                allowed.join(", ")
+            ))),
+            None => Ok(()),
+        }
+    }
+}
@@ -230,6 +230,8 @@ fn stripped_ids_resolve_for_show_state_update_and_edges() {
                 title: Some("Source updated".to_string()),
                 description: None,
                 acceptance: None,
+                estimate: None,
+                custom_fields: Vec::new(),
                 priority: None,
                 status: None,
                 knot_type: None,
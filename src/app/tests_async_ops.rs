@@ -0,0 +1,89 @@
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use super::async_ops::spawn_blocking;
+use super::AppPool;
+use crate::project::{DistributionMode, ProjectContext, StorePaths};
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Minimal executor: parks the test thread until the future wakes it.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(value) => return value,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+fn open_pool(root: &std::path::Path) -> AppPool {
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let context = ProjectContext {
+        project_id: None,
+        store_paths: StorePaths {
+            root: root.join(".knots"),
+        },
+        repo_root: root.to_path_buf(),
+        distribution: DistributionMode::Git,
+    };
+    AppPool::open(&context, db_path.to_str().expect("utf8 db path"), 2).expect("pool should open")
+}
+
+#[test]
+fn spawn_blocking_resolves_with_the_operation_result() {
+    assert_eq!(block_on(spawn_blocking(|| 6 * 7)), 42);
+}
+
+#[test]
+fn spawn_blocking_resumes_a_panic_when_polled() {
+    let outcome = std::panic::catch_unwind(|| {
+        block_on(spawn_blocking(|| -> u32 { panic!("worker failed") }))
+    });
+    let payload = outcome.expect_err("panic should propagate");
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"worker failed"));
+}
+
+#[test]
+fn async_replication_round_trips_through_the_remote() {
+    let root = crate::perf::setup_workspace().expect("workspace should set up");
+    let pool = open_pool(&root);
+    let app = pool.get().expect("app should check out");
+    app.init_remote().expect("remote branch should initialize");
+    app.create_knot(
+        "Async push",
+        None,
+        Some("ready_for_planning"),
+        Some("default"),
+    )
+    .expect("knot should be created");
+    drop(app);
+
+    let pushed = block_on(pool.push_async()).expect("push should succeed");
+    assert!(pushed.pushed);
+    block_on(pool.pull_async()).expect("pull should succeed");
+    block_on(pool.sync_async()).expect("sync should succeed");
+    let _ = std::fs::remove_dir_all(root.parent().expect("workspace parent"));
+}
+
+#[test]
+fn async_replication_reports_errors_from_the_worker() {
+    let root = std::env::temp_dir().join(format!("knots-async-{}", uuid::Uuid::now_v7()));
+    let pool = open_pool(&root);
+    assert!(block_on(pool.pull_async()).is_err());
+    let _ = std::fs::remove_dir_all(root);
+}
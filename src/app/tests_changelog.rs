@@ -0,0 +1,98 @@
+use std::path::Path;
+use std::process::Command;
+
+use super::{App, UpdateKnotPatch};
+
+fn open_app() -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-changelog-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn run_git(root: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .expect("git command should run");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn finish(app: &App, title: &str, tag: Option<&str>, state: &str) -> String {
+    let knot = app.create_knot(title, None, None, None).expect("create");
+    if let Some(tag) = tag {
+        app.update_knot(
+            &knot.id,
+            UpdateKnotPatch {
+                add_tags: vec![tag.to_string()],
+                ..UpdateKnotPatch::default()
+            },
+        )
+        .expect("tag");
+    }
+    app.set_state(&knot.id, state, true, None).expect("finish");
+    knot.id
+}
+
+#[test]
+fn changelog_groups_finished_knots_by_kind_tag() {
+    let (root, app) = open_app();
+    let bug = finish(&app, "Fix crash", Some("type/bug"), "shipped");
+    let feature = finish(&app, "Add login", Some("feature"), "shipped");
+    let plain = finish(&app, "Tidy", None, "shipped");
+    let dropped = finish(&app, "Dropped", Some("bug"), "abandoned");
+    let open = app
+        .create_knot("Still open", None, None, None)
+        .expect("open");
+    app.set_state(&open.id, "planning", false, None)
+        .expect("plan");
+
+    let changelog = app.changelog("today", false).expect("changelog");
+    let kinds: Vec<_> = changelog
+        .sections
+        .iter()
+        .map(|section| section.kind.as_str())
+        .collect();
+    assert_eq!(kinds, ["feature", "bug", "other"]);
+    assert_eq!(changelog.sections[0].entries[0].id, feature);
+    assert_eq!(changelog.sections[1].entries.len(), 1);
+    assert_eq!(changelog.sections[1].entries[0].id, bug);
+    assert_eq!(changelog.sections[2].entries[0].id, plain);
+
+    let with_abandoned = app.changelog("today", true).expect("changelog");
+    let bugs: Vec<_> = with_abandoned.sections[1]
+        .entries
+        .iter()
+        .map(|entry| entry.id.as_str())
+        .collect();
+    assert_eq!(bugs, [bug.as_str(), dropped.as_str()]);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn changelog_since_accepts_git_tags() {
+    let (root, app) = open_app();
+    run_git(&root, &["init"]);
+    run_git(&root, &["config", "user.email", "knots@example.com"]);
+    run_git(&root, &["config", "user.name", "Knots Test"]);
+    run_git(&root, &["commit", "--allow-empty", "-m", "release"]);
+    run_git(&root, &["tag", "v1.0.0"]);
+    let shipped = finish(&app, "After release", None, "shipped");
+
+    let changelog = app.changelog("v1.0.0", false).expect("changelog");
+    assert_eq!(changelog.since, "v1.0.0");
+    assert_eq!(changelog.sections[0].entries[0].id, shipped);
+    let err = app.changelog("no-such-tag", false).expect_err("unknown");
+    assert!(err.to_string().contains("no-such-tag"), "{err}");
+    let _ = std::fs::remove_dir_all(root);
+}
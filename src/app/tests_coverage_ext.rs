@@ -104,6 +104,8 @@ pub(super) fn default_update_patch() -> UpdateKnotPatch {
         title: None,
         description: None,
         acceptance: None,
+        estimate: None,
+        custom_fields: Vec::new(),
         priority: None,
         status: None,
         knot_type: None,
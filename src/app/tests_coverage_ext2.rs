@@ -82,6 +82,8 @@ fn update_knot_state_change_writes_actor_metadata() {
                 title: None,
                 description: None,
                 acceptance: None,
+                estimate: None,
+                custom_fields: Vec::new(),
                 priority: None,
                 status: Some("planning".to_string()),
                 knot_type: None,
@@ -338,6 +340,8 @@ fn evaluate_gate_failure_reopens_linked_knots_and_adds_metadata() {
                 title: None,
                 description: None,
                 acceptance: None,
+                estimate: None,
+                custom_fields: Vec::new(),
                 priority: None,
                 status: None,
                 knot_type: None,
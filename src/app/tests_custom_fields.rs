@@ -0,0 +1,77 @@
+use super::{App, AppError, CreateKnotOptions, UpdateKnotPatch};
+
+fn open_app() -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-fields-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        r#"
+[fields.customer]
+type = "string"
+
+[fields.severity]
+type = "enum"
+values = ["low", "high"]
+
+[fields.points]
+type = "integer"
+"#,
+    )
+    .expect("repo config should be writable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn set_fields(app: &App, id: &str, fields: &[&str]) -> Result<super::KnotView, AppError> {
+    app.update_knot(
+        id,
+        UpdateKnotPatch {
+            custom_fields: fields.iter().map(|field| field.to_string()).collect(),
+            ..UpdateKnotPatch::default()
+        },
+    )
+}
+
+#[test]
+fn custom_fields_are_validated_and_replayed() {
+    let (root, app) = open_app();
+    let knot = app
+        .create_knot_with_options(
+            "Outage",
+            None,
+            Some("ready_for_planning"),
+            None,
+            None,
+            CreateKnotOptions {
+                custom_fields: vec!["customer=Acme".to_string(), "severity=HIGH".to_string()],
+                ..CreateKnotOptions::default()
+            },
+        )
+        .expect("create knot");
+    assert_eq!(knot.custom_fields["customer"], "Acme");
+    assert_eq!(knot.custom_fields["severity"], "high");
+
+    let updated = set_fields(&app, &knot.id, &["points=3", "customer="]).expect("update fields");
+    assert_eq!(
+        updated.custom_fields.get("points").map(String::as_str),
+        Some("3")
+    );
+    assert!(!updated.custom_fields.contains_key("customer"));
+
+    for invalid in ["severity=urgent", "points=many", "owner=me", "customer"] {
+        assert!(
+            matches!(
+                set_fields(&app, &knot.id, &[invalid]),
+                Err(AppError::InvalidArgument(_))
+            ),
+            "{invalid}"
+        );
+    }
+
+    let rehydrated = app.rehydrate(&knot.id).expect("rehydrate").expect("knot");
+    assert_eq!(rehydrated.custom_fields, updated.custom_fields);
+    let _ = std::fs::remove_dir_all(root);
+}
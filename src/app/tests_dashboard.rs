@@ -0,0 +1,150 @@
+use std::path::PathBuf;
+
+use super::{App, UpdateKnotPatch};
+use crate::domain::metadata::MetadataEntryInput;
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-dashboard-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+#[test]
+fn dashboard_knot_has_activity_edges_and_hides_sensitive_fields() {
+    let (root, app) = open_app();
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "sensitive_fields = [\"body\"]\n",
+    )
+    .expect("repo config");
+    let parent = app
+        .create_knot("Parent", Some("private body"), None, None)
+        .expect("create");
+    let child = app.create_knot("Child", None, None, None).expect("create");
+    app.add_edge(&parent.id, "parent_of", &child.id)
+        .expect("edge");
+    app.update_knot(
+        &parent.id,
+        UpdateKnotPatch {
+            add_note: Some(MetadataEntryInput {
+                content: "Scoped it".to_string(),
+                username: Some("alice".to_string()),
+                datetime: Some("2030-01-01T00:00:00Z".to_string()),
+                ..MetadataEntryInput::default()
+            }),
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("note");
+
+    let detail = app
+        .dashboard_knot(&parent.id)
+        .expect("detail")
+        .expect("knot exists");
+    assert_eq!(detail["knot"]["title"], "Parent");
+    assert!(detail["knot"].get("body").is_none());
+    assert_eq!(detail["activity"][0]["kind"], "note");
+    assert_eq!(detail["activity"][0]["actor"], "alice");
+    assert_eq!(detail["activity"][0]["summary"], "Scoped it");
+    assert_eq!(detail["edges"][0]["dst"], child.id.as_str());
+    assert!(!matches!(app.dashboard_knot("missing-knot"), Ok(Some(_))));
+
+    let board = app.dashboard_board().expect("board");
+    assert_eq!(board["knots"].as_array().expect("knots").len(), 2);
+    assert!(board["knots"][0].get("body").is_none());
+    let graph = app.dashboard_graph().expect("graph");
+    assert_eq!(graph["nodes"].as_array().expect("nodes").len(), 2);
+    assert_eq!(graph["edges"][0]["kind"], "parent_of");
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn dashboard_activity_leaves_out_sensitive_notes_handoffs_and_steps() {
+    let (root, app) = open_app();
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "sensitive_fields = [\"notes\", \"handoff_capsules\", \"step_history\"]\n",
+    )
+    .expect("repo config");
+    let knot = app.create_knot("Quiet", None, None, None).expect("create");
+    let entry = |content: &str| MetadataEntryInput {
+        content: content.to_string(),
+        ..MetadataEntryInput::default()
+    };
+    app.update_knot(
+        &knot.id,
+        UpdateKnotPatch {
+            add_note: Some(entry("SECRET-NOTE-CONTENT")),
+            add_handoff_capsule: Some(entry("SECRET-HANDOFF-CONTENT")),
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("note");
+
+    let detail = app
+        .dashboard_knot(&knot.id)
+        .expect("detail")
+        .expect("knot exists");
+    let payload = detail.to_string();
+    assert!(!payload.contains("SECRET-NOTE-CONTENT"), "{payload}");
+    assert!(!payload.contains("SECRET-HANDOFF-CONTENT"), "{payload}");
+    assert_eq!(detail["activity"], serde_json::json!([]));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn dashboard_snapshot_keeps_only_edges_between_exported_knots() {
+    let (root, app) = open_app();
+    let parent = app.create_knot("Parent", None, None, None).expect("create");
+    let child = app
+        .create_knot("Child </script>", None, None, None)
+        .expect("create");
+    let outside = app
+        .create_knot("Outside", None, None, None)
+        .expect("create");
+    app.add_edge(&parent.id, "parent_of", &child.id)
+        .expect("edge");
+    app.add_edge(&child.id, "blocked_by", &outside.id)
+        .expect("edge");
+
+    let knots: Vec<_> = app
+        .list_knots()
+        .expect("list")
+        .into_iter()
+        .filter(|knot| knot.id != outside.id)
+        .collect();
+    let snapshot = app.dashboard_snapshot(&knots).expect("snapshot");
+    let routes = &snapshot["routes"];
+    assert_eq!(
+        routes["/api/board"]["knots"]
+            .as_array()
+            .expect("knots")
+            .len(),
+        2
+    );
+    assert_eq!(
+        routes["/api/graph"]["edges"]
+            .as_array()
+            .expect("edges")
+            .len(),
+        1
+    );
+    let child_page = &routes[format!("/api/knots/{}", child.id).as_str()];
+    assert_eq!(child_page["knot"]["title"], "Child </script>");
+    assert_eq!(child_page["edges"].as_array().expect("edges").len(), 1);
+    assert!(routes.get(format!("/api/knots/{}", outside.id)).is_none());
+    assert!(snapshot["generated_at"].is_string());
+
+    let html = crate::dashboard::snapshot_html(&app, &knots).expect("html");
+    assert!(html.contains("window.KNOTS_SNAPSHOT = {"));
+    assert_eq!(
+        html.matches("</script>").count(),
+        2,
+        "titles cannot close the tag"
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
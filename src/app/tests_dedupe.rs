@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use super::{App, UpdateKnotPatch};
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-dedupe-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+#[test]
+fn similar_knots_rank_open_lookalikes_first() {
+    let (root, app) = open_app();
+    let exact = app
+        .create_knot("Fix login timeout", None, None, None)
+        .expect("create");
+    let close = app
+        .create_knot("Fix the login timeouts", None, None, None)
+        .expect("create");
+    app.create_knot("Render release notes", None, None, None)
+        .expect("create");
+
+    let similar = app
+        .similar_knots("fix login timeout", None, 0.6)
+        .expect("similar");
+    let ids: Vec<&str> = similar.iter().map(|entry| entry.knot.id.as_str()).collect();
+    assert_eq!(ids, vec![exact.id.as_str(), close.id.as_str()]);
+    assert_eq!(similar[0].score, 1.0);
+
+    app.update_knot(
+        &exact.id,
+        UpdateKnotPatch {
+            status: Some("abandoned".to_string()),
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("abandon");
+    let similar = app
+        .similar_knots("fix login timeout", None, 0.6)
+        .expect("similar");
+    assert_eq!(similar.len(), 1);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn duplicate_pairs_skip_terminal_knots_unless_asked() {
+    let (root, app) = open_app();
+    let first = app
+        .create_knot("Cache warmup on boot", Some("preload hot rows"), None, None)
+        .expect("create");
+    let second = app
+        .create_knot(
+            "Cache warm-up on boot",
+            Some("preload the hot rows"),
+            None,
+            None,
+        )
+        .expect("create");
+    app.create_knot("Rename the binary", None, None, None)
+        .expect("create");
+
+    let pairs = app.duplicate_pairs(0.6, false).expect("pairs");
+    assert_eq!(pairs.len(), 1);
+    let mut ids = [pairs[0].left.id.clone(), pairs[0].right.id.clone()];
+    ids.sort();
+    let mut expected = [first.id.clone(), second.id.clone()];
+    expected.sort();
+    assert_eq!(ids, expected);
+
+    app.update_knot(
+        &second.id,
+        UpdateKnotPatch {
+            status: Some("abandoned".to_string()),
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("abandon");
+    assert!(app.duplicate_pairs(0.6, false).expect("pairs").is_empty());
+    assert_eq!(app.duplicate_pairs(0.6, true).expect("pairs").len(), 1);
+    let _ = std::fs::remove_dir_all(root);
+}
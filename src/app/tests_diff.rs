@@ -0,0 +1,120 @@
+use super::diff::{parse_since, parse_span};
+use super::{App, AppError, UpdateKnotPatch};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+fn open_app() -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-diff-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn at(raw: &str) -> OffsetDateTime {
+    OffsetDateTime::parse(raw, &Rfc3339).expect("valid timestamp")
+}
+
+#[test]
+fn parse_since_accepts_spans_and_timestamps() {
+    let now = at("2025-06-10T00:00:00Z");
+    assert_eq!(
+        parse_since("7d", now).expect("7d"),
+        at("2025-06-03T00:00:00Z")
+    );
+    assert_eq!(
+        parse_since("2w", now).expect("2w"),
+        at("2025-05-27T00:00:00Z")
+    );
+    assert_eq!(
+        parse_since("12h", now).expect("12h"),
+        at("2025-06-09T12:00:00Z")
+    );
+    assert_eq!(
+        parse_since("2025-06-01T00:00:00Z", now).expect("timestamp"),
+        at("2025-06-01T00:00:00Z")
+    );
+    assert!(matches!(
+        parse_since("soon", now),
+        Err(AppError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        parse_since("-3d", now),
+        Err(AppError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        parse_since("9223372036854775807w", now),
+        Err(AppError::InvalidArgument(_))
+    ));
+}
+
+#[test]
+fn parse_span_rejects_non_ascii_units_without_panicking() {
+    for raw in ["5é", "é", "5日", "🙂", "", "d", "5 d"] {
+        assert_eq!(parse_span(raw), None, "{raw}");
+    }
+    assert!(matches!(
+        parse_since("5é", at("2025-06-10T00:00:00Z")),
+        Err(AppError::InvalidArgument(_))
+    ));
+    assert_eq!(parse_span(" 30m "), Some(time::Duration::minutes(30)));
+}
+
+#[test]
+fn diff_since_reports_changes_inside_window() {
+    let (root, app) = open_app();
+    let knot = app
+        .create_knot("Original", None, Some("ready_for_planning"), None)
+        .expect("create knot");
+    app.update_knot(
+        &knot.id,
+        UpdateKnotPatch {
+            title: Some("Renamed".to_string()),
+            add_tags: vec!["release".to_string()],
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("update knot");
+
+    let diff = app.diff_since(&knot.id, "1d").expect("diff");
+    let fields: Vec<_> = diff.changes.iter().map(|c| c.field).collect();
+    assert!(fields.contains(&"title"), "{fields:?}");
+    assert!(fields.contains(&"tags"), "{fields:?}");
+    let title = diff
+        .changes
+        .iter()
+        .find(|c| c.field == "title")
+        .expect("title");
+    assert_eq!(title.before, None);
+    assert_eq!(title.after.as_deref(), Some("Renamed"));
+
+    let none = app
+        .diff_since(&knot.id, "2100-01-01T00:00:00Z")
+        .expect("future cutoff");
+    assert!(none.changes.is_empty(), "{:?}", none.changes);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn diff_knots_compares_current_projections() {
+    let (root, app) = open_app();
+    let a = app
+        .create_knot("Same title", None, Some("ready_for_planning"), None)
+        .expect("create a");
+    let b = app
+        .create_knot("Same title", None, Some("planning"), None)
+        .expect("create b");
+
+    let diff = app.diff_knots(&a.id, &b.id).expect("diff");
+    assert_eq!(diff.left, a.id);
+    assert_eq!(diff.right, b.id);
+    assert_eq!(diff.changes.len(), 1);
+    assert_eq!(diff.changes[0].field, "state");
+    assert!(matches!(
+        app.diff_knots(&a.id, "missing"),
+        Err(AppError::NotFound(_))
+    ));
+    let _ = std::fs::remove_dir_all(root);
+}
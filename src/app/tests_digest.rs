@@ -0,0 +1,90 @@
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use super::digest::parse_window_start;
+use super::{App, DigestGrouping, StateActorMetadata};
+
+fn open_app() -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-digest-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn at(raw: &str) -> OffsetDateTime {
+    OffsetDateTime::parse(raw, &Rfc3339).expect("timestamp")
+}
+
+fn agent(name: &str) -> StateActorMetadata {
+    StateActorMetadata {
+        actor_kind: Some("agent".to_string()),
+        agent_name: Some(name.to_string()),
+        ..StateActorMetadata::default()
+    }
+}
+
+#[test]
+fn digest_buckets_activity_and_groups_by_actor() {
+    let (root, app) = open_app();
+    let shipped = app
+        .create_knot("Ship it", None, None, None)
+        .expect("create");
+    let stuck = app.create_knot("Stuck", None, None, None).expect("create");
+    let moving = app.create_knot("Moving", None, None, None).expect("create");
+    app.set_state_with_actor(&shipped.id, "abandoned", false, None, agent("codex"))
+        .expect("abandon");
+    app.set_state_with_actor(&stuck.id, "blocked", false, None, agent("claude"))
+        .expect("block");
+    app.set_state_with_actor(&moving.id, "planning", false, None, agent("codex"))
+        .expect("plan");
+
+    let digest = app.digest("today", DigestGrouping::None).expect("digest");
+    assert_eq!(digest.groups.len(), 1);
+    let all = &digest.groups[0];
+    assert_eq!(all.actor, None);
+    assert_eq!(all.created.len(), 3);
+    assert_eq!(all.completed[0].title, "Ship it");
+    assert_eq!(all.completed[0].to.as_deref(), Some("abandoned"));
+    assert_eq!(all.blocked[0].id, stuck.id);
+    assert_eq!(
+        all.state_changes[0].from.as_deref(),
+        Some("ready_for_planning")
+    );
+
+    let digest = app.digest("1h", DigestGrouping::Actor).expect("digest");
+    let actors: Vec<_> = digest
+        .groups
+        .iter()
+        .map(|group| group.actor.as_deref().unwrap_or_default())
+        .collect();
+    assert_eq!(actors, ["claude", "codex", "unattributed"]);
+    assert_eq!(digest.groups[1].completed.len(), 1);
+    assert_eq!(digest.groups[1].state_changes.len(), 1);
+    assert_eq!(digest.groups[2].created.len(), 3);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn window_start_accepts_named_days_dates_and_spans() {
+    let now = at("2026-03-10T15:30:00Z");
+    assert_eq!(
+        parse_window_start("yesterday", now).expect("yesterday"),
+        at("2026-03-09T00:00:00Z")
+    );
+    assert_eq!(
+        parse_window_start("Today", now).expect("today"),
+        at("2026-03-10T00:00:00Z")
+    );
+    assert_eq!(
+        parse_window_start("2026-03-01", now).expect("date"),
+        at("2026-03-01T00:00:00Z")
+    );
+    assert_eq!(
+        parse_window_start("2d", now).expect("span"),
+        at("2026-03-08T15:30:00Z")
+    );
+    assert!(parse_window_start("2026-13-01", now).is_err());
+}
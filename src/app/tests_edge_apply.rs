@@ -0,0 +1,100 @@
+use super::App;
+
+fn open_app() -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-edge-apply-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn short(id: &str) -> &str {
+    crate::knot_id::display_id(id)
+}
+
+#[test]
+fn edge_files_add_and_remove_edges_with_a_dry_run_diff() {
+    let (root, app) = open_app();
+    let epic = app.create_knot("Epic", None, None, None).expect("epic");
+    let api = app.create_knot("API", None, None, None).expect("api");
+    let ui = app.create_knot("UI", None, None, None).expect("ui");
+    app.add_edge(&ui.id, "blocked_by", &epic.id)
+        .expect("stale edge");
+    let file = format!(
+        "# wire the epic\n\
+         {epic} parent_of {api}\n\
+         + {epic} parent_of {ui}\n\
+         \n\
+         {ui} blocked_by {api}   # UI needs the API\n\
+         - {ui} blocked_by {epic}\n\
+         {epic} parent_of {api}\n",
+        epic = short(&epic.id),
+        api = short(&api.id),
+        ui = short(&ui.id),
+    );
+
+    let preview = app.apply_edge_file(&file, true).expect("dry run");
+    assert!(!preview.applied);
+    let ops: Vec<(usize, &str)> = preview
+        .changes
+        .iter()
+        .map(|change| (change.line, change.op))
+        .collect();
+    assert_eq!(ops, vec![(2, "add"), (3, "add"), (5, "add"), (6, "remove")]);
+    assert_eq!(preview.changes[0].src, epic.id);
+    assert_eq!(preview.changes[0].dst, api.id);
+    assert_eq!(
+        app.list_edges(&epic.id, "outgoing").expect("edges").len(),
+        0
+    );
+
+    let applied = app.apply_edge_file(&file, false).expect("apply");
+    assert!(applied.applied);
+    assert_eq!(applied.changes, preview.changes);
+    assert_eq!(
+        app.list_edges(&epic.id, "outgoing").expect("edges").len(),
+        2
+    );
+    let ui_edges = app.list_edges(&ui.id, "outgoing").expect("edges");
+    assert_eq!(ui_edges.len(), 1);
+    assert_eq!(ui_edges[0].dst, api.id);
+
+    let again = app.apply_edge_file(&file, false).expect("reapply");
+    assert!(again.changes.is_empty());
+    assert_eq!(again.unchanged, 4);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn edge_files_with_any_bad_line_change_nothing() {
+    let (root, app) = open_app();
+    let epic = app.create_knot("Epic", None, None, None).expect("epic");
+    let api = app.create_knot("API", None, None, None).expect("api");
+    let file = format!(
+        "{epic} parent_of {api}\n\
+         {epic} parent_of\n\
+         {epic} parent_of nope-missing\n\
+         - {epic} parent_of {api}\n",
+        epic = short(&epic.id),
+        api = short(&api.id),
+    );
+    let err = app.apply_edge_file(&file, false).expect_err("bad file");
+    let message = err.to_string();
+    assert!(
+        message.contains("3 problem(s); nothing was applied"),
+        "{message}"
+    );
+    assert!(
+        message.contains("line 2: expected `<src> <kind> <dst>`"),
+        "{message}"
+    );
+    assert!(message.contains("line 3:"), "{message}");
+    assert!(message.contains("line 4: contradicts line 1"), "{message}");
+    assert!(app
+        .list_edges(&epic.id, "outgoing")
+        .expect("edges")
+        .is_empty());
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -31,9 +31,11 @@ fn sample_record() -> KnotCacheRecord {
         body: None,
         description: None,
         acceptance: None,
+        estimate: None,
         priority: None,
         knot_type: None,
         tags: Vec::new(),
+        custom_fields: Default::default(),
         notes: Vec::new(),
         handoff_capsules: Vec::new(),
         invariants: Vec::new(),
@@ -48,6 +50,7 @@ fn sample_record() -> KnotCacheRecord {
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: None,
+        snoozed_until: None,
     }
 }
 
@@ -128,9 +131,11 @@ fn seed_projection() -> RehydrateProjection {
         body: None,
         description: None,
         acceptance: None,
+        estimate: None,
         priority: None,
         knot_type: KnotType::default(),
         tags: Vec::new(),
+        custom_fields: Default::default(),
         notes: Vec::new(),
         handoff_capsules: Vec::new(),
         invariants: Vec::new(),
@@ -144,6 +149,7 @@ fn seed_projection() -> RehydrateProjection {
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: None,
+        snoozed_until: None,
     }
 }
 
@@ -155,7 +161,7 @@ fn apply_core_events(projection: &mut RehydrateProjection) {
         FullEventKind::KnotCreated.as_str(),
         json!("not-object"),
     );
-    apply_rehydrate_event(projection, &non_object);
+    apply_rehydrate_event(projection, &non_object, &Default::default());
 
     let created = FullEvent::with_identity(
         "e1",
@@ -168,7 +174,7 @@ fn apply_core_events(projection: &mut RehydrateProjection) {
             "profile_id": "default"
         }),
     );
-    apply_rehydrate_event(projection, &created);
+    apply_rehydrate_event(projection, &created, &Default::default());
 
     let title_set = FullEvent::with_identity(
         "e2",
@@ -177,7 +183,7 @@ fn apply_core_events(projection: &mut RehydrateProjection) {
         FullEventKind::KnotTitleSet.as_str(),
         json!({"to":"Renamed"}),
     );
-    apply_rehydrate_event(projection, &title_set);
+    apply_rehydrate_event(projection, &title_set, &Default::default());
 
     let state_set = FullEvent::with_identity(
         "e3",
@@ -186,7 +192,7 @@ fn apply_core_events(projection: &mut RehydrateProjection) {
         FullEventKind::KnotStateSet.as_str(),
         json!({"to":"implementing"}),
     );
-    apply_rehydrate_event(projection, &state_set);
+    apply_rehydrate_event(projection, &state_set, &Default::default());
 
     let description_set = FullEvent::with_identity(
         "e4",
@@ -195,7 +201,7 @@ fn apply_core_events(projection: &mut RehydrateProjection) {
         FullEventKind::KnotDescriptionSet.as_str(),
         json!({"description":"details"}),
     );
-    apply_rehydrate_event(projection, &description_set);
+    apply_rehydrate_event(projection, &description_set, &Default::default());
 
     let priority_set = FullEvent::with_identity(
         "e5",
@@ -204,7 +210,7 @@ fn apply_core_events(projection: &mut RehydrateProjection) {
         FullEventKind::KnotPrioritySet.as_str(),
         json!({"priority":2}),
     );
-    apply_rehydrate_event(projection, &priority_set);
+    apply_rehydrate_event(projection, &priority_set, &Default::default());
 
     let type_set = FullEvent::with_identity(
         "e6",
@@ -213,7 +219,7 @@ fn apply_core_events(projection: &mut RehydrateProjection) {
         FullEventKind::KnotTypeSet.as_str(),
         json!({"type":"task"}),
     );
-    apply_rehydrate_event(projection, &type_set);
+    apply_rehydrate_event(projection, &type_set, &Default::default());
 }
 
 fn apply_metadata_events(projection: &mut RehydrateProjection) {
@@ -224,8 +230,8 @@ fn apply_metadata_events(projection: &mut RehydrateProjection) {
         FullEventKind::KnotTagAdd.as_str(),
         json!({"tag":"Release"}),
     );
-    apply_rehydrate_event(projection, &tag_add);
-    apply_rehydrate_event(projection, &tag_add);
+    apply_rehydrate_event(projection, &tag_add, &Default::default());
+    apply_rehydrate_event(projection, &tag_add, &Default::default());
 
     let note = FullEvent::with_identity(
         "e8",
@@ -242,7 +248,7 @@ fn apply_metadata_events(projection: &mut RehydrateProjection) {
             "version":"v"
         }),
     );
-    apply_rehydrate_event(projection, &note);
+    apply_rehydrate_event(projection, &note, &Default::default());
 
     let handoff = FullEvent::with_identity(
         "e9",
@@ -259,7 +265,7 @@ fn apply_metadata_events(projection: &mut RehydrateProjection) {
             "version":"v"
         }),
     );
-    apply_rehydrate_event(projection, &handoff);
+    apply_rehydrate_event(projection, &handoff, &Default::default());
 
     let tag_remove = FullEvent::with_identity(
         "e10",
@@ -268,7 +274,7 @@ fn apply_metadata_events(projection: &mut RehydrateProjection) {
         FullEventKind::KnotTagRemove.as_str(),
         json!({"tag":"release"}),
     );
-    apply_rehydrate_event(projection, &tag_remove);
+    apply_rehydrate_event(projection, &tag_remove, &Default::default());
 }
 
 #[test]
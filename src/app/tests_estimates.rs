@@ -0,0 +1,113 @@
+use super::{App, AppError, CreateKnotOptions, EstimateGrouping, UpdateKnotPatch};
+
+fn open_app() -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-estimates-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn create_estimated(app: &App, title: &str, state: &str, estimate: &str, tags: &[&str]) -> String {
+    let knot = app
+        .create_knot_with_options(
+            title,
+            None,
+            Some(state),
+            None,
+            None,
+            CreateKnotOptions {
+                estimate: Some(estimate.to_string()),
+                ..CreateKnotOptions::default()
+            },
+        )
+        .expect("create knot");
+    if !tags.is_empty() {
+        app.update_knot(
+            &knot.id,
+            UpdateKnotPatch {
+                add_tags: tags.iter().map(|tag| tag.to_string()).collect(),
+                ..UpdateKnotPatch::default()
+            },
+        )
+        .expect("tag knot");
+    }
+    knot.id
+}
+
+#[test]
+fn estimate_is_normalized_on_create_and_update() {
+    let (root, app) = open_app();
+    let id = create_estimated(&app, "Estimated", "ready_for_planning", "90m", &[]);
+    let shown = app.show_knot(&id).expect("show").expect("knot");
+    assert_eq!(shown.estimate.as_deref(), Some("1h30m"));
+
+    let updated = app
+        .update_knot(
+            &id,
+            UpdateKnotPatch {
+                estimate: Some("5 pts".to_string()),
+                ..UpdateKnotPatch::default()
+            },
+        )
+        .expect("update estimate");
+    assert_eq!(updated.estimate.as_deref(), Some("5pt"));
+
+    assert!(matches!(
+        app.update_knot(
+            &id,
+            UpdateKnotPatch {
+                estimate: Some("soon".to_string()),
+                ..UpdateKnotPatch::default()
+            },
+        ),
+        Err(AppError::InvalidArgument(_))
+    ));
+
+    let cleared = app
+        .update_knot(
+            &id,
+            UpdateKnotPatch {
+                estimate: Some(" ".to_string()),
+                ..UpdateKnotPatch::default()
+            },
+        )
+        .expect("clear estimate");
+    assert_eq!(cleared.estimate, None);
+    let rehydrated = app.rehydrate(&id).expect("rehydrate").expect("knot");
+    assert_eq!(rehydrated.estimate, None);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn estimate_report_groups_shipped_knots() {
+    let (root, app) = open_app();
+    create_estimated(&app, "A", "shipped", "3", &["api"]);
+    create_estimated(&app, "B", "shipped", "2pt", &["api", "ui"]);
+    create_estimated(&app, "C", "shipped", "2h", &[]);
+    create_estimated(&app, "Open", "ready_for_planning", "8", &["api"]);
+    create_estimated(&app, "Dropped", "abandoned", "8", &["api"]);
+
+    let by_type = app
+        .estimate_report(EstimateGrouping::Type)
+        .expect("report by type");
+    assert_eq!(by_type.groups.len(), 1);
+    let work = &by_type.groups[0];
+    assert_eq!(work.key, "work");
+    let points = work.points.as_ref().expect("point totals");
+    assert_eq!((points.knots, points.estimated), (2, 5));
+    let duration = work.duration.as_ref().expect("duration totals");
+    assert_eq!((duration.knots, duration.estimated), (1, 120));
+
+    let by_tag = app
+        .estimate_report(EstimateGrouping::Tag)
+        .expect("report by tag");
+    let keys: Vec<_> = by_tag.groups.iter().map(|g| g.key.as_str()).collect();
+    assert_eq!(keys, ["(untagged)", "api", "ui"]);
+    let api = by_tag.groups[1].points.as_ref().expect("api points");
+    assert_eq!((api.knots, api.estimated), (2, 5));
+    assert!(by_tag.groups[0].points.is_none());
+    let _ = std::fs::remove_dir_all(root);
+}
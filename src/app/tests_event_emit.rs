@@ -0,0 +1,178 @@
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use crate::app::{App, AppError};
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-event-emit-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn rejected(app: &App, event_type: &str, knot: &str, data: serde_json::Value) -> String {
+    match app.emit_event(event_type, knot, &data) {
+        Err(AppError::InvalidArgument(message)) => message,
+        other => panic!("expected InvalidArgument, got {other:?}"),
+    }
+}
+
+#[test]
+fn emitted_events_apply_like_cli_edits() {
+    let (root, app) = open_app();
+    let knot = app
+        .create_knot("Emit target", None, None, None)
+        .expect("create");
+    let other = app.create_knot("Other", None, None, None).expect("create");
+
+    let noted = app
+        .emit_event(
+            "knot.note_added",
+            &knot.id,
+            &json!({"content": "from CI", "agentname": "ci-bot"}),
+        )
+        .expect("note");
+    assert_eq!(noted.notes.len(), 1);
+    assert_eq!(noted.notes[0].agentname, "ci-bot");
+
+    let tagged = app
+        .emit_event("knot.tag_add", &knot.id, &json!({"tag": "ci"}))
+        .expect("tag");
+    assert_eq!(tagged.tags, vec!["ci".to_string()]);
+    let renamed = app
+        .emit_event("knot.title_set", &knot.id, &json!({"to": "Renamed"}))
+        .expect("title");
+    assert_eq!(renamed.title, "Renamed");
+    app.emit_event(
+        "knot.edge_add",
+        &knot.id,
+        &json!({"kind": "blocked_by", "dst": other.id}),
+    )
+    .expect("edge");
+    let edges = app.list_edges(&knot.id, "outgoing").expect("edges");
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edges[0].dst, other.id);
+
+    let shown = app.show_knot(&knot.id).expect("show").expect("knot");
+    assert_eq!(shown.title, "Renamed");
+    assert_eq!(shown.notes[0].content, "from CI");
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn emit_rejects_unknown_types_and_malformed_data() {
+    let (root, app) = open_app();
+    let knot = app.create_knot("Strict", None, None, None).expect("create");
+
+    let message = rejected(&app, "knot.exploded", &knot.id, json!({}));
+    assert!(message.contains("supported types"), "{message}");
+    assert!(message.contains("knot.note_added"), "{message}");
+    let message = rejected(&app, "knot.note_added", &knot.id, json!("hi"));
+    assert!(message.contains("must be a JSON object"), "{message}");
+    let message = rejected(&app, "knot.note_added", &knot.id, json!({"text": "hi"}));
+    assert!(message.contains("unknown field `text`"), "{message}");
+    let message = rejected(&app, "knot.note_added", &knot.id, json!({}));
+    assert!(message.contains("missing field `content`"), "{message}");
+    let message = rejected(
+        &app,
+        "knot.priority_set",
+        &knot.id,
+        json!({"priority": "high"}),
+    );
+    assert!(
+        message.contains("invalid knot.priority_set data"),
+        "{message}"
+    );
+    let message = rejected(
+        &app,
+        "knot.note_added",
+        &knot.id,
+        json!({"content": "x", "datetime": "2020-01-01T00:00:00Z"}),
+    );
+    assert!(message.contains("unknown field `datetime`"), "{message}");
+
+    let shown = app.show_knot(&knot.id).expect("show").expect("knot");
+    assert!(shown.notes.is_empty());
+    assert!(app
+        .emit_event("knot.tag_add", "missing-knot", &json!({"tag": "x"}))
+        .is_err());
+    let _ = std::fs::remove_dir_all(root);
+}
+
+const CUSTOM_EVENT_CONFIG: &str = r#"
+[fields.build]
+type = "enum"
+values = ["green", "red"]
+
+[event_types."ci.build_finished"]
+description = "A CI build finished"
+set = { build = "status" }
+
+[event_types."ci.undeclared"]
+set = { runner = "host" }
+
+[event_types."knot.build_finished"]
+set = { build = "status" }
+"#;
+
+#[test]
+fn custom_event_types_project_onto_declared_fields() {
+    let (root, app) = open_app();
+    std::fs::write(root.join(".knots/config.toml"), CUSTOM_EVENT_CONFIG).expect("write config");
+    let knot = app
+        .create_knot("Build me", None, None, None)
+        .expect("create");
+
+    let built = app
+        .emit_event(
+            "ci.build_finished",
+            &knot.id,
+            &json!({"status": "green", "run": 42}),
+        )
+        .expect("custom event");
+    assert_eq!(
+        built.custom_fields.get("build").map(String::as_str),
+        Some("green")
+    );
+    let replayed = app
+        .replay("2999-01-01T00:00:00Z", Some(&knot.id))
+        .expect("replay");
+    assert_eq!(
+        replayed[0].custom_fields.get("build").map(String::as_str),
+        Some("green")
+    );
+
+    let cleared = app
+        .emit_event("ci.build_finished", &knot.id, &json!({"status": null}))
+        .expect("null clears");
+    assert!(!cleared.custom_fields.contains_key("build"));
+
+    let missing = rejected(&app, "ci.build_finished", &knot.id, json!({"run": 1}));
+    assert!(missing.contains("missing `status`"), "{missing}");
+    let casing = rejected(
+        &app,
+        "ci.build_finished",
+        &knot.id,
+        json!({"status": "Green"}),
+    );
+    assert!(casing.contains("must be 'green'"), "{casing}");
+    let undeclared = rejected(&app, "ci.undeclared", &knot.id, json!({"host": "gha"}));
+    assert!(
+        undeclared.contains("not declared under [fields]"),
+        "{undeclared}"
+    );
+    let builtin = rejected(
+        &app,
+        "knot.build_finished",
+        &knot.id,
+        json!({"status": "red"}),
+    );
+    assert!(builtin.contains("built-in namespace"), "{builtin}");
+    let unknown = rejected(&app, "ci.other", &knot.id, json!({}));
+    assert!(unknown.contains("ci.build_finished"), "{unknown}");
+    let _ = std::fs::remove_dir_all(root);
+}
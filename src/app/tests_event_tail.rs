@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use super::types::UpdateKnotPatch;
+use super::App;
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-event-tail-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn write_pulled(root: &std::path::Path, name: &str, body: &str) -> PathBuf {
+    let path = root.join(format!(".knots/_worktree/.knots/events/2026/10/16/{name}"));
+    std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+    std::fs::write(&path, body).expect("event should be writable");
+    path
+}
+
+#[test]
+fn event_tail_returns_each_event_once_in_order() {
+    let (root, app) = open_app();
+    let knot = app
+        .create_knot("Tail me", None, None, None)
+        .expect("create");
+    let mut tail = app.event_tail();
+    let first = tail.poll().expect("poll");
+    assert!(!first.is_empty());
+    assert!(first.iter().all(|event| event.knot_id == knot.id));
+    assert!(first
+        .windows(2)
+        .all(|pair| pair[0].occurred_at <= pair[1].occurred_at));
+    assert!(tail.poll().expect("poll").is_empty());
+
+    let patch = UpdateKnotPatch {
+        title: Some("Tailed".to_string()),
+        ..UpdateKnotPatch::default()
+    };
+    app.update_knot(&knot.id, patch).expect("update");
+    let next = tail.poll().expect("poll");
+    assert_eq!(next.len(), 1);
+    assert_eq!(next[0].event_type, "knot.title_set");
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn event_tail_picks_up_pulled_events_once_they_parse() {
+    let (root, app) = open_app();
+    let mut tail = app.event_tail();
+    assert!(tail.poll().expect("poll").is_empty());
+
+    let body = serde_json::json!({
+        "event_id": "0001", "occurred_at": "2026-10-16T09:00:00Z", "knot_id": "remote-k1",
+        "type": "knot.title_set", "data": {"to": "From a teammate"}
+    })
+    .to_string();
+    let path = write_pulled(&root, "0001-knot.title_set.json", &body[..body.len() / 2]);
+    assert!(tail.poll().expect("poll").is_empty());
+    std::fs::write(&path, &body).expect("finish write");
+    let pulled = tail.poll().expect("poll");
+    assert_eq!(pulled.len(), 1);
+    assert_eq!(pulled[0].knot_id, "remote-k1");
+
+    write_pulled(&root, "copy/0001-knot.title_set.json", &body);
+    assert!(tail.poll().expect("poll").is_empty());
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,129 @@
+use std::path::PathBuf;
+
+use super::{App, UpdateKnotPatch};
+use crate::domain::metadata::MetadataEntryInput;
+use crate::grep::{GrepField, Matcher};
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-grep-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn grep(app: &App, pattern: &str, regex: bool, fields: Option<&str>) -> Vec<(String, String)> {
+    let matcher = Matcher::new(pattern, regex, true).expect("matcher");
+    let fields = GrepField::parse_list(fields).expect("fields");
+    app.grep(&matcher, &fields)
+        .expect("grep")
+        .into_iter()
+        .map(|hit| (hit.id, hit.field.as_str().to_string()))
+        .collect()
+}
+
+#[test]
+fn indexed_and_scanned_searches_follow_knot_edits() {
+    let (root, app) = open_app();
+    let plan = app
+        .create_knot(
+            "Write the Migration Plan",
+            Some("step one\nrun the migration"),
+            None,
+            None,
+        )
+        .expect("create");
+    let other = app
+        .create_knot("Unrelated", None, None, None)
+        .expect("create");
+    app.update_knot(
+        &other.id,
+        UpdateKnotPatch {
+            add_note: Some(MetadataEntryInput {
+                content: "see the migration plan draft".to_string(),
+                ..MetadataEntryInput::default()
+            }),
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("note");
+
+    let mut hits = grep(&app, "migration plan", false, None);
+    hits.sort();
+    let mut expected = vec![
+        (plan.id.clone(), "title".to_string()),
+        (other.id.clone(), "notes".to_string()),
+    ];
+    expected.sort();
+    assert_eq!(hits, expected);
+    assert_eq!(
+        grep(&app, "migration", false, Some("description")),
+        vec![(plan.id.clone(), "description".to_string())]
+    );
+    assert_eq!(grep(&app, "migr\\w+ plan", true, Some("notes")).len(), 1);
+
+    app.update_knot(
+        &plan.id,
+        UpdateKnotPatch {
+            title: Some("Write the rollout".to_string()),
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("update");
+    assert_eq!(grep(&app, "migration plan", false, Some("title")), vec![]);
+    assert_eq!(
+        grep(&app, "rollout", false, Some("title")),
+        vec![(plan.id.clone(), "title".to_string())]
+    );
+    let events = grep(&app, "migration plan", false, Some("events"));
+    assert!(events.contains(&(plan.id.clone(), "events".to_string())));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn sensitive_fields_drop_out_of_visible_hits() {
+    let (root, app) = open_app();
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "sensitive_fields = [\"description\"]\n",
+    )
+    .expect("repo config");
+    app.create_knot("Public needle", Some("private needle"), None, None)
+        .expect("create");
+
+    let matcher = Matcher::new("needle", false, false).expect("matcher");
+    let hits = app.grep(&matcher, &GrepField::ALL).expect("grep");
+    assert!(hits.iter().any(|hit| hit.field == GrepField::Description));
+    let visible = app.visible_grep_hits(hits).expect("visible");
+    assert_eq!(
+        visible.iter().map(|hit| hit.field).collect::<Vec<_>>(),
+        vec![GrepField::Title]
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn notes_with_quotes_and_backslashes_match_literally() {
+    let (root, app) = open_app();
+    let knot = app
+        .create_knot("Path handling", None, None, None)
+        .expect("create");
+    app.update_knot(
+        &knot.id,
+        UpdateKnotPatch {
+            add_note: Some(MetadataEntryInput {
+                content: r#"set "C:\temp" as the root"#.to_string(),
+                ..MetadataEntryInput::default()
+            }),
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("note");
+
+    let expected = vec![(knot.id.clone(), "notes".to_string())];
+    assert_eq!(grep(&app, r#""C:\temp""#, false, None), expected);
+    assert_eq!(grep(&app, r"C:\\te", true, Some("notes")), expected);
+    let _ = std::fs::remove_dir_all(root);
+}
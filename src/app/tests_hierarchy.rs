@@ -121,6 +121,8 @@ fn deferred_child_blocks_using_deferred_from_state_progress() {
         title: None,
         description: None,
         acceptance: None,
+        estimate: None,
+        custom_fields: Vec::new(),
         priority: None,
         status: Some("ready_for_implementation_review".to_string()),
         knot_type: None,
@@ -330,6 +332,8 @@ fn update_knot_terminal_cascade_with_approval_succeeds() {
         title: None,
         description: None,
         acceptance: None,
+        estimate: None,
+        custom_fields: Vec::new(),
         priority: None,
         status: Some("abandoned".to_string()),
         knot_type: None,
@@ -144,9 +144,11 @@ fn show_knot_fails_when_cache_contains_legacy_workflow_id() {
             body: None,
             description: None,
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: Some("work"),
             tags: &[],
+            custom_fields: &Default::default(),
             notes: &[],
             handoff_capsules: &[],
             invariants: &[],
@@ -160,6 +162,7 @@ fn show_knot_fails_when_cache_contains_legacy_workflow_id() {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: Some("2026-02-25T10:00:00Z"),
+            snoozed_until: None,
         },
     )
     .expect("legacy row should upsert");
@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+
+use super::types::UpdateKnotPatch;
+use super::App;
+use crate::db;
+use crate::domain::metadata::MetadataEntryInput;
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-lint-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn tag(app: &App, id: &str) {
+    let patch = UpdateKnotPatch {
+        add_tags: vec!["area:core".to_string()],
+        ..UpdateKnotPatch::default()
+    };
+    app.update_knot(id, patch).expect("tag should be added");
+}
+
+#[test]
+fn lint_reports_orphans_missing_note_refs_and_deferred_without_origin() {
+    let (root, app) = open_app();
+    let parent = app.create_knot("Parent", None, None, None).expect("create");
+    let child = app.create_knot("Child", None, None, None).expect("create");
+    let loose = app.create_knot("Loose", None, None, None).expect("create");
+    tag(&app, &parent.id);
+    app.add_edge(&parent.id, "parent_of", &child.id)
+        .expect("edge");
+    assert!(app
+        .lint()
+        .expect("lint")
+        .orphans
+        .iter()
+        .any(|k| k.id == loose.id));
+
+    let (prefix, _) = loose.id.rsplit_once('-').expect("prefixed id");
+    let missing = format!("{prefix}-0bad");
+    let patch = UpdateKnotPatch {
+        add_note: Some(MetadataEntryInput {
+            content: format!(
+                "Blocked on {missing}: see {} and other-repo:{missing}.",
+                child.id
+            ),
+            ..MetadataEntryInput::default()
+        }),
+        add_tags: vec!["area:core".to_string()],
+        ..UpdateKnotPatch::default()
+    };
+    app.update_knot(&loose.id, patch).expect("note");
+    app.set_state(&child.id, "deferred", false, None)
+        .expect("defer");
+
+    let report = app.lint().expect("lint");
+    assert!(report.orphans.is_empty());
+    assert_eq!(report.missing_note_refs.len(), 1);
+    assert_eq!(report.missing_note_refs[0].reference, missing);
+    assert_eq!(report.missing_note_refs[0].knot.id, loose.id);
+    assert!(report.deferred_without_origin.is_empty());
+
+    app.conn
+        .execute(
+            "UPDATE knot_hot SET deferred_from_state = NULL WHERE id = ?1",
+            [&child.id],
+        )
+        .expect("clear origin");
+    let report = app.lint().expect("lint");
+    assert_eq!(report.deferred_without_origin.len(), 1);
+    assert_eq!(report.deferred_without_origin[0].id, child.id);
+    assert_eq!(report.issue_count(), 2);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn lint_fix_removes_dangling_edges_through_doctor() {
+    let (root, app) = open_app();
+    let knot = app.create_knot("Tagged", None, None, None).expect("create");
+    tag(&app, &knot.id);
+    let missing = format!("{}-dead", knot.id.rsplit_once('-').expect("prefix").0);
+    db::insert_edge(&app.conn, &knot.id, "blocked_by", &missing).expect("edge");
+    db::insert_edge(&app.conn, &missing, "related", &knot.id).expect("edge");
+    db::insert_edge(&app.conn, &knot.id, "related", "other-repo:abc-1234").expect("edge");
+
+    let report = app.lint().expect("lint");
+    assert_eq!(report.dangling_edges.len(), 2);
+    assert!(report
+        .dangling_edges
+        .iter()
+        .all(|edge| edge.src == missing || edge.dst == missing));
+
+    let fixed = app.lint_fix().expect("fix");
+    assert_eq!(fixed.issue_count(), 0);
+    let kept = app.list_edges(&knot.id, "both").expect("edges");
+    assert_eq!(kept.len(), 1);
+    assert_eq!(kept[0].dst, "other-repo:abc-1234");
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+
+use super::App;
+use crate::org::state_keyword;
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-org-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+#[test]
+fn export_nests_children_and_import_writes_edits_back_once() {
+    let (root, app) = open_app();
+    let parent = app.create_knot("Parent", None, None, None).expect("create");
+    let child = app.create_knot("Child", None, None, None).expect("create");
+    app.add_edge(&parent.id, "parent_of", &child.id)
+        .expect("edge");
+
+    let org = app
+        .export_org(app.list_knots().expect("list"))
+        .expect("export");
+    let keyword = state_keyword(&parent.state);
+    assert!(org.contains(&format!("#+TODO: {keyword} ")), "{org}");
+    assert!(org.contains(" | "));
+    let parent_heading = format!("* {keyword} Parent\n:PROPERTIES:\n:ID: {}\n", parent.id);
+    let child_heading = format!("** {keyword} Child\n:PROPERTIES:\n:ID: {}\n", child.id);
+    assert!(org.contains(&parent_heading), "{org}");
+    assert!(org.contains(&child_heading), "{org}");
+    let unchanged = app.import_org(&org, false).expect("import");
+    assert!(unchanged.changed.is_empty());
+    assert_eq!(unchanged.unchanged, 2);
+
+    let edited = org
+        .replace(
+            &format!("* {keyword} Parent\n"),
+            "* PLANNING Parent, renamed :api:org:\n",
+        )
+        .replace("** ", "** PLANNING ")
+        .replace(&format!("PLANNING {keyword} Child"), "PLANNING Child")
+        + "* TODO A new heading\n";
+    let dry = app.import_org(&edited, true).expect("dry run");
+    assert_eq!(dry.changed.len(), 2);
+    assert_eq!(dry.changed[0].fields, vec!["title", "state", "tags"]);
+    assert_eq!(dry.changed[1].fields, vec!["state"]);
+    assert_eq!(dry.skipped.len(), 1);
+    assert_eq!(
+        app.show_knot(&parent.id)
+            .expect("show")
+            .expect("knot")
+            .title,
+        "Parent"
+    );
+
+    app.import_org(&edited, false).expect("import");
+    let updated = app.show_knot(&parent.id).expect("show").expect("knot");
+    assert_eq!(updated.title, "Parent, renamed");
+    assert_eq!(updated.state, "planning");
+    assert_eq!(updated.tags, vec!["api", "org"]);
+    let again = app.import_org(&edited, false).expect("re-import");
+    assert!(again.changed.is_empty());
+    assert_eq!(again.unchanged, 2);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn hidden_titles_are_exported_as_placeholders_and_never_imported() {
+    let (root, app) = open_app();
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "sensitive_fields = [\"title\", \"description\"]\n",
+    )
+    .expect("repo config");
+    let knot = app
+        .create_knot("Secret title", Some("secret body"), None, None)
+        .expect("create");
+
+    let org = app
+        .export_org(app.list_knots().expect("list"))
+        .expect("export");
+    assert!(!org.contains("Secret title"));
+    assert!(!org.contains("secret body"));
+    assert!(org.contains(":KNOTS_HIDDEN: title\n"));
+
+    let summary = app.import_org(&org, false).expect("import");
+    assert!(summary.changed.is_empty());
+    assert_eq!(
+        app.show_knot(&knot.id).expect("show").expect("knot").title,
+        "Secret title"
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
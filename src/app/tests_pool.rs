@@ -0,0 +1,69 @@
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use super::AppPool;
+use crate::project::{DistributionMode, ProjectContext, StorePaths};
+
+fn open_pool(max_size: usize) -> (std::path::PathBuf, AppPool) {
+    let root = std::env::temp_dir().join(format!("knots-pool-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let context = ProjectContext {
+        project_id: None,
+        store_paths: StorePaths {
+            root: root.join(".knots"),
+        },
+        repo_root: root.clone(),
+        distribution: DistributionMode::Git,
+    };
+    let pool = AppPool::open(&context, db_path.to_str().expect("utf8 db path"), max_size)
+        .expect("pool should open");
+    (root, pool)
+}
+
+#[test]
+fn pool_reuses_idle_apps_and_opens_more_only_under_load() {
+    let (root, pool) = open_pool(3);
+    assert_eq!(pool.open_count(), 1);
+
+    let created = pool
+        .get()
+        .expect("app should check out")
+        .create_knot("Pooled", None, Some("ready_for_planning"), Some("default"))
+        .expect("knot should be created");
+    let shown = pool.get().expect("app should check out");
+    assert_eq!(
+        shown.show_knot(&created.id).expect("show").map(|k| k.title),
+        Some("Pooled".to_string())
+    );
+    assert_eq!(pool.open_count(), 1);
+
+    let second = pool.get().expect("second app should open");
+    assert_eq!(pool.open_count(), 2);
+    drop((shown, second));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn pool_waits_for_a_returned_app_when_full() {
+    let (root, pool) = open_pool(1);
+    let held = pool.get().expect("app should check out");
+    let (sender, receiver) = mpsc::channel();
+    let waiter = {
+        let pool = pool.clone();
+        thread::spawn(move || {
+            let app = pool.get().expect("app should come back");
+            sender
+                .send(app.list_knots().expect("list").len())
+                .expect("send");
+        })
+    };
+    assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+    drop(held);
+    assert_eq!(receiver.recv_timeout(Duration::from_secs(10)), Ok(0));
+    waiter.join().expect("waiter should finish");
+    assert_eq!(pool.open_count(), 1);
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,239 @@
+use std::path::{Path, PathBuf};
+
+use super::{App, CreateKnotOptions};
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-private-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn json_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries {
+            let path = entry.expect("dir entry").path();
+            if path.is_dir() {
+                stack.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+fn private_events(store: &Path) -> Vec<PathBuf> {
+    let private = store.join("private");
+    let mut files = json_files(&private.join("events"));
+    files.extend(json_files(&private.join("index")));
+    files
+}
+
+fn reopen_without_cache(root: &Path) -> App {
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::remove_file(&db_path).expect("cache should delete");
+    App::open(db_path.to_str().expect("utf8"), root.to_path_buf()).expect("reopen")
+}
+
+fn create_private(app: &App, title: &str) -> String {
+    app.create_knot_with_options(
+        title,
+        None,
+        None,
+        None,
+        None,
+        CreateKnotOptions {
+            private: true,
+            ..CreateKnotOptions::default()
+        },
+    )
+    .expect("private knot should be created")
+    .id
+}
+
+#[test]
+fn private_knot_events_stay_out_of_the_shared_stream_until_published() {
+    let (root, app) = open_app();
+    let store = root.join(".knots");
+    let shared = app.create_knot("Team", None, None, None).expect("create");
+    let private = create_private(&app, "Mine");
+    app.set_state(&private, "planning", false, None)
+        .expect("private knot should advance");
+
+    let private_files = private_events(&store);
+    assert!(private_files.len() >= 3, "{private_files:?}");
+    let shared_files = json_files(&store.join("events"));
+    assert!(!shared_files.is_empty());
+    for path in shared_files.iter().chain(&json_files(&store.join("index"))) {
+        let raw = std::fs::read_to_string(path).expect("event should read");
+        assert!(
+            !raw.contains(&private),
+            "{} leaked {private}",
+            path.display()
+        );
+    }
+    assert!(app.show_knot(&private).expect("show").is_some());
+    assert!(app.show_knot(&shared.id).expect("show").is_some());
+
+    let err = app.publish_knot(&shared.id).unwrap_err();
+    assert!(err.to_string().contains("is not private"), "{err}");
+    let published = app.publish_knot(&private).expect("publish");
+    assert_eq!(published.event_files, private_files.len() as u64);
+    assert!(json_files(&store.join("private")).is_empty());
+    app.set_state(&private, "ready_for_implementation", true, None)
+        .expect("published knot should advance");
+    let after: Vec<_> = json_files(&store.join("events"))
+        .into_iter()
+        .filter(|path| std::fs::read_to_string(path).unwrap().contains(&private))
+        .collect();
+    assert!(after.len() >= 2, "{after:?}");
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn publishing_a_draft_restamps_its_staged_events() {
+    let (root, app) = open_app();
+    let store = root.join(".knots");
+    let draft = app
+        .create_knot_with_options(
+            "Half-formed",
+            None,
+            None,
+            None,
+            None,
+            CreateKnotOptions {
+                draft: true,
+                ..CreateKnotOptions::default()
+            },
+        )
+        .expect("draft should be created");
+    assert_eq!(private_events(&store).len(), 2);
+    assert!(json_files(&store.join("events")).is_empty());
+
+    let published = app.publish_knot(&draft.id).expect("publish");
+    assert!(published.draft);
+    assert_eq!(published.event_files, 2);
+    let shown = app.show_knot(&draft.id).expect("show").expect("knot");
+    let created_at = shown.created_at.expect("created_at");
+    assert!(created_at > draft.created_at.clone().expect("created_at"));
+    let files = json_files(&store.join("events"))
+        .into_iter()
+        .chain(json_files(&store.join("index")));
+    for path in files {
+        let raw: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).expect("read")).expect("json");
+        assert_eq!(
+            raw["occurred_at"],
+            created_at.as_str(),
+            "{}",
+            path.display()
+        );
+    }
+    assert!(json_files(&store.join("private")).is_empty());
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn reopened_app_keeps_routing_private_knot_events() {
+    let (root, app) = open_app();
+    let private = create_private(&app, "Mine");
+    drop(app);
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    let app = App::open(db_path.to_str().expect("utf8"), root.clone()).expect("reopen");
+    app.set_state(&private, "planning", false, None)
+        .expect("private knot should advance");
+    assert!(json_files(&root.join(".knots").join("events")).is_empty());
+    assert!(crate::db::list_private_knots(&app.conn)
+        .expect("private knots")
+        .contains(&private));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn private_knots_survive_a_deleted_cache() {
+    let (root, app) = open_app();
+    let private = create_private(&app, "Mine");
+    app.set_state(&private, "planning", false, None)
+        .expect("private knot should advance");
+    drop(app);
+    let app = reopen_without_cache(&root);
+
+    let listed = app.list_knots().expect("list");
+    assert!(listed
+        .iter()
+        .any(|knot| knot.id == private && knot.state == "planning"));
+    app.set_state(&private, "ready_for_implementation", true, None)
+        .expect("restored knot should advance");
+    assert!(json_files(&root.join(".knots").join("events")).is_empty());
+    assert!(!app.publish_knot(&private).expect("publish").draft);
+    assert!(json_files(&root.join(".knots").join("private")).is_empty());
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn drafts_survive_a_deleted_cache() {
+    let (root, app) = open_app();
+    let draft = app
+        .create_knot_with_options(
+            "Half-formed",
+            None,
+            None,
+            None,
+            None,
+            CreateKnotOptions {
+                draft: true,
+                ..CreateKnotOptions::default()
+            },
+        )
+        .expect("draft should be created");
+    drop(app);
+    let app = reopen_without_cache(&root);
+
+    let shown = app.show_knot(&draft.id).expect("show").expect("knot");
+    assert_eq!(shown.title, "Half-formed");
+    let published = app.publish_knot(&draft.id).expect("publish");
+    assert!(published.draft);
+    assert_eq!(published.event_files, 2);
+    let shown = app.show_knot(&draft.id).expect("show").expect("knot");
+    assert!(shown.created_at > draft.created_at);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn edges_to_private_knots_wait_until_they_are_published() {
+    let (root, app) = open_app();
+    let store = root.join(".knots");
+    let shared = app.create_knot("Team", None, None, None).expect("create");
+    let parent = create_private(&app, "Epic");
+    let child = create_private(&app, "Step");
+
+    let err = app
+        .add_edge(&shared.id, "parent_of", &child)
+        .expect_err("shared knots cannot point at private ones");
+    assert!(err.to_string().contains("kno publish"), "{err}");
+    for path in json_files(&store.join("events")) {
+        let raw = std::fs::read_to_string(&path).expect("event should read");
+        assert!(!raw.contains(&child), "{} leaked {child}", path.display());
+    }
+
+    app.add_edge(&parent, "parent_of", &child)
+        .expect("private knots may link to each other");
+    let err = app
+        .publish_knot(&parent)
+        .expect_err("the edge would publish the child's id");
+    assert!(err.to_string().contains("publish"), "{err}");
+    app.publish_knot(&child).expect("publish child");
+    app.publish_knot(&parent).expect("publish parent");
+    app.add_edge(&shared.id, "blocked_by", &child)
+        .expect("published knots can be linked");
+    let _ = std::fs::remove_dir_all(root);
+}
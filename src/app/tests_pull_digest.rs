@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use super::{App, UpdateKnotPatch};
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-pull-digest-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+#[test]
+fn digest_sorts_changes_into_new_state_and_other_with_claims_first() {
+    let (root, app) = open_app();
+    let quiet = app.create_knot("Quiet", None, None, None).expect("create");
+    let mine = app.create_knot("Mine", None, None, None).expect("create");
+    let theirs = app.create_knot("Theirs", None, None, None).expect("create");
+    let edited = app.create_knot("Edited", None, None, None).expect("create");
+    app.conn
+        .execute(
+            "UPDATE knot_hot SET lease_id = 'lease-1' WHERE id = ?1",
+            [&mine.id],
+        )
+        .expect("claim");
+    let baseline = app.pull_digest_baseline().expect("baseline");
+    assert!(app.pull_digest(&baseline).expect("digest").is_empty());
+
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    let added = app.create_knot("Added", None, None, None).expect("create");
+    app.set_state(&theirs.id, "planning", false, None)
+        .expect("advance");
+    app.set_state(&mine.id, "planning", false, None)
+        .expect("advance");
+    app.update_knot(
+        &edited.id,
+        UpdateKnotPatch {
+            title: Some("Edited again".to_string()),
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("edit");
+
+    let digest = app.pull_digest(&baseline).expect("digest");
+    let ids = |knots: &[super::DigestKnot]| -> Vec<String> {
+        knots.iter().map(|knot| knot.id.clone()).collect()
+    };
+    assert_eq!(ids(&digest.new_knots), vec![added.id]);
+    assert_eq!(ids(&digest.state_changes), vec![mine.id, theirs.id]);
+    assert!(digest.state_changes[0].claimed && !digest.state_changes[1].claimed);
+    assert_eq!(
+        digest.state_changes[0].previous_state.as_deref(),
+        Some(mine.state.as_str())
+    );
+    assert_eq!(ids(&digest.other_updates), vec![edited.id]);
+    assert!(!ids(&digest.other_updates).contains(&quiet.id));
+    let _ = std::fs::remove_dir_all(root);
+}
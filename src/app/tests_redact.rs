@@ -0,0 +1,194 @@
+use std::path::{Path, PathBuf};
+
+use super::{App, ConfigScope, UpdateKnotPatch};
+use crate::db;
+use crate::domain::metadata::MetadataEntryInput;
+use crate::grep::{GrepField, Matcher};
+
+const SECRET: &str = "sk-live-0123456789";
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-redact-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn event_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries {
+            let path = entry.expect("dir entry").path();
+            if path.is_dir() {
+                stack.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+fn files_containing(dir: &Path, text: &str) -> Vec<PathBuf> {
+    event_files(dir)
+        .into_iter()
+        .filter(|path| std::fs::read_to_string(path).is_ok_and(|raw| raw.contains(text)))
+        .collect()
+}
+
+fn event_id_of(path: &Path) -> String {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .expect("name");
+    let event_type = name.find("-knot.").expect("full event file name");
+    name[..event_type].to_string()
+}
+
+#[test]
+fn redact_rewrites_the_event_scrubs_the_cache_and_records_a_marker() {
+    let (root, app) = open_app();
+    let body = format!("token {SECRET} leaked");
+    let knot = app
+        .create_knot("Leaky", Some(&body), None, None)
+        .expect("create");
+    let events = root.join(".knots").join("events");
+    let leaked = files_containing(&events, SECRET);
+    assert_eq!(leaked.len(), 1, "{leaked:?}");
+    let event_id = event_id_of(&leaked[0]);
+
+    let redaction = app
+        .redact_event(&event_id, &[SECRET.to_string()], "[REDACTED]")
+        .expect("redact should succeed");
+    assert_eq!(redaction.knot_id, knot.id);
+    assert_eq!(redaction.replacements, 1);
+    assert_eq!(redaction.fields, vec!["body".to_string()]);
+    assert!(!redaction.published);
+    assert!(files_containing(&root.join(".knots"), SECRET).is_empty());
+    let shown = app.show_knot(&knot.id).expect("show").expect("knot");
+    assert_eq!(shown.body.as_deref(), Some("token [REDACTED] leaked"));
+
+    let markers: Vec<_> = event_files(&events)
+        .into_iter()
+        .filter(|path| path.to_string_lossy().ends_with("-knot.redacted.json"))
+        .collect();
+    assert_eq!(markers.len(), 1);
+    let marker = std::fs::read_to_string(&markers[0]).expect("marker");
+    assert!(marker.contains(&event_id) && !marker.contains(SECRET));
+    let report = crate::fsck::run_fsck(&root).expect("fsck");
+    assert!(report.ok(), "{:?}", report.issues);
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn redact_rejects_unknown_events_and_missing_matches() {
+    let (root, app) = open_app();
+    app.create_knot("Clean", Some("nothing here"), None, None)
+        .expect("create");
+    let pattern = [SECRET.to_string()];
+    let err = app
+        .redact_event("no-such-event", &pattern, "[REDACTED]")
+        .expect_err("unknown event should fail");
+    assert!(err.to_string().contains("not found"), "{err}");
+
+    let clean = files_containing(&root.join(".knots").join("events"), "nothing here");
+    let event_id = event_id_of(&clean[0]);
+    let err = app
+        .redact_event(&event_id, &pattern, "[REDACTED]")
+        .expect_err("no match should fail");
+    assert!(err.to_string().contains("no --pattern match"), "{err}");
+    let err = app
+        .redact_event(&event_id, &["here".to_string()], "still here")
+        .expect_err("replacement containing the pattern should fail");
+    assert!(err.to_string().contains("--replacement"), "{err}");
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn redact_scrubs_compressed_snapshots() {
+    let (root, app) = open_app();
+    app.config_set("compression", "zstd", ConfigScope::Local)
+        .expect("compression should be settable");
+    app.create_knot("Leaky", Some(&format!("token {SECRET}")), None, None)
+        .expect("create");
+    let written = app.compact_write_snapshots().expect("snapshots");
+    let decoded = |path: &Path| {
+        let raw = std::fs::read(path).expect("snapshot");
+        assert!(crate::compression::is_zstd(&raw));
+        String::from_utf8(
+            crate::compression::decode(&raw)
+                .expect("decode")
+                .into_owned(),
+        )
+        .expect("utf8")
+    };
+    assert!(decoded(&written.active_path).contains(SECRET));
+    let leaked = files_containing(&root.join(".knots").join("events"), SECRET);
+    let event_id = event_id_of(&leaked[0]);
+
+    let redaction = app
+        .redact_event(&event_id, &[SECRET.to_string()], "[REDACTED]")
+        .expect("redact should succeed");
+    assert_eq!(redaction.snapshot_files, 1);
+    for path in event_files(&root.join(".knots").join("snapshots")) {
+        assert!(!decoded(&path).contains(SECRET), "{path:?}");
+    }
+    assert!(decoded(&written.active_path).contains("token [REDACTED]"));
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn redact_drops_the_secret_from_the_search_indexes() {
+    let (root, app) = open_app();
+    let knot = app.create_knot("Leaky", None, None, None).expect("create");
+    app.update_knot(
+        &knot.id,
+        UpdateKnotPatch {
+            add_note: Some(MetadataEntryInput {
+                content: format!("token {SECRET}"),
+                ..MetadataEntryInput::default()
+            }),
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("note");
+    let grep = |app: &App| {
+        let matcher = Matcher::new(SECRET, false, false).expect("matcher");
+        let fields = GrepField::parse_list(None).expect("fields");
+        app.grep(&matcher, &fields).expect("grep").len()
+    };
+    assert_eq!(grep(&app), 1);
+    #[cfg(feature = "semantic")]
+    db::upsert_knot_embedding(&app.conn, &knot.id, "hash", &[0, 0, 128, 63]).expect("embedding");
+    let leaked = files_containing(&root.join(".knots").join("events"), SECRET);
+    assert_eq!(leaked.len(), 1, "{leaked:?}");
+
+    app.redact_event(
+        &event_id_of(&leaked[0]),
+        &[SECRET.to_string()],
+        "[REDACTED]",
+    )
+    .expect("redact should succeed");
+    assert_eq!(grep(&app), 0);
+    let candidates = db::grep_index_candidates(&app.conn, &["notes"], SECRET).expect("candidates");
+    assert!(candidates.is_empty(), "{candidates:?}");
+    let indexed =
+        db::grep_index_candidates(&app.conn, &["notes"], "[REDACTED]").expect("candidates");
+    assert_eq!(indexed, vec![knot.id.clone()]);
+    #[cfg(feature = "semantic")]
+    assert!(db::list_knot_embeddings(&app.conn)
+        .expect("embeddings")
+        .is_empty());
+
+    let _ = std::fs::remove_dir_all(root);
+}
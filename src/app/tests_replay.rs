@@ -0,0 +1,139 @@
+use super::{App, AppError};
+use std::path::{Path, PathBuf};
+
+fn unique_root() -> PathBuf {
+    let root = std::env::temp_dir().join(format!("knots-replay-{}", uuid::Uuid::now_v7()));
+    std::fs::create_dir_all(&root).expect("root should be creatable");
+    root
+}
+
+fn open_app(root: &Path) -> App {
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    App::open(db_path.to_str().expect("utf8 db path"), root.to_path_buf()).expect("app should open")
+}
+
+fn write_json(path: PathBuf, body: serde_json::Value) {
+    std::fs::create_dir_all(path.parent().expect("parent should exist")).expect("mkdir");
+    std::fs::write(path, body.to_string()).expect("event should be writable");
+}
+
+fn write_full(store: &Path, id: &str, at: &str, knot: &str, kind: &str, data: serde_json::Value) {
+    write_json(
+        store.join(format!("events/2025/05/01/{id}-{kind}.json")),
+        serde_json::json!({
+            "event_id": id, "occurred_at": at, "knot_id": knot, "type": kind, "data": data
+        }),
+    );
+}
+
+fn write_head(store: &Path, id: &str, at: &str, knot: &str, title: &str, state: &str) {
+    write_json(
+        store.join(format!("index/2025/05/01/{id}-idx.knot_head.json")),
+        serde_json::json!({
+            "event_id": id, "occurred_at": at, "type": "idx.knot_head",
+            "data": {
+                "knot_id": knot, "title": title, "state": state, "updated_at": at,
+                "workflow_id": "work_sdlc", "profile_id": "autopilot", "terminal": false
+            }
+        }),
+    );
+}
+
+fn seed_history(store: &Path) {
+    write_full(
+        store,
+        "0001",
+        "2025-05-01T10:00:00Z",
+        "K-a",
+        "knot.created",
+        serde_json::json!({
+            "title": "Alpha", "state": "ready_for_planning",
+            "workflow_id": "work_sdlc", "profile_id": "autopilot",
+            "body": "initial brief"
+        }),
+    );
+    write_head(
+        store,
+        "0002",
+        "2025-05-01T10:00:00Z",
+        "K-a",
+        "Alpha",
+        "ready_for_planning",
+    );
+    write_full(
+        store,
+        "0003",
+        "2025-06-02T09:00:00Z",
+        "K-a",
+        "knot.description_set",
+        serde_json::json!({"description": "after release"}),
+    );
+    write_head(
+        store,
+        "0004",
+        "2025-06-02T09:00:00Z",
+        "K-a",
+        "Alpha v2",
+        "shipped",
+    );
+    write_head(
+        store,
+        "0005",
+        "2025-06-03T09:00:00Z",
+        "K-b",
+        "Beta",
+        "ready_for_planning",
+    );
+}
+
+#[test]
+fn replay_rebuilds_knots_as_of_timestamp() {
+    let root = unique_root();
+    let app = open_app(&root);
+    seed_history(&root.join(".knots"));
+
+    let before = app.replay("2025-06-01T00:00:00Z", None).expect("replay");
+    assert_eq!(before.len(), 1);
+    assert_eq!(before[0].id, "K-a");
+    assert_eq!(before[0].title, "Alpha");
+    assert_eq!(before[0].state, "ready_for_planning");
+    assert_eq!(before[0].description.as_deref(), Some("initial brief"));
+
+    let after = app.replay("2025-06-05T00:00:00Z", None).expect("replay");
+    assert_eq!(after.len(), 2);
+    assert_eq!(after[0].state, "shipped");
+    assert_eq!(after[0].description.as_deref(), Some("after release"));
+    assert!(app.list_knots().expect("list").is_empty());
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn replay_filters_by_id_and_reads_worktree_events_once() {
+    let root = unique_root();
+    let app = open_app(&root);
+    seed_history(&root.join(".knots"));
+    seed_history(&root.join(".knots/_worktree/.knots"));
+
+    let only = app
+        .replay("2025-06-02T09:00:00Z", Some("K-a"))
+        .expect("replay");
+    assert_eq!(only.len(), 1);
+    assert_eq!(only[0].title, "Alpha v2");
+
+    let missing = app
+        .replay("2025-06-02T09:00:00Z", Some("K-b"))
+        .expect_err("knot did not exist yet");
+    assert!(matches!(missing, AppError::NotFound(id) if id == "K-b"));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn replay_rejects_invalid_timestamp() {
+    let root = unique_root();
+    let app = open_app(&root);
+    let err = app.replay("yesterday", None).expect_err("bad timestamp");
+    assert!(matches!(err, AppError::InvalidArgument(msg) if msg.contains("RFC 3339")));
+    let _ = std::fs::remove_dir_all(root);
+}
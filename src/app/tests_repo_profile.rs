@@ -0,0 +1,55 @@
+use super::App;
+
+fn open_app() -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-repo-profile-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app = App::open(db_path.to_str().expect("utf8 db path"), root.clone())
+        .expect("app should open")
+        .with_home_override(Some(root.join("home")));
+    (root, app)
+}
+
+#[test]
+fn repo_default_profile_wins_over_user_default() {
+    let (root, app) = open_app();
+    let user = app
+        .set_default_quick_profile_id("semiauto")
+        .expect("user quick");
+    assert_eq!(app.default_quick_profile_id().expect("quick"), user);
+
+    let repo = app
+        .set_repo_default_profile_id("autopilot", true)
+        .expect("repo quick");
+    assert_eq!(app.default_quick_profile_id().expect("quick"), repo);
+
+    let repo = app
+        .set_repo_default_profile_id("semiauto", false)
+        .expect("repo default");
+    assert_eq!(app.default_profile_id().expect("default"), repo);
+
+    let raw = std::fs::read_to_string(root.join(".knots/config.toml")).expect("config");
+    assert!(raw.contains("default_profile = \"semiauto\""), "{raw}");
+    assert!(
+        raw.contains("default_quick_profile = \"autopilot\""),
+        "{raw}"
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn repo_default_profile_rejects_unknown_and_ignores_stale_ids() {
+    let (root, app) = open_app();
+    assert!(app.set_repo_default_profile_id("nope", false).is_err());
+    assert!(!root.join(".knots/config.toml").exists());
+
+    let fallback = app.default_profile_id().expect("fallback");
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "version = 1\ndefault_profile = \"removed_profile\"\n",
+    )
+    .expect("write config");
+    assert_eq!(app.default_profile_id().expect("default"), fallback);
+    let _ = std::fs::remove_dir_all(root);
+}
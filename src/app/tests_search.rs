@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+use super::App;
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-search-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+#[test]
+fn search_ranks_knots_covering_the_query() {
+    let (root, app) = open_app();
+    let flaky = app
+        .create_knot("Retry on CI", Some("the auth tests are flaky"), None, None)
+        .expect("create");
+    let partial = app
+        .create_knot("Auth token refresh", None, None, None)
+        .expect("create");
+    app.create_knot("Render release notes", None, None, None)
+        .expect("create");
+
+    let hits = app
+        .search_knots("flaky auth tests", false, 10)
+        .expect("search");
+    let ids: Vec<&str> = hits.iter().map(|hit| hit.knot.id.as_str()).collect();
+    assert_eq!(ids, vec![flaky.id.as_str()]);
+    assert_eq!(hits[0].score, 1.0);
+    let hits = app.search_knots("auth", false, 10).expect("search");
+    assert_eq!(hits.len(), 2);
+    assert_eq!(app.search_knots("auth", false, 1).expect("search").len(), 1);
+    assert!(hits.iter().any(|hit| hit.knot.id == partial.id));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[cfg(feature = "semantic")]
+mod semantic {
+    use std::cell::RefCell;
+
+    use super::open_app;
+    use crate::semantic::EmbeddingProvider;
+
+    /// Bag-of-words vectors over a tiny vocabulary, recording every text
+    /// it is asked to embed.
+    struct FakeEmbeddings {
+        calls: RefCell<Vec<String>>,
+    }
+
+    const VOCABULARY: [&str; 4] = ["auth", "flaky", "release", "notes"];
+
+    impl EmbeddingProvider for FakeEmbeddings {
+        fn model(&self) -> &str {
+            "fake"
+        }
+
+        fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+            self.calls.borrow_mut().extend(texts.iter().cloned());
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let text = text.to_lowercase();
+                    VOCABULARY
+                        .iter()
+                        .map(|word| text.matches(word).count() as f32)
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn semantic_search_embeds_changed_knots_once_and_skips_sensitive_text() {
+        let (root, app) = open_app();
+        std::fs::write(
+            root.join(".knots/config.toml"),
+            "sensitive_fields = [\"description\"]\n",
+        )
+        .expect("repo config");
+        let auth = app
+            .create_knot("Flaky auth", Some("secret release notes"), None, None)
+            .expect("create");
+        let notes = app
+            .create_knot("Release notes", None, None, None)
+            .expect("create");
+        let provider = FakeEmbeddings {
+            calls: RefCell::new(Vec::new()),
+        };
+
+        let hits = app
+            .semantic_search(&provider, "auth is flaky", false, 10)
+            .expect("search");
+        assert_eq!(hits[0].knot.id, auth.id);
+        assert_eq!(hits[0].score, 1.0);
+        assert_eq!(hits[1].knot.id, notes.id);
+        assert_eq!(hits[1].score, 0.0);
+        let mut calls = provider.calls.borrow().clone();
+        calls[..2].sort();
+        assert_eq!(calls, vec!["Flaky auth", "Release notes", "auth is flaky"]);
+
+        provider.calls.borrow_mut().clear();
+        app.semantic_search(&provider, "notes", false, 1)
+            .expect("search");
+        assert_eq!(*provider.calls.borrow(), vec!["notes"]);
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn endpoint_must_be_set_in_this_clone() {
+        let (root, app) = open_app();
+        assert!(app.embedding_endpoint().is_err());
+        app.config_set(
+            "semantic.endpoint",
+            "http://localhost:11434/v1/embeddings",
+            crate::app::ConfigScope::Repo,
+        )
+        .expect("repo config");
+        assert!(app.embedding_endpoint().is_err());
+        app.config_set(
+            "semantic.endpoint",
+            "http://localhost:11434/v1/embeddings",
+            crate::app::ConfigScope::Local,
+        )
+        .expect("local config");
+        let endpoint = app.embedding_endpoint().expect("endpoint");
+        assert_eq!(endpoint.endpoint, "http://localhost:11434/v1/embeddings");
+        assert!(app
+            .config_set(
+                "semantic.endpoint",
+                "ftp://x",
+                crate::app::ConfigScope::Local
+            )
+            .is_err());
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,102 @@
+use super::{App, AppError, ConfigScope};
+
+fn open_app() -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-settings-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+#[test]
+fn config_list_reports_seeded_defaults() {
+    let (root, app) = open_app();
+    let entries = app.config_list().expect("list");
+    assert_eq!(entries.len(), 29);
+    let policy = entries
+        .iter()
+        .find(|e| e.key == "sync_policy")
+        .expect("sync_policy listed");
+    assert_eq!(policy.value, "auto");
+    assert_eq!(policy.source, "default");
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn config_set_validates_and_normalizes_values() {
+    let (root, app) = open_app();
+    let entry = app
+        .config_set("sync_policy", " NEVER ", ConfigScope::Local)
+        .expect("set policy");
+    assert_eq!(entry.value, "never");
+    assert_eq!(entry.source, "local");
+    assert_eq!(app.config_get("sync_policy").expect("get").value, "never");
+
+    let entry = app
+        .config_set("hot_window_days", "14", ConfigScope::Local)
+        .expect("set days");
+    assert_eq!(entry.value, "14");
+    assert_eq!(crate::db::get_hot_window_days(&app.conn).expect("days"), 14);
+
+    for (key, value) in [("sync_policy", "sometimes"), ("hot_window_days", "-1")] {
+        let err = app
+            .config_set(key, value, ConfigScope::Local)
+            .expect_err("invalid value");
+        assert!(
+            matches!(&err, AppError::InvalidArgument(msg) if msg.contains(key)),
+            "{err}"
+        );
+    }
+    let err = app.config_get("nope").expect_err("unknown key");
+    assert!(matches!(err, AppError::InvalidArgument(msg) if msg.contains("sync_policy")));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn repo_config_applies_unless_overridden_locally() {
+    let (root, app) = open_app();
+    let entry = app
+        .config_set("hot_window_days", "30", ConfigScope::Repo)
+        .expect("set repo value");
+    assert_eq!((entry.value.as_str(), entry.source), ("30", "repo"));
+    let raw = std::fs::read_to_string(root.join(".knots/config.toml")).expect("config file");
+    assert!(raw.contains("version = 1"), "{raw}");
+    assert!(raw.contains("hot_window_days = 30"), "{raw}");
+    assert_eq!(crate::db::get_hot_window_days(&app.conn).expect("days"), 30);
+
+    app.config_set("hot_window_days", "3", ConfigScope::Local)
+        .expect("local override");
+    app.apply_repo_config().expect("reapply");
+    let entry = app.config_get("hot_window_days").expect("get");
+    assert_eq!((entry.value.as_str(), entry.source), ("3", "local"));
+
+    let entry = app
+        .config_unset("hot_window_days", ConfigScope::Local)
+        .expect("drop override");
+    assert_eq!((entry.value.as_str(), entry.source), ("30", "repo"));
+    let entry = app
+        .config_unset("hot_window_days", ConfigScope::Repo)
+        .expect("drop repo value");
+    assert_eq!((entry.value.as_str(), entry.source), ("7", "default"));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn repo_config_written_by_another_clone_is_picked_up_on_open() {
+    let (root, app) = open_app();
+    drop(app);
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "version = 1\n\n[settings]\nsync_policy = \"never\"\npull_drift_warn_threshold = \"x\"\n",
+    )
+    .expect("write config");
+    let db_path = root.join(".knots/cache/state.sqlite");
+    let app = App::open(db_path.to_str().expect("utf8"), root.clone()).expect("reopen");
+    let policy = app.config_get("sync_policy").expect("get");
+    assert_eq!((policy.value.as_str(), policy.source), ("never", "repo"));
+    let drift = app.config_get("pull_drift_warn_threshold").expect("get");
+    assert_eq!(drift.source, "default");
+    let _ = std::fs::remove_dir_all(root);
+}
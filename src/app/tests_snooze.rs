@@ -0,0 +1,109 @@
+use super::{App, AppError, StateActorMetadata};
+
+fn open_app() -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-snooze-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn make_due(app: &App, id: &str) {
+    app.conn
+        .execute(
+            "UPDATE knot_hot SET snoozed_until = '2000-01-01T00:00:00Z' WHERE id = ?1",
+            [id],
+        )
+        .expect("snooze should be backdated");
+}
+
+#[test]
+fn snooze_defers_knot_with_wake_time() {
+    let (root, app) = open_app();
+    let knot = app.create_knot("Later", None, None, None).expect("create");
+    let snoozed = app
+        .snooze_knot(&knot.id, "2999-09-01", StateActorMetadata::default())
+        .expect("snooze");
+    assert_eq!(snoozed.state, "deferred");
+    assert_eq!(
+        snoozed.deferred_from_state.as_deref(),
+        Some(knot.state.as_str())
+    );
+    assert_eq!(
+        snoozed.snoozed_until.as_deref(),
+        Some("2999-09-01T00:00:00Z")
+    );
+
+    let err = app
+        .snooze_knot(&knot.id, "2001-01-01", StateActorMetadata::default())
+        .expect_err("past wake time");
+    assert!(matches!(err, AppError::InvalidArgument(_)));
+    let err = app
+        .snooze_knot(&knot.id, "next week", StateActorMetadata::default())
+        .expect_err("bad wake time");
+    assert!(
+        matches!(err, AppError::InvalidArgument(ref message) if message.contains("YYYY-MM-DD"))
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn wake_resumes_due_knots_and_clears_snooze() {
+    let (root, app) = open_app();
+    let due = app
+        .create_knot("Due", None, None, None)
+        .expect("create due");
+    let later = app
+        .create_knot("Later", None, None, None)
+        .expect("create later");
+    for knot in [&due, &later] {
+        app.snooze_knot(
+            &knot.id,
+            "2999-09-01T12:00:00+02:00",
+            StateActorMetadata::default(),
+        )
+        .expect("snooze");
+    }
+    make_due(&app, &due.id);
+
+    let woken = app.wake_snoozed_knots().expect("wake");
+    assert_eq!(woken.len(), 1);
+    assert_eq!(woken[0].id, due.id);
+    assert_eq!(woken[0].state, due.state);
+    assert_eq!(woken[0].snoozed_until, None);
+
+    let later = app
+        .show_knot(&later.id)
+        .expect("show")
+        .expect("later exists");
+    assert_eq!(later.state, "deferred");
+    assert_eq!(later.snoozed_until.as_deref(), Some("2999-09-01T10:00:00Z"));
+    assert!(app.wake_snoozed_knots().expect("second wake").is_empty());
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn manual_resume_clears_snooze_in_replayed_history() {
+    let (root, app) = open_app();
+    let knot = app.create_knot("Resume", None, None, None).expect("create");
+    app.snooze_knot(&knot.id, "2999-09-01", StateActorMetadata::default())
+        .expect("snooze");
+    let resumed = app
+        .set_state(&knot.id, &knot.state, false, None)
+        .expect("manual resume");
+    assert_eq!(resumed.snoozed_until, None);
+
+    let replayed = super::rehydrate::rehydrate_from_events(
+        &app.store_paths.root,
+        &knot.id,
+        resumed.title.clone(),
+        resumed.state.clone(),
+        resumed.updated_at.clone(),
+    )
+    .expect("rehydrate");
+    assert_eq!(replayed.snoozed_until, None);
+    assert_eq!(replayed.state, knot.state);
+    let _ = std::fs::remove_dir_all(root);
+}
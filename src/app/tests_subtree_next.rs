@@ -0,0 +1,62 @@
+use super::{App, StateActorMetadata};
+
+fn open_app() -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-subtree-next-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+#[test]
+fn advance_children_moves_eligible_children_and_reports_blocked_ones() {
+    let (root, app) = open_app();
+    let epic = app.create_knot("Epic", None, None, None).expect("epic");
+    let ready = app.create_knot("Ready", None, None, None).expect("ready");
+    let waiting = app
+        .create_knot("Waiting", None, None, None)
+        .expect("waiting");
+    let parked = app.create_knot("Parked", None, None, None).expect("parked");
+    let done = app.create_knot("Done", None, None, None).expect("done");
+    let blocker = app
+        .create_knot("Blocker", None, None, None)
+        .expect("blocker");
+    for child in [&ready, &waiting, &parked, &done] {
+        app.add_edge(&epic.id, "parent_of", &child.id)
+            .expect("parent edge");
+    }
+    app.add_edge(&waiting.id, "blocked_by", &blocker.id)
+        .expect("blocked_by edge");
+    app.set_state(&parked.id, "deferred", false, None)
+        .expect("defer");
+    app.set_state(&done.id, "abandoned", true, None)
+        .expect("abandon");
+
+    let report = app
+        .advance_children(&epic.id, StateActorMetadata::default())
+        .expect("advance children");
+    assert_eq!(report.parent, epic.id);
+    assert_eq!(report.advanced.len(), 1);
+    assert_eq!(report.advanced[0].id, ready.id);
+    assert_eq!(report.advanced[0].previous_state, ready.state);
+    assert_ne!(report.advanced[0].state, ready.state);
+
+    let reasons: Vec<_> = report
+        .blocked
+        .iter()
+        .map(|child| (child.id.as_str(), child.reason.as_str()))
+        .collect();
+    assert_eq!(reasons.len(), 2, "{reasons:?}");
+    assert!(reasons
+        .iter()
+        .any(|(id, reason)| *id == waiting.id && reason.starts_with("blocked by ")));
+    assert!(reasons
+        .iter()
+        .any(|(id, reason)| *id == parked.id && *reason == "knot is deferred"));
+
+    let waiting = app.show_knot(&waiting.id).expect("show").expect("waiting");
+    assert_eq!(waiting.state, ready.state);
+    let _ = std::fs::remove_dir_all(root);
+}
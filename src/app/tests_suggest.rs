@@ -0,0 +1,84 @@
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+
+use super::suggest::score_candidate;
+use super::{App, UpdateKnotPatch};
+
+fn open_app(config: &str) -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-suggest-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    std::fs::write(root.join(".knots/config.toml"), config).expect("repo config should write");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn patch(app: &App, id: &str, priority: Option<i64>, tags: &[&str]) {
+    app.update_knot(
+        id,
+        UpdateKnotPatch {
+            priority,
+            add_tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("patch knot");
+}
+
+#[test]
+fn ranks_by_affinity_unblocking_and_priority_within_capacity() {
+    let (root, app) = open_app("[agents.codex]\ntags = [\"area/*\"]\ncapacity = 2\n");
+    let plain = app.create_knot("Plain", None, None, None).expect("plain");
+    let urgent = app.create_knot("Urgent", None, None, None).expect("urgent");
+    let backend = app
+        .create_knot("Backend", None, None, None)
+        .expect("backend");
+    let waiting = app
+        .create_knot("Waiting", None, None, None)
+        .expect("waiting");
+    patch(&app, &urgent.id, Some(0), &[]);
+    patch(&app, &backend.id, Some(3), &["area/backend"]);
+    app.add_edge(&waiting.id, "blocked_by", &backend.id)
+        .expect("edge");
+
+    let candidates = vec![
+        app.show_knot(&plain.id).expect("show").expect("plain"),
+        app.show_knot(&urgent.id).expect("show").expect("urgent"),
+        app.show_knot(&backend.id).expect("show").expect("backend"),
+    ];
+    let report = app
+        .suggest_for_agent("Codex", candidates, 5)
+        .expect("suggest");
+    assert_eq!(report.capacity, Some(2));
+    assert_eq!(report.active, 0);
+    let ids: Vec<_> = report.suggestions.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, [backend.id.as_str(), urgent.id.as_str()]);
+    assert_eq!(
+        report.suggestions[0].reasons,
+        ["priority 3", "unblocks 1 knot(s)", "matches area/backend"]
+    );
+    assert_eq!(report.suggestions[0].score, 20 + 15 + 20);
+    assert_eq!(report.suggestions[1].score, 50);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn unknown_agents_rank_without_affinity_or_capacity() {
+    let (root, app) = open_app("");
+    let knot = app.create_knot("Old", None, None, None).expect("create");
+    let mut view = app.show_knot(&knot.id).expect("show").expect("knot");
+    let now = OffsetDateTime::now_utc();
+    view.created_at = Some((now - Duration::days(90)).format(&Rfc3339).expect("format"));
+    let (score, reasons) = score_candidate(&view, now, 0, &[]);
+    assert_eq!(score, 30);
+    assert_eq!(reasons, ["waiting 30 day(s)"]);
+
+    let report = app
+        .suggest_for_agent("claude", vec![view], 5)
+        .expect("suggest");
+    assert_eq!(report.capacity, None);
+    assert_eq!(report.suggestions.len(), 1);
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,169 @@
+use std::cell::RefCell;
+use std::path::PathBuf;
+
+use super::summarize::SUMMARY_AGENT;
+use super::types::UpdateKnotPatch;
+use super::{App, ConfigScope};
+use crate::domain::metadata::MetadataEntryInput;
+use crate::summarize::Summarizer;
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-summarize-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn add_note(app: &App, id: &str, content: &str) {
+    let patch = UpdateKnotPatch {
+        add_note: Some(MetadataEntryInput {
+            content: content.to_string(),
+            ..MetadataEntryInput::default()
+        }),
+        ..UpdateKnotPatch::default()
+    };
+    app.update_knot(id, patch).expect("note should be added");
+}
+
+/// Answers with a fixed reply, or an error, recording every prompt.
+struct FakeSummarizer {
+    reply: Result<String, String>,
+    prompts: RefCell<Vec<String>>,
+}
+
+impl FakeSummarizer {
+    fn new(reply: Result<&str, &str>) -> Self {
+        Self {
+            reply: reply.map(str::to_string).map_err(str::to_string),
+            prompts: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl Summarizer for FakeSummarizer {
+    fn model(&self) -> &str {
+        "fake-llm"
+    }
+
+    fn summarize(&self, _system: &str, prompt: &str) -> Result<String, String> {
+        self.prompts.borrow_mut().push(prompt.to_string());
+        self.reply.clone()
+    }
+}
+
+#[test]
+fn extractive_summaries_add_capsules_and_roll_forward() {
+    let (root, app) = open_app();
+    let knot = app
+        .create_knot("Cache layer", None, None, None)
+        .expect("create");
+    assert!(app.summarize_knot(&knot.id, None, false).is_err());
+    add_note(&app, &knot.id, "The cache drops writes under load.");
+    add_note(&app, &knot.id, "Cache writes now go through a retry queue.");
+
+    let preview = app.summarize_knot(&knot.id, None, true).expect("dry run");
+    assert!(!preview.written);
+    let shown = app.show_knot(&knot.id).expect("show").expect("knot");
+    assert!(shown.handoff_capsules.is_empty());
+
+    let summary = app.summarize_knot(&knot.id, None, false).expect("summary");
+    assert_eq!(summary.model, "extractive");
+    assert_eq!(summary.entries, 2);
+    assert_eq!(summary.content, preview.content);
+    assert!(summary
+        .content
+        .contains("- Cache writes now go through a retry queue."));
+    let shown = app.show_knot(&knot.id).expect("show").expect("knot");
+    let capsule = &shown.handoff_capsules[0];
+    assert_eq!(capsule.agentname, SUMMARY_AGENT);
+    assert_eq!(capsule.model, "extractive");
+    assert_eq!(capsule.content, summary.content);
+
+    let err = app
+        .summarize_knot(&knot.id, None, false)
+        .expect_err("nothing new");
+    assert!(err.to_string().contains("nothing to summarize"));
+    add_note(
+        &app,
+        &knot.id,
+        "Load test passes with the retry queue enabled.",
+    );
+    let next = app.summarize_knot(&knot.id, None, false).expect("summary");
+    assert_eq!(next.entries, 2);
+    assert!(next
+        .content
+        .starts_with("Summary of 1 note and 1 handoff capsule"));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn llm_summaries_are_used_unless_notes_are_sensitive_or_the_call_fails() {
+    let (root, app) = open_app();
+    let knot = app
+        .create_knot("Cache layer", None, None, None)
+        .expect("create");
+    add_note(&app, &knot.id, "The cache drops writes under load.");
+
+    let llm = FakeSummarizer::new(Ok("- retry queue landed"));
+    let summary = app
+        .summarize_knot(&knot.id, Some(&llm), true)
+        .expect("summary");
+    assert_eq!(summary.model, "fake-llm");
+    assert_eq!(summary.content, "- retry queue landed");
+    let prompt = llm.prompts.borrow()[0].clone();
+    assert!(prompt.contains("Work item: Cache layer"));
+    assert!(prompt.contains("The cache drops writes under load."));
+
+    let failing = FakeSummarizer::new(Err("summarize request failed"));
+    let fallback = app
+        .summarize_knot(&knot.id, Some(&failing), true)
+        .expect("summary");
+    assert_eq!(fallback.model, "extractive");
+
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "sensitive_fields = [\"notes\"]\n",
+    )
+    .expect("repo config");
+    let private = FakeSummarizer::new(Ok("- leaked"));
+    let local = app
+        .summarize_knot(&knot.id, Some(&private), true)
+        .expect("summary");
+    assert_eq!(local.model, "extractive");
+    assert!(private.prompts.borrow().is_empty());
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn summarize_endpoint_is_only_honored_when_set_locally() {
+    let (root, app) = open_app();
+    assert!(app.summarize_endpoint().expect("endpoint").is_none());
+    app.config_set(
+        "summarize.endpoint",
+        "http://localhost:11434/api/chat",
+        ConfigScope::Repo,
+    )
+    .expect("repo setting");
+    assert!(app.summarize_endpoint().expect("endpoint").is_none());
+    app.config_set(
+        "summarize.endpoint",
+        "http://localhost:11434/api/chat",
+        ConfigScope::Local,
+    )
+    .expect("local setting");
+    app.config_set("summarize.model", "llama3.2", ConfigScope::Local)
+        .expect("model");
+    let endpoint = app
+        .summarize_endpoint()
+        .expect("endpoint")
+        .expect("configured");
+    assert_eq!(endpoint.endpoint, "http://localhost:11434/api/chat");
+    assert_eq!(endpoint.model, "llama3.2");
+    assert!(app
+        .config_set("summarize.endpoint", "localhost:11434", ConfigScope::Local)
+        .is_err());
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,111 @@
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use super::App;
+use crate::sync::{GitAdapter, IncrementalApplier};
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-sync-conflicts-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+/// Applies a tag event whose precondition names an etag the knot has
+/// already moved past, as a pull would.
+fn skip_stale_tag(app: &App, root: &std::path::Path, knot_id: &str, tag: &str) {
+    let worktree = root.join("incoming");
+    let relative = PathBuf::from(".knots/events/2026/10/16/1001-knot.tag_add.json");
+    let absolute = worktree.join(&relative);
+    std::fs::create_dir_all(absolute.parent().expect("event parent should exist"))
+        .expect("event dir should be creatable");
+    let event = json!({
+        "event_id": "1001",
+        "occurred_at": "2026-10-16T12:00:00Z",
+        "knot_id": knot_id,
+        "type": "knot.tag_add",
+        "data": {"tag": tag},
+        "precondition": {"profile_etag": "etag-old"},
+    });
+    std::fs::write(&absolute, event.to_string()).expect("event should write");
+    IncrementalApplier::new_with_builtins(&app.conn, worktree, GitAdapter::new())
+        .apply_event_files(Vec::new(), vec![relative])
+        .expect("stale event should be skipped");
+}
+
+#[test]
+fn stale_events_are_listed_and_can_be_reapplied_with_force() {
+    let (root, app) = open_app();
+    let knot = app
+        .create_knot(
+            "Conflicted",
+            None,
+            Some("ready_for_planning"),
+            Some("default"),
+        )
+        .expect("knot should be created");
+    skip_stale_tag(&app, &root, &knot.id, "late");
+
+    let conflicts = app.sync_conflicts(None).expect("conflicts should list");
+    assert_eq!(conflicts.len(), 1);
+    let conflict = &conflicts[0];
+    assert_eq!(conflict.knot_id, knot.id);
+    assert_eq!(conflict.event_type, "knot.tag_add");
+    assert_eq!(conflict.expected_etag, "etag-old");
+    assert_eq!(conflict.actual_etag, knot.profile_etag);
+    assert!(conflict.reason.contains("stale precondition"));
+    assert_eq!(
+        app.sync_conflicts(Some(&knot.id)).expect("filtered").len(),
+        1
+    );
+
+    let refused = app
+        .reapply_sync_conflict(conflict.id, false)
+        .expect_err("reapply without force should fail");
+    assert!(refused.to_string().contains("--force"));
+
+    app.reapply_sync_conflict(conflict.id, true)
+        .expect("forced reapply should succeed");
+    let shown = app
+        .show_knot(&knot.id)
+        .expect("show")
+        .expect("knot should exist");
+    assert_eq!(shown.tags, vec!["late".to_string()]);
+    assert!(app.sync_conflicts(None).expect("list").is_empty());
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn dismissing_drops_a_conflict_without_applying_it() {
+    let (root, app) = open_app();
+    let knot = app
+        .create_knot(
+            "Dismissed",
+            None,
+            Some("ready_for_planning"),
+            Some("default"),
+        )
+        .expect("knot should be created");
+    skip_stale_tag(&app, &root, &knot.id, "ignored");
+    let id = app.sync_conflicts(None).expect("list")[0].id;
+
+    app.dismiss_sync_conflict(id)
+        .expect("dismiss should succeed");
+    assert!(app.sync_conflicts(None).expect("list").is_empty());
+    let shown = app
+        .show_knot(&knot.id)
+        .expect("show")
+        .expect("knot should exist");
+    assert!(shown.tags.is_empty());
+    let missing = app
+        .dismiss_sync_conflict(id)
+        .expect_err("second dismiss should fail");
+    assert!(missing.to_string().contains("no sync conflict"));
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,59 @@
+use super::{App, AppError, UpdateKnotPatch};
+
+fn open_app(config: Option<&str>) -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-tags-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    if let Some(config) = config {
+        std::fs::write(root.join(".knots/config.toml"), config)
+            .expect("repo config should be writable");
+    }
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn add_tags(app: &App, id: &str, tags: &[&str]) -> Result<super::KnotView, AppError> {
+    app.update_knot(
+        id,
+        UpdateKnotPatch {
+            add_tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..UpdateKnotPatch::default()
+        },
+    )
+}
+
+#[test]
+fn tag_groups_collect_counts_by_namespace() {
+    let (root, app) = open_app(None);
+    let a = app.create_knot("A", None, None, None).expect("create a");
+    let b = app.create_knot("B", None, None, None).expect("create b");
+    add_tags(&app, &a.id, &["area/backend", "release"]).expect("tag a");
+    add_tags(&app, &b.id, &["Area/Backend", "area/ui"]).expect("tag b");
+
+    let groups = app.tag_groups().expect("tag groups");
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].namespace, None);
+    assert_eq!(groups[0].tags[0].tag, "release");
+    assert_eq!(groups[1].namespace.as_deref(), Some("area"));
+    let counts: Vec<_> = groups[1]
+        .tags
+        .iter()
+        .map(|count| (count.tag.as_str(), count.knots))
+        .collect();
+    assert_eq!(counts, [("area/backend", 2), ("area/ui", 1)]);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn declared_namespaces_restrict_namespaced_tags_on_update() {
+    let (root, app) = open_app(Some("tag_namespaces = [\"area\"]\n"));
+    let knot = app.create_knot("A", None, None, None).expect("create");
+    add_tags(&app, &knot.id, &["area/backend", "release"]).expect("allowed tags");
+    let err = add_tags(&app, &knot.id, &["customer/acme"]).expect_err("undeclared namespace");
+    assert!(
+        matches!(err, AppError::InvalidArgument(ref message) if message.contains("customer/acme"))
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -47,6 +47,8 @@ fn update_can_abandon_parent_with_deferred_child_without_auto_resolution() {
                 title: None,
                 description: None,
                 acceptance: None,
+                estimate: None,
+                custom_fields: Vec::new(),
                 priority: None,
                 status: Some("abandoned".to_string()),
                 knot_type: None,
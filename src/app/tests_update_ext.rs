@@ -30,6 +30,8 @@ fn empty_patch() -> UpdateKnotPatch {
         title: None,
         description: None,
         acceptance: None,
+        estimate: None,
+        custom_fields: Vec::new(),
         priority: None,
         status: None,
         knot_type: None,
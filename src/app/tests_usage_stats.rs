@@ -0,0 +1,74 @@
+use super::usage_stats::percentile;
+use super::{App, ConfigScope};
+use crate::db::{insert_usage_metric, UsageMetricRecord};
+
+fn open_app() -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-usage-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn record(app: &App, command: &str, duration_ms: u64, ok: bool, sync_files: Option<u64>) {
+    insert_usage_metric(
+        &app.conn,
+        &UsageMetricRecord {
+            command: command.to_string(),
+            started_at: "2026-10-16T12:00:00Z".to_string(),
+            duration_ms,
+            ok,
+            sync_files,
+        },
+    )
+    .expect("usage metric should insert");
+}
+
+#[test]
+fn percentile_uses_nearest_rank() {
+    assert_eq!(percentile(&[], 95), None);
+    assert_eq!(percentile(&[7], 50), Some(7));
+    let values: Vec<u64> = (1..=20).collect();
+    assert_eq!(percentile(&values, 50), Some(10));
+    assert_eq!(percentile(&values, 95), Some(19));
+    assert_eq!(percentile(&values, 100), Some(20));
+}
+
+#[test]
+fn usage_report_groups_runs_by_command() {
+    let (root, app) = open_app();
+    let report = app.usage_report(None).expect("report");
+    assert!(!report.enabled);
+    assert!(report.commands.is_empty());
+    assert_eq!(report.sync_auto_budget_ms, 750);
+
+    app.config_set("metrics.local", "true", ConfigScope::Local)
+        .expect("enable metrics");
+    for ms in [10, 30, 20] {
+        record(&app, "ls", ms, true, None);
+    }
+    record(&app, "sync", 400, true, Some(5));
+    record(&app, "sync", 900, false, None);
+    record(&app, "sync", 300, true, Some(2));
+
+    let report = app.usage_report(None).expect("report");
+    assert!(report.enabled);
+    let names: Vec<&str> = report.commands.iter().map(|c| c.command.as_str()).collect();
+    assert_eq!(names, vec!["ls", "sync"]);
+    let ls = &report.commands[0];
+    assert_eq!((ls.runs, ls.failures), (3, 0));
+    assert_eq!((ls.p50_ms, ls.p95_ms, ls.max_ms), (20, 30, 30));
+    assert_eq!(ls.sync_files_p50, None);
+    let sync = &report.commands[1];
+    assert_eq!((sync.runs, sync.failures), (3, 1));
+    assert_eq!(
+        (sync.sync_files_p50, sync.sync_files_p95),
+        (Some(2), Some(5))
+    );
+
+    let only_sync = app.usage_report(Some("sync")).expect("filtered report");
+    assert_eq!(only_sync.commands.len(), 1);
+    let _ = std::fs::remove_dir_all(root);
+}
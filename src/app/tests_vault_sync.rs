@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+
+use super::{App, UpdateKnotPatch};
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-vault-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+#[test]
+fn sync_writes_linked_notes_and_ingests_note_edits() {
+    let (root, app) = open_app();
+    let vault = root.join("vault");
+    let parent = app
+        .create_knot("Epic", Some("the plan"), None, None)
+        .expect("create");
+    let child = app.create_knot("Task", None, None, None).expect("create");
+    app.add_edge(&parent.id, "parent_of", &child.id)
+        .expect("edge");
+
+    let dry = app.sync_vault(&vault, true).expect("dry run");
+    assert_eq!(dry.written.len(), 2);
+    assert!(!vault.exists());
+
+    let first = app.sync_vault(&vault, false).expect("sync");
+    assert_eq!(first.written.len(), 2);
+    let parent_path = vault.join(format!("{}.md", parent.id));
+    let note = std::fs::read_to_string(&parent_path).expect("parent note");
+    assert!(note.contains("\n# Epic\n\nthe plan\n"), "{note}");
+    assert!(note.contains(&format!("- child: [[{}|Task]]\n", child.id)));
+    let child_note =
+        std::fs::read_to_string(vault.join(format!("{}.md", child.id))).expect("child note");
+    assert!(child_note.contains(&format!("- parent: [[{}|Epic]]\n", parent.id)));
+    let again = app.sync_vault(&vault, false).expect("resync");
+    assert!(again.written.is_empty() && again.ingested.is_empty());
+    assert_eq!(again.unchanged, 2);
+
+    let edited = note
+        .replace("\n# Epic\n", "\n# Epic, renamed\n")
+        .replace("the plan", "the new plan")
+        .replace("tags: []", "tags:\n  - obsidian");
+    std::fs::write(&parent_path, edited).expect("edit note");
+    let ingested = app.sync_vault(&vault, false).expect("ingest");
+    assert_eq!(ingested.ingested.len(), 1);
+    assert_eq!(
+        ingested.ingested[0].fields,
+        vec!["title", "tags", "description"]
+    );
+    let knot = app.show_knot(&parent.id).expect("show").expect("knot");
+    assert_eq!(knot.title, "Epic, renamed");
+    assert_eq!(knot.description.as_deref(), Some("the new plan"));
+    assert_eq!(knot.tags, vec!["obsidian"]);
+    let note = std::fs::read_to_string(&parent_path).expect("rewritten note");
+    assert!(note.contains(&format!("updated_at: {}\n", knot.updated_at)));
+
+    app.update_knot(
+        &parent.id,
+        UpdateKnotPatch {
+            title: Some("Renamed in knots".to_string()),
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("update");
+    let refreshed = app.sync_vault(&vault, false).expect("refresh");
+    assert!(refreshed.ingested.is_empty());
+    assert!(refreshed.written.contains(&parent.id));
+    let note = std::fs::read_to_string(&parent_path).expect("refreshed note");
+    assert!(note.contains("\n# Renamed in knots\n"));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn sensitive_descriptions_stay_out_of_the_vault() {
+    let (root, app) = open_app();
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "sensitive_fields = [\"description\"]\n",
+    )
+    .expect("repo config");
+    let vault = root.join("vault");
+    let knot = app
+        .create_knot("Public", Some("private body"), None, None)
+        .expect("create");
+
+    app.sync_vault(&vault, false).expect("sync");
+    let path = vault.join(format!("{}.md", knot.id));
+    let note = std::fs::read_to_string(&path).expect("note");
+    assert!(!note.contains("private body"));
+    assert!(note.contains("hidden: [\"description\"]\n"));
+
+    std::fs::write(&path, format!("{note}\nleaked edit\n")).expect("edit note");
+    let summary = app.sync_vault(&vault, false).expect("resync");
+    assert!(summary.ingested.is_empty());
+    let shown = app.show_knot(&knot.id).expect("show").expect("knot");
+    assert_eq!(shown.description.as_deref(), Some("private body"));
+    let _ = std::fs::remove_dir_all(root);
+}
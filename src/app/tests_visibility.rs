@@ -0,0 +1,72 @@
+use crate::domain::metadata::MetadataEntryInput;
+
+use super::{App, UpdateKnotPatch};
+
+fn open_app(config: &str) -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-visibility-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    std::fs::write(root.join(".knots/config.toml"), config).expect("repo config should write");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+#[test]
+fn export_json_drops_sensitive_fields_unless_included() {
+    let (root, app) = open_app(
+        r#"sensitive_fields = ["description", "notes", "fields.customer"]
+
+[fields.customer]
+type = "string"
+
+[fields.team]
+type = "string"
+"#,
+    );
+    let knot = app
+        .create_knot("Call back", Some("phone 555-0100"), None, None)
+        .expect("create");
+    let knot = app
+        .update_knot(
+            &knot.id,
+            UpdateKnotPatch {
+                custom_fields: vec!["customer=Jane Roe".to_string(), "team=billing".to_string()],
+                add_note: Some(MetadataEntryInput {
+                    content: "prefers evenings".to_string(),
+                    ..MetadataEntryInput::default()
+                }),
+                ..UpdateKnotPatch::default()
+            },
+        )
+        .expect("update");
+
+    let hidden = app
+        .knots_for_export(std::slice::from_ref(&knot), false)
+        .expect("export");
+    let hidden = &hidden[0];
+    assert_eq!(hidden["title"], "Call back");
+    assert!(hidden.get("description").is_none() && hidden.get("notes").is_none());
+    assert!(hidden["custom_fields"].get("customer").is_none());
+    assert_eq!(hidden["custom_fields"]["team"], "billing");
+
+    let full = app.knots_for_export(&[knot], true).expect("export");
+    assert_eq!(full[0]["description"], "phone 555-0100");
+    assert_eq!(full[0]["custom_fields"]["customer"], "Jane Roe");
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn unknown_sensitive_fields_are_rejected() {
+    let (root, app) = open_app("sensitive_fields = [\"descriptoin\"]\n");
+    let knot = app.create_knot("Typo", None, None, None).expect("create");
+    let err = app
+        .knots_for_export(std::slice::from_ref(&knot), false)
+        .expect_err("typo should not silently expose the field");
+    assert!(err.to_string().contains("'descriptoin'"), "{err}");
+    assert!(app.knots_for_export(&[knot], true).is_ok());
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+
+use super::{App, UpdateKnotPatch};
+use crate::domain::metadata::MetadataEntryInput;
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-watch-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+#[test]
+fn watched_changes_land_in_the_inbox_until_acknowledged() {
+    let (root, app) = open_app();
+    let watched = app
+        .create_knot("Watched", None, None, None)
+        .expect("create");
+    let ignored = app
+        .create_knot("Ignored", None, None, None)
+        .expect("create");
+    let added = app.set_watch(&watched.id, true).expect("watch");
+    assert_eq!(added.title.as_deref(), Some("Watched"));
+    assert!(app.set_watch("missing-knot", true).is_err());
+    assert_eq!(app.watched_knots().expect("list"), vec![added]);
+
+    let baseline = app.pull_digest_baseline().expect("baseline");
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    app.set_state(&watched.id, "planning", false, None)
+        .expect("advance");
+    app.set_state(&ignored.id, "planning", false, None)
+        .expect("advance");
+    let digest = app.pull_digest(&baseline).expect("digest");
+    let items = app.record_watched_changes(&digest).expect("record");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].knot_id, watched.id);
+    assert_eq!(items[0].kind, "watch");
+    assert_eq!(
+        items[0].summary,
+        format!("Watched: {} -> planning", watched.state)
+    );
+    let again = app.record_watched_changes(&digest).expect("record");
+    assert_eq!(app.inbox().expect("inbox").len(), 2);
+
+    assert_eq!(app.ack_inbox(&[items[0].id]).expect("ack"), 1);
+    assert_eq!(app.inbox().expect("inbox"), again);
+    assert_eq!(app.ack_inbox(&[]).expect("ack all"), 1);
+    assert!(app.inbox().expect("inbox").is_empty());
+
+    app.set_watch(&watched.id, false).expect("unwatch");
+    assert!(app.watched_knots().expect("list").is_empty());
+    assert!(app
+        .record_watched_changes(&digest)
+        .expect("record")
+        .is_empty());
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn new_notes_that_mention_the_handle_file_mention_items() {
+    let (root, app) = open_app();
+    let knot = app.create_knot("Parser", None, None, None).expect("create");
+    app.config_set("inbox.handle", "@Bob", super::ConfigScope::Local)
+        .expect("handle");
+    assert_eq!(app.inbox_handle().expect("handle").as_deref(), Some("bob"));
+    let baseline = app.pull_digest_baseline().expect("baseline");
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    let note = |content: &str, username: &str| UpdateKnotPatch {
+        add_note: Some(MetadataEntryInput {
+            content: content.to_string(),
+            username: Some(username.to_string()),
+            ..MetadataEntryInput::default()
+        }),
+        ..UpdateKnotPatch::default()
+    };
+    app.update_knot(&knot.id, note("@bob which grammar?", "alice"))
+        .expect("note");
+    app.update_knot(&knot.id, note("reminder for @bob", "bob"))
+        .expect("own note");
+    app.update_knot(&knot.id, note("cc @carol", "alice"))
+        .expect("other note");
+
+    let digest = app.pull_digest(&baseline).expect("digest");
+    assert_eq!(digest.new_notes.len(), 3);
+    let items = app.record_pull_inbox(&digest).expect("record");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].kind, "mention");
+    assert_eq!(
+        items[0].summary,
+        "alice mentioned you in a note on Parser: @bob which grammar?"
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,198 @@
+use super::{App, AppError, UpdateKnotPatch};
+
+fn open_app(config: &str) -> (std::path::PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-wip-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    std::fs::write(root.join(".knots/config.toml"), config).expect("repo config should write");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+#[test]
+fn transitions_into_a_full_state_fail_unless_forced() {
+    let (root, app) = open_app("[wip_limits]\nplanning = 1\n");
+    let first = app.create_knot("First", None, None, None).expect("first");
+    let second = app.create_knot("Second", None, None, None).expect("second");
+    let third = app.create_knot("Third", None, None, None).expect("third");
+    app.set_state(&first.id, "planning", false, None)
+        .expect("first fits under the limit");
+
+    let err = app
+        .set_state(&second.id, "planning", false, None)
+        .expect_err("limit reached");
+    assert!(
+        matches!(err, AppError::WipLimitReached { ref state, limit: 1 } if state == "planning")
+    );
+    assert!(err.to_string().contains("--force"), "{err}");
+    let err = app
+        .update_knot(
+            &third.id,
+            UpdateKnotPatch {
+                status: Some("planning".to_string()),
+                ..UpdateKnotPatch::default()
+            },
+        )
+        .expect_err("update --status respects the limit too");
+    assert!(matches!(err, AppError::WipLimitReached { .. }));
+
+    app.set_state(&second.id, "planning", true, None)
+        .expect("force overrides the limit");
+    let usage = app.wip_usage().expect("usage");
+    assert_eq!(usage.len(), 1);
+    assert_eq!(usage[0].state, "planning");
+    assert_eq!(usage[0].active, 2);
+    assert_eq!(usage[0].limit, 1);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn unlimited_states_and_repos_without_limits_are_unaffected() {
+    let (root, app) = open_app("[wip_limits]\nimplementation = 0\n");
+    let knot = app.create_knot("Plan", None, None, None).expect("create");
+    app.set_state(&knot.id, "planning", false, None)
+        .expect("planning has no limit");
+    assert_eq!(app.wip_usage().expect("usage")[0].active, 0);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn state_age_flags_knots_past_their_state_budget() {
+    let (root, app) = open_app("[state_budgets]\nready_for_planning = \"2h\"\n");
+    let knot = app.create_knot("Aging", None, None, None).expect("create");
+    let knot = app.show_knot(&knot.id).expect("show").expect("knot");
+    assert_eq!(knot.state, "ready_for_planning");
+
+    let fresh = app.state_age(&knot).expect("age");
+    assert_eq!(fresh.since, knot.created_at.clone().expect("created_at"));
+    assert_eq!(fresh.budget_seconds, Some(7_200));
+    assert!(!fresh.over_budget);
+
+    let later = time::OffsetDateTime::now_utc() + time::Duration::hours(27);
+    let stale = app.state_age_at(&knot, later).expect("later age");
+    assert!(stale.over_budget);
+    assert_eq!(stale.waited(), "1d 3h");
+    assert_eq!(stale.budget().as_deref(), Some("2h 0m"));
+
+    let claimed = app
+        .set_state(&knot.id, "planning", false, None)
+        .expect("claim");
+    let claimed = app.show_knot(&claimed.id).expect("show").expect("knot");
+    let age = app.state_age(&claimed).expect("age");
+    assert_eq!(age.since, claimed.step_history[0].started_at);
+    assert_eq!(age.budget_seconds, None);
+    let _ = std::fs::remove_dir_all(root);
+
+    let (root, app) = open_app("[state_budgets]\nplanning = \"soon\"\n");
+    let knot = app
+        .create_knot("Bad budget", None, None, None)
+        .expect("create");
+    let err = app.state_age(&knot).expect_err("bad span");
+    assert!(err.to_string().contains("state_budgets"), "{err}");
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn queue_reports_list_review_queues_and_stale_knots_for_their_profiles() {
+    use crate::repo_config::DigestTarget;
+
+    let (root, app) = open_app("[state_budgets]\nready_for_planning = \"2h\"\n");
+    let review = app
+        .create_knot(
+            "Review",
+            None,
+            Some("ready_for_plan_review"),
+            Some("autopilot"),
+        )
+        .expect("review");
+    let waiting = app
+        .create_knot("Waiting", None, None, Some("autopilot"))
+        .expect("waiting");
+    app.create_knot(
+        "Other",
+        None,
+        Some("ready_for_plan_review"),
+        Some("semiauto"),
+    )
+    .expect("other profile");
+    let target = DigestTarget {
+        profiles: vec!["autopilot".to_string()],
+        ..DigestTarget::default()
+    };
+
+    let now = app
+        .queue_report("team", &target)
+        .expect("report right away");
+    assert_eq!(now.profiles, vec!["autopilot".to_string()]);
+    assert_eq!(now.review_queue.len(), 1);
+    assert_eq!(now.review_queue[0].id, review.id);
+    assert!(now.stale.is_empty());
+
+    let later = time::OffsetDateTime::now_utc() + time::Duration::hours(3);
+    let report = app
+        .queue_report_at("team", &target, later)
+        .expect("report later");
+    let stale: Vec<_> = report.stale.iter().map(|knot| knot.id.as_str()).collect();
+    assert_eq!(stale, vec![waiting.id.as_str()]);
+    assert_eq!(report.stale[0].budget.as_deref(), Some("2h 0m"));
+
+    let week = time::OffsetDateTime::now_utc() + time::Duration::days(8);
+    let report = app
+        .queue_report_at("team", &target, week)
+        .expect("report after a week");
+    assert_eq!(report.stale.len(), 2, "stale_after defaults to 7d");
+
+    let bad = DigestTarget {
+        every: Some("daily".to_string()),
+        ..DigestTarget::default()
+    };
+    let err = app.digest_due("team", &bad).expect_err("bad span");
+    assert!(err.to_string().contains("digests.team.every"), "{err}");
+    assert!(!app
+        .digest_due("team", &DigestTarget::default())
+        .expect("no schedule"));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn age_reports_bucket_open_knots_per_state_and_flag_budget_breaches() {
+    let (root, app) = open_app("[state_budgets]\nready_for_planning = \"2h\"\n");
+    let first = app.create_knot("First", None, None, None).expect("first");
+    app.create_knot("Second", None, None, None).expect("second");
+    app.create_knot("Implementing", None, Some("ready_for_implementation"), None)
+        .expect("implementing");
+    let shipped = app
+        .create_knot("Shipped", None, Some("ready_for_implementation"), None)
+        .expect("shipped");
+    app.set_state(&shipped.id, "abandoned", false, None)
+        .expect("abandon");
+
+    let later = time::OffsetDateTime::now_utc() + time::Duration::hours(5);
+    let report = app.age_report_at(None, later).expect("report");
+    assert_eq!(report.buckets.len(), 8);
+    let states: Vec<_> = report.states.iter().map(|s| s.state.as_str()).collect();
+    assert_eq!(
+        states,
+        vec!["ready_for_implementation", "ready_for_planning"]
+    );
+    let planning = &report.states[1];
+    assert_eq!(planning.knots, 2);
+    assert_eq!(planning.counts, vec![0, 0, 2, 0, 0, 0, 0, 0]);
+    assert_eq!(planning.budget_seconds, Some(7_200));
+    assert_eq!(planning.median(), "5h 0m");
+    assert_eq!(planning.outliers.len(), 2);
+    assert_eq!(planning.outliers[0].reason, "over_budget");
+    assert!(report.states[0].outliers.is_empty());
+
+    let filtered = app
+        .age_report_at(Some("ready_for_planning"), later)
+        .expect("filtered");
+    assert_eq!(filtered.states.len(), 1);
+    assert!(filtered.states[0]
+        .outliers
+        .iter()
+        .any(|outlier| outlier.id == first.id));
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -53,6 +53,8 @@ fn home_override_round_trips_user_config_without_real_home() {
         default_profile: Some("autopilot".to_string()),
         default_quick_profile: Some("autopilot_no_planning".to_string()),
         active_project: Some("demo".to_string()),
+        theme: None,
+        cache_location: None,
     };
 
     app.write_user_config(&config)
@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::db;
+use crate::domain::metadata::MetadataEntryInput;
+use crate::locks::FileLock;
+
+use super::error::AppError;
+use super::knot_update::update_knot_locked;
+use super::types::UpdateKnotPatch;
+use super::App;
+
+/// Terminal state the source knot is left in once its history has moved.
+const MOVED_STATE: &str = "abandoned";
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct KnotTransfer {
+    pub source_id: String,
+    pub id: String,
+    pub target: PathBuf,
+    pub events_written: u64,
+    pub edges_dropped: u64,
+}
+
+impl App {
+    /// Moves knot `id` into the repo at `to`: its event history is imported
+    /// there under a new id, then the knot here is closed as `abandoned`
+    /// with a `moved_to` note. Both repos' locks are held throughout.
+    pub fn transfer_knot(&self, id: &str, to: &Path) -> Result<KnotTransfer, AppError> {
+        let id = self.resolve_knot_token(id)?;
+        let target_store = to.join(".knots");
+        if !target_store.is_dir() {
+            return Err(AppError::InvalidArgument(format!(
+                "'{}' is not a knots repo; run `kno init` there first",
+                to.display()
+            )));
+        }
+        if same_dir(&target_store, &self.store_paths.root) {
+            return Err(AppError::InvalidArgument(
+                "transfer target is this repo".to_string(),
+            ));
+        }
+
+        let _repo_guard = FileLock::acquire(&self.repo_lock_path(), Duration::from_millis(5_000))?;
+        let _cache_guard =
+            FileLock::acquire(&self.cache_lock_path(), Duration::from_millis(5_000))?;
+        let current =
+            db::get_knot_hot(&self.conn, &id)?.ok_or_else(|| AppError::NotFound(id.clone()))?;
+        let children = db::list_edges(&self.conn, &id, db::EdgeDirection::Outgoing)?
+            .into_iter()
+            .filter(|edge| edge.kind == "parent_of")
+            .count();
+        if children > 0 {
+            return Err(AppError::InvalidArgument(format!(
+                "knot '{}' has {} child knot(s); copy the tree with \
+                 `kno import knots --subtree` instead",
+                id, children
+            )));
+        }
+
+        let target_db = target_store.join("cache/state.sqlite");
+        let target = App::open(&target_db.to_string_lossy(), to.to_path_buf())?;
+        let summary = target.import_from_store(self.store_paths.root.clone(), &id, false, false)?;
+        let moved = summary
+            .knots
+            .first()
+            .map(|knot| knot.id.clone())
+            .ok_or_else(|| AppError::NotFound(id.clone()))?;
+
+        let patch = UpdateKnotPatch {
+            status: Some(MOVED_STATE.to_string()),
+            force: true,
+            add_note: Some(MetadataEntryInput {
+                content: format!("moved_to: {} ({})", moved, to.display()),
+                ..MetadataEntryInput::default()
+            }),
+            ..UpdateKnotPatch::default()
+        };
+        update_knot_locked(self, &id, current, patch, false)?;
+        Ok(KnotTransfer {
+            source_id: id,
+            id: moved,
+            target: to.to_path_buf(),
+            events_written: summary.events_written,
+            edges_dropped: summary.edges_dropped,
+        })
+    }
+}
+
+fn same_dir(left: &Path, right: &Path) -> bool {
+    match (left.canonicalize(), right.canonicalize()) {
+        (Ok(left), Ok(right)) => left == right,
+        _ => false,
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::Serialize;
 
 use crate::db::{EdgeRecord, KnotCacheRecord};
@@ -21,6 +23,9 @@ pub struct KnotView {
     pub body: Option<String>,
     pub description: Option<String>,
     pub acceptance: Option<String>,
+    pub estimate: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom_fields: BTreeMap<String, String>,
     pub priority: Option<i64>,
     #[serde(rename = "type")]
     pub knot_type: KnotType,
@@ -46,6 +51,8 @@ pub struct KnotView {
     pub blocked_from_state: Option<String>,
     pub created_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snoozed_until: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub step_metadata: Option<StepMetadata>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub next_step_metadata: Option<StepMetadata>,
@@ -53,6 +60,15 @@ pub struct KnotView {
     pub edges: Vec<EdgeView>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub child_summaries: Vec<ChildSummary>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub git_links: Vec<crate::db::GitLinkRecord>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub criteria: Vec<crate::db::CriterionRecord>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub handbacks: Vec<crate::db::HandbackRecord>,
+    /// Latest version of the plan attached with `kno plan set`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<crate::db::PlanRecord>,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
@@ -112,6 +128,10 @@ pub struct UpdateKnotPatch {
     pub title: Option<String>,
     pub description: Option<String>,
     pub acceptance: Option<String>,
+    pub estimate: Option<String>,
+    /// `name=value` assignments of repo-declared custom fields; an empty
+    /// value clears the field.
+    pub custom_fields: Vec<String>,
     pub priority: Option<i64>,
     pub status: Option<String>,
     pub knot_type: Option<KnotType>,
@@ -135,6 +155,8 @@ impl UpdateKnotPatch {
         self.title.is_some()
             || self.description.is_some()
             || self.acceptance.is_some()
+            || self.estimate.is_some()
+            || !self.custom_fields.is_empty()
             || self.priority.is_some()
             || self.status.is_some()
             || self.knot_type.is_some()
@@ -157,6 +179,12 @@ pub struct CreateKnotOptions {
     pub gate_data: GateData,
     pub lease_data: LeaseData,
     pub acceptance: Option<String>,
+    pub estimate: Option<String>,
+    pub custom_fields: Vec<String>,
+    /// Keep the knot's events out of the published stream.
+    pub private: bool,
+    /// Stage the knot like a private one; publishing restamps its events.
+    pub draft: bool,
 }
 
 impl From<KnotCacheRecord> for KnotView {
@@ -174,6 +202,8 @@ impl From<KnotCacheRecord> for KnotView {
             body: value.body,
             description: value.description,
             acceptance: value.acceptance,
+            estimate: value.estimate,
+            custom_fields: value.custom_fields,
             priority: value.priority,
             knot_type,
             tags: value.tags,
@@ -192,10 +222,15 @@ impl From<KnotCacheRecord> for KnotView {
             deferred_from_state: value.deferred_from_state,
             blocked_from_state: value.blocked_from_state,
             created_at: value.created_at,
+            snoozed_until: value.snoozed_until,
             step_metadata: None,
             next_step_metadata: None,
             edges: Vec::new(),
             child_summaries: Vec::new(),
+            git_links: Vec::new(),
+            criteria: Vec::new(),
+            handbacks: Vec::new(),
+            plan: None,
         }
     }
 }
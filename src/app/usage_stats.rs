@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::db;
+
+use super::error::AppError;
+use super::App;
+
+/// Latency and sync-size percentiles per command, from the local
+/// `usage_metric` table that `metrics.local` fills.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct UsageReport {
+    pub enabled: bool,
+    pub sync_auto_budget_ms: u64,
+    pub commands: Vec<CommandUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct CommandUsage {
+    pub command: String,
+    pub runs: u64,
+    pub failures: u64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub max_ms: u64,
+    /// Percentiles over the runs that pushed or pulled event files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sync_files_p50: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sync_files_p95: Option<u64>,
+}
+
+impl App {
+    pub fn usage_report(&self, command: Option<&str>) -> Result<UsageReport, AppError> {
+        let mut grouped: BTreeMap<String, Vec<db::UsageMetricRecord>> = BTreeMap::new();
+        for record in db::list_usage_metrics(&self.conn)? {
+            if command.is_none_or(|command| command == record.command) {
+                grouped
+                    .entry(record.command.clone())
+                    .or_default()
+                    .push(record);
+            }
+        }
+        Ok(UsageReport {
+            enabled: db::get_local_metrics_enabled(&self.conn)?,
+            sync_auto_budget_ms: self
+                .config_get("sync_auto_budget_ms")?
+                .value
+                .parse()
+                .unwrap_or(0),
+            commands: grouped
+                .into_iter()
+                .map(|(command, records)| command_usage(command, &records))
+                .collect(),
+        })
+    }
+}
+
+fn command_usage(command: String, records: &[db::UsageMetricRecord]) -> CommandUsage {
+    let mut durations: Vec<u64> = records.iter().map(|record| record.duration_ms).collect();
+    durations.sort_unstable();
+    let mut sync_files: Vec<u64> = records
+        .iter()
+        .filter_map(|record| record.sync_files)
+        .collect();
+    sync_files.sort_unstable();
+    CommandUsage {
+        command,
+        runs: records.len() as u64,
+        failures: records.iter().filter(|record| !record.ok).count() as u64,
+        p50_ms: percentile(&durations, 50).unwrap_or(0),
+        p95_ms: percentile(&durations, 95).unwrap_or(0),
+        max_ms: durations.last().copied().unwrap_or(0),
+        sync_files_p50: percentile(&sync_files, 50),
+        sync_files_p95: percentile(&sync_files, 95),
+    }
+}
+
+/// Nearest-rank percentile of already sorted values.
+pub(crate) fn percentile(sorted: &[u64], pct: usize) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    Some(sorted[rank - 1])
+}
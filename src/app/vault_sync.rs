@@ -0,0 +1,245 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::error::AppError;
+use super::types::{KnotView, UpdateKnotPatch};
+use super::App;
+use crate::vault::{self, NoteEdits, NoteLink, VaultNote};
+
+/// Hashes of the notes the last sync wrote, kept in the vault so an edited
+/// note can be told apart from one the knot has since outgrown. Obsidian
+/// does not index dotfiles.
+const SYNC_STATE_FILE: &str = ".knots-sync.json";
+
+type LinkMap = HashMap<String, Vec<NoteLink>>;
+
+/// One knot whose note edits a sync wrote back (or would, on a dry run).
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultIngest {
+    pub id: String,
+    /// Knot fields the note changed: `title`, `state`, `priority`, `tags`,
+    /// `description`.
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultSyncSummary {
+    pub vault: PathBuf,
+    pub dry_run: bool,
+    pub ingested: Vec<VaultIngest>,
+    /// Notes created or rewritten from their knot.
+    pub written: Vec<String>,
+    pub unchanged: usize,
+    /// Notes whose edits could not be applied; they are left as edited and
+    /// retried on the next sync.
+    pub errors: Vec<String>,
+}
+
+impl App {
+    /// Writes every knot as a markdown note in `vault` and applies edits
+    /// made to notes since the last sync as knot updates. A note edited
+    /// since the last sync wins over knot changes made in the meantime.
+    pub fn sync_vault(&self, vault: &Path, dry_run: bool) -> Result<VaultSyncSummary, AppError> {
+        if !dry_run {
+            std::fs::create_dir_all(vault)?;
+        }
+        let state_path = vault.join(SYNC_STATE_FILE);
+        let mut synced: BTreeMap<String, String> = match std::fs::read_to_string(&state_path) {
+            Ok(raw) => serde_json::from_str(&raw).map_err(|err| {
+                AppError::InvalidArgument(format!("{}: {err}", state_path.display()))
+            })?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+        let mut summary = VaultSyncSummary {
+            vault: vault.to_path_buf(),
+            dry_run,
+            ingested: Vec::new(),
+            written: Vec::new(),
+            unchanged: 0,
+            errors: Vec::new(),
+        };
+        let knots = self.list_knots()?;
+        let links = self.vault_links(&knots)?;
+        let mut notes = self.vault_notes(&knots, &links)?;
+        for knot in &knots {
+            let path = vault.join(vault::note_file_name(&knot.id));
+            let existing = match std::fs::read_to_string(&path) {
+                Ok(text) => Some(text),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+                Err(err) => return Err(err.into()),
+            };
+            let Some(mut note) = notes.remove(&knot.id) else {
+                continue;
+            };
+            if let Some(text) = existing.as_deref() {
+                let edited = synced.get(&knot.id) != Some(&vault::content_hash(text));
+                let edits = vault::parse_note(text);
+                if edits.id.as_deref().is_some_and(|id| id != knot.id) {
+                    summary.errors.push(format!(
+                        "{}: front matter id does not match the file name",
+                        path.display()
+                    ));
+                    continue;
+                }
+                let (patch, fields) = note_patch(&note, &edits);
+                if edited && !fields.is_empty() {
+                    if dry_run {
+                        summary.ingested.push(VaultIngest {
+                            id: knot.id.clone(),
+                            fields,
+                        });
+                        continue;
+                    }
+                    match self.update_knot(&knot.id, patch) {
+                        Ok(updated) => {
+                            let mut refreshed = self.vault_notes(&[updated], &links)?;
+                            note = refreshed.remove(&knot.id).unwrap_or(note);
+                            summary.ingested.push(VaultIngest {
+                                id: knot.id.clone(),
+                                fields,
+                            });
+                        }
+                        Err(err) => {
+                            summary.errors.push(format!("{}: {err}", path.display()));
+                            continue;
+                        }
+                    }
+                }
+            }
+            let rendered = vault::render_note(&note);
+            if existing.as_deref() == Some(rendered.as_str()) {
+                summary.unchanged += 1;
+            } else {
+                if !dry_run {
+                    std::fs::write(&path, &rendered)?;
+                }
+                summary.written.push(knot.id.clone());
+            }
+            synced.insert(knot.id.clone(), vault::content_hash(&rendered));
+        }
+        if !dry_run {
+            let state = serde_json::to_string_pretty(&synced).map_err(std::io::Error::other)?;
+            std::fs::write(&state_path, state)?;
+        }
+        Ok(summary)
+    }
+
+    fn vault_notes(
+        &self,
+        knots: &[KnotView],
+        links: &LinkMap,
+    ) -> Result<HashMap<String, VaultNote>, AppError> {
+        let exported = self.knots_for_export(knots, false)?;
+        Ok(knots
+            .iter()
+            .zip(&exported)
+            .map(|(knot, value)| {
+                let note = VaultNote {
+                    id: knot.id.clone(),
+                    state: knot.state.clone(),
+                    knot_type: knot.knot_type.as_str().to_string(),
+                    priority: knot.priority,
+                    updated_at: knot.updated_at.clone(),
+                    title: value.get("title").map(|_| knot.title.clone()),
+                    tags: value.get("tags").map(|_| knot.tags.clone()),
+                    description: value
+                        .get("description")
+                        .map(|_| knot.description.clone().unwrap_or_default()),
+                    links: links.get(&knot.id).cloned().unwrap_or_default(),
+                };
+                (knot.id.clone(), note)
+            })
+            .collect())
+    }
+
+    /// Parent, child, and blocking wikilinks for each knot, labelled with
+    /// the other knot's title unless titles are sensitive.
+    fn vault_links(&self, knots: &[KnotView]) -> Result<LinkMap, AppError> {
+        let titles_hidden = self
+            .sensitive_fields()?
+            .iter()
+            .any(|field| field == "title");
+        let titles: HashMap<&str, &str> = knots
+            .iter()
+            .map(|knot| (knot.id.as_str(), knot.title.as_str()))
+            .collect();
+        let label = |id: &str| {
+            (!titles_hidden)
+                .then(|| titles.get(id).map(|title| title.to_string()))
+                .flatten()
+        };
+        let mut links = LinkMap::new();
+        for edge in self.list_layout_edges()? {
+            if !titles.contains_key(edge.src.as_str()) || !titles.contains_key(edge.dst.as_str()) {
+                continue;
+            }
+            let (forward, backward) = match edge.kind.as_str() {
+                "parent_of" => ("child", "parent"),
+                "blocked_by" => ("blocked_by", "blocks"),
+                _ => ("blocks", "blocked_by"),
+            };
+            links.entry(edge.src.clone()).or_default().push(NoteLink {
+                relation: forward.to_string(),
+                id: edge.dst.clone(),
+                title: label(&edge.dst),
+            });
+            links.entry(edge.dst.clone()).or_default().push(NoteLink {
+                relation: backward.to_string(),
+                id: edge.src.clone(),
+                title: label(&edge.src),
+            });
+        }
+        for list in links.values_mut() {
+            list.sort();
+            list.dedup();
+        }
+        Ok(links)
+    }
+}
+
+/// The update that makes a knot match its edited note, and the fields it
+/// touches. Fields the note hides are never written back.
+fn note_patch(note: &VaultNote, edits: &NoteEdits) -> (UpdateKnotPatch, Vec<String>) {
+    let mut patch = UpdateKnotPatch::default();
+    let mut fields = Vec::new();
+    let hidden = |field: &str| edits.hidden.iter().any(|hidden| hidden == field);
+    if let Some(title) = edits.title.as_ref().filter(|_| !hidden("title")) {
+        if note.title.as_ref() != Some(title) && !title.is_empty() {
+            patch.title = Some(title.clone());
+            fields.push("title".to_string());
+        }
+    }
+    if let Some(state) = edits.state.as_ref().filter(|state| **state != note.state) {
+        patch.status = Some(state.clone());
+        fields.push("state".to_string());
+    }
+    if edits.priority.is_some() && edits.priority != note.priority {
+        patch.priority = edits.priority;
+        fields.push("priority".to_string());
+    }
+    if let (Some(wanted), Some(current)) = (edits.tags.as_ref(), note.tags.as_ref()) {
+        let wanted: BTreeSet<&String> = wanted.iter().collect();
+        let current: BTreeSet<&String> = current.iter().collect();
+        patch.add_tags = wanted
+            .difference(&current)
+            .map(|tag| (*tag).clone())
+            .collect();
+        patch.remove_tags = current
+            .difference(&wanted)
+            .map(|tag| (*tag).clone())
+            .collect();
+        if !patch.add_tags.is_empty() || !patch.remove_tags.is_empty() {
+            fields.push("tags".to_string());
+        }
+    }
+    if let (Some(wanted), Some(current)) = (edits.description.as_ref(), note.description.as_ref()) {
+        if !hidden("description") && wanted.trim() != current.trim() {
+            patch.description = Some(wanted.clone());
+            fields.push("description".to_string());
+        }
+    }
+    (patch, fields)
+}
@@ -0,0 +1,91 @@
+use serde_json::Value;
+
+use super::error::AppError;
+use super::types::KnotView;
+use super::App;
+
+/// Knot JSON keys `sensitive_fields` may name; `fields.<name>` names one
+/// custom field.
+const HIDEABLE_FIELDS: [&str; 16] = [
+    "title",
+    "body",
+    "description",
+    "acceptance",
+    "estimate",
+    "custom_fields",
+    "tags",
+    "notes",
+    "handoff_capsules",
+    "invariants",
+    "step_history",
+    "gate",
+    "lease",
+    "lease_agent",
+    "git_links",
+    "criteria",
+];
+
+impl App {
+    /// The repo config's `sensitive_fields`, rejecting names that would
+    /// hide nothing so a typo cannot quietly leak a field.
+    pub fn sensitive_fields(&self) -> Result<Vec<String>, AppError> {
+        let fields = self
+            .read_repo_config()?
+            .unwrap_or_default()
+            .sensitive_fields;
+        for field in &fields {
+            let known = match field.strip_prefix("fields.") {
+                Some(name) => !name.is_empty(),
+                None => HIDEABLE_FIELDS.contains(&field.as_str()),
+            };
+            if !known {
+                return Err(AppError::InvalidArgument(format!(
+                    "unknown sensitive_fields entry '{field}' in .knots/config.toml; \
+                     expected fields.<name> or one of: {}",
+                    HIDEABLE_FIELDS.join(", ")
+                )));
+            }
+        }
+        Ok(fields)
+    }
+
+    /// `knots` as JSON for output leaving the clone, without the repo's
+    /// sensitive fields unless `include_sensitive`.
+    pub fn knots_for_export(
+        &self,
+        knots: &[KnotView],
+        include_sensitive: bool,
+    ) -> Result<Vec<Value>, AppError> {
+        let hidden = if include_sensitive {
+            Vec::new()
+        } else {
+            self.sensitive_fields()?
+        };
+        knots
+            .iter()
+            .map(|knot| {
+                let mut value = serde_json::to_value(knot).map_err(std::io::Error::other)?;
+                hide_fields(&mut value, &hidden);
+                Ok(value)
+            })
+            .collect()
+    }
+}
+
+fn hide_fields(knot: &mut Value, hidden: &[String]) {
+    let Some(object) = knot.as_object_mut() else {
+        return;
+    };
+    for field in hidden {
+        match field.strip_prefix("fields.") {
+            Some(name) => {
+                if let Some(Value::Object(custom)) = object.get_mut("custom_fields") {
+                    custom.remove(name);
+                }
+            }
+            None => {
+                object.remove(field);
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::db::{self, InboxItemRecord};
+use crate::domain::mention::mentions;
+
+use super::error::AppError;
+use super::pull_digest::{DigestKnot, PullDigest};
+use super::App;
+
+pub(crate) const WATCH_INBOX_KIND: &str = "watch";
+pub(crate) const MENTION_INBOX_KIND: &str = "mention";
+const INBOX_HANDLE_KEY: &str = "inbox.handle";
+const MENTION_EXCERPT_CHARS: usize = 80;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct WatchedKnot {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl App {
+    /// Adds the knot to, or drops it from, this clone's watch list.
+    pub fn set_watch(&self, token: &str, watch: bool) -> Result<WatchedKnot, AppError> {
+        let id = self.resolve_knot_token(token)?;
+        let knot = if watch {
+            let knot = self
+                .show_knot(&id)?
+                .ok_or_else(|| AppError::NotFound(token.to_string()))?;
+            db::insert_watched_knot(&self.conn, &knot.id)?;
+            knot.id
+        } else {
+            db::delete_watched_knot(&self.conn, &id)?;
+            id
+        };
+        Ok(self.watched_knot(knot)?)
+    }
+
+    pub fn watched_knots(&self) -> Result<Vec<WatchedKnot>, AppError> {
+        db::list_watched_knots(&self.conn)?
+            .into_iter()
+            .map(|id| Ok(self.watched_knot(id)?))
+            .collect()
+    }
+
+    fn watched_knot(&self, id: String) -> rusqlite::Result<WatchedKnot> {
+        let title = db::get_knot_hot(&self.conn, &id)?.map(|knot| knot.title);
+        Ok(WatchedKnot { id, title })
+    }
+
+    /// Files inbox items for what `digest` brought in, watched knots first
+    /// and then mentions, returning them so the pull can highlight them.
+    pub fn record_pull_inbox(&self, digest: &PullDigest) -> Result<Vec<InboxItemRecord>, AppError> {
+        let mut items = self.record_watched_changes(digest)?;
+        items.extend(self.record_mentions(digest)?);
+        Ok(items)
+    }
+
+    /// Files an inbox item for each watched knot in `digest`.
+    pub fn record_watched_changes(
+        &self,
+        digest: &PullDigest,
+    ) -> Result<Vec<InboxItemRecord>, AppError> {
+        let watched: HashSet<String> = db::list_watched_knots(&self.conn)?.into_iter().collect();
+        if watched.is_empty() {
+            return Ok(Vec::new());
+        }
+        let changes = digest
+            .new_knots
+            .iter()
+            .chain(&digest.state_changes)
+            .chain(&digest.other_updates)
+            .filter(|knot| watched.contains(&knot.id));
+        let mut items = Vec::new();
+        for knot in changes {
+            items.push(db::insert_inbox_item(
+                &self.conn,
+                &knot.id,
+                WATCH_INBOX_KIND,
+                &watch_summary(knot),
+            )?);
+        }
+        Ok(items)
+    }
+
+    /// Files an inbox item for each new note or handoff capsule in `digest`
+    /// that mentions this clone's handle and was written by someone else.
+    pub fn record_mentions(&self, digest: &PullDigest) -> Result<Vec<InboxItemRecord>, AppError> {
+        let Some(handle) = self.inbox_handle()? else {
+            return Ok(Vec::new());
+        };
+        let mut items = Vec::new();
+        for note in &digest.new_notes {
+            if note.author.eq_ignore_ascii_case(&handle)
+                || !mentions(&note.content).contains(&handle)
+            {
+                continue;
+            }
+            let summary = format!(
+                "{} mentioned you in a {} on {}: {}",
+                note.author,
+                note.kind,
+                note.title,
+                excerpt(&note.content)
+            );
+            items.push(db::insert_inbox_item(
+                &self.conn,
+                &note.knot_id,
+                MENTION_INBOX_KIND,
+                &summary,
+            )?);
+        }
+        Ok(items)
+    }
+
+    /// The `@handle` that reaches this clone: `inbox.handle`, else `$USER`.
+    pub fn inbox_handle(&self) -> Result<Option<String>, AppError> {
+        let configured = self.config_get(INBOX_HANDLE_KEY)?.value;
+        let handle = match configured.trim() {
+            "" => std::env::var("USER").unwrap_or_default(),
+            value => value.to_string(),
+        };
+        let handle = handle.trim().trim_start_matches('@').to_lowercase();
+        Ok((!handle.is_empty()).then_some(handle))
+    }
+
+    /// Unacknowledged inbox items, oldest first.
+    pub fn inbox(&self) -> Result<Vec<InboxItemRecord>, AppError> {
+        Ok(db::list_inbox_items(&self.conn)?)
+    }
+
+    /// Acknowledges `ids`, or the whole inbox when empty.
+    pub fn ack_inbox(&self, ids: &[i64]) -> Result<usize, AppError> {
+        Ok(db::ack_inbox_items(&self.conn, ids)?)
+    }
+}
+
+fn excerpt(content: &str) -> String {
+    let line = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= MENTION_EXCERPT_CHARS {
+        return line;
+    }
+    let cut: String = line.chars().take(MENTION_EXCERPT_CHARS).collect();
+    format!("{}...", cut.trim_end())
+}
+
+fn watch_summary(knot: &DigestKnot) -> String {
+    match &knot.previous_state {
+        Some(previous) => format!("{}: {previous} -> {}", knot.title, knot.state),
+        None => format!("{}: updated [{}]", knot.title, knot.state),
+    }
+}
@@ -0,0 +1,169 @@
+use serde::Serialize;
+use time::OffsetDateTime;
+
+use crate::db::{self, KnotCacheRecord};
+
+use super::diff::parse_span;
+use super::error::AppError;
+use super::helpers::normalize_state_input;
+use super::rehydrate::replay::parse_replay_timestamp;
+use super::types::KnotView;
+use super::App;
+
+mod age_report;
+
+pub use age_report::AgeReport;
+#[cfg(test)]
+pub(crate) use age_report::{AgeOutlier, StateAgeHistogram};
+
+/// Occupancy of one state that has a WIP limit in the repo config.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct WipUsage {
+    pub state: String,
+    pub active: usize,
+    pub limit: usize,
+}
+
+/// How long a knot has been in its state, against the state's budget in
+/// the repo config.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct StateAge {
+    pub state: String,
+    /// When the knot entered `state`, as far as its step history shows.
+    pub since: String,
+    pub waited_seconds: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget_seconds: Option<u64>,
+    pub over_budget: bool,
+}
+
+impl StateAge {
+    pub fn waited(&self) -> String {
+        format_span(self.waited_seconds)
+    }
+
+    pub fn budget(&self) -> Option<String> {
+        self.budget_seconds.map(format_span)
+    }
+}
+
+impl App {
+    /// Current occupancy of every limited state, in state-name order.
+    pub fn wip_usage(&self) -> Result<Vec<WipUsage>, AppError> {
+        let mut usage = Vec::new();
+        for (state, limit) in self.wip_limits()? {
+            let active = db::count_knot_hot_in_state(&self.conn, &state)?;
+            usage.push(WipUsage {
+                state,
+                active: usize::try_from(active).unwrap_or_default(),
+                limit,
+            });
+        }
+        Ok(usage)
+    }
+
+    /// Rejects moving `current` into a state that is already at its WIP
+    /// limit. `force` overrides the limit; the state event records it.
+    pub(crate) fn enforce_wip_limit(
+        &self,
+        current: &KnotCacheRecord,
+        next_state: &str,
+        force: bool,
+    ) -> Result<(), AppError> {
+        if force || current.state == next_state {
+            return Ok(());
+        }
+        let Some((state, limit)) = self
+            .wip_limits()?
+            .into_iter()
+            .find(|(state, _)| state == next_state)
+        else {
+            return Ok(());
+        };
+        let active = db::count_knot_hot_in_state(&self.conn, &state)?;
+        if usize::try_from(active).unwrap_or_default() >= limit {
+            return Err(AppError::WipLimitReached { state, limit });
+        }
+        Ok(())
+    }
+
+    /// How long `knot` has been in its current state and whether that is
+    /// past the state's budget.
+    pub fn state_age(&self, knot: &KnotView) -> Result<StateAge, AppError> {
+        self.state_age_at(knot, OffsetDateTime::now_utc())
+    }
+
+    pub(crate) fn state_age_at(
+        &self,
+        knot: &KnotView,
+        now: OffsetDateTime,
+    ) -> Result<StateAge, AppError> {
+        let since = entered_state_at(knot);
+        let entered = parse_replay_timestamp(since)?;
+        let waited = (now - entered).whole_seconds().max(0) as u64;
+        let budget = self
+            .state_budgets()?
+            .into_iter()
+            .find(|(state, _)| *state == knot.state)
+            .map(|(_, budget)| budget);
+        Ok(StateAge {
+            state: knot.state.clone(),
+            since: since.to_string(),
+            waited_seconds: waited,
+            budget_seconds: budget,
+            over_budget: budget.is_some_and(|budget| waited > budget),
+        })
+    }
+
+    fn state_budgets(&self) -> Result<Vec<(String, u64)>, AppError> {
+        let budgets = self.read_repo_config()?.unwrap_or_default().state_budgets;
+        budgets
+            .into_iter()
+            .map(|(state, raw)| {
+                let seconds = parse_span(&raw)
+                    .filter(|span| span.is_positive())
+                    .ok_or_else(|| {
+                        AppError::InvalidArgument(format!(
+                            "invalid state_budgets entry {state} = '{raw}' in \
+                             .knots/config.toml; use a span like 30m, 4h, 2d, or 1w"
+                        ))
+                    })?;
+                Ok((
+                    normalize_state_input(&state)?,
+                    seconds.whole_seconds() as u64,
+                ))
+            })
+            .collect()
+    }
+
+    fn wip_limits(&self) -> Result<Vec<(String, usize)>, AppError> {
+        let limits = self.read_repo_config()?.unwrap_or_default().wip_limits;
+        limits
+            .into_iter()
+            .map(|(state, limit)| Ok((normalize_state_input(&state)?, limit)))
+            .collect()
+    }
+}
+
+/// When `knot` entered its state. Steps only record moves out of action
+/// states, so a knot last moved by hand falls back to its last update.
+fn entered_state_at(knot: &KnotView) -> &str {
+    match knot.step_history.last() {
+        Some(step) if step.is_active() && step.step == knot.state => &step.started_at,
+        Some(step) if step.to_state.as_deref() == Some(knot.state.as_str()) => {
+            step.ended_at.as_deref().unwrap_or(&knot.updated_at)
+        }
+        Some(_) => &knot.updated_at,
+        None => knot.created_at.as_deref().unwrap_or(&knot.updated_at),
+    }
+}
+
+/// `2d 3h`, `4h 10m`, or `12m`: the two largest units.
+fn format_span(seconds: u64) -> String {
+    let (days, hours, minutes) = (seconds / 86_400, seconds / 3_600 % 24, seconds / 60 % 60);
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h"),
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::domain::knot_type::KnotType;
+
+use super::super::error::AppError;
+use super::super::App;
+use super::format_span;
+
+/// Upper bounds, in seconds, of every histogram bucket but the last.
+const BUCKET_BOUNDS: [(u64, &str); 7] = [
+    (3_600, "<1h"),
+    (4 * 3_600, "1h-4h"),
+    (86_400, "4h-1d"),
+    (3 * 86_400, "1d-3d"),
+    (7 * 86_400, "3d-1w"),
+    (14 * 86_400, "1w-2w"),
+    (28 * 86_400, "2w-4w"),
+];
+const LAST_BUCKET: &str = "4w+";
+/// States with fewer knots than this get no statistical outliers, only
+/// budget breaches.
+const MIN_KNOTS_FOR_FENCE: usize = 4;
+
+/// Time-in-current-state histograms for `kno stats age`, one per state.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct AgeReport {
+    pub generated_at: String,
+    pub buckets: Vec<String>,
+    pub states: Vec<StateAgeHistogram>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct StateAgeHistogram {
+    pub state: String,
+    pub knots: usize,
+    /// Knot counts lined up with the report's `buckets`.
+    pub counts: Vec<usize>,
+    pub median_seconds: u64,
+    pub max_seconds: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget_seconds: Option<u64>,
+    pub outliers: Vec<AgeOutlier>,
+}
+
+/// A knot that has waited past its state's budget, or well past its
+/// neighbours (beyond the upper quartile plus 1.5 interquartile ranges).
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct AgeOutlier {
+    pub id: String,
+    pub title: String,
+    pub waited_seconds: u64,
+    pub reason: &'static str,
+}
+
+impl StateAgeHistogram {
+    pub fn median(&self) -> String {
+        format_span(self.median_seconds)
+    }
+
+    pub fn max(&self) -> String {
+        format_span(self.max_seconds)
+    }
+}
+
+impl AgeOutlier {
+    pub fn waited(&self) -> String {
+        format_span(self.waited_seconds)
+    }
+}
+
+impl App {
+    /// Histograms of how long open knots have sat in their current state,
+    /// optionally for one state only. Terminal and lease knots are left out.
+    pub fn age_report(&self, state: Option<&str>) -> Result<AgeReport, AppError> {
+        self.age_report_at(state, OffsetDateTime::now_utc())
+    }
+
+    pub(crate) fn age_report_at(
+        &self,
+        state: Option<&str>,
+        now: OffsetDateTime,
+    ) -> Result<AgeReport, AppError> {
+        let mut by_state: BTreeMap<String, Vec<(u64, String, String, bool)>> = BTreeMap::new();
+        let mut budgets = BTreeMap::new();
+        for knot in self.list_knots()? {
+            if knot.knot_type == KnotType::Lease
+                || state.is_some_and(|state| state != knot.state)
+                || self
+                    .profile_registry
+                    .require(&knot.profile_id)?
+                    .is_terminal_state(&knot.state)
+            {
+                continue;
+            }
+            let age = self.state_age_at(&knot, now)?;
+            budgets.insert(knot.state.clone(), age.budget_seconds);
+            by_state.entry(knot.state).or_default().push((
+                age.waited_seconds,
+                knot.id,
+                knot.title,
+                age.over_budget,
+            ));
+        }
+        let states = by_state
+            .into_iter()
+            .map(|(state, mut knots)| {
+                knots.sort_by_key(|(waited, ..)| *waited);
+                let budget_seconds = budgets.get(&state).copied().flatten();
+                histogram(state, &knots, budget_seconds)
+            })
+            .collect();
+        Ok(AgeReport {
+            generated_at: now
+                .format(&Rfc3339)
+                .expect("RFC3339 formatting for UTC timestamp should never fail"),
+            buckets: BUCKET_BOUNDS
+                .iter()
+                .map(|(_, label)| label.to_string())
+                .chain([LAST_BUCKET.to_string()])
+                .collect(),
+            states,
+        })
+    }
+}
+
+/// `knots` is sorted by wait, shortest first.
+fn histogram(
+    state: String,
+    knots: &[(u64, String, String, bool)],
+    budget_seconds: Option<u64>,
+) -> StateAgeHistogram {
+    let mut counts = vec![0; BUCKET_BOUNDS.len() + 1];
+    for (waited, ..) in knots {
+        let bucket = BUCKET_BOUNDS
+            .iter()
+            .position(|(bound, _)| waited < bound)
+            .unwrap_or(BUCKET_BOUNDS.len());
+        counts[bucket] += 1;
+    }
+    let waits: Vec<u64> = knots.iter().map(|(waited, ..)| *waited).collect();
+    let fence = (waits.len() >= MIN_KNOTS_FOR_FENCE).then(|| {
+        let (q1, q3) = (quantile(&waits, 1, 4), quantile(&waits, 3, 4));
+        q3 + (q3 - q1) * 3 / 2
+    });
+    let outliers = knots
+        .iter()
+        .rev()
+        .filter_map(|(waited, id, title, over_budget)| {
+            let reason = if *over_budget {
+                "over_budget"
+            } else if fence.is_some_and(|fence| *waited > fence) {
+                "outlier"
+            } else {
+                return None;
+            };
+            Some(AgeOutlier {
+                id: id.clone(),
+                title: title.clone(),
+                waited_seconds: *waited,
+                reason,
+            })
+        })
+        .collect();
+    StateAgeHistogram {
+        state,
+        knots: knots.len(),
+        counts,
+        median_seconds: quantile(&waits, 1, 2),
+        max_seconds: waits.last().copied().unwrap_or_default(),
+        budget_seconds,
+        outliers,
+    }
+}
+
+/// Nearest-rank quantile `num/den` of sorted, non-empty `values`.
+fn quantile(values: &[u64], num: usize, den: usize) -> u64 {
+    let rank = (values.len() * num).div_ceil(den).max(1);
+    values[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{histogram, quantile};
+
+    fn knots(waits: &[u64]) -> Vec<(u64, String, String, bool)> {
+        waits
+            .iter()
+            .enumerate()
+            .map(|(i, waited)| (*waited, format!("k{i}"), format!("Knot {i}"), false))
+            .collect()
+    }
+
+    #[test]
+    fn flags_waits_beyond_the_interquartile_fence() {
+        let hour = 3_600;
+        let waits = knots(&[hour, 2 * hour, 2 * hour, 3 * hour, 40 * 86_400]);
+        let state = histogram("ready".to_string(), &waits, None);
+        assert_eq!(state.counts, vec![0, 4, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(state.median_seconds, 2 * hour);
+        assert_eq!(state.max(), "40d 0h");
+        assert_eq!(state.outliers.len(), 1);
+        assert_eq!(state.outliers[0].id, "k4");
+        assert_eq!(state.outliers[0].reason, "outlier");
+
+        let few = histogram("ready".to_string(), &knots(&[hour, 40 * 86_400]), None);
+        assert!(few.outliers.is_empty(), "too few knots for a fence");
+        assert_eq!(quantile(&[1, 2, 3, 4], 1, 2), 2);
+    }
+}
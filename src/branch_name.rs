@@ -0,0 +1,71 @@
+use crate::app::KnotView;
+
+pub const DEFAULT_BRANCH_TEMPLATE: &str = "{alias}-{slug}";
+const PLACEHOLDERS: [&str; 5] = ["alias", "id", "short_id", "slug", "type"];
+/// Longest `{slug}`; titles are cut at a word boundary below this.
+const MAX_SLUG_LEN: usize = 48;
+
+/// Renders a `kno branch` name template for `knot`. Placeholders: `{alias}`
+/// (the hierarchical alias, else the short id), `{id}`, `{short_id}`,
+/// `{slug}` for the slugified title, and `{type}`.
+pub fn render_branch_name(template: &str, knot: &KnotView) -> Result<String, String> {
+    let short_id = crate::knot_id::display_id(&knot.id).to_string();
+    render(template, |name| match name {
+        "alias" => knot.alias.clone().unwrap_or_else(|| short_id.clone()),
+        "id" => knot.id.clone(),
+        "short_id" => short_id.clone(),
+        "slug" => slugify(&knot.title),
+        _ => knot.knot_type.to_string(),
+    })
+    .map(|name| name.trim_matches('-').to_string())
+}
+
+/// Checks that `template` only uses known placeholders.
+pub fn check_branch_template(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err("branch template must not be empty".to_string());
+    }
+    render(template, |_| String::new()).map(|_| ())
+}
+
+fn render(template: &str, value: impl Fn(&str) -> String) -> Result<String, String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("unclosed '{{' in branch template '{template}'"))?;
+        let name = after[..end].trim();
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "unknown placeholder {{{name}}} in branch template; use one of {}",
+                PLACEHOLDERS.map(|name| format!("{{{name}}}")).join(", ")
+            ));
+        }
+        rendered.push_str(&value(name));
+        rest = &after[end + 1..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+/// Lowercases `title` and joins its ASCII words with `-`.
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for word in title
+        .split(|ch: char| !ch.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        if !slug.is_empty() && slug.len() + 1 + word.len() > MAX_SLUG_LEN {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word.to_ascii_lowercase());
+    }
+    slug.truncate(MAX_SLUG_LEN);
+    slug
+}
@@ -0,0 +1,27 @@
+use crate::branch_name::{check_branch_template, slugify, DEFAULT_BRANCH_TEMPLATE};
+
+#[test]
+fn slugify_joins_lowercase_ascii_words() {
+    assert_eq!(slugify("Fix login & signup!"), "fix-login-signup");
+    assert_eq!(slugify("  Ünïcode -- title  "), "n-code-title");
+    assert_eq!(slugify("***"), "");
+}
+
+#[test]
+fn slugify_stops_at_a_word_boundary() {
+    let slug = slugify(&"word ".repeat(20));
+    assert!(slug.len() <= 48, "{slug}");
+    assert!(slug.ends_with("word"), "{slug}");
+    assert_eq!(slugify(&"x".repeat(60)).len(), 48);
+}
+
+#[test]
+fn branch_templates_only_accept_known_placeholders() {
+    assert!(check_branch_template(DEFAULT_BRANCH_TEMPLATE).is_ok());
+    assert!(check_branch_template("{type}/{short_id}-{slug}").is_ok());
+    assert!(check_branch_template("feature/{title}")
+        .unwrap_err()
+        .contains("unknown placeholder {title}"));
+    assert!(check_branch_template("{slug").is_err());
+    assert!(check_branch_template(" ").is_err());
+}
@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+/// File in an XDG cache directory naming the store it belongs to, so the
+/// store can be found again from the database path.
+const STORE_ROOT_FILE: &str = "store_root";
+const CACHE_DB_FILE: &str = "state.sqlite";
+
+/// Where a new cache database goes: inside the store (`repo`, the default)
+/// or under `$XDG_CACHE_HOME/knots/<hash>/` (`xdg`), which keeps the
+/// working tree free of the cache. Existing caches are found in either
+/// place whatever this says.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CacheLocation {
+    #[default]
+    Repo,
+    Xdg,
+}
+
+impl CacheLocation {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "repo" => Some(Self::Repo),
+            "xdg" => Some(Self::Xdg),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Repo => "repo",
+            Self::Xdg => "xdg",
+        }
+    }
+
+    /// `KNOTS_CACHE_LOCATION`, else `cache_location` in the user config,
+    /// else `repo`. Unknown names fall back to `repo`.
+    pub fn from_env() -> Self {
+        std::env::var("KNOTS_CACHE_LOCATION")
+            .ok()
+            .or_else(|| {
+                crate::project::read_global_config(None)
+                    .ok()
+                    .and_then(|config| config.cache_location)
+            })
+            .and_then(|name| Self::parse(&name))
+            .unwrap_or_default()
+    }
+}
+
+/// `$XDG_CACHE_HOME/knots`, else `~/.cache/knots`.
+pub fn xdg_cache_root() -> Option<PathBuf> {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .map(|dir| dir.join("knots"))
+}
+
+/// The XDG database path for a store, keyed by a hash of its root.
+pub fn xdg_db_path(store_root: &Path) -> Option<PathBuf> {
+    let digest = Sha256::digest(store_root.to_string_lossy().as_bytes());
+    let key = format!("{digest:x}");
+    xdg_cache_root().map(|root| root.join(&key[..16]).join(CACHE_DB_FILE))
+}
+
+/// Creates the XDG directory for `db_path` and records which store owns it.
+pub fn claim_xdg_dir(db_path: &Path, store_root: &Path) -> std::io::Result<()> {
+    let Some(dir) = db_path.parent() else {
+        return Ok(());
+    };
+    fs::create_dir_all(dir)?;
+    fs::write(
+        dir.join(STORE_ROOT_FILE),
+        format!("{}\n", store_root.display()),
+    )
+}
+
+/// Claims the XDG directory when `db_path` is `store_root`'s XDG cache and
+/// nothing has claimed it yet.
+pub fn claim_if_xdg(db_path: &Path, store_root: &Path) -> std::io::Result<()> {
+    if xdg_db_path(store_root).as_deref() == Some(db_path) && claimed_store_root(db_path).is_none()
+    {
+        return claim_xdg_dir(db_path, store_root);
+    }
+    Ok(())
+}
+
+/// The store that claimed the directory holding `db_path`, if any.
+pub fn claimed_store_root(db_path: &Path) -> Option<PathBuf> {
+    let raw = fs::read_to_string(db_path.parent()?.join(STORE_ROOT_FILE)).ok()?;
+    let root = raw.trim();
+    (!root.is_empty()).then(|| PathBuf::from(root))
+}
+
+/// Removes a claimed XDG directory once its database has gone.
+pub fn release_xdg_dir(db_path: &Path) -> std::io::Result<()> {
+    match db_path.parent() {
+        Some(dir) if claimed_store_root(db_path).is_some() => fs::remove_dir_all(dir),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use uuid::Uuid;
+
+    use super::{claim_xdg_dir, claimed_store_root, release_xdg_dir, xdg_db_path, CacheLocation};
+
+    #[test]
+    fn parse_accepts_known_locations_only() {
+        assert_eq!(CacheLocation::parse(" XDG "), Some(CacheLocation::Xdg));
+        assert_eq!(CacheLocation::parse("repo"), Some(CacheLocation::Repo));
+        assert_eq!(CacheLocation::parse("tmp"), None);
+        assert_eq!(CacheLocation::Xdg.as_str(), "xdg");
+    }
+
+    #[test]
+    fn xdg_paths_are_stable_per_store_and_claims_round_trip() {
+        let first = xdg_db_path(Path::new("/repo/a/.knots")).expect("xdg path");
+        assert_eq!(
+            xdg_db_path(Path::new("/repo/a/.knots")),
+            Some(first.clone())
+        );
+        assert_ne!(
+            xdg_db_path(Path::new("/repo/b/.knots")),
+            Some(first.clone())
+        );
+        assert!(first.ends_with("state.sqlite"));
+
+        let dir = std::env::temp_dir().join(format!("knots-xdg-claim-{}", Uuid::now_v7()));
+        let db = dir.join("state.sqlite");
+        assert_eq!(claimed_store_root(&db), None);
+        claim_xdg_dir(&db, Path::new("/repo/a/.knots")).expect("claim");
+        assert_eq!(
+            claimed_store_root(&db).as_deref(),
+            Some(Path::new("/repo/a/.knots"))
+        );
+        release_xdg_dir(&db).expect("release");
+        assert!(!dir.exists());
+    }
+}
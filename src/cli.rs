@@ -6,9 +6,25 @@ use clap::{Args, Parser, Subcommand};
 use clap::CommandFactory;
 
 pub use crate::cli_agent::*;
+pub use crate::cli_criteria::*;
+pub use crate::cli_db::*;
+pub use crate::cli_events::*;
+pub use crate::cli_import::*;
+pub use crate::cli_init::*;
+pub use crate::cli_links::*;
 pub use crate::cli_loom::*;
+pub use crate::cli_new::*;
 pub use crate::cli_ops::*;
+pub use crate::cli_perf::*;
+pub use crate::cli_plan::*;
+pub use crate::cli_profile::*;
+pub use crate::cli_redact::*;
+pub use crate::cli_reports::*;
+pub use crate::cli_serve::*;
 pub use crate::cli_skills::*;
+pub use crate::cli_space::*;
+pub use crate::cli_sync::*;
+pub use crate::cli_watch::*;
 pub use crate::cli_workflow::*;
 
 pub fn styled_command() -> clap::Command {
@@ -54,6 +70,13 @@ pub struct Cli {
     )]
     pub project: Option<String>,
 
+    #[arg(
+        long,
+        env = "KNOTS_SPACE",
+        help = "Knot space to target; `root` for the repository's main store."
+    )]
+    pub space: Option<String>,
+
     #[arg(
         long,
         env = "KNO_TRACE",
@@ -62,6 +85,20 @@ pub struct Cli {
     )]
     pub trace: bool,
 
+    #[arg(
+        long,
+        global = true,
+        help = "Print a lock wait, sync, db, event write, and git time breakdown to stderr."
+    )]
+    pub timing: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Print long output directly instead of through $PAGER."
+    )]
+    pub no_pager: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -71,10 +108,16 @@ pub struct Cli {
 pub enum Commands {
     #[command(about = "Create a new knot.", alias = "create")]
     New(NewArgs),
+    #[command(
+        about = "Share a private or draft knot by moving its events into the published stream."
+    )]
+    Publish(PublishArgs),
     #[command(about = "Set a knot state with transition validation.")]
     State(StateArgs),
     #[command(about = "Update knot fields and metadata in one command.")]
     Update(UpdateArgs),
+    #[command(about = "Condense a knot's notes into a fresh handoff capsule.")]
+    Summarize(SummarizeArgs),
     #[command(about = "Self-update the kno binary.")]
     Upgrade(SelfUpdateArgs),
     #[command(about = "Uninstall kno from the system.")]
@@ -83,40 +126,104 @@ pub enum Commands {
     Ls(ListArgs),
     #[command(about = "Show one knot by id or alias.")]
     Show(ShowArgs),
+    #[command(about = "Fuzzy-pick a knot interactively and print its id.")]
+    Pick(PickArgs),
+    #[command(about = "Search knot titles, descriptions, notes, and events for text.")]
+    Grep(GrepArgs),
+    #[command(about = "Rank knots by how well they match a query, optionally by meaning.")]
+    Search(SearchArgs),
+    #[command(about = "Find knots that look like duplicates of each other.")]
+    Dedupe(DedupeArgs),
     #[command(about = "Inspect and manage workflow profiles.")]
     Profile(ProfileArgs),
     #[command(about = "Manage installed workflows.")]
     Workflow(WorkflowArgs),
     #[command(about = "Manage named Knots projects.")]
     Project(ProjectArgs),
+    #[command(about = "Manage knot spaces: separate backlogs in one repository.")]
+    Space(SpaceArgs),
     #[command(about = "Manage Loom workflows.")]
     Loom(LoomArgs),
     #[command(about = "Pull knot updates from the remote knots branch.")]
-    Pull(SyncArgs),
+    Pull(PullArgs),
     #[command(about = "Push local knot updates to the remote knots branch.")]
     Push(SyncArgs),
     #[command(about = "Push then pull knot updates.")]
-    Sync(SyncArgs),
+    Sync(SyncCommandArgs),
     #[command(about = "Initialize local store and remote or named project state.")]
-    Init,
+    Init(InitArgs),
     #[command(about = "Remove local knots store artifacts and delete remote branch.")]
-    Uninit,
+    Uninit(UninitArgs),
     #[command(about = "Create remote knots branch and ensure .knots is gitignored.")]
-    InitRemote,
+    InitRemote(KnotsRefArgs),
+    #[command(about = "Serve a central knots store for remote clients.")]
+    Serve(ServeArgs),
+    #[command(about = "Speak JSON-RPC over stdin/stdout for editor plugins.")]
+    Rpc(RpcArgs),
     #[command(about = "Validate on-disk knots event/index data.")]
     Fsck(FsckArgs),
+    #[command(about = "Scrub a leaked secret from one event's stored files.")]
+    Redact(RedactArgs),
     #[command(about = "Run repository health diagnostics.")]
     Doctor(DoctorArgs),
+    #[command(about = "Report orphaned knots, dangling edges, and broken references.")]
+    Lint(LintArgs),
+    #[command(about = "Summarize sync, cache, lock, and doctor state in one view.")]
+    Status(StatusArgs),
+    #[command(about = "Inspect and maintain the local cache database.")]
+    Db(DbArgs),
     #[command(about = "Run performance harness checks.")]
     Perf(PerfArgs),
     #[command(about = "Run compaction operations.")]
     Compact(CompactArgs),
+    #[command(about = "Inspect and verify catalog snapshots.")]
+    Snapshot(SnapshotArgs),
+    #[command(about = "Report on knot history, e.g. estimates against actual time.")]
+    Stats(StatsArgs),
+    #[command(about = "Summarize recent knot activity as markdown for standups.")]
+    Digest(DigestArgs),
+    #[command(about = "List knots finished since a tag or date, formatted as release notes.")]
+    Changelog(ChangelogArgs),
+    #[command(about = "Import knots from another repo or an org file.")]
+    Import(ImportArgs),
+    #[command(about = "Export knots to another format, such as org-mode.")]
+    Export(ExportArgs),
+    #[command(about = "Sync knots with an Obsidian or markdown vault.")]
+    Vault(VaultArgs),
+    #[command(about = "Move a knot into another repo, closing it here.")]
+    Transfer(TransferArgs),
+    #[command(about = "Open a knot's PR, CI, or docs link from the repo's URL templates.")]
+    Open(OpenArgs),
+
+    #[command(about = "Link a knot to a commit or branch of the code repo.")]
+    Link(LinkArgs),
+    #[command(about = "Manage a knot's acceptance criteria checklist.")]
+    Criteria(CriteriaArgs),
+    #[command(about = "Attach plan documents to knots and plan knots into calendar weeks.")]
+    Plan(PlanArgs),
+
+    #[command(about = "Suggest, create, or check out a code branch named after a knot.")]
+    Branch(BranchArgs),
+
+    #[command(about = "Render a markdown PR description from a knot.")]
+    PrBody(PrBodyArgs),
+
+    #[command(about = "Advance knots referenced by a merged PR; run from CI after merge.")]
+    AutomergeHook(AutomergeHookArgs),
     #[command(about = "Cold-tier operations.")]
     Cold(ColdArgs),
     #[command(about = "Rehydrate one knot from warm/cold/event data.")]
     Rehydrate(RehydrateArgs),
+    #[command(about = "Rebuild knots as of a timestamp without touching the cache.")]
+    Replay(ReplayArgs),
+    #[command(about = "Get, set, or list operational settings.")]
+    Config(ConfigArgs),
+    #[command(about = "Diff one knot over a time window, or two knots side by side.")]
+    Diff(DiffArgs),
     #[command(about = "Manage knot edges.")]
     Edge(EdgeArgs),
+    #[command(about = "Inspect tags in use.")]
+    Tag(TagArgs),
     #[command(about = "Manage gate decisions and metadata.")]
     Gate(GateArgs),
     #[command(about = "Advance a knot to its next happy-path state.")]
@@ -126,6 +233,16 @@ pub enum Commands {
         alias = "rb"
     )]
     Rollback(RollbackArgs),
+    #[command(about = "Defer a knot until a wake date, then resume it automatically.")]
+    Snooze(SnoozeArgs),
+    #[command(about = "Release a claimed knot back to its ready state, with a reason.")]
+    Handback(HandbackArgs),
+    #[command(about = "Track specific knots; pulls put their changes in `kno inbox`.")]
+    Watch(WatchArgs),
+    #[command(about = "List or acknowledge changes to watched knots.")]
+    Inbox(InboxArgs),
+    #[command(about = "Read the raw event stream.")]
+    Events(EventsArgs),
     #[command(about = "Print the skill prompt for a knot's next action state.")]
     Skill(SkillArgs),
     #[command(about = "Manage Knots-managed agent skills.")]
@@ -134,12 +251,18 @@ pub enum Commands {
     Q(QuickNewArgs),
     #[command(about = "Generate or install shell completions.")]
     Completions(CompletionsArgs),
+    #[command(about = "Generate man pages for kno and its commands.")]
+    Man(ManArgs),
     #[command(about = "Peek at the highest-priority claimable knot.")]
     Poll(PollArgs),
+    #[command(about = "Rank claimable knots for an agent, with reasons.")]
+    Suggest(SuggestArgs),
     #[command(about = "Claim a knot and get its action prompt.")]
     Claim(ClaimArgs),
     #[command(about = "List knots queued for action (ready_for_* states).")]
     Ready(ReadyArgs),
+    #[command(about = "Inspect queue utilization against WIP limits.")]
+    Queue(QueueArgs),
     #[command(about = "Manage step execution history.")]
     Step(StepArgs),
     #[command(about = "Manage lease sessions.")]
@@ -149,20 +272,14 @@ pub enum Commands {
 }
 
 #[derive(Debug, Args)]
-#[command(about = "Quick-create a knot.")]
-pub struct QuickNewArgs {
-    #[arg(help = "Knot title.")]
-    pub title: String,
-
-    #[arg(short = 'd', long = "desc", help = "Optional description text.")]
-    pub desc: Option<String>,
-
+#[command(about = "Generate man pages.")]
+pub struct ManArgs {
     #[arg(
-        short = 's',
         long,
-        help = "Initial knot state (defaults to profile initial_state)."
+        value_name = "DIR",
+        help = "Write kno.1 and one page per command into DIR instead of printing kno.1."
     )]
-    pub state: Option<String>,
+    pub out_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Args)]
@@ -179,73 +296,6 @@ pub struct CompletionsArgs {
     pub install: bool,
 }
 
-#[derive(Debug, Args)]
-#[command(about = "Create a new knot.")]
-pub struct NewArgs {
-    #[arg(help = "Knot title.")]
-    pub title: String,
-
-    #[arg(short = 'd', long = "desc", help = "Optional description text.")]
-    pub desc: Option<String>,
-
-    #[arg(long, help = "Optional acceptance criteria.")]
-    pub acceptance: Option<String>,
-
-    #[arg(
-        short = 's',
-        long,
-        help = "Initial knot state (defaults to profile initial_state)."
-    )]
-    pub state: Option<String>,
-
-    #[arg(
-        short = 'p',
-        long = "profile",
-        help = "Profile id (defaults to the user default profile)."
-    )]
-    pub profile: Option<String>,
-
-    #[arg(
-        short = 'w',
-        long = "workflow",
-        help = "Workflow id (defaults to the repo default workflow)."
-    )]
-    pub workflow: Option<String>,
-
-    #[arg(
-        short = 'k',
-        long = "type",
-        help = "Knot type (work, gate, lease, or explore)."
-    )]
-    pub knot_type: Option<String>,
-
-    #[arg(long = "gate-owner-kind", help = "Gate owner kind: human or agent.")]
-    pub gate_owner_kind: Option<String>,
-
-    #[arg(
-        long = "gate-failure-mode",
-        help = "Gate failure mapping '<invariant>=<knot-id[,knot-id...]>' (repeatable)."
-    )]
-    pub gate_failure_modes: Vec<String>,
-
-    #[arg(
-        short = 'f',
-        long = "fast",
-        help = "Use the default quick profile (skips planning)."
-    )]
-    pub fast: bool,
-
-    #[arg(
-        short = 'e',
-        long = "exploration",
-        help = "Use the explore knot type (lightweight investigation)."
-    )]
-    pub exploration: bool,
-
-    #[arg(long, help = "Bind a lease to this knot.")]
-    pub lease: Option<String>,
-}
-
 #[derive(Debug, Args)]
 #[command(about = "Set knot state.")]
 pub struct StateArgs {
@@ -281,6 +331,12 @@ pub struct StateArgs {
 
     #[arg(long = "agent-version", help = "Agent version for step metadata.")]
     pub agent_version: Option<String>,
+
+    #[arg(
+        long = "idempotency-key",
+        help = "Return the original result when this key was already applied."
+    )]
+    pub idempotency_key: Option<String>,
 }
 
 #[derive(Debug, Args)]
@@ -305,9 +361,26 @@ pub struct ListArgs {
     #[arg(short = 'p', long = "profile", help = "Filter by profile id.")]
     pub profile_id: Option<String>,
 
-    #[arg(short = 'g', long = "tag", help = "Require tag (repeatable).")]
+    #[arg(
+        short = 'g',
+        long = "tag",
+        help = "Require tag (repeatable); `ns/*` matches any tag in namespace `ns`."
+    )]
     pub tags: Vec<String>,
 
+    #[arg(
+        long = "field",
+        value_name = "NAME=VALUE",
+        help = "Require a custom field value (repeatable)."
+    )]
+    pub fields: Vec<String>,
+
+    #[arg(
+        long,
+        help = "Only knots planned into this week: current, next, last, or 2026-W42."
+    )]
+    pub week: Option<String>,
+
     #[arg(
         short = 'q',
         long,
@@ -327,91 +400,42 @@ pub struct ListArgs {
 
     #[arg(long, help = "Stream results as one JSON object per line (NDJSON).")]
     pub stream: bool,
-}
-
-#[derive(Debug, Args)]
-#[command(about = "Show one knot.")]
-pub struct ShowArgs {
-    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
-    pub id: String,
-
-    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
-    pub json: bool,
-
-    #[arg(short = 'v', long, help = "Show all notes and handoff capsules.")]
-    pub verbose: bool,
-}
 
-#[derive(Debug, Args)]
-#[command(about = "Profile commands.")]
-pub struct ProfileArgs {
-    #[command(subcommand)]
-    pub command: ProfileSubcommands,
-}
+    #[arg(
+        long,
+        value_name = "COLUMNS",
+        help = "Render a table with these columns, e.g. id,state,priority,title:50,tags."
+    )]
+    pub columns: Option<String>,
 
-#[derive(Debug, Subcommand)]
-pub enum ProfileSubcommands {
-    #[command(about = "List available profiles.", alias = "ls")]
-    List(ProfileListArgs),
-    #[command(about = "Show one profile definition.")]
-    Show(ProfileShowArgs),
-    #[command(about = "Set the user default profile id.")]
-    SetDefault(ProfileSetDefaultArgs),
-    #[command(about = "Set the user default quick profile id.")]
-    SetDefaultQuick(ProfileSetDefaultArgs),
-    #[command(about = "Set one knot profile and optionally remap state.")]
-    Set(ProfileSetArgs),
-}
+    #[arg(
+        long,
+        help = "Widen table columns to fit every cell instead of truncating."
+    )]
+    pub no_truncate: bool,
 
-#[derive(Debug, Args)]
-#[command(about = "List profiles.")]
-pub struct ProfileListArgs {
-    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
-    pub json: bool,
+    #[arg(
+        long,
+        help = "Keep the fields the repo config lists as sensitive_fields in JSON output."
+    )]
+    pub include_sensitive: bool,
 }
 
 #[derive(Debug, Args)]
-#[command(about = "Show one profile definition.")]
-pub struct ProfileShowArgs {
-    #[arg(help = "Profile id.")]
+#[command(about = "Show one knot.")]
+pub struct ShowArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
     pub id: String,
 
     #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
     pub json: bool,
-}
-
-#[derive(Debug, Args)]
-#[command(about = "Set the user default profile.")]
-pub struct ProfileSetDefaultArgs {
-    #[arg(help = "Profile id.")]
-    pub id: String,
-}
-
-#[derive(Debug, Args)]
-#[command(about = "Set one knot profile.")]
-pub struct ProfileSetArgs {
-    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
-    pub id: String,
-
-    #[arg(help = "Target profile id.")]
-    pub profile: String,
-
-    #[arg(short = 's', long, help = "Target state in the new profile.")]
-    pub state: Option<String>,
 
     #[arg(
-        short = 'm',
-        long = "if-match",
-        help = "Require this profile etag to match before writing."
+        short = 'v',
+        long,
+        help = "Show all notes, handoff capsules, and handbacks."
     )]
-    pub if_match: Option<String>,
-}
-
-#[derive(Debug, Args)]
-#[command(about = "Replication output options.")]
-pub struct SyncArgs {
-    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
-    pub json: bool,
+    pub verbose: bool,
 }
 
 #[cfg(test)]
@@ -33,6 +33,11 @@ pub struct NextArgs {
     pub cascade_terminal_descendants: bool,
     #[arg(long, help = "Validate lease ownership before advancing.")]
     pub lease: Option<String>,
+    #[arg(
+        long,
+        help = "Treat the id as a parent and advance each eligible child instead."
+    )]
+    pub subtree: bool,
 }
 
 #[derive(Debug, Args)]
@@ -55,6 +60,57 @@ pub struct RollbackArgs {
     pub agent_version: Option<String>,
 }
 
+#[derive(Debug, Args)]
+#[command(about = "Defer a knot until a wake date, then resume it automatically.")]
+pub struct SnoozeArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+    #[arg(
+        long,
+        help = "Wake date (YYYY-MM-DD, midnight UTC) or RFC 3339 timestamp."
+    )]
+    pub until: String,
+    #[arg(long = "actor-kind", help = "Actor kind for the step: human or agent.")]
+    pub actor_kind: Option<String>,
+    #[arg(long = "agent-name", help = "Agent name for step metadata.")]
+    pub agent_name: Option<String>,
+    #[arg(long = "agent-model", help = "Agent model for step metadata.")]
+    pub agent_model: Option<String>,
+    #[arg(long = "agent-version", help = "Agent version for step metadata.")]
+    pub agent_version: Option<String>,
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Release a claimed knot back to its ready state, with a reason.")]
+pub struct HandbackArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+    #[arg(
+        long,
+        help = "Why: blocked, needs_input, out_of_scope, failed, or other."
+    )]
+    pub reason: String,
+    #[arg(long, help = "Detail recorded with the reason.")]
+    pub note: Option<String>,
+    #[arg(
+        long,
+        value_name = "SPAN",
+        help = "Keep the same agent from reclaiming the knot for this long (e.g. 30m, 2h)."
+    )]
+    pub cooldown: Option<String>,
+    #[arg(long = "actor-kind", help = "Actor kind for the step: human or agent.")]
+    pub actor_kind: Option<String>,
+    #[arg(
+        long = "agent-name",
+        help = "Agent name for step metadata (default: the claiming agent)."
+    )]
+    pub agent_name: Option<String>,
+    #[arg(long = "agent-model", help = "Agent model for step metadata.")]
+    pub agent_model: Option<String>,
+    #[arg(long = "agent-version", help = "Agent version for step metadata.")]
+    pub agent_version: Option<String>,
+}
+
 #[derive(Debug, Args)]
 #[command(about = "Print skill for knot's next state.")]
 pub struct SkillArgs {
@@ -87,6 +143,24 @@ pub struct PollArgs {
     pub timeout_seconds: Option<u64>,
 }
 
+#[derive(Debug, Args)]
+#[command(about = "Rank claimable knots for an agent, with reasons.")]
+pub struct SuggestArgs {
+    #[arg(long, help = "Agent name; matches [agents.<name>] in the repo config.")]
+    pub agent: String,
+    #[arg(help = "Optional stage filter (e.g. implementation).")]
+    pub stage: Option<String>,
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 5,
+        help = "Most suggestions to show."
+    )]
+    pub limit: usize,
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
 #[derive(Debug, Args)]
 #[command(about = "Claim a knot and get its action prompt.")]
 pub struct ClaimArgs {
@@ -108,6 +182,26 @@ pub struct ClaimArgs {
     pub lease: Option<String>,
     #[arg(long, help = "Lease timeout in seconds (default: 600).")]
     pub timeout_seconds: Option<u64>,
+    #[arg(
+        long,
+        help = "Claim past a WIP limit on the next state or a handback cooldown."
+    )]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct SummarizeArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+    #[arg(
+        long,
+        help = "Skip summarize.endpoint and pick key sentences from the notes."
+    )]
+    pub extractive: bool,
+    #[arg(long = "dry-run", help = "Print the summary without adding a capsule.")]
+    pub dry_run: bool,
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
 }
 
 #[derive(Debug, Args)]
@@ -121,6 +215,17 @@ pub struct UpdateArgs {
     pub description: Option<String>,
     #[arg(long, help = "Set acceptance criteria.")]
     pub acceptance: Option<String>,
+    #[arg(
+        long,
+        help = "Set estimate: points (3, 3pt) or a duration (90m, 2h, 1d); empty clears."
+    )]
+    pub estimate: Option<String>,
+    #[arg(
+        long = "field",
+        value_name = "NAME=VALUE",
+        help = "Set a custom field (repeatable); an empty value clears it."
+    )]
+    pub fields: Vec<String>,
     #[arg(short = 'p', long, help = "Set priority (0-4).")]
     pub priority: Option<i64>,
     #[arg(short = 's', long, help = "Set state.")]
@@ -213,6 +318,11 @@ pub struct UpdateArgs {
     pub cascade_terminal_descendants: bool,
     #[arg(long, help = "Bind a lease to this knot.")]
     pub lease: Option<String>,
+    #[arg(
+        long = "idempotency-key",
+        help = "Return the original result when this key was already applied."
+    )]
+    pub idempotency_key: Option<String>,
 }
 
 #[derive(Debug, Args)]
@@ -0,0 +1,48 @@
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Args)]
+#[command(about = "Manage a knot's acceptance criteria checklist.")]
+pub struct CriteriaArgs {
+    #[command(subcommand)]
+    pub command: CriteriaSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CriteriaSubcommands {
+    #[command(about = "Add an unchecked acceptance criterion.")]
+    Add(CriteriaAddArgs),
+    #[command(about = "Check a criterion off by its number.")]
+    Check(CriteriaCheckArgs),
+    #[command(about = "Uncheck a criterion by its number.")]
+    Uncheck(CriteriaCheckArgs),
+    #[command(about = "List a knot's criteria as a checklist.")]
+    List(CriteriaListArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CriteriaAddArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+    #[arg(help = "What must hold for the knot to be done.")]
+    pub text: String,
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct CriteriaCheckArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+    #[arg(help = "Criterion number, as shown by `kno criteria list`.")]
+    pub number: usize,
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct CriteriaListArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
@@ -0,0 +1,45 @@
+use clap::{Args, Subcommand, ValueEnum};
+
+#[derive(Debug, Args)]
+pub struct DbArgs {
+    #[command(subcommand)]
+    pub command: DbSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DbSubcommands {
+    #[command(about = "Show schema versions and applied migrations.")]
+    Info(DbInfoArgs),
+    #[command(about = "Check integrity, truncate the WAL, and vacuum the cache.")]
+    Vacuum(DbVacuumArgs),
+    #[command(about = "Move the cache between the repo and the XDG cache directory.")]
+    Move(DbMoveArgs),
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub enum DbLocation {
+    Repo,
+    Xdg,
+}
+
+#[derive(Debug, Args)]
+pub struct DbMoveArgs {
+    #[arg(
+        long,
+        value_enum,
+        help = "repo (.knots/cache) or xdg ($XDG_CACHE_HOME/knots/<hash>)."
+    )]
+    pub to: DbLocation,
+}
+
+#[derive(Debug, Args)]
+pub struct DbVacuumArgs {
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct DbInfoArgs {
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
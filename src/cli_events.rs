@@ -0,0 +1,59 @@
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Args)]
+pub struct EventsArgs {
+    #[command(subcommand)]
+    pub command: EventsSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum EventsSubcommands {
+    #[command(about = "Print recent events as JSON lines, optionally following new ones.")]
+    Tail(EventsTailArgs),
+    #[command(about = "Record an event from an external tool, validated against its type.")]
+    Emit(EventsEmitArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct EventsTailArgs {
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 10,
+        help = "How many existing events to print first."
+    )]
+    pub lines: usize,
+    #[arg(
+        short = 'f',
+        long,
+        help = "Keep running and print events as they are written or pulled."
+    )]
+    pub follow: bool,
+    #[arg(
+        long = "interval-ms",
+        default_value_t = 500,
+        requires = "follow",
+        help = "With --follow, how often to look for new events."
+    )]
+    pub interval_ms: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct EventsEmitArgs {
+    #[arg(
+        long = "type",
+        value_name = "TYPE",
+        help = "Event type, e.g. knot.note_added."
+    )]
+    pub event_type: String,
+    #[arg(long, help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub knot: String,
+    #[arg(
+        long,
+        value_name = "JSON",
+        help = "Event data as inline JSON, @file, or @- for stdin."
+    )]
+    pub data: String,
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
@@ -1,5 +1,3 @@
-use std::io::IsTerminal;
-
 use clap::CommandFactory;
 
 use crate::cli::Cli;
@@ -18,7 +16,7 @@ pub fn is_toplevel_help(args: &[String]) -> bool {
 }
 
 pub fn print_custom_help() {
-    let color = std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal();
+    let color = crate::ui::stdout_color_enabled();
     print_custom_help_with_color(color);
 }
 
@@ -60,6 +58,16 @@ fn print_custom_help_with_color(color: bool) {
         }
     }
 
+    println!("\n{}", p.cyan_bold("Guides:"));
+    for topic in crate::help_topics::TOPICS {
+        let name = format!("help {}", topic.name);
+        println!(
+            "  {}  {}",
+            p.green_bold(&format!("{name:<pad$}")),
+            topic.summary
+        );
+    }
+
     println!("\n{}", p.cyan_bold("Options:"));
     for arg in cmd.get_arguments() {
         if arg.is_hide_set() {
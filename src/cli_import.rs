@@ -0,0 +1,252 @@
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Args)]
+#[command(about = "Import knots from another source.")]
+pub struct ImportArgs {
+    #[command(subcommand)]
+    pub command: ImportSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ImportSubcommands {
+    #[command(about = "Copy a knot subtree from another knots repo, with new ids.")]
+    Knots(ImportKnotsArgs),
+    #[command(about = "Write title, state, and tag edits from an org file back to knots.")]
+    Org(ImportOrgArgs),
+    #[command(about = "Stream knots from a JSONL dump, one record per line; resumable.")]
+    Jsonl(ImportJsonlArgs),
+    #[command(about = "Close the knots an earlier `kno import knots` run brought in.")]
+    Rollback(ImportRollbackArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ImportKnotsArgs {
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Source repo root or its .knots directory."
+    )]
+    pub from: PathBuf,
+
+    #[arg(
+        long,
+        value_name = "ID",
+        help = "Source knot (full id or short suffix) to copy with its descendants."
+    )]
+    pub subtree: String,
+
+    #[arg(long, help = "Show what would be imported without writing events.")]
+    pub dry_run: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportOrgArgs {
+    #[arg(value_name = "PATH", help = "Org file written by `kno export org`.")]
+    pub path: PathBuf,
+
+    #[arg(long, help = "Show what would change without writing events.")]
+    pub dry_run: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportJsonlArgs {
+    #[arg(
+        value_name = "PATH",
+        help = "JSONL file with one knot record per line."
+    )]
+    pub path: PathBuf,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Lines between saved checkpoints (default 500)."
+    )]
+    pub chunk_size: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 1,
+        help = "Threads parsing lines ahead of the writer."
+    )]
+    pub workers: usize,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "TOML file mapping the exporter's fields, states, and tags onto knots."
+    )]
+    pub mapping: Option<PathBuf>,
+
+    #[arg(long, help = "Continue an interrupted import of the same file.")]
+    pub resume: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "resume",
+        help = "Discard an interrupted import's checkpoint and start over."
+    )]
+    pub restart: bool,
+
+    #[arg(long, help = "Check every line without writing events.")]
+    pub dry_run: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportRollbackArgs {
+    #[arg(
+        long,
+        value_name = "KEY",
+        help = "Import run id, or the source repo whose imports to roll back."
+    )]
+    pub source: String,
+
+    #[arg(long, help = "Show what would be closed without writing events.")]
+    pub dry_run: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Export knots to another format.")]
+pub struct ExportArgs {
+    #[command(subcommand)]
+    pub command: ExportSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ExportSubcommands {
+    #[command(about = "Write knots as an org-mode outline with TODO states from their profiles.")]
+    Org(ExportOrgArgs),
+    #[command(
+        about = "Write a static, read-only copy of the dashboard: board, knot pages, and graph."
+    )]
+    Html(ExportHtmlArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ExportOrgArgs {
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        help = "File to write instead of stdout."
+    )]
+    pub output: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub filter: ExportFilterArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportHtmlArgs {
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Directory to write index.html into, e.g. a GitHub Pages folder."
+    )]
+    pub out: PathBuf,
+
+    #[command(flatten)]
+    pub filter: ExportFilterArgs,
+}
+
+/// The `ls` filters, for exporting a subset of knots. Unlike `ls`, every
+/// state is included unless `--state` narrows it.
+#[derive(Debug, Args)]
+pub struct ExportFilterArgs {
+    #[arg(short = 's', long, help = "Export only knots in this state.")]
+    pub state: Option<String>,
+
+    #[arg(short = 't', long = "type", help = "Export only knots of this type.")]
+    pub knot_type: Option<String>,
+
+    #[arg(
+        short = 'p',
+        long = "profile",
+        help = "Export only knots with this profile id."
+    )]
+    pub profile_id: Option<String>,
+
+    #[arg(
+        short = 'g',
+        long = "tag",
+        help = "Require tag (repeatable); `ns/*` matches any tag in namespace `ns`."
+    )]
+    pub tags: Vec<String>,
+
+    #[arg(
+        long = "field",
+        value_name = "NAME=VALUE",
+        help = "Require a custom field value (repeatable)."
+    )]
+    pub fields: Vec<String>,
+
+    #[arg(
+        short = 'q',
+        long,
+        help = "Text query over id, alias, title, and description."
+    )]
+    pub query: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "ID",
+        help = "Export only this knot and its parent_of descendants."
+    )]
+    pub subtree: Option<String>,
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Mirror knots as notes in an Obsidian or markdown vault.")]
+pub struct VaultArgs {
+    #[command(subcommand)]
+    pub command: VaultSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum VaultSubcommands {
+    #[command(about = "Write knot notes to the vault and apply note edits back to knots.")]
+    Sync(VaultSyncArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct VaultSyncArgs {
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Vault folder to sync (default: the vault.path setting)."
+    )]
+    pub vault: Option<PathBuf>,
+
+    #[arg(long, help = "Show what would change without writing notes or events.")]
+    pub dry_run: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Move a knot and its event history into another repo.")]
+pub struct TransferArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+
+    #[arg(long, value_name = "PATH", help = "Target repo root.")]
+    pub to: PathBuf,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+#[derive(Debug, Default, Args)]
+pub struct InitArgs {
+    #[arg(
+        short = 'i',
+        long,
+        help = "Prompt for default profile, sync policy, branch, and id prefix."
+    )]
+    pub interactive: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "interactive",
+        help = "Bootstrap from the existing remote knots branch: load the newest \
+                snapshots, apply newer events, and report tier counts."
+    )]
+    pub from_remote: bool,
+
+    #[command(flatten)]
+    pub knots_ref: KnotsRefArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct UninitArgs {
+    #[arg(long, help = "Remove the store even when events have not been pushed.")]
+    pub force: bool,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Write events, index, snapshots, and config to a .tar.zst archive first."
+    )]
+    pub export: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Args)]
+pub struct KnotsRefArgs {
+    #[arg(long, help = "Branch that carries knots data (default: knots).")]
+    pub branch: Option<String>,
+
+    #[arg(long, help = "Git remote for the knots branch (default: origin).")]
+    pub remote: Option<String>,
+}
+
+impl KnotsRefArgs {
+    pub fn is_set(&self) -> bool {
+        self.branch.is_some() || self.remote.is_some()
+    }
+}
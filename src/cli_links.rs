@@ -0,0 +1,107 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub struct OpenArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+
+    #[arg(help = "Link name from [links] in .knots/config.toml, e.g. pr, ci, docs.")]
+    pub link: Option<String>,
+
+    #[arg(long, help = "Print the URL instead of opening a browser.")]
+    pub print: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct LinkArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+
+    #[arg(
+        long,
+        value_name = "SHA",
+        required_unless_present = "branch",
+        conflicts_with = "branch",
+        help = "Commit to link; any revision git can resolve."
+    )]
+    pub commit: Option<String>,
+
+    #[arg(long, value_name = "NAME", help = "Branch to link.")]
+    pub branch: Option<String>,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct BranchArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+
+    #[arg(long, help = "Create the branch at HEAD if it does not exist.")]
+    pub create: bool,
+
+    #[arg(long, help = "Create the branch if needed and check it out.")]
+    pub checkout: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct PrBodyArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct AutomergeHookArgs {
+    #[arg(long, value_name = "SHA", help = "Merge commit of the PR.")]
+    pub commit: String,
+
+    #[arg(
+        long,
+        value_name = "REF",
+        help = "PR number or URL, noted on the knot."
+    )]
+    pub pr: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "PR head branch; advances knots linked to it."
+    )]
+    pub branch: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "TEXT",
+        help = "PR title and body; KNO: <id> lines name knots."
+    )]
+    pub message: Option<String>,
+
+    #[arg(
+        long = "knot",
+        value_name = "ID",
+        help = "Knot to advance; repeatable."
+    )]
+    pub knots: Vec<String>,
+
+    #[arg(long = "actor-kind", help = "Actor kind for the step: human or agent.")]
+    pub actor_kind: Option<String>,
+
+    #[arg(long = "agent-name", help = "Agent name for step metadata.")]
+    pub agent_name: Option<String>,
+
+    #[arg(long = "agent-model", help = "Agent model for step metadata.")]
+    pub agent_model: Option<String>,
+
+    #[arg(long = "agent-version", help = "Agent version for step metadata.")]
+    pub agent_version: Option<String>,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
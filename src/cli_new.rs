@@ -0,0 +1,126 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+#[command(about = "Create a new knot.")]
+pub struct NewArgs {
+    #[arg(help = "Knot title.")]
+    pub title: String,
+
+    #[arg(short = 'd', long = "desc", help = "Optional description text.")]
+    pub desc: Option<String>,
+
+    #[arg(long, help = "Optional acceptance criteria.")]
+    pub acceptance: Option<String>,
+
+    #[arg(
+        long,
+        help = "Optional estimate: points (3, 3pt) or a duration (90m, 2h, 1d)."
+    )]
+    pub estimate: Option<String>,
+
+    #[arg(
+        long = "field",
+        value_name = "NAME=VALUE",
+        help = "Set a custom field declared in the repo config (repeatable)."
+    )]
+    pub fields: Vec<String>,
+
+    #[arg(
+        short = 's',
+        long,
+        help = "Initial knot state (defaults to profile initial_state)."
+    )]
+    pub state: Option<String>,
+
+    #[arg(
+        short = 'p',
+        long = "profile",
+        help = "Profile id (defaults to the user default profile)."
+    )]
+    pub profile: Option<String>,
+
+    #[arg(
+        short = 'w',
+        long = "workflow",
+        help = "Workflow id (defaults to the repo default workflow)."
+    )]
+    pub workflow: Option<String>,
+
+    #[arg(
+        short = 'k',
+        long = "type",
+        help = "Knot type (work, gate, lease, or explore)."
+    )]
+    pub knot_type: Option<String>,
+
+    #[arg(long = "gate-owner-kind", help = "Gate owner kind: human or agent.")]
+    pub gate_owner_kind: Option<String>,
+
+    #[arg(
+        long = "gate-failure-mode",
+        help = "Gate failure mapping '<invariant>=<knot-id[,knot-id...]>' (repeatable)."
+    )]
+    pub gate_failure_modes: Vec<String>,
+
+    #[arg(
+        short = 'f',
+        long = "fast",
+        help = "Use the default quick profile (skips planning)."
+    )]
+    pub fast: bool,
+
+    #[arg(
+        short = 'e',
+        long = "exploration",
+        help = "Use the explore knot type (lightweight investigation)."
+    )]
+    pub exploration: bool,
+
+    #[arg(long, help = "Bind a lease to this knot.")]
+    pub lease: Option<String>,
+
+    #[arg(
+        long = "idempotency-key",
+        help = "Return the original result when this key was already applied."
+    )]
+    pub idempotency_key: Option<String>,
+
+    #[arg(
+        long,
+        help = "Keep the knot's events in this clone only; `kno publish` shares it later."
+    )]
+    pub private: bool,
+
+    #[arg(
+        long,
+        help = "Stage the knot as a draft; `kno publish` shares it with fresh timestamps."
+    )]
+    pub draft: bool,
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Quick-create a knot.")]
+pub struct QuickNewArgs {
+    #[arg(help = "Knot title.")]
+    pub title: String,
+
+    #[arg(short = 'd', long = "desc", help = "Optional description text.")]
+    pub desc: Option<String>,
+
+    #[arg(
+        short = 's',
+        long,
+        help = "Initial knot state (defaults to profile initial_state)."
+    )]
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Share a private or draft knot by moving its events into the published stream.")]
+pub struct PublishArgs {
+    #[arg(help = "Private or draft knot id or alias.")]
+    pub id: String,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
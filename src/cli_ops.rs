@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use clap::{Args, Subcommand};
+use clap::{Args, Subcommand, ValueEnum};
 
 #[derive(Debug, Args)]
 #[command(about = "Manage named Knots projects.")]
@@ -131,13 +131,34 @@ pub struct SelfUpdateArgs {
     #[arg(short = 'i', long, help = "Install destination directory.")]
     pub install_dir: Option<PathBuf>,
 
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ReleaseChannel::Stable,
+        help = "Release channel to follow when no --version is given."
+    )]
+    pub channel: ReleaseChannel,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["version", "install_dir", "script_url"],
+        help = "Report the latest release on the channel without installing."
+    )]
+    pub check: bool,
+
     #[arg(
         short = 'u',
         long,
-        default_value = "https://raw.githubusercontent.com/acartine/knots/main/install.sh",
-        help = "Installer script URL."
+        help = "Run this installer script instead of the checksum-verified download."
     )]
-    pub script_url: String,
+    pub script_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum, PartialEq, Eq)]
+pub enum ReleaseChannel {
+    #[default]
+    Stable,
+    Beta,
 }
 
 #[derive(Debug, Args)]
@@ -172,6 +193,8 @@ pub enum EdgeSubcommands {
     Remove(EdgeRemoveArgs),
     #[command(about = "List edges for a knot.")]
     List(EdgeListArgs),
+    #[command(about = "Add and remove many edges from a file of `src kind dst` lines.")]
+    Apply(EdgeApplyArgs),
 }
 
 #[derive(Debug, Args)]
@@ -212,6 +235,22 @@ pub struct EdgeListArgs {
     pub json: bool,
 }
 
+#[derive(Debug, Args)]
+pub struct EdgeApplyArgs {
+    #[arg(
+        value_name = "FILE",
+        help = "Edge file, or - for stdin. One `src kind dst` per line; \
+                a leading - removes the edge and # starts a comment."
+    )]
+    pub file: String,
+
+    #[arg(long, help = "Show the edges that would change without writing them.")]
+    pub dry_run: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
 #[derive(Debug, Args)]
 #[command(about = "Cold-tier commands.")]
 pub struct ColdArgs {
@@ -225,6 +264,20 @@ pub enum ColdSubcommands {
     Sync(crate::cli::SyncArgs),
     #[command(about = "Search cold catalog by term.")]
     Search(ColdSearchArgs),
+    #[command(about = "List every knot in the cold catalog.")]
+    Ls(ColdLsArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ColdLsArgs {
+    #[arg(
+        long,
+        help = "Read the newest cold snapshot file directly instead of the cache."
+    )]
+    pub from_snapshot: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
 }
 
 #[derive(Debug, Args)]
@@ -248,31 +301,30 @@ pub struct FsckArgs {
 }
 
 #[derive(Debug, Args)]
-#[command(about = "Run repository diagnostics.")]
-pub struct DoctorArgs {
+pub struct StatusArgs {
     #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
     pub json: bool,
-
-    #[arg(long, help = "Attempt to fix non-pass doctor checks.")]
-    pub fix: bool,
 }
 
 #[derive(Debug, Args)]
-#[command(about = "Run performance harness.")]
-pub struct PerfArgs {
+#[command(about = "Run repository diagnostics.")]
+pub struct DoctorArgs {
     #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
     pub json: bool,
 
     #[arg(
-        short = 'n',
         long,
-        default_value_t = 5,
-        help = "Number of harness iterations."
+        help = "Attempt to fix non-pass doctor checks, asking about each one on a terminal."
     )]
-    pub iterations: u32,
+    pub fix: bool,
 
-    #[arg(short = 'S', long, help = "Fail when any measurement is over budget.")]
-    pub strict: bool,
+    #[arg(
+        short = 'y',
+        long,
+        requires = "fix",
+        help = "With --fix, apply every fix without asking."
+    )]
+    pub yes: bool,
 }
 
 #[derive(Debug, Args)]
@@ -289,6 +341,25 @@ pub struct CompactArgs {
     pub json: bool,
 }
 
+#[derive(Debug, Args)]
+#[command(about = "Inspect and verify catalog snapshots.")]
+pub struct SnapshotArgs {
+    #[command(subcommand)]
+    pub command: SnapshotSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SnapshotSubcommands {
+    #[command(about = "Check the newest snapshot against a replay of the event log.")]
+    Verify(SnapshotVerifyArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct SnapshotVerifyArgs {
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
 #[derive(Debug, Args)]
 #[command(about = "Rehydrate one knot.")]
 pub struct RehydrateArgs {
@@ -299,6 +370,108 @@ pub struct RehydrateArgs {
     pub json: bool,
 }
 
+#[derive(Debug, Args)]
+#[command(about = "Read and change operational settings.")]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigSubcommands {
+    #[command(about = "Print the effective value of one setting.")]
+    Get(ConfigGetArgs),
+    #[command(about = "Validate and store a setting for this clone or the repo.")]
+    Set(ConfigSetArgs),
+    #[command(about = "Remove a setting so the next layer applies.")]
+    Unset(ConfigUnsetArgs),
+    #[command(about = "List every setting with its value and source.")]
+    List(ConfigListArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigGetArgs {
+    #[arg(help = "Setting key, e.g. sync_policy.")]
+    pub key: String,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigSetArgs {
+    #[arg(help = "Setting key, e.g. sync_policy.")]
+    pub key: String,
+
+    #[arg(help = "New value.")]
+    pub value: String,
+
+    #[arg(
+        long,
+        help = "Write to the shared .knots/config.toml instead of this clone."
+    )]
+    pub repo: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigUnsetArgs {
+    #[arg(help = "Setting key, e.g. sync_policy.")]
+    pub key: String,
+
+    #[arg(
+        long,
+        help = "Remove from the shared .knots/config.toml instead of this clone."
+    )]
+    pub repo: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigListArgs {
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Show field-level changes over time or between two knots.")]
+pub struct DiffArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+
+    #[arg(
+        help = "Second knot to compare against.",
+        required_unless_present = "since",
+        conflicts_with = "since"
+    )]
+    pub other: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "SPAN",
+        help = "Window to diff, e.g. 7d, 12h, 2w, or an RFC 3339 timestamp."
+    )]
+    pub since: Option<String>,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Rebuild knots as of a point in time from the event log.")]
+pub struct ReplayArgs {
+    #[arg(
+        long,
+        value_name = "RFC3339",
+        help = "Replay events up to and including this timestamp."
+    )]
+    pub until: String,
+
+    #[arg(long, help = "Only replay this knot (full id, stripped id, or alias).")]
+    pub id: Option<String>,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
 #[derive(Debug, Args)]
 #[command(about = "List knots queued for action.")]
 pub struct ReadyArgs {
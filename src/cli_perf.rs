@@ -0,0 +1,128 @@
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Args)]
+#[command(about = "Run performance harness.")]
+pub struct PerfArgs {
+    #[command(subcommand)]
+    pub command: Option<PerfSubcommands>,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 5,
+        help = "Number of harness iterations."
+    )]
+    pub iterations: u32,
+
+    #[arg(
+        short = 'S',
+        long,
+        help = "Fail when any measurement is over budget, or regressed past --baseline."
+    )]
+    pub strict: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Compare with a baseline from `kno perf baseline write` instead of fixed budgets."
+    )]
+    pub baseline: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "PCT",
+        help = "Allowed slowdown against --baseline in percent (default: the baseline's, else 20)."
+    )]
+    pub max_regression: Option<f64>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PerfSubcommands {
+    #[command(about = "Manage the committed perf baseline.")]
+    Baseline(PerfBaselineArgs),
+    #[command(about = "Stress-test lock contention with parallel in-process writers.")]
+    Contention(PerfContentionArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct PerfContentionArgs {
+    #[arg(long, default_value_t = 8, help = "Number of parallel writers.")]
+    pub writers: u32,
+
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 10,
+        help = "Create/update/state cycles per writer."
+    )]
+    pub ops: u32,
+
+    #[arg(
+        long,
+        value_name = "SECS",
+        default_value_t = 30,
+        help = "Report writers as stalled after this long without progress."
+    )]
+    pub stall_timeout: u64,
+
+    #[arg(
+        long,
+        value_name = "SIZE",
+        help = "Share one pool of SIZE connections across writers, as a server would."
+    )]
+    pub pool: Option<u32>,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+
+    #[arg(
+        short = 'S',
+        long,
+        help = "Fail on any lock timeout, write error, or stalled writer."
+    )]
+    pub strict: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct PerfBaselineArgs {
+    #[command(subcommand)]
+    pub command: PerfBaselineSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PerfBaselineSubcommands {
+    #[command(about = "Run the harness and save its timings as the baseline.")]
+    Write(PerfBaselineWriteArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct PerfBaselineWriteArgs {
+    #[arg(
+        long,
+        value_name = "PATH",
+        default_value = ".ci/perf-baseline.json",
+        help = "Where to write the baseline."
+    )]
+    pub out: PathBuf,
+
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 5,
+        help = "Number of harness iterations."
+    )]
+    pub iterations: u32,
+
+    #[arg(
+        long,
+        value_name = "PCT",
+        default_value_t = crate::perf::DEFAULT_MAX_REGRESSION_PCT,
+        help = "Allowed slowdown in percent, stored in the baseline."
+    )]
+    pub max_regression: f64,
+}
@@ -0,0 +1,82 @@
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Args)]
+#[command(about = "Attach plan documents to knots and plan knots into calendar weeks.")]
+pub struct PlanArgs {
+    #[command(subcommand)]
+    pub command: PlanSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PlanSubcommands {
+    #[command(about = "Record a new plan version from a file.")]
+    Set(PlanSetArgs),
+    #[command(about = "Print the latest plan, or an earlier version.")]
+    Show(PlanShowArgs),
+    #[command(about = "List a knot's plan versions.")]
+    Log(PlanLogArgs),
+    #[command(about = "Plan ready knots into a calendar week.")]
+    Week(PlanWeekArgs),
+    #[command(about = "Report which of a week's knots finished and which carry over.")]
+    Carryover(PlanCarryoverArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct PlanSetArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+    #[arg(long, value_name = "FILE", help = "Plan document, or - for stdin.")]
+    pub file: String,
+    #[arg(long = "agent-name", help = "Who wrote this version of the plan.")]
+    pub agent_name: Option<String>,
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct PlanShowArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+    #[arg(long, help = "Plan version to print, as listed by `kno plan log`.")]
+    pub version: Option<usize>,
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct PlanLogArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct PlanWeekArgs {
+    #[arg(help = "Knots to plan; picks from ready knots on a terminal when omitted.")]
+    pub ids: Vec<String>,
+    #[arg(
+        long,
+        default_value = "current",
+        help = "current, next, last, or an ISO week like 2026-W42."
+    )]
+    pub week: String,
+    #[arg(long, help = "Take the knots out of the week instead.")]
+    pub remove: bool,
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct PlanCarryoverArgs {
+    #[arg(
+        long,
+        default_value = "current",
+        help = "current, next, last, or an ISO week like 2026-W42."
+    )]
+    pub week: String,
+    #[arg(long, help = "Also plan the unfinished knots into the following week.")]
+    pub carry: bool,
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
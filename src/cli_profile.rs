@@ -0,0 +1,72 @@
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Args)]
+#[command(about = "Profile commands.")]
+pub struct ProfileArgs {
+    #[command(subcommand)]
+    pub command: ProfileSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ProfileSubcommands {
+    #[command(about = "List available profiles.", alias = "ls")]
+    List(ProfileListArgs),
+    #[command(about = "Show one profile definition.")]
+    Show(ProfileShowArgs),
+    #[command(about = "Set the user default profile id.")]
+    SetDefault(ProfileSetDefaultArgs),
+    #[command(about = "Set the user default quick profile id.")]
+    SetDefaultQuick(ProfileSetDefaultArgs),
+    #[command(about = "Set one knot profile and optionally remap state.")]
+    Set(ProfileSetArgs),
+}
+
+#[derive(Debug, Args)]
+#[command(about = "List profiles.")]
+pub struct ProfileListArgs {
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Show one profile definition.")]
+pub struct ProfileShowArgs {
+    #[arg(help = "Profile id.")]
+    pub id: String,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Set the user default profile.")]
+pub struct ProfileSetDefaultArgs {
+    #[arg(help = "Profile id.")]
+    pub id: String,
+
+    #[arg(
+        long,
+        help = "Store in the shared .knots/config.toml; wins over user defaults."
+    )]
+    pub repo: bool,
+}
+
+#[derive(Debug, Args)]
+#[command(about = "Set one knot profile.")]
+pub struct ProfileSetArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+
+    #[arg(help = "Target profile id.")]
+    pub profile: String,
+
+    #[arg(short = 's', long, help = "Target state in the new profile.")]
+    pub state: Option<String>,
+
+    #[arg(
+        short = 'm',
+        long = "if-match",
+        help = "Require this profile etag to match before writing."
+    )]
+    pub if_match: Option<String>,
+}
@@ -0,0 +1,33 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+#[command(
+    about = "Scrub a leaked secret from one event's stored files.",
+    long_about = "Replace each literal --pattern match in an event's data, in the local \
+store and the publish worktree, and record a knot.redacted marker event. If the event \
+was already pushed, the knots branch history still holds the secret: rewrite it and \
+force-push as described in the README."
+)]
+pub struct RedactArgs {
+    #[arg(help = "Id of the event to redact, as in its file name.")]
+    pub event_id: String,
+
+    #[arg(
+        long = "pattern",
+        value_name = "TEXT",
+        required = true,
+        help = "Literal text to replace; repeat for several secrets."
+    )]
+    pub patterns: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "TEXT",
+        default_value = "[REDACTED]",
+        help = "Placeholder written in place of each match."
+    )]
+    pub replacement: String,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
@@ -0,0 +1,292 @@
+use clap::{Args, Subcommand, ValueEnum};
+
+#[derive(Debug, Args)]
+pub struct StatsArgs {
+    #[command(subcommand)]
+    pub command: StatsSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StatsSubcommands {
+    #[command(about = "Compare estimates of shipped knots with cycle and tracked time.")]
+    Estimates(StatsEstimatesArgs),
+    #[command(about = "Show local command latencies and sync sizes (needs metrics.local).")]
+    Usage(StatsUsageArgs),
+    #[command(about = "Histogram of time in the current state per state, with outliers.")]
+    Age(StatsAgeArgs),
+    #[command(about = "Edge counts, longest dependency chain, top blockers, and orphans.")]
+    Graph(StatsGraphArgs),
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub enum StatsGroupBy {
+    Type,
+    Tag,
+}
+
+#[derive(Debug, Args)]
+pub struct StatsEstimatesArgs {
+    #[arg(
+        long,
+        value_enum,
+        default_value = "type",
+        help = "Group knots by type or by tag."
+    )]
+    pub by: StatsGroupBy,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct StatsUsageArgs {
+    #[arg(long, help = "Only report this command, e.g. sync or ls.")]
+    pub command: Option<String>,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct StatsAgeArgs {
+    #[arg(long, help = "Only report knots in this state.")]
+    pub state: Option<String>,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct StatsGraphArgs {
+    #[arg(long, default_value_t = 5, help = "How many blocking knots to list.")]
+    pub top: usize,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ChangelogArgs {
+    #[arg(
+        long,
+        help = "Window start: a git tag, YYYY-MM-DD, yesterday, a span like 7d, or RFC 3339."
+    )]
+    pub since: String,
+
+    #[arg(long, help = "Also list knots that were abandoned.")]
+    pub include_abandoned: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub enum DigestGroupBy {
+    None,
+    Actor,
+}
+
+#[derive(Debug, Args)]
+pub struct DigestArgs {
+    #[command(subcommand)]
+    pub command: Option<DigestSubcommands>,
+
+    #[arg(
+        long,
+        default_value = "yesterday",
+        help = "Window start: today, yesterday, YYYY-MM-DD, a span like 7d, or RFC 3339."
+    )]
+    pub since: String,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "none",
+        help = "Group activity by actor, or not at all."
+    )]
+    pub by: DigestGroupBy,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DigestSubcommands {
+    #[command(about = "Mail or post the review queue and stale knots to [digests] recipients.")]
+    Send(DigestSendArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct DigestSendArgs {
+    #[arg(help = "Digests to send, by name; every configured digest when omitted.")]
+    pub names: Vec<String>,
+
+    #[arg(
+        long,
+        help = "Only send digests whose `every` span has passed since this clone last sent them."
+    )]
+    pub due: bool,
+
+    #[arg(long, help = "Print each digest and its recipients without sending.")]
+    pub dry_run: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct PickArgs {
+    #[arg(short = 's', long, help = "Only offer knots in this state.")]
+    pub state: Option<String>,
+
+    #[arg(short = 't', long = "type", help = "Only offer knots of this type.")]
+    pub knot_type: Option<String>,
+
+    #[arg(
+        short = 'a',
+        long = "all",
+        help = "Include shipped and abandoned knots."
+    )]
+    pub all: bool,
+
+    #[arg(short = 'q', long, help = "Initial search text.")]
+    pub query: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct DedupeArgs {
+    #[command(subcommand)]
+    pub command: DedupeSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DedupeSubcommands {
+    #[command(about = "List pairs of knots whose titles and descriptions look alike.")]
+    Report(DedupeReportArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct DedupeReportArgs {
+    #[arg(
+        long,
+        default_value_t = crate::app::DEFAULT_SIMILARITY_THRESHOLD,
+        help = "Similarity score from 0 to 1 at which a pair is listed."
+    )]
+    pub threshold: f64,
+
+    #[arg(
+        short = 'a',
+        long = "all",
+        help = "Include shipped and abandoned knots."
+    )]
+    pub all: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct SearchArgs {
+    #[arg(help = "What to look for, in your own words.")]
+    pub query: String,
+
+    #[arg(
+        long,
+        help = "Rank by embeddings from semantic.endpoint (needs the `semantic` build feature)."
+    )]
+    pub semantic: bool,
+
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 10,
+        help = "Most results to show."
+    )]
+    pub limit: usize,
+
+    #[arg(
+        short = 'a',
+        long = "all",
+        help = "Include shipped and abandoned knots."
+    )]
+    pub all: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct GrepArgs {
+    #[arg(help = "Text to find; a regular expression with --regex.")]
+    pub pattern: String,
+
+    #[arg(
+        long = "in",
+        value_name = "FIELDS",
+        help = "Comma-separated fields to search: title, description, notes, handoffs, events. \
+                Defaults to all but events."
+    )]
+    pub fields: Option<String>,
+
+    #[arg(short = 'E', long, help = "Treat the pattern as a regular expression.")]
+    pub regex: bool,
+
+    #[arg(short = 'i', long, help = "Match regardless of case.")]
+    pub ignore_case: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+
+    #[arg(
+        long,
+        help = "Keep matches in the fields the repo config lists as sensitive_fields in JSON output."
+    )]
+    pub include_sensitive: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct TagArgs {
+    #[command(subcommand)]
+    pub command: TagSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TagSubcommands {
+    #[command(about = "List tags with knot counts, grouped by namespace.")]
+    List(TagListArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct TagListArgs {
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct QueueArgs {
+    #[command(subcommand)]
+    pub command: QueueSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum QueueSubcommands {
+    #[command(about = "Show how full each WIP-limited state is.")]
+    Status(QueueStatusArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct QueueStatusArgs {
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct LintArgs {
+    #[arg(
+        long,
+        help = "Apply the doctor repairs for the issues that have one, then lint again."
+    )]
+    pub fix: bool,
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
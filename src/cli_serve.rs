@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    #[command(subcommand)]
+    pub command: Option<ServeSubcommands>,
+
+    #[arg(
+        long,
+        help = "Serve push/pull of event files over stdin/stdout, e.g. as an ssh command."
+    )]
+    pub replication: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "replication",
+        help = "Serve a read-only web dashboard of this repo's knots over HTTP."
+    )]
+    pub ui: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "replication",
+        help = "Serve a read-only GraphQL endpoint at /graphql over HTTP."
+    )]
+    pub graphql: bool,
+
+    #[arg(
+        long,
+        default_value = "127.0.0.1:7420",
+        help = "Address the --ui dashboard and --graphql endpoint listen on."
+    )]
+    pub addr: String,
+
+    #[arg(long, help = "Directory that holds the served knots store.")]
+    pub root: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Token and role file for clients (default: server.toml in --root, or in .knots for --ui/--graphql)."
+    )]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ServeSubcommands {
+    #[command(about = "Show the server access log, newest first.")]
+    Audit(ServeAuditArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ServeAuditArgs {
+    #[arg(long, help = "Directory that holds the served knots store.")]
+    pub root: PathBuf,
+
+    #[arg(long, help = "Only show requests made with this token name.")]
+    pub token: Option<String>,
+
+    #[arg(long, default_value_t = 50, help = "Show at most this many requests.")]
+    pub limit: u64,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RpcArgs {
+    #[arg(
+        long,
+        default_value_t = 1000,
+        help = "How often, in milliseconds, to check for knot changes to notify about."
+    )]
+    pub poll_ms: u64,
+}
@@ -0,0 +1,43 @@
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Args)]
+#[command(about = "Manage knot spaces: separate backlogs in one repository.")]
+pub struct SpaceArgs {
+    #[command(subcommand)]
+    pub command: SpaceSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SpaceSubcommands {
+    #[command(about = "List spaces, marking the default.")]
+    List(SpaceListArgs),
+    #[command(about = "Create a space, or set up one another clone created.")]
+    Create(SpaceCreateArgs),
+    #[command(about = "Show or set the space commands use without --space.")]
+    Default(SpaceDefaultArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct SpaceListArgs {
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct SpaceCreateArgs {
+    #[arg(help = "Space name: lowercase letters, digits, '-', or '_'.")]
+    pub name: String,
+}
+
+#[derive(Debug, Args)]
+pub struct SpaceDefaultArgs {
+    #[arg(help = "Space to make the default; `root` for the main store.")]
+    pub name: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "name",
+        help = "Go back to the main store by default."
+    )]
+    pub clear: bool,
+}
@@ -0,0 +1,82 @@
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Args)]
+#[command(about = "Replication output options.")]
+pub struct SyncArgs {
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct SyncCommandArgs {
+    #[command(subcommand)]
+    pub command: Option<SyncSubcommands>,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+
+    #[arg(long, help = NOTIFY_HELP)]
+    pub notify: bool,
+}
+
+const NOTIFY_HELP: &str = "Also pipe the digest of pulled changes to the notify.command sink.";
+
+#[derive(Debug, Subcommand)]
+pub enum SyncSubcommands {
+    #[command(about = "Review events sync skipped for stale preconditions.")]
+    Conflicts(SyncConflictsArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct SyncConflictsArgs {
+    #[arg(long, value_name = "ID", help = "Only list conflicts for this knot.")]
+    pub knot: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CONFLICT",
+        conflicts_with = "dismiss",
+        help = "Re-apply this skipped event to the local cache (needs --force)."
+    )]
+    pub reapply: Option<i64>,
+
+    #[arg(
+        long,
+        value_name = "CONFLICT",
+        help = "Drop this skipped event from review without applying it."
+    )]
+    pub dismiss: Option<i64>,
+
+    #[arg(
+        short = 'f',
+        long,
+        help = "Confirm re-applying a skipped event over newer knot state."
+    )]
+    pub force: bool,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct PullArgs {
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+
+    #[arg(
+        long,
+        help = "List changed knots, stale events, and unreadable event files; \
+                unreadable files are skipped instead of failing the pull."
+    )]
+    pub details: bool,
+
+    #[arg(
+        long,
+        help = "Write progress to stderr as JSON lines for wrapping tools \
+                instead of the spinner and stage messages."
+    )]
+    pub json_progress: bool,
+
+    #[arg(long, help = NOTIFY_HELP)]
+    pub notify: bool,
+}
@@ -97,9 +97,15 @@ fn doctor_json_flag_parses() {
 fn doctor_fix_flag_parses() {
     let cli = parse(&["kno", "doctor", "--fix"]);
     match cli.command {
-        Commands::Doctor(args) => assert!(args.fix),
+        Commands::Doctor(args) => assert!(args.fix && !args.yes),
         other => panic!("expected Doctor, got {:?}", other),
     }
+    let cli = parse(&["kno", "doctor", "--fix", "-y"]);
+    match cli.command {
+        Commands::Doctor(args) => assert!(args.fix && args.yes),
+        other => panic!("expected Doctor, got {:?}", other),
+    }
+    assert!(Cli::try_parse_from(["kno", "doctor", "--yes"]).is_err());
 }
 
 #[test]
@@ -0,0 +1,52 @@
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Args)]
+#[command(about = "Track specific knots; pulls put their changes in `kno inbox`.")]
+pub struct WatchArgs {
+    #[command(subcommand)]
+    pub command: WatchSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum WatchSubcommands {
+    #[command(about = "Start watching a knot.")]
+    Add(WatchIdArgs),
+    #[command(about = "Stop watching a knot.")]
+    Remove(WatchIdArgs),
+    #[command(about = "List watched knots.")]
+    List(WatchListArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct WatchIdArgs {
+    #[arg(help = "Knot full id, stripped id, or hierarchical alias.")]
+    pub id: String,
+}
+
+#[derive(Debug, Args)]
+pub struct WatchListArgs {
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+#[command(about = "List unacknowledged changes to watched knots.")]
+pub struct InboxArgs {
+    #[command(subcommand)]
+    pub command: Option<InboxSubcommands>,
+
+    #[arg(short = 'j', long, help = "Render machine-readable JSON.")]
+    pub json: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum InboxSubcommands {
+    #[command(about = "Acknowledge inbox items; all of them when no ids are given.")]
+    Ack(InboxAckArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct InboxAckArgs {
+    #[arg(value_name = "ITEM", help = "Inbox item numbers from `kno inbox`.")]
+    pub ids: Vec<i64>,
+}
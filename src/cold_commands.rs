@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+
+use crate::app::{self, ColdKnotView};
+use crate::cli::{ColdArgs, ColdLsArgs, ColdSubcommands};
+use crate::print_json;
+use crate::project::ProjectContext;
+use crate::snapshots::read_latest_snapshot;
+
+pub fn run_cold(app: &app::App, args: ColdArgs) -> Result<(), app::AppError> {
+    match args.command {
+        ColdSubcommands::Sync(sync_args) => {
+            let summary = crate::trace::measure("cold_sync", || app.cold_sync())?;
+            if sync_args.json {
+                print_json(&summary);
+            } else {
+                println!(
+                    concat!(
+                        "cold sync head={} index_files={} full_files={} ",
+                        "knot_updates={} edge_adds={} edge_removes={}"
+                    ),
+                    summary.target_head,
+                    summary.index_files,
+                    summary.full_files,
+                    summary.knot_updates,
+                    summary.edge_adds,
+                    summary.edge_removes
+                );
+            }
+        }
+        ColdSubcommands::Search(search_args) => {
+            let matches =
+                crate::trace::measure("cold_search", || app.cold_search(&search_args.term))?;
+            if search_args.json {
+                print_json(&matches);
+            } else if matches.is_empty() {
+                println!("no cold knots matched '{}'", search_args.term);
+            } else {
+                print!("{}", render_cold_knots(&matches));
+            }
+        }
+        ColdSubcommands::Ls(ls_args) => {
+            let knots = crate::trace::measure("cold_list", || app.cold_list())?;
+            print_cold_list(&knots, ls_args.json);
+        }
+    }
+    Ok(())
+}
+
+/// `kno cold ls --from-snapshot`: reads the newest cold snapshot straight
+/// from disk, so it works in a bare checkout of the knots branch where no
+/// cache has ever been built.
+pub fn run_cold_ls_from_snapshot(
+    args: &ColdLsArgs,
+    context: &ProjectContext,
+) -> Result<(), app::AppError> {
+    let dirs = snapshot_dirs(context);
+    let snapshot = read_latest_snapshot(&dirs)?.ok_or_else(|| {
+        app::AppError::InvalidArgument(
+            "no snapshot found; run `kno compact --write-snapshots` and push first".to_string(),
+        )
+    })?;
+    let knots: Vec<ColdKnotView> = snapshot
+        .cold
+        .into_iter()
+        .map(|record| ColdKnotView {
+            id: record.id,
+            title: record.title,
+            state: record.state,
+            updated_at: record.updated_at,
+        })
+        .collect();
+    if !args.json {
+        let source = snapshot.cold_path.unwrap_or(snapshot.active_path);
+        eprintln!("snapshot {} ({})", source.display(), snapshot.written_at);
+    }
+    print_cold_list(&knots, args.json);
+    Ok(())
+}
+
+/// The local store and the knots worktree. In a plain checkout of the
+/// knots branch the branch's `.knots/snapshots` is the local store's.
+fn snapshot_dirs(context: &ProjectContext) -> Vec<PathBuf> {
+    vec![
+        context.store_paths.root.join("snapshots"),
+        context.store_paths.worktree_path().join(".knots/snapshots"),
+    ]
+}
+
+fn print_cold_list(knots: &[ColdKnotView], json: bool) {
+    if json {
+        print_json(&knots);
+    } else if knots.is_empty() {
+        println!("no cold knots");
+    } else {
+        print!("{}", render_cold_knots(knots));
+    }
+}
+
+fn render_cold_knots(knots: &[ColdKnotView]) -> String {
+    knots
+        .iter()
+        .map(|knot| {
+            format!(
+                "{} [{}] {} ({})\n",
+                knot.id, knot.state, knot.title, knot.updated_at
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,184 @@
+use crate::app::{App, AppError};
+use crate::{
+    cold_commands, config_commands, criteria_commands, dedupe_commands, digest_commands,
+    edge_commands, event_commands, grep_commands, http_serve, import_commands, link_commands,
+    lint_commands, pick_commands, plan_commands, poll_claim, publish_commands, queue_commands,
+    redact_commands, replay_commands, rpc, run_commands, stats_commands, status_commands,
+    summarize_commands, sync_commands, tag_commands, watch_commands, week_plan,
+};
+
+pub(crate) fn command_name(command: &crate::cli::Commands) -> &'static str {
+    use crate::cli::Commands;
+
+    match command {
+        Commands::New(_) => "new",
+        Commands::Publish(_) => "publish",
+        Commands::State(_) => "state",
+        Commands::Update(_) => "update",
+        Commands::Summarize(_) => "summarize",
+        Commands::Upgrade(_) => "upgrade",
+        Commands::Uninstall(_) => "uninstall",
+        Commands::Ls(_) => "ls",
+        Commands::Show(_) => "show",
+        Commands::Profile(_) => "profile",
+        Commands::Workflow(_) => "workflow",
+        Commands::Project(_) => "project",
+        Commands::Space(_) => "space",
+        Commands::Loom(_) => "loom",
+        Commands::Pull(_) => "pull",
+        Commands::Push(_) => "push",
+        Commands::Sync(_) => "sync",
+        Commands::Init(_) => "init",
+        Commands::Uninit(_) => "uninit",
+        Commands::InitRemote(_) => "init-remote",
+        Commands::Serve(_) => "serve",
+        Commands::Rpc(_) => "rpc",
+        Commands::Fsck(_) => "fsck",
+        Commands::Redact(_) => "redact",
+        Commands::Doctor(_) => "doctor",
+        Commands::Lint(_) => "lint",
+        Commands::Status(_) => "status",
+        Commands::Db(_) => "db",
+        Commands::Perf(_) => "perf",
+        Commands::Compact(_) => "compact",
+        Commands::Snapshot(_) => "snapshot",
+        Commands::Stats(_) => "stats",
+        Commands::Digest(_) => "digest",
+        Commands::Pick(_) => "pick",
+        Commands::Grep(_) => "grep",
+        Commands::Search(_) => "search",
+        Commands::Dedupe(_) => "dedupe",
+        Commands::Changelog(_) => "changelog",
+        Commands::Tag(_) => "tag",
+        Commands::Import(_) => "import",
+        Commands::Export(_) => "export",
+        Commands::Vault(_) => "vault",
+        Commands::Transfer(_) => "transfer",
+        Commands::Open(_) => "open",
+        Commands::Link(_) => "link",
+        Commands::Criteria(_) => "criteria",
+        Commands::Plan(_) => "plan",
+        Commands::Branch(_) => "branch",
+        Commands::PrBody(_) => "pr-body",
+        Commands::AutomergeHook(_) => "automerge-hook",
+        Commands::Cold(_) => "cold",
+        Commands::Rehydrate(_) => "rehydrate",
+        Commands::Replay(_) => "replay",
+        Commands::Diff(_) => "diff",
+        Commands::Config(_) => "config",
+        Commands::Edge(_) => "edge",
+        Commands::Gate(_) => "gate",
+        Commands::Next(_) => "next",
+        Commands::Rollback(_) => "rollback",
+        Commands::Snooze(_) => "snooze",
+        Commands::Handback(_) => "handback",
+        Commands::Watch(_) => "watch",
+        Commands::Inbox(_) => "inbox",
+        Commands::Events(_) => "events",
+        Commands::Skill(_) => "skill",
+        Commands::Skills(_) => "skills",
+        Commands::Q(_) => "q",
+        Commands::Completions(_) => "completions",
+        Commands::Man(_) => "man",
+        Commands::Poll(_) => "poll",
+        Commands::Suggest(_) => "suggest",
+        Commands::Claim(_) => "claim",
+        Commands::Ready(_) => "ready",
+        Commands::Queue(_) => "queue",
+        Commands::Step(_) => "step",
+        Commands::Lease(_) => "lease",
+        Commands::Hooks(_) => "hooks",
+    }
+}
+
+pub(crate) fn dispatch_read_command(
+    command: crate::cli::Commands,
+    app: &App,
+) -> Result<(), AppError> {
+    use crate::cli::{Commands, EdgeSubcommands};
+    match command {
+        Commands::Ls(args) => run_commands::run_ls(app, week_plan::with_week_tag(args)?),
+        Commands::Show(args) => run_commands::run_show(app, args),
+        Commands::Publish(args) => publish_commands::run_publish(app, args),
+        Commands::Pull(args) => sync_commands::run_pull(app, args),
+        Commands::Push(args) => sync_commands::run_push(app, args),
+        Commands::Sync(args) => sync_commands::run_sync(app, args),
+        Commands::InitRemote(args) => {
+            if args.is_set() {
+                app.set_repo_knots_ref(args.branch.as_deref(), args.remote.as_deref())?;
+            }
+            app.init_remote()?;
+            let (remote, branch) = app.knots_ref();
+            println!("initialized remote branch {remote}/{branch}");
+            Ok(())
+        }
+        Commands::Fsck(args) => run_commands::run_fsck(app, args),
+        Commands::Redact(args) => redact_commands::run_redact(app, args),
+        Commands::Doctor(args) => run_commands::run_doctor(app, args),
+        Commands::Lint(args) => lint_commands::run_lint(app, args),
+        Commands::Status(args) => status_commands::run_status(app, args),
+        Commands::Compact(args) => run_commands::run_compact(app, args),
+        Commands::Snapshot(args) => replay_commands::run_snapshot(app, args),
+        Commands::Stats(args) => stats_commands::run_stats(app, args),
+        Commands::Digest(args) => digest_commands::run_digest(app, args),
+        Commands::Pick(args) => pick_commands::run_pick(app, args),
+        Commands::Grep(args) => grep_commands::run_grep(app, args),
+        Commands::Search(args) => grep_commands::run_search(app, args),
+        Commands::Dedupe(args) => dedupe_commands::run_dedupe(app, args),
+        Commands::Summarize(args) => summarize_commands::run_summarize(app, args),
+        Commands::Changelog(args) => digest_commands::run_changelog(app, args),
+        Commands::Tag(args) => tag_commands::run_tag(app, args),
+        Commands::Import(args) => import_commands::run_import(app, args),
+        Commands::Export(args) => import_commands::run_export(app, args),
+        Commands::Vault(args) => import_commands::run_vault(app, args),
+        Commands::Transfer(args) => import_commands::run_transfer(app, args),
+        Commands::Open(args) => link_commands::run_open(app, args),
+        Commands::Link(args) => link_commands::run_link(app, args),
+        Commands::Criteria(args) => criteria_commands::run_criteria(app, args),
+        Commands::Plan(args) => plan_commands::run_plan(app, args),
+        Commands::Branch(args) => link_commands::run_branch(app, args),
+        Commands::PrBody(args) => link_commands::run_pr_body(app, args),
+        Commands::AutomergeHook(args) => link_commands::run_automerge_hook(app, args),
+        Commands::Cold(args) => cold_commands::run_cold(app, args),
+        Commands::Rehydrate(args) => run_commands::run_rehydrate(app, args),
+        Commands::Replay(args) => replay_commands::run_replay(app, args),
+        Commands::Diff(args) => replay_commands::run_diff(app, args),
+        Commands::Config(args) => config_commands::run_config(app, args),
+        Commands::Edge(args) => match args.command {
+            EdgeSubcommands::List(edge_args) => run_commands::run_edge_list(app, edge_args),
+            EdgeSubcommands::Apply(edge_args) => edge_commands::run_edge_apply(app, edge_args),
+            _ => unreachable!("queued write commands handled before app init"),
+        },
+        Commands::Skill(args) => run_commands::run_skill(app, args),
+        Commands::Poll(args) => {
+            if args.claim {
+                unreachable!("queued write commands handled before app init");
+            }
+            poll_claim::run_poll(app, args)
+        }
+        Commands::Suggest(args) => poll_claim::run_suggest(app, args),
+        Commands::Claim(args) => {
+            if !args.peek {
+                unreachable!("queued write commands handled before app init");
+            }
+            poll_claim::run_claim(app, args)
+        }
+        Commands::Ready(args) => poll_claim::run_ready(app, args),
+        Commands::Queue(args) => queue_commands::run_queue(app, args),
+        Commands::Lease(args) => run_commands::run_lease_read(app, args),
+        Commands::Watch(args) => watch_commands::run_watch(app, args),
+        Commands::Inbox(args) => watch_commands::run_inbox(app, args),
+        Commands::Events(args) => event_commands::run_events(app, args),
+        Commands::Serve(args) => http_serve::serve_http(
+            app,
+            &args.addr,
+            http_serve::HttpRoutes {
+                ui: args.ui,
+                graphql: args.graphql,
+            },
+            args.config.as_deref(),
+        ),
+        Commands::Rpc(args) => rpc::run_rpc(app, args),
+        _ => unreachable!("handled before app initialization"),
+    }
+}
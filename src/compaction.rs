@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+use time::OffsetDateTime;
+
+use crate::db;
+use crate::snapshots::{write_snapshots_at_store, SnapshotError, SnapshotWriteSummary};
+
+/// Local event file count recorded at the last automatic compaction.
+const EVENT_FILES_META: &str = "compact_event_files";
+const EVENT_DIRS: [&str; 2] = ["index", "events"];
+
+/// The `compact.*` settings, read once per push or sync.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactionPolicy {
+    /// Days between snapshots; `None` when `compact.auto` is `off`.
+    pub interval_days: Option<i64>,
+    /// New event files that make compaction due before the interval; 0 disables.
+    pub event_threshold: u64,
+    pub prune_events: bool,
+}
+
+impl CompactionPolicy {
+    pub fn read(conn: &Connection) -> rusqlite::Result<Self> {
+        let interval_days = match db::get_compact_auto(conn)?.as_str() {
+            "daily" => Some(1),
+            "weekly" => Some(7),
+            "monthly" => Some(30),
+            _ => None,
+        };
+        Ok(Self {
+            interval_days,
+            event_threshold: db::get_compact_event_threshold(conn)?,
+            prune_events: db::get_compact_prune_events(conn)?,
+        })
+    }
+
+    /// Due when the last snapshot is older than the interval, or when enough
+    /// event files arrived since the last compaction. A clone that never
+    /// wrote a snapshot is due as soon as it has events.
+    pub fn is_due(
+        &self,
+        last_snapshot: Option<OffsetDateTime>,
+        now: OffsetDateTime,
+        new_event_files: u64,
+    ) -> bool {
+        let Some(interval_days) = self.interval_days else {
+            return false;
+        };
+        if new_event_files == 0 {
+            return false;
+        }
+        let Some(last_snapshot) = last_snapshot else {
+            return true;
+        };
+        (now - last_snapshot).whole_days() >= interval_days
+            || (self.event_threshold > 0 && new_event_files >= self.event_threshold)
+    }
+}
+
+/// Snapshots written by an automatic compaction, plus the local event files
+/// they cover. Those files are only removed once a push has published them.
+#[derive(Debug)]
+pub struct AutoCompaction {
+    pub snapshots: SnapshotWriteSummary,
+    covered: Vec<PathBuf>,
+    prune_events: bool,
+}
+
+/// Writes snapshots into `store_root` when the `compact.*` policy says they
+/// are due. Callers hold the repo lock.
+pub fn compact_if_due(
+    conn: &Connection,
+    store_root: &Path,
+) -> Result<Option<AutoCompaction>, SnapshotError> {
+    let policy = CompactionPolicy::read(conn)?;
+    if policy.interval_days.is_none() {
+        return Ok(None);
+    }
+    let covered = local_event_files(store_root)?;
+    let recorded = db::get_meta(conn, EVENT_FILES_META)?
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(0);
+    let new_event_files = (covered.len() as u64).saturating_sub(recorded);
+    let last_snapshot = db::get_meta(conn, crate::snapshots::LAST_WRITTEN_META)?.and_then(|raw| {
+        OffsetDateTime::parse(&raw, &time::format_description::well_known::Rfc3339).ok()
+    });
+    if !policy.is_due(last_snapshot, OffsetDateTime::now_utc(), new_event_files) {
+        return Ok(None);
+    }
+    let snapshots = write_snapshots_at_store(conn, store_root)?;
+    db::set_meta(conn, EVENT_FILES_META, &covered.len().to_string())?;
+    Ok(Some(AutoCompaction {
+        snapshots,
+        covered,
+        prune_events: policy.prune_events,
+    }))
+}
+
+impl AutoCompaction {
+    /// Removes the covered event files when `compact.prune_events` is set.
+    /// Call only after a successful push: the remote keeps the full history
+    /// and the snapshot carries the state forward. Returns the files removed.
+    pub fn prune_published(&self, conn: &Connection) -> Result<u64, SnapshotError> {
+        if !self.prune_events {
+            return Ok(0);
+        }
+        let mut removed = 0u64;
+        for path in &self.covered {
+            match std::fs::remove_file(path) {
+                Ok(()) => removed += 1,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        let recorded = db::get_meta(conn, EVENT_FILES_META)?
+            .and_then(|raw| raw.parse::<u64>().ok())
+            .unwrap_or(0);
+        db::set_meta(
+            conn,
+            EVENT_FILES_META,
+            &recorded.saturating_sub(removed).to_string(),
+        )?;
+        Ok(removed)
+    }
+}
+
+fn local_event_files(store_root: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut stack: Vec<PathBuf> = EVENT_DIRS.iter().map(|dir| store_root.join(dir)).collect();
+    while let Some(dir) = stack.pop() {
+        if !dir.is_dir() {
+            continue;
+        }
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "json") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
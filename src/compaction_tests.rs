@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+
+use time::{Duration, OffsetDateTime};
+
+use crate::compaction::{compact_if_due, CompactionPolicy};
+use crate::db;
+
+fn setup() -> (PathBuf, rusqlite::Connection) {
+    let root = std::env::temp_dir().join(format!("knots-compaction-{}", uuid::Uuid::now_v7()));
+    std::fs::create_dir_all(root.join("cache")).expect("mkdir");
+    let conn = db::open_connection(root.join("cache/state.sqlite").to_str().expect("utf8"))
+        .expect("open db");
+    (root, conn)
+}
+
+fn write_event(root: &std::path::Path, name: &str) -> PathBuf {
+    let path = root.join("events/2026/10/16").join(name);
+    std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+    std::fs::write(&path, b"{}").expect("write event");
+    path
+}
+
+#[test]
+fn policy_is_due_by_age_or_event_count() {
+    let now = OffsetDateTime::now_utc();
+    let weekly = CompactionPolicy {
+        interval_days: Some(7),
+        event_threshold: 100,
+        prune_events: false,
+    };
+    assert!(weekly.is_due(None, now, 1));
+    assert!(!weekly.is_due(None, now, 0));
+    assert!(!weekly.is_due(Some(now - Duration::days(3)), now, 10));
+    assert!(weekly.is_due(Some(now - Duration::days(8)), now, 10));
+    assert!(weekly.is_due(Some(now - Duration::days(1)), now, 100));
+
+    let no_threshold = CompactionPolicy {
+        event_threshold: 0,
+        ..weekly.clone()
+    };
+    assert!(!no_threshold.is_due(Some(now - Duration::days(1)), now, 100_000));
+
+    let off = CompactionPolicy {
+        interval_days: None,
+        ..weekly
+    };
+    assert!(!off.is_due(None, now, 100_000));
+}
+
+#[test]
+fn policy_reads_compact_settings() {
+    let (root, conn) = setup();
+    let policy = CompactionPolicy::read(&conn).expect("read policy");
+    assert_eq!(policy.interval_days, None);
+    assert_eq!(policy.event_threshold, 5000);
+    assert!(!policy.prune_events);
+
+    db::set_meta(&conn, "compact.auto", "monthly").expect("set");
+    db::set_meta(&conn, "compact.event_threshold", "20").expect("set");
+    db::set_meta(&conn, "compact.prune_events", "true").expect("set");
+    let policy = CompactionPolicy::read(&conn).expect("read policy");
+    assert_eq!(policy.interval_days, Some(30));
+    assert_eq!(policy.event_threshold, 20);
+    assert!(policy.prune_events);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn compaction_runs_once_per_interval_and_prunes_covered_events() {
+    let (root, conn) = setup();
+    let first = write_event(&root, "a.json");
+    assert!(compact_if_due(&conn, &root).expect("compact").is_none());
+
+    db::set_meta(&conn, "compact.auto", "weekly").expect("set");
+    db::set_meta(&conn, "compact.prune_events", "true").expect("set");
+    let compaction = compact_if_due(&conn, &root)
+        .expect("compact")
+        .expect("first compaction is due");
+    assert!(compaction.snapshots.active_path.exists());
+
+    let second = write_event(&root, "b.json");
+    assert!(
+        compact_if_due(&conn, &root).expect("compact").is_none(),
+        "a fresh snapshot is not due again"
+    );
+
+    assert_eq!(compaction.prune_published(&conn).expect("prune"), 1);
+    assert!(!first.exists());
+    assert!(second.exists(), "events newer than the snapshot stay");
+
+    db::set_meta(&conn, "compact.event_threshold", "1").expect("set");
+    assert!(compact_if_due(&conn, &root).expect("compact").is_some());
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,31 @@
+use std::borrow::Cow;
+use std::io;
+use std::path::Path;
+
+/// Frame magic that starts every zstd stream.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const ZSTD_LEVEL: i32 = 19;
+
+pub fn is_zstd(bytes: &[u8]) -> bool {
+    bytes.starts_with(&ZSTD_MAGIC)
+}
+
+/// Returns `bytes` as plain JSON, decompressing zstd frames. Plain payloads
+/// pass through untouched, so readers accept both formats.
+pub fn decode(bytes: &[u8]) -> io::Result<Cow<'_, [u8]>> {
+    if is_zstd(bytes) {
+        zstd::decode_all(bytes).map(Cow::Owned)
+    } else {
+        Ok(Cow::Borrowed(bytes))
+    }
+}
+
+pub fn encode(bytes: &[u8]) -> io::Result<Vec<u8>> {
+    zstd::encode_all(bytes, ZSTD_LEVEL)
+}
+
+/// Reads a knots data file, decompressing it when needed.
+pub fn read(path: &Path) -> io::Result<Vec<u8>> {
+    let bytes = std::fs::read(path)?;
+    Ok(decode(&bytes)?.into_owned())
+}
@@ -0,0 +1,24 @@
+use crate::compression::{decode, encode, is_zstd, read};
+
+#[test]
+fn encoded_payloads_round_trip_and_plain_json_passes_through() {
+    let json = br#"{"event_id":"e1","data":{"title":"compress me"}}"#.repeat(20);
+    let packed = encode(&json).expect("encode");
+    assert!(is_zstd(&packed));
+    assert!(packed.len() < json.len());
+    assert_eq!(decode(&packed).expect("decode").as_ref(), json.as_slice());
+    assert!(!is_zstd(&json));
+    assert_eq!(decode(&json).expect("plain").as_ref(), json.as_slice());
+
+    let path = std::env::temp_dir().join(format!("knots-zstd-{}.json", uuid::Uuid::now_v7()));
+    std::fs::write(&path, &packed).expect("write");
+    assert_eq!(read(&path).expect("read"), json);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn corrupt_frames_are_reported() {
+    let mut packed = encode(b"{\"a\":1}").expect("encode");
+    packed.truncate(6);
+    assert!(decode(&packed).is_err());
+}
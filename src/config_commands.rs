@@ -0,0 +1,50 @@
+use crate::cli::{ConfigArgs, ConfigSubcommands};
+use crate::{app, print_json};
+
+pub fn run_config(app: &app::App, args: ConfigArgs) -> Result<(), app::AppError> {
+    match args.command {
+        ConfigSubcommands::Get(get) => {
+            let entry = app.config_get(&get.key)?;
+            if get.json {
+                print_json(&entry);
+            } else {
+                println!("{}", entry.value);
+            }
+        }
+        ConfigSubcommands::Set(set) => {
+            let entry = app.config_set(&set.key, &set.value, scope(set.repo))?;
+            println!("{} = {} ({})", entry.key, entry.value, entry.source);
+        }
+        ConfigSubcommands::Unset(unset) => {
+            let entry = app.config_unset(&unset.key, scope(unset.repo))?;
+            println!("{} = {} ({})", entry.key, entry.value, entry.source);
+        }
+        ConfigSubcommands::List(list) => {
+            let entries = app.config_list()?;
+            if list.json {
+                print_json(&entries);
+            } else {
+                for line in format_config_list(&entries) {
+                    println!("{line}");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn scope(repo: bool) -> app::ConfigScope {
+    if repo {
+        app::ConfigScope::Repo
+    } else {
+        app::ConfigScope::Local
+    }
+}
+
+pub(crate) fn format_config_list(entries: &[app::ConfigEntry]) -> Vec<String> {
+    let width = entries.iter().map(|e| e.key.len()).max().unwrap_or(0);
+    entries
+        .iter()
+        .map(|e| format!("{:<width$}  {}  ({})", e.key, e.value, e.source))
+        .collect()
+}
@@ -0,0 +1,40 @@
+use crate::app::{self, App};
+use crate::cli::{CriteriaArgs, CriteriaSubcommands};
+use crate::db::CriterionRecord;
+use crate::print_json;
+
+pub fn run_criteria(app: &App, args: CriteriaArgs) -> Result<(), app::AppError> {
+    let (criteria, json) = match args.command {
+        CriteriaSubcommands::Add(add) => (app.add_criterion(&add.id, &add.text)?, add.json),
+        CriteriaSubcommands::Check(check) => (
+            app.check_criterion(&check.id, check.number, true)?,
+            check.json,
+        ),
+        CriteriaSubcommands::Uncheck(check) => (
+            app.check_criterion(&check.id, check.number, false)?,
+            check.json,
+        ),
+        CriteriaSubcommands::List(list) => (app.criteria(&list.id)?, list.json),
+    };
+    if json {
+        print_json(&criteria);
+    } else {
+        print!("{}", render_criteria(&criteria));
+    }
+    Ok(())
+}
+
+pub(crate) fn render_criteria(criteria: &[CriterionRecord]) -> String {
+    if criteria.is_empty() {
+        return "no acceptance criteria\n".to_string();
+    }
+    let done = criteria
+        .iter()
+        .filter(|criterion| criterion.checked)
+        .count();
+    format!(
+        "{}\n{done}/{} checked\n",
+        crate::ui::format_criteria(criteria),
+        criteria.len()
+    )
+}
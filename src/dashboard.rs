@@ -0,0 +1,44 @@
+use crate::app::{App, AppError, KnotView};
+use crate::http_serve::Response;
+
+/// The whole web UI: one page that reads the JSON endpoints below.
+const INDEX_HTML: &str = include_str!("dashboard/index.html");
+
+/// Answers one dashboard request for `path` (query string already removed).
+/// Every route is a read; other methods get 405.
+pub(crate) fn route(app: &App, method: &str, path: &str) -> Response {
+    if method != "GET" && method != "HEAD" {
+        return Response::error(405, "the dashboard is read-only");
+    }
+    let result = match path {
+        "/" | "/index.html" => {
+            return Response {
+                status: 200,
+                content_type: "text/html",
+                body: INDEX_HTML.as_bytes().to_vec(),
+            }
+        }
+        "/api/board" => app.dashboard_board().map(Some),
+        "/api/graph" => app.dashboard_graph().map(Some),
+        _ => match path.strip_prefix("/api/knots/") {
+            Some(id) if !id.is_empty() && !id.contains('/') => app.dashboard_knot(id),
+            _ => return Response::error(404, "not found"),
+        },
+    };
+    match result {
+        Ok(Some(value)) => Response::json(200, &value),
+        Ok(None) | Err(AppError::NotFound(_)) => Response::error(404, "knot not found"),
+        Err(err) => Response::error(500, &err.to_string()),
+    }
+}
+
+/// The dashboard page with every response for `knots` inlined, so it needs
+/// no server: it can be opened from disk or published as a static site.
+pub(crate) fn snapshot_html(app: &App, knots: &[KnotView]) -> Result<String, AppError> {
+    let snapshot = app.dashboard_snapshot(knots)?;
+    // `<` only occurs inside JSON strings, where the escape keeps a
+    // `</script>` in a knot's text from closing the tag.
+    let data = snapshot.to_string().replace('<', "\\u003c");
+    let script = format!("<script>window.KNOTS_SNAPSHOT = {data};</script>\n<script>");
+    Ok(INDEX_HTML.replacen("<script>", &script, 1))
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::thread;
 use std::time::Duration;
 
@@ -13,36 +14,96 @@ use crate::domain::invariant::Invariant;
 use crate::domain::lease::LeaseData;
 use crate::domain::metadata::MetadataEntry;
 use crate::domain::step_history::StepRecord;
+use crate::trace::TimingCategory;
 
-pub const CURRENT_SCHEMA_VERSION: i64 = 16;
+pub const CURRENT_SCHEMA_VERSION: i64 = 34;
+/// `--db` value for a throwaway cache rebuilt from the knots worktree.
+pub const MEMORY_DB_PATH: &str = ":memory:";
 
 mod catalog;
+mod criteria;
+#[cfg(feature = "semantic")]
+mod embeddings;
+mod git_links;
+mod grep_index;
+mod handbacks;
+mod idempotency;
+mod maintenance;
 mod migrations;
+mod pagination;
+mod plans;
+mod private_knots;
+mod schema_info;
+mod server_access;
+mod sync_skipped;
+mod usage;
+mod watch;
 
 pub use catalog::{
-    count_active_leases, delete_cold_catalog, delete_edge, delete_knot_warm, get_cold_catalog,
-    get_hot_window_days, get_knot_warm, get_pull_drift_warn_threshold,
-    get_sync_fetch_blob_limit_kb, insert_edge, list_cold_catalog, list_edges, list_edges_by_kind,
-    list_knot_warm, search_cold_catalog, update_lease_expiry_ts, upsert_cold_catalog,
-    upsert_knot_warm, EdgeDirection, EdgeRecord,
+    count_active_leases, count_knot_hot_in_state, count_tier_rows, delete_cold_catalog,
+    delete_edge, delete_knot_warm, delete_meta, get_cold_catalog, get_compact_auto,
+    get_compact_event_threshold, get_compact_prune_events, get_compression, get_hot_window_days,
+    get_knot_warm, get_local_metrics_enabled, get_pull_drift_warn_threshold, get_remote_store,
+    get_sync_fetch_blob_limit_kb, get_sync_mode, insert_edge, list_cold_catalog,
+    list_dangling_edges, list_edges, list_edges_by_kind, list_edges_touching, list_knot_warm,
+    list_known_knot_ids, list_local_edges, search_cold_catalog, update_lease_expiry_ts,
+    upsert_cold_catalog, upsert_knot_warm, EdgeDirection, EdgeRecord,
+};
+pub use criteria::{insert_criterion, list_criteria, set_criterion_checked, CriterionRecord};
+#[cfg(feature = "semantic")]
+pub use embeddings::{delete_knot_embedding, list_knot_embeddings, upsert_knot_embedding};
+pub use git_links::{insert_git_link, knots_with_git_link, list_git_links, GitLinkRecord};
+pub use grep_index::{forget_grep_index_entry, grep_index_candidates, refresh_grep_index};
+pub use handbacks::{insert_handback, list_handback_cooldowns, list_handbacks, HandbackRecord};
+pub use idempotency::{get_idempotency_record, insert_idempotency_record, IdempotencyRecord};
+pub use maintenance::{checkpoint_truncate, integrity_problems, vacuum};
+pub use pagination::{list_knot_hot_paginated, ListHotParams};
+pub use plans::{insert_plan, list_plans, PlanRecord};
+pub use private_knots::{
+    delete_private_knot, insert_private_knot, list_private_knots, private_knot_draft,
+    restamp_knot_hot,
+};
+pub use schema_info::schema_info;
+pub use server_access::{
+    count_recent_server_access, insert_server_access, list_server_access, ServerAccessRecord,
+};
+pub use sync_skipped::{
+    delete_sync_skipped, get_sync_skipped, list_sync_skipped, record_sync_skipped,
+    SyncSkippedRecord,
+};
+pub use usage::{insert_usage_metric, list_usage_metrics, UsageMetricRecord};
+pub use watch::{
+    ack_inbox_items, delete_watched_knot, insert_inbox_item, insert_watched_knot, list_inbox_items,
+    list_watched_knots, InboxItemRecord,
 };
 
 const SQLITE_LOCK_RETRY_LIMIT: usize = 2;
 const SQLITE_LOCK_RETRY_BASE_DELAY_MS: u64 = 10;
 const SQLITE_LOCK_RETRY_MAX_DELAY_MS: u64 = 250;
+/// Prepared statements each connection keeps for `prepare_cached`; enough
+/// for the hot read paths of a long-lived `App`.
+const STATEMENT_CACHE_CAPACITY: usize = 64;
 
 #[cfg(test)]
 pub fn needs_schema_bootstrap(conn: &rusqlite::Connection) -> Result<bool> {
-    migrations::needs_schema_bootstrap(conn)
+    schema_info::needs_schema_bootstrap(conn)
 }
 
 pub fn open_connection(path: &str) -> Result<Connection> {
-    let mut conn = Connection::open(path)?;
-    configure_for_speed(&conn)?;
-    if migrations::needs_schema_bootstrap(&conn)? {
-        with_write_retry(|| migrations::apply_migrations(&mut conn))?;
-    }
-    Ok(conn)
+    crate::trace::timed(TimingCategory::Db, || {
+        let mut conn = Connection::open(path)?;
+        configure_for_speed(&conn)?;
+        conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+        schema_info::ensure_schema_supported(&conn)?;
+        if schema_info::needs_schema_bootstrap(&conn)? {
+            with_write_retry(|| migrations::apply_migrations(&mut conn))?;
+        }
+        Ok(conn)
+    })
+}
+
+pub fn is_memory_path(path: &str) -> bool {
+    path == MEMORY_DB_PATH
 }
 
 /// Open a connection with pragmas but without applying migrations.
@@ -67,17 +128,19 @@ fn with_write_retry<T, F>(mut operation: F) -> Result<T>
 where
     F: FnMut() -> Result<T>,
 {
-    let mut retry = 0usize;
-    loop {
-        match operation() {
-            Ok(value) => return Ok(value),
-            Err(err) if is_retryable_lock_error(&err) && retry < SQLITE_LOCK_RETRY_LIMIT => {
-                thread::sleep(lock_retry_delay(retry));
-                retry += 1;
+    crate::trace::timed(TimingCategory::Db, || {
+        let mut retry = 0usize;
+        loop {
+            match operation() {
+                Ok(value) => return Ok(value),
+                Err(err) if is_retryable_lock_error(&err) && retry < SQLITE_LOCK_RETRY_LIMIT => {
+                    thread::sleep(lock_retry_delay(retry));
+                    retry += 1;
+                }
+                Err(err) => return Err(err),
             }
-            Err(err) => return Err(err),
         }
-    }
+    })
 }
 
 fn is_retryable_lock_error(err: &rusqlite::Error) -> bool {
@@ -99,7 +162,7 @@ fn lock_retry_delay(retry: usize) -> Duration {
     Duration::from_millis(base + jitter)
 }
 
-fn now_utc_rfc3339() -> String {
+pub(crate) fn now_utc_rfc3339() -> String {
     OffsetDateTime::now_utc()
         .format(&Rfc3339)
         .expect("RFC3339 formatting for UTC timestamp should never fail")
@@ -124,6 +187,10 @@ pub struct KnotCacheRecord {
     pub body: Option<String>,
     pub description: Option<String>,
     pub acceptance: Option<String>,
+    #[serde(default)]
+    pub estimate: Option<String>,
+    #[serde(default)]
+    pub custom_fields: BTreeMap<String, String>,
     pub priority: Option<i64>,
     pub knot_type: Option<String>,
     pub tags: Vec<String>,
@@ -147,6 +214,8 @@ pub struct KnotCacheRecord {
     pub deferred_from_state: Option<String>,
     pub blocked_from_state: Option<String>,
     pub created_at: Option<String>,
+    #[serde(default)]
+    pub snoozed_until: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -171,6 +240,8 @@ pub struct UpsertKnotHot<'a> {
     pub body: Option<&'a str>,
     pub description: Option<&'a str>,
     pub acceptance: Option<&'a str>,
+    pub estimate: Option<&'a str>,
+    pub custom_fields: &'a BTreeMap<String, String>,
     pub priority: Option<i64>,
     pub knot_type: Option<&'a str>,
     pub tags: &'a [String],
@@ -187,6 +258,7 @@ pub struct UpsertKnotHot<'a> {
     pub deferred_from_state: Option<&'a str>,
     pub blocked_from_state: Option<&'a str>,
     pub created_at: Option<&'a str>,
+    pub snoozed_until: Option<&'a str>,
 }
 
 pub fn upsert_knot_hot(conn: &Connection, args: &UpsertKnotHot<'_>) -> Result<()> {
@@ -197,6 +269,7 @@ pub fn upsert_knot_hot(conn: &Connection, args: &UpsertKnotHot<'_>) -> Result<()
     let step_history_json = to_json_text(args.step_history)?;
     let gate_data_json = to_json_text(args.gate_data)?;
     let lease_data_json = to_json_text(args.lease_data)?;
+    let custom_json = to_json_text(args.custom_fields)?;
     with_write_retry(|| {
         conn.execute(
             r#"
@@ -206,7 +279,8 @@ INSERT INTO knot_hot (
     handoff_capsules_json, invariants_json, step_history_json,
     gate_data_json, lease_data_json, lease_id,
     workflow_id, profile_id, profile_etag,
-    deferred_from_state, blocked_from_state, created_at
+    deferred_from_state, blocked_from_state, created_at, estimate, custom_json,
+    snoozed_until
 )
 VALUES (
     ?1, ?2, ?3, ?4, ?5, ?6, ?7,
@@ -214,7 +288,8 @@ VALUES (
     ?12, ?13, ?14, ?15,
     ?16, ?17,
     ?18, ?19, ?20,
-    ?21, ?22, ?23
+    ?21, ?22, ?23, ?24, ?25,
+    ?26
 )
 ON CONFLICT(id) DO UPDATE SET
     title = excluded.title,
@@ -238,7 +313,10 @@ ON CONFLICT(id) DO UPDATE SET
     profile_etag = excluded.profile_etag,
     deferred_from_state = excluded.deferred_from_state,
     blocked_from_state = excluded.blocked_from_state,
-    created_at = COALESCE(knot_hot.created_at, excluded.created_at)
+    created_at = COALESCE(knot_hot.created_at, excluded.created_at),
+    estimate = excluded.estimate,
+    custom_json = excluded.custom_json,
+    snoozed_until = excluded.snoozed_until
 "#,
             params![
                 args.id,
@@ -263,7 +341,10 @@ ON CONFLICT(id) DO UPDATE SET
                 args.profile_etag,
                 args.deferred_from_state,
                 args.blocked_from_state,
-                args.created_at
+                args.created_at,
+                args.estimate,
+                custom_json,
+                args.snoozed_until
             ],
         )?;
         Ok(())
@@ -277,32 +358,41 @@ ON CONFLICT(id) DO UPDATE SET
 }
 
 pub fn get_knot_hot(conn: &Connection, id: &str) -> Result<Option<KnotCacheRecord>> {
-    conn.query_row(
+    crate::trace::timed(TimingCategory::Db, || get_knot_hot_row(conn, id))
+}
+
+fn get_knot_hot_row(conn: &Connection, id: &str) -> Result<Option<KnotCacheRecord>> {
+    conn.prepare_cached(
         r#"
 SELECT id, title, state, updated_at, body, description, acceptance,
        priority, knot_type, tags_json, notes_json,
        handoff_capsules_json, invariants_json, step_history_json,
        gate_data_json, lease_data_json, lease_id, lease_expiry_ts,
        workflow_id, profile_id, profile_etag,
-       deferred_from_state, blocked_from_state, created_at
+       deferred_from_state, blocked_from_state, created_at, estimate, custom_json,
+       snoozed_until
 FROM knot_hot
 WHERE id = ?1
 "#,
-        params![id],
-        row_to_knot_cache_record,
-    )
+    )?
+    .query_row(params![id], row_to_knot_cache_record)
     .optional()
 }
 
 pub fn list_knot_hot(conn: &Connection) -> Result<Vec<KnotCacheRecord>> {
-    let mut stmt = conn.prepare(
+    crate::trace::timed(TimingCategory::Db, || list_knot_hot_rows(conn))
+}
+
+fn list_knot_hot_rows(conn: &Connection) -> Result<Vec<KnotCacheRecord>> {
+    let mut stmt = conn.prepare_cached(
         r#"
 SELECT id, title, state, updated_at, body, description, acceptance,
        priority, knot_type, tags_json, notes_json,
        handoff_capsules_json, invariants_json, step_history_json,
        gate_data_json, lease_data_json, lease_id, lease_expiry_ts,
        workflow_id, profile_id, profile_etag,
-       deferred_from_state, blocked_from_state, created_at
+       deferred_from_state, blocked_from_state, created_at, estimate, custom_json,
+       snoozed_until
 FROM knot_hot
 ORDER BY updated_at DESC, id ASC
 "#,
@@ -317,87 +407,6 @@ ORDER BY updated_at DESC, id ASC
     Ok(result)
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct ListHotParams {
-    pub state: Option<String>,
-    pub knot_type: Option<String>,
-    pub limit: Option<usize>,
-    pub offset: Option<usize>,
-}
-
-pub fn list_knot_hot_paginated(
-    conn: &Connection,
-    params: &ListHotParams,
-) -> Result<(Vec<KnotCacheRecord>, i64)> {
-    let (where_clause, bind_values) = build_hot_where(params);
-    let total = count_knot_hot_filtered(conn, &where_clause, &bind_values)?;
-
-    let mut sql = format!(
-        "SELECT id, title, state, updated_at, body, description, \
-         acceptance, priority, knot_type, tags_json, notes_json, \
-         handoff_capsules_json, invariants_json, step_history_json, \
-         gate_data_json, lease_data_json, lease_id, lease_expiry_ts, \
-         workflow_id, profile_id, profile_etag, \
-         deferred_from_state, blocked_from_state, created_at \
-         FROM knot_hot{} ORDER BY updated_at DESC, id ASC",
-        where_clause
-    );
-    let mut all_binds: Vec<Box<dyn rusqlite::types::ToSql>> =
-        bind_values.into_iter().map(|s| box_str(s)).collect();
-    if let Some(limit) = params.limit {
-        sql.push_str(" LIMIT ?");
-        all_binds.push(Box::new(limit as i64));
-    }
-    if let Some(offset) = params.offset {
-        sql.push_str(" OFFSET ?");
-        all_binds.push(Box::new(offset as i64));
-    }
-
-    let refs: Vec<&dyn rusqlite::types::ToSql> = all_binds.iter().map(|b| b.as_ref()).collect();
-    let mut stmt = conn.prepare(&sql)?;
-    let mut rows = stmt.query(refs.as_slice())?;
-    let mut result = Vec::new();
-    while let Some(row) = rows.next()? {
-        result.push(row_to_knot_cache_record(row)?);
-    }
-    Ok((result, total))
-}
-
-fn count_knot_hot_filtered(
-    conn: &Connection,
-    where_clause: &str,
-    bind_values: &[String],
-) -> Result<i64> {
-    let sql = format!("SELECT COUNT(*) FROM knot_hot{}", where_clause);
-    let refs: Vec<&dyn rusqlite::types::ToSql> = bind_values
-        .iter()
-        .map(|s| s as &dyn rusqlite::types::ToSql)
-        .collect();
-    conn.query_row(&sql, refs.as_slice(), |row| row.get(0))
-}
-
-fn build_hot_where(params: &ListHotParams) -> (String, Vec<String>) {
-    let mut conditions: Vec<String> = Vec::new();
-    let mut values: Vec<String> = Vec::new();
-    if let Some(ref state) = params.state {
-        values.push(state.to_ascii_lowercase());
-        conditions.push(format!("LOWER(state) = ?{}", values.len()));
-    }
-    if let Some(ref knot_type) = params.knot_type {
-        values.push(knot_type.to_ascii_lowercase());
-        conditions.push(format!("LOWER(knot_type) = ?{}", values.len()));
-    }
-    if conditions.is_empty() {
-        (String::new(), values)
-    } else {
-        (format!(" WHERE {}", conditions.join(" AND ")), values)
-    }
-}
-
-fn box_str(s: String) -> Box<dyn rusqlite::types::ToSql> {
-    Box::new(s)
-}
-
 fn row_to_knot_cache_record(row: &rusqlite::Row<'_>) -> Result<KnotCacheRecord> {
     let tags_json: String = row.get(9)?;
     let notes_json: String = row.get(10)?;
@@ -406,6 +415,7 @@ fn row_to_knot_cache_record(row: &rusqlite::Row<'_>) -> Result<KnotCacheRecord>
     let step_history_json: String = row.get(13)?;
     let gate_data_json: String = row.get(14)?;
     let lease_data_json: String = row.get(15)?;
+    let custom_json: String = row.get(25)?;
     Ok(KnotCacheRecord {
         id: row.get(0)?,
         title: row.get(1)?,
@@ -431,6 +441,9 @@ fn row_to_knot_cache_record(row: &rusqlite::Row<'_>) -> Result<KnotCacheRecord>
         deferred_from_state: row.get(21)?,
         blocked_from_state: row.get(22)?,
         created_at: row.get(23)?,
+        estimate: row.get(24)?,
+        custom_fields: from_json_text(custom_json, 25)?,
+        snoozed_until: row.get(26)?,
     })
 }
 
@@ -1,6 +1,7 @@
-use rusqlite::{params, Connection, OptionalExtension, Result};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Result};
 
 use super::{with_write_retry, ColdCatalogRecord, WarmKnotRecord};
+use crate::domain::edge_ref::RemoteKnotRef;
 
 pub fn delete_knot_warm(conn: &Connection, id: &str) -> Result<()> {
     with_write_retry(|| {
@@ -177,6 +178,59 @@ pub fn get_sync_fetch_blob_limit_kb(conn: &Connection) -> Result<Option<u64>> {
     }
 }
 
+pub fn get_sync_mode(conn: &Connection) -> Result<String> {
+    let value = super::get_meta(conn, "sync_mode")?;
+    Ok(value
+        .map(|raw| raw.trim().to_ascii_lowercase())
+        .filter(|raw| !raw.is_empty())
+        .unwrap_or_else(|| "worktree".to_string()))
+}
+
+pub fn get_compression(conn: &Connection) -> Result<String> {
+    let value = super::get_meta(conn, "compression")?;
+    Ok(value
+        .map(|raw| raw.trim().to_ascii_lowercase())
+        .filter(|raw| !raw.is_empty())
+        .unwrap_or_else(|| "none".to_string()))
+}
+
+pub fn get_compact_auto(conn: &Connection) -> Result<String> {
+    let value = super::get_meta(conn, "compact.auto")?;
+    Ok(value
+        .map(|raw| raw.trim().to_ascii_lowercase())
+        .filter(|raw| !raw.is_empty())
+        .unwrap_or_else(|| "off".to_string()))
+}
+
+pub fn get_compact_event_threshold(conn: &Connection) -> Result<u64> {
+    let value = super::get_meta(conn, "compact.event_threshold")?;
+    let parsed = value
+        .as_deref()
+        .unwrap_or("5000")
+        .trim()
+        .parse::<u64>()
+        .unwrap_or(5000);
+    Ok(parsed)
+}
+
+pub fn get_compact_prune_events(conn: &Connection) -> Result<bool> {
+    let value = super::get_meta(conn, "compact.prune_events")?;
+    Ok(value.as_deref().map(str::trim) == Some("true"))
+}
+
+pub fn get_local_metrics_enabled(conn: &Connection) -> Result<bool> {
+    let value = super::get_meta(conn, "metrics.local")?;
+    Ok(value.as_deref().map(str::trim) == Some("true"))
+}
+
+pub fn get_remote_store(conn: &Connection) -> Result<String> {
+    let value = super::get_meta(conn, "remote_store")?;
+    Ok(value
+        .map(|raw| raw.trim().to_string())
+        .filter(|raw| !raw.is_empty())
+        .unwrap_or_else(|| "git".to_string()))
+}
+
 pub fn get_pull_drift_warn_threshold(conn: &Connection) -> Result<u64> {
     let value = super::get_meta(conn, "pull_drift_warn_threshold")?;
     let parsed = value
@@ -188,6 +242,14 @@ pub fn get_pull_drift_warn_threshold(conn: &Connection) -> Result<u64> {
     Ok(parsed)
 }
 
+pub fn delete_meta(conn: &Connection, key: &str) -> Result<()> {
+    with_write_retry(|| {
+        conn.execute("DELETE FROM meta WHERE key = ?1", params![key])?;
+        Ok(())
+    })?;
+    Ok(())
+}
+
 pub fn count_active_leases(conn: &Connection) -> Result<i64> {
     conn.query_row(
         r#"
@@ -201,6 +263,27 @@ WHERE knot_type = 'lease'
     )
 }
 
+/// Rows in the hot, warm, and cold tiers, in that order.
+pub fn count_tier_rows(conn: &Connection) -> Result<[i64; 3]> {
+    conn.query_row(
+        r#"
+SELECT (SELECT COUNT(*) FROM knot_hot),
+       (SELECT COUNT(*) FROM knot_warm),
+       (SELECT COUNT(*) FROM cold_catalog)
+"#,
+        [],
+        |row| Ok([row.get(0)?, row.get(1)?, row.get(2)?]),
+    )
+}
+
+pub fn count_knot_hot_in_state(conn: &Connection, state: &str) -> Result<i64> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM knot_hot WHERE state = ?1",
+        params![state],
+        |row| row.get(0),
+    )
+}
+
 pub fn update_lease_expiry_ts(conn: &Connection, id: &str, ts: i64) -> Result<()> {
     super::with_write_retry(|| {
         conn.execute(
@@ -225,11 +308,14 @@ pub enum EdgeDirection {
     Both,
 }
 
+/// Cross-repo destinations (`repo-name:knot-id`) keep their repo in
+/// `dst_repo` so local graph queries can leave them out.
 pub fn insert_edge(conn: &Connection, src: &str, kind: &str, dst: &str) -> Result<()> {
+    let dst_repo = RemoteKnotRef::parse(dst).map(|remote| remote.repo);
     with_write_retry(|| {
         conn.execute(
-            "INSERT OR IGNORE INTO edge (src, kind, dst) VALUES (?1, ?2, ?3)",
-            params![src, kind, dst],
+            "INSERT OR IGNORE INTO edge (src, kind, dst, dst_repo) VALUES (?1, ?2, ?3, ?4)",
+            params![src, kind, dst, dst_repo],
         )?;
         Ok(())
     })?;
@@ -276,9 +362,36 @@ pub fn list_edges(
     Ok(result)
 }
 
+/// Every edge that starts or ends at one of `knot_ids`, in one query.
+pub fn list_edges_touching(conn: &Connection, knot_ids: &[String]) -> Result<Vec<EdgeRecord>> {
+    if knot_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let placeholders = vec!["?"; knot_ids.len()].join(", ");
+    let sql = format!(
+        "SELECT src, kind, dst FROM edge WHERE src IN ({placeholders}) OR dst IN ({placeholders}) \
+         ORDER BY src, kind, dst"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let mut rows = stmt.query(params_from_iter(knot_ids.iter().chain(knot_ids)))?;
+    let mut result = Vec::new();
+    while let Some(row) = rows.next()? {
+        result.push(EdgeRecord {
+            src: row.get(0)?,
+            kind: row.get(1)?,
+            dst: row.get(2)?,
+        });
+    }
+    Ok(result)
+}
+
+/// Local edges of one kind; cross-repo edges are not part of this repo's
+/// graph and are skipped.
 pub fn list_edges_by_kind(conn: &Connection, kind: &str) -> Result<Vec<EdgeRecord>> {
-    let mut stmt =
-        conn.prepare("SELECT src, kind, dst FROM edge WHERE kind = ?1 ORDER BY src ASC, dst ASC")?;
+    let mut stmt = conn.prepare(
+        "SELECT src, kind, dst FROM edge WHERE kind = ?1 AND dst_repo IS NULL \
+         ORDER BY src ASC, dst ASC",
+    )?;
     let mut rows = stmt.query(params![kind])?;
     let mut result = Vec::new();
     while let Some(row) = rows.next()? {
@@ -290,3 +403,47 @@ pub fn list_edges_by_kind(conn: &Connection, kind: &str) -> Result<Vec<EdgeRecor
     }
     Ok(result)
 }
+
+/// Every local edge, whatever its kind.
+pub fn list_local_edges(conn: &Connection) -> Result<Vec<EdgeRecord>> {
+    let mut stmt = conn.prepare(
+        "SELECT src, kind, dst FROM edge WHERE dst_repo IS NULL ORDER BY src, kind, dst",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(EdgeRecord {
+            src: row.get(0)?,
+            kind: row.get(1)?,
+            dst: row.get(2)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Ids of every knot in the hot, warm, or cold tier.
+pub fn list_known_knot_ids(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM knot_hot UNION SELECT id FROM knot_warm \
+         UNION SELECT id FROM cold_catalog ORDER BY id",
+    )?;
+    let rows = stmt.query_map([], |row| row.get(0))?;
+    rows.collect()
+}
+
+/// Local edges whose source or target is not a knot in any tier.
+pub fn list_dangling_edges(conn: &Connection) -> Result<Vec<EdgeRecord>> {
+    let known = "SELECT id FROM knot_hot UNION SELECT id FROM knot_warm \
+                 UNION SELECT id FROM cold_catalog";
+    let mut stmt = conn.prepare(&format!(
+        "SELECT src, kind, dst FROM edge \
+         WHERE src NOT IN ({known}) OR (dst_repo IS NULL AND dst NOT IN ({known})) \
+         ORDER BY src, kind, dst"
+    ))?;
+    let rows = stmt.query_map([], |row| {
+        Ok(EdgeRecord {
+            src: row.get(0)?,
+            kind: row.get(1)?,
+            dst: row.get(2)?,
+        })
+    })?;
+    rows.collect()
+}
@@ -0,0 +1,84 @@
+use rusqlite::{params, Connection, Result};
+use serde::Serialize;
+
+use super::with_write_retry;
+
+/// One acceptance criterion recorded against a knot by `kno criteria`.
+/// Criteria are numbered from 1 in the order they were added.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct CriterionRecord {
+    #[serde(rename = "id")]
+    pub criterion_id: String,
+    pub text: String,
+    pub checked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked_at: Option<String>,
+    pub added_at: String,
+}
+
+/// Records a criterion; a repeated event keeps the first record.
+pub fn insert_criterion(
+    conn: &Connection,
+    knot_id: &str,
+    criterion: &CriterionRecord,
+) -> Result<bool> {
+    with_write_retry(|| {
+        let inserted = conn.execute(
+            r#"
+INSERT OR IGNORE INTO knot_criterion (knot_id, criterion_id, text, added_at, checked, checked_at)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+"#,
+            params![
+                knot_id,
+                criterion.criterion_id,
+                criterion.text,
+                criterion.added_at,
+                criterion.checked,
+                criterion.checked_at
+            ],
+        )?;
+        Ok(inserted > 0)
+    })
+}
+
+/// Checks or unchecks a criterion; `checked_at` is cleared on uncheck.
+pub fn set_criterion_checked(
+    conn: &Connection,
+    knot_id: &str,
+    criterion_id: &str,
+    checked: bool,
+    at: &str,
+) -> Result<bool> {
+    with_write_retry(|| {
+        let updated = conn.execute(
+            r#"
+UPDATE knot_criterion
+SET checked = ?3, checked_at = CASE WHEN ?3 THEN ?4 ELSE NULL END
+WHERE knot_id = ?1 AND criterion_id = ?2
+"#,
+            params![knot_id, criterion_id, checked, at],
+        )?;
+        Ok(updated > 0)
+    })
+}
+
+pub fn list_criteria(conn: &Connection, knot_id: &str) -> Result<Vec<CriterionRecord>> {
+    let mut stmt = conn.prepare(
+        r#"
+SELECT criterion_id, text, checked, checked_at, added_at
+FROM knot_criterion
+WHERE knot_id = ?1
+ORDER BY added_at ASC, criterion_id ASC
+"#,
+    )?;
+    let rows = stmt.query_map(params![knot_id], |row| {
+        Ok(CriterionRecord {
+            criterion_id: row.get(0)?,
+            text: row.get(1)?,
+            checked: row.get(2)?,
+            checked_at: row.get(3)?,
+            added_at: row.get(4)?,
+        })
+    })?;
+    rows.collect()
+}
@@ -0,0 +1,57 @@
+use rusqlite::{params, Connection, Result};
+
+use super::{now_utc_rfc3339, with_write_retry};
+
+/// A stored embedding; `content_hash` covers the model and the embedded
+/// text, so either changing marks the vector stale.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnotEmbeddingRecord {
+    pub knot_id: String,
+    pub content_hash: String,
+    pub vector: Vec<u8>,
+}
+
+pub fn list_knot_embeddings(conn: &Connection) -> Result<Vec<KnotEmbeddingRecord>> {
+    let mut stmt = conn
+        .prepare("SELECT knot_id, content_hash, vector FROM knot_embedding ORDER BY knot_id ASC")?;
+    let rows = stmt.query_map([], |row| {
+        Ok(KnotEmbeddingRecord {
+            knot_id: row.get(0)?,
+            content_hash: row.get(1)?,
+            vector: row.get(2)?,
+        })
+    })?;
+    rows.collect()
+}
+
+pub fn upsert_knot_embedding(
+    conn: &Connection,
+    knot_id: &str,
+    content_hash: &str,
+    vector: &[u8],
+) -> Result<()> {
+    with_write_retry(|| {
+        conn.execute(
+            r#"
+INSERT INTO knot_embedding (knot_id, content_hash, vector, updated_at)
+VALUES (?1, ?2, ?3, ?4)
+ON CONFLICT(knot_id) DO UPDATE SET
+    content_hash = excluded.content_hash,
+    vector = excluded.vector,
+    updated_at = excluded.updated_at
+"#,
+            params![knot_id, content_hash, vector, now_utc_rfc3339()],
+        )?;
+        Ok(())
+    })
+}
+
+pub fn delete_knot_embedding(conn: &Connection, knot_id: &str) -> Result<()> {
+    with_write_retry(|| {
+        conn.execute(
+            "DELETE FROM knot_embedding WHERE knot_id = ?1",
+            params![knot_id],
+        )?;
+        Ok(())
+    })
+}
@@ -0,0 +1,71 @@
+use rusqlite::{params, Connection, Result};
+use serde::Serialize;
+
+use super::with_write_retry;
+
+/// A commit or branch recorded against a knot by `kno link` or a `KNO:`
+/// commit trailer.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct GitLinkRecord {
+    /// `commit` or `branch`.
+    pub kind: String,
+    /// Full commit sha or branch name.
+    pub target: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    pub linked_at: String,
+}
+
+/// Records a link; relinking the same target keeps the first record.
+pub fn insert_git_link(conn: &Connection, knot_id: &str, link: &GitLinkRecord) -> Result<bool> {
+    with_write_retry(|| {
+        let inserted = conn.execute(
+            r#"
+INSERT OR IGNORE INTO knot_git_link (knot_id, kind, target, subject, linked_at)
+VALUES (?1, ?2, ?3, ?4, ?5)
+"#,
+            params![
+                knot_id,
+                link.kind,
+                link.target,
+                link.subject,
+                link.linked_at
+            ],
+        )?;
+        Ok(inserted > 0)
+    })
+}
+
+pub fn list_git_links(conn: &Connection, knot_id: &str) -> Result<Vec<GitLinkRecord>> {
+    let mut stmt = conn.prepare(
+        r#"
+SELECT kind, target, subject, linked_at
+FROM knot_git_link
+WHERE knot_id = ?1
+ORDER BY linked_at ASC, kind ASC, target ASC
+"#,
+    )?;
+    let rows = stmt.query_map(params![knot_id], |row| {
+        Ok(GitLinkRecord {
+            kind: row.get(0)?,
+            target: row.get(1)?,
+            subject: row.get(2)?,
+            linked_at: row.get(3)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Knots that link `target` as a `kind` (`commit` or `branch`).
+pub fn knots_with_git_link(conn: &Connection, kind: &str, target: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        r#"
+SELECT knot_id
+FROM knot_git_link
+WHERE kind = ?1 AND target = ?2
+ORDER BY linked_at ASC, knot_id ASC
+"#,
+    )?;
+    let rows = stmt.query_map(params![kind, target], |row| row.get(0))?;
+    rows.collect()
+}
@@ -0,0 +1,107 @@
+use rusqlite::{params, Connection, Result};
+
+use super::with_write_retry;
+
+/// `knot_fts_state` rows that no longer describe their `knot_hot` row:
+/// the knot changed, was removed, or was rewritten under a new rowid.
+const STALE_STATE: &str = r#"
+NOT EXISTS (
+    SELECT 1 FROM knot_hot h
+    WHERE h.id = s.knot_id AND h.rowid = s.hot_rowid AND h.updated_at = s.updated_at
+)
+"#;
+
+/// The note or handoff bodies in a metadata JSON array, one per line, so
+/// the index holds the text users search rather than escaped JSON.
+fn entry_bodies(column: &str) -> String {
+    format!(
+        "COALESCE((SELECT group_concat(json_extract(e.value, '$.content'), char(10)) \
+         FROM json_each({column}) e), '')"
+    )
+}
+
+/// Brings the `knot_fts` trigram index in line with `knot_hot`, reindexing
+/// only knots whose `updated_at` moved since the last refresh.
+pub fn refresh_grep_index(conn: &Connection) -> Result<()> {
+    with_write_retry(|| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            &format!(
+                "DELETE FROM knot_fts WHERE rowid IN \
+                 (SELECT s.hot_rowid FROM knot_fts_state s WHERE {STALE_STATE})"
+            ),
+            [],
+        )?;
+        tx.execute(
+            &format!("DELETE FROM knot_fts_state AS s WHERE {STALE_STATE}"),
+            [],
+        )?;
+        tx.execute(
+            &format!(
+                r#"
+INSERT INTO knot_fts (rowid, title, description, notes, handoffs)
+SELECT h.rowid, h.title, COALESCE(h.description, ''), {}, {}
+FROM knot_hot h
+WHERE NOT EXISTS (SELECT 1 FROM knot_fts_state s WHERE s.knot_id = h.id)
+"#,
+                entry_bodies("h.notes_json"),
+                entry_bodies("h.handoff_capsules_json")
+            ),
+            [],
+        )?;
+        tx.execute(
+            r#"
+INSERT INTO knot_fts_state (knot_id, hot_rowid, updated_at)
+SELECT h.id, h.rowid, h.updated_at
+FROM knot_hot h
+WHERE NOT EXISTS (SELECT 1 FROM knot_fts_state s WHERE s.knot_id = h.id)
+"#,
+            [],
+        )?;
+        tx.commit()
+    })
+}
+
+/// Drops `knot_id` from the index so the next refresh reindexes it, for a
+/// knot rewritten in place without a new `updated_at`. The index is then
+/// merged so the removed text leaves its segments too.
+pub fn forget_grep_index_entry(conn: &Connection, knot_id: &str) -> Result<()> {
+    with_write_retry(|| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM knot_fts WHERE rowid IN \
+             (SELECT hot_rowid FROM knot_fts_state WHERE knot_id = ?1)",
+            params![knot_id],
+        )?;
+        tx.execute(
+            "DELETE FROM knot_fts_state WHERE knot_id = ?1",
+            params![knot_id],
+        )?;
+        tx.execute("INSERT INTO knot_fts (knot_fts) VALUES ('optimize')", [])?;
+        tx.commit()
+    })
+}
+
+/// Ids of knots whose indexed `columns` contain `needle`, ignoring ASCII
+/// case. Callers still confirm each candidate against the knot itself.
+pub fn grep_index_candidates(
+    conn: &Connection,
+    columns: &[&str],
+    needle: &str,
+) -> Result<Vec<String>> {
+    let query = format!(
+        "{{{}}} : \"{}\"",
+        columns.join(" "),
+        needle.replace('"', "\"\"")
+    );
+    let mut stmt = conn.prepare(
+        r#"
+SELECT h.id FROM knot_fts f
+JOIN knot_hot h ON h.rowid = f.rowid
+WHERE knot_fts MATCH ?1
+ORDER BY h.id ASC
+"#,
+    )?;
+    let ids = stmt.query_map(params![query], |row| row.get(0))?;
+    ids.collect()
+}
@@ -0,0 +1,95 @@
+use rusqlite::{params, Connection, Result};
+use serde::Serialize;
+
+use super::with_write_retry;
+
+/// A claim released by `kno handback`: why the agent gave the knot back
+/// and, with a cooldown, until when that agent should leave it alone.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct HandbackRecord {
+    #[serde(rename = "id")]
+    pub handback_id: String,
+    pub reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_name: Option<String>,
+    pub from_state: String,
+    pub to_state: String,
+    pub handed_back_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cooldown_until: Option<String>,
+}
+
+/// Records a handback; a repeated event keeps the first record.
+pub fn insert_handback(
+    conn: &Connection,
+    knot_id: &str,
+    handback: &HandbackRecord,
+) -> Result<bool> {
+    with_write_retry(|| {
+        let inserted = conn.execute(
+            r#"
+INSERT OR IGNORE INTO knot_handback (
+    knot_id, handback_id, reason, note, agent_name,
+    from_state, to_state, handed_back_at, cooldown_until
+)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+"#,
+            params![
+                knot_id,
+                handback.handback_id,
+                handback.reason,
+                handback.note,
+                handback.agent_name,
+                handback.from_state,
+                handback.to_state,
+                handback.handed_back_at,
+                handback.cooldown_until
+            ],
+        )?;
+        Ok(inserted > 0)
+    })
+}
+
+pub fn list_handbacks(conn: &Connection, knot_id: &str) -> Result<Vec<HandbackRecord>> {
+    let mut stmt = conn.prepare(
+        r#"
+SELECT handback_id, reason, note, agent_name, from_state, to_state,
+       handed_back_at, cooldown_until
+FROM knot_handback
+WHERE knot_id = ?1
+ORDER BY handed_back_at ASC, handback_id ASC
+"#,
+    )?;
+    let rows = stmt.query_map(params![knot_id], |row| {
+        Ok(HandbackRecord {
+            handback_id: row.get(0)?,
+            reason: row.get(1)?,
+            note: row.get(2)?,
+            agent_name: row.get(3)?,
+            from_state: row.get(4)?,
+            to_state: row.get(5)?,
+            handed_back_at: row.get(6)?,
+            cooldown_until: row.get(7)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// `(knot_id, cooldown_until)` for every handback by `agent_name` that set
+/// a cooldown. Callers compare the times; expired rows are kept as history.
+pub fn list_handback_cooldowns(
+    conn: &Connection,
+    agent_name: &str,
+) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        r#"
+SELECT knot_id, cooldown_until
+FROM knot_handback
+WHERE agent_name = ?1 AND cooldown_until IS NOT NULL
+"#,
+    )?;
+    let rows = stmt.query_map(params![agent_name], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
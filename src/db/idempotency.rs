@@ -0,0 +1,54 @@
+use rusqlite::{params, Connection, OptionalExtension, Result};
+
+use super::with_write_retry;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdempotencyRecord {
+    pub key: String,
+    pub command: String,
+    pub fingerprint: String,
+    pub output: String,
+    pub created_at: String,
+}
+
+pub fn get_idempotency_record(conn: &Connection, key: &str) -> Result<Option<IdempotencyRecord>> {
+    conn.query_row(
+        r#"
+SELECT key, command, fingerprint, output, created_at
+FROM idempotency_keys
+WHERE key = ?1
+"#,
+        params![key],
+        |row| {
+            Ok(IdempotencyRecord {
+                key: row.get(0)?,
+                command: row.get(1)?,
+                fingerprint: row.get(2)?,
+                output: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        },
+    )
+    .optional()
+}
+
+pub fn insert_idempotency_record(conn: &Connection, record: &IdempotencyRecord) -> Result<()> {
+    with_write_retry(|| {
+        conn.execute(
+            r#"
+INSERT INTO idempotency_keys (key, command, fingerprint, output, created_at)
+VALUES (?1, ?2, ?3, ?4, ?5)
+ON CONFLICT(key) DO NOTHING
+"#,
+            params![
+                record.key,
+                record.command,
+                record.fingerprint,
+                record.output,
+                record.created_at
+            ],
+        )?;
+        Ok(())
+    })?;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use rusqlite::{Connection, Result};
+
+/// Problems reported by `PRAGMA integrity_check`; empty when the database
+/// is sound.
+pub fn integrity_problems(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    let problems = rows.collect::<Result<Vec<_>>>()?;
+    Ok(problems
+        .into_iter()
+        .filter(|problem| problem != "ok")
+        .collect())
+}
+
+/// Copies the WAL into the database file and truncates it to zero bytes.
+pub fn checkpoint_truncate(conn: &Connection) -> Result<()> {
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+}
+
+pub fn vacuum(conn: &Connection) -> Result<()> {
+    conn.execute_batch("VACUUM")
+}
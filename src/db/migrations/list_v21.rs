@@ -0,0 +1,195 @@
+use super::list::Migration;
+
+/// Migrations from version 21 on, applied after [`super::list::MIGRATIONS`].
+pub(super) const MIGRATIONS_FROM_V21: [Migration; 14] = [
+    Migration {
+        version: 21,
+        name: "knot_custom_fields_v1",
+        sql: r#"
+ALTER TABLE knot_hot ADD COLUMN custom_json TEXT NOT NULL DEFAULT '{}';
+"#,
+    },
+    Migration {
+        version: 22,
+        name: "knot_snooze_v1",
+        sql: r#"
+ALTER TABLE knot_hot ADD COLUMN snoozed_until TEXT;
+"#,
+    },
+    Migration {
+        version: 23,
+        name: "usage_metric_v1",
+        sql: r#"
+CREATE TABLE IF NOT EXISTS usage_metric (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    command TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    ok INTEGER NOT NULL,
+    sync_files INTEGER
+);
+CREATE INDEX IF NOT EXISTS idx_usage_metric_command ON usage_metric(command);
+"#,
+    },
+    Migration {
+        version: 24,
+        name: "sync_skipped_v1",
+        sql: r#"
+CREATE TABLE IF NOT EXISTS sync_skipped (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    path TEXT NOT NULL UNIQUE,
+    stream TEXT NOT NULL,
+    knot_id TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    expected_etag TEXT NOT NULL,
+    actual_etag TEXT,
+    event_json TEXT NOT NULL,
+    recorded_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_sync_skipped_knot ON sync_skipped(knot_id);
+"#,
+    },
+    Migration {
+        version: 25,
+        name: "private_knot_v1",
+        sql: r#"
+CREATE TABLE IF NOT EXISTS private_knot (
+    knot_id TEXT PRIMARY KEY,
+    created_at TEXT NOT NULL
+);
+"#,
+    },
+    Migration {
+        version: 26,
+        name: "private_knot_draft_v1",
+        sql: r#"
+ALTER TABLE private_knot ADD COLUMN draft INTEGER NOT NULL DEFAULT 0;
+"#,
+    },
+    Migration {
+        version: 27,
+        name: "watch_inbox_v1",
+        sql: r#"
+CREATE TABLE IF NOT EXISTS watched_knot (
+    knot_id TEXT PRIMARY KEY,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS inbox_item (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    knot_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    acked_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_inbox_item_unacked ON inbox_item(acked_at, id);
+"#,
+    },
+    Migration {
+        version: 28,
+        name: "server_access_log_v1",
+        sql: r#"
+CREATE TABLE IF NOT EXISTS server_access_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    token_name TEXT NOT NULL,
+    verb TEXT NOT NULL,
+    key TEXT NOT NULL,
+    outcome TEXT NOT NULL,
+    message TEXT,
+    created_at TEXT NOT NULL,
+    at_ms INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_server_access_log_token ON server_access_log(token_name, at_ms);
+"#,
+    },
+    Migration {
+        version: 29,
+        name: "grep_index_v1",
+        sql: r#"
+CREATE VIRTUAL TABLE IF NOT EXISTS knot_fts USING fts5(
+    title, description, notes, handoffs, tokenize = 'trigram'
+);
+
+CREATE TABLE IF NOT EXISTS knot_fts_state (
+    knot_id TEXT PRIMARY KEY,
+    hot_rowid INTEGER NOT NULL,
+    updated_at TEXT NOT NULL
+);
+"#,
+    },
+    Migration {
+        version: 30,
+        name: "knot_embedding_v1",
+        sql: r#"
+CREATE TABLE IF NOT EXISTS knot_embedding (
+    knot_id TEXT PRIMARY KEY,
+    content_hash TEXT NOT NULL,
+    vector BLOB NOT NULL,
+    updated_at TEXT NOT NULL
+);
+"#,
+    },
+    Migration {
+        version: 31,
+        name: "knot_criterion_v1",
+        sql: r#"
+CREATE TABLE IF NOT EXISTS knot_criterion (
+    knot_id TEXT NOT NULL,
+    criterion_id TEXT NOT NULL,
+    text TEXT NOT NULL,
+    added_at TEXT NOT NULL,
+    checked INTEGER NOT NULL DEFAULT 0,
+    checked_at TEXT,
+    PRIMARY KEY (knot_id, criterion_id)
+);
+"#,
+    },
+    Migration {
+        version: 32,
+        name: "knot_handback_v1",
+        sql: r#"
+CREATE TABLE IF NOT EXISTS knot_handback (
+    knot_id TEXT NOT NULL,
+    handback_id TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    note TEXT,
+    agent_name TEXT,
+    from_state TEXT NOT NULL,
+    to_state TEXT NOT NULL,
+    handed_back_at TEXT NOT NULL,
+    cooldown_until TEXT,
+    PRIMARY KEY (knot_id, handback_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_knot_handback_agent ON knot_handback(agent_name);
+"#,
+    },
+    Migration {
+        version: 33,
+        name: "knot_plan_v1",
+        sql: r#"
+CREATE TABLE IF NOT EXISTS knot_plan (
+    knot_id TEXT NOT NULL,
+    plan_id TEXT NOT NULL,
+    content TEXT NOT NULL,
+    digest TEXT NOT NULL,
+    author TEXT,
+    set_at TEXT NOT NULL,
+    PRIMARY KEY (knot_id, plan_id)
+);
+"#,
+    },
+    Migration {
+        version: 34,
+        name: "grep_index_v2",
+        sql: r#"
+DELETE FROM knot_fts;
+DELETE FROM knot_fts_state;
+"#,
+    },
+];
@@ -0,0 +1,67 @@
+use rusqlite::{params, Connection, Result};
+use serde::Serialize;
+
+use super::with_write_retry;
+
+/// One version of a knot's plan document, set with `kno plan set`.
+/// Versions are numbered from 1 in the order they were set.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct PlanRecord {
+    #[serde(rename = "id")]
+    pub plan_id: String,
+    pub version: usize,
+    pub content: String,
+    pub digest: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    pub set_at: String,
+}
+
+/// Records a plan version; a repeated event keeps the first record. The
+/// record's `version` is derived on read and not stored.
+pub fn insert_plan(conn: &Connection, knot_id: &str, plan: &PlanRecord) -> Result<bool> {
+    with_write_retry(|| {
+        let inserted = conn.execute(
+            r#"
+INSERT OR IGNORE INTO knot_plan (knot_id, plan_id, content, digest, author, set_at)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+"#,
+            params![
+                knot_id,
+                plan.plan_id,
+                plan.content,
+                plan.digest,
+                plan.author,
+                plan.set_at
+            ],
+        )?;
+        Ok(inserted > 0)
+    })
+}
+
+/// Every plan version of a knot, oldest first.
+pub fn list_plans(conn: &Connection, knot_id: &str) -> Result<Vec<PlanRecord>> {
+    let mut stmt = conn.prepare(
+        r#"
+SELECT plan_id, content, digest, author, set_at
+FROM knot_plan
+WHERE knot_id = ?1
+ORDER BY set_at ASC, plan_id ASC
+"#,
+    )?;
+    let rows = stmt.query_map(params![knot_id], |row| {
+        Ok(PlanRecord {
+            plan_id: row.get(0)?,
+            version: 0,
+            content: row.get(1)?,
+            digest: row.get(2)?,
+            author: row.get(3)?,
+            set_at: row.get(4)?,
+        })
+    })?;
+    let mut plans = rows.collect::<Result<Vec<_>>>()?;
+    for (index, plan) in plans.iter_mut().enumerate() {
+        plan.version = index + 1;
+    }
+    Ok(plans)
+}
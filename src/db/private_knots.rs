@@ -0,0 +1,56 @@
+use std::collections::HashSet;
+
+use rusqlite::{params, Connection, OptionalExtension, Result};
+
+use super::{now_utc_rfc3339, with_write_retry};
+
+/// Marks a knot private: its events stay out of the published stream until
+/// `kno publish` moves them. Drafts are also restamped when published.
+pub fn insert_private_knot(conn: &Connection, knot_id: &str, draft: bool) -> Result<()> {
+    with_write_retry(|| {
+        conn.execute(
+            "INSERT OR IGNORE INTO private_knot (knot_id, created_at, draft) VALUES (?1, ?2, ?3)",
+            params![knot_id, now_utc_rfc3339(), draft],
+        )?;
+        Ok(())
+    })
+}
+
+/// `None` when the knot is not private, otherwise whether it is a draft.
+pub fn private_knot_draft(conn: &Connection, knot_id: &str) -> Result<Option<bool>> {
+    conn.query_row(
+        "SELECT draft FROM private_knot WHERE knot_id = ?1",
+        params![knot_id],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Moves a published draft's creation and update times to `at`, matching
+/// its restamped events.
+pub fn restamp_knot_hot(conn: &Connection, knot_id: &str, at: &str) -> Result<()> {
+    with_write_retry(|| {
+        conn.execute(
+            "UPDATE knot_hot SET created_at = ?2, updated_at = ?2 WHERE id = ?1",
+            params![knot_id, at],
+        )?;
+        Ok(())
+    })
+}
+
+/// Returns whether the knot was private.
+pub fn delete_private_knot(conn: &Connection, knot_id: &str) -> Result<bool> {
+    with_write_retry(|| {
+        let removed = conn.execute(
+            "DELETE FROM private_knot WHERE knot_id = ?1",
+            params![knot_id],
+        )?;
+        Ok(removed > 0)
+    })
+}
+
+pub fn list_private_knots(conn: &Connection) -> Result<HashSet<String>> {
+    let mut stmt = conn.prepare("SELECT knot_id FROM private_knot")?;
+    let ids = stmt.query_map([], |row| row.get(0))?;
+    ids.collect()
+}
@@ -0,0 +1,122 @@
+use rusqlite::{ffi, params, Connection, Result};
+
+use super::migrations::{META_DEFAULTS, SCHEMA_WRITER_VERSION_META};
+use super::{get_meta, CURRENT_SCHEMA_VERSION};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaInfo {
+    pub schema_version: Option<i64>,
+    pub writer_version: Option<String>,
+    pub migrations: Vec<AppliedMigration>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub applied_at: String,
+}
+
+impl SchemaInfo {
+    /// Highest schema version this database claims, from either the meta
+    /// row or the migration ledger.
+    pub fn effective_version(&self) -> Option<i64> {
+        let ledger = self
+            .migrations
+            .iter()
+            .map(|migration| migration.version)
+            .max();
+        self.schema_version.max(ledger)
+    }
+}
+
+/// Schema state read without migrating, so it also works on caches this
+/// binary cannot open.
+pub fn schema_info(conn: &Connection) -> Result<SchemaInfo> {
+    let mut info = SchemaInfo {
+        schema_version: None,
+        writer_version: None,
+        migrations: Vec::new(),
+    };
+    if table_exists(conn, "meta")? {
+        info.schema_version =
+            get_meta(conn, "schema_version")?.and_then(|raw| raw.trim().parse().ok());
+        info.writer_version = get_meta(conn, SCHEMA_WRITER_VERSION_META)?;
+    }
+    if table_exists(conn, "schema_migrations")? {
+        let mut stmt = conn
+            .prepare("SELECT version, name, applied_at FROM schema_migrations ORDER BY version")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(AppliedMigration {
+                version: row.get(0)?,
+                name: row.get(1)?,
+                applied_at: row.get(2)?,
+            })
+        })?;
+        info.migrations = rows.collect::<Result<_>>()?;
+    }
+    Ok(info)
+}
+
+/// Refuses caches migrated by a newer kno. Migrating them here would stamp
+/// an older `schema_version` over tables this binary does not understand.
+pub(super) fn ensure_schema_supported(conn: &Connection) -> Result<()> {
+    let info = schema_info(conn)?;
+    match info.effective_version() {
+        Some(version) if version > CURRENT_SCHEMA_VERSION => {
+            let writer = info
+                .writer_version
+                .map(|writer| format!(" by kno {writer}"))
+                .unwrap_or_default();
+            Err(rusqlite::Error::SqliteFailure(
+                ffi::Error::new(ffi::SQLITE_CANTOPEN),
+                Some(format!(
+                    "cache schema version {version} was written{writer}, but kno {} supports \
+                     up to {CURRENT_SCHEMA_VERSION}; upgrade with `kno upgrade`",
+                    env!("CARGO_PKG_VERSION")
+                )),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub(super) fn needs_schema_bootstrap(conn: &Connection) -> Result<bool> {
+    if !table_exists(conn, "schema_migrations")? || !table_exists(conn, "meta")? {
+        return Ok(true);
+    }
+
+    let applied_count: i64 =
+        conn.query_row("SELECT COUNT(*) FROM schema_migrations", [], |row| {
+            row.get(0)
+        })?;
+    if applied_count < CURRENT_SCHEMA_VERSION {
+        return Ok(true);
+    }
+
+    let expected_schema_version = CURRENT_SCHEMA_VERSION.to_string();
+    let schema_version = get_meta(conn, "schema_version")?;
+    if schema_version.as_deref() != Some(expected_schema_version.as_str()) {
+        return Ok(true);
+    }
+
+    for (key, _) in META_DEFAULTS {
+        if get_meta(conn, key)?.is_none() {
+            return Ok(true);
+        }
+    }
+    if get_meta(conn, SCHEMA_WRITER_VERSION_META)?.is_none() {
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
+fn table_exists(conn: &Connection, table_name: &str) -> Result<bool> {
+    let exists: i64 = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name=?1)",
+        params![table_name],
+        |row| row.get(0),
+    )?;
+    Ok(exists == 1)
+}
@@ -0,0 +1,99 @@
+use rusqlite::{params, Connection, Result};
+use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use super::with_write_retry;
+
+/// One request answered by `kno serve --replication`. Rows are only ever
+/// appended.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ServerAccessRecord {
+    pub id: i64,
+    /// Token name from `server.toml`, or `-` before a client authenticates
+    /// and on servers without tokens.
+    pub token_name: String,
+    pub verb: String,
+    pub key: String,
+    /// `ok`, `denied` (authentication, role, or rate limit), or `error`.
+    pub outcome: String,
+    pub message: Option<String>,
+    pub created_at: String,
+}
+
+pub fn insert_server_access(
+    conn: &Connection,
+    token_name: &str,
+    verb: &str,
+    key: &str,
+    outcome: &str,
+    message: Option<&str>,
+) -> Result<()> {
+    let now = OffsetDateTime::now_utc();
+    let created_at = now
+        .format(&Rfc3339)
+        .expect("RFC3339 formatting for UTC timestamp should never fail");
+    let at_ms = epoch_ms(now);
+    with_write_retry(|| {
+        conn.execute(
+            r#"
+INSERT INTO server_access_log (token_name, verb, key, outcome, message, created_at, at_ms)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+"#,
+            params![token_name, verb, key, outcome, message, created_at, at_ms],
+        )?;
+        Ok(())
+    })
+}
+
+/// Requests `token_name` made in the last `window_ms` that were not denied,
+/// which is what a rate limit counts.
+pub fn count_recent_server_access(
+    conn: &Connection,
+    token_name: &str,
+    window_ms: i64,
+) -> Result<u64> {
+    let since_ms = epoch_ms(OffsetDateTime::now_utc()) - window_ms;
+    let count: i64 = conn.query_row(
+        r#"
+SELECT COUNT(*) FROM server_access_log
+WHERE token_name = ?1 AND at_ms >= ?2 AND outcome <> 'denied'
+"#,
+        params![token_name, since_ms],
+        |row| row.get(0),
+    )?;
+    Ok(count.max(0) as u64)
+}
+
+/// The newest `limit` rows, optionally for one token, newest first.
+pub fn list_server_access(
+    conn: &Connection,
+    token_name: Option<&str>,
+    limit: u64,
+) -> Result<Vec<ServerAccessRecord>> {
+    let mut stmt = conn.prepare(
+        r#"
+SELECT id, token_name, verb, key, outcome, message, created_at
+FROM server_access_log
+WHERE ?1 IS NULL OR token_name = ?1
+ORDER BY id DESC
+LIMIT ?2
+"#,
+    )?;
+    let rows = stmt.query_map(params![token_name, limit as i64], |row| {
+        Ok(ServerAccessRecord {
+            id: row.get(0)?,
+            token_name: row.get(1)?,
+            verb: row.get(2)?,
+            key: row.get(3)?,
+            outcome: row.get(4)?,
+            message: row.get(5)?,
+            created_at: row.get(6)?,
+        })
+    })?;
+    rows.collect()
+}
+
+fn epoch_ms(at: OffsetDateTime) -> i64 {
+    (at.unix_timestamp_nanos() / 1_000_000) as i64
+}
@@ -0,0 +1,111 @@
+use rusqlite::{params, Connection, OptionalExtension, Result, Row};
+
+use super::{now_utc_rfc3339, with_write_retry};
+
+/// An incoming event that sync left out because its knot had moved past the
+/// event's `profile_etag` precondition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncSkippedRecord {
+    pub id: i64,
+    /// Path of the event file relative to the knots branch root.
+    pub path: String,
+    /// `index` or `full`, the event stream the file belongs to.
+    pub stream: String,
+    pub knot_id: String,
+    pub event_id: String,
+    pub event_type: String,
+    pub reason: String,
+    pub expected_etag: String,
+    pub actual_etag: Option<String>,
+    /// The event as read, so it can be re-applied without the source file.
+    pub event_json: String,
+    pub recorded_at: String,
+}
+
+/// Records a skipped event; a file skipped again replaces its earlier row.
+pub fn record_sync_skipped(conn: &Connection, record: &SyncSkippedRecord) -> Result<()> {
+    with_write_retry(|| {
+        conn.execute(
+            r#"
+INSERT INTO sync_skipped (
+    path, stream, knot_id, event_id, event_type, reason,
+    expected_etag, actual_etag, event_json, recorded_at
+)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+ON CONFLICT(path) DO UPDATE SET
+    reason = excluded.reason,
+    expected_etag = excluded.expected_etag,
+    actual_etag = excluded.actual_etag,
+    event_json = excluded.event_json,
+    recorded_at = excluded.recorded_at
+"#,
+            params![
+                record.path,
+                record.stream,
+                record.knot_id,
+                record.event_id,
+                record.event_type,
+                record.reason,
+                record.expected_etag,
+                record.actual_etag,
+                record.event_json,
+                now_utc_rfc3339()
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+/// Skipped events, oldest first, optionally for one knot.
+pub fn list_sync_skipped(
+    conn: &Connection,
+    knot_id: Option<&str>,
+) -> Result<Vec<SyncSkippedRecord>> {
+    let mut stmt = conn.prepare(
+        r#"
+SELECT id, path, stream, knot_id, event_id, event_type, reason,
+       expected_etag, actual_etag, event_json, recorded_at
+FROM sync_skipped
+WHERE ?1 IS NULL OR knot_id = ?1
+ORDER BY id ASC
+"#,
+    )?;
+    let rows = stmt.query_map(params![knot_id], row_to_record)?;
+    rows.collect()
+}
+
+pub fn get_sync_skipped(conn: &Connection, id: i64) -> Result<Option<SyncSkippedRecord>> {
+    conn.query_row(
+        r#"
+SELECT id, path, stream, knot_id, event_id, event_type, reason,
+       expected_etag, actual_etag, event_json, recorded_at
+FROM sync_skipped
+WHERE id = ?1
+"#,
+        params![id],
+        row_to_record,
+    )
+    .optional()
+}
+
+pub fn delete_sync_skipped(conn: &Connection, id: i64) -> Result<bool> {
+    with_write_retry(|| {
+        Ok(conn.execute("DELETE FROM sync_skipped WHERE id = ?1", params![id])? > 0)
+    })
+}
+
+fn row_to_record(row: &Row<'_>) -> Result<SyncSkippedRecord> {
+    Ok(SyncSkippedRecord {
+        id: row.get(0)?,
+        path: row.get(1)?,
+        stream: row.get(2)?,
+        knot_id: row.get(3)?,
+        event_id: row.get(4)?,
+        event_type: row.get(5)?,
+        reason: row.get(6)?,
+        expected_etag: row.get(7)?,
+        actual_etag: row.get(8)?,
+        event_json: row.get(9)?,
+        recorded_at: row.get(10)?,
+    })
+}
@@ -1,6 +1,7 @@
+use super::migrations::SCHEMA_WRITER_VERSION_META;
 use super::{
-    get_pull_drift_warn_threshold, get_sync_fetch_blob_limit_kb, open_connection, set_meta,
-    CURRENT_SCHEMA_VERSION,
+    get_meta, get_pull_drift_warn_threshold, get_sync_fetch_blob_limit_kb, open_connection,
+    open_connection_raw, schema_info, set_meta, CURRENT_SCHEMA_VERSION,
 };
 use rusqlite::params;
 use std::sync::mpsc;
@@ -216,6 +217,64 @@ fn reapplies_migrations_idempotently() {
     cleanup_db_files(&path);
 }
 
+#[test]
+fn migrations_record_the_writer_version_and_refuse_newer_schemas() {
+    let path = unique_db_path();
+    let conn = open_connection(&path).expect("open should initialize schema");
+    assert_eq!(
+        get_meta(&conn, SCHEMA_WRITER_VERSION_META)
+            .expect("meta read")
+            .as_deref(),
+        Some(env!("CARGO_PKG_VERSION"))
+    );
+    let info = schema_info(&conn).expect("schema info");
+    assert_eq!(info.effective_version(), Some(CURRENT_SCHEMA_VERSION));
+    assert_eq!(info.migrations.len() as i64, CURRENT_SCHEMA_VERSION);
+
+    let newer = CURRENT_SCHEMA_VERSION + 1;
+    conn.execute(
+        "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?1, 'future', 'later')",
+        params![newer],
+    )
+    .expect("future migration row");
+    set_meta(&conn, SCHEMA_WRITER_VERSION_META, "99.0.0").expect("writer");
+    drop(conn);
+
+    let err = open_connection(&path).expect_err("newer schema should be refused");
+    let message = err.to_string();
+    assert!(message.contains(&format!("cache schema version {newer}")));
+    assert!(message.contains("by kno 99.0.0"));
+    assert!(message.contains("kno upgrade"));
+
+    let raw = open_connection_raw(&path).expect("raw open still works");
+    assert_eq!(
+        get_meta(&raw, "schema_version").expect("meta").as_deref(),
+        Some(CURRENT_SCHEMA_VERSION.to_string().as_str()),
+        "a refused open must not rewrite the schema version"
+    );
+
+    cleanup_db_files(&path);
+}
+
+#[test]
+fn opening_backfills_a_missing_writer_version() {
+    let path = unique_db_path();
+    let conn = open_connection(&path).expect("open should initialize schema");
+    conn.execute(
+        "DELETE FROM meta WHERE key = ?1",
+        params![SCHEMA_WRITER_VERSION_META],
+    )
+    .expect("drop writer");
+    drop(conn);
+
+    let conn = open_connection(&path).expect("reopen");
+    assert!(get_meta(&conn, SCHEMA_WRITER_VERSION_META)
+        .expect("meta read")
+        .is_some());
+
+    cleanup_db_files(&path);
+}
+
 #[test]
 fn migrations_add_parity_columns_and_backfill_profile_defaults() {
     let path = unique_db_path();
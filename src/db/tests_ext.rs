@@ -28,9 +28,11 @@ fn upsert_and_get_knot_hot_round_trips_invariants() {
             body: None,
             description: Some("test invariants"),
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: Some("work"),
             tags: &["alpha".to_string()],
+            custom_fields: &Default::default(),
             notes: &[],
             handoff_capsules: &[],
             invariants: &invariants,
@@ -44,6 +46,7 @@ fn upsert_and_get_knot_hot_round_trips_invariants() {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: Some("2026-03-05T09:00:00Z"),
+            snoozed_until: None,
         },
     )
     .expect("upsert with invariants should succeed");
@@ -77,9 +80,11 @@ fn upsert_knot_hot_with_empty_invariants_round_trips() {
             body: None,
             description: None,
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: None,
             tags: &[],
+            custom_fields: &Default::default(),
             notes: &[],
             handoff_capsules: &[],
             invariants: &[],
@@ -93,6 +98,7 @@ fn upsert_knot_hot_with_empty_invariants_round_trips() {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: None,
+            snoozed_until: None,
         },
     )
     .expect("upsert with empty invariants should succeed");
@@ -139,9 +145,11 @@ fn count_active_leases_returns_count() {
                 body: None,
                 description: None,
                 acceptance: None,
+                estimate: None,
                 priority: None,
                 knot_type,
                 tags: &[],
+                custom_fields: &Default::default(),
                 notes: &[],
                 handoff_capsules: &[],
                 invariants: &[],
@@ -155,6 +163,7 @@ fn count_active_leases_returns_count() {
                 deferred_from_state: None,
                 blocked_from_state: None,
                 created_at: None,
+                snoozed_until: None,
             },
         )
         .expect("upsert should succeed");
@@ -188,9 +197,11 @@ fn get_knot_hot_accepts_legacy_empty_lease_data_json() {
             body: None,
             description: None,
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: Some("work"),
             tags: &[],
+            custom_fields: &Default::default(),
             notes: &[],
             handoff_capsules: &[],
             invariants: &[],
@@ -204,6 +215,7 @@ fn get_knot_hot_accepts_legacy_empty_lease_data_json() {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: None,
+            snoozed_until: None,
         },
     )
     .expect("upsert should succeed");
@@ -256,3 +268,86 @@ fn fetch_blob_limit_env_override_covers_env_path() {
 
     cleanup_db_files(&path);
 }
+
+#[test]
+fn cross_repo_edges_are_listed_per_knot_but_not_by_kind() {
+    use crate::db::{insert_edge, list_edges, list_edges_by_kind, EdgeDirection};
+
+    let path = unique_db_path();
+    let conn = open_connection(&path).expect("connection should open");
+    insert_edge(&conn, "K-1", "blocked_by", "K-2").expect("local edge");
+    insert_edge(&conn, "K-1", "blocked_by", "backend:backend-a1").expect("remote edge");
+
+    let by_kind = list_edges_by_kind(&conn, "blocked_by").expect("by kind");
+    assert_eq!(by_kind.len(), 1);
+    assert_eq!(by_kind[0].dst, "K-2");
+    let outgoing = list_edges(&conn, "K-1", EdgeDirection::Outgoing).expect("outgoing");
+    assert_eq!(outgoing.len(), 2);
+    let dst_repo: Option<String> = conn
+        .query_row(
+            "SELECT dst_repo FROM edge WHERE dst = ?1",
+            params!["backend:backend-a1"],
+            |row| row.get(0),
+        )
+        .expect("dst_repo");
+    assert_eq!(dst_repo.as_deref(), Some("backend"));
+    cleanup_db_files(&path);
+}
+
+#[test]
+fn git_links_keep_the_first_record_per_target() {
+    use crate::db::{insert_git_link, list_git_links, GitLinkRecord};
+
+    let path = unique_db_path();
+    let conn = open_connection(&path).expect("connection should open");
+    let link = GitLinkRecord {
+        kind: "commit".to_string(),
+        target: "a".repeat(40),
+        subject: Some("Fix login".to_string()),
+        linked_at: "2026-01-01T00:00:00Z".to_string(),
+    };
+    assert!(insert_git_link(&conn, "K-1", &link).expect("insert"));
+    let relinked = GitLinkRecord {
+        linked_at: "2026-02-01T00:00:00Z".to_string(),
+        ..link.clone()
+    };
+    assert!(!insert_git_link(&conn, "K-1", &relinked).expect("reinsert"));
+    assert_eq!(list_git_links(&conn, "K-1").expect("list"), vec![link]);
+    assert!(list_git_links(&conn, "K-2").expect("list").is_empty());
+    cleanup_db_files(&path);
+}
+
+#[test]
+fn handback_cooldowns_list_only_the_agents_own_cooldowns() {
+    use crate::db::{insert_handback, list_handback_cooldowns, list_handbacks, HandbackRecord};
+
+    let path = unique_db_path();
+    let conn = open_connection(&path).expect("connection should open");
+    let handback = HandbackRecord {
+        handback_id: "H-1".to_string(),
+        reason: "blocked".to_string(),
+        note: None,
+        agent_name: Some("codex".to_string()),
+        from_state: "implementation".to_string(),
+        to_state: "ready_for_implementation".to_string(),
+        handed_back_at: "2026-01-01T00:00:00Z".to_string(),
+        cooldown_until: Some("2026-01-01T01:00:00Z".to_string()),
+    };
+    assert!(insert_handback(&conn, "K-1", &handback).expect("insert"));
+    assert!(!insert_handback(&conn, "K-1", &handback).expect("reinsert"));
+    let no_cooldown = HandbackRecord {
+        handback_id: "H-2".to_string(),
+        cooldown_until: None,
+        ..handback.clone()
+    };
+    assert!(insert_handback(&conn, "K-2", &no_cooldown).expect("insert"));
+    assert_eq!(list_handbacks(&conn, "K-1").expect("list"), vec![handback]);
+    assert_eq!(
+        list_handback_cooldowns(&conn, "codex").expect("cooldowns"),
+        vec![("K-1".to_string(), "2026-01-01T01:00:00Z".to_string())]
+    );
+    assert!(list_handback_cooldowns(&conn, "claude")
+        .expect("cooldowns")
+        .is_empty());
+    cleanup_db_files(&path);
+}
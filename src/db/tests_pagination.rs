@@ -35,9 +35,11 @@ fn insert_test_knots(conn: &rusqlite::Connection, count: usize) {
                 body: None,
                 description: None,
                 acceptance: None,
+                estimate: None,
                 priority: None,
                 knot_type,
                 tags: &[],
+                custom_fields: &Default::default(),
                 notes: &[],
                 handoff_capsules: &[],
                 invariants: &[],
@@ -51,6 +53,7 @@ fn insert_test_knots(conn: &rusqlite::Connection, count: usize) {
                 deferred_from_state: None,
                 blocked_from_state: None,
                 created_at: None,
+                snoozed_until: None,
             },
         )
         .expect("upsert should succeed");
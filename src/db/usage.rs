@@ -0,0 +1,61 @@
+use rusqlite::{params, Connection, Result};
+
+use super::with_write_retry;
+
+/// Rows kept in `usage_metric`; older invocations are dropped on insert.
+const USAGE_METRIC_RETAIN: i64 = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageMetricRecord {
+    pub command: String,
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub ok: bool,
+    pub sync_files: Option<u64>,
+}
+
+pub fn insert_usage_metric(conn: &Connection, record: &UsageMetricRecord) -> Result<()> {
+    with_write_retry(|| {
+        conn.execute(
+            r#"
+INSERT INTO usage_metric (command, started_at, duration_ms, ok, sync_files)
+VALUES (?1, ?2, ?3, ?4, ?5)
+"#,
+            params![
+                record.command,
+                record.started_at,
+                record.duration_ms as i64,
+                record.ok,
+                record.sync_files.map(|files| files as i64)
+            ],
+        )?;
+        conn.execute(
+            "DELETE FROM usage_metric WHERE id <= (SELECT MAX(id) FROM usage_metric) - ?1",
+            params![USAGE_METRIC_RETAIN],
+        )?;
+        Ok(())
+    })
+}
+
+/// Every recorded invocation, oldest first.
+pub fn list_usage_metrics(conn: &Connection) -> Result<Vec<UsageMetricRecord>> {
+    let mut stmt = conn.prepare(
+        r#"
+SELECT command, started_at, duration_ms, ok, sync_files
+FROM usage_metric
+ORDER BY id
+"#,
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(UsageMetricRecord {
+            command: row.get(0)?,
+            started_at: row.get(1)?,
+            duration_ms: row.get::<_, i64>(2)?.max(0) as u64,
+            ok: row.get(3)?,
+            sync_files: row
+                .get::<_, Option<i64>>(4)?
+                .map(|files| files.max(0) as u64),
+        })
+    })?;
+    rows.collect()
+}
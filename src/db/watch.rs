@@ -0,0 +1,111 @@
+use rusqlite::{params, params_from_iter, Connection, Result};
+use serde::Serialize;
+
+use super::{now_utc_rfc3339, with_write_retry};
+
+/// A change waiting in `kno inbox`, recorded locally during a pull.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct InboxItemRecord {
+    pub id: i64,
+    pub knot_id: String,
+    /// What put it in the inbox, e.g. `watch`.
+    pub kind: String,
+    pub summary: String,
+    pub created_at: String,
+}
+
+/// Returns whether the knot was not watched already.
+pub fn insert_watched_knot(conn: &Connection, knot_id: &str) -> Result<bool> {
+    with_write_retry(|| {
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO watched_knot (knot_id, created_at) VALUES (?1, ?2)",
+            params![knot_id, now_utc_rfc3339()],
+        )?;
+        Ok(inserted > 0)
+    })
+}
+
+/// Returns whether the knot was watched.
+pub fn delete_watched_knot(conn: &Connection, knot_id: &str) -> Result<bool> {
+    with_write_retry(|| {
+        let removed = conn.execute(
+            "DELETE FROM watched_knot WHERE knot_id = ?1",
+            params![knot_id],
+        )?;
+        Ok(removed > 0)
+    })
+}
+
+pub fn list_watched_knots(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT knot_id FROM watched_knot ORDER BY knot_id ASC")?;
+    let ids = stmt.query_map([], |row| row.get(0))?;
+    ids.collect()
+}
+
+pub fn insert_inbox_item(
+    conn: &Connection,
+    knot_id: &str,
+    kind: &str,
+    summary: &str,
+) -> Result<InboxItemRecord> {
+    let created_at = now_utc_rfc3339();
+    with_write_retry(|| {
+        conn.execute(
+            r#"
+INSERT INTO inbox_item (knot_id, kind, summary, created_at)
+VALUES (?1, ?2, ?3, ?4)
+"#,
+            params![knot_id, kind, summary, created_at],
+        )?;
+        Ok(InboxItemRecord {
+            id: conn.last_insert_rowid(),
+            knot_id: knot_id.to_string(),
+            kind: kind.to_string(),
+            summary: summary.to_string(),
+            created_at: created_at.clone(),
+        })
+    })
+}
+
+/// Unacknowledged items, oldest first.
+pub fn list_inbox_items(conn: &Connection) -> Result<Vec<InboxItemRecord>> {
+    let mut stmt = conn.prepare(
+        r#"
+SELECT id, knot_id, kind, summary, created_at
+FROM inbox_item
+WHERE acked_at IS NULL
+ORDER BY id ASC
+"#,
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(InboxItemRecord {
+            id: row.get(0)?,
+            knot_id: row.get(1)?,
+            kind: row.get(2)?,
+            summary: row.get(3)?,
+            created_at: row.get(4)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Acknowledges the given items, or every open item when `ids` is empty.
+/// Returns how many were acknowledged.
+pub fn ack_inbox_items(conn: &Connection, ids: &[i64]) -> Result<usize> {
+    let acked_at = now_utc_rfc3339();
+    with_write_retry(|| {
+        if ids.is_empty() {
+            return conn.execute(
+                "UPDATE inbox_item SET acked_at = ?1 WHERE acked_at IS NULL",
+                params![acked_at],
+            );
+        }
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let sql = format!(
+            "UPDATE inbox_item SET acked_at = ? WHERE acked_at IS NULL AND id IN ({placeholders})"
+        );
+        let values = std::iter::once(rusqlite::types::Value::Text(acked_at.clone()))
+            .chain(ids.iter().map(|id| rusqlite::types::Value::Integer(*id)));
+        conn.execute(&sql, params_from_iter(values))
+    })
+}
@@ -0,0 +1,389 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::app::AppError;
+use crate::cache_location::{self, CacheLocation};
+use crate::cli::{DbArgs, DbInfoArgs, DbLocation, DbMoveArgs, DbSubcommands, DbVacuumArgs};
+use crate::db::{self, CURRENT_SCHEMA_VERSION};
+use crate::locks::FileLock;
+use crate::project::{ProjectContext, StorePaths};
+use crate::status_commands::format_bytes;
+use crate::ui::Palette;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct DbInfoReport {
+    pub path: String,
+    /// `repo`, `xdg`, or `custom` (a `--db` path).
+    pub location: &'static str,
+    pub db_bytes: u64,
+    pub binary_version: String,
+    pub supported_schema_version: i64,
+    pub schema_version: Option<i64>,
+    /// kno version that last migrated this cache, if it recorded one.
+    pub writer_version: Option<String>,
+    /// `current`, `needs_migration`, or `newer_than_binary`.
+    pub compatibility: &'static str,
+    pub migrations: Vec<MigrationRow>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct MigrationRow {
+    pub version: i64,
+    pub name: String,
+    pub applied_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct DbVacuumReport {
+    pub path: String,
+    /// `PRAGMA integrity_check` findings; empty when the cache is sound.
+    pub integrity_problems: Vec<String>,
+    /// False when integrity problems stopped the run before compaction.
+    pub vacuumed: bool,
+    /// Database plus WAL file.
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+pub fn run_db(args: &DbArgs, context: &ProjectContext, db_path: &str) -> Result<(), AppError> {
+    match &args.command {
+        DbSubcommands::Info(info_args) => run_db_info(info_args, &context.store_paths, db_path),
+        DbSubcommands::Vacuum(vacuum_args) => run_db_vacuum(vacuum_args, context, db_path),
+        DbSubcommands::Move(move_args) => {
+            let target = db_move(&context.store_paths, db_path, move_args)?;
+            println!("moved cache database to {}", target.display());
+            Ok(())
+        }
+    }
+}
+
+/// Takes the repo and cache locks in the order writes do, so nothing
+/// commits while the cache file is rewritten or moved.
+fn acquire_write_locks(store_paths: &StorePaths) -> Result<(FileLock, FileLock), AppError> {
+    let timeout = Duration::from_millis(5_000);
+    let repo_guard = FileLock::acquire(&store_paths.repo_lock_path(), timeout)?;
+    let cache_guard = FileLock::acquire(&store_paths.cache_lock_path(), timeout)?;
+    Ok((repo_guard, cache_guard))
+}
+
+fn ensure_cache_exists(db_path: &str) -> Result<(), AppError> {
+    if Path::new(db_path).exists() {
+        return Ok(());
+    }
+    Err(AppError::InvalidArgument(format!(
+        "no knots cache at {db_path}; run `kno init` first"
+    )))
+}
+
+fn location_of(store_paths: &StorePaths, db_path: &Path) -> &'static str {
+    if db_path == store_paths.store_db_path() {
+        CacheLocation::Repo.as_str()
+    } else if store_paths.xdg_db_path().as_deref() == Some(db_path) {
+        CacheLocation::Xdg.as_str()
+    } else {
+        "custom"
+    }
+}
+
+/// Moves the cache database to `args.to`. Later commands find it there on
+/// their own, since the other location is then empty.
+pub(crate) fn db_move(
+    store_paths: &StorePaths,
+    db_path: &str,
+    args: &DbMoveArgs,
+) -> Result<PathBuf, AppError> {
+    ensure_cache_exists(db_path)?;
+    let source = Path::new(db_path);
+    let target = match args.to {
+        DbLocation::Repo => store_paths.store_db_path(),
+        DbLocation::Xdg => store_paths.xdg_db_path().ok_or_else(|| {
+            AppError::InvalidArgument(
+                "no XDG cache directory: set XDG_CACHE_HOME or HOME".to_string(),
+            )
+        })?,
+    };
+    if target == source {
+        return Ok(target);
+    }
+    if target.exists() {
+        return Err(AppError::InvalidArgument(format!(
+            "a cache already exists at {}; remove it first",
+            target.display()
+        )));
+    }
+    let _locks = acquire_write_locks(store_paths)?;
+    // Fold the WAL in so the database file alone carries everything.
+    db::checkpoint_truncate(&db::open_connection(db_path)?)?;
+    match args.to {
+        DbLocation::Xdg => cache_location::claim_xdg_dir(&target, &store_paths.root)?,
+        DbLocation::Repo => {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+    }
+    if std::fs::rename(source, &target).is_err() {
+        // Different filesystems: copy, then drop the original.
+        std::fs::copy(source, &target)?;
+        std::fs::remove_file(source)?;
+    }
+    for suffix in ["-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{db_path}{suffix}"));
+    }
+    cache_location::release_xdg_dir(source)?;
+    Ok(target)
+}
+
+fn run_db_vacuum(
+    args: &DbVacuumArgs,
+    context: &ProjectContext,
+    db_path: &str,
+) -> Result<(), AppError> {
+    let report = db_vacuum(context, db_path)?;
+    if args.json {
+        crate::print_json(&report);
+    } else {
+        for line in render_db_vacuum(&report) {
+            println!("{line}");
+        }
+    }
+    if report.integrity_problems.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::InvalidArgument(format!(
+            "cache failed integrity check with {} problem(s); not vacuumed",
+            report.integrity_problems.len()
+        )))
+    }
+}
+
+/// Holds the repo and cache locks, like a write, so nothing commits while
+/// the WAL is truncated and the file is rebuilt.
+pub(crate) fn db_vacuum(
+    context: &ProjectContext,
+    db_path: &str,
+) -> Result<DbVacuumReport, AppError> {
+    ensure_cache_exists(db_path)?;
+    let _locks = acquire_write_locks(&context.store_paths)?;
+    let conn = db::open_connection(db_path)?;
+    let bytes_before = disk_bytes(db_path);
+    let integrity_problems = db::integrity_problems(&conn)?;
+    let vacuumed = integrity_problems.is_empty();
+    if vacuumed {
+        db::checkpoint_truncate(&conn)?;
+        db::vacuum(&conn)?;
+        // VACUUM in WAL mode writes through the log; fold it back in.
+        db::checkpoint_truncate(&conn)?;
+    }
+    Ok(DbVacuumReport {
+        path: db_path.to_string(),
+        integrity_problems,
+        vacuumed,
+        bytes_before,
+        bytes_after: disk_bytes(db_path),
+    })
+}
+
+fn disk_bytes(db_path: &str) -> u64 {
+    [db_path.to_string(), format!("{db_path}-wal")]
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+pub(crate) fn render_db_vacuum(report: &DbVacuumReport) -> Vec<String> {
+    if !report.vacuumed {
+        let mut lines = vec![format!(
+            "integrity check failed for {}; not vacuumed:",
+            report.path
+        )];
+        lines.extend(
+            report
+                .integrity_problems
+                .iter()
+                .map(|problem| format!("  {problem}")),
+        );
+        return lines;
+    }
+    vec![
+        "integrity ok".to_string(),
+        format!(
+            "vacuumed {}: {} -> {}",
+            report.path,
+            format_bytes(report.bytes_before),
+            format_bytes(report.bytes_after)
+        ),
+    ]
+}
+
+fn run_db_info(args: &DbInfoArgs, store_paths: &StorePaths, db_path: &str) -> Result<(), AppError> {
+    let report = db_info(store_paths, db_path)?;
+    if args.json {
+        crate::print_json(&report);
+    } else {
+        for line in render_db_info(&report, &Palette::auto()) {
+            println!("{line}");
+        }
+    }
+    Ok(())
+}
+
+/// Reads the cache without migrating it, so `kno db info` still explains a
+/// cache that a newer kno wrote.
+pub(crate) fn db_info(store_paths: &StorePaths, db_path: &str) -> Result<DbInfoReport, AppError> {
+    ensure_cache_exists(db_path)?;
+    let conn = db::open_connection_raw(db_path)?;
+    let info = db::schema_info(&conn)?;
+    let compatibility = match info.effective_version() {
+        Some(version) if version > CURRENT_SCHEMA_VERSION => "newer_than_binary",
+        Some(version) if version == CURRENT_SCHEMA_VERSION => "current",
+        _ => "needs_migration",
+    };
+    Ok(DbInfoReport {
+        path: db_path.to_string(),
+        location: location_of(store_paths, Path::new(db_path)),
+        db_bytes: std::fs::metadata(db_path)
+            .map(|meta| meta.len())
+            .unwrap_or(0),
+        binary_version: env!("CARGO_PKG_VERSION").to_string(),
+        supported_schema_version: CURRENT_SCHEMA_VERSION,
+        schema_version: info.schema_version,
+        writer_version: info.writer_version,
+        compatibility,
+        migrations: info
+            .migrations
+            .into_iter()
+            .map(|migration| MigrationRow {
+                version: migration.version,
+                name: migration.name,
+                applied_at: migration.applied_at,
+            })
+            .collect(),
+    })
+}
+
+pub(crate) fn render_db_info(report: &DbInfoReport, palette: &Palette) -> Vec<String> {
+    let row =
+        |label: &str, value: String| format!("{} {value}", palette.label(&format!("{label:<7}")));
+    let schema = match report.schema_version {
+        Some(version) => version.to_string(),
+        None => "none".to_string(),
+    };
+    let status = match report.compatibility {
+        "current" => "ok".to_string(),
+        "newer_than_binary" => {
+            "written by a newer kno; upgrade with `kno upgrade` before using it".to_string()
+        }
+        _ => "older than this kno; the next command migrates it".to_string(),
+    };
+    let mut lines = vec![
+        row(
+            "path",
+            format!(
+                "{} ({}, {})",
+                report.path,
+                report.location,
+                format_bytes(report.db_bytes)
+            ),
+        ),
+        row(
+            "schema",
+            format!(
+                "{schema} (kno {} supports up to {})",
+                report.binary_version, report.supported_schema_version
+            ),
+        ),
+        row(
+            "writer",
+            report
+                .writer_version
+                .as_ref()
+                .map(|version| format!("kno {version}"))
+                .unwrap_or_else(|| "unknown".to_string()),
+        ),
+        row("status", status),
+        row(
+            "applied",
+            format!("{} migration(s)", report.migrations.len()),
+        ),
+    ];
+    lines.extend(report.migrations.iter().map(|migration| {
+        format!(
+            "  {:>3}  {:<32} {}",
+            migration.version, migration.name, migration.applied_at
+        )
+    }));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_db_info, render_db_vacuum, DbInfoReport, DbVacuumReport, MigrationRow};
+    use crate::ui::{Palette, Theme};
+
+    fn plain() -> Palette {
+        Palette {
+            enabled: false,
+            theme: Theme::Default,
+        }
+    }
+
+    #[test]
+    fn render_db_info_explains_a_cache_from_a_newer_binary() {
+        let report = DbInfoReport {
+            path: "/repo/.knots/cache/state.sqlite".to_string(),
+            location: "repo",
+            db_bytes: 4096,
+            binary_version: "0.14.0".to_string(),
+            supported_schema_version: 23,
+            schema_version: Some(24),
+            writer_version: Some("0.15.0".to_string()),
+            compatibility: "newer_than_binary",
+            migrations: vec![MigrationRow {
+                version: 1,
+                name: "base_schema_v1".to_string(),
+                applied_at: "2026-01-01T00:00:00Z".to_string(),
+            }],
+        };
+        let lines = render_db_info(&report, &plain());
+        assert_eq!(
+            lines,
+            vec![
+                "path    /repo/.knots/cache/state.sqlite (repo, 4.0 KiB)",
+                "schema  24 (kno 0.14.0 supports up to 23)",
+                "writer  kno 0.15.0",
+                "status  written by a newer kno; upgrade with `kno upgrade` before using it",
+                "applied 1 migration(s)",
+                "    1  base_schema_v1                   2026-01-01T00:00:00Z",
+            ]
+        );
+    }
+
+    #[test]
+    fn render_db_vacuum_reports_sizes_or_integrity_problems() {
+        let mut report = DbVacuumReport {
+            path: "state.sqlite".to_string(),
+            integrity_problems: Vec::new(),
+            vacuumed: true,
+            bytes_before: 3 * 1024 * 1024,
+            bytes_after: 1536,
+        };
+        assert_eq!(
+            render_db_vacuum(&report),
+            vec!["integrity ok", "vacuumed state.sqlite: 3.0 MiB -> 1.5 KiB"]
+        );
+
+        report.vacuumed = false;
+        report.integrity_problems = vec!["row 4 missing from index idx_knot_hot_state".to_string()];
+        assert_eq!(
+            render_db_vacuum(&report),
+            vec![
+                "integrity check failed for state.sqlite; not vacuumed:",
+                "  row 4 missing from index idx_knot_hot_state",
+            ]
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use crate::app::{self, DuplicatePair};
+use crate::cli::{DedupeArgs, DedupeReportArgs, DedupeSubcommands};
+use crate::knot_id::display_id;
+use crate::ui::Palette;
+
+pub fn run_dedupe(app: &app::App, args: DedupeArgs) -> Result<(), app::AppError> {
+    match args.command {
+        DedupeSubcommands::Report(args) => run_report(app, args),
+    }
+}
+
+fn run_report(app: &app::App, args: DedupeReportArgs) -> Result<(), app::AppError> {
+    if !(args.threshold > 0.0 && args.threshold <= 1.0) {
+        return Err(app::AppError::InvalidArgument(
+            "--threshold must be above 0 and at most 1".to_string(),
+        ));
+    }
+    let pairs = crate::trace::measure("dedupe report", || {
+        app.duplicate_pairs(args.threshold, args.all)
+    })?;
+    if args.json {
+        crate::print_json(&pairs);
+    } else {
+        print!("{}", render_pairs(&pairs, &Palette::auto()));
+    }
+    Ok(())
+}
+
+/// Each pair as its score followed by both knots, one per line.
+pub(crate) fn render_pairs(pairs: &[DuplicatePair], palette: &Palette) -> String {
+    if pairs.is_empty() {
+        return "no likely duplicates\n".to_string();
+    }
+    let mut out = String::new();
+    for pair in pairs {
+        out.push_str(&format!("{:.2}\n", pair.score));
+        for knot in [&pair.left, &pair.right] {
+            let id = knot.alias.as_deref().unwrap_or(display_id(&knot.id));
+            out.push_str(&format!(
+                "  {} {} {}\n",
+                palette.id(id),
+                palette.state(&knot.state),
+                knot.title
+            ));
+        }
+    }
+    out
+}
@@ -0,0 +1,177 @@
+use crate::app::{self, Changelog, Digest, DigestGrouping};
+use crate::cli::{ChangelogArgs, DigestArgs, DigestGroupBy, DigestSubcommands};
+
+pub fn run_digest(app: &app::App, args: DigestArgs) -> Result<(), app::AppError> {
+    if let Some(DigestSubcommands::Send(send)) = args.command {
+        return crate::digest_send::run_digest_send(app, send);
+    }
+    let grouping = match args.by {
+        DigestGroupBy::None => DigestGrouping::None,
+        DigestGroupBy::Actor => DigestGrouping::Actor,
+    };
+    let digest = crate::trace::measure("digest", || app.digest(&args.since, grouping))?;
+    if args.json {
+        crate::print_json(&digest);
+    } else {
+        print!("{}", render_digest(&digest));
+    }
+    Ok(())
+}
+
+pub fn run_changelog(app: &app::App, args: ChangelogArgs) -> Result<(), app::AppError> {
+    let changelog = crate::trace::measure("changelog", || {
+        app.changelog(&args.since, args.include_abandoned)
+    })?;
+    if args.json {
+        crate::print_json(&changelog);
+    } else {
+        print!("{}", render_changelog(&changelog));
+    }
+    Ok(())
+}
+
+/// Release-note markdown: one `###` section per kind, oldest entry first.
+pub fn render_changelog(changelog: &Changelog) -> String {
+    let mut out = format!(
+        "## Changes since {}
+",
+        changelog.since
+    );
+    if changelog.sections.is_empty() {
+        out.push_str("\nNo knots finished in this window.\n");
+        return out;
+    }
+    for section in &changelog.sections {
+        let heading = match section.kind.as_str() {
+            "feature" => "Features",
+            "bug" => "Bug fixes",
+            "chore" => "Chores",
+            _ => "Other",
+        };
+        out.push_str(&format!("\n### {heading}\n"));
+        for entry in &section.entries {
+            let id = crate::knot_id::display_id(&entry.id);
+            let state = if entry.state == "shipped" {
+                String::new()
+            } else {
+                format!(" ({})", entry.state)
+            };
+            out.push_str(&format!("- {} (`{id}`){state}\n", entry.title));
+        }
+    }
+    out
+}
+
+/// Markdown for pasting into a standup thread: one `##` heading per actor
+/// when grouped, then a bullet list per kind of activity.
+pub fn render_digest(digest: &Digest) -> String {
+    let mut out = format!("# Knots digest since {}\n", digest.since);
+    if digest.groups.is_empty() {
+        out.push_str("\nNo activity.\n");
+        return out;
+    }
+    for group in &digest.groups {
+        let level = match &group.actor {
+            Some(actor) => {
+                out.push_str(&format!("\n## {actor}\n"));
+                "###"
+            }
+            None => "##",
+        };
+        let sections = [
+            ("Created", &group.created),
+            ("Completed", &group.completed),
+            ("Blocked", &group.blocked),
+            ("State changes", &group.state_changes),
+        ];
+        for (heading, items) in sections {
+            if items.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n{level} {heading}\n"));
+            for item in items {
+                let id = crate::knot_id::display_id(&item.id);
+                let change = match (item.from.as_deref(), item.to.as_deref()) {
+                    (Some(from), Some(to)) => format!(" ({from} -> {to})"),
+                    (None, Some(to)) => format!(" ({to})"),
+                    _ => String::new(),
+                };
+                out.push_str(&format!("- `{id}` {}{change}\n", item.title));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_changelog, render_digest};
+    use crate::app::{
+        Changelog, ChangelogEntry, ChangelogSection, Digest, DigestGroup, DigestItem,
+    };
+
+    fn item(id: &str, title: &str, from: Option<&str>, to: &str) -> DigestItem {
+        DigestItem {
+            id: id.to_string(),
+            title: title.to_string(),
+            from: from.map(str::to_string),
+            to: Some(to.to_string()),
+            at: "2026-01-02T10:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn renders_actor_sections_as_markdown() {
+        let digest = Digest {
+            since: "2026-01-01T00:00:00Z".to_string(),
+            groups: vec![DigestGroup {
+                actor: Some("codex".to_string()),
+                created: vec![item("knots-a1", "Add login", None, "ready_for_planning")],
+                completed: vec![item("knots-b2", "Fix crash", Some("shipment"), "shipped")],
+                ..DigestGroup::default()
+            }],
+        };
+        assert_eq!(
+            render_digest(&digest),
+            "# Knots digest since 2026-01-01T00:00:00Z\n\
+             \n## codex\n\
+             \n### Created\n- `a1` Add login (ready_for_planning)\n\
+             \n### Completed\n- `b2` Fix crash (shipment -> shipped)\n"
+        );
+        let empty = Digest {
+            since: digest.since.clone(),
+            groups: Vec::new(),
+        };
+        assert!(render_digest(&empty).ends_with("\nNo activity.\n"));
+    }
+
+    #[test]
+    fn renders_changelog_sections_for_release_notes() {
+        let entry = |id: &str, title: &str, state: &str| ChangelogEntry {
+            id: id.to_string(),
+            title: title.to_string(),
+            state: state.to_string(),
+            at: "2026-01-02T10:00:00Z".to_string(),
+        };
+        let changelog = Changelog {
+            since: "v1.2.0".to_string(),
+            since_at: "2026-01-01T00:00:00Z".to_string(),
+            sections: vec![
+                ChangelogSection {
+                    kind: "bug".to_string(),
+                    entries: vec![entry("knots-a1", "Fix crash", "shipped")],
+                },
+                ChangelogSection {
+                    kind: "other".to_string(),
+                    entries: vec![entry("knots-b2", "Old idea", "abandoned")],
+                },
+            ],
+        };
+        assert_eq!(
+            render_changelog(&changelog),
+            "## Changes since v1.2.0\n\
+             \n### Bug fixes\n- Fix crash (`a1`)\n\
+             \n### Other\n- Old idea (`b2`) (abandoned)\n"
+        );
+    }
+}
@@ -0,0 +1,314 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::app::digest::{QueueReport, ReportKnot};
+use crate::app::{self, App};
+use crate::cli::DigestSendArgs;
+use crate::http_json::{self, quote_config_value};
+use crate::knot_id::display_id;
+use crate::repo_config::DigestTarget;
+
+const SMTP_URL_KEY: &str = "digest.smtp_url";
+const SMTP_FROM_KEY: &str = "digest.smtp_from";
+/// Environment variable holding `user:password` for `digest.smtp_url`.
+pub const SMTP_CREDENTIALS_ENV: &str = "KNOTS_SMTP_CREDENTIALS";
+
+/// One digest as `--json` prints it: the report and where it went.
+#[derive(Debug, Serialize)]
+struct SentDigest {
+    #[serde(flatten)]
+    report: QueueReport,
+    email: Vec<String>,
+    webhook: bool,
+    sent: bool,
+}
+
+/// The SMTP server and sender from this clone's settings.
+struct Mailer {
+    url: String,
+    from: String,
+    credentials: Option<String>,
+}
+
+pub fn run_digest_send(app: &App, args: DigestSendArgs) -> Result<(), app::AppError> {
+    let targets = select_targets(app.digest_targets()?, &args.names)?;
+    let mut results = Vec::new();
+    for (name, target) in targets {
+        if args.due && !app.digest_due(&name, &target)? {
+            continue;
+        }
+        let report = app.queue_report(&name, &target)?;
+        if !args.dry_run {
+            deliver(app, &report, &target)?;
+            app.record_digest_sent(&name, &report.generated_at)?;
+        }
+        results.push(SentDigest {
+            report,
+            email: target.email,
+            webhook: target.webhook.is_some(),
+            sent: !args.dry_run,
+        });
+    }
+    if args.json {
+        crate::print_json(&results);
+        return Ok(());
+    }
+    if results.is_empty() {
+        println!("no digests due");
+    }
+    for result in &results {
+        if args.dry_run {
+            println!("{}", render_recipients(result));
+            print!("{}", render_report(&result.report));
+        } else {
+            println!(
+                "sent {} {} ({} awaiting review, {} stale)",
+                result.report.name,
+                render_recipients(result),
+                result.report.review_queue.len(),
+                result.report.stale.len()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// The named digests, or every configured one when none are named.
+fn select_targets(
+    mut configured: BTreeMap<String, DigestTarget>,
+    names: &[String],
+) -> Result<Vec<(String, DigestTarget)>, app::AppError> {
+    if configured.is_empty() {
+        return Err(app::AppError::InvalidArgument(
+            "no digests configured; add a [digests.<name>] table to .knots/config.toml".to_string(),
+        ));
+    }
+    if names.is_empty() {
+        return Ok(configured.into_iter().collect());
+    }
+    names
+        .iter()
+        .map(|name| {
+            let target = configured.remove(name).ok_or_else(|| {
+                app::AppError::InvalidArgument(format!("unknown digest '{name}'"))
+            })?;
+            Ok((name.clone(), target))
+        })
+        .collect()
+}
+
+fn deliver(app: &App, report: &QueueReport, target: &DigestTarget) -> Result<(), app::AppError> {
+    if target.email.is_empty() && target.webhook.is_none() {
+        return Err(app::AppError::InvalidArgument(format!(
+            "digest '{}' has no email recipients or webhook",
+            report.name
+        )));
+    }
+    if let Some(recipient) = target
+        .email
+        .iter()
+        .find(|recipient| !is_email_address(recipient))
+    {
+        return Err(app::AppError::InvalidArgument(format!(
+            "digest '{}' lists '{recipient}', which is not an email address",
+            report.name
+        )));
+    }
+    let body = render_report(report);
+    if !target.email.is_empty() {
+        let mailer = mailer(app, &report.name)?;
+        let message = render_message(&mailer.from, &target.email, &subject(report), &body);
+        send_mail(&mailer, &target.email, &message).map_err(std::io::Error::other)?;
+    }
+    if let Some(webhook) = &target.webhook {
+        let payload = json!({ "text": body }).to_string();
+        http_json::post_json(webhook, None, &payload).map_err(std::io::Error::other)?;
+    }
+    Ok(())
+}
+
+/// The SMTP settings, which only count when set in this clone since the
+/// digest leaves the machine through them.
+fn mailer(app: &App, digest: &str) -> Result<Mailer, app::AppError> {
+    let url = app.config_get(SMTP_URL_KEY)?;
+    let from = app.config_get(SMTP_FROM_KEY)?;
+    if url.value.is_empty() || url.source != "local" || from.value.is_empty() {
+        return Err(app::AppError::InvalidArgument(format!(
+            "digest '{digest}' mails recipients but this clone has no SMTP server; \
+             run `kno config set {SMTP_URL_KEY} <smtps://host:port>` and \
+             `kno config set {SMTP_FROM_KEY} <address>`"
+        )));
+    }
+    Ok(Mailer {
+        url: url.value,
+        from: from.value,
+        credentials: std::env::var(SMTP_CREDENTIALS_ENV).ok(),
+    })
+}
+
+/// Sends through `curl`'s SMTP support. The message goes to a temporary
+/// file since curl reads its config, and so the credentials, from stdin.
+fn send_mail(mailer: &Mailer, recipients: &[String], message: &str) -> Result<(), String> {
+    let path = std::env::temp_dir().join(format!("knots-digest-{}.eml", uuid::Uuid::now_v7()));
+    std::fs::write(&path, message).map_err(|err| format!("could not stage mail: {err}"))?;
+    let mut config = format!(
+        "url = {}\nmail-from = {}\n",
+        quote_config_value(&mailer.url),
+        quote_config_value(&mailer.from)
+    );
+    for recipient in recipients {
+        config.push_str(&format!("mail-rcpt = {}\n", quote_config_value(recipient)));
+    }
+    if let Some(credentials) = mailer.credentials.as_deref().filter(|c| !c.is_empty()) {
+        config.push_str(&format!("user = {}\n", quote_config_value(credentials)));
+    }
+    config.push_str(&format!(
+        "upload-file = {}\n",
+        quote_config_value(&path.to_string_lossy())
+    ));
+    let result = http_json::run_curl(&config, &mailer.url).map(|_| ());
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+/// A bare `user@host` with nothing that could break a mail header.
+pub(crate) fn is_email_address(raw: &str) -> bool {
+    let Some((user, host)) = raw.split_once('@') else {
+        return false;
+    };
+    !user.is_empty()
+        && host.contains('.')
+        && !host.starts_with('.')
+        && !host.ends_with('.')
+        && !raw
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || "<>,;\"".contains(c))
+}
+
+fn subject(report: &QueueReport) -> String {
+    format!(
+        "Knots digest {}: {} awaiting review, {} stale",
+        report.name,
+        report.review_queue.len(),
+        report.stale.len()
+    )
+}
+
+fn render_message(from: &str, to: &[String], subject: &str, body: &str) -> String {
+    format!(
+        "From: {from}\r\nTo: {}\r\nSubject: {subject}\r\nMIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=utf-8\r\n\r\n{}",
+        to.join(", "),
+        body.replace('\n', "\r\n")
+    )
+}
+
+fn render_recipients(result: &SentDigest) -> String {
+    let mut parts = Vec::new();
+    if !result.email.is_empty() {
+        parts.push(result.email.join(", "));
+    }
+    if result.webhook {
+        parts.push("webhook".to_string());
+    }
+    if parts.is_empty() {
+        return "to nobody".to_string();
+    }
+    format!("to {}", parts.join(" and "))
+}
+
+/// Markdown that reads the same in a mail client and a chat webhook.
+pub(crate) fn render_report(report: &QueueReport) -> String {
+    let scope = if report.profiles.is_empty() {
+        "all profiles".to_string()
+    } else {
+        report.profiles.join(", ")
+    };
+    let mut out = format!(
+        "# Knots digest: {}\n\n{scope}, as of {}\n",
+        report.name, report.generated_at
+    );
+    let sections = [
+        ("Awaiting review", &report.review_queue),
+        ("Stale", &report.stale),
+    ];
+    for (heading, knots) in sections {
+        out.push_str(&format!("\n## {heading} ({})\n", knots.len()));
+        if knots.is_empty() {
+            out.push_str("None.\n");
+        }
+        for knot in knots {
+            out.push_str(&render_knot(knot));
+        }
+    }
+    out
+}
+
+fn render_knot(knot: &ReportKnot) -> String {
+    let budget = knot
+        .budget
+        .as_deref()
+        .map(|budget| format!(", budget {budget}"))
+        .unwrap_or_default();
+    format!(
+        "- `{}` {} [{}] for {}{budget}\n",
+        display_id(&knot.id),
+        knot.title,
+        knot.state,
+        knot.waited
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_email_address, render_message, render_report};
+    use crate::app::digest::{QueueReport, ReportKnot};
+
+    #[test]
+    fn renders_review_and_stale_sections() {
+        let knot = ReportKnot {
+            id: "knots-a1".to_string(),
+            title: "Add login".to_string(),
+            state: "ready_for_implementation_review".to_string(),
+            profile_id: "semiauto".to_string(),
+            waited: "2d 3h".to_string(),
+            waited_seconds: 183_600,
+            budget: Some("1d 0h".to_string()),
+        };
+        let report = QueueReport {
+            name: "reviewers".to_string(),
+            profiles: vec!["semiauto".to_string()],
+            generated_at: "2026-01-02T10:00:00Z".to_string(),
+            review_queue: vec![knot],
+            stale: Vec::new(),
+        };
+        assert_eq!(
+            render_report(&report),
+            "# Knots digest: reviewers\n\nsemiauto, as of 2026-01-02T10:00:00Z\n\
+             \n## Awaiting review (1)\n\
+             - `a1` Add login [ready_for_implementation_review] for 2d 3h, budget 1d 0h\n\
+             \n## Stale (0)\nNone.\n"
+        );
+    }
+
+    #[test]
+    fn mail_headers_use_crlf_and_addresses_reject_header_breaks() {
+        let message = render_message(
+            "knots@example.com",
+            &["a@example.com".to_string(), "b@example.com".to_string()],
+            "Knots digest",
+            "# Title\nbody\n",
+        );
+        assert!(
+            message.starts_with("From: knots@example.com\r\nTo: a@example.com, b@example.com\r\n")
+        );
+        assert!(message.ends_with("\r\n\r\n# Title\r\nbody\r\n"));
+        assert!(is_email_address("team@example.com"));
+        assert!(!is_email_address("team@example.com\r\nBcc: x@y.z"));
+        assert!(!is_email_address("team"));
+        assert!(!is_email_address("@example.com"));
+        assert!(!is_email_address("team@localhost"));
+    }
+}
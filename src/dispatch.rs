@@ -78,9 +78,11 @@ mod tests {
             body: None,
             description: None,
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: crate::domain::knot_type::KnotType::Work,
             tags: Vec::new(),
+            custom_fields: Default::default(),
             notes: Vec::new(),
             handoff_capsules: Vec::new(),
             invariants: Vec::new(),
@@ -96,10 +98,15 @@ mod tests {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: None,
+            snoozed_until: None,
             step_metadata: None,
             next_step_metadata: None,
             edges: Vec::new(),
             child_summaries: Vec::new(),
+            git_links: Vec::new(),
+            criteria: Vec::new(),
+            handbacks: Vec::new(),
+            plan: None,
         };
         assert_eq!(profile_lookup_id(&knot), "custom-wf/autopilot");
     }
@@ -115,9 +122,11 @@ mod tests {
             body: None,
             description: None,
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: crate::domain::knot_type::KnotType::Work,
             tags: Vec::new(),
+            custom_fields: Default::default(),
             notes: Vec::new(),
             handoff_capsules: Vec::new(),
             invariants: Vec::new(),
@@ -133,10 +142,15 @@ mod tests {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: None,
+            snoozed_until: None,
             step_metadata: None,
             next_step_metadata: None,
             edges: Vec::new(),
             child_summaries: Vec::new(),
+            git_links: Vec::new(),
+            criteria: Vec::new(),
+            handbacks: Vec::new(),
+            plan: None,
         };
         assert_eq!(profile_lookup_id(&knot), "default");
     }
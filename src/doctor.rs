@@ -92,13 +92,14 @@ pub fn run_doctor_at(
     let mut checks = vec![
         check_locks(&store_paths)?,
         check_worktree(repo_root, &store_paths, distribution),
-        check_remote(repo_root, distribution)?,
+        check_remote(repo_root, store_root, distribution)?,
         check_version(),
         check_hooks(repo_root, distribution),
         crate::doctor_workflows::check_registered_workflows(repo_root),
         check_schema_version(&store_paths)?,
         check_stuck_leases(&store_paths)?,
         check_terminal_parents(repo_root, &store_paths)?,
+        crate::doctor_edges::check_dangling_edges(&store_paths)?,
     ];
     checks.extend(crate::managed_skills::doctor_checks(repo_root));
     Ok(DoctorReport { checks })
@@ -131,6 +132,20 @@ pub fn run_doctor_with_fix_at(
     run_doctor_at(repo_root, store_root, distribution)
 }
 
+/// Runs the checks, applies the fixes `confirm` accepts, and re-runs them.
+pub fn run_doctor_with_confirm_at(
+    repo_root: &Path,
+    store_root: &Path,
+    distribution: DistributionMode,
+    confirm: impl FnMut(&DoctorCheck, &str) -> bool,
+) -> Result<DoctorReport, DoctorError> {
+    let report = run_doctor_at(repo_root, store_root, distribution)?;
+    if distribution == DistributionMode::Git {
+        crate::doctor_fix::apply_confirmed_fixes(repo_root, &report.checks, confirm);
+    }
+    run_doctor_at(repo_root, store_root, distribution)
+}
+
 fn check_locks(store_paths: &StorePaths) -> Result<DoctorCheck, DoctorError> {
     let repo_lock_path = store_paths.repo_lock_path();
     let cache_lock_path = store_paths.cache_lock_path();
@@ -210,6 +225,7 @@ mod doctor_workflows_tests;
 
 fn check_remote(
     repo_root: &Path,
+    store_root: &Path,
     distribution: DistributionMode,
 ) -> Result<DoctorCheck, DoctorError> {
     if distribution != DistributionMode::Git {
@@ -227,24 +243,25 @@ fn check_remote(
         });
     }
 
+    let (remote, branch) = crate::repo_config::knots_ref(store_root);
     let remote_url = Command::new("git")
         .arg("-C")
         .arg(repo_root)
-        .args(["remote", "get-url", "origin"])
+        .args(["remote", "get-url", &remote])
         .output()?;
 
     if !remote_url.status.success() {
         return Ok(DoctorCheck {
             name: "remote".to_string(),
             status: DoctorStatus::Fail,
-            detail: "remote 'origin' is not configured".to_string(),
+            detail: format!("remote '{remote}' is not configured"),
         });
     }
 
     let ls_remote = Command::new("git")
         .arg("-C")
         .arg(repo_root)
-        .args(["ls-remote", "--heads", "origin"])
+        .args(["ls-remote", "--heads", &remote])
         .output()?;
 
     if !ls_remote.status.success() {
@@ -252,25 +269,26 @@ fn check_remote(
             name: "remote".to_string(),
             status: DoctorStatus::Fail,
             detail: format!(
-                "origin is not reachable: {}",
+                "{remote} is not reachable: {}",
                 String::from_utf8_lossy(&ls_remote.stderr).trim()
             ),
         });
     }
 
+    let branch_ref = format!("refs/heads/{branch}");
     let knots_exists = String::from_utf8_lossy(&ls_remote.stdout)
         .lines()
-        .any(|line| line.contains("refs/heads/knots"));
+        .any(|line| line.split_whitespace().nth(1) == Some(branch_ref.as_str()));
 
     let (status, detail) = if knots_exists {
         (
             DoctorStatus::Pass,
-            "origin reachable; knots branch exists".to_string(),
+            format!("{remote} reachable; {branch} branch exists"),
         )
     } else {
         (
             DoctorStatus::Warn,
-            "origin reachable; knots branch missing (run `kno init`)".to_string(),
+            format!("{remote} reachable; {branch} branch missing (run `kno init`)"),
         )
     };
 
@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use crate::app::App;
+use crate::doctor::{DoctorCheck, DoctorError, DoctorStatus};
+use crate::project::StorePaths;
+
+pub const CHECK_NAME: &str = "dangling_edges";
+
+pub fn check_dangling_edges(store_paths: &StorePaths) -> Result<DoctorCheck, DoctorError> {
+    let db_path = store_paths.db_path();
+    if !db_path.exists() {
+        return Ok(DoctorCheck {
+            name: CHECK_NAME.to_string(),
+            status: DoctorStatus::Pass,
+            detail: "no cache database found".to_string(),
+        });
+    }
+    let conn = crate::db::open_connection(db_path.to_str().unwrap_or("cache/state.sqlite"))
+        .map_err(|err| DoctorError::Io(std::io::Error::other(err.to_string())))?;
+    let edges = crate::db::list_dangling_edges(&conn)
+        .map_err(|err| DoctorError::Io(std::io::Error::other(err.to_string())))?;
+    Ok(dangling_edges_check(edges.len()))
+}
+
+pub(crate) fn dangling_edges_check(count: usize) -> DoctorCheck {
+    if count == 0 {
+        return DoctorCheck {
+            name: CHECK_NAME.to_string(),
+            status: DoctorStatus::Pass,
+            detail: "every edge points at an existing knot".to_string(),
+        };
+    }
+    DoctorCheck {
+        name: CHECK_NAME.to_string(),
+        status: DoctorStatus::Warn,
+        detail: format!("{count} edge(s) point at knots that do not exist (run `kno lint`)"),
+    }
+}
+
+/// Removes dangling edges. Edges from an existing knot are removed with an
+/// event so the removal syncs; edges from a missing knot only live in the
+/// cache and are deleted there.
+pub(crate) fn fix_dangling_edges(repo_root: &Path) {
+    let db_path = repo_root.join(".knots").join("cache").join("state.sqlite");
+    let Some(db_path) = db_path.to_str() else {
+        return;
+    };
+    let Ok(conn) = crate::db::open_connection(db_path) else {
+        return;
+    };
+    let Ok(edges) = crate::db::list_dangling_edges(&conn) else {
+        return;
+    };
+    if edges.is_empty() {
+        return;
+    }
+    let Ok(app) = App::open(db_path, repo_root.to_path_buf()) else {
+        return;
+    };
+    for edge in edges {
+        let src_exists = crate::db::get_knot_hot(&conn, &edge.src)
+            .ok()
+            .flatten()
+            .is_some();
+        if !src_exists || app.remove_edge(&edge.src, &edge.kind, &edge.dst).is_err() {
+            let _ = crate::db::delete_edge(&conn, &edge.src, &edge.kind, &edge.dst);
+        }
+    }
+}
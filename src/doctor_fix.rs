@@ -1,3 +1,4 @@
+use std::io::{BufRead, Write};
 use std::path::Path;
 use std::process::Command;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -30,26 +31,126 @@ pub(crate) fn set_version_fix_applied_for_tests(applied: bool) {
 }
 
 pub(crate) fn apply_fixes(repo_root: &Path, checks: &[DoctorCheck]) {
+    apply_confirmed_fixes(repo_root, checks, |_, _| true);
+}
+
+/// Applies the fix for each non-pass check that `confirm` accepts. `confirm`
+/// sees the check and a description of the exact change.
+pub(crate) fn apply_confirmed_fixes(
+    repo_root: &Path,
+    checks: &[DoctorCheck],
+    mut confirm: impl FnMut(&DoctorCheck, &str) -> bool,
+) {
     set_version_fix_applied(false);
     for check in checks {
         if check.status == DoctorStatus::Pass {
             continue;
         }
+        let Some(change) = describe_fix(&check.name) else {
+            continue;
+        };
+        if confirm(check, &change) {
+            apply_fix(repo_root, &check.name);
+        }
+    }
+}
+
+/// What `kno doctor --fix` would change for check `name`, if it can fix it.
+pub(crate) fn describe_fix(name: &str) -> Option<String> {
+    let change = match name {
+        "lock_health" => "delete .knots/locks/repo.lock and .knots/cache/cache.lock",
+        "worktree" => {
+            "recreate .knots/_worktree if needed, then run `git reset --hard HEAD` \
+             and `git clean -fd` inside it"
+        }
+        "remote" => "create the knots branch on the remote",
+        "version" => "run `kno upgrade`",
+        "hooks" => "remove legacy git hooks and install the knots hooks",
+        "workflow_registry" => "re-register the built-in workflows",
+        "schema_version" => "reopen .knots/cache/state.sqlite to apply pending migrations",
+        "stuck_leases" => "terminate every active lease and unbind the knots it holds",
+        "terminal_parents" => {
+            "move parents whose children are all terminal into the matching terminal state"
+        }
+        crate::doctor_edges::CHECK_NAME => {
+            "remove edges whose source or target knot does not exist"
+        }
+        name if name.starts_with("skills_") => {
+            return Some(format!(
+                "reinstall the managed {} skills",
+                name.trim_start_matches("skills_")
+            ));
+        }
+        _ => return None,
+    };
+    Some(change.to_string())
+}
 
-        match check.name.as_str() {
-            "lock_health" => fix_lock_health(repo_root),
-            "worktree" => fix_worktree(repo_root),
-            "remote" => fix_remote(repo_root),
-            "version" => fix_version(),
-            "hooks" => fix_hooks(repo_root),
-            "workflow_registry" => fix_workflow_registry(repo_root),
-            "schema_version" => fix_schema_version(repo_root),
-            "stuck_leases" => fix_stuck_leases(repo_root),
-            "terminal_parents" => fix_terminal_parents(repo_root),
-            name if name.starts_with("skills_") => {
-                crate::managed_skills::fix_doctor_check(repo_root, name)
+fn apply_fix(repo_root: &Path, name: &str) {
+    match name {
+        "lock_health" => fix_lock_health(repo_root),
+        "worktree" => fix_worktree(repo_root),
+        "remote" => fix_remote(repo_root),
+        "version" => fix_version(),
+        "hooks" => fix_hooks(repo_root),
+        "workflow_registry" => fix_workflow_registry(repo_root),
+        "schema_version" => fix_schema_version(repo_root),
+        "stuck_leases" => fix_stuck_leases(repo_root),
+        "terminal_parents" => fix_terminal_parents(repo_root),
+        crate::doctor_edges::CHECK_NAME => crate::doctor_edges::fix_dangling_edges(repo_root),
+        name if name.starts_with("skills_") => {
+            crate::managed_skills::fix_doctor_check(repo_root, name)
+        }
+        _ => {}
+    }
+}
+
+/// Asks on `out` whether to apply each fix: `y` applies, `n` or Enter skips,
+/// `a` applies this and every remaining fix, `q` skips the rest.
+pub(crate) struct FixPrompt<R, W> {
+    input: R,
+    out: W,
+    all: bool,
+    quit: bool,
+}
+
+impl<R: BufRead, W: Write> FixPrompt<R, W> {
+    pub(crate) fn new(input: R, out: W) -> Self {
+        Self {
+            input,
+            out,
+            all: false,
+            quit: false,
+        }
+    }
+
+    pub(crate) fn confirm(&mut self, check: &DoctorCheck, change: &str) -> bool {
+        if self.all || self.quit {
+            return self.all;
+        }
+        let _ = writeln!(self.out, "{}: {}", check.name, check.detail);
+        let _ = writeln!(self.out, "  fix: {change}");
+        loop {
+            let _ = write!(self.out, "  apply? [y/N/a/q] ");
+            let _ = self.out.flush();
+            let mut answer = String::new();
+            if self.input.read_line(&mut answer).unwrap_or(0) == 0 {
+                self.quit = true;
+                return false;
+            }
+            match answer.trim().to_ascii_lowercase().as_str() {
+                "y" | "yes" => return true,
+                "" | "n" | "no" => return false,
+                "a" | "all" => {
+                    self.all = true;
+                    return true;
+                }
+                "q" | "quit" => {
+                    self.quit = true;
+                    return false;
+                }
+                _ => continue,
             }
-            _ => {}
         }
     }
 }
@@ -4,7 +4,8 @@ use std::process::Command;
 use uuid::Uuid;
 
 use super::{
-    apply_fixes, has_non_pass_checks, set_version_fix_applied_for_tests, version_fix_applied,
+    apply_confirmed_fixes, apply_fixes, describe_fix, has_non_pass_checks,
+    set_version_fix_applied_for_tests, version_fix_applied, FixPrompt,
 };
 use crate::doctor::{DoctorCheck, DoctorStatus};
 use crate::domain::knot_type::KnotType;
@@ -152,6 +153,69 @@ fn apply_fixes_removes_lock_files() {
     let _ = std::fs::remove_dir_all(root);
 }
 
+#[test]
+fn apply_confirmed_fixes_leaves_declined_fixes_alone() {
+    let root = unique_workspace();
+    let repo_lock = root.join(".knots/locks/repo.lock");
+    std::fs::create_dir_all(repo_lock.parent().expect("repo lock parent should exist"))
+        .expect("repo lock parent should be creatable");
+    std::fs::write(&repo_lock, "busy").expect("repo lock fixture should be writable");
+
+    let checks = vec![
+        sample_check("lock_health", DoctorStatus::Warn),
+        sample_check("unknown_check", DoctorStatus::Fail),
+        sample_check("hooks", DoctorStatus::Pass),
+    ];
+    let mut asked = Vec::new();
+    apply_confirmed_fixes(&root, &checks, |check, _| {
+        asked.push(check.name.clone());
+        false
+    });
+
+    assert_eq!(asked, vec!["lock_health".to_string()]);
+    assert!(repo_lock.exists());
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn describe_fix_covers_every_fixable_check() {
+    assert!(describe_fix("lock_health")
+        .expect("lock fix")
+        .contains("repo.lock"));
+    assert_eq!(
+        describe_fix("skills_codex").as_deref(),
+        Some("reinstall the managed codex skills")
+    );
+    assert_eq!(describe_fix("unknown_check"), None);
+}
+
+#[test]
+fn fix_prompt_handles_yes_no_all_and_quit() {
+    let check = sample_check("lock_health", DoctorStatus::Warn);
+    let answers = |input: &str| {
+        let mut out = Vec::new();
+        let mut prompt = FixPrompt::new(std::io::Cursor::new(input.to_string()), &mut out);
+        let decisions: Vec<bool> = (0..3).map(|_| prompt.confirm(&check, "do it")).collect();
+        drop(prompt);
+        (
+            decisions,
+            String::from_utf8(out).expect("prompt output is utf8"),
+        )
+    };
+
+    let (decisions, out) = answers("y\n\nN\n");
+    assert_eq!(decisions, vec![true, false, false]);
+    assert!(out.contains("  fix: do it"), "{out}");
+    assert_eq!(out.matches("apply? [y/N/a/q]").count(), 3);
+
+    let (decisions, out) = answers("maybe\na\n");
+    assert_eq!(decisions, vec![true, true, true]);
+    assert_eq!(out.matches("apply?").count(), 2);
+
+    assert_eq!(answers("q\ny\n").0, vec![false, false, false]);
+    assert_eq!(answers("y\n").0, vec![true, false, false]);
+}
+
 #[test]
 fn apply_fixes_recreates_non_git_worktree_directory() {
     let (root, local) = setup_repo_with_origin();
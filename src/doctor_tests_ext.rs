@@ -251,9 +251,11 @@ fn check_stuck_leases_warns_when_active() {
             body: None,
             description: None,
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: Some("lease"),
             tags: &[],
+            custom_fields: &Default::default(),
             notes: &[],
             handoff_capsules: &[],
             invariants: &[],
@@ -267,6 +269,7 @@ fn check_stuck_leases_warns_when_active() {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: None,
+            snoozed_until: None,
         },
     )
     .expect("upsert should succeed");
@@ -318,9 +321,11 @@ fn fix_stuck_leases_terminates_active() {
             body: None,
             description: None,
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: Some("lease"),
             tags: &[],
+            custom_fields: &Default::default(),
             notes: &[],
             handoff_capsules: &[],
             invariants: &[],
@@ -334,6 +339,7 @@ fn fix_stuck_leases_terminates_active() {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: None,
+            snoozed_until: None,
         },
     )
     .expect("upsert should succeed");
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+/// Value type of a custom field declared under `[fields.<name>]` in the
+/// repo config.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldKind {
+    #[default]
+    String,
+    Integer,
+    Boolean,
+    Enum,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FieldSpec {
+    #[serde(rename = "type", default)]
+    pub kind: FieldKind,
+    /// Allowed values of an `enum` field.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl FieldSpec {
+    /// Checks `raw` against the field type and returns the stored form.
+    pub fn normalize(&self, name: &str, raw: &str) -> Result<String, String> {
+        let value = raw.trim();
+        match self.kind {
+            FieldKind::String => Ok(value.to_string()),
+            FieldKind::Integer => value
+                .parse::<i64>()
+                .map(|number| number.to_string())
+                .map_err(|_| format!("field '{name}' expects an integer, got '{value}'")),
+            FieldKind::Boolean => match value.to_ascii_lowercase().as_str() {
+                "true" | "yes" | "1" => Ok("true".to_string()),
+                "false" | "no" | "0" => Ok("false".to_string()),
+                _ => Err(format!(
+                    "field '{name}' expects true or false, got '{value}'"
+                )),
+            },
+            FieldKind::Enum => self
+                .values
+                .iter()
+                .find(|allowed| allowed.eq_ignore_ascii_case(value))
+                .cloned()
+                .ok_or_else(|| {
+                    format!(
+                        "field '{name}' must be one of {}, got '{value}'",
+                        self.values.join(", ")
+                    )
+                }),
+        }
+    }
+}
+
+/// Splits a `key=value` flag. The key is lowercased; the value may be empty.
+pub fn parse_assignment(raw: &str) -> Result<(String, String), String> {
+    let (key, value) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("expected key=value, got '{raw}'"))?;
+    let key = key.trim().to_ascii_lowercase();
+    if key.is_empty() {
+        return Err(format!("expected key=value, got '{raw}'"));
+    }
+    Ok((key, value.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_assignment, FieldKind, FieldSpec};
+
+    #[test]
+    fn normalizes_values_by_kind() {
+        let integer = FieldSpec {
+            kind: FieldKind::Integer,
+            ..FieldSpec::default()
+        };
+        assert_eq!(integer.normalize("n", " 42 "), Ok("42".to_string()));
+        assert!(integer.normalize("n", "many").is_err());
+
+        let flag = FieldSpec {
+            kind: FieldKind::Boolean,
+            ..FieldSpec::default()
+        };
+        assert_eq!(flag.normalize("f", "Yes"), Ok("true".to_string()));
+        assert!(flag.normalize("f", "maybe").is_err());
+
+        let severity = FieldSpec {
+            kind: FieldKind::Enum,
+            values: vec!["low".to_string(), "high".to_string()],
+            description: None,
+        };
+        assert_eq!(
+            severity.normalize("severity", "HIGH"),
+            Ok("high".to_string())
+        );
+        let err = severity
+            .normalize("severity", "urgent")
+            .expect_err("not allowed");
+        assert!(err.contains("low, high"), "{err}");
+    }
+
+    #[test]
+    fn parses_key_value_assignments() {
+        assert_eq!(
+            parse_assignment("Customer = Acme Co"),
+            Ok(("customer".to_string(), "Acme Co".to_string()))
+        );
+        assert_eq!(
+            parse_assignment("customer="),
+            Ok(("customer".to_string(), String::new()))
+        );
+        assert!(parse_assignment("customer").is_err());
+        assert!(parse_assignment("=acme").is_err());
+    }
+}
@@ -0,0 +1,39 @@
+/// An edge destination of the form `repo-name:knot-id`, naming a knot in
+/// another registered workspace repo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoteKnotRef<'a> {
+    pub repo: &'a str,
+    pub id: &'a str,
+}
+
+impl<'a> RemoteKnotRef<'a> {
+    /// Returns `None` for plain local ids, which never contain `:`.
+    pub fn parse(dst: &'a str) -> Option<Self> {
+        let (repo, id) = dst.trim().split_once(':')?;
+        let (repo, id) = (repo.trim(), id.trim());
+        if repo.is_empty() || id.is_empty() || id.contains(':') {
+            return None;
+        }
+        Some(Self { repo, id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RemoteKnotRef;
+
+    #[test]
+    fn parses_qualified_refs_only() {
+        assert_eq!(
+            RemoteKnotRef::parse("backend:backend-a1b2"),
+            Some(RemoteKnotRef {
+                repo: "backend",
+                id: "backend-a1b2"
+            })
+        );
+        assert_eq!(RemoteKnotRef::parse("knots-a1b2"), None);
+        assert_eq!(RemoteKnotRef::parse(":knots-a1b2"), None);
+        assert_eq!(RemoteKnotRef::parse("backend:"), None);
+        assert_eq!(RemoteKnotRef::parse("a:b:c"), None);
+    }
+}
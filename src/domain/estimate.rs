@@ -0,0 +1,117 @@
+use std::fmt;
+
+const MINUTES_PER_HOUR: u64 = 60;
+const MINUTES_PER_DAY: u64 = 24 * MINUTES_PER_HOUR;
+const MINUTES_PER_WEEK: u64 = 7 * MINUTES_PER_DAY;
+
+/// How much work a knot is expected to take: story points (`3`, `3pt`) or
+/// a duration (`90m`, `2h`, `1d4h`, `1w`). Days and weeks are calendar
+/// time so they compare directly with measured cycle time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Estimate {
+    Points(u32),
+    Minutes(u64),
+}
+
+impl Estimate {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let value = raw.trim().to_ascii_lowercase();
+        let invalid = || {
+            format!(
+                "invalid estimate '{}'; use points like 3 or 3pt, or a duration like 90m, 2h, 1d4h",
+                raw.trim()
+            )
+        };
+        let points = ["points", "point", "pts", "pt"]
+            .iter()
+            .find_map(|suffix| value.strip_suffix(suffix))
+            .unwrap_or(&value)
+            .trim();
+        if let Ok(points) = points.parse::<u32>() {
+            return match points {
+                0 => Err(invalid()),
+                points => Ok(Self::Points(points)),
+            };
+        }
+
+        let mut minutes = 0u64;
+        let mut digits = String::new();
+        for ch in value.chars().filter(|ch| !ch.is_whitespace()) {
+            if ch.is_ascii_digit() {
+                digits.push(ch);
+                continue;
+            }
+            let unit = match ch {
+                'm' => 1,
+                'h' => MINUTES_PER_HOUR,
+                'd' => MINUTES_PER_DAY,
+                'w' => MINUTES_PER_WEEK,
+                _ => return Err(invalid()),
+            };
+            let amount = digits.parse::<u64>().map_err(|_| invalid())?;
+            minutes = amount
+                .checked_mul(unit)
+                .and_then(|part| minutes.checked_add(part))
+                .ok_or_else(invalid)?;
+            digits.clear();
+        }
+        if !digits.is_empty() || minutes == 0 {
+            return Err(invalid());
+        }
+        Ok(Self::Minutes(minutes))
+    }
+}
+
+/// Canonical form stored in events and the cache: `3pt`, `1d2h30m`.
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Points(points) => write!(f, "{points}pt"),
+            Self::Minutes(minutes) => f.write_str(&format_minutes(minutes)),
+        }
+    }
+}
+
+/// Formats a duration with day, hour, and minute parts, e.g. `1d2h30m`.
+pub fn format_minutes(minutes: u64) -> String {
+    let parts = [
+        (minutes / MINUTES_PER_DAY, "d"),
+        (minutes % MINUTES_PER_DAY / MINUTES_PER_HOUR, "h"),
+        (minutes % MINUTES_PER_HOUR, "m"),
+    ];
+    let formatted: String = parts
+        .iter()
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, unit)| format!("{amount}{unit}"))
+        .collect();
+    if formatted.is_empty() {
+        "0m".to_string()
+    } else {
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_minutes, Estimate};
+
+    #[test]
+    fn parses_points_and_durations() {
+        assert_eq!(Estimate::parse("3"), Ok(Estimate::Points(3)));
+        assert_eq!(Estimate::parse(" 5 PTS "), Ok(Estimate::Points(5)));
+        assert_eq!(Estimate::parse("90m"), Ok(Estimate::Minutes(90)));
+        assert_eq!(Estimate::parse("1d 4h"), Ok(Estimate::Minutes(28 * 60)));
+        assert_eq!(Estimate::parse("1w"), Ok(Estimate::Minutes(7 * 24 * 60)));
+        for invalid in ["", "0", "0h", "2x", "h", "3h5", "-1"] {
+            assert!(Estimate::parse(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn displays_canonical_form() {
+        assert_eq!(Estimate::Points(3).to_string(), "3pt");
+        assert_eq!(Estimate::parse("90m").expect("valid").to_string(), "1h30m");
+        assert_eq!(Estimate::parse("26h").expect("valid").to_string(), "1d2h");
+        assert_eq!(format_minutes(0), "0m");
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A team-defined event type declared under `[event_types."<type>"]` in the
+/// repo config, with the custom fields it sets from its payload.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EventTypeSpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Custom field name to the payload key it is set from; `a.b` reads a
+    /// nested object.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub set: BTreeMap<String, String>,
+}
+
+impl EventTypeSpec {
+    /// The field changes an event with `data` makes: a string, number, or
+    /// boolean sets the field, `null` clears it, and a missing key leaves it
+    /// alone. Objects and arrays are stored as their JSON text.
+    pub fn field_changes(&self, data: &Map<String, Value>) -> Vec<(String, Option<String>)> {
+        self.set
+            .iter()
+            .filter_map(|(field, key)| {
+                let value = payload_value(data, key)?;
+                let value = match value {
+                    Value::Null => None,
+                    Value::String(text) => Some(text.clone()),
+                    other => Some(other.to_string()),
+                };
+                Some((field.clone(), value))
+            })
+            .collect()
+    }
+}
+
+pub fn payload_value<'a>(data: &'a Map<String, Value>, key: &str) -> Option<&'a Value> {
+    let mut parts = key.split('.');
+    let first = data.get(parts.next()?)?;
+    parts.try_fold(first, |value, part| value.get(part))
+}
+
+/// Custom event types may not reuse the built-in `knot.` and `idx.`
+/// namespaces.
+pub fn validate_event_type_name(name: &str) -> Result<(), String> {
+    let valid_chars = name
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-'));
+    if name.is_empty() || !valid_chars || !name.contains('.') {
+        return Err(format!(
+            "event type '{name}' must look like 'team.something_happened'"
+        ));
+    }
+    if name.starts_with("knot.") || name.starts_with("idx.") {
+        return Err(format!(
+            "event type '{name}' uses a built-in namespace; pick another prefix"
+        ));
+    }
+    Ok(())
+}
+
+pub fn apply_field_changes(
+    fields: &mut BTreeMap<String, String>,
+    changes: &[(String, Option<String>)],
+) {
+    for (field, value) in changes {
+        match value {
+            Some(value) => fields.insert(field.clone(), value.clone()),
+            None => fields.remove(field),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::json;
+
+    use super::{apply_field_changes, validate_event_type_name, EventTypeSpec};
+
+    #[test]
+    fn projects_payload_keys_onto_fields() {
+        let spec = EventTypeSpec {
+            description: None,
+            set: BTreeMap::from([
+                ("build".to_string(), "status".to_string()),
+                ("runner".to_string(), "ci.host".to_string()),
+                ("attempts".to_string(), "attempts".to_string()),
+                ("owner".to_string(), "owner".to_string()),
+                ("missing".to_string(), "nowhere".to_string()),
+            ]),
+        };
+        let data = json!({
+            "status": "green", "ci": {"host": "gha"}, "attempts": 3, "owner": null
+        });
+        let changes = spec.field_changes(data.as_object().expect("object"));
+        assert_eq!(
+            changes,
+            vec![
+                ("attempts".to_string(), Some("3".to_string())),
+                ("build".to_string(), Some("green".to_string())),
+                ("owner".to_string(), None),
+                ("runner".to_string(), Some("gha".to_string())),
+            ]
+        );
+        let mut fields = BTreeMap::from([("owner".to_string(), "ana".to_string())]);
+        apply_field_changes(&mut fields, &changes);
+        assert!(!fields.contains_key("owner"));
+        assert_eq!(fields.get("runner").map(String::as_str), Some("gha"));
+    }
+
+    #[test]
+    fn custom_type_names_stay_out_of_builtin_namespaces() {
+        assert!(validate_event_type_name("ci.build_finished").is_ok());
+        assert!(validate_event_type_name("knot.build_finished").is_err());
+        assert!(validate_event_type_name("idx.head").is_err());
+        assert!(validate_event_type_name("finished").is_err());
+        assert!(validate_event_type_name("ci.build finished").is_err());
+    }
+}
@@ -0,0 +1,43 @@
+/// Handles mentioned as `@name` in `text`, lowercased and in order of first
+/// appearance. An `@` glued to a preceding word, as in an email address, is
+/// not a mention, and trailing punctuation is not part of the handle.
+pub fn mentions(text: &str) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    for (index, ch) in text.char_indices() {
+        let glued = previous.is_some_and(|p| p.is_alphanumeric() || is_handle_char(p));
+        previous = Some(ch);
+        if ch != '@' || glued {
+            continue;
+        }
+        let rest = &text[index + 1..];
+        let end = rest
+            .char_indices()
+            .find(|(_, c)| !is_handle_char(*c))
+            .map_or(rest.len(), |(end, _)| end);
+        let handle = rest[..end].trim_end_matches(['.', '-']).to_lowercase();
+        if !handle.is_empty() && !found.contains(&handle) {
+            found.push(handle);
+        }
+    }
+    found
+}
+
+fn is_handle_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mentions;
+
+    #[test]
+    fn finds_handles_but_not_emails_or_trailing_punctuation() {
+        assert_eq!(
+            mentions("@Ana can you check this? cc @bo.li, @ana."),
+            vec!["ana", "bo.li"]
+        );
+        assert!(mentions("mail ana@example.com or use @ alone").is_empty());
+        assert_eq!(mentions("(@dev-team) ping"), vec!["dev-team"]);
+    }
+}
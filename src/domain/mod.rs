@@ -1,7 +1,15 @@
+pub mod custom_field;
+pub mod edge_ref;
+pub mod estimate;
+pub mod event_type;
 pub mod gate;
 pub mod invariant;
 pub mod knot_type;
 pub mod lease;
+pub mod mention;
 pub mod metadata;
+pub mod secret;
+pub mod similarity;
 pub mod state;
 pub mod step_history;
+pub mod tag;
@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+/// Share of the score carried by titles when both knots have descriptions;
+/// titles alone decide otherwise.
+const TITLE_WEIGHT: f64 = 0.7;
+
+/// Trigram sets of a knot's title and description, built once so a knot
+/// can be compared against many others cheaply.
+#[derive(Debug, Clone, Default)]
+pub struct TextProfile {
+    title: HashSet<String>,
+    description: HashSet<String>,
+}
+
+impl TextProfile {
+    pub fn new(title: &str, description: Option<&str>) -> Self {
+        Self {
+            title: trigrams(title),
+            description: description.map(trigrams).unwrap_or_default(),
+        }
+    }
+
+    /// A score from 0 (nothing shared) to 1 (same words): the Dice
+    /// coefficient of the two titles' trigrams, blended with the
+    /// descriptions' when both knots have one.
+    pub fn similarity(&self, other: &TextProfile) -> f64 {
+        let title = dice(&self.title, &other.title);
+        if self.description.is_empty() || other.description.is_empty() {
+            return title;
+        }
+        TITLE_WEIGHT * title + (1.0 - TITLE_WEIGHT) * dice(&self.description, &other.description)
+    }
+
+    /// Share of this profile's trigrams, title and description together,
+    /// that also appear in `other`: how well a short query is covered by a
+    /// knot, however much else the knot says.
+    pub fn coverage(&self, other: &TextProfile) -> f64 {
+        let wanted: HashSet<&String> = self.title.union(&self.description).collect();
+        if wanted.is_empty() {
+            return 0.0;
+        }
+        let found = wanted
+            .iter()
+            .filter(|gram| other.title.contains(**gram) || other.description.contains(**gram))
+            .count();
+        found as f64 / wanted.len() as f64
+    }
+}
+
+/// Word trigrams in the style of PostgreSQL's `pg_trgm`: text is
+/// lowercased, split on anything but letters and digits, and each word is
+/// padded with two leading spaces and one trailing space, so short words
+/// and word starts still count.
+fn trigrams(text: &str) -> HashSet<String> {
+    let lowered = text.to_lowercase();
+    let mut grams = HashSet::new();
+    for word in lowered
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        let padded: Vec<char> = format!("  {word} ").chars().collect();
+        for window in padded.windows(3) {
+            grams.insert(window.iter().collect());
+        }
+    }
+    grams
+}
+
+fn dice(left: &HashSet<String>, right: &HashSet<String>) -> f64 {
+    if left.is_empty() || right.is_empty() {
+        return 0.0;
+    }
+    let shared = left.intersection(right).count();
+    (2 * shared) as f64 / (left.len() + right.len()) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextProfile;
+
+    fn score(left: &str, right: &str) -> f64 {
+        TextProfile::new(left, None).similarity(&TextProfile::new(right, None))
+    }
+
+    #[test]
+    fn near_duplicate_titles_score_high_and_unrelated_ones_low() {
+        assert_eq!(score("Fix login timeout", "fix LOGIN timeout!"), 1.0);
+        assert!(score("Fix login timeout", "Fix the login timeouts") > 0.6);
+        assert!(score("Fix login timeout", "Render release notes") < 0.2);
+        assert_eq!(score("", "anything"), 0.0);
+    }
+
+    #[test]
+    fn descriptions_only_count_when_both_knots_have_one() {
+        let bare = TextProfile::new("Cache warmup", None);
+        let described = TextProfile::new("Cache warmup", Some("preload hot rows"));
+        assert_eq!(bare.similarity(&described), 1.0);
+        let unrelated = TextProfile::new("Cache warmup", Some("rename the binary"));
+        let score = described.similarity(&unrelated);
+        assert!(score > 0.7 && score < 0.8, "{score}");
+    }
+
+    #[test]
+    fn coverage_scores_short_queries_against_long_knots() {
+        let query = TextProfile::new("flaky auth", None);
+        let knot = TextProfile::new("Retry", Some("the auth tests are flaky on CI"));
+        assert_eq!(query.coverage(&knot), 1.0);
+        assert!(knot.coverage(&query) < 0.5);
+        assert_eq!(TextProfile::new("", None).coverage(&knot), 0.0);
+    }
+}
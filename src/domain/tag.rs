@@ -0,0 +1,71 @@
+/// Separator between a tag's namespace and its name, as in `area/backend`.
+pub const NAMESPACE_SEPARATOR: char = '/';
+
+/// The namespace of `tag`: everything before the last `/`, or `None` for a
+/// plain tag.
+pub fn namespace(tag: &str) -> Option<&str> {
+    tag.rsplit_once(NAMESPACE_SEPARATOR)
+        .map(|(namespace, _)| namespace)
+        .filter(|namespace| !namespace.is_empty())
+}
+
+/// Whether `tag` satisfies a list filter. `area/*` matches every tag under
+/// the `area` namespace, nested ones included; anything else must match
+/// exactly. Both sides are expected to be normalized already.
+pub fn matches_filter(tag: &str, filter: &str) -> bool {
+    match filter.strip_suffix("/*") {
+        Some(prefix) => tag
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with(NAMESPACE_SEPARATOR) && rest.len() > 1),
+        None => tag == filter,
+    }
+}
+
+/// Whether namespaced `tag` falls under one of `allowed`. Plain tags are
+/// always allowed.
+pub fn namespace_allowed(tag: &str, allowed: &[String]) -> bool {
+    let Some(namespace) = namespace(tag) else {
+        return true;
+    };
+    allowed.iter().any(|allowed| {
+        let allowed = allowed.trim().trim_end_matches(NAMESPACE_SEPARATOR);
+        namespace == allowed
+            || namespace
+                .strip_prefix(allowed)
+                .is_some_and(|rest| rest.starts_with(NAMESPACE_SEPARATOR))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{matches_filter, namespace, namespace_allowed};
+
+    #[test]
+    fn splits_namespace_at_last_separator() {
+        assert_eq!(namespace("area/backend"), Some("area"));
+        assert_eq!(namespace("area/backend/api"), Some("area/backend"));
+        assert_eq!(namespace("release"), None);
+        assert_eq!(namespace("/odd"), None);
+    }
+
+    #[test]
+    fn prefix_filters_match_whole_namespaces() {
+        assert!(matches_filter("area/backend", "area/*"));
+        assert!(matches_filter("area/backend/api", "area/*"));
+        assert!(!matches_filter("areas/backend", "area/*"));
+        assert!(!matches_filter("area", "area/*"));
+        assert!(matches_filter("area/backend", "area/backend"));
+        assert!(!matches_filter("area/backend", "area"));
+    }
+
+    #[test]
+    fn validates_against_declared_namespaces() {
+        let allowed = vec!["area".to_string(), "team/".to_string()];
+        assert!(namespace_allowed("release", &allowed));
+        assert!(namespace_allowed("area/backend", &allowed));
+        assert!(namespace_allowed("area/backend/api", &allowed));
+        assert!(namespace_allowed("team/core", &allowed));
+        assert!(!namespace_allowed("customer/acme", &allowed));
+        assert!(!namespace_allowed("areas/x", &allowed));
+    }
+}
@@ -0,0 +1,47 @@
+use std::io::Read;
+
+use crate::app::{self, App, EdgePlan};
+use crate::cli::EdgeApplyArgs;
+use crate::print_json;
+
+pub fn run_edge_apply(app: &App, args: EdgeApplyArgs) -> Result<(), app::AppError> {
+    let text = if args.file == "-" {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        text
+    } else {
+        std::fs::read_to_string(&args.file)?
+    };
+    let plan = app.apply_edge_file(&text, args.dry_run)?;
+    if args.json {
+        print_json(&plan);
+    } else {
+        print!("{}", render_edge_plan(&plan));
+    }
+    Ok(())
+}
+
+pub(crate) fn render_edge_plan(plan: &EdgePlan) -> String {
+    let mut out = String::new();
+    for change in &plan.changes {
+        let sign = if change.op == "add" { '+' } else { '-' };
+        out.push_str(&format!(
+            "{sign} {} -[{}]-> {}\n",
+            change.src, change.kind, change.dst
+        ));
+    }
+    let count = plan.changes.len();
+    let noun = if count == 1 { "change" } else { "changes" };
+    if plan.applied {
+        out.push_str(&format!(
+            "applied {count} edge {noun} ({} unchanged)\n",
+            plan.unchanged
+        ));
+    } else {
+        out.push_str(&format!(
+            "dry run: {count} edge {noun} ({} unchanged); nothing written\n",
+            plan.unchanged
+        ));
+    }
+    out
+}
@@ -0,0 +1,73 @@
+use std::io::{Read, Write};
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::app::{self, App};
+use crate::cli::{EventsArgs, EventsEmitArgs, EventsSubcommands, EventsTailArgs};
+use crate::events::FullEvent;
+use crate::knot_id::display_id;
+
+pub fn run_events(app: &App, args: EventsArgs) -> Result<(), app::AppError> {
+    match args.command {
+        EventsSubcommands::Tail(tail) => run_tail(app, tail),
+        EventsSubcommands::Emit(emit) => run_emit(app, emit),
+    }
+}
+
+fn run_emit(app: &App, args: EventsEmitArgs) -> Result<(), app::AppError> {
+    let raw = read_data_arg(&args.data)?;
+    let data: Value = serde_json::from_str(&raw)
+        .map_err(|err| app::AppError::InvalidArgument(format!("--data is not JSON: {err}")))?;
+    let knot = app.emit_event(&args.event_type, &args.knot, &data)?;
+    if args.json {
+        crate::print_json(&knot);
+    } else {
+        println!(
+            "emitted {} on {}",
+            args.event_type,
+            knot.alias.as_deref().unwrap_or(display_id(&knot.id))
+        );
+    }
+    Ok(())
+}
+
+/// `@-` reads stdin and `@path` reads a file, as curl does; anything else
+/// is the JSON itself.
+fn read_data_arg(arg: &str) -> Result<String, app::AppError> {
+    match arg.strip_prefix('@') {
+        Some("-") => {
+            let mut raw = String::new();
+            std::io::stdin().read_to_string(&mut raw)?;
+            Ok(raw)
+        }
+        Some(path) => Ok(std::fs::read_to_string(path)?),
+        None => Ok(arg.to_string()),
+    }
+}
+
+/// One compact JSON object per line, so the stream pipes into `jq` and
+/// friends. Following stops quietly when the reader goes away.
+fn run_tail(app: &App, args: EventsTailArgs) -> Result<(), app::AppError> {
+    let mut tail = app.event_tail();
+    let existing = tail.poll()?;
+    let skip = existing.len().saturating_sub(args.lines);
+    let mut out = std::io::stdout().lock();
+    if write_events(&mut out, &existing[skip..]).is_err() || !args.follow {
+        return Ok(());
+    }
+    loop {
+        std::thread::sleep(Duration::from_millis(args.interval_ms));
+        if write_events(&mut out, &tail.poll()?).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+fn write_events(out: &mut impl Write, events: &[FullEvent]) -> std::io::Result<()> {
+    for event in events {
+        let line = serde_json::to_string(event).map_err(std::io::Error::other)?;
+        writeln!(out, "{line}")?;
+    }
+    out.flush()
+}
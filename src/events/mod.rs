@@ -1,9 +1,19 @@
 #![allow(dead_code)]
 
 mod error;
+mod private;
+mod redact;
 
 pub use error::EventWriteError;
-
+pub use private::{
+    count_private_event_files, private_event_files, private_root, publish_private_events,
+    read_private_registry, register_private_knot, unregister_private_knot,
+};
+pub use redact::redact_event_files;
+pub(crate) use redact::redact_value;
+
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -14,6 +24,8 @@ use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+use crate::trace::TimingCategory;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EventStream {
     Full,
@@ -37,6 +49,9 @@ pub enum FullEventKind {
     KnotBodySet,
     KnotDescriptionSet,
     KnotAcceptanceSet,
+    KnotEstimateSet,
+    KnotFieldSet,
+    KnotSnoozeSet,
     KnotStateSet,
     KnotPrioritySet,
     KnotTypeSet,
@@ -53,6 +68,13 @@ pub enum FullEventKind {
     KnotReviewDecision,
     KnotLeaseDataSet,
     KnotLeaseIdSet,
+    KnotGitLinkAdded,
+    KnotCriterionAdded,
+    KnotCriterionChecked,
+    KnotHandback,
+    KnotPlanSet,
+    KnotRedacted,
+    KnotImported,
 }
 
 impl FullEventKind {
@@ -63,6 +85,9 @@ impl FullEventKind {
             FullEventKind::KnotBodySet => "knot.body_set",
             FullEventKind::KnotDescriptionSet => "knot.description_set",
             FullEventKind::KnotAcceptanceSet => "knot.acceptance_set",
+            FullEventKind::KnotEstimateSet => "knot.estimate_set",
+            FullEventKind::KnotFieldSet => "knot.field_set",
+            FullEventKind::KnotSnoozeSet => "knot.snooze_set",
             FullEventKind::KnotStateSet => "knot.state_set",
             FullEventKind::KnotPrioritySet => "knot.priority_set",
             FullEventKind::KnotTypeSet => "knot.type_set",
@@ -79,6 +104,13 @@ impl FullEventKind {
             FullEventKind::KnotReviewDecision => "knot.review_decision",
             FullEventKind::KnotLeaseDataSet => "knot.lease_data_set",
             FullEventKind::KnotLeaseIdSet => "knot.lease_id_set",
+            FullEventKind::KnotGitLinkAdded => "knot.git_link_added",
+            FullEventKind::KnotCriterionAdded => "knot.criterion_added",
+            FullEventKind::KnotCriterionChecked => "knot.criterion_checked",
+            FullEventKind::KnotHandback => "knot.handback",
+            FullEventKind::KnotPlanSet => "knot.plan_set",
+            FullEventKind::KnotRedacted => "knot.redacted",
+            FullEventKind::KnotImported => "knot.imported",
         }
     }
 }
@@ -231,6 +263,15 @@ impl EventRecord {
         }
     }
 
+    /// The knot an event belongs to: the `knot_id` of a full event, or the
+    /// `knot_id` in an index event's data.
+    pub fn knot_id(&self) -> Option<&str> {
+        match self {
+            EventRecord::Full(event) => Some(&event.knot_id),
+            EventRecord::Index(event) => event.data.get("knot_id").and_then(Value::as_str),
+        }
+    }
+
     pub fn event_type(&self) -> &str {
         match self {
             EventRecord::Full(event) => &event.event_type,
@@ -242,23 +283,79 @@ impl EventRecord {
 #[derive(Debug, Clone)]
 pub struct EventWriter {
     store_root: PathBuf,
+    private_knots: RefCell<HashSet<String>>,
+    /// Files written since `begin_batch`, synced together by `flush_batch`.
+    unsynced: RefCell<Option<Vec<PathBuf>>>,
 }
 
 impl EventWriter {
     pub fn new(store_root: impl Into<PathBuf>) -> Self {
         Self {
             store_root: store_root.into(),
+            private_knots: RefCell::default(),
+            unsynced: RefCell::default(),
+        }
+    }
+
+    /// Defers the per-file fsync until `flush_batch`, for bulk writers that
+    /// record their own progress only after flushing.
+    pub fn begin_batch(&self) {
+        self.unsynced.borrow_mut().get_or_insert_with(Vec::new);
+    }
+
+    /// Syncs every file written since the batch began; the batch stays open
+    /// unless `end` is set.
+    pub fn flush_batch(&self, end: bool) -> Result<usize, EventWriteError> {
+        let pending = if end {
+            self.unsynced.take().unwrap_or_default()
+        } else {
+            self.unsynced
+                .borrow_mut()
+                .as_mut()
+                .map(std::mem::take)
+                .unwrap_or_default()
+        };
+        for path in &pending {
+            fs::File::open(path)?.sync_all()?;
+        }
+        Ok(pending.len())
+    }
+
+    /// Routes events for `knot_ids` to the store's private directory, which
+    /// push never publishes.
+    pub fn with_private_knots(self, knot_ids: HashSet<String>) -> Self {
+        self.private_knots.replace(knot_ids);
+        self
+    }
+
+    pub fn set_private(&self, knot_id: &str, private: bool) {
+        let mut knots = self.private_knots.borrow_mut();
+        if private {
+            knots.insert(knot_id.to_string());
+        } else {
+            knots.remove(knot_id);
         }
     }
 
     pub fn write(&self, event: &EventRecord) -> Result<PathBuf, EventWriteError> {
+        crate::trace::timed(TimingCategory::EventWrite, || self.write_event(event))
+    }
+
+    fn write_event(&self, event: &EventRecord) -> Result<PathBuf, EventWriteError> {
         let rel_path = relative_path_for_event(
             event.stream(),
             event.occurred_at(),
             event.event_id(),
             event.event_type(),
         )?;
-        let abs_path = self.store_root.join(&rel_path);
+        let private = event
+            .knot_id()
+            .is_some_and(|id| self.private_knots.borrow().contains(id));
+        let abs_path = if private {
+            private_root(&self.store_root).join(&rel_path)
+        } else {
+            self.store_root.join(&rel_path)
+        };
         if let Some(parent) = abs_path.parent() {
             fs::create_dir_all(parent)?;
         }
@@ -269,7 +366,10 @@ impl EventWriter {
             .open(&abs_path)?;
         serde_json::to_writer_pretty(&mut file, event)?;
         file.write_all(b"\n")?;
-        file.sync_all()?;
+        match self.unsynced.borrow_mut().as_mut() {
+            Some(unsynced) => unsynced.push(abs_path),
+            None => file.sync_all()?,
+        }
 
         Ok(rel_path)
     }
@@ -325,137 +425,7 @@ fn validate_filename_component(field: &'static str, value: &str) -> Result<(), E
 }
 
 #[cfg(test)]
-mod tests {
-    use super::{
-        relative_path_for_event, EventRecord, EventStream, EventWriter, FullEvent, FullEventKind,
-        IndexEvent, IndexEventKind,
-    };
-    use serde_json::json;
-    use std::path::PathBuf;
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    fn unique_tmp_dir() -> PathBuf {
-        let nanos = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("system clock before UNIX_EPOCH")
-            .as_nanos();
-        std::env::temp_dir().join(format!("knots-events-{}", nanos))
-    }
-
-    #[test]
-    fn builds_deterministic_full_event_path() {
-        let path = relative_path_for_event(
-            EventStream::Full,
-            "2026-02-22T17:00:00Z",
-            "018f4f7f-7dc7-7f4e-954b-64f8a2273ec8",
-            FullEventKind::KnotStateSet.as_str(),
-        )
-        .expect("path should build");
-        assert_eq!(
-            path.to_string_lossy(),
-            "events/2026/02/22/018f4f7f-7dc7-7f4e-954b-64f8a2273ec8-knot.state_set.json"
-        );
-    }
-
-    #[test]
-    fn builds_deterministic_index_event_path() {
-        let path = relative_path_for_event(
-            EventStream::Index,
-            "2026-02-22T17:00:00Z",
-            "018f4f7f-7dc7-7f4e-954b-64f8a2273ec8",
-            IndexEventKind::KnotHead.as_str(),
-        )
-        .expect("path should build");
-        assert_eq!(
-            path.to_string_lossy(),
-            "index/2026/02/22/018f4f7f-7dc7-7f4e-954b-64f8a2273ec8-idx.knot_head.json"
-        );
-    }
-
-    #[test]
-    fn acceptance_event_kind_uses_expected_string() {
-        assert_eq!(
-            FullEventKind::KnotAcceptanceSet.as_str(),
-            "knot.acceptance_set"
-        );
-    }
-
-    #[test]
-    fn writes_append_only_full_event_file() {
-        let root = unique_tmp_dir();
-        let writer = EventWriter::new(&root);
-        let event = EventRecord::full(FullEvent::with_identity(
-            "018f4f7f-7dc7-7f4e-954b-64f8a2273ec8",
-            "2026-02-22T17:00:00Z",
-            "K-123",
-            FullEventKind::KnotCreated.as_str(),
-            json!({"title":"Build cache"}),
-        ));
-
-        let relative = writer.write(&event).expect("first write should succeed");
-        assert_eq!(
-            relative.to_string_lossy(),
-            "events/2026/02/22/018f4f7f-7dc7-7f4e-954b-64f8a2273ec8-knot.created.json"
-        );
-
-        let absolute = root.join(&relative);
-        let saved: serde_json::Value = serde_json::from_slice(
-            &std::fs::read(&absolute).expect("event JSON file should be readable"),
-        )
-        .expect("event JSON should parse");
-
-        assert_eq!(saved["type"], "knot.created");
-        assert_eq!(saved["knot_id"], "K-123");
-
-        let second_write = writer.write(&event);
-        assert!(second_write.is_err());
-        if let Err(err) = second_write {
-            assert!(
-                err.to_string().contains("I/O error"),
-                "expected create_new collision, got: {}",
-                err
-            );
-        }
-
-        let _ = std::fs::remove_dir_all(root);
-    }
-
-    #[test]
-    fn rejects_invalid_file_component() {
-        let result = relative_path_for_event(
-            EventStream::Full,
-            "2026-02-22T17:00:00Z",
-            "bad/id",
-            "knot.created",
-        );
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn writes_index_event() {
-        let root = unique_tmp_dir();
-        let writer = EventWriter::new(&root);
-        let event = EventRecord::index(IndexEvent::with_identity(
-            "018f4f7f-7dc7-7f4e-954b-64f8a2273ec8",
-            "2026-02-22T17:00:00Z",
-            IndexEventKind::KnotHead.as_str(),
-            json!({
-                "knot_id":"K-123",
-                "title":"Build cache",
-                "state":"implementing",
-                "updated_at":"2026-02-22T17:00:00Z"
-            }),
-        ));
-
-        let relative = writer.write(&event).expect("index write should succeed");
-        assert_eq!(
-            relative.to_string_lossy(),
-            "index/2026/02/22/018f4f7f-7dc7-7f4e-954b-64f8a2273ec8-idx.knot_head.json"
-        );
-
-        let _ = std::fs::remove_dir_all(root);
-    }
-}
+mod tests;
 
 #[cfg(test)]
 #[path = "tests_ext.rs"]
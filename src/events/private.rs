@@ -0,0 +1,202 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::{relative_path_for_event, EventRecord, EventStream, EventWriteError};
+
+const PRIVATE_DIR: &str = "private";
+/// Which knots are private, and which of those are drafts. The cache's
+/// `private_knot` table is rebuilt from this file.
+const REGISTRY_FILE: &str = "knots.json";
+
+/// Where a store keeps events for private knots. It sits beside `events/`
+/// and `index/`, so push, which only copies those, never sees it.
+pub fn private_root(store_root: &Path) -> PathBuf {
+    store_root.join(PRIVATE_DIR)
+}
+
+/// Every registered private knot, mapped to whether it is a draft.
+pub fn read_private_registry(store_root: &Path) -> Result<BTreeMap<String, bool>, EventWriteError> {
+    let path = private_root(store_root).join(REGISTRY_FILE);
+    if !path.is_file() {
+        return Ok(BTreeMap::new());
+    }
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+/// Records `knot_id` as private (and a draft or not) in the registry.
+pub fn register_private_knot(
+    store_root: &Path,
+    knot_id: &str,
+    draft: bool,
+) -> Result<(), EventWriteError> {
+    let mut registry = read_private_registry(store_root)?;
+    registry.insert(knot_id.to_string(), draft);
+    write_registry(store_root, &registry)
+}
+
+/// Drops `knot_id` from the registry, removing the file once it is empty.
+pub fn unregister_private_knot(store_root: &Path, knot_id: &str) -> Result<(), EventWriteError> {
+    let mut registry = read_private_registry(store_root)?;
+    if registry.remove(knot_id).is_none() {
+        return Ok(());
+    }
+    write_registry(store_root, &registry)
+}
+
+fn write_registry(
+    store_root: &Path,
+    registry: &BTreeMap<String, bool>,
+) -> Result<(), EventWriteError> {
+    let path = private_root(store_root).join(REGISTRY_FILE);
+    if registry.is_empty() {
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    fs::create_dir_all(private_root(store_root))?;
+    let mut bytes = serde_json::to_vec_pretty(registry)?;
+    bytes.push(b'\n');
+    fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// How many private event files the store holds. Push never copies them,
+/// so removing the store loses them.
+pub fn count_private_event_files(store_root: &Path) -> Result<u64, EventWriteError> {
+    let private = private_root(store_root);
+    let mut count = 0;
+    for stream in [EventStream::Full, EventStream::Index] {
+        count += stream_files(&private.join(stream.root_dir()))?.len() as u64;
+    }
+    Ok(count)
+}
+
+/// The private index and full event files of `knot_ids`, relative to the
+/// private directory and in replay order, for rebuilding their cache rows.
+pub fn private_event_files(
+    store_root: &Path,
+    knot_ids: &HashSet<&str>,
+) -> Result<(Vec<PathBuf>, Vec<PathBuf>), EventWriteError> {
+    let private = private_root(store_root);
+    let mut streams = [Vec::new(), Vec::new()];
+    for (files, stream) in streams
+        .iter_mut()
+        .zip([EventStream::Index, EventStream::Full])
+    {
+        for path in stream_files(&private.join(stream.root_dir()))? {
+            let Some(record) = read_event(&path)? else {
+                continue;
+            };
+            if record.knot_id().is_some_and(|id| knot_ids.contains(id)) {
+                let relative = path.strip_prefix(&private).unwrap_or(&path);
+                files.push((record.event_id().to_string(), relative.to_path_buf()));
+            }
+        }
+        files.sort();
+    }
+    let [index, full] = streams.map(|files| files.into_iter().map(|(_, path)| path).collect());
+    Ok((index, full))
+}
+
+/// Moves every private event file for `knot_id` to the same path in the
+/// shared stream, returning how many moved. The next push publishes them.
+/// With `restamp`, each event is rewritten to occur at that time instead,
+/// which is how a draft is finalized.
+pub fn publish_private_events(
+    store_root: &Path,
+    knot_id: &str,
+    restamp: Option<&str>,
+) -> Result<u64, EventWriteError> {
+    let private = private_root(store_root);
+    let mut moved = 0;
+    for stream in [EventStream::Full, EventStream::Index] {
+        for path in stream_files(&private.join(stream.root_dir()))? {
+            let Some(record) = read_event(&path)? else {
+                continue;
+            };
+            if record.knot_id() != Some(knot_id) {
+                continue;
+            }
+            match restamp {
+                Some(at) => write_restamped(store_root, &path, record, at)?,
+                None => {
+                    let relative = path.strip_prefix(&private).unwrap_or(&path);
+                    move_file(&path, &store_root.join(relative))?;
+                }
+            }
+            moved += 1;
+        }
+    }
+    Ok(moved)
+}
+
+fn stream_files(dir: &Path) -> Result<Vec<PathBuf>, EventWriteError> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        if !dir.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn read_event(path: &Path) -> Result<Option<EventRecord>, EventWriteError> {
+    if path.extension().is_none_or(|ext| ext != "json") {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+}
+
+fn move_file(from: &Path, to: &Path) -> Result<(), EventWriteError> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(from, to)?;
+    Ok(())
+}
+
+/// Keeps the event id, so replay order is unchanged, but files the event
+/// under the new day and drops the staged copy.
+fn write_restamped(
+    store_root: &Path,
+    path: &Path,
+    mut record: EventRecord,
+    at: &str,
+) -> Result<(), EventWriteError> {
+    match &mut record {
+        EventRecord::Full(event) => event.occurred_at = at.to_string(),
+        EventRecord::Index(event) => {
+            event.occurred_at = at.to_string();
+            if let Some(updated_at) = event.data.get_mut("updated_at") {
+                *updated_at = Value::String(at.to_string());
+            }
+        }
+    }
+    let target = store_root.join(relative_path_for_event(
+        record.stream(),
+        record.occurred_at(),
+        record.event_id(),
+        record.event_type(),
+    )?);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut bytes = serde_json::to_vec_pretty(&record)?;
+    bytes.push(b'\n');
+    fs::write(&target, bytes)?;
+    fs::remove_file(path)?;
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::{EventRecord, EventStream, EventWriteError};
+use crate::compression;
+
+/// What `redact_event_files` changed across every copy of one event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RedactedEventFiles {
+    pub knot_id: Option<String>,
+    /// Every copy of the event that was found, matched or not.
+    pub paths: Vec<PathBuf>,
+    pub replacements: u64,
+    /// Top-level `data` keys that held a match, sorted.
+    pub fields: Vec<String>,
+}
+
+/// Rewrites every file for `event_id` under `roots` (a store root, its
+/// private directory, the publish worktree's `.knots`), replacing each
+/// literal `patterns` match in the event's data with `replacement`. The
+/// envelope is left alone so the file keeps its name and place in replay.
+pub fn redact_event_files(
+    roots: &[PathBuf],
+    event_id: &str,
+    patterns: &[String],
+    replacement: &str,
+) -> Result<RedactedEventFiles, EventWriteError> {
+    let prefix = format!("{event_id}-");
+    let mut fields = BTreeSet::new();
+    let mut redacted = RedactedEventFiles::default();
+    for root in roots {
+        for stream in [EventStream::Full, EventStream::Index] {
+            for path in event_files(&root.join(stream.root_dir()), &prefix)? {
+                let Some((record, count)) = redact_file(&path, patterns, replacement, &mut fields)?
+                else {
+                    continue;
+                };
+                redacted.paths.push(path);
+                redacted.replacements += count;
+                if redacted.knot_id.is_none() {
+                    redacted.knot_id = record.knot_id().map(str::to_string);
+                }
+            }
+        }
+    }
+    redacted.fields = fields.into_iter().collect();
+    Ok(redacted)
+}
+
+fn event_files(dir: &Path, prefix: &str) -> Result<Vec<PathBuf>, EventWriteError> {
+    let mut stack = vec![dir.to_path_buf()];
+    let mut paths = Vec::new();
+    while let Some(dir) = stack.pop() {
+        if !dir.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else if path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(prefix) && name.ends_with(".json"))
+            {
+                paths.push(path);
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Returns the rewritten record and how many matches it replaced, or
+/// `None` when the file is not an event file.
+fn redact_file(
+    path: &Path,
+    patterns: &[String],
+    replacement: &str,
+    fields: &mut BTreeSet<String>,
+) -> Result<Option<(EventRecord, u64)>, EventWriteError> {
+    let raw = fs::read(path)?;
+    let compressed = compression::is_zstd(&raw);
+    let Ok(mut record) = serde_json::from_slice::<EventRecord>(&compression::decode(&raw)?) else {
+        return Ok(None);
+    };
+    let data = match &mut record {
+        EventRecord::Full(event) => &mut event.data,
+        EventRecord::Index(event) => &mut event.data,
+    };
+    let mut count = 0;
+    if let Value::Object(map) = data {
+        for (key, value) in map.iter_mut() {
+            let replaced = redact_value(value, patterns, replacement);
+            if replaced > 0 {
+                fields.insert(key.clone());
+                count += replaced;
+            }
+        }
+    }
+    if count == 0 {
+        return Ok(Some((record, 0)));
+    }
+    let mut bytes = serde_json::to_vec_pretty(&record)?;
+    bytes.push(b'\n');
+    if compressed {
+        bytes = compression::encode(&bytes)?;
+    }
+    fs::write(path, bytes)?;
+    Ok(Some((record, count)))
+}
+
+/// Replaces matches in every string inside `value`, returning the count.
+pub(crate) fn redact_value(value: &mut Value, patterns: &[String], replacement: &str) -> u64 {
+    match value {
+        Value::String(text) => {
+            let mut count = 0;
+            for pattern in patterns {
+                let found = text.matches(pattern.as_str()).count() as u64;
+                if found > 0 {
+                    *text = text.replace(pattern.as_str(), replacement);
+                    count += found;
+                }
+            }
+            count
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .map(|item| redact_value(item, patterns, replacement))
+            .sum(),
+        Value::Object(map) => map
+            .values_mut()
+            .map(|item| redact_value(item, patterns, replacement))
+            .sum(),
+        _ => 0,
+    }
+}
@@ -0,0 +1,160 @@
+use super::{
+    relative_path_for_event, EventRecord, EventStream, EventWriter, FullEvent, FullEventKind,
+    IndexEvent, IndexEventKind,
+};
+use serde_json::json;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn unique_tmp_dir() -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before UNIX_EPOCH")
+        .as_nanos();
+    std::env::temp_dir().join(format!("knots-events-{}", nanos))
+}
+
+#[test]
+fn builds_deterministic_full_event_path() {
+    let path = relative_path_for_event(
+        EventStream::Full,
+        "2026-02-22T17:00:00Z",
+        "018f4f7f-7dc7-7f4e-954b-64f8a2273ec8",
+        FullEventKind::KnotStateSet.as_str(),
+    )
+    .expect("path should build");
+    assert_eq!(
+        path.to_string_lossy(),
+        "events/2026/02/22/018f4f7f-7dc7-7f4e-954b-64f8a2273ec8-knot.state_set.json"
+    );
+}
+
+#[test]
+fn builds_deterministic_index_event_path() {
+    let path = relative_path_for_event(
+        EventStream::Index,
+        "2026-02-22T17:00:00Z",
+        "018f4f7f-7dc7-7f4e-954b-64f8a2273ec8",
+        IndexEventKind::KnotHead.as_str(),
+    )
+    .expect("path should build");
+    assert_eq!(
+        path.to_string_lossy(),
+        "index/2026/02/22/018f4f7f-7dc7-7f4e-954b-64f8a2273ec8-idx.knot_head.json"
+    );
+}
+
+#[test]
+fn acceptance_event_kind_uses_expected_string() {
+    assert_eq!(
+        FullEventKind::KnotAcceptanceSet.as_str(),
+        "knot.acceptance_set"
+    );
+}
+
+#[test]
+fn writes_append_only_full_event_file() {
+    let root = unique_tmp_dir();
+    let writer = EventWriter::new(&root);
+    let event = EventRecord::full(FullEvent::with_identity(
+        "018f4f7f-7dc7-7f4e-954b-64f8a2273ec8",
+        "2026-02-22T17:00:00Z",
+        "K-123",
+        FullEventKind::KnotCreated.as_str(),
+        json!({"title":"Build cache"}),
+    ));
+
+    let relative = writer.write(&event).expect("first write should succeed");
+    assert_eq!(
+        relative.to_string_lossy(),
+        "events/2026/02/22/018f4f7f-7dc7-7f4e-954b-64f8a2273ec8-knot.created.json"
+    );
+
+    let absolute = root.join(&relative);
+    let saved: serde_json::Value = serde_json::from_slice(
+        &std::fs::read(&absolute).expect("event JSON file should be readable"),
+    )
+    .expect("event JSON should parse");
+
+    assert_eq!(saved["type"], "knot.created");
+    assert_eq!(saved["knot_id"], "K-123");
+
+    let second_write = writer.write(&event);
+    assert!(second_write.is_err());
+    if let Err(err) = second_write {
+        assert!(
+            err.to_string().contains("I/O error"),
+            "expected create_new collision, got: {}",
+            err
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn rejects_invalid_file_component() {
+    let result = relative_path_for_event(
+        EventStream::Full,
+        "2026-02-22T17:00:00Z",
+        "bad/id",
+        "knot.created",
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn writes_index_event() {
+    let root = unique_tmp_dir();
+    let writer = EventWriter::new(&root);
+    let event = EventRecord::index(IndexEvent::with_identity(
+        "018f4f7f-7dc7-7f4e-954b-64f8a2273ec8",
+        "2026-02-22T17:00:00Z",
+        IndexEventKind::KnotHead.as_str(),
+        json!({
+            "knot_id":"K-123",
+            "title":"Build cache",
+            "state":"implementing",
+            "updated_at":"2026-02-22T17:00:00Z"
+        }),
+    ));
+
+    let relative = writer.write(&event).expect("index write should succeed");
+    assert_eq!(
+        relative.to_string_lossy(),
+        "index/2026/02/22/018f4f7f-7dc7-7f4e-954b-64f8a2273ec8-idx.knot_head.json"
+    );
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn batched_writes_are_synced_by_flush() {
+    let root = unique_tmp_dir();
+    let writer = EventWriter::new(&root);
+    writer.begin_batch();
+    for (id, at) in [
+        (
+            "018f4f7f-7dc7-7f4e-954b-64f8a2273ec1",
+            "2026-02-22T17:00:00Z",
+        ),
+        (
+            "018f4f7f-7dc7-7f4e-954b-64f8a2273ec2",
+            "2026-02-22T17:00:01Z",
+        ),
+    ] {
+        writer
+            .write(&EventRecord::full(FullEvent::with_identity(
+                id,
+                at,
+                "K-123",
+                FullEventKind::KnotCreated.as_str(),
+                json!({"title":"Build cache"}),
+            )))
+            .expect("batched write should succeed");
+    }
+    assert_eq!(writer.flush_batch(false).expect("flush"), 2);
+    assert_eq!(writer.flush_batch(true).expect("end batch"), 0);
+    assert_eq!(writer.flush_batch(true).expect("no batch"), 0);
+    let _ = std::fs::remove_dir_all(root);
+}
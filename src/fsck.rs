@@ -90,7 +90,7 @@ fn validate_single_event(
     known_knot_ids: &mut HashSet<String>,
     edge_refs: &mut Vec<(PathBuf, String, String)>,
 ) {
-    let raw = match std::fs::read(path) {
+    let raw = match crate::compression::read(path) {
         Ok(value) => value,
         Err(err) => {
             issues.push(issue(path, &format!("unable to read file: {}", err)));
@@ -156,6 +156,9 @@ fn validate_single_event(
             edge_refs.push((path.to_path_buf(), knot_id, dst));
         }
     }
+    if event_type == "knot.redacted" {
+        require_data_string(data, "redacted_event_id", path, issues);
+    }
 }
 
 fn check_duplicate_event_id(
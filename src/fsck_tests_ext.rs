@@ -206,3 +206,33 @@ fn reports_schema_and_reference_issues_for_malformed_events() {
 
     let _ = std::fs::remove_dir_all(root);
 }
+
+#[test]
+fn accepts_redaction_markers_that_name_their_event() {
+    let root = unique_workspace();
+    let marker = |id: &str, data: &str| {
+        format!(
+            "{{\n  \"event_id\": \"{id}\",\n  \"occurred_at\": \"2026-02-25T10:00:00Z\",\n  \
+             \"type\": \"knot.redacted\",\n  \"knot_id\": \"K-a\",\n  \"data\": {data}\n}}\n"
+        )
+    };
+    let valid = root.join(".knots/events/2026/02/25/3000-knot.redacted.json");
+    write_file(
+        &valid,
+        &marker(
+            "3000",
+            r#"{"redacted_event_id": "1000", "replacements": 1}"#,
+        ),
+    );
+    assert!(run_fsck(&root).expect("fsck should complete").ok());
+
+    let unnamed = root.join(".knots/events/2026/02/25/3001-knot.redacted.json");
+    write_file(&unnamed, &marker("3001", "{}"));
+    let report = run_fsck(&root).expect("fsck should complete");
+    assert_eq!(report.issues.len(), 1, "{:?}", report.issues);
+    assert!(report.issues[0]
+        .message
+        .contains("missing required string field data.redacted_event_id"));
+
+    let _ = std::fs::remove_dir_all(root);
+}
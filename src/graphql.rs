@@ -0,0 +1,48 @@
+use serde_json::{json, Map, Value};
+
+use crate::app::App;
+
+mod parse;
+mod resolve;
+#[cfg(test)]
+mod tests;
+
+/// Runs one read-only GraphQL query over the cache and returns the
+/// response document: `{"data": ...}` or `{"errors": [...]}`.
+///
+/// The schema is fixed:
+/// - `Query.knots(state, tag, type, limit)` and `Query.knot(id)`
+/// - `Knot` scalars (`id`, `title`, `state`, `updatedAt`, ...) plus `notes`,
+///   `handoffs`, `steps`, `edges(direction, kind)`, `children`, `parent`,
+///   and `events(limit)`
+/// - `Edge { src kind dst source target }`, `Note`, `Step`, and
+///   `Event { eventId occurredAt type data }`
+pub fn execute(app: &App, query: &str, variables: &Map<String, Value>) -> Value {
+    let result = parse::parse_query(query, variables).and_then(|fields| {
+        let context = resolve::Context::load(app)?;
+        context.query(&fields)
+    });
+    match result {
+        Ok(data) => json!({ "data": data }),
+        Err(message) => json!({ "errors": [{ "message": message }] }),
+    }
+}
+
+/// Runs the query in a `{"query": ..., "variables": ...}` request body.
+pub fn execute_request(app: &App, body: &[u8]) -> Value {
+    let request: Value = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(err) => {
+            return json!({ "errors": [{ "message": format!("invalid request body: {err}") }] })
+        }
+    };
+    let Some(query) = request.get("query").and_then(Value::as_str) else {
+        return json!({ "errors": [{ "message": "request body needs a \"query\" string" }] });
+    };
+    let variables = request
+        .get("variables")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    execute(app, query, &variables)
+}
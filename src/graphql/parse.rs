@@ -0,0 +1,291 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+/// One field of a selection set, with its arguments already resolved
+/// against the request variables.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Field {
+    pub(super) alias: Option<String>,
+    pub(super) name: String,
+    pub(super) args: BTreeMap<String, Value>,
+    pub(super) selections: Vec<Field>,
+}
+
+impl Field {
+    /// The key this field's value takes in the response.
+    pub(super) fn response_key(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Parses a single read-only operation: an optional `query Name($var: Type
+/// = default)` header and one selection set. Fragments, directives, and
+/// mutations are rejected.
+pub(super) fn parse_query(
+    source: &str,
+    variables: &Map<String, Value>,
+) -> Result<Vec<Field>, String> {
+    let mut parser = Parser {
+        chars: source.chars().collect(),
+        pos: 0,
+        variables: variables.clone(),
+    };
+    parser.skip_ignored();
+    if parser.peek() != Some('{') {
+        match parser.name()?.as_str() {
+            "query" => {}
+            "mutation" | "subscription" => {
+                return Err("only queries are supported; this endpoint is read-only".to_string())
+            }
+            other => return Err(format!("unexpected '{other}' at the start of the document")),
+        }
+        if parser.peek().is_some_and(is_name_start) {
+            parser.name()?;
+        }
+        if parser.peek() == Some('(') {
+            parser.variable_definitions()?;
+        }
+    }
+    let selections = parser.selection_set()?;
+    if parser.peek().is_some() {
+        return Err("only one operation per request is supported".to_string());
+    }
+    Ok(selections)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    variables: Map<String, Value>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_ignored(&mut self) {
+        while let Some(ch) = self.peek() {
+            if ch == '#' {
+                while self.peek().is_some_and(|ch| ch != '\n') {
+                    self.pos += 1;
+                }
+            } else if ch.is_whitespace() || ch == ',' || ch == '\u{feff}' {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn expect(&mut self, want: char) -> Result<(), String> {
+        match self.peek() {
+            Some(ch) if ch == want => {
+                self.pos += 1;
+                self.skip_ignored();
+                Ok(())
+            }
+            Some(ch) => Err(format!("expected '{want}', found '{ch}'")),
+            None => Err(format!("expected '{want}', found end of document")),
+        }
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        if !self.peek().is_some_and(is_name_start) {
+            return Err(match self.peek() {
+                Some('.') => "fragments are not supported".to_string(),
+                Some('@') => "directives are not supported".to_string(),
+                Some(ch) => format!("expected a name, found '{ch}'"),
+                None => "expected a name, found end of document".to_string(),
+            });
+        }
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+        {
+            self.pos += 1;
+        }
+        let name = self.chars[start..self.pos].iter().collect();
+        self.skip_ignored();
+        Ok(name)
+    }
+
+    fn selection_set(&mut self) -> Result<Vec<Field>, String> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        while self.peek() != Some('}') {
+            fields.push(self.field()?);
+        }
+        self.expect('}')?;
+        if fields.is_empty() {
+            return Err("selection sets must not be empty".to_string());
+        }
+        Ok(fields)
+    }
+
+    fn field(&mut self) -> Result<Field, String> {
+        let first = self.name()?;
+        let (alias, name) = if self.peek() == Some(':') {
+            self.expect(':')?;
+            (Some(first), self.name()?)
+        } else {
+            (None, first)
+        };
+        let mut args = BTreeMap::new();
+        if self.peek() == Some('(') {
+            self.expect('(')?;
+            while self.peek() != Some(')') {
+                let arg = self.name()?;
+                self.expect(':')?;
+                args.insert(arg, self.value()?);
+            }
+            self.expect(')')?;
+        }
+        if self.peek() == Some('@') {
+            return Err("directives are not supported".to_string());
+        }
+        let selections = if self.peek() == Some('{') {
+            self.selection_set()?
+        } else {
+            Vec::new()
+        };
+        Ok(Field {
+            alias,
+            name,
+            args,
+            selections,
+        })
+    }
+
+    /// `($name: Type = default, ...)`; defaults fill variables the request
+    /// did not send.
+    fn variable_definitions(&mut self) -> Result<(), String> {
+        self.expect('(')?;
+        while self.peek() != Some(')') {
+            self.expect('$')?;
+            let name = self.name()?;
+            self.expect(':')?;
+            self.type_ref()?;
+            if self.peek() == Some('=') {
+                self.expect('=')?;
+                let default = self.value()?;
+                self.variables.entry(name).or_insert(default);
+            }
+        }
+        self.expect(')')
+    }
+
+    fn type_ref(&mut self) -> Result<(), String> {
+        if self.peek() == Some('[') {
+            self.expect('[')?;
+            self.type_ref()?;
+            self.expect(']')?;
+        } else {
+            self.name()?;
+        }
+        if self.peek() == Some('!') {
+            self.expect('!')?;
+        }
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        match self.peek() {
+            Some('$') => {
+                self.expect('$')?;
+                let name = self.name()?;
+                Ok(self.variables.get(&name).cloned().unwrap_or(Value::Null))
+            }
+            Some('"') => self.string().map(Value::String),
+            Some('[') => {
+                self.expect('[')?;
+                let mut items = Vec::new();
+                while self.peek() != Some(']') {
+                    items.push(self.value()?);
+                }
+                self.expect(']')?;
+                Ok(Value::Array(items))
+            }
+            Some('{') => {
+                self.expect('{')?;
+                let mut object = Map::new();
+                while self.peek() != Some('}') {
+                    let key = self.name()?;
+                    self.expect(':')?;
+                    object.insert(key, self.value()?);
+                }
+                self.expect('}')?;
+                Ok(Value::Object(object))
+            }
+            Some(ch) if ch == '-' || ch.is_ascii_digit() => self.number(),
+            _ => Ok(match self.name()?.as_str() {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                "null" => Value::Null,
+                // Enum values are passed on as their names.
+                other => Value::String(other.to_string()),
+            }),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, String> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|ch| ch.is_ascii_digit() || matches!(ch, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            self.pos += 1;
+        }
+        let raw: String = self.chars[start..self.pos].iter().collect();
+        self.skip_ignored();
+        serde_json::from_str::<serde_json::Number>(&raw)
+            .map(Value::Number)
+            .map_err(|_| format!("invalid number '{raw}'"))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let Some(ch) = self.peek() else {
+                return Err("unterminated string".to_string());
+            };
+            self.pos += 1;
+            match ch {
+                '"' => break,
+                '\\' => {
+                    let escaped = self.peek().ok_or("unterminated string")?;
+                    self.pos += 1;
+                    match escaped {
+                        'n' => out.push('\n'),
+                        't' => out.push('\t'),
+                        'r' => out.push('\r'),
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        'u' => out.push(self.unicode_escape()?),
+                        other => out.push(other),
+                    }
+                }
+                '\n' => return Err("unterminated string".to_string()),
+                other => out.push(other),
+            }
+        }
+        self.skip_ignored();
+        Ok(out)
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, String> {
+        let hex: String = self.chars.iter().skip(self.pos).take(4).collect();
+        self.pos += 4;
+        u32::from_str_radix(&hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| format!("invalid unicode escape '\\u{hex}'"))
+    }
+}
+
+fn is_name_start(ch: char) -> bool {
+    ch.is_ascii_alphabetic() || ch == '_'
+}
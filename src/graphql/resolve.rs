@@ -0,0 +1,443 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde_json::{json, Map, Value};
+
+use crate::app::{App, EdgeView};
+
+use super::parse::Field;
+
+/// Nesting past this depth is refused so one request cannot walk the whole
+/// graph repeatedly.
+const MAX_DEPTH: usize = 12;
+
+/// GraphQL field name and the `KnotView` JSON key it reads.
+const KNOT_SCALARS: [(&str, &str); 17] = [
+    ("id", "id"),
+    ("alias", "alias"),
+    ("title", "title"),
+    ("state", "state"),
+    ("type", "type"),
+    ("priority", "priority"),
+    ("tags", "tags"),
+    ("body", "body"),
+    ("description", "description"),
+    ("acceptance", "acceptance"),
+    ("estimate", "estimate"),
+    ("customFields", "custom_fields"),
+    ("workflowId", "workflow_id"),
+    ("profileId", "profile_id"),
+    ("createdAt", "created_at"),
+    ("updatedAt", "updated_at"),
+    ("snoozedUntil", "snoozed_until"),
+];
+const NOTE_FIELDS: [(&str, &str); 6] = [
+    ("content", "content"),
+    ("username", "username"),
+    ("datetime", "datetime"),
+    ("agentname", "agentname"),
+    ("model", "model"),
+    ("version", "version"),
+];
+const STEP_FIELDS: [(&str, &str); 9] = [
+    ("step", "step"),
+    ("phase", "phase"),
+    ("fromState", "from_state"),
+    ("toState", "to_state"),
+    ("status", "status"),
+    ("actorKind", "actor_kind"),
+    ("agentName", "agent_name"),
+    ("startedAt", "started_at"),
+    ("endedAt", "ended_at"),
+];
+
+/// Per-request state: every shared knot is read from the cache once, already
+/// stripped of the repo's sensitive fields, and each nested field is then
+/// resolved for all knots of a level with one batched load. Private knots
+/// and drafts, and edges to them, are never served.
+pub(super) struct Context<'a> {
+    app: &'a App,
+    knots: HashMap<String, Value>,
+    order: Vec<String>,
+    /// Sensitive field names, also dropped from event data.
+    hidden: Vec<String>,
+    private: HashSet<String>,
+}
+
+impl<'a> Context<'a> {
+    pub(super) fn load(app: &'a App) -> Result<Self, String> {
+        let views = app.list_shared_knots().map_err(|err| err.to_string())?;
+        let exported = app
+            .knots_for_export(&views, false)
+            .map_err(|err| err.to_string())?;
+        let order = views.iter().map(|knot| knot.id.clone()).collect();
+        let knots = views
+            .into_iter()
+            .zip(exported)
+            .map(|(view, value)| (view.id, value))
+            .collect();
+        let hidden = app.sensitive_fields().map_err(|err| err.to_string())?;
+        let private = app.private_knot_ids().map_err(|err| err.to_string())?;
+        Ok(Self {
+            app,
+            knots,
+            order,
+            hidden,
+            private,
+        })
+    }
+
+    pub(super) fn query(&self, fields: &[Field]) -> Result<Value, String> {
+        let mut data = Map::new();
+        for field in fields {
+            let value = match field.name.as_str() {
+                "__typename" => json!("Query"),
+                "knots" => {
+                    check_args(field, &["state", "tag", "type", "limit"])?;
+                    let ids = self.filter_knots(&field.args)?;
+                    Value::Array(self.knots_at(&ids, object_fields(field)?, 1)?)
+                }
+                "knot" => {
+                    check_args(field, &["id"])?;
+                    let token = field.args.get("id").and_then(Value::as_str);
+                    let token = token.ok_or("knot(id:) needs a string id")?;
+                    match self.resolve_id(token) {
+                        Some(id) => self.knots_at(&[id], object_fields(field)?, 1)?.remove(0),
+                        None => Value::Null,
+                    }
+                }
+                other => return Err(unknown_field(other, "Query")),
+            };
+            data.insert(field.response_key().to_string(), value);
+        }
+        Ok(Value::Object(data))
+    }
+
+    fn resolve_id(&self, token: &str) -> Option<String> {
+        let id = self.app.show_knot(token).ok().flatten()?.id;
+        self.knots.contains_key(&id).then_some(id)
+    }
+
+    fn filter_knots(&self, args: &BTreeMap<String, Value>) -> Result<Vec<String>, String> {
+        let text = |name: &str| args.get(name).and_then(Value::as_str);
+        let limit = match args.get("limit") {
+            None | Some(Value::Null) => usize::MAX,
+            Some(value) => value
+                .as_u64()
+                .ok_or("knots(limit:) must be a non-negative integer")?
+                as usize,
+        };
+        let matches = |knot: &Value| {
+            text("state").is_none_or(|state| knot["state"] == state)
+                && text("type").is_none_or(|kind| knot["type"] == kind)
+                && text("tag").is_none_or(|tag| {
+                    knot["tags"]
+                        .as_array()
+                        .is_some_and(|tags| tags.iter().any(|item| item == tag))
+                })
+        };
+        Ok(self
+            .order
+            .iter()
+            .filter(|id| matches(&self.knots[*id]))
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    /// Resolves `fields` for every knot in `ids` (missing ids become null).
+    fn knots_at(
+        &self,
+        ids: &[String],
+        fields: &[Field],
+        depth: usize,
+    ) -> Result<Vec<Value>, String> {
+        if depth > MAX_DEPTH {
+            return Err(format!("queries may nest at most {MAX_DEPTH} levels"));
+        }
+        let mut objects: Vec<Map<String, Value>> = vec![Map::new(); ids.len()];
+        for field in fields {
+            let values = self.knot_field(ids, field, depth)?;
+            for (object, value) in objects.iter_mut().zip(values) {
+                object.insert(field.response_key().to_string(), value);
+            }
+        }
+        Ok(ids
+            .iter()
+            .zip(objects)
+            .map(|(id, object)| match self.knots.contains_key(id) {
+                true => Value::Object(object),
+                false => Value::Null,
+            })
+            .collect())
+    }
+
+    /// One field's value for every knot in `ids`, loaded in one batch.
+    fn knot_field(
+        &self,
+        ids: &[String],
+        field: &Field,
+        depth: usize,
+    ) -> Result<Vec<Value>, String> {
+        let knot = |id: &String| self.knots.get(id).unwrap_or(&Value::Null);
+        let name = field.name.as_str();
+        if let Some((_, key)) = KNOT_SCALARS.iter().find(|(graph, _)| *graph == name) {
+            scalar_field(field)?;
+            return Ok(ids.iter().map(|id| field_value(knot(id), key)).collect());
+        }
+        match name {
+            "__typename" => Ok(vec![json!("Knot"); ids.len()]),
+            "notes" | "handoffs" | "steps" => {
+                let (key, allowed, type_name) = match name {
+                    "notes" => ("notes", &NOTE_FIELDS[..], "Note"),
+                    "handoffs" => ("handoff_capsules", &NOTE_FIELDS[..], "Note"),
+                    _ => ("step_history", &STEP_FIELDS[..], "Step"),
+                };
+                let selections = object_fields(field)?;
+                ids.iter()
+                    .map(|id| {
+                        let items = knot(id)[key].as_array().cloned().unwrap_or_default();
+                        items
+                            .iter()
+                            .map(|item| select(item, selections, allowed, type_name))
+                            .collect::<Result<Vec<_>, _>>()
+                            .map(Value::Array)
+                    })
+                    .collect()
+            }
+            "edges" => self.edges_field(ids, field, depth),
+            "children" | "parent" => self.relatives_field(ids, field, depth),
+            "events" => self.events_field(ids, field),
+            other => Err(unknown_field(other, "Knot")),
+        }
+    }
+
+    fn load_edges(&self, ids: &[String]) -> Result<Vec<EdgeView>, String> {
+        let mut edges = self
+            .app
+            .edges_for_knots(ids)
+            .map_err(|err| err.to_string())?;
+        edges.retain(|edge| !self.private.contains(&edge.src) && !self.private.contains(&edge.dst));
+        Ok(edges)
+    }
+
+    fn edges_field(
+        &self,
+        ids: &[String],
+        field: &Field,
+        depth: usize,
+    ) -> Result<Vec<Value>, String> {
+        check_args(field, &["direction", "kind"])?;
+        let direction = field.args.get("direction").and_then(Value::as_str);
+        let direction = direction.unwrap_or("both").to_ascii_lowercase();
+        if !matches!(direction.as_str(), "incoming" | "outgoing" | "both") {
+            return Err("edges(direction:) must be incoming, outgoing, or both".to_string());
+        }
+        let kind = field.args.get("kind").and_then(Value::as_str);
+        let edges = self.load_edges(ids)?;
+        let per_knot: Vec<Vec<&EdgeView>> = ids
+            .iter()
+            .map(|id| {
+                edges
+                    .iter()
+                    .filter(|edge| kind.is_none_or(|kind| edge.kind == kind))
+                    .filter(|edge| match direction.as_str() {
+                        "incoming" => edge.dst == *id,
+                        "outgoing" => edge.src == *id,
+                        _ => edge.src == *id || edge.dst == *id,
+                    })
+                    .collect()
+            })
+            .collect();
+        let all: Vec<&EdgeView> = per_knot.iter().flatten().copied().collect();
+        let mut columns: HashMap<&str, Vec<Value>> = HashMap::new();
+        for selection in object_fields(field)? {
+            let column = match selection.name.as_str() {
+                "__typename" => vec![json!("Edge"); all.len()],
+                "src" | "kind" | "dst" => {
+                    scalar_field(selection)?;
+                    all.iter()
+                        .map(|edge| {
+                            json!(match selection.name.as_str() {
+                                "src" => &edge.src,
+                                "kind" => &edge.kind,
+                                _ => &edge.dst,
+                            })
+                        })
+                        .collect()
+                }
+                "source" | "target" => {
+                    let ends: Vec<String> = all
+                        .iter()
+                        .map(|edge| match selection.name.as_str() {
+                            "source" => edge.src.clone(),
+                            _ => edge.dst.clone(),
+                        })
+                        .collect();
+                    self.knots_at(&ends, object_fields(selection)?, depth + 1)?
+                }
+                other => return Err(unknown_field(other, "Edge")),
+            };
+            columns.insert(selection.response_key(), column);
+        }
+        let mut index = 0;
+        Ok(per_knot
+            .iter()
+            .map(|edges| {
+                let objects = (index..index + edges.len())
+                    .map(|row| {
+                        let mut object = Map::new();
+                        for selection in &field.selections {
+                            let key = selection.response_key();
+                            object.insert(key.to_string(), columns[key][row].clone());
+                        }
+                        Value::Object(object)
+                    })
+                    .collect();
+                index += edges.len();
+                Value::Array(objects)
+            })
+            .collect())
+    }
+
+    fn relatives_field(
+        &self,
+        ids: &[String],
+        field: &Field,
+        depth: usize,
+    ) -> Result<Vec<Value>, String> {
+        check_args(field, &[])?;
+        let edges = self.load_edges(ids)?;
+        let children = field.name == "children";
+        let per_knot: Vec<Vec<String>> = ids
+            .iter()
+            .map(|id| {
+                edges
+                    .iter()
+                    .filter(|edge| edge.kind == "parent_of")
+                    .filter_map(|edge| match children {
+                        true => (edge.src == *id).then(|| edge.dst.clone()),
+                        false => (edge.dst == *id).then(|| edge.src.clone()),
+                    })
+                    .filter(|related| self.knots.contains_key(related))
+                    .collect()
+            })
+            .collect();
+        let flat: Vec<String> = per_knot.iter().flatten().cloned().collect();
+        let mut resolved = self
+            .knots_at(&flat, object_fields(field)?, depth + 1)?
+            .into_iter();
+        Ok(per_knot
+            .iter()
+            .map(|related| {
+                let values: Vec<Value> = resolved.by_ref().take(related.len()).collect();
+                match children {
+                    true => Value::Array(values),
+                    false => values.into_iter().next().unwrap_or(Value::Null),
+                }
+            })
+            .collect())
+    }
+
+    fn events_field(&self, ids: &[String], field: &Field) -> Result<Vec<Value>, String> {
+        check_args(field, &["limit"])?;
+        let limit = field.args.get("limit").and_then(Value::as_u64);
+        let selections = object_fields(field)?;
+        let mut events = self.app.knot_events(ids).map_err(|err| err.to_string())?;
+        ids.iter()
+            .map(|id| {
+                let history = events.remove(id).unwrap_or_default();
+                let skip = limit.map_or(0, |limit| history.len().saturating_sub(limit as usize));
+                history
+                    .into_iter()
+                    .skip(skip)
+                    .map(|mut event| {
+                        if let Value::Object(data) = &mut event.data {
+                            data.retain(|key, _| !self.hidden.contains(key));
+                        }
+                        let value = json!({
+                            "eventId": event.event_id,
+                            "occurredAt": event.occurred_at,
+                            "type": event.event_type,
+                            "data": event.data,
+                        });
+                        select(&value, selections, &EVENT_FIELDS, "Event")
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Value::Array)
+            })
+            .collect()
+    }
+}
+
+const EVENT_FIELDS: [(&str, &str); 4] = [
+    ("eventId", "eventId"),
+    ("occurredAt", "occurredAt"),
+    ("type", "type"),
+    ("data", "data"),
+];
+
+fn select(
+    source: &Value,
+    selections: &[Field],
+    allowed: &[(&str, &str)],
+    type_name: &str,
+) -> Result<Value, String> {
+    let mut object = Map::new();
+    for selection in selections {
+        let value = if selection.name == "__typename" {
+            json!(type_name)
+        } else {
+            let Some((_, key)) = allowed.iter().find(|(graph, _)| *graph == selection.name) else {
+                return Err(unknown_field(&selection.name, type_name));
+            };
+            scalar_field(selection)?;
+            field_value(source, key)
+        };
+        object.insert(selection.response_key().to_string(), value);
+    }
+    Ok(Value::Object(object))
+}
+
+fn field_value(source: &Value, key: &str) -> Value {
+    source.get(key).cloned().unwrap_or(Value::Null)
+}
+
+fn scalar_field(field: &Field) -> Result<(), String> {
+    if field.selections.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "field '{}' is a scalar and takes no selections",
+            field.name
+        ))
+    }
+}
+
+fn object_fields(field: &Field) -> Result<&[Field], String> {
+    if field.selections.is_empty() {
+        Err(format!(
+            "field '{}' needs a selection of subfields",
+            field.name
+        ))
+    } else {
+        Ok(&field.selections)
+    }
+}
+
+fn check_args(field: &Field, allowed: &[&str]) -> Result<(), String> {
+    match field
+        .args
+        .keys()
+        .find(|arg| !allowed.contains(&arg.as_str()))
+    {
+        Some(arg) => Err(format!(
+            "unknown argument '{arg}' on field '{}'",
+            field.name
+        )),
+        None => Ok(()),
+    }
+}
+
+fn unknown_field(name: &str, type_name: &str) -> String {
+    format!("cannot query field '{name}' on type '{type_name}'")
+}
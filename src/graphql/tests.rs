@@ -0,0 +1,160 @@
+use std::path::PathBuf;
+
+use serde_json::{json, Map, Value};
+
+use super::execute;
+use super::parse::parse_query;
+use crate::app::{App, CreateKnotOptions, UpdateKnotPatch};
+use crate::domain::metadata::MetadataEntryInput;
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-graphql-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn variables(value: Value) -> Map<String, Value> {
+    value.as_object().cloned().unwrap_or_default()
+}
+
+#[test]
+fn parser_resolves_aliases_arguments_and_variables() {
+    let fields = parse_query(
+        r#"query Board($state: String = "planning", $n: Int!) {
+            # comment
+            open: knots(state: $state, limit: $n, tag: "a\"b") { id, title }
+        }"#,
+        &variables(json!({ "n": 3 })),
+    )
+    .expect("parse");
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].response_key(), "open");
+    assert_eq!(fields[0].name, "knots");
+    assert_eq!(fields[0].args["state"], "planning");
+    assert_eq!(fields[0].args["limit"], 3);
+    assert_eq!(fields[0].args["tag"], "a\"b");
+    assert_eq!(fields[0].selections.len(), 2);
+
+    let err = |query: &str| parse_query(query, &Map::new()).expect_err("should fail");
+    assert!(err("mutation { x }").contains("read-only"));
+    assert!(err("{ knots { ...F } }").contains("fragments"));
+    assert!(err("{ knots @skip(if: true) { id } }").contains("directives"));
+    assert!(err("{ knots { id }").contains("end of document"));
+    assert!(err("{ a } { b }").contains("one operation"));
+}
+
+#[test]
+fn queries_nest_children_edges_notes_and_events_in_one_request() {
+    let (root, app) = open_app();
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "sensitive_fields = [\"body\"]\n",
+    )
+    .expect("repo config");
+    let parent = app
+        .create_knot("Parent", Some("secret plan"), None, None)
+        .expect("create");
+    let child = app.create_knot("Child", None, None, None).expect("create");
+    app.add_edge(&parent.id, "parent_of", &child.id)
+        .expect("edge");
+    app.update_knot(
+        &child.id,
+        UpdateKnotPatch {
+            add_note: Some(MetadataEntryInput {
+                content: "Halfway".to_string(),
+                ..MetadataEntryInput::default()
+            }),
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("note");
+
+    let response = execute(
+        &app,
+        r#"query($id: String!) {
+            knot(id: $id) {
+                title body
+                children { title notes { content } parent { title } }
+                edges(direction: outgoing) { kind target { id } }
+                events(limit: 1) { type data }
+            }
+            missing: knot(id: "nope-1234") { id }
+        }"#,
+        &variables(json!({ "id": parent.id })),
+    );
+    let knot = &response["data"]["knot"];
+    assert_eq!(knot["title"], "Parent");
+    assert_eq!(knot["body"], Value::Null);
+    assert_eq!(knot["children"][0]["title"], "Child");
+    assert_eq!(knot["children"][0]["notes"][0]["content"], "Halfway");
+    assert_eq!(knot["children"][0]["parent"]["title"], "Parent");
+    assert_eq!(knot["edges"][0]["kind"], "parent_of");
+    assert_eq!(knot["edges"][0]["target"]["id"], child.id.as_str());
+    assert_eq!(knot["events"].as_array().expect("events").len(), 1);
+    assert_eq!(response["data"]["missing"], Value::Null);
+
+    let created = execute(
+        &app,
+        "{ knots(limit: 1) { events { type data } } }",
+        &Map::new(),
+    );
+    let events = created["data"]["knots"][0]["events"]
+        .as_array()
+        .expect("events");
+    assert!(events
+        .iter()
+        .all(|event| event["data"].get("body").is_none()));
+
+    let errors = execute(&app, "{ knots { title { x } } }", &Map::new());
+    assert!(errors["errors"][0]["message"]
+        .as_str()
+        .is_some_and(|message| message.contains("scalar")));
+    let errors = execute(&app, "{ knots(owner: \"x\") { id } }", &Map::new());
+    assert!(errors["errors"][0]["message"]
+        .as_str()
+        .is_some_and(|message| message.contains("unknown argument 'owner'")));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn private_knots_drafts_and_their_edges_are_not_served() {
+    let (root, app) = open_app();
+    let shared = app.create_knot("Shared", None, None, None).expect("create");
+    let hidden: Vec<String> = [false, true]
+        .into_iter()
+        .map(|draft| {
+            let options = CreateKnotOptions {
+                private: !draft,
+                draft,
+                ..CreateKnotOptions::default()
+            };
+            app.create_knot_with_options("Hidden", None, None, None, None, options)
+                .expect("create")
+                .id
+        })
+        .collect();
+    for id in &hidden {
+        app.add_edge(id, "blocked_by", &shared.id).expect("edge");
+    }
+
+    let response = execute(
+        &app,
+        r#"query($a: String!, $b: String!) {
+            knots { id }
+            private: knot(id: $a) { id }
+            draft: knot(id: $b) { id }
+        }"#,
+        &variables(json!({ "a": hidden[0], "b": hidden[1] })),
+    );
+    assert_eq!(response["data"]["knots"], json!([{ "id": shared.id }]));
+    assert_eq!(response["data"]["private"], Value::Null);
+    assert_eq!(response["data"]["draft"], Value::Null);
+
+    let response = execute(&app, "{ knots { edges { kind src } } }", &Map::new());
+    assert_eq!(response["data"]["knots"][0]["edges"], json!([]));
+    let _ = std::fs::remove_dir_all(root);
+}
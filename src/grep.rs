@@ -0,0 +1,224 @@
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+
+/// Characters of a long line kept around its first match.
+const SNIPPET_CHARS: usize = 120;
+/// Context shown before the first match when a line is cut.
+const SNIPPET_LEAD: usize = 30;
+
+/// Knot content `kno grep` can search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GrepField {
+    Title,
+    Description,
+    Notes,
+    Handoffs,
+    Events,
+}
+
+impl GrepField {
+    pub const ALL: [GrepField; 5] = [
+        GrepField::Title,
+        GrepField::Description,
+        GrepField::Notes,
+        GrepField::Handoffs,
+        GrepField::Events,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GrepField::Title => "title",
+            GrepField::Description => "description",
+            GrepField::Notes => "notes",
+            GrepField::Handoffs => "handoffs",
+            GrepField::Events => "events",
+        }
+    }
+
+    /// The `knot_fts` column holding this field; events are not indexed.
+    pub fn index_column(self) -> Option<&'static str> {
+        (self != GrepField::Events).then(|| self.as_str())
+    }
+
+    /// The knot JSON key `sensitive_fields` uses for this field.
+    pub fn sensitive_key(self) -> &'static str {
+        match self {
+            GrepField::Handoffs => "handoff_capsules",
+            other => other.as_str(),
+        }
+    }
+
+    /// Parses a comma-separated `--in` list. Without one, everything but
+    /// the event log is searched.
+    pub fn parse_list(raw: Option<&str>) -> Result<Vec<GrepField>, String> {
+        let Some(raw) = raw else {
+            return Ok(Self::ALL[..4].to_vec());
+        };
+        let mut fields = Vec::new();
+        for name in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            let name = match name {
+                "description" | "desc" => "description",
+                "note" => "notes",
+                "handoff" | "handoff_capsules" => "handoffs",
+                "event" => "events",
+                other => other,
+            };
+            let Some(field) = Self::ALL.into_iter().find(|field| field.as_str() == name) else {
+                return Err(format!(
+                    "unknown --in field '{name}'; expected title, description, notes, handoffs, or events"
+                ));
+            };
+            fields.push(field);
+        }
+        fields.sort();
+        fields.dedup();
+        if fields.is_empty() {
+            return Err("--in needs at least one field".to_string());
+        }
+        Ok(fields)
+    }
+}
+
+/// A compiled `kno grep` pattern: a literal substring unless `--regex`.
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    regex: Regex,
+    literal: Option<String>,
+}
+
+impl Matcher {
+    pub fn new(pattern: &str, is_regex: bool, ignore_case: bool) -> Result<Self, String> {
+        if pattern.is_empty() {
+            return Err("grep pattern must not be empty".to_string());
+        }
+        let source = if is_regex {
+            pattern.to_string()
+        } else {
+            regex::escape(pattern)
+        };
+        let regex = RegexBuilder::new(&source)
+            .case_insensitive(ignore_case)
+            .build()
+            .map_err(|err| format!("invalid pattern '{pattern}': {err}"))?;
+        if regex.is_match("") {
+            return Err(format!("pattern '{pattern}' matches empty text"));
+        }
+        Ok(Self {
+            regex,
+            literal: (!is_regex).then(|| pattern.to_string()),
+        })
+    }
+
+    /// The substring a plain pattern looks for, which the trigram index can
+    /// narrow down to candidate knots.
+    pub fn literal(&self) -> Option<&str> {
+        self.literal.as_deref()
+    }
+
+    /// Every line of `text` the pattern matches, as a highlighted snippet.
+    pub fn match_lines(&self, text: &str) -> Vec<LineMatch> {
+        text.lines()
+            .filter_map(|line| self.match_line(line))
+            .collect()
+    }
+
+    fn match_line(&self, line: &str) -> Option<LineMatch> {
+        let chars: Vec<char> = line.chars().collect();
+        let offsets: Vec<usize> = line.char_indices().map(|(at, _)| at).collect();
+        let char_at = |byte: usize| offsets.partition_point(|&at| at < byte);
+        let found: Vec<(usize, usize)> = self
+            .regex
+            .find_iter(line)
+            .filter(|found| !found.is_empty())
+            .map(|found| (char_at(found.start()), char_at(found.end())))
+            .collect();
+        let first = found.first()?.0;
+        let (lo, hi) = if chars.len() <= SNIPPET_CHARS {
+            (0, chars.len())
+        } else {
+            let lo = first.saturating_sub(SNIPPET_LEAD);
+            (lo, (lo + SNIPPET_CHARS).min(chars.len()))
+        };
+        let mut snippet = String::new();
+        let mut spans = Vec::new();
+        if lo > 0 {
+            snippet.push('…');
+        }
+        let mut cursor = lo;
+        for (from, to) in found {
+            let (from, to) = (from.max(lo), to.min(hi));
+            if from >= to {
+                continue;
+            }
+            snippet.extend(&chars[cursor..from]);
+            let begin = snippet.len();
+            snippet.extend(&chars[from..to]);
+            spans.push((begin, snippet.len()));
+            cursor = to;
+        }
+        snippet.extend(&chars[cursor..hi]);
+        if hi < chars.len() {
+            snippet.push('…');
+        }
+        Some(LineMatch { snippet, spans })
+    }
+}
+
+/// One matching line, trimmed around its first match.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LineMatch {
+    pub snippet: String,
+    /// Byte ranges of the matches within `snippet`, for highlighting.
+    #[serde(skip)]
+    pub spans: Vec<(usize, usize)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GrepField, Matcher};
+
+    #[test]
+    fn literal_patterns_match_every_occurrence_on_each_line() {
+        let matcher = Matcher::new("plan", false, true).expect("matcher");
+        assert_eq!(matcher.literal(), Some("plan"));
+        let found = matcher.match_lines("Migration Plan\nnothing\nplan, then plan");
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].snippet, "Migration Plan");
+        assert_eq!(found[0].spans, vec![(10, 14)]);
+        assert_eq!(found[1].spans, vec![(0, 4), (11, 15)]);
+        let exact = Matcher::new("a.b", false, false).expect("matcher");
+        assert!(exact.match_lines("axb").is_empty());
+        assert_eq!(exact.match_lines("a.b").len(), 1);
+    }
+
+    #[test]
+    fn long_lines_are_cut_around_the_first_match() {
+        let line = format!("{}needle{}", "x".repeat(200), "y".repeat(200));
+        let matcher = Matcher::new("ne+dle", true, false).expect("matcher");
+        assert_eq!(matcher.literal(), None);
+        let found = matcher.match_lines(&line);
+        let snippet = &found[0].snippet;
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        let (from, to) = found[0].spans[0];
+        assert_eq!(&snippet[from..to], "needle");
+        assert_eq!(snippet.chars().count(), 122);
+    }
+
+    #[test]
+    fn rejects_empty_matches_and_unknown_fields() {
+        assert!(Matcher::new("", false, false).is_err());
+        assert!(Matcher::new("a*", true, false).is_err());
+        assert!(Matcher::new("(", true, false).is_err());
+        assert_eq!(
+            GrepField::parse_list(Some("notes, desc,notes")).expect("fields"),
+            vec![GrepField::Description, GrepField::Notes]
+        );
+        assert_eq!(GrepField::parse_list(None).expect("fields").len(), 4);
+        assert!(GrepField::parse_list(Some("comments")).is_err());
+    }
+}
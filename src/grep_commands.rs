@@ -0,0 +1,108 @@
+use crate::app::{self, GrepHit, SimilarKnot};
+use crate::cli::{GrepArgs, SearchArgs};
+use crate::grep::{GrepField, Matcher};
+use crate::knot_id::display_id;
+use crate::ui::Palette;
+
+/// ANSI code for matched text, as `grep --color` paints it.
+const MATCH_COLOR: &str = "1;31";
+
+pub fn run_grep(app: &app::App, args: GrepArgs) -> Result<(), app::AppError> {
+    let fields =
+        GrepField::parse_list(args.fields.as_deref()).map_err(app::AppError::InvalidArgument)?;
+    let matcher = Matcher::new(&args.pattern, args.regex, args.ignore_case)
+        .map_err(app::AppError::InvalidArgument)?;
+    let hits = crate::trace::measure("grep", || app.grep(&matcher, &fields))?;
+    if args.json {
+        let hits = if args.include_sensitive {
+            hits
+        } else {
+            app.visible_grep_hits(hits)?
+        };
+        crate::print_json(&hits);
+    } else {
+        print!("{}", render_hits(&hits, &Palette::auto()));
+    }
+    Ok(())
+}
+
+pub fn run_search(app: &app::App, args: SearchArgs) -> Result<(), app::AppError> {
+    let hits = if args.semantic {
+        semantic_search(app, &args)?
+    } else {
+        crate::trace::measure("search", || {
+            app.search_knots(&args.query, args.all, args.limit)
+        })?
+    };
+    if args.json {
+        crate::print_json(&hits);
+    } else {
+        print!("{}", render_ranked(&hits, &Palette::auto()));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "semantic")]
+fn semantic_search(app: &app::App, args: &SearchArgs) -> Result<Vec<SimilarKnot>, app::AppError> {
+    let endpoint = app.embedding_endpoint()?;
+    crate::trace::measure("semantic search", || {
+        app.semantic_search(&endpoint, &args.query, args.all, args.limit)
+    })
+}
+
+#[cfg(not(feature = "semantic"))]
+fn semantic_search(_app: &app::App, _args: &SearchArgs) -> Result<Vec<SimilarKnot>, app::AppError> {
+    Err(app::AppError::InvalidArgument(
+        "this kno was built without semantic search; reinstall with \
+         `cargo install knots --features semantic`"
+            .to_string(),
+    ))
+}
+
+/// Best match first: score, knot id, state, and title.
+pub(crate) fn render_ranked(hits: &[SimilarKnot], palette: &Palette) -> String {
+    if hits.is_empty() {
+        return "no matching knots\n".to_string();
+    }
+    let mut out = String::new();
+    for hit in hits {
+        let knot = &hit.knot;
+        let id = knot.alias.as_deref().unwrap_or(display_id(&knot.id));
+        out.push_str(&format!(
+            "{:.2} {} {} {}\n",
+            hit.score,
+            palette.id(id),
+            palette.state(&knot.state),
+            knot.title
+        ));
+    }
+    out
+}
+
+/// One line per match: knot id, field, and the snippet with matches
+/// highlighted.
+pub(crate) fn render_hits(hits: &[GrepHit], palette: &Palette) -> String {
+    let mut out = String::new();
+    for hit in hits {
+        let id = hit.alias.as_deref().unwrap_or(display_id(&hit.id));
+        let field = match &hit.event_type {
+            Some(event_type) => format!("{}:{event_type}", hit.field.as_str()),
+            None => hit.field.as_str().to_string(),
+        };
+        let mut snippet = String::new();
+        let mut cursor = 0;
+        for (from, to) in &hit.line.spans {
+            snippet.push_str(&hit.line.snippet[cursor..*from]);
+            snippet.push_str(&palette.paint(MATCH_COLOR, &hit.line.snippet[*from..*to]));
+            cursor = *to;
+        }
+        snippet.push_str(&hit.line.snippet[cursor..]);
+        out.push_str(&format!(
+            "{} {} {}\n",
+            palette.id(id),
+            palette.dim(&field),
+            snippet
+        ));
+    }
+    out
+}
@@ -0,0 +1,147 @@
+use crate::ui::Palette;
+
+/// Long-form guide shown by `kno help <name>`.
+pub(crate) struct HelpTopic {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub body: &'static str,
+}
+
+pub(crate) const TOPICS: &[HelpTopic] = &[
+    HelpTopic {
+        name: "agents",
+        summary: "Poll, claim, and lease work as an agent.",
+        body: include_str!("../docs/help/agents.md"),
+    },
+    HelpTopic {
+        name: "profiles",
+        summary: "How workflow profiles shape a knot's steps and owners.",
+        body: include_str!("../docs/help/profiles.md"),
+    },
+    HelpTopic {
+        name: "sync",
+        summary: "Push, pull, and where knots data is replicated.",
+        body: include_str!("../docs/help/sync.md"),
+    },
+];
+
+pub(crate) fn find_topic(name: &str) -> Option<&'static HelpTopic> {
+    TOPICS
+        .iter()
+        .find(|topic| topic.name.eq_ignore_ascii_case(name))
+}
+
+/// Handles `kno help <topic>` and `kno help topics`. Anything else (including
+/// `kno help <command>`) is left to clap.
+pub fn maybe_print_topic(args: &[String]) -> bool {
+    let [_, help, name] = args else {
+        return false;
+    };
+    if help != "help" {
+        return false;
+    }
+    let palette = Palette::auto();
+    let lines = if name == "topics" {
+        format_topic_list(&palette)
+    } else if let Some(topic) = find_topic(name) {
+        render_markdown(topic.body, &palette)
+    } else {
+        return false;
+    };
+    crate::pager::print_paged(&lines);
+    true
+}
+
+pub(crate) fn format_topic_list(palette: &Palette) -> Vec<String> {
+    let mut lines = vec![palette.heading("Guides")];
+    lines.extend(
+        TOPICS
+            .iter()
+            .map(|topic| format!("  kno help {:<10} {}", topic.name, topic.summary)),
+    );
+    lines
+}
+
+/// Terminal rendering for the small markdown subset the guides use:
+/// headings, fenced blocks, and inline code.
+pub(crate) fn render_markdown(markdown: &str, palette: &Palette) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            lines.push(format!("    {}", palette.dim(line)));
+        } else if let Some(title) = line.strip_prefix("# ") {
+            lines.push(palette.heading(&title.to_uppercase()));
+        } else if let Some(title) = line.strip_prefix("## ") {
+            lines.push(palette.heading(title));
+        } else {
+            lines.push(render_inline_code(line, palette));
+        }
+    }
+    lines
+}
+
+fn render_inline_code(line: &str, palette: &Palette) -> String {
+    line.split('`')
+        .enumerate()
+        .map(|(index, part)| {
+            if index % 2 == 1 {
+                palette.label(part)
+            } else {
+                part.to_string()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_topic, format_topic_list, maybe_print_topic, render_markdown, TOPICS};
+    use crate::ui::{Palette, Theme};
+
+    fn plain() -> Palette {
+        Palette {
+            enabled: false,
+            theme: Theme::Default,
+        }
+    }
+
+    #[test]
+    fn every_topic_has_a_title_and_is_found_case_insensitively() {
+        for topic in TOPICS {
+            assert!(topic.body.starts_with("# "), "{}", topic.name);
+            assert!(find_topic(&topic.name.to_uppercase()).is_some());
+        }
+        assert!(find_topic("ls").is_none());
+        assert_eq!(format_topic_list(&plain()).len(), TOPICS.len() + 1);
+    }
+
+    #[test]
+    fn render_markdown_handles_headings_fences_and_inline_code() {
+        let lines = render_markdown(
+            "# Title\n## Part\nrun `kno sync` now\n```\nkno push\n```",
+            &plain(),
+        );
+        assert_eq!(
+            lines,
+            vec!["TITLE", "Part", "run kno sync now", "    kno push"]
+        );
+        let colored = Palette {
+            enabled: true,
+            theme: Theme::Default,
+        };
+        assert!(render_markdown("use `kno`", &colored)[0].contains("\u{1b}[36mkno"));
+    }
+
+    #[test]
+    fn maybe_print_topic_only_claims_known_topics() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(!maybe_print_topic(&args(&["kno", "help", "ls"])));
+        assert!(!maybe_print_topic(&args(&["kno", "ls", "sync"])));
+        assert!(!maybe_print_topic(&args(&["kno", "help"])));
+    }
+}
@@ -0,0 +1,81 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const REQUEST_TIMEOUT_SECS: u64 = 60;
+
+/// POSTs a JSON `body` to `endpoint` through `curl` and returns the
+/// response body. The request goes over stdin as a curl config, so neither
+/// the token nor knot text shows up in the process list.
+pub fn post_json(endpoint: &str, api_key: Option<&str>, body: &str) -> Result<String, String> {
+    run_curl(&curl_config(endpoint, api_key, body), endpoint)
+}
+
+/// Runs `curl` with `config` on stdin and returns what it printed; errors
+/// name `target`.
+pub fn run_curl(config: &str, target: &str) -> Result<String, String> {
+    let mut child = Command::new("curl")
+        .args([
+            "-fsS",
+            "--max-time",
+            &REQUEST_TIMEOUT_SECS.to_string(),
+            "-K",
+            "-",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("could not run curl: {err}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(config.as_bytes())
+            .map_err(|err| format!("could not write to curl: {err}"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("curl failed: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "request to {target} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// A double-quoted curl config value.
+pub fn quote_config_value(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn curl_config(endpoint: &str, api_key: Option<&str>, body: &str) -> String {
+    let quote = quote_config_value;
+    let mut config = format!("url = {}\n", quote(endpoint));
+    config.push_str("header = \"Content-Type: application/json\"\n");
+    if let Some(key) = api_key.filter(|key| !key.is_empty()) {
+        config.push_str(&format!(
+            "header = {}\n",
+            quote(&format!("Authorization: Bearer {key}"))
+        ));
+    }
+    config.push_str(&format!("data-binary = {}\n", quote(body)));
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::curl_config;
+
+    #[test]
+    fn curl_config_quotes_the_body_and_token() {
+        let config = curl_config(
+            "http://localhost/v1/embeddings",
+            Some("t0k"),
+            r#"say "hi" \o/"#,
+        );
+        assert!(config.starts_with("url = \"http://localhost/v1/embeddings\"\n"));
+        assert!(config.contains("header = \"Authorization: Bearer t0k\"\n"));
+        assert!(config.ends_with("data-binary = \"say \\\"hi\\\" \\\\o/\"\n"));
+        assert!(!curl_config("http://x", None, "{}").contains("Authorization"));
+    }
+}
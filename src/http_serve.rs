@@ -0,0 +1,165 @@
+use std::io::{BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use crate::app::{App, AppError};
+use crate::{dashboard, graphql};
+
+mod access;
+mod request;
+
+use access::HttpAccess;
+use request::{read_request, DeadlineReader, Request};
+
+/// How long one connection may take to send its request and read the
+/// answer; connections are served one at a time.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Which endpoints `kno serve` exposes over HTTP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpRoutes {
+    /// The dashboard page and its `/api/*` endpoints.
+    pub ui: bool,
+    /// `POST /graphql`.
+    pub graphql: bool,
+}
+
+/// What an HTTP request answers with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Response {
+    pub(crate) status: u16,
+    pub(crate) content_type: &'static str,
+    pub(crate) body: Vec<u8>,
+}
+
+impl Response {
+    pub(crate) fn json(status: u16, value: &Value) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: serde_json::to_vec(value).unwrap_or_default(),
+        }
+    }
+
+    pub(crate) fn error(status: u16, message: &str) -> Self {
+        Self::json(status, &json!({ "error": message }))
+    }
+
+    /// The message of an `error` response, for the access log.
+    fn error_message(&self) -> Option<String> {
+        let body: Value = serde_json::from_slice(&self.body).ok()?;
+        body.get("error")?.as_str().map(str::to_string)
+    }
+}
+
+/// Serves `routes` on `addr` until the process is stopped, one request at
+/// a time. Requests need a token from `config` (default: `server.toml` in
+/// the store) when it lists any, which it must to listen beyond loopback,
+/// and are logged like replication requests. Private knots and drafts are
+/// never served, and nothing served here writes to the repo.
+pub fn serve_http(
+    app: &App,
+    addr: &str,
+    routes: HttpRoutes,
+    config: Option<&Path>,
+) -> Result<(), AppError> {
+    let access = HttpAccess::open(app.store_root(), config)?;
+    let listener = TcpListener::bind(addr)?;
+    let local = listener.local_addr()?;
+    if !local.ip().is_loopback() && !access.has_tokens() {
+        return Err(AppError::InvalidArgument(format!(
+            "serving on {local} needs tokens in {} (or --config); without them, \
+             listen on 127.0.0.1",
+            access.config_path().display()
+        )));
+    }
+    let what = match (routes.ui, routes.graphql) {
+        (true, true) => "dashboard and graphql",
+        (false, true) => "graphql",
+        _ => "dashboard",
+    };
+    println!("serving {what} at http://{local}");
+    std::io::stdout().flush()?;
+    for stream in listener.incoming() {
+        // One client's broken connection must not stop the server.
+        let _ = stream.map(|stream| handle_connection(app, routes, &access, stream));
+    }
+    Ok(())
+}
+
+fn handle_connection(
+    app: &App,
+    routes: HttpRoutes,
+    access: &HttpAccess,
+    stream: TcpStream,
+) -> std::io::Result<()> {
+    let deadline = Instant::now() + REQUEST_TIMEOUT;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(DeadlineReader {
+        stream: &stream,
+        deadline,
+    });
+    let (method, response) = match read_request(&mut reader)? {
+        Ok(request) => (
+            request.method.clone(),
+            respond(app, routes, access, &request),
+        ),
+        Err(response) => (String::new(), response),
+    };
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}; charset=utf-8\r\nContent-Length: {}\r\n\
+         Cache-Control: no-store\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        response.content_type,
+        response.body.len()
+    )?;
+    if method != "HEAD" {
+        stream.write_all(&response.body)?;
+    }
+    stream.flush()
+}
+
+/// The dashboard page holds no knot data, so it loads without a token and
+/// asks for one when its API calls are refused.
+fn respond(app: &App, routes: HttpRoutes, access: &HttpAccess, request: &Request) -> Response {
+    let method = request.method.as_str();
+    let path = request.target.split(['?', '#']).next().unwrap_or_default();
+    if routes.ui && matches!(path, "/" | "/index.html") {
+        return dashboard::route(app, method, path);
+    }
+    access.serve(method, path, request.token.as_deref(), || {
+        route(app, routes, method, path, &request.body)
+    })
+}
+
+fn route(app: &App, routes: HttpRoutes, method: &str, path: &str, body: &[u8]) -> Response {
+    if routes.graphql && path == "/graphql" {
+        if method != "POST" {
+            return Response::error(405, "POST a JSON body with a \"query\" to /graphql");
+        }
+        return Response::json(200, &graphql::execute_request(app, body));
+    }
+    if routes.ui {
+        return dashboard::route(app, method, path);
+    }
+    Response::error(404, "not found")
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        429 => "Too Many Requests",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
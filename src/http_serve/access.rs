@@ -0,0 +1,83 @@
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+
+use crate::app::AppError;
+use crate::db;
+use crate::replication::{
+    permit, server_audit_db_path, Role, ServerConfig, ANONYMOUS, SERVER_CONFIG_FILE,
+};
+
+use super::Response;
+
+/// Token checks, rate limits, and the access log for HTTP requests: the
+/// same `server.toml` tokens and `server.sqlite` log as
+/// `kno serve --replication`, kept in the store directory.
+pub(super) struct HttpAccess {
+    config: ServerConfig,
+    config_path: PathBuf,
+    audit: Connection,
+}
+
+impl HttpAccess {
+    /// Tokens come from `config`, or `server.toml` in `store_root`.
+    pub(super) fn open(store_root: &Path, config: Option<&Path>) -> Result<Self, AppError> {
+        let config_path =
+            config.map_or_else(|| store_root.join(SERVER_CONFIG_FILE), Path::to_path_buf);
+        let audit = db::open_connection(&server_audit_db_path(store_root).to_string_lossy())?;
+        Ok(Self {
+            config: ServerConfig::load(&config_path)?,
+            config_path,
+            audit,
+        })
+    }
+
+    pub(super) fn has_tokens(&self) -> bool {
+        self.config.has_tokens()
+    }
+
+    pub(super) fn config_path(&self) -> &Path {
+        &self.config_path
+    }
+
+    /// Answers `method path` with `handle` when `token` may read, then logs
+    /// the outcome. Every token can read, so only a missing or unknown
+    /// token, or the rate limit, refuses a request.
+    pub(super) fn serve(
+        &self,
+        method: &str,
+        path: &str,
+        token: Option<&str>,
+        handle: impl FnOnce() -> Response,
+    ) -> Response {
+        let client = self
+            .config
+            .initial_client()
+            .or_else(|| token.and_then(|token| self.config.authenticate(token)));
+        let response = match (&client, token) {
+            (None, Some(_)) => Response::error(401, "invalid token"),
+            (None, None) => Response::error(401, "authentication required"),
+            (Some(client), _) => {
+                match permit(Some(client), Some(&self.audit), Role::Reader, path) {
+                    Ok(()) => handle(),
+                    Err(message) => Response::error(429, &message),
+                }
+            }
+        };
+        let outcome = match response.status {
+            200..=299 => "ok",
+            401 | 429 => "denied",
+            _ => "error",
+        };
+        let message = (outcome != "ok")
+            .then(|| response.error_message())
+            .flatten();
+        let name = client.as_ref().map_or(ANONYMOUS, |client| &client.name);
+        if let Err(err) =
+            db::insert_server_access(&self.audit, name, method, path, outcome, message.as_deref())
+        {
+            eprintln!("warning: could not log {method} {path}: {err}");
+        }
+        response
+    }
+}
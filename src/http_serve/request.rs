@@ -0,0 +1,143 @@
+use std::io::{self, BufRead, Read};
+use std::net::TcpStream;
+use std::time::Instant;
+
+use super::Response;
+
+/// Longest request line or header line accepted.
+const MAX_LINE_BYTES: u64 = 8 * 1024;
+/// Most bytes the request line and headers may take together.
+const MAX_HEAD_BYTES: u64 = 64 * 1024;
+/// Request bodies larger than this are refused; queries are small.
+const MAX_BODY_BYTES: usize = 1 << 20;
+
+/// One parsed HTTP request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Request {
+    pub(super) method: String,
+    pub(super) target: String,
+    /// The `Authorization: Bearer` token, if any.
+    pub(super) token: Option<String>,
+    pub(super) body: Vec<u8>,
+}
+
+/// Reads `stream` until `deadline`, so one slow client can only hold up the
+/// server until then.
+pub(super) struct DeadlineReader<'a> {
+    pub(super) stream: &'a TcpStream,
+    pub(super) deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "request took too long",
+            ));
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        let mut stream = self.stream;
+        stream.read(buf)
+    }
+}
+
+/// Reads one request from `reader`. A line, header block, or body over its
+/// limit is answered with the error response instead.
+pub(super) fn read_request(reader: &mut impl BufRead) -> io::Result<Result<Request, Response>> {
+    let mut head = Read::take(&mut *reader, MAX_HEAD_BYTES);
+    let Some(request_line) = read_line(&mut head)? else {
+        return Ok(Err(Response::error(431, "request line too long")));
+    };
+    let mut content_length = 0usize;
+    let mut token = None;
+    loop {
+        let Some(header) = read_line(&mut head)? else {
+            return Ok(Err(Response::error(431, "request headers too large")));
+        };
+        if header.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let (name, value) = (name.trim(), value.trim());
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().unwrap_or(0);
+        } else if name.eq_ignore_ascii_case("authorization") {
+            token = value
+                .split_once(' ')
+                .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+                .map(|(_, token)| token.trim().to_string());
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Ok(Err(Response::error(413, "request body too large")));
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+    let mut parts = request_line.split_whitespace();
+    Ok(Ok(Request {
+        method: parts.next().unwrap_or_default().to_string(),
+        target: parts.next().unwrap_or_default().to_string(),
+        token,
+        body,
+    }))
+}
+
+/// One CRLF- or LF-terminated line, or `None` when it runs past
+/// `MAX_LINE_BYTES` or the head limit before its end.
+fn read_line(head: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = Vec::new();
+    Read::take(&mut *head, MAX_LINE_BYTES).read_until(b'\n', &mut line)?;
+    if !line.ends_with(b"\n") {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{read_request, MAX_HEAD_BYTES, MAX_LINE_BYTES};
+
+    #[test]
+    fn reads_the_bearer_token_and_body() {
+        let raw = "POST /graphql HTTP/1.1\r\nAuthorization: bearer s3cret\r\n\
+                   Content-Length: 2\r\n\r\n{}";
+        let request = read_request(&mut Cursor::new(raw))
+            .expect("read")
+            .expect("request");
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.target, "/graphql");
+        assert_eq!(request.token.as_deref(), Some("s3cret"));
+        assert_eq!(request.body, b"{}");
+    }
+
+    #[test]
+    fn refuses_oversized_lines_headers_and_bodies() {
+        let status = |raw: String| {
+            read_request(&mut Cursor::new(raw))
+                .expect("read")
+                .expect_err("refused")
+                .status
+        };
+        let long_line = format!(
+            "GET /{} HTTP/1.1\r\n\r\n",
+            "a".repeat(MAX_LINE_BYTES as usize)
+        );
+        assert_eq!(status(long_line), 431);
+        let header = format!("X-Pad: {}\r\n", "a".repeat(1000));
+        let many = format!(
+            "GET / HTTP/1.1\r\n{}\r\n",
+            header.repeat(MAX_HEAD_BYTES as usize / 1000)
+        );
+        assert_eq!(status(many), 431);
+        assert_eq!(
+            status("POST / HTTP/1.1\r\nContent-Length: 99999999\r\n\r\n".to_string()),
+            413
+        );
+    }
+}
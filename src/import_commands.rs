@@ -0,0 +1,270 @@
+use crate::cli::{
+    ExportArgs, ExportFilterArgs, ExportHtmlArgs, ExportSubcommands, ImportArgs, ImportJsonlArgs,
+    ImportOrgArgs, ImportRollbackArgs, ImportSubcommands, TransferArgs, VaultArgs,
+    VaultSubcommands,
+};
+use crate::knot_id::display_id;
+use crate::{app, listing, print_json};
+
+pub fn run_import(app: &app::App, args: ImportArgs) -> Result<(), app::AppError> {
+    let args = match args.command {
+        ImportSubcommands::Knots(args) => args,
+        ImportSubcommands::Org(args) => return run_import_org(app, &args),
+        ImportSubcommands::Jsonl(args) => return run_import_jsonl(app, &args),
+        ImportSubcommands::Rollback(args) => return run_import_rollback(app, &args),
+    };
+    let summary = crate::trace::measure("import knots", || {
+        app.import_knots_subtree(&args.from, &args.subtree, args.dry_run)
+    })?;
+    if args.json {
+        print_json(&summary);
+        return Ok(());
+    }
+    let verb = if summary.dry_run {
+        "would import"
+    } else {
+        "imported"
+    };
+    println!(
+        "{} {} knot(s) from {} events={} edges_dropped={}",
+        verb,
+        summary.knots.len(),
+        summary.source.display(),
+        summary.events_written,
+        summary.edges_dropped
+    );
+    if !summary.dry_run {
+        println!("run: {}", summary.run);
+    }
+    for knot in &summary.knots {
+        println!("  {} -> {}", knot.source_id, knot.id);
+    }
+    Ok(())
+}
+
+fn run_import_jsonl(app: &app::App, args: &ImportJsonlArgs) -> Result<(), app::AppError> {
+    let mut options = app::JsonlImportOptions {
+        resume: args.resume,
+        restart: args.restart,
+        dry_run: args.dry_run,
+        workers: args.workers,
+        ..app::JsonlImportOptions::default()
+    };
+    if let Some(chunk_size) = args.chunk_size {
+        options.chunk_size = chunk_size;
+    }
+    if let Some(mapping) = &args.mapping {
+        options.mapping = Some(app::ImportMapping::load(mapping)?);
+    }
+    let stop = crate::interrupt::watch_sigint();
+    let summary = crate::trace::measure("import jsonl", || {
+        app.import_jsonl(&args.path, options, stop)
+    })?;
+    if args.json {
+        print_json(&summary);
+    } else {
+        let verb = if summary.dry_run {
+            "would import"
+        } else {
+            "imported"
+        };
+        println!(
+            "{} {} knot(s) from {} lines={}-{} errors={} records/sec={}",
+            verb,
+            summary.imported,
+            summary.source,
+            summary.start_line,
+            summary.next_line.saturating_sub(1),
+            summary.error_count,
+            summary.records_per_sec
+        );
+        if summary.already_imported > 0 {
+            println!(
+                "  {} already imported before the interruption",
+                summary.already_imported
+            );
+        }
+        if !summary.dry_run {
+            println!("run: {}", summary.run);
+        }
+        for error in &summary.errors {
+            eprintln!("warning: {error}");
+        }
+    }
+    if summary.interrupted {
+        return Err(app::AppError::InvalidArgument(format!(
+            "import interrupted before line {}; rerun with --resume to continue",
+            summary.next_line
+        )));
+    }
+    Ok(())
+}
+
+fn run_import_rollback(app: &app::App, args: &ImportRollbackArgs) -> Result<(), app::AppError> {
+    let rollback = crate::trace::measure("import rollback", || {
+        app.rollback_import(&args.source, args.dry_run)
+    })?;
+    if args.json {
+        print_json(&rollback);
+        return Ok(());
+    }
+    let verb = if rollback.dry_run {
+        "would close"
+    } else {
+        "closed"
+    };
+    println!(
+        "{} {} knot(s) from run(s) {} edges_removed={} skipped={}",
+        verb,
+        rollback.closed.len(),
+        rollback.runs.join(", "),
+        rollback.edges_removed.len(),
+        rollback.skipped.len()
+    );
+    for knot in &rollback.closed {
+        println!("  {} (from {})", display_id(&knot.id), knot.source_id);
+    }
+    Ok(())
+}
+
+pub fn run_transfer(app: &app::App, args: TransferArgs) -> Result<(), app::AppError> {
+    let transfer = crate::trace::measure("transfer", || app.transfer_knot(&args.id, &args.to))?;
+    if args.json {
+        print_json(&transfer);
+        return Ok(());
+    }
+    println!(
+        "moved {} -> {} in {} events={} edges_dropped={}",
+        transfer.source_id,
+        transfer.id,
+        transfer.target.display(),
+        transfer.events_written,
+        transfer.edges_dropped
+    );
+    Ok(())
+}
+
+fn run_import_org(app: &app::App, args: &ImportOrgArgs) -> Result<(), app::AppError> {
+    let text = std::fs::read_to_string(&args.path)?;
+    let summary = crate::trace::measure("import org", || app.import_org(&text, args.dry_run))?;
+    if args.json {
+        print_json(&summary);
+        return Ok(());
+    }
+    let verb = if summary.dry_run {
+        "would update"
+    } else {
+        "updated"
+    };
+    println!(
+        "{} {} knot(s) from {} unchanged={} skipped={}",
+        verb,
+        summary.changed.len(),
+        args.path.display(),
+        summary.unchanged,
+        summary.skipped.len()
+    );
+    for change in &summary.changed {
+        println!("  {} {}", display_id(&change.id), change.fields.join(","));
+    }
+    for skipped in &summary.skipped {
+        println!("  skipped {skipped}");
+    }
+    Ok(())
+}
+
+pub fn run_export(app: &app::App, args: ExportArgs) -> Result<(), app::AppError> {
+    let args = match args.command {
+        ExportSubcommands::Org(args) => args,
+        ExportSubcommands::Html(args) => return run_export_html(app, &args),
+    };
+    let org = app.export_org(export_knots(app, &args.filter)?)?;
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, org)?;
+            println!("exported org outline to {}", path.display());
+        }
+        None => print!("{org}"),
+    }
+    Ok(())
+}
+
+fn run_export_html(app: &app::App, args: &ExportHtmlArgs) -> Result<(), app::AppError> {
+    let knots = export_knots(app, &args.filter)?;
+    let html = crate::dashboard::snapshot_html(app, &knots)?;
+    std::fs::create_dir_all(&args.out)?;
+    let path = args.out.join("index.html");
+    std::fs::write(&path, html)?;
+    println!("exported {} knot(s) to {}", knots.len(), path.display());
+    Ok(())
+}
+
+/// The knots `filter` selects, in `ls` order.
+fn export_knots(
+    app: &app::App,
+    filter: &ExportFilterArgs,
+) -> Result<Vec<app::KnotView>, app::AppError> {
+    let list_filter = listing::KnotListFilter {
+        include_all: true,
+        state: filter.state.clone(),
+        knot_type: filter.knot_type.clone(),
+        profile_id: filter.profile_id.clone(),
+        tags: filter.tags.clone(),
+        custom_fields: crate::run_commands::field_filters(&filter.fields)?,
+        query: filter.query.clone(),
+    };
+    let mut knots = listing::apply_filters(app.list_knots()?, &list_filter);
+    if let Some(root) = &filter.subtree {
+        let members = app.subtree_ids(root)?;
+        knots.retain(|knot| members.contains(&knot.id));
+    }
+    Ok(knots)
+}
+
+const VAULT_PATH_KEY: &str = "vault.path";
+
+pub fn run_vault(app: &app::App, args: VaultArgs) -> Result<(), app::AppError> {
+    let VaultSubcommands::Sync(args) = args.command;
+    let vault = match args.vault {
+        Some(vault) => vault,
+        None => configured_vault(app)?,
+    };
+    let summary = crate::trace::measure("vault sync", || app.sync_vault(&vault, args.dry_run))?;
+    if args.json {
+        print_json(&summary);
+        return Ok(());
+    }
+    let (ingest_verb, write_verb) = if summary.dry_run {
+        ("would apply", "would write")
+    } else {
+        ("applied", "wrote")
+    };
+    println!(
+        "{} note edits to {} knot(s), {} {} note(s) in {} unchanged={}",
+        ingest_verb,
+        summary.ingested.len(),
+        write_verb,
+        summary.written.len(),
+        summary.vault.display(),
+        summary.unchanged
+    );
+    for ingest in &summary.ingested {
+        println!("  {} {}", display_id(&ingest.id), ingest.fields.join(","));
+    }
+    for error in &summary.errors {
+        eprintln!("warning: {error}");
+    }
+    Ok(())
+}
+
+/// The `vault.path` setting; a shared config.toml must not point every
+/// clone's writes at the same folder.
+fn configured_vault(app: &app::App) -> Result<std::path::PathBuf, app::AppError> {
+    let entry = app.config_get(VAULT_PATH_KEY)?;
+    if entry.value.trim().is_empty() || entry.source != "local" {
+        return Err(app::AppError::InvalidArgument(format!(
+            "kno vault sync needs --vault or `kno config set {VAULT_PATH_KEY} <path>` in this clone"
+        )));
+    }
+    Ok(std::path::PathBuf::from(entry.value))
+}
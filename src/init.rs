@@ -1,12 +1,14 @@
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 use crate::app::AppError;
 use crate::db;
 use crate::remote_init::{
-    detect_beads_hooks, init_remote_knots_branch, remote_branch_exists, uninit_remote_knots_branch,
+    detect_beads_hooks, init_remote_branch, remote_branch_exists, uninit_remote_knots_branch,
     RemoteInitError,
 };
+use crate::repo_config::knots_ref;
 
 const ANSI_RESET: &str = "\x1b[0m";
 const ANSI_BOLD_CYAN: &str = "\x1b[1;36m";
@@ -23,40 +25,82 @@ pub(crate) fn init_all(repo_root: &Path, db_path: &str) -> Result<(), AppError>
     init_local_store(repo_root, db_path)?;
     progress_ok("local store initialized")?;
     warn_if_beads_hooks_present(repo_root)?;
-    if remote_branch_exists(repo_root, "origin", "knots")? {
-        progress("found existing remote branch origin/knots")?;
+    let (remote, branch) = knots_ref(&store_root_for_db(db_path));
+    if remote_branch_exists(repo_root, &remote, &branch)? {
+        progress(&format!("found existing remote branch {remote}/{branch}"))?;
         progress("pulling knots from remote")?;
         pull_knots_from_remote(repo_root.to_path_buf(), db_path)?;
         progress_ok("knots pulled from remote")?;
     } else {
-        progress("initializing remote branch origin/knots")?;
+        progress(&format!("initializing remote branch {remote}/{branch}"))?;
         progress_note("this can take a bit...")?;
-        init_remote_knots_branch(repo_root)?;
-        progress_ok("remote branch origin/knots initialized")?;
+        init_remote_branch(repo_root, &remote, &branch)?;
+        progress_ok(&format!("remote branch {remote}/{branch} initialized"))?;
     }
+    install_sync_hooks(repo_root)
+}
+
+/// `kno init --from-remote`: the one-shot path for a new contributor. The
+/// first pull into an empty cache loads the newest snapshots before applying
+/// events, so this only has to make sure the branch exists and report what
+/// landed in each tier.
+pub(crate) fn bootstrap_from_remote(repo_root: &Path, db_path: &str) -> Result<(), AppError> {
+    let started = Instant::now();
+    print_banner("FIT TO BE TIED 🎉")?;
+    let (remote, branch) = knots_ref(&store_root_for_db(db_path));
+    if !remote_branch_exists(repo_root, &remote, &branch)? {
+        return Err(AppError::InvalidArgument(format!(
+            "remote branch {remote}/{branch} does not exist; run `kno init` to create it"
+        )));
+    }
+    progress("initializing local store")?;
+    init_local_store(repo_root, db_path)?;
+    progress(&format!(
+        "fetching {remote}/{branch} and loading the newest snapshots"
+    ))?;
+    let app = crate::app::App::open(db_path, repo_root.to_path_buf())?;
+    let summary = app.pull()?;
+    drop(app);
+    progress_ok(&format!(
+        "applied {} event file(s) on top of the snapshots",
+        summary.index_files + summary.full_files
+    ))?;
+    let [hot, warm, cold] = db::count_tier_rows(&db::open_connection(db_path)?)?;
+    progress_ok(&format!("hot {hot}, warm {warm}, cold {cold}"))?;
+    warn_if_beads_hooks_present(repo_root)?;
+    install_sync_hooks(repo_root)?;
+    progress_note(&format!(
+        "ready in {:.1}s; try `kno ls`",
+        started.elapsed().as_secs_f64()
+    ))
+}
+
+fn install_sync_hooks(repo_root: &Path) -> Result<(), AppError> {
     progress("installing sync hooks (post-merge)")?;
     match crate::git_hooks::install_hooks(repo_root) {
-        Ok(_) => progress_ok("sync hooks installed")?,
-        Err(err) => progress_warn(&format!("sync hook install failed: {err}"))?,
+        Ok(_) => progress_ok("sync hooks installed"),
+        Err(err) => progress_warn(&format!("sync hook install failed: {err}")),
     }
-    Ok(())
 }
 
 pub(crate) fn uninit_all(repo_root: &Path, db_path: &str) -> Result<(), AppError> {
     print_banner("UNTYING THE KNOT 🎉")?;
+    let (remote, branch) = knots_ref(&store_root_for_db(db_path));
     progress("removing local store")?;
     uninit_local_store(repo_root, db_path)?;
     progress_ok("local store removed")?;
-    progress("removing remote branch origin/knots")?;
+    progress(&format!("removing remote branch {remote}/{branch}"))?;
     progress_note("this can take a bit...")?;
-    match uninit_remote_knots_branch(repo_root, "origin", "knots") {
-        Ok(true) => progress_ok("remote branch origin/knots removed")?,
-        Ok(false) => progress_warn("remote branch origin/knots not present")?,
+    match uninit_remote_knots_branch(repo_root, &remote, &branch) {
+        Ok(true) => progress_ok(&format!("remote branch {remote}/{branch} removed"))?,
+        Ok(false) => progress_warn(&format!("remote branch {remote}/{branch} not present"))?,
         Err(RemoteInitError::NotGitRepository) => {
             progress_warn("not a git repository; skipping remote branch cleanup")?;
         }
         Err(RemoteInitError::MissingRemote(_)) => {
-            progress_warn("origin remote is not configured; skipping remote branch cleanup")?;
+            progress_warn(&format!(
+                "{remote} remote is not configured; skipping remote branch cleanup"
+            ))?;
         }
         Err(err) => return Err(err.into()),
     }
@@ -67,8 +111,12 @@ pub(crate) fn init_local_store(repo_root: &Path, db_path: &str) -> Result<(), Ap
     if let Some(parent) = Path::new(db_path).parent() {
         std::fs::create_dir_all(parent)?;
     }
+    crate::cache_location::claim_if_xdg(
+        Path::new(db_path),
+        &crate::project::canonical_or_original(repo_root).join(".knots"),
+    )?;
     progress(&format!("opening cache database at {db_path}"))?;
-    let _ = db::open_connection(db_path)?;
+    let conn = db::open_connection(db_path)?;
     let store_root = crate::project::canonical_or_original(&store_root_for_db(db_path));
     let repo_root = crate::project::canonical_or_original(repo_root);
     let git_store_root = crate::project::canonical_or_original(&repo_root.join(".knots"));
@@ -82,7 +130,7 @@ pub(crate) fn init_local_store(repo_root: &Path, db_path: &str) -> Result<(), Ap
         progress("registering builtin workflows by knot type")?;
         crate::installed_workflows::ensure_builtin_workflows_registered(workflow_root)?;
     }
-    if store_root == git_store_root {
+    if store_root == git_store_root && crate::repo_config::gitignore_managed(&conn, &store_root)? {
         progress("ensuring gitignore includes .knots rule")?;
         ensure_knots_gitignore(&repo_root)?;
     }
@@ -90,7 +138,7 @@ pub(crate) fn init_local_store(repo_root: &Path, db_path: &str) -> Result<(), Ap
     Ok(())
 }
 
-fn pull_knots_from_remote(repo_root: PathBuf, db_path: &str) -> Result<(), AppError> {
+pub(crate) fn pull_knots_from_remote(repo_root: PathBuf, db_path: &str) -> Result<(), AppError> {
     let app = crate::app::App::open(db_path, repo_root)?;
     let _ = app.pull()?;
     Ok(())
@@ -104,6 +152,7 @@ pub(crate) fn uninit_local_store(repo_root: &Path, db_path: &str) -> Result<(),
         remove_gitignore_entries(repo_root)?;
     }
     remove_db_file(db_path)?;
+    crate::cache_location::release_xdg_dir(Path::new(db_path))?;
     if store_root.exists() {
         std::fs::remove_dir_all(&store_root)?;
     }
@@ -111,31 +160,31 @@ pub(crate) fn uninit_local_store(repo_root: &Path, db_path: &str) -> Result<(),
     Ok(())
 }
 
-fn progress(message: &str) -> Result<(), AppError> {
+pub(crate) fn progress(message: &str) -> Result<(), AppError> {
     println!("{ANSI_BOLD_CYAN}•{ANSI_RESET} {message}");
     io::stdout().flush()?;
     Ok(())
 }
 
-fn progress_ok(message: &str) -> Result<(), AppError> {
+pub(crate) fn progress_ok(message: &str) -> Result<(), AppError> {
     println!("{ANSI_BOLD_GREEN}✓{ANSI_RESET} {message}");
     io::stdout().flush()?;
     Ok(())
 }
 
-fn progress_warn(message: &str) -> Result<(), AppError> {
+pub(crate) fn progress_warn(message: &str) -> Result<(), AppError> {
     println!("{ANSI_BOLD_YELLOW}!{ANSI_RESET} {message}");
     io::stdout().flush()?;
     Ok(())
 }
 
-fn progress_note(message: &str) -> Result<(), AppError> {
+pub(crate) fn progress_note(message: &str) -> Result<(), AppError> {
     println!("{ANSI_DIM}{message}{ANSI_RESET}");
     io::stdout().flush()?;
     Ok(())
 }
 
-fn print_banner(title: &str) -> Result<(), AppError> {
+pub(crate) fn print_banner(title: &str) -> Result<(), AppError> {
     println!("{ANSI_BOLD_MAGENTA}{title}{ANSI_RESET}");
     println!("{ANSI_BOLD_CYAN}Welcome to Knots!{ANSI_RESET}");
     println!(
@@ -147,7 +196,7 @@ fn print_banner(title: &str) -> Result<(), AppError> {
     Ok(())
 }
 
-fn warn_if_beads_hooks_present(repo_root: &Path) -> Result<(), AppError> {
+pub(crate) fn warn_if_beads_hooks_present(repo_root: &Path) -> Result<(), AppError> {
     let report = detect_beads_hooks(repo_root);
     if report.is_empty() {
         return Ok(());
@@ -235,8 +284,11 @@ fn remove_db_file(db_path: &str) -> Result<(), AppError> {
     Ok(())
 }
 
-fn store_root_for_db(db_path: &str) -> PathBuf {
+pub(crate) fn store_root_for_db(db_path: &str) -> PathBuf {
     let path = Path::new(db_path);
+    if let Some(store_root) = crate::cache_location::claimed_store_root(path) {
+        return store_root;
+    }
     path.parent()
         .and_then(Path::parent)
         .map(Path::to_path_buf)
@@ -257,234 +309,8 @@ fn contains_knots_ignore(contents: &str) -> bool {
 }
 
 #[cfg(test)]
-mod tests {
-    use std::path::PathBuf;
-    use std::process::Command;
-    use uuid::Uuid;
-
-    use crate::db;
-
-    use super::{init_all, init_local_store, uninit_all, uninit_local_store, KNOTS_IGNORE_RULE};
-
-    fn unique_dir() -> PathBuf {
-        let dir = std::env::temp_dir().join(format!("knots-init-test-{}", Uuid::now_v7()));
-        std::fs::create_dir_all(&dir).expect("temp directory should be creatable");
-        dir
-    }
-
-    fn remove_dir_if_exists(root: &PathBuf) {
-        if root.exists() {
-            let _ = std::fs::remove_dir_all(root);
-        }
-    }
-
-    fn run_git(cwd: &PathBuf, args: &[&str]) {
-        let output = Command::new("git")
-            .arg("-C")
-            .arg(cwd)
-            .args(args)
-            .output()
-            .expect("git command should run");
-        assert!(
-            output.status.success(),
-            "git {:?} failed: {}",
-            args,
-            String::from_utf8_lossy(&output.stderr)
-        );
-    }
-
-    fn setup_repo_with_remote() -> (PathBuf, PathBuf) {
-        let root = unique_dir();
-        let remote = root.join("remote.git");
-        let local = root.join("local");
-
-        std::fs::create_dir_all(&local).expect("local dir should be creatable");
-        run_git(
-            &root,
-            &["init", "--bare", remote.to_str().expect("utf8 path")],
-        );
-        run_git(&local, &["init"]);
-        run_git(&local, &["config", "user.email", "knots@example.com"]);
-        run_git(&local, &["config", "user.name", "Knots Test"]);
-        std::fs::write(local.join("README.md"), "# knots\n").expect("readme should be writable");
-        run_git(&local, &["add", "README.md"]);
-        run_git(&local, &["commit", "-m", "init"]);
-        run_git(&local, &["branch", "-M", "main"]);
-        run_git(
-            &local,
-            &[
-                "remote",
-                "add",
-                "origin",
-                remote.to_str().expect("utf8 path"),
-            ],
-        );
-        run_git(&local, &["push", "-u", "origin", "main"]);
-        let output = Command::new("git")
-            .arg("--git-dir")
-            .arg(&remote)
-            .args(["symbolic-ref", "HEAD", "refs/heads/main"])
-            .output()
-            .expect("git symbolic-ref should run");
-        assert!(
-            output.status.success(),
-            "git symbolic-ref failed: {}",
-            String::from_utf8_lossy(&output.stderr)
-        );
-
-        (root, local)
-    }
-
-    #[test]
-    fn init_local_store_writes_expected_artifacts() {
-        let root = unique_dir();
-        let db_path = root.join(".knots/cache/state.sqlite");
-
-        init_local_store(&root, db_path.to_str().expect("utf8 path"))
-            .expect("local init should work");
-
-        assert!(db_path.exists());
-
-        let gitignore =
-            std::fs::read_to_string(root.join(".gitignore")).expect("gitignore should be readable");
-        assert!(gitignore.lines().any(|line| line == KNOTS_IGNORE_RULE));
-        remove_dir_if_exists(&root);
-    }
-
-    #[test]
-    fn init_local_store_is_idempotent_with_gitignore() {
-        let root = unique_dir();
-        let db_path = root.join(".knots/cache/state.sqlite");
-
-        init_local_store(&root, db_path.to_str().expect("utf8 path"))
-            .expect("first init should work");
-        init_local_store(&root, db_path.to_str().expect("utf8 path"))
-            .expect("second init should remain idempotent");
-
-        let gitignore =
-            std::fs::read_to_string(root.join(".gitignore")).expect("gitignore should be readable");
-        let ignore_count = gitignore
-            .lines()
-            .filter(|line| *line == KNOTS_IGNORE_RULE)
-            .count();
-        assert_eq!(ignore_count, 1);
-        remove_dir_if_exists(&root);
-    }
-
-    #[test]
-    fn init_all_bootstraps_local_store_and_remote_branch() {
-        let (root, local) = setup_repo_with_remote();
-        let db_path = local.join(".knots/cache/state.sqlite");
-
-        init_all(&local, db_path.to_str().expect("utf8 path")).expect("init should succeed");
-
-        let output = Command::new("git")
-            .arg("-C")
-            .arg(&local)
-            .args(["ls-remote", "--heads", "origin", "knots"])
-            .output()
-            .expect("git ls-remote should run");
-        assert!(output.status.success());
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        assert!(stdout.contains("refs/heads/knots"));
-
-        let gitignore = std::fs::read_to_string(local.join(".gitignore"))
-            .expect("gitignore should be readable");
-        assert!(gitignore.lines().any(|line| line == KNOTS_IGNORE_RULE));
-        remove_dir_if_exists(&root);
-    }
-
-    #[test]
-    fn init_all_pulls_knots_when_remote_branch_already_exists() {
-        let (root, local) = setup_repo_with_remote();
-        let local_db_path = local.join(".knots/cache/state.sqlite");
-        init_all(&local, local_db_path.to_str().expect("utf8 path"))
-            .expect("first init should succeed");
-
-        let app = crate::app::App::open(local_db_path.to_str().expect("utf8 path"), local.clone())
-            .expect("app should open");
-        let created = app
-            .create_knot(
-                "Bootstrap knot",
-                Some("pulled from remote"),
-                Some("ready_for_planning"),
-                Some("autopilot"),
-            )
-            .expect("knot should be creatable");
-        app.push().expect("push should succeed");
-
-        let clone = root.join("clone");
-        run_git(
-            &root,
-            &[
-                "clone",
-                root.join("remote.git").to_str().expect("utf8 path"),
-                clone.to_str().expect("utf8 path"),
-            ],
-        );
-        run_git(&clone, &["config", "user.email", "knots@example.com"]);
-        run_git(&clone, &["config", "user.name", "Knots Test"]);
-
-        let clone_db_path = clone.join(".knots/cache/state.sqlite");
-        init_all(&clone, clone_db_path.to_str().expect("utf8 path"))
-            .expect("clone init should succeed");
-
-        let clone_conn = db::open_connection(clone_db_path.to_str().expect("utf8 path"))
-            .expect("clone db should open");
-        let knot = db::get_knot_hot(&clone_conn, &created.id)
-            .expect("knot query should succeed")
-            .expect("knot should be pulled into clone");
-        assert_eq!(knot.title, "Bootstrap knot");
-        assert_eq!(knot.state, "ready_for_planning");
-
-        remove_dir_if_exists(&root);
-    }
-
-    #[test]
-    fn uninit_local_store_cleans_local_artifacts_and_gitignore() {
-        let root = unique_dir();
-        let db_path = root.join(".knots/cache/state.sqlite");
-        let gitignore_path = root.join(".gitignore");
-
-        init_local_store(&root, db_path.to_str().expect("utf8 path"))
-            .expect("local init should succeed");
-        assert!(root.join(".knots").exists());
-        assert!(db_path.exists());
-
-        uninit_local_store(&root, db_path.to_str().expect("utf8 path"))
-            .expect("local uninit should succeed");
-
-        assert!(!root.join(".knots").exists());
-        assert!(!db_path.exists());
-        if gitignore_path.exists() {
-            let gitignore =
-                std::fs::read_to_string(&gitignore_path).expect("gitignore should be readable");
-            assert!(!gitignore.lines().any(|line| line == KNOTS_IGNORE_RULE));
-        }
-        remove_dir_if_exists(&root);
-    }
-
-    #[test]
-    fn uninit_all_removes_remote_and_local_store() {
-        let (root, local) = setup_repo_with_remote();
-        let db_path = local.join(".knots/cache/state.sqlite");
-
-        init_all(&local, db_path.to_str().expect("utf8 path")).expect("init should succeed");
-        uninit_all(&local, db_path.to_str().expect("utf8 path")).expect("uninit should succeed");
-
-        assert!(!local.join(".knots").exists());
-        let output = Command::new("git")
-            .arg("-C")
-            .arg(&local)
-            .args(["ls-remote", "--heads", "origin", "knots"])
-            .output()
-            .expect("git ls-remote should run");
-        assert!(output.status.success());
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        assert!(!stdout.contains("refs/heads/knots"));
-        remove_dir_if_exists(&root);
-    }
-}
+#[path = "init_tests.rs"]
+mod tests;
 
 #[cfg(test)]
 #[path = "init_tests_ext.rs"]
@@ -0,0 +1,260 @@
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use crate::app::{normalize_setting, App, AppError, ConfigScope};
+use crate::init;
+use crate::remote_init::{init_remote_branch, remote_branch_exists};
+use crate::repo_config::validate_ref_name;
+
+/// Values offered as defaults by `kno init --interactive`.
+pub(crate) struct WizardDefaults {
+    pub profiles: Vec<String>,
+    pub profile: String,
+    pub sync_policy: String,
+    pub remote: String,
+    pub branch: String,
+    pub id_prefix: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WizardAnswers {
+    pub profile: String,
+    pub sync_policy: String,
+    pub branch: String,
+    pub id_prefix: String,
+    pub create_remote: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RemoteOutcome {
+    Created,
+    Pulled,
+    Skipped,
+    Failed(String),
+}
+
+pub(crate) fn run_interactive_init(repo_root: &Path, db_path: &str) -> Result<(), AppError> {
+    init::print_banner("FIT TO BE TIED 🎉")?;
+    init::progress("initializing local store")?;
+    init::init_local_store(repo_root, db_path)?;
+    init::warn_if_beads_hooks_present(repo_root)?;
+
+    let app = App::open(db_path, repo_root.to_path_buf())?;
+    let defaults = wizard_defaults(&app, repo_root)?;
+    let answers = ask(&mut io::stdin().lock(), &mut io::stdout(), &defaults)?;
+    apply_answers(&app, &answers)?;
+    let remote_name = defaults.remote;
+    let config_path = app.repo_config_path();
+    init::progress_ok(&format!("wrote {}", config_path.display()))?;
+    drop(app);
+
+    let remote = if answers.create_remote {
+        setup_remote(repo_root, db_path, &remote_name, &answers.branch)?
+    } else {
+        RemoteOutcome::Skipped
+    };
+    let hooks = match crate::git_hooks::install_hooks(repo_root) {
+        Ok(_) => "installed".to_string(),
+        Err(err) => format!("not installed ({err})"),
+    };
+
+    let mut out = io::stdout();
+    let remote = (remote_name.as_str(), remote);
+    for line in summary_lines(db_path, &config_path, &answers, &remote, &hooks) {
+        writeln!(out, "{line}")?;
+    }
+    Ok(())
+}
+
+fn wizard_defaults(app: &App, repo_root: &Path) -> Result<WizardDefaults, AppError> {
+    let workflow_id = app.default_workflow_id()?;
+    let profiles = app
+        .profile_registry()
+        .list()
+        .into_iter()
+        .filter(|profile| profile.workflow_id == workflow_id)
+        .map(|profile| profile.id)
+        .collect();
+    let id_prefix = match app.config_get("id_prefix")?.value {
+        value if value.is_empty() => crate::knot_id::repo_slug(repo_root),
+        value => value,
+    };
+    let (remote, branch) = app.knots_ref();
+    Ok(WizardDefaults {
+        profiles,
+        profile: app.default_profile_id()?,
+        sync_policy: app.config_get("sync_policy")?.value,
+        remote,
+        branch,
+        id_prefix,
+    })
+}
+
+/// Asks each setup question in turn. Blank input or end of input keeps the
+/// default; invalid input is explained and the question is asked again.
+pub(crate) fn ask<R: BufRead, W: Write>(
+    input: &mut R,
+    out: &mut W,
+    defaults: &WizardDefaults,
+) -> Result<WizardAnswers, AppError> {
+    if !defaults.profiles.is_empty() {
+        writeln!(out, "available profiles:")?;
+        for (index, profile) in defaults.profiles.iter().enumerate() {
+            writeln!(out, "  {}. {}", index + 1, profile)?;
+        }
+    }
+    let profile = prompt(input, out, "default profile", &defaults.profile, |raw| {
+        pick_profile(&defaults.profiles, raw)
+    })?;
+    let sync_policy = prompt(
+        input,
+        out,
+        "sync policy (auto, always, never)",
+        &defaults.sync_policy,
+        |raw| normalize_setting("sync_policy", raw),
+    )?;
+    let branch = prompt(
+        input,
+        out,
+        "remote branch name",
+        &defaults.branch,
+        validate_ref_name,
+    )?;
+    let id_prefix = prompt(input, out, "knot id prefix", &defaults.id_prefix, |raw| {
+        normalize_setting("id_prefix", raw)
+    })?;
+    let question = format!(
+        "create or pull remote branch {}/{branch} now (y/n)",
+        defaults.remote
+    );
+    let create_remote = prompt(input, out, &question, "y", parse_yes_no)? == "y";
+    Ok(WizardAnswers {
+        profile,
+        sync_policy,
+        branch,
+        id_prefix,
+        create_remote,
+    })
+}
+
+fn prompt<R, W, F>(
+    input: &mut R,
+    out: &mut W,
+    label: &str,
+    default: &str,
+    validate: F,
+) -> Result<String, AppError>
+where
+    R: BufRead,
+    W: Write,
+    F: Fn(&str) -> Result<String, String>,
+{
+    let mut line = String::new();
+    loop {
+        write!(out, "{label} [{default}]: ")?;
+        out.flush()?;
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(out)?;
+            return Ok(default.to_string());
+        }
+        let answer = line.trim();
+        let answer = if answer.is_empty() { default } else { answer };
+        match validate(answer) {
+            Ok(value) => return Ok(value),
+            Err(reason) => writeln!(out, "{reason}")?,
+        }
+    }
+}
+
+fn pick_profile(profiles: &[String], raw: &str) -> Result<String, String> {
+    if let Ok(index) = raw.parse::<usize>() {
+        if (1..=profiles.len()).contains(&index) {
+            return Ok(profiles[index - 1].clone());
+        }
+    }
+    if profiles.is_empty() || profiles.iter().any(|profile| profile == raw) {
+        return Ok(raw.to_string());
+    }
+    Err(format!(
+        "'{}' is not an available profile; enter a name or a number between 1 and {}",
+        raw,
+        profiles.len()
+    ))
+}
+
+fn parse_yes_no(raw: &str) -> Result<String, String> {
+    match raw.to_ascii_lowercase().as_str() {
+        "y" | "yes" => Ok("y".to_string()),
+        "n" | "no" => Ok("n".to_string()),
+        _ => Err("answer y or n".to_string()),
+    }
+}
+
+fn apply_answers(app: &App, answers: &WizardAnswers) -> Result<(), AppError> {
+    app.set_repo_default_profile_id(&answers.profile, false)?;
+    app.config_set("sync_policy", &answers.sync_policy, ConfigScope::Repo)?;
+    app.config_set("id_prefix", &answers.id_prefix, ConfigScope::Repo)?;
+    app.set_repo_knots_ref(Some(&answers.branch), None)
+}
+
+fn setup_remote(
+    repo_root: &Path,
+    db_path: &str,
+    remote: &str,
+    branch: &str,
+) -> Result<RemoteOutcome, AppError> {
+    let outcome = match remote_branch_exists(repo_root, remote, branch) {
+        Ok(true) => {
+            init::progress(&format!("pulling knots from {remote}/{branch}"))?;
+            init::pull_knots_from_remote(repo_root.to_path_buf(), db_path)
+                .map(|_| RemoteOutcome::Pulled)
+                .unwrap_or_else(|err| RemoteOutcome::Failed(err.to_string()))
+        }
+        Ok(false) => {
+            init::progress(&format!("initializing remote branch {remote}/{branch}"))?;
+            init::progress_note("this can take a bit...")?;
+            init_remote_branch(repo_root, remote, branch)
+                .map(|_| RemoteOutcome::Created)
+                .unwrap_or_else(|err| RemoteOutcome::Failed(err.to_string()))
+        }
+        Err(err) => RemoteOutcome::Failed(err.to_string()),
+    };
+    match &outcome {
+        RemoteOutcome::Failed(reason) => {
+            init::progress_warn(&format!("remote branch setup failed: {reason}"))?
+        }
+        _ => init::progress_ok(&format!("remote branch {remote}/{branch} ready"))?,
+    }
+    Ok(outcome)
+}
+
+fn summary_lines(
+    db_path: &str,
+    config_path: &Path,
+    answers: &WizardAnswers,
+    (remote_name, remote): &(&str, RemoteOutcome),
+    hooks: &str,
+) -> Vec<String> {
+    let remote = match remote {
+        RemoteOutcome::Created => "created".to_string(),
+        RemoteOutcome::Pulled => "existed; pulled".to_string(),
+        RemoteOutcome::Skipped => "skipped; run `kno init-remote` later".to_string(),
+        RemoteOutcome::Failed(reason) => format!("failed ({reason})"),
+    };
+    vec![
+        "summary:".to_string(),
+        format!("  cache database    {db_path}"),
+        format!("  repo config       {}", config_path.display()),
+        format!("    default_profile {}", answers.profile),
+        format!("    sync_policy     {}", answers.sync_policy),
+        format!("    branch          {}", answers.branch),
+        format!("    id_prefix       {}", answers.id_prefix),
+        format!(
+            "  remote branch     {remote_name}/{} ({remote})",
+            answers.branch
+        ),
+        format!("  sync hooks        {hooks}"),
+        "run `kno push` to share .knots/config.toml with other clones".to_string(),
+    ]
+}
@@ -0,0 +1,52 @@
+use std::io::Cursor;
+
+use crate::init_wizard::{ask, WizardAnswers, WizardDefaults};
+
+fn defaults() -> WizardDefaults {
+    WizardDefaults {
+        profiles: vec!["autopilot".to_string(), "semiauto".to_string()],
+        profile: "autopilot".to_string(),
+        sync_policy: "auto".to_string(),
+        remote: "origin".to_string(),
+        branch: "knots".to_string(),
+        id_prefix: "crate".to_string(),
+    }
+}
+
+fn run(input: &str) -> (WizardAnswers, String) {
+    let mut out = Vec::new();
+    let answers = ask(&mut Cursor::new(input), &mut out, &defaults()).expect("wizard answers");
+    (answers, String::from_utf8(out).expect("utf8 output"))
+}
+
+#[test]
+fn blank_answers_and_end_of_input_keep_defaults() {
+    let (answers, out) = run("\n\n");
+    assert_eq!(
+        answers,
+        WizardAnswers {
+            profile: "autopilot".to_string(),
+            sync_policy: "auto".to_string(),
+            branch: "knots".to_string(),
+            id_prefix: "crate".to_string(),
+            create_remote: true,
+        }
+    );
+    assert!(out.contains("  2. semiauto"));
+    assert!(out.contains("knot id prefix [crate]: "));
+}
+
+#[test]
+fn invalid_answers_are_explained_and_asked_again() {
+    let (answers, out) =
+        run("nope\n2\nsometimes\nNEVER\nbad branch\ntracker\nOps!\nops\nmaybe\nn\n");
+    assert_eq!(answers.profile, "semiauto");
+    assert_eq!(answers.sync_policy, "never");
+    assert_eq!(answers.branch, "tracker");
+    assert_eq!(answers.id_prefix, "ops");
+    assert!(!answers.create_remote);
+    assert!(out.contains("'nope' is not an available profile"));
+    assert!(out.contains("'bad branch' is not a valid branch or remote name"));
+    assert!(out.contains("answer y or n"));
+    assert!(out.contains("origin/tracker now"));
+}
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::profile::{normalize_profile_id, ProfileError};
+use crate::profile::{normalize_profile_id, DoneRequirement, ProfileError};
 
 use super::profile_json::{build_json_profile, BundleIndexes};
 use super::{PromptDefinition, PromptParamDefinition, WorkflowDefinition};
@@ -66,6 +66,8 @@ pub(crate) struct JsonProfileSection {
     pub outputs: BTreeMap<String, JsonOutputEntry>,
     #[serde(default)]
     pub executors: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub done_requires: Vec<DoneRequirement>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use serde::Deserialize;
 
-use crate::profile::{normalize_profile_id, OwnerKind, ProfileError, StepOwner};
+use crate::profile::{normalize_profile_id, DoneRequirement, OwnerKind, ProfileError, StepOwner};
 
 use super::bundle_json::{
     JsonKnotsBundle, JsonOutputEntry, JsonPhaseSection, JsonProfileSection, JsonPromptOutcome,
@@ -72,6 +72,8 @@ pub(crate) struct BundleProfileSection {
     pub outputs: BTreeMap<String, BundleOutputEntry>,
     #[serde(default)]
     pub overrides: BTreeMap<String, String>,
+    #[serde(default)]
+    pub done_requires: Vec<DoneRequirement>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -186,6 +188,7 @@ fn build_json_profiles(
                 })
                 .collect(),
             executors: profile.overrides,
+            done_requires: profile.done_requires,
         })
         .collect()
 }
@@ -133,6 +133,7 @@ fn assemble_profile(
         action_prompts,
         prompt_acceptance,
         review_hints,
+        done_requires: profile_section.done_requires.clone(),
     })
 }
 
@@ -72,3 +72,34 @@ fn build_prompt_params_uses_empty_output_when_state_omits_artifact() {
     );
     assert!(!params.contains_key("output_hint"));
 }
+
+#[test]
+fn done_requires_parses_into_the_profile_and_rejects_unknown_items() {
+    use crate::profile::DoneRequirement;
+
+    let strict = SAMPLE_BUNDLE.replace(
+        "phases = [\"main\"]\n",
+        "phases = [\"main\"]\ndone_requires = [\"handoff_capsule\", \"linked_commit\"]\n",
+    );
+    let workflow = parse_bundle_toml(&strict).expect("bundle should parse");
+    let profile = workflow.require_profile("autopilot").expect("profile");
+    assert_eq!(
+        profile.done_requires,
+        vec![
+            DoneRequirement::HandoffCapsule,
+            DoneRequirement::LinkedCommit
+        ]
+    );
+    let plain = parse_bundle_toml(SAMPLE_BUNDLE).expect("bundle should parse");
+    assert!(plain
+        .require_profile("autopilot")
+        .expect("profile")
+        .done_requires
+        .is_empty());
+
+    let unknown = SAMPLE_BUNDLE.replace(
+        "phases = [\"main\"]\n",
+        "phases = [\"main\"]\ndone_requires = [\"signed_off\"]\n",
+    );
+    assert!(parse_bundle_toml(&unknown).is_err());
+}
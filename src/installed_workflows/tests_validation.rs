@@ -95,6 +95,7 @@ fn profile_def_validates_empty_phase() {
         phases: Vec::new(),
         outputs: BTreeMap::new(),
         overrides: BTreeMap::new(),
+        done_requires: Vec::new(),
     };
     let err = build_profile_definition("wf", "empty", &empty, &states, &steps, &phases, &prompts)
         .expect_err("empty profile should fail");
@@ -107,6 +108,7 @@ fn broken_profile(phases: &[&str]) -> BundleProfileSection {
         phases: phases.iter().map(|s| s.to_string()).collect(),
         outputs: BTreeMap::new(),
         overrides: BTreeMap::new(),
+        done_requires: Vec::new(),
     }
 }
 
@@ -0,0 +1,29 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Turns Ctrl-C into a flag long-running commands poll, so they can save
+/// their progress before exiting. Returns the flag.
+pub fn watch_sigint() -> &'static AtomicBool {
+    #[cfg(unix)]
+    {
+        const SIGINT: i32 = 2;
+        extern "C" fn on_sigint(_signal: i32) {
+            INTERRUPTED.store(true, Ordering::SeqCst);
+        }
+        // SAFETY: the handler only stores to an atomic, which is
+        // async-signal-safe.
+        unsafe {
+            libc_signal(SIGINT, on_sigint as extern "C" fn(i32) as usize);
+        }
+    }
+    &INTERRUPTED
+}
+
+#[cfg(unix)]
+unsafe fn libc_signal(signum: i32, handler: usize) -> usize {
+    extern "C" {
+        fn signal(signum: i32, handler: usize) -> usize;
+    }
+    unsafe { signal(signum, handler) }
+}
@@ -0,0 +1,105 @@
+use crate::app;
+use crate::cli::{AutomergeHookArgs, BranchArgs, LinkArgs, OpenArgs, PrBodyArgs};
+
+pub fn run_open(app: &app::App, args: OpenArgs) -> Result<(), app::AppError> {
+    let link = crate::trace::measure("open", || app.knot_link(&args.id, args.link.as_deref()))?;
+    if args.print {
+        println!("{}", link.url);
+        return Ok(());
+    }
+    crate::links::open_url(&link.url).map_err(|err| {
+        app::AppError::InvalidArgument(format!(
+            "could not open {} ({err}); rerun with --print",
+            link.url
+        ))
+    })?;
+    println!("opened {} {}", link.name, link.url);
+    Ok(())
+}
+
+pub fn run_link(app: &app::App, args: LinkArgs) -> Result<(), app::AppError> {
+    let (kind, target) = match (args.commit.as_deref(), args.branch.as_deref()) {
+        (Some(commit), _) => (app::GitLinkKind::Commit, commit),
+        (None, Some(branch)) => (app::GitLinkKind::Branch, branch),
+        (None, None) => unreachable!("clap requires --commit or --branch"),
+    };
+    let link = crate::trace::measure("link", || app.link_git(&args.id, kind, target))?;
+    if args.json {
+        crate::print_json(&link);
+        return Ok(());
+    }
+    println!(
+        "linked {} {} to {}",
+        link.kind,
+        short_target(&link),
+        args.id
+    );
+    Ok(())
+}
+
+fn short_target(link: &crate::db::GitLinkRecord) -> &str {
+    if link.kind == app::GitLinkKind::Commit.as_str() {
+        &link.target[..link.target.len().min(12)]
+    } else {
+        &link.target
+    }
+}
+
+pub fn run_branch(app: &app::App, args: BranchArgs) -> Result<(), app::AppError> {
+    let branch = crate::trace::measure("branch", || {
+        app.knot_branch(&args.id, args.create, args.checkout)
+    })?;
+    if args.json {
+        crate::print_json(&branch);
+    } else if branch.checked_out {
+        println!("switched to {}", branch.branch);
+    } else if branch.created {
+        println!("created {}", branch.branch);
+    } else {
+        println!("{}", branch.branch);
+    }
+    Ok(())
+}
+
+pub fn run_pr_body(app: &app::App, args: PrBodyArgs) -> Result<(), app::AppError> {
+    let body = crate::trace::measure("pr_body", || app.pr_body(&args.id))?;
+    if args.json {
+        crate::print_json(&body);
+    } else {
+        print!("{}", crate::pr_body::render_pr_body(&body));
+    }
+    Ok(())
+}
+
+pub fn run_automerge_hook(app: &app::App, args: AutomergeHookArgs) -> Result<(), app::AppError> {
+    let merged = app::MergedPr {
+        commit: args.commit,
+        pr: args.pr,
+        branch: args.branch,
+        message: args.message,
+        knots: args.knots,
+        actor: app::StateActorMetadata {
+            actor_kind: Some(args.actor_kind.unwrap_or_else(|| "agent".to_string())),
+            agent_name: Some(
+                args.agent_name
+                    .unwrap_or_else(|| "kno automerge-hook".to_string()),
+            ),
+            agent_model: args.agent_model,
+            agent_version: args.agent_version,
+        },
+    };
+    let outcomes = crate::trace::measure("automerge_hook", || app.automerge_hook(&merged))?;
+    if args.json {
+        crate::print_json(&outcomes);
+        return Ok(());
+    }
+    for outcome in outcomes {
+        let id = crate::knot_id::display_id(&outcome.id);
+        match (outcome.to_state, outcome.skipped) {
+            (Some(to), _) => println!("{id}: {} -> {to}", outcome.from_state),
+            (None, Some(reason)) => println!("{id}: linked only ({reason})"),
+            (None, None) => println!("{id}: linked"),
+        }
+    }
+    Ok(())
+}
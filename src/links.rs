@@ -0,0 +1,89 @@
+use std::io;
+use std::process::{Command, Stdio};
+
+use crate::app::KnotView;
+
+/// Renders a `kno open` URL template for `knot`. Placeholders:
+/// `{id}`, `{short_id}`, `{alias}`, `{title}`, `{state}`, `{type}`,
+/// `{priority}`, and `{tag:NAME}` for the value of a `NAME:value` tag.
+/// Values are percent-encoded; unknown or empty placeholders are errors so
+/// a half-filled URL never reaches the browser.
+pub fn render_link(template: &str, knot: &KnotView) -> Result<String, String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("unclosed '{{' in link template '{template}'"))?;
+        let name = after[..end].trim();
+        let value = placeholder_value(name, knot)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| format!("knot '{}' has no value for {{{name}}}", knot.id))?;
+        rendered.push_str(&percent_encode(&value));
+        rest = &after[end + 1..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+fn placeholder_value(name: &str, knot: &KnotView) -> Option<String> {
+    if let Some(tag) = name.strip_prefix("tag:") {
+        let prefix = format!("{}:", tag.trim().to_ascii_lowercase());
+        return knot
+            .tags
+            .iter()
+            .find_map(|value| value.strip_prefix(&prefix))
+            .map(|value| value.trim().to_string());
+    }
+    match name {
+        "id" => Some(knot.id.clone()),
+        "short_id" => Some(crate::knot_id::display_id(&knot.id).to_string()),
+        "alias" => knot.alias.clone(),
+        "title" => Some(knot.title.clone()),
+        "state" => Some(knot.state.clone()),
+        "type" => Some(knot.knot_type.to_string()),
+        "priority" => knot.priority.map(|priority| priority.to_string()),
+        _ => None,
+    }
+}
+
+/// Encodes everything outside the RFC 3986 unreserved set.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Opens `url` with `$BROWSER` when set, else the platform opener.
+pub fn open_url(url: &str) -> io::Result<()> {
+    let mut command = match std::env::var("BROWSER") {
+        Ok(browser) if !browser.trim().is_empty() => Command::new(browser.trim()),
+        _ if cfg!(target_os = "macos") => Command::new("open"),
+        _ if cfg!(target_os = "windows") => {
+            let mut command = Command::new("cmd");
+            command.args(["/C", "start", ""]);
+            command
+        }
+        _ => Command::new("xdg-open"),
+    };
+    let status = command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("browser exited with {status}")))
+    }
+}
@@ -0,0 +1,75 @@
+use crate::app::KnotView;
+use crate::domain::knot_type::KnotType;
+use crate::links::render_link;
+
+fn knot() -> KnotView {
+    KnotView {
+        id: "knots-a1b2".to_string(),
+        alias: None,
+        title: "Fix login & signup".to_string(),
+        state: "implementation".to_string(),
+        updated_at: "2026-10-16T00:00:00Z".to_string(),
+        body: None,
+        description: None,
+        acceptance: None,
+        estimate: None,
+        priority: Some(1),
+        knot_type: KnotType::default(),
+        tags: vec!["pr:42".to_string(), "area:auth".to_string()],
+        custom_fields: Default::default(),
+        notes: vec![],
+        handoff_capsules: vec![],
+        invariants: vec![],
+        step_history: vec![],
+        gate: None,
+        lease: None,
+        lease_id: None,
+        lease_expiry_ts: 0,
+        lease_agent: None,
+        workflow_id: "work_sdlc".to_string(),
+        profile_id: "autopilot".to_string(),
+        profile_etag: None,
+        deferred_from_state: None,
+        blocked_from_state: None,
+        created_at: None,
+        snoozed_until: None,
+        step_metadata: None,
+        next_step_metadata: None,
+        edges: vec![],
+        child_summaries: vec![],
+        git_links: vec![],
+        criteria: Vec::new(),
+        handbacks: Vec::new(),
+        plan: None,
+    }
+}
+
+#[test]
+fn renders_fields_and_tag_values_percent_encoded() {
+    let knot = knot();
+    assert_eq!(
+        render_link("https://git.example/pull/{tag:pr}", &knot).expect("pr"),
+        "https://git.example/pull/42"
+    );
+    assert_eq!(
+        render_link(
+            "https://ci.example/?q={short_id}&t={title}&p={priority}",
+            &knot
+        )
+        .expect("ci"),
+        "https://ci.example/?q=a1b2&t=Fix%20login%20%26%20signup&p=1"
+    );
+    assert_eq!(
+        render_link("https://docs.example/{tag:AREA}/{id}", &knot).expect("docs"),
+        "https://docs.example/auth/knots-a1b2"
+    );
+}
+
+#[test]
+fn missing_or_unknown_placeholders_are_errors() {
+    let knot = knot();
+    assert!(render_link("https://x/{tag:issue}", &knot).is_err());
+    assert!(render_link("https://x/{alias}", &knot).is_err());
+    assert!(render_link("https://x/{nope}", &knot).is_err());
+    assert!(render_link("https://x/{id", &knot).is_err());
+}
@@ -0,0 +1,98 @@
+use crate::app::{self, LintReport};
+use crate::cli::LintArgs;
+use crate::knot_id::display_id;
+use crate::ui::Palette;
+
+pub fn run_lint(app: &app::App, args: LintArgs) -> Result<(), app::AppError> {
+    let report = crate::trace::measure(
+        "lint",
+        || {
+            if args.fix {
+                app.lint_fix()
+            } else {
+                app.lint()
+            }
+        },
+    )?;
+    if args.json {
+        crate::print_json(&report);
+    } else {
+        print!("{}", render_lint(&report, &Palette::auto()));
+    }
+    if !args.fix && !report.dangling_edges.is_empty() {
+        eprintln!("kno lint --fix to remove dangling edges");
+    }
+    let issues = report.issue_count();
+    if issues > 0 {
+        return Err(app::AppError::InvalidArgument(format!(
+            "lint found {issues} issue(s)"
+        )));
+    }
+    Ok(())
+}
+
+/// One section per kind of finding, skipping the empty ones.
+pub(crate) fn render_lint(report: &LintReport, palette: &Palette) -> String {
+    if report.issue_count() == 0 {
+        return "no lint issues\n".to_string();
+    }
+    let name =
+        |id: &str, alias: &Option<String>| palette.id(alias.as_deref().unwrap_or(display_id(id)));
+    let mut out = String::new();
+    let mut section = |title: &str, lines: Vec<String>| {
+        if lines.is_empty() {
+            return;
+        }
+        out.push_str(&format!("{} ({})\n", palette.heading(title), lines.len()));
+        for line in lines {
+            out.push_str(&format!("  {line}\n"));
+        }
+    };
+    section(
+        "Orphans: no parent and no tags",
+        report
+            .orphans
+            .iter()
+            .map(|knot| {
+                format!(
+                    "{} [{}] {}",
+                    name(&knot.id, &knot.alias),
+                    knot.state,
+                    knot.title
+                )
+            })
+            .collect(),
+    );
+    section(
+        "Dangling edges",
+        report
+            .dangling_edges
+            .iter()
+            .map(|edge| format!("{} {} {}", edge.src, edge.kind, edge.dst))
+            .collect(),
+    );
+    section(
+        "Notes naming missing knots",
+        report
+            .missing_note_refs
+            .iter()
+            .map(|missing| {
+                format!(
+                    "{} note {} mentions {}",
+                    name(&missing.knot.id, &missing.knot.alias),
+                    palette.dim(&missing.note_entry_id),
+                    missing.reference
+                )
+            })
+            .collect(),
+    );
+    section(
+        "Deferred without deferred_from_state",
+        report
+            .deferred_without_origin
+            .iter()
+            .map(|knot| format!("{} {}", name(&knot.id, &knot.alias), knot.title))
+            .collect(),
+    );
+    out
+}
@@ -9,6 +9,125 @@ pub struct DisplayKnot {
     pub depth: usize,
 }
 
+/// A column `kno ls` can render in table mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    Id,
+    Alias,
+    State,
+    Priority,
+    Type,
+    Tags,
+    Updated,
+    Assignee,
+    Due,
+    Title,
+}
+
+/// One table column; `width` overrides the kind's default width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    pub kind: ColumnKind,
+    pub width: Option<usize>,
+}
+
+const COLUMN_NAMES: [(&str, ColumnKind); 10] = [
+    ("id", ColumnKind::Id),
+    ("alias", ColumnKind::Alias),
+    ("state", ColumnKind::State),
+    ("priority", ColumnKind::Priority),
+    ("type", ColumnKind::Type),
+    ("tags", ColumnKind::Tags),
+    ("updated", ColumnKind::Updated),
+    ("assignee", ColumnKind::Assignee),
+    ("due", ColumnKind::Due),
+    ("title", ColumnKind::Title),
+];
+
+impl ColumnKind {
+    pub fn name(self) -> &'static str {
+        COLUMN_NAMES
+            .iter()
+            .find(|(_, kind)| *kind == self)
+            .map_or("", |(name, _)| name)
+    }
+
+    /// Table heading; short enough to fit the default width.
+    pub fn header(self) -> String {
+        match self {
+            ColumnKind::Priority => "PRI".to_string(),
+            kind => kind.name().to_ascii_uppercase(),
+        }
+    }
+
+    pub fn default_width(self) -> usize {
+        match self {
+            ColumnKind::Id => 10,
+            ColumnKind::Alias | ColumnKind::Assignee => 12,
+            ColumnKind::State => 24,
+            ColumnKind::Priority => 3,
+            ColumnKind::Type => 7,
+            ColumnKind::Tags => 20,
+            ColumnKind::Updated | ColumnKind::Due => 10,
+            ColumnKind::Title => 40,
+        }
+    }
+}
+
+impl Column {
+    /// Rendered width; an unsized title in the last position is unbounded.
+    pub fn width(&self, last: bool) -> Option<usize> {
+        match (self.width, self.kind, last) {
+            (Some(width), _, _) => Some(width),
+            (None, ColumnKind::Title, true) => None,
+            (None, kind, _) => Some(kind.default_width()),
+        }
+    }
+}
+
+/// Parses a column list such as `id,state,title:50,tags`. An empty spec
+/// yields no columns, which keeps the classic one-line layout.
+pub fn parse_columns(spec: &str) -> Result<Vec<Column>, String> {
+    let mut columns = Vec::new();
+    for raw in spec.split(',').map(str::trim).filter(|raw| !raw.is_empty()) {
+        let (name, width) = match raw.split_once(':') {
+            Some((name, width)) => {
+                let width = width
+                    .trim()
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|width| *width > 0)
+                    .ok_or_else(|| format!("column '{raw}' needs a positive width"))?;
+                (name.trim(), Some(width))
+            }
+            None => (raw, None),
+        };
+        let name = name.to_ascii_lowercase();
+        let kind = COLUMN_NAMES
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, kind)| *kind)
+            .ok_or_else(|| {
+                let known: Vec<_> = COLUMN_NAMES.iter().map(|(name, _)| *name).collect();
+                format!("unknown column '{name}'; expected {}", known.join(", "))
+            })?;
+        columns.push(Column { kind, width });
+    }
+    Ok(columns)
+}
+
+/// Canonical spelling of `columns`, as stored in the `ls.columns` setting.
+pub fn format_columns(columns: &[Column]) -> String {
+    columns
+        .iter()
+        .map(|column| match column.width {
+            Some(width) => format!("{}:{width}", column.kind.name()),
+            None => column.kind.name().to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 type ChildrenByParent = HashMap<String, Vec<String>>;
 type ChildIds = HashSet<String>;
 type BlockersByKnot = HashMap<String, Vec<String>>;
@@ -11,9 +11,11 @@ fn knot(id: &str, state: &str) -> KnotView {
         body: None,
         description: None,
         acceptance: None,
+        estimate: None,
         priority: None,
         knot_type: crate::domain::knot_type::KnotType::default(),
         tags: Vec::new(),
+        custom_fields: Default::default(),
         notes: Vec::new(),
         handoff_capsules: Vec::new(),
         invariants: Vec::new(),
@@ -29,10 +31,15 @@ fn knot(id: &str, state: &str) -> KnotView {
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: None,
+        snoozed_until: None,
         step_metadata: None,
         next_step_metadata: None,
         edges: Vec::new(),
         child_summaries: vec![],
+        git_links: vec![],
+        criteria: Vec::new(),
+        handbacks: Vec::new(),
+        plan: None,
     }
 }
 
@@ -1,5 +1,5 @@
 use crate::app::{EdgeView, KnotView};
-use crate::list_layout::layout_knots;
+use crate::list_layout::{format_columns, layout_knots, parse_columns, Column, ColumnKind};
 
 fn knot(id: &str, alias: Option<&str>, title: &str, state: &str) -> KnotView {
     KnotView {
@@ -11,9 +11,11 @@ fn knot(id: &str, alias: Option<&str>, title: &str, state: &str) -> KnotView {
         body: None,
         description: None,
         acceptance: None,
+        estimate: None,
         priority: None,
         knot_type: crate::domain::knot_type::KnotType::default(),
         tags: Vec::new(),
+        custom_fields: Default::default(),
         notes: Vec::new(),
         handoff_capsules: Vec::new(),
         invariants: Vec::new(),
@@ -29,10 +31,15 @@ fn knot(id: &str, alias: Option<&str>, title: &str, state: &str) -> KnotView {
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: None,
+        snoozed_until: None,
         step_metadata: None,
         next_step_metadata: None,
         edges: Vec::new(),
         child_summaries: vec![],
+        git_links: vec![],
+        criteria: Vec::new(),
+        handbacks: Vec::new(),
+        plan: None,
     }
 }
 
@@ -150,3 +157,40 @@ fn priority_tiebreaker_sorts_lower_priority_first() {
     assert_eq!(rows[0].knot.id, "K-1");
     assert_eq!(rows[1].knot.id, "K-2");
 }
+
+#[test]
+fn column_specs_parse_with_optional_widths() {
+    let columns = parse_columns(" ID, state ,title:50,,tags").expect("valid spec");
+    assert_eq!(
+        columns,
+        [
+            Column {
+                kind: ColumnKind::Id,
+                width: None
+            },
+            Column {
+                kind: ColumnKind::State,
+                width: None
+            },
+            Column {
+                kind: ColumnKind::Title,
+                width: Some(50)
+            },
+            Column {
+                kind: ColumnKind::Tags,
+                width: None
+            },
+        ]
+    );
+    assert_eq!(format_columns(&columns), "id,state,title:50,tags");
+    assert_eq!(parse_columns("").expect("empty"), []);
+    assert_eq!(columns[0].width(false), Some(10));
+    let title = parse_columns("title").expect("title");
+    assert_eq!(title[0].width(true), None);
+    assert_eq!(title[0].width(false), Some(40));
+
+    let err = parse_columns("id,owner").expect_err("unknown column");
+    assert!(err.contains("unknown column 'owner'"), "{err}");
+    assert!(parse_columns("title:0").is_err());
+    assert!(parse_columns("title:wide").is_err());
+}
@@ -9,6 +9,9 @@ pub struct KnotListFilter {
     pub knot_type: Option<String>,
     pub profile_id: Option<String>,
     pub tags: Vec<String>,
+    /// Custom field `(name, value)` pairs; an empty value matches knots
+    /// without the field.
+    pub custom_fields: Vec<(String, String)>,
     pub query: Option<String>,
 }
 
@@ -31,6 +34,7 @@ struct NormalizedFilter {
     knot_type: Option<String>,
     profile_id: Option<String>,
     tags: Vec<String>,
+    custom_fields: Vec<(String, String)>,
     query: Option<String>,
 }
 
@@ -40,6 +44,7 @@ impl NormalizedFilter {
             && self.knot_type.is_none()
             && self.profile_id.is_none()
             && self.tags.is_empty()
+            && self.custom_fields.is_empty()
             && self.query.is_none()
     }
 }
@@ -56,6 +61,7 @@ impl From<&KnotListFilter> for NormalizedFilter {
                 .iter()
                 .filter_map(|tag| normalize_scalar(Some(tag)))
                 .collect(),
+            custom_fields: value.custom_fields.clone(),
             query: normalize_scalar(value.query.as_deref()),
         }
     }
@@ -92,6 +98,10 @@ fn matches_filter(knot: &KnotView, filter: &NormalizedFilter) -> bool {
         return false;
     }
 
+    if !has_all_fields(knot, &filter.custom_fields) {
+        return false;
+    }
+
     if let Some(query) = filter.query.as_deref() {
         return matches_query(knot, query);
     }
@@ -136,9 +146,20 @@ fn has_all_tags(knot: &KnotView, required_tags: &[String]) -> bool {
         .iter()
         .map(|tag| tag.trim().to_ascii_lowercase())
         .collect();
-    required_tags
+    required_tags.iter().all(|tag| {
+        knot_tags
+            .iter()
+            .any(|existing| crate::domain::tag::matches_filter(existing, tag))
+    })
+}
+
+fn has_all_fields(knot: &KnotView, required: &[(String, String)]) -> bool {
+    required
         .iter()
-        .all(|tag| knot_tags.iter().any(|existing| existing == tag))
+        .all(|(name, expected)| match knot.custom_fields.get(name) {
+            Some(actual) => actual.eq_ignore_ascii_case(expected),
+            None => expected.is_empty(),
+        })
 }
 
 fn matches_query(knot: &KnotView, query: &str) -> bool {
@@ -178,320 +199,8 @@ fn normalize_knot_type_filter(raw: Option<&str>) -> Option<String> {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::{apply_filters, KnotListFilter};
-    use crate::app::KnotView;
-
-    fn knot(
-        id: &str,
-        title: &str,
-        state: &str,
-        knot_type: Option<&str>,
-        tags: &[&str],
-        description: Option<&str>,
-    ) -> KnotView {
-        KnotView {
-            id: id.to_string(),
-            alias: None,
-            title: title.to_string(),
-            state: state.to_string(),
-            updated_at: "2026-02-23T10:00:00Z".to_string(),
-            body: None,
-            description: description.map(|value| value.to_string()),
-            acceptance: None,
-            priority: None,
-            knot_type: crate::domain::knot_type::parse_knot_type(knot_type),
-            tags: tags.iter().map(|value| (*value).to_string()).collect(),
-            notes: Vec::new(),
-            handoff_capsules: Vec::new(),
-            invariants: Vec::new(),
-            step_history: Vec::new(),
-            gate: None,
-            lease: None,
-            lease_id: None,
-            lease_expiry_ts: 0,
-            lease_agent: None,
-            workflow_id: "work_sdlc".to_string(),
-            profile_id: "autopilot".to_string(),
-            profile_etag: None,
-            deferred_from_state: None,
-            blocked_from_state: None,
-            created_at: None,
-            step_metadata: None,
-            next_step_metadata: None,
-            edges: Vec::new(),
-            child_summaries: vec![],
-        }
-    }
-
-    #[test]
-    fn filters_by_state_case_insensitive() {
-        let knots = vec![
-            knot("K-1", "Plan filters", "idea", Some("task"), &["ux"], None),
-            knot(
-                "K-2",
-                "Ship UI",
-                "implementing",
-                Some("task"),
-                &["release"],
-                None,
-            ),
-        ];
-        let filter = KnotListFilter {
-            include_all: false,
-            state: Some("ImPlementing".to_string()),
-            knot_type: None,
-            profile_id: None,
-            tags: Vec::new(),
-            query: None,
-        };
-
-        let filtered = apply_filters(knots, &filter);
-        assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].id, "K-2");
-    }
-
-    #[test]
-    fn filters_by_multiple_tags() {
-        let knots = vec![
-            knot(
-                "K-1",
-                "Importer",
-                "work_item",
-                Some("task"),
-                &["migration", "sync"],
-                None,
-            ),
-            knot(
-                "K-2",
-                "Docs",
-                "work_item",
-                Some("task"),
-                &["migration"],
-                None,
-            ),
-        ];
-        let filter = KnotListFilter {
-            include_all: false,
-            state: None,
-            knot_type: None,
-            profile_id: None,
-            tags: vec!["migration".to_string(), "sync".to_string()],
-            query: None,
-        };
-
-        let filtered = apply_filters(knots, &filter);
-        assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].id, "K-1");
-    }
-
-    #[test]
-    fn filters_by_query_across_title_and_description() {
-        let knots = vec![
-            knot(
-                "K-1",
-                "Polish ls output",
-                "reviewing",
-                Some("task"),
-                &["ux"],
-                Some("needs style"),
-            ),
-            knot(
-                "K-2",
-                "Refactor imports",
-                "implementing",
-                Some("task"),
-                &["infra"],
-                Some("carry checkpoint"),
-            ),
-        ];
-        let filter = KnotListFilter {
-            include_all: false,
-            state: None,
-            knot_type: None,
-            profile_id: None,
-            tags: Vec::new(),
-            query: Some("STYLE".to_string()),
-        };
-
-        let filtered = apply_filters(knots, &filter);
-        assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].id, "K-1");
-    }
-
-    #[test]
-    fn combines_filters() {
-        let knots = vec![
-            knot(
-                "K-1",
-                "Release flow",
-                "implementing",
-                Some("work"),
-                &["release", "cli"],
-                None,
-            ),
-            knot(
-                "K-2",
-                "Release docs",
-                "implementing",
-                Some("work"),
-                &["release", "docs"],
-                None,
-            ),
-        ];
-        let filter = KnotListFilter {
-            include_all: false,
-            state: Some("implementing".to_string()),
-            knot_type: Some("work".to_string()),
-            profile_id: None,
-            tags: vec!["release".to_string()],
-            query: Some("flow".to_string()),
-        };
-
-        let filtered = apply_filters(knots, &filter);
-        assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].id, "K-1");
-    }
-
-    #[test]
-    fn excludes_shipped_by_default() {
-        let knots = vec![
-            knot("K-1", "Active", "implementing", Some("task"), &[], None),
-            knot("K-2", "Done", "shipped", Some("task"), &[], None),
-        ];
-        let filter = KnotListFilter::default();
-
-        let filtered = apply_filters(knots, &filter);
-        assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].id, "K-1");
-    }
-
-    #[test]
-    fn includes_shipped_with_all_flag() {
-        let knots = vec![
-            knot("K-1", "Active", "implementing", Some("task"), &[], None),
-            knot("K-2", "Done", "shipped", Some("task"), &[], None),
-        ];
-        let filter = KnotListFilter {
-            include_all: true,
-            state: None,
-            knot_type: None,
-            profile_id: None,
-            tags: Vec::new(),
-            query: None,
-        };
-
-        let filtered = apply_filters(knots, &filter);
-        assert_eq!(filtered.len(), 2);
-    }
-
-    #[test]
-    fn allows_state_shipped_without_all_flag() {
-        let knots = vec![
-            knot("K-1", "Active", "implementing", Some("task"), &[], None),
-            knot("K-2", "Done", "shipped", Some("task"), &[], None),
-        ];
-        let filter = KnotListFilter {
-            include_all: false,
-            state: Some("shipped".to_string()),
-            knot_type: None,
-            profile_id: None,
-            tags: Vec::new(),
-            query: None,
-        };
-
-        let filtered = apply_filters(knots, &filter);
-        assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].id, "K-2");
-    }
-
-    #[test]
-    fn excludes_abandoned_by_default() {
-        let knots = vec![
-            knot("K-1", "Active", "implementing", Some("task"), &[], None),
-            knot("K-2", "Gone", "abandoned", Some("task"), &[], None),
-        ];
-        let filter = KnotListFilter::default();
-        let filtered = apply_filters(knots, &filter);
-        assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].id, "K-1");
-    }
-
-    #[test]
-    fn includes_deferred_by_default() {
-        let knots = vec![
-            knot("K-1", "Active", "implementing", Some("task"), &[], None),
-            knot("K-2", "Later", "deferred", Some("task"), &[], None),
-        ];
-        let filter = KnotListFilter::default();
-        let filtered = apply_filters(knots, &filter);
-        assert_eq!(filtered.len(), 2);
-    }
-
-    #[test]
-    fn includes_abandoned_and_deferred_with_all_flag() {
-        let knots = vec![
-            knot("K-1", "Active", "implementing", Some("task"), &[], None),
-            knot("K-2", "Gone", "abandoned", Some("task"), &[], None),
-            knot("K-3", "Later", "deferred", Some("task"), &[], None),
-        ];
-        let filter = KnotListFilter {
-            include_all: true,
-            ..KnotListFilter::default()
-        };
-        let filtered = apply_filters(knots, &filter);
-        assert_eq!(filtered.len(), 3);
-    }
-
-    #[test]
-    fn allows_state_abandoned_explicit() {
-        let knots = vec![
-            knot("K-1", "Active", "implementing", Some("task"), &[], None),
-            knot("K-2", "Gone", "abandoned", Some("task"), &[], None),
-        ];
-        let filter = KnotListFilter {
-            include_all: false,
-            state: Some("abandoned".to_string()),
-            ..KnotListFilter::default()
-        };
-        let filtered = apply_filters(knots, &filter);
-        assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].id, "K-2");
-    }
-
-    #[test]
-    fn filters_by_knot_type() {
-        let knots = vec![
-            knot("K-1", "Bug fix", "planning", Some("task"), &[], None),
-            knot("K-2", "Quality gate", "planning", Some("gate"), &[], None),
-        ];
-        let filter = KnotListFilter {
-            include_all: false,
-            knot_type: Some("gate".to_string()),
-            ..KnotListFilter::default()
-        };
-        let filtered = apply_filters(knots, &filter);
-        assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].id, "K-2");
-    }
-
-    #[test]
-    fn allows_state_deferred_explicit() {
-        let knots = vec![
-            knot("K-1", "Active", "implementing", Some("task"), &[], None),
-            knot("K-2", "Later", "deferred", Some("task"), &[], None),
-        ];
-        let filter = KnotListFilter {
-            include_all: false,
-            state: Some("deferred".to_string()),
-            ..KnotListFilter::default()
-        };
-        let filtered = apply_filters(knots, &filter);
-        assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].id, "K-2");
-    }
-}
-
+#[path = "listing_tests.rs"]
+mod tests;
 #[cfg(test)]
 #[path = "listing_tests_ext.rs"]
 mod tests_ext;
@@ -19,9 +19,11 @@ fn knot(
         body: None,
         description: description.map(|value| value.to_string()),
         acceptance: None,
+        estimate: None,
         priority: None,
         knot_type: crate::domain::knot_type::parse_knot_type(knot_type),
         tags: tags.iter().map(|value| (*value).to_string()).collect(),
+        custom_fields: Default::default(),
         notes: Vec::new(),
         handoff_capsules: Vec::new(),
         invariants: Vec::new(),
@@ -37,10 +39,15 @@ fn knot(
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: None,
+        snoozed_until: None,
         step_metadata: None,
         next_step_metadata: None,
         edges: Vec::new(),
         child_summaries: vec![],
+        git_links: vec![],
+        criteria: Vec::new(),
+        handbacks: Vec::new(),
+        plan: None,
     }
 }
 
@@ -56,6 +63,7 @@ fn filters_by_type_normalizes_legacy_aliases() {
         knot_type: Some("task".to_string()),
         profile_id: None,
         tags: Vec::new(),
+        custom_fields: Vec::new(),
         query: None,
     };
 
@@ -72,6 +80,7 @@ fn invalid_type_filter_is_ignored() {
         knot_type: Some("epic".to_string()),
         profile_id: None,
         tags: Vec::new(),
+        custom_fields: Vec::new(),
         query: None,
     };
 
@@ -91,6 +100,7 @@ fn include_all_with_user_filter_includes_terminal_knots() {
         knot_type: None,
         profile_id: None,
         tags: vec!["cli".to_string()],
+        custom_fields: Vec::new(),
         query: None,
     };
 
@@ -127,6 +137,7 @@ fn empty_state_filter_is_treated_as_no_filter() {
         knot_type: None,
         profile_id: None,
         tags: Vec::new(),
+        custom_fields: Vec::new(),
         query: None,
     };
 
@@ -150,6 +161,7 @@ fn whitespace_only_query_filter_is_treated_as_no_filter() {
         knot_type: None,
         profile_id: None,
         tags: Vec::new(),
+        custom_fields: Vec::new(),
         query: Some("   ".to_string()),
     };
 
@@ -201,6 +213,7 @@ fn filters_by_alias_query() {
         knot_type: None,
         profile_id: None,
         tags: Vec::new(),
+        custom_fields: Vec::new(),
         query: Some("root.1".to_string()),
     };
 
@@ -220,6 +233,7 @@ fn filters_by_profile_id() {
         knot_type: None,
         profile_id: Some("triage".to_string()),
         tags: Vec::new(),
+        custom_fields: Vec::new(),
         query: None,
     };
 
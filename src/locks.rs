@@ -1,9 +1,25 @@
+use std::cell::RefCell;
 use std::fs::{File, OpenOptions};
 use std::io::{ErrorKind, Read, Write};
 use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::{Duration, Instant};
 
+use crate::trace::TimingCategory;
+
+thread_local! {
+    static LOCK_WAITS: RefCell<Option<Vec<Duration>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` and returns how long each lock it acquired on this thread was
+/// waited for, in acquisition order. Used by `kno perf contention`.
+pub fn record_lock_waits<T>(f: impl FnOnce() -> T) -> (T, Vec<Duration>) {
+    let previous = LOCK_WAITS.with(|waits| waits.replace(Some(Vec::new())));
+    let result = f();
+    let waits = LOCK_WAITS.with(|waits| waits.replace(previous));
+    (result, waits.unwrap_or_default())
+}
+
 #[derive(Debug)]
 pub enum LockError {
     Busy(PathBuf),
@@ -35,16 +51,26 @@ pub struct FileLock {
 
 impl FileLock {
     pub fn acquire(path: &Path, timeout: Duration) -> Result<Self, LockError> {
-        let start = Instant::now();
-        loop {
-            match try_acquire(path)? {
-                Some(guard) => return Ok(guard),
-                None if start.elapsed() >= timeout => {
-                    return Err(LockError::Busy(path.to_path_buf()));
+        crate::trace::timed(TimingCategory::LockWait, || {
+            let start = Instant::now();
+            loop {
+                match try_acquire(path)? {
+                    Some(guard) => {
+                        let waited = start.elapsed();
+                        LOCK_WAITS.with(|waits| {
+                            if let Some(waits) = waits.borrow_mut().as_mut() {
+                                waits.push(waited);
+                            }
+                        });
+                        return Ok(guard);
+                    }
+                    None if start.elapsed() >= timeout => {
+                        return Err(LockError::Busy(path.to_path_buf()));
+                    }
+                    None => thread::sleep(Duration::from_millis(10)),
                 }
-                None => thread::sleep(Duration::from_millis(10)),
             }
-        }
+        })
     }
 
     pub fn try_acquire(path: &Path) -> Result<Option<Self>, LockError> {
@@ -144,7 +170,7 @@ mod tests {
     use std::time::Duration;
     use uuid::Uuid;
 
-    use super::{process_alive, reclaim_stale, FileLock, LockError};
+    use super::{process_alive, reclaim_stale, record_lock_waits, FileLock, LockError};
 
     fn lock_path() -> PathBuf {
         std::env::temp_dir().join(format!("knots-lock-test-{}.lock", Uuid::now_v7()))
@@ -162,6 +188,22 @@ mod tests {
         let _ = std::fs::remove_file(path);
     }
 
+    #[test]
+    fn record_lock_waits_collects_only_successful_acquisitions_inside_the_scope() {
+        let path = lock_path();
+        drop(FileLock::acquire(&path, Duration::from_millis(50)).expect("unrecorded lock"));
+        let (_, waits) = record_lock_waits(|| {
+            let held = FileLock::acquire(&path, Duration::from_millis(50)).expect("lock");
+            assert!(FileLock::acquire(&path, Duration::from_millis(20)).is_err());
+            drop(held);
+            drop(FileLock::acquire(&path, Duration::from_millis(50)).expect("relock"));
+        });
+        assert_eq!(waits.len(), 2);
+        let (_, empty) = record_lock_waits(|| ());
+        assert!(empty.is_empty());
+        let _ = std::fs::remove_file(path);
+    }
+
     #[test]
     fn acquire_times_out_when_held() {
         let path = lock_path();
@@ -1,4 +1,3 @@
-use std::io::IsTerminal;
 use std::path::Path;
 
 use crate::app::AppError;
@@ -21,8 +20,7 @@ pub(crate) fn run_loom_command(args: &LoomArgs, _repo_root: &Path) -> Result<(),
             let result = if inner.json {
                 loom_compat_harness::run_compat_test(&config)?
             } else {
-                let color =
-                    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+                let color = crate::ui::stdout_color_enabled();
                 loom_compat_harness::run_compat_test_with_progress(&config, |update| {
                     println!("{}", render_progress(&update, color));
                 })?
@@ -3,32 +3,86 @@ mod app;
 mod artifact_target;
 #[cfg(test)]
 mod artifact_target_tests;
+mod branch_name;
+#[cfg(test)]
+mod branch_name_tests;
+mod cache_location;
 mod cli;
 mod cli_agent;
+mod cli_criteria;
+mod cli_db;
+mod cli_events;
 mod cli_help;
+mod cli_import;
+mod cli_init;
+mod cli_links;
 mod cli_loom;
+mod cli_new;
 mod cli_ops;
+mod cli_perf;
+mod cli_plan;
+mod cli_profile;
+mod cli_redact;
+mod cli_reports;
+mod cli_serve;
 mod cli_skills;
+mod cli_space;
+mod cli_sync;
+mod cli_watch;
 mod cli_workflow;
+mod cold_commands;
+mod command_dispatch;
+mod compaction;
+#[cfg(test)]
+mod compaction_tests;
 mod completions;
+mod compression;
+#[cfg(test)]
+mod compression_tests;
+mod config_commands;
+mod criteria_commands;
+mod dashboard;
 mod db;
+mod db_commands;
+mod dedupe_commands;
+mod digest_commands;
+mod digest_send;
 mod dispatch;
 mod doctor;
+mod doctor_edges;
 mod doctor_fix;
 mod doctor_workflows;
 mod domain;
+mod edge_commands;
+mod event_commands;
 mod events;
 mod fsck;
 mod git_hooks;
 #[cfg(test)]
 mod git_hooks_tests;
+mod graphql;
+mod grep;
+mod grep_commands;
+mod help_topics;
 mod hierarchy_alias;
+mod http_json;
+mod http_serve;
+mod import_commands;
 mod init;
+mod init_wizard;
+#[cfg(test)]
+mod init_wizard_tests;
 mod installed_workflows;
+mod interrupt;
 mod knot_id;
 mod lease;
 mod lease_expiry;
 mod lease_guard;
+mod link_commands;
+mod links;
+#[cfg(test)]
+mod links_tests;
+mod lint_commands;
 mod list_layout;
 #[cfg(test)]
 mod list_layout_tests;
@@ -49,9 +103,21 @@ mod loom_lease_bundle;
 mod loom_work_bundle;
 #[cfg(test)]
 mod main_tests;
+mod man_pages;
 mod managed_skills;
+mod notify;
+mod org;
+mod pager;
 mod perf;
+mod perf_contention;
+mod pick_commands;
+#[cfg(test)]
+mod pick_commands_tests;
+mod plan_commands;
 mod poll_claim;
+mod pr_body;
+#[cfg(test)]
+mod pr_body_tests;
 mod profile;
 mod profile_behavior;
 mod profile_commands;
@@ -66,20 +132,46 @@ mod project_tests_ext;
 mod prompt;
 #[cfg(test)]
 mod prompt_tests;
+mod publish_commands;
+mod queue_commands;
+mod redact_commands;
 mod release_version;
 mod remote_init;
+mod replay_commands;
+#[cfg(test)]
+mod replay_commands_tests;
 mod replication;
+mod repo_config;
+#[cfg(test)]
+mod repo_config_tests;
 mod rollback;
+mod rpc;
 mod run_commands;
 mod self_manage;
+#[cfg(feature = "semantic")]
+mod semantic;
+mod serve_commands;
 mod snapshots;
+mod space;
+mod space_commands;
 mod state_hierarchy;
+mod stats_commands;
+mod status_commands;
 mod stream_output;
+mod summarize;
+mod summarize_commands;
 mod sync;
+mod sync_commands;
+mod tag_commands;
 mod tiering;
 mod trace;
 mod ui;
+mod uninit_guard;
 mod upgrade_notice;
+mod usage_metrics;
+mod vault;
+mod watch_commands;
+mod week_plan;
 mod workflow;
 mod workflow_commands;
 mod workflow_diagram;
@@ -87,6 +179,8 @@ mod workflow_runtime;
 mod write_dispatch;
 mod write_queue;
 
+use command_dispatch::command_name;
+
 fn main() {
     upgrade_notice::maybe_print_upgrade_notice();
     let args: Vec<String> = std::env::args().collect();
@@ -94,7 +188,12 @@ fn main() {
         cli_help::print_custom_help();
         return;
     }
-    if let Err(err) = run() {
+    if help_topics::maybe_print_topic(&args) {
+        return;
+    }
+    let result = run();
+    usage_metrics::finish(result.is_ok());
+    if let Err(err) = result {
         eprint!("{}", format_error(&err));
         std::process::exit(1);
     }
@@ -119,12 +218,11 @@ pub(crate) fn print_json(val: &impl serde::Serialize) {
 }
 
 fn resolve_db_path(context: &project::ProjectContext, db_path: Option<&str>) -> String {
-    let default_path = context.store_paths.db_path();
     let Some(db_path) = db_path else {
-        return default_path.display().to_string();
+        return context.store_paths.db_path().display().to_string();
     };
     let db = std::path::Path::new(db_path);
-    if db.is_absolute() {
+    if db.is_absolute() || db::is_memory_path(db_path) {
         return db.display().to_string();
     }
     match context.distribution {
@@ -139,6 +237,35 @@ pub(crate) fn progress_reporter(enabled: bool) -> Option<ui::StdoutProgressRepor
     enabled.then(ui::StdoutProgressReporter::auto)
 }
 
+fn run_repo_init(
+    cli: &cli::Cli,
+    init_args: &cli::InitArgs,
+    cwd: &std::path::Path,
+) -> Result<(), app::AppError> {
+    let context = project::resolve_context(None, cli.repo_root.as_deref(), cwd, None)
+        .map_err(app::AppError::InvalidArgument)?;
+    let db_path = resolve_db_path(&context, cli.db.as_deref());
+    if init_args.knots_ref.is_set() {
+        let knots_ref = &init_args.knots_ref;
+        repo_config::set_knots_ref(
+            &init::store_root_for_db(&db_path),
+            knots_ref.branch.as_deref(),
+            knots_ref.remote.as_deref(),
+        )
+        .map_err(app::AppError::InvalidArgument)?;
+    }
+    if init_args.from_remote {
+        return init::bootstrap_from_remote(&context.repo_root, &db_path);
+    }
+    if init_args.interactive {
+        init_wizard::run_interactive_init(&context.repo_root, &db_path)?;
+    } else {
+        init::init_all(&context.repo_root, &db_path)?;
+    }
+    println!("kno init completed");
+    Ok(())
+}
+
 fn run() -> Result<(), app::AppError> {
     use clap::FromArgMatches;
     use cli::Commands;
@@ -147,6 +274,9 @@ fn run() -> Result<(), app::AppError> {
         .expect("arg matches should be valid");
     let args = std::env::args().skip(1).collect::<Vec<_>>();
     let _trace = trace::TraceSession::start(command_name(&cli.command), &args, cli.trace);
+    let _timing = trace::TimingSession::start(cli.timing);
+    usage_metrics::begin(command_name(&cli.command));
+    pager::set_disabled(cli.no_pager);
     let cwd = std::env::current_dir()?;
     let explicit_repo_root = cli.repo_root.as_deref();
 
@@ -158,9 +288,21 @@ fn run() -> Result<(), app::AppError> {
     if let Commands::Project(args) = &cli.command {
         return project_commands::run_project_command(args, None, explicit_repo_root);
     }
+    if let Commands::Serve(args) = &cli.command {
+        if !args.ui && !args.graphql {
+            return serve_commands::run_serve(args);
+        }
+    }
 
-    if let Commands::Init = &cli.command {
+    if let Commands::Init(init_args) = &cli.command {
         if let Some(project_id) = cli.project.as_deref() {
+            if init_args.interactive || init_args.from_remote || init_args.knots_ref.is_set() {
+                return Err(app::AppError::InvalidArgument(
+                    "--interactive, --from-remote, --branch, and --remote are not supported \
+                     with --project"
+                        .to_string(),
+                ));
+            }
             let repo_root = explicit_repo_root.or(Some(cwd.as_path()));
             let _ = project::load_named_project(None, project_id)
                 .or_else(|_| project::create_named_project(None, project_id, repo_root))
@@ -174,18 +316,19 @@ fn run() -> Result<(), app::AppError> {
             println!("kno init completed");
             return Ok(());
         }
-        let context = project::resolve_context(None, explicit_repo_root, &cwd, None)
-            .map_err(app::AppError::InvalidArgument)?;
-        let db_path = resolve_db_path(&context, cli.db.as_deref());
-        init::init_all(&context.repo_root, &db_path)?;
-        println!("kno init completed");
-        return Ok(());
+        return run_repo_init(&cli, init_args, &cwd);
     }
     let context = project::resolve_context(cli.project.as_deref(), explicit_repo_root, &cwd, None)
         .map_err(app::AppError::InvalidArgument)?;
+    if let Commands::Space(args) = &cli.command {
+        return space_commands::run_space(args, &context);
+    }
+    let context = select_space(&cli, context)?;
     let db_path = resolve_db_path(&context, cli.db.as_deref());
+    let cli = pick_commands::resolve_picks(cli, &context, &db_path)?;
 
-    if let Commands::Uninit = &cli.command {
+    if let Commands::Uninit(args) = &cli.command {
+        uninit_guard::prepare_uninit(&context, &db_path, args)?;
         match context.distribution {
             project::DistributionMode::Git => init::uninit_all(&context.repo_root, &db_path)?,
             project::DistributionMode::LocalOnly => {
@@ -209,20 +352,11 @@ fn run() -> Result<(), app::AppError> {
         }
         return run_hooks_command(&context.repo_root, &args.command);
     }
-    if let Commands::Completions(args) = &cli.command {
-        return completions::run_completions_command(args.shell.as_deref(), args.install);
-    }
-    if let Commands::Skills(args) = &cli.command {
-        return run_skills_command(&context.repo_root, args);
-    }
-    if let Commands::Profile(args) = &cli.command {
-        return profile_commands::run_profile_command_with_context(args, &context, &db_path);
+    if let Some(result) = run_standalone_command(&cli.command) {
+        return result;
     }
-    if let Commands::Workflow(args) = &cli.command {
-        return workflow_commands::run_workflow_command(args, context.workflow_root());
-    }
-    if let Commands::Loom(args) = &cli.command {
-        return loom_compat_commands::run_loom_command(args, &context.repo_root);
+    if let Some(result) = run_context_command(&cli.command, &context, &db_path) {
+        return result;
     }
     if let Some(output) =
         write_dispatch::maybe_run_queued_command_with_context(&cli, &context, &db_path)?
@@ -232,92 +366,63 @@ fn run() -> Result<(), app::AppError> {
     }
 
     let app = app::App::open_with_context(&context, &db_path)?;
-    dispatch_read_command(cli.command, &app)
+    command_dispatch::dispatch_read_command(cli.command, &app)
 }
 
-fn command_name(command: &cli::Commands) -> &'static str {
-    use cli::Commands;
+fn select_space(
+    cli: &cli::Cli,
+    context: project::ProjectContext,
+) -> Result<project::ProjectContext, app::AppError> {
+    let root_store = context.store_paths.root.clone();
+    let context = space::select_space(context, cli.space.as_deref())
+        .map_err(app::AppError::InvalidArgument)?;
+    if cli.db.is_some() && context.store_paths.root != root_store {
+        return Err(app::AppError::InvalidArgument(
+            "--db cannot be combined with a space; each space keeps its own cache".to_string(),
+        ));
+    }
+    Ok(context)
+}
 
+/// Commands that need no knots store: completions, man pages, and the perf
+/// harness (which builds its own scratch repo).
+fn run_standalone_command(command: &cli::Commands) -> Option<Result<(), app::AppError>> {
     match command {
-        Commands::New(_) => "new",
-        Commands::State(_) => "state",
-        Commands::Update(_) => "update",
-        Commands::Upgrade(_) => "upgrade",
-        Commands::Uninstall(_) => "uninstall",
-        Commands::Ls(_) => "ls",
-        Commands::Show(_) => "show",
-        Commands::Profile(_) => "profile",
-        Commands::Workflow(_) => "workflow",
-        Commands::Project(_) => "project",
-        Commands::Loom(_) => "loom",
-        Commands::Pull(_) => "pull",
-        Commands::Push(_) => "push",
-        Commands::Sync(_) => "sync",
-        Commands::Init => "init",
-        Commands::Uninit => "uninit",
-        Commands::InitRemote => "init-remote",
-        Commands::Fsck(_) => "fsck",
-        Commands::Doctor(_) => "doctor",
-        Commands::Perf(_) => "perf",
-        Commands::Compact(_) => "compact",
-        Commands::Cold(_) => "cold",
-        Commands::Rehydrate(_) => "rehydrate",
-        Commands::Edge(_) => "edge",
-        Commands::Gate(_) => "gate",
-        Commands::Next(_) => "next",
-        Commands::Rollback(_) => "rollback",
-        Commands::Skill(_) => "skill",
-        Commands::Skills(_) => "skills",
-        Commands::Q(_) => "q",
-        Commands::Completions(_) => "completions",
-        Commands::Poll(_) => "poll",
-        Commands::Claim(_) => "claim",
-        Commands::Ready(_) => "ready",
-        Commands::Step(_) => "step",
-        Commands::Lease(_) => "lease",
-        Commands::Hooks(_) => "hooks",
+        cli::Commands::Completions(args) => Some(completions::run_completions_command(
+            args.shell.as_deref(),
+            args.install,
+        )),
+        cli::Commands::Man(args) => Some(man_pages::run_man_command(args)),
+        cli::Commands::Perf(args) => Some(run_commands::run_perf(args)),
+        _ => None,
     }
 }
 
-fn dispatch_read_command(command: cli::Commands, app: &app::App) -> Result<(), app::AppError> {
-    use cli::{Commands, EdgeSubcommands};
+/// Commands that work from the resolved project context without opening
+/// the cache through `App`.
+fn run_context_command(
+    command: &cli::Commands,
+    context: &project::ProjectContext,
+    db_path: &str,
+) -> Option<Result<(), app::AppError>> {
     match command {
-        Commands::Ls(args) => run_commands::run_ls(app, args),
-        Commands::Show(args) => run_commands::run_show(app, args),
-        Commands::Pull(args) => run_commands::run_pull(app, args),
-        Commands::Push(args) => run_commands::run_push(app, args),
-        Commands::Sync(args) => run_commands::run_sync(app, args),
-        Commands::InitRemote => {
-            app.init_remote()?;
-            println!("initialized remote branch origin/knots");
-            Ok(())
-        }
-        Commands::Fsck(args) => run_commands::run_fsck(app, args),
-        Commands::Doctor(args) => run_commands::run_doctor(app, args),
-        Commands::Perf(args) => run_commands::run_perf(app, args),
-        Commands::Compact(args) => run_commands::run_compact(app, args),
-        Commands::Cold(args) => run_commands::run_cold(app, args),
-        Commands::Rehydrate(args) => run_commands::run_rehydrate(app, args),
-        Commands::Edge(args) => match args.command {
-            EdgeSubcommands::List(edge_args) => run_commands::run_edge_list(app, edge_args),
-            _ => unreachable!("queued write commands handled before app init"),
-        },
-        Commands::Skill(args) => run_commands::run_skill(app, args),
-        Commands::Poll(args) => {
-            if args.claim {
-                unreachable!("queued write commands handled before app init");
-            }
-            poll_claim::run_poll(app, args)
-        }
-        Commands::Claim(args) => {
-            if !args.peek {
-                unreachable!("queued write commands handled before app init");
-            }
-            poll_claim::run_claim(app, args)
-        }
-        Commands::Ready(args) => poll_claim::run_ready(app, args),
-        Commands::Lease(args) => run_commands::run_lease_read(app, args),
-        _ => unreachable!("handled before app initialization"),
+        cli::Commands::Skills(args) => Some(run_skills_command(&context.repo_root, args)),
+        cli::Commands::Profile(args) => Some(profile_commands::run_profile_command_with_context(
+            args, context, db_path,
+        )),
+        cli::Commands::Workflow(args) => Some(workflow_commands::run_workflow_command(
+            args,
+            context.workflow_root(),
+        )),
+        cli::Commands::Loom(args) => Some(loom_compat_commands::run_loom_command(
+            args,
+            &context.repo_root,
+        )),
+        cli::Commands::Db(args) => Some(db_commands::run_db(args, context, db_path)),
+        cli::Commands::Cold(cli::ColdArgs {
+            command: cli::ColdSubcommands::Ls(args),
+        }) if args.from_snapshot => Some(cold_commands::run_cold_ls_from_snapshot(args, context)),
+        _ => None,
     }
 }
 
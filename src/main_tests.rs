@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 use std::process::Command;
 
-use crate::cli::{Commands, HooksSubcommands, SelfUninstallArgs, SelfUpdateArgs};
+use crate::cli::{Commands, HooksSubcommands, ReleaseChannel, SelfUninstallArgs, SelfUpdateArgs};
 
 use crate::dispatch::knot_ref;
 use crate::self_manage::maybe_run_self_command;
@@ -73,9 +73,11 @@ fn knot_ref_prefers_alias_when_available() {
         body: None,
         description: None,
         acceptance: None,
+        estimate: None,
         priority: None,
         knot_type: crate::domain::knot_type::KnotType::default(),
         tags: Vec::new(),
+        custom_fields: Default::default(),
         notes: Vec::new(),
         handoff_capsules: Vec::new(),
         invariants: Vec::new(),
@@ -91,10 +93,15 @@ fn knot_ref_prefers_alias_when_available() {
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: None,
+        snoozed_until: None,
         step_metadata: None,
         next_step_metadata: None,
         edges: Vec::new(),
         child_summaries: vec![],
+        git_links: vec![],
+        criteria: Vec::new(),
+        handbacks: Vec::new(),
+        plan: None,
     };
     assert_eq!(knot_ref(&with_alias), "A.1 (123)");
 
@@ -106,7 +113,8 @@ fn knot_ref_prefers_alias_when_available() {
 #[test]
 fn maybe_run_self_command_returns_none_for_non_self_commands() {
     let cwd = std::env::current_dir().expect("cwd should resolve");
-    let outcome = maybe_run_self_command(&Commands::Init, &cwd).expect("init probe should succeed");
+    let outcome = maybe_run_self_command(&Commands::Init(Default::default()), &cwd)
+        .expect("init probe should succeed");
     assert!(outcome.is_none());
 }
 
@@ -136,7 +144,9 @@ fn maybe_run_self_command_update_and_uninstall_paths_execute() {
             version: Some("v1.2.3".to_string()),
             repo: Some("acartine/knots".to_string()),
             install_dir: Some(dir.clone()),
-            script_url: script_url.clone(),
+            channel: ReleaseChannel::Stable,
+            check: false,
+            script_url: Some(script_url.clone()),
         }),
         &dir,
     )
@@ -154,7 +164,9 @@ fn maybe_run_self_command_update_and_uninstall_paths_execute() {
             version: Some("v1.2.4".to_string()),
             repo: Some("acartine/knots".to_string()),
             install_dir: Some(dir.clone()),
-            script_url,
+            channel: ReleaseChannel::Stable,
+            check: false,
+            script_url: Some(script_url),
         }),
         &dir,
     )
@@ -220,7 +232,9 @@ fn maybe_run_self_command_upgrade_hint_tracks_hook_health() {
             version: None,
             repo: None,
             install_dir: Some(dir.clone()),
-            script_url: script_url.clone(),
+            channel: ReleaseChannel::Stable,
+            check: false,
+            script_url: Some(script_url.clone()),
         }),
         &clean_repo,
     )
@@ -234,7 +248,9 @@ fn maybe_run_self_command_upgrade_hint_tracks_hook_health() {
             version: None,
             repo: None,
             install_dir: Some(dir.clone()),
-            script_url: script_url.clone(),
+            channel: ReleaseChannel::Stable,
+            check: false,
+            script_url: Some(script_url.clone()),
         }),
         &missing_repo,
     )
@@ -254,7 +270,9 @@ fn maybe_run_self_command_upgrade_hint_tracks_hook_health() {
             version: None,
             repo: None,
             install_dir: Some(dir.clone()),
-            script_url,
+            channel: ReleaseChannel::Stable,
+            check: false,
+            script_url: Some(script_url),
         }),
         &stale_repo,
     )
@@ -320,9 +338,11 @@ fn knot_json_serialization_always_includes_step_history_field() {
         body: None,
         description: None,
         acceptance: None,
+        estimate: None,
         priority: None,
         knot_type: crate::domain::knot_type::KnotType::default(),
         tags: Vec::new(),
+        custom_fields: Default::default(),
         notes: Vec::new(),
         handoff_capsules: Vec::new(),
         invariants: Vec::new(),
@@ -338,10 +358,15 @@ fn knot_json_serialization_always_includes_step_history_field() {
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: None,
+        snoozed_until: None,
         step_metadata: None,
         next_step_metadata: None,
         edges: Vec::new(),
         child_summaries: vec![],
+        git_links: vec![],
+        criteria: Vec::new(),
+        handbacks: Vec::new(),
+        plan: None,
     };
 
     let json = serde_json::to_value(&knot).expect("serialize knot");
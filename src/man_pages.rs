@@ -0,0 +1,81 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use clap_mangen::Man;
+
+use crate::app::AppError;
+use crate::cli::ManArgs;
+
+pub fn run_man_command(args: &ManArgs) -> Result<(), AppError> {
+    let cmd = crate::cli::styled_command();
+    match args.out_dir.as_deref() {
+        Some(dir) => {
+            for path in write_man_pages(&cmd, dir)? {
+                println!("{}", path.display());
+            }
+        }
+        None => io::stdout().write_all(&render_page(cmd, "kno")?)?,
+    }
+    Ok(())
+}
+
+/// Writes `kno.1` plus one `kno-<command>.1` page per visible subcommand,
+/// nested commands included, and returns the paths written.
+pub(crate) fn write_man_pages(cmd: &clap::Command, dir: &Path) -> Result<Vec<PathBuf>, AppError> {
+    std::fs::create_dir_all(dir)?;
+    let mut pages = vec![("kno".to_string(), cmd.clone())];
+    collect_subcommands(cmd, "kno", &mut pages);
+    let mut written = Vec::with_capacity(pages.len());
+    for (name, page) in pages {
+        let path = dir.join(format!("{name}.1"));
+        std::fs::write(&path, render_page(page, &name)?)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Adds a page for every visible subcommand of `cmd`, whose invocation is
+/// `usage` (e.g. `kno config`).
+fn collect_subcommands(cmd: &clap::Command, usage: &str, pages: &mut Vec<(String, clap::Command)>) {
+    for sub in cmd.get_subcommands() {
+        if sub.is_hide_set() || sub.get_name() == "help" {
+            continue;
+        }
+        let usage = format!("{usage} {}", sub.get_name());
+        let name = usage.replace(' ', "-");
+        let page = sub
+            .clone()
+            .display_name(name.clone())
+            .bin_name(usage.clone())
+            .version(env!("CARGO_PKG_VERSION"))
+            .disable_version_flag(true);
+        pages.push((name, page));
+        collect_subcommands(sub, &usage, pages);
+    }
+}
+
+fn render_page(cmd: clap::Command, title: &str) -> Result<Vec<u8>, AppError> {
+    let mut buf = Vec::new();
+    Man::new(cmd).title(title.to_uppercase()).render(&mut buf)?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_man_pages;
+
+    #[test]
+    fn writes_a_page_for_the_root_and_nested_commands() {
+        let dir = std::env::temp_dir().join(format!("knots-man-{}", uuid::Uuid::now_v7()));
+        let written =
+            write_man_pages(&crate::cli::styled_command(), &dir).expect("pages should be written");
+        assert!(written.contains(&dir.join("kno.1")));
+        let ls = std::fs::read_to_string(dir.join("kno-ls.1")).expect("ls page");
+        assert!(ls.contains("kno\\-ls"), "{ls}");
+        assert!(ls.contains("\\fBkno ls\\fR"), "{ls}");
+        let nested = std::fs::read_to_string(dir.join("kno-config-get.1")).expect("nested page");
+        assert!(nested.contains("\\fBkno config get\\fR"), "{nested}");
+        assert!(!dir.join("kno-help.1").exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,113 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::app::{self, PullDigest};
+use crate::knot_id::display_id;
+
+const NOTIFY_COMMAND_KEY: &str = "notify.command";
+
+/// One section per kind of change; knots this clone had claimed are
+/// flagged, and listed first by `pull_digest`.
+pub fn render_pull_digest(digest: &PullDigest) -> String {
+    if digest.is_empty() {
+        return "no remote changes\n".to_string();
+    }
+    let mut out = String::new();
+    let sections = [
+        ("new knots", &digest.new_knots),
+        ("state changes", &digest.state_changes),
+        ("other updates", &digest.other_updates),
+    ];
+    for (heading, knots) in sections {
+        if knots.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("{heading} ({}):\n", knots.len()));
+        for knot in knots {
+            let state = match &knot.previous_state {
+                Some(previous) => format!("{previous} -> {}", knot.state),
+                None => knot.state.clone(),
+            };
+            let claimed = if knot.claimed { " (claimed)" } else { "" };
+            out.push_str(&format!(
+                "  {} {} [{state}]{claimed}\n",
+                display_id(&knot.id),
+                knot.title
+            ));
+        }
+    }
+    out
+}
+
+/// Pipes `text` to the `notify.command` shell command. A missing or failing
+/// sink only warns: the pull itself already succeeded.
+pub fn send_notification(app: &app::App, text: &str) -> Result<(), app::AppError> {
+    let entry = app.config_get(NOTIFY_COMMAND_KEY)?;
+    if entry.value.trim().is_empty() {
+        eprintln!(
+            "warning: --notify needs a sink; run `kno config set {NOTIFY_COMMAND_KEY} <command>`"
+        );
+        return Ok(());
+    }
+    // A shared config.toml must not make every clone run its command.
+    if entry.source != "local" {
+        eprintln!(
+            "warning: ignoring {NOTIFY_COMMAND_KEY} from .knots/config.toml; set it in this clone"
+        );
+        return Ok(());
+    }
+    if let Err(err) = run_sink(&entry.value, text) {
+        eprintln!("warning: {NOTIFY_COMMAND_KEY} failed: {err}");
+    }
+    Ok(())
+}
+
+fn run_sink(command: &str, text: &str) -> std::io::Result<()> {
+    let mut child = Command::new("sh")
+        .args(["-c", command])
+        .stdin(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!("exited with {status}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_pull_digest;
+    use crate::app::{DigestKnot, PullDigest};
+
+    fn knot(id: &str, state: &str, previous: Option<&str>, claimed: bool) -> DigestKnot {
+        DigestKnot {
+            id: id.to_string(),
+            title: format!("Title {id}"),
+            state: state.to_string(),
+            previous_state: previous.map(str::to_string),
+            claimed,
+        }
+    }
+
+    #[test]
+    fn renders_sections_with_transitions_and_claims() {
+        assert_eq!(
+            render_pull_digest(&PullDigest::default()),
+            "no remote changes\n"
+        );
+        let digest = PullDigest {
+            new_knots: vec![knot("demo-k1", "ready_for_planning", None, false)],
+            state_changes: vec![knot("demo-k2", "review", Some("implementation"), true)],
+            ..PullDigest::default()
+        };
+        assert_eq!(
+            render_pull_digest(&digest),
+            "new knots (1):\n  k1 Title demo-k1 [ready_for_planning]\n\
+             state changes (1):\n  k2 Title demo-k2 [implementation -> review] (claimed)\n"
+        );
+    }
+}
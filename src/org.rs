@@ -0,0 +1,265 @@
+/// One knot as an org heading; `None` fields were hidden by the repo's
+/// `sensitive_fields` and are written as placeholders import leaves alone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrgEntry {
+    pub id: String,
+    pub state: String,
+    pub title: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub children: Vec<OrgEntry>,
+}
+
+/// The `#+TODO` line: open states before the bar, terminal states after.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrgKeywords {
+    pub open: Vec<String>,
+    pub done: Vec<String>,
+}
+
+/// A heading read back from an org file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrgHeading {
+    /// 1-based line of the heading, for error messages.
+    pub line: usize,
+    /// Number of leading stars.
+    pub level: usize,
+    pub id: Option<String>,
+    /// The knot state the TODO keyword names, if the heading has one.
+    pub state: Option<String>,
+    pub title: String,
+    pub tags: Vec<String>,
+    /// Fields exported as placeholders, which import must not write back.
+    pub hidden: Vec<String>,
+}
+
+const HIDDEN_PROPERTY: &str = ":KNOTS_HIDDEN:";
+const HIDDEN_TITLE: &str = "(hidden)";
+
+/// Org keyword for a knot state, e.g. `READY_FOR_PLANNING`.
+pub fn state_keyword(state: &str) -> String {
+    state.to_ascii_uppercase()
+}
+
+pub fn render_org(keywords: &OrgKeywords, entries: &[OrgEntry]) -> String {
+    let mut out = String::from("#+TITLE: Knots\n");
+    let upper = |states: &[String]| {
+        states
+            .iter()
+            .map(|state| state_keyword(state))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    out.push_str(&format!(
+        "#+TODO: {} | {}\n",
+        upper(&keywords.open),
+        upper(&keywords.done)
+    ));
+    for entry in entries {
+        render_entry(&mut out, entry, 1);
+    }
+    out
+}
+
+fn render_entry(out: &mut String, entry: &OrgEntry, depth: usize) {
+    let mut heading = format!(
+        "{} {} {}",
+        "*".repeat(depth),
+        state_keyword(&entry.state),
+        entry.title.as_deref().unwrap_or(HIDDEN_TITLE)
+    );
+    if let Some(tags) = entry.tags.as_ref().filter(|tags| !tags.is_empty()) {
+        heading.push_str(&format!(" :{}:", tags.join(":")));
+    }
+    out.push_str(&heading);
+    out.push('\n');
+    out.push_str(":PROPERTIES:\n");
+    out.push_str(&format!(":ID: {}\n", entry.id));
+    let hidden: Vec<&str> = [
+        ("title", entry.title.is_none()),
+        ("tags", entry.tags.is_none()),
+    ]
+    .into_iter()
+    .filter_map(|(field, hidden)| hidden.then_some(field))
+    .collect();
+    if !hidden.is_empty() {
+        out.push_str(&format!("{HIDDEN_PROPERTY} {}\n", hidden.join(" ")));
+    }
+    out.push_str(":END:\n");
+    // Indented so a description line starting with `*` is never a heading.
+    for line in entry.description.as_deref().unwrap_or_default().lines() {
+        if !line.is_empty() {
+            out.push_str("  ");
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    for child in &entry.children {
+        render_entry(out, child, depth + 1);
+    }
+}
+
+/// Headings in `text`, with TODO keywords taken from its `#+TODO`,
+/// `#+SEQ_TODO`, and `#+TYP_TODO` lines (org's `TODO`/`DONE` without one).
+pub fn parse_org(text: &str) -> Vec<OrgHeading> {
+    let keywords = declared_keywords(text);
+    let mut headings: Vec<OrgHeading> = Vec::new();
+    let mut in_drawer = false;
+    for (index, line) in text.lines().enumerate() {
+        if let Some((level, rest)) = heading_text(line) {
+            headings.push(parse_heading(index + 1, level, rest, &keywords));
+            in_drawer = false;
+            continue;
+        }
+        let Some(heading) = headings.last_mut() else {
+            continue;
+        };
+        let trimmed = line.trim();
+        if trimmed.eq_ignore_ascii_case(":PROPERTIES:") {
+            in_drawer = true;
+        } else if trimmed.eq_ignore_ascii_case(":END:") {
+            in_drawer = false;
+        } else if in_drawer {
+            if let Some(id) = property(trimmed, ":ID:") {
+                heading.id = Some(id.to_string());
+            } else if let Some(fields) = property(trimmed, HIDDEN_PROPERTY) {
+                heading.hidden = fields.split_whitespace().map(str::to_string).collect();
+            }
+        }
+    }
+    headings
+}
+
+fn declared_keywords(text: &str) -> Vec<String> {
+    let mut keywords: Vec<String> = text
+        .lines()
+        .filter_map(|line| {
+            let upper = line.trim_start().to_ascii_uppercase();
+            ["#+TODO:", "#+SEQ_TODO:", "#+TYP_TODO:"]
+                .iter()
+                .find(|prefix| upper.starts_with(**prefix))
+                .map(|prefix| line.trim_start()[prefix.len()..].to_string())
+        })
+        .flat_map(|declared| {
+            declared
+                .split_whitespace()
+                .filter(|word| *word != "|")
+                .map(|word| word.split('(').next().unwrap_or(word).to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    if keywords.is_empty() {
+        keywords = vec!["TODO".to_string(), "DONE".to_string()];
+    }
+    keywords
+}
+
+/// The stars and text of a heading line.
+fn heading_text(line: &str) -> Option<(usize, &str)> {
+    let rest = line.trim_start_matches('*');
+    let level = line.len() - rest.len();
+    (level > 0 && rest.starts_with(' ')).then(|| (level, rest.trim()))
+}
+
+fn parse_heading(line: usize, level: usize, text: &str, keywords: &[String]) -> OrgHeading {
+    let mut rest = text;
+    let mut state = None;
+    let (first, after) = rest.split_once(' ').unwrap_or((rest, ""));
+    if keywords.iter().any(|keyword| keyword == first) {
+        state = Some(first.to_ascii_lowercase());
+        rest = after.trim_start();
+    }
+    if rest.starts_with("[#") && rest.get(3..4) == Some("]") {
+        rest = rest[4..].trim_start();
+    }
+    let mut tags = Vec::new();
+    let (title, last) = rest.rsplit_once(char::is_whitespace).unwrap_or(("", rest));
+    if last.len() > 1 && last.starts_with(':') && last.ends_with(':') {
+        tags = last
+            .split(':')
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect();
+        rest = title.trim_end();
+    }
+    OrgHeading {
+        line,
+        level,
+        id: None,
+        state,
+        title: rest.to_string(),
+        tags,
+        hidden: Vec::new(),
+    }
+}
+
+fn property<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let prefix = line.get(..name.len())?;
+    prefix
+        .eq_ignore_ascii_case(name)
+        .then(|| line[name.len()..].trim())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_org, render_org, OrgEntry, OrgKeywords};
+
+    fn entry(id: &str, state: &str, title: &str) -> OrgEntry {
+        OrgEntry {
+            id: id.to_string(),
+            state: state.to_string(),
+            title: Some(title.to_string()),
+            tags: Some(Vec::new()),
+            description: None,
+            children: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn renders_nested_headings_that_parse_back() {
+        let keywords = OrgKeywords {
+            open: vec!["planning".to_string(), "implementation".to_string()],
+            done: vec!["shipped".to_string()],
+        };
+        let mut parent = entry("k-1", "planning", "Parent");
+        parent.tags = Some(vec!["api".to_string(), "v2".to_string()]);
+        parent.description = Some("* not a heading\n\nsecond".to_string());
+        let mut child = entry("k-2", "shipped", "Child");
+        child.title = None;
+        child.tags = None;
+        parent.children.push(child);
+
+        let org = render_org(&keywords, &[parent]);
+        assert!(org.contains("#+TODO: PLANNING IMPLEMENTATION | SHIPPED\n"));
+        assert!(org.contains("* PLANNING Parent :api:v2:\n"));
+        assert!(org.contains("  * not a heading\n\n  second\n"));
+        assert!(org.contains("** SHIPPED (hidden)\n"));
+
+        let headings = parse_org(&org);
+        assert_eq!(headings.len(), 2);
+        assert_eq!(headings[0].id.as_deref(), Some("k-1"));
+        assert_eq!(headings[0].state.as_deref(), Some("planning"));
+        assert_eq!(headings[0].title, "Parent");
+        assert_eq!(headings[0].tags, vec!["api", "v2"]);
+        assert_eq!(headings[1].id.as_deref(), Some("k-2"));
+        assert_eq!(headings[1].level, 2);
+        assert_eq!(headings[1].hidden, vec!["title", "tags"]);
+    }
+
+    #[test]
+    fn parses_priorities_missing_keywords_and_default_todo_words() {
+        let headings = parse_org(
+            "* TODO [#A] Write docs :docs:\n:PROPERTIES:\n:id: abc\n:END:\n\
+             * Plain heading\n*bold* is not a heading\n",
+        );
+        assert_eq!(headings.len(), 2);
+        assert_eq!(headings[0].state.as_deref(), Some("todo"));
+        assert_eq!(headings[0].title, "Write docs");
+        assert_eq!(headings[0].tags, vec!["docs"]);
+        assert_eq!(headings[0].id.as_deref(), Some("abc"));
+        assert_eq!(headings[1].state, None);
+        assert_eq!(headings[1].title, "Plain heading");
+        assert_eq!(headings[1].id, None);
+    }
+}
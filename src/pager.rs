@@ -0,0 +1,120 @@
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const DEFAULT_PAGER: &str = "less";
+
+static DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turns paging off for the rest of the process (`--no-pager`).
+pub fn set_disabled(disabled: bool) {
+    DISABLED.store(disabled, Ordering::Relaxed);
+}
+
+/// Prints `lines`, through the pager when stdout is a terminal and they
+/// would not fit on one screen. Falls back to plain stdout if the pager
+/// cannot be started.
+pub fn print_paged(lines: &[String]) {
+    if !DISABLED.load(Ordering::Relaxed) && io::stdout().is_terminal() {
+        if let Some(pager) = pager_command(
+            std::env::var("KNOTS_PAGER").ok().as_deref(),
+            std::env::var("PAGER").ok().as_deref(),
+        ) {
+            if should_page(lines.len(), terminal_height()) && spawn_pager(&pager, lines).is_ok() {
+                return;
+            }
+        }
+    }
+    let mut stdout = io::stdout().lock();
+    for line in lines {
+        if writeln!(stdout, "{line}").is_err() {
+            return;
+        }
+    }
+}
+
+/// `KNOTS_PAGER`, else `PAGER`, else `less`. An empty value or `cat`
+/// means no pager.
+pub(crate) fn pager_command(knots_pager: Option<&str>, pager: Option<&str>) -> Option<String> {
+    let command = knots_pager.or(pager).unwrap_or(DEFAULT_PAGER).trim();
+    (!command.is_empty() && command != "cat").then(|| command.to_string())
+}
+
+/// Pages when the output is taller than the terminal, or when the height is
+/// unknown (the default `less -F` still exits on short output).
+pub(crate) fn should_page(lines: usize, height: Option<usize>) -> bool {
+    height.is_none_or(|height| lines >= height)
+}
+
+/// `LINES`, else what `tput lines` reports for the controlling terminal.
+fn terminal_height() -> Option<usize> {
+    if let Some(lines) = std::env::var("LINES")
+        .ok()
+        .and_then(|raw| raw.trim().parse().ok())
+    {
+        return Some(lines);
+    }
+    let output = Command::new("tput")
+        .arg("lines")
+        .stderr(Stdio::inherit())
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+fn spawn_pager(pager: &str, lines: &[String]) -> io::Result<()> {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", pager]);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.args(["-c", pager]);
+        command
+    };
+    // Same defaults as git: quit if one screen, keep colors, no clear.
+    if std::env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+    if std::env::var_os("LV").is_none() {
+        command.env("LV", "-c");
+    }
+    let mut child = command.stdin(Stdio::piped()).spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        for line in lines {
+            // The user quitting the pager early closes the pipe; stop quietly.
+            if writeln!(stdin, "{line}").is_err() {
+                break;
+            }
+        }
+    }
+    child.wait()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pager_command, should_page};
+
+    #[test]
+    fn pager_command_prefers_knots_pager_and_treats_cat_as_none() {
+        assert_eq!(
+            pager_command(Some("most"), Some("less -S")).as_deref(),
+            Some("most")
+        );
+        assert_eq!(
+            pager_command(None, Some("less -S")).as_deref(),
+            Some("less -S")
+        );
+        assert_eq!(pager_command(None, None).as_deref(), Some("less"));
+        assert_eq!(pager_command(Some(""), Some("less")), None);
+        assert_eq!(pager_command(None, Some(" cat ")), None);
+    }
+
+    #[test]
+    fn should_page_only_when_output_exceeds_the_screen() {
+        assert!(!should_page(10, Some(40)));
+        assert!(should_page(40, Some(40)));
+        assert!(should_page(3, None));
+    }
+}
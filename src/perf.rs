@@ -1,27 +1,43 @@
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::Instant;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::app::{App, UpdateKnotPatch};
 use crate::db;
 
+/// Allowed slowdown against a baseline when neither the flag nor the
+/// baseline file sets one.
+pub const DEFAULT_MAX_REGRESSION_PCT: f64 = 20.0;
+/// Slowdowns smaller than this are timer noise on sub-millisecond
+/// measurements, whatever their percentage.
+const REGRESSION_NOISE_MS: f64 = 1.0;
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 pub struct PerfMeasurement {
     pub name: String,
     pub elapsed_ms: f64,
     pub budget_ms: f64,
     pub within_budget: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regression_pct: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub within_baseline: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq)]
 pub struct PerfReport {
     pub iterations: u32,
     pub measurements: Vec<PerfMeasurement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_regression_pct: Option<f64>,
 }
 
 impl PerfReport {
@@ -31,6 +47,79 @@ impl PerfReport {
             .filter(|m| !m.within_budget)
             .count()
     }
+
+    pub fn regression_count(&self) -> usize {
+        self.measurements
+            .iter()
+            .filter(|m| m.within_baseline == Some(false))
+            .count()
+    }
+
+    /// Marks each measurement the baseline knows as within it or not.
+    /// Measurements missing from the baseline are left unchecked.
+    pub fn compare_to_baseline(&mut self, baseline: &PerfBaseline, max_regression_pct: f64) {
+        self.max_regression_pct = Some(max_regression_pct);
+        for measurement in &mut self.measurements {
+            let Some(&baseline_ms) = baseline.measurements.get(&measurement.name) else {
+                continue;
+            };
+            let slower_ms = measurement.elapsed_ms - baseline_ms;
+            let regression_pct = if baseline_ms > 0.0 {
+                slower_ms / baseline_ms * 100.0
+            } else {
+                0.0
+            };
+            measurement.baseline_ms = Some(baseline_ms);
+            measurement.regression_pct = Some(regression_pct);
+            measurement.within_baseline =
+                Some(slower_ms < REGRESSION_NOISE_MS || regression_pct <= max_regression_pct);
+        }
+    }
+}
+
+/// Committed reference timings for `kno perf --baseline`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PerfBaseline {
+    #[serde(default = "default_max_regression_pct")]
+    pub max_regression_pct: f64,
+    pub measurements: BTreeMap<String, f64>,
+}
+
+fn default_max_regression_pct() -> f64 {
+    DEFAULT_MAX_REGRESSION_PCT
+}
+
+impl PerfBaseline {
+    pub fn from_report(report: &PerfReport, max_regression_pct: f64) -> Self {
+        Self {
+            max_regression_pct,
+            measurements: report
+                .measurements
+                .iter()
+                .map(|m| (m.name.clone(), m.elapsed_ms))
+                .collect(),
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Self, PerfError> {
+        let raw = std::fs::read_to_string(path)?;
+        serde_json::from_str(&raw).map_err(|err| {
+            PerfError::Other(format!("invalid perf baseline {}: {err}", path.display()))
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), PerfError> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json =
+            serde_json::to_string_pretty(self).map_err(|err| PerfError::Other(err.to_string()))?;
+        std::fs::write(path, format!("{json}\n"))?;
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -112,6 +201,7 @@ pub fn run_perf_harness(iterations: u32) -> Result<PerfReport, PerfError> {
     Ok(PerfReport {
         iterations,
         measurements,
+        max_regression_pct: None,
     })
 }
 
@@ -134,6 +224,8 @@ fn benchmark_write_path(app: &App, iterations: u32) -> Result<f64, PerfError> {
                     title: None,
                     description: Some("updated".to_string()),
                     acceptance: None,
+                    estimate: None,
+                    custom_fields: Vec::new(),
                     priority: Some(1),
                     status: Some("implementing".to_string()),
                     knot_type: Some(crate::domain::knot_type::KnotType::Work),
@@ -180,7 +272,7 @@ fn benchmark_hot_reads(app: &App, iterations: u32) -> Result<f64, PerfError> {
     Ok((start.elapsed().as_secs_f64() * 1000.0) / iterations as f64)
 }
 
-fn setup_workspace() -> Result<PathBuf, PerfError> {
+pub(crate) fn setup_workspace() -> Result<PathBuf, PerfError> {
     let root = std::env::temp_dir().join(format!("knots-perf-test-{}", Uuid::now_v7()));
     std::fs::create_dir_all(&root)?;
 
@@ -248,6 +340,9 @@ fn measurement(name: &str, elapsed_ms: f64, budget_ms: f64) -> PerfMeasurement {
         elapsed_ms,
         budget_ms,
         within_budget: elapsed_ms <= budget_ms,
+        baseline_ms: None,
+        regression_pct: None,
+        within_baseline: None,
     }
 }
 
@@ -257,7 +352,8 @@ mod tests {
     use std::path::Path;
 
     use super::{
-        run_git, run_perf_harness, set_bare_head_to_main, PerfError, PerfMeasurement, PerfReport,
+        measurement, run_git, run_perf_harness, set_bare_head_to_main, PerfBaseline, PerfError,
+        PerfMeasurement, PerfReport,
     };
 
     #[test]
@@ -280,14 +376,21 @@ mod tests {
                     elapsed_ms: 1.0,
                     budget_ms: 2.0,
                     within_budget: true,
+                    baseline_ms: None,
+                    regression_pct: None,
+                    within_baseline: None,
                 },
                 PerfMeasurement {
                     name: "slow".to_string(),
                     elapsed_ms: 3.0,
                     budget_ms: 2.0,
                     within_budget: false,
+                    baseline_ms: None,
+                    regression_pct: None,
+                    within_baseline: None,
                 },
             ],
+            max_regression_pct: None,
         };
         assert_eq!(report.over_budget_count(), 1);
 
@@ -312,4 +415,67 @@ mod tests {
         let bad_head = set_bare_head_to_main(Path::new("/no/such/bare-repo.git"));
         assert!(bad_head.is_err());
     }
+
+    #[test]
+    fn compare_to_baseline_flags_regressions_beyond_the_allowed_percent() {
+        let mut report = PerfReport {
+            iterations: 1,
+            measurements: vec![
+                measurement("read_hot_avg", 0.9, 20.0),
+                measurement("write_avg", 60.0, 150.0),
+                measurement("sync", 130.0, 1000.0),
+                measurement("new_metric", 5.0, 10.0),
+            ],
+            max_regression_pct: None,
+        };
+        let baseline = PerfBaseline {
+            max_regression_pct: 20.0,
+            measurements: [
+                ("read_hot_avg".to_string(), 0.3),
+                ("write_avg".to_string(), 40.0),
+                ("sync".to_string(), 120.0),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        report.compare_to_baseline(&baseline, 25.0);
+
+        let within: Vec<Option<bool>> = report
+            .measurements
+            .iter()
+            .map(|m| m.within_baseline)
+            .collect();
+        // 200% slower but only 0.6ms: noise. 50% slower: regression.
+        assert_eq!(within, vec![Some(true), Some(false), Some(true), None]);
+        assert_eq!(report.regression_count(), 1);
+        assert_eq!(report.measurements[1].regression_pct, Some(50.0));
+        assert_eq!(report.max_regression_pct, Some(25.0));
+    }
+
+    #[test]
+    fn baseline_round_trips_and_defaults_the_allowed_regression() {
+        let report = PerfReport {
+            iterations: 1,
+            measurements: vec![measurement("sync", 120.0, 1000.0)],
+            max_regression_pct: None,
+        };
+        let dir =
+            std::env::temp_dir().join(format!("knots-perf-baseline-{}", uuid::Uuid::now_v7()));
+        let path = dir.join("nested").join("baseline.json");
+        let baseline = PerfBaseline::from_report(&report, 15.0);
+        baseline.write(&path).expect("baseline should write");
+        assert_eq!(
+            PerfBaseline::read(&path).expect("baseline should read"),
+            baseline
+        );
+
+        std::fs::write(&path, r#"{"measurements":{"sync":1.0}}"#).expect("write raw");
+        let read = PerfBaseline::read(&path).expect("baseline without pct");
+        assert_eq!(read.max_regression_pct, super::DEFAULT_MAX_REGRESSION_PCT);
+
+        std::fs::write(&path, "not json").expect("write raw");
+        let err = PerfBaseline::read(&path).expect_err("invalid baseline");
+        assert!(err.to_string().contains("invalid perf baseline"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
 }
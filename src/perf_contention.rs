@@ -0,0 +1,284 @@
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::app::{self, App, AppError, AppPool, UpdateKnotPatch};
+use crate::db;
+use crate::locks::{self, LockError};
+use crate::perf::{setup_workspace, PerfError};
+use crate::project::{DistributionMode, ProjectContext, StorePaths};
+
+/// Lock-wait distribution and failure counts from parallel in-process
+/// writers sharing one workspace.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ContentionReport {
+    pub writers: u32,
+    pub ops_per_writer: u32,
+    /// Connections shared by all writers; absent when each writer opens
+    /// its own `App`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pool_size: Option<u32>,
+    pub elapsed_ms: f64,
+    pub completed_ops: u64,
+    /// Operations that gave up on a lock after its acquire timeout.
+    pub lock_timeouts: u64,
+    pub errors: u64,
+    /// Writers still running when no writer made progress for the stall
+    /// timeout; a deadlock or a hung lock holder.
+    pub stalled_writers: u32,
+    pub lock_acquisitions: u64,
+    pub lock_wait_p50_ms: f64,
+    pub lock_wait_p95_ms: f64,
+    pub lock_wait_max_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_error: Option<String>,
+}
+
+impl ContentionReport {
+    pub fn is_clean(&self) -> bool {
+        self.lock_timeouts == 0 && self.errors == 0 && self.stalled_writers == 0
+    }
+}
+
+enum WriterMessage {
+    Op {
+        waits: Vec<Duration>,
+        result: Result<(), AppError>,
+    },
+    Done,
+}
+
+/// Runs `writers` threads, each doing `ops_per_writer` create/update/state
+/// cycles against a throwaway workspace. Each writer opens its own `App`,
+/// or with `pool_size` checks one out of a shared `AppPool` per cycle.
+pub fn run_contention(
+    writers: u32,
+    ops_per_writer: u32,
+    stall_timeout: Duration,
+    pool_size: Option<u32>,
+) -> Result<ContentionReport, PerfError> {
+    let writers = writers.max(1);
+    let ops_per_writer = ops_per_writer.max(1);
+    let root = setup_workspace()?;
+    let db_path = root.join(".knots/cache/state.sqlite");
+    std::fs::create_dir_all(
+        db_path
+            .parent()
+            .expect("db parent should exist for contention run"),
+    )?;
+    let db_path = db_path.to_str().expect("utf8 path").to_string();
+    App::open(&db_path, root.clone()).map_err(|err| PerfError::Other(err.to_string()))?;
+    db::set_meta(&db::open_connection(&db_path)?, "sync_policy", "never")?;
+    let pool_size = pool_size.map(|size| size.max(1));
+    let pool = match pool_size {
+        Some(size) => Some(
+            AppPool::open(&contention_context(root.clone()), &db_path, size as usize)
+                .map_err(|err| PerfError::Other(err.to_string()))?,
+        ),
+        None => None,
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let start = Instant::now();
+    for writer in 0..writers {
+        let sender = sender.clone();
+        let db_path = db_path.clone();
+        let root = root.clone();
+        let pool = pool.clone();
+        thread::spawn(move || {
+            match pool {
+                Some(pool) => run_pooled_writer(writer, ops_per_writer, &pool, &sender),
+                None => run_writer(writer, ops_per_writer, &db_path, root, &sender),
+            }
+            let _ = sender.send(WriterMessage::Done);
+        });
+    }
+    drop(sender);
+
+    let mut report = ContentionReport {
+        writers,
+        ops_per_writer,
+        pool_size,
+        elapsed_ms: 0.0,
+        completed_ops: 0,
+        lock_timeouts: 0,
+        errors: 0,
+        stalled_writers: 0,
+        lock_acquisitions: 0,
+        lock_wait_p50_ms: 0.0,
+        lock_wait_p95_ms: 0.0,
+        lock_wait_max_ms: 0.0,
+        first_error: None,
+    };
+    let mut waits_us = Vec::new();
+    let mut running = writers;
+    while running > 0 {
+        match receiver.recv_timeout(stall_timeout) {
+            Ok(WriterMessage::Op { waits, result }) => {
+                waits_us.extend(waits.iter().map(|wait| wait.as_micros() as u64));
+                match result {
+                    Ok(()) => report.completed_ops += 1,
+                    Err(AppError::Lock(LockError::Busy(_))) => report.lock_timeouts += 1,
+                    Err(err) => {
+                        report.errors += 1;
+                        report.first_error.get_or_insert_with(|| err.to_string());
+                    }
+                }
+            }
+            Ok(WriterMessage::Done) => running -= 1,
+            Err(RecvTimeoutError::Timeout) => {
+                report.stalled_writers = running;
+                break;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    report.elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    waits_us.sort_unstable();
+    let ms = |us: Option<u64>| us.unwrap_or(0) as f64 / 1000.0;
+    report.lock_acquisitions = waits_us.len() as u64;
+    report.lock_wait_p50_ms = ms(app::percentile(&waits_us, 50));
+    report.lock_wait_p95_ms = ms(app::percentile(&waits_us, 95));
+    report.lock_wait_max_ms = ms(waits_us.last().copied());
+
+    // Stalled writers may still be using the workspace.
+    if report.stalled_writers == 0 {
+        let _ = std::fs::remove_dir_all(root);
+    }
+    Ok(report)
+}
+
+fn run_writer(
+    writer: u32,
+    ops: u32,
+    db_path: &str,
+    root: std::path::PathBuf,
+    sender: &mpsc::Sender<WriterMessage>,
+) {
+    let app = match App::open(db_path, root) {
+        Ok(app) => app,
+        Err(err) => {
+            let _ = sender.send(WriterMessage::Op {
+                waits: Vec::new(),
+                result: Err(err),
+            });
+            return;
+        }
+    };
+    for op in 0..ops {
+        let (result, waits) = locks::record_lock_waits(|| write_cycle(&app, writer, op));
+        if sender.send(WriterMessage::Op { waits, result }).is_err() {
+            return;
+        }
+    }
+}
+
+fn run_pooled_writer(writer: u32, ops: u32, pool: &AppPool, sender: &mpsc::Sender<WriterMessage>) {
+    for op in 0..ops {
+        let (result, waits) = locks::record_lock_waits(|| {
+            let app = pool.get()?;
+            write_cycle(&app, writer, op)
+        });
+        if sender.send(WriterMessage::Op { waits, result }).is_err() {
+            return;
+        }
+    }
+}
+
+/// The context `App::open` builds for a plain git workspace at `root`.
+fn contention_context(root: std::path::PathBuf) -> ProjectContext {
+    ProjectContext {
+        project_id: None,
+        store_paths: StorePaths {
+            root: root.join(".knots"),
+        },
+        repo_root: root,
+        distribution: DistributionMode::Git,
+    }
+}
+
+fn write_cycle(app: &App, writer: u32, op: u32) -> Result<(), AppError> {
+    let knot = app.create_knot(
+        &format!("contention-{writer}-{op}"),
+        Some("body"),
+        Some("ready_for_planning"),
+        Some("default"),
+    )?;
+    let knot = app.update_knot(
+        &knot.id,
+        UpdateKnotPatch {
+            description: Some("updated".to_string()),
+            priority: Some(1),
+            expected_profile_etag: knot.profile_etag.clone(),
+            ..UpdateKnotPatch::default()
+        },
+    )?;
+    app.set_state(&knot.id, "planning", false, knot.profile_etag.as_deref())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{run_contention, ContentionReport};
+
+    #[test]
+    fn parallel_writers_complete_every_cycle_and_record_lock_waits() {
+        let report = run_contention(3, 2, Duration::from_secs(60), None).expect("contention run");
+        assert_eq!(report.completed_ops, 6, "{:?}", report.first_error);
+        assert!(report.is_clean());
+        // Each cycle takes the repo and cache locks for all three writes.
+        assert!(report.lock_acquisitions >= 36);
+        assert!(report.lock_wait_p50_ms <= report.lock_wait_p95_ms);
+        assert!(report.lock_wait_p95_ms <= report.lock_wait_max_ms);
+    }
+
+    #[test]
+    fn pooled_writers_share_connections_and_complete_every_cycle() {
+        let report =
+            run_contention(4, 2, Duration::from_secs(60), Some(2)).expect("contention run");
+        assert_eq!(report.pool_size, Some(2));
+        assert_eq!(report.completed_ops, 8, "{:?}", report.first_error);
+        assert!(report.is_clean());
+    }
+
+    #[test]
+    fn timeouts_errors_and_stalls_make_a_run_unclean() {
+        let clean = ContentionReport {
+            writers: 1,
+            ops_per_writer: 1,
+            pool_size: None,
+            elapsed_ms: 1.0,
+            completed_ops: 1,
+            lock_timeouts: 0,
+            errors: 0,
+            stalled_writers: 0,
+            lock_acquisitions: 2,
+            lock_wait_p50_ms: 0.0,
+            lock_wait_p95_ms: 0.0,
+            lock_wait_max_ms: 0.0,
+            first_error: None,
+        };
+        assert!(clean.is_clean());
+        for unclean in [
+            ContentionReport {
+                lock_timeouts: 1,
+                ..clean.clone()
+            },
+            ContentionReport {
+                errors: 1,
+                ..clean.clone()
+            },
+            ContentionReport {
+                stalled_writers: 1,
+                ..clean.clone()
+            },
+        ] {
+            assert!(!unclean.is_clean());
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use clap::FromArgMatches;
+
+use crate::app::{self, KnotView};
+use crate::cli::{Cli, PickArgs};
+use crate::listing::{self, KnotListFilter};
+use crate::project::ProjectContext;
+
+/// Argument that `kno` replaces with an interactively picked knot id.
+pub(crate) const PICK_TOKEN: &str = "@pick";
+const SHOWN: usize = 10;
+
+pub fn run_pick(app: &app::App, args: PickArgs) -> Result<(), app::AppError> {
+    let filter = KnotListFilter {
+        include_all: args.all,
+        state: args.state,
+        knot_type: args.knot_type,
+        ..KnotListFilter::default()
+    };
+    let knots = listing::apply_filters(app.list_knots()?, &filter);
+    let id = pick_from(
+        &mut io::stdin().lock(),
+        &mut io::stderr(),
+        &knots,
+        args.query.as_deref().unwrap_or(""),
+    )?;
+    println!("{}", id.ok_or_else(no_pick)?);
+    Ok(())
+}
+
+/// Re-parses the command line with every `@pick` argument replaced by a knot
+/// id chosen on the terminal; `cli` is returned as is when there are none.
+pub(crate) fn resolve_picks(
+    cli: Cli,
+    context: &ProjectContext,
+    db_path: &str,
+) -> Result<Cli, app::AppError> {
+    let Some(expanded) = expand_pick_args(std::env::args().collect(), context, db_path)? else {
+        return Ok(cli);
+    };
+    let mut matches = crate::cli::styled_command().get_matches_from(expanded);
+    Ok(Cli::from_arg_matches_mut(&mut matches).expect("arg matches should be valid"))
+}
+
+/// Replaces `@pick` arguments with knot ids chosen on the terminal. Returns
+/// `None` when no argument asks for a pick.
+fn expand_pick_args(
+    args: Vec<String>,
+    context: &ProjectContext,
+    db_path: &str,
+) -> Result<Option<Vec<String>>, app::AppError> {
+    if !args.iter().skip(1).any(|arg| arg == PICK_TOKEN) {
+        return Ok(None);
+    }
+    if !io::stdin().is_terminal() {
+        return Err(app::AppError::InvalidArgument(format!(
+            "{PICK_TOKEN} needs an interactive terminal on stdin"
+        )));
+    }
+    let knots = app::App::open_with_context(context, db_path)?.list_knots()?;
+    let knots = listing::apply_filters(knots, &KnotListFilter::default());
+    let mut expanded = Vec::with_capacity(args.len());
+    for (index, arg) in args.into_iter().enumerate() {
+        if index > 0 && arg == PICK_TOKEN {
+            let id = pick_from(&mut io::stdin().lock(), &mut io::stderr(), &knots, "")?;
+            expanded.push(id.ok_or_else(no_pick)?);
+        } else {
+            expanded.push(arg);
+        }
+    }
+    Ok(Some(expanded))
+}
+
+fn no_pick() -> app::AppError {
+    app::AppError::InvalidArgument("no knot picked".to_string())
+}
+
+/// Line-driven picker: typing text narrows the list, a number or an empty
+/// line picks, `q` or end of input cancels.
+pub(crate) fn pick_from<R: BufRead, W: Write>(
+    input: &mut R,
+    out: &mut W,
+    knots: &[KnotView],
+    query: &str,
+) -> Result<Option<String>, app::AppError> {
+    let mut query = query.trim().to_string();
+    loop {
+        let matches = rank(&query, knots);
+        for line in format_matches(&query, &matches) {
+            writeln!(out, "{line}")?;
+        }
+        write!(out, "pick> ")?;
+        out.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        match line.trim() {
+            "q" => return Ok(None),
+            "" => {
+                if let Some(first) = matches.first() {
+                    return Ok(Some(first.id.clone()));
+                }
+            }
+            choice => match choice.parse::<usize>() {
+                Ok(n) if (1..=matches.len().min(SHOWN)).contains(&n) => {
+                    return Ok(Some(matches[n - 1].id.clone()));
+                }
+                _ => query = choice.to_string(),
+            },
+        }
+    }
+}
+
+pub(crate) fn format_matches(query: &str, matches: &[&KnotView]) -> Vec<String> {
+    let mut lines = vec![match query {
+        "" => format!("{} knot(s)", matches.len()),
+        query => format!("{} match(es) for '{query}'", matches.len()),
+    }];
+    for (index, knot) in matches.iter().take(SHOWN).enumerate() {
+        lines.push(format!(
+            "{:>3}) {}  [{}]  {}",
+            index + 1,
+            crate::knot_id::display_id(&knot.id),
+            knot.state,
+            knot.title
+        ));
+    }
+    if matches.len() > SHOWN {
+        lines.push(format!(
+            "     … {} more; type to narrow",
+            matches.len() - SHOWN
+        ));
+    }
+    lines
+}
+
+/// Knots whose id, alias, title, or tags fuzzily match `query`, best first.
+pub(crate) fn rank<'a>(query: &str, knots: &'a [KnotView]) -> Vec<&'a KnotView> {
+    let mut scored: Vec<(i64, &KnotView)> = knots
+        .iter()
+        .filter_map(|knot| {
+            let haystack = format!(
+                "{} {} {} {}",
+                crate::knot_id::display_id(&knot.id),
+                knot.alias.as_deref().unwrap_or(""),
+                knot.title,
+                knot.tags.join(" ")
+            );
+            fuzzy_score(query, &haystack).map(|score| (score, knot))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, knot)| knot).collect()
+}
+
+/// Case-insensitive subsequence match. Runs of consecutive characters and
+/// matches at word starts score higher; `None` when `query` does not match.
+pub(crate) fn fuzzy_score(query: &str, haystack: &str) -> Option<i64> {
+    let haystack: Vec<char> = haystack.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut from = 0;
+    let mut previous: Option<usize> = None;
+    for wanted in query
+        .to_lowercase()
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+    {
+        let offset = haystack[from..].iter().position(|ch| *ch == wanted)?;
+        let at = from + offset;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == at) {
+            score += 5;
+        }
+        if at == 0 || !haystack[at - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(at);
+        from = at + 1;
+    }
+    Some(score)
+}
@@ -0,0 +1,118 @@
+use std::io::Cursor;
+
+use crate::app::KnotView;
+use crate::domain::knot_type::KnotType;
+use crate::pick_commands::{format_matches, fuzzy_score, pick_from, rank};
+
+fn knot(id: &str, title: &str, tags: &[&str]) -> KnotView {
+    KnotView {
+        id: id.to_string(),
+        alias: None,
+        title: title.to_string(),
+        state: "implementation".to_string(),
+        updated_at: "2026-10-16T00:00:00Z".to_string(),
+        body: None,
+        description: None,
+        acceptance: None,
+        estimate: None,
+        priority: Some(1),
+        knot_type: KnotType::default(),
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        custom_fields: Default::default(),
+        notes: vec![],
+        handoff_capsules: vec![],
+        invariants: vec![],
+        step_history: vec![],
+        gate: None,
+        lease: None,
+        lease_id: None,
+        lease_expiry_ts: 0,
+        lease_agent: None,
+        workflow_id: "work_sdlc".to_string(),
+        profile_id: "autopilot".to_string(),
+        profile_etag: None,
+        deferred_from_state: None,
+        blocked_from_state: None,
+        created_at: None,
+        snoozed_until: None,
+        step_metadata: None,
+        next_step_metadata: None,
+        edges: vec![],
+        child_summaries: vec![],
+        git_links: vec![],
+        criteria: Vec::new(),
+        handbacks: Vec::new(),
+        plan: None,
+    }
+}
+
+fn knots() -> Vec<KnotView> {
+    vec![
+        knot("knots-a1b2", "Fix login redirect", &["auth"]),
+        knot("knots-c3d4", "Add billing export", &["billing"]),
+        knot("knots-e5f6", "Flaky integration tests", &["ci"]),
+    ]
+}
+
+#[test]
+fn fuzzy_score_prefers_word_starts_and_runs() {
+    assert_eq!(fuzzy_score("", "anything"), Some(0));
+    assert_eq!(fuzzy_score("xyz", "fix login"), None);
+    let run = fuzzy_score("login", "fix login").expect("run matches");
+    let scattered = fuzzy_score("login", "lazy old gnu in nest").expect("subsequence matches");
+    assert!(run > scattered, "{run} <= {scattered}");
+    assert!(fuzzy_score("FL", "flaky integration").is_some());
+}
+
+#[test]
+fn rank_matches_ids_titles_and_tags() {
+    let knots = knots();
+    let ids = |query: &str| -> Vec<String> {
+        rank(query, &knots)
+            .into_iter()
+            .map(|knot| knot.id.clone())
+            .collect()
+    };
+    assert_eq!(ids("billing")[0], "knots-c3d4");
+    assert_eq!(ids("e5f6"), vec!["knots-e5f6"]);
+    assert_eq!(ids("auth"), vec!["knots-a1b2"]);
+    assert_eq!(ids("").len(), 3);
+}
+
+#[test]
+fn pick_from_narrows_then_picks_by_number_or_enter() {
+    let knots = knots();
+    let mut out = Vec::new();
+    let picked =
+        pick_from(&mut Cursor::new("flaky\n1\n"), &mut out, &knots, "").expect("pick should run");
+    assert_eq!(picked.as_deref(), Some("knots-e5f6"));
+    let shown = String::from_utf8(out).expect("utf8");
+    assert!(shown.contains("3 knot(s)"), "{shown}");
+    assert!(shown.contains("1 match(es) for 'flaky'"), "{shown}");
+
+    let picked = pick_from(&mut Cursor::new("\n"), &mut Vec::new(), &knots, "billing")
+        .expect("pick should run");
+    assert_eq!(picked.as_deref(), Some("knots-c3d4"));
+}
+
+#[test]
+fn pick_from_cancels_on_q_or_end_of_input() {
+    let knots = knots();
+    let quit = pick_from(&mut Cursor::new("q\n"), &mut Vec::new(), &knots, "");
+    assert_eq!(quit.expect("pick should run"), None);
+    let eof = pick_from(&mut Cursor::new("zzz\n"), &mut Vec::new(), &knots, "");
+    assert_eq!(eof.expect("pick should run"), None);
+}
+
+#[test]
+fn format_matches_lists_numbered_rows_and_overflow() {
+    let many: Vec<KnotView> = (0..12)
+        .map(|n| knot(&format!("knots-{n:04}"), &format!("Knot {n}"), &[]))
+        .collect();
+    let refs: Vec<&KnotView> = many.iter().collect();
+    let lines = format_matches("", &refs);
+    assert_eq!(lines[0], "12 knot(s)");
+    assert_eq!(lines[1], "  1) 0000  [implementation]  Knot 0");
+    assert_eq!(lines.len(), 12);
+    assert!(lines[11].contains("2 more"));
+}
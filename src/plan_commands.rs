@@ -0,0 +1,77 @@
+use std::io::Read;
+
+use crate::app::{self, App};
+use crate::cli::{PlanArgs, PlanSubcommands};
+use crate::db::PlanRecord;
+use crate::print_json;
+
+pub fn run_plan(app: &App, args: PlanArgs) -> Result<(), app::AppError> {
+    match args.command {
+        PlanSubcommands::Set(set) => {
+            let content = if set.file == "-" {
+                let mut text = String::new();
+                std::io::stdin().read_to_string(&mut text)?;
+                text
+            } else {
+                std::fs::read_to_string(&set.file)?
+            };
+            let plan = app.set_plan(&set.id, &content, set.agent_name.as_deref())?;
+            if set.json {
+                print_json(&plan);
+            } else {
+                println!("plan {}", crate::ui::format_plan_version(&plan));
+            }
+        }
+        PlanSubcommands::Show(show) => {
+            let plan = select_version(app.plans(&show.id)?, &show.id, show.version)?;
+            if show.json {
+                print_json(&plan);
+            } else {
+                print!("{}", plan.content);
+                if !plan.content.ends_with('\n') {
+                    println!();
+                }
+            }
+        }
+        PlanSubcommands::Week(week) => crate::week_plan::run_plan_week(app, week)?,
+        PlanSubcommands::Carryover(report) => crate::week_plan::run_plan_carryover(app, report)?,
+        PlanSubcommands::Log(log) => {
+            let plans = app.plans(&log.id)?;
+            if log.json {
+                print_json(&plans);
+            } else if plans.is_empty() {
+                println!("no plan");
+            } else {
+                for plan in plans.iter().rev() {
+                    println!("{}", crate::ui::format_plan_version(plan));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The requested version, or the latest when none is named.
+fn select_version(
+    mut plans: Vec<PlanRecord>,
+    id: &str,
+    version: Option<usize>,
+) -> Result<PlanRecord, app::AppError> {
+    let count = plans.len();
+    if count == 0 {
+        return Err(app::AppError::InvalidArgument(format!(
+            "{id} has no plan; attach one with `kno plan set --file`"
+        )));
+    }
+    let Some(version) = version else {
+        return Ok(plans.pop().expect("plans is non-empty"));
+    };
+    plans
+        .into_iter()
+        .find(|plan| plan.version == version)
+        .ok_or_else(|| {
+            app::AppError::InvalidArgument(format!(
+                "{id} has {count} plan versions; there is no v{version}"
+            ))
+        })
+}
@@ -1,5 +1,5 @@
 use crate::action_prompt;
-use crate::app::{App, AppError, KnotView, StateActorMetadata};
+use crate::app::{App, AppError, KnotView, StateActorMetadata, StateAge};
 use crate::cli::{ClaimArgs, PollArgs};
 use crate::dispatch::profile_lookup_id;
 use crate::domain::knot_type::KnotType;
@@ -16,6 +16,9 @@ use crate::cli::ReadyArgs;
 use ready::normalize_ready_type;
 use ready::parse_owner_filter;
 pub use ready::{list_queue_candidates, run_ready};
+#[path = "poll_claim/suggest.rs"]
+mod suggest;
+pub use suggest::run_suggest;
 
 const AGENT_COMPLETION_METADATA_FLAGS: &str = concat!(
     "--actor-kind agent ",
@@ -28,12 +31,19 @@ pub struct PollResult {
     pub knot: KnotView,
     pub skill: String,
     pub completion_cmd: String,
+    /// Time the knot spent in its queue state before this hand-off.
+    pub queue_age: Option<StateAge>,
 }
 
 pub fn run_poll(app: &App, args: PollArgs) -> Result<(), AppError> {
     use crate::lease_expiry::DEFAULT_LEASE_TIMEOUT_SECONDS;
     let result = crate::trace::measure("poll_queue", || {
-        poll_queue(app, args.stage.as_deref(), args.owner.as_deref())
+        poll_queue(
+            app,
+            args.stage.as_deref(),
+            args.owner.as_deref(),
+            args.agent_name.as_deref(),
+        )
     })?;
     match result {
         None => {
@@ -106,11 +116,14 @@ pub fn peek_knot(app: &App, id: &str) -> Result<PollResult, AppError> {
         ))
     })?;
     let skill = prompt_body_for_state(registry, &profile_id, &next_action)?;
+    let skill = expand_skill_for(app, &skill, &knot)?;
     let completion_cmd = completion_command(&knot.id, &next_action, None);
+    let queue_age = Some(app.state_age(&knot)?);
     Ok(PollResult {
         knot,
         skill,
         completion_cmd,
+        queue_age,
     })
 }
 
@@ -128,16 +141,29 @@ fn print_result_verbose(result: &PollResult, json: bool, verbose: bool) {
     }
 }
 
+/// The top claimable knot, skipping knots `agent` handed back that are
+/// still cooling down.
 pub fn poll_queue(
     app: &App,
     stage: Option<&str>,
     owner_filter: Option<&str>,
+    agent: Option<&str>,
 ) -> Result<Option<PollResult>, AppError> {
+    app.wake_snoozed_knots()?;
     let registry = app.profile_registry();
     let owner_kind = parse_owner_filter(owner_filter);
+    let cooling = match agent {
+        Some(agent) => app.handback_cooldowns(agent)?,
+        None => Default::default(),
+    };
     let knots = list_queue_candidates(app, stage)?;
     for knot in knots {
-        if let Some(result) = match_pollable(&knot, registry, &owner_kind)? {
+        if cooling.contains_key(&knot.id) {
+            continue;
+        }
+        if let Some(mut result) = match_pollable(&knot, registry, &owner_kind)? {
+            result.skill = expand_skill_for(app, &result.skill, &result.knot)?;
+            result.queue_age = Some(app.state_age(&result.knot)?);
             return Ok(Some(result));
         }
     }
@@ -150,6 +176,19 @@ pub fn claim_knot(
     actor: StateActorMetadata,
     external_lease: Option<&str>,
     timeout_seconds: u64,
+) -> Result<PollResult, AppError> {
+    claim_knot_with_force(app, id, actor, external_lease, timeout_seconds, false)
+}
+
+/// `force` claims past a WIP limit on the next state and past the
+/// claiming agent's handback cooldown.
+pub fn claim_knot_with_force(
+    app: &App,
+    id: &str,
+    actor: StateActorMetadata,
+    external_lease: Option<&str>,
+    timeout_seconds: u64,
+    force: bool,
 ) -> Result<PollResult, AppError> {
     let registry = app.profile_registry();
     let knot = app
@@ -162,6 +201,9 @@ pub fn claim_knot(
         knot
     };
     require_queue_state(registry, &knot)?;
+    if !force {
+        reject_cooling_claim(app, &knot, actor.agent_name.as_deref())?;
+    }
     let profile_id = profile_lookup_id(&knot);
     let next_action = workflow_runtime::next_happy_path_state(
         registry,
@@ -176,6 +218,7 @@ pub fn claim_knot(
         ))
     })?;
     let skill = prompt_body_for_state(registry, &profile_id, &next_action)?;
+    let queue_age = Some(app.state_age(&knot)?);
     let claim_actor = StateActorMetadata {
         actor_kind: Some(actor.actor_kind.unwrap_or_else(|| "agent".to_string())),
         ..actor
@@ -187,7 +230,7 @@ pub fn claim_knot(
     let claimed = app.set_state_with_actor_and_options(
         &knot.id,
         &next_action,
-        false,
+        force,
         knot.profile_etag.as_deref(),
         claim_actor,
         false,
@@ -208,9 +251,10 @@ pub fn claim_knot(
         .ok_or_else(|| AppError::NotFound(claimed.id.clone()))?;
     let completion_cmd = completion_command(&bound.id, &bound.state, bound_lease_id.as_deref());
     Ok(PollResult {
+        skill: expand_skill_for(app, &skill, &bound)?,
         knot: bound,
-        skill,
         completion_cmd,
+        queue_age,
     })
 }
 
@@ -246,19 +290,31 @@ fn create_and_bind_lease(
 }
 
 pub fn render_text(result: &PollResult) -> String {
-    prompt::render_prompt(&result.knot, &result.skill, &result.completion_cmd)
+    render_text_verbose(result, false)
 }
 
 pub fn render_text_verbose(result: &PollResult, verbose: bool) -> String {
-    prompt::render_prompt_verbose(&result.knot, &result.skill, &result.completion_cmd, verbose)
+    prompt::render_prompt_verbose(
+        &result.knot,
+        &result.skill,
+        &result.completion_cmd,
+        verbose,
+        result.queue_age.as_ref(),
+    )
 }
 
 pub fn render_json(result: &PollResult) -> serde_json::Value {
-    prompt::render_prompt_json(&result.knot, &result.skill, &result.completion_cmd)
+    render_json_verbose(result, false)
 }
 
 pub fn render_json_verbose(result: &PollResult, verbose: bool) -> serde_json::Value {
-    prompt::render_prompt_json_verbose(&result.knot, &result.skill, &result.completion_cmd, verbose)
+    prompt::render_prompt_json_verbose(
+        &result.knot,
+        &result.skill,
+        &result.completion_cmd,
+        verbose,
+        result.queue_age.as_ref(),
+    )
 }
 
 fn match_pollable(
@@ -299,6 +355,7 @@ fn match_pollable(
         knot: knot.clone(),
         skill,
         completion_cmd,
+        queue_age: None,
     }))
 }
 
@@ -362,71 +419,29 @@ fn prompt_body_for_state(
     })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn parse_owner_defaults_to_agent() {
-        assert_eq!(parse_owner_filter(None), OwnerKind::Agent);
-        assert_eq!(parse_owner_filter(Some("")), OwnerKind::Agent);
-        assert_eq!(parse_owner_filter(Some("agent")), OwnerKind::Agent);
-    }
-
-    #[test]
-    fn parse_owner_recognizes_human() {
-        assert_eq!(parse_owner_filter(Some("human")), OwnerKind::Human);
-        assert_eq!(parse_owner_filter(Some("Human")), OwnerKind::Human);
-    }
-
-    #[test]
-    fn normalize_ready_type_none_returns_none() {
-        assert_eq!(normalize_ready_type(None), None);
-    }
-
-    #[test]
-    fn normalize_ready_type_empty_returns_none() {
-        assert_eq!(normalize_ready_type(Some("")), None);
-        assert_eq!(normalize_ready_type(Some("  ")), None);
-    }
-
-    #[test]
-    fn normalize_ready_type_strips_prefix() {
-        assert_eq!(
-            normalize_ready_type(Some("ready_for_planning")),
-            Some("planning".to_string())
-        );
-    }
-
-    #[test]
-    fn normalize_ready_type_passes_through_stage() {
-        assert_eq!(normalize_ready_type(Some("plan")), Some("plan".to_string()));
-        assert_eq!(
-            normalize_ready_type(Some("implementation")),
-            Some("implementation".to_string())
-        );
-    }
-
-    #[test]
-    fn normalize_ready_type_lowercases_and_replaces_dashes() {
-        assert_eq!(
-            normalize_ready_type(Some("Plan-Review")),
-            Some("plan_review".to_string())
-        );
+fn reject_cooling_claim(app: &App, knot: &KnotView, agent: Option<&str>) -> Result<(), AppError> {
+    let Some(agent) = agent else {
+        return Ok(());
+    };
+    match app.handback_cooldowns(agent)?.get(&knot.id) {
+        Some(until) => Err(AppError::InvalidArgument(format!(
+            "{agent} handed knot '{}' back and cannot reclaim it until {until} \
+             (use --force to claim anyway)",
+            knot.id
+        ))),
+        None => Ok(()),
     }
+}
 
-    #[test]
-    fn completion_command_includes_agent_metadata_flags() {
-        let cmd = completion_command("knots-27ef", "implementation", None);
-        assert_eq!(
-            cmd,
-            "kno next knots-27ef --expected-state implementation --actor-kind agent \
-             --agent-name <AGENT_NAME> --agent-model <AGENT_MODEL> \
-             --agent-version <AGENT_VERSION>"
-        );
-    }
+fn expand_skill_for(app: &App, skill: &str, knot: &KnotView) -> Result<String, AppError> {
+    let profile = app.profile_registry().require(&profile_lookup_id(knot))?;
+    Ok(app.expand_skill(skill, profile, Some(knot)))
 }
 
+#[cfg(test)]
+#[path = "poll_claim/tests.rs"]
+mod tests;
+
 #[cfg(test)]
 #[path = "poll_claim/tests_ext2.rs"]
 mod tests_ext2;
@@ -41,6 +41,7 @@ pub fn list_queue_candidates(app: &App, stage: Option<&str>) -> Result<Vec<KnotV
         knot_type: None,
         profile_id: None,
         tags: Vec::new(),
+        custom_fields: Vec::new(),
         query: None,
     };
     let mut knots = apply_filters(app.list_knots()?, &filter);
@@ -0,0 +1,98 @@
+use crate::app::{App, AppError, SuggestReport};
+use crate::cli::SuggestArgs;
+use crate::workflow::OwnerKind;
+
+use super::{list_queue_candidates, match_pollable};
+
+pub fn run_suggest(app: &App, args: SuggestArgs) -> Result<(), AppError> {
+    let report = crate::trace::measure("suggest", || {
+        app.wake_snoozed_knots()?;
+        let registry = app.profile_registry();
+        let cooling = app.handback_cooldowns(&args.agent)?;
+        let mut candidates = Vec::new();
+        for knot in list_queue_candidates(app, args.stage.as_deref())? {
+            if cooling.contains_key(&knot.id) {
+                continue;
+            }
+            if match_pollable(&knot, registry, &OwnerKind::Agent)?.is_some() {
+                candidates.push(knot);
+            }
+        }
+        app.suggest_for_agent(&args.agent, candidates, args.limit)
+    })?;
+    if args.json {
+        crate::print_json(&report);
+    } else {
+        print!("{}", render_suggestions(&report));
+    }
+    Ok(())
+}
+
+/// A capacity line, then one numbered row per suggestion with its reasons.
+pub fn render_suggestions(report: &SuggestReport) -> String {
+    let mut out = match report.capacity {
+        Some(capacity) => format!(
+            "{}: {} of {capacity} slot(s) in use\n",
+            report.agent, report.active
+        ),
+        None => format!("{}: {} active claim(s)\n", report.agent, report.active),
+    };
+    if report.suggestions.is_empty() {
+        let reason = match report.capacity {
+            Some(capacity) if report.active >= capacity => "agent is at capacity",
+            _ => "no claimable knots",
+        };
+        out.push_str(&format!("no suggestions: {reason}\n"));
+        return out;
+    }
+    for (rank, suggestion) in report.suggestions.iter().enumerate() {
+        let sid = crate::knot_id::display_id(&suggestion.id);
+        let id = suggestion
+            .alias
+            .as_deref()
+            .map_or(sid.to_string(), |alias| format!("{alias} ({sid})"));
+        out.push_str(&format!(
+            "{}. {id} [{}] {} (score {})\n",
+            rank + 1,
+            suggestion.state,
+            suggestion.title,
+            suggestion.score
+        ));
+        if !suggestion.reasons.is_empty() {
+            out.push_str(&format!("   {}\n", suggestion.reasons.join("; ")));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_suggestions;
+    use crate::app::{SuggestReport, Suggestion};
+
+    #[test]
+    fn renders_ranked_rows_and_capacity() {
+        let mut report = SuggestReport {
+            agent: "codex".to_string(),
+            capacity: Some(2),
+            active: 1,
+            suggestions: vec![Suggestion {
+                id: "knots-a1b2".to_string(),
+                alias: None,
+                title: "Fix login".to_string(),
+                state: "ready_for_implementation".to_string(),
+                score: 60,
+                reasons: vec!["priority 1".to_string(), "matches area/auth".to_string()],
+            }],
+        };
+        assert_eq!(
+            render_suggestions(&report),
+            "codex: 1 of 2 slot(s) in use\n\
+             1. a1b2 [ready_for_implementation] Fix login (score 60)\n   \
+             priority 1; matches area/auth\n"
+        );
+        report.active = 2;
+        report.suggestions.clear();
+        assert!(render_suggestions(&report).ends_with("no suggestions: agent is at capacity\n"));
+    }
+}
@@ -334,7 +334,7 @@ fn claim_poll_and_peek_use_installed_workflow_prompt_body() {
     assert!(peeked.skill.contains("Ship {{ output }} output."));
     assert!(peeked.skill.contains("Built output"));
 
-    let polled = poll_queue(&app, None, None)
+    let polled = poll_queue(&app, None, None, None)
         .expect("poll should succeed")
         .expect("queue should contain knot");
     assert!(polled.skill.contains("Ship {{ output }} output."));
@@ -55,14 +55,15 @@ fn list_and_poll_gate_candidates_respect_stage_and_owner() {
     assert_eq!(evaluate_candidates.len(), 1);
     assert_eq!(evaluate_candidates[0].id, gate.id);
 
-    let human = poll_queue(&app, Some("evaluate"), Some("human"))
+    let human = poll_queue(&app, Some("evaluate"), Some("human"), None)
         .expect("human poll should work")
         .expect("human should see gate");
     assert_eq!(human.knot.id, gate.id);
     assert!(human.skill.contains("# Evaluating"));
     assert!(human.completion_cmd.contains("--expected-state evaluating"));
 
-    let agent = poll_queue(&app, Some("evaluate"), Some("agent")).expect("agent poll should work");
+    let agent =
+        poll_queue(&app, Some("evaluate"), Some("agent"), None).expect("agent poll should work");
     assert!(agent.is_none());
 
     let _ = std::fs::remove_dir_all(root);
@@ -179,6 +180,7 @@ fn run_poll_and_claim_cover_json_and_text_rendering_paths() {
             verbose: false,
             lease: None,
             timeout_seconds: None,
+            force: false,
         },
     )
     .expect("claim should succeed");
@@ -195,6 +197,7 @@ fn run_poll_and_claim_cover_json_and_text_rendering_paths() {
             verbose: true,
             lease: None,
             timeout_seconds: None,
+            force: false,
         },
     )
     .expect("peek claim should succeed");
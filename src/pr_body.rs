@@ -0,0 +1,46 @@
+use crate::app::PrBody;
+
+/// Renders `body` as a markdown PR description. Empty sections are left out.
+pub fn render_pr_body(body: &PrBody) -> String {
+    let mut out = format!("## {}\n", body.title.trim());
+    if let Some(description) = non_blank(body.description.as_deref()) {
+        out.push_str(&format!("\n{description}\n"));
+    }
+    if let Some(acceptance) = non_blank(body.acceptance.as_deref()) {
+        out.push_str(&format!("\n### Acceptance\n\n{acceptance}\n"));
+    }
+    if !body.criteria.is_empty() {
+        out.push_str("\n### Acceptance criteria\n\n");
+        for criterion in &body.criteria {
+            let mark = if criterion.checked { "x" } else { " " };
+            out.push_str(&format!("- [{mark}] {}\n", criterion.text));
+        }
+    }
+    if !body.children.is_empty() {
+        out.push_str("\n### Child knots\n\n");
+        for child in &body.children {
+            out.push_str(&format!(
+                "- [{}] `{}` {} ({})\n",
+                if child.done { "x" } else { " " },
+                crate::knot_id::display_id(&child.id),
+                child.title,
+                child.state
+            ));
+        }
+    }
+    if !body.checklist.is_empty() {
+        out.push_str("\n### Review checklist\n");
+        for gate in &body.checklist {
+            out.push_str(&format!("\n**{}**\n\n", gate.state.replace('_', " ")));
+            for item in &gate.items {
+                out.push_str(&format!("- [ ] {item}\n"));
+            }
+        }
+    }
+    out.push_str(&format!("\nKnot: `{}`\n", body.id));
+    out
+}
+
+fn non_blank(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
@@ -0,0 +1,90 @@
+use crate::app::{PrBody, PrBodyChild, ReviewChecklist};
+use crate::db::CriterionRecord;
+use crate::pr_body::render_pr_body;
+
+fn body() -> PrBody {
+    PrBody {
+        id: "knots-a1b2".to_string(),
+        title: "Fix login".to_string(),
+        description: Some("Redirect loops after SSO.\n".to_string()),
+        acceptance: Some("- lands on the dashboard".to_string()),
+        criteria: vec![
+            criterion("returns 400 on bad input", true),
+            criterion("logs the SSO error", false),
+        ],
+        children: vec![
+            PrBodyChild {
+                id: "knots-c3d4".to_string(),
+                title: "Patch callback".to_string(),
+                state: "shipped".to_string(),
+                done: true,
+            },
+            PrBodyChild {
+                id: "knots-e5f6".to_string(),
+                title: "Add test".to_string(),
+                state: "implementation".to_string(),
+                done: false,
+            },
+        ],
+        checklist: vec![ReviewChecklist {
+            state: "implementation_review".to_string(),
+            items: vec!["Tests cover required behavior".to_string()],
+        }],
+    }
+}
+
+fn criterion(text: &str, checked: bool) -> CriterionRecord {
+    CriterionRecord {
+        criterion_id: format!("c-{text}"),
+        text: text.to_string(),
+        checked,
+        checked_at: None,
+        added_at: "2026-01-01T00:00:00Z".to_string(),
+    }
+}
+
+#[test]
+fn renders_all_sections_in_order() {
+    let rendered = render_pr_body(&body());
+    assert_eq!(
+        rendered,
+        "## Fix login\n\
+         \n\
+         Redirect loops after SSO.\n\
+         \n\
+         ### Acceptance\n\
+         \n\
+         - lands on the dashboard\n\
+         \n\
+         ### Acceptance criteria\n\
+         \n\
+         - [x] returns 400 on bad input\n\
+         - [ ] logs the SSO error\n\
+         \n\
+         ### Child knots\n\
+         \n\
+         - [x] `c3d4` Patch callback (shipped)\n\
+         - [ ] `e5f6` Add test (implementation)\n\
+         \n\
+         ### Review checklist\n\
+         \n\
+         **implementation review**\n\
+         \n\
+         - [ ] Tests cover required behavior\n\
+         \n\
+         Knot: `knots-a1b2`\n"
+    );
+}
+
+#[test]
+fn omits_empty_sections() {
+    let rendered = render_pr_body(&PrBody {
+        description: Some("  ".to_string()),
+        acceptance: None,
+        criteria: vec![],
+        children: vec![],
+        checklist: vec![],
+        ..body()
+    });
+    assert_eq!(rendered, "## Fix login\n\nKnot: `knots-a1b2`\n");
+}
@@ -63,6 +63,28 @@ pub struct ProfileOwners {
     pub states: BTreeMap<String, StepOwner>,
 }
 
+/// Something a profile requires on a knot before it may finish.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DoneRequirement {
+    /// At least one handoff capsule.
+    HandoffCapsule,
+    /// Every acceptance criterion checked.
+    CriteriaChecked,
+    /// At least one linked commit.
+    LinkedCommit,
+}
+
+impl DoneRequirement {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DoneRequirement::HandoffCapsule => "handoff_capsule",
+            DoneRequirement::CriteriaChecked => "criteria_checked",
+            DoneRequirement::LinkedCommit => "linked_commit",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ProfileDefinition {
     pub id: String,
@@ -97,6 +119,9 @@ pub struct ProfileDefinition {
     pub prompt_acceptance: BTreeMap<String, Vec<String>>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub review_hints: BTreeMap<String, String>,
+    /// Checked on every move to a terminal state other than `abandoned`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub done_requires: Vec<DoneRequirement>,
 }
 
 #[derive(Debug, Clone)]
@@ -75,13 +75,24 @@ pub(crate) fn run_profile_command_with_context_and_home(
         }
         ProfileSubcommands::SetDefault(set_default_args) => {
             let app = open_app()?;
-            let profile_id = app.set_default_profile_id(&set_default_args.id)?;
-            println!("default profile: {}", profile_id);
+            if set_default_args.repo {
+                let profile_id = app.set_repo_default_profile_id(&set_default_args.id, false)?;
+                println!("repo default profile: {}", profile_id);
+            } else {
+                let profile_id = app.set_default_profile_id(&set_default_args.id)?;
+                println!("default profile: {}", profile_id);
+            }
         }
         ProfileSubcommands::SetDefaultQuick(set_default_quick_args) => {
             let app = open_app()?;
-            let profile_id = app.set_default_quick_profile_id(&set_default_quick_args.id)?;
-            println!("default quick profile: {}", profile_id);
+            if set_default_quick_args.repo {
+                let profile_id =
+                    app.set_repo_default_profile_id(&set_default_quick_args.id, true)?;
+                println!("repo default quick profile: {}", profile_id);
+            } else {
+                let profile_id = app.set_default_quick_profile_id(&set_default_quick_args.id)?;
+                println!("default quick profile: {}", profile_id);
+            }
         }
         ProfileSubcommands::Set(set_args) => {
             run_profile_set(&registry, set_args, &open_app)?;
@@ -186,6 +197,14 @@ fn print_profile_show(
             ProfileField::new("initial_state", profile.initial_state.clone()),
             ProfileField::new("terminal_states", profile.terminal_states.join(", ")),
         ];
+        if !profile.done_requires.is_empty() {
+            let done_requires: Vec<&str> = profile
+                .done_requires
+                .iter()
+                .map(|requirement| requirement.as_str())
+                .collect();
+            fields.push(ProfileField::new("done_requires", done_requires.join(", ")));
+        }
         if let Some(description) = profile.description.as_deref() {
             fields.insert(1, ProfileField::new("description", description));
         }
@@ -295,7 +314,7 @@ pub(crate) struct ProfilePalette {
 
 impl ProfilePalette {
     pub fn auto() -> Self {
-        let enabled = std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal();
+        let enabled = crate::ui::stdout_color_enabled();
         Self { enabled }
     }
 
@@ -135,6 +135,7 @@ fn run_profile_command_handles_list_show_and_set_default() {
         &ProfileArgs {
             command: ProfileSubcommands::SetDefault(ProfileSetDefaultArgs {
                 id: "semiauto".to_string(),
+                repo: false,
             }),
         },
         &root,
@@ -152,6 +153,7 @@ fn run_profile_command_handles_list_show_and_set_default() {
         &ProfileArgs {
             command: ProfileSubcommands::SetDefaultQuick(ProfileSetDefaultArgs {
                 id: "autopilot_no_planning".to_string(),
+                repo: false,
             }),
         },
         &root,
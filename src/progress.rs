@@ -1,5 +1,7 @@
 use std::io;
 
+use serde::Serialize;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ProgressKind {
     Stage,
@@ -8,8 +10,38 @@ pub(crate) enum ProgressKind {
     Warn,
 }
 
+/// The long-running halves of a pull that report counts as they go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PullPhase {
+    /// Objects received from the remote.
+    Fetch,
+    /// Event files applied to the cache.
+    Apply,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PullProgress {
+    pub phase: PullPhase,
+    pub done: u64,
+    pub total: u64,
+    /// The `yyyy/mm/dd` event partition being applied.
+    pub partition: Option<String>,
+}
+
+impl PullProgress {
+    pub(crate) fn is_complete(&self) -> bool {
+        self.done >= self.total
+    }
+}
+
 pub(crate) trait ProgressReporter {
     fn emit(&mut self, kind: ProgressKind, message: &str) -> io::Result<()>;
+
+    /// Called for every counted step; reporters throttle their own output.
+    fn update(&mut self, _progress: &PullProgress) -> io::Result<()> {
+        Ok(())
+    }
 }
 
 pub(crate) fn emit_progress(
@@ -23,3 +55,34 @@ pub(crate) fn emit_progress(
         Ok(())
     }
 }
+
+pub(crate) fn update_progress(
+    reporter: &mut Option<&mut dyn ProgressReporter>,
+    progress: PullProgress,
+) -> io::Result<()> {
+    if let Some(reporter) = reporter.as_deref_mut() {
+        reporter.update(&progress)
+    } else {
+        Ok(())
+    }
+}
+
+/// Extracts the `yyyy/mm/dd` partition from an event path such as
+/// `.knots/events/2026/03/05/<id>-<type>.json`.
+pub(crate) fn event_partition(path: &std::path::Path) -> Option<String> {
+    let parts: Vec<_> = path
+        .parent()?
+        .components()
+        .rev()
+        .take(3)
+        .map(|part| part.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let numeric =
+        |part: &String, len: usize| part.len() == len && part.chars().all(|ch| ch.is_ascii_digit());
+    match parts.as_slice() {
+        [day, month, year] if numeric(year, 4) && numeric(month, 2) && numeric(day, 2) => {
+            Some(format!("{year}/{month}/{day}"))
+        }
+        _ => None,
+    }
+}
@@ -4,6 +4,8 @@ use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use crate::cache_location::CacheLocation;
+
 const CONFIG_DIR_NAME: &str = "knots";
 const PROJECTS_DIR_NAME: &str = "projects";
 
@@ -19,9 +21,24 @@ pub struct StorePaths {
 }
 
 impl StorePaths {
+    /// The cache database: the in-store or XDG copy, whichever exists, else
+    /// wherever `CacheLocation` puts new caches.
     pub fn db_path(&self) -> PathBuf {
+        let in_store = self.store_db_path();
+        if in_store.exists() {
+            return in_store;
+        }
+        match self.xdg_db_path() {
+            Some(xdg) if xdg.exists() || CacheLocation::from_env() == CacheLocation::Xdg => xdg,
+            _ => in_store,
+        }
+    }
+    pub fn store_db_path(&self) -> PathBuf {
         self.root.join("cache").join("state.sqlite")
     }
+    pub fn xdg_db_path(&self) -> Option<PathBuf> {
+        crate::cache_location::xdg_db_path(&self.root)
+    }
     pub fn locks_dir(&self) -> PathBuf {
         self.root.join("locks")
     }
@@ -67,6 +84,11 @@ pub struct GlobalConfig {
     pub default_quick_profile: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub active_project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// `repo` or `xdg`; see `CacheLocation`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_location: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -270,9 +292,9 @@ pub fn resolve_context(
     if let Some(active) = config.active_project.as_deref() {
         return named_project_context(home_override, active);
     }
-    let git_root = find_git_root(cwd)
+    let repo_root = find_repo_root(cwd)
         .ok_or_else(|| "no active project and not inside a git repository".to_string())?;
-    Ok(git_context(&git_root))
+    Ok(git_context(&repo_root))
 }
 
 pub fn prompt_for_project_selection(
@@ -376,6 +398,21 @@ pub fn find_git_root(start: &Path) -> Option<PathBuf> {
     }
 }
 
+/// The root a command run from `start` targets: the nearest ancestor, up to
+/// the git root, that holds a `.knots` store, else the git root itself. A
+/// workspace inside a larger repository can keep its own store this way.
+pub fn find_repo_root(start: &Path) -> Option<PathBuf> {
+    let git_root = find_git_root(start)?;
+    let mut current = canonical_or_original(start);
+    while current != git_root && current.starts_with(&git_root) {
+        if current.join(".knots").is_dir() && !is_inside_knots_store(&current) {
+            return Some(current);
+        }
+        current.pop();
+    }
+    Some(git_root)
+}
+
 fn is_inside_knots_store(path: &Path) -> bool {
     path.components().any(|c| c.as_os_str() == ".knots")
 }
@@ -421,79 +458,5 @@ fn project_file(home_override: Option<&Path>, id: &str) -> Result<PathBuf, Strin
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn temp_home() -> PathBuf {
-        let path =
-            std::env::temp_dir().join(format!("knots-project-test-{}", uuid::Uuid::now_v7()));
-        fs::create_dir_all(&path).expect("temp home should be creatable");
-        path
-    }
-
-    #[test]
-    fn create_list_and_resolve_named_projects() {
-        let home = temp_home();
-        let project = create_named_project(Some(&home), "demo", None).expect("create project");
-        assert_eq!(project.id, "demo");
-        let listed = list_named_projects(Some(&home)).expect("list projects");
-        assert_eq!(listed.len(), 1);
-        set_active_project(Some(&home), "demo").expect("set active project");
-        let context = resolve_context(None, None, &home, Some(&home)).expect("resolve context");
-        assert_eq!(context.project_id.as_deref(), Some("demo"));
-        assert_eq!(context.distribution, DistributionMode::LocalOnly);
-        let _ = fs::remove_dir_all(home);
-    }
-
-    #[test]
-    fn explicit_repo_root_beats_active_project() {
-        let home = temp_home();
-        create_named_project(Some(&home), "demo", None).expect("create project");
-        set_active_project(Some(&home), "demo").expect("set active project");
-        let repo_root = home.join("repo");
-        fs::create_dir_all(repo_root.join(".git")).expect("git dir should exist");
-        let context =
-            resolve_context(None, Some(&repo_root), &home, Some(&home)).expect("resolve git");
-        assert_eq!(context.project_id, None);
-        assert_eq!(context.distribution, DistributionMode::Git);
-        let _ = fs::remove_dir_all(home);
-    }
-
-    #[test]
-    fn find_git_root_skips_knots_worktree() {
-        let root = temp_home();
-        let repo = root.join("repo");
-        fs::create_dir_all(repo.join(".git")).expect("repo .git");
-        // Simulate a knots sync worktree inside .knots/_worktree
-        let worktree = repo.join(".knots").join("_worktree");
-        fs::create_dir_all(&worktree).expect("worktree dir");
-        fs::write(worktree.join(".git"), "gitdir: /tmp/fake").expect(".git file");
-        // Starting from inside the worktree should skip it and find the real repo
-        let found = find_git_root(&worktree);
-        let expected = canonical_or_original(&repo);
-        assert_eq!(found.as_deref(), Some(expected.as_path()));
-        let _ = fs::remove_dir_all(root);
-    }
-
-    #[test]
-    fn delete_project_removes_store_and_clears_active_project() {
-        let home = temp_home();
-        let project = create_named_project(Some(&home), "demo", None).expect("create project");
-        set_active_project(Some(&home), "demo").expect("set active project");
-
-        let store = project
-            .store_paths(Some(&home))
-            .expect("store paths should resolve");
-        fs::write(store.root.join("marker.txt"), "x").expect("marker should be writable");
-        delete_named_project(Some(&home), "demo").expect("delete project");
-
-        assert!(!store.root.exists());
-        assert!(!projects_dir(Some(&home))
-            .expect("projects dir should resolve")
-            .join("demo.toml")
-            .exists());
-        let config = read_global_config(Some(&home)).expect("config should load");
-        assert_eq!(config.active_project, None);
-        let _ = fs::remove_dir_all(home);
-    }
-}
+#[path = "project_tests.rs"]
+mod tests;
@@ -0,0 +1,105 @@
+use super::*;
+
+fn temp_home() -> PathBuf {
+    let path = std::env::temp_dir().join(format!("knots-project-test-{}", uuid::Uuid::now_v7()));
+    fs::create_dir_all(&path).expect("temp home should be creatable");
+    path
+}
+
+#[test]
+fn create_list_and_resolve_named_projects() {
+    let home = temp_home();
+    let project = create_named_project(Some(&home), "demo", None).expect("create project");
+    assert_eq!(project.id, "demo");
+    let listed = list_named_projects(Some(&home)).expect("list projects");
+    assert_eq!(listed.len(), 1);
+    set_active_project(Some(&home), "demo").expect("set active project");
+    let context = resolve_context(None, None, &home, Some(&home)).expect("resolve context");
+    assert_eq!(context.project_id.as_deref(), Some("demo"));
+    assert_eq!(context.distribution, DistributionMode::LocalOnly);
+    let _ = fs::remove_dir_all(home);
+}
+
+#[test]
+fn explicit_repo_root_beats_active_project() {
+    let home = temp_home();
+    create_named_project(Some(&home), "demo", None).expect("create project");
+    set_active_project(Some(&home), "demo").expect("set active project");
+    let repo_root = home.join("repo");
+    fs::create_dir_all(repo_root.join(".git")).expect("git dir should exist");
+    let context = resolve_context(None, Some(&repo_root), &home, Some(&home)).expect("resolve git");
+    assert_eq!(context.project_id, None);
+    assert_eq!(context.distribution, DistributionMode::Git);
+    let _ = fs::remove_dir_all(home);
+}
+
+#[test]
+fn find_git_root_skips_knots_worktree() {
+    let root = temp_home();
+    let repo = root.join("repo");
+    fs::create_dir_all(repo.join(".git")).expect("repo .git");
+    // Simulate a knots sync worktree inside .knots/_worktree
+    let worktree = repo.join(".knots").join("_worktree");
+    fs::create_dir_all(&worktree).expect("worktree dir");
+    fs::write(worktree.join(".git"), "gitdir: /tmp/fake").expect(".git file");
+    // Starting from inside the worktree should skip it and find the real repo
+    let found = find_git_root(&worktree);
+    let expected = canonical_or_original(&repo);
+    assert_eq!(found.as_deref(), Some(expected.as_path()));
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn delete_project_removes_store_and_clears_active_project() {
+    let home = temp_home();
+    let project = create_named_project(Some(&home), "demo", None).expect("create project");
+    set_active_project(Some(&home), "demo").expect("set active project");
+
+    let store = project
+        .store_paths(Some(&home))
+        .expect("store paths should resolve");
+    fs::write(store.root.join("marker.txt"), "x").expect("marker should be writable");
+    delete_named_project(Some(&home), "demo").expect("delete project");
+
+    assert!(!store.root.exists());
+    assert!(!projects_dir(Some(&home))
+        .expect("projects dir should resolve")
+        .join("demo.toml")
+        .exists());
+    let config = read_global_config(Some(&home)).expect("config should load");
+    assert_eq!(config.active_project, None);
+    let _ = fs::remove_dir_all(home);
+}
+
+#[test]
+fn find_repo_root_prefers_the_nearest_knots_store_below_the_git_root() {
+    let root = temp_home();
+    let repo = root.join("repo");
+    fs::create_dir_all(repo.join(".git")).expect("repo .git");
+    let deep = repo.join("services").join("api").join("src");
+    fs::create_dir_all(&deep).expect("deep dir");
+    let expected_repo = canonical_or_original(&repo);
+    assert_eq!(
+        find_repo_root(&deep).as_deref(),
+        Some(expected_repo.as_path())
+    );
+
+    let workspace = repo.join("services").join("api");
+    fs::create_dir_all(workspace.join(".knots")).expect("workspace store");
+    let expected_workspace = canonical_or_original(&workspace);
+    assert_eq!(
+        find_repo_root(&deep).as_deref(),
+        Some(expected_workspace.as_path())
+    );
+    let inside_store = workspace.join(".knots").join("_worktree");
+    fs::create_dir_all(inside_store.join(".knots")).expect("worktree store");
+    assert_eq!(
+        find_repo_root(&inside_store).as_deref(),
+        Some(expected_workspace.as_path())
+    );
+
+    let context = resolve_context(None, None, &deep, Some(&root)).expect("resolve context");
+    assert_eq!(context.repo_root, expected_workspace);
+    assert!(find_repo_root(Path::new("/definitely/not/a/repo")).is_none());
+    let _ = fs::remove_dir_all(root);
+}
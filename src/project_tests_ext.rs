@@ -54,6 +54,8 @@ fn global_config_round_trips_and_active_project_can_be_cleared() {
         default_profile: Some("autopilot".to_string()),
         default_quick_profile: Some("quick".to_string()),
         active_project: Some("demo".to_string()),
+        theme: None,
+        cache_location: None,
     };
     write_global_config(Some(&home), &config).expect("config should write");
     let loaded = read_global_config(Some(&home)).expect("config should load");
@@ -1,18 +1,22 @@
-use crate::app::KnotView;
+use crate::app::{KnotView, StateAge};
 use crate::domain::metadata::MetadataEntry;
 use crate::knot_id::display_id;
 
+#[cfg(test)]
 pub fn render_prompt(knot: &KnotView, skill: &str, completion_cmd: &str) -> String {
-    render_prompt_inner(knot, skill, completion_cmd, false)
+    render_prompt_inner(knot, skill, completion_cmd, false, None)
 }
 
+/// `queue_age` is how long the knot waited in its queue state before this
+/// hand-off, when known.
 pub fn render_prompt_verbose(
     knot: &KnotView,
     skill: &str,
     completion_cmd: &str,
     verbose: bool,
+    queue_age: Option<&StateAge>,
 ) -> String {
-    render_prompt_inner(knot, skill, completion_cmd, verbose)
+    render_prompt_inner(knot, skill, completion_cmd, verbose, queue_age)
 }
 
 fn render_prompt_inner(
@@ -20,11 +24,15 @@ fn render_prompt_inner(
     skill: &str,
     completion_cmd: &str,
     verbose: bool,
+    queue_age: Option<&StateAge>,
 ) -> String {
     let mut out = String::new();
     out.push_str(&format!("# {}\n\n", knot.title));
     out.push_str(&render_header(knot));
     out.push('\n');
+    if let Some(age) = queue_age {
+        out.push_str(&render_queue_age(age));
+    }
     if let Some(body) = knot.body.as_deref().filter(|b| !b.is_empty()) {
         out.push_str("## Description\n\n");
         out.push_str(body);
@@ -117,8 +125,9 @@ fn render_prompt_inner(
     out
 }
 
+#[cfg(test)]
 pub fn render_prompt_json(knot: &KnotView, skill: &str, completion_cmd: &str) -> serde_json::Value {
-    render_prompt_json_verbose(knot, skill, completion_cmd, false)
+    render_prompt_json_verbose(knot, skill, completion_cmd, false, None)
 }
 
 pub fn render_prompt_json_verbose(
@@ -126,8 +135,9 @@ pub fn render_prompt_json_verbose(
     skill: &str,
     completion_cmd: &str,
     verbose: bool,
+    queue_age: Option<&StateAge>,
 ) -> serde_json::Value {
-    let prompt_text = render_prompt_inner(knot, skill, completion_cmd, verbose);
+    let prompt_text = render_prompt_inner(knot, skill, completion_cmd, verbose, queue_age);
     let mut json = serde_json::json!({
         "id": knot.id,
         "title": knot.title,
@@ -145,6 +155,9 @@ pub fn render_prompt_json_verbose(
         "next_step_metadata": knot.next_step_metadata,
         "prompt": prompt_text,
     });
+    if let Some(age) = queue_age {
+        json["queue_age"] = serde_json::json!(age);
+    }
     if !verbose {
         let hint = crate::ui::hidden_metadata_hint(knot);
         if !hint.is_empty() {
@@ -181,6 +194,27 @@ fn render_workflow_boundary(state: &str, allows_child_claims: bool) -> String {
     )
 }
 
+fn render_queue_age(age: &StateAge) -> String {
+    let mut out = format!(
+        "## Queue Age\n\n- Waited {} in `{}` (since {}).\n",
+        age.waited(),
+        age.state,
+        age.since
+    );
+    if let Some(budget) = age.budget() {
+        if age.over_budget {
+            out.push_str(&format!(
+                "- **Over budget**: this state's budget is {budget}. Treat the knot as \
+                 escalated and favor finishing it over polish.\n"
+            ));
+        } else {
+            out.push_str(&format!("- Within this state's budget of {budget}.\n"));
+        }
+    }
+    out.push('\n');
+    out
+}
+
 fn render_header(knot: &KnotView) -> String {
     let sid = display_id(&knot.id);
     let prio = knot.priority.map_or("none".to_string(), |p| p.to_string());
@@ -1,4 +1,4 @@
-use crate::app::KnotView;
+use crate::app::{KnotView, StateAge};
 use crate::domain::knot_type::KnotType;
 use crate::domain::metadata::MetadataEntry;
 use crate::prompt::{
@@ -15,9 +15,11 @@ fn sample_knot() -> KnotView {
         body: Some("Implement kno poll and kno claim".to_string()),
         description: None,
         acceptance: None,
+        estimate: None,
         priority: Some(1),
         knot_type: KnotType::default(),
         tags: vec![],
+        custom_fields: Default::default(),
         notes: vec![MetadataEntry {
             entry_id: "e1".to_string(),
             content: "Plan approved".to_string(),
@@ -41,10 +43,15 @@ fn sample_knot() -> KnotView {
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: None,
+        snoozed_until: None,
         step_metadata: None,
         next_step_metadata: None,
         edges: vec![],
         child_summaries: vec![],
+        git_links: vec![],
+        criteria: Vec::new(),
+        handbacks: Vec::new(),
+        plan: None,
     }
 }
 
@@ -211,7 +218,7 @@ fn render_non_verbose_shows_only_latest_note() {
         make_entry("n1", "old note", "agent1"),
         make_entry("n2", "new note", "agent2"),
     ];
-    let output = render_prompt_verbose(&knot, "# S\n", "cmd", false);
+    let output = render_prompt_verbose(&knot, "# S\n", "cmd", false, None);
     assert!(!output.contains("old note"));
     assert!(output.contains("new note"));
     assert!(output.contains("1 older note"));
@@ -224,7 +231,7 @@ fn render_verbose_shows_all_notes() {
         make_entry("n1", "old note", "agent1"),
         make_entry("n2", "new note", "agent2"),
     ];
-    let output = render_prompt_verbose(&knot, "# S\n", "cmd", true);
+    let output = render_prompt_verbose(&knot, "# S\n", "cmd", true, None);
     assert!(output.contains("old note"));
     assert!(output.contains("new note"));
     assert!(!output.contains("not shown"));
@@ -237,7 +244,7 @@ fn render_non_verbose_shows_latest_handoff() {
         make_entry("h1", "old handoff", "a1"),
         make_entry("h2", "new handoff", "a2"),
     ];
-    let output = render_prompt_verbose(&knot, "# S\n", "cmd", false);
+    let output = render_prompt_verbose(&knot, "# S\n", "cmd", false, None);
     assert!(!output.contains("old handoff"));
     assert!(output.contains("new handoff"));
 }
@@ -246,7 +253,7 @@ fn render_non_verbose_shows_latest_handoff() {
 fn json_verbose_omits_other_field() {
     let mut knot = sample_knot();
     knot.notes = vec![make_entry("n1", "old", "a"), make_entry("n2", "new", "a")];
-    let json = render_prompt_json_verbose(&knot, "# S\n", "cmd", true);
+    let json = render_prompt_json_verbose(&knot, "# S\n", "cmd", true, None);
     assert!(json.get("other").is_none());
 }
 
@@ -254,7 +261,7 @@ fn json_verbose_omits_other_field() {
 fn json_non_verbose_includes_other_field() {
     let mut knot = sample_knot();
     knot.notes = vec![make_entry("n1", "old", "a"), make_entry("n2", "new", "a")];
-    let json = render_prompt_json_verbose(&knot, "# S\n", "cmd", false);
+    let json = render_prompt_json_verbose(&knot, "# S\n", "cmd", false, None);
     let other = json["other"].as_str().unwrap();
     assert!(other.contains("1 older note"));
 }
@@ -262,7 +269,7 @@ fn json_non_verbose_includes_other_field() {
 #[test]
 fn json_no_other_when_single_entries() {
     let knot = sample_knot();
-    let json = render_prompt_json_verbose(&knot, "# S\n", "cmd", false);
+    let json = render_prompt_json_verbose(&knot, "# S\n", "cmd", false, None);
     assert!(json.get("other").is_none());
 }
 
@@ -333,3 +340,33 @@ fn json_output_includes_child_summaries() {
     assert_eq!(children[0]["id"], "K-child1");
     assert_eq!(children[0]["state"], "planning");
 }
+
+#[test]
+fn queue_age_section_flags_over_budget_knots() {
+    let knot = sample_knot();
+    let age = StateAge {
+        state: "ready_for_implementation".to_string(),
+        since: "2026-02-25T10:00:00Z".to_string(),
+        waited_seconds: 2 * 86_400 + 3_600,
+        budget_seconds: Some(86_400),
+        over_budget: true,
+    };
+    let output = render_prompt_verbose(&knot, "# S\n", "cmd", false, Some(&age));
+    assert!(output.contains(
+        "## Queue Age\n\n- Waited 2d 1h in `ready_for_implementation` (since 2026-02-25T10:00:00Z)."
+    ));
+    assert!(output.contains("**Over budget**: this state's budget is 1d 0h."));
+    let json = render_prompt_json_verbose(&knot, "# S\n", "cmd", false, Some(&age));
+    assert_eq!(json["queue_age"]["over_budget"], true);
+    assert_eq!(json["queue_age"]["waited_seconds"], 2 * 86_400 + 3_600);
+
+    let within = StateAge {
+        over_budget: false,
+        waited_seconds: 600,
+        ..age
+    };
+    let output = render_prompt_verbose(&knot, "# S\n", "cmd", false, Some(&within));
+    assert!(output.contains("- Waited 10m in"));
+    assert!(output.contains("Within this state's budget of 1d 0h."));
+    assert!(!render_prompt(&knot, "# S\n", "cmd").contains("Queue Age"));
+}
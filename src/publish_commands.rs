@@ -0,0 +1,17 @@
+use crate::app::{self, App};
+use crate::cli::PublishArgs;
+use crate::print_json;
+
+pub fn run_publish(app: &App, args: PublishArgs) -> Result<(), app::AppError> {
+    let published = app.publish_knot(&args.id)?;
+    if args.json {
+        print_json(&published);
+    } else {
+        let kind = if published.draft { "draft " } else { "" };
+        println!(
+            "published {kind}{} ({} event file(s)); run `kno push` to share it",
+            published.id, published.event_files
+        );
+    }
+    Ok(())
+}
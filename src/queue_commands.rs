@@ -0,0 +1,71 @@
+use crate::app::{self, WipUsage};
+use crate::cli::{QueueArgs, QueueSubcommands};
+
+pub fn run_queue(app: &app::App, args: QueueArgs) -> Result<(), app::AppError> {
+    let QueueSubcommands::Status(args) = args.command;
+    let usage = crate::trace::measure("queue status", || app.wip_usage())?;
+    if args.json {
+        crate::print_json(&usage);
+    } else {
+        print!("{}", render_wip_usage(&usage));
+    }
+    Ok(())
+}
+
+pub fn render_wip_usage(usage: &[WipUsage]) -> String {
+    if usage.is_empty() {
+        return "no WIP limits configured; add [wip_limits] to .knots/config.toml\n".to_string();
+    }
+    let width = usage
+        .iter()
+        .map(|row| row.state.len())
+        .max()
+        .unwrap_or_default()
+        .max("state".len());
+    let mut out = format!(
+        "{:<width$}  {:>6}  {:>5}  {:>4}\n",
+        "state", "active", "limit", "use"
+    );
+    for row in usage {
+        let percent = (row.active * 100).checked_div(row.limit).unwrap_or(100);
+        let full = if row.active >= row.limit {
+            "  full"
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "{:<width$}  {:>6}  {:>5}  {:>3}%{full}\n",
+            row.state, row.active, row.limit, percent
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_wip_usage;
+    use crate::app::WipUsage;
+
+    #[test]
+    fn renders_utilization_and_marks_full_states() {
+        let usage = vec![
+            WipUsage {
+                state: "implementation".to_string(),
+                active: 3,
+                limit: 3,
+            },
+            WipUsage {
+                state: "planning".to_string(),
+                active: 1,
+                limit: 4,
+            },
+        ];
+        assert_eq!(
+            render_wip_usage(&usage),
+            "state           active  limit   use\n\
+             implementation       3      3  100%  full\n\
+             planning             1      4   25%\n"
+        );
+        assert!(render_wip_usage(&[]).starts_with("no WIP limits"));
+    }
+}
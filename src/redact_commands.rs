@@ -0,0 +1,29 @@
+use crate::app::{self, App};
+use crate::cli::RedactArgs;
+use crate::knot_id::display_id;
+use crate::print_json;
+
+pub fn run_redact(app: &App, args: RedactArgs) -> Result<(), app::AppError> {
+    let redaction = app.redact_event(&args.event_id, &args.patterns, &args.replacement)?;
+    if args.json {
+        print_json(&redaction);
+        return Ok(());
+    }
+    println!(
+        "redacted {} match(es) in {} of {} ({} event file(s), {} snapshot(s)); marker {}",
+        redaction.replacements,
+        redaction.fields.join(", "),
+        display_id(&redaction.knot_id),
+        redaction.event_files,
+        redaction.snapshot_files,
+        redaction.marker_event_id
+    );
+    if redaction.published {
+        println!(
+            "the event was already pushed: rewrite the knots branch history and \
+             force-push it, then have other clones re-clone (see README, \"Redacting \
+             leaked secrets\")"
+        );
+    }
+    Ok(())
+}
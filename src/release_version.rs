@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::process::Command;
 
 pub(crate) const RELEASES_LATEST_URL: &str = "https://github.com/acartine/knots/releases/latest";
@@ -15,6 +16,34 @@ pub(crate) fn fetch_latest_tag(url: &str, timeout_secs: u32) -> Option<String> {
     parse_location_tag(&headers)
 }
 
+/// Lists a repository's releases through the GitHub API. The beta channel
+/// needs this because `/releases/latest` never points at a prerelease.
+pub(crate) fn fetch_releases_json(url: &str, timeout_secs: u32) -> Option<String> {
+    let output = Command::new("curl")
+        .args(["--max-time", &timeout_secs.to_string(), "-fsSL"])
+        .args(["-H", "Accept: application/vnd.github+json", url])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+/// Picks the highest published (non-draft) tag, prereleases included.
+pub(crate) fn newest_release_tag(releases_json: &str) -> Option<String> {
+    let releases: Vec<serde_json::Value> = serde_json::from_str(releases_json).ok()?;
+    releases
+        .iter()
+        .filter(|release| !release["draft"].as_bool().unwrap_or(false))
+        .filter_map(|release| release["tag_name"].as_str())
+        .filter(|tag| parse_version(strip_v_prefix(tag)).is_some())
+        .max_by(|a, b| {
+            compare_versions(strip_v_prefix(a), strip_v_prefix(b)).unwrap_or(Ordering::Equal)
+        })
+        .map(str::to_string)
+}
+
 pub(crate) fn latest_available_version(current: &str, tag: Option<String>) -> Option<String> {
     let tag = tag?;
     let latest = strip_v_prefix(&tag);
@@ -40,25 +69,63 @@ pub(crate) fn strip_v_prefix(tag: &str) -> &str {
 }
 
 pub(crate) fn is_outdated(current: &str, latest: &str) -> Option<bool> {
-    let cur: Vec<u64> = current
-        .split('.')
-        .map(|s| s.parse().ok())
-        .collect::<Option<Vec<_>>>()?;
-    let lat: Vec<u64> = latest
+    compare_versions(current, latest).map(|order| order == Ordering::Less)
+}
+
+/// Orders `x.y.z` and `x.y.z-pre` versions the semver way: a prerelease
+/// sorts before its release, and numeric prerelease parts compare as numbers.
+pub(crate) fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let (a_core, a_pre) = parse_version(a)?;
+    let (b_core, b_pre) = parse_version(b)?;
+    Some(a_core.cmp(&b_core).then_with(|| match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a_pre), Some(b_pre)) => compare_prerelease(a_pre, b_pre),
+    }))
+}
+
+fn parse_version(version: &str) -> Option<([u64; 3], Option<&str>)> {
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) if !pre.is_empty() => (core, Some(pre)),
+        Some(_) => return None,
+        None => (version, None),
+    };
+    let parts = core
         .split('.')
-        .map(|s| s.parse().ok())
-        .collect::<Option<Vec<_>>>()?;
-    if cur.len() != 3 || lat.len() != 3 {
-        return None;
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    Some((parts.try_into().ok()?, pre))
+}
+
+fn compare_prerelease(a: &str, b: &str) -> Ordering {
+    let mut left = a.split('.');
+    let mut right = b.split('.');
+    loop {
+        let order = match (left.next(), right.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) => match (l.parse::<u64>(), r.parse::<u64>()) {
+                (Ok(l), Ok(r)) => l.cmp(&r),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => l.cmp(r),
+            },
+        };
+        if order != Ordering::Equal {
+            return order;
+        }
     }
-    Some(cur < lat)
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        fetch_latest_tag, is_outdated, latest_available_version, parse_location_tag, strip_v_prefix,
+        compare_versions, fetch_latest_tag, is_outdated, latest_available_version,
+        newest_release_tag, parse_location_tag, strip_v_prefix,
     };
+    use std::cmp::Ordering;
 
     #[test]
     fn fetch_latest_tag_returns_none_for_unreachable_url() {
@@ -130,4 +197,42 @@ mod tests {
         assert_eq!(is_outdated("0.2", "0.2.2"), None);
         assert_eq!(is_outdated("0.2.2.1", "0.2.2"), None);
     }
+
+    #[test]
+    fn compare_versions_orders_prereleases_before_their_release() {
+        assert_eq!(
+            compare_versions("0.5.0-beta.1", "0.5.0"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_versions("0.5.0-beta.2", "0.5.0-beta.10"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_versions("0.5.0-rc.1", "0.5.0-beta.3"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare_versions("0.5.0-beta.1", "0.4.9"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(is_outdated("0.5.0-beta.1", "0.5.0"), Some(true));
+        assert_eq!(compare_versions("0.5.0-", "0.5.0"), None);
+    }
+
+    #[test]
+    fn newest_release_tag_includes_prereleases_and_skips_drafts() {
+        let releases = r#"[
+            {"tag_name": "v0.4.0", "draft": false, "prerelease": false},
+            {"tag_name": "v0.5.0-beta.2", "draft": false, "prerelease": true},
+            {"tag_name": "v0.6.0-beta.1", "draft": true, "prerelease": true},
+            {"tag_name": "nightly", "draft": false, "prerelease": true}
+        ]"#;
+        assert_eq!(
+            newest_release_tag(releases),
+            Some("v0.5.0-beta.2".to_string())
+        );
+        assert_eq!(newest_release_tag("[]"), None);
+        assert_eq!(newest_release_tag("not json"), None);
+    }
 }
@@ -87,7 +87,10 @@ impl From<std::io::Error> for RemoteInitError {
 }
 
 pub fn init_remote_knots_branch(repo_root: &Path) -> Result<(), RemoteInitError> {
-    init_remote_branch(repo_root, "origin", "knots")
+    let store_root = repo_root.join(".knots");
+    let remote = crate::repo_config::knots_remote(&store_root);
+    let branch = crate::repo_config::knots_branch(&store_root);
+    init_remote_branch(repo_root, &remote, &branch)
 }
 
 pub fn detect_beads_hooks(repo_root: &Path) -> BeadsHookReport {
@@ -170,7 +173,11 @@ pub fn uninit_remote_knots_branch(
     Ok(true)
 }
 
-fn init_remote_branch(repo_root: &Path, remote: &str, branch: &str) -> Result<(), RemoteInitError> {
+pub fn init_remote_branch(
+    repo_root: &Path,
+    remote: &str,
+    branch: &str,
+) -> Result<(), RemoteInitError> {
     if !repo_root.join(".git").exists() {
         return Err(RemoteInitError::NotGitRepository);
     }
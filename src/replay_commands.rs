@@ -0,0 +1,138 @@
+use crate::cli::{DiffArgs, ReplayArgs, SnapshotArgs, SnapshotSubcommands};
+use crate::{app, dispatch, print_json, ui};
+
+pub fn run_replay(app: &app::App, args: ReplayArgs) -> Result<(), app::AppError> {
+    let knots = crate::trace::measure("replay", || app.replay(&args.until, args.id.as_deref()))?;
+    if args.json {
+        print_json(&knots);
+        return Ok(());
+    }
+    if args.id.is_some() {
+        if let Some(knot) = knots.first() {
+            println!("as of {}", args.until.trim());
+            ui::print_knot_show(knot, false);
+        }
+        return Ok(());
+    }
+    if knots.is_empty() {
+        println!("no knots existed as of {}", args.until.trim());
+        return Ok(());
+    }
+    println!("{} knot(s) as of {}", knots.len(), args.until.trim());
+    for knot in &knots {
+        println!(
+            "{} [{}] {}",
+            dispatch::knot_ref(knot),
+            knot.state,
+            knot.title
+        );
+    }
+    Ok(())
+}
+
+pub fn run_snapshot(app: &app::App, args: SnapshotArgs) -> Result<(), app::AppError> {
+    let SnapshotSubcommands::Verify(args) = args.command;
+    let report = crate::trace::measure("snapshot verify", || app.verify_snapshot())?;
+    if args.json {
+        print_json(&report);
+    } else {
+        println!(
+            "snapshot {} written_at={} knots_checked={} mismatches={}",
+            report.active_path.display(),
+            report.written_at,
+            report.knots_checked,
+            report.mismatches.len()
+        );
+        for mismatch in &report.mismatches {
+            println!(
+                "  - {} ({}): {}",
+                mismatch.id,
+                mismatch.tier,
+                mismatch.fields.join(", ")
+            );
+        }
+    }
+    if !report.ok() {
+        return Err(app::AppError::InvalidArgument(format!(
+            "snapshot differs from event replay for {} knot(s)",
+            report.mismatches.len()
+        )));
+    }
+    Ok(())
+}
+
+pub fn run_diff(app: &app::App, args: DiffArgs) -> Result<(), app::AppError> {
+    let diff = crate::trace::measure("diff", || match (&args.other, &args.since) {
+        (Some(other), _) => app.diff_knots(&args.id, other),
+        (None, Some(since)) => app.diff_since(&args.id, since),
+        (None, None) => Err(app::AppError::InvalidArgument(
+            "kno diff needs a second knot id or --since".to_string(),
+        )),
+    })?;
+    if args.json {
+        print_json(&diff);
+        return Ok(());
+    }
+    println!("--- {}", diff.left);
+    println!("+++ {}", diff.right);
+    if diff.changes.is_empty() {
+        println!("no field changes");
+        return Ok(());
+    }
+    for change in &diff.changes {
+        println!("{}:", change.field);
+        for line in render_change(change) {
+            println!("  {line}");
+        }
+    }
+    Ok(())
+}
+
+fn render_change(change: &app::FieldChange) -> Vec<String> {
+    let before = change.before.as_deref().unwrap_or("");
+    let after = change.after.as_deref().unwrap_or("");
+    if before.contains('\n') || after.contains('\n') {
+        return line_diff(before, after);
+    }
+    let mut out = Vec::new();
+    if change.before.is_some() {
+        out.push(format!("- {before}"));
+    }
+    if change.after.is_some() {
+        out.push(format!("+ {after}"));
+    }
+    out
+}
+
+/// Minimal LCS line diff; knot text fields are small enough that the
+/// quadratic table is not a concern.
+pub(crate) fn line_diff(before: &str, after: &str) -> Vec<String> {
+    let a: Vec<&str> = before.lines().collect();
+    let b: Vec<&str> = after.lines().collect();
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::new();
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            out.push(format!("  {}", a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push(format!("- {}", a[i]));
+            i += 1;
+        } else {
+            out.push(format!("+ {}", b[j]));
+            j += 1;
+        }
+    }
+    out
+}
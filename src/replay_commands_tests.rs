@@ -0,0 +1,16 @@
+use crate::replay_commands::line_diff;
+
+#[test]
+fn line_diff_marks_removed_added_and_kept_lines() {
+    let out = line_diff("intro\nold step\noutro", "intro\nnew step\noutro\nextra");
+    assert_eq!(
+        out,
+        vec!["  intro", "- old step", "+ new step", "  outro", "+ extra"]
+    );
+}
+
+#[test]
+fn line_diff_handles_empty_sides() {
+    assert_eq!(line_diff("", "a\nb"), vec!["+ a", "+ b"]);
+    assert_eq!(line_diff("a", ""), vec!["- a"]);
+}
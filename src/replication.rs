@@ -1,11 +1,30 @@
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 use rusqlite::Connection;
 use serde::Serialize;
 
 use crate::progress::{emit_progress, ProgressKind, ProgressReporter};
 use crate::project::StorePaths;
+use crate::repo_config;
 use crate::sync::{GitAdapter, KnotsWorktree, SyncError, SyncService, SyncSummary};
+use crate::trace::{timed, TimingCategory};
+
+mod branch_push;
+mod files;
+mod mirror;
+mod object_store;
+mod s3;
+mod server;
+mod server_auth;
+mod ssh;
+mod store;
+
+pub use server::{serve_replication_stdio, server_audit_db_path};
+pub(crate) use server_auth::{permit, Role, ServerConfig, ANONYMOUS, SERVER_CONFIG_FILE};
+pub use store::{RemoteStore, RemoteStoreSpec};
+
+pub(crate) const LAST_PUSH_AT_META: &str = "last_push_success_at_ms";
+pub(crate) const LAST_PUSH_HEAD_META: &str = "last_push_head_commit";
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub struct PushSummary {
@@ -69,13 +88,8 @@ impl<'a> ReplicationService<'a> {
 
     #[allow(dead_code)]
     pub fn pull(&self) -> Result<SyncSummary, SyncError> {
-        self.require_no_active_leases()?;
-        let service = SyncService::with_store_paths(
-            self.conn,
-            self.repo_root.clone(),
-            self.store_paths.clone(),
-        );
-        service.sync()
+        let mut reporter = None;
+        self.pull_with_progress(&mut reporter)
     }
 
     pub fn pull_with_progress(
@@ -83,12 +97,52 @@ impl<'a> ReplicationService<'a> {
         reporter: &mut Option<&mut dyn ProgressReporter>,
     ) -> Result<SyncSummary, SyncError> {
         self.require_no_active_leases()?;
+        let summary = timed(TimingCategory::Sync, || self.remote_store()?.pull(reporter))?;
+        crate::usage_metrics::add_sync_files(summary.index_files + summary.full_files);
+        Ok(summary)
+    }
+
+    fn pull_from_branch(
+        &self,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<SyncSummary, SyncError> {
         let service = SyncService::with_store_paths(
             self.conn,
             self.repo_root.clone(),
             self.store_paths.clone(),
         );
-        service.sync_with_progress(reporter)
+        let summary = service.sync_with_progress(reporter)?;
+        if self.mirror_mode()? {
+            if let Some(source) = self.adopt_config_from_mirror()? {
+                emit_progress(
+                    reporter,
+                    ProgressKind::Info,
+                    format!("updated .knots/config.toml from {source}"),
+                )?;
+            }
+            return Ok(summary);
+        }
+        let worktree = KnotsWorktree::with_store_paths(self.repo_root.clone(), &self.store_paths);
+        if repo_config::adopt_from_worktree(
+            self.conn,
+            &self.local_store(),
+            &worktree.path().join(".knots"),
+        )? {
+            emit_progress(
+                reporter,
+                ProgressKind::Info,
+                format!(
+                    "updated .knots/config.toml from {}/{}",
+                    worktree.remote(),
+                    worktree.branch()
+                ),
+            )?;
+        }
+        Ok(summary)
+    }
+
+    fn local_store(&self) -> PathBuf {
+        self.store_paths.root.clone()
     }
 
     pub fn push(&self) -> Result<PushSummary, SyncError> {
@@ -101,155 +155,42 @@ impl<'a> ReplicationService<'a> {
         reporter: &mut Option<&mut dyn ProgressReporter>,
     ) -> Result<PushSummary, SyncError> {
         self.require_no_active_leases()?;
-        const MAX_ATTEMPTS: usize = 3;
-
-        emit_progress(
-            reporter,
-            ProgressKind::Stage,
-            "publishing local knots events",
-        )?;
-        let worktree = KnotsWorktree::with_store_paths(self.repo_root.clone(), &self.store_paths);
-        emit_progress(reporter, ProgressKind::Info, "preparing knots worktree")?;
-        worktree.ensure_exists(&self.git)?;
-
-        emit_progress(
-            reporter,
-            ProgressKind::Info,
-            "scanning local knots event files",
-        )?;
-        let local_files = self.collect_local_event_files()?;
-        let local_event_files = local_files.len() as u64;
-        if local_event_files == 0 {
-            emit_progress(
-                reporter,
-                ProgressKind::Success,
-                "no local knots events found; nothing to push",
-            )?;
-            return Ok(PushSummary {
-                local_event_files,
-                copied_files: 0,
-                committed: false,
-                pushed: false,
-                commit: None,
-            });
-        }
-
-        for attempt in 0..MAX_ATTEMPTS {
-            match self.attempt_push(&worktree, &local_files, local_event_files, reporter)? {
-                PushAttemptResult::Success(summary) => return Ok(summary),
-                PushAttemptResult::AlreadySynced(summary) => return Ok(summary),
-                PushAttemptResult::Retry(err) if attempt + 1 < MAX_ATTEMPTS => {
-                    emit_progress(
-                        reporter,
-                        ProgressKind::Warn,
-                        format!(
-                            "push was rejected; refreshing remote \
-                             state and retrying ({}/{})",
-                            attempt + 2,
-                            MAX_ATTEMPTS
-                        ),
-                    )?;
-                    let _ = err;
-                    continue;
-                }
-                PushAttemptResult::Retry(_) => {
-                    return Err(SyncError::MergeConflictEscalation {
-                        message: "push rejected as non-fast-forward \
-                                  after retries"
-                            .to_string(),
-                    });
-                }
+        let compaction = self.compact_if_due(reporter)?;
+        let summary = timed(TimingCategory::Sync, || self.remote_store()?.push(reporter))?;
+        record_push(self.conn, &summary)?;
+        crate::usage_metrics::add_sync_files(summary.copied_files);
+        if let Some(compaction) = compaction {
+            let pruned = compaction.prune_published(self.conn)?;
+            if pruned > 0 {
+                emit_progress(
+                    reporter,
+                    ProgressKind::Info,
+                    format!("pruned {pruned} published local event file(s)"),
+                )?;
             }
         }
-
-        Err(SyncError::MergeConflictEscalation {
-            message: "push retries exhausted".to_string(),
-        })
+        Ok(summary)
     }
 
-    fn attempt_push(
+    /// Runs the `compact.auto` policy so snapshots ride along with this push.
+    fn compact_if_due(
         &self,
-        worktree: &KnotsWorktree,
-        local_files: &[PathBuf],
-        local_event_files: u64,
         reporter: &mut Option<&mut dyn ProgressReporter>,
-    ) -> Result<PushAttemptResult, SyncError> {
-        self.reset_worktree_to_remote_or_local(worktree, reporter)?;
-        worktree.ensure_clean(&self.git)?;
-
-        emit_progress(
-            reporter,
-            ProgressKind::Info,
-            format!(
-                "copying {local_event_files} local knot file(s) \
-                 into the publish worktree"
-            ),
-        )?;
-        let copied_files = self.copy_files_into_worktree(worktree.path(), local_files)?;
-        let stage_paths = stage_paths(worktree.path());
-        if stage_paths.is_empty() {
+    ) -> Result<Option<crate::compaction::AutoCompaction>, SyncError> {
+        let compaction = crate::compaction::compact_if_due(self.conn, &self.store_paths.root)?;
+        if let Some(compaction) = &compaction {
             emit_progress(
                 reporter,
-                ProgressKind::Success,
-                "remote knots already includes the local events",
+                ProgressKind::Info,
+                format!(
+                    "auto compaction wrote snapshots (hot={} warm={} cold={})",
+                    compaction.snapshots.hot_count,
+                    compaction.snapshots.warm_count,
+                    compaction.snapshots.cold_count
+                ),
             )?;
-            return Ok(PushAttemptResult::AlreadySynced(PushSummary {
-                local_event_files,
-                copied_files,
-                committed: false,
-                pushed: false,
-                commit: None,
-            }));
-        }
-
-        self.git.add_paths(worktree.path(), &stage_paths)?;
-
-        if !self.git.has_staged_changes(worktree.path(), &stage_paths)? {
-            emit_progress(
-                reporter,
-                ProgressKind::Success,
-                "remote knots already includes the local events",
-            )?;
-            return Ok(PushAttemptResult::AlreadySynced(PushSummary {
-                local_event_files,
-                copied_files,
-                committed: false,
-                pushed: false,
-                commit: None,
-            }));
-        }
-
-        emit_progress(reporter, ProgressKind::Info, "creating a publish commit")?;
-        let commit = self
-            .git
-            .commit(worktree.path(), "knots: publish local events")?;
-
-        emit_progress(
-            reporter,
-            ProgressKind::Info,
-            "pushing knots branch to origin",
-        )?;
-        match self
-            .git
-            .push_branch(worktree.path(), worktree.remote(), worktree.branch())
-        {
-            Ok(()) => {
-                emit_progress(
-                    reporter,
-                    ProgressKind::Success,
-                    format!("push complete at {}", short_commit(&commit)),
-                )?;
-                Ok(PushAttemptResult::Success(PushSummary {
-                    local_event_files,
-                    copied_files,
-                    committed: true,
-                    pushed: true,
-                    commit: Some(commit),
-                }))
-            }
-            Err(err) if err.is_non_fast_forward() => Ok(PushAttemptResult::Retry(err)),
-            Err(err) => Err(err),
         }
+        Ok(compaction)
     }
 
     pub fn sync(&self) -> Result<ReplicationSummary, SyncError> {
@@ -283,6 +224,13 @@ impl<'a> ReplicationService<'a> {
     }
 
     pub fn count_unpushed_event_files(&self) -> Result<u64, SyncError> {
+        self.remote_store()?.count_unpushed()
+    }
+
+    fn count_unpushed_on_branch(&self) -> Result<u64, SyncError> {
+        if self.mirror_mode()? {
+            return self.count_unpushed_via_mirror();
+        }
         let worktree = KnotsWorktree::with_store_paths(self.repo_root.clone(), &self.store_paths);
         worktree.ensure_exists(&self.git)?;
         let mut reporter = None;
@@ -307,7 +255,11 @@ impl<'a> ReplicationService<'a> {
         emit_progress(
             reporter,
             ProgressKind::Info,
-            "refreshing knots worktree from origin/knots",
+            format!(
+                "refreshing knots worktree from {}/{}",
+                worktree.remote(),
+                worktree.branch()
+            ),
         )?;
         match self.git.fetch_branch_with_filter(
             &self.repo_root,
@@ -330,7 +282,11 @@ impl<'a> ReplicationService<'a> {
                 emit_progress(
                     reporter,
                     ProgressKind::Warn,
-                    "origin/knots is unavailable; using local knots worktree state",
+                    format!(
+                        "{}/{} is unavailable; using local knots worktree state",
+                        worktree.remote(),
+                        worktree.branch()
+                    ),
                 )?;
                 let head = self.git.rev_parse(worktree.path(), "HEAD")?;
                 self.git.reset_hard(worktree.path(), &head)?;
@@ -340,93 +296,6 @@ impl<'a> ReplicationService<'a> {
         }
     }
 
-    fn collect_local_event_files(&self) -> Result<Vec<PathBuf>, SyncError> {
-        let mut files = Vec::new();
-        for rel_root in [".knots/index", ".knots/events", ".knots/snapshots"] {
-            let root = self.repo_root.join(rel_root);
-            if !root.exists() {
-                continue;
-            }
-            let mut stack = vec![root];
-            while let Some(dir) = stack.pop() {
-                for entry in std::fs::read_dir(&dir)? {
-                    let path = entry?.path();
-                    if path.is_dir() {
-                        stack.push(path);
-                        continue;
-                    }
-                    if path.extension().is_none_or(|ext| ext != "json") {
-                        continue;
-                    }
-                    let relative = path
-                        .strip_prefix(&self.repo_root)
-                        .map_err(|err| SyncError::InvalidEvent {
-                            path: path.clone(),
-                            message: format!("failed to relativize event file: {}", err),
-                        })?
-                        .to_path_buf();
-                    files.push(relative);
-                }
-            }
-        }
-
-        files.sort();
-        Ok(files)
-    }
-
-    fn copy_files_into_worktree(
-        &self,
-        worktree_root: &Path,
-        relative_files: &[PathBuf],
-    ) -> Result<u64, SyncError> {
-        let mut copied = 0u64;
-        for relative in relative_files {
-            let src = self.repo_root.join(relative);
-            if !src.exists() {
-                continue;
-            }
-            let dst = worktree_root.join(relative);
-            if let Some(parent) = dst.parent() {
-                std::fs::create_dir_all(parent)?;
-            }
-
-            let src_bytes = std::fs::read(&src)?;
-            if dst.exists() {
-                let dst_bytes = std::fs::read(&dst)?;
-                if dst_bytes == src_bytes {
-                    continue;
-                }
-                return Err(SyncError::FileConflict {
-                    path: relative.clone(),
-                });
-            }
-
-            std::fs::write(&dst, src_bytes)?;
-            copied += 1;
-        }
-
-        Ok(copied)
-    }
-
-    fn event_file_missing_or_changed(
-        &self,
-        worktree_root: &Path,
-        relative_file: &Path,
-    ) -> Result<bool, SyncError> {
-        let src = self.repo_root.join(relative_file);
-        if !src.exists() {
-            return Ok(false);
-        }
-
-        let dst = worktree_root.join(relative_file);
-        let src_bytes = std::fs::read(&src)?;
-        if !dst.exists() {
-            return Ok(true);
-        }
-        let dst_bytes = std::fs::read(&dst)?;
-        Ok(dst_bytes != src_bytes)
-    }
-
     fn require_no_active_leases(&self) -> Result<(), SyncError> {
         let count = crate::db::count_active_leases(self.conn)?;
         if count > 0 {
@@ -436,19 +305,25 @@ impl<'a> ReplicationService<'a> {
     }
 }
 
-fn short_commit(commit: &str) -> &str {
-    &commit[..commit.len().min(12)]
+/// Remembers when this clone last pushed (and the commit it published) so
+/// `kno status` can report it.
+fn record_push(conn: &Connection, summary: &PushSummary) -> Result<(), SyncError> {
+    crate::db::set_meta(
+        conn,
+        LAST_PUSH_AT_META,
+        &crate::sync::current_unix_ms_string(),
+    )?;
+    if let Some(commit) = &summary.commit {
+        crate::db::set_meta(conn, LAST_PUSH_HEAD_META, commit)?;
+    }
+    Ok(())
 }
 
-fn stage_paths(worktree_root: &Path) -> Vec<&'static str> {
-    let mut out = Vec::new();
-    for path in [".knots/index", ".knots/events", ".knots/snapshots"] {
-        if worktree_root.join(path).exists() {
-            out.push(path);
-        }
-    }
-    out
+fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(12)]
 }
 
+#[cfg(test)]
+mod store_tests;
 #[cfg(test)]
 mod tests;
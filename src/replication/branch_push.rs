@@ -0,0 +1,197 @@
+use std::path::{Path, PathBuf};
+
+use crate::progress::{emit_progress, ProgressKind, ProgressReporter};
+use crate::repo_config;
+use crate::sync::{KnotsWorktree, SyncError};
+
+use super::{short_commit, PushAttemptResult, PushSummary, ReplicationService};
+
+impl ReplicationService<'_> {
+    pub(super) fn push_to_branch(
+        &self,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<PushSummary, SyncError> {
+        const MAX_ATTEMPTS: usize = 3;
+
+        emit_progress(
+            reporter,
+            ProgressKind::Stage,
+            "publishing local knots events",
+        )?;
+        let mirror_mode = self.mirror_mode()?;
+        let worktree = KnotsWorktree::with_store_paths(self.repo_root.clone(), &self.store_paths);
+        if !mirror_mode {
+            emit_progress(reporter, ProgressKind::Info, "preparing knots worktree")?;
+            worktree.ensure_exists(&self.git)?;
+        }
+
+        emit_progress(
+            reporter,
+            ProgressKind::Info,
+            "scanning local knots event files",
+        )?;
+        let local_files = self.collect_local_event_files()?;
+        let local_event_files = local_files.len() as u64;
+        let has_repo_config = repo_config::repo_config_path(&self.local_store()).exists();
+        if local_event_files == 0 && !has_repo_config {
+            emit_progress(
+                reporter,
+                ProgressKind::Success,
+                "no local knots events found; nothing to push",
+            )?;
+            return Ok(PushSummary {
+                local_event_files,
+                copied_files: 0,
+                committed: false,
+                pushed: false,
+                commit: None,
+            });
+        }
+
+        for attempt in 0..MAX_ATTEMPTS {
+            let result = if mirror_mode {
+                self.attempt_mirror_push(&local_files, local_event_files, reporter)?
+            } else {
+                self.attempt_push(&worktree, &local_files, local_event_files, reporter)?
+            };
+            match result {
+                PushAttemptResult::Success(summary) | PushAttemptResult::AlreadySynced(summary) => {
+                    repo_config::mark_published(self.conn, &self.local_store())?;
+                    return Ok(summary);
+                }
+                PushAttemptResult::Retry(err) if attempt + 1 < MAX_ATTEMPTS => {
+                    emit_progress(
+                        reporter,
+                        ProgressKind::Warn,
+                        format!(
+                            "push was rejected; refreshing remote \
+                             state and retrying ({}/{})",
+                            attempt + 2,
+                            MAX_ATTEMPTS
+                        ),
+                    )?;
+                    let _ = err;
+                    continue;
+                }
+                PushAttemptResult::Retry(_) => {
+                    return Err(SyncError::MergeConflictEscalation {
+                        message: "push rejected as non-fast-forward \
+                                  after retries"
+                            .to_string(),
+                    });
+                }
+            }
+        }
+
+        Err(SyncError::MergeConflictEscalation {
+            message: "push retries exhausted".to_string(),
+        })
+    }
+
+    fn attempt_push(
+        &self,
+        worktree: &KnotsWorktree,
+        local_files: &[PathBuf],
+        local_event_files: u64,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<PushAttemptResult, SyncError> {
+        self.reset_worktree_to_remote_or_local(worktree, reporter)?;
+        worktree.ensure_clean(&self.git)?;
+
+        emit_progress(
+            reporter,
+            ProgressKind::Info,
+            format!(
+                "copying {local_event_files} local knot file(s) \
+                 into the publish worktree"
+            ),
+        )?;
+        let mut copied_files = self.copy_files_into_worktree(worktree.path(), local_files)?;
+        if repo_config::publish_into_worktree(
+            self.conn,
+            &self.local_store(),
+            &worktree.path().join(".knots"),
+        )? {
+            copied_files += 1;
+        }
+        let stage_paths = stage_paths(worktree.path());
+        if stage_paths.is_empty() {
+            emit_progress(
+                reporter,
+                ProgressKind::Success,
+                "remote knots already includes the local events",
+            )?;
+            return Ok(PushAttemptResult::AlreadySynced(PushSummary {
+                local_event_files,
+                copied_files,
+                committed: false,
+                pushed: false,
+                commit: None,
+            }));
+        }
+
+        self.git.add_paths(worktree.path(), &stage_paths)?;
+
+        if !self.git.has_staged_changes(worktree.path(), &stage_paths)? {
+            emit_progress(
+                reporter,
+                ProgressKind::Success,
+                "remote knots already includes the local events",
+            )?;
+            return Ok(PushAttemptResult::AlreadySynced(PushSummary {
+                local_event_files,
+                copied_files,
+                committed: false,
+                pushed: false,
+                commit: None,
+            }));
+        }
+
+        emit_progress(reporter, ProgressKind::Info, "creating a publish commit")?;
+        let commit = self
+            .git
+            .commit(worktree.path(), "knots: publish local events")?;
+
+        emit_progress(
+            reporter,
+            ProgressKind::Info,
+            format!("pushing knots branch to {}", worktree.remote()),
+        )?;
+        match self
+            .git
+            .push_branch(worktree.path(), worktree.remote(), worktree.branch())
+        {
+            Ok(()) => {
+                emit_progress(
+                    reporter,
+                    ProgressKind::Success,
+                    format!("push complete at {}", short_commit(&commit)),
+                )?;
+                Ok(PushAttemptResult::Success(PushSummary {
+                    local_event_files,
+                    copied_files,
+                    committed: true,
+                    pushed: true,
+                    commit: Some(commit),
+                }))
+            }
+            Err(err) if err.is_non_fast_forward() => Ok(PushAttemptResult::Retry(err)),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn stage_paths(worktree_root: &Path) -> Vec<&'static str> {
+    let mut out = Vec::new();
+    for path in [
+        ".knots/index",
+        ".knots/events",
+        ".knots/snapshots",
+        ".knots/config.toml",
+    ] {
+        if worktree_root.join(path).exists() {
+            out.push(path);
+        }
+    }
+    out
+}
@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+
+use crate::sync::SyncError;
+
+use super::ReplicationService;
+
+impl ReplicationService<'_> {
+    /// Local event, index, and snapshot files as `.knots/...` paths, the
+    /// layout every knots branch uses whichever store they came from.
+    pub(super) fn collect_local_event_files(&self) -> Result<Vec<PathBuf>, SyncError> {
+        let mut files = Vec::new();
+        let store_root = &self.store_paths.root;
+        for dir_name in ["index", "events", "snapshots"] {
+            let root = store_root.join(dir_name);
+            if !root.exists() {
+                continue;
+            }
+            let mut stack = vec![root];
+            while let Some(dir) = stack.pop() {
+                for entry in std::fs::read_dir(&dir)? {
+                    let path = entry?.path();
+                    if path.is_dir() {
+                        stack.push(path);
+                        continue;
+                    }
+                    if path.extension().is_none_or(|ext| ext != "json") {
+                        continue;
+                    }
+                    let relative =
+                        path.strip_prefix(store_root)
+                            .map_err(|err| SyncError::InvalidEvent {
+                                path: path.clone(),
+                                message: format!("failed to relativize event file: {}", err),
+                            })?;
+                    files.push(Path::new(".knots").join(relative));
+                }
+            }
+        }
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// Where a branch-relative `.knots/...` path lives in the local store.
+    pub(super) fn local_file(&self, relative: &Path) -> PathBuf {
+        match relative.strip_prefix(".knots") {
+            Ok(rest) => self.store_paths.root.join(rest),
+            Err(_) => self.repo_root.join(relative),
+        }
+    }
+
+    pub(super) fn copy_files_into_worktree(
+        &self,
+        worktree_root: &Path,
+        relative_files: &[PathBuf],
+    ) -> Result<u64, SyncError> {
+        let mut copied = 0u64;
+        for relative in relative_files {
+            let src = self.local_file(relative);
+            if !src.exists() {
+                continue;
+            }
+            let dst = worktree_root.join(relative);
+            if let Some(parent) = dst.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            let src_bytes = std::fs::read(&src)?;
+            if dst.exists() {
+                let dst_bytes = std::fs::read(&dst)?;
+                if dst_bytes == src_bytes {
+                    continue;
+                }
+                return Err(SyncError::FileConflict {
+                    path: relative.clone(),
+                });
+            }
+
+            std::fs::write(&dst, src_bytes)?;
+            copied += 1;
+        }
+
+        Ok(copied)
+    }
+
+    pub(super) fn event_file_missing_or_changed(
+        &self,
+        worktree_root: &Path,
+        relative_file: &Path,
+    ) -> Result<bool, SyncError> {
+        let src = self.local_file(relative_file);
+        if !src.exists() {
+            return Ok(false);
+        }
+
+        let dst = worktree_root.join(relative_file);
+        let src_bytes = std::fs::read(&src)?;
+        if !dst.exists() {
+            return Ok(true);
+        }
+        let dst_bytes = std::fs::read(&dst)?;
+        Ok(dst_bytes != src_bytes)
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::progress::{emit_progress, ProgressKind, ProgressReporter};
+use crate::repo_config;
+use crate::sync::{KnotsMirror, SyncError};
+
+use super::{short_commit, PushAttemptResult, PushSummary, ReplicationService};
+
+const CONFIG_PATH: &str = ".knots/config.toml";
+
+impl ReplicationService<'_> {
+    pub(super) fn mirror_mode(&self) -> Result<bool, SyncError> {
+        Ok(crate::db::get_sync_mode(self.conn)? == "mirror")
+    }
+
+    fn mirror(&self) -> KnotsMirror {
+        KnotsMirror::with_store_paths(self.repo_root.clone(), &self.store_paths)
+    }
+
+    /// Publishes local event files straight into the bare mirror: blobs
+    /// are hashed into its object store and committed on top of the fetched
+    /// head, so no worktree is ever checked out.
+    pub(super) fn attempt_mirror_push(
+        &self,
+        local_files: &[PathBuf],
+        local_event_files: u64,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<PushAttemptResult, SyncError> {
+        let mirror = self.mirror();
+        let (head, remote_blobs) = self.refresh_mirror(&mirror, reporter)?;
+        let mut entries = self.new_event_entries(&mirror, &remote_blobs, local_files)?;
+        let remote_config = match &head {
+            Some(head) => mirror.read_blob(&self.git, head, Path::new(CONFIG_PATH))?,
+            None => None,
+        };
+        if repo_config::config_to_publish(self.conn, &self.local_store(), remote_config.as_deref())?
+            .is_some()
+        {
+            let shas =
+                mirror.hash_files(&self.git, &[self.local_file(Path::new(CONFIG_PATH))], true)?;
+            entries.extend(
+                shas.into_iter()
+                    .map(|sha| (PathBuf::from(CONFIG_PATH), sha)),
+            );
+        }
+
+        if entries.is_empty() {
+            emit_progress(
+                reporter,
+                ProgressKind::Success,
+                "remote knots already includes the local events",
+            )?;
+            return Ok(PushAttemptResult::AlreadySynced(PushSummary {
+                local_event_files,
+                copied_files: 0,
+                committed: false,
+                pushed: false,
+                commit: None,
+            }));
+        }
+
+        emit_progress(reporter, ProgressKind::Info, "creating a publish commit")?;
+        let commit = mirror.commit_entries(
+            &self.git,
+            head.as_deref(),
+            &entries,
+            "knots: publish local events",
+        )?;
+        emit_progress(
+            reporter,
+            ProgressKind::Info,
+            format!("pushing knots branch to {}", mirror.remote()),
+        )?;
+        match mirror.push(&self.git, &commit) {
+            Ok(()) => {
+                emit_progress(
+                    reporter,
+                    ProgressKind::Success,
+                    format!("push complete at {}", short_commit(&commit)),
+                )?;
+                Ok(PushAttemptResult::Success(PushSummary {
+                    local_event_files,
+                    copied_files: entries.len() as u64,
+                    committed: true,
+                    pushed: true,
+                    commit: Some(commit),
+                }))
+            }
+            Err(err) if err.is_non_fast_forward() => Ok(PushAttemptResult::Retry(err)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Adopts the repo config published at the mirror head.
+    pub(super) fn adopt_config_from_mirror(&self) -> Result<Option<String>, SyncError> {
+        let mirror = self.mirror();
+        let Some(head) = mirror.head(&self.git)? else {
+            return Ok(None);
+        };
+        let remote = mirror.read_blob(&self.git, &head, Path::new(CONFIG_PATH))?;
+        if repo_config::adopt_config(self.conn, &self.local_store(), remote)? {
+            return Ok(Some(format!("{}/{}", mirror.remote(), mirror.branch())));
+        }
+        Ok(None)
+    }
+
+    pub(super) fn count_unpushed_via_mirror(&self) -> Result<u64, SyncError> {
+        let mirror = self.mirror();
+        let mut reporter = None;
+        let (_, remote_blobs) = self.refresh_mirror(&mirror, &mut reporter)?;
+        let local_files = self.collect_local_event_files()?;
+        let absolute: Vec<PathBuf> = local_files
+            .iter()
+            .map(|relative| self.local_file(relative))
+            .collect();
+        let shas = mirror.hash_files(&self.git, &absolute, false)?;
+        Ok(local_files
+            .iter()
+            .zip(shas)
+            .filter(|(relative, sha)| remote_blobs.get(*relative) != Some(sha))
+            .count() as u64)
+    }
+
+    fn refresh_mirror(
+        &self,
+        mirror: &KnotsMirror,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<(Option<String>, HashMap<PathBuf, String>), SyncError> {
+        emit_progress(
+            reporter,
+            ProgressKind::Info,
+            format!(
+                "refreshing knots mirror from {}/{}",
+                mirror.remote(),
+                mirror.branch()
+            ),
+        )?;
+        mirror.ensure_exists(&self.git)?;
+        let head = mirror.fetch(&self.git)?;
+        let blobs = match &head {
+            Some(head) => mirror.tree_blobs(&self.git, head, ".knots")?,
+            None => HashMap::new(),
+        };
+        Ok((head, blobs))
+    }
+
+    /// Index entries for local files the remote lacks. A remote file with
+    /// the same path but different content is a conflict, as in the
+    /// worktree copy.
+    fn new_event_entries(
+        &self,
+        mirror: &KnotsMirror,
+        remote_blobs: &HashMap<PathBuf, String>,
+        local_files: &[PathBuf],
+    ) -> Result<Vec<(PathBuf, String)>, SyncError> {
+        let absolute: Vec<PathBuf> = local_files
+            .iter()
+            .map(|relative| self.local_file(relative))
+            .collect();
+        let shas = mirror.hash_files(&self.git, &absolute, false)?;
+        let mut missing = Vec::new();
+        for (relative, sha) in local_files.iter().zip(shas) {
+            match remote_blobs.get(relative) {
+                Some(remote) if *remote == sha => {}
+                Some(_) => {
+                    return Err(SyncError::FileConflict {
+                        path: relative.clone(),
+                    })
+                }
+                None => missing.push(relative.clone()),
+            }
+        }
+        let absolute: Vec<PathBuf> = missing
+            .iter()
+            .map(|relative| self.local_file(relative))
+            .collect();
+        let written = mirror.hash_files(&self.git, &absolute, true)?;
+        Ok(missing.into_iter().zip(written).collect())
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::progress::{
+    emit_progress, update_progress, ProgressKind, ProgressReporter, PullPhase, PullProgress,
+};
+use crate::repo_config;
+use crate::sync::{known_workflow_ids, IncrementalApplier, SyncError, SyncSummary};
+
+use super::store::RemoteStore;
+use super::{PushSummary, ReplicationService};
+
+const CONFIG_KEY: &str = ".knots/config.toml";
+const EVENT_PREFIXES: [&str; 3] = [".knots/index/", ".knots/events/", ".knots/snapshots/"];
+const STAGING_DIR: &str = "remote";
+
+/// Flat key/value storage for event files. Keys are repo-relative paths
+/// such as `.knots/events/...json`.
+pub trait ObjectStore {
+    fn describe(&self) -> String;
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, SyncError>;
+
+    /// Contents of `key`, or `None` when the store does not have it.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, SyncError>;
+
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<(), SyncError>;
+}
+
+/// A plain directory, e.g. a shared mount or an rsync target.
+pub struct DirStore {
+    root: PathBuf,
+}
+
+impl DirStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+}
+
+impl ObjectStore for DirStore {
+    fn describe(&self) -> String {
+        self.root.display().to_string()
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, SyncError> {
+        let start = self.root.join(prefix);
+        if !start.exists() {
+            return Ok(Vec::new());
+        }
+        let mut keys = Vec::new();
+        let mut stack = vec![start];
+        while let Some(dir) = stack.pop() {
+            for entry in std::fs::read_dir(&dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    stack.push(path);
+                } else if let Ok(relative) = path.strip_prefix(&self.root) {
+                    keys.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, SyncError> {
+        match std::fs::read(self.root.join(key)) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<(), SyncError> {
+        write_atomic(&self.root.join(key), bytes)
+    }
+}
+
+/// Replicates through an [`ObjectStore`]. Event file names are unique, so an
+/// existing key counts as published. Fetched files are kept under
+/// `.knots/remote` so each pull only downloads and applies new keys.
+pub struct ObjectRemote<'s, 'a, S> {
+    service: &'s ReplicationService<'a>,
+    store: S,
+}
+
+impl<'s, 'a, S: ObjectStore> ObjectRemote<'s, 'a, S> {
+    pub fn new(service: &'s ReplicationService<'a>, store: S) -> Self {
+        Self { service, store }
+    }
+
+    fn remote_keys(&self) -> Result<BTreeSet<String>, SyncError> {
+        let mut keys = BTreeSet::new();
+        for prefix in EVENT_PREFIXES {
+            keys.extend(
+                self.store
+                    .list(prefix)?
+                    .into_iter()
+                    .filter(|key| key.ends_with(".json")),
+            );
+        }
+        Ok(keys)
+    }
+
+    fn staging_root(&self) -> PathBuf {
+        self.service.store_paths.root.join(STAGING_DIR)
+    }
+
+    fn local_keys(&self) -> Result<Vec<(String, PathBuf)>, SyncError> {
+        Ok(self
+            .service
+            .collect_local_event_files()?
+            .into_iter()
+            .map(|relative| (relative.to_string_lossy().replace('\\', "/"), relative))
+            .collect())
+    }
+}
+
+impl<S: ObjectStore> RemoteStore for ObjectRemote<'_, '_, S> {
+    fn describe(&self) -> String {
+        self.store.describe()
+    }
+
+    fn push(
+        &self,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<PushSummary, SyncError> {
+        emit_progress(
+            reporter,
+            ProgressKind::Stage,
+            "publishing local knots events",
+        )?;
+        let local = self.local_keys()?;
+        emit_progress(
+            reporter,
+            ProgressKind::Info,
+            format!("listing {}", self.describe()),
+        )?;
+        let remote = self.remote_keys()?;
+        let staging = self.staging_root();
+        let mut copied_files = 0u64;
+        for (key, relative) in &local {
+            if remote.contains(key) {
+                continue;
+            }
+            let bytes = std::fs::read(self.service.local_file(relative))?;
+            self.store.put(key, &bytes)?;
+            // The cache already reflects local events; staging them keeps
+            // the next pull from downloading them again.
+            write_atomic(&staging.join(relative), &bytes)?;
+            copied_files += 1;
+        }
+        let remote_config = self.store.get(CONFIG_KEY)?;
+        let local_store = self.service.local_store();
+        if let Some(config) = repo_config::config_to_publish(
+            self.service.conn,
+            &local_store,
+            remote_config.as_deref(),
+        )? {
+            self.store.put(CONFIG_KEY, &config)?;
+            copied_files += 1;
+        }
+        repo_config::mark_published(self.service.conn, &local_store)?;
+        emit_progress(
+            reporter,
+            ProgressKind::Success,
+            format!("published {copied_files} file(s) to {}", self.describe()),
+        )?;
+        Ok(PushSummary {
+            local_event_files: local.len() as u64,
+            copied_files,
+            committed: false,
+            pushed: copied_files > 0,
+            commit: None,
+        })
+    }
+
+    fn pull(
+        &self,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<SyncSummary, SyncError> {
+        emit_progress(reporter, ProgressKind::Stage, "importing knots updates")?;
+        emit_progress(
+            reporter,
+            ProgressKind::Info,
+            format!("listing {}", self.describe()),
+        )?;
+        let remote = self.remote_keys()?;
+        let staging = self.staging_root();
+        let bootstrap = crate::db::get_meta(self.service.conn, "last_index_head_commit")?.is_none();
+        let mut index_files = Vec::new();
+        let mut full_files = Vec::new();
+        for (position, key) in remote.iter().enumerate() {
+            update_progress(
+                reporter,
+                PullProgress {
+                    phase: PullPhase::Fetch,
+                    done: position as u64 + 1,
+                    total: remote.len() as u64,
+                    partition: None,
+                },
+            )?;
+            let relative = PathBuf::from(key);
+            let staged = staging.join(&relative);
+            if !staged.exists() {
+                let Some(bytes) = self.store.get(key)? else {
+                    continue;
+                };
+                write_atomic(&staged, &bytes)?;
+            } else if !bootstrap {
+                continue;
+            }
+            if key.starts_with(EVENT_PREFIXES[0]) {
+                index_files.push(relative);
+            } else if key.starts_with(EVENT_PREFIXES[1]) {
+                full_files.push(relative);
+            }
+        }
+
+        emit_progress(
+            reporter,
+            ProgressKind::Info,
+            "applying knots events to the local cache",
+        )?;
+        let mut applier = IncrementalApplier::new(
+            self.service.conn,
+            staging,
+            self.service.git.clone(),
+            known_workflow_ids(&self.service.repo_root),
+        )
+        .with_event_types(repo_config::event_types(&self.service.local_store()));
+        let summary =
+            applier.apply_files(&listing_digest(&remote), index_files, full_files, reporter)?;
+        let config = self.store.get(CONFIG_KEY)?;
+        if repo_config::adopt_config(self.service.conn, &self.service.local_store(), config)? {
+            emit_progress(
+                reporter,
+                ProgressKind::Info,
+                format!("updated .knots/config.toml from {}", self.describe()),
+            )?;
+        }
+        emit_progress(
+            reporter,
+            ProgressKind::Success,
+            format!(
+                "pull complete (index={}, full={})",
+                summary.index_files, summary.full_files
+            ),
+        )?;
+        Ok(summary)
+    }
+
+    fn count_unpushed(&self) -> Result<u64, SyncError> {
+        let remote = self.remote_keys()?;
+        Ok(self
+            .local_keys()?
+            .iter()
+            .filter(|(key, _)| !remote.contains(key))
+            .count() as u64)
+    }
+}
+
+/// Stands in for a head commit: stores without history are identified by
+/// the set of keys they hold.
+fn listing_digest(keys: &BTreeSet<String>) -> String {
+    let mut hasher = Sha256::new();
+    for key in keys {
+        hasher.update(key.as_bytes());
+        hasher.update(b"\n");
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), SyncError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use crate::sync::SyncError;
+
+use super::object_store::ObjectStore;
+
+/// S3-compatible object storage through the `aws` CLI, which supplies
+/// credentials, region, and `AWS_ENDPOINT_URL` for non-AWS providers.
+/// `KNOTS_AWS_CLI` overrides the binary.
+pub struct S3Store {
+    bucket: String,
+    prefix: String,
+}
+
+impl S3Store {
+    pub fn new(bucket: String, prefix: String) -> Self {
+        Self { bucket, prefix }
+    }
+
+    fn object_key(&self, key: &str) -> String {
+        if self.prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}/{}", self.prefix, key)
+        }
+    }
+
+    fn object_url(&self, key: &str) -> String {
+        format!("s3://{}/{}", self.bucket, self.object_key(key))
+    }
+
+    fn run(&self, args: &[String], stdin: Option<&[u8]>) -> Result<Output, SyncError> {
+        let binary = std::env::var("KNOTS_AWS_CLI").unwrap_or_else(|_| "aws".to_string());
+        let mut command = Command::new(&binary);
+        command
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        command.stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        });
+        let mut child = command.spawn().map_err(|err| SyncError::CommandFailed {
+            command: format!("{binary} {}", args.join(" ")),
+            code: None,
+            stderr: format!("failed to run the aws CLI: {err}"),
+        })?;
+        if let (Some(bytes), Some(mut pipe)) = (stdin, child.stdin.take()) {
+            pipe.write_all(bytes)?;
+        }
+        Ok(child.wait_with_output()?)
+    }
+
+    fn run_checked(&self, args: &[String], stdin: Option<&[u8]>) -> Result<Vec<u8>, SyncError> {
+        let output = self.run(args, stdin)?;
+        if output.status.success() {
+            return Ok(output.stdout);
+        }
+        Err(command_failed(args, &output))
+    }
+}
+
+impl ObjectStore for S3Store {
+    fn describe(&self) -> String {
+        self.object_url("").trim_end_matches('/').to_string()
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, SyncError> {
+        let args = strings(&[
+            "s3api",
+            "list-objects-v2",
+            "--bucket",
+            &self.bucket,
+            "--prefix",
+            &self.object_key(prefix),
+            "--query",
+            "Contents[].Key",
+            "--output",
+            "json",
+        ]);
+        let stdout = self.run_checked(&args, None)?;
+        Ok(parse_key_listing(&stdout, &self.prefix))
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, SyncError> {
+        let args = strings(&["s3", "cp", &self.object_url(key), "-"]);
+        let output = self.run(&args, None)?;
+        if output.status.success() {
+            return Ok(Some(output.stdout));
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("404") || stderr.contains("NoSuchKey") || stderr.contains("Not Found") {
+            return Ok(None);
+        }
+        Err(command_failed(&args, &output))
+    }
+
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<(), SyncError> {
+        let args = strings(&["s3", "cp", "-", &self.object_url(key)]);
+        self.run_checked(&args, Some(bytes))?;
+        Ok(())
+    }
+}
+
+/// Keys from `list-objects-v2 --query Contents[].Key`, relative to `prefix`.
+/// An empty listing prints `null`.
+pub(super) fn parse_key_listing(stdout: &[u8], prefix: &str) -> Vec<String> {
+    let keys: Option<Vec<String>> = serde_json::from_slice(stdout).unwrap_or_default();
+    keys.unwrap_or_default()
+        .into_iter()
+        .filter_map(|key| {
+            if prefix.is_empty() {
+                Some(key)
+            } else {
+                key.strip_prefix(prefix)?
+                    .strip_prefix('/')
+                    .map(str::to_string)
+            }
+        })
+        .collect()
+}
+
+fn command_failed(args: &[String], output: &Output) -> SyncError {
+    SyncError::CommandFailed {
+        command: format!("aws {}", args.join(" ")),
+        code: output.status.code(),
+        stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    }
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
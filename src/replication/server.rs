@@ -0,0 +1,214 @@
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use rusqlite::Connection;
+
+use crate::db;
+use crate::sync::SyncError;
+
+use super::object_store::{DirStore, ObjectStore};
+use super::server_auth::{
+    permit, required_role, Client, ServerConfig, ANONYMOUS, SERVER_CONFIG_FILE,
+};
+
+/// Greeting sent by `kno serve --replication`; clients refuse other versions.
+pub(super) const PROTOCOL_GREETING: &str = "KNOTS-REPLICATION 1";
+const MUTABLE_KEYS: [&str; 1] = [".knots/config.toml"];
+const SERVER_AUDIT_DB_FILE: &str = "server.sqlite";
+
+/// Serves a replication store rooted at `root` over stdin/stdout, which is
+/// what an SSH session hands to the remote command. Tokens come from
+/// `config`, or `<root>/server.toml` when it exists, and every request is
+/// logged to `<root>/server.sqlite`.
+pub fn serve_replication_stdio(root: &Path, config: Option<&Path>) -> Result<(), SyncError> {
+    std::fs::create_dir_all(root)?;
+    let config = match config {
+        Some(path) => ServerConfig::load(path)?,
+        None => ServerConfig::load(&root.join(SERVER_CONFIG_FILE))?,
+    };
+    let audit = db::open_connection(&server_audit_db_path(root).to_string_lossy())?;
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    serve_replication(
+        root,
+        &config,
+        Some(&audit),
+        &mut stdin.lock(),
+        &mut stdout.lock(),
+    )
+}
+
+/// The access log of the server rooted at `root`, beside `server.toml`.
+pub fn server_audit_db_path(root: &Path) -> PathBuf {
+    root.join(SERVER_AUDIT_DB_FILE)
+}
+
+/// Line protocol, one request at a time:
+/// - `LIST <prefix>` -> `OK <n>` then `n` key lines
+/// - `GET <key>` -> `OK <len>` then `len` bytes, or `MISSING`
+/// - `PUT <key> <len>` then `len` bytes -> `OK`
+/// - `AUTH <token>` -> `OK <role> <name>`
+/// - `QUIT`
+///
+/// Failures answer `ERR <message>`. Event files are append-only: a `PUT`
+/// with different bytes for an existing event key is refused. When `config`
+/// lists tokens, every other request waits for an `AUTH` whose role covers
+/// it. A `PUT` over the configured size is refused without reading its body,
+/// which leaves the stream out of step, so the session ends after it.
+/// Requests other than `QUIT` are appended to `audit`, which also backs
+/// per-token rate limits.
+pub(super) fn serve_replication<R: BufRead, W: Write>(
+    root: &Path,
+    config: &ServerConfig,
+    audit: Option<&Connection>,
+    input: &mut R,
+    output: &mut W,
+) -> Result<(), SyncError> {
+    let store = DirStore::new(root.to_path_buf());
+    let mut client = config.initial_client();
+    writeln!(output, "{PROTOCOL_GREETING}")?;
+    output.flush()?;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let mut parts = line.trim_end().splitn(3, ' ');
+        let verb = parts.next().unwrap_or_default();
+        let key = parts.next().unwrap_or_default();
+        let len = parts.next();
+        let max_put = config.max_put_bytes();
+        let oversized = verb == "PUT" && put_length(len).is_ok_and(|len| len > max_put);
+        let permit = match verb {
+            "LIST" | "GET" | "PUT" => permit(
+                client.as_ref(),
+                audit,
+                required_role(verb, MUTABLE_KEYS.contains(&key)),
+                &format!("{verb} {key}"),
+            ),
+            _ => Ok(()),
+        };
+        let denied = permit.is_err() || verb == "AUTH" || oversized;
+        let result = match verb {
+            "QUIT" => return Ok(()),
+            "AUTH" => auth(config, key, &mut client, output),
+            "LIST" => permit.and_then(|()| list(&store, key, output)),
+            "GET" => permit.and_then(|()| get(&store, key, output)),
+            "PUT" if oversized => Err(format!("PUT body is over the {max_put}-byte limit")),
+            "PUT" => put(&store, key, len, permit, input, output),
+            _ => Err(format!("unknown request '{verb}'")),
+        };
+        if let Some(conn) = audit {
+            // Never log the token an AUTH carried.
+            let logged_key = if verb == "AUTH" { "" } else { key };
+            let (outcome, message) = match &result {
+                Ok(()) => ("ok", None),
+                Err(message) if denied => ("denied", Some(message.as_str())),
+                Err(message) => ("error", Some(message.as_str())),
+            };
+            let name = client.as_ref().map_or(ANONYMOUS, |client| &client.name);
+            db::insert_server_access(conn, name, verb, logged_key, outcome, message)?;
+        }
+        if let Err(message) = result {
+            writeln!(output, "ERR {message}")?;
+        }
+        output.flush()?;
+        if oversized {
+            return Ok(());
+        }
+    }
+}
+
+fn auth<W: Write>(
+    config: &ServerConfig,
+    token: &str,
+    client: &mut Option<Client>,
+    output: &mut W,
+) -> Result<(), String> {
+    let Some(granted) = config.authenticate(token) else {
+        return Err("invalid token".to_string());
+    };
+    let reply = format!("OK {} {}", granted.role.as_str(), granted.name);
+    *client = Some(granted);
+    writeln!(output, "{reply}").map_err(|err| err.to_string())
+}
+
+fn list<W: Write>(store: &DirStore, prefix: &str, output: &mut W) -> Result<(), String> {
+    check_key(prefix)?;
+    let keys = store.list(prefix).map_err(|err| err.to_string())?;
+    let keys: Vec<_> = keys.iter().filter(|key| !key.ends_with(".tmp")).collect();
+    let mut out = format!("OK {}\n", keys.len());
+    for key in keys {
+        out.push_str(key);
+        out.push('\n');
+    }
+    output
+        .write_all(out.as_bytes())
+        .map_err(|err| err.to_string())
+}
+
+fn get<W: Write>(store: &DirStore, key: &str, output: &mut W) -> Result<(), String> {
+    check_key(key)?;
+    match store.get(key).map_err(|err| err.to_string())? {
+        Some(bytes) => {
+            writeln!(output, "OK {}", bytes.len()).map_err(|err| err.to_string())?;
+            output.write_all(&bytes).map_err(|err| err.to_string())
+        }
+        None => writeln!(output, "MISSING").map_err(|err| err.to_string()),
+    }
+}
+
+fn put<R: BufRead, W: Write>(
+    store: &DirStore,
+    key: &str,
+    len: Option<&str>,
+    permit: Result<(), String>,
+    input: &mut R,
+    output: &mut W,
+) -> Result<(), String> {
+    let len = put_length(len)?;
+    // Check access before reading, and skip a refused body unbuffered so
+    // the stream stays in step.
+    if let Err(message) = permit.and_then(|()| check_key(key)) {
+        io::copy(&mut Read::take(&mut *input, len as u64), &mut io::sink())
+            .map_err(|err| err.to_string())?;
+        return Err(message);
+    }
+    let mut bytes = vec![0u8; len];
+    input
+        .read_exact(&mut bytes)
+        .map_err(|err| err.to_string())?;
+    if !MUTABLE_KEYS.contains(&key) {
+        match store.get(key).map_err(|err| err.to_string())? {
+            Some(existing) if existing == bytes => return ok(output),
+            Some(_) => return Err(format!("conflict: '{key}' already holds different content")),
+            None => {}
+        }
+    }
+    store.put(key, &bytes).map_err(|err| err.to_string())?;
+    ok(output)
+}
+
+fn put_length(raw: Option<&str>) -> Result<usize, String> {
+    raw.and_then(|raw| raw.parse::<usize>().ok())
+        .ok_or_else(|| "PUT needs a byte length".to_string())
+}
+
+fn ok<W: Write>(output: &mut W) -> Result<(), String> {
+    writeln!(output, "OK").map_err(|err| err.to_string())
+}
+
+/// Keys stay inside the served `.knots` tree.
+fn check_key(key: &str) -> Result<(), String> {
+    let path = Path::new(key);
+    let inside = key.starts_with(".knots/")
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+    if inside {
+        Ok(())
+    } else {
+        Err(format!("key '{key}' is outside the knots store"))
+    }
+}
@@ -0,0 +1,164 @@
+use std::path::Path;
+
+use rusqlite::Connection;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::db;
+use crate::sync::SyncError;
+
+/// Where `kno serve` looks for tokens, beside (not inside) the served
+/// `.knots` tree so clients can never overwrite it.
+pub(crate) const SERVER_CONFIG_FILE: &str = "server.toml";
+
+/// What a client may do once authenticated; each role includes the ones
+/// before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Role {
+    /// `LIST` and `GET`.
+    Reader,
+    /// Also `PUT` of event, index, and snapshot files.
+    Contributor,
+    /// Also `PUT` of the shared `.knots/config.toml`.
+    Maintainer,
+}
+
+impl Role {
+    pub(super) fn as_str(self) -> &'static str {
+        match self {
+            Role::Reader => "reader",
+            Role::Contributor => "contributor",
+            Role::Maintainer => "maintainer",
+        }
+    }
+}
+
+/// Span a token's `rate_limit` counts requests over.
+const RATE_WINDOW_MS: i64 = 60_000;
+/// Largest `PUT` body a server accepts unless `max_put_bytes` says otherwise.
+const DEFAULT_MAX_PUT_BYTES: usize = 64 * 1024 * 1024;
+
+/// `server.toml`: one `[[tokens]]` entry per client, holding the SHA-256 of
+/// the token rather than the token itself.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ServerConfig {
+    #[serde(default)]
+    tokens: Vec<TokenEntry>,
+    /// Largest `PUT` body in bytes; bigger ones are refused unread.
+    #[serde(default)]
+    max_put_bytes: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TokenEntry {
+    name: String,
+    sha256: String,
+    role: Role,
+    /// Requests allowed per minute; unlimited when unset.
+    #[serde(default)]
+    rate_limit: Option<u32>,
+}
+
+/// Who a session speaks for once it has a role.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Client {
+    pub(crate) name: String,
+    pub(super) role: Role,
+    pub(super) rate_limit: Option<u32>,
+}
+
+/// Access log name for sessions without a token.
+pub(crate) const ANONYMOUS: &str = "-";
+
+impl ServerConfig {
+    /// A missing file is an open server, as before tokens existed.
+    pub(crate) fn load(path: &Path) -> Result<Self, SyncError> {
+        let raw = match std::fs::read_to_string(path) {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.into()),
+        };
+        toml::from_str(&raw)
+            .map_err(|err| SyncError::InvalidConfig(format!("{}: {err}", path.display())))
+    }
+
+    /// The client a session starts as: an unlimited maintainer when no
+    /// tokens are set, nobody until `AUTH` otherwise.
+    pub(crate) fn initial_client(&self) -> Option<Client> {
+        self.tokens.is_empty().then(|| Client {
+            name: ANONYMOUS.to_string(),
+            role: Role::Maintainer,
+            rate_limit: None,
+        })
+    }
+
+    pub(crate) fn has_tokens(&self) -> bool {
+        !self.tokens.is_empty()
+    }
+
+    pub(super) fn max_put_bytes(&self) -> usize {
+        self.max_put_bytes.unwrap_or(DEFAULT_MAX_PUT_BYTES)
+    }
+
+    /// The client whose entry hash matches `token`.
+    pub(crate) fn authenticate(&self, token: &str) -> Option<Client> {
+        let digest = hex_sha256(token);
+        self.tokens
+            .iter()
+            .find(|entry| entry.sha256.trim().eq_ignore_ascii_case(&digest))
+            .map(|entry| Client {
+                name: entry.name.clone(),
+                role: entry.role,
+                rate_limit: entry.rate_limit,
+            })
+    }
+}
+
+fn hex_sha256(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// The least role that may send `verb` for `key`; `mutable` marks keys a
+/// `PUT` may overwrite, which only maintainers can touch.
+pub(super) fn required_role(verb: &str, mutable: bool) -> Role {
+    match verb {
+        "PUT" if mutable => Role::Maintainer,
+        "PUT" => Role::Contributor,
+        _ => Role::Reader,
+    }
+}
+
+/// Whether `client` may make `request` (e.g. `GET .knots/a.json`) now: it
+/// needs the `needed` role and room under its rate limit, counted in
+/// `audit`.
+pub(crate) fn permit(
+    client: Option<&Client>,
+    audit: Option<&Connection>,
+    needed: Role,
+    request: &str,
+) -> Result<(), String> {
+    let Some(client) = client else {
+        return Err("authentication required".to_string());
+    };
+    if client.role < needed {
+        return Err(format!(
+            "{request} needs the {} role, token has {}",
+            needed.as_str(),
+            client.role.as_str()
+        ));
+    }
+    if let (Some(limit), Some(conn)) = (client.rate_limit, audit) {
+        let used = db::count_recent_server_access(conn, &client.name, RATE_WINDOW_MS)
+            .map_err(|err| err.to_string())?;
+        if used >= u64::from(limit) {
+            return Err(format!("rate limit of {limit} requests per minute reached"));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use std::cell::RefCell;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use crate::sync::SyncError;
+
+use super::object_store::ObjectStore;
+use super::server::PROTOCOL_GREETING;
+
+/// Talks to `kno serve --replication` on a central host. The session is
+/// opened on first use and reused for every request of one push or pull.
+/// `KNOTS_SSH_COMMAND` replaces `ssh` (any command that connects stdio to
+/// the remote works), `KNOTS_REMOTE_KNO` names the remote binary, and
+/// `KNOTS_SERVER_TOKEN` is sent as `AUTH` when the server requires tokens.
+pub struct SshStore {
+    destination: String,
+    port: Option<u16>,
+    path: String,
+    session: RefCell<Option<Session>>,
+}
+
+struct Session {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl SshStore {
+    pub fn new(destination: String, port: Option<u16>, path: String) -> Self {
+        Self {
+            destination,
+            port,
+            path,
+            session: RefCell::new(None),
+        }
+    }
+
+    fn remote_command(&self) -> String {
+        let kno = std::env::var("KNOTS_REMOTE_KNO").unwrap_or_else(|_| "kno".to_string());
+        // Leave a leading `~/` unquoted so the remote shell expands it.
+        let (home, rest) = match self.path.strip_prefix("~/") {
+            Some(rest) => ("~/", rest),
+            None => ("", self.path.as_str()),
+        };
+        format!(
+            "{} serve --replication --root {}'{}'",
+            kno,
+            home,
+            rest.replace('\'', r"'\''")
+        )
+    }
+
+    fn open(&self) -> Result<Session, SyncError> {
+        let ssh = std::env::var("KNOTS_SSH_COMMAND").unwrap_or_else(|_| "ssh".to_string());
+        let mut words = ssh.split_whitespace();
+        let program = words.next().unwrap_or("ssh");
+        let mut command = Command::new(program);
+        command.args(words);
+        if let Some(port) = self.port {
+            command.arg("-p").arg(port.to_string());
+        }
+        command
+            .arg(&self.destination)
+            .arg(self.remote_command())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit());
+        let mut child = command.spawn().map_err(|err| SyncError::CommandFailed {
+            command: format!("{ssh} {}", self.destination),
+            code: None,
+            stderr: format!("failed to start ssh: {err}"),
+        })?;
+        let stdin = child.stdin.take().expect("piped stdin");
+        let mut stdout = BufReader::new(child.stdout.take().expect("piped stdout"));
+        let greeting = read_line(&mut stdout)?;
+        if greeting != PROTOCOL_GREETING {
+            let _ = child.kill();
+            return Err(
+                self.protocol_error(format!("expected '{PROTOCOL_GREETING}', got '{greeting}'"))
+            );
+        }
+        let mut session = Session {
+            child,
+            stdin,
+            stdout,
+        };
+        if let Ok(token) = std::env::var("KNOTS_SERVER_TOKEN") {
+            self.authenticate(&mut session, token.trim())?;
+        }
+        Ok(session)
+    }
+
+    fn authenticate(&self, session: &mut Session, token: &str) -> Result<(), SyncError> {
+        if token.is_empty() || token.contains(char::is_whitespace) {
+            return Err(SyncError::InvalidConfig(
+                "KNOTS_SERVER_TOKEN must be a single non-empty word".to_string(),
+            ));
+        }
+        writeln!(session.stdin, "AUTH {token}")?;
+        session.stdin.flush()?;
+        let status = read_line(&mut session.stdout)?;
+        if status.starts_with("OK") {
+            return Ok(());
+        }
+        let _ = session.child.kill();
+        let message = status.strip_prefix("ERR ").unwrap_or(&status);
+        Err(self.protocol_error(message.to_string()))
+    }
+
+    /// Sends one request and returns the status line after `OK`, or `None`
+    /// for `MISSING`.
+    fn request(
+        &self,
+        header: &str,
+        body: Option<&[u8]>,
+        read_body: bool,
+    ) -> Result<Option<(String, Vec<u8>)>, SyncError> {
+        let mut slot = self.session.borrow_mut();
+        if slot.is_none() {
+            *slot = Some(self.open()?);
+        }
+        let session = slot.as_mut().expect("session opened");
+        session.stdin.write_all(header.as_bytes())?;
+        session.stdin.write_all(b"\n")?;
+        if let Some(body) = body {
+            session.stdin.write_all(body)?;
+        }
+        session.stdin.flush()?;
+        let status = read_line(&mut session.stdout)?;
+        if status == "MISSING" {
+            return Ok(None);
+        }
+        if let Some(message) = status.strip_prefix("ERR ") {
+            return Err(self.protocol_error(message.to_string()));
+        }
+        let Some(rest) = status.strip_prefix("OK").map(str::trim) else {
+            return Err(self.protocol_error(format!("unexpected reply '{status}'")));
+        };
+        let mut bytes = Vec::new();
+        if read_body {
+            let len = rest
+                .parse::<usize>()
+                .map_err(|_| self.protocol_error(format!("bad length in '{status}'")))?;
+            bytes.resize(len, 0);
+            session.stdout.read_exact(&mut bytes)?;
+        }
+        Ok(Some((rest.to_string(), bytes)))
+    }
+
+    fn protocol_error(&self, message: String) -> SyncError {
+        SyncError::CommandFailed {
+            command: format!("replication session with {}", self.describe()),
+            code: None,
+            stderr: message,
+        }
+    }
+}
+
+impl ObjectStore for SshStore {
+    fn describe(&self) -> String {
+        let port = self.port.map(|port| format!(":{port}")).unwrap_or_default();
+        let sep = if self.path.starts_with('/') { "" } else { "/" };
+        format!("ssh://{}{}{}{}", self.destination, port, sep, self.path)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, SyncError> {
+        let Some((count, _)) = self.request(&format!("LIST {prefix}"), None, false)? else {
+            return Ok(Vec::new());
+        };
+        let count = count
+            .parse::<usize>()
+            .map_err(|_| self.protocol_error(format!("bad key count '{count}'")))?;
+        let mut slot = self.session.borrow_mut();
+        let session = slot.as_mut().expect("session opened");
+        (0..count).map(|_| read_line(&mut session.stdout)).collect()
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, SyncError> {
+        Ok(self
+            .request(&format!("GET {key}"), None, true)?
+            .map(|(_, bytes)| bytes))
+    }
+
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<(), SyncError> {
+        match self.request(&format!("PUT {key} {}", bytes.len()), Some(bytes), false) {
+            Ok(_) => Ok(()),
+            Err(SyncError::CommandFailed { stderr, .. }) if stderr.starts_with("conflict") => {
+                Err(SyncError::FileConflict { path: key.into() })
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl Drop for SshStore {
+    fn drop(&mut self) {
+        if let Some(mut session) = self.session.get_mut().take() {
+            let _ = session.stdin.write_all(b"QUIT\n");
+            let _ = session.stdin.flush();
+            drop(session.stdin);
+            let _ = session.child.wait();
+        }
+    }
+}
+
+fn read_line<R: BufRead>(reader: &mut R) -> Result<String, SyncError> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(SyncError::CommandFailed {
+            command: "replication session".to_string(),
+            code: None,
+            stderr: "connection closed by the knots server".to_string(),
+        });
+    }
+    Ok(line.trim_end_matches(['\n', '\r']).to_string())
+}
@@ -0,0 +1,164 @@
+use std::path::PathBuf;
+
+use crate::progress::ProgressReporter;
+use crate::sync::{SyncError, SyncSummary};
+
+use super::object_store::{DirStore, ObjectRemote};
+use super::s3::S3Store;
+use super::ssh::SshStore;
+use super::{PushSummary, ReplicationService};
+
+/// Where `kno push` publishes events and `kno pull` reads them from.
+pub trait RemoteStore {
+    /// Location shown in progress output.
+    fn describe(&self) -> String;
+
+    fn push(
+        &self,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<PushSummary, SyncError>;
+
+    fn pull(
+        &self,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<SyncSummary, SyncError>;
+
+    /// Local event files the store does not have yet.
+    fn count_unpushed(&self) -> Result<u64, SyncError>;
+}
+
+/// The `remote_store` setting: `git`, `dir:<path>`, `s3://bucket[/prefix]`,
+/// or `ssh://[user@]host[:port]/path` for a `kno serve --replication` host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteStoreSpec {
+    Git,
+    Dir(PathBuf),
+    S3 {
+        bucket: String,
+        prefix: String,
+    },
+    Ssh {
+        destination: String,
+        port: Option<u16>,
+        path: String,
+    },
+}
+
+impl RemoteStoreSpec {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let raw = raw.trim();
+        if raw.is_empty() || raw == "git" {
+            return Ok(Self::Git);
+        }
+        if let Some(path) = raw.strip_prefix("dir:") {
+            if path.trim().is_empty() {
+                return Err("dir: needs a directory path".to_string());
+            }
+            return Ok(Self::Dir(PathBuf::from(path.trim())));
+        }
+        if let Some(rest) = raw.strip_prefix("s3://") {
+            let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+            if bucket.is_empty() {
+                return Err("s3:// needs a bucket name".to_string());
+            }
+            return Ok(Self::S3 {
+                bucket: bucket.to_string(),
+                prefix: prefix.trim_matches('/').to_string(),
+            });
+        }
+        if let Some(rest) = raw.strip_prefix("ssh://") {
+            return parse_ssh(rest);
+        }
+        Err(format!(
+            "'{}' is not one of git, dir:<path>, s3://bucket[/prefix], ssh://host/path",
+            raw
+        ))
+    }
+}
+
+/// `[user@]host[:port]/path`; the path is absolute unless it starts with `~`.
+fn parse_ssh(rest: &str) -> Result<RemoteStoreSpec, String> {
+    let (authority, path) = rest
+        .split_once('/')
+        .ok_or_else(|| "ssh:// needs a path on the server".to_string())?;
+    let (destination, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => {
+            let port = port
+                .parse::<u16>()
+                .map_err(|_| format!("'{}' is not a valid ssh port", port))?;
+            (host, Some(port))
+        }
+        None => (authority, None),
+    };
+    let host = destination.rsplit('@').next().unwrap_or_default();
+    if host.is_empty() || path.trim_matches('/').is_empty() {
+        return Err("ssh:// needs a host and a path on the server".to_string());
+    }
+    let path = if path.starts_with('~') {
+        path.to_string()
+    } else {
+        format!("/{path}")
+    };
+    Ok(RemoteStoreSpec::Ssh {
+        destination: destination.to_string(),
+        port,
+        path,
+    })
+}
+
+/// The knots branch on the configured git remote, through a worktree or
+/// the bare mirror depending on `sync_mode`.
+struct GitBranchStore<'s, 'a> {
+    service: &'s ReplicationService<'a>,
+}
+
+impl RemoteStore for GitBranchStore<'_, '_> {
+    fn describe(&self) -> String {
+        let (remote, branch) = crate::repo_config::knots_ref(&self.service.store_paths.root);
+        format!("{remote}/{branch}")
+    }
+
+    fn push(
+        &self,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<PushSummary, SyncError> {
+        self.service.push_to_branch(reporter)
+    }
+
+    fn pull(
+        &self,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<SyncSummary, SyncError> {
+        self.service.pull_from_branch(reporter)
+    }
+
+    fn count_unpushed(&self) -> Result<u64, SyncError> {
+        self.service.count_unpushed_on_branch()
+    }
+}
+
+impl<'a> ReplicationService<'a> {
+    pub(super) fn remote_store<'s>(&'s self) -> Result<Box<dyn RemoteStore + 's>, SyncError> {
+        let raw = crate::db::get_remote_store(self.conn)?;
+        let spec = RemoteStoreSpec::parse(&raw)
+            .map_err(|message| SyncError::InvalidConfig(format!("remote_store: {message}")))?;
+        Ok(match spec {
+            RemoteStoreSpec::Git => Box::new(GitBranchStore { service: self }),
+            RemoteStoreSpec::Dir(path) => Box::new(ObjectRemote::new(
+                self,
+                DirStore::new(self.repo_root.join(path)),
+            )),
+            RemoteStoreSpec::S3 { bucket, prefix } => {
+                Box::new(ObjectRemote::new(self, S3Store::new(bucket, prefix)))
+            }
+            RemoteStoreSpec::Ssh {
+                destination,
+                port,
+                path,
+            } => Box::new(ObjectRemote::new(
+                self,
+                SshStore::new(destination, port, path),
+            )),
+        })
+    }
+}
@@ -0,0 +1,260 @@
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use super::s3::parse_key_listing;
+use super::server::serve_replication;
+use super::server_auth::ServerConfig;
+use super::RemoteStoreSpec;
+
+#[test]
+fn remote_store_spec_parses_each_backend() {
+    assert_eq!(RemoteStoreSpec::parse("git"), Ok(RemoteStoreSpec::Git));
+    assert_eq!(RemoteStoreSpec::parse(""), Ok(RemoteStoreSpec::Git));
+    assert_eq!(
+        RemoteStoreSpec::parse("dir:/srv/knots"),
+        Ok(RemoteStoreSpec::Dir(PathBuf::from("/srv/knots")))
+    );
+    assert_eq!(
+        RemoteStoreSpec::parse("s3://team-bucket/knots/crate/"),
+        Ok(RemoteStoreSpec::S3 {
+            bucket: "team-bucket".to_string(),
+            prefix: "knots/crate".to_string(),
+        })
+    );
+    assert!(RemoteStoreSpec::parse("dir:").is_err());
+    assert!(RemoteStoreSpec::parse("s3://").is_err());
+    assert!(RemoteStoreSpec::parse("ftp://host").is_err());
+}
+
+#[test]
+fn s3_listing_strips_the_prefix_and_tolerates_null() {
+    let listing = br#"["knots/.knots/events/a.json", "other/.knots/events/b.json"]"#;
+    assert_eq!(
+        parse_key_listing(listing, "knots"),
+        vec![".knots/events/a.json".to_string()]
+    );
+    assert_eq!(
+        parse_key_listing(br#"[".knots/index/c.json"]"#, ""),
+        vec![".knots/index/c.json".to_string()]
+    );
+    assert!(parse_key_listing(b"null", "knots").is_empty());
+}
+
+#[test]
+fn ssh_spec_keeps_user_port_and_home_relative_paths() {
+    assert_eq!(
+        RemoteStoreSpec::parse("ssh://knots@central:2222/srv/knots"),
+        Ok(RemoteStoreSpec::Ssh {
+            destination: "knots@central".to_string(),
+            port: Some(2222),
+            path: "/srv/knots".to_string(),
+        })
+    );
+    assert_eq!(
+        RemoteStoreSpec::parse("ssh://central/~/knots"),
+        Ok(RemoteStoreSpec::Ssh {
+            destination: "central".to_string(),
+            port: None,
+            path: "~/knots".to_string(),
+        })
+    );
+    assert!(RemoteStoreSpec::parse("ssh://central").is_err());
+    assert!(RemoteStoreSpec::parse("ssh://central:port/x").is_err());
+}
+
+#[test]
+fn replication_server_serves_keys_and_refuses_rewrites() {
+    let root = std::env::temp_dir().join(format!("knots-serve-{}", uuid::Uuid::now_v7()));
+    let requests = concat!(
+        "PUT .knots/events/a.json 2\n{}",
+        "PUT .knots/events/a.json 2\n[]",
+        "PUT .knots/config.toml 1\nx",
+        "PUT .knots/config.toml 1\ny",
+        "GET .knots/events/a.json\n",
+        "GET .knots/events/missing.json\n",
+        "LIST .knots/events/\n",
+        "GET ../secret\n",
+        "QUIT\n",
+    );
+    let mut output = Vec::new();
+    serve_replication(
+        &root,
+        &ServerConfig::default(),
+        None,
+        &mut Cursor::new(requests),
+        &mut output,
+    )
+    .expect("serve");
+    let output = String::from_utf8(output).expect("utf8");
+    assert_eq!(
+        output,
+        concat!(
+            "KNOTS-REPLICATION 1\n",
+            "OK\n",
+            "ERR conflict: '.knots/events/a.json' already holds different content\n",
+            "OK\n",
+            "OK\n",
+            "OK 2\n{}",
+            "MISSING\n",
+            "OK 1\n.knots/events/a.json\n",
+            "ERR key '../secret' is outside the knots store\n",
+        )
+    );
+    assert_eq!(
+        std::fs::read_to_string(root.join(".knots/config.toml")).expect("config"),
+        "y"
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn replication_server_ends_the_session_on_an_oversized_put() {
+    let config: ServerConfig = toml::from_str("max_put_bytes = 4\n").expect("server config");
+    let root = std::env::temp_dir().join(format!("knots-serve-cap-{}", uuid::Uuid::now_v7()));
+    let requests = concat!(
+        "PUT .knots/events/a.json 2\n{}",
+        "PUT .knots/events/b.json 10\n0123456789",
+        "GET .knots/events/a.json\n",
+    );
+    let mut output = Vec::new();
+    serve_replication(
+        &root,
+        &config,
+        None,
+        &mut Cursor::new(requests),
+        &mut output,
+    )
+    .expect("serve");
+    assert_eq!(
+        String::from_utf8(output).expect("utf8"),
+        "KNOTS-REPLICATION 1\nOK\nERR PUT body is over the 4-byte limit\n"
+    );
+    assert!(!root.join(".knots/events/b.json").exists());
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn replication_server_gates_requests_by_token_role() {
+    use sha2::{Digest, Sha256};
+    let hash = |token: &str| -> String {
+        Sha256::digest(token.as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    };
+    let config: ServerConfig = toml::from_str(&format!(
+        "[[tokens]]\nname = \"ci\"\nsha256 = \"{}\"\nrole = \"reader\"\n\n\
+         [[tokens]]\nname = \"dev\"\nsha256 = \"{}\"\nrole = \"contributor\"\n",
+        hash("read-token"),
+        hash("dev-token").to_uppercase()
+    ))
+    .expect("server config");
+    let root = std::env::temp_dir().join(format!("knots-serve-auth-{}", uuid::Uuid::now_v7()));
+    let requests = concat!(
+        "LIST .knots/\n",
+        "AUTH wrong\n",
+        "AUTH read-token\n",
+        "LIST .knots/\n",
+        "PUT .knots/events/a.json 2\n{}",
+        "AUTH dev-token\n",
+        "PUT .knots/events/a.json 2\n{}",
+        "PUT .knots/config.toml 1\nx",
+        "GET .knots/events/a.json\n",
+        "QUIT\n",
+    );
+    let mut output = Vec::new();
+    serve_replication(
+        &root,
+        &config,
+        None,
+        &mut Cursor::new(requests),
+        &mut output,
+    )
+    .expect("serve");
+    let output = String::from_utf8(output).expect("utf8");
+    assert_eq!(
+        output,
+        concat!(
+            "KNOTS-REPLICATION 1\n",
+            "ERR authentication required\n",
+            "ERR invalid token\n",
+            "OK reader ci\n",
+            "OK 0\n",
+            "ERR PUT .knots/events/a.json needs the contributor role, token has reader\n",
+            "OK contributor dev\n",
+            "OK\n",
+            "ERR PUT .knots/config.toml needs the maintainer role, token has contributor\n",
+            "OK 2\n{}",
+        )
+    );
+    assert!(!root.join(".knots/config.toml").exists());
+    assert!(toml::from_str::<ServerConfig>(
+        "[[tokens]]\nname = \"x\"\nsha256 = \"y\"\nrole = \"admin\"\n"
+    )
+    .is_err());
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn replication_server_logs_requests_and_enforces_rate_limits() {
+    use sha2::{Digest, Sha256};
+    let digest: String = Sha256::digest(b"ci-token")
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    let config: ServerConfig = toml::from_str(&format!(
+        "[[tokens]]\nname = \"ci\"\nsha256 = \"{digest}\"\nrole = \"reader\"\nrate_limit = 2\n"
+    ))
+    .expect("server config");
+    let audit = crate::db::open_connection(":memory:").expect("audit db");
+    let root = std::env::temp_dir().join(format!("knots-serve-audit-{}", uuid::Uuid::now_v7()));
+    let requests = concat!(
+        "GET .knots/a.json\n",
+        "AUTH ci-token\n",
+        "GET .knots/a.json\n",
+        "LIST .knots/\n",
+        "QUIT\n",
+    );
+    let mut output = Vec::new();
+    serve_replication(
+        &root,
+        &config,
+        Some(&audit),
+        &mut Cursor::new(requests),
+        &mut output,
+    )
+    .expect("serve");
+    let output = String::from_utf8(output).expect("utf8");
+    assert!(output.ends_with("MISSING\nERR rate limit of 2 requests per minute reached\n"));
+
+    let log = crate::db::list_server_access(&audit, None, 10).expect("log");
+    let rows: Vec<_> = log
+        .iter()
+        .rev()
+        .map(|row| {
+            (
+                row.token_name.as_str(),
+                row.verb.as_str(),
+                row.key.as_str(),
+                row.outcome.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            ("-", "GET", ".knots/a.json", "denied"),
+            ("ci", "AUTH", "", "ok"),
+            ("ci", "GET", ".knots/a.json", "ok"),
+            ("ci", "LIST", ".knots/", "denied"),
+        ]
+    );
+    assert!(log.iter().all(|row| !row.key.contains("ci-token")));
+    assert_eq!(
+        crate::db::list_server_access(&audit, Some("ci"), 1)
+            .expect("filtered")
+            .len(),
+        1
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -368,9 +368,11 @@ fn push_blocks_with_active_leases() {
             body: None,
             description: None,
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: Some("lease"),
             tags: &[],
+            custom_fields: &Default::default(),
             notes: &[],
             handoff_capsules: &[],
             invariants: &[],
@@ -384,6 +386,7 @@ fn push_blocks_with_active_leases() {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: None,
+            snoozed_until: None,
         },
     )
     .expect("lease upsert should succeed");
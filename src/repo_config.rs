@@ -0,0 +1,426 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::db;
+use crate::domain::custom_field::FieldSpec;
+use crate::domain::event_type::EventTypeSpec;
+
+pub const REPO_CONFIG_FILE: &str = "config.toml";
+pub const REPO_CONFIG_VERSION: u32 = 1;
+pub const DEFAULT_KNOTS_BRANCH: &str = "knots";
+pub const DEFAULT_KNOTS_REMOTE: &str = "origin";
+const SYNCED_SHA_META_KEY: &str = "repo_config_synced_sha";
+
+/// Shared settings committed on the knots branch at `.knots/config.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RepoConfig {
+    #[serde(default = "current_version")]
+    pub version: u32,
+    /// Repo default profile; wins over the user default for `kno new`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_quick_profile: Option<String>,
+    /// Branch that carries knots data; `knots` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Git remote the knots branch is published to; `origin` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+    #[serde(default)]
+    pub settings: BTreeMap<String, toml::Value>,
+    /// URL templates for `kno open`, keyed by link name (`pr`, `ci`, ...).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub links: BTreeMap<String, String>,
+    /// Custom knot fields keyed by name, set with `--field name=value`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, FieldSpec>,
+    /// Namespaces allowed in `ns/name` tags; any namespace when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tag_namespaces: Vec<String>,
+    /// Most knots allowed in each state at once, keyed by state name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub wip_limits: BTreeMap<String, usize>,
+    /// Longest a knot should wait in each state, as a span like `4h` or
+    /// `2d`, keyed by state name; `poll` flags knots past it.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub state_budgets: BTreeMap<String, String>,
+    /// Profiles whose knots need every acceptance criterion checked before
+    /// reaching a terminal state other than abandoned; `*` for all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub require_criteria: Vec<String>,
+    /// Profiles whose knots need a plan from `kno plan set` before leaving
+    /// the planning state for anything but an escape state; `*` for all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub require_plan: Vec<String>,
+    /// Per-agent suggestion settings for `kno suggest`, keyed by agent name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub agents: BTreeMap<String, AgentConfig>,
+    /// Spaces created with `kno space create`, each synced on its own branch.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spaces: Vec<String>,
+    /// Space commands target when neither `--space` nor `KNOTS_SPACE` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_space: Option<String>,
+    /// Knot fields left out of `ls --json` and `ls --stream` unless
+    /// `--include-sensitive` is passed; `fields.<name>` names a custom field.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sensitive_fields: Vec<String>,
+    /// Team-defined event types for `kno events emit`, keyed by type name,
+    /// with the custom fields each one projects onto the knot.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub event_types: BTreeMap<String, EventTypeSpec>,
+    /// Review-queue and stale-knot digests for `kno digest send`, keyed by
+    /// digest name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub digests: BTreeMap<String, DigestTarget>,
+}
+
+/// `[agents.<name>]`: tags the agent is good at (`area/*` prefixes allowed)
+/// and how many knots it can hold at once.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AgentConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<usize>,
+}
+
+/// `[digests.<name>]`: the profiles a digest covers (all when empty), who
+/// receives it, and how often `kno digest send --due` sends it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DigestTarget {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub email: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub every: Option<String>,
+    /// Span after which a knot counts as stale even without a state budget.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale_after: Option<String>,
+}
+
+impl Default for RepoConfig {
+    fn default() -> Self {
+        Self {
+            version: REPO_CONFIG_VERSION,
+            default_profile: None,
+            default_quick_profile: None,
+            branch: None,
+            remote: None,
+            settings: BTreeMap::new(),
+            links: BTreeMap::new(),
+            fields: BTreeMap::new(),
+            tag_namespaces: Vec::new(),
+            wip_limits: BTreeMap::new(),
+            state_budgets: BTreeMap::new(),
+            require_criteria: Vec::new(),
+            require_plan: Vec::new(),
+            agents: BTreeMap::new(),
+            spaces: Vec::new(),
+            default_space: None,
+            sensitive_fields: Vec::new(),
+            event_types: BTreeMap::new(),
+            digests: BTreeMap::new(),
+        }
+    }
+}
+
+impl RepoConfig {
+    /// Dotted keys such as `compact.auto` match either a quoted key or the
+    /// nested `[settings.compact]` table a hand-edited file might use.
+    pub fn setting(&self, key: &str) -> Option<String> {
+        let value = self.settings.get(key).or_else(|| {
+            let (head, rest) = key.split_once('.')?;
+            rest.split('.')
+                .try_fold(self.settings.get(head)?, |value, part| value.get(part))
+        })?;
+        match value {
+            toml::Value::String(value) => Some(value.clone()),
+            toml::Value::Integer(value) => Some(value.to_string()),
+            toml::Value::Boolean(value) => Some(value.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Stores `value` as a TOML integer or boolean when it parses as one so
+    /// the committed file stays idiomatic.
+    pub fn set_setting(&mut self, key: &str, value: &str) {
+        let typed = if let Ok(number) = value.parse::<i64>() {
+            toml::Value::Integer(number)
+        } else if let Ok(flag) = value.parse::<bool>() {
+            toml::Value::Boolean(flag)
+        } else {
+            toml::Value::String(value.to_string())
+        };
+        self.settings.insert(key.to_string(), typed);
+    }
+
+    pub fn remove_setting(&mut self, key: &str) -> bool {
+        self.settings.remove(key).is_some()
+    }
+}
+
+/// The knots branch named by the repo config under `store_root`, falling
+/// back to `knots` when the config is missing, unreadable, or silent.
+pub fn knots_branch(store_root: &Path) -> String {
+    configured(store_root, |config| config.branch)
+        .unwrap_or_else(|| DEFAULT_KNOTS_BRANCH.to_string())
+}
+
+/// The custom event types declared in the repo config under `store_root`;
+/// none when the config is missing or unreadable.
+pub fn event_types(store_root: &Path) -> BTreeMap<String, EventTypeSpec> {
+    read_repo_config(store_root)
+        .ok()
+        .flatten()
+        .map(|config| config.event_types)
+        .unwrap_or_default()
+}
+
+/// The remote the knots branch lives on; `origin` unless configured.
+pub fn knots_remote(store_root: &Path) -> String {
+    configured(store_root, |config| config.remote)
+        .unwrap_or_else(|| DEFAULT_KNOTS_REMOTE.to_string())
+}
+
+/// The configured `(remote, branch)` pair for the store at `store_root`.
+pub fn knots_ref(store_root: &Path) -> (String, String) {
+    (knots_remote(store_root), knots_branch(store_root))
+}
+
+/// Records the knots branch and remote in the repo config. `None` leaves a
+/// value untouched; the defaults are stored as unset.
+pub fn set_knots_ref(
+    store_root: &Path,
+    branch: Option<&str>,
+    remote: Option<&str>,
+) -> Result<(), String> {
+    let mut config = read_repo_config(store_root)?.unwrap_or_default();
+    if let Some(branch) = branch {
+        let branch = validate_ref_name(branch)?;
+        config.branch = (branch != DEFAULT_KNOTS_BRANCH).then_some(branch);
+    }
+    if let Some(remote) = remote {
+        let remote = validate_ref_name(remote)?;
+        config.remote = (remote != DEFAULT_KNOTS_REMOTE).then_some(remote);
+    }
+    write_repo_config(store_root, &config)
+}
+
+/// Rejects names git would refuse as a branch or remote.
+pub fn validate_ref_name(raw: &str) -> Result<String, String> {
+    let raw = raw.trim();
+    let invalid = raw.is_empty()
+        || raw.starts_with('-')
+        || raw.starts_with('/')
+        || raw.ends_with('/')
+        || raw.ends_with(".lock")
+        || raw.contains("..")
+        || raw.contains("//")
+        || raw.contains("@{")
+        || raw
+            .chars()
+            .any(|ch| ch.is_whitespace() || ch.is_control() || "~^:?*[\\".contains(ch));
+    if invalid {
+        return Err(format!("'{}' is not a valid branch or remote name", raw));
+    }
+    Ok(raw.to_string())
+}
+
+fn configured(store_root: &Path, field: fn(RepoConfig) -> Option<String>) -> Option<String> {
+    read_repo_config(store_root)
+        .ok()
+        .flatten()
+        .and_then(field)
+        .filter(|value| !value.trim().is_empty())
+}
+
+fn current_version() -> u32 {
+    REPO_CONFIG_VERSION
+}
+
+pub fn repo_config_path(store_root: &Path) -> PathBuf {
+    store_root.join(REPO_CONFIG_FILE)
+}
+
+pub fn read_repo_config(store_root: &Path) -> Result<Option<RepoConfig>, String> {
+    let path = repo_config_path(store_root);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(&path).map_err(|err| err.to_string())?;
+    let config: RepoConfig = toml::from_str(&raw)
+        .map_err(|err| format!("invalid repo config '{}': {err}", path.display()))?;
+    if config.version > REPO_CONFIG_VERSION {
+        return Err(format!(
+            "repo config '{}' has version {}, but this kno supports up to {}; upgrade kno",
+            path.display(),
+            config.version,
+            REPO_CONFIG_VERSION
+        ));
+    }
+    Ok(Some(config))
+}
+
+pub fn write_repo_config(store_root: &Path, config: &RepoConfig) -> Result<(), String> {
+    fs::create_dir_all(store_root).map_err(|err| err.to_string())?;
+    let rendered = toml::to_string_pretty(config).map_err(|err| err.to_string())?;
+    fs::write(repo_config_path(store_root), rendered).map_err(|err| err.to_string())
+}
+
+/// Copies a locally edited repo config into the publish worktree. Returns
+/// whether the worktree copy changed.
+pub(crate) fn publish_into_worktree(
+    conn: &Connection,
+    local_store: &Path,
+    worktree_store: &Path,
+) -> std::io::Result<bool> {
+    let target = repo_config_path(worktree_store);
+    let remote = read_bytes(&target)?;
+    let Some(local) = config_to_publish(conn, local_store, remote.as_deref())? else {
+        return Ok(false);
+    };
+    fs::create_dir_all(worktree_store)?;
+    fs::write(target, local)?;
+    Ok(true)
+}
+
+/// The local repo config when it should replace the published copy.
+pub(crate) fn config_to_publish(
+    conn: &Connection,
+    local_store: &Path,
+    remote: Option<&[u8]>,
+) -> std::io::Result<Option<Vec<u8>>> {
+    let Some(local) = read_bytes(&repo_config_path(local_store))? else {
+        return Ok(None);
+    };
+    if remote == Some(local.as_slice()) {
+        return Ok(None);
+    }
+    if remote.is_some() && synced_sha(conn)? == Some(sha_hex(&local)) {
+        // Unchanged locally since the last sync; let pull adopt the remote copy.
+        return Ok(None);
+    }
+    Ok(Some(local))
+}
+
+/// Records the local repo config as in sync with the knots branch.
+pub(crate) fn mark_published(conn: &Connection, local_store: &Path) -> std::io::Result<()> {
+    if let Some(local) = read_bytes(&repo_config_path(local_store))? {
+        set_synced_sha(conn, &sha_hex(&local))?;
+    }
+    Ok(())
+}
+
+/// Adopts the repo config from the pulled worktree unless the local copy
+/// carries unpublished edits. Returns whether the local copy changed.
+pub(crate) fn adopt_from_worktree(
+    conn: &Connection,
+    local_store: &Path,
+    worktree_store: &Path,
+) -> std::io::Result<bool> {
+    let remote = read_bytes(&repo_config_path(worktree_store))?;
+    adopt_config(conn, local_store, remote)
+}
+
+/// Adopts `remote`, the published repo config, under the same rules as
+/// `adopt_from_worktree`.
+pub(crate) fn adopt_config(
+    conn: &Connection,
+    local_store: &Path,
+    remote: Option<Vec<u8>>,
+) -> std::io::Result<bool> {
+    let Some(remote) = remote else {
+        return Ok(false);
+    };
+    let remote_sha = sha_hex(&remote);
+    let synced = synced_sha(conn)?;
+    if synced.as_deref() == Some(remote_sha.as_str()) {
+        return Ok(false);
+    }
+    let target = repo_config_path(local_store);
+    let mut adopted = remote;
+    if let Some(local) = read_bytes(&target)? {
+        let local_sha = sha_hex(&local);
+        if local_sha == remote_sha {
+            set_synced_sha(conn, &remote_sha)?;
+            return Ok(false);
+        }
+        if synced.is_none() {
+            adopted = first_sync_merge(&local, adopted);
+        } else if synced.as_deref() != Some(local_sha.as_str()) {
+            return Ok(false);
+        }
+    }
+    fs::create_dir_all(local_store)?;
+    fs::write(target, adopted)?;
+    set_synced_sha(conn, &remote_sha)?;
+    Ok(true)
+}
+
+/// Whether `kno init` should maintain the `.knots` rule in `.gitignore`.
+/// A local `manage_gitignore` override wins over the repo config.
+pub(crate) fn gitignore_managed(
+    conn: &Connection,
+    store_root: &Path,
+) -> Result<bool, crate::app::AppError> {
+    let local = db::get_meta(conn, "config_source:manage_gitignore")?.as_deref() == Some("local");
+    let shared = read_repo_config(store_root)
+        .map_err(crate::app::AppError::InvalidArgument)?
+        .and_then(|config| config.setting("manage_gitignore"));
+    let value = match (local, shared) {
+        (false, Some(value)) => Some(value),
+        _ => db::get_meta(conn, "manage_gitignore")?,
+    };
+    Ok(value.is_none_or(|value| value.trim() != "false"))
+}
+
+/// A clone that never synced takes the shared copy, keeping only the knots
+/// branch and remote it was initialized with when the shared copy is silent.
+fn first_sync_merge(local: &[u8], remote: Vec<u8>) -> Vec<u8> {
+    let parse = |bytes: &[u8]| {
+        std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|raw| toml::from_str::<RepoConfig>(raw).ok())
+    };
+    let (Some(local), Some(mut shared)) = (parse(local), parse(&remote)) else {
+        return remote;
+    };
+    if (shared.branch.is_some() || local.branch.is_none())
+        && (shared.remote.is_some() || local.remote.is_none())
+    {
+        return remote;
+    }
+    shared.branch = shared.branch.or(local.branch);
+    shared.remote = shared.remote.or(local.remote);
+    toml::to_string_pretty(&shared).map_or(remote, String::into_bytes)
+}
+
+fn read_bytes(path: &Path) -> std::io::Result<Option<Vec<u8>>> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn sha_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn synced_sha(conn: &Connection) -> std::io::Result<Option<String>> {
+    db::get_meta(conn, SYNCED_SHA_META_KEY).map_err(std::io::Error::other)
+}
+
+fn set_synced_sha(conn: &Connection, sha: &str) -> std::io::Result<()> {
+    db::set_meta(conn, SYNCED_SHA_META_KEY, sha).map_err(std::io::Error::other)
+}
@@ -0,0 +1,130 @@
+use std::path::PathBuf;
+
+use crate::db;
+use crate::repo_config::{
+    adopt_from_worktree, gitignore_managed, knots_ref, mark_published, publish_into_worktree,
+    read_repo_config, repo_config_path, set_knots_ref, write_repo_config, RepoConfig,
+};
+
+fn setup() -> (PathBuf, rusqlite::Connection, PathBuf, PathBuf) {
+    let root = std::env::temp_dir().join(format!("knots-repo-config-{}", uuid::Uuid::now_v7()));
+    let local = root.join(".knots");
+    let worktree = local.join("_worktree/.knots");
+    std::fs::create_dir_all(&worktree).expect("mkdir");
+    let conn =
+        db::open_connection(local.join("cache.sqlite").to_str().expect("utf8")).expect("open db");
+    (root, conn, local, worktree)
+}
+
+fn write_setting(store: &std::path::Path, key: &str, value: &str) {
+    let mut config = read_repo_config(store).expect("read").unwrap_or_default();
+    config.set_setting(key, value);
+    write_repo_config(store, &config).expect("write");
+}
+
+#[test]
+fn set_setting_keeps_toml_types() {
+    let mut config = RepoConfig::default();
+    config.set_setting("hot_window_days", "14");
+    config.set_setting("manage_gitignore", "false");
+    config.set_setting("sync_policy", "never");
+    let rendered = toml::to_string_pretty(&config).expect("render");
+    assert!(rendered.contains("hot_window_days = 14"), "{rendered}");
+    assert!(rendered.contains("manage_gitignore = false"), "{rendered}");
+    assert!(rendered.contains("sync_policy = \"never\""), "{rendered}");
+    assert_eq!(config.setting("hot_window_days").as_deref(), Some("14"));
+}
+
+#[test]
+fn dotted_settings_read_quoted_or_nested_keys() {
+    let quoted: RepoConfig =
+        toml::from_str("version = 1\n[settings]\n\"compact.auto\" = \"weekly\"\n").expect("parse");
+    assert_eq!(quoted.setting("compact.auto").as_deref(), Some("weekly"));
+    let nested: RepoConfig =
+        toml::from_str("version = 1\n[settings.compact]\nauto = \"daily\"\nprune_events = true\n")
+            .expect("parse");
+    assert_eq!(nested.setting("compact.auto").as_deref(), Some("daily"));
+    assert_eq!(
+        nested.setting("compact.prune_events").as_deref(),
+        Some("true")
+    );
+    assert_eq!(nested.setting("compact.missing"), None);
+}
+
+#[test]
+fn newer_config_versions_are_rejected() {
+    let (root, _conn, local, _) = setup();
+    std::fs::write(repo_config_path(&local), "version = 99\n").expect("write");
+    let err = read_repo_config(&local).expect_err("future version");
+    assert!(err.contains("upgrade kno"), "{err}");
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn local_edits_publish_and_remote_edits_are_adopted() {
+    let (root, conn, local, worktree) = setup();
+    write_setting(&local, "sync_policy", "never");
+    assert!(publish_into_worktree(&conn, &local, &worktree).expect("publish"));
+    mark_published(&conn, &local).expect("mark");
+    assert!(!adopt_from_worktree(&conn, &local, &worktree).expect("adopt noop"));
+
+    write_setting(&worktree, "sync_policy", "always");
+    assert!(!publish_into_worktree(&conn, &local, &worktree).expect("stale local"));
+    assert!(adopt_from_worktree(&conn, &local, &worktree).expect("adopt"));
+    let adopted = read_repo_config(&local).expect("read").expect("config");
+    assert_eq!(adopted.setting("sync_policy").as_deref(), Some("always"));
+
+    write_setting(&local, "hot_window_days", "9");
+    write_setting(&worktree, "sync_policy", "auto");
+    assert!(!adopt_from_worktree(&conn, &local, &worktree).expect("keep local edits"));
+    assert!(publish_into_worktree(&conn, &local, &worktree).expect("publish edits"));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn gitignore_management_follows_repo_then_local_override() {
+    let (root, conn, local, _) = setup();
+    assert!(gitignore_managed(&conn, &local).expect("default"));
+    write_setting(&local, "manage_gitignore", "false");
+    assert!(!gitignore_managed(&conn, &local).expect("repo"));
+    db::set_meta(&conn, "manage_gitignore", "true").expect("meta");
+    db::set_meta(&conn, "config_source:manage_gitignore", "local").expect("meta");
+    assert!(gitignore_managed(&conn, &local).expect("local"));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn knots_ref_defaults_and_validates_names() {
+    let (root, _conn, local, _worktree) = setup();
+    assert_eq!(
+        knots_ref(&local),
+        ("origin".to_string(), "knots".to_string())
+    );
+
+    set_knots_ref(&local, Some("team/knots"), Some("upstream")).expect("set ref");
+    assert_eq!(
+        knots_ref(&local),
+        ("upstream".to_string(), "team/knots".to_string())
+    );
+    for bad in ["-x", "a..b", "has space", "ends/", "x.lock", ""] {
+        assert!(set_knots_ref(&local, Some(bad), None).is_err(), "{bad}");
+    }
+    set_knots_ref(&local, Some("knots"), None).expect("reset branch");
+    let config = read_repo_config(&local).expect("read").expect("config");
+    assert_eq!(config.branch, None);
+    assert_eq!(config.remote.as_deref(), Some("upstream"));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn first_sync_adopts_shared_config_but_keeps_local_knots_ref() {
+    let (root, conn, local, worktree) = setup();
+    set_knots_ref(&local, Some("tracker"), None).expect("set ref");
+    write_setting(&worktree, "hot_window_days", "30");
+
+    assert!(adopt_from_worktree(&conn, &local, &worktree).expect("adopt"));
+    let config = read_repo_config(&local).expect("read").expect("config");
+    assert_eq!(config.setting("hot_window_days").as_deref(), Some("30"));
+    assert_eq!(config.branch.as_deref(), Some("tracker"));
+    let _ = std::fs::remove_dir_all(root);
+}
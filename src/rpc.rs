@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use crate::app::{App, AppError};
+use crate::cli::RpcArgs;
+
+mod methods;
+
+#[cfg(test)]
+mod tests;
+
+/// Largest message body `kno rpc` accepts.
+const MAX_MESSAGE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Floor for `--poll-ms`, so a typo cannot spin the change check.
+const MIN_POLL_MS: u64 = 50;
+
+/// Notification sent when knots change, whoever changed them.
+pub(crate) const DID_CHANGE: &str = "knots/didChange";
+
+/// JSON-RPC error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+
+/// What the stdin reader hands the main loop.
+enum Incoming {
+    Message(Result<Value, String>),
+    Closed,
+}
+
+/// Speaks JSON-RPC 2.0 with LSP-style `Content-Length` framing on
+/// stdin/stdout until the client sends `exit` or closes stdin.
+pub fn run_rpc(app: &App, args: RpcArgs) -> Result<(), AppError> {
+    let (sender, messages) = mpsc::channel();
+    std::thread::spawn(move || read_messages(io::stdin().lock(), &sender));
+    let poll = Duration::from_millis(args.poll_ms.max(MIN_POLL_MS));
+    serve(app, &messages, &mut io::stdout().lock(), poll)
+}
+
+fn read_messages<R: BufRead>(mut input: R, sender: &Sender<Incoming>) {
+    loop {
+        let incoming = match read_frame(&mut input) {
+            Ok(Some(body)) => Incoming::Message(
+                serde_json::from_slice(&body).map_err(|err| format!("invalid JSON: {err}")),
+            ),
+            Ok(None) => Incoming::Closed,
+            Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+                Incoming::Message(Err(err.to_string()))
+            }
+            Err(_) => Incoming::Closed,
+        };
+        let closed = matches!(incoming, Incoming::Closed);
+        if sender.send(incoming).is_err() || closed {
+            return;
+        }
+    }
+}
+
+/// The next message body, or `None` at end of input. Headers other than
+/// `Content-Length` are read and ignored.
+fn read_frame<R: BufRead>(input: &mut R) -> io::Result<Option<Vec<u8>>> {
+    let mut length = None;
+    let mut saw_header = false;
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return if saw_header {
+                Err(io::Error::from(io::ErrorKind::UnexpectedEof))
+            } else {
+                Ok(None)
+            };
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            if saw_header {
+                break;
+            }
+            continue;
+        }
+        saw_header = true;
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let length = length
+        .filter(|length| *length <= MAX_MESSAGE_BYTES)
+        .ok_or_else(|| invalid_data("missing or oversized Content-Length header"))?;
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(body))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn write_frame<W: Write>(output: &mut W, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    output.flush()
+}
+
+fn serve<W: Write>(
+    app: &App,
+    messages: &Receiver<Incoming>,
+    output: &mut W,
+    poll: Duration,
+) -> Result<(), AppError> {
+    let mut session = Session::default();
+    let mut tracker = ChangeTracker::new(app)?;
+    let mut last_poll = Instant::now();
+    loop {
+        let wait = poll.saturating_sub(last_poll.elapsed());
+        match messages.recv_timeout(wait) {
+            Ok(Incoming::Message(message)) => {
+                if let Some(response) = session.handle(app, message) {
+                    write_frame(output, &response)?;
+                }
+                if session.exited {
+                    return Ok(());
+                }
+            }
+            Ok(Incoming::Closed) | Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+        if last_poll.elapsed() >= poll && !session.shut_down {
+            last_poll = Instant::now();
+            match tracker.poll(app) {
+                Ok(Some(notification)) => write_frame(output, &notification)?,
+                Ok(None) => {}
+                // A busy or mid-sync store is retried at the next poll.
+                Err(err) => eprintln!("kno rpc: change check failed: {err}"),
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Session {
+    shut_down: bool,
+    exited: bool,
+}
+
+impl Session {
+    /// The response to one message; notifications get none.
+    fn handle(&mut self, app: &App, message: Result<Value, String>) -> Option<Value> {
+        let message = match message {
+            Ok(message) => message,
+            Err(err) => return Some(error_response(Value::Null, PARSE_ERROR, &err)),
+        };
+        let id = message.get("id").cloned();
+        let method = message.get("method").and_then(Value::as_str);
+        let (Some(method), Some("2.0")) = (method, message.get("jsonrpc").and_then(Value::as_str))
+        else {
+            return id
+                .map(|id| error_response(id, INVALID_REQUEST, "expected a JSON-RPC 2.0 request"));
+        };
+        let Some(id) = id else {
+            self.exited |= method == "exit";
+            return None;
+        };
+        if self.shut_down {
+            return Some(error_response(
+                id,
+                INVALID_REQUEST,
+                "server is shutting down; send exit",
+            ));
+        }
+        if method == "shutdown" {
+            self.shut_down = true;
+            return Some(json!({ "jsonrpc": "2.0", "id": id, "result": null }));
+        }
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        Some(match methods::call(app, method, &params) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(err) => error_response(id, err.code, &err.message),
+        })
+    }
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+/// Remembers each knot's `updated_at` so the next poll can report what
+/// changed since, including writes made by other `kno` processes.
+struct ChangeTracker {
+    seen: BTreeMap<String, String>,
+}
+
+impl ChangeTracker {
+    fn new(app: &App) -> Result<Self, AppError> {
+        Ok(Self {
+            seen: fingerprint(app)?,
+        })
+    }
+
+    fn poll(&mut self, app: &App) -> Result<Option<Value>, AppError> {
+        let current = fingerprint(app)?;
+        let changed: Vec<&String> = current
+            .iter()
+            .filter(|(id, updated_at)| self.seen.get(*id) != Some(*updated_at))
+            .map(|(id, _)| id)
+            .collect();
+        let removed: Vec<&String> = self
+            .seen
+            .keys()
+            .filter(|id| !current.contains_key(*id))
+            .collect();
+        let notification = (!changed.is_empty() || !removed.is_empty()).then(|| {
+            json!({
+                "jsonrpc": "2.0",
+                "method": DID_CHANGE,
+                "params": { "changed": changed, "removed": removed },
+            })
+        });
+        self.seen = current;
+        Ok(notification)
+    }
+}
+
+fn fingerprint(app: &App) -> Result<BTreeMap<String, String>, AppError> {
+    Ok(app
+        .list_knots()?
+        .into_iter()
+        .map(|knot| (knot.id, knot.updated_at))
+        .collect())
+}
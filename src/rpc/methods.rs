@@ -0,0 +1,231 @@
+use std::str::FromStr;
+
+use serde_json::{json, Map, Value};
+
+use crate::app::{App, AppError, KnotView, StateActorMetadata, UpdateKnotPatch};
+use crate::domain::knot_type::KnotType;
+use crate::domain::metadata::MetadataEntryInput;
+use crate::lease_expiry::DEFAULT_LEASE_TIMEOUT_SECONDS;
+use crate::poll_claim;
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Application errors, in the range JSON-RPC leaves to servers.
+const SERVER_ERROR: i64 = -32000;
+const KNOT_NOT_FOUND: i64 = -32001;
+
+/// Request methods other than `shutdown`, which the session handles.
+const METHODS: [&str; 5] = [
+    "initialize",
+    "knots/list",
+    "knots/show",
+    "knots/update",
+    "knots/claim",
+];
+
+#[derive(Debug)]
+pub(super) struct RpcError {
+    pub(super) code: i64,
+    pub(super) message: String,
+}
+
+impl RpcError {
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self {
+            code: INVALID_PARAMS,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<AppError> for RpcError {
+    fn from(err: AppError) -> Self {
+        let code = match &err {
+            AppError::NotFound(_) => KNOT_NOT_FOUND,
+            AppError::InvalidArgument(_) | AppError::ParseState(_) => INVALID_PARAMS,
+            _ => SERVER_ERROR,
+        };
+        Self {
+            code,
+            message: err.to_string(),
+        }
+    }
+}
+
+pub(super) fn call(app: &App, method: &str, params: &Value) -> Result<Value, RpcError> {
+    let params = match params {
+        Value::Object(params) => params.clone(),
+        Value::Null => Map::new(),
+        _ => return Err(RpcError::invalid_params("params must be an object")),
+    };
+    match method {
+        "initialize" => Ok(initialize()),
+        "knots/list" => list(app, &params),
+        "knots/show" => show(app, &params),
+        "knots/update" => update(app, &params),
+        "knots/claim" => claim(app, &params),
+        other => Err(RpcError {
+            code: METHOD_NOT_FOUND,
+            message: format!("unknown method '{other}'"),
+        }),
+    }
+}
+
+fn initialize() -> Value {
+    json!({
+        "serverInfo": { "name": "kno", "version": env!("CARGO_PKG_VERSION") },
+        "capabilities": {
+            "methods": METHODS,
+            "notifications": [super::DID_CHANGE],
+        },
+    })
+}
+
+/// `{state?, tag?, type?, includeSensitive?}`: knots as `kno ls --json`
+/// shows them.
+fn list(app: &App, params: &Map<String, Value>) -> Result<Value, RpcError> {
+    let state = string_param(params, "state")?;
+    let tag = string_param(params, "tag")?;
+    let knot_type = knot_type_param(params)?;
+    let knots: Vec<KnotView> = app
+        .list_knots()?
+        .into_iter()
+        .filter(|knot| state.as_ref().is_none_or(|state| &knot.state == state))
+        .filter(|knot| tag.as_ref().is_none_or(|tag| knot.tags.contains(tag)))
+        .filter(|knot| knot_type.is_none_or(|knot_type| knot.knot_type == knot_type))
+        .collect();
+    export(app, &knots, params)
+}
+
+/// `{id, includeSensitive?}`: one knot with its edges.
+fn show(app: &App, params: &Map<String, Value>) -> Result<Value, RpcError> {
+    let id = required_string(params, "id")?;
+    let knot = app
+        .show_knot(&id)?
+        .ok_or_else(|| AppError::NotFound(id.clone()))?;
+    first(export(app, std::slice::from_ref(&knot), params)?)
+}
+
+/// `{id, title?, description?, acceptance?, priority?, state?, type?,
+/// addTags?, removeTags?, note?, force?}`, answered with the updated knot.
+fn update(app: &App, params: &Map<String, Value>) -> Result<Value, RpcError> {
+    let id = required_string(params, "id")?;
+    let patch = UpdateKnotPatch {
+        title: string_param(params, "title")?,
+        description: string_param(params, "description")?,
+        acceptance: string_param(params, "acceptance")?,
+        priority: int_param(params, "priority")?,
+        status: string_param(params, "state")?,
+        knot_type: knot_type_param(params)?,
+        add_tags: strings_param(params, "addTags")?,
+        remove_tags: strings_param(params, "removeTags")?,
+        add_note: string_param(params, "note")?.map(|content| MetadataEntryInput {
+            content,
+            ..MetadataEntryInput::default()
+        }),
+        force: bool_param(params, "force")?,
+        state_actor: actor(params)?,
+        ..UpdateKnotPatch::default()
+    };
+    let knot = app.update_knot(&id, patch)?;
+    first(export(app, std::slice::from_ref(&knot), params)?)
+}
+
+/// `{id, agentName?, agentModel?, agentVersion?, timeoutSeconds?}`,
+/// answered with the same JSON as `kno claim --json`.
+fn claim(app: &App, params: &Map<String, Value>) -> Result<Value, RpcError> {
+    let id = required_string(params, "id")?;
+    let timeout = match params.get("timeoutSeconds") {
+        None | Some(Value::Null) => DEFAULT_LEASE_TIMEOUT_SECONDS,
+        Some(value) => value
+            .as_u64()
+            .ok_or_else(|| RpcError::invalid_params("timeoutSeconds must be a number"))?,
+    };
+    let actor = StateActorMetadata {
+        actor_kind: Some("agent".to_string()),
+        ..actor(params)?
+    };
+    let result = poll_claim::claim_knot(app, &id, actor, None, timeout)?;
+    Ok(poll_claim::render_json_verbose(&result, false))
+}
+
+fn export(app: &App, knots: &[KnotView], params: &Map<String, Value>) -> Result<Value, RpcError> {
+    let include_sensitive = bool_param(params, "includeSensitive")?;
+    Ok(Value::Array(
+        app.knots_for_export(knots, include_sensitive)?,
+    ))
+}
+
+fn first(exported: Value) -> Result<Value, RpcError> {
+    match exported {
+        Value::Array(mut knots) if !knots.is_empty() => Ok(knots.swap_remove(0)),
+        _ => Err(AppError::NotFound("knot".to_string()).into()),
+    }
+}
+
+fn actor(params: &Map<String, Value>) -> Result<StateActorMetadata, RpcError> {
+    Ok(StateActorMetadata {
+        actor_kind: None,
+        agent_name: string_param(params, "agentName")?,
+        agent_model: string_param(params, "agentModel")?,
+        agent_version: string_param(params, "agentVersion")?,
+    })
+}
+
+fn string_param(params: &Map<String, Value>, name: &str) -> Result<Option<String>, RpcError> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(_) => Err(RpcError::invalid_params(format!("{name} must be a string"))),
+    }
+}
+
+fn required_string(params: &Map<String, Value>, name: &str) -> Result<String, RpcError> {
+    string_param(params, name)?
+        .ok_or_else(|| RpcError::invalid_params(format!("{name} is required")))
+}
+
+fn strings_param(params: &Map<String, Value>, name: &str) -> Result<Vec<String>, RpcError> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| {
+                item.as_str().map(str::to_string).ok_or_else(|| {
+                    RpcError::invalid_params(format!("{name} must be an array of strings"))
+                })
+            })
+            .collect(),
+        Some(_) => Err(RpcError::invalid_params(format!(
+            "{name} must be an array of strings"
+        ))),
+    }
+}
+
+fn int_param(params: &Map<String, Value>, name: &str) -> Result<Option<i64>, RpcError> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+            .as_i64()
+            .map(Some)
+            .ok_or_else(|| RpcError::invalid_params(format!("{name} must be an integer"))),
+    }
+}
+
+fn bool_param(params: &Map<String, Value>, name: &str) -> Result<bool, RpcError> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(false),
+        Some(Value::Bool(value)) => Ok(*value),
+        Some(_) => Err(RpcError::invalid_params(format!(
+            "{name} must be a boolean"
+        ))),
+    }
+}
+
+fn knot_type_param(params: &Map<String, Value>) -> Result<Option<KnotType>, RpcError> {
+    string_param(params, "type")?
+        .map(|raw| {
+            KnotType::from_str(&raw).map_err(|err| RpcError::invalid_params(err.to_string()))
+        })
+        .transpose()
+}
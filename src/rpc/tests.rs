@@ -0,0 +1,175 @@
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+
+use super::{read_frame, write_frame, ChangeTracker, Session, DID_CHANGE};
+use crate::app::{App, UpdateKnotPatch};
+
+fn open_app() -> (PathBuf, App) {
+    let root = std::env::temp_dir().join(format!("knots-rpc-{}", uuid::Uuid::now_v7()));
+    let db_path = root.join(".knots").join("cache").join("state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let app =
+        App::open(db_path.to_str().expect("utf8 db path"), root.clone()).expect("app should open");
+    (root, app)
+}
+
+fn request(session: &mut Session, app: &App, id: u64, method: &str, params: Value) -> Value {
+    session
+        .handle(
+            app,
+            Ok(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })),
+        )
+        .expect("requests get a response")
+}
+
+#[test]
+fn frames_round_trip_and_reject_missing_lengths() {
+    let mut buffer = Vec::new();
+    write_frame(&mut buffer, &json!({ "a": "ü" })).expect("write");
+    write_frame(&mut buffer, &json!([1])).expect("write");
+    let text = String::from_utf8(buffer.clone()).expect("utf8");
+    assert!(text.starts_with("Content-Length: 10\r\n\r\n{\"a\":\"ü\"}"));
+
+    let mut input = Cursor::new(buffer);
+    assert_eq!(
+        read_frame(&mut input).expect("read"),
+        Some("{\"a\":\"ü\"}".as_bytes().to_vec())
+    );
+    assert_eq!(read_frame(&mut input).expect("read"), Some(b"[1]".to_vec()));
+    assert_eq!(read_frame(&mut input).expect("read"), None);
+
+    let mut bad = Cursor::new(b"Content-Type: json\r\n\r\n{}".to_vec());
+    let err = read_frame(&mut bad).expect_err("no length");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn session_answers_requests_and_reports_errors() {
+    let (root, app) = open_app();
+    let knot = app
+        .create_knot("Editor knot", Some("secret"), None, None)
+        .expect("create");
+    let mut session = Session::default();
+
+    let init = request(&mut session, &app, 1, "initialize", Value::Null);
+    assert_eq!(
+        init["result"]["capabilities"]["notifications"][0],
+        DID_CHANGE
+    );
+
+    let listed = request(
+        &mut session,
+        &app,
+        2,
+        "knots/list",
+        json!({ "state": knot.state }),
+    );
+    assert_eq!(listed["result"][0]["id"], knot.id.as_str());
+    let none = request(
+        &mut session,
+        &app,
+        3,
+        "knots/list",
+        json!({ "tag": "nope" }),
+    );
+    assert_eq!(none["result"], json!([]));
+
+    let updated = request(
+        &mut session,
+        &app,
+        4,
+        "knots/update",
+        json!({ "id": knot.id, "title": "Renamed", "addTags": ["ide"], "note": "from vim" }),
+    );
+    assert_eq!(updated["result"]["title"], "Renamed");
+    assert_eq!(updated["result"]["tags"], json!(["ide"]));
+    let shown = request(
+        &mut session,
+        &app,
+        5,
+        "knots/show",
+        json!({ "id": knot.id }),
+    );
+    assert_eq!(shown["result"]["notes"][0]["content"], "from vim");
+
+    let code = |response: Value| response["error"]["code"].as_i64().expect("error code");
+    assert_eq!(
+        code(request(
+            &mut session,
+            &app,
+            6,
+            "knots/show",
+            json!({ "id": "zzz" })
+        )),
+        -32001
+    );
+    assert_eq!(
+        code(request(&mut session, &app, 7, "knots/show", json!({}))),
+        -32602
+    );
+    assert_eq!(
+        code(request(&mut session, &app, 8, "knots/nope", Value::Null)),
+        -32601
+    );
+    assert_eq!(
+        code(request(&mut session, &app, 9, "knots/list", json!([1]))),
+        -32602
+    );
+    let parse = session.handle(&app, Err("invalid JSON".to_string()));
+    assert_eq!(code(parse.expect("parse errors are answered")), -32700);
+    let invalid = session.handle(&app, Ok(json!({ "id": 10, "method": "knots/list" })));
+    assert_eq!(
+        code(invalid.expect("invalid requests are answered")),
+        -32600
+    );
+
+    assert_eq!(
+        request(&mut session, &app, 11, "shutdown", Value::Null)["result"],
+        Value::Null
+    );
+    assert_eq!(
+        code(request(&mut session, &app, 12, "knots/list", Value::Null)),
+        -32600
+    );
+    let exit = session.handle(&app, Ok(json!({ "jsonrpc": "2.0", "method": "exit" })));
+    assert!(exit.is_none());
+    assert!(session.exited);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn change_tracker_reports_changed_and_new_knots_once() {
+    let (root, app) = open_app();
+    let first = app.create_knot("First", None, None, None).expect("create");
+    let mut tracker = ChangeTracker::new(&app).expect("tracker");
+    assert!(tracker.poll(&app).expect("poll").is_none());
+
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    app.update_knot(
+        &first.id,
+        UpdateKnotPatch {
+            title: Some("First, renamed".to_string()),
+            ..UpdateKnotPatch::default()
+        },
+    )
+    .expect("update");
+    let second = app.create_knot("Second", None, None, None).expect("create");
+    let notification = tracker.poll(&app).expect("poll").expect("changes");
+    assert_eq!(notification["method"], DID_CHANGE);
+    let mut changed: Vec<&str> = notification["params"]["changed"]
+        .as_array()
+        .expect("changed ids")
+        .iter()
+        .filter_map(Value::as_str)
+        .collect();
+    changed.sort_unstable();
+    let mut expected = vec![first.id.as_str(), second.id.as_str()];
+    expected.sort_unstable();
+    assert_eq!(changed, expected);
+    assert_eq!(notification["params"]["removed"], json!([]));
+    assert!(tracker.poll(&app).expect("poll").is_none());
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -1,10 +1,14 @@
+use std::time::Duration;
+
 use crate::action_prompt;
 use crate::cli::{
-    ColdSubcommands, CompactArgs, DoctorArgs, FsckArgs, LeaseSubcommands, PerfArgs, SkillArgs,
+    CompactArgs, DoctorArgs, FsckArgs, LeaseSubcommands, PerfArgs, PerfBaselineSubcommands,
+    PerfContentionArgs, PerfSubcommands, SkillArgs,
 };
 use crate::db::ListHotParams;
-use crate::{app, dispatch, domain, lease, list_layout, listing, stream_output};
-use crate::{print_json, progress, progress_reporter, ui};
+use crate::perf::{run_perf_harness, PerfBaseline};
+use crate::perf_contention::run_contention;
+use crate::{app, dispatch, domain, lease, list_layout, listing, print_json, stream_output, ui};
 
 pub fn run_ls(app: &app::App, args: crate::cli::ListArgs) -> Result<(), app::AppError> {
     let is_paginated = args.limit.is_some() || args.offset.is_some();
@@ -22,6 +26,7 @@ fn run_ls_full(app: &app::App, args: crate::cli::ListArgs) -> Result<(), app::Ap
         knot_type: args.knot_type.clone(),
         profile_id: args.profile_id.clone(),
         tags: args.tags.clone(),
+        custom_fields: field_filters(&args.fields)?,
         query: args.query.clone(),
     };
     let mut knots = listing::apply_filters(app.list_knots()?, &filter);
@@ -29,16 +34,17 @@ fn run_ls_full(app: &app::App, args: crate::cli::ListArgs) -> Result<(), app::Ap
         knots.truncate(limit);
     }
     if args.stream {
-        stream_output::stream_ndjson_knots(&knots)
+        stream_output::stream_ndjson_knots(&app.knots_for_export(&knots, args.include_sensitive)?)
     } else if args.json {
-        print_json(&knots);
+        print_json(&app.knots_for_export(&knots, args.include_sensitive)?);
         Ok(())
     } else {
         let layout_edges = crate::trace::measure("list_layout_edges", || app.list_layout_edges())?;
         let rows = crate::trace::measure("layout_knots", || {
             list_layout::layout_knots(knots, &layout_edges)
         });
-        ui::print_knot_list(&rows, &filter);
+        let columns = list_columns(app, &args, &rows)?;
+        ui::print_knot_list(&rows, &filter, &columns);
         Ok(())
     }
 }
@@ -59,10 +65,12 @@ fn run_ls_paginated(app: &app::App, args: crate::cli::ListArgs) -> Result<(), ap
         knot_type: None,
         profile_id: args.profile_id.clone(),
         tags: args.tags.clone(),
+        custom_fields: field_filters(&args.fields)?,
         query: args.query.clone(),
     };
     let knots = listing::apply_filters(knots, &filter);
     if args.json {
+        let knots = app.knots_for_export(&knots, args.include_sensitive)?;
         let page = app::PaginatedList::new(knots, total, offset, limit);
         print_json(&page);
     } else {
@@ -70,11 +78,40 @@ fn run_ls_paginated(app: &app::App, args: crate::cli::ListArgs) -> Result<(), ap
         let rows = crate::trace::measure("layout_knots", || {
             list_layout::layout_knots(knots, &layout_edges)
         });
-        ui::print_knot_list(&rows, &filter);
+        let columns = list_columns(app, &args, &rows)?;
+        ui::print_knot_list(&rows, &filter, &columns);
     }
     Ok(())
 }
 
+/// `--columns` when given, else the `ls.columns` setting, widened to fit
+/// `rows` under `--no-truncate`.
+fn list_columns(
+    app: &app::App,
+    args: &crate::cli::ListArgs,
+    rows: &[list_layout::DisplayKnot],
+) -> Result<Vec<list_layout::Column>, app::AppError> {
+    let spec = match args.columns.as_deref() {
+        Some(spec) => spec.to_string(),
+        None => app.config_get("ls.columns")?.value,
+    };
+    let columns = list_layout::parse_columns(&spec).map_err(app::AppError::InvalidArgument)?;
+    Ok(if args.no_truncate {
+        ui::untruncated_columns(rows, &columns)
+    } else {
+        columns
+    })
+}
+
+pub(crate) fn field_filters(raw: &[String]) -> Result<Vec<(String, String)>, app::AppError> {
+    raw.iter()
+        .map(|raw| {
+            crate::domain::custom_field::parse_assignment(raw)
+                .map_err(app::AppError::InvalidArgument)
+        })
+        .collect()
+}
+
 pub fn run_show(app: &app::App, args: crate::cli::ShowArgs) -> Result<(), app::AppError> {
     match crate::trace::measure("show_knot", || app.show_knot(&args.id))? {
         Some(knot) => {
@@ -116,112 +153,6 @@ fn trim_show_json_metadata(value: &mut serde_json::Value, knot: &app::KnotView,
     }
 }
 
-pub fn run_pull(app: &app::App, args: crate::cli::SyncArgs) -> Result<(), app::AppError> {
-    let mut reporter = progress_reporter(!args.json);
-    let summary = app.pull_with_progress(
-        reporter
-            .as_mut()
-            .map(|r| r as &mut dyn progress::ProgressReporter),
-    )?;
-    let drift_warning = app.pull_drift_warning()?;
-    if args.json {
-        print_json(&summary);
-    } else {
-        println!(
-            concat!(
-                "pull head={} index_files={} full_files={} ",
-                "knot_updates={} edge_adds={} edge_removes={}"
-            ),
-            summary.target_head,
-            summary.index_files,
-            summary.full_files,
-            summary.knot_updates,
-            summary.edge_adds,
-            summary.edge_removes
-        );
-    }
-    if let Some(warning) = drift_warning {
-        eprintln!(
-            "warning: local knots drift is high (unpushed_event_files={} > \
-             threshold={}); run `kno push`",
-            warning.unpushed_event_files, warning.threshold
-        );
-    }
-    Ok(())
-}
-
-pub fn run_push(app: &app::App, args: crate::cli::SyncArgs) -> Result<(), app::AppError> {
-    let mut reporter = progress_reporter(!args.json);
-    let summary = app.push_with_progress(
-        reporter
-            .as_mut()
-            .map(|r| r as &mut dyn progress::ProgressReporter),
-    )?;
-    if args.json {
-        print_json(&summary);
-    } else {
-        println!(
-            "push local_event_files={} copied_files={} committed={} pushed={}{}",
-            summary.local_event_files,
-            summary.copied_files,
-            summary.committed,
-            summary.pushed,
-            summary
-                .commit
-                .as_ref()
-                .map(|c| format!(" commit={c}"))
-                .unwrap_or_default()
-        );
-    }
-    Ok(())
-}
-
-pub fn run_sync(app: &app::App, args: crate::cli::SyncArgs) -> Result<(), app::AppError> {
-    use crate::replication::SyncOutcome;
-    let mut reporter = progress_reporter(!args.json);
-    let outcome = app.sync_or_defer_with_progress(
-        reporter
-            .as_mut()
-            .map(|r| r as &mut dyn progress::ProgressReporter),
-    )?;
-    match outcome {
-        SyncOutcome::Completed(summary) => {
-            if args.json {
-                print_json(&summary);
-            } else {
-                println!(
-                    "sync push(local_event_files={} copied_files={} \
-                     committed={} pushed={}) \
-                     pull(head={} index_files={} full_files={} \
-                     knot_updates={} edge_adds={} edge_removes={})",
-                    summary.push.local_event_files,
-                    summary.push.copied_files,
-                    summary.push.committed,
-                    summary.push.pushed,
-                    summary.pull.target_head,
-                    summary.pull.index_files,
-                    summary.pull.full_files,
-                    summary.pull.knot_updates,
-                    summary.pull.edge_adds,
-                    summary.pull.edge_removes
-                );
-            }
-        }
-        SyncOutcome::Deferred { active_leases } => {
-            if args.json {
-                print_json(&outcome);
-            } else {
-                println!(
-                    "sync deferred: {} active lease(s); \
-                     sync will run when leases are terminated",
-                    active_leases
-                );
-            }
-        }
-    }
-    Ok(())
-}
-
 pub fn run_fsck(app: &app::App, args: FsckArgs) -> Result<(), app::AppError> {
     let report = crate::trace::measure("fsck", || app.fsck())?;
     if args.json {
@@ -246,7 +177,15 @@ pub fn run_fsck(app: &app::App, args: FsckArgs) -> Result<(), app::AppError> {
 }
 
 pub fn run_doctor(app: &app::App, args: DoctorArgs) -> Result<(), app::AppError> {
-    let report = app.doctor(args.fix)?;
+    let interactive =
+        args.fix && !args.yes && !args.json && std::io::IsTerminal::is_terminal(&std::io::stdin());
+    let report = if interactive {
+        let mut prompt =
+            crate::doctor_fix::FixPrompt::new(std::io::stdin().lock(), std::io::stderr());
+        app.doctor_with_confirm(|check, change| prompt.confirm(check, change))?
+    } else {
+        app.doctor(args.fix)?
+    };
     if args.json {
         print_json(&report);
     } else {
@@ -264,23 +203,95 @@ pub fn run_doctor(app: &app::App, args: DoctorArgs) -> Result<(), app::AppError>
     Ok(())
 }
 
-pub fn run_perf(app: &app::App, args: PerfArgs) -> Result<(), app::AppError> {
-    let report = app.perf_harness(args.iterations)?;
+/// The perf harness builds its own scratch repo, so it runs without a
+/// knots store (e.g. on a fresh CI checkout).
+pub fn run_perf(args: &PerfArgs) -> Result<(), app::AppError> {
+    match &args.command {
+        Some(PerfSubcommands::Baseline(baseline)) => {
+            let PerfBaselineSubcommands::Write(write) = &baseline.command;
+            let report = run_perf_harness(write.iterations)?;
+            PerfBaseline::from_report(&report, write.max_regression).write(&write.out)?;
+            println!("wrote perf baseline {}", write.out.display());
+            return Ok(());
+        }
+        Some(PerfSubcommands::Contention(contention)) => return run_perf_contention(contention),
+        None => {}
+    }
+    let mut report = run_perf_harness(args.iterations)?;
+    if let Some(path) = &args.baseline {
+        let baseline = PerfBaseline::read(path)?;
+        let max_regression = args.max_regression.unwrap_or(baseline.max_regression_pct);
+        report.compare_to_baseline(&baseline, max_regression);
+    }
     if args.json {
         print_json(&report);
     } else {
         println!("perf iterations={}", report.iterations);
         for m in &report.measurements {
+            let against_baseline = match (m.baseline_ms, m.regression_pct, m.within_baseline) {
+                (Some(baseline), Some(pct), Some(within)) => {
+                    format!(" baseline_ms={baseline:.2} change={pct:+.1}% within_baseline={within}")
+                }
+                _ => String::new(),
+            };
             println!(
-                "  {} elapsed_ms={:.2} budget_ms={:.2} within_budget={}",
+                "  {} elapsed_ms={:.2} budget_ms={:.2} within_budget={}{against_baseline}",
                 m.name, m.elapsed_ms, m.budget_ms, m.within_budget
             );
         }
     }
-    if args.strict && report.over_budget_count() > 0 {
-        return Err(app::AppError::InvalidArgument(format!(
+    if !args.strict {
+        return Ok(());
+    }
+    match report.max_regression_pct {
+        Some(pct) if report.regression_count() > 0 => Err(app::AppError::InvalidArgument(format!(
+            "perf regression: {} measurement(s) more than {pct}% slower than the baseline",
+            report.regression_count()
+        ))),
+        None if report.over_budget_count() > 0 => Err(app::AppError::InvalidArgument(format!(
             "perf regression: {} measurement(s) over budget",
             report.over_budget_count()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn run_perf_contention(args: &PerfContentionArgs) -> Result<(), app::AppError> {
+    let report = run_contention(
+        args.writers,
+        args.ops,
+        Duration::from_secs(args.stall_timeout.max(1)),
+        args.pool,
+    )?;
+    if args.json {
+        print_json(&report);
+    } else {
+        println!(
+            "perf contention writers={} ops_per_writer={} elapsed_ms={:.2}",
+            report.writers, report.ops_per_writer, report.elapsed_ms
+        );
+        if let Some(size) = report.pool_size {
+            println!("  pool size={size}");
+        }
+        println!(
+            "  ops completed={} lock_timeouts={} errors={} stalled_writers={}",
+            report.completed_ops, report.lock_timeouts, report.errors, report.stalled_writers
+        );
+        println!(
+            "  lock_wait acquisitions={} p50_ms={:.2} p95_ms={:.2} max_ms={:.2}",
+            report.lock_acquisitions,
+            report.lock_wait_p50_ms,
+            report.lock_wait_p95_ms,
+            report.lock_wait_max_ms
+        );
+        if let Some(err) = &report.first_error {
+            println!("  first_error {err}");
+        }
+    }
+    if args.strict && !report.is_clean() {
+        return Err(app::AppError::InvalidArgument(format!(
+            "perf contention: {} lock timeout(s), {} error(s), {} stalled writer(s)",
+            report.lock_timeouts, report.errors, report.stalled_writers
         )));
     }
     Ok(())
@@ -308,47 +319,6 @@ pub fn run_compact(app: &app::App, args: CompactArgs) -> Result<(), app::AppErro
     Ok(())
 }
 
-pub fn run_cold(app: &app::App, args: crate::cli::ColdArgs) -> Result<(), app::AppError> {
-    match args.command {
-        ColdSubcommands::Sync(sync_args) => {
-            let summary = crate::trace::measure("cold_sync", || app.cold_sync())?;
-            if sync_args.json {
-                print_json(&summary);
-            } else {
-                println!(
-                    concat!(
-                        "cold sync head={} index_files={} full_files={} ",
-                        "knot_updates={} edge_adds={} edge_removes={}"
-                    ),
-                    summary.target_head,
-                    summary.index_files,
-                    summary.full_files,
-                    summary.knot_updates,
-                    summary.edge_adds,
-                    summary.edge_removes
-                );
-            }
-        }
-        ColdSubcommands::Search(search_args) => {
-            let matches =
-                crate::trace::measure("cold_search", || app.cold_search(&search_args.term))?;
-            if search_args.json {
-                print_json(&matches);
-            } else if matches.is_empty() {
-                println!("no cold knots matched '{}'", search_args.term);
-            } else {
-                for knot in matches {
-                    println!(
-                        "{} [{}] {} ({})",
-                        knot.id, knot.state, knot.title, knot.updated_at
-                    );
-                }
-            }
-        }
-    }
-    Ok(())
-}
-
 pub fn run_rehydrate(app: &app::App, args: crate::cli::RehydrateArgs) -> Result<(), app::AppError> {
     match crate::trace::measure("rehydrate", || app.rehydrate(&args.id))? {
         Some(knot) => {
@@ -381,7 +351,13 @@ pub fn run_edge_list(
         println!("no edges for {}", edge_args.id);
     } else {
         for edge in edges {
-            println!("{} -[{}]-> {}", edge.src, edge.kind, edge.dst);
+            match app.resolve_remote_knot(&edge.dst) {
+                Some(remote) => println!(
+                    "{} -[{}]-> {} [{}] {}",
+                    edge.src, edge.kind, edge.dst, remote.state, remote.title
+                ),
+                None => println!("{} -[{}]-> {}", edge.src, edge.kind, edge.dst),
+            }
         }
     }
     Ok(())
@@ -405,9 +381,10 @@ fn resolve_skill_for_knot(
     let profile = app
         .profile_registry()
         .require(&dispatch::profile_lookup_id(knot))?;
-    action_prompt::render_for_profile(profile, &next).ok_or_else(|| {
+    let skill = action_prompt::render_for_profile(profile, &next).ok_or_else(|| {
         app::AppError::InvalidArgument(format!("'{}' is not a knot id or skill state name", id))
-    })
+    })?;
+    Ok(app.expand_skill(&skill, profile, Some(knot)))
 }
 
 fn resolve_skill_by_name(app: &app::App, id: &str) -> Result<String, app::AppError> {
@@ -423,7 +400,7 @@ fn resolve_skill_by_name(app: &app::App, id: &str) -> Result<String, app::AppErr
     for profile_id in profile_ids {
         let profile = app.profile_registry().require(&profile_id)?;
         if let Some(skill) = action_prompt::render_for_profile(profile, &normalized) {
-            return Ok(skill);
+            return Ok(app.expand_skill(&skill, profile, None));
         }
     }
     Err(app::AppError::InvalidArgument(format!(
@@ -256,9 +256,11 @@ fn show_json_value_hides_lease_id_and_keeps_lease_agent_metadata() {
         body: None,
         description: None,
         acceptance: None,
+        estimate: None,
         priority: None,
         knot_type: crate::domain::knot_type::KnotType::Work,
         tags: Vec::new(),
+        custom_fields: Default::default(),
         notes: Vec::new(),
         handoff_capsules: Vec::new(),
         invariants: Vec::new(),
@@ -280,10 +282,15 @@ fn show_json_value_hides_lease_id_and_keeps_lease_agent_metadata() {
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: None,
+        snoozed_until: None,
         step_metadata: None,
         next_step_metadata: None,
         edges: Vec::new(),
         child_summaries: Vec::new(),
+        git_links: Vec::new(),
+        criteria: Vec::new(),
+        handbacks: Vec::new(),
+        plan: None,
     };
 
     let value = show_json_value(&knot);
@@ -386,8 +393,13 @@ fn run_ls_stream_succeeds_with_knots() {
             knot_type: None,
             profile_id: None,
             tags: Vec::new(),
+            fields: Vec::new(),
             query: None,
             stream: true,
+            columns: None,
+            no_truncate: false,
+            include_sensitive: false,
+            week: None,
             limit: None,
             offset: None,
         },
@@ -419,8 +431,13 @@ fn run_ls_stream_with_limit_caps_output() {
             knot_type: None,
             profile_id: None,
             tags: Vec::new(),
+            fields: Vec::new(),
             query: None,
             stream: true,
+            columns: None,
+            no_truncate: false,
+            include_sensitive: false,
+            week: None,
             limit: Some(1),
             offset: None,
         },
@@ -0,0 +1,377 @@
+//! Verified upgrades: resolve a release on the requested channel, download
+//! its archive alongside the published checksums, and only swap the binary
+//! in once the sha256 matches.
+
+use std::cmp::Ordering;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use sha2::{Digest, Sha256};
+
+use super::{format_titled_fields, previous_paths, SelfUpdateOptions};
+use crate::cli::ReleaseChannel;
+use crate::release_version::{
+    compare_versions, fetch_latest_tag, fetch_releases_json, newest_release_tag, strip_v_prefix,
+};
+
+const DEFAULT_REPO: &str = "acartine/knots";
+const DEFAULT_DOWNLOAD_BASE: &str = "https://github.com";
+const DEFAULT_API_BASE: &str = "https://api.github.com";
+const RESOLVE_TIMEOUT_SECS: u32 = 10;
+
+pub(super) fn install_verified(options: &SelfUpdateOptions) -> io::Result<String> {
+    let repo = options.repo.as_deref().unwrap_or(DEFAULT_REPO);
+    let tag = resolve_release_tag(options.version.as_deref(), options.channel, repo)?;
+    let install_dir = match options.install_dir.clone() {
+        Some(dir) => dir,
+        None => current_install_dir()?,
+    };
+    install_release(&download_base(), repo, &tag, &install_dir)?;
+    Ok(tag)
+}
+
+pub(super) fn check_for_update(channel: ReleaseChannel, repo: Option<&str>) -> io::Result<String> {
+    let repo = repo.unwrap_or(DEFAULT_REPO);
+    let tag = resolve_release_tag(None, channel, repo)?;
+    Ok(format_upgrade_check(
+        env!("CARGO_PKG_VERSION"),
+        channel,
+        &tag,
+    ))
+}
+
+fn format_upgrade_check(current: &str, channel: ReleaseChannel, latest_tag: &str) -> String {
+    let latest = strip_v_prefix(latest_tag);
+    let status = match compare_versions(current, latest) {
+        Some(Ordering::Less) => match channel {
+            ReleaseChannel::Stable => "update available: run `kno upgrade`",
+            ReleaseChannel::Beta => "update available: run `kno upgrade --channel beta`",
+        },
+        Some(_) => "up to date",
+        None => "unable to compare versions",
+    };
+    let fields = [
+        ("current", current.to_string()),
+        ("channel", channel_name(channel).to_string()),
+        ("latest", latest.to_string()),
+        ("status", status.to_string()),
+    ];
+    format_titled_fields("Upgrade check", &fields)
+}
+
+fn channel_name(channel: ReleaseChannel) -> &'static str {
+    match channel {
+        ReleaseChannel::Stable => "stable",
+        ReleaseChannel::Beta => "beta",
+    }
+}
+
+fn resolve_release_tag(
+    version: Option<&str>,
+    channel: ReleaseChannel,
+    repo: &str,
+) -> io::Result<String> {
+    if let Some(version) = version {
+        return Ok(format!("v{}", strip_v_prefix(version)));
+    }
+    let tag = match channel {
+        // Prereleases are never "latest", so the stable channel can keep
+        // using the redirect and stay clear of API rate limits.
+        ReleaseChannel::Stable => fetch_latest_tag(
+            &format!("{}/{repo}/releases/latest", download_base()),
+            RESOLVE_TIMEOUT_SECS,
+        ),
+        ReleaseChannel::Beta => fetch_releases_json(
+            &format!("{}/repos/{repo}/releases", api_base()),
+            RESOLVE_TIMEOUT_SECS,
+        )
+        .and_then(|json| newest_release_tag(&json)),
+    };
+    tag.ok_or_else(|| {
+        io::Error::other(format!(
+            "could not resolve the latest {} release of {repo}",
+            channel_name(channel)
+        ))
+    })
+}
+
+fn download_base() -> String {
+    base_from_env("KNOTS_RELEASE_DOWNLOAD_BASE", DEFAULT_DOWNLOAD_BASE)
+}
+
+fn api_base() -> String {
+    base_from_env("KNOTS_RELEASE_API_BASE", DEFAULT_API_BASE)
+}
+
+fn base_from_env(key: &str, default: &str) -> String {
+    std::env::var(key)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| default.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+fn current_install_dir() -> io::Result<PathBuf> {
+    let exe = std::fs::canonicalize(std::env::current_exe()?)?;
+    exe.parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| io::Error::other("could not determine the install directory"))
+}
+
+fn target_suffix() -> io::Result<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("macos", "aarch64") => Ok("darwin-arm64"),
+        ("linux", "x86_64") => Ok("linux-x86_64"),
+        ("linux", "aarch64") => Ok("linux-aarch64"),
+        (os, arch) => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("no release build for {os}/{arch}; use --script-url or build from source"),
+        )),
+    }
+}
+
+fn install_release(
+    download_base: &str,
+    repo: &str,
+    tag: &str,
+    install_dir: &Path,
+) -> io::Result<PathBuf> {
+    let suffix = target_suffix()?;
+    let asset = format!("knots-{tag}-{suffix}.tar.gz");
+    let checksums = format!("knots-{tag}-checksums.txt");
+    let release_url = format!("{download_base}/{repo}/releases/download/{tag}");
+    let scratch = scratch_dir()?;
+    let result = (|| {
+        download(&format!("{release_url}/{asset}"), &scratch.join(&asset))?;
+        download(
+            &format!("{release_url}/{checksums}"),
+            &scratch.join(&checksums),
+        )?;
+        let published = std::fs::read_to_string(scratch.join(&checksums))?;
+        verify_checksum(&scratch.join(&asset), &asset, &published)?;
+        extract(&scratch.join(&asset), &scratch)?;
+        install_binary(&scratch.join("knots"), install_dir)
+    })();
+    let _ = std::fs::remove_dir_all(&scratch);
+    result
+}
+
+fn scratch_dir() -> io::Result<PathBuf> {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+    let dir = std::env::temp_dir().join(format!("knots-upgrade-{}-{nanos}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn download(url: &str, dest: &Path) -> io::Result<()> {
+    let status = Command::new("curl")
+        .args(["-fsSL", url, "-o"])
+        .arg(dest)
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "download of {url} failed with status {status}"
+        )))
+    }
+}
+
+fn verify_checksum(archive: &Path, name: &str, published: &str) -> io::Result<()> {
+    let expected = published
+        .lines()
+        .find_map(|line| {
+            let mut parts = line.split_whitespace();
+            let sum = parts.next()?;
+            let file = parts.next()?.trim_start_matches('*');
+            (file == name).then_some(sum)
+        })
+        .ok_or_else(|| io::Error::other(format!("checksum entry for {name} was not found")))?;
+    let actual = format!("{:x}", Sha256::digest(std::fs::read(archive)?));
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("checksum verification failed for {name}: expected {expected}, got {actual}"),
+        ))
+    }
+}
+
+fn extract(archive: &Path, dest: &Path) -> io::Result<()> {
+    let status = Command::new("tar")
+        .arg("-xzf")
+        .arg(archive)
+        .arg("-C")
+        .arg(dest)
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "extracting {} failed with status {status}",
+            archive.display()
+        )));
+    }
+    if dest.join("knots").is_file() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "expected 'knots' binary in {}",
+            archive.display()
+        )))
+    }
+}
+
+fn install_binary(extracted: &Path, install_dir: &Path) -> io::Result<PathBuf> {
+    std::fs::create_dir_all(install_dir)?;
+    let destination = install_dir.join("knots");
+    if destination.is_file() {
+        for previous in previous_paths(&destination) {
+            std::fs::copy(&destination, previous)?;
+        }
+    }
+    let staging = install_dir.join("knots.new");
+    std::fs::copy(extracted, &staging)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staging, std::fs::Permissions::from_mode(0o755))?;
+    }
+    std::fs::rename(&staging, &destination)?;
+    #[cfg(unix)]
+    {
+        let alias = install_dir.join("kno");
+        super::remove_file_if_present(&alias)?;
+        std::os::unix::fs::symlink("knots", alias)?;
+    }
+    Ok(destination)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_upgrade_check, install_release, target_suffix, verify_checksum};
+    use crate::cli::ReleaseChannel;
+    use sha2::{Digest, Sha256};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    fn unique_temp_dir(label: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("knots-{label}-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).expect("temp dir should be created");
+        dir
+    }
+
+    fn publish_release(base: &Path, tag: &str, binary: &str, tamper: bool) {
+        let suffix = target_suffix().expect("test platform should have a release build");
+        let release_dir = base.join(format!("acartine/knots/releases/download/{tag}"));
+        let staging = base.join("staging");
+        std::fs::create_dir_all(&release_dir).expect("release dir should be created");
+        std::fs::create_dir_all(&staging).expect("staging dir should be created");
+        std::fs::write(staging.join("knots"), binary).expect("binary fixture should be written");
+        let asset = format!("knots-{tag}-{suffix}.tar.gz");
+        let status = Command::new("tar")
+            .arg("-czf")
+            .arg(release_dir.join(&asset))
+            .arg("-C")
+            .arg(&staging)
+            .arg("knots")
+            .status()
+            .expect("tar should run");
+        assert!(status.success());
+        let bytes = std::fs::read(release_dir.join(&asset)).expect("archive should be readable");
+        let mut sum = format!("{:x}", Sha256::digest(bytes));
+        if tamper {
+            sum = "0".repeat(sum.len());
+        }
+        std::fs::write(
+            release_dir.join(format!("knots-{tag}-checksums.txt")),
+            format!("{sum}  {asset}\n"),
+        )
+        .expect("checksums should be written");
+    }
+
+    #[test]
+    fn install_release_verifies_checksum_and_keeps_previous_binary() {
+        let base = unique_temp_dir("release-fixture");
+        let install_dir = base.join("bin");
+        std::fs::create_dir_all(&install_dir).expect("install dir should be created");
+        std::fs::write(install_dir.join("knots"), "old").expect("old binary should be written");
+        publish_release(&base, "v9.9.9", "new", false);
+
+        let download_base = format!("file://{}", base.display());
+        let installed = install_release(&download_base, "acartine/knots", "v9.9.9", &install_dir)
+            .expect("verified install should succeed");
+
+        assert_eq!(std::fs::read_to_string(installed).unwrap(), "new");
+        assert_eq!(
+            std::fs::read_to_string(install_dir.join("knots.previous")).unwrap(),
+            "old"
+        );
+        assert_eq!(
+            std::fs::read_to_string(install_dir.join("kno.previous")).unwrap(),
+            "old"
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            std::fs::read_link(install_dir.join("kno")).unwrap(),
+            Path::new("knots")
+        );
+        let _ = std::fs::remove_dir_all(base);
+    }
+
+    #[test]
+    fn install_release_refuses_archives_that_fail_verification() {
+        let base = unique_temp_dir("release-tampered");
+        let install_dir = base.join("bin");
+        std::fs::create_dir_all(&install_dir).expect("install dir should be created");
+        std::fs::write(install_dir.join("knots"), "old").expect("old binary should be written");
+        publish_release(&base, "v9.9.9", "evil", true);
+
+        let download_base = format!("file://{}", base.display());
+        let err = install_release(&download_base, "acartine/knots", "v9.9.9", &install_dir)
+            .expect_err("tampered archive should be rejected");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            std::fs::read_to_string(install_dir.join("knots")).unwrap(),
+            "old"
+        );
+        assert!(!install_dir.join("knots.previous").exists());
+
+        let missing = install_release(&download_base, "acartine/knots", "v0.0.1", &install_dir);
+        assert!(missing.is_err());
+        let _ = std::fs::remove_dir_all(base);
+    }
+
+    #[test]
+    fn verify_checksum_requires_an_entry_for_the_asset() {
+        let dir = unique_temp_dir("checksum-entry");
+        let archive = dir.join("knots.tar.gz");
+        std::fs::write(&archive, "payload").expect("archive should be written");
+        let sum = format!("{:x}", Sha256::digest(b"payload"));
+
+        verify_checksum(&archive, "knots.tar.gz", &format!("{sum} *knots.tar.gz\n"))
+            .expect("binary-mode entries should match");
+        let err = verify_checksum(&archive, "knots.tar.gz", &format!("{sum}  other.tar.gz\n"))
+            .expect_err("missing entry should fail");
+        assert!(err.to_string().contains("was not found"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn upgrade_check_reports_status_per_channel() {
+        std::env::set_var("NO_COLOR", "1");
+        let behind = format_upgrade_check("0.4.0", ReleaseChannel::Beta, "v0.5.0-beta.1");
+        let current = format_upgrade_check("0.4.0", ReleaseChannel::Stable, "v0.4.0");
+        std::env::remove_var("NO_COLOR");
+
+        assert!(behind.starts_with("Upgrade check"));
+        assert!(behind.contains("channel:  beta"));
+        assert!(behind.contains("latest:  0.5.0-beta.1"));
+        assert!(behind.contains("kno upgrade --channel beta"));
+        assert!(current.contains("status:  up to date"));
+    }
+}
@@ -0,0 +1,156 @@
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::http_json;
+
+/// Environment variable holding the bearer token for the endpoint, kept
+/// out of the config so it never reaches the knots branch.
+pub const API_KEY_ENV: &str = "KNOTS_EMBEDDINGS_API_KEY";
+
+/// Turns texts into vectors, one per text and in the same order.
+pub trait EmbeddingProvider {
+    /// Names the model, so switching models re-embeds every knot.
+    fn model(&self) -> &str;
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String>;
+}
+
+/// An OpenAI-style `/v1/embeddings` endpoint (OpenAI, Ollama, llama.cpp,
+/// vLLM, ...), called through `curl`.
+#[derive(Debug, Clone)]
+pub struct HttpEmbeddings {
+    pub endpoint: String,
+    pub model: String,
+    pub api_key: Option<String>,
+}
+
+impl EmbeddingProvider for HttpEmbeddings {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let body = json!({ "model": self.model, "input": texts }).to_string();
+        let response = http_json::post_json(&self.endpoint, self.api_key.as_deref(), &body)
+            .map_err(|err| format!("embedding {err}"))?;
+        let vectors = parse_response(&response)?;
+        if vectors.len() != texts.len() {
+            return Err(format!(
+                "embedding endpoint returned {} vectors for {} texts",
+                vectors.len(),
+                texts.len()
+            ));
+        }
+        Ok(vectors)
+    }
+}
+
+/// Reads `{"data": [{"index", "embedding"}]}`, or Ollama's native
+/// `{"embeddings": [[...]]}`.
+fn parse_response(body: &str) -> Result<Vec<Vec<f32>>, String> {
+    let value: Value =
+        serde_json::from_str(body).map_err(|err| format!("invalid embedding response: {err}"))?;
+    let to_vector = |item: &Value| -> Option<Vec<f32>> {
+        item.as_array()?
+            .iter()
+            .map(|number| number.as_f64().map(|number| number as f32))
+            .collect()
+    };
+    if let Some(data) = value.get("data").and_then(Value::as_array) {
+        let mut indexed = data
+            .iter()
+            .enumerate()
+            .map(|(position, item)| {
+                let index = item.get("index").and_then(Value::as_u64);
+                let vector = item.get("embedding").and_then(to_vector);
+                vector.map(|vector| (index.unwrap_or(position as u64), vector))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or("embedding response has an entry without a numeric embedding")?;
+        indexed.sort_by_key(|(index, _)| *index);
+        return Ok(indexed.into_iter().map(|(_, vector)| vector).collect());
+    }
+    if let Some(embeddings) = value.get("embeddings").and_then(Value::as_array) {
+        return embeddings
+            .iter()
+            .map(to_vector)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| "embedding response has a non-numeric vector".to_string());
+    }
+    Err("embedding response has neither `data` nor `embeddings`".to_string())
+}
+
+/// Hash of what a vector was made from; a changed model or text misses.
+pub fn content_hash(model: &str, text: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(model.as_bytes());
+    hasher.update([0]);
+    hasher.update(text.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+pub fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+pub fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+/// Cosine similarity; vectors of different lengths (another model) or
+/// zero length score 0.
+pub fn cosine(left: &[f32], right: &[f32]) -> f64 {
+    if left.len() != right.len() || left.is_empty() {
+        return 0.0;
+    }
+    let (mut dot, mut left_norm, mut right_norm) = (0.0f64, 0.0f64, 0.0f64);
+    for (a, b) in left.iter().zip(right) {
+        let (a, b) = (f64::from(*a), f64::from(*b));
+        dot += a * b;
+        left_norm += a * a;
+        right_norm += b * b;
+    }
+    if left_norm == 0.0 || right_norm == 0.0 {
+        return 0.0;
+    }
+    dot / (left_norm.sqrt() * right_norm.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cosine, decode_vector, encode_vector, parse_response};
+
+    #[test]
+    fn parses_openai_and_ollama_responses_in_input_order() {
+        let openai = r#"{"data":[{"index":1,"embedding":[0.5,1]},{"index":0,"embedding":[1,0]}]}"#;
+        assert_eq!(
+            parse_response(openai).expect("openai"),
+            vec![vec![1.0, 0.0], vec![0.5, 1.0]]
+        );
+        let ollama = r#"{"embeddings":[[0.25,0.75]]}"#;
+        assert_eq!(
+            parse_response(ollama).expect("ollama"),
+            vec![vec![0.25, 0.75]]
+        );
+        assert!(parse_response(r#"{"error":"no model"}"#).is_err());
+        assert!(parse_response(r#"{"data":[{"embedding":"x"}]}"#).is_err());
+    }
+
+    #[test]
+    fn vectors_round_trip_and_compare_by_angle() {
+        let vector = vec![0.5f32, -1.25, 3.0];
+        assert_eq!(decode_vector(&encode_vector(&vector)), vector);
+        assert!((cosine(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-9);
+        assert!(cosine(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-9);
+        assert_eq!(cosine(&[1.0], &[1.0, 0.0]), 0.0);
+    }
+}
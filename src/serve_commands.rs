@@ -0,0 +1,67 @@
+use crate::app::AppError;
+use crate::cli::{ServeArgs, ServeAuditArgs, ServeSubcommands};
+use crate::db::{self, ServerAccessRecord};
+use crate::print_json;
+use crate::replication;
+
+pub fn run_serve(args: &ServeArgs) -> Result<(), AppError> {
+    if let Some(ServeSubcommands::Audit(audit)) = &args.command {
+        return run_serve_audit(audit);
+    }
+    if !args.replication {
+        return Err(AppError::InvalidArgument(
+            "kno serve needs --replication, --ui, or --graphql".to_string(),
+        ));
+    }
+    let Some(root) = args.root.as_deref() else {
+        return Err(AppError::InvalidArgument(
+            "kno serve --replication needs --root".to_string(),
+        ));
+    };
+    Ok(replication::serve_replication_stdio(
+        root,
+        args.config.as_deref(),
+    )?)
+}
+
+fn run_serve_audit(args: &ServeAuditArgs) -> Result<(), AppError> {
+    let path = replication::server_audit_db_path(&args.root);
+    if !path.exists() {
+        return Err(AppError::InvalidArgument(format!(
+            "no access log at {}; has this server answered a request yet?",
+            path.display()
+        )));
+    }
+    let conn = db::open_connection(&path.to_string_lossy())?;
+    let records = db::list_server_access(&conn, args.token.as_deref(), args.limit)?;
+    if args.json {
+        print_json(&records);
+    } else {
+        print!("{}", render_server_access(&records));
+    }
+    Ok(())
+}
+
+fn render_server_access(records: &[ServerAccessRecord]) -> String {
+    if records.is_empty() {
+        return "no requests logged\n".to_string();
+    }
+    records
+        .iter()
+        .map(|record| {
+            let request = format!("{} {}", record.verb, record.key);
+            let mut line = format!(
+                "{} {} {} {}",
+                record.created_at,
+                record.token_name,
+                request.trim_end(),
+                record.outcome
+            );
+            if let Some(message) = &record.message {
+                line.push_str(&format!(": {message}"));
+            }
+            line.push('\n');
+            line
+        })
+        .collect()
+}
@@ -8,9 +8,15 @@ use time::OffsetDateTime;
 
 use crate::db::{self, ColdCatalogRecord, KnotCacheRecord, UpsertKnotHot, WarmKnotRecord};
 
+mod read;
+
+pub use read::read_latest_snapshot;
+
 const SNAPSHOT_SCHEMA_VERSION: i64 = 1;
 const ACTIVE_SUFFIX: &str = "-active_catalog.snapshot.json";
 const COLD_SUFFIX: &str = "-cold_catalog.snapshot.json";
+/// Meta key holding when this clone last wrote snapshots (RFC 3339).
+pub const LAST_WRITTEN_META: &str = "snapshot_last_written";
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub struct SnapshotWriteSummary {
@@ -102,9 +108,14 @@ pub fn write_snapshots_at_store(
     conn: &Connection,
     store_root: &Path,
 ) -> Result<SnapshotWriteSummary, SnapshotError> {
-    let hot = db::list_knot_hot(conn)?;
-    let warm = db::list_knot_warm(conn)?;
-    let cold = db::list_cold_catalog(conn)?;
+    // Snapshots are published, so private knots stay out of them.
+    let private = db::list_private_knots(conn)?;
+    let mut hot = db::list_knot_hot(conn)?;
+    hot.retain(|knot| !private.contains(&knot.id));
+    let mut warm = db::list_knot_warm(conn)?;
+    warm.retain(|knot| !private.contains(&knot.id));
+    let mut cold = db::list_cold_catalog(conn)?;
+    cold.retain(|knot| !private.contains(&knot.id));
 
     let written_at = current_rfc3339();
     let stamp = filename_timestamp();
@@ -126,8 +137,14 @@ pub fn write_snapshots_at_store(
     let active_path = snapshots_dir.join(format!("{stamp}{ACTIVE_SUFFIX}"));
     let cold_path = snapshots_dir.join(format!("{stamp}{COLD_SUFFIX}"));
 
-    std::fs::write(&active_path, serde_json::to_vec_pretty(&active)?)?;
-    std::fs::write(&cold_path, serde_json::to_vec_pretty(&cold_snapshot)?)?;
+    let compress = db::get_compression(conn)? == "zstd";
+    write_snapshot_file(&active_path, &serde_json::to_vec_pretty(&active)?, compress)?;
+    write_snapshot_file(
+        &cold_path,
+        &serde_json::to_vec_pretty(&cold_snapshot)?,
+        compress,
+    )?;
+    db::set_meta(conn, LAST_WRITTEN_META, &active.written_at)?;
 
     Ok(SnapshotWriteSummary {
         active_path,
@@ -138,6 +155,53 @@ pub fn write_snapshots_at_store(
     })
 }
 
+/// Snapshot names keep the `.json` suffix either way; readers detect
+/// compression from the zstd magic bytes.
+fn write_snapshot_file(path: &Path, json: &[u8], compress: bool) -> std::io::Result<()> {
+    if compress {
+        std::fs::write(path, crate::compression::encode(json)?)
+    } else {
+        std::fs::write(path, json)
+    }
+}
+
+/// Replaces each literal `patterns` match in every snapshot under
+/// `store_roots`, keeping each file's compression, and returns the files
+/// that changed. Snapshots hold projected knots, so one written before a
+/// redaction still carries the secret.
+pub fn redact_snapshot_files(
+    store_roots: &[PathBuf],
+    patterns: &[String],
+    replacement: &str,
+) -> Result<Vec<PathBuf>, SnapshotError> {
+    let mut changed = Vec::new();
+    for dir in store_roots.iter().map(|root| root.join("snapshots")) {
+        if !dir.is_dir() {
+            continue;
+        }
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+            let raw = std::fs::read(&path)?;
+            let Ok(mut snapshot) =
+                serde_json::from_slice::<serde_json::Value>(&crate::compression::decode(&raw)?)
+            else {
+                continue;
+            };
+            if crate::events::redact_value(&mut snapshot, patterns, replacement) == 0 {
+                continue;
+            }
+            let json = serde_json::to_vec_pretty(&snapshot)?;
+            write_snapshot_file(&path, &json, crate::compression::is_zstd(&raw))?;
+            changed.push(path);
+        }
+    }
+    changed.sort();
+    Ok(changed)
+}
+
 pub fn apply_latest_snapshots(
     conn: &Connection,
     repo_root: &Path,
@@ -167,7 +231,7 @@ pub fn apply_latest_snapshots_at_store(
     let mut cold_count = 0u64;
 
     if let Some(path) = active_path.as_ref() {
-        let payload = std::fs::read(path)?;
+        let payload = crate::compression::read(path)?;
         let snapshot: ActiveCatalogSnapshot = serde_json::from_slice(&payload)?;
         for record in &snapshot.hot {
             db::upsert_knot_hot(
@@ -180,6 +244,8 @@ pub fn apply_latest_snapshots_at_store(
                     body: record.body.as_deref(),
                     description: record.description.as_deref(),
                     acceptance: record.acceptance.as_deref(),
+                    estimate: record.estimate.as_deref(),
+                    custom_fields: &record.custom_fields,
                     priority: record.priority,
                     knot_type: record.knot_type.as_deref(),
                     tags: &record.tags,
@@ -196,6 +262,7 @@ pub fn apply_latest_snapshots_at_store(
                     deferred_from_state: record.deferred_from_state.as_deref(),
                     blocked_from_state: record.blocked_from_state.as_deref(),
                     created_at: record.created_at.as_deref(),
+                    snoozed_until: record.snoozed_until.as_deref(),
                 },
             )?;
             hot_count += 1;
@@ -208,7 +275,7 @@ pub fn apply_latest_snapshots_at_store(
     }
 
     if let Some(path) = cold_path.as_ref() {
-        let payload = std::fs::read(path)?;
+        let payload = crate::compression::read(path)?;
         let snapshot: ColdCatalogSnapshot = serde_json::from_slice(&payload)?;
         for record in &snapshot.cold {
             db::upsert_cold_catalog(
@@ -269,174 +336,4 @@ fn filename_timestamp() -> String {
 }
 
 #[cfg(test)]
-mod tests {
-    use std::error::Error;
-    use std::path::PathBuf;
-
-    use uuid::Uuid;
-
-    use super::{apply_latest_snapshots, latest_snapshot_path, write_snapshots, SnapshotError};
-    use crate::db::{self, UpsertKnotHot};
-
-    fn unique_workspace() -> PathBuf {
-        let root = std::env::temp_dir().join(format!("knots-snapshot-test-{}", Uuid::now_v7()));
-        std::fs::create_dir_all(&root).expect("workspace should be creatable");
-        root
-    }
-
-    #[test]
-    fn writes_and_loads_snapshots() {
-        let root = unique_workspace();
-        let db_path = root.join(".knots/cache/state.sqlite");
-        std::fs::create_dir_all(
-            db_path
-                .parent()
-                .expect("db parent should exist for snapshot test"),
-        )
-        .expect("db parent should be creatable");
-
-        let conn = db::open_connection(db_path.to_str().expect("utf8 path"))
-            .expect("snapshot db should open");
-        db::upsert_knot_hot(
-            &conn,
-            &UpsertKnotHot {
-                id: "K-hot",
-                title: "Hot",
-                state: "work_item",
-                updated_at: "2026-02-24T10:00:00Z",
-                body: Some("hot body"),
-                description: Some("hot body"),
-                acceptance: None,
-                priority: Some(1),
-                knot_type: Some("task"),
-                tags: &["ops".to_string()],
-                notes: &[],
-                handoff_capsules: &[],
-                invariants: &[],
-                step_history: &[],
-                gate_data: &crate::domain::gate::GateData::default(),
-                lease_data: &crate::domain::lease::LeaseData::default(),
-                lease_id: None,
-                workflow_id: "work_sdlc",
-                profile_id: "default",
-                profile_etag: Some("evt-1"),
-                deferred_from_state: None,
-                blocked_from_state: None,
-                created_at: Some("2026-02-24T10:00:00Z"),
-            },
-        )
-        .expect("hot upsert should succeed");
-        db::upsert_knot_warm(&conn, "K-warm", "Warm").expect("warm upsert should succeed");
-        db::upsert_cold_catalog(&conn, "K-cold", "Cold", "shipped", "2026-02-24T10:01:00Z")
-            .expect("cold upsert should succeed");
-
-        let written = write_snapshots(&conn, &root).expect("snapshot write should succeed");
-        assert!(written.active_path.exists());
-        assert!(written.cold_path.exists());
-
-        let root2 = unique_workspace();
-        let db2_path = root2.join(".knots/cache/state.sqlite");
-        std::fs::create_dir_all(
-            db2_path
-                .parent()
-                .expect("db parent should exist for restore test"),
-        )
-        .expect("restore db parent should be creatable");
-        let conn2 = db::open_connection(db2_path.to_str().expect("utf8 path"))
-            .expect("restore db should open");
-
-        let snapshots_target = root2.join(".knots/snapshots");
-        std::fs::create_dir_all(&snapshots_target).expect("snapshot target should exist");
-        std::fs::copy(
-            &written.active_path,
-            snapshots_target.join(
-                written
-                    .active_path
-                    .file_name()
-                    .expect("active filename should exist"),
-            ),
-        )
-        .expect("active snapshot should copy");
-        std::fs::copy(
-            &written.cold_path,
-            snapshots_target.join(
-                written
-                    .cold_path
-                    .file_name()
-                    .expect("cold filename should exist"),
-            ),
-        )
-        .expect("cold snapshot should copy");
-
-        let loaded = apply_latest_snapshots(&conn2, &root2).expect("snapshot load should succeed");
-        assert_eq!(loaded.hot_count, 1);
-        assert_eq!(loaded.warm_count, 1);
-        assert_eq!(loaded.cold_count, 1);
-
-        let hot = db::get_knot_hot(&conn2, "K-hot")
-            .expect("hot query should succeed")
-            .expect("hot knot should exist");
-        assert_eq!(hot.title, "Hot");
-
-        let _ = std::fs::remove_dir_all(root);
-        let _ = std::fs::remove_dir_all(root2);
-    }
-
-    #[test]
-    fn snapshot_error_display_source_and_from_cover_variants() {
-        let io: SnapshotError = std::io::Error::other("disk").into();
-        assert!(io.to_string().contains("I/O error"));
-        assert!(io.source().is_some());
-
-        let db: SnapshotError = rusqlite::Error::InvalidQuery.into();
-        assert!(db.to_string().contains("database error"));
-        assert!(db.source().is_some());
-
-        let json_err = serde_json::from_slice::<serde_json::Value>(b"{")
-            .expect_err("invalid json should fail");
-        let json: SnapshotError = json_err.into();
-        assert!(json.to_string().contains("JSON error"));
-        assert!(json.source().is_some());
-    }
-
-    #[test]
-    fn latest_snapshot_path_skips_directories_and_invalid_filenames() {
-        let root = unique_workspace();
-        let snapshots = root.join(".knots/snapshots");
-        std::fs::create_dir_all(&snapshots).expect("snapshots directory should be creatable");
-
-        std::fs::create_dir_all(snapshots.join("20260225T100000Z-active_catalog.snapshot.json"))
-            .expect("directory fixture should be creatable");
-        std::fs::write(
-            snapshots.join("20260225T100001Z-active_catalog.snapshot.json"),
-            b"{}",
-        )
-        .expect("older snapshot should write");
-        std::fs::write(
-            snapshots.join("20260225T100002Z-active_catalog.snapshot.json"),
-            b"{}",
-        )
-        .expect("latest snapshot should write");
-
-        #[cfg(unix)]
-        {
-            use std::ffi::OsString;
-            use std::os::unix::ffi::OsStringExt;
-            let mut bytes = b"invalid-utf8-".to_vec();
-            bytes.push(0xFF);
-            bytes.extend_from_slice(b"-active_catalog.snapshot.json");
-            let non_utf8 = OsString::from_vec(bytes);
-            let _ = std::fs::write(snapshots.join(non_utf8), b"{}");
-        }
-
-        let latest = latest_snapshot_path(&snapshots, "-active_catalog.snapshot.json")
-            .expect("latest snapshot lookup should succeed")
-            .expect("latest snapshot should exist");
-        assert!(latest
-            .file_name()
-            .and_then(|value| value.to_str())
-            .is_some_and(|name| name.starts_with("20260225T100002Z")));
-
-        let _ = std::fs::remove_dir_all(root);
-    }
-}
+mod tests;
@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use crate::db::{ColdCatalogRecord, KnotCacheRecord, WarmKnotRecord};
+
+use super::{
+    latest_snapshot_path, ActiveCatalogSnapshot, ColdCatalogSnapshot, SnapshotError, ACTIVE_SUFFIX,
+    COLD_SUFFIX,
+};
+
+/// The newest active snapshot and the cold snapshot written alongside it.
+#[derive(Debug, Clone)]
+pub struct SnapshotContents {
+    pub active_path: PathBuf,
+    pub cold_path: Option<PathBuf>,
+    pub written_at: String,
+    pub hot: Vec<KnotCacheRecord>,
+    pub warm: Vec<WarmKnotRecord>,
+    pub cold: Vec<ColdCatalogRecord>,
+}
+
+/// Reads the newest snapshot pair found in any of `dirs`, comparing file
+/// stamps so a local snapshot and one pulled into the worktree rank fairly.
+pub fn read_latest_snapshot(dirs: &[PathBuf]) -> Result<Option<SnapshotContents>, SnapshotError> {
+    let mut newest: Option<PathBuf> = None;
+    for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+        if let Some(path) = latest_snapshot_path(dir, ACTIVE_SUFFIX)? {
+            if newest
+                .as_ref()
+                .is_none_or(|current| current.file_name() < path.file_name())
+            {
+                newest = Some(path);
+            }
+        }
+    }
+    let Some(active_path) = newest else {
+        return Ok(None);
+    };
+    let active: ActiveCatalogSnapshot =
+        serde_json::from_slice(&crate::compression::read(&active_path)?)?;
+
+    let name = active_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let cold_path = active_path.with_file_name(name.replace(ACTIVE_SUFFIX, COLD_SUFFIX));
+    let (cold_path, cold) = if cold_path.is_file() {
+        let snapshot: ColdCatalogSnapshot =
+            serde_json::from_slice(&crate::compression::read(&cold_path)?)?;
+        (Some(cold_path), snapshot.cold)
+    } else {
+        (None, Vec::new())
+    };
+
+    Ok(Some(SnapshotContents {
+        active_path,
+        cold_path,
+        written_at: active.written_at,
+        hot: active.hot,
+        warm: active.warm,
+        cold,
+    }))
+}
@@ -0,0 +1,196 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+use super::{apply_latest_snapshots, latest_snapshot_path, write_snapshots, SnapshotError};
+use crate::db::{self, UpsertKnotHot};
+
+fn unique_workspace() -> PathBuf {
+    let root = std::env::temp_dir().join(format!("knots-snapshot-test-{}", Uuid::now_v7()));
+    std::fs::create_dir_all(&root).expect("workspace should be creatable");
+    root
+}
+
+#[test]
+fn writes_and_loads_snapshots() {
+    let root = unique_workspace();
+    let db_path = root.join(".knots/cache/state.sqlite");
+    std::fs::create_dir_all(
+        db_path
+            .parent()
+            .expect("db parent should exist for snapshot test"),
+    )
+    .expect("db parent should be creatable");
+
+    let conn =
+        db::open_connection(db_path.to_str().expect("utf8 path")).expect("snapshot db should open");
+    db::upsert_knot_hot(
+        &conn,
+        &UpsertKnotHot {
+            id: "K-hot",
+            title: "Hot",
+            state: "work_item",
+            updated_at: "2026-02-24T10:00:00Z",
+            body: Some("hot body"),
+            description: Some("hot body"),
+            acceptance: None,
+            estimate: None,
+            priority: Some(1),
+            knot_type: Some("task"),
+            tags: &["ops".to_string()],
+            custom_fields: &Default::default(),
+            notes: &[],
+            handoff_capsules: &[],
+            invariants: &[],
+            step_history: &[],
+            gate_data: &crate::domain::gate::GateData::default(),
+            lease_data: &crate::domain::lease::LeaseData::default(),
+            lease_id: None,
+            workflow_id: "work_sdlc",
+            profile_id: "default",
+            profile_etag: Some("evt-1"),
+            deferred_from_state: None,
+            blocked_from_state: None,
+            created_at: Some("2026-02-24T10:00:00Z"),
+            snoozed_until: None,
+        },
+    )
+    .expect("hot upsert should succeed");
+    db::upsert_knot_warm(&conn, "K-warm", "Warm").expect("warm upsert should succeed");
+    db::upsert_cold_catalog(&conn, "K-cold", "Cold", "shipped", "2026-02-24T10:01:00Z")
+        .expect("cold upsert should succeed");
+
+    let written = write_snapshots(&conn, &root).expect("snapshot write should succeed");
+    assert!(written.active_path.exists());
+    assert!(written.cold_path.exists());
+
+    let root2 = unique_workspace();
+    let db2_path = root2.join(".knots/cache/state.sqlite");
+    std::fs::create_dir_all(
+        db2_path
+            .parent()
+            .expect("db parent should exist for restore test"),
+    )
+    .expect("restore db parent should be creatable");
+    let conn2 =
+        db::open_connection(db2_path.to_str().expect("utf8 path")).expect("restore db should open");
+
+    let snapshots_target = root2.join(".knots/snapshots");
+    std::fs::create_dir_all(&snapshots_target).expect("snapshot target should exist");
+    std::fs::copy(
+        &written.active_path,
+        snapshots_target.join(
+            written
+                .active_path
+                .file_name()
+                .expect("active filename should exist"),
+        ),
+    )
+    .expect("active snapshot should copy");
+    std::fs::copy(
+        &written.cold_path,
+        snapshots_target.join(
+            written
+                .cold_path
+                .file_name()
+                .expect("cold filename should exist"),
+        ),
+    )
+    .expect("cold snapshot should copy");
+
+    let loaded = apply_latest_snapshots(&conn2, &root2).expect("snapshot load should succeed");
+    assert_eq!(loaded.hot_count, 1);
+    assert_eq!(loaded.warm_count, 1);
+    assert_eq!(loaded.cold_count, 1);
+
+    let hot = db::get_knot_hot(&conn2, "K-hot")
+        .expect("hot query should succeed")
+        .expect("hot knot should exist");
+    assert_eq!(hot.title, "Hot");
+
+    let _ = std::fs::remove_dir_all(root);
+    let _ = std::fs::remove_dir_all(root2);
+}
+
+#[test]
+fn compressed_snapshots_round_trip_when_enabled() {
+    let root = unique_workspace();
+    let db_path = root.join(".knots/cache/state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let conn =
+        db::open_connection(db_path.to_str().expect("utf8 path")).expect("snapshot db should open");
+    db::set_meta(&conn, "compression", "zstd").expect("compression meta should set");
+    db::upsert_knot_warm(&conn, "K-warm", "Warm").expect("warm upsert should succeed");
+    db::upsert_cold_catalog(&conn, "K-cold", "Cold", "shipped", "2026-02-24T10:01:00Z")
+        .expect("cold upsert should succeed");
+
+    let written = write_snapshots(&conn, &root).expect("snapshot write should succeed");
+    let raw = std::fs::read(&written.active_path).expect("active snapshot should read");
+    assert!(crate::compression::is_zstd(&raw));
+
+    let loaded = apply_latest_snapshots(&conn, &root).expect("snapshot load should succeed");
+    assert_eq!(loaded.warm_count, 1);
+    assert_eq!(loaded.cold_count, 1);
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn snapshot_error_display_source_and_from_cover_variants() {
+    let io: SnapshotError = std::io::Error::other("disk").into();
+    assert!(io.to_string().contains("I/O error"));
+    assert!(io.source().is_some());
+
+    let db: SnapshotError = rusqlite::Error::InvalidQuery.into();
+    assert!(db.to_string().contains("database error"));
+    assert!(db.source().is_some());
+
+    let json_err =
+        serde_json::from_slice::<serde_json::Value>(b"{").expect_err("invalid json should fail");
+    let json: SnapshotError = json_err.into();
+    assert!(json.to_string().contains("JSON error"));
+    assert!(json.source().is_some());
+}
+
+#[test]
+fn latest_snapshot_path_skips_directories_and_invalid_filenames() {
+    let root = unique_workspace();
+    let snapshots = root.join(".knots/snapshots");
+    std::fs::create_dir_all(&snapshots).expect("snapshots directory should be creatable");
+
+    std::fs::create_dir_all(snapshots.join("20260225T100000Z-active_catalog.snapshot.json"))
+        .expect("directory fixture should be creatable");
+    std::fs::write(
+        snapshots.join("20260225T100001Z-active_catalog.snapshot.json"),
+        b"{}",
+    )
+    .expect("older snapshot should write");
+    std::fs::write(
+        snapshots.join("20260225T100002Z-active_catalog.snapshot.json"),
+        b"{}",
+    )
+    .expect("latest snapshot should write");
+
+    #[cfg(unix)]
+    {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+        let mut bytes = b"invalid-utf8-".to_vec();
+        bytes.push(0xFF);
+        bytes.extend_from_slice(b"-active_catalog.snapshot.json");
+        let non_utf8 = OsString::from_vec(bytes);
+        let _ = std::fs::write(snapshots.join(non_utf8), b"{}");
+    }
+
+    let latest = latest_snapshot_path(&snapshots, "-active_catalog.snapshot.json")
+        .expect("latest snapshot lookup should succeed")
+        .expect("latest snapshot should exist");
+    assert!(latest
+        .file_name()
+        .and_then(|value| value.to_str())
+        .is_some_and(|name| name.starts_with("20260225T100002Z")));
+
+    let _ = std::fs::remove_dir_all(root);
+}
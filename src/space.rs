@@ -0,0 +1,178 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::project::{DistributionMode, ProjectContext, StorePaths};
+use crate::repo_config::{self, RepoConfig};
+
+/// Name that always targets the repository's main store.
+pub const ROOT_SPACE: &str = "root";
+const SPACES_DIR: &str = "spaces";
+
+/// One knot space: a separate backlog kept in its own store under
+/// `.knots/spaces/<name>/` and published on its own branch.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SpaceEntry {
+    pub name: String,
+    pub branch: String,
+    /// Whether this clone has the space's cache set up.
+    pub local: bool,
+    pub default: bool,
+}
+
+/// Space names become directory and branch suffixes, so they stay to
+/// lowercase letters, digits, `-`, and `_`.
+pub fn validate_space_name(raw: &str) -> Result<String, String> {
+    let name = raw.trim();
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name.starts_with(|ch: char| ch.is_ascii_lowercase() || ch.is_ascii_digit())
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-' || ch == '_');
+    if !valid {
+        return Err(format!(
+            "invalid space '{name}': use lowercase letters, digits, '-', or '_'"
+        ));
+    }
+    Ok(name.to_string())
+}
+
+pub fn space_store_paths(root_store: &Path, name: &str) -> StorePaths {
+    StorePaths {
+        root: root_store.join(SPACES_DIR).join(name),
+    }
+}
+
+/// The branch a space publishes to: the repo's knots branch plus `-<name>`.
+pub fn space_branch(root_store: &Path, name: &str) -> String {
+    format!("{}-{name}", repo_config::knots_branch(root_store))
+}
+
+/// The space named by `.knots/config.toml`, if any.
+pub fn default_space(root_store: &Path) -> Option<String> {
+    read_config(root_store)
+        .ok()
+        .and_then(|config| config.default_space)
+        .filter(|name| !name.trim().is_empty())
+}
+
+/// Registered spaces plus any set up only in this clone, sorted by name.
+pub fn list_spaces(root_store: &Path) -> Result<Vec<SpaceEntry>, String> {
+    let config = read_config(root_store)?;
+    let mut names = config.spaces.clone();
+    let dir = root_store.join(SPACES_DIR);
+    if dir.is_dir() {
+        for entry in std::fs::read_dir(&dir).map_err(|err| err.to_string())? {
+            let entry = entry.map_err(|err| err.to_string())?;
+            if entry.path().is_dir() {
+                names.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+    }
+    names.sort();
+    names.dedup();
+    Ok(names
+        .into_iter()
+        .map(|name| SpaceEntry {
+            branch: space_branch(root_store, &name),
+            local: is_set_up(&space_store_paths(root_store, &name)),
+            default: config.default_space.as_deref() == Some(name.as_str()),
+            name,
+        })
+        .collect())
+}
+
+/// Records `name` in the repo config and points the space's own config at
+/// its branch, returning the space's store paths.
+pub fn register_space(root_store: &Path, name: &str) -> Result<StorePaths, String> {
+    let name = validate_space_name(name)?;
+    if name == ROOT_SPACE {
+        return Err(format!(
+            "'{ROOT_SPACE}' names the main store; pick another space name"
+        ));
+    }
+    let mut config = read_config(root_store)?;
+    if !config.spaces.contains(&name) {
+        config.spaces.push(name.clone());
+        config.spaces.sort();
+        repo_config::write_repo_config(root_store, &config)?;
+    }
+    let paths = space_store_paths(root_store, &name);
+    if repo_config::read_repo_config(&paths.root)?.is_none() {
+        let branch = space_branch(root_store, &name);
+        let remote = repo_config::knots_remote(root_store);
+        repo_config::set_knots_ref(&paths.root, Some(&branch), Some(&remote))?;
+    }
+    Ok(paths)
+}
+
+/// Sets (or with `None` clears) the default space in the repo config.
+pub fn set_default_space(root_store: &Path, name: Option<&str>) -> Result<(), String> {
+    let mut config = read_config(root_store)?;
+    config.default_space = match name {
+        Some(ROOT_SPACE) | None => None,
+        Some(name) => {
+            let name = validate_space_name(name)?;
+            if !list_spaces(root_store)?
+                .iter()
+                .any(|space| space.name == name)
+            {
+                return Err(unknown_space(&name));
+            }
+            Some(name)
+        }
+    };
+    repo_config::write_repo_config(root_store, &config)
+}
+
+/// Narrows a git context to the space named by `explicit`, else the repo's
+/// default space. `root` (or no space at all) keeps the main store.
+pub fn select_space(
+    context: ProjectContext,
+    explicit: Option<&str>,
+) -> Result<ProjectContext, String> {
+    let root_store = context.store_paths.root.clone();
+    let name = match explicit {
+        Some(name) => Some(name.trim().to_string()),
+        None if context.distribution == DistributionMode::Git => default_space(&root_store),
+        None => None,
+    };
+    let Some(name) = name.filter(|name| name != ROOT_SPACE) else {
+        return Ok(context);
+    };
+    if context.distribution != DistributionMode::Git {
+        return Err("spaces need a git-backed store; named projects have none".to_string());
+    }
+    let name = validate_space_name(&name)?;
+    let store_paths = space_store_paths(&root_store, &name);
+    if !is_set_up(&store_paths) {
+        let registered = read_config(&root_store)?.spaces.contains(&name);
+        return Err(if registered {
+            format!("space '{name}' is not set up in this clone; run `kno space create {name}`")
+        } else {
+            unknown_space(&name)
+        });
+    }
+    Ok(ProjectContext {
+        store_paths,
+        ..context
+    })
+}
+
+/// A space is set up in this clone once its cache exists.
+fn is_set_up(store_paths: &StorePaths) -> bool {
+    store_paths.db_path().exists()
+}
+
+fn unknown_space(name: &str) -> String {
+    format!("unknown space '{name}'; create it with `kno space create {name}`")
+}
+
+fn read_config(root_store: &Path) -> Result<RepoConfig, String> {
+    Ok(repo_config::read_repo_config(root_store)?.unwrap_or_default())
+}
+
+#[cfg(test)]
+#[path = "space_tests.rs"]
+mod tests;
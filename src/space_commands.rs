@@ -0,0 +1,92 @@
+use crate::app::{App, AppError};
+use crate::cli::{SpaceArgs, SpaceDefaultArgs, SpaceListArgs, SpaceSubcommands};
+use crate::project::{DistributionMode, ProjectContext};
+use crate::remote_init::remote_branch_exists;
+use crate::space::{self, SpaceEntry, ROOT_SPACE};
+use crate::{print_json, repo_config};
+
+/// Runs `kno space` against the main store of `context`, whatever space
+/// the rest of the command line selected.
+pub(crate) fn run_space(args: &SpaceArgs, context: &ProjectContext) -> Result<(), AppError> {
+    if context.distribution != DistributionMode::Git {
+        return Err(AppError::UnsupportedDistribution {
+            action: "space".to_string(),
+            mode: "local-only".to_string(),
+        });
+    }
+    if !context.store_paths.root.exists() {
+        return Err(AppError::NotInitialized);
+    }
+    match &args.command {
+        SpaceSubcommands::List(list) => list_spaces(list, context),
+        SpaceSubcommands::Create(create) => create_space(&create.name, context),
+        SpaceSubcommands::Default(default) => default_space(default, context),
+    }
+}
+
+fn list_spaces(args: &SpaceListArgs, context: &ProjectContext) -> Result<(), AppError> {
+    let root_store = &context.store_paths.root;
+    let mut spaces = space::list_spaces(root_store).map_err(AppError::InvalidArgument)?;
+    spaces.insert(
+        0,
+        SpaceEntry {
+            name: ROOT_SPACE.to_string(),
+            branch: repo_config::knots_branch(root_store),
+            local: true,
+            default: !spaces.iter().any(|space| space.default),
+        },
+    );
+    if args.json {
+        print_json(&spaces);
+        return Ok(());
+    }
+    let width = spaces
+        .iter()
+        .map(|space| space.name.len())
+        .max()
+        .unwrap_or(0);
+    for space in spaces {
+        let marker = if space.default { "*" } else { " " };
+        let note = if space.local {
+            ""
+        } else {
+            "  (not set up here)"
+        };
+        println!("{marker} {:<width$}  {}{note}", space.name, space.branch);
+    }
+    Ok(())
+}
+
+/// Registers the space, opens its cache, and either pulls its branch (when
+/// another clone created it) or publishes a new one.
+fn create_space(name: &str, context: &ProjectContext) -> Result<(), AppError> {
+    let store_paths = space::register_space(&context.store_paths.root, name)
+        .map_err(AppError::InvalidArgument)?;
+    let name = space::validate_space_name(name).map_err(AppError::InvalidArgument)?;
+    let space = ProjectContext {
+        store_paths,
+        ..context.clone()
+    };
+    let db_path = space.store_paths.db_path().display().to_string();
+    let app = App::open_with_context(&space, &db_path)?;
+    let (remote, branch) = app.knots_ref();
+    if remote_branch_exists(&space.repo_root, &remote, &branch)? {
+        app.pull()?;
+        println!("set up space {name} from {remote}/{branch}");
+    } else {
+        app.init_remote()?;
+        println!("created space {name} on {remote}/{branch}");
+    }
+    Ok(())
+}
+
+fn default_space(args: &SpaceDefaultArgs, context: &ProjectContext) -> Result<(), AppError> {
+    let root_store = &context.store_paths.root;
+    if args.clear || args.name.is_some() {
+        space::set_default_space(root_store, args.name.as_deref())
+            .map_err(AppError::InvalidArgument)?;
+    }
+    let current = space::default_space(root_store).unwrap_or_else(|| ROOT_SPACE.to_string());
+    println!("default space: {current}");
+    Ok(())
+}
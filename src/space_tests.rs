@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use super::{
+    list_spaces, register_space, select_space, set_default_space, space_branch, validate_space_name,
+};
+use crate::project::{DistributionMode, ProjectContext, StorePaths};
+
+fn unique_root() -> PathBuf {
+    let root = std::env::temp_dir().join(format!("knots-space-{}", uuid::Uuid::now_v7()));
+    std::fs::create_dir_all(root.join(".knots")).expect("store dir should be creatable");
+    root
+}
+
+fn git_context(root: &std::path::Path) -> ProjectContext {
+    ProjectContext {
+        project_id: None,
+        repo_root: root.to_path_buf(),
+        store_paths: StorePaths {
+            root: root.join(".knots"),
+        },
+        distribution: DistributionMode::Git,
+    }
+}
+
+#[test]
+fn space_names_are_lowercase_path_and_branch_safe() {
+    assert_eq!(validate_space_name(" payments ").unwrap(), "payments");
+    assert_eq!(validate_space_name("web-2_ui").unwrap(), "web-2_ui");
+    for bad in ["", "Payments", "-x", "a/b", "a b", "a.lock"] {
+        assert!(
+            validate_space_name(bad).is_err(),
+            "{bad:?} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn registering_a_space_records_it_and_points_it_at_its_own_branch() {
+    let root = unique_root();
+    let store = root.join(".knots");
+    let paths = register_space(&store, "payments").expect("space should register");
+    assert_eq!(paths.root, store.join("spaces").join("payments"));
+    assert_eq!(space_branch(&store, "payments"), "knots-payments");
+    assert_eq!(
+        crate::repo_config::knots_branch(&paths.root),
+        "knots-payments"
+    );
+    register_space(&store, "payments").expect("registering twice should be a no-op");
+    assert!(register_space(&store, "root").is_err());
+
+    let spaces = list_spaces(&store).expect("spaces should list");
+    assert_eq!(spaces.len(), 1);
+    assert_eq!(spaces[0].name, "payments");
+    assert!(!spaces[0].local);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn select_space_uses_the_explicit_name_then_the_default() {
+    let root = unique_root();
+    let store = root.join(".knots");
+    let paths = register_space(&store, "payments").expect("space should register");
+
+    let err = select_space(git_context(&root), Some("payments")).unwrap_err();
+    assert!(err.contains("not set up in this clone"), "{err}");
+    let err = select_space(git_context(&root), Some("billing")).unwrap_err();
+    assert!(err.contains("unknown space 'billing'"), "{err}");
+    assert!(set_default_space(&store, Some("billing")).is_err());
+
+    let db = paths.store_db_path();
+    std::fs::create_dir_all(db.parent().unwrap()).expect("space cache should be creatable");
+    std::fs::write(&db, b"").expect("space cache should be writable");
+    let selected = select_space(git_context(&root), Some("payments")).expect("space selects");
+    assert_eq!(selected.store_paths, paths);
+    assert_eq!(selected.repo_root, root);
+
+    let unchanged = select_space(git_context(&root), None).expect("no space keeps root");
+    assert_eq!(unchanged.store_paths.root, store);
+    set_default_space(&store, Some("payments")).expect("default should set");
+    let defaulted = select_space(git_context(&root), None).expect("default space selects");
+    assert_eq!(defaulted.store_paths, paths);
+    let explicit_root = select_space(git_context(&root), Some("root")).expect("root selects");
+    assert_eq!(explicit_root.store_paths.root, store);
+    assert!(list_spaces(&store).unwrap()[0].default);
+
+    set_default_space(&store, None).expect("default should clear");
+    let cleared = select_space(git_context(&root), None).expect("cleared default keeps root");
+    assert_eq!(cleared.store_paths.root, store);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn named_projects_reject_spaces() {
+    let root = unique_root();
+    let mut context = git_context(&root);
+    context.distribution = DistributionMode::LocalOnly;
+    let err = select_space(context, Some("payments")).unwrap_err();
+    assert!(err.contains("git-backed"), "{err}");
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -28,9 +28,11 @@ fn sample_record(id: &str, state: &str, deferred_from_state: Option<&str>) -> Kn
         body: None,
         description: None,
         acceptance: None,
+        estimate: None,
         priority: None,
         knot_type: None,
         tags: Vec::new(),
+        custom_fields: Default::default(),
         notes: Vec::new(),
         handoff_capsules: Vec::new(),
         invariants: Vec::new(),
@@ -45,6 +47,7 @@ fn sample_record(id: &str, state: &str, deferred_from_state: Option<&str>) -> Kn
         deferred_from_state: deferred_from_state.map(ToString::to_string),
         blocked_from_state: None,
         created_at: None,
+        snoozed_until: None,
     }
 }
 
@@ -0,0 +1,388 @@
+use crate::app::edges::GraphReport;
+use crate::app::{self, AgeReport, EstimateGrouping, EstimateReport, EstimateTotals, UsageReport};
+use crate::cli::{
+    StatsAgeArgs, StatsArgs, StatsEstimatesArgs, StatsGraphArgs, StatsGroupBy, StatsSubcommands,
+    StatsUsageArgs,
+};
+use crate::domain::estimate::format_minutes;
+use crate::knot_id::display_id;
+use crate::ui::Palette;
+
+pub fn run_stats(app: &app::App, args: StatsArgs) -> Result<(), app::AppError> {
+    match args.command {
+        StatsSubcommands::Estimates(args) => run_estimates(app, args),
+        StatsSubcommands::Usage(args) => run_usage(app, args),
+        StatsSubcommands::Age(args) => run_age(app, args),
+        StatsSubcommands::Graph(args) => run_graph(app, args),
+    }
+}
+
+fn run_estimates(app: &app::App, args: StatsEstimatesArgs) -> Result<(), app::AppError> {
+    let group_by = match args.by {
+        StatsGroupBy::Type => EstimateGrouping::Type,
+        StatsGroupBy::Tag => EstimateGrouping::Tag,
+    };
+    let report = crate::trace::measure("stats estimates", || app.estimate_report(group_by))?;
+    if args.json {
+        crate::print_json(&report);
+    } else {
+        print!("{}", render_estimate_report(&report));
+    }
+    Ok(())
+}
+
+/// One block per group; point estimates report time per point and duration
+/// estimates report actual time as a multiple of the estimate.
+pub fn render_estimate_report(report: &EstimateReport) -> String {
+    if report.groups.is_empty() {
+        return "no shipped knots with estimates\n".to_string();
+    }
+    let mut out = String::new();
+    for group in &report.groups {
+        out.push_str(&format!("{}\n", group.key));
+        if let Some(points) = &group.points {
+            out.push_str(&format!(
+                "  points    {} knot(s), {}pt, cycle {}, tracked {}\n",
+                points.knots,
+                points.estimated,
+                per_point(points.cycle_minutes, points),
+                per_point(points.tracked_minutes, points)
+            ));
+        }
+        if let Some(duration) = &group.duration {
+            out.push_str(&format!(
+                "  duration  {} knot(s), estimated {}, cycle {}, tracked {}\n",
+                duration.knots,
+                format_minutes(duration.estimated),
+                against_estimate(duration.cycle_minutes, duration),
+                against_estimate(duration.tracked_minutes, duration)
+            ));
+        }
+    }
+    out
+}
+
+fn run_usage(app: &app::App, args: StatsUsageArgs) -> Result<(), app::AppError> {
+    let report = app.usage_report(args.command.as_deref())?;
+    if args.json {
+        crate::print_json(&report);
+    } else {
+        print!("{}", render_usage_report(&report));
+    }
+    Ok(())
+}
+
+/// One row per command with nearest-rank latency percentiles, then the
+/// implicit sync budget to compare the sync rows against.
+pub fn render_usage_report(report: &UsageReport) -> String {
+    if report.commands.is_empty() {
+        return if report.enabled {
+            "no commands recorded yet\n".to_string()
+        } else {
+            "local metrics are off; enable them with `kno config set metrics.local true`\n"
+                .to_string()
+        };
+    }
+    let mut out = format!(
+        "{:<12} {:>6} {:>6} {:>8} {:>8} {:>8}  {}\n",
+        "command", "runs", "failed", "p50", "p95", "max", "sync files p50/p95"
+    );
+    for usage in &report.commands {
+        let sync_files = match (usage.sync_files_p50, usage.sync_files_p95) {
+            (Some(p50), Some(p95)) => format!("{p50}/{p95}"),
+            _ => "-".to_string(),
+        };
+        out.push_str(&format!(
+            "{:<12} {:>6} {:>6} {:>8} {:>8} {:>8}  {}\n",
+            usage.command,
+            usage.runs,
+            usage.failures,
+            format!("{}ms", usage.p50_ms),
+            format!("{}ms", usage.p95_ms),
+            format!("{}ms", usage.max_ms),
+            sync_files
+        ));
+    }
+    out.push_str(&format!(
+        "sync_auto_budget_ms = {}\n",
+        report.sync_auto_budget_ms
+    ));
+    if !report.enabled {
+        out.push_str("local metrics are off; these are earlier recordings\n");
+    }
+    out
+}
+
+fn run_age(app: &app::App, args: StatsAgeArgs) -> Result<(), app::AppError> {
+    let report = crate::trace::measure("stats age", || app.age_report(args.state.as_deref()))?;
+    if args.json {
+        crate::print_json(&report);
+    } else {
+        print!("{}", render_age_report(&report, &Palette::auto()));
+    }
+    Ok(())
+}
+
+/// One row of bucket counts per state, followed by that state's outliers.
+pub(crate) fn render_age_report(report: &AgeReport, palette: &Palette) -> String {
+    if report.states.is_empty() {
+        return "no open knots\n".to_string();
+    }
+    let width = report
+        .states
+        .iter()
+        .map(|state| state.state.len())
+        .max()
+        .unwrap_or_default()
+        .max("state".len());
+    let mut header = format!("{:<width$} {:>5}", "state", "knots");
+    for bucket in &report.buckets {
+        header.push_str(&format!(" {bucket:>6}"));
+    }
+    header.push_str(&format!(" {:>8} {:>8}", "median", "max"));
+    let mut out = format!("{}\n", palette.heading(&header));
+    for state in &report.states {
+        let mut row = format!("{:<width$} {:>5}", state.state, state.knots);
+        for count in &state.counts {
+            row.push_str(&format!(" {count:>6}"));
+        }
+        row.push_str(&format!(" {:>8} {:>8}", state.median(), state.max()));
+        out.push_str(&format!("{row}\n"));
+        for outlier in &state.outliers {
+            let line = format!(
+                "  ! {} {} waited {} ({})",
+                display_id(&outlier.id),
+                outlier.title,
+                outlier.waited(),
+                outlier.reason.replace('_', " ")
+            );
+            out.push_str(&format!("{}\n", palette.overdue(&line)));
+        }
+    }
+    out
+}
+
+fn run_graph(app: &app::App, args: StatsGraphArgs) -> Result<(), app::AppError> {
+    let report = crate::trace::measure("stats graph", || app.graph_report(args.top))?;
+    if args.json {
+        crate::print_json(&report);
+    } else {
+        print!("{}", render_graph_report(&report));
+    }
+    Ok(())
+}
+
+pub fn render_graph_report(report: &GraphReport) -> String {
+    let edges = if report.edges.is_empty() {
+        "none".to_string()
+    } else {
+        let counts: Vec<String> = report
+            .edges
+            .iter()
+            .map(|(kind, count)| format!("{kind} {count}"))
+            .collect();
+        counts.join(", ")
+    };
+    let chain: Vec<&str> = report
+        .longest_chain
+        .iter()
+        .map(|id| display_id(id))
+        .collect();
+    let mut out = format!(
+        "open knots      {}\nedges           {edges}\nlongest chain   {}{}\n\
+         orphans         {}\ndangling edges  {}\n",
+        report.open_knots,
+        chain.len(),
+        if chain.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", chain.join(" -> "))
+        },
+        report.orphans,
+        report.dangling_edges
+    );
+    if !report.most_blocking.is_empty() {
+        out.push_str("most blocking\n");
+    }
+    for knot in &report.most_blocking {
+        out.push_str(&format!(
+            "  {} {} [{}] blocks {} directly, {} in total\n",
+            display_id(&knot.id),
+            knot.title,
+            knot.state,
+            knot.direct,
+            knot.total
+        ));
+    }
+    out
+}
+
+fn per_point(actual: u64, totals: &EstimateTotals) -> String {
+    format!(
+        "{} ({}/pt)",
+        format_minutes(actual),
+        format_minutes(actual / totals.estimated.max(1))
+    )
+}
+
+fn against_estimate(actual: u64, totals: &EstimateTotals) -> String {
+    format!(
+        "{} ({:.2}x)",
+        format_minutes(actual),
+        actual as f64 / totals.estimated.max(1) as f64
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        render_age_report, render_estimate_report, render_graph_report, render_usage_report,
+    };
+    use crate::app::edges::{BlockingKnot, GraphReport};
+    use crate::app::wip_limits::{AgeOutlier, StateAgeHistogram};
+    use crate::app::{
+        AgeReport, CommandUsage, EstimateGroup, EstimateGrouping, EstimateReport, EstimateTotals,
+        UsageReport,
+    };
+    use crate::ui::{Palette, Theme};
+
+    #[test]
+    fn renders_time_per_point_and_ratio_to_estimate() {
+        let report = EstimateReport {
+            group_by: EstimateGrouping::Type,
+            groups: vec![EstimateGroup {
+                key: "work".to_string(),
+                points: Some(EstimateTotals {
+                    knots: 2,
+                    estimated: 4,
+                    cycle_minutes: 480,
+                    tracked_minutes: 120,
+                }),
+                duration: Some(EstimateTotals {
+                    knots: 1,
+                    estimated: 120,
+                    cycle_minutes: 180,
+                    tracked_minutes: 60,
+                }),
+            }],
+        };
+        assert_eq!(
+            render_estimate_report(&report),
+            "work\n  points    2 knot(s), 4pt, cycle 8h (2h/pt), tracked 2h (30m/pt)\n  \
+             duration  1 knot(s), estimated 2h, cycle 3h (1.50x), tracked 1h (0.50x)\n"
+        );
+        let empty = EstimateReport {
+            group_by: EstimateGrouping::Tag,
+            groups: Vec::new(),
+        };
+        assert_eq!(
+            render_estimate_report(&empty),
+            "no shipped knots with estimates\n"
+        );
+    }
+
+    #[test]
+    fn renders_usage_percentiles_and_the_sync_budget() {
+        let mut report = UsageReport {
+            enabled: true,
+            sync_auto_budget_ms: 750,
+            commands: vec![
+                CommandUsage {
+                    command: "ls".to_string(),
+                    runs: 20,
+                    failures: 0,
+                    p50_ms: 12,
+                    p95_ms: 40,
+                    max_ms: 55,
+                    sync_files_p50: None,
+                    sync_files_p95: None,
+                },
+                CommandUsage {
+                    command: "sync".to_string(),
+                    runs: 4,
+                    failures: 1,
+                    p50_ms: 310,
+                    p95_ms: 900,
+                    max_ms: 900,
+                    sync_files_p50: Some(3),
+                    sync_files_p95: Some(18),
+                },
+            ],
+        };
+        assert_eq!(
+            render_usage_report(&report),
+            "command        runs failed      p50      p95      max  sync files p50/p95\n\
+             ls               20      0     12ms     40ms     55ms  -\n\
+             sync              4      1    310ms    900ms    900ms  3/18\n\
+             sync_auto_budget_ms = 750\n"
+        );
+        report.commands.clear();
+        assert_eq!(render_usage_report(&report), "no commands recorded yet\n");
+        report.enabled = false;
+        assert!(render_usage_report(&report).contains("kno config set metrics.local true"));
+    }
+
+    #[test]
+    fn renders_age_buckets_per_state_and_outliers_below_them() {
+        let palette = Palette {
+            enabled: false,
+            theme: Theme::Default,
+        };
+        let mut report = AgeReport {
+            generated_at: "2026-10-16T00:00:00Z".to_string(),
+            buckets: vec!["<1d".to_string(), "1d+".to_string()],
+            states: vec![StateAgeHistogram {
+                state: "review".to_string(),
+                knots: 3,
+                counts: vec![2, 1],
+                median_seconds: 7_200,
+                max_seconds: 3 * 86_400,
+                budget_seconds: None,
+                outliers: vec![AgeOutlier {
+                    id: "knots-abc1".to_string(),
+                    title: "Stuck".to_string(),
+                    waited_seconds: 3 * 86_400,
+                    reason: "over_budget",
+                }],
+            }],
+        };
+        assert_eq!(
+            render_age_report(&report, &palette),
+            "state  knots    <1d    1d+   median      max\n\
+             review     3      2      1    2h 0m    3d 0h\n\
+             \x20 ! abc1 Stuck waited 3d 0h (over budget)\n"
+        );
+        report.states.clear();
+        assert_eq!(render_age_report(&report, &palette), "no open knots\n");
+    }
+
+    #[test]
+    fn renders_graph_metrics_with_the_chain_and_top_blockers() {
+        let mut report = GraphReport {
+            open_knots: 4,
+            edges: [("blocked_by".to_string(), 2), ("parent_of".to_string(), 1)]
+                .into_iter()
+                .collect(),
+            dangling_edges: 0,
+            longest_chain: vec!["knots-aaa1".to_string(), "knots-bbb2".to_string()],
+            most_blocking: vec![BlockingKnot {
+                id: "knots-bbb2".to_string(),
+                title: "Schema".to_string(),
+                state: "planning".to_string(),
+                direct: 2,
+                total: 3,
+            }],
+            orphans: 1,
+        };
+        assert_eq!(
+            render_graph_report(&report),
+            "open knots      4\nedges           blocked_by 2, parent_of 1\n\
+             longest chain   2 (aaa1 -> bbb2)\norphans         1\ndangling edges  0\n\
+             most blocking\n  bbb2 Schema [planning] blocks 2 directly, 3 in total\n"
+        );
+        report.edges.clear();
+        report.longest_chain.clear();
+        report.most_blocking.clear();
+        assert!(render_graph_report(&report).contains("edges           none\nlongest chain   0\n"));
+    }
+}
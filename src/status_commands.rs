@@ -0,0 +1,189 @@
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::app::{self, StatusReport};
+use crate::cli::StatusArgs;
+use crate::ui::Palette;
+
+pub fn run_status(app: &app::App, args: StatusArgs) -> Result<(), app::AppError> {
+    let report = crate::trace::measure("status", || app.status())?;
+    if args.json {
+        crate::print_json(&report);
+    } else {
+        for line in render_status(&report, &Palette::auto()) {
+            println!("{line}");
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn render_status(report: &StatusReport, palette: &Palette) -> Vec<String> {
+    let row =
+        |label: &str, value: String| format!("{} {value}", palette.label(&format!("{label:<7}")));
+    let sync = if report.distribution == "git" {
+        let unpushed = match report.unpushed_event_files {
+            Some(count) => format!("{count} unpushed event file(s)"),
+            None => "unpushed events unknown".to_string(),
+        };
+        let pending = if report.sync_pending {
+            ", local changes not yet synced"
+        } else {
+            ""
+        };
+        format!(
+            "policy {}, store {}, {unpushed}{pending}",
+            report.sync_policy, report.remote_store
+        )
+    } else {
+        "local-only (nothing to push or pull)".to_string()
+    };
+    let locks = |held: bool| if held { "held" } else { "free" };
+    let mut lines = vec![
+        row("sync", sync),
+        row("pull", sync_mark(report.last_pull.as_ref())),
+        row("push", sync_mark(report.last_push.as_ref())),
+        row(
+            "cache",
+            format!(
+                "hot {}, warm {}, cold {}; {} on disk",
+                report.cache.hot,
+                report.cache.warm,
+                report.cache.cold,
+                format_bytes(report.cache.db_bytes)
+            ),
+        ),
+        row(
+            "locks",
+            format!(
+                "repo {}, cache {}",
+                locks(report.locks.repo_held),
+                locks(report.locks.cache_held)
+            ),
+        ),
+    ];
+    if report.is_healthy() {
+        lines.push(row("health", "ok".to_string()));
+    } else {
+        lines.push(row(
+            "health",
+            format!("{} doctor warning(s)", report.warnings.len()),
+        ));
+        lines.extend(
+            report
+                .warnings
+                .iter()
+                .map(|check| format!("  {}: {}", check.name, check.detail)),
+        );
+    }
+    lines
+}
+
+fn sync_mark(mark: Option<&app::SyncMark>) -> String {
+    let Some(mark) = mark else {
+        return "never".to_string();
+    };
+    let at = mark
+        .at_ms
+        .and_then(|ms| OffsetDateTime::from_unix_timestamp_nanos(i128::from(ms) * 1_000_000).ok())
+        .and_then(|at| at.format(&Rfc3339).ok())
+        .unwrap_or_else(|| "unknown time".to_string());
+    match &mark.head {
+        Some(head) => format!("{at} at {}", &head[..head.len().min(12)]),
+        None => at,
+    }
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_bytes, render_status};
+    use crate::app::{CacheStatus, LockStatus, StatusReport, SyncMark};
+    use crate::doctor::{DoctorCheck, DoctorStatus};
+    use crate::ui::{Palette, Theme};
+
+    fn report() -> StatusReport {
+        StatusReport {
+            distribution: "git".to_string(),
+            sync_policy: "auto".to_string(),
+            remote_store: "git".to_string(),
+            unpushed_event_files: Some(3),
+            sync_pending: true,
+            last_pull: Some(SyncMark {
+                at_ms: Some(1_700_000_000_000),
+                head: Some("0123456789abcdef0123".to_string()),
+            }),
+            last_push: None,
+            cache: CacheStatus {
+                hot: 4,
+                warm: 2,
+                cold: 9,
+                db_bytes: 2048,
+            },
+            locks: LockStatus {
+                repo_held: true,
+                cache_held: false,
+            },
+            warnings: Vec::new(),
+        }
+    }
+
+    fn plain() -> Palette {
+        Palette {
+            enabled: false,
+            theme: Theme::Default,
+        }
+    }
+
+    #[test]
+    fn render_status_summarizes_each_area() {
+        let lines = render_status(&report(), &plain());
+        assert_eq!(
+            lines,
+            vec![
+                "sync    policy auto, store git, 3 unpushed event file(s), \
+                 local changes not yet synced",
+                "pull    2023-11-14T22:13:20Z at 0123456789ab",
+                "push    never",
+                "cache   hot 4, warm 2, cold 9; 2.0 KiB on disk",
+                "locks   repo held, cache free",
+                "health  ok",
+            ]
+        );
+    }
+
+    #[test]
+    fn render_status_lists_doctor_warnings_and_local_only_sync() {
+        let mut report = report();
+        report.distribution = "local-only".to_string();
+        report.warnings.push(DoctorCheck {
+            name: "remote".to_string(),
+            status: DoctorStatus::Warn,
+            detail: "origin unreachable".to_string(),
+        });
+        let lines = render_status(&report, &plain());
+        assert_eq!(lines[0], "sync    local-only (nothing to push or pull)");
+        assert_eq!(lines[5], "health  1 doctor warning(s)");
+        assert_eq!(lines[6], "  remote: origin unreachable");
+    }
+
+    #[test]
+    fn format_bytes_picks_a_readable_unit() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -1,6 +1,8 @@
 use std::io::{BufWriter, Write};
 
-use crate::app::{AppError, KnotView};
+use serde::Serialize;
+
+use crate::app::AppError;
 
 /// Write knots as NDJSON (one JSON object per line) to stdout.
 ///
@@ -9,13 +11,13 @@ use crate::app::{AppError, KnotView};
 /// the total count, and `complete: true`.
 ///
 /// Flushes after every line so pipe consumers see partial results.
-pub fn stream_ndjson_knots(knots: &[KnotView]) -> Result<(), AppError> {
+pub fn stream_ndjson_knots<T: Serialize>(knots: &[T]) -> Result<(), AppError> {
     let stdout = std::io::stdout();
     let writer = BufWriter::new(stdout.lock());
     write_ndjson(knots, writer)
 }
 
-fn write_ndjson<W: Write>(knots: &[KnotView], mut writer: W) -> Result<(), AppError> {
+fn write_ndjson<T: Serialize, W: Write>(knots: &[T], mut writer: W) -> Result<(), AppError> {
     for knot in knots {
         let line = serde_json::to_string(knot)
             .map_err(|e| AppError::InvalidArgument(format!("json serialize: {e}")))?;
@@ -39,6 +41,7 @@ fn io_error(e: std::io::Error) -> AppError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::KnotView;
     use crate::domain::knot_type::KnotType;
 
     fn sample_knot(id: &str, title: &str, state: &str) -> KnotView {
@@ -51,9 +54,11 @@ mod tests {
             body: None,
             description: None,
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: KnotType::Work,
             tags: Vec::new(),
+            custom_fields: Default::default(),
             notes: Vec::new(),
             handoff_capsules: Vec::new(),
             invariants: Vec::new(),
@@ -69,10 +74,15 @@ mod tests {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: None,
+            snoozed_until: None,
             step_metadata: None,
             next_step_metadata: None,
             edges: Vec::new(),
             child_summaries: Vec::new(),
+            git_links: Vec::new(),
+            criteria: Vec::new(),
+            handbacks: Vec::new(),
+            plan: None,
         }
     }
 
@@ -0,0 +1,326 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_json::{json, Value};
+
+use crate::http_json;
+
+/// Environment variable holding the bearer token for `summarize.endpoint`.
+pub const API_KEY_ENV: &str = "KNOTS_SUMMARIZE_API_KEY";
+/// Sentences an extractive summary keeps.
+const MAX_SENTENCES: usize = 6;
+/// Characters kept of one extracted sentence.
+const MAX_SENTENCE_CHARS: usize = 200;
+
+const SYSTEM_PROMPT: &str = "You write handoff notes for the next engineer or agent picking up \
+a work item. Condense the history you are given into at most 8 short bullet points covering \
+current status, decisions made, and open questions or next steps. Reply with the bullets only.";
+
+const STOPWORDS: &[&str] = &[
+    "about", "after", "again", "all", "also", "and", "any", "are", "because", "been", "before",
+    "but", "can", "could", "did", "does", "for", "from", "had", "has", "have", "here", "how",
+    "into", "its", "just", "more", "not", "now", "once", "only", "other", "our", "out", "over",
+    "should", "some", "than", "that", "the", "their", "them", "then", "there", "these", "they",
+    "this", "those", "too", "was", "were", "what", "when", "where", "which", "while", "who", "why",
+    "will", "with", "would", "you", "your",
+];
+
+/// One note or handoff capsule going into a summary, oldest first.
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub kind: &'static str,
+    pub datetime: String,
+    pub content: String,
+}
+
+/// Condenses text with a language model.
+pub trait Summarizer {
+    fn model(&self) -> &str;
+    fn summarize(&self, system: &str, prompt: &str) -> Result<String, String>;
+}
+
+/// An OpenAI-style `/v1/chat/completions` endpoint, or Ollama's
+/// `/api/chat`, called through `curl`.
+#[derive(Debug, Clone)]
+pub struct HttpSummarizer {
+    pub endpoint: String,
+    pub model: String,
+    pub api_key: Option<String>,
+}
+
+impl Summarizer for HttpSummarizer {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn summarize(&self, system: &str, prompt: &str) -> Result<String, String> {
+        let body = json!({
+            "model": self.model,
+            "stream": false,
+            "messages": [
+                { "role": "system", "content": system },
+                { "role": "user", "content": prompt },
+            ],
+        })
+        .to_string();
+        let response = http_json::post_json(&self.endpoint, self.api_key.as_deref(), &body)
+            .map_err(|err| format!("summarize {err}"))?;
+        parse_response(&response)
+    }
+}
+
+/// Reads `{"choices": [{"message": {"content"}}]}`, or Ollama's native
+/// `{"message": {"content"}}`.
+fn parse_response(body: &str) -> Result<String, String> {
+    let value: Value =
+        serde_json::from_str(body).map_err(|err| format!("invalid summarize response: {err}"))?;
+    let message = value
+        .pointer("/choices/0/message")
+        .or_else(|| value.get("message"));
+    let content = message
+        .and_then(|message| message.get("content"))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or_default();
+    if content.is_empty() {
+        return Err("summarize response has no message content".to_string());
+    }
+    Ok(content.to_string())
+}
+
+/// The system prompt and the user prompt sent to a summarize endpoint.
+pub fn llm_prompt(title: Option<&str>, entries: &[HistoryEntry]) -> (&'static str, String) {
+    let mut prompt = String::new();
+    if let Some(title) = title {
+        prompt.push_str(&format!("Work item: {title}\n\n"));
+    }
+    prompt.push_str("History, oldest first:\n");
+    for entry in entries {
+        prompt.push_str(&format!(
+            "\n[{} {}]\n{}\n",
+            entry.kind,
+            entry.datetime,
+            entry.content.trim()
+        ));
+    }
+    (SYSTEM_PROMPT, prompt)
+}
+
+/// A deterministic summary that needs no model: the sentences whose words
+/// recur most across the history, favouring recent entries, kept in the
+/// order they were written.
+pub fn extractive(entries: &[HistoryEntry]) -> String {
+    let sentences = sentences(entries);
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for sentence in &sentences {
+        for word in &sentence.words {
+            *counts.entry(word.clone()).or_default() += 1;
+        }
+    }
+    let last = entries.len().saturating_sub(1).max(1) as f64;
+    let mut ranked: Vec<(usize, f64)> = sentences
+        .iter()
+        .enumerate()
+        .map(|(position, sentence)| {
+            let weight: usize = sentence.words.iter().map(|word| counts[word]).sum();
+            let density = weight as f64 / sentence.words.len().max(1) as f64;
+            (position, density * (1.0 + sentence.entry as f64 / last))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    let mut kept: Vec<usize> = ranked
+        .into_iter()
+        .take(MAX_SENTENCES)
+        .map(|(position, _)| position)
+        .collect();
+    kept.sort_unstable();
+
+    let mut summary = header(entries);
+    for position in kept {
+        summary.push_str(&format!("\n- {}", sentences[position].text));
+    }
+    summary
+}
+
+fn header(entries: &[HistoryEntry]) -> String {
+    let count = |kind: &str| entries.iter().filter(|entry| entry.kind == kind).count();
+    let plural = |count: usize, noun: &str| {
+        let s = if count == 1 { "" } else { "s" };
+        format!("{count} {noun}{s}")
+    };
+    let mut parts = Vec::new();
+    for (kind, noun) in [("note", "note"), ("handoff", "handoff capsule")] {
+        let count = count(kind);
+        if count > 0 {
+            parts.push(plural(count, noun));
+        }
+    }
+    let day = |entry: Option<&HistoryEntry>| {
+        entry
+            .map(|entry| entry.datetime.chars().take(10).collect::<String>())
+            .unwrap_or_default()
+    };
+    let (first, last) = (day(entries.first()), day(entries.last()));
+    let span = if first == last {
+        format!("on {first}")
+    } else {
+        format!("from {first} to {last}")
+    };
+    format!("Summary of {} {span}:", parts.join(" and "))
+}
+
+struct Sentence {
+    entry: usize,
+    text: String,
+    words: Vec<String>,
+}
+
+/// Splits entries into distinct sentences, dropping list markers and
+/// fragments too short to carry meaning.
+fn sentences(entries: &[HistoryEntry]) -> Vec<Sentence> {
+    let mut seen = HashSet::new();
+    let mut found = Vec::new();
+    for (entry, history) in entries.iter().enumerate() {
+        for line in history.content.lines() {
+            let line = line.trim().trim_start_matches(['-', '*', '>', '#']).trim();
+            if line.starts_with("Summary of ") && line.ends_with(':') {
+                continue;
+            }
+            for text in split_sentences(line) {
+                let words = content_words(&text);
+                if words.len() < 2 || !seen.insert(text.to_lowercase()) {
+                    continue;
+                }
+                let text = if text.chars().count() > MAX_SENTENCE_CHARS {
+                    let cut: String = text.chars().take(MAX_SENTENCE_CHARS).collect();
+                    format!("{}…", cut.trim_end())
+                } else {
+                    text
+                };
+                found.push(Sentence { entry, text, words });
+            }
+        }
+    }
+    found
+}
+
+fn split_sentences(line: &str) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    for index in 0..chars.len() {
+        let ends = matches!(chars[index], '.' | '!' | '?')
+            && chars.get(index + 1).is_none_or(|next| next.is_whitespace());
+        if ends || index + 1 == chars.len() {
+            let text: String = chars[start..=index].iter().collect();
+            if !text.trim().is_empty() {
+                sentences.push(text.trim().to_string());
+            }
+            start = index + 1;
+        }
+    }
+    sentences
+}
+
+fn content_words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 3 && !STOPWORDS.contains(word))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extractive, llm_prompt, parse_response, HistoryEntry};
+
+    fn entry(kind: &'static str, datetime: &str, content: &str) -> HistoryEntry {
+        HistoryEntry {
+            kind,
+            datetime: datetime.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn extractive_summary_keeps_recurring_sentences_in_order() {
+        let entries = vec![
+            entry(
+                "note",
+                "2026-01-02T09:00:00Z",
+                "Started on the cache layer. Lunch.",
+            ),
+            entry(
+                "note",
+                "2026-01-03T09:00:00Z",
+                "- The cache layer drops writes under load.\n- Cache writes need a retry queue.",
+            ),
+            entry(
+                "handoff",
+                "2026-01-04T09:00:00Z",
+                "Retry queue for cache writes is done; load test remains.",
+            ),
+        ];
+        let summary = extractive(&entries);
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Summary of 2 notes and 1 handoff capsule from 2026-01-02 to 2026-01-04:"
+        );
+        assert!(!summary.contains("Lunch"));
+        assert_eq!(
+            lines.last(),
+            Some(&"- Retry queue for cache writes is done; load test remains.")
+        );
+        assert_eq!(lines[1], "- Started on the cache layer.");
+        assert_eq!(extractive(&entries), summary);
+    }
+
+    #[test]
+    fn extractive_summary_caps_sentences_and_drops_repeats() {
+        let content = (0..10)
+            .map(|n| format!("Checked shard number{n} again."))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let entries = vec![
+            entry("note", "2026-01-02T09:00:00Z", &content),
+            entry(
+                "note",
+                "2026-01-02T10:00:00Z",
+                "checked shard NUMBER0 again.",
+            ),
+        ];
+        let summary = extractive(&entries);
+        assert!(summary.starts_with("Summary of 2 notes on 2026-01-02:"));
+        assert_eq!(summary.lines().count(), 7);
+        assert_eq!(summary.to_lowercase().matches("number0").count(), 1);
+    }
+
+    #[test]
+    fn parses_openai_and_ollama_chat_responses() {
+        let openai = r#"{"choices":[{"message":{"role":"assistant","content":" - done\n"}}]}"#;
+        assert_eq!(parse_response(openai).expect("openai"), "- done");
+        let ollama = r#"{"message":{"role":"assistant","content":"- next"}}"#;
+        assert_eq!(parse_response(ollama).expect("ollama"), "- next");
+        assert!(parse_response(r#"{"choices":[]}"#).is_err());
+        assert!(parse_response("not json").is_err());
+    }
+
+    #[test]
+    fn llm_prompt_lists_entries_oldest_first() {
+        let entries = vec![
+            entry("note", "2026-01-02T09:00:00Z", "first"),
+            entry("handoff", "2026-01-03T09:00:00Z", "second"),
+        ];
+        let (system, prompt) = llm_prompt(Some("Fix cache"), &entries);
+        assert!(system.contains("bullet"));
+        assert!(prompt.starts_with("Work item: Fix cache\n"));
+        let first = prompt
+            .find("[note 2026-01-02T09:00:00Z]\nfirst")
+            .expect("note");
+        let second = prompt
+            .find("[handoff 2026-01-03T09:00:00Z]\nsecond")
+            .expect("handoff");
+        assert!(first < second);
+        assert!(!llm_prompt(None, &entries).1.contains("Work item"));
+    }
+}
@@ -0,0 +1,49 @@
+use crate::app::{self, KnotSummary};
+use crate::cli::SummarizeArgs;
+use crate::knot_id::display_id;
+use crate::summarize::Summarizer;
+use crate::ui::Palette;
+
+pub fn run_summarize(app: &app::App, args: SummarizeArgs) -> Result<(), app::AppError> {
+    let endpoint = if args.extractive {
+        None
+    } else {
+        app.summarize_endpoint()?
+    };
+    let summary = crate::trace::measure("summarize", || {
+        app.summarize_knot(
+            &args.id,
+            endpoint
+                .as_ref()
+                .map(|endpoint| endpoint as &dyn Summarizer),
+            args.dry_run,
+        )
+    })?;
+    if args.json {
+        crate::print_json(&summary);
+    } else {
+        print!("{}", render_summary(&summary, &Palette::auto()));
+    }
+    Ok(())
+}
+
+/// The capsule text under a line saying where it went and how it was made.
+pub(crate) fn render_summary(summary: &KnotSummary, palette: &Palette) -> String {
+    let id = summary.alias.as_deref().unwrap_or(display_id(&summary.id));
+    let noun = if summary.entries == 1 {
+        "entry"
+    } else {
+        "entries"
+    };
+    let action = if summary.written {
+        "added handoff capsule to"
+    } else {
+        "dry run, nothing written for"
+    };
+    format!(
+        "{action} {} {}\n{}\n",
+        palette.id(id),
+        palette.dim(&format!("({}, {} {noun})", summary.model, summary.entries)),
+        summary.content
+    )
+}
@@ -1,32 +1,46 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use rusqlite::Connection;
-use serde_json::Value;
-use time::OffsetDateTime;
 
 use crate::db;
+use crate::domain::event_type::EventTypeSpec;
 use crate::events::{FullEvent, IndexEvent, IndexEventKind};
+use crate::progress::{
+    event_partition, update_progress, ProgressReporter, PullPhase, PullProgress,
+};
 use crate::snapshots::apply_latest_snapshots;
-use crate::tiering::{classify_knot_tier, CacheTier};
+use crate::tiering::CacheTier;
 
-use super::{GitAdapter, SyncError, SyncSummary};
+use super::{details, GitAdapter, KnotsMirror, SyncError, SyncSummary};
 
 #[path = "apply_helpers.rs"]
 mod apply_helpers;
+pub(crate) use apply_helpers::current_unix_ms_string;
+#[path = "apply_metadata.rs"]
+mod apply_metadata;
+#[path = "apply_records.rs"]
+mod apply_records;
+#[path = "apply_skipped.rs"]
+mod apply_skipped;
 use apply_helpers::{
-    build_index_upsert, current_unix_ms_string, invalid_event, is_stale_precondition, optional_i64,
-    optional_string, parse_gate_data, parse_invariants, parse_lease_data, parse_metadata_entry,
-    read_json_file, required_profile_id, required_string, required_workflow_id, IndexUpsertParams,
-    MetadataProjection,
+    build_index_upsert, invalid_event, is_stale_precondition, parse_json_bytes, read_json_file,
+    required_profile_id, required_string, required_workflow_id, resolve_tier, IndexUpsertParams,
+};
+use apply_records::{
+    apply_criterion_added, apply_criterion_checked, apply_git_link, apply_handback, apply_plan_set,
 };
+use apply_skipped::{skip_unparseable, SKIPPED_FULL_STREAM, SKIPPED_INDEX_STREAM};
 
 pub struct IncrementalApplier<'a> {
     conn: &'a Connection,
     worktree: PathBuf,
     git: GitAdapter,
     known_workflows: HashSet<String>,
+    event_types: BTreeMap<String, EventTypeSpec>,
     warned_legacy: HashSet<String>,
+    mirror: Option<KnotsMirror>,
+    head: String,
 }
 
 impl<'a> IncrementalApplier<'a> {
@@ -41,10 +55,32 @@ impl<'a> IncrementalApplier<'a> {
             worktree,
             git,
             known_workflows,
+            event_types: BTreeMap::new(),
             warned_legacy: HashSet::new(),
+            mirror: None,
+            head: String::new(),
         }
     }
 
+    /// Reads events from the bare mirror with `git cat-file` instead of a
+    /// checked-out worktree.
+    pub fn for_mirror(
+        conn: &'a Connection,
+        mirror: KnotsMirror,
+        git: GitAdapter,
+        known_workflows: HashSet<String>,
+    ) -> Self {
+        let mut applier = Self::new(conn, mirror.path().to_path_buf(), git, known_workflows);
+        applier.mirror = Some(mirror);
+        applier
+    }
+
+    /// Custom event types whose projections full events of that type apply.
+    pub fn with_event_types(mut self, event_types: BTreeMap<String, EventTypeSpec>) -> Self {
+        self.event_types = event_types;
+        self
+    }
+
     #[cfg(test)]
     pub fn new_with_builtins(conn: &'a Connection, worktree: PathBuf, git: GitAdapter) -> Self {
         let known_workflows = crate::domain::knot_type::KnotType::ALL
@@ -55,56 +91,109 @@ impl<'a> IncrementalApplier<'a> {
     }
 
     pub fn apply_to_head(&mut self, target_head: &str) -> Result<SyncSummary, SyncError> {
+        self.apply_to_head_with_progress(target_head, &mut None)
+    }
+
+    pub fn apply_to_head_with_progress(
+        &mut self,
+        target_head: &str,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<SyncSummary, SyncError> {
+        self.head = target_head.to_string();
+        let index_files = crate::trace::measure("changed_index_files", || {
+            self.changed_files("last_index_head_commit", ".knots/index", target_head)
+        })?;
+        let full_files = crate::trace::measure("changed_event_files", || {
+            self.changed_files("last_full_head_commit", ".knots/events", target_head)
+        })?;
+        self.apply_files(target_head, index_files, full_files, reporter)
+    }
+
+    /// Applies the given event files and records `target_head` as synced.
+    /// Stores without git history pass the files they just fetched.
+    pub fn apply_files(
+        &mut self,
+        target_head: &str,
+        index_files: Vec<PathBuf>,
+        full_files: Vec<PathBuf>,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<SyncSummary, SyncError> {
         let bootstrap = db::get_meta(self.conn, "last_index_head_commit")?.is_none()
             && db::get_meta(self.conn, "last_full_head_commit")?.is_none();
         if bootstrap {
-            let _ = crate::trace::measure("apply_snapshots", || {
-                apply_latest_snapshots(self.conn, &self.worktree).map_err(|err| {
+            let _ = crate::trace::measure("apply_snapshots", || match &self.mirror {
+                Some(mirror) => mirror.apply_snapshots(&self.git, self.conn, target_head),
+                None => apply_latest_snapshots(self.conn, &self.worktree).map_err(|err| {
                     SyncError::SnapshotLoad {
                         message: err.to_string(),
                     }
-                })
+                }),
             })?;
         }
 
-        let index_files = crate::trace::measure("changed_index_files", || {
-            self.changed_files("last_index_head_commit", ".knots/index", target_head)
-        })?;
-        let full_files = crate::trace::measure("changed_event_files", || {
-            self.changed_files("last_full_head_commit", ".knots/events", target_head)
-        })?;
+        let mut summary =
+            self.apply_event_files_with_progress(index_files, full_files, reporter)?;
+        summary.target_head = target_head.to_string();
 
+        db::set_meta(self.conn, "last_index_head_commit", target_head)?;
+        db::set_meta(self.conn, "last_full_head_commit", target_head)?;
+        db::set_meta(self.conn, "sync_pending", "false")?;
+        db::set_meta(
+            self.conn,
+            "last_sync_success_at_ms",
+            &current_unix_ms_string(),
+        )?;
+        Ok(summary)
+    }
+
+    /// Applies event files in the given order without touching sync
+    /// watermarks, for events this clone wrote itself (e.g. an import).
+    pub fn apply_event_files(
+        &mut self,
+        index_files: Vec<PathBuf>,
+        full_files: Vec<PathBuf>,
+    ) -> Result<SyncSummary, SyncError> {
+        self.apply_event_files_with_progress(index_files, full_files, &mut None)
+    }
+
+    fn apply_event_files_with_progress(
+        &mut self,
+        index_files: Vec<PathBuf>,
+        full_files: Vec<PathBuf>,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<SyncSummary, SyncError> {
+        let total = (index_files.len() + full_files.len()) as u64;
         let mut summary = SyncSummary {
-            target_head: target_head.to_string(),
+            target_head: String::new(),
             index_files: index_files.len() as u64,
             full_files: full_files.len() as u64,
             knot_updates: 0,
             edge_adds: 0,
             edge_removes: 0,
+            details: None,
         };
 
+        let mut done = 0;
         for rel_path in index_files {
-            if self.apply_index_event(&rel_path)? {
-                summary.knot_updates += 1;
+            match self.apply_index_event(&rel_path) {
+                Ok(true) => summary.knot_updates += 1,
+                Ok(false) => {}
+                Err(err) => skip_unparseable(&rel_path, err)?,
             }
+            done += 1;
+            report_applied(reporter, done, total, &rel_path)?;
         }
 
         for rel_path in full_files {
-            match self.apply_full_event(&rel_path)? {
-                FullApplyOutcome::EdgeAdded => summary.edge_adds += 1,
-                FullApplyOutcome::EdgeRemoved => summary.edge_removes += 1,
-                FullApplyOutcome::Ignored => {}
+            match self.apply_full_event(&rel_path) {
+                Ok(FullApplyOutcome::EdgeAdded) => summary.edge_adds += 1,
+                Ok(FullApplyOutcome::EdgeRemoved) => summary.edge_removes += 1,
+                Ok(FullApplyOutcome::Ignored) => {}
+                Err(err) => skip_unparseable(&rel_path, err)?,
             }
+            done += 1;
+            report_applied(reporter, done, total, &rel_path)?;
         }
-
-        db::set_meta(self.conn, "last_index_head_commit", target_head)?;
-        db::set_meta(self.conn, "last_full_head_commit", target_head)?;
-        db::set_meta(self.conn, "sync_pending", "false")?;
-        db::set_meta(
-            self.conn,
-            "last_sync_success_at_ms",
-            &current_unix_ms_string(),
-        )?;
         Ok(summary)
     }
 
@@ -140,6 +229,13 @@ impl<'a> IncrementalApplier<'a> {
     }
 
     fn scan_json_files(&self, prefix: &str) -> Result<Vec<PathBuf>, SyncError> {
+        if let Some(mirror) = &self.mirror {
+            let blobs = mirror.tree_blobs(&self.git, &self.head, prefix)?;
+            return Ok(blobs
+                .into_keys()
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect());
+        }
         let root = self.worktree.join(prefix);
         if !root.exists() {
             return Ok(Vec::new());
@@ -170,13 +266,42 @@ impl<'a> IncrementalApplier<'a> {
         Ok(files)
     }
 
-    fn apply_index_event(&mut self, relative_path: &Path) -> Result<bool, SyncError> {
+    /// Parses the event at `relative_path`, or `None` when it was deleted.
+    fn read_event<T>(&self, relative_path: &Path) -> Result<Option<(PathBuf, T)>, SyncError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
         let absolute_path = self.worktree.join(relative_path);
+        if let Some(mirror) = &self.mirror {
+            return match mirror.read_blob(&self.git, &self.head, relative_path)? {
+                Some(bytes) => Ok(Some((
+                    absolute_path.clone(),
+                    parse_json_bytes(&absolute_path, &bytes)?,
+                ))),
+                None => Ok(None),
+            };
+        }
         if !absolute_path.exists() {
-            return Ok(false);
+            return Ok(None);
         }
+        let event = read_json_file(&absolute_path)?;
+        Ok(Some((absolute_path, event)))
+    }
 
-        let event: IndexEvent = read_json_file(&absolute_path)?;
+    fn apply_index_event(&mut self, relative_path: &Path) -> Result<bool, SyncError> {
+        let Some((absolute_path, event)) = self.read_event::<IndexEvent>(relative_path)? else {
+            return Ok(false);
+        };
+        self.apply_index(relative_path, &absolute_path, event, false)
+    }
+
+    fn apply_index(
+        &mut self,
+        relative_path: &Path,
+        absolute_path: &Path,
+        event: IndexEvent,
+        force: bool,
+    ) -> Result<bool, SyncError> {
         if event.event_type != IndexEventKind::KnotHead.as_str() {
             return Ok(false);
         }
@@ -184,14 +309,14 @@ impl<'a> IncrementalApplier<'a> {
         let data = event
             .data
             .as_object()
-            .ok_or_else(|| invalid_event(&absolute_path, "idx.knot_head data must be an object"))?;
-
-        let knot_id = required_string(data, "knot_id", &absolute_path)?;
-        let title = required_string(data, "title", &absolute_path)?;
-        let state = required_string(data, "state", &absolute_path)?;
-        let updated_at = required_string(data, "updated_at", &absolute_path)?;
-        let profile_id = required_profile_id(data, &absolute_path)?.to_ascii_lowercase();
-        let resolved = required_workflow_id(data, &absolute_path)?;
+            .ok_or_else(|| invalid_event(absolute_path, "idx.knot_head data must be an object"))?;
+
+        let knot_id = required_string(data, "knot_id", absolute_path)?;
+        let title = required_string(data, "title", absolute_path)?;
+        let state = required_string(data, "state", absolute_path)?;
+        let updated_at = required_string(data, "updated_at", absolute_path)?;
+        let profile_id = required_profile_id(data, absolute_path)?.to_ascii_lowercase();
+        let resolved = required_workflow_id(data, absolute_path)?;
         if let Some(ref legacy_id) = resolved.converted_from {
             if self.warned_legacy.insert(legacy_id.clone()) {
                 eprintln!(
@@ -203,7 +328,7 @@ impl<'a> IncrementalApplier<'a> {
         }
         if !self.known_workflows.contains(&resolved.id) {
             return Err(invalid_event(
-                &absolute_path,
+                absolute_path,
                 &format!(
                     "unrecognized workflow '{}'; \
                      upgrade knots with `kno upgrade`",
@@ -213,9 +338,11 @@ impl<'a> IncrementalApplier<'a> {
         }
         let workflow_id = resolved.id;
 
-        if is_stale_precondition(self.conn, &knot_id, event.precondition.as_ref())? {
+        if !force && is_stale_precondition(self.conn, &knot_id, event.precondition.as_ref())? {
+            self.record_skipped(relative_path, SKIPPED_INDEX_STREAM, &knot_id, &event)?;
             return Ok(false);
         }
+        details::note_changed(&knot_id);
 
         let tier = resolve_tier(self.conn, data, &state, &updated_at)?;
 
@@ -229,7 +356,7 @@ impl<'a> IncrementalApplier<'a> {
         let upsert = build_index_upsert(&IndexUpsertParams {
             conn: self.conn,
             data,
-            absolute_path: &absolute_path,
+            absolute_path,
             knot_id: &knot_id,
             title: &title,
             state: &state,
@@ -255,184 +382,104 @@ impl<'a> IncrementalApplier<'a> {
     }
 
     fn apply_full_event(&self, relative_path: &Path) -> Result<FullApplyOutcome, SyncError> {
-        let absolute_path = self.worktree.join(relative_path);
-        if !absolute_path.exists() {
+        let Some((absolute_path, event)) = self.read_event::<FullEvent>(relative_path)? else {
             return Ok(FullApplyOutcome::Ignored);
-        }
+        };
+        self.apply_full(relative_path, &absolute_path, event, false)
+    }
 
-        let event: FullEvent = read_json_file(&absolute_path)?;
+    fn apply_full(
+        &self,
+        relative_path: &Path,
+        absolute_path: &Path,
+        event: FullEvent,
+        force: bool,
+    ) -> Result<FullApplyOutcome, SyncError> {
         let data = event
             .data
             .as_object()
-            .ok_or_else(|| invalid_event(&absolute_path, "full event data must be an object"))?;
+            .ok_or_else(|| invalid_event(absolute_path, "full event data must be an object"))?;
 
-        if is_stale_precondition(self.conn, &event.knot_id, event.precondition.as_ref())? {
+        if !force && is_stale_precondition(self.conn, &event.knot_id, event.precondition.as_ref())?
+        {
+            self.record_skipped(relative_path, SKIPPED_FULL_STREAM, &event.knot_id, &event)?;
             return Ok(FullApplyOutcome::Ignored);
         }
 
-        match event.event_type.as_str() {
+        let outcome = match event.event_type.as_str() {
             "knot.edge_add" => {
-                let kind = required_string(data, "kind", &absolute_path)?;
-                let dst = required_string(data, "dst", &absolute_path)?;
+                let kind = required_string(data, "kind", absolute_path)?;
+                let dst = required_string(data, "dst", absolute_path)?;
                 db::insert_edge(self.conn, &event.knot_id, &kind, &dst)?;
-                Ok(FullApplyOutcome::EdgeAdded)
+                FullApplyOutcome::EdgeAdded
             }
             "knot.edge_remove" => {
-                let kind = required_string(data, "kind", &absolute_path)?;
-                let dst = required_string(data, "dst", &absolute_path)?;
+                let kind = required_string(data, "kind", absolute_path)?;
+                let dst = required_string(data, "dst", absolute_path)?;
                 db::delete_edge(self.conn, &event.knot_id, &kind, &dst)?;
-                Ok(FullApplyOutcome::EdgeRemoved)
-            }
-            t => {
-                self.apply_metadata_event(t, data, &event.knot_id, &absolute_path)?;
-                Ok(FullApplyOutcome::Ignored)
+                FullApplyOutcome::EdgeRemoved
             }
-        }
-    }
-
-    fn apply_metadata_event(
-        &self,
-        event_type: &str,
-        data: &serde_json::Map<String, Value>,
-        knot_id: &str,
-        path: &Path,
-    ) -> Result<(), SyncError> {
-        match event_type {
-            "knot.description_set" => self.apply_metadata_update(knot_id, |r| {
-                r.description = optional_string(data.get("description"));
-                r.body = r.description.clone();
-            }),
-            "knot.acceptance_set" => self.apply_metadata_update(knot_id, |r| {
-                r.acceptance = optional_string(data.get("acceptance"));
-            }),
-            "knot.priority_set" => self.apply_metadata_update(knot_id, |r| {
-                r.priority = optional_i64(data.get("priority"));
-            }),
-            "knot.type_set" => self.apply_metadata_update(knot_id, |r| {
-                r.knot_type = optional_string(data.get("type"));
-            }),
-            "knot.tag_add" => self.apply_tag_add(data, knot_id, path),
-            "knot.tag_remove" => self.apply_tag_remove(data, knot_id, path),
-            "knot.note_added" => {
-                let entry = parse_metadata_entry(data, path)?;
-                self.apply_metadata_update(knot_id, |r| {
-                    if !r.notes.iter().any(|e| e.entry_id == entry.entry_id) {
-                        r.notes.push(entry.clone());
-                    }
-                })
+            "knot.git_link_added" => {
+                apply_git_link(self.conn, &event, data, absolute_path)?;
+                FullApplyOutcome::Ignored
             }
-            "knot.handoff_capsule_added" => {
-                let entry = parse_metadata_entry(data, path)?;
-                self.apply_metadata_update(knot_id, |r| {
-                    if !r
-                        .handoff_capsules
-                        .iter()
-                        .any(|e| e.entry_id == entry.entry_id)
-                    {
-                        r.handoff_capsules.push(entry.clone());
-                    }
-                })
+            "knot.criterion_added" => {
+                apply_criterion_added(self.conn, &event, data, absolute_path)?;
+                FullApplyOutcome::Ignored
             }
-            "knot.invariants_set" => {
-                let invariants = parse_invariants(data, path)?;
-                self.apply_metadata_update(knot_id, |r| r.invariants = invariants)
+            "knot.criterion_checked" => {
+                apply_criterion_checked(self.conn, &event, data, absolute_path)?;
+                FullApplyOutcome::Ignored
             }
-            "knot.gate_data_set" => {
-                let gate_data = parse_gate_data(data, path)?;
-                self.apply_metadata_update(knot_id, |r| r.gate_data = gate_data)
+            "knot.handback" => {
+                apply_handback(self.conn, &event, data, absolute_path)?;
+                FullApplyOutcome::Ignored
             }
-            "knot.lease_data_set" => {
-                let ld = parse_lease_data(data, path)?;
-                self.apply_metadata_update(knot_id, |r| r.lease_data = ld)
+            "knot.plan_set" => {
+                apply_plan_set(self.conn, &event, data, absolute_path)?;
+                FullApplyOutcome::Ignored
             }
-            "knot.lease_id_set" => {
-                let lid = optional_string(data.get("lease_id"));
-                self.apply_metadata_update(knot_id, |r| r.lease_id = lid)
+            t => {
+                self.apply_metadata_event(t, data, &event.knot_id, absolute_path)?;
+                FullApplyOutcome::Ignored
             }
-            _ => Ok(()),
-        }
-    }
-
-    fn apply_tag_add(
-        &self,
-        data: &serde_json::Map<String, Value>,
-        knot_id: &str,
-        path: &Path,
-    ) -> Result<(), SyncError> {
-        let tag = required_string(data, "tag", path)?
-            .trim()
-            .to_ascii_lowercase();
-        if !tag.is_empty() {
-            self.apply_metadata_update(knot_id, |r| {
-                if !r.tags.iter().any(|existing| existing == &tag) {
-                    r.tags.push(tag.clone());
-                }
-            })?;
-        }
-        Ok(())
-    }
-
-    fn apply_tag_remove(
-        &self,
-        data: &serde_json::Map<String, Value>,
-        knot_id: &str,
-        path: &Path,
-    ) -> Result<(), SyncError> {
-        let tag = required_string(data, "tag", path)?
-            .trim()
-            .to_ascii_lowercase();
-        if !tag.is_empty() {
-            self.apply_metadata_update(knot_id, |r| {
-                r.tags.retain(|existing| existing != &tag);
-            })?;
-        }
-        Ok(())
-    }
-
-    fn apply_metadata_update<F>(&self, knot_id: &str, mutate: F) -> Result<(), SyncError>
-    where
-        F: FnOnce(&mut MetadataProjection),
-    {
-        let Some(existing) = db::get_knot_hot(self.conn, knot_id)? else {
-            return Ok(());
         };
-
-        let mut projection = MetadataProjection::from_existing(&existing);
-        mutate(&mut projection);
-        projection.upsert(self.conn, knot_id)?;
-        Ok(())
+        details::note_changed(&event.knot_id);
+        Ok(outcome)
     }
 }
 
+fn report_applied(
+    reporter: &mut Option<&mut dyn ProgressReporter>,
+    done: u64,
+    total: u64,
+    rel_path: &Path,
+) -> Result<(), SyncError> {
+    update_progress(
+        reporter,
+        PullProgress {
+            phase: PullPhase::Apply,
+            done,
+            total,
+            partition: event_partition(rel_path),
+        },
+    )?;
+    Ok(())
+}
+
 enum FullApplyOutcome {
     EdgeAdded,
     EdgeRemoved,
     Ignored,
 }
 
-fn resolve_tier(
-    conn: &Connection,
-    data: &serde_json::Map<String, Value>,
-    state: &str,
-    updated_at: &str,
-) -> Result<CacheTier, SyncError> {
-    let hot_window_days = db::get_hot_window_days(conn)?;
-    let terminal_flag = data
-        .get("terminal")
-        .and_then(Value::as_bool)
-        .unwrap_or(false);
-    let now = OffsetDateTime::now_utc();
-    if terminal_flag {
-        Ok(CacheTier::Cold)
-    } else {
-        Ok(classify_knot_tier(state, updated_at, hot_window_days, now))
-    }
-}
-
 #[cfg(test)]
 #[path = "apply_tests_acceptance_ext.rs"]
 mod tests_acceptance_ext;
 #[cfg(test)]
+#[path = "apply_tests_details.rs"]
+mod tests_details;
+#[cfg(test)]
 #[path = "apply_tests_event_paths.rs"]
 mod tests_event_paths;
 #[cfg(test)]
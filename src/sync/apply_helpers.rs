@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use rusqlite::Connection;
 use serde::de::DeserializeOwned;
 use serde_json::{Map, Value};
+use time::OffsetDateTime;
 
 use crate::db::{self, KnotCacheRecord, UpsertKnotHot};
 use crate::domain::gate::GateData;
@@ -12,10 +14,11 @@ use crate::domain::lease::LeaseData;
 use crate::domain::metadata::MetadataEntry;
 use crate::domain::step_history::StepRecord;
 use crate::installed_workflows;
+use crate::tiering::{classify_knot_tier, CacheTier};
 
 use super::SyncError;
 
-pub(super) fn current_unix_ms_string() -> String {
+pub(crate) fn current_unix_ms_string() -> String {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
@@ -30,6 +33,8 @@ pub(super) struct MetadataProjection {
     pub body: Option<String>,
     pub description: Option<String>,
     pub acceptance: Option<String>,
+    pub estimate: Option<String>,
+    pub custom_fields: BTreeMap<String, String>,
     pub priority: Option<i64>,
     pub knot_type: Option<String>,
     pub tags: Vec<String>,
@@ -46,6 +51,7 @@ pub(super) struct MetadataProjection {
     pub deferred_from_state: Option<String>,
     pub blocked_from_state: Option<String>,
     pub created_at: Option<String>,
+    pub snoozed_until: Option<String>,
 }
 
 impl MetadataProjection {
@@ -57,6 +63,8 @@ impl MetadataProjection {
             body: existing.body.clone(),
             description: existing.description.clone(),
             acceptance: existing.acceptance.clone(),
+            estimate: existing.estimate.clone(),
+            custom_fields: existing.custom_fields.clone(),
             priority: existing.priority,
             knot_type: existing.knot_type.clone(),
             tags: existing.tags.clone(),
@@ -73,6 +81,7 @@ impl MetadataProjection {
             deferred_from_state: existing.deferred_from_state.clone(),
             blocked_from_state: existing.blocked_from_state.clone(),
             created_at: existing.created_at.clone(),
+            snoozed_until: existing.snoozed_until.clone(),
         }
     }
 
@@ -87,6 +96,8 @@ impl MetadataProjection {
                 body: self.body.as_deref(),
                 description: self.description.as_deref(),
                 acceptance: self.acceptance.as_deref(),
+                estimate: self.estimate.as_deref(),
+                custom_fields: &self.custom_fields,
                 priority: self.priority,
                 knot_type: self.knot_type.as_deref(),
                 tags: &self.tags,
@@ -103,6 +114,7 @@ impl MetadataProjection {
                 deferred_from_state: self.deferred_from_state.as_deref(),
                 blocked_from_state: self.blocked_from_state.as_deref(),
                 created_at: self.created_at.as_deref(),
+                snoozed_until: self.snoozed_until.as_deref(),
             },
         )?;
         Ok(())
@@ -114,6 +126,15 @@ where
     T: DeserializeOwned,
 {
     let bytes = std::fs::read(path)?;
+    parse_json_bytes(path, &bytes)
+}
+
+pub(super) fn parse_json_bytes<T>(path: &Path, bytes: &[u8]) -> Result<T, SyncError>
+where
+    T: DeserializeOwned,
+{
+    let bytes = crate::compression::decode(bytes)
+        .map_err(|err| invalid_event(path, &format!("invalid zstd payload: {}", err)))?;
     serde_json::from_slice(&bytes)
         .map_err(|err| invalid_event(path, &format!("invalid JSON payload: {}", err)))
 }
@@ -284,6 +305,11 @@ pub(super) fn build_index_upsert(
     let body = existing.as_ref().and_then(|r| r.body.clone());
     let description = existing.as_ref().and_then(|r| r.description.clone());
     let acceptance = existing.as_ref().and_then(|r| r.acceptance.clone());
+    let estimate = existing.as_ref().and_then(|r| r.estimate.clone());
+    let custom_fields = existing
+        .as_ref()
+        .map(|r| r.custom_fields.clone())
+        .unwrap_or_default();
     let priority = existing.as_ref().and_then(|r| r.priority);
     let knot_type = existing.as_ref().and_then(|r| r.knot_type.clone());
     let tags = existing
@@ -329,6 +355,7 @@ pub(super) fn build_index_upsert(
         });
     let blocked_from_state = optional_string(params.data.get("blocked_from_state"))
         .or_else(|| existing.as_ref().and_then(|r| r.blocked_from_state.clone()));
+    let snoozed_until = existing.as_ref().and_then(|r| r.snoozed_until.clone());
     let created_at = existing
         .as_ref()
         .and_then(|r| r.created_at.clone())
@@ -341,6 +368,8 @@ pub(super) fn build_index_upsert(
         body,
         description,
         acceptance,
+        estimate,
+        custom_fields,
         priority,
         knot_type,
         tags,
@@ -357,5 +386,25 @@ pub(super) fn build_index_upsert(
         deferred_from_state,
         blocked_from_state,
         created_at: Some(created_at),
+        snoozed_until,
     })
 }
+
+pub(super) fn resolve_tier(
+    conn: &Connection,
+    data: &serde_json::Map<String, Value>,
+    state: &str,
+    updated_at: &str,
+) -> Result<CacheTier, SyncError> {
+    let hot_window_days = db::get_hot_window_days(conn)?;
+    let terminal_flag = data
+        .get("terminal")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let now = OffsetDateTime::now_utc();
+    if terminal_flag {
+        Ok(CacheTier::Cold)
+    } else {
+        Ok(classify_knot_tier(state, updated_at, hot_window_days, now))
+    }
+}
@@ -0,0 +1,160 @@
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::db;
+use crate::domain::event_type::apply_field_changes;
+
+use super::apply_helpers::{
+    optional_i64, optional_string, parse_gate_data, parse_invariants, parse_lease_data,
+    parse_metadata_entry, required_string, MetadataProjection,
+};
+use super::{IncrementalApplier, SyncError};
+
+impl IncrementalApplier<'_> {
+    pub(super) fn apply_metadata_event(
+        &self,
+        event_type: &str,
+        data: &serde_json::Map<String, Value>,
+        knot_id: &str,
+        path: &Path,
+    ) -> Result<(), SyncError> {
+        match event_type {
+            "knot.created" => match optional_string(data.get("body")) {
+                Some(body) => self.apply_metadata_update(knot_id, |r| {
+                    r.description = Some(body.clone());
+                    r.body = Some(body.clone());
+                }),
+                None => Ok(()),
+            },
+            "knot.description_set" => self.apply_metadata_update(knot_id, |r| {
+                r.description = optional_string(data.get("description"));
+                r.body = r.description.clone();
+            }),
+            "knot.acceptance_set" => self.apply_metadata_update(knot_id, |r| {
+                r.acceptance = optional_string(data.get("acceptance"));
+            }),
+            "knot.estimate_set" => self.apply_metadata_update(knot_id, |r| {
+                r.estimate = optional_string(data.get("estimate"));
+            }),
+            "knot.field_set" => self.apply_metadata_update(knot_id, |r| {
+                let Some(name) = optional_string(data.get("field")) else {
+                    return;
+                };
+                match optional_string(data.get("value")) {
+                    Some(value) => r.custom_fields.insert(name, value),
+                    None => r.custom_fields.remove(&name),
+                };
+            }),
+            "knot.snooze_set" => self.apply_metadata_update(knot_id, |r| {
+                r.snoozed_until = optional_string(data.get("until"));
+            }),
+            "knot.priority_set" => self.apply_metadata_update(knot_id, |r| {
+                r.priority = optional_i64(data.get("priority"));
+            }),
+            "knot.type_set" => self.apply_metadata_update(knot_id, |r| {
+                r.knot_type = optional_string(data.get("type"));
+            }),
+            "knot.tag_add" => self.apply_tag_add(data, knot_id, path),
+            "knot.tag_remove" => self.apply_tag_remove(data, knot_id, path),
+            "knot.note_added" => {
+                let entry = parse_metadata_entry(data, path)?;
+                self.apply_metadata_update(knot_id, |r| {
+                    if !r.notes.iter().any(|e| e.entry_id == entry.entry_id) {
+                        r.notes.push(entry.clone());
+                    }
+                })
+            }
+            "knot.handoff_capsule_added" => {
+                let entry = parse_metadata_entry(data, path)?;
+                self.apply_metadata_update(knot_id, |r| {
+                    if !r
+                        .handoff_capsules
+                        .iter()
+                        .any(|e| e.entry_id == entry.entry_id)
+                    {
+                        r.handoff_capsules.push(entry.clone());
+                    }
+                })
+            }
+            "knot.invariants_set" => {
+                let invariants = parse_invariants(data, path)?;
+                self.apply_metadata_update(knot_id, |r| r.invariants = invariants)
+            }
+            "knot.gate_data_set" => {
+                let gate_data = parse_gate_data(data, path)?;
+                self.apply_metadata_update(knot_id, |r| r.gate_data = gate_data)
+            }
+            "knot.lease_data_set" => {
+                let ld = parse_lease_data(data, path)?;
+                self.apply_metadata_update(knot_id, |r| r.lease_data = ld)
+            }
+            "knot.lease_id_set" => {
+                let lid = optional_string(data.get("lease_id"));
+                self.apply_metadata_update(knot_id, |r| r.lease_id = lid)
+            }
+            custom => match self.event_types.get(custom) {
+                Some(spec) => {
+                    let changes = spec.field_changes(data);
+                    if changes.is_empty() {
+                        return Ok(());
+                    }
+                    self.apply_metadata_update(knot_id, |r| {
+                        apply_field_changes(&mut r.custom_fields, &changes)
+                    })
+                }
+                None => Ok(()),
+            },
+        }
+    }
+
+    fn apply_tag_add(
+        &self,
+        data: &serde_json::Map<String, Value>,
+        knot_id: &str,
+        path: &Path,
+    ) -> Result<(), SyncError> {
+        let tag = required_string(data, "tag", path)?
+            .trim()
+            .to_ascii_lowercase();
+        if !tag.is_empty() {
+            self.apply_metadata_update(knot_id, |r| {
+                if !r.tags.iter().any(|existing| existing == &tag) {
+                    r.tags.push(tag.clone());
+                }
+            })?;
+        }
+        Ok(())
+    }
+
+    fn apply_tag_remove(
+        &self,
+        data: &serde_json::Map<String, Value>,
+        knot_id: &str,
+        path: &Path,
+    ) -> Result<(), SyncError> {
+        let tag = required_string(data, "tag", path)?
+            .trim()
+            .to_ascii_lowercase();
+        if !tag.is_empty() {
+            self.apply_metadata_update(knot_id, |r| {
+                r.tags.retain(|existing| existing != &tag);
+            })?;
+        }
+        Ok(())
+    }
+
+    fn apply_metadata_update<F>(&self, knot_id: &str, mutate: F) -> Result<(), SyncError>
+    where
+        F: FnOnce(&mut MetadataProjection),
+    {
+        let Some(existing) = db::get_knot_hot(self.conn, knot_id)? else {
+            return Ok(());
+        };
+
+        let mut projection = MetadataProjection::from_existing(&existing);
+        mutate(&mut projection);
+        projection.upsert(self.conn, knot_id)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use std::path::Path;
+
+use rusqlite::Connection;
+use serde_json::{Map, Value};
+
+use crate::db::{self, CriterionRecord, GitLinkRecord, HandbackRecord, PlanRecord};
+use crate::events::FullEvent;
+
+use super::apply_helpers::{invalid_event, optional_string, required_string};
+use super::SyncError;
+
+pub(super) fn apply_git_link(
+    conn: &Connection,
+    event: &FullEvent,
+    data: &Map<String, Value>,
+    path: &Path,
+) -> Result<(), SyncError> {
+    let link = GitLinkRecord {
+        kind: required_string(data, "kind", path)?,
+        target: required_string(data, "target", path)?,
+        subject: optional_string(data.get("subject")),
+        linked_at: event.occurred_at.clone(),
+    };
+    db::insert_git_link(conn, &event.knot_id, &link)?;
+    Ok(())
+}
+
+pub(super) fn apply_criterion_added(
+    conn: &Connection,
+    event: &FullEvent,
+    data: &Map<String, Value>,
+    path: &Path,
+) -> Result<(), SyncError> {
+    let criterion = CriterionRecord {
+        criterion_id: required_string(data, "criterion_id", path)?,
+        text: required_string(data, "text", path)?,
+        checked: false,
+        checked_at: None,
+        added_at: event.occurred_at.clone(),
+    };
+    db::insert_criterion(conn, &event.knot_id, &criterion)?;
+    Ok(())
+}
+
+pub(super) fn apply_criterion_checked(
+    conn: &Connection,
+    event: &FullEvent,
+    data: &Map<String, Value>,
+    path: &Path,
+) -> Result<(), SyncError> {
+    let criterion_id = required_string(data, "criterion_id", path)?;
+    let checked = data
+        .get("checked")
+        .and_then(Value::as_bool)
+        .ok_or_else(|| invalid_event(path, "missing 'checked' boolean field"))?;
+    db::set_criterion_checked(
+        conn,
+        &event.knot_id,
+        &criterion_id,
+        checked,
+        &event.occurred_at,
+    )?;
+    Ok(())
+}
+
+pub(super) fn apply_handback(
+    conn: &Connection,
+    event: &FullEvent,
+    data: &Map<String, Value>,
+    path: &Path,
+) -> Result<(), SyncError> {
+    let handback = HandbackRecord {
+        handback_id: event.event_id.clone(),
+        reason: required_string(data, "reason", path)?,
+        note: optional_string(data.get("note")),
+        agent_name: optional_string(data.get("agent_name")),
+        from_state: required_string(data, "from_state", path)?,
+        to_state: required_string(data, "to_state", path)?,
+        handed_back_at: event.occurred_at.clone(),
+        cooldown_until: optional_string(data.get("cooldown_until")),
+    };
+    db::insert_handback(conn, &event.knot_id, &handback)?;
+    Ok(())
+}
+
+pub(super) fn apply_plan_set(
+    conn: &Connection,
+    event: &FullEvent,
+    data: &Map<String, Value>,
+    path: &Path,
+) -> Result<(), SyncError> {
+    let plan = PlanRecord {
+        plan_id: event.event_id.clone(),
+        version: 0,
+        content: required_string(data, "content", path)?,
+        digest: required_string(data, "digest", path)?,
+        author: optional_string(data.get("author")),
+        set_at: event.occurred_at.clone(),
+    };
+    db::insert_plan(conn, &event.knot_id, &plan)?;
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use std::path::Path;
+
+use crate::db;
+use crate::events::{FullEvent, IndexEvent};
+
+use super::apply_helpers::{invalid_event, parse_json_bytes};
+use super::{details, IncrementalApplier, SyncError};
+
+impl IncrementalApplier<'_> {
+    /// Re-applies an event that sync skipped for a stale precondition,
+    /// ignoring the precondition this time.
+    pub fn reapply_skipped(&mut self, record: &db::SyncSkippedRecord) -> Result<(), SyncError> {
+        let relative_path = Path::new(&record.path);
+        let absolute_path = self.worktree.join(relative_path);
+        let bytes = record.event_json.as_bytes();
+        if record.stream == SKIPPED_INDEX_STREAM {
+            let event = parse_json_bytes(&absolute_path, bytes)?;
+            self.apply_index(relative_path, &absolute_path, event, true)?;
+        } else {
+            let event = parse_json_bytes(&absolute_path, bytes)?;
+            self.apply_full(relative_path, &absolute_path, event, true)?;
+        }
+        Ok(())
+    }
+
+    /// Keeps a stale-precondition event in `sync_skipped` for review with
+    /// `kno sync conflicts`.
+    pub(super) fn record_skipped<E: SkippableEvent>(
+        &self,
+        relative_path: &Path,
+        stream: &str,
+        knot_id: &str,
+        event: &E,
+    ) -> Result<(), SyncError> {
+        details::note_stale(relative_path, knot_id);
+        let expected_etag = event.expected_etag().unwrap_or_default().to_string();
+        let actual_etag = db::get_knot_hot(self.conn, knot_id)?.and_then(|r| r.profile_etag);
+        let event_json = serde_json::to_string(event)
+            .map_err(|err| invalid_event(relative_path, &err.to_string()))?;
+        db::record_sync_skipped(
+            self.conn,
+            &db::SyncSkippedRecord {
+                id: 0,
+                path: relative_path.display().to_string(),
+                stream: stream.to_string(),
+                knot_id: knot_id.to_string(),
+                event_id: event.event_id().to_string(),
+                event_type: event.event_type().to_string(),
+                reason: format!(
+                    "stale precondition: event expects profile_etag {}, cache has {}",
+                    expected_etag,
+                    actual_etag.as_deref().unwrap_or("none")
+                ),
+                expected_etag,
+                actual_etag,
+                event_json,
+                recorded_at: String::new(),
+            },
+        )?;
+        Ok(())
+    }
+}
+
+/// While sync details are recorded, an unreadable event file is noted and
+/// skipped so one bad file does not hide the rest of the pull.
+pub(super) fn skip_unparseable(rel_path: &Path, err: SyncError) -> Result<(), SyncError> {
+    match err {
+        SyncError::InvalidEvent { message, .. } if details::is_recording() => {
+            details::note_parse_failure(rel_path, &message);
+            Ok(())
+        }
+        err => Err(err),
+    }
+}
+
+pub(super) const SKIPPED_INDEX_STREAM: &str = "index";
+pub(super) const SKIPPED_FULL_STREAM: &str = "full";
+
+pub(super) trait SkippableEvent: serde::Serialize {
+    fn event_id(&self) -> &str;
+    fn event_type(&self) -> &str;
+    fn expected_etag(&self) -> Option<&str>;
+}
+
+impl SkippableEvent for IndexEvent {
+    fn event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    fn expected_etag(&self) -> Option<&str> {
+        self.precondition.as_ref().map(|p| p.profile_etag.as_str())
+    }
+}
+
+impl SkippableEvent for FullEvent {
+    fn event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    fn expected_etag(&self) -> Option<&str> {
+        self.precondition.as_ref().map(|p| p.profile_etag.as_str())
+    }
+}
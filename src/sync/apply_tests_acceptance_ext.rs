@@ -61,9 +61,11 @@ fn apply_full_event_updates_acceptance_metadata() {
             body: None,
             description: None,
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: None,
             tags: &[],
+            custom_fields: &Default::default(),
             notes: &[],
             handoff_capsules: &[],
             invariants: &[],
@@ -77,6 +79,7 @@ fn apply_full_event_updates_acceptance_metadata() {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: Some("2026-03-22T10:00:00Z"),
+            snoozed_until: None,
         },
     )
     .expect("seed knot should upsert");
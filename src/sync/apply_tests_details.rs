@@ -0,0 +1,193 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::db::{self, UpsertKnotHot};
+use crate::progress::{event_partition, ProgressKind, ProgressReporter, PullPhase, PullProgress};
+use crate::sync::{record_details, GitAdapter, SyncError};
+
+use super::IncrementalApplier;
+
+fn open_workspace() -> (PathBuf, rusqlite::Connection) {
+    let root = std::env::temp_dir().join(format!("knots-sync-details-{}", Uuid::now_v7()));
+    let db_path = root.join(".knots/cache/state.sqlite");
+    std::fs::create_dir_all(db_path.parent().expect("db parent should exist"))
+        .expect("db parent should be creatable");
+    let conn =
+        db::open_connection(db_path.to_str().expect("utf8 db path")).expect("db should open");
+    db::upsert_knot_hot(
+        &conn,
+        &UpsertKnotHot {
+            id: "K-1",
+            title: "Seed",
+            state: "work_item",
+            updated_at: "2026-02-25T10:00:00Z",
+            body: None,
+            description: None,
+            acceptance: None,
+            estimate: None,
+            priority: None,
+            knot_type: None,
+            tags: &[],
+            custom_fields: &Default::default(),
+            notes: &[],
+            handoff_capsules: &[],
+            invariants: &[],
+            step_history: &[],
+            gate_data: &crate::domain::gate::GateData::default(),
+            lease_data: &crate::domain::lease::LeaseData::default(),
+            lease_id: None,
+            workflow_id: "knots_sdlc",
+            profile_id: "automation_granular",
+            profile_etag: Some("etag-1"),
+            deferred_from_state: None,
+            blocked_from_state: None,
+            created_at: Some("2026-02-25T10:00:00Z"),
+            snoozed_until: None,
+        },
+    )
+    .expect("hot knot should upsert");
+    (root, conn)
+}
+
+fn write_event(root: &Path, name: &str, body: &str) -> PathBuf {
+    let relative = PathBuf::from(".knots/events/2026/02/25").join(name);
+    let absolute = root.join(&relative);
+    std::fs::create_dir_all(absolute.parent().expect("event parent should exist"))
+        .expect("event dir should be creatable");
+    std::fs::write(absolute, body).expect("event should write");
+    relative
+}
+
+fn tag_event(event_id: &str, tag: &str, precondition: Option<&str>) -> String {
+    let mut event = json!({
+        "event_id": event_id,
+        "occurred_at": "2026-02-25T10:00:00Z",
+        "knot_id": "K-1",
+        "type": "knot.tag_add",
+        "data": {"tag": tag},
+    });
+    if let Some(etag) = precondition {
+        event["precondition"] = json!({"profile_etag": etag});
+    }
+    Value::to_string(&event)
+}
+
+#[test]
+fn recorded_details_list_changed_knots_stale_events_and_parse_failures() {
+    let (root, conn) = open_workspace();
+    let files = vec![
+        write_event(
+            &root,
+            "1000-knot.tag_add.json",
+            &tag_event("1000", "fresh", None),
+        ),
+        write_event(
+            &root,
+            "1001-knot.tag_add.json",
+            &tag_event("1001", "late", Some("etag-old")),
+        ),
+        write_event(&root, "1002-knot.tag_add.json", "{not json"),
+    ];
+    let mut applier = IncrementalApplier::new_with_builtins(&conn, root.clone(), GitAdapter::new());
+
+    let (summary, details) = record_details(|| applier.apply_event_files(Vec::new(), files));
+    summary.expect("unreadable files are skipped while recording");
+    assert_eq!(details.changed_knots, vec!["K-1".to_string()]);
+    assert_eq!(details.stale_events.len(), 1);
+    assert_eq!(details.stale_events[0].knot_id, "K-1");
+    assert!(details.stale_events[0]
+        .path
+        .ends_with("1001-knot.tag_add.json"));
+    assert_eq!(details.parse_failures.len(), 1);
+    assert!(details.parse_failures[0]
+        .path
+        .ends_with("1002-knot.tag_add.json"));
+    assert!(details.parse_failures[0].message.contains("invalid JSON"));
+    let record = db::get_knot_hot(&conn, "K-1")
+        .expect("knot should load")
+        .expect("knot should exist");
+    assert_eq!(record.tags, vec!["fresh".to_string()]);
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn unreadable_files_still_fail_the_pull_without_details() {
+    let (root, conn) = open_workspace();
+    let files = vec![write_event(&root, "1002-knot.tag_add.json", "{not json")];
+    let mut applier = IncrementalApplier::new_with_builtins(&conn, root.clone(), GitAdapter::new());
+
+    let err = applier
+        .apply_event_files(Vec::new(), files)
+        .expect_err("unreadable file should fail");
+    assert!(matches!(err, SyncError::InvalidEvent { .. }));
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[derive(Default)]
+struct RecordingReporter {
+    updates: Vec<PullProgress>,
+}
+
+impl ProgressReporter for RecordingReporter {
+    fn emit(&mut self, _kind: ProgressKind, _message: &str) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn update(&mut self, progress: &PullProgress) -> std::io::Result<()> {
+        self.updates.push(progress.clone());
+        Ok(())
+    }
+}
+
+#[test]
+fn applying_event_files_reports_counts_and_partition() {
+    let (root, conn) = open_workspace();
+    let files = vec![
+        write_event(
+            &root,
+            "1000-knot.tag_add.json",
+            &tag_event("1000", "a", None),
+        ),
+        write_event(
+            &root,
+            "1001-knot.tag_add.json",
+            &tag_event("1001", "b", None),
+        ),
+    ];
+    let mut applier = IncrementalApplier::new_with_builtins(&conn, root.clone(), GitAdapter::new());
+    let mut recorder = RecordingReporter::default();
+
+    applier
+        .apply_event_files_with_progress(Vec::new(), files, &mut Some(&mut recorder))
+        .expect("events should apply");
+
+    let counts: Vec<_> = recorder
+        .updates
+        .iter()
+        .map(|update| (update.phase, update.done, update.total))
+        .collect();
+    assert_eq!(
+        counts,
+        vec![(PullPhase::Apply, 1, 2), (PullPhase::Apply, 2, 2)]
+    );
+    assert!(recorder
+        .updates
+        .iter()
+        .all(|update| update.partition.as_deref() == Some("2026/02/25")));
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn event_partition_reads_the_date_directories() {
+    assert_eq!(
+        event_partition(Path::new(".knots/index/2026/03/05/1-idx.knot_head.json")).as_deref(),
+        Some("2026/03/05")
+    );
+    assert_eq!(event_partition(Path::new(".knots/config.toml")), None);
+    assert_eq!(event_partition(Path::new("a/2026/3/05/x.json")), None);
+}
@@ -58,9 +58,11 @@ fn seed_hot_knot(conn: &rusqlite::Connection, knot_id: &str) {
             body: None,
             description: None,
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: None,
             tags: &[],
+            custom_fields: &Default::default(),
             notes: &[],
             handoff_capsules: &[],
             invariants: &[],
@@ -74,6 +76,7 @@ fn seed_hot_knot(conn: &rusqlite::Connection, knot_id: &str) {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: Some("2026-02-25T10:00:00Z"),
+            snoozed_until: None,
         },
     )
     .expect("hot knot should upsert");
@@ -264,3 +267,45 @@ fn apply_full_event_covers_priority_type_tag_remove_note_and_handoff() {
 
     let _ = std::fs::remove_dir_all(root);
 }
+
+#[test]
+fn apply_full_event_projects_custom_event_types_onto_fields() {
+    let root = setup_repo();
+    let conn = open_conn(&root);
+    seed_hot_knot(&conn, "K-1");
+    let spec = crate::domain::event_type::EventTypeSpec {
+        description: None,
+        set: [("build".to_string(), "status".to_string())].into(),
+    };
+    let applier = IncrementalApplier::new_with_builtins(&conn, root.clone(), GitAdapter::new())
+        .with_event_types([("ci.build_finished".to_string(), spec)].into());
+
+    let events_dir = root.join(".knots/events/2026/02/25");
+    std::fs::create_dir_all(&events_dir).expect("events directory should be creatable");
+    for (id, kind, data) in [
+        ("5100", "ci.build_finished", r#"{"status": "green"}"#),
+        ("5101", "ci.unregistered", r#"{"status": "red"}"#),
+    ] {
+        let filename = format!("{id}-{kind}.json");
+        write_event_file(
+            &events_dir,
+            &filename,
+            &format!(
+                r#"{{"event_id": "{id}", "occurred_at": "2026-02-25T10:00:00Z",
+                    "knot_id": "K-1", "type": "{kind}", "data": {data}}}"#
+            ),
+        );
+        applier
+            .apply_full_event(&Path::new(".knots/events/2026/02/25").join(filename))
+            .expect("custom event should apply");
+    }
+
+    let updated = db::get_knot_hot(&conn, "K-1")
+        .expect("hot lookup should succeed")
+        .expect("hot knot should remain present");
+    assert_eq!(
+        updated.custom_fields.get("build").map(String::as_str),
+        Some("green")
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
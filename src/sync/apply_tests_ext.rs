@@ -8,10 +8,8 @@ use crate::db::{self, UpsertKnotHot};
 use crate::events::WorkflowPrecondition;
 use crate::sync::{GitAdapter, SyncError};
 
-use super::{
-    invalid_event, is_stale_precondition, optional_i64, optional_string, parse_metadata_entry,
-    required_workflow_id, IncrementalApplier,
-};
+use super::apply_helpers::{optional_i64, optional_string, parse_metadata_entry};
+use super::{invalid_event, is_stale_precondition, required_workflow_id, IncrementalApplier};
 
 fn unique_workspace() -> PathBuf {
     let root = std::env::temp_dir().join(format!("knots-sync-apply-ext-{}", Uuid::now_v7()));
@@ -63,9 +61,11 @@ fn seed_hot_knot(conn: &rusqlite::Connection, knot_id: &str) {
             body: None,
             description: None,
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: None,
             tags: &[],
+            custom_fields: &Default::default(),
             notes: &[],
             handoff_capsules: &[],
             invariants: &[],
@@ -79,6 +79,7 @@ fn seed_hot_knot(conn: &rusqlite::Connection, knot_id: &str) {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: Some("2026-02-25T10:00:00Z"),
+            snoozed_until: None,
         },
     )
     .expect("hot knot should upsert");
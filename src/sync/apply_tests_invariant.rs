@@ -60,9 +60,11 @@ fn seed_hot_knot(conn: &rusqlite::Connection, knot_id: &str) {
             body: None,
             description: None,
             acceptance: None,
+            estimate: None,
             priority: None,
             knot_type: None,
             tags: &[],
+            custom_fields: &Default::default(),
             notes: &[],
             handoff_capsules: &[],
             invariants: &[],
@@ -76,6 +78,7 @@ fn seed_hot_knot(conn: &rusqlite::Connection, knot_id: &str) {
             deferred_from_state: None,
             blocked_from_state: None,
             created_at: Some("2026-02-25T10:00:00Z"),
+            snoozed_until: None,
         },
     )
     .expect("hot knot should upsert");
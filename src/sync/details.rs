@@ -0,0 +1,92 @@
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use serde::Serialize;
+
+/// Per-knot detail behind the counts in a `SyncSummary`.
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct SyncDetails {
+    /// Knots whose cached state an applied event touched, sorted.
+    pub changed_knots: Vec<String>,
+    /// Events left out because the knot had moved past their precondition.
+    pub stale_events: Vec<SkippedEvent>,
+    /// Event files that could not be read; they are skipped rather than
+    /// failing the pull while details are recorded.
+    pub parse_failures: Vec<ParseFailure>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SkippedEvent {
+    pub path: String,
+    pub knot_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ParseFailure {
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Default)]
+struct Recorder {
+    changed: BTreeSet<String>,
+    stale: Vec<SkippedEvent>,
+    failures: Vec<ParseFailure>,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` and returns the detail the sync applier noted on this thread
+/// meanwhile. Outside of this, the applier keeps only counts.
+pub fn record_details<T>(f: impl FnOnce() -> T) -> (T, SyncDetails) {
+    let outer = RECORDER.with(|cell| cell.replace(Some(Recorder::default())));
+    let value = f();
+    let recorder = RECORDER
+        .with(|cell| cell.replace(outer))
+        .unwrap_or_default();
+    let details = SyncDetails {
+        changed_knots: recorder.changed.into_iter().collect(),
+        stale_events: recorder.stale,
+        parse_failures: recorder.failures,
+    };
+    (value, details)
+}
+
+pub(super) fn is_recording() -> bool {
+    RECORDER.with(|cell| cell.borrow().is_some())
+}
+
+fn with_recorder(note: impl FnOnce(&mut Recorder)) {
+    RECORDER.with(|cell| {
+        if let Some(recorder) = cell.borrow_mut().as_mut() {
+            note(recorder);
+        }
+    });
+}
+
+pub(super) fn note_changed(knot_id: &str) {
+    with_recorder(|recorder| {
+        recorder.changed.insert(knot_id.to_string());
+    });
+}
+
+pub(super) fn note_stale(path: &Path, knot_id: &str) {
+    with_recorder(|recorder| {
+        recorder.stale.push(SkippedEvent {
+            path: path.display().to_string(),
+            knot_id: knot_id.to_string(),
+        });
+    });
+}
+
+pub(super) fn note_parse_failure(path: &Path, message: &str) {
+    with_recorder(|recorder| {
+        recorder.failures.push(ParseFailure {
+            path: path.display().to_string(),
+            message: message.to_string(),
+        });
+    });
+}
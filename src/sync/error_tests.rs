@@ -91,4 +91,8 @@ fn sync_error_display_source_and_from_cover_all_variants() {
         .contains("terminate leases before syncing"));
     assert!(active_leases.source().is_none());
     assert!(active_leases.is_active_leases());
+
+    let config = SyncError::InvalidConfig("remote_store: bad".to_string());
+    assert!(config.to_string().contains("invalid sync config"));
+    assert!(config.source().is_none());
 }
@@ -2,10 +2,22 @@ use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
 
 use super::SyncError;
+use crate::trace::TimingCategory;
+
+#[path = "git_progress.rs"]
+mod progress;
 
 #[derive(Debug, Clone, Default)]
 pub struct GitAdapter;
 
+/// One commit from [`GitAdapter::log_commits`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedCommit {
+    pub sha: String,
+    pub subject: String,
+    pub trailers: Vec<String>,
+}
+
 impl GitAdapter {
     pub fn new() -> Self {
         Self
@@ -18,16 +30,7 @@ impl GitAdapter {
         branch: &str,
         blob_limit_kb: Option<u64>,
     ) -> Result<(), SyncError> {
-        let mut args = vec![
-            "fetch".to_string(),
-            "--no-tags".to_string(),
-            "--prune".to_string(),
-        ];
-        if let Some(limit_kb) = blob_limit_kb {
-            args.push(format!("--filter=blob:limit={}k", limit_kb));
-        }
-        args.push(remote.to_string());
-        args.push(branch.to_string());
+        let args = fetch_args(remote, branch, blob_limit_kb);
         self.run_checked(repo_root, args)?;
         Ok(())
     }
@@ -79,6 +82,24 @@ impl GitAdapter {
         )
     }
 
+    /// Creates `branch` at HEAD without switching to it.
+    pub fn create_branch(&self, cwd: &Path, branch: &str) -> Result<(), SyncError> {
+        self.run_checked(cwd, vec!["branch".to_string(), branch.to_string()])?;
+        Ok(())
+    }
+
+    pub fn is_valid_branch_name(&self, cwd: &Path, branch: &str) -> Result<bool, SyncError> {
+        let output = self.run_allow_failure(
+            cwd,
+            vec![
+                "check-ref-format".to_string(),
+                "--branch".to_string(),
+                branch.to_string(),
+            ],
+        )?;
+        Ok(output.status.success())
+    }
+
     pub fn checkout_branch(&self, cwd: &Path, branch: &str) -> Result<(), SyncError> {
         self.run_checked(cwd, vec!["checkout".to_string(), branch.to_string()])?;
         Ok(())
@@ -204,10 +225,62 @@ impl GitAdapter {
         Ok(())
     }
 
-    fn run_checked(&self, cwd: &Path, args: Vec<String>) -> Result<String, SyncError> {
+    /// Lists up to `max_count` commits reachable from `revs`, newest first,
+    /// with the values of each commit's `trailer_key` trailers.
+    pub fn log_commits(
+        &self,
+        cwd: &Path,
+        revs: &[&str],
+        trailer_key: &str,
+        max_count: usize,
+    ) -> Result<Vec<LoggedCommit>, SyncError> {
+        let mut args = vec![
+            "log".to_string(),
+            format!("--max-count={max_count}"),
+            format!(
+                "--format=%H%x1f%s%x1f%(trailers:key={trailer_key},valueonly,separator=%x1f)%x1e"
+            ),
+        ];
+        args.extend(revs.iter().map(|rev| rev.to_string()));
+        args.push("--".to_string());
+        let output = self.run_checked(cwd, args)?;
+        Ok(output
+            .split('\x1e')
+            .filter_map(|record| {
+                let mut fields = record.trim_start_matches('\n').split('\x1f');
+                let sha = fields.next().filter(|sha| !sha.is_empty())?.to_string();
+                let subject = fields.next().unwrap_or_default().to_string();
+                let trailers = fields
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                Some(LoggedCommit {
+                    sha,
+                    subject,
+                    trailers,
+                })
+            })
+            .collect())
+    }
+
+    pub(super) fn run_checked(&self, cwd: &Path, args: Vec<String>) -> Result<String, SyncError> {
+        let output = self.run_checked_raw(cwd, args, None)?;
+        Ok(String::from_utf8_lossy(&output).trim().to_string())
+    }
+
+    /// Runs git and returns raw stdout. `index` points `GIT_INDEX_FILE` at a
+    /// scratch index so plumbing commands never touch a checkout.
+    pub(super) fn run_checked_raw(
+        &self,
+        cwd: &Path,
+        args: Vec<String>,
+        index: Option<&Path>,
+    ) -> Result<Vec<u8>, SyncError> {
         let phase_name = trace_name(&args);
-        let output =
-            crate::trace::measure(&phase_name, || self.run_allow_failure(cwd, args.clone()))?;
+        let output = crate::trace::measure(&phase_name, || {
+            self.run_with_index(cwd, args.clone(), index)
+        })?;
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
             return Err(SyncError::GitCommandFailed {
@@ -216,13 +289,29 @@ impl GitAdapter {
                 stderr,
             });
         }
-        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        Ok(output.stdout)
     }
 
-    fn run_allow_failure(&self, cwd: &Path, args: Vec<String>) -> Result<Output, SyncError> {
+    pub(super) fn run_allow_failure(
+        &self,
+        cwd: &Path,
+        args: Vec<String>,
+    ) -> Result<Output, SyncError> {
+        self.run_with_index(cwd, args, None)
+    }
+
+    fn run_with_index(
+        &self,
+        cwd: &Path,
+        args: Vec<String>,
+        index: Option<&Path>,
+    ) -> Result<Output, SyncError> {
         let mut cmd = Command::new("git");
         cmd.arg("-C").arg(cwd).args(&args);
-        cmd.output().map_err(|err| {
+        if let Some(index) = index {
+            cmd.env("GIT_INDEX_FILE", index);
+        }
+        crate::trace::timed(TimingCategory::Git, || cmd.output()).map_err(|err| {
             if err.kind() == std::io::ErrorKind::NotFound {
                 SyncError::GitUnavailable
             } else {
@@ -232,6 +321,20 @@ impl GitAdapter {
     }
 }
 
+fn fetch_args(remote: &str, branch: &str, blob_limit_kb: Option<u64>) -> Vec<String> {
+    let mut args = vec![
+        "fetch".to_string(),
+        "--no-tags".to_string(),
+        "--prune".to_string(),
+    ];
+    if let Some(limit_kb) = blob_limit_kb {
+        args.push(format!("--filter=blob:limit={}k", limit_kb));
+    }
+    args.push(remote.to_string());
+    args.push(branch.to_string());
+    args
+}
+
 fn trace_name(args: &[String]) -> String {
     match args.first().map(String::as_str) {
         Some("fetch") => "git_fetch".to_string(),
@@ -0,0 +1,136 @@
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use super::{display_command, trace_name, GitAdapter, SyncError};
+use crate::trace::TimingCategory;
+
+impl GitAdapter {
+    /// Fetches like [`GitAdapter::fetch_branch_with_filter`], passing each
+    /// received-object count from git's progress output to `on_objects` as
+    /// `(received, total)`.
+    pub fn fetch_branch_with_progress(
+        &self,
+        repo_root: &Path,
+        remote: &str,
+        branch: &str,
+        blob_limit_kb: Option<u64>,
+        on_objects: &mut dyn FnMut(u64, u64),
+    ) -> Result<(), SyncError> {
+        let mut args = super::fetch_args(remote, branch, blob_limit_kb);
+        args.insert(1, "--progress".to_string());
+        self.run_checked_with_progress(repo_root, args, on_objects)
+    }
+
+    pub(in crate::sync) fn run_checked_with_progress(
+        &self,
+        cwd: &Path,
+        args: Vec<String>,
+        on_objects: &mut dyn FnMut(u64, u64),
+    ) -> Result<(), SyncError> {
+        let phase_name = trace_name(&args);
+        let (success, code, stderr) = crate::trace::measure(&phase_name, || {
+            crate::trace::timed(TimingCategory::Git, || {
+                run_streaming_stderr(cwd, &args, on_objects)
+            })
+        })?;
+        if !success {
+            return Err(SyncError::GitCommandFailed {
+                command: display_command(cwd, &args),
+                code,
+                stderr: stderr.trim().to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Runs git with stderr piped, reporting progress counters as they arrive
+/// and keeping every other stderr line for error messages.
+fn run_streaming_stderr(
+    cwd: &Path,
+    args: &[String],
+    on_objects: &mut dyn FnMut(u64, u64),
+) -> Result<(bool, Option<i32>, String), SyncError> {
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(cwd)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                SyncError::GitUnavailable
+            } else {
+                SyncError::Io(err)
+            }
+        })?;
+    let kept = match child.stderr.take() {
+        Some(stderr) => scan_progress(stderr, on_objects)?,
+        None => String::new(),
+    };
+    let status = child.wait()?;
+    Ok((status.success(), status.code(), kept))
+}
+
+/// Splits git's stderr on the `\r` and `\n` that end each progress
+/// redraw, reporting object counts and returning every other line.
+pub(super) fn scan_progress(
+    mut stderr: impl Read,
+    on_objects: &mut dyn FnMut(u64, u64),
+) -> std::io::Result<String> {
+    let mut kept = String::new();
+    let mut pending = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let read = stderr.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        for &byte in &chunk[..read] {
+            if byte == b'\r' || byte == b'\n' {
+                take_segment(&mut pending, &mut kept, on_objects);
+            } else {
+                pending.push(byte);
+            }
+        }
+    }
+    take_segment(&mut pending, &mut kept, on_objects);
+    Ok(kept)
+}
+
+fn take_segment(pending: &mut Vec<u8>, kept: &mut String, on_objects: &mut dyn FnMut(u64, u64)) {
+    let segment = String::from_utf8_lossy(pending).into_owned();
+    pending.clear();
+    if segment.trim().is_empty() {
+        return;
+    }
+    if let Some((done, total)) = parse_received_objects(&segment) {
+        on_objects(done, total);
+    } else if !is_progress_counter(&segment) {
+        kept.push_str(&segment);
+        kept.push('\n');
+    }
+}
+
+/// Parses `Receiving objects:  45% (450/1000), 1.2 MiB | 3 MiB/s`, or the
+/// `Unpacking objects:` line git prints instead for small fetches.
+fn parse_received_objects(line: &str) -> Option<(u64, u64)> {
+    let line = line.trim();
+    let rest = line
+        .strip_prefix("Receiving objects:")
+        .or_else(|| line.strip_prefix("Unpacking objects:"))?;
+    let counts = rest.split_once('(')?.1.split_once(')')?.0;
+    let (done, total) = counts.split_once('/')?;
+    Some((done.trim().parse().ok()?, total.trim().parse().ok()?))
+}
+
+fn is_progress_counter(line: &str) -> bool {
+    line.rsplit_once(':')
+        .is_some_and(|(head, _)| head.ends_with("objects") || head.ends_with("deltas"))
+}
+
+#[cfg(test)]
+#[path = "git_progress_tests.rs"]
+mod tests;
@@ -0,0 +1,96 @@
+use std::path::Path;
+use std::process::Command;
+
+use uuid::Uuid;
+
+use super::{is_progress_counter, parse_received_objects, scan_progress};
+use crate::sync::GitAdapter;
+
+#[test]
+fn parses_received_object_counts() {
+    assert_eq!(
+        parse_received_objects("Receiving objects:  45% (450/1000), 1.20 MiB | 3.00 MiB/s"),
+        Some((450, 1000))
+    );
+    assert_eq!(
+        parse_received_objects("Receiving objects: 100% (3/3), done."),
+        Some((3, 3))
+    );
+    assert_eq!(
+        parse_received_objects("Unpacking objects:  66% (2/3)"),
+        Some((2, 3))
+    );
+    assert_eq!(
+        parse_received_objects("remote: Counting objects: 100% (3/3)"),
+        None
+    );
+    assert_eq!(parse_received_objects("fatal: no such remote"), None);
+}
+
+#[test]
+fn scanning_reports_each_redraw_and_keeps_other_lines() {
+    let stderr = b"remote: Counting objects: 100% (5/5), done.\n\
+        Receiving objects:  40% (2/5)\rReceiving objects: 100% (5/5), done.\n\
+        From ../origin\n";
+    let mut counts = Vec::new();
+    let kept = scan_progress(&stderr[..], &mut |done, total| counts.push((done, total)))
+        .expect("scan should read");
+    assert_eq!(counts, vec![(2, 5), (5, 5)]);
+    assert_eq!(kept, "From ../origin\n");
+}
+
+#[test]
+fn recognizes_progress_counters_but_keeps_errors() {
+    assert!(is_progress_counter(
+        "remote: Compressing objects: 100% (2/2), done."
+    ));
+    assert!(is_progress_counter("Resolving deltas: 100% (1/1), done."));
+    assert!(!is_progress_counter(
+        "fatal: couldn't find remote ref knots"
+    ));
+}
+
+fn git(cwd: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(cwd)
+        .args(args)
+        .output()
+        .expect("git should run");
+    assert!(status.status.success(), "git {args:?} failed: {status:?}");
+}
+
+#[test]
+fn fetch_with_progress_keeps_git_errors() {
+    let root = std::env::temp_dir().join(format!("knots-git-progress-{}", Uuid::now_v7()));
+    let origin = root.join("origin");
+    let clone = root.join("clone");
+    std::fs::create_dir_all(&origin).expect("origin dir should be creatable");
+    std::fs::create_dir_all(&clone).expect("clone dir should be creatable");
+    git(&origin, &["init", "-q", "-b", "knots"]);
+    git(&origin, &["config", "user.email", "knots@example.com"]);
+    git(&origin, &["config", "user.name", "Knots Test"]);
+    std::fs::write(origin.join("event.json"), "{}").expect("file should write");
+    git(&origin, &["add", "."]);
+    git(&origin, &["commit", "-q", "-m", "seed"]);
+    git(&clone, &["init", "-q"]);
+    git(
+        &clone,
+        &["remote", "add", "origin", origin.to_str().expect("utf8")],
+    );
+
+    GitAdapter::new()
+        .fetch_branch_with_progress(&clone, "origin", "knots", None, &mut |_, _| {})
+        .expect("fetch should succeed");
+    assert!(clone.join(".git/refs/remotes/origin/knots").exists());
+
+    let err = GitAdapter::new()
+        .fetch_branch_with_progress(&clone, "origin", "missing", None, &mut |_, _| {})
+        .expect_err("missing branch should fail");
+    assert!(
+        err.to_string().contains("couldn't find remote ref missing"),
+        "stderr should survive scanning: {err}"
+    );
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,338 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::progress::{emit_progress, ProgressKind, ProgressReporter};
+use crate::project::StorePaths;
+use crate::snapshots::{apply_latest_snapshots_at_store, SnapshotLoadSummary};
+
+use super::apply::IncrementalApplier;
+use super::{fetch_progress, GitAdapter, SyncError, SyncService, SyncSummary};
+
+const MIRROR_DIR: &str = "mirror.git";
+const PUBLISH_INDEX: &str = "knots-publish.index";
+const SNAPSHOT_SCRATCH: &str = "knots-snapshots";
+const HASH_BATCH: usize = 256;
+
+/// A bare mirror of the knots branch at `.knots/mirror.git`. Events are read
+/// with `git cat-file` and published with plumbing commands, so there is no
+/// checkout that can go dirty.
+#[derive(Debug, Clone)]
+pub struct KnotsMirror {
+    root: PathBuf,
+    path: PathBuf,
+    branch: String,
+    remote: String,
+}
+
+impl KnotsMirror {
+    pub fn with_store_paths(root: impl Into<PathBuf>, store_paths: &StorePaths) -> Self {
+        let (remote, branch) = crate::repo_config::knots_ref(&store_paths.root);
+        Self {
+            root: root.into(),
+            path: store_paths.root.join(MIRROR_DIR),
+            branch,
+            remote,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn branch(&self) -> &str {
+        &self.branch
+    }
+
+    pub fn remote(&self) -> &str {
+        &self.remote
+    }
+
+    pub fn ensure_exists(&self, git: &GitAdapter) -> Result<(), SyncError> {
+        if self.path.join("HEAD").exists() {
+            return Ok(());
+        }
+        std::fs::create_dir_all(&self.path)?;
+        git.run_checked(&self.path, args(&["init", "--bare", "--quiet"]))?;
+        Ok(())
+    }
+
+    /// Fetches the remote knots branch into the mirror and returns its head.
+    /// `None` means neither the remote nor the mirror has the branch yet.
+    pub fn fetch(&self, git: &GitAdapter) -> Result<Option<String>, SyncError> {
+        self.fetch_with_progress(git, &mut |_, _| {})
+    }
+
+    /// Fetches like [`KnotsMirror::fetch`], passing received object counts
+    /// to `on_objects`.
+    pub fn fetch_with_progress(
+        &self,
+        git: &GitAdapter,
+        on_objects: &mut dyn FnMut(u64, u64),
+    ) -> Result<Option<String>, SyncError> {
+        let url = match self.remote_url(git)? {
+            Some(url) => url,
+            None => return self.head(git),
+        };
+        let refspec = format!("+refs/heads/{0}:refs/heads/{0}", self.branch);
+        match git.run_checked_with_progress(
+            &self.path,
+            args(&["fetch", "--progress", "--no-tags", &url, &refspec]),
+            on_objects,
+        ) {
+            Ok(_) => {}
+            Err(err) if err.is_missing_remote() || is_missing_remote_ref(&err) => {}
+            Err(err) => return Err(err),
+        }
+        self.head(git)
+    }
+
+    pub fn head(&self, git: &GitAdapter) -> Result<Option<String>, SyncError> {
+        let output = git.run_allow_failure(
+            &self.path,
+            args(&[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("refs/heads/{}", self.branch),
+            ]),
+        )?;
+        let head = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok((output.status.success() && !head.is_empty()).then_some(head))
+    }
+
+    /// Contents of `path` at `rev`, or `None` when the commit lacks it.
+    pub fn read_blob(
+        &self,
+        git: &GitAdapter,
+        rev: &str,
+        path: &Path,
+    ) -> Result<Option<Vec<u8>>, SyncError> {
+        let spec = format!("{}:{}", rev, path.to_string_lossy());
+        let exists = git.run_allow_failure(&self.path, args(&["cat-file", "-e", &spec]))?;
+        if !exists.status.success() {
+            return Ok(None);
+        }
+        git.run_checked_raw(&self.path, args(&["cat-file", "blob", &spec]), None)
+            .map(Some)
+    }
+
+    /// Blob ids of every file under `prefix` at `rev`, keyed by path.
+    pub fn tree_blobs(
+        &self,
+        git: &GitAdapter,
+        rev: &str,
+        prefix: &str,
+    ) -> Result<HashMap<PathBuf, String>, SyncError> {
+        let listing = git.run_checked(&self.path, args(&["ls-tree", "-r", rev, "--", prefix]))?;
+        Ok(listing
+            .lines()
+            .filter_map(|line| {
+                let (meta, path) = line.split_once('\t')?;
+                let sha = meta.split_whitespace().nth(2)?;
+                Some((PathBuf::from(path), sha.to_string()))
+            })
+            .collect())
+    }
+
+    /// Loads the snapshots committed at `rev` into the cache. The loader
+    /// reads from disk, so the snapshot blobs are staged in a scratch store
+    /// inside the mirror and removed afterwards.
+    pub fn apply_snapshots(
+        &self,
+        git: &GitAdapter,
+        conn: &rusqlite::Connection,
+        rev: &str,
+    ) -> Result<SnapshotLoadSummary, SyncError> {
+        let scratch = self.path.join(SNAPSHOT_SCRATCH);
+        let _ = std::fs::remove_dir_all(&scratch);
+        let snapshots_dir = scratch.join("snapshots");
+        std::fs::create_dir_all(&snapshots_dir)?;
+        for path in self.tree_blobs(git, rev, ".knots/snapshots")?.into_keys() {
+            if let (Some(name), Some(bytes)) = (path.file_name(), self.read_blob(git, rev, &path)?)
+            {
+                std::fs::write(snapshots_dir.join(name), bytes)?;
+            }
+        }
+        let result = apply_latest_snapshots_at_store(conn, &scratch);
+        let _ = std::fs::remove_dir_all(&scratch);
+        result.map_err(|err| SyncError::SnapshotLoad {
+            message: err.to_string(),
+        })
+    }
+
+    /// Blob ids for `files` (absolute paths). `write` also stores the blobs.
+    pub fn hash_files(
+        &self,
+        git: &GitAdapter,
+        files: &[PathBuf],
+        write: bool,
+    ) -> Result<Vec<String>, SyncError> {
+        let mut out = Vec::with_capacity(files.len());
+        for chunk in files.chunks(HASH_BATCH) {
+            let mut command = args(&["hash-object"]);
+            if write {
+                command.push("-w".to_string());
+            }
+            command.push("--".to_string());
+            command.extend(chunk.iter().map(|path| path.to_string_lossy().to_string()));
+            let stdout = git.run_checked(&self.path, command)?;
+            out.extend(stdout.lines().map(str::to_string));
+        }
+        Ok(out)
+    }
+
+    /// Commits `entries` (path, blob id) on top of `parent` without a
+    /// checkout and returns the new commit id.
+    pub fn commit_entries(
+        &self,
+        git: &GitAdapter,
+        parent: Option<&str>,
+        entries: &[(PathBuf, String)],
+        message: &str,
+    ) -> Result<String, SyncError> {
+        let index = self.path.join(PUBLISH_INDEX);
+        let _ = std::fs::remove_file(&index);
+        let base = parent.map_or_else(
+            || args(&["read-tree", "--empty"]),
+            |p| args(&["read-tree", p]),
+        );
+        git.run_checked_raw(&self.path, base, Some(&index))?;
+        for chunk in entries.chunks(HASH_BATCH) {
+            let mut command = args(&["update-index", "--add"]);
+            for (path, sha) in chunk {
+                command.push("--cacheinfo".to_string());
+                command.push(format!("100644,{},{}", sha, path.to_string_lossy()));
+            }
+            git.run_checked_raw(&self.path, command, Some(&index))?;
+        }
+        let tree = git.run_checked_raw(&self.path, args(&["write-tree"]), Some(&index))?;
+        let _ = std::fs::remove_file(&index);
+        let tree = String::from_utf8_lossy(&tree).trim().to_string();
+
+        let mut command = self.identity_args(git)?;
+        command.extend(args(&["commit-tree", &tree, "-m", message]));
+        if let Some(parent) = parent {
+            command.extend(args(&["-p", parent]));
+        }
+        git.run_checked(&self.path, command)
+    }
+
+    /// Pushes `commit` to the remote knots branch and advances the mirror.
+    pub fn push(&self, git: &GitAdapter, commit: &str) -> Result<(), SyncError> {
+        let url = self
+            .remote_url(git)?
+            .ok_or_else(|| SyncError::GitCommandFailed {
+                command: format!("git remote get-url {}", self.remote),
+                code: None,
+                stderr: format!("No such remote '{}'", self.remote),
+            })?;
+        git.run_checked(
+            &self.path,
+            vec![
+                "push".to_string(),
+                "--no-verify".to_string(),
+                url,
+                format!("{}:refs/heads/{}", commit, self.branch),
+            ],
+        )?;
+        git.run_checked(
+            &self.path,
+            args(&["update-ref", &format!("refs/heads/{}", self.branch), commit]),
+        )?;
+        Ok(())
+    }
+
+    fn remote_url(&self, git: &GitAdapter) -> Result<Option<String>, SyncError> {
+        let output =
+            git.run_allow_failure(&self.root, args(&["remote", "get-url", &self.remote]))?;
+        let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !output.status.success() || url.is_empty() {
+            return Ok(None);
+        }
+        // Relative remote paths are resolved against the repo, not the mirror.
+        let local = self.root.join(&url);
+        Ok(Some(if local.exists() {
+            local.to_string_lossy().to_string()
+        } else {
+            url
+        }))
+    }
+
+    /// The bare mirror has no config of its own, so commits borrow the
+    /// repository's identity when one is set.
+    fn identity_args(&self, git: &GitAdapter) -> Result<Vec<String>, SyncError> {
+        let mut out = Vec::new();
+        for key in ["user.name", "user.email"] {
+            let output = git.run_allow_failure(&self.root, args(&["config", key]))?;
+            let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if output.status.success() && !value.is_empty() {
+                out.push("-c".to_string());
+                out.push(format!("{key}={value}"));
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl SyncService<'_> {
+    pub(super) fn sync_via_mirror(
+        &self,
+        reporter: &mut Option<&mut dyn ProgressReporter>,
+    ) -> Result<SyncSummary, SyncError> {
+        let mirror = KnotsMirror::with_store_paths(self.repo_root.clone(), &self.store_paths);
+        emit_progress(reporter, ProgressKind::Info, "preparing knots mirror")?;
+        mirror.ensure_exists(&self.git)?;
+        emit_progress(
+            reporter,
+            ProgressKind::Info,
+            format!(
+                "fetching {}/{} into the knots mirror",
+                mirror.remote(),
+                mirror.branch()
+            ),
+        )?;
+        let fetched = mirror.fetch_with_progress(&self.git, &mut fetch_progress(reporter))?;
+        let Some(target_head) = fetched else {
+            emit_progress(
+                reporter,
+                ProgressKind::Warn,
+                format!(
+                    "{}/{} is unavailable; nothing to pull",
+                    mirror.remote(),
+                    mirror.branch()
+                ),
+            )?;
+            return Ok(SyncSummary {
+                target_head: String::new(),
+                index_files: 0,
+                full_files: 0,
+                knot_updates: 0,
+                edge_adds: 0,
+                edge_removes: 0,
+                details: None,
+            });
+        };
+        emit_progress(
+            reporter,
+            ProgressKind::Info,
+            "applying knots events to the local cache",
+        )?;
+        let mut applier = IncrementalApplier::for_mirror(
+            self.conn,
+            mirror,
+            self.git.clone(),
+            self.known_workflow_ids(),
+        )
+        .with_event_types(crate::repo_config::event_types(&self.store_paths.root));
+        applier.apply_to_head_with_progress(&target_head, reporter)
+    }
+}
+
+fn is_missing_remote_ref(err: &SyncError) -> bool {
+    matches!(err, SyncError::GitCommandFailed { stderr, .. }
+        if stderr.to_ascii_lowercase().contains("couldn't find remote ref"))
+}
+
+fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
@@ -1,21 +1,28 @@
 use std::collections::HashSet;
 use std::error::Error;
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use rusqlite::Connection;
 use serde::Serialize;
 
 use crate::installed_workflows;
-use crate::progress::{emit_progress, ProgressKind, ProgressReporter};
+use crate::progress::{
+    emit_progress, update_progress, ProgressKind, ProgressReporter, PullPhase, PullProgress,
+};
 use crate::project::StorePaths;
 
 mod apply;
+mod details;
 mod git;
+mod mirror;
 mod worktree;
 
-use apply::IncrementalApplier;
+pub(crate) use apply::current_unix_ms_string;
+pub use apply::IncrementalApplier;
+pub use details::{record_details, SyncDetails};
 pub use git::GitAdapter;
+pub use mirror::KnotsMirror;
 pub use worktree::KnotsWorktree;
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
@@ -26,6 +33,9 @@ pub struct SyncSummary {
     pub knot_updates: u64,
     pub edge_adds: u64,
     pub edge_removes: u64,
+    /// Set only when details were asked for (`kno pull --details`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<SyncDetails>,
 }
 
 pub struct SyncService<'a> {
@@ -45,15 +55,7 @@ impl<'a> SyncService<'a> {
     }
 
     fn known_workflow_ids(&self) -> HashSet<String> {
-        if let Ok(registry) = installed_workflows::InstalledWorkflowRegistry::load(&self.repo_root)
-        {
-            registry.list().iter().map(|w| w.id.clone()).collect()
-        } else {
-            crate::domain::knot_type::KnotType::ALL
-                .into_iter()
-                .map(installed_workflows::builtin_workflow_id_for_knot_type)
-                .collect()
-        }
+        known_workflow_ids(&self.repo_root)
     }
 
     pub fn with_store_paths(
@@ -80,16 +82,25 @@ impl<'a> SyncService<'a> {
         reporter: &mut Option<&mut dyn ProgressReporter>,
     ) -> Result<SyncSummary, SyncError> {
         emit_progress(reporter, ProgressKind::Stage, "importing knots updates")?;
+        if crate::db::get_sync_mode(self.conn)? == "mirror" {
+            let summary = self.sync_via_mirror(reporter)?;
+            if !summary.target_head.is_empty() {
+                report_pull_complete(reporter, &summary)?;
+            }
+            return Ok(summary);
+        }
         let worktree = KnotsWorktree::with_store_paths(self.repo_root.clone(), &self.store_paths);
         emit_progress(reporter, ProgressKind::Info, "preparing knots worktree")?;
         worktree.ensure_exists(&self.git)?;
 
-        let target_head = match self.git.fetch_branch_with_filter(
+        let fetched = self.git.fetch_branch_with_progress(
             &self.repo_root,
             worktree.remote(),
             worktree.branch(),
             crate::db::get_sync_fetch_blob_limit_kb(self.conn)?,
-        ) {
+            &mut fetch_progress(reporter),
+        );
+        let target_head = match fetched {
             Ok(()) => {
                 let remote_ref = format!("{}/{}", worktree.remote(), worktree.branch());
                 emit_progress(
@@ -105,7 +116,11 @@ impl<'a> SyncService<'a> {
                 emit_progress(
                     reporter,
                     ProgressKind::Warn,
-                    "origin/knots is unavailable; using local knots worktree state",
+                    format!(
+                        "{}/{} is unavailable; using local knots worktree state",
+                        worktree.remote(),
+                        worktree.branch()
+                    ),
                 )?;
                 self.git.rev_parse(worktree.path(), "HEAD")?
             }
@@ -125,22 +140,61 @@ impl<'a> SyncService<'a> {
             worktree.path().to_path_buf(),
             self.git.clone(),
             known,
-        );
-        let summary = applier.apply_to_head(&target_head)?;
-        emit_progress(
-            reporter,
-            ProgressKind::Success,
-            format!(
-                "pull complete at {} (index={}, full={})",
-                short_commit(&summary.target_head),
-                summary.index_files,
-                summary.full_files
-            ),
-        )?;
+        )
+        .with_event_types(crate::repo_config::event_types(&self.store_paths.root));
+        let summary = applier.apply_to_head_with_progress(&target_head, reporter)?;
+        report_pull_complete(reporter, &summary)?;
         Ok(summary)
     }
 }
 
+/// Forwards git's received-object counts to the reporter. A failed write
+/// only loses a progress line, so it does not fail the fetch.
+pub(super) fn fetch_progress<'r>(
+    reporter: &'r mut Option<&mut dyn ProgressReporter>,
+) -> Box<dyn FnMut(u64, u64) + 'r> {
+    Box::new(move |done, total| {
+        let _ = update_progress(
+            reporter,
+            PullProgress {
+                phase: PullPhase::Fetch,
+                done,
+                total,
+                partition: None,
+            },
+        );
+    })
+}
+
+fn report_pull_complete(
+    reporter: &mut Option<&mut dyn ProgressReporter>,
+    summary: &SyncSummary,
+) -> Result<(), SyncError> {
+    emit_progress(
+        reporter,
+        ProgressKind::Success,
+        format!(
+            "pull complete at {} (index={}, full={})",
+            short_commit(&summary.target_head),
+            summary.index_files,
+            summary.full_files
+        ),
+    )?;
+    Ok(())
+}
+
+/// Workflow ids the applier accepts from synced events.
+pub fn known_workflow_ids(repo_root: &Path) -> HashSet<String> {
+    if let Ok(registry) = installed_workflows::InstalledWorkflowRegistry::load(repo_root) {
+        registry.list().iter().map(|w| w.id.clone()).collect()
+    } else {
+        crate::domain::knot_type::KnotType::ALL
+            .into_iter()
+            .map(installed_workflows::builtin_workflow_id_for_knot_type)
+            .collect()
+    }
+}
+
 fn short_commit(commit: &str) -> &str {
     &commit[..commit.len().min(12)]
 }
@@ -170,6 +224,12 @@ pub enum SyncError {
         message: String,
     },
     ActiveLeasesExist(i64),
+    InvalidConfig(String),
+    CommandFailed {
+        command: String,
+        code: Option<i32>,
+        stderr: String,
+    },
 }
 
 impl SyncError {
@@ -260,6 +320,16 @@ impl fmt::Display for SyncError {
                     count
                 )
             }
+            SyncError::InvalidConfig(message) => write!(f, "invalid sync config: {}", message),
+            SyncError::CommandFailed {
+                command,
+                code,
+                stderr,
+            } => write!(
+                f,
+                "command failed (code {:?}): {} ({})",
+                code, command, stderr
+            ),
         }
     }
 }
@@ -277,6 +347,8 @@ impl Error for SyncError {
             SyncError::MergeConflictEscalation { .. } => None,
             SyncError::SnapshotLoad { .. } => None,
             SyncError::ActiveLeasesExist(_) => None,
+            SyncError::InvalidConfig(_) => None,
+            SyncError::CommandFailed { .. } => None,
         }
     }
 }
@@ -293,6 +365,17 @@ impl From<rusqlite::Error> for SyncError {
     }
 }
 
+impl From<crate::snapshots::SnapshotError> for SyncError {
+    fn from(value: crate::snapshots::SnapshotError) -> Self {
+        use crate::snapshots::SnapshotError;
+        match value {
+            SnapshotError::Io(err) => SyncError::Io(err),
+            SnapshotError::Db(err) => SyncError::Db(err),
+            SnapshotError::Json(err) => SyncError::Io(std::io::Error::other(err)),
+        }
+    }
+}
+
 #[cfg(test)]
 #[path = "error_tests.rs"]
 mod error_tests;
@@ -27,8 +27,8 @@ impl KnotsWorktree {
         Self {
             path: store_paths.worktree_path(),
             root,
-            branch: "knots".to_string(),
-            remote: "origin".to_string(),
+            branch: crate::repo_config::knots_branch(&store_paths.root),
+            remote: crate::repo_config::knots_remote(&store_paths.root),
         }
     }
 
@@ -0,0 +1,241 @@
+use crate::app::{self, SyncConflict};
+use crate::cli::SyncConflictsArgs;
+use crate::{notify, print_json, progress, progress_reporter, ui, watch_commands};
+
+pub fn run_pull(app: &app::App, args: crate::cli::PullArgs) -> Result<(), app::AppError> {
+    let mut human = progress_reporter(!args.json && !args.json_progress);
+    let mut json = args.json_progress.then(ui::JsonProgressReporter::stderr);
+    let reporter: Option<&mut dyn progress::ProgressReporter> = match json.as_mut() {
+        Some(json) => Some(json),
+        None => human
+            .as_mut()
+            .map(|r| r as &mut dyn progress::ProgressReporter),
+    };
+    let baseline = app.pull_digest_baseline()?;
+    let summary = if args.details {
+        app.pull_with_details(reporter)?
+    } else {
+        app.pull_with_progress(reporter)?
+    };
+    let digest = app.pull_digest(&baseline)?;
+    let watched = app.record_pull_inbox(&digest)?;
+    let drift_warning = app.pull_drift_warning()?;
+    if args.json {
+        print_json(&summary);
+    } else {
+        println!(
+            concat!(
+                "pull head={} index_files={} full_files={} ",
+                "knot_updates={} edge_adds={} edge_removes={}"
+            ),
+            summary.target_head,
+            summary.index_files,
+            summary.full_files,
+            summary.knot_updates,
+            summary.edge_adds,
+            summary.edge_removes
+        );
+        if let Some(details) = &summary.details {
+            print_sync_details(details);
+        }
+        print!("{}", notify::render_pull_digest(&digest));
+        print_new_inbox_items(&watched);
+    }
+    if args.notify {
+        notify_digest(app, &digest)?;
+    }
+    if let Some(warning) = drift_warning {
+        eprintln!(
+            "warning: local knots drift is high (unpushed_event_files={} > \
+             threshold={}); run `kno push`",
+            warning.unpushed_event_files, warning.threshold
+        );
+    }
+    Ok(())
+}
+
+/// Sends the digest to the notify sink when the pull brought anything in.
+fn notify_digest(app: &app::App, digest: &app::PullDigest) -> Result<(), app::AppError> {
+    if digest.is_empty() {
+        return Ok(());
+    }
+    notify::send_notification(app, &notify::render_pull_digest(digest))
+}
+
+fn print_new_inbox_items(items: &[crate::db::InboxItemRecord]) {
+    if !items.is_empty() {
+        print!("{}", watch_commands::render_new_inbox_items(items));
+    }
+}
+
+fn print_sync_details(details: &crate::sync::SyncDetails) {
+    println!("changed_knots={}", details.changed_knots.len());
+    for id in &details.changed_knots {
+        println!("  {id}");
+    }
+    println!("stale_events={}", details.stale_events.len());
+    for event in &details.stale_events {
+        println!("  {} {}", event.knot_id, event.path);
+    }
+    println!("parse_failures={}", details.parse_failures.len());
+    for failure in &details.parse_failures {
+        println!("  {}: {}", failure.path, failure.message);
+    }
+}
+
+pub fn run_push(app: &app::App, args: crate::cli::SyncArgs) -> Result<(), app::AppError> {
+    let mut reporter = progress_reporter(!args.json);
+    let summary = app.push_with_progress(
+        reporter
+            .as_mut()
+            .map(|r| r as &mut dyn progress::ProgressReporter),
+    )?;
+    if args.json {
+        print_json(&summary);
+    } else {
+        println!(
+            "push local_event_files={} copied_files={} committed={} pushed={}{}",
+            summary.local_event_files,
+            summary.copied_files,
+            summary.committed,
+            summary.pushed,
+            summary
+                .commit
+                .as_ref()
+                .map(|c| format!(" commit={c}"))
+                .unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+pub fn run_sync(app: &app::App, args: crate::cli::SyncCommandArgs) -> Result<(), app::AppError> {
+    use crate::replication::SyncOutcome;
+    if let Some(crate::cli::SyncSubcommands::Conflicts(conflicts)) = args.command {
+        return run_sync_conflicts(app, conflicts);
+    }
+    let mut reporter = progress_reporter(!args.json);
+    let baseline = app.pull_digest_baseline()?;
+    let outcome = app.sync_or_defer_with_progress(
+        reporter
+            .as_mut()
+            .map(|r| r as &mut dyn progress::ProgressReporter),
+    )?;
+    match outcome {
+        SyncOutcome::Completed(summary) => {
+            if args.json {
+                print_json(&summary);
+            } else {
+                println!(
+                    "sync push(local_event_files={} copied_files={} \
+                     committed={} pushed={}) \
+                     pull(head={} index_files={} full_files={} \
+                     knot_updates={} edge_adds={} edge_removes={})",
+                    summary.push.local_event_files,
+                    summary.push.copied_files,
+                    summary.push.committed,
+                    summary.push.pushed,
+                    summary.pull.target_head,
+                    summary.pull.index_files,
+                    summary.pull.full_files,
+                    summary.pull.knot_updates,
+                    summary.pull.edge_adds,
+                    summary.pull.edge_removes
+                );
+            }
+            let digest = app.pull_digest(&baseline)?;
+            let watched = app.record_pull_inbox(&digest)?;
+            if !args.json {
+                print!("{}", notify::render_pull_digest(&digest));
+                print_new_inbox_items(&watched);
+            }
+            if args.notify {
+                notify_digest(app, &digest)?;
+            }
+        }
+        SyncOutcome::Deferred { active_leases } => {
+            if args.json {
+                print_json(&outcome);
+            } else {
+                println!(
+                    "sync deferred: {} active lease(s); \
+                     sync will run when leases are terminated",
+                    active_leases
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn run_sync_conflicts(app: &app::App, args: SyncConflictsArgs) -> Result<(), app::AppError> {
+    let resolved = match (args.reapply, args.dismiss) {
+        (Some(id), _) => Some(("reapplied", app.reapply_sync_conflict(id, args.force)?)),
+        (None, Some(id)) => Some(("dismissed", app.dismiss_sync_conflict(id)?)),
+        (None, None) => None,
+    };
+    if let Some((action, conflict)) = resolved {
+        if args.json {
+            print_json(&conflict);
+        } else {
+            println!(
+                "{action} sync conflict {} ({} {})",
+                conflict.id, conflict.knot_id, conflict.event_type
+            );
+        }
+        return Ok(());
+    }
+    let conflicts = app.sync_conflicts(args.knot.as_deref())?;
+    if args.json {
+        print_json(&conflicts);
+    } else {
+        print!("{}", render_sync_conflicts(&conflicts));
+    }
+    Ok(())
+}
+
+pub fn render_sync_conflicts(conflicts: &[SyncConflict]) -> String {
+    if conflicts.is_empty() {
+        return "no sync conflicts\n".to_string();
+    }
+    let mut out = String::new();
+    for conflict in conflicts {
+        out.push_str(&format!(
+            "{} {} {} {}\n  {}\n  {}\n",
+            conflict.id,
+            conflict.knot_id,
+            conflict.event_type,
+            conflict.recorded_at,
+            conflict.reason,
+            conflict.path
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_sync_conflicts;
+    use crate::app::SyncConflict;
+
+    #[test]
+    fn render_lists_each_conflict_with_reason_and_path() {
+        assert_eq!(render_sync_conflicts(&[]), "no sync conflicts\n");
+        let conflict = SyncConflict {
+            id: 3,
+            knot_id: "K-1".to_string(),
+            event_type: "knot.tag_add".to_string(),
+            event_id: "1001".to_string(),
+            path: ".knots/events/1001-knot.tag_add.json".to_string(),
+            reason: "stale precondition".to_string(),
+            expected_etag: "etag-old".to_string(),
+            actual_etag: Some("etag-1".to_string()),
+            recorded_at: "2026-10-16T12:00:00Z".to_string(),
+        };
+        assert_eq!(
+            render_sync_conflicts(&[conflict]),
+            "3 K-1 knot.tag_add 2026-10-16T12:00:00Z\n  stale precondition\n  \
+             .knots/events/1001-knot.tag_add.json\n"
+        );
+    }
+}
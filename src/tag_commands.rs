@@ -0,0 +1,65 @@
+use crate::app::{self, TagGroup};
+use crate::cli::{TagArgs, TagSubcommands};
+
+pub fn run_tag(app: &app::App, args: TagArgs) -> Result<(), app::AppError> {
+    let TagSubcommands::List(args) = args.command;
+    let groups = crate::trace::measure("tag list", || app.tag_groups())?;
+    if args.json {
+        crate::print_json(&groups);
+    } else {
+        print!("{}", render_tag_groups(&groups));
+    }
+    Ok(())
+}
+
+/// Plain tags first, then one indented block per namespace.
+pub fn render_tag_groups(groups: &[TagGroup]) -> String {
+    if groups.is_empty() {
+        return "no tags\n".to_string();
+    }
+    let mut out = String::new();
+    for group in groups {
+        let indent = match &group.namespace {
+            Some(namespace) => {
+                out.push_str(&format!("{namespace}/\n"));
+                "  "
+            }
+            None => "",
+        };
+        for tag in &group.tags {
+            out.push_str(&format!("{indent}{} ({})\n", tag.tag, tag.knots));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_tag_groups;
+    use crate::app::{TagCount, TagGroup};
+
+    #[test]
+    fn renders_plain_tags_then_namespaces() {
+        let groups = vec![
+            TagGroup {
+                namespace: None,
+                tags: vec![TagCount {
+                    tag: "release".to_string(),
+                    knots: 2,
+                }],
+            },
+            TagGroup {
+                namespace: Some("area".to_string()),
+                tags: vec![TagCount {
+                    tag: "area/backend".to_string(),
+                    knots: 1,
+                }],
+            },
+        ];
+        assert_eq!(
+            render_tag_groups(&groups),
+            "release (2)\narea/\n  area/backend (1)\n"
+        );
+        assert_eq!(render_tag_groups(&[]), "no tags\n");
+    }
+}
@@ -3,6 +3,49 @@ use std::time::{Duration, Instant};
 
 thread_local! {
     static ACTIVE_TRACE: RefCell<Option<TraceState>> = const { RefCell::new(None) };
+    static ACTIVE_TIMING: RefCell<Option<TimingState>> = const { RefCell::new(None) };
+}
+
+/// Buckets of the `--timing` breakdown. They can nest: sync time includes
+/// the git commands and lock waits it runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimingCategory {
+    LockWait,
+    Sync,
+    Db,
+    EventWrite,
+    Git,
+}
+
+impl TimingCategory {
+    const ALL: [Self; 5] = [
+        Self::LockWait,
+        Self::Sync,
+        Self::Db,
+        Self::EventWrite,
+        Self::Git,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::LockWait => "lock wait",
+            Self::Sync => "sync",
+            Self::Db => "db",
+            Self::EventWrite => "event write",
+            Self::Git => "git",
+        }
+    }
+}
+
+struct TimingState {
+    start: Instant,
+    totals: [(Duration, u32); 5],
+    active: [bool; 5],
+}
+
+/// Prints the `--timing` breakdown to stderr when dropped.
+pub struct TimingSession {
+    enabled: bool,
 }
 
 struct TraceState {
@@ -80,6 +123,77 @@ impl Drop for TraceSession {
     }
 }
 
+impl TimingSession {
+    pub fn start(enabled: bool) -> Self {
+        if enabled {
+            ACTIVE_TIMING.with(|slot| {
+                *slot.borrow_mut() = Some(TimingState {
+                    start: Instant::now(),
+                    totals: [(Duration::ZERO, 0); 5],
+                    active: [false; 5],
+                });
+            });
+        }
+        Self { enabled }
+    }
+}
+
+impl Drop for TimingSession {
+    fn drop(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        if let Some(state) = ACTIVE_TIMING.with(|slot| slot.borrow_mut().take()) {
+            for line in format_timing(state.start.elapsed(), &state.totals) {
+                eprintln!("{line}");
+            }
+        }
+    }
+}
+
+fn format_timing(total: Duration, totals: &[(Duration, u32); 5]) -> Vec<String> {
+    let mut lines = vec![format!("timing: total {}", format_ms(total))];
+    for (category, (elapsed, calls)) in TimingCategory::ALL.iter().zip(totals) {
+        lines.push(format!(
+            "  {:<12}{:>10}  ({calls} call(s))",
+            category.label(),
+            format_ms(*elapsed)
+        ));
+    }
+    lines
+}
+
+fn format_ms(elapsed: Duration) -> String {
+    format!("{:.1}ms", elapsed.as_secs_f64() * 1000.0)
+}
+
+/// Runs `f`, adding its wall time to `category` when `--timing` is on.
+/// Calls nested in the same category are counted once, by the outer call.
+pub fn timed<T>(category: TimingCategory, f: impl FnOnce() -> T) -> T {
+    let index = category as usize;
+    let outermost = ACTIVE_TIMING.with(|slot| match slot.borrow_mut().as_mut() {
+        Some(state) if !state.active[index] => {
+            state.active[index] = true;
+            true
+        }
+        _ => false,
+    });
+    if !outermost {
+        return f();
+    }
+    let start = Instant::now();
+    let value = f();
+    let elapsed = start.elapsed();
+    ACTIVE_TIMING.with(|slot| {
+        if let Some(state) = slot.borrow_mut().as_mut() {
+            state.active[index] = false;
+            state.totals[index].0 += elapsed;
+            state.totals[index].1 += 1;
+        }
+    });
+    value
+}
+
 impl TracePhaseGuard {
     #[allow(dead_code)]
     pub fn detail(&mut self, detail: impl Into<String>) {
@@ -146,7 +260,10 @@ fn record_phase(name: String, elapsed: Duration, detail: Option<String>) {
 mod tests {
     use std::time::Duration;
 
-    use super::{measure, phase, TraceSession};
+    use super::{
+        format_timing, measure, phase, timed, TimingCategory, TimingSession, TraceSession,
+        ACTIVE_TIMING,
+    };
 
     #[test]
     fn trace_session_records_manual_and_measured_phases() {
@@ -173,4 +290,31 @@ mod tests {
     fn record_noop_when_disabled() {
         super::record("orphan", Duration::from_millis(1), None);
     }
+
+    #[test]
+    fn timed_accumulates_per_category_only_inside_a_session() {
+        assert_eq!(timed(TimingCategory::Git, || 1), 1);
+        let _session = TimingSession::start(true);
+        timed(TimingCategory::Git, || ());
+        timed(TimingCategory::Git, || ());
+        timed(TimingCategory::LockWait, || {
+            timed(TimingCategory::LockWait, || ())
+        });
+        let calls = ACTIVE_TIMING.with(|slot| {
+            let state = slot.borrow();
+            let totals = &state.as_ref().expect("timing state").totals;
+            totals.map(|(_, calls)| calls)
+        });
+        assert_eq!(calls, [1, 0, 0, 0, 2]);
+    }
+
+    #[test]
+    fn format_timing_lists_every_category() {
+        let mut totals = [(Duration::ZERO, 0); 5];
+        totals[2] = (Duration::from_micros(12_340), 3);
+        let lines = format_timing(Duration::from_millis(20), &totals);
+        assert_eq!(lines[0], "timing: total 20.0ms");
+        assert_eq!(lines[3], "  db              12.3ms  (3 call(s))");
+        assert_eq!(lines.len(), 6);
+    }
 }
@@ -1,16 +1,30 @@
 use crate::app::KnotView;
 use crate::doctor::{DoctorCheck, DoctorReport, DoctorStatus};
-use crate::list_layout::DisplayKnot;
+use crate::list_layout::{Column, ColumnKind, DisplayKnot};
 use crate::listing::KnotListFilter;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 mod palette;
 mod progress;
+mod show;
+mod theme;
 #[cfg(test)]
 pub(crate) use palette::state_color_code;
 pub(crate) use palette::Palette;
+#[cfg(test)]
 use palette::ShowField;
 #[cfg(test)]
-pub(crate) use progress::format_progress_line;
-pub(crate) use progress::StdoutProgressReporter;
+pub(crate) use progress::{format_progress_line, format_pull_progress};
+pub(crate) use progress::{JsonProgressReporter, StdoutProgressReporter};
+use show::format_knot_show;
+pub use show::hidden_metadata_hint;
+pub(crate) use show::{format_criteria, format_plan_version};
+#[cfg(test)]
+use show::{
+    format_entry_inline, format_show_fields, knot_show_fields, wrap_split_index, wrap_value,
+};
+pub(crate) use theme::stdout_color_enabled;
+#[cfg(test)]
+pub(crate) use theme::{color_enabled, Theme};
 const SHOW_VALUE_WIDTH: usize = 80;
 pub fn trim_json_metadata(value: &mut serde_json::Value, knot: &KnotView) {
     if let Some(obj) = value.as_object_mut() {
@@ -36,26 +50,42 @@ pub fn trim_json_metadata(value: &mut serde_json::Value, knot: &KnotView) {
         }
     }
 }
-pub fn print_knot_list(knots: &[DisplayKnot], filter: &KnotListFilter) {
+/// Prints one line per knot, or a table when `columns` is non-empty,
+/// through the pager when it runs past one screen.
+pub fn print_knot_list(knots: &[DisplayKnot], filter: &KnotListFilter, columns: &[Column]) {
     let p = Palette::auto();
-    println!("{}", p.heading("Knots"));
+    crate::pager::print_paged(&format_knot_list(knots, filter, columns, &p));
+}
+pub(crate) fn format_knot_list(
+    knots: &[DisplayKnot],
+    filter: &KnotListFilter,
+    columns: &[Column],
+    p: &Palette,
+) -> Vec<String> {
+    let mut lines = vec![p.heading("Knots")];
     if let Some(s) = filter_summary(filter) {
-        println!("{}", p.dim(&format!("filters: {s}")));
+        lines.push(p.dim(&format!("filters: {s}")));
     }
     if knots.is_empty() {
-        println!("{}", p.dim("no knots matched"));
-        return;
+        lines.push(p.dim("no knots matched"));
+        return lines;
+    }
+    if !columns.is_empty() {
+        lines.push(format_table_header(columns, p));
     }
     for k in knots {
-        println!("{}", format_knot_row(k, &p));
+        if columns.is_empty() {
+            lines.push(format_knot_row(k, p));
+        } else {
+            lines.push(format_table_row(k, columns, p));
+        }
     }
-    println!("{}", p.dim(&format!("{} knot(s)", knots.len())));
+    lines.push(p.dim(&format!("{} knot(s)", knots.len())));
+    lines
 }
 pub fn print_knot_show(knot: &KnotView, verbose: bool) {
     let p = Palette::auto();
-    for line in format_knot_show(knot, &p, SHOW_VALUE_WIDTH, verbose) {
-        println!("{line}");
-    }
+    crate::pager::print_paged(&format_knot_show(knot, &p, SHOW_VALUE_WIDTH, verbose));
 }
 pub fn print_doctor_report(report: &DoctorReport) {
     let p = Palette::auto();
@@ -115,6 +145,124 @@ pub fn format_knot_row(row: &DisplayKnot, palette: &Palette) -> String {
     }
     line
 }
+pub(crate) fn format_table_header(columns: &[Column], palette: &Palette) -> String {
+    join_cells(columns, |column| {
+        palette.label(&fit_cell(&column.kind.header(), column.width(false)))
+    })
+}
+pub(crate) fn format_table_row(row: &DisplayKnot, columns: &[Column], palette: &Palette) -> String {
+    let last = columns.len().saturating_sub(1);
+    let today = crate::events::now_utc_rfc3339();
+    let mut index = 0;
+    join_cells(columns, |column| {
+        let width = column.width(index == last);
+        index += 1;
+        let k = &row.knot;
+        let text = fit_cell(&cell_text(row, column.kind), width);
+        match column.kind {
+            ColumnKind::Id => palette.id(&text),
+            ColumnKind::State => palette.state_cell(&k.state, &text),
+            ColumnKind::Type => palette.type_cell(&text),
+            ColumnKind::Tags => palette.tags(&text),
+            ColumnKind::Due
+                if is_overdue(
+                    k,
+                    k.custom_fields.get("due").map_or("", String::as_str),
+                    &today,
+                ) =>
+            {
+                palette.overdue(&text)
+            }
+            _ => text,
+        }
+    })
+}
+/// Unpadded, uncolored text for one table cell.
+fn cell_text(row: &DisplayKnot, kind: ColumnKind) -> String {
+    let k = &row.knot;
+    match kind {
+        ColumnKind::Id => crate::knot_id::display_id(&k.id).to_string(),
+        ColumnKind::Alias => k.alias.clone().unwrap_or_default(),
+        ColumnKind::State => k.state.clone(),
+        ColumnKind::Priority => k.priority.map(|p| p.to_string()).unwrap_or_default(),
+        ColumnKind::Type => k.knot_type.as_str().to_string(),
+        ColumnKind::Tags => k.tags.join(","),
+        ColumnKind::Updated => k.updated_at.get(..10).unwrap_or(&k.updated_at).to_string(),
+        ColumnKind::Assignee => assignee(k),
+        ColumnKind::Due => k.custom_fields.get("due").cloned().unwrap_or_default(),
+        ColumnKind::Title => {
+            let indent = match row.depth {
+                0 => String::new(),
+                depth => format!("{}\u{21b3} ", "  ".repeat(depth - 1)),
+            };
+            format!("{indent}{}", k.title)
+        }
+    }
+}
+/// `columns` widened so no cell in `rows` (or header) gets truncated; the
+/// unbounded trailing title stays unbounded.
+pub(crate) fn untruncated_columns(rows: &[DisplayKnot], columns: &[Column]) -> Vec<Column> {
+    let last = columns.len().saturating_sub(1);
+    columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| Column {
+            kind: column.kind,
+            width: column.width(index == last).map(|width| {
+                rows.iter()
+                    .map(|row| display_width(&cell_text(row, column.kind)))
+                    .chain([width, display_width(&column.kind.header())])
+                    .max()
+                    .unwrap_or(width)
+            }),
+        })
+        .collect()
+}
+/// A `due` date (compared on its `YYYY-MM-DD` prefix) before today on a
+/// knot that is still open.
+fn is_overdue(knot: &KnotView, due: &str, today: &str) -> bool {
+    let (Some(due), Some(today)) = (due.get(..10), today.get(..10)) else {
+        return false;
+    };
+    due < today && !crate::state_hierarchy::is_terminal_state(&knot.state).unwrap_or(false)
+}
+/// The `assignee` custom field, else the agent holding the knot's lease.
+fn assignee(knot: &KnotView) -> String {
+    knot.custom_fields
+        .get("assignee")
+        .cloned()
+        .or_else(|| knot.lease_agent.as_ref().map(|a| a.agent_name.clone()))
+        .unwrap_or_default()
+}
+fn join_cells(columns: &[Column], mut render: impl FnMut(&Column) -> String) -> String {
+    columns
+        .iter()
+        .map(&mut render)
+        .collect::<Vec<_>>()
+        .join("  ")
+        .trim_end()
+        .to_string()
+}
+/// Pads `text` to `width` characters, cutting it with an ellipsis when it
+/// is longer. `None` leaves the text as is.
+/// Pads or truncates `text` to `width` terminal columns, counting wide
+/// (CJK, emoji) characters as two and marking cuts with an ellipsis.
+pub(crate) fn fit_cell(text: &str, width: Option<usize>) -> String {
+    let Some(width) = width else {
+        return text.to_string();
+    };
+    let used = display_width(text);
+    if used <= width {
+        return format!("{text}{}", " ".repeat(width - used));
+    }
+    let cut = byte_index_at_width(text, width.saturating_sub(1));
+    let kept = display_width(&text[..cut]);
+    format!(
+        "{}\u{2026}{}",
+        &text[..cut],
+        " ".repeat(width.saturating_sub(kept + 1))
+    )
+}
 fn indentation_prefix(depth: usize, palette: &Palette) -> String {
     if depth == 0 {
         return String::new();
@@ -148,6 +296,9 @@ fn filter_summary(filter: &KnotListFilter) -> Option<String> {
             parts.push(format!("tags={}", tags.join(",")));
         }
     }
+    for (name, value) in &filter.custom_fields {
+        parts.push(format!("{name}={value}"));
+    }
     if let Some(q) = filter.query.as_deref().and_then(non_empty) {
         parts.push(format!("query={q}"));
     }
@@ -157,278 +308,25 @@ fn filter_summary(filter: &KnotListFilter) -> Option<String> {
         Some(parts.join(" "))
     }
 }
-fn format_knot_show(knot: &KnotView, palette: &Palette, vw: usize, verbose: bool) -> Vec<String> {
-    let fields = knot_show_fields(knot, verbose);
-    let mut lines = format_show_fields(&fields, palette, vw);
-    if !verbose {
-        let hint = hidden_metadata_hint(knot);
-        if !hint.is_empty() {
-            lines.push(String::new());
-            lines.push(palette.dim(&hint));
-        }
-    }
-    lines
-}
-fn format_entry_inline(entry: &crate::domain::metadata::MetadataEntry) -> String {
-    let who = if entry.agentname != "unknown" {
-        &entry.agentname
-    } else {
-        &entry.username
-    };
-    format!(
-        "[{} {}] {}",
-        who,
-        &entry.datetime[..10.min(entry.datetime.len())],
-        entry.content
-    )
-}
-pub fn hidden_metadata_hint(knot: &KnotView) -> String {
-    let mut parts = Vec::new();
-    if knot.notes.len() > 1 {
-        parts.push(older_item_hint(knot.notes.len() - 1, "note", "notes"));
-    }
-    if knot.handoff_capsules.len() > 1 {
-        parts.push(older_item_hint(
-            knot.handoff_capsules.len() - 1,
-            "handoff capsule",
-            "handoff capsules",
-        ));
-    }
-    if parts.is_empty() {
-        return String::new();
-    }
-    format!(
-        "{} not shown. Use -v/--verbose to see all.",
-        parts.join(" and ")
-    )
-}
-fn older_item_hint(count: usize, singular: &str, plural: &str) -> String {
-    format!(
-        "{count} older {}",
-        if count == 1 { singular } else { plural }
-    )
-}
-fn knot_show_fields(knot: &KnotView, verbose: bool) -> Vec<ShowField> {
-    let mut f = vec![ShowField::new("id", crate::knot_id::display_id(&knot.id))];
-    if let Some(a) = knot.alias.as_deref() {
-        f.push(ShowField::new("alias", a));
-    }
-    f.push(ShowField::new("title", knot.title.clone()));
-    f.push(ShowField::new("state", knot.state.clone()));
-    f.push(ShowField::new("updated_at", knot.updated_at.clone()));
-    if let Some(v) = knot.created_at.as_deref() {
-        f.push(ShowField::new("created_at", v));
-    }
-    if let Some(v) = knot.body.as_deref() {
-        f.push(ShowField::new("body", v));
-    }
-    if let Some(v) = knot.description.as_deref() {
-        f.push(ShowField::new("description", v));
-    }
-    if let Some(v) = knot.priority {
-        f.push(ShowField::new("priority", v.to_string()));
-    }
-    f.push(ShowField::new("type", knot.knot_type.as_str()));
-    f.push(ShowField::new("profile_id", knot.profile_id.clone()));
-    if !knot.tags.is_empty() {
-        f.push(ShowField::new("tags", knot.tags.join(", ")));
-    }
-    append_step_metadata_fields(&mut f, knot);
-    append_metadata_fields(&mut f, knot, verbose);
-    append_lease_agent_fields(&mut f, knot);
-    append_gate_fields(&mut f, knot);
-    append_edge_fields(&mut f, knot);
-    f
-}
-fn append_metadata_fields(f: &mut Vec<ShowField>, knot: &KnotView, verbose: bool) {
-    if !knot.notes.is_empty() {
-        if verbose {
-            for e in &knot.notes {
-                f.push(ShowField::new("note", format_entry_inline(e)));
-            }
-        } else if let Some(l) = knot.notes.last() {
-            f.push(ShowField::new("note", format_entry_inline(l)));
-        }
-    }
-    if !knot.handoff_capsules.is_empty() {
-        if verbose {
-            for e in &knot.handoff_capsules {
-                f.push(ShowField::new("handoff_capsule", format_entry_inline(e)));
-            }
-        } else if let Some(l) = knot.handoff_capsules.last() {
-            f.push(ShowField::new("handoff_capsule", format_entry_inline(l)));
-        }
-    }
-    if !knot.invariants.is_empty() {
-        f.push(ShowField::new(
-            "invariants",
-            knot.invariants
-                .iter()
-                .map(|i| i.to_string())
-                .collect::<Vec<_>>()
-                .join(", "),
-        ));
-    }
-}
-fn append_step_metadata_fields(f: &mut Vec<ShowField>, knot: &KnotView) {
-    if let Some(meta) = &knot.step_metadata {
-        f.push(ShowField::new("step_owner", format_step_owner(meta)));
-        if let Some(output) = &meta.output {
-            f.push(ShowField::new("step_artifact", &output.artifact_type));
-        }
-        if let Some(hint) = &meta.review_hint {
-            f.push(ShowField::new("step_review_hint", hint));
-        }
-    }
-    if let Some(meta) = &knot.next_step_metadata {
-        f.push(ShowField::new("next_owner", format_step_owner(meta)));
-        if let Some(output) = &meta.output {
-            f.push(ShowField::new("next_artifact", &output.artifact_type));
-        }
-        if let Some(hint) = &meta.review_hint {
-            f.push(ShowField::new("next_review_hint", hint));
-        }
-    }
-}
-
-fn format_step_owner(meta: &crate::workflow::StepMetadata) -> String {
-    match &meta.owner {
-        Some(o) => match o.kind {
-            crate::workflow::OwnerKind::Human => "human".to_string(),
-            crate::workflow::OwnerKind::Agent => "agent".to_string(),
-        },
-        None => "unspecified".to_string(),
-    }
-}
-
-fn append_gate_fields(f: &mut Vec<ShowField>, knot: &KnotView) {
-    if let Some(g) = knot.gate.as_ref() {
-        f.push(ShowField::new("gate_owner_kind", g.owner_kind.to_string()));
-        if !g.failure_modes.is_empty() {
-            f.push(ShowField::new(
-                "gate_failure_modes",
-                g.failure_modes
-                    .iter()
-                    .map(|(i, t)| format!("{i} => {}", t.join(", ")))
-                    .collect::<Vec<_>>()
-                    .join("\n"),
-            ));
-        }
-    }
-}
-
-fn append_lease_agent_fields(f: &mut Vec<ShowField>, knot: &KnotView) {
-    let Some(agent) = knot.lease_agent.as_ref() else {
-        return;
-    };
-    f.push(ShowField::new(
-        "lease_agent",
-        format!(
-            "agent_type={} provider={} agent_name={} model={} model_version={}",
-            agent.agent_type, agent.provider, agent.agent_name, agent.model, agent.model_version
-        ),
-    ));
-}
-
-fn append_edge_fields(f: &mut Vec<ShowField>, knot: &KnotView) {
-    if !knot.edges.is_empty() {
-        for (kind, targets) in &group_edges_by_kind(&knot.edges, &knot.id) {
-            f.push(ShowField::new(kind, targets.join(", ")));
-        }
-    }
-}
-fn group_edges_by_kind(
-    edges: &[crate::app::EdgeView],
-    knot_id: &str,
-) -> Vec<(String, Vec<String>)> {
-    use std::collections::BTreeMap;
-    let mut g: BTreeMap<String, Vec<String>> = BTreeMap::new();
-    for e in edges {
-        let (l, t) = if e.src == knot_id {
-            (
-                e.kind.clone(),
-                crate::knot_id::display_id(&e.dst).to_string(),
-            )
-        } else {
-            (
-                format!("{} (incoming)", e.kind),
-                crate::knot_id::display_id(&e.src).to_string(),
-            )
-        };
-        g.entry(l).or_default().push(t);
-    }
-    g.into_iter().collect()
-}
-fn format_show_fields(fields: &[ShowField], palette: &Palette, vw: usize) -> Vec<String> {
-    if fields.is_empty() {
-        return Vec::new();
-    }
-    let lw = fields.iter().map(|f| f.label.len() + 1).max().unwrap_or(0);
-    let mut lines = Vec::new();
-    for field in fields {
-        let wrapped = wrap_value(&field.value, vw.max(1));
-        let label = format!("{}:", field.label);
-        for (i, chunk) in wrapped.iter().enumerate() {
-            let lt = if i == 0 {
-                format!("{label:>lw$}")
-            } else {
-                " ".repeat(lw)
-            };
-            lines.push(format!("{}  {}", palette.label(&lt), chunk));
-        }
-    }
-    lines
-}
-fn wrap_value(value: &str, width: usize) -> Vec<String> {
-    if value.is_empty() {
-        return vec![String::new()];
-    }
-    value
-        .split('\n')
-        .flat_map(|l| wrap_single_line(l, width))
-        .collect()
-}
-fn wrap_single_line(line: &str, width: usize) -> Vec<String> {
-    if line.is_empty() {
-        return vec![String::new()];
-    }
-    let mut w = Vec::new();
-    let mut r = line.trim_end_matches('\r');
-    while char_count(r) > width {
-        let si = wrap_split_index(r, width);
-        w.push(r[..si].trim_end().to_string());
-        r = r[si..].trim_start();
-        if r.is_empty() {
-            break;
-        }
-    }
-    w.push(r.to_string());
-    w
-}
-fn wrap_split_index(text: &str, width: usize) -> usize {
-    let mut lw = None;
-    for (idx, ch, count) in indexed_chars(text) {
-        if count > width {
-            break;
-        }
-        if ch.is_whitespace() {
-            lw = Some(idx);
-        }
-    }
-    lw.unwrap_or_else(|| byte_index_at_char(text, width))
-}
-fn char_count(text: &str) -> usize {
-    text.chars().count()
+/// Terminal columns `text` occupies.
+pub(crate) fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
 }
+/// Each char's byte index with the display width up to and including it.
 fn indexed_chars(text: &str) -> impl Iterator<Item = (usize, char, usize)> + '_ {
-    text.char_indices()
-        .enumerate()
-        .map(|(pos, (idx, ch))| (idx, ch, pos + 1))
+    text.char_indices().scan(0, |used, (idx, ch)| {
+        *used += UnicodeWidthChar::width(ch).unwrap_or(0);
+        Some((idx, ch, *used))
+    })
+}
+/// Byte index of the first char that would not fit in `width` columns.
+fn byte_index_at_width(text: &str, width: usize) -> usize {
+    indexed_chars(text)
+        .find(|(_, _, used)| *used > width)
+        .map_or(text.len(), |(idx, _, _)| idx)
 }
-fn byte_index_at_char(text: &str, tc: usize) -> usize {
-    text.char_indices()
-        .nth(tc)
-        .map_or(text.len(), |(idx, _)| idx)
+fn first_char_len(text: &str) -> usize {
+    text.chars().next().map_or(0, char::len_utf8)
 }
 fn non_empty(raw: &str) -> Option<&str> {
     let t = raw.trim();
@@ -1,11 +1,15 @@
-use std::io::{self, IsTerminal};
+use super::theme::{stdout_color_enabled, Theme};
+
 pub(crate) struct Palette {
-    pub(super) enabled: bool,
+    pub(crate) enabled: bool,
+    pub(crate) theme: Theme,
 }
 impl Palette {
     pub(crate) fn auto() -> Self {
+        let theme = Theme::from_env();
         Self {
-            enabled: std::env::var_os("NO_COLOR").is_none() && io::stdout().is_terminal(),
+            enabled: theme != Theme::None && stdout_color_enabled(),
+            theme,
         }
     }
     pub(crate) fn paint(&self, code: &str, text: &str) -> String {
@@ -16,49 +20,40 @@ impl Palette {
         }
     }
     pub(crate) fn heading(&self, text: &str) -> String {
-        self.paint("1;36", text)
+        self.paint(self.theme.colors().heading, text)
     }
     pub(crate) fn label(&self, text: &str) -> String {
-        self.paint("36", text)
+        self.paint(self.theme.colors().label, text)
     }
     pub(crate) fn dim(&self, text: &str) -> String {
-        self.paint("2", text)
+        self.paint(self.theme.colors().dim, text)
     }
     pub(crate) fn id(&self, text: &str) -> String {
-        self.paint("1;94", text)
+        self.paint(self.theme.colors().id, text)
     }
     pub(crate) fn state(&self, state: &str) -> String {
         let u = state.to_ascii_uppercase();
-        self.paint(state_color_code(state), &format!("[{u}]"))
+        self.paint(self.theme.state_code(state), &format!("[{u}]"))
+    }
+    pub(crate) fn state_cell(&self, state: &str, text: &str) -> String {
+        self.paint(self.theme.state_code(state), text)
     }
     pub(crate) fn type_label(&self, knot_type: &str) -> String {
-        self.paint("35", &format!("({knot_type})"))
+        self.paint(self.theme.colors().type_label, &format!("({knot_type})"))
+    }
+    pub(crate) fn type_cell(&self, text: &str) -> String {
+        self.paint(self.theme.colors().type_label, text)
     }
     pub(crate) fn tags(&self, text: &str) -> String {
-        self.paint("90", text)
+        self.paint(self.theme.colors().tags, text)
+    }
+    pub(crate) fn overdue(&self, text: &str) -> String {
+        self.paint(self.theme.colors().overdue, text)
     }
 }
+#[cfg(test)]
 pub(crate) fn state_color_code(state: &str) -> &'static str {
-    match state.trim().to_ascii_lowercase().as_str() {
-        "planning"
-        | "plan_review"
-        | "evaluating"
-        | "implementation"
-        | "implementation_review"
-        | "shipment"
-        | "shipment_review" => "32",
-        "ready_for_planning"
-        | "ready_for_plan_review"
-        | "ready_to_evaluate"
-        | "ready_for_implementation"
-        | "ready_for_implementation_review"
-        | "ready_for_shipment"
-        | "ready_for_shipment_review" => "33",
-        "abandoned" => "31",
-        "shipped" => "34",
-        "deferred" => "35",
-        _ => "37",
-    }
+    Theme::Default.state_code(state)
 }
 pub(super) struct ShowField {
     pub(super) label: String,
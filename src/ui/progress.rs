@@ -1,12 +1,20 @@
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
 
-use crate::progress::{ProgressKind, ProgressReporter};
+use serde_json::json;
+
+use crate::progress::{ProgressKind, ProgressReporter, PullPhase, PullProgress};
 
 use super::Palette;
 
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+const JSON_PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
 pub(crate) struct StdoutProgressReporter {
     stdout: io::Stdout,
     palette: Palette,
+    spinner: Option<Spinner>,
 }
 
 impl StdoutProgressReporter {
@@ -14,12 +22,16 @@ impl StdoutProgressReporter {
         Self {
             stdout: io::stdout(),
             palette: Palette::auto(),
+            spinner: io::stderr().is_terminal().then(Spinner::default),
         }
     }
 }
 
 impl ProgressReporter for StdoutProgressReporter {
     fn emit(&mut self, kind: ProgressKind, message: &str) -> io::Result<()> {
+        if let Some(spinner) = self.spinner.as_mut() {
+            spinner.clear()?;
+        }
         writeln!(
             self.stdout,
             "{}",
@@ -27,6 +39,151 @@ impl ProgressReporter for StdoutProgressReporter {
         )?;
         self.stdout.flush()
     }
+
+    fn update(&mut self, progress: &PullProgress) -> io::Result<()> {
+        match self.spinner.as_mut() {
+            Some(spinner) => spinner.draw(progress),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for StdoutProgressReporter {
+    fn drop(&mut self) {
+        if let Some(spinner) = self.spinner.as_mut() {
+            let _ = spinner.clear();
+        }
+    }
+}
+
+/// A single stderr line redrawn in place while a pull counts through its
+/// phases.
+#[derive(Default)]
+struct Spinner {
+    throttle: Throttle,
+    frame: usize,
+    drawn: bool,
+}
+
+impl Spinner {
+    fn draw(&mut self, progress: &PullProgress) -> io::Result<()> {
+        if !self.throttle.ready(progress, SPINNER_INTERVAL) {
+            return Ok(());
+        }
+        self.frame = (self.frame + 1) % SPINNER_FRAMES.len();
+        self.drawn = true;
+        let mut stderr = io::stderr();
+        write!(
+            stderr,
+            "\r\x1b[2K{}",
+            format_pull_progress(SPINNER_FRAMES[self.frame], progress)
+        )?;
+        stderr.flush()
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        if !std::mem::take(&mut self.drawn) {
+            return Ok(());
+        }
+        let mut stderr = io::stderr();
+        write!(stderr, "\r\x1b[2K")?;
+        stderr.flush()
+    }
+}
+
+/// Writes one JSON record per line for wrappers (`kno pull --json-progress`):
+/// `{"type":"message",...}` for each stage and `{"type":"progress",...}`
+/// at most every half second per phase, plus each phase's final count.
+pub(crate) struct JsonProgressReporter<W: Write> {
+    out: W,
+    throttle: Throttle,
+    interval: Duration,
+}
+
+impl JsonProgressReporter<io::Stderr> {
+    pub(crate) fn stderr() -> Self {
+        Self::new(io::stderr(), JSON_PROGRESS_INTERVAL)
+    }
+}
+
+impl<W: Write> JsonProgressReporter<W> {
+    pub(crate) fn new(out: W, interval: Duration) -> Self {
+        Self {
+            out,
+            throttle: Throttle::default(),
+            interval,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn write_record(&mut self, record: serde_json::Value) -> io::Result<()> {
+        writeln!(self.out, "{record}")?;
+        self.out.flush()
+    }
+}
+
+impl<W: Write> ProgressReporter for JsonProgressReporter<W> {
+    fn emit(&mut self, kind: ProgressKind, message: &str) -> io::Result<()> {
+        let kind = match kind {
+            ProgressKind::Stage => "stage",
+            ProgressKind::Info => "info",
+            ProgressKind::Success => "success",
+            ProgressKind::Warn => "warn",
+        };
+        self.write_record(json!({"type": "message", "kind": kind, "message": message}))
+    }
+
+    fn update(&mut self, progress: &PullProgress) -> io::Result<()> {
+        if !self.throttle.ready(progress, self.interval) {
+            return Ok(());
+        }
+        self.write_record(json!({
+            "type": "progress",
+            "phase": progress.phase,
+            "done": progress.done,
+            "total": progress.total,
+            "partition": progress.partition,
+        }))
+    }
+}
+
+/// Lets the first update of a phase, its final count, and anything at
+/// least `interval` after the last one through.
+#[derive(Default)]
+struct Throttle {
+    last: Option<(PullPhase, Instant)>,
+}
+
+impl Throttle {
+    fn ready(&mut self, progress: &PullProgress, interval: Duration) -> bool {
+        let now = Instant::now();
+        let ready = match self.last {
+            Some((phase, at)) if phase == progress.phase => {
+                progress.is_complete() || now.duration_since(at) >= interval
+            }
+            _ => true,
+        };
+        if ready {
+            self.last = Some((progress.phase, now));
+        }
+        ready
+    }
+}
+
+pub(crate) fn format_pull_progress(frame: char, progress: &PullProgress) -> String {
+    let label = match progress.phase {
+        PullPhase::Fetch => "fetching objects",
+        PullPhase::Apply => "applying event files",
+    };
+    let mut line = format!("{frame} {label} {}/{}", progress.done, progress.total);
+    if let Some(partition) = &progress.partition {
+        line.push_str(&format!(" ({partition})"));
+    }
+    line
 }
 
 pub(crate) fn format_progress_line(palette: &Palette, kind: ProgressKind, message: &str) -> String {
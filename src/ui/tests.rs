@@ -1,4 +1,4 @@
-use super::{filter_summary, format_show_fields, knot_show_fields, Palette, ShowField};
+use super::{filter_summary, format_show_fields, knot_show_fields, Palette, ShowField, Theme};
 use crate::app::KnotView;
 use crate::domain::lease::AgentInfo;
 use crate::domain::metadata::MetadataEntry;
@@ -11,6 +11,7 @@ fn filter_summary_formats_only_active_filters() {
         knot_type: Some("task".into()),
         profile_id: Some("default".into()),
         tags: vec!["release".into(), "".into()],
+        custom_fields: Vec::new(),
         query: Some("sync".into()),
     };
     assert_eq!(
@@ -30,6 +31,7 @@ fn filter_summary_includes_all_flag() {
         knot_type: None,
         profile_id: None,
         tags: Vec::new(),
+        custom_fields: Vec::new(),
         query: None,
     };
     assert_eq!(filter_summary(&f).expect("s"), "all=true");
@@ -40,7 +42,10 @@ fn show_fields_right_align_labels() {
         ShowField::new("id", "knot-123"),
         ShowField::new("profile_id", "default"),
     ];
-    let p = Palette { enabled: false };
+    let p = Palette {
+        enabled: false,
+        theme: Theme::Default,
+    };
     let l = format_show_fields(&f, &p, 80);
     assert_eq!(l[0], "        id:  knot-123");
     assert_eq!(l[1], "profile_id:  default");
@@ -50,7 +55,10 @@ fn show_fields_wrap_values() {
     let v = format!("{} {}", "a".repeat(40), "b".repeat(50));
     let l = format_show_fields(
         &[ShowField::new("body", v)],
-        &Palette { enabled: false },
+        &Palette {
+            enabled: false,
+            theme: Theme::Default,
+        },
         20,
     );
     assert_eq!(l.len(), 5);
@@ -77,9 +85,11 @@ fn minimal_knot() -> KnotView {
         body: None,
         description: None,
         acceptance: None,
+        estimate: None,
         priority: None,
         knot_type: crate::domain::knot_type::KnotType::default(),
         tags: vec![],
+        custom_fields: Default::default(),
         notes: vec![],
         handoff_capsules: vec![],
         invariants: vec![],
@@ -95,10 +105,15 @@ fn minimal_knot() -> KnotView {
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: None,
+        snoozed_until: None,
         step_metadata: None,
         next_step_metadata: None,
         edges: vec![],
         child_summaries: vec![],
+        git_links: vec![],
+        criteria: Vec::new(),
+        handbacks: Vec::new(),
+        plan: None,
     }
 }
 #[test]
@@ -112,9 +127,11 @@ fn knot_show_fields_include_optional_sections() {
         body: Some("Body".into()),
         description: Some("Desc".into()),
         acceptance: None,
+        estimate: None,
         priority: Some(2),
         knot_type: crate::domain::knot_type::KnotType::Work,
         tags: vec!["cli".into()],
+        custom_fields: Default::default(),
         notes: vec![MetadataEntry {
             entry_id: "n1".into(),
             content: "note".into(),
@@ -146,10 +163,15 @@ fn knot_show_fields_include_optional_sections() {
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: Some("2026-02-25T14:00:00Z".into()),
+        snoozed_until: None,
         step_metadata: None,
         next_step_metadata: None,
         edges: vec![],
         child_summaries: vec![],
+        git_links: vec![],
+        criteria: Vec::new(),
+        handbacks: Vec::new(),
+        plan: None,
     };
     let labels = knot_show_fields(&k, false)
         .iter()
@@ -243,7 +265,16 @@ fn trim_json_no_other() {
 fn show_hint_hidden() {
     let mut k = minimal_knot();
     k.notes = vec![make_entry("n1", "a"), make_entry("n2", "b")];
-    let j = super::format_knot_show(&k, &Palette { enabled: false }, 80, false).join("\n");
+    let j = super::format_knot_show(
+        &k,
+        &Palette {
+            enabled: false,
+            theme: Theme::Default,
+        },
+        80,
+        false,
+    )
+    .join("\n");
     assert!(j.contains("1 older note"));
     assert!(j.contains("-v/--verbose"));
 }
@@ -251,7 +282,15 @@ fn show_hint_hidden() {
 fn show_verbose_no_hint() {
     let mut k = minimal_knot();
     k.notes = vec![make_entry("n1", "a"), make_entry("n2", "b")];
-    let l = super::format_knot_show(&k, &Palette { enabled: false }, 80, true);
+    let l = super::format_knot_show(
+        &k,
+        &Palette {
+            enabled: false,
+            theme: Theme::Default,
+        },
+        80,
+        true,
+    );
     assert!(!l.join("\n").contains("not shown"));
     assert_eq!(l.iter().filter(|x| x.contains("note:")).count(), 2);
 }
@@ -0,0 +1,163 @@
+/// Named color schemes for terminal output. `none` turns color off even on
+/// a terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum Theme {
+    #[default]
+    Default,
+    Light,
+    Solarized,
+    None,
+}
+
+/// SGR codes for each painted role.
+pub(crate) struct ThemeColors {
+    pub(crate) heading: &'static str,
+    pub(crate) label: &'static str,
+    pub(crate) dim: &'static str,
+    pub(crate) id: &'static str,
+    pub(crate) type_label: &'static str,
+    pub(crate) tags: &'static str,
+    pub(crate) overdue: &'static str,
+    active: &'static str,
+    queue: &'static str,
+    blocked: &'static str,
+    abandoned: &'static str,
+    shipped: &'static str,
+    deferred: &'static str,
+    other: &'static str,
+}
+
+const DEFAULT: ThemeColors = ThemeColors {
+    heading: "1;36",
+    label: "36",
+    dim: "2",
+    id: "1;94",
+    type_label: "35",
+    tags: "90",
+    overdue: "1;31",
+    active: "32",
+    queue: "33",
+    blocked: "1;91",
+    abandoned: "31",
+    shipped: "34",
+    deferred: "35",
+    other: "37",
+};
+
+/// Avoids yellow and bright white, which wash out on light backgrounds.
+const LIGHT: ThemeColors = ThemeColors {
+    heading: "1;34",
+    label: "34",
+    dim: "2",
+    id: "1;35",
+    type_label: "35",
+    tags: "2;30",
+    overdue: "1;31",
+    active: "32",
+    queue: "36",
+    blocked: "1;31",
+    abandoned: "31",
+    shipped: "34",
+    deferred: "2;35",
+    other: "30",
+};
+
+/// Ethan Schoonover's Solarized accents as 256-color codes.
+const SOLARIZED: ThemeColors = ThemeColors {
+    heading: "1;38;5;33",
+    label: "38;5;37",
+    dim: "38;5;240",
+    id: "1;38;5;33",
+    type_label: "38;5;125",
+    tags: "38;5;245",
+    overdue: "1;38;5;166",
+    active: "38;5;64",
+    queue: "38;5;136",
+    blocked: "1;38;5;166",
+    abandoned: "38;5;160",
+    shipped: "38;5;33",
+    deferred: "38;5;61",
+    other: "38;5;244",
+};
+
+impl Theme {
+    pub(crate) fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "default" => Some(Self::Default),
+            "light" => Some(Self::Light),
+            "solarized" => Some(Self::Solarized),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+
+    /// `KNOTS_THEME`, else `theme` in the user config, else the default.
+    /// Unknown names fall back to the default rather than failing output.
+    pub(crate) fn from_env() -> Self {
+        std::env::var("KNOTS_THEME")
+            .ok()
+            .or_else(|| {
+                crate::project::read_global_config(None)
+                    .ok()
+                    .and_then(|config| config.theme)
+            })
+            .and_then(|name| Self::parse(&name))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn colors(self) -> &'static ThemeColors {
+        match self {
+            Self::Default | Self::None => &DEFAULT,
+            Self::Light => &LIGHT,
+            Self::Solarized => &SOLARIZED,
+        }
+    }
+
+    pub(crate) fn state_code(self, state: &str) -> &'static str {
+        let colors = self.colors();
+        match state.trim().to_ascii_lowercase().as_str() {
+            "planning"
+            | "plan_review"
+            | "evaluating"
+            | "implementation"
+            | "implementation_review"
+            | "shipment"
+            | "shipment_review" => colors.active,
+            "ready_for_planning"
+            | "ready_for_plan_review"
+            | "ready_to_evaluate"
+            | "ready_for_implementation"
+            | "ready_for_implementation_review"
+            | "ready_for_shipment"
+            | "ready_for_shipment_review" => colors.queue,
+            "blocked" => colors.blocked,
+            "abandoned" => colors.abandoned,
+            "shipped" => colors.shipped,
+            "deferred" => colors.deferred,
+            _ => colors.other,
+        }
+    }
+}
+
+/// Whether to emit color: `NO_COLOR` always wins, then a non-`0`
+/// `CLICOLOR_FORCE` forces it on, otherwise only when stdout is a terminal.
+pub(crate) fn color_enabled(no_color: bool, force: Option<&str>, is_terminal: bool) -> bool {
+    if no_color {
+        return false;
+    }
+    match force {
+        Some(value) if !value.is_empty() && value != "0" => true,
+        _ => is_terminal,
+    }
+}
+
+/// `color_enabled` for the current process's stdout.
+pub(crate) fn stdout_color_enabled() -> bool {
+    use std::io::IsTerminal;
+    let force = std::env::var("CLICOLOR_FORCE").ok();
+    color_enabled(
+        std::env::var_os("NO_COLOR").is_some(),
+        force.as_deref(),
+        std::io::stdout().is_terminal(),
+    )
+}
@@ -1,14 +1,18 @@
+use std::time::Duration;
+
 use super::{
-    format_doctor_line, format_doctor_line_with_width, format_knot_row, format_progress_line,
-    format_show_fields, indentation_prefix, knot_show_fields, print_doctor_report, print_knot_list,
-    print_knot_show, state_color_code, wrap_split_index, wrap_value, Palette, ShowField,
+    color_enabled, display_width, fit_cell, format_doctor_line, format_doctor_line_with_width,
+    format_knot_row, format_progress_line, format_pull_progress, format_show_fields,
+    format_table_header, format_table_row, indentation_prefix, knot_show_fields,
+    print_doctor_report, print_knot_list, print_knot_show, state_color_code, untruncated_columns,
+    wrap_split_index, wrap_value, Palette, ShowField, Theme,
 };
 use crate::app::KnotView;
 use crate::doctor::{DoctorCheck, DoctorReport, DoctorStatus};
 use crate::domain::metadata::MetadataEntry;
-use crate::list_layout::DisplayKnot;
+use crate::list_layout::{parse_columns, DisplayKnot};
 use crate::listing::KnotListFilter;
-use crate::progress::ProgressKind;
+use crate::progress::{ProgressKind, ProgressReporter, PullPhase, PullProgress};
 
 fn sample_knot() -> KnotView {
     KnotView {
@@ -20,9 +24,11 @@ fn sample_knot() -> KnotView {
         body: Some("Long body for wrapping".to_string()),
         description: Some("Description".to_string()),
         acceptance: None,
+        estimate: None,
         priority: Some(2),
         knot_type: crate::domain::knot_type::KnotType::Work,
         tags: vec!["alpha".to_string(), "beta".to_string()],
+        custom_fields: Default::default(),
         notes: vec![MetadataEntry {
             entry_id: "n1".to_string(),
             content: "note".to_string(),
@@ -54,16 +60,24 @@ fn sample_knot() -> KnotView {
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: Some("2026-02-24T10:00:00Z".to_string()),
+        snoozed_until: None,
         step_metadata: None,
         next_step_metadata: None,
         edges: vec![],
         child_summaries: vec![],
+        git_links: vec![],
+        criteria: Vec::new(),
+        handbacks: Vec::new(),
+        plan: None,
     }
 }
 
 #[test]
 fn row_and_indent_formatting_cover_alias_tag_and_type_paths() {
-    let palette = Palette { enabled: false };
+    let palette = Palette {
+        enabled: false,
+        theme: Theme::Default,
+    };
     assert_eq!(indentation_prefix(0, &palette), "");
     assert!(indentation_prefix(2, &palette).contains("↳"));
 
@@ -106,11 +120,17 @@ fn wrap_helpers_cover_empty_multiline_and_no_whitespace_paths() {
 
 #[test]
 fn palette_and_state_color_cover_all_branches() {
-    let enabled = Palette { enabled: true };
+    let enabled = Palette {
+        enabled: true,
+        theme: Theme::Default,
+    };
     assert!(enabled.paint("36", "x").contains("\u{1b}[36m"));
-    assert!(!Palette { enabled: false }
-        .paint("36", "x")
-        .contains("\u{1b}["));
+    assert!(!Palette {
+        enabled: false,
+        theme: Theme::Default
+    }
+    .paint("36", "x")
+    .contains("\u{1b}["));
     assert!(enabled.heading("h").contains('h'));
     assert!(enabled.label("l").contains('l'));
     assert!(enabled.dim("d").contains('d'));
@@ -137,7 +157,10 @@ fn palette_and_state_color_cover_all_branches() {
 
 #[test]
 fn show_and_print_paths_cover_empty_field_and_public_print_functions() {
-    let palette = Palette { enabled: false };
+    let palette = Palette {
+        enabled: false,
+        theme: Theme::Default,
+    };
     assert!(format_show_fields(&[], &palette, 20).is_empty());
 
     let fields = knot_show_fields(&sample_knot(), false);
@@ -155,8 +178,8 @@ fn show_and_print_paths_cover_empty_field_and_public_print_functions() {
         depth: 1,
     };
 
-    print_knot_list(&[], &filter);
-    print_knot_list(&[row], &filter);
+    print_knot_list(&[], &filter, &[]);
+    print_knot_list(&[row], &filter, &[]);
     print_knot_show(&sample_knot(), false);
     print_knot_show(&sample_knot(), true);
 }
@@ -168,7 +191,10 @@ fn doctor_pass_renders_green_checkmark() {
         status: DoctorStatus::Pass,
         detail: "all good".to_string(),
     };
-    let palette = Palette { enabled: true };
+    let palette = Palette {
+        enabled: true,
+        theme: Theme::Default,
+    };
     let line = format_doctor_line(&check, &palette);
     assert!(line.contains("\u{2713}"), "should contain checkmark");
     assert!(line.contains("\x1b[32m"), "should contain green ANSI code");
@@ -183,7 +209,10 @@ fn doctor_warn_renders_yellow_warning() {
         status: DoctorStatus::Warn,
         detail: "locks busy".to_string(),
     };
-    let palette = Palette { enabled: true };
+    let palette = Palette {
+        enabled: true,
+        theme: Theme::Default,
+    };
     let line = format_doctor_line(&check, &palette);
     assert!(line.contains("\u{26a0}"), "should contain warning icon");
     assert!(line.contains("\x1b[33m"), "should contain yellow ANSI code");
@@ -196,7 +225,10 @@ fn doctor_fail_renders_red_x() {
         status: DoctorStatus::Fail,
         detail: "not a git repo".to_string(),
     };
-    let palette = Palette { enabled: true };
+    let palette = Palette {
+        enabled: true,
+        theme: Theme::Default,
+    };
     let line = format_doctor_line(&check, &palette);
     assert!(line.contains("\u{2717}"), "should contain X mark");
     assert!(line.contains("\x1b[31m"), "should contain red ANSI code");
@@ -209,7 +241,10 @@ fn doctor_no_color_omits_ansi_codes() {
         status: DoctorStatus::Pass,
         detail: "origin reachable".to_string(),
     };
-    let palette = Palette { enabled: false };
+    let palette = Palette {
+        enabled: false,
+        theme: Theme::Default,
+    };
     let line = format_doctor_line(&check, &palette);
     assert!(!line.contains("\x1b["), "should not contain ANSI codes");
     assert!(line.contains("\u{2713}"));
@@ -224,7 +259,10 @@ fn doctor_lines_align_titles_when_label_width_is_provided() {
         status: DoctorStatus::Warn,
         detail: "origin unreachable".to_string(),
     };
-    let palette = Palette { enabled: false };
+    let palette = Palette {
+        enabled: false,
+        theme: Theme::Default,
+    };
     let line = format_doctor_line_with_width(&check, &palette, 12);
     assert!(line.starts_with("     remote:"));
     assert!(line.contains("⚠ origin unreachable"));
@@ -257,7 +295,10 @@ fn print_doctor_report_covers_all_statuses() {
 #[test]
 fn progress_lines_use_palette_colors_and_plain_fallback() {
     let colored = format_progress_line(
-        &Palette { enabled: true },
+        &Palette {
+            enabled: true,
+            theme: Theme::Default,
+        },
         ProgressKind::Stage,
         "preparing knots worktree",
     );
@@ -265,16 +306,194 @@ fn progress_lines_use_palette_colors_and_plain_fallback() {
     assert!(colored.contains("preparing knots worktree"));
 
     let success = format_progress_line(
-        &Palette { enabled: false },
+        &Palette {
+            enabled: false,
+            theme: Theme::Default,
+        },
         ProgressKind::Success,
         "push complete at abc123",
     );
     assert_eq!(success, "✓ push complete at abc123");
 
     let warn = format_progress_line(
-        &Palette { enabled: false },
+        &Palette {
+            enabled: false,
+            theme: Theme::Default,
+        },
         ProgressKind::Warn,
         "origin/knots is unavailable",
     );
     assert_eq!(warn, "! origin/knots is unavailable");
 }
+
+#[test]
+fn table_rows_pad_and_truncate_configured_columns() {
+    let palette = Palette {
+        enabled: false,
+        theme: Theme::Default,
+    };
+    let columns = parse_columns("id:4,priority,state:8,title").expect("columns");
+    assert_eq!(
+        format_table_header(&columns, &palette),
+        "ID    PRI  STATE     TITLE"
+    );
+    let mut knot = sample_knot();
+    knot.custom_fields
+        .insert("due".to_string(), "2026-03-01".to_string());
+    let row = DisplayKnot { knot, depth: 1 };
+    assert_eq!(
+        format_table_row(&row, &columns, &palette),
+        "1     2    impleme\u{2026}  \u{21b3} Sample knot"
+    );
+    let due = parse_columns("due,alias").expect("columns");
+    assert_eq!(format_table_row(&row, &due, &palette), "2026-03-01  A.1");
+
+    assert_eq!(fit_cell("abc", Some(5)), "abc  ");
+    assert_eq!(fit_cell("abcdef", Some(4)), "abc\u{2026}");
+    assert_eq!(fit_cell("abcdef", None), "abcdef");
+}
+
+#[test]
+fn themes_parse_and_give_blocked_its_own_color() {
+    assert_eq!(Theme::parse(" Solarized "), Some(Theme::Solarized));
+    assert_eq!(Theme::parse("light"), Some(Theme::Light));
+    assert_eq!(Theme::parse("none"), Some(Theme::None));
+    assert_eq!(Theme::parse("neon"), None);
+    for theme in [Theme::Default, Theme::Light, Theme::Solarized] {
+        let blocked = theme.state_code("blocked");
+        assert_ne!(blocked, theme.state_code("ready_for_implementation"));
+        assert_ne!(blocked, theme.state_code("implementation"));
+    }
+    assert_eq!(state_color_code("blocked"), "1;91");
+    assert_ne!(
+        Theme::Light.state_code("ready_for_planning"),
+        Theme::Default.state_code("ready_for_planning")
+    );
+    let solarized = Palette {
+        enabled: true,
+        theme: Theme::Solarized,
+    };
+    assert!(solarized.heading("h").contains("\u{1b}[1;38;5;33m"));
+}
+
+#[test]
+fn color_enabled_respects_no_color_and_clicolor_force() {
+    assert!(color_enabled(false, None, true));
+    assert!(!color_enabled(false, None, false));
+    assert!(color_enabled(false, Some("1"), false));
+    assert!(!color_enabled(false, Some("0"), false));
+    assert!(!color_enabled(false, Some(""), false));
+    assert!(!color_enabled(true, Some("1"), true));
+}
+
+#[test]
+fn table_due_cell_highlights_overdue_open_knots() {
+    let palette = Palette {
+        enabled: true,
+        theme: Theme::Default,
+    };
+    let columns = parse_columns("id,due").expect("columns should parse");
+    let mut knot = sample_knot();
+    knot.custom_fields
+        .insert("due".to_string(), "2000-01-01".to_string());
+    let row = DisplayKnot {
+        knot: knot.clone(),
+        depth: 0,
+    };
+    assert!(format_table_row(&row, &columns, &palette).contains("\u{1b}[1;31m2000-01-01"));
+
+    knot.state = "shipped".to_string();
+    let shipped = DisplayKnot { knot, depth: 0 };
+    assert!(!format_table_row(&shipped, &columns, &palette).contains("\u{1b}[1;31m"));
+}
+
+#[test]
+fn table_cells_pad_and_truncate_by_display_width() {
+    assert_eq!(display_width("日本語"), 6);
+    assert_eq!(fit_cell("日本語", Some(8)), "日本語  ");
+    // A wide char that would straddle the cut is dropped and padded over.
+    assert_eq!(fit_cell("日本語テキスト", Some(6)), "日本\u{2026} ");
+    assert_eq!(display_width(&fit_cell("🚀 launch plan", Some(6))), 6);
+    assert_eq!(
+        wrap_value("日本語 テキスト", 8),
+        vec!["日本語".to_string(), "テキスト".to_string()]
+    );
+    assert_eq!(
+        wrap_value("日本", 1),
+        vec!["日".to_string(), "本".to_string()]
+    );
+
+    let mut knot = sample_knot();
+    knot.title = "修正: 长标题".to_string();
+    let rows = vec![DisplayKnot { knot, depth: 0 }];
+    let columns = parse_columns("title:4,id").expect("columns");
+    let palette = Palette {
+        enabled: false,
+        theme: Theme::Default,
+    };
+    assert_eq!(
+        format_table_row(&rows[0], &columns, &palette),
+        "修\u{2026}   1"
+    );
+    let wide = untruncated_columns(&rows, &columns);
+    assert_eq!(wide[0].width, Some(12));
+    assert_eq!(wide[1].width, Some(10));
+    assert_eq!(
+        format_table_row(&rows[0], &wide, &palette),
+        "修正: 长标题  1"
+    );
+}
+
+fn pull_progress(phase: PullPhase, done: u64, total: u64) -> PullProgress {
+    PullProgress {
+        phase,
+        done,
+        total,
+        partition: (phase == PullPhase::Apply).then(|| "2026/03/05".to_string()),
+    }
+}
+
+#[test]
+fn pull_progress_line_names_phase_counts_and_partition() {
+    assert_eq!(
+        format_pull_progress('⠋', &pull_progress(PullPhase::Fetch, 450, 1000)),
+        "⠋ fetching objects 450/1000"
+    );
+    assert_eq!(
+        format_pull_progress('⠙', &pull_progress(PullPhase::Apply, 3, 9)),
+        "⠙ applying event files 3/9 (2026/03/05)"
+    );
+}
+
+#[test]
+fn json_progress_throttles_within_a_phase_but_keeps_final_counts() {
+    let mut reporter = super::JsonProgressReporter::new(Vec::new(), Duration::from_secs(60));
+    reporter
+        .emit(ProgressKind::Stage, "importing knots updates")
+        .expect("emit");
+    for done in 1..=3 {
+        reporter
+            .update(&pull_progress(PullPhase::Fetch, done, 3))
+            .expect("update");
+    }
+    for done in 1..=2 {
+        reporter
+            .update(&pull_progress(PullPhase::Apply, done, 4))
+            .expect("update");
+    }
+    let out = String::from_utf8(reporter.into_inner()).expect("utf8");
+    let records: Vec<serde_json::Value> = out
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line is JSON"))
+        .collect();
+    assert_eq!(records.len(), 4, "{out}");
+    assert_eq!(records[0]["type"], "message");
+    assert_eq!(records[0]["kind"], "stage");
+    assert_eq!(records[1]["phase"], "fetch");
+    assert_eq!(records[1]["done"], 1);
+    assert_eq!(records[2]["done"], 3);
+    assert_eq!(records[2]["partition"], serde_json::Value::Null);
+    assert_eq!(records[3]["type"], "progress");
+    assert_eq!(records[3]["phase"], "apply");
+    assert_eq!(records[3]["partition"], "2026/03/05");
+}
@@ -0,0 +1,140 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use crate::app::{App, AppError};
+use crate::cli::UninitArgs;
+use crate::events::count_private_event_files;
+use crate::project::{DistributionMode, ProjectContext};
+
+mod store_archive;
+
+use store_archive::write_store_archive;
+
+/// Runs before `kno uninit` removes anything. `--export` writes a backup
+/// archive first; otherwise unpushed events, including every private knot's
+/// events, stop the removal unless `--force` is given or, on a terminal, the
+/// user takes a backup.
+pub(crate) fn prepare_uninit(
+    context: &ProjectContext,
+    db_path: &str,
+    args: &UninitArgs,
+) -> Result<(), AppError> {
+    let store_root = &context.store_paths.root;
+    if let Some(dest) = &args.export {
+        return export(store_root, dest);
+    }
+    if args.force {
+        return Ok(());
+    }
+    let unpushed = count_unpushed(context, db_path)?;
+    if unpushed == 0 {
+        return Ok(());
+    }
+    if io::stdin().is_terminal() {
+        let default = context.repo_root.join(default_backup_name());
+        let answer = prompt_for_export(
+            &mut io::stdin().lock(),
+            &mut io::stderr(),
+            unpushed,
+            &default,
+        )?;
+        if let Some(dest) = answer {
+            return export(store_root, &dest);
+        }
+    }
+    Err(AppError::InvalidArgument(format!(
+        "{unpushed} event file(s) have not been pushed; run `kno push` (after `kno publish` \
+         for private knots), keep a copy with `--export <file>.tar.zst`, or pass --force to \
+         remove them anyway"
+    )))
+}
+
+/// Private knots' events never leave the machine, so they count in any
+/// distribution mode.
+fn count_unpushed(context: &ProjectContext, db_path: &str) -> Result<u64, AppError> {
+    let private = count_private_event_files(&context.store_paths.root)?;
+    if context.distribution != DistributionMode::Git || !Path::new(db_path).exists() {
+        return Ok(private);
+    }
+    Ok(private + App::open_with_context(context, db_path)?.count_unpushed_event_files()?)
+}
+
+fn export(store_root: &Path, dest: &Path) -> Result<(), AppError> {
+    let files = write_store_archive(store_root, dest)?;
+    println!("exported {files} file(s) to {}", dest.display());
+    Ok(())
+}
+
+fn default_backup_name() -> String {
+    let now = time::OffsetDateTime::now_utc();
+    format!(
+        "knots-backup-{:04}{:02}{:02}T{:02}{:02}{:02}Z.tar.zst",
+        now.year(),
+        u8::from(now.month()),
+        now.day(),
+        now.hour(),
+        now.minute(),
+        now.second()
+    )
+}
+
+/// Asks whether to back up before removing. An empty answer or `y` takes
+/// `default`, any other non-`n` answer is used as the path, and `n` (or
+/// end of input) returns `None`.
+fn prompt_for_export(
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    unpushed: u64,
+    default: &Path,
+) -> Result<Option<PathBuf>, AppError> {
+    writeln!(
+        out,
+        "{unpushed} event file(s) have not been pushed and would be lost."
+    )?;
+    write!(
+        out,
+        "Export a backup to {} first? [Y/n/path] ",
+        default.display()
+    )?;
+    out.flush()?;
+    let mut answer = String::new();
+    if input.read_line(&mut answer)? == 0 {
+        return Ok(None);
+    }
+    Ok(match answer.trim() {
+        "" | "y" | "Y" | "yes" => Some(default.to_path_buf()),
+        "n" | "N" | "no" => None,
+        path => Some(PathBuf::from(path)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::prompt_for_export;
+
+    fn answer(reply: &str) -> Option<PathBuf> {
+        let mut out = Vec::new();
+        let choice = prompt_for_export(&mut reply.as_bytes(), &mut out, 3, Path::new("b.tar.zst"))
+            .expect("prompt");
+        let shown = String::from_utf8(out).expect("utf8");
+        assert!(
+            shown.contains("3 event file(s) have not been pushed"),
+            "{shown}"
+        );
+        choice
+    }
+
+    #[test]
+    fn prompt_takes_the_default_a_custom_path_or_declines() {
+        assert_eq!(answer("\n"), Some(PathBuf::from("b.tar.zst")));
+        assert_eq!(answer("yes\n"), Some(PathBuf::from("b.tar.zst")));
+        assert_eq!(
+            answer("/tmp/keep.tar.zst\n"),
+            Some(PathBuf::from("/tmp/keep.tar.zst"))
+        );
+        assert_eq!(answer("n\n"), None);
+        assert_eq!(answer(""), None);
+    }
+}
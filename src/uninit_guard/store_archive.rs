@@ -0,0 +1,147 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Store directories captured by `kno uninit --export`, plus `config.toml`.
+const ARCHIVED_DIRS: [&str; 4] = ["index", "events", "snapshots", "private"];
+const BLOCK: usize = 512;
+
+/// Writes the events, index, snapshots, private knots, and config of
+/// `store_root` to `dest` as a zstd-compressed ustar archive with
+/// `.knots/...` paths, so `tar --zstd -xf` at the repo root puts everything
+/// back where it was.
+/// Returns the number of files archived.
+pub(crate) fn write_store_archive(store_root: &Path, dest: &Path) -> io::Result<usize> {
+    let mut files = Vec::new();
+    for dir in ARCHIVED_DIRS {
+        collect_files(&store_root.join(dir), &mut files)?;
+    }
+    let config = store_root.join("config.toml");
+    if config.is_file() {
+        files.push(config);
+    }
+    files.sort();
+
+    let mut tar = Vec::new();
+    for path in &files {
+        let relative = path.strip_prefix(store_root).map_err(io::Error::other)?;
+        let name = Path::new(".knots").join(relative);
+        append_entry(&mut tar, &name.to_string_lossy(), &std::fs::read(path)?)?;
+    }
+    tar.resize(tar.len() + 2 * BLOCK, 0);
+    if let Some(parent) = dest
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(dest, crate::compression::encode(&tar)?)?;
+    Ok(files.len())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn append_entry(tar: &mut Vec<u8>, name: &str, contents: &[u8]) -> io::Result<()> {
+    let (prefix, name) = split_name(name)?;
+    let mut header = [0u8; BLOCK];
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    write_octal(&mut header[100..108], 0o644);
+    write_octal(&mut header[108..116], 0);
+    write_octal(&mut header[116..124], 0);
+    write_octal(&mut header[124..136], contents.len() as u64);
+    write_octal(&mut header[136..148], 0);
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+    // The checksum is computed with its own field read as spaces.
+    header[148..156].fill(b' ');
+    let checksum: u64 = header.iter().map(|byte| u64::from(*byte)).sum();
+    header[148..155].copy_from_slice(format!("{checksum:06o}\0").as_bytes());
+
+    tar.extend_from_slice(&header);
+    tar.extend_from_slice(contents);
+    tar.resize(tar.len().div_ceil(BLOCK) * BLOCK, 0);
+    Ok(())
+}
+
+/// ustar keeps names up to 100 bytes, with up to 155 more in a prefix
+/// that must end at a `/`.
+fn split_name(path: &str) -> io::Result<(&str, &str)> {
+    if path.len() <= 100 {
+        return Ok(("", path));
+    }
+    path.char_indices()
+        .filter(|(index, ch)| *ch == '/' && *index <= 155 && path.len() - index - 1 <= 100)
+        .map(|(index, _)| (&path[..index], &path[index + 1..]))
+        .next()
+        .ok_or_else(|| io::Error::other(format!("path is too long to archive: {path}")))
+}
+
+fn write_octal(field: &mut [u8], value: u64) {
+    let digits = format!("{value:0width$o}\0", width = field.len() - 1);
+    field.copy_from_slice(digits.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_name, write_store_archive};
+
+    fn field(header: &[u8], range: std::ops::Range<usize>) -> String {
+        String::from_utf8_lossy(&header[range])
+            .trim_end_matches('\0')
+            .to_string()
+    }
+
+    #[test]
+    fn archives_store_files_under_knots_paths() {
+        let root = std::env::temp_dir().join(format!("knots-archive-{}", uuid::Uuid::now_v7()));
+        let store = root.join(".knots");
+        std::fs::create_dir_all(store.join("events/2026/10")).expect("events dir");
+        std::fs::create_dir_all(store.join("cache")).expect("cache dir");
+        std::fs::write(store.join("events/2026/10/e1.json"), "{\"a\":1}").expect("event");
+        std::fs::write(store.join("config.toml"), "id_prefix = \"k\"\n").expect("config");
+        std::fs::write(store.join("cache/state.sqlite"), "skip me").expect("cache");
+
+        let dest = root.join("backup.tar.zst");
+        assert_eq!(write_store_archive(&store, &dest).expect("archive"), 2);
+        let tar = zstd::decode_all(std::fs::read(&dest).expect("read").as_slice()).expect("zstd");
+        assert_eq!(tar.len() % 512, 0);
+        assert_eq!(field(&tar, 0..100), ".knots/config.toml");
+        assert_eq!(field(&tar, 257..263), "ustar");
+        let size = u64::from_str_radix(&field(&tar, 124..135), 8).expect("size");
+        assert_eq!(size, 16);
+        let second = &tar[1024..];
+        assert_eq!(field(second, 0..100), ".knots/events/2026/10/e1.json");
+        assert_eq!(&second[512..519], b"{\"a\":1}");
+        let checksum = u64::from_str_radix(field(&tar, 148..154).trim(), 8).expect("checksum");
+        let mut blank = tar[..512].to_vec();
+        blank[148..156].fill(b' ');
+        assert_eq!(
+            checksum,
+            blank.iter().map(|byte| u64::from(*byte)).sum::<u64>()
+        );
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn long_names_split_into_a_prefix_at_a_slash() {
+        let long = format!(".knots/events/{}/{}.json", "d".repeat(60), "e".repeat(60));
+        let (prefix, name) = split_name(&long).expect("split");
+        assert_eq!(format!("{prefix}/{name}"), long);
+        assert!(name.len() <= 100 && prefix.len() <= 155);
+        assert!(split_name(&"x".repeat(300)).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::db::{self, UsageMetricRecord};
+
+/// The invocation being measured. Nothing is written unless a store that
+/// has `metrics.local` turned on was opened during the command.
+struct Invocation {
+    command: &'static str,
+    started_at: String,
+    start: Instant,
+    db_path: Option<String>,
+    sync_files: Option<u64>,
+}
+
+static CURRENT: Mutex<Option<Invocation>> = Mutex::new(None);
+
+fn with_current(update: impl FnOnce(&mut Invocation)) {
+    if let Ok(mut current) = CURRENT.lock() {
+        if let Some(invocation) = current.as_mut() {
+            update(invocation);
+        }
+    }
+}
+
+pub fn begin(command: &'static str) {
+    if let Ok(mut current) = CURRENT.lock() {
+        *current = Some(Invocation {
+            command,
+            started_at: db::now_utc_rfc3339(),
+            start: Instant::now(),
+            db_path: None,
+            sync_files: None,
+        });
+    }
+}
+
+/// Called when a store is opened; `enabled` is its `metrics.local` setting.
+pub fn note_store(db_path: &str, enabled: bool) {
+    with_current(|invocation| invocation.db_path = enabled.then(|| db_path.to_string()));
+}
+
+/// Adds event files moved by a push or pull to this invocation.
+pub fn add_sync_files(files: u64) {
+    with_current(|invocation| {
+        invocation.sync_files = Some(invocation.sync_files.unwrap_or(0) + files);
+    });
+}
+
+/// Records the invocation started by `begin`. Metrics are a convenience,
+/// so failures to write them are ignored.
+pub fn finish(ok: bool) {
+    let Some(invocation) = CURRENT.lock().ok().and_then(|mut current| current.take()) else {
+        return;
+    };
+    let Some(db_path) = invocation.db_path else {
+        return;
+    };
+    let Ok(conn) = db::open_connection(&db_path) else {
+        return;
+    };
+    let _ = db::insert_usage_metric(
+        &conn,
+        &UsageMetricRecord {
+            command: invocation.command.to_string(),
+            started_at: invocation.started_at,
+            duration_ms: invocation.start.elapsed().as_millis() as u64,
+            ok,
+            sync_files: invocation.sync_files,
+        },
+    );
+}
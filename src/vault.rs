@@ -0,0 +1,261 @@
+use sha2::{Digest, Sha256};
+
+/// The part of a knot a vault note shows; `None` fields were hidden by
+/// the repo's `sensitive_fields` and are neither written nor read back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VaultNote {
+    pub id: String,
+    pub state: String,
+    pub knot_type: String,
+    pub priority: Option<i64>,
+    pub updated_at: String,
+    pub title: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub links: Vec<NoteLink>,
+}
+
+/// A wikilink in the note's links section, e.g. `- parent: [[id|Title]]`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NoteLink {
+    pub relation: String,
+    pub id: String,
+    /// Link label; `None` when titles are sensitive.
+    pub title: Option<String>,
+}
+
+/// Fields read back from a note a person may have edited.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NoteEdits {
+    pub id: Option<String>,
+    pub state: Option<String>,
+    pub priority: Option<i64>,
+    pub title: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub hidden: Vec<String>,
+}
+
+const LINKS_HEADING: &str = "## Links";
+const HIDDEN_TITLE: &str = "(hidden)";
+
+/// File name of a knot's note; ids never change, so links stay valid
+/// across title edits.
+pub fn note_file_name(id: &str) -> String {
+    format!("{id}.md")
+}
+
+pub fn content_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+pub fn render_note(note: &VaultNote) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("id: {}\n", note.id));
+    out.push_str(&format!("state: {}\n", note.state));
+    out.push_str(&format!("type: {}\n", note.knot_type));
+    if let Some(priority) = note.priority {
+        out.push_str(&format!("priority: {priority}\n"));
+    }
+    if let Some(tags) = &note.tags {
+        out.push_str(&format!("tags: {}\n", flow_list(tags)));
+    }
+    if let Some(title) = &note.title {
+        out.push_str(&format!(
+            "aliases: {}\n",
+            flow_list(std::slice::from_ref(title))
+        ));
+    }
+    let hidden: Vec<String> = [
+        ("title", note.title.is_none()),
+        ("tags", note.tags.is_none()),
+        ("description", note.description.is_none()),
+    ]
+    .into_iter()
+    .filter(|(_, hidden)| *hidden)
+    .map(|(field, _)| field.to_string())
+    .collect();
+    if !hidden.is_empty() {
+        out.push_str(&format!("hidden: {}\n", flow_list(&hidden)));
+    }
+    out.push_str(&format!("updated_at: {}\n---\n", note.updated_at));
+    out.push_str(&format!(
+        "# {}\n",
+        note.title.as_deref().unwrap_or(HIDDEN_TITLE)
+    ));
+    if let Some(description) = note.description.as_deref().map(str::trim) {
+        if !description.is_empty() {
+            out.push_str(&format!("\n{description}\n"));
+        }
+    }
+    if !note.links.is_empty() {
+        out.push_str(&format!("\n{LINKS_HEADING}\n"));
+        for link in &note.links {
+            let (relation, id) = (&link.relation, &link.id);
+            match &link.title {
+                Some(title) => out.push_str(&format!("- {relation}: [[{id}|{title}]]\n")),
+                None => out.push_str(&format!("- {relation}: [[{id}]]\n")),
+            }
+        }
+    }
+    out
+}
+
+fn flow_list(items: &[String]) -> String {
+    serde_json::to_string(items).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Reads front matter and body back; block lists (`- item` lines), which
+/// Obsidian's property editor writes, are read like flow lists.
+pub fn parse_note(text: &str) -> NoteEdits {
+    let mut edits = NoteEdits::default();
+    let (front, body) = split_front_matter(text);
+    let mut lines = front.lines().peekable();
+    while let Some(line) = lines.next() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let mut value = value.trim().to_string();
+        let mut items = None;
+        if value.is_empty() {
+            let mut block = Vec::new();
+            while let Some(item) = lines.peek().and_then(|line| line.trim().strip_prefix("- ")) {
+                block.push(unquote(item.trim()));
+                lines.next();
+            }
+            items = Some(block);
+        } else if value.starts_with('[') {
+            items = Some(parse_flow_list(&value));
+        } else {
+            value = unquote(&value);
+        }
+        match key.trim() {
+            "id" => edits.id = Some(value),
+            "state" => edits.state = Some(value),
+            "priority" => edits.priority = value.parse().ok(),
+            "tags" => edits.tags = Some(items.unwrap_or_default()),
+            "hidden" => edits.hidden = items.unwrap_or_default(),
+            _ => {}
+        }
+    }
+    let body = body.trim_start_matches('\n');
+    let (heading, rest) = body.split_once('\n').unwrap_or((body, ""));
+    if let Some(title) = heading.strip_prefix("# ") {
+        edits.title = Some(title.trim().to_string());
+        let description = match rest.rfind(&format!("\n{LINKS_HEADING}\n")) {
+            Some(end) => &rest[..end],
+            None => rest
+                .strip_prefix(&format!("{LINKS_HEADING}\n"))
+                .map_or(rest, |_| ""),
+        };
+        edits.description = Some(description.trim().to_string());
+    }
+    edits
+}
+
+fn split_front_matter(text: &str) -> (&str, &str) {
+    let Some(rest) = text.strip_prefix("---\n") else {
+        return ("", text);
+    };
+    match rest.find("\n---\n") {
+        Some(end) => (&rest[..end], &rest[end + 5..]),
+        None => match rest.strip_suffix("\n---") {
+            Some(front) => (front, ""),
+            None => ("", text),
+        },
+    }
+}
+
+fn parse_flow_list(value: &str) -> Vec<String> {
+    if let Ok(items) = serde_json::from_str::<Vec<String>>(value) {
+        return items;
+    }
+    value
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(|item| unquote(item.trim()))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn unquote(value: &str) -> String {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        if let Ok(unquoted) = serde_json::from_str::<String>(value) {
+            return unquoted;
+        }
+    }
+    value.trim_matches('\'').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_note, render_note, NoteLink, VaultNote};
+
+    fn note() -> VaultNote {
+        VaultNote {
+            id: "k-1".to_string(),
+            state: "planning".to_string(),
+            knot_type: "work".to_string(),
+            priority: Some(2),
+            updated_at: "2026-10-01T00:00:00Z".to_string(),
+            title: Some("Ship \"vault\" sync".to_string()),
+            tags: Some(vec!["api".to_string(), "docs".to_string()]),
+            description: Some("First line.\n\n## Links in prose\nstill body".to_string()),
+            links: vec![NoteLink {
+                relation: "parent".to_string(),
+                id: "k-0".to_string(),
+                title: Some("Epic".to_string()),
+            }],
+        }
+    }
+
+    #[test]
+    fn rendered_notes_parse_back_to_their_fields() {
+        let text = render_note(&note());
+        assert!(text.starts_with("---\nid: k-1\nstate: planning\n"));
+        assert!(text.contains("tags: [\"api\",\"docs\"]\n"));
+        assert!(text.contains("\n# Ship \"vault\" sync\n"));
+        assert!(text.ends_with("\n## Links\n- parent: [[k-0|Epic]]\n"));
+
+        let edits = parse_note(&text);
+        assert_eq!(edits.id.as_deref(), Some("k-1"));
+        assert_eq!(edits.state.as_deref(), Some("planning"));
+        assert_eq!(edits.priority, Some(2));
+        assert_eq!(edits.title.as_deref(), Some("Ship \"vault\" sync"));
+        assert_eq!(
+            edits.tags,
+            Some(vec!["api".to_string(), "docs".to_string()])
+        );
+        assert_eq!(
+            edits.description.as_deref(),
+            Some("First line.\n\n## Links in prose\nstill body")
+        );
+        assert!(edits.hidden.is_empty());
+    }
+
+    #[test]
+    fn reads_block_lists_and_hidden_fields() {
+        let mut hidden = note();
+        hidden.title = None;
+        hidden.description = None;
+        hidden.links.clear();
+        let text = render_note(&hidden);
+        assert!(text.contains("hidden: [\"title\",\"description\"]\n"));
+        assert!(text.ends_with("---\n# (hidden)\n"));
+
+        let edited = text.replace(
+            "tags: [\"api\",\"docs\"]",
+            "tags:\n  - api\n  - \"new tag\"",
+        );
+        let edits = parse_note(&edited);
+        assert_eq!(
+            edits.tags,
+            Some(vec!["api".to_string(), "new tag".to_string()])
+        );
+        assert_eq!(edits.hidden, vec!["title", "description"]);
+    }
+}
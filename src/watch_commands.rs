@@ -0,0 +1,105 @@
+use crate::app::{self, App};
+use crate::cli::{InboxArgs, InboxSubcommands, WatchArgs, WatchSubcommands};
+use crate::db::InboxItemRecord;
+use crate::knot_id::display_id;
+use crate::print_json;
+
+pub fn run_watch(app: &App, args: WatchArgs) -> Result<(), app::AppError> {
+    match args.command {
+        WatchSubcommands::Add(add) => {
+            let knot = app.set_watch(&add.id, true)?;
+            println!("watching {}", display_id(&knot.id));
+        }
+        WatchSubcommands::Remove(remove) => {
+            let knot = app.set_watch(&remove.id, false)?;
+            println!("stopped watching {}", display_id(&knot.id));
+        }
+        WatchSubcommands::List(list) => {
+            let watched = app.watched_knots()?;
+            if list.json {
+                print_json(&watched);
+            } else if watched.is_empty() {
+                println!("no watched knots");
+            } else {
+                for knot in watched {
+                    let title = knot.title.unwrap_or_default();
+                    println!("{} {title}", display_id(&knot.id));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn run_inbox(app: &App, args: InboxArgs) -> Result<(), app::AppError> {
+    if let Some(InboxSubcommands::Ack(ack)) = args.command {
+        let acked = app.ack_inbox(&ack.ids)?;
+        println!("acknowledged {acked} inbox item(s)");
+        return Ok(());
+    }
+    let items = app.inbox()?;
+    if args.json {
+        print_json(&items);
+    } else {
+        print!("{}", render_inbox(&items));
+    }
+    Ok(())
+}
+
+pub fn render_inbox(items: &[InboxItemRecord]) -> String {
+    if items.is_empty() {
+        return "inbox is empty\n".to_string();
+    }
+    items
+        .iter()
+        .map(|item| {
+            format!(
+                "{:>4} {} {} {}\n",
+                item.id,
+                item.kind,
+                display_id(&item.knot_id),
+                item.summary
+            )
+        })
+        .collect()
+}
+
+/// The lines a pull adds when it filed inbox items.
+pub fn render_new_inbox_items(items: &[InboxItemRecord]) -> String {
+    let mut out = format!("new inbox items ({}); see `kno inbox`:\n", items.len());
+    for item in items {
+        out.push_str(&format!(
+            "  {} {} {}\n",
+            item.kind,
+            display_id(&item.knot_id),
+            item.summary
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_inbox, render_new_inbox_items};
+    use crate::db::InboxItemRecord;
+
+    #[test]
+    fn renders_inbox_rows_and_pull_highlights() {
+        assert_eq!(render_inbox(&[]), "inbox is empty\n");
+        let item = InboxItemRecord {
+            id: 7,
+            knot_id: "demo-k1".to_string(),
+            kind: "watch".to_string(),
+            summary: "Fix login: implementation -> review".to_string(),
+            created_at: "2026-10-16T12:00:00Z".to_string(),
+        };
+        assert_eq!(
+            render_inbox(std::slice::from_ref(&item)),
+            "   7 watch k1 Fix login: implementation -> review\n"
+        );
+        assert_eq!(
+            render_new_inbox_items(&[item]),
+            "new inbox items (1); see `kno inbox`:\n  watch k1 Fix login: implementation -> review\n"
+        );
+    }
+}
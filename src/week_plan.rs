@@ -0,0 +1,377 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use serde::Serialize;
+use time::{Date, Duration, OffsetDateTime, Weekday};
+
+use crate::app::{self, App, KnotView, UpdateKnotPatch};
+use crate::cli::{ListArgs, PlanCarryoverArgs, PlanWeekArgs};
+use crate::knot_id::display_id;
+
+/// Tag namespace that records which calendar week a knot is planned for,
+/// e.g. `week/2026-w42`.
+const WEEK_NAMESPACE: &str = "week";
+/// Candidates the interactive picker lists at once.
+const SHOWN: usize = 30;
+
+/// An ISO 8601 week, the unit `kno plan week` plans into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Week {
+    year: i32,
+    number: u8,
+}
+
+impl Week {
+    fn containing(date: Date) -> Self {
+        let (year, number, _) = date.to_iso_week_date();
+        Self { year, number }
+    }
+
+    fn monday(self) -> Date {
+        Date::from_iso_week_date(self.year, self.number, Weekday::Monday)
+            .expect("a parsed week has a Monday")
+    }
+
+    fn offset(self, weeks: i64) -> Self {
+        Self::containing(self.monday() + Duration::weeks(weeks))
+    }
+
+    /// `current`/`this`, `next`, `last`/`previous`, or `2026-W42`.
+    pub(crate) fn parse(raw: &str, today: Date) -> Result<Self, app::AppError> {
+        let current = Self::containing(today);
+        let raw = raw.trim();
+        match raw.to_ascii_lowercase().as_str() {
+            "current" | "this" => return Ok(current),
+            "next" => return Ok(current.offset(1)),
+            "last" | "previous" => return Ok(current.offset(-1)),
+            _ => {}
+        }
+        let invalid = || {
+            app::AppError::InvalidArgument(format!(
+                "invalid week '{raw}': use current, next, last, or an ISO week like 2026-W42"
+            ))
+        };
+        let (year, number) = raw
+            .to_ascii_uppercase()
+            .split_once("-W")
+            .and_then(|(year, number)| Some((year.parse().ok()?, number.parse().ok()?)))
+            .ok_or_else(invalid)?;
+        Date::from_iso_week_date(year, number, Weekday::Monday).map_err(|_| invalid())?;
+        Ok(Self { year, number })
+    }
+
+    pub(crate) fn label(self) -> String {
+        format!("{}-W{:02}", self.year, self.number)
+    }
+
+    pub(crate) fn tag(self) -> String {
+        format!("{WEEK_NAMESPACE}/{}", self.label().to_ascii_lowercase())
+    }
+}
+
+/// One week's planned knots split by whether they finished.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub(crate) struct Carryover {
+    pub week: String,
+    pub finished: Vec<WeekKnot>,
+    pub carried_over: Vec<WeekKnot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carried_to: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub(crate) struct WeekKnot {
+    pub id: String,
+    pub title: String,
+    pub state: String,
+}
+
+impl From<&KnotView> for WeekKnot {
+    fn from(knot: &KnotView) -> Self {
+        Self {
+            id: knot.id.clone(),
+            title: knot.title.clone(),
+            state: knot.state.clone(),
+        }
+    }
+}
+
+fn today() -> Date {
+    OffsetDateTime::now_utc().date()
+}
+
+/// Turns `kno ls --week` into the matching week tag filter.
+pub(crate) fn with_week_tag(mut args: ListArgs) -> Result<ListArgs, app::AppError> {
+    if let Some(week) = args.week.take() {
+        args.tags.push(Week::parse(&week, today())?.tag());
+    }
+    Ok(args)
+}
+
+pub fn run_plan_week(app: &App, args: PlanWeekArgs) -> Result<(), app::AppError> {
+    let week = Week::parse(&args.week, today())?;
+    let tag = week.tag();
+    let ids = if !args.ids.is_empty() {
+        args.ids
+    } else if args.remove {
+        return Err(app::AppError::InvalidArgument(
+            "name the knots to take out of the week".to_string(),
+        ));
+    } else if io::stdin().is_terminal() {
+        let candidates = ready_knots(app, &tag)?;
+        choose_many(&mut io::stdin().lock(), &mut io::stderr(), &candidates)?
+    } else {
+        return Err(app::AppError::InvalidArgument(
+            "name the knots to plan, or run on a terminal to pick from ready knots".to_string(),
+        ));
+    };
+    let mut planned = Vec::new();
+    for id in ids {
+        let patch = if args.remove {
+            UpdateKnotPatch {
+                remove_tags: vec![tag.clone()],
+                ..UpdateKnotPatch::default()
+            }
+        } else {
+            UpdateKnotPatch {
+                add_tags: vec![tag.clone()],
+                ..UpdateKnotPatch::default()
+            }
+        };
+        planned.push(WeekKnot::from(&app.update_knot(&id, patch)?));
+    }
+    if args.json {
+        crate::print_json(&planned);
+        return Ok(());
+    }
+    let verb = if args.remove {
+        "removed from"
+    } else {
+        "planned into"
+    };
+    for knot in &planned {
+        println!(
+            "{verb} {}: {} {}",
+            week.label(),
+            display_id(&knot.id),
+            knot.title
+        );
+    }
+    if planned.is_empty() {
+        println!("nothing planned into {}", week.label());
+    }
+    Ok(())
+}
+
+pub fn run_plan_carryover(app: &App, args: PlanCarryoverArgs) -> Result<(), app::AppError> {
+    let week = Week::parse(&args.week, today())?;
+    let mut report = carryover(app, week)?;
+    if args.carry {
+        let next = week.offset(1);
+        for knot in &report.carried_over {
+            app.update_knot(
+                &knot.id,
+                UpdateKnotPatch {
+                    add_tags: vec![next.tag()],
+                    ..UpdateKnotPatch::default()
+                },
+            )?;
+        }
+        report.carried_to = Some(next.label());
+    }
+    if args.json {
+        crate::print_json(&report);
+    } else {
+        print!("{}", render_carryover(&report));
+    }
+    Ok(())
+}
+
+fn carryover(app: &App, week: Week) -> Result<Carryover, app::AppError> {
+    let tag = week.tag();
+    let mut report = Carryover {
+        week: week.label(),
+        finished: Vec::new(),
+        carried_over: Vec::new(),
+        carried_to: None,
+    };
+    for knot in app.list_knots()? {
+        if !knot.tags.contains(&tag) {
+            continue;
+        }
+        let finished = app
+            .profile_registry()
+            .require(&knot.profile_id)?
+            .is_terminal_state(&knot.state);
+        if finished {
+            report.finished.push(WeekKnot::from(&knot));
+        } else {
+            report.carried_over.push(WeekKnot::from(&knot));
+        }
+    }
+    Ok(report)
+}
+
+/// Knots waiting in a queue state that are not yet planned into the week.
+fn ready_knots(app: &App, tag: &str) -> Result<Vec<WeekKnot>, app::AppError> {
+    let mut ready = Vec::new();
+    for knot in app.list_knots()? {
+        let profile = app.profile_registry().require(&knot.profile_id)?;
+        if profile.is_queue_state(&knot.state)
+            && !profile.is_terminal_state(&knot.state)
+            && !knot.tags.iter().any(|existing| existing == tag)
+        {
+            ready.push(WeekKnot::from(&knot));
+        }
+    }
+    Ok(ready)
+}
+
+/// Lists `knots` and reads the numbers to plan, separated by spaces or
+/// commas. An empty line or end of input plans nothing.
+pub(crate) fn choose_many<R: BufRead, W: Write>(
+    input: &mut R,
+    out: &mut W,
+    knots: &[WeekKnot],
+) -> Result<Vec<String>, app::AppError> {
+    if knots.is_empty() {
+        writeln!(out, "no ready knots to plan")?;
+        return Ok(Vec::new());
+    }
+    let shown = &knots[..knots.len().min(SHOWN)];
+    for (index, knot) in shown.iter().enumerate() {
+        writeln!(
+            out,
+            "{:>3}) {}  [{}]  {}",
+            index + 1,
+            display_id(&knot.id),
+            knot.state,
+            knot.title
+        )?;
+    }
+    loop {
+        write!(out, "plan (numbers, blank to finish)> ")?;
+        out.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(Vec::new());
+        }
+        let picks: Result<Vec<usize>, _> = line
+            .split([',', ' '])
+            .filter(|part| !part.trim().is_empty())
+            .map(|part| part.trim().parse::<usize>())
+            .collect();
+        match picks {
+            Ok(picks) if picks.iter().all(|n| (1..=shown.len()).contains(n)) => {
+                let mut ids: Vec<String> = Vec::new();
+                for n in picks {
+                    let id = shown[n - 1].id.clone();
+                    if !ids.contains(&id) {
+                        ids.push(id);
+                    }
+                }
+                return Ok(ids);
+            }
+            _ => writeln!(out, "pick numbers between 1 and {}", shown.len())?,
+        }
+    }
+}
+
+pub(crate) fn render_carryover(report: &Carryover) -> String {
+    let mut out = format!(
+        "{}: {} finished, {} carried over\n",
+        report.week,
+        report.finished.len(),
+        report.carried_over.len()
+    );
+    let sections = [
+        ("finished", &report.finished),
+        ("carried over", &report.carried_over),
+    ];
+    for (heading, knots) in sections {
+        if knots.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("{heading}:\n"));
+        for knot in knots {
+            out.push_str(&format!(
+                "  {} {} [{}]\n",
+                display_id(&knot.id),
+                knot.title,
+                knot.state
+            ));
+        }
+    }
+    if let Some(next) = &report.carried_to {
+        out.push_str(&format!("carried over knots planned into {next}\n"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{Date, Month};
+
+    use super::{choose_many, render_carryover, Carryover, Week, WeekKnot};
+
+    #[test]
+    fn weeks_parse_relative_names_and_iso_labels() {
+        let today = Date::from_calendar_date(2026, Month::January, 1).expect("date");
+        let current = Week::parse("current", today).expect("current");
+        assert_eq!(current.label(), "2026-W01");
+        assert_eq!(current.tag(), "week/2026-w01");
+        assert_eq!(
+            Week::parse("last", today).expect("last").label(),
+            "2025-W52"
+        );
+        assert_eq!(
+            Week::parse("next", today).expect("next").label(),
+            "2026-W02"
+        );
+        assert_eq!(
+            Week::parse("2026-w42", today).expect("iso").label(),
+            "2026-W42"
+        );
+        assert!(Week::parse("2026-W54", today).is_err());
+        assert!(Week::parse("someday", today).is_err());
+    }
+
+    fn knot(id: &str, state: &str) -> WeekKnot {
+        WeekKnot {
+            id: id.to_string(),
+            title: format!("Title {id}"),
+            state: state.to_string(),
+        }
+    }
+
+    #[test]
+    fn choose_many_reads_numbers_and_reprompts_on_bad_input() {
+        let ready = [
+            knot("demo-a1", "ready_for_planning"),
+            knot("demo-b2", "ready_for_implementation"),
+        ];
+        let mut out = Vec::new();
+        let picked = choose_many(&mut "3\n2, 1 2\n".as_bytes(), &mut out, &ready).expect("picks");
+        assert_eq!(picked, vec!["demo-b2".to_string(), "demo-a1".to_string()]);
+        let out = String::from_utf8(out).expect("utf8");
+        assert!(out.starts_with("  1) a1  [ready_for_planning]  Title demo-a1\n"));
+        assert!(out.contains("pick numbers between 1 and 2"), "{out}");
+        let none = choose_many(&mut "\n".as_bytes(), &mut Vec::new(), &ready).expect("none");
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn renders_finished_and_carried_over_sections() {
+        let report = Carryover {
+            week: "2026-W42".to_string(),
+            finished: vec![knot("demo-a1", "shipped")],
+            carried_over: vec![knot("demo-b2", "implementation")],
+            carried_to: Some("2026-W43".to_string()),
+        };
+        assert_eq!(
+            render_carryover(&report),
+            "2026-W42: 1 finished, 1 carried over\nfinished:\n  a1 Title demo-a1 [shipped]\n\
+             carried over:\n  b2 Title demo-b2 [implementation]\n\
+             carried over knots planned into 2026-W43\n"
+        );
+    }
+}
@@ -1,8 +1,8 @@
 #![allow(unused_imports)]
 
 pub use crate::profile::{
-    normalize_profile_id, normalize_profile_id as normalize_workflow_id, ActionOutputDef, GateMode,
-    InvalidWorkflowTransition, OwnerKind, ProfileDefinition,
+    normalize_profile_id, normalize_profile_id as normalize_workflow_id, ActionOutputDef,
+    DoneRequirement, GateMode, InvalidWorkflowTransition, OwnerKind, ProfileDefinition,
     ProfileDefinition as WorkflowDefinition, ProfileError, ProfileError as WorkflowError,
     ProfileOwners, ProfileRegistry, ProfileRegistry as WorkflowRegistry, StepMetadata, StepOwner,
     WorkflowTransition,
@@ -38,6 +38,11 @@ pub fn maybe_run_queued_command_with_context(
     let Some(operation) = operation_from_command(&cli.command) else {
         return Ok(None);
     };
+    if crate::db::is_memory_path(db_path) {
+        return Err(AppError::InvalidArgument(
+            "--db :memory: is read-only; drop it to write knots".to_string(),
+        ));
+    }
 
     let response = write_queue::enqueue_and_wait_with_context(
         &context.repo_root,
@@ -114,3 +119,7 @@ mod tests_lease_ext4;
 #[cfg(test)]
 #[path = "write_dispatch/tests_lease_ext5.rs"]
 mod tests_lease_ext5;
+
+#[cfg(test)]
+#[path = "write_dispatch/tests_idempotency_ext.rs"]
+mod tests_idempotency_ext;
@@ -1,7 +1,10 @@
+use crate::app::knot_lease::{handback_cooldown_until, normalize_handback_reason};
 use crate::app::{App, AppError, StateActorMetadata, UpdateKnotPatch};
+use crate::db::HandbackRecord;
 use crate::dispatch::{knot_ref, resolve_next_state};
 use crate::domain::knot_type::KnotType;
 use crate::domain::metadata::MetadataEntryInput;
+use crate::events::{new_event_id, now_utc_rfc3339};
 use crate::lease_guard::{release_bound_lease, validate_next_bound_lease};
 use crate::rollback::resolve_rollback_state;
 use crate::ui;
@@ -9,8 +12,8 @@ use crate::write_queue::NextOperation;
 
 use crate::write_dispatch::helpers::{
     execute_with_terminal_cascade_prompt, format_next_output, format_rollback_output,
-    normalize_expected_state, parse_gate_failure_modes_option, parse_gate_owner_kind_arg,
-    resolve_lease_agent_info, validate_non_claim_lease,
+    format_subtree_output, normalize_expected_state, parse_gate_failure_modes_option,
+    parse_gate_owner_kind_arg, resolve_lease_agent_info, validate_non_claim_lease,
 };
 
 pub(super) fn execute_update(
@@ -78,6 +81,8 @@ fn build_update_patch(
         title: args.title.clone(),
         description: args.description.clone(),
         acceptance: args.acceptance.clone(),
+        estimate: args.estimate.clone(),
+        custom_fields: args.fields.clone(),
         priority: args.priority,
         status: args.status.clone(),
         knot_type: args
@@ -172,6 +177,9 @@ fn build_handoff_input(
 }
 
 pub(super) fn execute_next(app: &App, args: &NextOperation) -> Result<String, AppError> {
+    if args.subtree {
+        return execute_next_subtree(app, args);
+    }
     let knot = app
         .show_knot(&args.id)?
         .ok_or_else(|| AppError::NotFound(args.id.clone()))?;
@@ -208,6 +216,26 @@ pub(super) fn execute_next(app: &App, args: &NextOperation) -> Result<String, Ap
     ))
 }
 
+fn execute_next_subtree(app: &App, args: &NextOperation) -> Result<String, AppError> {
+    if args.expected_state.is_some() || args.lease_id.is_some() || args.approve_terminal_cascade {
+        return Err(AppError::InvalidArgument(
+            "--subtree cannot be combined with an expected state, --lease, or \
+             --cascade-terminal-descendants"
+                .to_string(),
+        ));
+    }
+    let report = app.advance_children(
+        &args.id,
+        StateActorMetadata {
+            actor_kind: args.actor_kind.clone(),
+            agent_name: args.agent_name.clone(),
+            agent_model: args.agent_model.clone(),
+            agent_version: args.agent_version.clone(),
+        },
+    )?;
+    Ok(format_subtree_output(&report, args.json))
+}
+
 fn validate_next_preconditions(
     app: &App,
     knot: &crate::app::KnotView,
@@ -265,3 +293,102 @@ pub(super) fn execute_rollback(
         false,
     ))
 }
+
+pub(super) fn execute_snooze(
+    app: &App,
+    args: &crate::write_queue::SnoozeOperation,
+) -> Result<String, AppError> {
+    let knot = app.snooze_knot(
+        &args.id,
+        &args.until,
+        StateActorMetadata {
+            actor_kind: args.actor_kind.clone(),
+            agent_name: args.agent_name.clone(),
+            agent_model: args.agent_model.clone(),
+            agent_version: args.agent_version.clone(),
+        },
+    )?;
+    let palette = ui::Palette::auto();
+    Ok(format!(
+        "snoozed {} until {}\n",
+        palette.id(&knot_ref(&knot)),
+        knot.snoozed_until.as_deref().unwrap_or(&args.until)
+    ))
+}
+
+pub(super) fn execute_handback(
+    app: &App,
+    args: &crate::write_queue::HandbackOperation,
+) -> Result<String, AppError> {
+    let reason = normalize_handback_reason(&args.reason)?;
+    let cooldown_until = args
+        .cooldown
+        .as_deref()
+        .map(handback_cooldown_until)
+        .transpose()?;
+    let resolution = resolve_rollback_state(app, &args.id)?;
+    let agent_name = args
+        .agent_name
+        .clone()
+        .or_else(|| claiming_agent(&resolution.knot));
+    if cooldown_until.is_some() && agent_name.is_none() {
+        return Err(AppError::InvalidArgument(
+            "--cooldown needs an agent; the knot has no claiming agent, so pass --agent-name"
+                .to_string(),
+        ));
+    }
+    let updated = app.set_state_with_actor_and_options(
+        &resolution.knot.id,
+        &resolution.target_state,
+        resolution.requires_force,
+        None,
+        StateActorMetadata {
+            actor_kind: args.actor_kind.clone(),
+            agent_name: agent_name.clone(),
+            agent_model: args.agent_model.clone(),
+            agent_version: args.agent_version.clone(),
+        },
+        false,
+        false,
+    )?;
+    if updated.lease_id.is_some() {
+        release_bound_lease(app, &updated.id)?;
+    }
+    let handback = HandbackRecord {
+        handback_id: new_event_id(),
+        reason,
+        note: args.note.clone().filter(|note| !note.trim().is_empty()),
+        agent_name,
+        from_state: resolution.knot.state.clone(),
+        to_state: resolution.target_state.clone(),
+        handed_back_at: now_utc_rfc3339(),
+        cooldown_until,
+    };
+    app.record_handback(&updated.id, &handback)?;
+    let palette = ui::Palette::auto();
+    let mut output = format!(
+        "handed back {} -> {} ({})\n",
+        palette.id(&knot_ref(&updated)),
+        palette.state(&handback.to_state),
+        handback.reason
+    );
+    if let (Some(agent), Some(until)) = (&handback.agent_name, &handback.cooldown_until) {
+        output.push_str(&format!("{agent} cannot reclaim it until {until}\n"));
+    }
+    Ok(output)
+}
+
+/// Agent on the knot's open step, or on its bound lease.
+fn claiming_agent(knot: &crate::app::KnotView) -> Option<String> {
+    knot.step_history
+        .iter()
+        .rev()
+        .find(|step| step.is_active())
+        .and_then(|step| step.agent_name.clone())
+        .or_else(|| {
+            knot.lease_agent
+                .as_ref()
+                .map(|info| info.agent_name.clone())
+        })
+        .filter(|name| !name.is_empty())
+}
@@ -0,0 +1,49 @@
+use sha2::{Digest, Sha256};
+
+use crate::app::{App, AppError};
+use crate::write_queue::WriteOperation;
+
+pub(super) fn execute_once<F>(
+    app: &App,
+    operation: &WriteOperation,
+    run: F,
+) -> Result<String, AppError>
+where
+    F: FnOnce() -> Result<String, AppError>,
+{
+    let Some((command, key)) = idempotency_key(operation) else {
+        return run();
+    };
+    let fingerprint = fingerprint(operation);
+    if let Some(output) = app.lookup_idempotent_output(key, command, &fingerprint)? {
+        return Ok(output);
+    }
+    let output = run()?;
+    app.record_idempotent_output(key, command, &fingerprint, &output)?;
+    Ok(output)
+}
+
+fn idempotency_key(operation: &WriteOperation) -> Option<(&'static str, &str)> {
+    let (command, key) = match operation {
+        WriteOperation::New(args) => ("new", args.idempotency_key.as_deref()?),
+        WriteOperation::Update(args) => ("update", args.idempotency_key.as_deref()?),
+        WriteOperation::State(args) => ("state", args.idempotency_key.as_deref()?),
+        _ => return None,
+    };
+    let key = key.trim();
+    (!key.is_empty()).then_some((command, key))
+}
+
+fn fingerprint(operation: &WriteOperation) -> String {
+    let mut payload = operation.clone();
+    match &mut payload {
+        WriteOperation::New(args) => args.idempotency_key = None,
+        WriteOperation::Update(args) => args.idempotency_key = None,
+        WriteOperation::State(args) => args.idempotency_key = None,
+        _ => {}
+    }
+    let bytes = serde_json::to_vec(&payload).expect("write operation should serialize");
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    format!("{:x}", hasher.finalize())
+}
@@ -14,8 +14,13 @@ use super::helpers::{
 };
 
 mod execute_write_ops;
+mod idempotency;
 
 pub(crate) fn execute_operation(app: &App, operation: &WriteOperation) -> Result<String, AppError> {
+    idempotency::execute_once(app, operation, || dispatch_operation(app, operation))
+}
+
+fn dispatch_operation(app: &App, operation: &WriteOperation) -> Result<String, AppError> {
     match operation {
         WriteOperation::New(args) => execute_new(app, args),
         WriteOperation::QuickNew(args) => execute_quick_new(app, args),
@@ -23,6 +28,8 @@ pub(crate) fn execute_operation(app: &App, operation: &WriteOperation) -> Result
         WriteOperation::Update(args) => execute_write_ops::execute_update(app, args),
         WriteOperation::Next(args) => execute_write_ops::execute_next(app, args),
         WriteOperation::Rollback(args) => execute_write_ops::execute_rollback(app, args),
+        WriteOperation::Snooze(args) => execute_write_ops::execute_snooze(app, args),
+        WriteOperation::Handback(args) => execute_write_ops::execute_handback(app, args),
         WriteOperation::Claim(args) => execute_claim(app, args),
         WriteOperation::PollClaim(args) => execute_poll_claim(app, args),
         WriteOperation::GateEvaluate(args) => execute_gate_evaluate(app, args),
@@ -85,6 +92,7 @@ fn execute_new(app: &App, args: &crate::write_queue::NewOperation) -> Result<Str
         &args.gate_failure_modes,
         knot_type,
     )?;
+    app.warn_similar_knots(&args.title, args.description.as_deref())?;
     let knot = app.create_knot_with_options(
         &args.title,
         args.description.as_deref(),
@@ -93,14 +101,25 @@ fn execute_new(app: &App, args: &crate::write_queue::NewOperation) -> Result<Str
         workflow,
         CreateKnotOptions {
             acceptance: args.acceptance.clone(),
+            estimate: args.estimate.clone(),
+            custom_fields: args.fields.clone(),
             knot_type,
             gate_data,
+            private: args.private,
+            draft: args.draft,
             ..CreateKnotOptions::default()
         },
     )?;
     let palette = ui::Palette::auto();
+    let private = if args.draft {
+        " (draft)"
+    } else if args.private {
+        " (private)"
+    } else {
+        ""
+    };
     Ok(format!(
-        "created {} {} {}\n",
+        "created {} {} {}{private}\n",
         palette.id(&knot_ref(&knot)),
         palette.state(&knot.state),
         knot.title
@@ -112,6 +131,7 @@ fn execute_quick_new(
     args: &crate::write_queue::QuickNewOperation,
 ) -> Result<String, AppError> {
     let quick_profile = app.default_quick_profile_id()?;
+    app.warn_similar_knots(&args.title, args.description.as_deref())?;
     let knot = app.create_knot(
         &args.title,
         args.description.as_deref(),
@@ -166,7 +186,14 @@ fn execute_claim(app: &App, args: &crate::write_queue::ClaimOperation) -> Result
     let timeout = args
         .timeout_seconds
         .unwrap_or(DEFAULT_LEASE_TIMEOUT_SECONDS);
-    let claimed = poll_claim::claim_knot(app, &args.id, actor, args.lease_id.as_deref(), timeout)?;
+    let claimed = poll_claim::claim_knot_with_force(
+        app,
+        &args.id,
+        actor,
+        args.lease_id.as_deref(),
+        timeout,
+        args.force,
+    )?;
     if args.json {
         let value = poll_claim::render_json_verbose(&claimed, args.verbose);
         Ok(format_json(&value))
@@ -180,7 +207,12 @@ fn execute_poll_claim(
     args: &crate::write_queue::PollClaimOperation,
 ) -> Result<String, AppError> {
     use crate::lease_expiry::DEFAULT_LEASE_TIMEOUT_SECONDS;
-    let polled = poll_claim::poll_queue(app, args.stage.as_deref(), args.owner.as_deref())?;
+    let polled = poll_claim::poll_queue(
+        app,
+        args.stage.as_deref(),
+        args.owner.as_deref(),
+        args.agent_name.as_deref(),
+    )?;
     let Some(polled) = polled else {
         return Err(AppError::InvalidArgument(
             "no claimable knots found".to_string(),
@@ -6,6 +6,7 @@ use crate::dispatch::knot_ref;
 use crate::domain::gate::{parse_failure_mode_spec, GateData, GateOwnerKind};
 use crate::domain::knot_type::KnotType;
 use crate::domain::state::KnotState;
+use crate::knot_id::display_id;
 use crate::ui;
 
 const CLAIM_ONLY_LEASE_BINDING: &str = "lease binding is only allowed during claim operations";
@@ -226,6 +227,36 @@ pub(crate) fn format_next_output(
     )
 }
 
+pub(crate) fn format_subtree_output(report: &crate::app::SubtreeAdvance, json: bool) -> String {
+    if json {
+        return format_json(&serde_json::to_value(report).expect("subtree report serializes"));
+    }
+    let palette = ui::Palette::auto();
+    let mut out = format!(
+        "advanced {} of {} children of {}\n",
+        report.advanced.len(),
+        report.advanced.len() + report.blocked.len(),
+        palette.id(display_id(&report.parent)),
+    );
+    for child in &report.advanced {
+        out.push_str(&format!(
+            "  {} {} -> {}\n",
+            palette.id(display_id(&child.id)),
+            child.previous_state,
+            palette.state(&child.state),
+        ));
+    }
+    for child in &report.blocked {
+        out.push_str(&format!(
+            "  {} {} blocked: {}\n",
+            palette.id(display_id(&child.id)),
+            palette.state(&child.state),
+            child.reason,
+        ));
+    }
+    out
+}
+
 pub(crate) fn format_rollback_output(
     knot: &crate::app::KnotView,
     target_state: &str,
@@ -1,9 +1,9 @@
 use crate::cli::{Commands, EdgeSubcommands, GateSubcommands, LeaseSubcommands, StepSubcommands};
 use crate::write_queue::{
-    ClaimOperation, EdgeOperation, GateEvaluateOperation, LeaseCreateOperation,
+    ClaimOperation, EdgeOperation, GateEvaluateOperation, HandbackOperation, LeaseCreateOperation,
     LeaseExtendOperation, LeaseTerminateOperation, NewOperation, NextOperation, PollClaimOperation,
-    QuickNewOperation, RollbackOperation, StateOperation, StepAnnotateOperation, UpdateOperation,
-    WriteOperation,
+    QuickNewOperation, RollbackOperation, SnoozeOperation, StateOperation, StepAnnotateOperation,
+    UpdateOperation, WriteOperation,
 };
 
 pub(crate) fn operation_from_command(command: &Commands) -> Option<WriteOperation> {
@@ -14,6 +14,8 @@ pub(crate) fn operation_from_command(command: &Commands) -> Option<WriteOperatio
         Commands::Update(args) => Some(map_update(args)),
         Commands::Next(args) => Some(map_next(args)),
         Commands::Rollback(args) => Some(map_rollback(args)),
+        Commands::Snooze(args) => Some(map_snooze(args)),
+        Commands::Handback(args) => Some(map_handback(args)),
         Commands::Claim(args) if !args.peek => Some(map_claim(args)),
         Commands::Poll(args) if args.claim => Some(map_poll_claim(args)),
         Commands::Gate(args) => match &args.command {
@@ -33,6 +35,8 @@ fn map_new(args: &crate::cli::NewArgs) -> WriteOperation {
         title: args.title.clone(),
         description: args.desc.clone(),
         acceptance: args.acceptance.clone(),
+        estimate: args.estimate.clone(),
+        fields: args.fields.clone(),
         state: args.state.clone(),
         profile: args.profile.clone(),
         workflow: args.workflow.clone(),
@@ -42,6 +46,9 @@ fn map_new(args: &crate::cli::NewArgs) -> WriteOperation {
         gate_owner_kind: args.gate_owner_kind.clone(),
         gate_failure_modes: args.gate_failure_modes.clone(),
         lease_id: args.lease.clone(),
+        idempotency_key: args.idempotency_key.clone(),
+        private: args.private,
+        draft: args.draft,
     })
 }
 
@@ -64,6 +71,7 @@ fn map_state(args: &crate::cli::StateArgs) -> WriteOperation {
         agent_name: args.agent_name.clone(),
         agent_model: args.agent_model.clone(),
         agent_version: args.agent_version.clone(),
+        idempotency_key: args.idempotency_key.clone(),
     })
 }
 
@@ -73,6 +81,8 @@ fn map_update(args: &crate::cli::UpdateArgs) -> WriteOperation {
         title: args.title.clone(),
         description: args.description.clone(),
         acceptance: args.acceptance.clone(),
+        estimate: args.estimate.clone(),
+        fields: args.fields.clone(),
         priority: args.priority,
         status: args.status.clone(),
         knot_type: args.knot_type.clone(),
@@ -104,6 +114,7 @@ fn map_update(args: &crate::cli::UpdateArgs) -> WriteOperation {
         force: args.force,
         approve_terminal_cascade: args.cascade_terminal_descendants,
         lease_id: args.lease.clone(),
+        idempotency_key: args.idempotency_key.clone(),
     })
 }
 
@@ -121,6 +132,7 @@ fn map_next(args: &crate::cli::NextArgs) -> WriteOperation {
         agent_model: args.agent_model.clone(),
         agent_version: args.agent_version.clone(),
         lease_id: args.lease.clone(),
+        subtree: args.subtree,
     })
 }
 
@@ -135,6 +147,30 @@ fn map_rollback(args: &crate::cli::RollbackArgs) -> WriteOperation {
     })
 }
 
+fn map_snooze(args: &crate::cli::SnoozeArgs) -> WriteOperation {
+    WriteOperation::Snooze(SnoozeOperation {
+        id: args.id.clone(),
+        until: args.until.clone(),
+        actor_kind: args.actor_kind.clone(),
+        agent_name: args.agent_name.clone(),
+        agent_model: args.agent_model.clone(),
+        agent_version: args.agent_version.clone(),
+    })
+}
+
+fn map_handback(args: &crate::cli::HandbackArgs) -> WriteOperation {
+    WriteOperation::Handback(HandbackOperation {
+        id: args.id.clone(),
+        reason: args.reason.clone(),
+        note: args.note.clone(),
+        cooldown: args.cooldown.clone(),
+        actor_kind: args.actor_kind.clone(),
+        agent_name: args.agent_name.clone(),
+        agent_model: args.agent_model.clone(),
+        agent_version: args.agent_version.clone(),
+    })
+}
+
 fn map_claim(args: &crate::cli::ClaimArgs) -> WriteOperation {
     WriteOperation::Claim(ClaimOperation {
         id: args.id.clone(),
@@ -145,6 +181,7 @@ fn map_claim(args: &crate::cli::ClaimArgs) -> WriteOperation {
         agent_version: args.agent_version.clone(),
         lease_id: args.lease.clone(),
         timeout_seconds: args.timeout_seconds,
+        force: args.force,
     })
 }
 
@@ -185,7 +222,7 @@ fn map_edge(args: &crate::cli::EdgeArgs) -> Option<WriteOperation> {
             kind: edge.kind.clone(),
             dst: edge.dst.clone(),
         })),
-        EdgeSubcommands::List(_) => None,
+        EdgeSubcommands::List(_) | EdgeSubcommands::Apply(_) => None,
     }
 }
 
@@ -55,6 +55,8 @@ fn execute_queued_request_returns_failure_when_app_open_fails() {
             title: "queued".to_string(),
             description: None,
             acceptance: None,
+            estimate: None,
+            fields: Vec::new(),
             state: None,
             profile: None,
             workflow: None,
@@ -64,6 +66,9 @@ fn execute_queued_request_returns_failure_when_app_open_fails() {
             gate_owner_kind: None,
             gate_failure_modes: vec![],
             lease_id: None,
+            idempotency_key: None,
+            private: false,
+            draft: false,
         }),
     };
     let response = execute_queued_request(&request);
@@ -134,6 +139,7 @@ fn execute_operation_next_rejects_mismatched_state() {
         agent_model: None,
         agent_version: None,
         lease_id: None,
+        subtree: false,
     });
     let err = execute_operation(&app, &op).expect_err("mismatch");
     match err {
@@ -337,6 +343,8 @@ fn exploration_op() -> NewOperation {
         title: "Explore caching".to_string(),
         description: None,
         acceptance: None,
+        estimate: None,
+        fields: Vec::new(),
         state: None,
         profile: None,
         workflow: None,
@@ -346,6 +354,9 @@ fn exploration_op() -> NewOperation {
         gate_owner_kind: None,
         gate_failure_modes: vec![],
         lease_id: None,
+        idempotency_key: None,
+        private: false,
+        draft: false,
     }
 }
 
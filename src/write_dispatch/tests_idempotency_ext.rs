@@ -0,0 +1,99 @@
+use clap::Parser;
+
+use super::tests_lease_ext::{open_app, setup_repo, unique_workspace};
+use super::{execute_operation, operation_from_command};
+use crate::app::AppError;
+use crate::cli::Cli;
+use crate::write_queue::WriteOperation;
+
+fn operation(args: &[&str]) -> WriteOperation {
+    let cli = Cli::parse_from(args);
+    operation_from_command(&cli.command).expect("command should map to a write operation")
+}
+
+#[test]
+fn new_with_same_idempotency_key_returns_original_output() {
+    let root = unique_workspace();
+    setup_repo(&root);
+    let app = open_app(&root);
+    let op = operation(&["kno", "new", "Retry me", "--idempotency-key", "tok-1"]);
+
+    let first = execute_operation(&app, &op).expect("first create should succeed");
+    let second = execute_operation(&app, &op).expect("retry should succeed");
+
+    assert_eq!(first, second);
+    assert_eq!(app.list_knots().expect("list").len(), 1);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn idempotency_key_reuse_with_different_payload_is_rejected() {
+    let root = unique_workspace();
+    setup_repo(&root);
+    let app = open_app(&root);
+    let first = operation(&["kno", "new", "Original", "--idempotency-key", "tok-2"]);
+    let changed = operation(&["kno", "new", "Changed", "--idempotency-key", "tok-2"]);
+
+    execute_operation(&app, &first).expect("first create should succeed");
+    let err = execute_operation(&app, &changed).expect_err("changed payload should fail");
+
+    match err {
+        AppError::InvalidArgument(msg) => {
+            assert!(msg.contains("tok-2"), "unexpected message: {msg}");
+            assert!(msg.contains("different new request"), "{msg}");
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+    assert_eq!(app.list_knots().expect("list").len(), 1);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn state_and_update_retries_do_not_reapply() {
+    let root = unique_workspace();
+    setup_repo(&root);
+    let app = open_app(&root);
+    let knot = app
+        .create_knot("Transition", None, Some("ready_for_planning"), None)
+        .expect("create knot");
+    let state = operation(&[
+        "kno",
+        "state",
+        &knot.id,
+        "planning",
+        "--idempotency-key",
+        "s-1",
+    ]);
+    let first = execute_operation(&app, &state).expect("state should apply");
+    let retried = execute_operation(&app, &state).expect("state retry should succeed");
+    assert_eq!(first, retried);
+
+    let note = operation(&[
+        "kno",
+        "update",
+        &knot.id,
+        "--add-note",
+        "once",
+        "--idempotency-key",
+        "u-1",
+    ]);
+    execute_operation(&app, &note).expect("update should apply");
+    execute_operation(&app, &note).expect("update retry should succeed");
+    let shown = app.show_knot(&knot.id).expect("show").expect("knot exists");
+    assert_eq!(shown.notes.len(), 1);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn blank_idempotency_key_is_ignored() {
+    let root = unique_workspace();
+    setup_repo(&root);
+    let app = open_app(&root);
+    let op = operation(&["kno", "new", "Blank key", "--idempotency-key", "  "]);
+
+    execute_operation(&app, &op).expect("first create should succeed");
+    execute_operation(&app, &op).expect("second create should succeed");
+
+    assert_eq!(app.list_knots().expect("list").len(), 2);
+    let _ = std::fs::remove_dir_all(root);
+}
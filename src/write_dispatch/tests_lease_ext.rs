@@ -93,6 +93,7 @@ fn next_terminates_lease() {
         agent_model: Some("test-model".to_string()),
         agent_version: Some("1.0".to_string()),
         lease_id: Some(lease_id.clone()),
+        subtree: false,
     });
     execute_operation(&app, &next_op).expect("next should succeed");
 
@@ -301,6 +302,8 @@ fn new_with_lease_flag_rejects() {
         title: "Lease-bound new".to_string(),
         description: None,
         acceptance: None,
+        estimate: None,
+        fields: Vec::new(),
         state: None,
         profile: None,
         workflow: None,
@@ -310,6 +313,9 @@ fn new_with_lease_flag_rejects() {
         gate_owner_kind: None,
         gate_failure_modes: vec![],
         lease_id: Some(lease_id.clone()),
+        idempotency_key: None,
+        private: false,
+        draft: false,
     });
     let err = execute_operation(&app, &op).expect_err("new should reject lease binding");
     let err_msg = err.to_string();
@@ -347,6 +353,8 @@ fn update_with_lease_flag_rejects_unbound() {
         title: Some("Updated with lease".to_string()),
         description: None,
         acceptance: None,
+        estimate: None,
+        fields: Vec::new(),
         priority: None,
         status: None,
         knot_type: None,
@@ -378,6 +386,7 @@ fn update_with_lease_flag_rejects_unbound() {
         force: false,
         approve_terminal_cascade: false,
         lease_id: Some(lease_id.clone()),
+        idempotency_key: None,
     });
     let err = execute_operation(&app, &op).expect_err("update should reject lease binding");
     let err_msg = err.to_string();
@@ -410,6 +419,8 @@ fn note_auto_fills_from_lease_agent_info() {
         title: None,
         description: None,
         acceptance: None,
+        estimate: None,
+        fields: Vec::new(),
         priority: None,
         status: None,
         knot_type: None,
@@ -441,6 +452,7 @@ fn note_auto_fills_from_lease_agent_info() {
         force: false,
         approve_terminal_cascade: false,
         lease_id: None,
+        idempotency_key: None,
     });
     execute_operation(&app, &op).expect("update with note should succeed");
 
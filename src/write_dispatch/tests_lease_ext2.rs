@@ -22,6 +22,8 @@ fn explicit_note_flags_override_lease_agent_info() {
         title: None,
         description: None,
         acceptance: None,
+        estimate: None,
+        fields: Vec::new(),
         priority: None,
         status: None,
         knot_type: None,
@@ -53,6 +55,7 @@ fn explicit_note_flags_override_lease_agent_info() {
         force: false,
         approve_terminal_cascade: false,
         lease_id: None,
+        idempotency_key: None,
     });
     execute_operation(&app, &op).expect("update should succeed");
 
@@ -81,6 +84,8 @@ fn note_defaults_preserved_without_lease() {
         title: None,
         description: None,
         acceptance: None,
+        estimate: None,
+        fields: Vec::new(),
         priority: None,
         status: None,
         knot_type: None,
@@ -112,6 +117,7 @@ fn note_defaults_preserved_without_lease() {
         force: false,
         approve_terminal_cascade: false,
         lease_id: None,
+        idempotency_key: None,
     });
     execute_operation(&app, &op).expect("update should succeed");
 
@@ -140,6 +146,8 @@ fn handoff_capsule_auto_fills_from_lease_agent_info() {
         title: None,
         description: None,
         acceptance: None,
+        estimate: None,
+        fields: Vec::new(),
         priority: None,
         status: None,
         knot_type: None,
@@ -171,6 +179,7 @@ fn handoff_capsule_auto_fills_from_lease_agent_info() {
         force: false,
         approve_terminal_cascade: false,
         lease_id: None,
+        idempotency_key: None,
     });
     execute_operation(&app, &op).expect("update with handoff should succeed");
 
@@ -204,6 +213,8 @@ fn explicit_handoff_flags_override_lease_agent_info() {
         title: None,
         description: None,
         acceptance: None,
+        estimate: None,
+        fields: Vec::new(),
         priority: None,
         status: None,
         knot_type: None,
@@ -235,6 +246,7 @@ fn explicit_handoff_flags_override_lease_agent_info() {
         force: false,
         approve_terminal_cascade: false,
         lease_id: None,
+        idempotency_key: None,
     });
     execute_operation(&app, &op).expect("update should succeed");
 
@@ -353,6 +365,7 @@ fn next_with_matching_lease_succeeds() {
         agent_model: None,
         agent_version: None,
         lease_id: Some(lease_id),
+        subtree: false,
     });
     let result = execute_operation(&app, &next_op);
     assert!(result.is_ok(), "next with matching lease should succeed");
@@ -388,6 +401,7 @@ fn next_with_wrong_lease_fails() {
         agent_model: None,
         agent_version: None,
         lease_id: Some("wrong-lease-id".to_string()),
+        subtree: false,
     });
     let result = execute_operation(&app, &next_op);
     assert!(result.is_err(), "next with wrong lease should fail");
@@ -429,6 +443,7 @@ fn next_without_lease_fails_when_knot_has_bound_lease() {
         agent_model: None,
         agent_version: None,
         lease_id: None,
+        subtree: false,
     });
     let result = execute_operation(&app, &next_op);
     assert!(
@@ -443,45 +458,3 @@ fn next_without_lease_fails_when_knot_has_bound_lease() {
 
     let _ = std::fs::remove_dir_all(root);
 }
-
-#[test]
-fn next_with_lease_on_unleasedknot_fails() {
-    let root = unique_workspace();
-    setup_repo(&root);
-    let app = open_app(&root);
-
-    let work = app
-        .create_knot("No lease on knot", None, Some("work_item"), Some("default"))
-        .expect("create knot");
-
-    // Claim without creating a lease (no agent_name)
-    let actor = StateActorMetadata {
-        actor_kind: Some("agent".to_string()),
-        agent_name: None,
-        agent_model: None,
-        agent_version: None,
-    };
-    let claimed = poll_claim::claim_knot(&app, &work.id, actor, None, 600).expect("claim");
-    assert!(claimed.knot.lease_id.is_none(), "should not have a lease");
-
-    let next_op = WriteOperation::Next(NextOperation {
-        id: work.id.clone(),
-        expected_state: Some(claimed.knot.state.clone()),
-        json: false,
-        approve_terminal_cascade: false,
-        actor_kind: None,
-        agent_name: None,
-        agent_model: None,
-        agent_version: None,
-        lease_id: Some("fake-lease".to_string()),
-    });
-    let result = execute_operation(&app, &next_op);
-    assert!(result.is_err(), "should fail when knot has no lease");
-    let err = result.unwrap_err().to_string();
-    assert!(
-        err.contains("no active lease"),
-        "error should mention no active lease: {err}"
-    );
-
-    let _ = std::fs::remove_dir_all(root);
-}
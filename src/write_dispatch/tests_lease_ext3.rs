@@ -1,6 +1,6 @@
 use crate::app::StateActorMetadata;
 use crate::poll_claim;
-use crate::write_queue::{UpdateOperation, WriteOperation};
+use crate::write_queue::{NextOperation, UpdateOperation, WriteOperation};
 
 use super::execute_operation;
 use super::tests_lease_ext::{create_test_lease, open_app, setup_repo, unique_workspace};
@@ -11,6 +11,8 @@ fn update_operation(id: &str, title: &str, lease_id: Option<String>) -> WriteOpe
         title: Some(title.to_string()),
         description: None,
         acceptance: None,
+        estimate: None,
+        fields: Vec::new(),
         priority: None,
         status: None,
         knot_type: None,
@@ -42,6 +44,7 @@ fn update_operation(id: &str, title: &str, lease_id: Option<String>) -> WriteOpe
         force: false,
         approve_terminal_cascade: false,
         lease_id,
+        idempotency_key: None,
     })
 }
 
@@ -176,3 +179,46 @@ fn claimed_without_lease_then_update_cannot_bind() {
 
     let _ = std::fs::remove_dir_all(root);
 }
+
+#[test]
+fn next_with_lease_on_unleasedknot_fails() {
+    let root = unique_workspace();
+    setup_repo(&root);
+    let app = open_app(&root);
+
+    let work = app
+        .create_knot("No lease on knot", None, Some("work_item"), Some("default"))
+        .expect("create knot");
+
+    // Claim without creating a lease (no agent_name)
+    let actor = StateActorMetadata {
+        actor_kind: Some("agent".to_string()),
+        agent_name: None,
+        agent_model: None,
+        agent_version: None,
+    };
+    let claimed = poll_claim::claim_knot(&app, &work.id, actor, None, 600).expect("claim");
+    assert!(claimed.knot.lease_id.is_none(), "should not have a lease");
+
+    let next_op = WriteOperation::Next(NextOperation {
+        id: work.id.clone(),
+        expected_state: Some(claimed.knot.state.clone()),
+        json: false,
+        approve_terminal_cascade: false,
+        actor_kind: None,
+        agent_name: None,
+        agent_model: None,
+        agent_version: None,
+        lease_id: Some("fake-lease".to_string()),
+        subtree: false,
+    });
+    let result = execute_operation(&app, &next_op);
+    assert!(result.is_err(), "should fail when knot has no lease");
+    let err = result.unwrap_err().to_string();
+    assert!(
+        err.contains("no active lease"),
+        "error should mention no active lease: {err}"
+    );
+
+    let _ = std::fs::remove_dir_all(root);
+}
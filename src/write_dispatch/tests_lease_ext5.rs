@@ -21,6 +21,8 @@ fn update_op_with_lease(id: &str, lease_id: &str) -> WriteOperation {
         title: Some("heartbeat-check".to_string()),
         description: None,
         acceptance: None,
+        estimate: None,
+        fields: Vec::new(),
         priority: None,
         status: None,
         knot_type: None,
@@ -52,6 +54,7 @@ fn update_op_with_lease(id: &str, lease_id: &str) -> WriteOperation {
         force: false,
         approve_terminal_cascade: false,
         lease_id: Some(lease_id.to_string()),
+        idempotency_key: None,
     })
 }
 
@@ -255,9 +255,11 @@ fn normalize_and_format_helpers() {
         body: None,
         description: None,
         acceptance: None,
+        estimate: None,
         priority: None,
         knot_type: KnotType::Work,
         tags: vec![],
+        custom_fields: Default::default(),
         notes: vec![],
         handoff_capsules: vec![],
         invariants: vec![],
@@ -273,10 +275,15 @@ fn normalize_and_format_helpers() {
         deferred_from_state: None,
         blocked_from_state: None,
         created_at: None,
+        snoozed_until: None,
         step_metadata: None,
         next_step_metadata: None,
         edges: vec![],
         child_summaries: vec![],
+        git_links: vec![],
+        criteria: Vec::new(),
+        handbacks: Vec::new(),
+        plan: None,
     };
 
     let text = format_next_output(&knot, "idea", Some("agent"), false);
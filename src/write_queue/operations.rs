@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NewOperation {
+    pub title: String,
+    pub description: Option<String>,
+    pub acceptance: Option<String>,
+    #[serde(default)]
+    pub estimate: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<String>,
+    pub state: Option<String>,
+    pub profile: Option<String>,
+    pub workflow: Option<String>,
+    pub fast: bool,
+    pub exploration: bool,
+    pub knot_type: Option<String>,
+    pub gate_owner_kind: Option<String>,
+    pub gate_failure_modes: Vec<String>,
+    pub lease_id: Option<String>,
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    #[serde(default)]
+    pub private: bool,
+    #[serde(default)]
+    pub draft: bool,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuickNewOperation {
+    pub title: String,
+    pub description: Option<String>,
+    pub state: Option<String>,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StateOperation {
+    pub id: String,
+    pub state: String,
+    pub force: bool,
+    pub approve_terminal_cascade: bool,
+    pub if_match: Option<String>,
+    pub actor_kind: Option<String>,
+    pub agent_name: Option<String>,
+    pub agent_model: Option<String>,
+    pub agent_version: Option<String>,
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UpdateOperation {
+    pub id: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub acceptance: Option<String>,
+    #[serde(default)]
+    pub estimate: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<String>,
+    pub priority: Option<i64>,
+    pub status: Option<String>,
+    pub knot_type: Option<String>,
+    pub add_tags: Vec<String>,
+    pub remove_tags: Vec<String>,
+    pub add_invariants: Vec<String>,
+    pub remove_invariants: Vec<String>,
+    pub clear_invariants: bool,
+    pub gate_owner_kind: Option<String>,
+    pub gate_failure_modes: Vec<String>,
+    pub clear_gate_failure_modes: bool,
+    pub add_note: Option<String>,
+    pub note_username: Option<String>,
+    pub note_datetime: Option<String>,
+    pub note_agentname: Option<String>,
+    pub note_model: Option<String>,
+    pub note_version: Option<String>,
+    pub add_handoff_capsule: Option<String>,
+    pub handoff_username: Option<String>,
+    pub handoff_datetime: Option<String>,
+    pub handoff_agentname: Option<String>,
+    pub handoff_model: Option<String>,
+    pub handoff_version: Option<String>,
+    pub if_match: Option<String>,
+    pub actor_kind: Option<String>,
+    pub agent_name: Option<String>,
+    pub agent_model: Option<String>,
+    pub agent_version: Option<String>,
+    pub force: bool,
+    pub approve_terminal_cascade: bool,
+    pub lease_id: Option<String>,
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NextOperation {
+    pub id: String,
+    pub expected_state: Option<String>,
+    pub json: bool,
+    pub approve_terminal_cascade: bool,
+    pub actor_kind: Option<String>,
+    pub agent_name: Option<String>,
+    pub agent_model: Option<String>,
+    pub agent_version: Option<String>,
+    pub lease_id: Option<String>,
+    #[serde(default)]
+    pub subtree: bool,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RollbackOperation {
+    pub id: String,
+    pub dry_run: bool,
+    pub actor_kind: Option<String>,
+    pub agent_name: Option<String>,
+    pub agent_model: Option<String>,
+    pub agent_version: Option<String>,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SnoozeOperation {
+    pub id: String,
+    pub until: String,
+    pub actor_kind: Option<String>,
+    pub agent_name: Option<String>,
+    pub agent_model: Option<String>,
+    pub agent_version: Option<String>,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HandbackOperation {
+    pub id: String,
+    pub reason: String,
+    pub note: Option<String>,
+    pub cooldown: Option<String>,
+    pub actor_kind: Option<String>,
+    pub agent_name: Option<String>,
+    pub agent_model: Option<String>,
+    pub agent_version: Option<String>,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ClaimOperation {
+    pub id: String,
+    pub json: bool,
+    pub verbose: bool,
+    pub agent_name: Option<String>,
+    pub agent_model: Option<String>,
+    pub agent_version: Option<String>,
+    pub lease_id: Option<String>,
+    pub timeout_seconds: Option<u64>,
+    #[serde(default)]
+    pub force: bool,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PollClaimOperation {
+    pub stage: Option<String>,
+    pub owner: Option<String>,
+    pub json: bool,
+    pub agent_name: Option<String>,
+    pub agent_model: Option<String>,
+    pub agent_version: Option<String>,
+    pub timeout_seconds: Option<u64>,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GateEvaluateOperation {
+    pub id: String,
+    pub decision: String,
+    pub invariant: Option<String>,
+    pub json: bool,
+    pub actor_kind: Option<String>,
+    pub agent_name: Option<String>,
+    pub agent_model: Option<String>,
+    pub agent_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EdgeOperation {
+    pub src: String,
+    pub kind: String,
+    pub dst: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StepAnnotateOperation {
+    pub id: String,
+    pub actor_kind: Option<String>,
+    pub agent_name: Option<String>,
+    pub agent_model: Option<String>,
+    pub agent_version: Option<String>,
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LeaseCreateOperation {
+    pub nickname: String,
+    pub lease_type: String,
+    pub agent_type: Option<String>,
+    pub provider: Option<String>,
+    pub agent_name: Option<String>,
+    pub model: Option<String>,
+    pub model_version: Option<String>,
+    pub json: bool,
+    pub timeout_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LeaseTerminateOperation {
+    pub id: String,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LeaseExtendOperation {
+    pub lease_id: String,
+    pub timeout_seconds: Option<u64>,
+    pub json: bool,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
+pub enum WriteOperation {
+    New(NewOperation),
+    QuickNew(QuickNewOperation),
+    State(StateOperation),
+    Update(UpdateOperation),
+    Next(NextOperation),
+    Rollback(RollbackOperation),
+    Snooze(SnoozeOperation),
+    Handback(HandbackOperation),
+    Claim(ClaimOperation),
+    PollClaim(PollClaimOperation),
+    GateEvaluate(GateEvaluateOperation),
+    EdgeAdd(EdgeOperation),
+    EdgeRemove(EdgeOperation),
+    StepAnnotate(StepAnnotateOperation),
+    LeaseCreate(LeaseCreateOperation),
+    LeaseTerminate(LeaseTerminateOperation),
+    LeaseExtend(LeaseExtendOperation),
+}
@@ -41,6 +41,7 @@ fn state_request(
             agent_name: None,
             agent_model: None,
             agent_version: None,
+            idempotency_key: None,
         }),
     }
 }
@@ -96,6 +97,7 @@ fn enqueue_and_wait_round_trips_response() {
             agent_model: None,
             agent_version: None,
             lease_id: None,
+            subtree: false,
         }),
         |request| QueuedWriteResponse::success(request.request_id.clone()),
     )
@@ -174,6 +176,7 @@ fn enqueue_and_wait_spins_until_worker_lock_is_released() {
             agent_version: None,
             lease_id: None,
             timeout_seconds: None,
+            force: false,
         }),
         |request| QueuedWriteResponse::success(request.request_id.clone()),
     )
@@ -0,0 +1,92 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+
+fn knot_json(root: &std::path::Path, db: &std::path::Path, id: &str) -> serde_json::Value {
+    let shown = run_knots(root, db, &["show", id, "--json"]);
+    assert_success(&shown);
+    serde_json::from_slice(&shown.stdout).expect("show json should parse")
+}
+
+#[test]
+fn automerge_hook_advances_knots_referenced_by_the_merged_pr() {
+    let root = unique_workspace("knots-cli-automerge");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let mut ids = Vec::new();
+    for (title, profile) in [
+        ("By trailer", "autopilot_with_pr"),
+        ("By branch", "autopilot_with_pr"),
+        ("No PR output", "autopilot"),
+    ] {
+        let created = run_knots(&root, &db, &["new", title, "--profile", profile]);
+        assert_success(&created);
+        ids.push(parse_created_id(&created));
+    }
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["link", &ids[1], "--branch", "feature/login"],
+    ));
+
+    std::fs::write(root.join("login.rs"), "fn login() {}\n").expect("source should write");
+    run_git(&root, &["add", "login.rs"]);
+    let message = format!("Fix login (#42)\n\nKNO: {}", ids[0]);
+    run_git(&root, &["commit", "-m", &message]);
+
+    let hook = run_knots(
+        &root,
+        &db,
+        &[
+            "automerge-hook",
+            "--commit",
+            "HEAD",
+            "--pr",
+            "#42",
+            "--branch",
+            "feature/login",
+            "--knot",
+            &ids[2],
+            "--agent-name",
+            "ci",
+            "--json",
+        ],
+    );
+    assert_success(&hook);
+    let outcomes: serde_json::Value = serde_json::from_slice(&hook.stdout).expect("json");
+    let outcomes = outcomes.as_array().expect("outcomes");
+    assert_eq!(outcomes.len(), 3, "{outcomes:?}");
+    for id in &ids[..2] {
+        let knot = knot_json(&root, &db, id);
+        assert_eq!(knot["state"], "ready_for_shipment_review", "{knot}");
+        let note = knot["notes"].as_array().expect("notes").last().cloned();
+        let note = note.expect("merge note");
+        assert!(note["content"].as_str().expect("content").contains("#42"));
+        assert_eq!(note["agentname"], "ci");
+        let links = knot["git_links"].as_array().expect("git links");
+        assert!(links
+            .iter()
+            .any(|link| link["kind"] == "commit" && link["subject"] == "Fix login (#42)"));
+    }
+    let untouched = knot_json(&root, &db, &ids[2]);
+    assert_eq!(untouched["state"], "ready_for_planning");
+    assert_eq!(untouched["git_links"][0]["kind"], "commit");
+    assert_eq!(
+        outcomes[0]["skipped"],
+        "profile 'autopilot' has no pr output"
+    );
+    assert_eq!(outcomes[1]["to_state"], "ready_for_shipment_review");
+
+    let again = run_knots(
+        &root,
+        &db,
+        &["automerge-hook", "--commit", "HEAD", "--knot", &ids[0]],
+    );
+    assert_success(&again);
+    assert!(String::from_utf8_lossy(&again.stdout).contains("linked only (already at"));
+
+    let unreferenced = run_knots(&root, &db, &["automerge-hook", "--commit", "HEAD~1"]);
+    assert_failure(&unreferenced);
+    assert!(String::from_utf8_lossy(&unreferenced.stderr).contains("references no knots"));
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,35 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+
+#[test]
+fn changelog_lists_knots_shipped_since_a_tag() {
+    let root = unique_workspace("knots-cli-changelog");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let tag = std::process::Command::new("git")
+        .arg("-C")
+        .arg(&root)
+        .args(["tag", "v0.1.0"])
+        .output()
+        .expect("git tag");
+    assert!(tag.status.success());
+    let created = run_knots(&root, &db, &["new", "Fix the parser"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run_knots(&root, &db, &["update", &id, "--add-tag", "bug"]));
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["state", &id, "shipped", "--force"],
+    ));
+
+    let output = run_knots(&root, &db, &["changelog", "--since", "v0.1.0"]);
+    assert_success(&output);
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        text,
+        format!("## Changes since v0.1.0\n\n### Bug fixes\n- Fix the parser (`{id}`)\n")
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,67 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+fn write_snapshot(dir: &std::path::Path, stamp: &str, title: &str) {
+    std::fs::create_dir_all(dir).expect("snapshots dir should be creatable");
+    let written_at = "2026-10-01T00:00:00Z";
+    let active = serde_json::json!({
+        "schema_version": 1, "written_at": written_at, "hot": [], "warm": [],
+    });
+    let cold = serde_json::json!({
+        "schema_version": 1,
+        "written_at": written_at,
+        "cold": [{
+            "id": "knots-c0ld",
+            "title": title,
+            "state": "shipped",
+            "updated_at": "2026-09-01T00:00:00Z",
+        }],
+    });
+    std::fs::write(
+        dir.join(format!("{stamp}-active_catalog.snapshot.json")),
+        active.to_string(),
+    )
+    .expect("active snapshot should write");
+    std::fs::write(
+        dir.join(format!("{stamp}-cold_catalog.snapshot.json")),
+        cold.to_string(),
+    )
+    .expect("cold snapshot should write");
+}
+
+#[test]
+fn cold_ls_from_snapshot_reads_a_knots_branch_checkout_without_a_cache() {
+    let root = unique_workspace("knots-cli-cold-ls-snapshot");
+    setup_repo(&root);
+    let db = root.join("cache/state.sqlite");
+    let snapshots = root.join(".knots/snapshots");
+
+    let missing = run_knots(&root, &db, &["cold", "ls", "--from-snapshot"]);
+    assert_failure(&missing);
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("no snapshot found"));
+
+    write_snapshot(&snapshots, "20260901T000000Z", "Old cold");
+    write_snapshot(&snapshots, "20261001T000000Z", "Archived");
+    let listed = run_knots(&root, &db, &["cold", "ls", "--from-snapshot", "--json"]);
+    assert_success(&listed);
+    let knots: Value = serde_json::from_slice(&listed.stdout).expect("cold ls json");
+    assert_eq!(knots.as_array().map(Vec::len), Some(1));
+    assert_eq!(knots[0]["title"], "Archived");
+
+    let text = run_knots(&root, &db, &["cold", "ls", "--from-snapshot"]);
+    assert_success(&text);
+    assert_eq!(
+        String::from_utf8_lossy(&text.stdout),
+        "knots-c0ld [shipped] Archived (2026-09-01T00:00:00Z)\n"
+    );
+    assert!(String::from_utf8_lossy(&text.stderr).contains("20261001T000000Z"));
+    assert!(!db.exists(), "the cache should never be opened");
+
+    let cached = run_knots(&root, &db, &["cold", "ls"]);
+    assert_success(&cached);
+    assert_eq!(String::from_utf8_lossy(&cached.stdout), "no cold knots\n");
+
+    let _ = std::fs::remove_dir_all(root);
+}
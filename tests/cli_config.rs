@@ -0,0 +1,226 @@
+mod cli_dispatch_helpers;
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn config_get_set_and_list_round_trip() {
+    let root = unique_workspace("knots-cli-config");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+
+    let get = run_knots(&root, &db, &["config", "get", "sync_policy"]);
+    assert_success(&get);
+    assert_eq!(String::from_utf8_lossy(&get.stdout).trim(), "auto");
+
+    let set = run_knots(&root, &db, &["config", "set", "sync_policy", "never"]);
+    assert_success(&set);
+    assert!(String::from_utf8_lossy(&set.stdout).contains("sync_policy = never"));
+
+    let bad = run_knots(
+        &root,
+        &db,
+        &["config", "set", "sync_auto_budget_ms", "fast"],
+    );
+    assert_failure(&bad);
+    assert!(String::from_utf8_lossy(&bad.stderr).contains("non-negative integer"));
+
+    let list = run_knots(&root, &db, &["config", "list", "--json"]);
+    assert_success(&list);
+    let entries: Value = serde_json::from_slice(&list.stdout).expect("list json");
+    let policy = entries
+        .as_array()
+        .expect("array")
+        .iter()
+        .find(|e| e["key"] == "sync_policy")
+        .expect("sync_policy entry");
+    assert_eq!(policy["value"], "never");
+    assert_eq!(policy["source"], "local");
+
+    let text = run_knots(&root, &db, &["config", "list"]);
+    assert_success(&text);
+    assert!(String::from_utf8_lossy(&text.stdout).contains("hot_window_days"));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn repo_config_travels_through_the_knots_branch() {
+    let root = unique_workspace("knots-cli-repo-config");
+    let remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["init"]));
+
+    let set = run_knots(
+        &root,
+        &db,
+        &["config", "set", "--repo", "pull_drift_warn_threshold", "40"],
+    );
+    assert_success(&set);
+    assert!(String::from_utf8_lossy(&set.stdout).contains("(repo)"));
+    assert_success(&run_knots(&root, &db, &["push"]));
+
+    let clone = unique_workspace("knots-cli-repo-config-clone");
+    let cloned = std::process::Command::new("git")
+        .arg("clone")
+        .arg(&remote)
+        .arg(&clone)
+        .output()
+        .expect("git clone should run");
+    assert!(cloned.status.success());
+    run_git(&clone, &["config", "user.email", "knots@example.com"]);
+    run_git(&clone, &["config", "user.name", "Knots Test"]);
+    let clone_db = clone.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&clone, &clone_db, &["init"]));
+
+    let get = run_knots(
+        &clone,
+        &clone_db,
+        &["config", "get", "pull_drift_warn_threshold", "--json"],
+    );
+    assert_success(&get);
+    let entry: Value = serde_json::from_slice(&get.stdout).expect("get json");
+    assert_eq!(entry["value"], "40");
+    assert_eq!(entry["source"], "repo");
+
+    let _ = std::fs::remove_dir_all(root);
+    let _ = std::fs::remove_dir_all(clone);
+}
+
+fn run_knots_with_input(root: &Path, db: &Path, args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(knots_binary())
+        .arg("--repo-root")
+        .arg(root)
+        .arg("--db")
+        .arg(db)
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .env("HOME", root)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("knots command should spawn");
+    child
+        .stdin
+        .as_mut()
+        .expect("stdin should be available")
+        .write_all(input.as_bytes())
+        .expect("stdin should be writable");
+    child
+        .wait_with_output()
+        .expect("knots command should finish")
+}
+
+#[test]
+fn interactive_init_writes_answers_and_creates_branch() {
+    let root = unique_workspace("knots-cli-init-wizard");
+    let remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+
+    let init = run_knots_with_input(
+        &root,
+        &db,
+        &["init", "--interactive"],
+        "semiauto\nnever\ntracker\nops\ny\n",
+    );
+    assert_success(&init);
+    let stdout = String::from_utf8_lossy(&init.stdout);
+    assert!(
+        stdout.contains("remote branch     origin/tracker (created)"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("kno init completed"));
+
+    let config = std::fs::read_to_string(root.join(".knots/config.toml")).expect("config");
+    assert!(
+        config.contains("default_profile = \"semiauto\""),
+        "{config}"
+    );
+    assert!(config.contains("branch = \"tracker\""), "{config}");
+    assert!(config.contains("sync_policy = \"never\""), "{config}");
+    assert!(config.contains("id_prefix = \"ops\""), "{config}");
+
+    let heads = Command::new("git")
+        .arg("--git-dir")
+        .arg(&remote)
+        .args(["branch", "--list"])
+        .output()
+        .expect("git branch should run");
+    let heads = String::from_utf8_lossy(&heads.stdout);
+    assert!(
+        heads.contains("tracker") && !heads.contains("knots"),
+        "{heads}"
+    );
+
+    let created = run_knots(&root, &db, &["new", "Wizard knot"]);
+    assert_success(&created);
+    let show = run_knots(&root, &db, &["show", &parse_created_id(&created), "--json"]);
+    assert_success(&show);
+    let knot: Value = serde_json::from_slice(&show.stdout).expect("show json");
+    assert!(
+        knot["id"].as_str().expect("id").starts_with("ops-"),
+        "{knot}"
+    );
+    assert_success(&run_knots(&root, &db, &["push"]));
+    let heads = Command::new("git")
+        .arg("--git-dir")
+        .arg(&remote)
+        .args(["ls-tree", "-r", "--name-only", "tracker"])
+        .output()
+        .expect("git ls-tree should run");
+    assert!(String::from_utf8_lossy(&heads.stdout).contains(".knots/config.toml"));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn init_uses_custom_branch_and_remote_names() {
+    let root = unique_workspace("knots-cli-knots-ref");
+    setup_repo(&root);
+    let remote = root.join("remote.git");
+    run_git(&root, &["init", "--bare", remote.to_str().expect("utf8")]);
+    run_git(
+        &root,
+        &["remote", "add", "upstream", remote.to_str().expect("utf8")],
+    );
+    let db = root.join(".knots/cache/state.sqlite");
+
+    let init = run_knots(
+        &root,
+        &db,
+        &["init", "--branch", "tracker", "--remote", "upstream"],
+    );
+    assert_success(&init);
+    assert!(String::from_utf8_lossy(&init.stdout).contains("upstream/tracker initialized"));
+
+    assert_success(&run_knots(&root, &db, &["new", "Custom ref knot"]));
+    let push = run_knots(&root, &db, &["push"]);
+    assert_success(&push);
+    let log = Command::new("git")
+        .arg("--git-dir")
+        .arg(&remote)
+        .args(["log", "--oneline", "tracker"])
+        .output()
+        .expect("git log should run");
+    assert!(String::from_utf8_lossy(&log.stdout).contains("knots: publish local events"));
+
+    let doctor = run_knots(&root, &db, &["doctor", "--json"]);
+    let report: Value = serde_json::from_slice(&doctor.stdout).expect("doctor json");
+    let remote_check = report["checks"]
+        .as_array()
+        .expect("checks")
+        .iter()
+        .find(|check| check["name"] == "remote")
+        .expect("remote check");
+    assert_eq!(
+        remote_check["detail"],
+        "upstream reachable; tracker branch exists"
+    );
+
+    let bad = run_knots(&root, &db, &["init-remote", "--branch", "bad name"]);
+    assert_failure(&bad);
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,58 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn criteria_are_checked_off_and_rendered_as_checkboxes() {
+    let root = unique_workspace("knots-cli-criteria");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Fix login"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    let empty = run_knots(&root, &db, &["criteria", "list", &id]);
+    assert_success(&empty);
+    assert!(String::from_utf8_lossy(&empty.stdout).contains("no acceptance criteria"));
+    for text in ["returns 400 on bad input", "logs the SSO error"] {
+        assert_success(&run_knots(&root, &db, &["criteria", "add", &id, text]));
+    }
+    let checked = run_knots(&root, &db, &["criteria", "check", &id, "2"]);
+    assert_success(&checked);
+    let stdout = String::from_utf8_lossy(&checked.stdout);
+    assert!(
+        stdout.contains("[ ] 1. returns 400 on bad input"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("[x] 2. logs the SSO error"), "{stdout}");
+    assert!(stdout.contains("1/2 checked"), "{stdout}");
+    assert_failure(&run_knots(&root, &db, &["criteria", "check", &id, "3"]));
+
+    let listed = run_knots(&root, &db, &["criteria", "list", &id, "--json"]);
+    assert_success(&listed);
+    let criteria: Value = serde_json::from_slice(&listed.stdout).expect("criteria json");
+    assert_eq!(criteria.as_array().map(Vec::len), Some(2));
+    assert_eq!(criteria[1]["checked"], true);
+
+    let shown = run_knots(&root, &db, &["show", &id]);
+    assert_success(&shown);
+    let stdout = String::from_utf8_lossy(&shown.stdout);
+    assert!(stdout.contains("[x] 2. logs the SSO error"), "{stdout}");
+
+    let body = run_knots(&root, &db, &["pr-body", &id]);
+    assert_success(&body);
+    assert_contains_in_order(
+        &String::from_utf8_lossy(&body.stdout),
+        &[
+            "### Acceptance criteria",
+            "- [ ] returns 400 on bad input",
+            "- [x] logs the SSO error",
+        ],
+    );
+
+    assert_success(&run_knots(&root, &db, &["criteria", "uncheck", &id, "2"]));
+    let listed = run_knots(&root, &db, &["criteria", "list", &id]);
+    assert!(String::from_utf8_lossy(&listed.stdout).contains("0/2 checked"));
+    let _ = std::fs::remove_dir_all(root);
+}
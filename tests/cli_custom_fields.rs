@@ -0,0 +1,61 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+
+#[test]
+fn custom_fields_set_on_new_and_update_and_filter_ls() {
+    let root = unique_workspace("knots-cli-custom-fields");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    std::fs::create_dir_all(root.join(".knots")).expect("create store dir");
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "[fields.customer]\ntype = \"string\"\n\n[fields.severity]\ntype = \"enum\"\nvalues = [\"low\", \"high\"]\n",
+    )
+    .expect("write repo config");
+
+    let acme = run_knots(
+        &root,
+        &db,
+        &[
+            "new",
+            "Acme outage",
+            "--field",
+            "customer=acme",
+            "--field",
+            "severity=high",
+        ],
+    );
+    assert_success(&acme);
+    let acme = parse_created_id(&acme);
+    let other = run_knots(&root, &db, &["new", "Other work"]);
+    assert_success(&other);
+    let other = parse_created_id(&other);
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["update", &other, "--field", "customer=globex"],
+    ));
+    assert_failure(&run_knots(
+        &root,
+        &db,
+        &["update", &other, "--field", "severity=urgent"],
+    ));
+
+    let listed = run_knots(&root, &db, &["ls", "--field", "customer=acme", "--json"]);
+    assert_success(&listed);
+    let listed: serde_json::Value = serde_json::from_slice(&listed.stdout).expect("json");
+    let listed = listed.as_array().expect("array");
+    assert_eq!(listed.len(), 1);
+    assert!(listed[0]["id"]
+        .as_str()
+        .expect("id")
+        .ends_with(acme.as_str()));
+    assert_eq!(listed[0]["custom_fields"]["severity"], "high");
+
+    let unset = run_knots(&root, &db, &["ls", "--field", "severity=", "--json"]);
+    assert_success(&unset);
+    let unset: serde_json::Value = serde_json::from_slice(&unset.stdout).expect("json");
+    assert_eq!(unset.as_array().expect("array").len(), 1);
+    let _ = std::fs::remove_dir_all(root);
+}
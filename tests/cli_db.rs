@@ -0,0 +1,153 @@
+mod cli_dispatch_helpers;
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+/// Runs without `--db`, so the cache location is discovered.
+fn run_discovered(root: &Path, location: &str, args: &[&str]) -> Output {
+    Command::new(knots_binary())
+        .arg("--repo-root")
+        .arg(root)
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .env("HOME", root)
+        .env("XDG_CACHE_HOME", root.join("xdg-cache"))
+        .env("KNOTS_CACHE_LOCATION", location)
+        .args(args)
+        .output()
+        .expect("knots command should run")
+}
+
+#[test]
+fn db_info_reports_versions_and_newer_caches_are_refused() {
+    let root = unique_workspace("knots-cli-db-info");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["new", "Schema knot"]));
+
+    let info = run_knots(&root, &db, &["db", "info", "--json"]);
+    assert_success(&info);
+    let info: Value = serde_json::from_slice(&info.stdout).expect("db info json");
+    let supported = info["supported_schema_version"]
+        .as_i64()
+        .expect("supported version");
+    assert_eq!(info["schema_version"], supported);
+    assert_eq!(info["compatibility"], "current");
+    assert_eq!(info["writer_version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        info["migrations"].as_array().expect("migrations").len() as i64,
+        supported
+    );
+
+    let conn = rusqlite::Connection::open(&db).expect("open cache");
+    conn.execute(
+        "UPDATE meta SET value = ?1 WHERE key = 'schema_version'",
+        [(supported + 1).to_string()],
+    )
+    .expect("bump schema version");
+    conn.execute(
+        "UPDATE meta SET value = '99.0.0' WHERE key = 'schema_writer_version'",
+        [],
+    )
+    .expect("set writer");
+    drop(conn);
+
+    let refused = run_knots(&root, &db, &["ls"]);
+    assert_failure(&refused);
+    let stderr = String::from_utf8_lossy(&refused.stderr);
+    assert!(stderr.contains("written by kno 99.0.0"), "{stderr}");
+    assert!(stderr.contains("kno upgrade"), "{stderr}");
+
+    let text = run_knots(&root, &db, &["db", "info"]);
+    assert_success(&text);
+    assert_contains_in_order(
+        &String::from_utf8_lossy(&text.stdout),
+        &[
+            "schema",
+            &format!("{} (kno", supported + 1),
+            "writer",
+            "kno 99.0.0",
+            "status",
+            "written by a newer kno",
+            "applied",
+        ],
+    );
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn db_vacuum_checks_integrity_and_reports_sizes() {
+    let root = unique_workspace("knots-cli-db-vacuum");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    for idx in 0..5 {
+        assert_success(&run_knots(&root, &db, &["new", &format!("Churn {idx}")]));
+    }
+
+    let vacuum = run_knots(&root, &db, &["db", "vacuum", "--json"]);
+    assert_success(&vacuum);
+    let report: Value = serde_json::from_slice(&vacuum.stdout).expect("vacuum json");
+    assert_eq!(report["integrity_problems"], Value::Array(Vec::new()));
+    assert_eq!(report["vacuumed"], true);
+    assert!(report["bytes_before"].as_u64().expect("before") > 0);
+    assert!(report["bytes_after"].as_u64().expect("after") > 0);
+    let wal = std::fs::metadata(format!("{}-wal", db.display()))
+        .map(|meta| meta.len())
+        .unwrap_or(0);
+    assert_eq!(wal, 0, "wal should be truncated");
+
+    let text = run_knots(&root, &db, &["db", "vacuum"]);
+    assert_success(&text);
+    assert_contains_in_order(
+        &String::from_utf8_lossy(&text.stdout),
+        &["integrity ok", "vacuumed", " -> "],
+    );
+    assert_success(&run_knots(&root, &db, &["ls"]));
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn xdg_caches_are_discovered_and_can_move_back_into_the_repo() {
+    let root = unique_workspace("knots-cli-db-xdg");
+    setup_repo(&root);
+    let root = std::fs::canonicalize(&root).expect("canonical root");
+
+    assert_success(&run_discovered(&root, "xdg", &["new", "XDG knot"]));
+    assert!(!root.join(".knots/cache/state.sqlite").exists());
+    let info = run_discovered(&root, "repo", &["db", "info", "--json"]);
+    assert_success(&info);
+    let info: Value = serde_json::from_slice(&info.stdout).expect("db info json");
+    assert_eq!(
+        info["location"], "xdg",
+        "existing xdg cache wins over the preference"
+    );
+    let xdg_db = info["path"].as_str().expect("path").to_string();
+    assert!(xdg_db.starts_with(root.join("xdg-cache/knots").to_str().expect("utf8")));
+
+    assert_success(&run_discovered(
+        &root,
+        "xdg",
+        &["db", "move", "--to", "repo"],
+    ));
+    assert!(root.join(".knots/cache/state.sqlite").exists());
+    assert!(!Path::new(&xdg_db).exists());
+    let listed = run_discovered(&root, "xdg", &["ls"]);
+    assert_success(&listed);
+    assert!(String::from_utf8_lossy(&listed.stdout).contains("XDG knot"));
+
+    assert_success(&run_discovered(
+        &root,
+        "repo",
+        &["db", "move", "--to", "xdg"],
+    ));
+    assert!(Path::new(&xdg_db).exists());
+    assert!(!root.join(".knots/cache/state.sqlite").exists());
+    let listed = run_discovered(&root, "repo", &["ls"]);
+    assert!(String::from_utf8_lossy(&listed.stdout).contains("XDG knot"));
+
+    let _ = std::fs::remove_dir_all(root);
+}
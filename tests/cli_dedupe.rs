@@ -0,0 +1,49 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn new_warns_about_similar_knots_once_enabled_and_report_lists_pairs() {
+    let root = unique_workspace("knots-cli-dedupe");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let first = run_knots(&root, &db, &["new", "Fix login timeout"]);
+    assert_success(&first);
+    let first_id = parse_created_id(&first);
+
+    let quiet = run_knots(&root, &db, &["new", "Fix the login timeouts"]);
+    assert_success(&quiet);
+    assert!(!String::from_utf8_lossy(&quiet.stderr).contains("similar"));
+
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["config", "set", "dedupe.on_create", "warn"],
+    ));
+    let warned = run_knots(&root, &db, &["new", "fix login timeout"]);
+    assert_success(&warned);
+    let stderr = String::from_utf8_lossy(&warned.stderr);
+    assert!(
+        stderr.contains("warning: 2 similar knots exist:"),
+        "{stderr}"
+    );
+    assert!(stderr.contains(&first_id), "{stderr}");
+
+    let report = run_knots(&root, &db, &["dedupe", "report", "--json"]);
+    assert_success(&report);
+    let pairs: Value = serde_json::from_slice(&report.stdout).expect("report json");
+    let pairs = pairs.as_array().expect("pairs array");
+    assert_eq!(pairs.len(), 3);
+    assert_eq!(pairs[0]["score"], 1.0);
+
+    let text = run_knots(&root, &db, &["dedupe", "report", "--threshold", "0.99"]);
+    assert_success(&text);
+    let stdout = String::from_utf8_lossy(&text.stdout);
+    assert!(stdout.starts_with("1.00\n"), "{stdout}");
+    assert_failure(&run_knots(
+        &root,
+        &db,
+        &["dedupe", "report", "--threshold", "2"],
+    ));
+}
@@ -0,0 +1,134 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+
+const STRICT_BUNDLE: &str = r#"
+[workflow]
+name = "strict_flow"
+version = 1
+default_profile = "strict"
+
+[states.ready_for_work]
+kind = "queue"
+
+[states.work]
+kind = "action"
+action_type = "produce"
+executor = "agent"
+prompt = "work"
+
+[states.done]
+kind = "terminal"
+
+[states.blocked]
+kind = "escape"
+
+[states.deferred]
+kind = "escape"
+
+[states.abandoned]
+kind = "terminal"
+
+[steps.work_step]
+queue = "ready_for_work"
+action = "work"
+
+[phases.main]
+produce = "work_step"
+
+[profiles.strict]
+phases = ["main"]
+done_requires = ["handoff_capsule", "criteria_checked", "linked_commit"]
+
+[prompts.work]
+body = "Do the work."
+
+[prompts.work.success]
+complete = "done"
+
+[prompts.work.failure]
+blocked = "blocked"
+"#;
+
+fn install_strict_flow(root: &std::path::Path, db: &std::path::Path) {
+    bootstrap_builtin_workflows(root, db);
+    let bundle = root.join("strict-flow.toml");
+    std::fs::write(&bundle, STRICT_BUNDLE).expect("bundle should write");
+    let bundle = bundle.to_str().expect("utf8 path");
+    assert_success(&run_knots(
+        root,
+        db,
+        &["workflow", "install", "--type", "work", bundle],
+    ));
+    assert_success(&run_knots(root, db, &["workflow", "use", "strict_flow"]));
+}
+
+#[test]
+fn terminal_moves_list_every_missing_artifact_until_all_are_present() {
+    let root = unique_workspace("knots-cli-dod");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    install_strict_flow(&root, &db);
+    let created = run_knots(&root, &db, &["new", "Strict work"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run_knots(&root, &db, &["state", &id, "work"]));
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["criteria", "add", &id, "returns 400 on bad input"],
+    ));
+
+    let blocked = run_knots(&root, &db, &["state", &id, "done"]);
+    assert_failure(&blocked);
+    let stderr = String::from_utf8_lossy(&blocked.stderr);
+    assert!(
+        stderr.contains("a handoff capsule (none recorded)"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("(1 of 1 unchecked)"), "{stderr}");
+    assert!(stderr.contains("a linked commit"), "{stderr}");
+
+    assert_success(&run_knots(&root, &db, &["criteria", "check", &id, "1"]));
+    assert_success(&run_knots(&root, &db, &["link", &id, "--commit", "HEAD"]));
+    let blocked = run_knots(&root, &db, &["state", &id, "done"]);
+    assert_failure(&blocked);
+    let stderr = String::from_utf8_lossy(&blocked.stderr);
+    assert!(stderr.contains("requires a handoff capsule"), "{stderr}");
+    assert!(!stderr.contains("linked commit"), "{stderr}");
+
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &[
+            "update",
+            &id,
+            "--status",
+            "done",
+            "--add-handoff-capsule",
+            "Shipped the validation fix.",
+        ],
+    ));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn abandon_and_force_skip_the_definition_of_done() {
+    let root = unique_workspace("knots-cli-dod-skip");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    install_strict_flow(&root, &db);
+    for (title, args) in [
+        ("Dropped", vec!["abandoned"]),
+        ("Forced", vec!["done", "--force"]),
+    ] {
+        let created = run_knots(&root, &db, &["new", title]);
+        assert_success(&created);
+        let id = parse_created_id(&created);
+        assert_success(&run_knots(&root, &db, &["state", &id, "work"]));
+        let mut state = vec!["state", id.as_str()];
+        state.extend(args);
+        assert_success(&run_knots(&root, &db, &state));
+    }
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,52 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn digest_prints_markdown_grouped_by_actor() {
+    let root = unique_workspace("knots-cli-digest");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Write the digest"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &[
+            "state",
+            &id,
+            "planning",
+            "--actor-kind",
+            "agent",
+            "--agent-name",
+            "codex",
+        ],
+    ));
+
+    let output = run_knots(&root, &db, &["digest", "--since", "today", "--by", "actor"]);
+    assert_success(&output);
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.starts_with("# Knots digest since "), "{text}");
+    assert!(text.contains("\n## codex\n\n### State changes\n"), "{text}");
+    assert!(
+        text.contains(&format!(
+            "- `{id}` Write the digest (ready_for_planning -> planning)"
+        )),
+        "{text}"
+    );
+    assert!(
+        text.contains("\n## unattributed\n\n### Created\n"),
+        "{text}"
+    );
+
+    let output = run_knots(&root, &db, &["digest", "--json"]);
+    assert_success(&output);
+    let digest: Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(
+        digest["groups"][0]["created"][0]["title"],
+        "Write the digest"
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
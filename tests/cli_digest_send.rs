@@ -0,0 +1,100 @@
+mod cli_dispatch_helpers;
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+/// Accepts one POST and hands its body back once the CLI has sent it.
+fn spawn_webhook() -> (String, std::thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind webhook");
+    let url = format!("http://{}/hook", listener.local_addr().expect("addr"));
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("accept");
+        let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).expect("header line");
+            if line == "\r\n" || line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                length = value.trim().parse().expect("content length");
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).expect("body");
+        let mut stream = stream;
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+            .expect("respond");
+        String::from_utf8(body).expect("utf8 body")
+    });
+    (url, handle)
+}
+
+#[test]
+fn digest_send_posts_the_review_queue_and_honours_due() {
+    let root = unique_workspace("knots-cli-digest-send");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let (webhook, received) = spawn_webhook();
+    std::fs::create_dir_all(root.join(".knots")).expect("create store dir");
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        format!(
+            "[digests.reviewers]\nprofiles = [\"autopilot\"]\nwebhook = \"{webhook}\"\n\
+             every = \"1d\"\n\n[digests.mail]\nemail = [\"team@example.com\"]\n"
+        ),
+    )
+    .expect("write repo config");
+    let created = run_knots(
+        &root,
+        &db,
+        &[
+            "new",
+            "Review me",
+            "--profile",
+            "autopilot",
+            "--state",
+            "ready_for_plan_review",
+        ],
+    );
+    assert_success(&created);
+
+    let dry = run_knots(&root, &db, &["digest", "send", "reviewers", "--dry-run"]);
+    assert_success(&dry);
+    let dry = String::from_utf8_lossy(&dry.stdout);
+    assert!(
+        dry.starts_with("to webhook\n# Knots digest: reviewers"),
+        "{dry}"
+    );
+    assert!(dry.contains("## Awaiting review (1)\n- `"), "{dry}");
+    assert!(dry.contains("Review me [ready_for_plan_review]"), "{dry}");
+
+    let sent = run_knots(&root, &db, &["digest", "send", "reviewers", "--due"]);
+    assert_success(&sent);
+    assert!(String::from_utf8_lossy(&sent.stdout)
+        .contains("sent reviewers to webhook (1 awaiting review, 0 stale)"));
+    let body: Value = serde_json::from_str(&received.join().expect("webhook")).expect("json");
+    assert!(body["text"]
+        .as_str()
+        .expect("text")
+        .contains("Review me [ready_for_plan_review]"));
+
+    let again = run_knots(&root, &db, &["digest", "send", "--due", "--json"]);
+    assert_success(&again);
+    let again: Value = serde_json::from_slice(&again.stdout).expect("json");
+    assert_eq!(again, Value::Array(Vec::new()));
+
+    let mail = run_knots(&root, &db, &["digest", "send", "mail"]);
+    assert_failure(&mail);
+    assert!(String::from_utf8_lossy(&mail.stderr).contains("kno config set digest.smtp_url"));
+    let unknown = run_knots(&root, &db, &["digest", "send", "nope"]);
+    assert_failure(&unknown);
+    assert!(String::from_utf8_lossy(&unknown.stderr).contains("unknown digest 'nope'"));
+
+    let _ = std::fs::remove_dir_all(root);
+}
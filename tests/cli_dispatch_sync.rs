@@ -175,3 +175,304 @@ fn cli_dispatch_covers_json_branches_and_cold_search_results() {
 
     let _ = std::fs::remove_dir_all(root);
 }
+
+#[test]
+fn mirror_mode_syncs_between_clones_without_a_worktree() {
+    let root = unique_workspace("knots-cli-sync-mirror");
+    let remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["init"]));
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["config", "set", "sync_mode", "mirror"],
+    ));
+    let created = run_knots(&root, &db, &["new", "Mirror knot"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    let push = run_knots(&root, &db, &["push"]);
+    assert_success(&push);
+    assert!(String::from_utf8_lossy(&push.stdout).contains("refreshing knots mirror"));
+
+    let clone = unique_workspace("knots-cli-sync-mirror-clone");
+    let cloned = std::process::Command::new("git")
+        .arg("clone")
+        .arg(&remote)
+        .arg(&clone)
+        .output()
+        .expect("git clone should run");
+    assert!(cloned.status.success());
+    run_git(&clone, &["config", "user.email", "knots@example.com"]);
+    run_git(&clone, &["config", "user.name", "Knots Test"]);
+    let clone_db = clone.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(
+        &clone,
+        &clone_db,
+        &["config", "set", "sync_mode", "mirror"],
+    ));
+    let pull = run_knots(&clone, &clone_db, &["pull"]);
+    assert_success(&pull);
+    assert!(String::from_utf8_lossy(&pull.stdout).contains("fetching origin/knots"));
+    assert_success(&run_knots(&clone, &clone_db, &["show", &id]));
+    assert!(clone.join(".knots/mirror.git").exists());
+    assert!(!clone.join(".knots/_worktree").exists());
+
+    let reply = run_knots(&clone, &clone_db, &["new", "Reply from clone"]);
+    assert_success(&reply);
+    let reply_id = parse_created_id(&reply);
+    assert_success(&run_knots(&clone, &clone_db, &["push"]));
+    assert_success(&run_knots(&root, &db, &["pull"]));
+    assert_success(&run_knots(&root, &db, &["show", &reply_id]));
+
+    let _ = std::fs::remove_dir_all(root);
+    let _ = std::fs::remove_dir_all(clone);
+}
+
+#[test]
+fn directory_remote_store_replicates_without_a_git_remote() {
+    let shared = unique_workspace("knots-cli-dir-store-shared");
+    let store = format!("dir:{}", shared.display());
+    let first = unique_workspace("knots-cli-dir-store-a");
+    let second = unique_workspace("knots-cli-dir-store-b");
+    setup_repo(&first);
+    setup_repo(&second);
+    let first_db = first.join(".knots/cache/state.sqlite");
+    let second_db = second.join(".knots/cache/state.sqlite");
+    for (root, db) in [(&first, &first_db), (&second, &second_db)] {
+        assert_success(&run_knots(
+            root,
+            db,
+            &["config", "set", "remote_store", &store],
+        ));
+    }
+
+    let created = run_knots(&first, &first_db, &["new", "Shared through a directory"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    let push = run_knots(&first, &first_db, &["push"]);
+    assert_success(&push);
+    assert!(String::from_utf8_lossy(&push.stdout).contains("published"));
+    assert!(shared.join(".knots/events").exists());
+
+    assert_success(&run_knots(&second, &second_db, &["pull"]));
+    assert_success(&run_knots(&second, &second_db, &["show", &id]));
+
+    let bad = run_knots(
+        &first,
+        &first_db,
+        &["config", "set", "remote_store", "ftp://nope"],
+    );
+    assert_failure(&bad);
+
+    for dir in [shared, first, second] {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+#[test]
+fn push_runs_due_auto_compaction_and_prunes_published_events() {
+    let shared = unique_workspace("knots-cli-compact-shared");
+    let store = format!("dir:{}", shared.display());
+    let first = unique_workspace("knots-cli-compact-a");
+    let second = unique_workspace("knots-cli-compact-b");
+    setup_repo(&first);
+    setup_repo(&second);
+    let first_db = first.join(".knots/cache/state.sqlite");
+    let second_db = second.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(
+        &second,
+        &second_db,
+        &["config", "set", "remote_store", &store],
+    ));
+    for args in [
+        ["config", "set", "remote_store", store.as_str()],
+        ["config", "set", "compact.auto", "weekly"],
+        ["config", "set", "compact.prune_events", "true"],
+    ] {
+        assert_success(&run_knots(&first, &first_db, &args));
+    }
+    assert_failure(&run_knots(
+        &first,
+        &first_db,
+        &["config", "set", "compact.auto", "hourly"],
+    ));
+
+    let created = run_knots(&first, &first_db, &["new", "Compacted on push"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    let push = run_knots(&first, &first_db, &["push"]);
+    assert_success(&push);
+    assert!(String::from_utf8_lossy(&push.stdout).contains("auto compaction"));
+    assert!(shared.join(".knots/snapshots").exists());
+    assert!(shared.join(".knots/events").exists());
+    let mut stack = vec![first.join(".knots/events")];
+    let mut local_events = 0;
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(&dir).expect("read events").flatten() {
+            if entry.path().is_dir() {
+                stack.push(entry.path());
+            } else {
+                local_events += 1;
+            }
+        }
+    }
+    assert_eq!(local_events, 0, "published events are pruned locally");
+
+    let again = run_knots(&first, &first_db, &["push"]);
+    assert_success(&again);
+    assert!(!String::from_utf8_lossy(&again.stdout).contains("auto compaction"));
+
+    assert_success(&run_knots(&second, &second_db, &["pull"]));
+    assert_success(&run_knots(&second, &second_db, &["show", &id]));
+    assert_success(&run_knots(&first, &first_db, &["show", &id]));
+
+    for dir in [shared, first, second] {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+#[test]
+fn ssh_remote_store_talks_to_kno_serve() {
+    let root = unique_workspace("knots-cli-ssh-store");
+    std::fs::create_dir_all(&root).expect("workspace");
+    let server = root.join("central");
+    // Stands in for ssh: drop the destination and run the remote command locally.
+    let fake_ssh = root.join("fake-ssh");
+    std::fs::write(&fake_ssh, "#!/bin/sh\nshift\nexec sh -c \"$1\"\n").expect("fake ssh");
+    std::process::Command::new("chmod")
+        .arg("+x")
+        .arg(&fake_ssh)
+        .status()
+        .expect("chmod");
+    let store = format!("ssh://central.example{}", server.display());
+    let run = |repo: &std::path::Path, db: &std::path::Path, args: &[&str]| {
+        std::process::Command::new(knots_binary())
+            .arg("--repo-root")
+            .arg(repo)
+            .arg("--db")
+            .arg(db)
+            .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+            .env("HOME", repo)
+            .env("KNOTS_SSH_COMMAND", &fake_ssh)
+            .env("KNOTS_REMOTE_KNO", knots_binary())
+            .args(args)
+            .output()
+            .expect("knots command should run")
+    };
+
+    let first = unique_workspace("knots-cli-ssh-store-a");
+    let second = unique_workspace("knots-cli-ssh-store-b");
+    setup_repo(&first);
+    setup_repo(&second);
+    let first_db = first.join(".knots/cache/state.sqlite");
+    let second_db = second.join(".knots/cache/state.sqlite");
+    for (repo, db) in [(&first, &first_db), (&second, &second_db)] {
+        assert_success(&run(repo, db, &["config", "set", "remote_store", &store]));
+    }
+
+    let created = run(&first, &first_db, &["new", "Shared over ssh"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run(&first, &first_db, &["push"]));
+    assert!(server.join(".knots/events").exists());
+
+    assert_success(&run(&second, &second_db, &["pull"]));
+    assert_success(&run(&second, &second_db, &["show", &id]));
+    for dir in [root, first, second] {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+#[test]
+fn pull_details_lists_the_knots_another_clone_changed() {
+    let root = unique_workspace("knots-cli-pull-details");
+    let remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["init"]));
+    assert_success(&run_knots(&root, &db, &["new", "Seen before"]));
+    assert_success(&run_knots(&root, &db, &["push"]));
+
+    let clone = unique_workspace("knots-cli-pull-details-clone");
+    let cloned = std::process::Command::new("git")
+        .arg("clone")
+        .arg(&remote)
+        .arg(&clone)
+        .output()
+        .expect("git clone should run");
+    assert!(cloned.status.success());
+    run_git(&clone, &["config", "user.email", "knots@example.com"]);
+    run_git(&clone, &["config", "user.name", "Knots Test"]);
+    let clone_db = clone.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&clone, &clone_db, &["pull"]));
+
+    let created = run_knots(&root, &db, &["new", "New upstream"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run_knots(&root, &db, &["push"]));
+
+    let pull = run_knots(&clone, &clone_db, &["pull", "--details", "--json"]);
+    assert_success(&pull);
+    let summary: Value = serde_json::from_slice(&pull.stdout).expect("pull json");
+    let changed = summary["details"]["changed_knots"]
+        .as_array()
+        .expect("changed knots");
+    assert_eq!(changed.len(), 1);
+    assert!(changed[0].as_str().is_some_and(|full| full.ends_with(&id)));
+    assert_eq!(summary["details"]["parse_failures"], serde_json::json!([]));
+
+    let quiet = run_knots(&clone, &clone_db, &["pull", "--json"]);
+    assert_success(&quiet);
+    let summary: Value = serde_json::from_slice(&quiet.stdout).expect("pull json");
+    assert!(summary.get("details").is_none());
+
+    let text = run_knots(&clone, &clone_db, &["pull", "--details"]);
+    assert_success(&text);
+    assert_contains_in_order(
+        &String::from_utf8_lossy(&text.stdout),
+        &["changed_knots=0", "stale_events=0", "parse_failures=0"],
+    );
+
+    let conflicts = run_knots(&clone, &clone_db, &["sync", "conflicts"]);
+    assert_success(&conflicts);
+    assert_eq!(
+        String::from_utf8_lossy(&conflicts.stdout),
+        "no sync conflicts\n"
+    );
+
+    let _ = std::fs::remove_dir_all(root);
+    let _ = std::fs::remove_dir_all(clone);
+}
+
+#[test]
+fn pull_json_progress_writes_progress_records_to_stderr() {
+    let root = unique_workspace("knots-cli-pull-json-progress");
+    setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["init"]));
+    assert_success(&run_knots(&root, &db, &["new", "Progress one"]));
+    assert_success(&run_knots(&root, &db, &["new", "Progress two"]));
+    assert_success(&run_knots(&root, &db, &["push"]));
+
+    let fresh_db = root.join(".knots/cache/fresh.sqlite");
+    let pull = run_knots(&root, &fresh_db, &["pull", "--json-progress"]);
+    assert_success(&pull);
+    let stdout = String::from_utf8_lossy(&pull.stdout);
+    assert!(stdout.starts_with("pull head="), "{stdout}");
+    let records: Vec<Value> = String::from_utf8_lossy(&pull.stderr)
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("stderr lines should be JSON"))
+        .collect();
+    assert!(records
+        .iter()
+        .any(|record| record["type"] == "message" && record["kind"] == "stage"));
+    let applied = records
+        .iter()
+        .rfind(|record| record["type"] == "progress" && record["phase"] == "apply")
+        .expect("apply progress should be reported");
+    assert_eq!(applied["done"], applied["total"]);
+    assert!(applied["partition"]
+        .as_str()
+        .is_some_and(|partition| partition.len() == "yyyy/mm/dd".len()));
+
+    let _ = std::fs::remove_dir_all(root);
+}
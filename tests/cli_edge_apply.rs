@@ -0,0 +1,69 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn edge_apply_previews_then_writes_edges_from_a_file() {
+    let root = unique_workspace("knots-cli-edge-apply");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let mut ids = Vec::new();
+    for title in ["Epic", "API", "UI"] {
+        let created = run_knots(&root, &db, &["new", title]);
+        assert_success(&created);
+        ids.push(parse_created_id(&created));
+    }
+    let graph = root.join("graph.txt");
+    std::fs::write(
+        &graph,
+        format!(
+            "# epic structure\n{0} parent_of {1}\n{0} parent_of {2}\n{2} blocked_by {1}\n",
+            ids[0], ids[1], ids[2]
+        ),
+    )
+    .expect("graph file");
+    let graph = graph.to_str().expect("utf8 path");
+
+    let preview = run_knots(&root, &db, &["edge", "apply", graph, "--dry-run"]);
+    assert_success(&preview);
+    let stdout = String::from_utf8_lossy(&preview.stdout);
+    assert_eq!(stdout.lines().filter(|l| l.starts_with("+ ")).count(), 3);
+    assert!(
+        stdout.contains("dry run: 3 edge changes (0 unchanged); nothing written"),
+        "{stdout}"
+    );
+    let edges = run_knots(&root, &db, &["edge", "list", &ids[0], "--json"]);
+    let edges: Value = serde_json::from_slice(&edges.stdout).expect("edges json");
+    assert_eq!(edges.as_array().map(Vec::len), Some(0));
+
+    let applied = run_knots(&root, &db, &["edge", "apply", graph]);
+    assert_success(&applied);
+    assert!(String::from_utf8_lossy(&applied.stdout).contains("applied 3 edge changes"));
+    let edges = run_knots(&root, &db, &["edge", "list", &ids[0], "--json"]);
+    let edges: Value = serde_json::from_slice(&edges.stdout).expect("edges json");
+    assert_eq!(edges.as_array().map(Vec::len), Some(2));
+
+    let removal = root.join("remove.txt");
+    std::fs::write(&removal, format!("- {} blocked_by {}\n", ids[2], ids[1])).expect("file");
+    let removed = run_knots(
+        &root,
+        &db,
+        &["edge", "apply", removal.to_str().expect("utf8"), "--json"],
+    );
+    assert_success(&removed);
+    let plan: Value = serde_json::from_slice(&removed.stdout).expect("plan json");
+    assert_eq!(plan["applied"], true);
+    assert_eq!(plan["changes"][0]["op"], "remove");
+
+    let broken = root.join("broken.txt");
+    std::fs::write(&broken, "only two\n").expect("file");
+    let failed = run_knots(
+        &root,
+        &db,
+        &["edge", "apply", broken.to_str().expect("utf8")],
+    );
+    assert_failure(&failed);
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("line 1: expected"));
+    let _ = std::fs::remove_dir_all(root);
+}
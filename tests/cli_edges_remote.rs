@@ -0,0 +1,90 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+fn full_id(root: &std::path::Path, db: &std::path::Path, id: &str) -> String {
+    let shown = run_knots(root, db, &["show", id, "--json"]);
+    assert_success(&shown);
+    let shown: Value = serde_json::from_slice(&shown.stdout).expect("show json");
+    shown["id"].as_str().expect("id").to_string()
+}
+
+#[test]
+fn cross_repo_edges_resolve_lazily_and_stay_out_of_local_layout() {
+    let backend = unique_workspace("knots-cli-remote-backend");
+    setup_repo(&backend);
+    let backend_db = backend.join(".knots/cache/state.sqlite");
+    let api = run_knots(&backend, &backend_db, &["new", "Ship the API"]);
+    assert_success(&api);
+    let api_id = full_id(&backend, &backend_db, &parse_created_id(&api));
+
+    let root = unique_workspace("knots-cli-remote-frontend");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let page = run_knots(&root, &db, &["new", "Build the page"]);
+    assert_success(&page);
+    let page_id = parse_created_id(&page);
+    let target = format!("backend:{api_id}");
+
+    let unregistered = run_knots(
+        &root,
+        &db,
+        &["edge", "add", &page_id, "blocked_by", &target],
+    );
+    assert_failure(&unregistered);
+    assert!(String::from_utf8_lossy(&unregistered.stderr).contains("project create backend"));
+
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &[
+            "project",
+            "create",
+            "backend",
+            "--repo-root",
+            backend.to_str().expect("utf8 path"),
+        ],
+    ));
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["edge", "add", &page_id, "blocked_by", &target],
+    ));
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["edge", "add", &page_id, "parent_of", &target],
+    ));
+
+    let listed = run_knots(&root, &db, &["edge", "list", &page_id]);
+    assert_success(&listed);
+    let text = String::from_utf8_lossy(&listed.stdout);
+    assert!(
+        text.contains(&format!("-[blocked_by]-> {target}")),
+        "{text}"
+    );
+    assert!(
+        text.contains("Ship the API"),
+        "remote title resolves: {text}"
+    );
+
+    let layout = run_knots(&root, &db, &["ls", "--json"]);
+    assert_success(&layout);
+    let knots: Value = serde_json::from_slice(&layout.stdout).expect("ls json");
+    assert_eq!(knots.as_array().map(Vec::len), Some(1));
+
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["edge", "remove", &page_id, "parent_of", &target],
+    ));
+    let json = run_knots(&root, &db, &["edge", "list", &page_id, "--json"]);
+    assert_success(&json);
+    let edges: Value = serde_json::from_slice(&json.stdout).expect("edge json");
+    assert_eq!(edges.as_array().map(Vec::len), Some(1));
+    assert_eq!(edges[0]["dst"], target.as_str());
+
+    let _ = std::fs::remove_dir_all(backend);
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,41 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+
+#[test]
+fn stats_estimates_reports_shipped_knots_by_type_and_tag() {
+    let root = unique_workspace("knots-cli-estimates");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(
+        &root,
+        &db,
+        &["new", "Sized", "--estimate", "3", "--state", "shipped"],
+    );
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run_knots(&root, &db, &["update", &id, "--add-tag", "api"]));
+    assert_failure(&run_knots(
+        &root,
+        &db,
+        &["update", &id, "--estimate", "soon"],
+    ));
+
+    let shown = run_knots(&root, &db, &["show", &id, "--json"]);
+    assert_success(&shown);
+    let shown: serde_json::Value = serde_json::from_slice(&shown.stdout).expect("json");
+    assert_eq!(shown["estimate"], "3pt");
+
+    let report = run_knots(&root, &db, &["stats", "estimates"]);
+    assert_success(&report);
+    let report = String::from_utf8_lossy(&report.stdout);
+    assert_contains_in_order(&report, &["work", "points", "1 knot(s), 3pt"]);
+
+    let json = run_knots(&root, &db, &["stats", "estimates", "--by", "tag", "--json"]);
+    assert_success(&json);
+    let json: serde_json::Value = serde_json::from_slice(&json.stdout).expect("json");
+    assert_eq!(json["group_by"], "tag");
+    assert_eq!(json["groups"][0]["key"], "api");
+    assert_eq!(json["groups"][0]["points"]["estimated"], 3);
+    let _ = std::fs::remove_dir_all(root);
+}
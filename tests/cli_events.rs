@@ -0,0 +1,140 @@
+mod cli_dispatch_helpers;
+
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn events_tail_prints_the_latest_events_as_json_lines() {
+    let root = unique_workspace("knots-cli-events-tail");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Tail me"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["update", &id, "--title", "Tailed"],
+    ));
+
+    let tail = run_knots(&root, &db, &["events", "tail", "-n", "1"]);
+    assert_success(&tail);
+    let stdout = String::from_utf8_lossy(&tail.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "{stdout}");
+    let event: Value = serde_json::from_str(lines[0]).expect("event json");
+    assert_eq!(event["type"], "knot.title_set");
+    assert_eq!(event["data"]["to"], "Tailed");
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn events_tail_follow_streams_new_events() {
+    let root = unique_workspace("knots-cli-events-follow");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Follow me"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    let mut command = Command::new(knots_binary());
+    command
+        .arg("--repo-root")
+        .arg(&root)
+        .arg("--db")
+        .arg(&db)
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .env("HOME", &root)
+        .args(["events", "tail", "-n", "0", "-f", "--interval-ms", "50"])
+        .stdout(Stdio::piped());
+    let mut follower = command.spawn().expect("follower should start");
+    let stdout = follower.stdout.take().expect("stdout");
+    let (sender, lines) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            let _ = sender.send(line);
+        }
+    });
+    // The follower only streams events written after its first scan, so
+    // keep writing until one comes through.
+    let mut line = None;
+    for attempt in 0..50 {
+        let title = format!("Followed {attempt}");
+        assert_success(&run_knots(&root, &db, &["update", &id, "--title", &title]));
+        if let Ok(received) = lines.recv_timeout(Duration::from_millis(300)) {
+            line = Some(received);
+            break;
+        }
+    }
+    let _ = follower.kill();
+    let _ = follower.wait();
+    let line = line.expect("follower should print a new event");
+    let event: Value = serde_json::from_str(&line).expect("event json");
+    assert_eq!(event["type"], "knot.title_set");
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn events_emit_validates_data_and_records_the_event() {
+    let root = unique_workspace("knots-cli-events-emit");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Emit me"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    let payload = root.join("payload.json");
+    std::fs::write(
+        &payload,
+        r#"{"content": "deploy finished", "agentname": "ci"}"#,
+    )
+    .expect("payload");
+    let data = format!("@{}", payload.display());
+    let emitted = run_knots(
+        &root,
+        &db,
+        &[
+            "events",
+            "emit",
+            "--type",
+            "knot.note_added",
+            "--knot",
+            &id,
+            "--data",
+            &data,
+        ],
+    );
+    assert_success(&emitted);
+    assert!(String::from_utf8_lossy(&emitted.stdout).contains("emitted knot.note_added"));
+
+    let bad = run_knots(
+        &root,
+        &db,
+        &[
+            "events",
+            "emit",
+            "--type",
+            "knot.note_added",
+            "--knot",
+            &id,
+            "--data",
+            r#"{"body":"x"}"#,
+        ],
+    );
+    assert_failure(&bad);
+    assert!(String::from_utf8_lossy(&bad.stderr).contains("unknown field `body`"));
+
+    let tail = run_knots(&root, &db, &["events", "tail", "-n", "1"]);
+    let event: Value =
+        serde_json::from_str(String::from_utf8_lossy(&tail.stdout).trim()).expect("event json");
+    assert_eq!(event["type"], "knot.note_added");
+    assert_eq!(event["data"]["content"], "deploy finished");
+    let shown = run_knots(&root, &db, &["show", &id]);
+    assert!(String::from_utf8_lossy(&shown.stdout).contains("deploy finished"));
+    let _ = std::fs::remove_dir_all(root);
+}
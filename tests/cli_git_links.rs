@@ -0,0 +1,136 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+
+fn show_json(root: &std::path::Path, db: &std::path::Path, id: &str) -> serde_json::Value {
+    let shown = run_knots(root, db, &["show", id, "--json"]);
+    assert_success(&shown);
+    serde_json::from_slice(&shown.stdout).expect("show json should parse")
+}
+
+#[test]
+fn link_records_commits_and_branches_on_show() {
+    let root = unique_workspace("knots-cli-git-link");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Fix login"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    let linked = run_knots(&root, &db, &["link", &id, "--commit", "HEAD", "--json"]);
+    assert_success(&linked);
+    let link: serde_json::Value = serde_json::from_slice(&linked.stdout).expect("link json");
+    assert_eq!(link["kind"], "commit");
+    assert_eq!(link["subject"], "init");
+    assert_eq!(link["target"].as_str().expect("sha").len(), 40);
+    assert_success(&run_knots(&root, &db, &["link", &id, "--commit", "HEAD"]));
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["link", &id, "--branch", "fix/login"],
+    ));
+    assert_failure(&run_knots(&root, &db, &["link", &id, "--commit", "nope"]));
+    assert_failure(&run_knots(&root, &db, &["link", &id]));
+
+    let links = show_json(&root, &db, &id)["git_links"].clone();
+    let links = links.as_array().expect("git_links array");
+    assert_eq!(links.len(), 2, "relinking a commit is a no-op: {links:?}");
+    assert_eq!(links[1]["kind"], "branch");
+    assert_eq!(links[1]["target"], "fix/login");
+
+    let text = run_knots(&root, &db, &["show", &id]);
+    assert_success(&text);
+    let text = String::from_utf8_lossy(&text.stdout);
+    assert!(text.contains("init"), "show should list the commit: {text}");
+    assert!(
+        text.contains("fix/login"),
+        "show should list the branch: {text}"
+    );
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn push_links_commits_with_kno_trailers() {
+    let root = unique_workspace("knots-cli-git-trailer");
+    let _remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["init"]));
+    let created = run_knots(&root, &db, &["new", "Fix login"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    std::fs::write(root.join("login.rs"), "fn login() {}\n").expect("source should write");
+    run_git(&root, &["add", "login.rs"]);
+    let message = format!("Fix login redirect\n\nKNO: {id}, missing-knot");
+    run_git(&root, &["commit", "-m", &message]);
+
+    assert_success(&run_knots(&root, &db, &["push"]));
+    let links = show_json(&root, &db, &id)["git_links"].clone();
+    let links = links.as_array().expect("git_links array");
+    assert_eq!(links.len(), 1, "{links:?}");
+    assert_eq!(links[0]["subject"], "Fix login redirect");
+
+    assert_success(&run_knots(&root, &db, &["push"]));
+    let links = show_json(&root, &db, &id)["git_links"].clone();
+    assert_eq!(links.as_array().map(Vec::len), Some(1));
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn branch_suggests_creates_and_checks_out_knot_branches() {
+    let root = unique_workspace("knots-cli-git-branch");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Fix login: redirect loop!"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    let suggested = run_knots(&root, &db, &["branch", &id]);
+    assert_success(&suggested);
+    let name = String::from_utf8_lossy(&suggested.stdout)
+        .trim()
+        .to_string();
+    assert!(
+        name.ends_with("-fix-login-redirect-loop") && name.contains(&id),
+        "{name}"
+    );
+    assert!(show_json(&root, &db, &id).get("git_links").is_none());
+
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &[
+            "config",
+            "set",
+            "branch_template",
+            "{type}/{short_id}-{slug}",
+        ],
+    ));
+    let checkout = run_knots(&root, &db, &["branch", &id, "--checkout", "--json"]);
+    assert_success(&checkout);
+    let branch: serde_json::Value = serde_json::from_slice(&checkout.stdout).expect("json");
+    let name = branch["branch"].as_str().expect("branch name").to_string();
+    assert!(name.starts_with("work/"), "{name}");
+    assert_eq!(branch["created"], true);
+    assert_eq!(branch["checked_out"], true);
+    let head = std::process::Command::new("git")
+        .arg("-C")
+        .arg(&root)
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .output()
+        .expect("git should run");
+    assert_eq!(String::from_utf8_lossy(&head.stdout).trim(), name);
+
+    let links = show_json(&root, &db, &id)["git_links"].clone();
+    assert_eq!(links[0]["kind"], "branch");
+    assert_eq!(links[0]["target"], name.as_str());
+
+    assert_failure(&run_knots(
+        &root,
+        &db,
+        &["config", "set", "branch_template", "{title}"],
+    ));
+    let _ = std::fs::remove_dir_all(root);
+}
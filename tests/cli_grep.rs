@@ -0,0 +1,62 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn grep_prints_id_field_and_snippet_per_matching_line() {
+    let root = unique_workspace("knots-cli-grep");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(
+        &root,
+        &db,
+        &["new", "Cut over", "-d", "intro\nfollow the migration plan"],
+    );
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["update", &id, "--add-note", "Migration Plan reviewed"],
+    ));
+    assert_success(&run_knots(&root, &db, &["new", "Unrelated"]));
+
+    let output = run_knots(
+        &root,
+        &db,
+        &["grep", "migration plan", "--in", "notes,description"],
+    );
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "{stdout}");
+    assert!(
+        lines[0].ends_with("description follow the migration plan"),
+        "{stdout}"
+    );
+
+    let json = run_knots(
+        &root,
+        &db,
+        &["grep", "migr\\w+\\s+plan", "--regex", "-i", "--json"],
+    );
+    assert_success(&json);
+    let hits: Value = serde_json::from_slice(&json.stdout).expect("grep json");
+    let fields: Vec<&str> = hits
+        .as_array()
+        .expect("hits array")
+        .iter()
+        .map(|hit| hit["field"].as_str().expect("field"))
+        .collect();
+    assert_eq!(fields, vec!["description", "notes"]);
+    assert!(hits[1]["id"].as_str().expect("id").ends_with(&id));
+    assert_eq!(hits[1]["snippet"], "Migration Plan reviewed");
+
+    assert_failure(&run_knots(&root, &db, &["grep", "(", "--regex"]));
+    assert_failure(&run_knots(
+        &root,
+        &db,
+        &["grep", "plan", "--in", "comments"],
+    ));
+}
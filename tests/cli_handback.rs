@@ -0,0 +1,103 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+fn create_ready_knot(root: &std::path::Path, db: &std::path::Path, title: &str) -> String {
+    let created = run_knots(
+        root,
+        db,
+        &["new", title, "--state", "ready_for_implementation"],
+    );
+    assert_success(&created);
+    parse_created_id(&created)
+}
+
+#[test]
+fn handback_requeues_records_the_reason_and_cools_down_the_agent() {
+    let root = unique_workspace("knots-cli-handback");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let id = create_ready_knot(&root, &db, "Needs secrets");
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["claim", &id, "--agent-name", "codex"],
+    ));
+
+    let handed = run_knots(
+        &root,
+        &db,
+        &[
+            "handback",
+            &id,
+            "--reason",
+            "blocked",
+            "--note",
+            "needs credentials",
+            "--cooldown",
+            "1h",
+        ],
+    );
+    assert_success(&handed);
+    let stdout = String::from_utf8_lossy(&handed.stdout);
+    assert!(stdout.contains("handed back"), "{stdout}");
+    assert!(stdout.contains("codex cannot reclaim it until"), "{stdout}");
+
+    let shown = run_knots(&root, &db, &["show", &id, "--json"]);
+    assert_success(&shown);
+    let knot: Value = serde_json::from_slice(&shown.stdout).expect("show json");
+    assert_eq!(knot["state"], "ready_for_implementation");
+    assert_eq!(knot["lease_id"], Value::Null);
+    let handback = &knot["handbacks"][0];
+    assert_eq!(handback["reason"], "blocked");
+    assert_eq!(handback["note"], "needs credentials");
+    assert_eq!(handback["agent_name"], "codex");
+    assert_eq!(handback["from_state"], "implementation");
+    assert!(handback["cooldown_until"].is_string());
+
+    let poll = run_knots(&root, &db, &["poll", "--agent-name", "codex"]);
+    assert_failure(&poll);
+    assert!(String::from_utf8_lossy(&poll.stderr).contains("no claimable knots found"));
+    assert_success(&run_knots(&root, &db, &["poll", "--agent-name", "claude"]));
+
+    let reclaim = run_knots(&root, &db, &["claim", &id, "--agent-name", "codex"]);
+    assert_failure(&reclaim);
+    assert!(String::from_utf8_lossy(&reclaim.stderr).contains("cannot reclaim it until"));
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["claim", &id, "--agent-name", "codex", "--force"],
+    ));
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn handback_rejects_unknown_reasons_and_cooldowns_without_an_agent() {
+    let root = unique_workspace("knots-cli-handback-invalid");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let id = create_ready_knot(&root, &db, "Anonymous claim");
+    assert_success(&run_knots(&root, &db, &["state", &id, "implementation"]));
+
+    let unknown = run_knots(&root, &db, &["handback", &id, "--reason", "bored"]);
+    assert_failure(&unknown);
+    assert!(String::from_utf8_lossy(&unknown.stderr).contains("unknown handback reason"));
+
+    let anonymous = run_knots(
+        &root,
+        &db,
+        &["handback", &id, "--reason", "other", "--cooldown", "30m"],
+    );
+    assert_failure(&anonymous);
+    assert!(String::from_utf8_lossy(&anonymous.stderr).contains("--cooldown needs an agent"));
+
+    let handed = run_knots(&root, &db, &["handback", &id, "--reason", "needs-input"]);
+    assert_success(&handed);
+    let shown = run_knots(&root, &db, &["show", &id]);
+    let stdout = String::from_utf8_lossy(&shown.stdout);
+    assert!(stdout.contains("needs_input at"), "{stdout}");
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,34 @@
+mod cli_dispatch_helpers;
+
+use std::process::Command;
+
+use cli_dispatch_helpers::*;
+
+#[test]
+fn help_topics_print_guides_and_fall_back_to_command_help() {
+    let run = |args: &[&str]| {
+        Command::new(knots_binary())
+            .args(args)
+            .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+            .env("NO_COLOR", "1")
+            .output()
+            .expect("kno should run")
+    };
+
+    let sync = run(&["help", "sync"]);
+    assert_success(&sync);
+    let text = String::from_utf8_lossy(&sync.stdout);
+    assert!(text.starts_with("SYNCING KNOTS\n"), "{text}");
+    assert!(text.contains("sync_policy"), "{text}");
+
+    let topics = run(&["help", "topics"]);
+    assert_success(&topics);
+    assert_contains_in_order(
+        &String::from_utf8_lossy(&topics.stdout),
+        &["kno help agents", "kno help profiles", "kno help sync"],
+    );
+
+    let ls = run(&["help", "ls"]);
+    assert_success(&ls);
+    assert!(String::from_utf8_lossy(&ls.stdout).contains("Usage: kno ls"));
+}
@@ -0,0 +1,101 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn replay_and_diff_report_history_from_events() {
+    let root = unique_workspace("knots-cli-history");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+
+    let created = run_knots(&root, &db, &["new", "History knot"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    let other = run_knots(&root, &db, &["new", "Other knot"]);
+    assert_success(&other);
+    let other_id = parse_created_id(&other);
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["update", &id, "--title", "History knot v2"],
+    ));
+
+    let early = run_knots(&root, &db, &["replay", "--until", "2000-01-01T00:00:00Z"]);
+    assert_success(&early);
+    assert!(String::from_utf8_lossy(&early.stdout).contains("no knots existed"));
+
+    let now = run_knots(
+        &root,
+        &db,
+        &["replay", "--until", "2100-01-01T00:00:00Z", "--json"],
+    );
+    assert_success(&now);
+    let knots: Value = serde_json::from_slice(&now.stdout).expect("replay json");
+    assert_eq!(knots.as_array().map(Vec::len), Some(2));
+
+    let window = run_knots(&root, &db, &["diff", &id, "--since", "1d"]);
+    assert_success(&window);
+    let text = String::from_utf8_lossy(&window.stdout);
+    assert!(text.contains("title:"), "{text}");
+    assert!(text.contains("+ History knot v2"), "{text}");
+
+    let pair = run_knots(&root, &db, &["diff", &id, &other_id, "--json"]);
+    assert_success(&pair);
+    let diff: Value = serde_json::from_slice(&pair.stdout).expect("diff json");
+    assert_eq!(diff["changes"][0]["field"], "title");
+
+    let bad = run_knots(&root, &db, &["diff", &id, &other_id, "--since", "1d"]);
+    assert_failure(&bad);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn snapshot_verify_matches_replay_and_flags_tampering() {
+    let root = unique_workspace("knots-cli-snapshot-verify");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+
+    let missing = run_knots(&root, &db, &["snapshot", "verify"]);
+    assert_failure(&missing);
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("no snapshot found"));
+
+    let created = run_knots(&root, &db, &["new", "Snapshot knot", "-d", "body text"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &[
+            "update",
+            &id,
+            "-a",
+            "ops",
+            "-p",
+            "2",
+            "--add-note",
+            "checked",
+        ],
+    ));
+    let written = run_knots(&root, &db, &["compact", "--write-snapshots", "--json"]);
+    assert_success(&written);
+    let written: Value = serde_json::from_slice(&written.stdout).expect("compact json");
+
+    let verify = run_knots(&root, &db, &["snapshot", "verify", "--json"]);
+    assert_success(&verify);
+    let report: Value = serde_json::from_slice(&verify.stdout).expect("verify json");
+    assert_eq!(report["knots_checked"], 1);
+    assert_eq!(report["mismatches"].as_array().map(Vec::len), Some(0));
+
+    let active = written["active_path"].as_str().expect("active path");
+    let tampered = std::fs::read_to_string(active)
+        .expect("read snapshot")
+        .replace("Snapshot knot", "Rewritten knot");
+    std::fs::write(active, tampered).expect("tamper snapshot");
+    let verify = run_knots(&root, &db, &["snapshot", "verify"]);
+    assert_failure(&verify);
+    let stdout = String::from_utf8_lossy(&verify.stdout);
+    assert!(stdout.contains("(hot): title"), "{stdout}");
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,462 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn import_knots_copies_subtree_with_new_ids_and_internal_edges() {
+    let source = unique_workspace("knots-cli-import-source");
+    setup_repo(&source);
+    let source_db = source.join(".knots/cache/state.sqlite");
+    let epic = run_knots(&source, &source_db, &["new", "Epic", "-d", "epic body"]);
+    assert_success(&epic);
+    let epic_id = parse_created_id(&epic);
+    let child = run_knots(&source, &source_db, &["new", "Child", "-d", "child body"]);
+    assert_success(&child);
+    let child_id = parse_created_id(&child);
+    let outside = run_knots(&source, &source_db, &["new", "Outside"]);
+    assert_success(&outside);
+    let outside_id = parse_created_id(&outside);
+    assert_success(&run_knots(
+        &source,
+        &source_db,
+        &["edge", "add", &epic_id, "parent_of", &child_id],
+    ));
+    assert_success(&run_knots(
+        &source,
+        &source_db,
+        &["edge", "add", &child_id, "blocked_by", &outside_id],
+    ));
+
+    let target = unique_workspace("knots-cli-import-target");
+    setup_repo(&target);
+    let db = target.join(".knots/cache/state.sqlite");
+    let existing = run_knots(&target, &db, &["new", "Already here"]);
+    assert_success(&existing);
+    let from = source.to_str().expect("utf8 path");
+
+    let dry = run_knots(
+        &target,
+        &db,
+        &[
+            "import",
+            "knots",
+            "--from",
+            from,
+            "--subtree",
+            &epic_id,
+            "--dry-run",
+        ],
+    );
+    assert_success(&dry);
+    assert!(String::from_utf8_lossy(&dry.stdout).contains("would import 2 knot(s)"));
+    let listed = run_knots(&target, &db, &["ls", "--json"]);
+    assert_success(&listed);
+    let listed: Value = serde_json::from_slice(&listed.stdout).expect("ls json");
+    assert_eq!(
+        listed.as_array().map(Vec::len),
+        Some(1),
+        "dry run writes nothing"
+    );
+
+    let imported = run_knots(
+        &target,
+        &db,
+        &[
+            "import",
+            "knots",
+            "--from",
+            from,
+            "--subtree",
+            &epic_id,
+            "--json",
+        ],
+    );
+    assert_success(&imported);
+    let summary: Value = serde_json::from_slice(&imported.stdout).expect("import json");
+    assert_eq!(summary["edges_dropped"], 1);
+    let knots = summary["knots"].as_array().expect("knots");
+    assert_eq!(knots.len(), 2);
+    let source_ids: Vec<&str> = knots
+        .iter()
+        .map(|knot| knot["source_id"].as_str().expect("source id"))
+        .collect();
+    assert!(source_ids[0].ends_with(&format!("-{epic_id}")));
+    assert!(source_ids[1].ends_with(&format!("-{child_id}")));
+    let new_epic = knots[0]["id"].as_str().expect("id").to_string();
+    let new_child = knots[1]["id"].as_str().expect("id").to_string();
+    assert!(
+        new_epic.starts_with("knots-cli-import-target"),
+        "{new_epic}"
+    );
+    assert_ne!(new_epic, new_child);
+
+    let shown = run_knots(&target, &db, &["show", &new_child, "--json"]);
+    assert_success(&shown);
+    let shown: Value = serde_json::from_slice(&shown.stdout).expect("show json");
+    assert_eq!(shown["title"], "Child");
+    assert_eq!(shown["description"], "child body");
+    let edges = serde_json::to_string(&shown["edges"]).expect("edges");
+    assert!(edges.contains(&new_epic), "{edges}");
+    assert!(!edges.contains("import-source"), "{edges}");
+
+    let missing = run_knots(
+        &target,
+        &db,
+        &["import", "knots", "--from", from, "--subtree", "nope"],
+    );
+    assert_failure(&missing);
+
+    let _ = std::fs::remove_dir_all(source);
+    let _ = std::fs::remove_dir_all(target);
+}
+
+#[test]
+fn transfer_moves_knot_and_closes_source_with_moved_to_note() {
+    let source = unique_workspace("knots-cli-transfer-source");
+    setup_repo(&source);
+    let source_db = source.join(".knots/cache/state.sqlite");
+    let created = run_knots(
+        &source,
+        &source_db,
+        &["new", "Movable", "-d", "moving body"],
+    );
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    let target = unique_workspace("knots-cli-transfer-target");
+    setup_repo(&target);
+    let target_db = target.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&target, &target_db, &["new", "Resident"]));
+    let to = target.to_str().expect("utf8 path");
+
+    let moved = run_knots(
+        &source,
+        &source_db,
+        &["transfer", &id, "--to", to, "--json"],
+    );
+    assert_success(&moved);
+    let moved: Value = serde_json::from_slice(&moved.stdout).expect("transfer json");
+    let new_id = moved["id"].as_str().expect("new id").to_string();
+    assert!(new_id.starts_with("knots-cli-transfer-target"), "{new_id}");
+
+    let shown = run_knots(&target, &target_db, &["show", &new_id, "--json"]);
+    assert_success(&shown);
+    let shown: Value = serde_json::from_slice(&shown.stdout).expect("show json");
+    assert_eq!(shown["title"], "Movable");
+    assert_eq!(shown["description"], "moving body");
+
+    let closed = run_knots(&source, &source_db, &["show", &id, "--json"]);
+    assert_success(&closed);
+    let closed: Value = serde_json::from_slice(&closed.stdout).expect("show json");
+    assert_eq!(closed["state"], "abandoned");
+    let notes = serde_json::to_string(&closed["notes"]).expect("notes");
+    assert!(notes.contains(&format!("moved_to: {new_id}")), "{notes}");
+
+    let bare = unique_workspace("knots-cli-transfer-bare");
+    let not_repo = run_knots(
+        &source,
+        &source_db,
+        &["transfer", &id, "--to", bare.to_str().expect("utf8 path")],
+    );
+    assert_failure(&not_repo);
+    assert!(!bare.join(".knots").exists());
+
+    let _ = std::fs::remove_dir_all(source);
+    let _ = std::fs::remove_dir_all(target);
+    let _ = std::fs::remove_dir_all(bare);
+}
+
+#[test]
+fn export_org_round_trips_title_edits_through_import_org() {
+    let root = unique_workspace("knots-cli-org");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Draft in org"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    let path = root.join("knots.org");
+    let path_arg = path.to_str().expect("utf8 path");
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["export", "org", "--output", path_arg],
+    ));
+    let org = std::fs::read_to_string(&path).expect("org file");
+    assert!(org.starts_with("#+TITLE: Knots\n#+TODO: "));
+    std::fs::write(&path, org.replace("Draft in org", "Edited in org :emacs:")).expect("edit");
+
+    let imported = run_knots(&root, &db, &["import", "org", path_arg, "--json"]);
+    assert_success(&imported);
+    let summary: Value = serde_json::from_slice(&imported.stdout).expect("summary json");
+    assert_eq!(
+        summary["changed"][0]["fields"],
+        serde_json::json!(["title", "tags"])
+    );
+    let shown = run_knots(&root, &db, &["show", &id, "--json"]);
+    let knot: Value = serde_json::from_slice(&shown.stdout).expect("knot json");
+    assert_eq!(knot["title"], "Edited in org");
+    assert_eq!(knot["tags"], serde_json::json!(["emacs"]));
+
+    let again = run_knots(&root, &db, &["import", "org", path_arg]);
+    assert_success(&again);
+    assert!(String::from_utf8_lossy(&again.stdout).starts_with("updated 0 knot(s)"));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn export_org_filters_by_subtree_and_tag() {
+    let root = unique_workspace("knots-cli-org-filter");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let epic = parse_created_id(&run_knots(&root, &db, &["new", "Partner epic"]));
+    let child = parse_created_id(&run_knots(&root, &db, &["new", "Partner child"]));
+    let other = parse_created_id(&run_knots(&root, &db, &["new", "Internal only"]));
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["edge", "add", &epic, "parent_of", &child],
+    ));
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["update", &other, "--add-tag", "internal"],
+    ));
+
+    let subtree = run_knots(&root, &db, &["export", "org", "--subtree", &epic]);
+    assert_success(&subtree);
+    let org = String::from_utf8_lossy(&subtree.stdout);
+    assert!(org.contains("Partner epic") && org.contains("** "), "{org}");
+    assert!(org.contains("Partner child") && !org.contains("Internal only"));
+
+    let tagged = run_knots(&root, &db, &["export", "org", "--tag", "internal"]);
+    assert_success(&tagged);
+    let org = String::from_utf8_lossy(&tagged.stdout);
+    assert!(
+        org.contains("Internal only") && !org.contains("Partner"),
+        "{org}"
+    );
+
+    assert_failure(&run_knots(
+        &root,
+        &db,
+        &["export", "org", "--subtree", "missing-knot"],
+    ));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn vault_sync_needs_a_local_vault_path_and_writes_notes() {
+    let root = unique_workspace("knots-cli-vault");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let id = parse_created_id(&run_knots(&root, &db, &["new", "Noted"]));
+    assert_failure(&run_knots(&root, &db, &["vault", "sync"]));
+
+    let vault = root.join("vault");
+    let vault_arg = vault.to_str().expect("utf8 path");
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["config", "set", "vault.path", vault_arg],
+    ));
+    let synced = run_knots(&root, &db, &["vault", "sync", "--json"]);
+    assert_success(&synced);
+    let summary: Value = serde_json::from_slice(&synced.stdout).expect("summary json");
+    assert_eq!(summary["written"].as_array().map(Vec::len), Some(1));
+    let note = std::fs::read_dir(&vault)
+        .expect("vault dir")
+        .filter_map(Result::ok)
+        .find(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .ends_with(&format!("{id}.md"))
+        })
+        .expect("note for the knot");
+    let text = std::fs::read_to_string(note.path()).expect("note");
+    assert!(text.contains("\n# Noted\n"));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn import_rollback_closes_imported_knots_and_detaches_them() {
+    let source = unique_workspace("knots-cli-rollback-source");
+    setup_repo(&source);
+    let source_db = source.join(".knots/cache/state.sqlite");
+    let epic = run_knots(&source, &source_db, &["new", "Epic"]);
+    assert_success(&epic);
+    let epic_id = parse_created_id(&epic);
+    let child = run_knots(&source, &source_db, &["new", "Child"]);
+    assert_success(&child);
+    let child_id = parse_created_id(&child);
+    assert_success(&run_knots(
+        &source,
+        &source_db,
+        &["edge", "add", &epic_id, "parent_of", &child_id],
+    ));
+
+    let target = unique_workspace("knots-cli-rollback-target");
+    setup_repo(&target);
+    let db = target.join(".knots/cache/state.sqlite");
+    let local = run_knots(&target, &db, &["new", "Local epic"]);
+    assert_success(&local);
+    let local_id = parse_created_id(&local);
+    let from = source.to_str().expect("utf8 path");
+    let imported = run_knots(
+        &target,
+        &db,
+        &[
+            "import",
+            "knots",
+            "--from",
+            from,
+            "--subtree",
+            &epic_id,
+            "--json",
+        ],
+    );
+    assert_success(&imported);
+    let summary: Value = serde_json::from_slice(&imported.stdout).expect("import json");
+    let run = summary["run"].as_str().expect("run").to_string();
+    let new_epic = summary["knots"][0]["id"].as_str().expect("id").to_string();
+    let new_child = summary["knots"][1]["id"].as_str().expect("id").to_string();
+    assert_success(&run_knots(
+        &target,
+        &db,
+        &["edge", "add", &local_id, "parent_of", &new_epic],
+    ));
+
+    let dry = run_knots(
+        &target,
+        &db,
+        &["import", "rollback", "--source", from, "--dry-run"],
+    );
+    assert_success(&dry);
+    assert!(String::from_utf8_lossy(&dry.stdout).contains("would close 2 knot(s)"));
+
+    let rolled = run_knots(
+        &target,
+        &db,
+        &["import", "rollback", "--source", from, "--json"],
+    );
+    assert_success(&rolled);
+    let rolled: Value = serde_json::from_slice(&rolled.stdout).expect("rollback json");
+    assert_eq!(rolled["runs"][0], run.as_str());
+    assert_eq!(rolled["closed"][0]["id"], new_child.as_str());
+    assert_eq!(rolled["edges_removed"].as_array().map(Vec::len), Some(1));
+    for id in [&new_epic, &new_child] {
+        let shown = run_knots(&target, &db, &["show", id, "--json"]);
+        assert_success(&shown);
+        let shown: Value = serde_json::from_slice(&shown.stdout).expect("show json");
+        assert_eq!(shown["state"], "abandoned");
+    }
+    let local_shown = run_knots(&target, &db, &["show", &local_id, "--json"]);
+    let local_shown: Value = serde_json::from_slice(&local_shown.stdout).expect("show json");
+    let edges = serde_json::to_string(&local_shown["edges"]).expect("edges");
+    assert!(!edges.contains(&new_epic), "{edges}");
+
+    let again = run_knots(&target, &db, &["import", "rollback", "--source", &run]);
+    assert_success(&again);
+    assert!(String::from_utf8_lossy(&again.stdout).contains("closed 0 knot(s)"));
+    let unknown = run_knots(&target, &db, &["import", "rollback", "--source", "nope"]);
+    assert_failure(&unknown);
+    assert!(String::from_utf8_lossy(&unknown.stderr).contains(&run));
+
+    let _ = std::fs::remove_dir_all(source);
+    let _ = std::fs::remove_dir_all(target);
+}
+
+#[test]
+fn import_jsonl_streams_records_and_reports_the_run() {
+    let root = unique_workspace("knots-cli-import-jsonl");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let dump = root.join("dump.jsonl");
+    std::fs::write(
+        &dump,
+        "{\"id\": \"A-1\", \"title\": \"From dump\", \"labels\": [\"ops\"]}\nnot json\n",
+    )
+    .expect("write dump");
+    let dump = dump.to_str().expect("utf8 path");
+
+    let imported = run_knots(
+        &root,
+        &db,
+        &["import", "jsonl", dump, "--chunk-size", "1", "--json"],
+    );
+    assert_success(&imported);
+    let summary: Value = serde_json::from_slice(&imported.stdout).expect("import json");
+    assert_eq!(summary["imported"], 1);
+    assert_eq!(summary["error_count"], 1);
+    assert_eq!(summary["interrupted"], false);
+    let listed = run_knots(&root, &db, &["ls", "--json"]);
+    let listed: Value = serde_json::from_slice(&listed.stdout).expect("ls json");
+    assert_eq!(listed[0]["title"], "From dump");
+
+    let resume = run_knots(&root, &db, &["import", "jsonl", dump, "--resume"]);
+    assert_failure(&resume);
+    assert!(String::from_utf8_lossy(&resume.stderr).contains("no interrupted import"));
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn import_jsonl_applies_a_mapping_file() {
+    let root = unique_workspace("knots-cli-import-jsonl-mapping");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let dump = root.join("jira.jsonl");
+    std::fs::write(
+        &dump,
+        "{\"key\": \"OPS-1\", \"fields\": {\"summary\": \"Mapped\", \"status\": \"Backlog\", \
+         \"labels\": [\"Bug\", \"wontfix\"]}}\n",
+    )
+    .expect("write dump");
+    let mapping = root.join("jira.toml");
+    std::fs::write(
+        &mapping,
+        "profile = \"autopilot\"\n\
+         [fields]\nid = \"key\"\ntitle = \"fields.summary\"\nstate = \"fields.status\"\n\
+         tags = \"fields.labels\"\n\
+         [states]\nBacklog = \"ready_for_implementation\"\n\
+         [tags]\ndrop = [\"wontfix\"]\nlowercase = true\nprefix = \"jira:\"\n",
+    )
+    .expect("write mapping");
+    let dump = dump.to_str().expect("utf8 path");
+    let mapping = mapping.to_str().expect("utf8 path");
+
+    let imported = run_knots(
+        &root,
+        &db,
+        &["import", "jsonl", dump, "--mapping", mapping, "--json"],
+    );
+    assert_success(&imported);
+    let summary: Value = serde_json::from_slice(&imported.stdout).expect("import json");
+    assert_eq!(summary["imported"], 1, "{summary}");
+    let listed = run_knots(&root, &db, &["ls", "--json"]);
+    let listed: Value = serde_json::from_slice(&listed.stdout).expect("ls json");
+    assert_eq!(listed[0]["title"], "Mapped");
+    assert_eq!(listed[0]["state"], "ready_for_implementation");
+    assert_eq!(listed[0]["profile_id"], "autopilot");
+    assert_eq!(listed[0]["tags"], serde_json::json!(["jira:bug"]));
+
+    let bad = root.join("bad.toml");
+    std::fs::write(&bad, "[fields]\nassignee = \"owner\"\n").expect("write bad mapping");
+    let rejected = run_knots(
+        &root,
+        &db,
+        &[
+            "import",
+            "jsonl",
+            dump,
+            "--mapping",
+            bad.to_str().expect("utf8"),
+        ],
+    );
+    assert_failure(&rejected);
+    assert!(String::from_utf8_lossy(&rejected.stderr).contains("unknown knot field 'assignee'"));
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,69 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+
+fn clone_repo(remote: &std::path::Path, prefix: &str) -> std::path::PathBuf {
+    let clone = unique_workspace(prefix);
+    let cloned = std::process::Command::new("git")
+        .arg("clone")
+        .arg(remote)
+        .arg(&clone)
+        .output()
+        .expect("git clone should run");
+    assert!(cloned.status.success());
+    run_git(&clone, &["config", "user.email", "knots@example.com"]);
+    run_git(&clone, &["config", "user.name", "Knots Test"]);
+    clone
+}
+
+#[test]
+fn init_from_remote_loads_snapshots_and_newer_events_in_one_step() {
+    let root = unique_workspace("knots-cli-init-from-remote");
+    let remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["init"]));
+    let snapshotted = parse_created_id(&run_knots(&root, &db, &["new", "In the snapshot"]));
+    assert_success(&run_knots(&root, &db, &["compact", "--write-snapshots"]));
+    let newer = parse_created_id(&run_knots(&root, &db, &["new", "After the snapshot"]));
+    assert_success(&run_knots(&root, &db, &["push"]));
+
+    let clone = clone_repo(&remote, "knots-cli-init-from-remote-clone");
+    let clone_db = clone.join(".knots/cache/state.sqlite");
+    let init = run_knots(&clone, &clone_db, &["init", "--from-remote"]);
+    assert_success(&init);
+    let stdout = String::from_utf8_lossy(&init.stdout);
+    assert!(stdout.contains("fetching origin/knots"), "{stdout}");
+    assert!(stdout.contains("hot 2, warm 0, cold 0"), "{stdout}");
+    assert!(stdout.contains("try `kno ls`"), "{stdout}");
+    let snapshots = clone.join(".knots/_worktree/.knots/snapshots");
+    assert!(std::fs::read_dir(&snapshots).is_ok_and(|mut dir| dir.next().is_some()));
+
+    let listed = run_knots(&clone, &clone_db, &["ls"]);
+    assert_success(&listed);
+    let listed = String::from_utf8_lossy(&listed.stdout);
+    assert!(
+        listed.contains(&snapshotted) && listed.contains(&newer),
+        "{listed}"
+    );
+
+    let _ = std::fs::remove_dir_all(root);
+    let _ = std::fs::remove_dir_all(clone);
+}
+
+#[test]
+fn init_from_remote_requires_an_existing_knots_branch() {
+    let root = unique_workspace("knots-cli-init-from-remote-missing");
+    setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+
+    let init = run_knots(&root, &db, &["init", "--from-remote"]);
+    assert_failure(&init);
+    let stderr = String::from_utf8_lossy(&init.stderr);
+    assert!(stderr.contains("origin/knots does not exist"), "{stderr}");
+    assert!(!db.exists());
+
+    let both = run_knots(&root, &db, &["init", "--from-remote", "--interactive"]);
+    assert_failure(&both);
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,39 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn lint_lists_orphans_and_passes_once_they_are_grouped() {
+    let root = unique_workspace("knots-cli-lint");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Loose end"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    let linted = run_knots(&root, &db, &["lint"]);
+    assert_failure(&linted);
+    let stdout = String::from_utf8_lossy(&linted.stdout);
+    assert!(
+        stdout.contains("Orphans: no parent and no tags (1)"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("Loose end"), "{stdout}");
+    assert!(String::from_utf8_lossy(&linted.stderr).contains("lint found 1 issue(s)"));
+
+    let json = run_knots(&root, &db, &["lint", "--json"]);
+    let report: Value = serde_json::from_slice(&json.stdout).expect("lint json");
+    assert_eq!(report["orphans"][0]["title"], "Loose end");
+    assert_eq!(report["dangling_edges"].as_array().map(Vec::len), Some(0));
+
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["update", &id, "--add-tag", "area:core"],
+    ));
+    let clean = run_knots(&root, &db, &["lint", "--fix"]);
+    assert_success(&clean);
+    assert!(String::from_utf8_lossy(&clean.stdout).contains("no lint issues"));
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,101 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+
+#[test]
+fn ls_renders_table_columns_from_flag_or_config() {
+    let root = unique_workspace("knots-cli-ls-columns");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Render columns"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run_knots(&root, &db, &["update", &id, "--priority", "1"]));
+
+    let output = run_knots(&root, &db, &["ls", "--columns", "id:6,priority,title"]);
+    assert_success(&output);
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.contains("\nID      PRI  TITLE\n"), "{text}");
+    assert!(
+        text.contains(&format!("\n{id:<6}  1    Render columns\n")),
+        "{text}"
+    );
+
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["config", "set", "ls.columns", "state:20, title"],
+    ));
+    let output = run_knots(&root, &db, &["ls"]);
+    assert_success(&output);
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        text.contains("\nready_for_planning    Render columns\n"),
+        "{text}"
+    );
+
+    let bad = run_knots(&root, &db, &["ls", "--columns", "id,owner"]);
+    assert_failure(&bad);
+    assert!(String::from_utf8_lossy(&bad.stderr).contains("unknown column 'owner'"));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn ls_keeps_wide_titles_aligned_and_no_truncate_widens_columns() {
+    let root = unique_workspace("knots-cli-ls-wide");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "日本語のタイトル"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    let output = run_knots(&root, &db, &["ls", "--columns", "title:10,id"]);
+    assert_success(&output);
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        text.contains(&format!("\n日本語の\u{2026}   {id}")),
+        "{text}"
+    );
+
+    let output = run_knots(
+        &root,
+        &db,
+        &["ls", "--columns", "title:10,id", "--no-truncate"],
+    );
+    assert_success(&output);
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        text.contains(&format!("\n日本語のタイトル  {id}")),
+        "{text}"
+    );
+    assert!(
+        text.contains(&format!("\nTITLE{}ID", " ".repeat(13))),
+        "{text}"
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn ls_and_show_accept_no_pager_and_print_directly_when_piped() {
+    let root = unique_workspace("knots-cli-ls-pager");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Paged knot"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    for args in [
+        vec!["ls"],
+        vec!["--no-pager", "ls"],
+        vec!["ls", "--no-pager"],
+        vec!["show", id.as_str(), "--no-pager"],
+    ] {
+        let output = run_knots(&root, &db, &args);
+        assert_success(&output);
+        assert!(
+            String::from_utf8_lossy(&output.stdout).contains("Paged knot"),
+            "{args:?}"
+        );
+    }
+    let _ = std::fs::remove_dir_all(root);
+}
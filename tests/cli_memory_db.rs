@@ -0,0 +1,37 @@
+mod cli_dispatch_helpers;
+
+use std::path::Path;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn memory_db_rebuilds_synced_knots_from_the_worktree_and_refuses_writes() {
+    let root = unique_workspace("knots-cli-memory-db");
+    let _remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let memory = Path::new(":memory:");
+
+    assert_success(&run_knots(&root, &db, &["init"]));
+    assert_success(&run_knots(&root, &db, &["new", "Synced knot"]));
+    assert_success(&run_knots(&root, &db, &["sync"]));
+    assert_success(&run_knots(&root, &db, &["new", "Unpushed knot"]));
+
+    let listed = run_knots(&root, memory, &["ls", "--json"]);
+    assert_success(&listed);
+    let knots: Value = serde_json::from_slice(&listed.stdout).expect("ls json");
+    let titles: Vec<&str> = knots
+        .as_array()
+        .expect("knot list")
+        .iter()
+        .filter_map(|knot| knot["title"].as_str())
+        .collect();
+    assert_eq!(titles, vec!["Synced knot"]);
+    assert!(!root.join(":memory:").exists());
+
+    let write = run_knots(&root, memory, &["new", "Lost knot"]);
+    assert_failure(&write);
+    assert!(String::from_utf8_lossy(&write.stderr).contains("--db :memory: is read-only"));
+
+    let _ = std::fs::remove_dir_all(root);
+}
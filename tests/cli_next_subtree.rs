@@ -0,0 +1,63 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+fn create(root: &std::path::Path, db: &std::path::Path, title: &str) -> String {
+    let output = run_knots(root, db, &["new", title]);
+    assert_success(&output);
+    parse_created_id(&output)
+}
+
+#[test]
+fn next_subtree_advances_children_and_explains_blocked_ones() {
+    let root = unique_workspace("knots-cli-next-subtree");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let epic = create(&root, &db, "Epic");
+    let ready = create(&root, &db, "Ready child");
+    let waiting = create(&root, &db, "Waiting child");
+    let blocker = create(&root, &db, "Blocker");
+    for child in [&ready, &waiting] {
+        assert_success(&run_knots(
+            &root,
+            &db,
+            &["edge", "add", &epic, "parent_of", child],
+        ));
+    }
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["edge", "add", &waiting, "blocked_by", &blocker],
+    ));
+
+    assert_failure(&run_knots(
+        &root,
+        &db,
+        &["next", "--subtree", &epic, "--expected-state", "planning"],
+    ));
+    let advanced = run_knots(&root, &db, &["next", "--subtree", &epic, "--json"]);
+    assert_success(&advanced);
+    let report: Value = serde_json::from_slice(&advanced.stdout).expect("json");
+    let advanced = report["advanced"].as_array().expect("advanced");
+    assert_eq!(advanced.len(), 1);
+    assert!(advanced[0]["id"]
+        .as_str()
+        .expect("id")
+        .ends_with(ready.as_str()));
+    let blocked = report["blocked"].as_array().expect("blocked");
+    assert_eq!(blocked.len(), 1);
+    assert!(blocked[0]["reason"]
+        .as_str()
+        .expect("reason")
+        .contains(blocker.as_str()));
+
+    let text = run_knots(&root, &db, &["next", "--subtree", &epic]);
+    assert_success(&text);
+    let stdout = String::from_utf8_lossy(&text.stdout);
+    assert_contains_in_order(
+        &stdout,
+        &["advanced 1 of 2 children", "blocked: blocked by"],
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
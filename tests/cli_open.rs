@@ -0,0 +1,82 @@
+mod cli_dispatch_helpers;
+
+use std::io::Write;
+use std::process::Command;
+
+use cli_dispatch_helpers::*;
+
+#[test]
+fn open_renders_repo_link_templates_and_launches_browser() {
+    let root = unique_workspace("knots-cli-open");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Fix login"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["update", &id, "--add-tag", "pr:42"],
+    ));
+
+    let unconfigured = run_knots(&root, &db, &["open", &id, "--print"]);
+    assert_failure(&unconfigured);
+    assert!(String::from_utf8_lossy(&unconfigured.stderr).contains("no links configured"));
+
+    let mut config = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(root.join(".knots/config.toml"))
+        .expect("repo config should open");
+    writeln!(
+        config,
+        "\n[links]\npr = \"https://git.example/pull/{{tag:pr}}\"\n\
+         docs = \"https://docs.example/{{short_id}}?t={{title}}\""
+    )
+    .expect("repo config should be writable");
+    drop(config);
+
+    let pr = run_knots(&root, &db, &["open", &id, "pr", "--print"]);
+    assert_success(&pr);
+    assert_eq!(
+        String::from_utf8_lossy(&pr.stdout).trim(),
+        "https://git.example/pull/42"
+    );
+    let ambiguous = run_knots(&root, &db, &["open", &id, "--print"]);
+    assert_failure(&ambiguous);
+    assert!(String::from_utf8_lossy(&ambiguous.stderr).contains("docs, pr"));
+    assert_failure(&run_knots(&root, &db, &["open", &id, "ci", "--print"]));
+
+    let seen = root.join("opened.txt");
+    let browser = root.join("browser.sh");
+    std::fs::write(
+        &browser,
+        format!("#!/bin/sh\necho \"$1\" > '{}'\n", seen.display()),
+    )
+    .expect("browser script should be writable");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&browser, std::fs::Permissions::from_mode(0o755))
+            .expect("browser script should be executable");
+        let opened = Command::new(knots_binary())
+            .arg("--repo-root")
+            .arg(&root)
+            .arg("--db")
+            .arg(&db)
+            .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+            .env("HOME", &root)
+            .env("BROWSER", &browser)
+            .args(["open", &id, "docs"])
+            .output()
+            .expect("knots command should run");
+        assert_success(&opened);
+        let url = std::fs::read_to_string(&seen).expect("browser should record the url");
+        assert!(
+            url.trim().ends_with(&format!("/{id}?t=Fix%20login")),
+            "{url}"
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(root);
+}
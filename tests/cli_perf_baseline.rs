@@ -0,0 +1,75 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn perf_baseline_write_then_strict_compare_without_a_store() {
+    let root = unique_workspace("knots-cli-perf-baseline");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let baseline = root.join(".ci/perf-baseline.json");
+
+    let written = run_knots(
+        &root,
+        &db,
+        &[
+            "perf",
+            "baseline",
+            "write",
+            "--out",
+            baseline.to_str().expect("utf8 path"),
+            "-n",
+            "1",
+            "--max-regression",
+            "400",
+        ],
+    );
+    assert_success(&written);
+    let saved: Value = serde_json::from_str(
+        &std::fs::read_to_string(&baseline).expect("baseline should be written"),
+    )
+    .expect("baseline json");
+    assert_eq!(saved["max_regression_pct"], 400.0);
+    assert!(saved["measurements"]["sync"].as_f64().is_some());
+
+    let compared = run_knots(
+        &root,
+        &db,
+        &[
+            "perf",
+            "-n",
+            "1",
+            "--json",
+            "--baseline",
+            baseline.to_str().expect("utf8 path"),
+        ],
+    );
+    assert_success(&compared);
+    let report: Value = serde_json::from_slice(&compared.stdout).expect("perf json");
+    assert_eq!(report["max_regression_pct"], 400.0);
+    assert!(report["measurements"][0]["baseline_ms"].as_f64().is_some());
+
+    let fast = serde_json::json!({
+        "max_regression_pct": 0.0,
+        "measurements": {"sync": 0.001, "write_avg": 0.001}
+    });
+    std::fs::write(&baseline, fast.to_string()).expect("rewrite baseline");
+    let regressed = run_knots(
+        &root,
+        &db,
+        &[
+            "perf",
+            "-n",
+            "1",
+            "--strict",
+            "--baseline",
+            baseline.to_str().expect("utf8 path"),
+        ],
+    );
+    assert_failure(&regressed);
+    assert!(String::from_utf8_lossy(&regressed.stderr).contains("slower than the baseline"));
+    assert!(!root.join(".knots").exists());
+
+    let _ = std::fs::remove_dir_all(root);
+}
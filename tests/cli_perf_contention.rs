@@ -0,0 +1,52 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn perf_contention_reports_lock_waits_for_parallel_writers() {
+    let root = unique_workspace("knots-cli-perf-contention");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+
+    let output = run_knots(
+        &root,
+        &db,
+        &[
+            "perf",
+            "contention",
+            "--writers",
+            "3",
+            "-n",
+            "2",
+            "--strict",
+            "--json",
+        ],
+    );
+    assert_success(&output);
+    let report: Value = serde_json::from_slice(&output.stdout).expect("contention json");
+    assert_eq!(report["writers"], 3);
+    assert_eq!(report["completed_ops"], 6);
+    assert_eq!(report["lock_timeouts"], 0);
+    assert_eq!(report["stalled_writers"], 0);
+    assert!(report["lock_acquisitions"].as_u64().expect("count") >= 36);
+    assert!(report["lock_wait_p95_ms"].as_f64().is_some());
+
+    let text = run_knots(
+        &root,
+        &db,
+        &["perf", "contention", "--writers", "2", "-n", "1"],
+    );
+    assert_success(&text);
+    assert_contains_in_order(
+        &String::from_utf8_lossy(&text.stdout),
+        &[
+            "perf contention writers=2 ops_per_writer=1",
+            "ops completed=2 lock_timeouts=0 errors=0 stalled_writers=0",
+            "lock_wait acquisitions=",
+            "p95_ms=",
+        ],
+    );
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,53 @@
+mod cli_dispatch_helpers;
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use cli_dispatch_helpers::*;
+
+#[test]
+fn pick_reads_choices_from_stdin_and_prints_the_full_id() {
+    let root = unique_workspace("knots-cli-pick");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["new", "Render columns"]));
+    let created = run_knots(&root, &db, &["new", "Fix pager"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    let mut child = Command::new(knots_binary())
+        .arg("--repo-root")
+        .arg(&root)
+        .arg("--db")
+        .arg(&db)
+        .args(["pick", "--state", "ready_for_planning"])
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .env("HOME", &root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("kno pick should start");
+    child
+        .stdin
+        .take()
+        .expect("stdin should be piped")
+        .write_all(b"pager\n1\n")
+        .expect("choices should be written");
+    let output = child.wait_with_output().expect("kno pick should finish");
+    assert_success(&output);
+    let picked = String::from_utf8_lossy(&output.stdout);
+    assert!(picked.trim().ends_with(&id), "{picked}");
+    let prompt = String::from_utf8_lossy(&output.stderr);
+    assert!(prompt.contains("2 knot(s)"), "{prompt}");
+    assert!(prompt.contains("1 match(es) for 'pager'"), "{prompt}");
+
+    let cancelled = run_knots(&root, &db, &["pick"]);
+    assert_failure(&cancelled);
+    assert!(String::from_utf8_lossy(&cancelled.stderr).contains("no knot picked"));
+
+    let piped = run_knots(&root, &db, &["show", "@pick"]);
+    assert_failure(&piped);
+    assert!(String::from_utf8_lossy(&piped.stderr).contains("@pick needs an interactive terminal"));
+    let _ = std::fs::remove_dir_all(root);
+}
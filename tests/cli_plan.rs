@@ -0,0 +1,67 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn plan_set_show_and_log_track_versions() {
+    let root = unique_workspace("knots-cli-plan");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "SSO login", "--state", "planning"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    let missing = run_knots(&root, &db, &["plan", "show", &id]);
+    assert_failure(&missing);
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("has no plan"));
+
+    let plan_path = root.join("plan.md");
+    std::fs::write(&plan_path, "# Plan\n\n1. add SSO\n").expect("plan should write");
+    let plan_file = plan_path.to_str().expect("utf8 path");
+    let set = run_knots(
+        &root,
+        &db,
+        &[
+            "plan",
+            "set",
+            &id,
+            "--file",
+            plan_file,
+            "--agent-name",
+            "codex",
+        ],
+    );
+    assert_success(&set);
+    assert!(String::from_utf8_lossy(&set.stdout).contains("plan v1 by codex"));
+
+    std::fs::write(&plan_path, "# Plan\n\n1. add SSO\n2. log errors\n").expect("plan");
+    let set = run_knots(
+        &root,
+        &db,
+        &["plan", "set", &id, "--file", plan_file, "--json"],
+    );
+    assert_success(&set);
+    let plan: Value = serde_json::from_slice(&set.stdout).expect("plan json");
+    assert_eq!(plan["version"], 2);
+
+    let latest = run_knots(&root, &db, &["plan", "show", &id]);
+    assert_success(&latest);
+    assert!(String::from_utf8_lossy(&latest.stdout).contains("2. log errors"));
+    let first = run_knots(&root, &db, &["plan", "show", &id, "--version", "1"]);
+    assert_success(&first);
+    assert!(!String::from_utf8_lossy(&first.stdout).contains("log errors"));
+    let absent = run_knots(&root, &db, &["plan", "show", &id, "--version", "3"]);
+    assert_failure(&absent);
+
+    let log = run_knots(&root, &db, &["plan", "log", &id]);
+    assert_success(&log);
+    let log = String::from_utf8_lossy(&log.stdout);
+    assert!(log.starts_with("v2 at"), "{log}");
+    assert!(log.contains("v1 by codex at"), "{log}");
+
+    let shown = run_knots(&root, &db, &["show", &id]);
+    assert!(String::from_utf8_lossy(&shown.stdout).contains("v2 at"));
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,74 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+fn create(root: &std::path::Path, db: &std::path::Path, title: &str) -> String {
+    let created = run_knots(
+        root,
+        db,
+        &["new", title, "--state", "ready_for_implementation"],
+    );
+    assert_success(&created);
+    parse_created_id(&created)
+}
+
+#[test]
+fn plan_week_tags_knots_and_carryover_reports_unfinished_work() {
+    let root = unique_workspace("knots-cli-plan-week");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let done = create(&root, &db, "Ship it");
+    let open = create(&root, &db, "Still going");
+    let later = create(&root, &db, "Not this week");
+
+    let planned = run_knots(&root, &db, &["plan", "week", &done, &open]);
+    assert_success(&planned);
+    assert!(String::from_utf8_lossy(&planned.stdout).contains("planned into"));
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["plan", "week", &later, "--week", "next"],
+    ));
+
+    let listed = run_knots(&root, &db, &["ls", "--week", "current", "--json"]);
+    assert_success(&listed);
+    let listed: Value = serde_json::from_slice(&listed.stdout).expect("ls json");
+    let ids: Vec<&str> = listed
+        .as_array()
+        .expect("array")
+        .iter()
+        .map(|knot| knot["id"].as_str().expect("id"))
+        .collect();
+    assert_eq!(ids.len(), 2, "{ids:?}");
+    assert!(!ids.iter().any(|id| id.ends_with(&later)));
+
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["state", &done, "shipped", "--force"],
+    ));
+    let report = run_knots(&root, &db, &["plan", "carryover", "--carry", "--json"]);
+    assert_success(&report);
+    let report: Value = serde_json::from_slice(&report.stdout).expect("report json");
+    let id_of = |knot: &Value| knot["id"].as_str().expect("id").to_string();
+    assert!(id_of(&report["finished"][0]).ends_with(&done));
+    assert!(id_of(&report["carried_over"][0]).ends_with(&open));
+    assert!(report["carried_to"].is_string());
+
+    let next = run_knots(&root, &db, &["ls", "--week", "next", "--json"]);
+    let next: Value = serde_json::from_slice(&next.stdout).expect("ls json");
+    assert_eq!(next.as_array().expect("array").len(), 2);
+
+    let removed = run_knots(
+        &root,
+        &db,
+        &["plan", "week", &later, "--week", "next", "--remove"],
+    );
+    assert_success(&removed);
+    let bad = run_knots(&root, &db, &["ls", "--week", "soon"]);
+    assert_failure(&bad);
+    assert!(String::from_utf8_lossy(&bad.stderr).contains("invalid week 'soon'"));
+
+    let _ = std::fs::remove_dir_all(root);
+}
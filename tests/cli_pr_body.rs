@@ -0,0 +1,64 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+
+#[test]
+fn pr_body_renders_knot_children_and_review_checklist() {
+    let root = unique_workspace("knots-cli-pr-body");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let parent = run_knots(
+        &root,
+        &db,
+        &[
+            "new",
+            "Fix login",
+            "--desc",
+            "Redirect loops after SSO.",
+            "--acceptance",
+            "Users land on the dashboard.",
+        ],
+    );
+    assert_success(&parent);
+    let parent = parse_created_id(&parent);
+    let child = run_knots(&root, &db, &["new", "Patch callback"]);
+    assert_success(&child);
+    let child = parse_created_id(&child);
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["edge", "add", &parent, "parent_of", &child],
+    ));
+
+    let rendered = run_knots(&root, &db, &["pr-body", &parent]);
+    assert_success(&rendered);
+    let rendered = String::from_utf8_lossy(&rendered.stdout);
+    assert_contains_in_order(
+        &rendered,
+        &[
+            "## Fix login",
+            "Redirect loops after SSO.",
+            "### Acceptance",
+            "Users land on the dashboard.",
+            "### Child knots",
+            "Patch callback",
+            "### Review checklist",
+            "**implementation review**",
+            "- [ ] ",
+            "Knot: `",
+        ],
+    );
+
+    let json = run_knots(&root, &db, &["pr-body", &parent, "--json"]);
+    assert_success(&json);
+    let json: serde_json::Value = serde_json::from_slice(&json.stdout).expect("json");
+    assert_eq!(json["children"][0]["done"], false);
+    assert!(json["checklist"]
+        .as_array()
+        .expect("checklist")
+        .iter()
+        .any(|gate| gate["state"] == "implementation_review"));
+
+    assert_failure(&run_knots(&root, &db, &["pr-body", "missing"]));
+    let _ = std::fs::remove_dir_all(root);
+}
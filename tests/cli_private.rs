@@ -0,0 +1,82 @@
+mod cli_dispatch_helpers;
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use cli_dispatch_helpers::*;
+
+fn run_in(cwd: &Path, args: &[&str]) -> Output {
+    Command::new(knots_binary())
+        .current_dir(cwd)
+        .env_remove("KNOTS_REPO_ROOT")
+        .env_remove("KNOTS_SPACE")
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .env("KNOTS_CACHE_LOCATION", "repo")
+        .env("HOME", cwd)
+        .args(args)
+        .output()
+        .expect("knots command should run")
+}
+
+fn stdout(output: &Output) -> String {
+    assert_success(output);
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn created_id(output: &str) -> String {
+    output
+        .split_whitespace()
+        .nth(1)
+        .expect("created output should name the knot")
+        .to_string()
+}
+
+#[test]
+fn private_knots_stay_local_until_published() {
+    let root = unique_workspace("knots-cli-private");
+    let remote = setup_repo_with_remote(&root);
+    assert_success(&run_in(&root, &["init"]));
+    assert_success(&run_in(&root, &["new", "Team knot"]));
+    let created = stdout(&run_in(&root, &["new", "Scratch idea", "--private"]));
+    assert!(created.contains("(private)"), "{created}");
+    let id = created_id(&created);
+    assert!(stdout(&run_in(&root, &["ls"])).contains("Scratch idea"));
+    assert_success(&run_in(&root, &["push"]));
+
+    let clone = unique_workspace("knots-cli-private-clone");
+    run_git(&clone, &["clone", remote.to_str().expect("utf8 path"), "."]);
+    run_git(&clone, &["config", "user.email", "knots@example.com"]);
+    run_git(&clone, &["config", "user.name", "Knots Test"]);
+    assert_success(&run_in(&clone, &["init"]));
+    let clone_ls = stdout(&run_in(&clone, &["ls"]));
+    assert!(clone_ls.contains("Team knot") && !clone_ls.contains("Scratch idea"));
+
+    let published = stdout(&run_in(&root, &["publish", &id]));
+    assert!(published.contains("run `kno push`"), "{published}");
+    let again = run_in(&root, &["publish", &id]);
+    assert_failure(&again);
+    assert!(String::from_utf8_lossy(&again.stderr).contains("is not private"));
+    assert_success(&run_in(&root, &["push"]));
+    assert_success(&run_in(&clone, &["pull"]));
+    assert!(stdout(&run_in(&clone, &["ls"])).contains("Scratch idea"));
+
+    let _ = std::fs::remove_dir_all(root);
+    let _ = std::fs::remove_dir_all(clone);
+}
+
+#[test]
+fn drafts_publish_with_fresh_timestamps() {
+    let root = unique_workspace("knots-cli-draft");
+    setup_repo_with_remote(&root);
+    assert_success(&run_in(&root, &["init"]));
+    let created = stdout(&run_in(&root, &["new", "Half-formed", "--draft"]));
+    assert!(created.contains("(draft)"), "{created}");
+    let id = created_id(&created);
+    let published = stdout(&run_in(&root, &["publish", &id, "--json"]));
+    let published: serde_json::Value = serde_json::from_str(&published).expect("json");
+    assert_eq!(published["draft"], true);
+    assert_eq!(published["event_files"], 2);
+    assert_success(&run_in(&root, &["push"]));
+
+    let _ = std::fs::remove_dir_all(root);
+}
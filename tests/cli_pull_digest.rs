@@ -0,0 +1,45 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+
+#[test]
+fn pull_prints_a_digest_and_notify_pipes_it_to_the_sink() {
+    let root = unique_workspace("knots-cli-pull-digest");
+    let remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["init"]));
+    assert_success(&run_knots(&root, &db, &["new", "Seen before"]));
+    assert_success(&run_knots(&root, &db, &["push"]));
+
+    let clone = unique_workspace("knots-cli-pull-digest-clone");
+    run_git(&clone, &["clone", remote.to_str().expect("utf8 path"), "."]);
+    run_git(&clone, &["config", "user.email", "knots@example.com"]);
+    run_git(&clone, &["config", "user.name", "Knots Test"]);
+    let clone_db = clone.join(".knots/cache/state.sqlite");
+    let first = run_knots(&clone, &clone_db, &["pull"]);
+    assert_success(&first);
+    assert_contains_in_order(
+        &String::from_utf8_lossy(&first.stdout),
+        &["pull head=", "new knots (1):", "Seen before"],
+    );
+    let quiet = run_knots(&clone, &clone_db, &["pull"]);
+    assert!(String::from_utf8_lossy(&quiet.stdout).contains("no remote changes"));
+
+    let sink = clone.join("digest.txt");
+    let command = format!("cat > '{}'", sink.display());
+    assert_success(&run_knots(
+        &clone,
+        &clone_db,
+        &["config", "set", "notify.command", &command],
+    ));
+    assert_success(&run_knots(&root, &db, &["new", "Fresh upstream"]));
+    assert_success(&run_knots(&root, &db, &["push"]));
+    let pulled = run_knots(&clone, &clone_db, &["pull", "--notify"]);
+    assert_success(&pulled);
+    let sent = std::fs::read_to_string(&sink).expect("sink should receive the digest");
+    assert!(sent.starts_with("new knots (1):"), "{sent}");
+    assert!(sent.contains("Fresh upstream") && !sent.contains("Seen before"));
+
+    let _ = std::fs::remove_dir_all(root);
+    let _ = std::fs::remove_dir_all(clone);
+}
@@ -0,0 +1,73 @@
+mod cli_dispatch_helpers;
+
+use std::path::{Path, PathBuf};
+
+use cli_dispatch_helpers::*;
+
+const SECRET: &str = "ghp_0123456789abcdef";
+
+fn files_containing(dir: &Path, text: &str) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries {
+            let path = entry.expect("dir entry").path();
+            if path.is_dir() {
+                stack.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "json")
+                && std::fs::read_to_string(&path).is_ok_and(|raw| raw.contains(text))
+            {
+                found.push(path);
+            }
+        }
+    }
+    found
+}
+
+#[test]
+fn redact_scrubs_pushed_event_copies_and_flags_the_history_rewrite() {
+    let root = unique_workspace("knots-cli-redact");
+    setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["init"]));
+    let body = format!("deploy with {SECRET}");
+    let created = run_knots(&root, &db, &["new", "Leaky", "--desc", &body]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run_knots(&root, &db, &["push"]));
+
+    let leaked = files_containing(&root, SECRET);
+    assert_eq!(leaked.len(), 2, "store and worktree copies: {leaked:?}");
+    let name = leaked[0]
+        .file_name()
+        .and_then(|n| n.to_str())
+        .expect("name");
+    let event_id = &name[..name.find("-knot.").expect("full event")];
+
+    let redact = run_knots(
+        &root,
+        &db,
+        &["redact", event_id, "--pattern", SECRET, "--json"],
+    );
+    assert_success(&redact);
+    let report: serde_json::Value =
+        serde_json::from_slice(&redact.stdout).expect("redact json should parse");
+    assert_eq!(report["published"], true);
+    assert_eq!(report["event_files"], 2);
+    assert_eq!(report["fields"], serde_json::json!(["body"]));
+    assert!(files_containing(&root, SECRET).is_empty());
+
+    let show = run_knots(&root, &db, &["show", &id]);
+    assert_success(&show);
+    assert!(String::from_utf8_lossy(&show.stdout).contains("[REDACTED]"));
+    assert_success(&run_knots(&root, &db, &["fsck"]));
+
+    let missing = run_knots(&root, &db, &["redact", event_id, "--pattern", SECRET]);
+    assert_failure(&missing);
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("no --pattern match"));
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,59 @@
+mod cli_dispatch_helpers;
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use cli_dispatch_helpers::*;
+
+/// Runs with neither `--repo-root` nor `--db`, from `cwd`.
+fn run_from(cwd: &Path, home: &Path, args: &[&str]) -> Output {
+    Command::new(knots_binary())
+        .current_dir(cwd)
+        .env_remove("KNOTS_REPO_ROOT")
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .env("KNOTS_CACHE_LOCATION", "repo")
+        .env("HOME", home)
+        .args(args)
+        .output()
+        .expect("knots command should run")
+}
+
+#[test]
+fn commands_find_the_nearest_knots_store_from_a_subdirectory() {
+    let root = unique_workspace("knots-cli-repo-discovery");
+    setup_repo_with_remote(&root);
+    let nested = root.join("src").join("deep");
+    std::fs::create_dir_all(&nested).expect("nested dir should be creatable");
+
+    assert_success(&run_from(&nested, &root, &["init"]));
+    assert_success(&run_from(&nested, &root, &["new", "Repo knot"]));
+    assert!(root.join(".knots").is_dir());
+    let listed = run_from(&nested, &root, &["ls"]);
+    assert_success(&listed);
+    assert!(String::from_utf8_lossy(&listed.stdout).contains("Repo knot"));
+
+    let workspace = root.join("services").join("api");
+    let inner = workspace.join("handlers");
+    std::fs::create_dir_all(workspace.join(".knots")).expect("workspace store");
+    std::fs::create_dir_all(&inner).expect("workspace dir");
+    assert_success(&run_from(&inner, &root, &["new", "Workspace knot"]));
+    let workspace_ls = run_from(&inner, &root, &["ls"]);
+    assert_success(&workspace_ls);
+    let workspace_out = String::from_utf8_lossy(&workspace_ls.stdout);
+    assert!(workspace_out.contains("Workspace knot"), "{workspace_out}");
+    assert!(!workspace_out.contains("Repo knot"), "{workspace_out}");
+
+    let overridden = Command::new(knots_binary())
+        .current_dir(&inner)
+        .env("KNOTS_REPO_ROOT", &root)
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .env("KNOTS_CACHE_LOCATION", "repo")
+        .env("HOME", &root)
+        .arg("ls")
+        .output()
+        .expect("knots command should run");
+    assert_success(&overridden);
+    assert!(String::from_utf8_lossy(&overridden.stdout).contains("Repo knot"));
+
+    let _ = std::fs::remove_dir_all(root);
+}
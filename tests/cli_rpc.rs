@@ -0,0 +1,112 @@
+mod cli_dispatch_helpers;
+use cli_dispatch_helpers::*;
+
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+
+use serde_json::{json, Value};
+
+fn send(input: &mut impl Write, message: Value) {
+    let body = message.to_string();
+    write!(input, "Content-Length: {}\r\n\r\n{body}", body.len()).expect("write frame");
+    input.flush().expect("flush");
+}
+
+fn receive(output: &mut impl BufRead) -> Value {
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        assert!(
+            output.read_line(&mut line).expect("header") > 0,
+            "rpc closed"
+        );
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length: ") {
+            length = value.parse().expect("length");
+        }
+    }
+    let mut body = vec![0; length];
+    output.read_exact(&mut body).expect("body");
+    serde_json::from_slice(&body).expect("json")
+}
+
+#[test]
+fn rpc_answers_requests_and_notifies_about_outside_changes() {
+    let root = unique_workspace("knots-cli-rpc");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let id = parse_created_id(&run_knots(&root, &db, &["new", "Edited from an editor"]));
+
+    let mut child = Command::new(knots_binary())
+        .arg("--repo-root")
+        .arg(&root)
+        .arg("--db")
+        .arg(&db)
+        .args(["rpc", "--poll-ms", "50"])
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .env("HOME", &root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("rpc should start");
+    let mut input = child.stdin.take().expect("stdin");
+    let mut output = BufReader::new(child.stdout.take().expect("stdout"));
+
+    send(
+        &mut input,
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize" }),
+    );
+    let init = receive(&mut output);
+    assert_eq!(init["id"], 1);
+    assert_eq!(
+        init["result"]["capabilities"]["notifications"],
+        json!(["knots/didChange"])
+    );
+
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["update", &id, "--title", "Renamed"],
+    ));
+    let notification = receive(&mut output);
+    assert_eq!(notification["method"], "knots/didChange");
+
+    send(
+        &mut input,
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "knots/show", "params": { "id": id } }),
+    );
+    let shown = receive(&mut output);
+    assert_eq!(shown["result"]["title"], "Renamed");
+    assert_eq!(
+        notification["params"]["changed"],
+        json!([shown["result"]["id"]])
+    );
+
+    send(
+        &mut input,
+        json!({ "jsonrpc": "2.0", "id": 3, "method": "knots/claim",
+                "params": { "id": id, "agentName": "editor" } }),
+    );
+    let mut claimed = receive(&mut output);
+    while claimed.get("id").is_none() {
+        claimed = receive(&mut output);
+    }
+    assert_eq!(claimed["id"], 3);
+    assert!(claimed["result"]["prompt"].is_string(), "{claimed}");
+
+    send(
+        &mut input,
+        json!({ "jsonrpc": "2.0", "id": 4, "method": "shutdown" }),
+    );
+    let mut shutdown = receive(&mut output);
+    while shutdown.get("id").is_none() {
+        shutdown = receive(&mut output);
+    }
+    assert_eq!(shutdown["result"], Value::Null);
+    send(&mut input, json!({ "jsonrpc": "2.0", "method": "exit" }));
+    assert!(child.wait().expect("rpc should exit").success());
+    let _ = std::fs::remove_dir_all(root);
+}
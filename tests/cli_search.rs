@@ -0,0 +1,35 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn search_ranks_matching_knots_and_semantic_needs_an_endpoint() {
+    let root = unique_workspace("knots-cli-search");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(
+        &root,
+        &db,
+        &["new", "Retry on CI", "-d", "the auth tests are flaky"],
+    );
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run_knots(&root, &db, &["new", "Render release notes"]));
+
+    let text = run_knots(&root, &db, &["search", "flaky auth tests"]);
+    assert_success(&text);
+    let stdout = String::from_utf8_lossy(&text.stdout);
+    assert!(stdout.starts_with("1.00 "), "{stdout}");
+    assert!(stdout.contains("Retry on CI"), "{stdout}");
+    assert_eq!(stdout.lines().count(), 1, "{stdout}");
+
+    let json = run_knots(&root, &db, &["search", "flaky auth", "--json"]);
+    assert_success(&json);
+    let hits: Value = serde_json::from_slice(&json.stdout).expect("search json");
+    assert!(hits[0]["id"].as_str().expect("id").ends_with(&id));
+    assert_eq!(hits[0]["score"], 1.0);
+
+    let semantic = run_knots(&root, &db, &["search", "--semantic", "flaky auth"]);
+    assert_failure(&semantic);
+}
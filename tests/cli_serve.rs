@@ -0,0 +1,334 @@
+mod cli_dispatch_helpers;
+use cli_dispatch_helpers::*;
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use serde_json::Value;
+
+#[test]
+fn serve_audit_lists_replication_requests() {
+    let root = unique_workspace("knots-cli-serve-audit");
+    std::fs::create_dir_all(&root).expect("workspace");
+    let mut child = Command::new(knots_binary())
+        .arg("serve")
+        .arg("--replication")
+        .arg("--root")
+        .arg(&root)
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("serve should start");
+    child
+        .stdin
+        .take()
+        .expect("stdin")
+        .write_all(b"PUT .knots/events/a.json 2\n{}GET ../x\nQUIT\n")
+        .expect("requests");
+    let served = child.wait_with_output().expect("serve should exit");
+    assert_success(&served);
+
+    let audit = Command::new(knots_binary())
+        .args(["serve", "audit", "--json", "--root"])
+        .arg(&root)
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .output()
+        .expect("audit should run");
+    assert_success(&audit);
+    let rows: Value = serde_json::from_slice(&audit.stdout).expect("audit json");
+    let rows = rows.as_array().expect("rows");
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["verb"], "GET");
+    assert_eq!(rows[0]["outcome"], "error");
+    assert_eq!(rows[1]["key"], ".knots/events/a.json");
+    assert_eq!(rows[1]["outcome"], "ok");
+
+    let text = Command::new(knots_binary())
+        .args(["serve", "audit", "--root"])
+        .arg(&root)
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .output()
+        .expect("audit should run");
+    assert_success(&text);
+    let text = String::from_utf8_lossy(&text.stdout);
+    assert!(text.contains("- PUT .knots/events/a.json ok"));
+
+    let missing = Command::new(knots_binary())
+        .args(["serve", "audit", "--root"])
+        .arg(root.join("nowhere"))
+        .output()
+        .expect("audit should run");
+    assert_failure(&missing);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+fn http_get(addr: &str, method: &str, path: &str) -> String {
+    use std::io::Read;
+    let mut stream = std::net::TcpStream::connect(addr).expect("dashboard should accept");
+    write!(stream, "{method} {path} HTTP/1.1\r\nHost: {addr}\r\n\r\n").expect("request");
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("response");
+    response
+}
+
+#[test]
+fn serve_ui_answers_board_knot_and_graph_requests_read_only() {
+    use std::io::BufRead;
+    let root = unique_workspace("knots-cli-serve-ui");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Shown on the board"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    let mut child = Command::new(knots_binary())
+        .arg("--repo-root")
+        .arg(&root)
+        .arg("--db")
+        .arg(&db)
+        .args(["serve", "--ui", "--addr", "127.0.0.1:0"])
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .env("HOME", &root)
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("dashboard should start");
+    let mut banner = String::new();
+    std::io::BufReader::new(child.stdout.as_mut().expect("stdout"))
+        .read_line(&mut banner)
+        .expect("banner");
+    let addr = banner
+        .trim()
+        .strip_prefix("serving dashboard at http://")
+        .expect("dashboard address")
+        .to_string();
+
+    let page = http_get(&addr, "GET", "/");
+    assert!(page.starts_with("HTTP/1.1 200 OK"));
+    assert!(page.contains("<title>knots</title>"));
+    let board = http_get(&addr, "GET", "/api/board");
+    assert!(board.contains("Shown on the board"));
+    let detail = http_get(&addr, "GET", &format!("/api/knots/{id}"));
+    assert!(detail.starts_with("HTTP/1.1 200 OK"));
+    assert!(detail.contains("\"activity\""));
+    assert!(http_get(&addr, "GET", "/api/graph").contains("\"nodes\""));
+    assert!(http_get(&addr, "GET", "/nope").starts_with("HTTP/1.1 404"));
+    assert!(http_get(&addr, "POST", "/api/board").starts_with("HTTP/1.1 405"));
+
+    let _ = child.kill();
+    let _ = child.wait();
+    let _ = std::fs::remove_dir_all(root);
+}
+
+fn http_get_as(addr: &str, path: &str, token: &str) -> String {
+    use std::io::Read;
+    let mut stream = std::net::TcpStream::connect(addr).expect("dashboard should accept");
+    write!(
+        stream,
+        "GET {path} HTTP/1.1\r\nHost: {addr}\r\nAuthorization: Bearer {token}\r\n\r\n"
+    )
+    .expect("request");
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("response");
+    response
+}
+
+#[test]
+fn serve_ui_checks_tokens_logs_requests_and_hides_private_knots() {
+    use sha2::{Digest, Sha256};
+    use std::io::BufRead;
+    let root = unique_workspace("knots-cli-serve-ui-auth");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["new", "Team work"]));
+    let private = parse_created_id(&run_knots(&root, &db, &["new", "Mine", "--private"]));
+    let serve = |addr: &str| {
+        Command::new(knots_binary())
+            .arg("--repo-root")
+            .arg(&root)
+            .arg("--db")
+            .arg(&db)
+            .args(["serve", "--ui", "--addr", addr])
+            .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+            .env("HOME", &root)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("dashboard should start")
+    };
+    let open = serve("0.0.0.0:0").wait_with_output().expect("exit");
+    assert_failure(&open);
+    assert!(String::from_utf8_lossy(&open.stderr).contains("needs tokens"));
+
+    let digest: String = Sha256::digest(b"team-token")
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    std::fs::write(
+        root.join(".knots/server.toml"),
+        format!("[[tokens]]\nname = \"team\"\nsha256 = \"{digest}\"\nrole = \"reader\"\n"),
+    )
+    .expect("server.toml");
+    let mut child = serve("127.0.0.1:0");
+    let mut banner = String::new();
+    std::io::BufReader::new(child.stdout.as_mut().expect("stdout"))
+        .read_line(&mut banner)
+        .expect("banner");
+    let addr = banner
+        .trim()
+        .strip_prefix("serving dashboard at http://")
+        .expect("dashboard address")
+        .to_string();
+
+    assert!(http_get(&addr, "GET", "/").starts_with("HTTP/1.1 200 OK"));
+    assert!(http_get(&addr, "GET", "/api/board").starts_with("HTTP/1.1 401"));
+    assert!(http_get_as(&addr, "/api/board", "wrong").starts_with("HTTP/1.1 401"));
+    let board = http_get_as(&addr, "/api/board", "team-token");
+    assert!(board.starts_with("HTTP/1.1 200 OK"), "{board}");
+    assert!(board.contains("Team work") && !board.contains("Mine"));
+    let hidden = http_get_as(&addr, &format!("/api/knots/{private}"), "team-token");
+    assert!(hidden.starts_with("HTTP/1.1 404"), "{hidden}");
+    let _ = child.kill();
+    let _ = child.wait();
+
+    let audit = Command::new(knots_binary())
+        .args(["serve", "audit", "--json", "--root"])
+        .arg(root.join(".knots"))
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .output()
+        .expect("audit should run");
+    assert_success(&audit);
+    let rows: Value = serde_json::from_slice(&audit.stdout).expect("audit json");
+    let outcomes: Vec<_> = rows
+        .as_array()
+        .expect("rows")
+        .iter()
+        .rev()
+        .map(|row| (row["token_name"].as_str(), row["outcome"].as_str()))
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            (Some("-"), Some("denied")),
+            (Some("-"), Some("denied")),
+            (Some("team"), Some("ok")),
+            (Some("team"), Some("error")),
+        ]
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn serve_graphql_answers_posted_queries() {
+    use std::io::{BufRead, Read};
+    let root = unique_workspace("knots-cli-serve-graphql");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["new", "Queried over http"]));
+
+    let mut child = Command::new(knots_binary())
+        .arg("--repo-root")
+        .arg(&root)
+        .arg("--db")
+        .arg(&db)
+        .args(["serve", "--graphql", "--addr", "127.0.0.1:0"])
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .env("HOME", &root)
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("graphql server should start");
+    let mut banner = String::new();
+    std::io::BufReader::new(child.stdout.as_mut().expect("stdout"))
+        .read_line(&mut banner)
+        .expect("banner");
+    let addr = banner
+        .trim()
+        .strip_prefix("serving graphql at http://")
+        .expect("graphql address")
+        .to_string();
+
+    let body = r#"{"query":"{ knots { title state } }"}"#;
+    let mut stream = std::net::TcpStream::connect(&addr).expect("connect");
+    write!(
+        stream,
+        "POST /graphql HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\n\r\n{body}",
+        body.len()
+    )
+    .expect("request");
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("response");
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    let json: Value =
+        serde_json::from_str(response.split("\r\n\r\n").nth(1).expect("body")).expect("json");
+    assert_eq!(json["data"]["knots"][0]["title"], "Queried over http");
+    assert!(http_get(&addr, "GET", "/graphql").starts_with("HTTP/1.1 405"));
+    assert!(http_get(&addr, "GET", "/api/board").starts_with("HTTP/1.1 404"));
+
+    let _ = child.kill();
+    let _ = child.wait();
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn export_html_writes_a_self_contained_dashboard() {
+    let root = unique_workspace("knots-cli-export-html");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let shared = parse_created_id(&run_knots(&root, &db, &["new", "Shared roadmap"]));
+    let hidden = parse_created_id(&run_knots(&root, &db, &["new", "Hidden work"]));
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["update", &shared, "--add-tag", "partner"],
+    ));
+
+    let site = root.join("site");
+    let out = site.to_str().expect("utf8 path");
+    let exported = run_knots(
+        &root,
+        &db,
+        &["export", "html", "--out", out, "--tag", "partner"],
+    );
+    assert_success(&exported);
+    assert!(String::from_utf8_lossy(&exported.stdout).starts_with("exported 1 knot(s)"));
+    let html = std::fs::read_to_string(site.join("index.html")).expect("index.html");
+    assert!(html.contains("window.KNOTS_SNAPSHOT"));
+    assert!(html.contains("Shared roadmap") && html.contains(&shared));
+    assert!(!html.contains("Hidden work") && !html.contains(&hidden));
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn export_html_leaves_out_sensitive_notes_and_handoffs() {
+    let root = unique_workspace("knots-cli-export-html-sensitive");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let id = parse_created_id(&run_knots(&root, &db, &["new", "Partner rollout"]));
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "sensitive_fields = [\"notes\", \"handoff_capsules\"]\n",
+    )
+    .expect("repo config");
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &[
+            "update",
+            &id,
+            "--add-note",
+            "SECRET-NOTE-CONTENT",
+            "--add-handoff-capsule",
+            "SECRET-HANDOFF-CONTENT",
+        ],
+    ));
+
+    let site = root.join("site");
+    let out = site.to_str().expect("utf8 path");
+    assert_success(&run_knots(&root, &db, &["export", "html", "--out", out]));
+    let html = std::fs::read_to_string(site.join("index.html")).expect("index.html");
+    assert!(html.contains("Partner rollout"));
+    assert!(!html.contains("SECRET-NOTE-CONTENT"));
+    assert!(!html.contains("SECRET-HANDOFF-CONTENT"));
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,112 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+const TEMPLATE_BUNDLE: &str = r#"
+[workflow]
+name = "templated_flow"
+version = 1
+default_profile = "templated"
+
+[states.ready_for_work]
+kind = "queue"
+
+[states.work]
+kind = "action"
+action_type = "produce"
+executor = "agent"
+prompt = "work"
+
+[states.done]
+kind = "terminal"
+
+[states.blocked]
+kind = "escape"
+
+[states.deferred]
+kind = "escape"
+
+[states.abandoned]
+kind = "terminal"
+
+[steps.work_step]
+queue = "ready_for_work"
+action = "work"
+
+[phases.main]
+produce = "work_step"
+
+[profiles.templated]
+phases = ["main"]
+
+[prompts.work]
+body = """
+Work on {{ knot.title }} ({{ knot.state }}) in {{ repo.name }}.
+Finish in one of: {{ profile.terminal_states }}.
+Keep {{ unknown.thing }} as written.
+"""
+
+[prompts.work.success]
+complete = "done"
+
+[prompts.work.failure]
+blocked = "blocked"
+"#;
+
+fn install_templated_flow(root: &std::path::Path, db: &std::path::Path) {
+    bootstrap_builtin_workflows(root, db);
+    let bundle = root.join("templated-flow.toml");
+    std::fs::write(&bundle, TEMPLATE_BUNDLE).expect("bundle should write");
+    let bundle = bundle.to_str().expect("utf8 path");
+    assert_success(&run_knots(
+        root,
+        db,
+        &["workflow", "install", "--type", "work", bundle],
+    ));
+    assert_success(&run_knots(root, db, &["workflow", "use", "templated_flow"]));
+}
+
+#[test]
+fn skill_poll_and_claim_expand_skill_variables() {
+    let root = unique_workspace("knots-cli-skill-vars");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    install_templated_flow(&root, &db);
+    let repo_name = root.file_name().unwrap().to_string_lossy().into_owned();
+    let created = run_knots(&root, &db, &["new", "Templated work"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    let skill = run_knots(&root, &db, &["skill", &id]);
+    assert_success(&skill);
+    let skill = String::from_utf8_lossy(&skill.stdout);
+    assert!(
+        skill.contains(&format!(
+            "Work on Templated work (ready_for_work) in {repo_name}."
+        )),
+        "{skill}"
+    );
+    assert!(skill.contains("one of: abandoned, done."), "{skill}");
+    assert!(skill.contains("Keep {{ unknown.thing }} as written."));
+
+    let by_name = run_knots(&root, &db, &["skill", "work"]);
+    assert_success(&by_name);
+    let by_name = String::from_utf8_lossy(&by_name.stdout);
+    assert!(by_name.contains("Work on {{ knot.title }}"), "{by_name}");
+    assert!(by_name.contains(&format!("in {repo_name}.")), "{by_name}");
+
+    let poll = run_knots(&root, &db, &["poll", "--json"]);
+    assert_success(&poll);
+    let poll: Value = serde_json::from_slice(&poll.stdout).expect("poll json");
+    let prompt = poll["prompt"].as_str().expect("prompt");
+    assert!(prompt.contains("Work on Templated work (ready_for_work)"));
+
+    let claim = run_knots(&root, &db, &["claim", &id, "--json"]);
+    assert_success(&claim);
+    let claim: Value = serde_json::from_slice(&claim.stdout).expect("claim json");
+    let prompt = claim["prompt"].as_str().expect("prompt");
+    assert!(prompt.contains("Work on Templated work (work)"), "{prompt}");
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,60 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn snoozed_knot_leaves_poll_until_its_wake_time() {
+    let root = unique_workspace("knots-cli-snooze");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+
+    let created = run_knots(
+        &root,
+        &db,
+        &[
+            "new",
+            "Revisit in autumn",
+            "--profile",
+            "autopilot",
+            "--state",
+            "ready_for_implementation",
+        ],
+    );
+    assert_success(&created);
+    let id = parse_created_id(&created);
+
+    assert_failure(&run_knots(
+        &root,
+        &db,
+        &["snooze", &id, "--until", "2001-01-01"],
+    ));
+    let snoozed = run_knots(&root, &db, &["snooze", &id, "--until", "2999-09-01"]);
+    assert_success(&snoozed);
+    assert!(String::from_utf8_lossy(&snoozed.stdout).contains("until 2999-09-01T00:00:00Z"));
+
+    let shown = run_knots(&root, &db, &["show", &id, "--json"]);
+    assert_success(&shown);
+    let shown: Value = serde_json::from_slice(&shown.stdout).expect("show json");
+    assert_eq!(shown["state"], "deferred");
+    assert_eq!(shown["snoozed_until"], "2999-09-01T00:00:00Z");
+    assert_failure(&run_knots(&root, &db, &["poll", "--json"]));
+
+    let conn = rusqlite::Connection::open(&db).expect("db should open");
+    conn.execute(
+        "UPDATE knot_hot SET snoozed_until = '2000-01-01T00:00:00Z'",
+        [],
+    )
+    .expect("snooze should be backdated");
+    drop(conn);
+
+    let poll = run_knots(&root, &db, &["poll", "--json"]);
+    assert_success(&poll);
+    let poll: Value = serde_json::from_slice(&poll.stdout).expect("poll json");
+    assert_eq!(poll["title"], "Revisit in autumn");
+    let shown = run_knots(&root, &db, &["show", &id, "--json"]);
+    let shown: Value = serde_json::from_slice(&shown.stdout).expect("show json");
+    assert_eq!(shown["state"], "ready_for_implementation");
+    assert!(shown.get("snoozed_until").is_none());
+    let _ = std::fs::remove_dir_all(root);
+}
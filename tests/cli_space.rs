@@ -0,0 +1,85 @@
+mod cli_dispatch_helpers;
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use cli_dispatch_helpers::*;
+
+/// Runs from `cwd` without `--db`, so each space resolves its own cache.
+fn run_in(cwd: &Path, args: &[&str]) -> Output {
+    Command::new(knots_binary())
+        .current_dir(cwd)
+        .env_remove("KNOTS_REPO_ROOT")
+        .env_remove("KNOTS_SPACE")
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1")
+        .env("KNOTS_CACHE_LOCATION", "repo")
+        .env("HOME", cwd)
+        .args(args)
+        .output()
+        .expect("knots command should run")
+}
+
+fn stdout(output: &Output) -> String {
+    assert_success(output);
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn spaces_keep_separate_backlogs_and_sync_on_their_own_branch() {
+    let root = unique_workspace("knots-cli-space");
+    let remote = setup_repo_with_remote(&root);
+    assert_success(&run_in(&root, &["init"]));
+    assert_success(&run_in(&root, &["new", "Root knot"]));
+
+    let created = stdout(&run_in(&root, &["space", "create", "payments"]));
+    assert!(created.contains("created space payments on origin/knots-payments"));
+    assert_success(&run_in(
+        &root,
+        &["--space", "payments", "new", "Payments knot"],
+    ));
+    let space_store = root.join(".knots").join("spaces").join("payments");
+    assert!(space_store.join("cache").join("state.sqlite").exists());
+
+    let space_ls = stdout(&run_in(&root, &["--space", "payments", "ls"]));
+    assert!(space_ls.contains("Payments knot") && !space_ls.contains("Root knot"));
+    let root_ls = stdout(&run_in(&root, &["ls"]));
+    assert!(root_ls.contains("Root knot") && !root_ls.contains("Payments knot"));
+
+    assert!(stdout(&run_in(&root, &["space", "default", "payments"])).contains("payments"));
+    assert!(stdout(&run_in(&root, &["ls"])).contains("Payments knot"));
+    assert!(stdout(&run_in(&root, &["--space", "root", "ls"])).contains("Root knot"));
+    let listed = stdout(&run_in(&root, &["space", "list"]));
+    assert_contains_in_order(
+        &listed,
+        &["  root", "knots", "* payments", "knots-payments"],
+    );
+    assert_success(&run_in(&root, &["space", "default", "--clear"]));
+
+    let unknown = run_in(&root, &["--space", "billing", "ls"]);
+    assert_failure(&unknown);
+    assert!(String::from_utf8_lossy(&unknown.stderr).contains("unknown space 'billing'"));
+    let with_db = run_in(&root, &["--space", "payments", "--db", "x.sqlite", "ls"]);
+    assert_failure(&with_db);
+
+    assert_success(&run_in(&root, &["--space", "payments", "push"]));
+    assert_success(&run_in(&root, &["push"]));
+
+    let clone = unique_workspace("knots-cli-space-clone");
+    run_git(&clone, &["clone", remote.to_str().expect("utf8 path"), "."]);
+    run_git(&clone, &["config", "user.email", "knots@example.com"]);
+    run_git(&clone, &["config", "user.name", "Knots Test"]);
+    assert_success(&run_in(&clone, &["init"]));
+    let clone_list = stdout(&run_in(&clone, &["space", "list"]));
+    assert!(clone_list.contains("payments") && clone_list.contains("(not set up here)"));
+    let not_set_up = run_in(&clone, &["--space", "payments", "ls"]);
+    assert_failure(&not_set_up);
+    assert!(String::from_utf8_lossy(&not_set_up.stderr).contains("kno space create payments"));
+
+    let joined = stdout(&run_in(&clone, &["space", "create", "payments"]));
+    assert!(joined.contains("set up space payments from origin/knots-payments"));
+    let clone_ls = stdout(&run_in(&clone, &["--space", "payments", "ls"]));
+    assert!(clone_ls.contains("Payments knot") && !clone_ls.contains("Root knot"));
+
+    let _ = std::fs::remove_dir_all(root);
+    let _ = std::fs::remove_dir_all(clone);
+}
@@ -0,0 +1,63 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+fn create(root: &std::path::Path, db: &std::path::Path, title: &str) -> String {
+    let created = run_knots(root, db, &["new", title]);
+    assert_success(&created);
+    parse_created_id(&created)
+}
+
+#[test]
+fn stats_graph_reports_edges_chains_blockers_and_orphans() {
+    let root = unique_workspace("knots-cli-stats-graph");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let schema = create(&root, &db, "Schema");
+    let api = create(&root, &db, "API");
+    let ui = create(&root, &db, "UI");
+    let docs = create(&root, &db, "Docs");
+    create(&root, &db, "Loose end");
+    for (src, dst) in [(&api, &schema), (&ui, &api), (&docs, &schema)] {
+        assert_success(&run_knots(
+            &root,
+            &db,
+            &["edge", "add", src, "blocked_by", dst],
+        ));
+    }
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["edge", "add", &docs, "related", &ui],
+    ));
+
+    let graph = run_knots(&root, &db, &["stats", "graph", "--json"]);
+    assert_success(&graph);
+    let report: Value = serde_json::from_slice(&graph.stdout).expect("graph json");
+    assert_eq!(report["open_knots"], 5);
+    assert_eq!(report["edges"]["blocked_by"], 3);
+    assert_eq!(report["edges"]["related"], 1);
+    assert_eq!(report["orphans"], 1);
+    let chain: Vec<&str> = report["longest_chain"]
+        .as_array()
+        .expect("chain")
+        .iter()
+        .map(|id| id.as_str().expect("id"))
+        .collect();
+    assert_eq!(chain.len(), 3, "{chain:?}");
+    assert!(chain[0].ends_with(&ui) && chain[2].ends_with(&schema));
+    let top = &report["most_blocking"][0];
+    assert!(top["id"].as_str().expect("id").ends_with(&schema));
+    assert_eq!(top["direct"], 2);
+    assert_eq!(top["total"], 3);
+
+    let text = run_knots(&root, &db, &["stats", "graph", "--top", "1"]);
+    assert_success(&text);
+    let stdout = String::from_utf8_lossy(&text.stdout);
+    assert!(stdout.contains("longest chain   3"), "{stdout}");
+    assert!(stdout.contains("Schema [ready_for_planning] blocks 2 directly, 3 in total"));
+    assert!(!stdout.contains("API [ready_for_planning]"), "{stdout}");
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,49 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn stats_usage_records_commands_only_when_enabled() {
+    let root = unique_workspace("knots-cli-stats-usage");
+    let _remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+
+    assert_success(&run_knots(&root, &db, &["init"]));
+    assert_success(&run_knots(&root, &db, &["ls"]));
+    let off = run_knots(&root, &db, &["stats", "usage"]);
+    assert_success(&off);
+    assert!(String::from_utf8_lossy(&off.stdout).contains("local metrics are off"));
+
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["config", "set", "metrics.local", "true"],
+    ));
+    assert_success(&run_knots(&root, &db, &["new", "Usage knot"]));
+    assert_success(&run_knots(&root, &db, &["ls"]));
+    assert_success(&run_knots(&root, &db, &["ls"]));
+    assert_success(&run_knots(&root, &db, &["push"]));
+
+    let usage = run_knots(&root, &db, &["stats", "usage", "--json"]);
+    assert_success(&usage);
+    let usage: Value = serde_json::from_slice(&usage.stdout).expect("usage json should parse");
+    assert_eq!(usage["enabled"], true);
+    let commands = usage["commands"].as_array().expect("commands array");
+    let find = |name: &str| {
+        commands
+            .iter()
+            .find(|entry| entry["command"] == name)
+            .unwrap_or_else(|| panic!("{name} should be recorded: {usage}"))
+    };
+    assert_eq!(find("ls")["runs"], 2);
+    assert!(find("push")["sync_files_p95"].as_u64().unwrap_or(0) >= 1);
+
+    let only_ls = run_knots(&root, &db, &["stats", "usage", "--command", "ls"]);
+    assert_success(&only_ls);
+    let text = String::from_utf8_lossy(&only_ls.stdout);
+    assert!(text.contains("sync_auto_budget_ms = 750"), "{text}");
+    assert!(!text.contains("push"), "{text}");
+
+    let _ = std::fs::remove_dir_all(root);
+}
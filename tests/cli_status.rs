@@ -0,0 +1,49 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn status_reports_sync_marks_cache_and_locks() {
+    let root = unique_workspace("knots-cli-status");
+    let _remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+
+    assert_success(&run_knots(&root, &db, &["init"]));
+    assert_success(&run_knots(&root, &db, &["new", "Status knot"]));
+
+    let before = run_knots(&root, &db, &["status", "--json"]);
+    assert_success(&before);
+    let before: Value = serde_json::from_slice(&before.stdout).expect("status json should parse");
+    assert_eq!(before["distribution"], "git");
+    assert_eq!(before["last_push"], Value::Null);
+    assert!(before["unpushed_event_files"].as_u64().unwrap_or(0) > 0);
+    assert!(before["cache"]["hot"].as_u64().unwrap_or(0) >= 1);
+    assert_eq!(before["locks"]["repo_held"], false);
+
+    assert_success(&run_knots(&root, &db, &["sync"]));
+    let after = run_knots(&root, &db, &["status", "--json"]);
+    assert_success(&after);
+    let after: Value = serde_json::from_slice(&after.stdout).expect("status json should parse");
+    assert_eq!(after["unpushed_event_files"], 0);
+    assert!(after["last_push"]["at_ms"].as_u64().is_some());
+    assert!(after["last_push"]["head"].as_str().is_some());
+    assert!(after["last_pull"]["at_ms"].as_u64().is_some());
+
+    let text = run_knots(&root, &db, &["status"]);
+    assert_success(&text);
+    assert_contains_in_order(
+        &String::from_utf8_lossy(&text.stdout),
+        &[
+            "sync",
+            "0 unpushed event file(s)",
+            "pull",
+            "push",
+            "cache",
+            "locks   repo free, cache free",
+            "health",
+        ],
+    );
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,65 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+fn create_ready(root: &std::path::Path, db: &std::path::Path, title: &str, tag: &str) -> String {
+    let output = run_knots(
+        root,
+        db,
+        &[
+            "new",
+            title,
+            "--profile",
+            "autopilot",
+            "--state",
+            "ready_for_implementation",
+        ],
+    );
+    assert_success(&output);
+    let id = parse_created_id(&output);
+    assert_success(&run_knots(root, db, &["update", &id, "--add-tag", tag]));
+    id
+}
+
+#[test]
+fn suggest_ranks_by_affinity_and_respects_capacity() {
+    let root = unique_workspace("knots-cli-suggest");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    std::fs::create_dir_all(root.join(".knots")).expect("create store dir");
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "[agents.codex]\ntags = [\"area/backend\"]\ncapacity = 1\n",
+    )
+    .expect("write repo config");
+    let _ui = create_ready(&root, &db, "UI work", "area/ui");
+    let backend = create_ready(&root, &db, "Backend work", "area/backend");
+
+    let output = run_knots(&root, &db, &["suggest", "--agent", "codex", "--json"]);
+    assert_success(&output);
+    let report: Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(report["capacity"], 1);
+    let suggestions = report["suggestions"].as_array().expect("suggestions");
+    assert_eq!(suggestions.len(), 1);
+    assert!(suggestions[0]["id"]
+        .as_str()
+        .expect("id")
+        .ends_with(&backend));
+    assert_eq!(suggestions[0]["reasons"][0], "matches area/backend");
+
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["claim", &backend, "--agent-name", "codex", "--json"],
+    ));
+    let output = run_knots(&root, &db, &["suggest", "--agent", "codex"]);
+    assert_success(&output);
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.starts_with("codex: 1 of 1 slot(s) in use\n"), "{text}");
+    assert!(
+        text.contains("no suggestions: agent is at capacity"),
+        "{text}"
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,60 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+#[test]
+fn summarize_adds_a_handoff_capsule_and_falls_back_when_the_endpoint_fails() {
+    let root = unique_workspace("knots-cli-summarize");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    let created = run_knots(&root, &db, &["new", "Cache layer"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_failure(&run_knots(&root, &db, &["summarize", &id]));
+    for note in [
+        "The cache drops writes under load.",
+        "Cache writes now go through a retry queue.",
+    ] {
+        assert_success(&run_knots(&root, &db, &["update", &id, "--add-note", note]));
+    }
+
+    let preview = run_knots(&root, &db, &["summarize", &id, "--dry-run"]);
+    assert_success(&preview);
+    let stdout = String::from_utf8_lossy(&preview.stdout);
+    assert!(
+        stdout.starts_with("dry run, nothing written for"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("(extractive, 2 entries)"), "{stdout}");
+
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &[
+            "config",
+            "set",
+            "summarize.endpoint",
+            "http://127.0.0.1:9/v1/chat/completions",
+        ],
+    ));
+    let written = run_knots(&root, &db, &["summarize", &id, "--json"]);
+    assert_success(&written);
+    let stderr = String::from_utf8_lossy(&written.stderr);
+    assert!(
+        stderr.contains("falling back to an extractive summary"),
+        "{stderr}"
+    );
+    let summary: Value = serde_json::from_slice(&written.stdout).expect("summary json");
+    assert_eq!(summary["model"], "extractive");
+    assert_eq!(summary["written"], true);
+
+    let shown = run_knots(&root, &db, &["show", &id, "--json"]);
+    assert_success(&shown);
+    let knot: Value = serde_json::from_slice(&shown.stdout).expect("show json");
+    let capsules = knot["handoff_capsules"].as_array().expect("capsules");
+    assert_eq!(capsules.len(), 1);
+    assert_eq!(capsules[0]["agentname"], "kno summarize");
+    assert_eq!(capsules[0]["content"], summary["content"]);
+    assert_failure(&run_knots(&root, &db, &["summarize", &id, "--extractive"]));
+}
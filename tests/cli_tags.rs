@@ -0,0 +1,64 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+
+#[test]
+fn namespaced_tags_filter_by_prefix_and_group_in_tag_list() {
+    let root = unique_workspace("knots-cli-tags");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    std::fs::create_dir_all(root.join(".knots")).expect("create store dir");
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "tag_namespaces = [\"area\"]\n",
+    )
+    .expect("write repo config");
+
+    let backend = run_knots(&root, &db, &["new", "Backend work"]);
+    assert_success(&backend);
+    let backend = parse_created_id(&backend);
+    let plain = run_knots(&root, &db, &["new", "Release work"]);
+    assert_success(&plain);
+    let plain = parse_created_id(&plain);
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["update", &backend, "--add-tag", "area/backend"],
+    ));
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["update", &plain, "--add-tag", "release"],
+    ));
+    assert_failure(&run_knots(
+        &root,
+        &db,
+        &["update", &plain, "--add-tag", "customer/acme"],
+    ));
+
+    let listed = run_knots(&root, &db, &["ls", "--tag", "area/*", "--json"]);
+    assert_success(&listed);
+    let listed: serde_json::Value = serde_json::from_slice(&listed.stdout).expect("json");
+    let listed = listed.as_array().expect("array");
+    assert_eq!(listed.len(), 1);
+    assert!(listed[0]["id"]
+        .as_str()
+        .expect("id")
+        .ends_with(backend.as_str()));
+
+    let tags = run_knots(&root, &db, &["tag", "list", "--json"]);
+    assert_success(&tags);
+    let tags: serde_json::Value = serde_json::from_slice(&tags.stdout).expect("json");
+    assert_eq!(tags[0]["namespace"], serde_json::Value::Null);
+    assert_eq!(tags[0]["tags"][0]["tag"], "release");
+    assert_eq!(tags[1]["namespace"], "area");
+    assert_eq!(tags[1]["tags"][0]["knots"], 1);
+
+    let text = run_knots(&root, &db, &["tag", "list"]);
+    assert_success(&text);
+    assert_contains_in_order(
+        &String::from_utf8_lossy(&text.stdout),
+        &["release (1)", "area/", "  area/backend (1)"],
+    );
+    let _ = std::fs::remove_dir_all(root);
+}
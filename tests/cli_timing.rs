@@ -0,0 +1,49 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+fn calls_for<'a>(stderr: &'a str, label: &str) -> &'a str {
+    stderr
+        .lines()
+        .find(|line| line.trim_start().starts_with(label))
+        .unwrap_or_else(|| panic!("{label} line missing: {stderr}"))
+}
+
+#[test]
+fn timing_flag_prints_breakdown_to_stderr_only() {
+    let root = unique_workspace("knots-cli-timing");
+    let _remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["init"]));
+
+    let created = run_knots(&root, &db, &["--timing", "new", "Timed knot"]);
+    assert_success(&created);
+    let stderr = String::from_utf8_lossy(&created.stderr);
+    assert_contains_in_order(
+        &stderr,
+        &[
+            "timing: total",
+            "lock wait",
+            "sync",
+            "db",
+            "event write",
+            "git",
+        ],
+    );
+    assert!(!calls_for(&stderr, "event write").ends_with("(0 call(s))"));
+
+    let listed = run_knots(&root, &db, &["ls", "--json", "--timing"]);
+    assert_success(&listed);
+    let _: Value = serde_json::from_slice(&listed.stdout).expect("stdout should stay json");
+    assert!(String::from_utf8_lossy(&listed.stderr).contains("timing: total"));
+
+    let pushed = run_knots(&root, &db, &["push", "--timing"]);
+    assert_success(&pushed);
+    let stderr = String::from_utf8_lossy(&pushed.stderr);
+    assert!(!calls_for(&stderr, "git ").ends_with("(0 call(s))"));
+
+    let plain = run_knots(&root, &db, &["ls"]);
+    assert!(!String::from_utf8_lossy(&plain.stderr).contains("timing:"));
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,78 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+
+#[test]
+fn uninit_refuses_unpushed_events_unless_exported_or_forced() {
+    let root = unique_workspace("knots-cli-uninit-guard");
+    let _remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["init"]));
+    assert_success(&run_knots(&root, &db, &["new", "Not pushed yet"]));
+
+    let refused = run_knots(&root, &db, &["uninit"]);
+    assert_failure(&refused);
+    let stderr = String::from_utf8_lossy(&refused.stderr);
+    assert!(stderr.contains("have not been pushed"), "{stderr}");
+    assert!(stderr.contains("--export"), "{stderr}");
+    assert!(root.join(".knots").exists());
+
+    let backup = root.join("backups/knots.tar.zst");
+    let exported = run_knots(
+        &root,
+        &db,
+        &["uninit", "--export", backup.to_str().expect("utf8 path")],
+    );
+    assert_success(&exported);
+    let stdout = String::from_utf8_lossy(&exported.stdout);
+    assert!(stdout.contains("exported"), "{stdout}");
+    assert!(stdout.contains("kno uninit completed"), "{stdout}");
+    assert!(!root.join(".knots").exists());
+    let archive = std::fs::read(&backup).expect("backup should exist");
+    assert!(archive.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]), "zstd frame");
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn uninit_force_skips_the_unpushed_check() {
+    let root = unique_workspace("knots-cli-uninit-force");
+    let _remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["init"]));
+    assert_success(&run_knots(&root, &db, &["new", "Throwaway"]));
+
+    assert_success(&run_knots(&root, &db, &["uninit", "--force"]));
+    assert!(!root.join(".knots").exists());
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn uninit_counts_and_exports_private_knot_events() {
+    let root = unique_workspace("knots-cli-uninit-private");
+    let _remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["init"]));
+    assert_success(&run_knots(&root, &db, &["new", "Scratch", "--private"]));
+
+    let refused = run_knots(&root, &db, &["uninit"]);
+    assert_failure(&refused);
+    let stderr = String::from_utf8_lossy(&refused.stderr);
+    assert!(stderr.contains("have not been pushed"), "{stderr}");
+    assert!(root.join(".knots/private").exists());
+
+    let backup = root.join("backups/private.tar.zst");
+    let exported = run_knots(
+        &root,
+        &db,
+        &["uninit", "--export", backup.to_str().expect("utf8 path")],
+    );
+    assert_success(&exported);
+    let archive = zstd::decode_all(&std::fs::read(&backup).expect("backup")[..]).expect("zstd");
+    let listing = String::from_utf8_lossy(&archive);
+    assert!(listing.contains(".knots/private/events/"), "private events");
+    assert!(listing.contains("knots.json"), "private registry");
+
+    let _ = std::fs::remove_dir_all(root);
+}
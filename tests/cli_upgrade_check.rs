@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use uuid::Uuid;
+
+fn unique_home(name: &str) -> PathBuf {
+    let home = std::env::temp_dir().join(format!("knots-upgrade-check-{}-{name}", Uuid::now_v7()));
+    std::fs::create_dir_all(&home).expect("temp home should be creatable");
+    home
+}
+
+fn install_stub_curl(bin_dir: &Path, latest_tag: &str) {
+    std::fs::create_dir_all(bin_dir).expect("bin dir should be creatable");
+    let script_path = bin_dir.join("curl");
+    let script = format!(
+        "#!/bin/sh\nprintf 'HTTP/2 302\\r\\nlocation: \
+         https://github.com/acartine/knots/releases/tag/{latest_tag}\\r\\n\\r\\n'\n"
+    );
+    std::fs::write(&script_path, script).expect("stub curl should be writable");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut perms = std::fs::metadata(&script_path)
+            .expect("stub curl metadata should exist")
+            .permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms).expect("stub curl should be executable");
+    }
+}
+
+fn run_upgrade(home: &Path, args: &[&str], configure: impl FnOnce(&mut Command)) -> String {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_knots"));
+    cmd.arg("upgrade")
+        .args(args)
+        .env("HOME", home)
+        .env("NO_COLOR", "1")
+        .env("KNOTS_SKIP_DOCTOR_UPGRADE", "1");
+    configure(&mut cmd);
+    let output = cmd.output().expect("knots upgrade should run");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn upgrade_check_reports_latest_stable_release_without_installing() {
+    let home = unique_home("stable");
+    let bin_dir = home.join("bin");
+    install_stub_curl(&bin_dir, "v9.9.9");
+    let existing_path = std::env::var_os("PATH").unwrap_or_default();
+    let path = std::env::join_paths(
+        std::iter::once(bin_dir.clone()).chain(std::env::split_paths(&existing_path)),
+    )
+    .expect("path should be joinable");
+
+    let stdout = run_upgrade(&home, &["--check"], |cmd| {
+        cmd.env("PATH", path);
+    });
+    assert!(stdout.starts_with("Upgrade check"), "{stdout}");
+    assert!(
+        stdout.contains(&format!("current:  {}", env!("CARGO_PKG_VERSION"))),
+        "{stdout}"
+    );
+    assert!(stdout.contains("channel:  stable"), "{stdout}");
+    assert!(stdout.contains("latest:  9.9.9"), "{stdout}");
+    assert!(stdout.contains("update available"), "{stdout}");
+    assert!(!bin_dir.join("knots").exists());
+
+    let _ = std::fs::remove_dir_all(home);
+}
+
+#[test]
+fn upgrade_check_on_beta_channel_considers_prereleases() {
+    let home = unique_home("beta");
+    let api = home.join("api");
+    let releases = api.join("repos/acartine/knots");
+    std::fs::create_dir_all(&releases).expect("api fixture dir should be creatable");
+    std::fs::write(
+        releases.join("releases"),
+        r#"[
+            {"tag_name": "v9.9.9-beta.1", "draft": false, "prerelease": true},
+            {"tag_name": "v9.9.8", "draft": false, "prerelease": false},
+            {"tag_name": "v10.0.0", "draft": true, "prerelease": false}
+        ]"#,
+    )
+    .expect("releases fixture should be writable");
+    let api_base = format!("file://{}", api.display());
+
+    let stdout = run_upgrade(&home, &["--check", "--channel", "beta"], |cmd| {
+        cmd.env("KNOTS_RELEASE_API_BASE", api_base);
+    });
+    assert!(stdout.contains("channel:  beta"), "{stdout}");
+    assert!(stdout.contains("latest:  9.9.9-beta.1"), "{stdout}");
+    assert!(stdout.contains("kno upgrade --channel beta"), "{stdout}");
+
+    let _ = std::fs::remove_dir_all(home);
+}
+
+#[test]
+fn upgrade_check_rejects_install_only_flags() {
+    let output = Command::new(env!("CARGO_BIN_EXE_knots"))
+        .args(["upgrade", "--check", "--version", "v1.0.0"])
+        .output()
+        .expect("knots upgrade should run");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+}
@@ -0,0 +1,80 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+
+#[test]
+fn pull_highlights_watched_knots_and_inbox_tracks_them() {
+    let root = unique_workspace("knots-cli-watch");
+    let remote = setup_repo_with_remote(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&root, &db, &["init"]));
+    let created = run_knots(&root, &db, &["new", "Shared work"]);
+    assert_success(&created);
+    let id = parse_created_id(&created);
+    assert_success(&run_knots(&root, &db, &["push"]));
+
+    let clone = unique_workspace("knots-cli-watch-clone");
+    run_git(&clone, &["clone", remote.to_str().expect("utf8 path"), "."]);
+    run_git(&clone, &["config", "user.email", "knots@example.com"]);
+    run_git(&clone, &["config", "user.name", "Knots Test"]);
+    let clone_db = clone.join(".knots/cache/state.sqlite");
+    assert_success(&run_knots(&clone, &clone_db, &["pull"]));
+    let watch = run_knots(&clone, &clone_db, &["watch", "add", &id]);
+    assert_success(&watch);
+    assert!(String::from_utf8_lossy(&watch.stdout).starts_with("watching "));
+    let listed = run_knots(&clone, &clone_db, &["watch", "list"]);
+    assert!(String::from_utf8_lossy(&listed.stdout).contains("Shared work"));
+
+    assert_success(&run_knots(&root, &db, &["state", &id, "planning"]));
+    assert_success(&run_knots(&root, &db, &["push"]));
+    let pulled = run_knots(&clone, &clone_db, &["pull"]);
+    assert_success(&pulled);
+    assert_contains_in_order(
+        &String::from_utf8_lossy(&pulled.stdout),
+        &["state changes (1):", "new inbox items (1)", "-> planning"],
+    );
+
+    let inbox = run_knots(&clone, &clone_db, &["inbox", "--json"]);
+    assert_success(&inbox);
+    let items: serde_json::Value = serde_json::from_slice(&inbox.stdout).expect("inbox json");
+    assert_eq!(items.as_array().map(Vec::len), Some(1));
+    assert_eq!(items[0]["kind"], "watch");
+    let ack = run_knots(&clone, &clone_db, &["inbox", "ack"]);
+    assert!(String::from_utf8_lossy(&ack.stdout).contains("acknowledged 1 inbox item(s)"));
+    let empty = run_knots(&clone, &clone_db, &["inbox"]);
+    assert_eq!(String::from_utf8_lossy(&empty.stdout), "inbox is empty\n");
+
+    assert_success(&run_knots(
+        &clone,
+        &clone_db,
+        &["config", "set", "inbox.handle", "bob"],
+    ));
+    assert_success(&run_knots(&clone, &clone_db, &["watch", "remove", &id]));
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &[
+            "update",
+            &id,
+            "--add-note",
+            "@bob can you review the parser?",
+            "--note-username",
+            "alice",
+        ],
+    ));
+    assert_success(&run_knots(&root, &db, &["push"]));
+    let pulled = run_knots(&clone, &clone_db, &["pull"]);
+    assert_contains_in_order(
+        &String::from_utf8_lossy(&pulled.stdout),
+        &[
+            "new inbox items (1)",
+            "mention",
+            "alice mentioned you in a note",
+        ],
+    );
+    let inbox = run_knots(&clone, &clone_db, &["inbox"]);
+    assert!(String::from_utf8_lossy(&inbox.stdout).contains("can you review the parser?"));
+
+    let _ = std::fs::remove_dir_all(root);
+    let _ = std::fs::remove_dir_all(clone);
+}
@@ -0,0 +1,58 @@
+mod cli_dispatch_helpers;
+
+use cli_dispatch_helpers::*;
+use serde_json::Value;
+
+fn create_ready(root: &std::path::Path, db: &std::path::Path, title: &str) -> String {
+    let output = run_knots(
+        root,
+        db,
+        &[
+            "new",
+            title,
+            "--profile",
+            "autopilot",
+            "--state",
+            "ready_for_implementation",
+        ],
+    );
+    assert_success(&output);
+    parse_created_id(&output)
+}
+
+#[test]
+fn claim_respects_wip_limits_and_queue_status_reports_usage() {
+    let root = unique_workspace("knots-cli-wip");
+    setup_repo(&root);
+    let db = root.join(".knots/cache/state.sqlite");
+    std::fs::create_dir_all(root.join(".knots")).expect("create store dir");
+    std::fs::write(
+        root.join(".knots/config.toml"),
+        "[wip_limits]\nimplementation = 1\n",
+    )
+    .expect("write repo config");
+    let first = create_ready(&root, &db, "First");
+    let second = create_ready(&root, &db, "Second");
+
+    assert_success(&run_knots(&root, &db, &["claim", &first, "--json"]));
+    let blocked = run_knots(&root, &db, &["claim", &second, "--json"]);
+    assert_failure(&blocked);
+    assert!(String::from_utf8_lossy(&blocked.stderr).contains("WIP limit reached"));
+
+    let status = run_knots(&root, &db, &["queue", "status", "--json"]);
+    assert_success(&status);
+    let status: Value = serde_json::from_slice(&status.stdout).expect("json");
+    assert_eq!(status[0]["state"], "implementation");
+    assert_eq!(status[0]["active"], 1);
+    assert_eq!(status[0]["limit"], 1);
+
+    assert_success(&run_knots(
+        &root,
+        &db,
+        &["claim", &second, "--force", "--json"],
+    ));
+    let text = run_knots(&root, &db, &["queue", "status"]);
+    assert_success(&text);
+    assert!(String::from_utf8_lossy(&text.stdout).contains("200%  full"));
+    let _ = std::fs::remove_dir_all(root);
+}